@@ -184,6 +184,9 @@ pub mod rpc {
 /// Ress subprotocol installation.
 pub mod ress;
 
+/// Static file exchange subprotocol installation.
+pub mod static_file_exchange;
+
 // re-export for convenience
 #[doc(inline)]
 pub use reth_cli_runner::{tokio_runtime, CliContext, CliRunner};
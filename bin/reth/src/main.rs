@@ -10,12 +10,27 @@
 static ALLOC: reth_cli_util::allocator::Allocator = reth_cli_util::allocator::new_allocator();
 
 use clap::Parser;
-use reth::{args::RessArgs, cli::Cli, ress::install_ress_subprotocol};
+use reth::{
+    args::{RessArgs, StaticFileExchangeArgs},
+    cli::Cli,
+    ress::install_ress_subprotocol,
+    static_file_exchange::install_static_file_exchange_subprotocol,
+};
 use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
-use reth_node_builder::NodeHandle;
+use reth_node_builder::{DebugNodeLauncher, NodeHandle};
 use reth_node_ethereum::EthereumNode;
+use reth_static_file_exchange::StaticFileExchangeClient;
 use tracing::info;
 
+/// Additional arguments of the node command.
+#[derive(Debug, clap::Args)]
+struct ExtArgs {
+    #[command(flatten)]
+    ress: RessArgs,
+    #[command(flatten)]
+    static_file_exchange: StaticFileExchangeArgs,
+}
+
 // LESSON 1: The Main Function - Where Everything Begins
 // This is the entry point of Reth. Notice how clean it is!
 // In Rust, main() is where your program starts, just like in C.
@@ -45,8 +60,9 @@ fn main() {
         // Cli::parse() reads command-line arguments (like --help or --datadir).
         // The type parameters <EthereumChainSpecParser, RessArgs> tell Rust:
         // - How to parse Ethereum-specific chain configurations
-        // - What additional arguments to accept (Ress is a Reth feature)
-        Cli::<EthereumChainSpecParser, RessArgs>::parse().run(async move |builder, ress_args| {
+        // - What additional arguments to accept (Ress and the static file exchange are Reth
+        //   features)
+        Cli::<EthereumChainSpecParser, ExtArgs>::parse().run(async move |builder, ext_args| {
             // LESSON 1: Structured Logging with Tracing
             // `info!` is like println! but much more powerful.
             // It includes timestamps, can be filtered by level, and sent to various outputs.
@@ -58,15 +74,41 @@ fn main() {
             // builder.node().launch_with_debug_capabilities()
             // This is the "builder pattern" - we configure step by step, then build.
             // The `await?` means: wait for this async operation, and if it fails, return the error.
-            let NodeHandle { node, node_exit_future } =
-                builder.node(EthereumNode::default()).launch_with_debug_capabilities().await?;
+            let static_file_exchange = ext_args
+                .static_file_exchange
+                .enabled
+                .then(|| {
+                    StaticFileExchangeClient::new(builder.config().datadir().static_file_exchange())
+                })
+                .transpose()?;
+            let NodeHandle { node, node_exit_future } = builder
+                .node(EthereumNode::default())
+                .launch_with_fn(|builder| {
+                    // large header gaps are synced from the static files of peers
+                    let mut launcher = builder.engine_api_launcher();
+                    if let Some(client) = static_file_exchange.clone() {
+                        launcher = launcher.with_static_file_exchange(client);
+                    }
+                    builder.launch_with(DebugNodeLauncher::new(launcher))
+                })
+                .await?;
+
+            if let Some(client) = static_file_exchange {
+                install_static_file_exchange_subprotocol(
+                    ext_args.static_file_exchange,
+                    client,
+                    node.provider.clone(),
+                    node.network.clone(),
+                    node.task_executor.clone(),
+                );
+            }
 
             // LESSON 1: Optional Features
             // Not all features are always enabled. This checks if the user wants
             // the "ress" subprotocol (a Reth-specific feature).
-            if ress_args.enabled {
+            if ext_args.ress.enabled {
                 install_ress_subprotocol(
-                    ress_args,
+                    ext_args.ress,
                     node.provider,      // Database access
                     node.evm_config,    // EVM configuration
                     node.network,       // P2P network access
@@ -0,0 +1,73 @@
+use reth_network::{protocol::IntoRlpxSubProtocol, NetworkProtocols};
+use reth_network_api::FullNetwork;
+use reth_node_core::args::StaticFileExchangeArgs;
+use reth_provider::{
+    providers::{BlockchainProvider, ProviderNodeTypes},
+    BlockIdReader, StaticFileProviderFactory,
+};
+use reth_static_file_exchange::{
+    ProtocolState, StaticFileDirectory, StaticFileExchangeClient, StaticFileExchangeHandler,
+};
+use reth_tasks::TaskExecutor;
+use std::{io, time::Duration};
+use tokio::sync::mpsc;
+use tracing::*;
+
+/// The interval in which the served chunks are updated.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Install the static file exchange subprotocol and forward its events to the given client.
+pub fn install_static_file_exchange_subprotocol<P, N>(
+    args: StaticFileExchangeArgs,
+    client: StaticFileExchangeClient,
+    provider: BlockchainProvider<P>,
+    network: N,
+    task_executor: TaskExecutor,
+) where
+    P: ProviderNodeTypes,
+    N: FullNetwork + NetworkProtocols,
+{
+    info!(target: "reth::cli", "Installing static file exchange subprotocol");
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let directory = StaticFileDirectory::new(provider.static_file_provider().directory());
+    network.add_rlpx_sub_protocol(
+        StaticFileExchangeHandler {
+            provider: directory.clone(),
+            peers_handle: network.peers_handle().clone(),
+            max_active_connections: args.max_active_connections,
+            state: ProtocolState::new(tx),
+        }
+        .into_rlpx_sub_protocol(),
+    );
+    info!(target: "reth::cli", "Static file exchange subprotocol support enabled");
+
+    let events_client = client.clone();
+    task_executor.spawn(async move {
+        while let Some(event) = rx.recv().await {
+            trace!(target: "reth::static_file_exchange", ?event, "Received static file exchange event");
+            events_client.on_event(event);
+        }
+    });
+
+    // serve the static files that were finalized since the last update
+    task_executor.spawn(async move {
+        let mut interval = tokio::time::interval(REFRESH_INTERVAL);
+        loop {
+            interval.tick().await;
+            let Ok(Some(finalized)) = provider.finalized_block_number() else { continue };
+            let highest = provider.static_file_provider().get_highest_static_files();
+            let directory = directory.clone();
+            let refreshed = tokio::task::spawn_blocking(move || directory.refresh(highest, finalized))
+                .await
+                .map_err(io::Error::other)
+                .and_then(|refreshed| refreshed);
+            match refreshed {
+                Ok(true) => client.announce_manifest(),
+                Ok(false) => {}
+                Err(err) => {
+                    warn!(target: "reth::static_file_exchange", %err, "Failed to update served static files")
+                }
+            }
+        }
+    });
+}
@@ -0,0 +1,153 @@
+//! Local LRU cache for state fetched over RPC.
+
+use alloy_eips::BlockId;
+use alloy_primitives::{Address, StorageKey, B256, U256};
+use parking_lot::Mutex;
+use reth_primitives::{Account, Bytecode};
+use schnellru::{ByLength, LruMap};
+use std::sync::Arc;
+
+/// Default number of entries kept per cache in [`AlloyRethStateCache`].
+pub const DEFAULT_STATE_CACHE_SIZE: u32 = 100_000;
+
+/// LRU cache for accounts, storage slots and bytecodes fetched from a remote node.
+///
+/// Accounts and storage slots are keyed by the hash of the block they were fetched at, and are only
+/// cached for blocks that are identified by hash, since block numbers and tags like `latest` can
+/// resolve to different blocks after a reorg or over time. Bytecodes are keyed by their hash and
+/// shared across all blocks.
+///
+/// The cache is cheap to clone and shared by all state providers of an
+/// [`AlloyRethProvider`](crate::AlloyRethProvider).
+#[derive(Debug, Clone)]
+pub struct AlloyRethStateCache {
+    inner: Arc<Mutex<StateCacheInner>>,
+}
+
+impl AlloyRethStateCache {
+    /// Creates a new cache that keeps at most `max_entries` entries per cached item kind.
+    pub fn new(max_entries: u32) -> Self {
+        let inner = StateCacheInner {
+            accounts: LruMap::new(ByLength::new(max_entries)),
+            storage: LruMap::new(ByLength::new(max_entries)),
+            bytecodes: LruMap::new(ByLength::new(max_entries)),
+        };
+        Self { inner: Arc::new(Mutex::new(inner)) }
+    }
+
+    /// Returns the cached account at the given block.
+    ///
+    /// The outer `Option` is `None` on a cache miss, the inner one is `None` if the account
+    /// doesn't exist.
+    pub fn account(&self, block_id: BlockId, address: Address) -> Option<Option<Account>> {
+        let block = cache_key(block_id)?;
+        self.inner.lock().accounts.get(&(block, address)).copied()
+    }
+
+    /// Caches the account at the given block.
+    pub fn insert_account(&self, block_id: BlockId, address: Address, account: Option<Account>) {
+        if let Some(block) = cache_key(block_id) {
+            self.inner.lock().accounts.insert((block, address), account);
+        }
+    }
+
+    /// Returns the cached storage value at the given block.
+    pub fn storage(&self, block_id: BlockId, address: Address, key: StorageKey) -> Option<U256> {
+        let block = cache_key(block_id)?;
+        self.inner.lock().storage.get(&(block, address, key)).copied()
+    }
+
+    /// Caches the storage value at the given block.
+    pub fn insert_storage(
+        &self,
+        block_id: BlockId,
+        address: Address,
+        key: StorageKey,
+        value: U256,
+    ) {
+        if let Some(block) = cache_key(block_id) {
+            self.inner.lock().storage.insert((block, address, key), value);
+        }
+    }
+
+    /// Returns the cached bytecode with the given hash.
+    pub fn bytecode(&self, code_hash: &B256) -> Option<Bytecode> {
+        self.inner.lock().bytecodes.get(code_hash).cloned()
+    }
+
+    /// Caches the bytecode under the given hash.
+    pub fn insert_bytecode(&self, code_hash: B256, bytecode: Bytecode) {
+        self.inner.lock().bytecodes.insert(code_hash, bytecode);
+    }
+}
+
+impl Default for AlloyRethStateCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_STATE_CACHE_SIZE)
+    }
+}
+
+/// Container type for shared state in [`AlloyRethStateCache`]
+struct StateCacheInner {
+    accounts: LruMap<(B256, Address), Option<Account>, ByLength>,
+    storage: LruMap<(B256, Address, StorageKey), U256, ByLength>,
+    bytecodes: LruMap<B256, Bytecode, ByLength>,
+}
+
+impl std::fmt::Debug for StateCacheInner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StateCacheInner")
+            .field("accounts", &self.accounts.len())
+            .field("storage", &self.storage.len())
+            .field("bytecodes", &self.bytecodes.len())
+            .finish()
+    }
+}
+
+/// Returns the cache key for the block, if state at this block is immutable.
+const fn cache_key(block_id: BlockId) -> Option<B256> {
+    match block_id {
+        BlockId::Hash(hash) => Some(hash.block_hash),
+        BlockId::Number(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn caches_only_blocks_by_hash() {
+        let cache = AlloyRethStateCache::new(10);
+        let address = Address::with_last_byte(1);
+        let account = Account { nonce: 1, ..Default::default() };
+        let (block1, block2) = (B256::with_last_byte(1), B256::with_last_byte(2));
+
+        cache.insert_account(BlockId::latest(), address, Some(account));
+        assert_eq!(cache.account(BlockId::latest(), address), None);
+
+        cache.insert_account(BlockId::number(1), address, Some(account));
+        assert_eq!(cache.account(BlockId::number(1), address), None);
+
+        cache.insert_account(BlockId::hash(block1), address, Some(account));
+        assert_eq!(cache.account(BlockId::hash(block1), address), Some(Some(account)));
+        assert_eq!(cache.account(BlockId::hash(block2), address), None);
+
+        cache.insert_account(BlockId::hash(block2), address, None);
+        assert_eq!(cache.account(BlockId::hash(block2), address), Some(None));
+
+        cache.insert_storage(BlockId::hash(block1), address, B256::ZERO, U256::from(7));
+        assert_eq!(cache.storage(BlockId::hash(block1), address, B256::ZERO), Some(U256::from(7)));
+        assert_eq!(cache.storage(BlockId::pending(), address, B256::ZERO), None);
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let cache = AlloyRethStateCache::new(2);
+        for i in 0..3u8 {
+            cache.insert_bytecode(B256::with_last_byte(i), Bytecode::default());
+        }
+        assert!(cache.bytecode(&B256::with_last_byte(0)).is_none());
+        assert!(cache.bytecode(&B256::with_last_byte(2)).is_some());
+    }
+}
@@ -8,6 +8,7 @@
 //! ## Features
 //!
 //! - Implements `StateProviderFactory` for remote RPC state access
+//! - Caches fetched accounts, storage and bytecode in a local LRU cache
 //! - Supports Ethereum and Optimism network
 //! - Useful for testing without requiring a full database
 //! - Can be used with reth ExEx (Execution Extensions) for testing
@@ -60,11 +61,24 @@ use std::{
 use tokio::{runtime::Handle, sync::broadcast};
 use tracing::trace;
 
+mod cache;
+pub use cache::{AlloyRethStateCache, DEFAULT_STATE_CACHE_SIZE};
+
 /// Configuration for `AlloyRethProvider`
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 pub struct AlloyRethProviderConfig {
     /// Whether to compute state root when creating execution outcomes
     pub compute_state_root: bool,
+    /// Maximum number of cached accounts, storage slots and bytecodes each.
+    ///
+    /// Caching is disabled if set to zero.
+    pub state_cache_size: u32,
+}
+
+impl Default for AlloyRethProviderConfig {
+    fn default() -> Self {
+        Self { compute_state_root: false, state_cache_size: DEFAULT_STATE_CACHE_SIZE }
+    }
 }
 
 impl AlloyRethProviderConfig {
@@ -73,6 +87,12 @@ impl AlloyRethProviderConfig {
         self.compute_state_root = compute;
         self
     }
+
+    /// Sets the maximum number of cached accounts, storage slots and bytecodes each.
+    pub const fn with_state_cache_size(mut self, size: u32) -> Self {
+        self.state_cache_size = size;
+        self
+    }
 }
 
 /// A provider implementation that uses Alloy RPC to fetch state data
@@ -101,6 +121,8 @@ where
     config: AlloyRethProviderConfig,
     /// Cached chain spec
     chain_spec: Arc<Node::ChainSpec>,
+    /// Cache for remote state, shared with all state providers
+    state_cache: Option<AlloyRethStateCache>,
 }
 
 impl<P, Node: NodeTypes, N> std::fmt::Debug for AlloyRethProvider<P, Node, N> {
@@ -124,6 +146,8 @@ impl<P, Node: NodeTypes, N> AlloyRethProvider<P, Node, N> {
         Node::ChainSpec: Default,
     {
         let (canon_state_notification, _) = broadcast::channel(1);
+        let state_cache = (config.state_cache_size > 0)
+            .then(|| AlloyRethStateCache::new(config.state_cache_size));
         Self {
             provider,
             node_types: std::marker::PhantomData,
@@ -131,9 +155,15 @@ impl<P, Node: NodeTypes, N> AlloyRethProvider<P, Node, N> {
             canon_state_notification,
             config,
             chain_spec: Arc::new(Node::ChainSpec::default()),
+            state_cache,
         }
     }
 
+    /// Returns the cache for remote state, if enabled.
+    pub const fn state_cache(&self) -> Option<&AlloyRethStateCache> {
+        self.state_cache.as_ref()
+    }
+
     /// Helper function to execute async operations in a blocking context
     fn block_on_async<F, T>(&self, fut: F) -> T
     where
@@ -157,12 +187,36 @@ where
     Node: NodeTypes,
 {
     /// Helper function to create a state provider for a given block ID
-    fn create_state_provider(&self, block_id: BlockId) -> AlloyRethStateProvider<P, Node, N> {
-        AlloyRethStateProvider::with_chain_spec(
+    ///
+    /// If the state cache is enabled, a block number is resolved to its hash first, so that state
+    /// is fetched and cached by block hash and cached entries can't go stale after a reorg.
+    fn create_state_provider(
+        &self,
+        block_id: BlockId,
+    ) -> Result<AlloyRethStateProvider<P, Node, N>, ProviderError> {
+        let Some(cache) = &self.state_cache else {
+            return Ok(AlloyRethStateProvider::with_chain_spec(
+                self.provider.clone(),
+                block_id,
+                self.chain_spec.clone(),
+            ))
+        };
+
+        let block_id = match block_id {
+            BlockId::Number(alloy_rpc_types::BlockNumberOrTag::Number(number)) => {
+                let hash =
+                    self.block_hash(number)?.ok_or(ProviderError::HeaderNotFound(number.into()))?;
+                BlockId::hash(hash)
+            }
+            block_id => block_id,
+        };
+
+        Ok(AlloyRethStateProvider::with_chain_spec(
             self.provider.clone(),
             block_id,
             self.chain_spec.clone(),
         )
+        .with_cache(cache.clone()))
     }
 
     /// Helper function to get state provider by block number
@@ -170,7 +224,7 @@ where
         &self,
         block_number: BlockNumber,
     ) -> Result<StateProviderBox, ProviderError> {
-        Ok(Box::new(self.create_state_provider(BlockId::number(block_number))))
+        Ok(Box::new(self.create_state_provider(BlockId::number(block_number))?))
     }
 }
 
@@ -578,7 +632,7 @@ where
     }
 
     fn state_by_block_id(&self, block_id: BlockId) -> Result<StateProviderBox, ProviderError> {
-        Ok(Box::new(self.create_state_provider(block_id)))
+        Ok(Box::new(self.create_state_provider(block_id)?))
     }
 
     fn state_by_block_number_or_tag(
@@ -613,7 +667,7 @@ where
     ) -> Result<StateProviderBox, ProviderError> {
         trace!(target: "alloy-provider", ?block_hash, "Getting state provider by block hash");
 
-        let block = self.block_on_async(async {
+        self.block_on_async(async {
             self.provider
                 .get_block_by_hash(block_hash)
                 .await
@@ -621,8 +675,7 @@ where
                 .ok_or(ProviderError::BlockHashNotFound(block_hash))
         })?;
 
-        let block_number = block.header().number();
-        Ok(Box::new(self.create_state_provider(BlockId::number(block_number))))
+        Ok(Box::new(self.create_state_provider(BlockId::hash(block_hash))?))
     }
 
     fn pending(&self) -> Result<StateProviderBox, ProviderError> {
@@ -655,7 +708,7 @@ where
             self.provider.get_block_number().await.map_err(ProviderError::other)
         })?;
 
-        Ok(self.create_state_provider(BlockId::number(block_number)))
+        self.create_state_provider(BlockId::number(block_number))
     }
 
     fn database_provider_rw(&self) -> Result<Self::ProviderRW, ProviderError> {
@@ -664,7 +717,7 @@ where
             self.provider.get_block_number().await.map_err(ProviderError::other)
         })?;
 
-        Ok(self.create_state_provider(BlockId::number(block_number)))
+        self.create_state_provider(BlockId::number(block_number))
     }
 }
 
@@ -747,6 +800,8 @@ where
     network: std::marker::PhantomData<N>,
     /// Cached chain spec (shared with parent provider)
     chain_spec: Option<Arc<Node::ChainSpec>>,
+    /// Cache for remote state (shared with parent provider)
+    cache: Option<AlloyRethStateCache>,
 }
 
 impl<P: std::fmt::Debug, Node: NodeTypes, N> std::fmt::Debug
@@ -773,6 +828,7 @@ impl<P: Clone, Node: NodeTypes, N> AlloyRethStateProvider<P, Node, N> {
             node_types: std::marker::PhantomData,
             network: std::marker::PhantomData,
             chain_spec: None,
+            cache: None,
         }
     }
 
@@ -788,9 +844,16 @@ impl<P: Clone, Node: NodeTypes, N> AlloyRethStateProvider<P, Node, N> {
             node_types: std::marker::PhantomData,
             network: std::marker::PhantomData,
             chain_spec: Some(chain_spec),
+            cache: None,
         }
     }
 
+    /// Sets the cache used for fetched state.
+    pub fn with_cache(mut self, cache: AlloyRethStateCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
     /// Helper function to execute async operations in a blocking context
     fn block_on_async<F, T>(&self, fut: F) -> T
     where
@@ -807,11 +870,37 @@ impl<P: Clone, Node: NodeTypes, N> AlloyRethStateProvider<P, Node, N> {
             node_types: self.node_types,
             network: self.network,
             chain_spec: self.chain_spec.clone(),
+            cache: self.cache.clone(),
         }
     }
 
-    /// Get account information from RPC
+    /// Get account information from the cache or RPC
     fn get_account(&self, address: Address) -> Result<Option<Account>, ProviderError>
+    where
+        P: Provider<N> + Clone + 'static,
+        N: Network,
+    {
+        if let Some(account) = self.cache.as_ref().and_then(|c| c.account(self.block_id, address)) {
+            return Ok(account)
+        }
+
+        let (account, bytecode) = self.fetch_account(address)?.unzip();
+        if let Some(cache) = &self.cache {
+            cache.insert_account(self.block_id, address, account);
+            if let (Some(hash), Some(bytecode)) =
+                (account.and_then(|account| account.bytecode_hash), bytecode.flatten())
+            {
+                cache.insert_bytecode(hash, bytecode);
+            }
+        }
+        Ok(account)
+    }
+
+    /// Fetches account information and code from RPC
+    fn fetch_account(
+        &self,
+        address: Address,
+    ) -> Result<Option<(Account, Option<Bytecode>)>, ProviderError>
     where
         P: Provider<N> + Clone + 'static,
         N: Network,
@@ -838,11 +927,12 @@ impl<P: Clone, Node: NodeTypes, N> AlloyRethStateProvider<P, Node, N> {
                     Some(Bytecode::new_raw(account_info.code))
                 };
 
-                Ok(Some(Account {
+                let account = Account {
                     balance: account_info.balance,
                     nonce: account_info.nonce,
                     bytecode_hash: bytecode.as_ref().map(|b| b.hash_slow()),
-                }))
+                };
+                Ok(Some((account, bytecode)))
             }
         })
     }
@@ -859,23 +949,42 @@ where
         address: Address,
         storage_key: StorageKey,
     ) -> Result<Option<U256>, ProviderError> {
-        self.block_on_async(async {
-            let value = self
-                .provider
-                .get_storage_at(address, storage_key.into())
-                .block_id(self.block_id)
-                .await
-                .map_err(ProviderError::other)?;
-
-            if value.is_zero() {
-                Ok(None)
-            } else {
-                Ok(Some(value))
+        let cached =
+            self.cache.as_ref().and_then(|c| c.storage(self.block_id, address, storage_key));
+        let value = match cached {
+            Some(value) => value,
+            None => {
+                let value = self.block_on_async(async {
+                    self.provider
+                        .get_storage_at(address, storage_key.into())
+                        .block_id(self.block_id)
+                        .await
+                        .map_err(ProviderError::other)
+                })?;
+                if let Some(cache) = &self.cache {
+                    cache.insert_storage(self.block_id, address, storage_key, value);
+                }
+                value
             }
-        })
+        };
+
+        if value.is_zero() {
+            Ok(None)
+        } else {
+            Ok(Some(value))
+        }
     }
 
     fn account_code(&self, addr: &Address) -> Result<Option<Bytecode>, ProviderError> {
+        if let Some(cache) = &self.cache {
+            // Resolving the account caches its bytecode as well.
+            let Some(account) = self.get_account(*addr)? else { return Ok(None) };
+            let Some(hash) = account.bytecode_hash else { return Ok(None) };
+            if let Some(bytecode) = cache.bytecode(&hash) {
+                return Ok(Some(bytecode))
+            }
+        }
+
         self.block_on_async(async {
             let code = self
                 .provider
@@ -907,9 +1016,14 @@ where
     N: Network,
     Node: NodeTypes,
 {
-    fn bytecode_by_hash(&self, _code_hash: &B256) -> Result<Option<Bytecode>, ProviderError> {
-        // Cannot fetch bytecode by hash via RPC
-        Err(ProviderError::UnsupportedProvider)
+    fn bytecode_by_hash(&self, code_hash: &B256) -> Result<Option<Bytecode>, ProviderError> {
+        // Cannot fetch bytecode by hash via RPC, but it may have been cached when the account was
+        // fetched.
+        self.cache
+            .as_ref()
+            .and_then(|cache| cache.bytecode(code_hash))
+            .map(Some)
+            .ok_or(ProviderError::UnsupportedProvider)
     }
 }
 
@@ -1034,6 +1148,15 @@ where
     ) -> Result<reth_trie::StorageMultiProof, ProviderError> {
         Err(ProviderError::UnsupportedProvider)
     }
+
+    fn storage_multiproof_from_nodes(
+        &self,
+        _input: TrieInput,
+        _address: Address,
+        _slots: &[B256],
+    ) -> Result<reth_trie::StorageMultiProof, ProviderError> {
+        Err(ProviderError::UnsupportedProvider)
+    }
 }
 
 impl<P, Node, N> reth_storage_api::StateProofProvider for AlloyRethStateProvider<P, Node, N>
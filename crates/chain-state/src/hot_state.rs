@@ -0,0 +1,513 @@
+//! Cache of frequently read state at the canonical head, shared by all state providers handed out
+//! for the head block.
+
+use crate::{CanonStateNotification, CanonStateNotifications};
+use alloy_consensus::BlockHeader;
+use alloy_primitives::{
+    map::DefaultHashBuilder, Address, BlockNumber, Bytes, StorageKey, StorageValue, B256,
+};
+use mini_moka::sync::CacheBuilder;
+use parking_lot::{Mutex, RwLock};
+use reth_errors::ProviderResult;
+use reth_metrics::{
+    metrics::{Counter, Gauge},
+    Metrics,
+};
+use reth_primitives_traits::{Account, Bytecode, NodePrimitives};
+use reth_storage_api::{
+    AccountReader, BlockHashReader, BytecodeReader, HashedPostStateProvider, StateProofProvider,
+    StateProvider, StateProviderBox, StateRootProvider, StorageRootProvider,
+};
+use reth_trie::{
+    updates::TrieUpdates, AccountProof, HashedPostState, HashedStorage, MultiProof,
+    MultiProofTargets, StorageMultiProof, TrieInput,
+};
+use revm_database::BundleState;
+use std::sync::Arc;
+use tokio::sync::broadcast::error::TryRecvError;
+
+/// Metrics for the hot state cache.
+#[derive(Metrics)]
+#[metrics(scope = "hot_state_cache")]
+pub(crate) struct HotStateCacheMetrics {
+    /// Number of reads served from the cache
+    hits: Counter,
+    /// Number of reads that were not cached
+    misses: Counter,
+    /// Number of cached accounts and storage slots
+    entries: Gauge,
+    /// Number of times the cache was cleared because the canonical chain was reorged
+    clears: Counter,
+}
+
+/// Cache of accounts and storage slots read from the state of the canonical head.
+///
+/// The same hot accounts, like routers and stablecoins, are read by every payload build iteration
+/// and most `eth_call`s. State providers for the head block are wrapped with
+/// [`HotStateCache::wrap`], so that these reads are only fetched from the database once per block.
+///
+/// The cache follows the canonical chain: when the chain is extended, the cached values that were
+/// changed by the new blocks are updated in place, on reorgs the cache is cleared. Values are only
+/// served to and inserted by providers of the block the cache is at. Lookups and inserts don't
+/// block each other, only moving the cache to a new block does.
+///
+/// Bytecodes don't depend on the block and are cached by the providers' shared bytecode cache.
+#[derive(Debug)]
+pub struct HotStateCache<N: NodePrimitives = reth_ethereum_primitives::EthPrimitives> {
+    inner: Arc<HotStateCacheInner<N>>,
+}
+
+impl<N: NodePrimitives> Clone for HotStateCache<N> {
+    fn clone(&self) -> Self {
+        Self { inner: self.inner.clone() }
+    }
+}
+
+type Cache<K, V> = mini_moka::sync::Cache<K, V, DefaultHashBuilder>;
+
+struct HotStateCacheInner<N: NodePrimitives> {
+    /// The block the cached accounts and storage slots belong to.
+    ///
+    /// Reads and inserts hold the read lock, moving the cache to a new block holds the write lock,
+    /// so values read from an older block are never inserted after the cache moved on.
+    block_hash: RwLock<B256>,
+    /// Cached accounts, `None` if the account doesn't exist.
+    accounts: Cache<Address, Option<Account>>,
+    /// Cached storage slots, `None` if the slot is empty.
+    storage: Cache<(Address, StorageKey), Option<StorageValue>>,
+    /// Canonical state notifications that move the cache to the new head.
+    notifications: Mutex<CanonStateNotifications<N>>,
+    /// Metrics for the cache.
+    metrics: HotStateCacheMetrics,
+}
+
+impl<N: NodePrimitives> std::fmt::Debug for HotStateCacheInner<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HotStateCacheInner")
+            .field("block_hash", &self.block_hash)
+            .field("accounts", &self.accounts.entry_count())
+            .field("storage", &self.storage.entry_count())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<N: NodePrimitives> HotStateCacheInner<N> {
+    /// Moves the cache to a new block and drops all accounts and storage slots.
+    fn clear(&self, block_hash: &mut B256, new_block_hash: B256) {
+        *block_hash = new_block_hash;
+        self.accounts.invalidate_all();
+        self.storage.invalidate_all();
+        self.metrics.clears.increment(1);
+    }
+
+    /// Moves the cache to the tip of the new chain segment, updating the cached accounts and
+    /// storage slots that were changed by it.
+    fn apply(&self, block_hash: &mut B256, tip: B256, bundle: &BundleState) {
+        *block_hash = tip;
+        let mut destroyed = Vec::new();
+        for (address, account) in bundle.state() {
+            if self.accounts.contains_key(address) {
+                self.accounts.insert(*address, account.info.as_ref().map(Account::from));
+            }
+            if account.was_destroyed() {
+                destroyed.push(*address);
+                continue
+            }
+            for (key, slot) in &account.storage {
+                let key = (*address, B256::from(*key));
+                if self.storage.contains_key(&key) {
+                    let value = Some(slot.present_value).filter(|value| !value.is_zero());
+                    self.storage.insert(key, value);
+                }
+            }
+        }
+
+        // accounts are rarely destroyed since EIP-6780, so finding their slots by scanning the
+        // cache is fine
+        if !destroyed.is_empty() {
+            let slots = self
+                .storage
+                .iter()
+                .map(|entry| *entry.key())
+                .filter(|(address, _)| destroyed.contains(address))
+                .collect::<Vec<_>>();
+            for key in &slots {
+                self.storage.invalidate(key);
+            }
+        }
+    }
+
+    /// Updates the gauge of cached entries.
+    fn update_entries(&self) {
+        let entries = self.accounts.entry_count() + self.storage.entry_count();
+        self.metrics.entries.set(entries as f64);
+    }
+}
+
+impl<N: NodePrimitives> HotStateCache<N> {
+    /// Creates an empty cache at the given canonical head, that follows the canonical chain with
+    /// the given notifications.
+    ///
+    /// At most `max_entries` accounts and `max_entries` storage slots are cached, the least
+    /// recently used entries are evicted first.
+    pub fn new(head: B256, notifications: CanonStateNotifications<N>, max_entries: usize) -> Self {
+        let max_entries = max_entries as u64;
+        Self {
+            inner: Arc::new(HotStateCacheInner {
+                block_hash: RwLock::new(head),
+                accounts: CacheBuilder::new(max_entries).build_with_hasher(Default::default()),
+                storage: CacheBuilder::new(max_entries).build_with_hasher(Default::default()),
+                notifications: Mutex::new(notifications),
+                metrics: Default::default(),
+            }),
+        }
+    }
+
+    /// Returns the block the cache is at.
+    pub fn block_hash(&self) -> B256 {
+        self.sync();
+        *self.inner.block_hash.read()
+    }
+
+    /// Wraps the state provider of the given block, so that it uses the cache.
+    ///
+    /// The provider is returned as is if the cache is not at that block.
+    pub fn wrap(&self, block_hash: B256, provider: StateProviderBox) -> StateProviderBox {
+        if self.block_hash() != block_hash {
+            return provider
+        }
+        Box::new(HotStateProvider { cache: self.clone(), block_hash, inner: provider })
+    }
+
+    /// Applies all pending canonical state notifications.
+    fn sync(&self) {
+        let mut notifications = self.inner.notifications.lock();
+        loop {
+            match notifications.try_recv() {
+                Ok(notification) => self.on_canon_state(&notification),
+                Err(TryRecvError::Lagged(_)) => {
+                    // notifications were missed, we don't know which values changed
+                    let mut block_hash = self.inner.block_hash.write();
+                    self.inner.clear(&mut block_hash, B256::ZERO);
+                }
+                Err(TryRecvError::Empty | TryRecvError::Closed) => break,
+            }
+        }
+        self.inner.update_entries();
+    }
+
+    /// Moves the cache to the new canonical head.
+    fn on_canon_state(&self, notification: &CanonStateNotification<N>) {
+        let new = notification.committed();
+        let tip = new.tip().hash();
+        let mut block_hash = self.inner.block_hash.write();
+        match notification {
+            CanonStateNotification::Commit { .. } if new.first().parent_hash() == *block_hash => {
+                self.inner.apply(&mut block_hash, tip, &new.execution_outcome().bundle)
+            }
+            _ => self.inner.clear(&mut block_hash, tip),
+        }
+    }
+
+    /// Returns the cached account, if the cache is at the given block.
+    fn account(&self, block_hash: B256, address: &Address) -> Option<Option<Account>> {
+        let cached = self.inner.block_hash.read();
+        let account = (*cached == block_hash).then(|| self.inner.accounts.get(address)).flatten();
+        self.record(account.is_some());
+        account
+    }
+
+    /// Caches the account, if the cache is at the given block.
+    fn insert_account(&self, block_hash: B256, address: Address, account: Option<Account>) {
+        let cached = self.inner.block_hash.read();
+        if *cached == block_hash {
+            self.inner.accounts.insert(address, account);
+        }
+    }
+
+    /// Returns the cached storage slot, if the cache is at the given block.
+    fn storage(
+        &self,
+        block_hash: B256,
+        address: &Address,
+        key: &StorageKey,
+    ) -> Option<Option<StorageValue>> {
+        let cached = self.inner.block_hash.read();
+        let value =
+            (*cached == block_hash).then(|| self.inner.storage.get(&(*address, *key))).flatten();
+        self.record(value.is_some());
+        value
+    }
+
+    /// Caches the storage slot, if the cache is at the given block.
+    fn insert_storage(
+        &self,
+        block_hash: B256,
+        address: Address,
+        key: StorageKey,
+        value: Option<StorageValue>,
+    ) {
+        let cached = self.inner.block_hash.read();
+        if *cached == block_hash {
+            self.inner.storage.insert((address, key), value);
+        }
+    }
+
+    fn record(&self, hit: bool) {
+        if hit {
+            self.inner.metrics.hits.increment(1);
+        } else {
+            self.inner.metrics.misses.increment(1);
+        }
+    }
+}
+
+/// A state provider that reads accounts and storage slots through the [`HotStateCache`].
+#[expect(missing_debug_implementations)]
+pub struct HotStateProvider<N: NodePrimitives> {
+    /// The shared cache.
+    cache: HotStateCache<N>,
+    /// The block of the wrapped provider.
+    block_hash: B256,
+    /// The wrapped provider.
+    inner: StateProviderBox,
+}
+
+impl<N: NodePrimitives> AccountReader for HotStateProvider<N> {
+    fn basic_account(&self, address: &Address) -> ProviderResult<Option<Account>> {
+        if let Some(account) = self.cache.account(self.block_hash, address) {
+            return Ok(account)
+        }
+        let account = self.inner.basic_account(address)?;
+        self.cache.insert_account(self.block_hash, *address, account);
+        Ok(account)
+    }
+}
+
+impl<N: NodePrimitives> StateProvider for HotStateProvider<N> {
+    fn storage(
+        &self,
+        account: Address,
+        storage_key: StorageKey,
+    ) -> ProviderResult<Option<StorageValue>> {
+        if let Some(value) = self.cache.storage(self.block_hash, &account, &storage_key) {
+            return Ok(value)
+        }
+        let value = self.inner.storage(account, storage_key)?;
+        self.cache.insert_storage(self.block_hash, account, storage_key, value);
+        Ok(value)
+    }
+}
+
+impl<N: NodePrimitives> BytecodeReader for HotStateProvider<N> {
+    fn bytecode_by_hash(&self, code_hash: &B256) -> ProviderResult<Option<Bytecode>> {
+        self.inner.bytecode_by_hash(code_hash)
+    }
+}
+
+impl<N: NodePrimitives> BlockHashReader for HotStateProvider<N> {
+    fn block_hash(&self, number: BlockNumber) -> ProviderResult<Option<B256>> {
+        self.inner.block_hash(number)
+    }
+
+    fn canonical_hashes_range(
+        &self,
+        start: BlockNumber,
+        end: BlockNumber,
+    ) -> ProviderResult<Vec<B256>> {
+        self.inner.canonical_hashes_range(start, end)
+    }
+}
+
+impl<N: NodePrimitives> StateRootProvider for HotStateProvider<N> {
+    fn state_root(&self, state: HashedPostState) -> ProviderResult<B256> {
+        self.inner.state_root(state)
+    }
+
+    fn state_root_from_nodes(&self, input: TrieInput) -> ProviderResult<B256> {
+        self.inner.state_root_from_nodes(input)
+    }
+
+    fn state_root_with_updates(
+        &self,
+        state: HashedPostState,
+    ) -> ProviderResult<(B256, TrieUpdates)> {
+        self.inner.state_root_with_updates(state)
+    }
+
+    fn state_root_from_nodes_with_updates(
+        &self,
+        input: TrieInput,
+    ) -> ProviderResult<(B256, TrieUpdates)> {
+        self.inner.state_root_from_nodes_with_updates(input)
+    }
+}
+
+impl<N: NodePrimitives> StorageRootProvider for HotStateProvider<N> {
+    fn storage_root(&self, address: Address, storage: HashedStorage) -> ProviderResult<B256> {
+        self.inner.storage_root(address, storage)
+    }
+
+    fn storage_proof(
+        &self,
+        address: Address,
+        slot: B256,
+        storage: HashedStorage,
+    ) -> ProviderResult<reth_trie::StorageProof> {
+        self.inner.storage_proof(address, slot, storage)
+    }
+
+    fn storage_multiproof(
+        &self,
+        address: Address,
+        slots: &[B256],
+        storage: HashedStorage,
+    ) -> ProviderResult<StorageMultiProof> {
+        self.inner.storage_multiproof(address, slots, storage)
+    }
+
+    fn storage_multiproof_from_nodes(
+        &self,
+        input: TrieInput,
+        address: Address,
+        slots: &[B256],
+    ) -> ProviderResult<StorageMultiProof> {
+        self.inner.storage_multiproof_from_nodes(input, address, slots)
+    }
+}
+
+impl<N: NodePrimitives> StateProofProvider for HotStateProvider<N> {
+    fn proof(
+        &self,
+        input: TrieInput,
+        address: Address,
+        slots: &[B256],
+    ) -> ProviderResult<AccountProof> {
+        self.inner.proof(input, address, slots)
+    }
+
+    fn multiproof(
+        &self,
+        input: TrieInput,
+        targets: MultiProofTargets,
+    ) -> ProviderResult<MultiProof> {
+        self.inner.multiproof(input, targets)
+    }
+
+    fn witness(&self, input: TrieInput, target: HashedPostState) -> ProviderResult<Vec<Bytes>> {
+        self.inner.witness(input, target)
+    }
+}
+
+impl<N: NodePrimitives> HashedPostStateProvider for HotStateProvider<N> {
+    fn hashed_post_state(&self, bundle_state: &BundleState) -> HashedPostState {
+        self.inner.hashed_post_state(bundle_state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TestBlockBuilder;
+    use alloy_primitives::U256;
+    use reth_execution_types::{Chain, ExecutionOutcome};
+    use reth_storage_api::noop::NoopProvider;
+    use revm_database::{AccountStatus, BundleAccount};
+    use revm_state::AccountInfo;
+    use tokio::sync::broadcast;
+
+    fn commit(parent: B256, bundle: BundleState) -> CanonStateNotification {
+        let block = TestBlockBuilder::eth().get_executed_block_with_number(1, parent);
+        let outcome = ExecutionOutcome::new(bundle, vec![vec![]], 1, vec![]);
+        let chain = Chain::from_block(block.recovered_block().clone(), outcome, None);
+        CanonStateNotification::Commit { new: Arc::new(chain) }
+    }
+
+    #[test]
+    fn follows_canonical_chain() {
+        let (tx, rx) = broadcast::channel(8);
+        let head = B256::with_last_byte(1);
+        let cache = HotStateCache::new(head, rx, 16);
+        let address = Address::with_last_byte(1);
+
+        // reads through a provider of the head block are cached
+        let provider = cache.wrap(head, Box::new(NoopProvider::default()));
+        assert_eq!(provider.basic_account(&address).unwrap(), None);
+        assert_eq!(cache.account(head, &address), Some(None));
+        assert_eq!(cache.account(B256::ZERO, &address), None);
+
+        // extending the chain updates the cached account
+        let info = AccountInfo { balance: U256::from(1), ..Default::default() };
+        let bundle = BundleState::new(
+            [(address, None, Some(info.clone()), Default::default())],
+            vec![],
+            vec![],
+        );
+        let notification = commit(head, bundle);
+        let tip = notification.tip().hash();
+        tx.send(notification).unwrap();
+        assert_eq!(cache.block_hash(), tip);
+        assert_eq!(cache.account(tip, &address), Some(Some(Account::from(&info))));
+
+        // a chain that doesn't extend the cached block clears the cache
+        tx.send(commit(B256::ZERO, BundleState::default())).unwrap();
+        let tip = cache.block_hash();
+        assert_eq!(cache.account(tip, &address), None);
+    }
+
+    #[test]
+    fn ignores_values_of_other_blocks() {
+        let (tx, rx) = broadcast::channel(8);
+        let head = B256::with_last_byte(1);
+        let cache = HotStateCache::new(head, rx, 16);
+        let address = Address::with_last_byte(1);
+
+        // a provider that was wrapped before the chain moved on doesn't fill the cache
+        let provider = cache.wrap(head, Box::new(NoopProvider::default()));
+        let notification = commit(head, BundleState::default());
+        let tip = notification.tip().hash();
+        tx.send(notification).unwrap();
+        assert_eq!(cache.block_hash(), tip);
+        assert_eq!(provider.basic_account(&address).unwrap(), None);
+        assert_eq!(cache.account(head, &address), None);
+        assert_eq!(cache.account(tip, &address), None);
+    }
+
+    #[test]
+    fn updates_and_drops_storage() {
+        let (tx, rx) = broadcast::channel(8);
+        let head = B256::with_last_byte(1);
+        let cache = HotStateCache::new(head, rx, 16);
+        let (changed, destroyed) = (Address::with_last_byte(1), Address::with_last_byte(2));
+        let key = B256::with_last_byte(3);
+
+        for address in [changed, destroyed] {
+            cache.insert_storage(head, address, key, Some(U256::from(1)));
+            assert_eq!(cache.storage(head, &address, &key), Some(Some(U256::from(1))));
+        }
+
+        let storage =
+            [(U256::from_be_bytes(key.0), (U256::from(1), U256::from(2)))].into_iter().collect();
+        let mut bundle = BundleState::new(
+            [(changed, None, Some(AccountInfo::default()), storage)],
+            vec![],
+            vec![],
+        );
+        bundle.state.insert(
+            destroyed,
+            BundleAccount {
+                info: None,
+                storage: Default::default(),
+                original_info: Some(AccountInfo::default()),
+                status: AccountStatus::Destroyed,
+            },
+        );
+        let notification = commit(head, bundle);
+        let tip = notification.tip().hash();
+        tx.send(notification).unwrap();
+        assert_eq!(cache.block_hash(), tip);
+
+        assert_eq!(cache.storage(tip, &changed, &key), Some(Some(U256::from(2))));
+        assert_eq!(cache.storage(tip, &destroyed, &key), None);
+    }
+}
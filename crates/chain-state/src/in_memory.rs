@@ -6,17 +6,18 @@ use crate::{
 };
 use alloy_consensus::{transaction::TransactionMeta, BlockHeader};
 use alloy_eips::{eip2718::Encodable2718, BlockHashOrNumber, BlockNumHash};
-use alloy_primitives::{map::HashMap, TxHash, B256};
+use alloy_primitives::{map::HashMap, TxHash, B256, U256};
 use parking_lot::RwLock;
 use reth_chainspec::ChainInfo;
 use reth_ethereum_primitives::EthPrimitives;
 use reth_execution_types::{Chain, ExecutionOutcome};
 use reth_metrics::{metrics::Gauge, Metrics};
 use reth_primitives_traits::{
-    BlockBody as _, NodePrimitives, RecoveredBlock, SealedBlock, SealedHeader, SignedTransaction,
+    Account, BlockBody as _, InMemorySize, NodePrimitives, RecoveredBlock, SealedBlock,
+    SealedHeader, SignedTransaction,
 };
 use reth_storage_api::StateProviderBox;
-use reth_trie::{updates::TrieUpdates, HashedPostState};
+use reth_trie::{updates::TrieUpdates, HashedPostState, HashedStorage};
 use std::{collections::BTreeMap, sync::Arc, time::Instant};
 use tokio::sync::{broadcast, watch};
 
@@ -122,6 +123,14 @@ impl<N: NodePrimitives> InMemoryState<N> {
         self.pending.borrow().clone()
     }
 
+    /// Returns an estimate of the memory used by all blocks and the pending block in bytes, see
+    /// [`ExecutedBlock::size`].
+    fn size(&self) -> usize {
+        let blocks =
+            self.blocks.read().values().map(|state| state.block_ref().size()).sum::<usize>();
+        blocks + self.pending.borrow().as_ref().map_or(0, |state| state.block_ref().size())
+    }
+
     #[cfg(test)]
     fn block_count(&self) -> usize {
         self.blocks.read().len()
@@ -534,6 +543,11 @@ impl<N: NodePrimitives> CanonicalInMemoryState<N> {
         self.inner.in_memory_state.head_state().into_iter().flat_map(|head| head.iter())
     }
 
+    /// Returns an estimate of the memory used by the blocks that aren't persisted yet in bytes.
+    pub fn memory_usage(&self) -> usize {
+        self.inner.in_memory_state.size()
+    }
+
     /// Returns [`SignedTransaction`] type for the given `TxHash` if found.
     pub fn transaction_by_hash(&self, hash: TxHash) -> Option<N::SignedTx> {
         for block_state in self.canonical_chain() {
@@ -796,6 +810,19 @@ impl<N: NodePrimitives> ExecutedBlock<N> {
     pub fn hashed_state(&self) -> &HashedPostState {
         &self.hashed_state
     }
+
+    /// Returns an estimate of the memory used by the block, its receipts and its hashed state in
+    /// bytes.
+    pub fn size(&self) -> usize {
+        let receipts = self.execution_output.receipts.iter().flatten().map(InMemorySize::size);
+        let storages = self.hashed_state.storages.values().map(|storage| {
+            size_of::<(B256, HashedStorage)>() + storage.storage.len() * size_of::<(B256, U256)>()
+        });
+        self.recovered_block.size() +
+            receipts.sum::<usize>() +
+            self.hashed_state.accounts.len() * size_of::<(B256, Option<Account>)>() +
+            storages.sum::<usize>()
+    }
 }
 
 /// Trie updates that result from calculating the state root for the block.
@@ -1129,6 +1156,15 @@ mod tests {
         ) -> ProviderResult<StorageMultiProof> {
             Ok(StorageMultiProof::empty())
         }
+
+        fn storage_multiproof_from_nodes(
+            &self,
+            _input: TrieInput,
+            _address: Address,
+            _slots: &[B256],
+        ) -> ProviderResult<StorageMultiProof> {
+            Ok(StorageMultiProof::empty())
+        }
     }
 
     impl StateProofProvider for MockStateProvider {
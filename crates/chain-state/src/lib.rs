@@ -18,14 +18,18 @@ pub use chain_info::ChainInfoTracker;
 
 mod notifications;
 pub use notifications::{
-    CanonStateNotification, CanonStateNotificationSender, CanonStateNotificationStream,
-    CanonStateNotifications, CanonStateSubscriptions, ForkChoiceNotifications, ForkChoiceStream,
+    CanonStateNotification, CanonStateNotificationBatch, CanonStateNotificationSender,
+    CanonStateNotificationStream, CanonStateNotifications, CanonStateSubscriptions,
+    CompactCanonStateNotificationStream, ForkChoiceNotifications, ForkChoiceStream,
     ForkChoiceSubscriptions,
 };
 
 mod memory_overlay;
 pub use memory_overlay::{MemoryOverlayStateProvider, MemoryOverlayStateProviderRef};
 
+mod hot_state;
+pub use hot_state::{HotStateCache, HotStateProvider};
+
 #[cfg(any(test, feature = "test-utils"))]
 /// Common test helpers
 pub mod test_utils;
@@ -4,8 +4,8 @@ use alloy_primitives::{keccak256, Address, BlockNumber, Bytes, StorageKey, Stora
 use reth_errors::ProviderResult;
 use reth_primitives_traits::{Account, Bytecode, NodePrimitives};
 use reth_storage_api::{
-    AccountReader, BlockHashReader, BytecodeReader, HashedPostStateProvider, StateProofProvider,
-    StateProvider, StateRootProvider, StorageRootProvider,
+    record_read, AccountReader, BlockHashReader, BytecodeReader, HashedPostStateProvider,
+    ReadSource, StateProofProvider, StateProvider, StateRootProvider, StorageRootProvider,
 };
 use reth_trie::{
     updates::TrieUpdates, AccountProof, HashedPostState, HashedStorage, MultiProof,
@@ -103,6 +103,7 @@ impl<N: NodePrimitives> AccountReader for MemoryOverlayStateProviderRef<'_, N> {
     fn basic_account(&self, address: &Address) -> ProviderResult<Option<Account>> {
         for block in &self.in_memory {
             if let Some(account) = block.execution_output.account(address) {
+                record_read(ReadSource::InMemory);
                 return Ok(account);
             }
         }
@@ -147,32 +148,33 @@ impl<N: NodePrimitives> StorageRootProvider for MemoryOverlayStateProviderRef<'_
         self.historical.storage_root(address, hashed_storage)
     }
 
-    // TODO: Currently this does not reuse available in-memory trie nodes.
     fn storage_proof(
         &self,
         address: Address,
         slot: B256,
         storage: HashedStorage,
     ) -> ProviderResult<reth_trie::StorageProof> {
-        let state = &self.trie_input().state;
-        let mut hashed_storage =
-            state.storages.get(&keccak256(address)).cloned().unwrap_or_default();
-        hashed_storage.extend(&storage);
-        self.historical.storage_proof(address, slot, hashed_storage)
+        Ok(self.storage_multiproof(address, &[slot], storage)?.storage_proof(slot)?)
     }
 
-    // TODO: Currently this does not reuse available in-memory trie nodes.
     fn storage_multiproof(
         &self,
         address: Address,
         slots: &[B256],
         storage: HashedStorage,
     ) -> ProviderResult<StorageMultiProof> {
-        let state = &self.trie_input().state;
-        let mut hashed_storage =
-            state.storages.get(&keccak256(address)).cloned().unwrap_or_default();
-        hashed_storage.extend(&storage);
-        self.historical.storage_multiproof(address, slots, hashed_storage)
+        let state = HashedPostState::default().with_storages([(keccak256(address), storage)]);
+        self.storage_multiproof_from_nodes(TrieInput::from_state(state), address, slots)
+    }
+
+    fn storage_multiproof_from_nodes(
+        &self,
+        mut input: TrieInput,
+        address: Address,
+        slots: &[B256],
+    ) -> ProviderResult<StorageMultiProof> {
+        input.prepend_self(self.trie_input().clone());
+        self.historical.storage_multiproof_from_nodes(input, address, slots)
     }
 }
 
@@ -216,6 +218,7 @@ impl<N: NodePrimitives> StateProvider for MemoryOverlayStateProviderRef<'_, N> {
     ) -> ProviderResult<Option<StorageValue>> {
         for block in &self.in_memory {
             if let Some(value) = block.execution_output.storage(&address, storage_key.into()) {
+                record_read(ReadSource::InMemory);
                 return Ok(Some(value));
             }
         }
@@ -228,6 +231,7 @@ impl<N: NodePrimitives> BytecodeReader for MemoryOverlayStateProviderRef<'_, N>
     fn bytecode_by_hash(&self, code_hash: &B256) -> ProviderResult<Option<Bytecode>> {
         for block in &self.in_memory {
             if let Some(contract) = block.execution_output.bytecode(code_hash) {
+                record_read(ReadSource::InMemory);
                 return Ok(Some(contract));
             }
         }
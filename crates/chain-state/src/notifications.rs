@@ -38,6 +38,17 @@ pub trait CanonStateSubscriptions: NodePrimitivesProvider + Send + Sync {
             st: BroadcastStream::new(self.subscribe_to_canonical_state()),
         }
     }
+
+    /// Convenience method to get a stream of [`CanonStateNotificationBatch`]es in which bursts of
+    /// consecutive commits are batched up to `max_blocks` blocks.
+    ///
+    /// See [`CompactCanonStateNotificationStream`].
+    fn compact_canonical_state_stream(
+        &self,
+        max_blocks: usize,
+    ) -> CompactCanonStateNotificationStream<Self::Primitives> {
+        CompactCanonStateNotificationStream::new(self.canonical_state_stream(), max_blocks)
+    }
 }
 
 impl<T: CanonStateSubscriptions> CanonStateSubscriptions for &T {
@@ -76,6 +87,127 @@ impl<N: NodePrimitives> Stream for CanonStateNotificationStream<N> {
     }
 }
 
+/// A [`CanonStateNotificationStream`] that coalesces bursts of consecutive commits.
+///
+/// Commits that are already buffered when a notification is polled are yielded as a single
+/// [`CanonStateNotificationBatch`] of up to `max_blocks` blocks, so that consumers don't need to
+/// be woken up per block when a large backlog is committed at once, e.g. after a restart. Reorgs
+/// are never batched, and all notifications are yielded in the order they were sent.
+#[derive(Debug)]
+#[pin_project::pin_project]
+pub struct CompactCanonStateNotificationStream<
+    N: NodePrimitives = reth_ethereum_primitives::EthPrimitives,
+> {
+    #[pin]
+    st: CanonStateNotificationStream<N>,
+    /// Maximum number of blocks of a merged commit.
+    max_blocks: usize,
+    /// A received notification that couldn't be merged into the previous one.
+    pending: Option<CanonStateNotification<N>>,
+}
+
+impl<N: NodePrimitives> CompactCanonStateNotificationStream<N> {
+    /// Creates a new stream that batches consecutive commits of the given stream up to
+    /// `max_blocks` blocks. Commits aren't batched if `max_blocks` is zero.
+    pub const fn new(st: CanonStateNotificationStream<N>, max_blocks: usize) -> Self {
+        Self { st, max_blocks, pending: None }
+    }
+}
+
+impl<N: NodePrimitives> Stream for CompactCanonStateNotificationStream<N> {
+    type Item = CanonStateNotificationBatch<N>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        let notification = match this.pending.take() {
+            Some(notification) => notification,
+            None => match ready!(this.st.as_mut().poll_next(cx)) {
+                Some(notification) => notification,
+                None => return Poll::Ready(None),
+            },
+        };
+        if let CanonStateNotification::Reorg { .. } = notification {
+            return Poll::Ready(Some(CanonStateNotificationBatch(vec![notification])))
+        }
+
+        let mut blocks = notification.committed().len();
+        let mut tip = notification.tip().hash();
+        let mut batch = vec![notification];
+
+        // batch the commits that are already buffered, without waiting for more
+        while let Poll::Ready(Some(next)) = this.st.as_mut().poll_next(cx) {
+            match &next {
+                CanonStateNotification::Commit { new }
+                    if blocks + new.len() <= *this.max_blocks && new.fork_block().hash == tip =>
+                {
+                    blocks += new.len();
+                    tip = new.tip().hash();
+                    batch.push(next);
+                }
+                _ => {
+                    *this.pending = Some(next);
+                    break
+                }
+            }
+        }
+
+        Poll::Ready(Some(CanonStateNotificationBatch(batch)))
+    }
+}
+
+/// Notifications yielded at once by a [`CompactCanonStateNotificationStream`].
+///
+/// A batch is either a single [`CanonStateNotification::Reorg`], or one or more
+/// [`CanonStateNotification::Commit`]s that each extend the previous one. The chains are shared
+/// with all other subscribers, so batching them doesn't copy any blocks.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CanonStateNotificationBatch<N: NodePrimitives = reth_ethereum_primitives::EthPrimitives>(
+    Vec<CanonStateNotification<N>>,
+);
+
+impl<N: NodePrimitives> CanonStateNotificationBatch<N> {
+    /// Returns the notifications of the batch, in the order they were sent.
+    pub fn notifications(&self) -> &[CanonStateNotification<N>] {
+        &self.0
+    }
+
+    /// Returns an iterator over the notifications of the batch, in the order they were sent.
+    pub fn iter(&self) -> impl Iterator<Item = &CanonStateNotification<N>> {
+        self.0.iter()
+    }
+
+    /// Merges the batch into a single notification.
+    ///
+    /// A batch of more than one commit is merged into a single chain, which copies its blocks and
+    /// execution outcomes because the chains are shared with the other subscribers. Consumers that
+    /// can handle the notifications one by one should iterate over the batch instead.
+    pub fn into_merged(self) -> CanonStateNotification<N> {
+        let mut notifications = self.0.into_iter();
+        let first = notifications.next().expect("batches are never empty");
+        let Some(second) = notifications.next() else { return first };
+
+        let into_chain = |notification| match notification {
+            CanonStateNotification::Commit { new } | CanonStateNotification::Reorg { new, .. } => {
+                Arc::unwrap_or_clone(new)
+            }
+        };
+        let mut merged = into_chain(first);
+        for next in std::iter::once(second).chain(notifications) {
+            merged.append_chain(into_chain(next)).expect("batched commits extend each other");
+        }
+        CanonStateNotification::Commit { new: Arc::new(merged) }
+    }
+}
+
+impl<N: NodePrimitives> IntoIterator for CanonStateNotificationBatch<N> {
+    type Item = CanonStateNotification<N>;
+    type IntoIter = std::vec::IntoIter<CanonStateNotification<N>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
 /// A notification that is sent when a new block is imported, or an old block is reverted.
 ///
 /// The notification contains at least one [`Chain`] with the imported segment. If some blocks were
@@ -222,6 +354,7 @@ mod tests {
     use reth_ethereum_primitives::{Receipt, TransactionSigned, TxType};
     use reth_execution_types::ExecutionOutcome;
     use reth_primitives_traits::SealedBlock;
+    use tokio_stream::StreamExt;
 
     #[test]
     fn test_commit_notification() {
@@ -473,4 +606,58 @@ mod tests {
         // Confirm this is from the committed segment.
         assert!(!block_receipts[1].1);
     }
+
+    #[tokio::test]
+    async fn test_compact_stream() {
+        let block = |number: u64, hash: u8, parent: u8| {
+            let mut block: RecoveredBlock<reth_ethereum_primitives::Block> = Default::default();
+            block.set_block_number(number);
+            block.set_hash(B256::new([hash; 32]));
+            block.set_parent_hash(B256::new([parent; 32]));
+            block
+        };
+        let chain = |blocks: Vec<RecoveredBlock<reth_ethereum_primitives::Block>>| {
+            Arc::new(Chain::new(blocks, ExecutionOutcome::default(), None))
+        };
+
+        let (tx, rx) = broadcast::channel(16);
+        let mut stream = CompactCanonStateNotificationStream::new(
+            CanonStateNotificationStream { st: BroadcastStream::new(rx) },
+            2,
+        );
+
+        let commits = (1..=3)
+            .map(|number| chain(vec![block(number, number as u8, number as u8 - 1)]))
+            .collect::<Vec<_>>();
+        for new in &commits {
+            tx.send(CanonStateNotification::Commit { new: new.clone() }).unwrap();
+        }
+        let reorg = CanonStateNotification::Reorg {
+            old: chain(vec![block(3, 3, 2)]),
+            new: chain(vec![block(3, 0x13, 2)]),
+        };
+        tx.send(reorg.clone()).unwrap();
+        tx.send(CanonStateNotification::Commit { new: chain(vec![block(4, 4, 0x13)]) }).unwrap();
+        drop(tx);
+
+        // the commits are batched up to the maximum number of blocks, without copying the chains
+        let batch = stream.next().await.unwrap();
+        assert_eq!(batch.notifications().len(), 2);
+        for (notification, new) in batch.iter().zip(&commits) {
+            assert!(Arc::ptr_eq(&notification.committed(), new));
+        }
+        let notification = batch.into_merged();
+        assert_eq!(notification.reverted(), None);
+        assert_eq!(notification.committed().range(), 1..=2);
+
+        // reorgs are not batched
+        let batch = stream.next().await.unwrap();
+        assert_eq!(batch.notifications().len(), 1);
+        assert!(Arc::ptr_eq(&batch.into_merged().committed(), &commits[2]));
+        assert_eq!(stream.next().await.unwrap().into_merged(), reorg);
+        let notification = stream.next().await.unwrap().into_merged();
+        assert_eq!(notification.reverted(), None);
+        assert_eq!(notification.committed().range(), 4..=4);
+        assert_eq!(stream.next().await, None);
+    }
 }
@@ -5,7 +5,7 @@ use crate::{
     constants::{MAINNET_DEPOSIT_CONTRACT, MAINNET_PRUNE_DELETE_LIMIT},
     EthChainSpec,
 };
-use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use alloc::{boxed::Box, string::ToString, sync::Arc, vec::Vec};
 use alloy_chains::{Chain, NamedChain};
 use alloy_consensus::{
     constants::{
@@ -17,7 +17,7 @@ use alloy_consensus::{
 use alloy_eips::{
     eip1559::INITIAL_BASE_FEE, eip7685::EMPTY_REQUESTS_HASH, eip7892::BlobScheduleBlobParams,
 };
-use alloy_genesis::Genesis;
+use alloy_genesis::{Genesis, GenesisAccount};
 use alloy_primitives::{address, b256, Address, BlockNumber, B256, U256};
 use alloy_trie::root::state_root_ref_unhashed;
 use core::fmt::Debug;
@@ -85,6 +85,9 @@ pub fn make_genesis_header(genesis: &Genesis, hardforks: &ChainHardforks) -> Hea
     }
 }
 
+/// Genesis config field of the block the deposit contract was deployed at.
+const DEPOSIT_CONTRACT_BLOCK_FIELD: &str = "depositContractBlock";
+
 /// The Ethereum mainnet spec
 pub static MAINNET: LazyLock<Arc<ChainSpec>> = LazyLock::new(|| {
     let genesis = serde_json::from_str(include_str!("../res/genesis/mainnet.json"))
@@ -612,6 +615,78 @@ impl ChainSpec {
         ChainSpecBuilder::default()
     }
 
+    /// Returns the [`Genesis`] of the chain with its config describing the chain ID, the
+    /// hardforks, the deposit contract and the blob schedule of this spec.
+    ///
+    /// The genesis can be serialized to a genesis JSON file. Converting it back into a
+    /// [`ChainSpec`] yields the same hardforks, deposit contract and blob parameters.
+    pub fn to_genesis(&self) -> Genesis {
+        let mut genesis = self.genesis.clone();
+        let config = &mut genesis.config;
+        config.chain_id = self.chain.id();
+
+        let block = |fork: EthereumHardfork| match self.hardforks.fork(fork) {
+            ForkCondition::Block(block) => Some(block),
+            _ => None,
+        };
+        config.homestead_block = block(EthereumHardfork::Homestead);
+        config.dao_fork_block = block(EthereumHardfork::Dao);
+        config.dao_fork_support = config.dao_fork_block.is_some();
+        config.eip150_block = block(EthereumHardfork::Tangerine);
+        config.eip155_block = block(EthereumHardfork::SpuriousDragon);
+        config.eip158_block = config.eip155_block;
+        config.byzantium_block = block(EthereumHardfork::Byzantium);
+        config.constantinople_block = block(EthereumHardfork::Constantinople);
+        config.petersburg_block = block(EthereumHardfork::Petersburg);
+        config.istanbul_block = block(EthereumHardfork::Istanbul);
+        config.muir_glacier_block = block(EthereumHardfork::MuirGlacier);
+        config.berlin_block = block(EthereumHardfork::Berlin);
+        config.london_block = block(EthereumHardfork::London);
+        config.arrow_glacier_block = block(EthereumHardfork::ArrowGlacier);
+        config.gray_glacier_block = block(EthereumHardfork::GrayGlacier);
+
+        (config.terminal_total_difficulty, config.merge_netsplit_block) =
+            match self.hardforks.fork(EthereumHardfork::Paris) {
+                ForkCondition::TTD { total_difficulty, fork_block, .. } => {
+                    (Some(total_difficulty), fork_block)
+                }
+                _ => (None, None),
+            };
+        config.terminal_total_difficulty_passed = config.terminal_total_difficulty.is_some();
+
+        let timestamp = |fork: EthereumHardfork| match self.hardforks.fork(fork) {
+            ForkCondition::Timestamp(timestamp) => Some(timestamp),
+            _ => None,
+        };
+        config.shanghai_time = timestamp(EthereumHardfork::Shanghai);
+        config.cancun_time = timestamp(EthereumHardfork::Cancun);
+        config.prague_time = timestamp(EthereumHardfork::Prague);
+        config.osaka_time = timestamp(EthereumHardfork::Osaka);
+
+        config.deposit_contract_address = self.deposit_contract.map(|contract| contract.address);
+        config.extra_fields.remove(DEPOSIT_CONTRACT_BLOCK_FIELD);
+        if let Some(contract) = self.deposit_contract.filter(|contract| contract.block != 0) {
+            config.extra_fields.insert(DEPOSIT_CONTRACT_BLOCK_FIELD.into(), contract.block.into());
+        }
+
+        config.blob_schedule.clear();
+        for (fork, name, params) in [
+            (EthereumHardfork::Cancun, "cancun", self.blob_params.cancun),
+            (EthereumHardfork::Prague, "prague", self.blob_params.prague),
+            (EthereumHardfork::Osaka, "osaka", self.blob_params.osaka),
+        ] {
+            if timestamp(fork).is_some() {
+                config.blob_schedule.insert(name.into(), params);
+            }
+        }
+        // scheduled blob parameter changes are keyed by their activation timestamp
+        for (timestamp, params) in &self.blob_params.scheduled {
+            config.blob_schedule.insert(timestamp.to_string(), *params);
+        }
+
+        genesis
+    }
+
     /// Returns the known bootnode records for the given chain.
     pub fn bootnodes(&self) -> Option<Vec<NodeRecord>> {
         use NamedChain as C;
@@ -717,13 +792,22 @@ impl From<Genesis> for ChainSpec {
         // Extract blob parameters directly from blob_schedule
         let blob_params = genesis.config.blob_schedule_blob_params();
 
-        // NOTE: in full node, we prune all receipts except the deposit contract's. We do not
-        // have the deployment block in the genesis file, so we use block zero. We use the same
-        // deposit topic as the mainnet contract if we have the deposit contract address in the
-        // genesis json.
-        let deposit_contract = genesis.config.deposit_contract_address.map(|address| {
-            DepositContract { address, block: 0, topic: MAINNET_DEPOSIT_CONTRACT.topic }
-        });
+        // NOTE: in full node, we prune all receipts except the deposit contract's. The
+        // deployment block is not part of the standard genesis file, so we use block zero if it
+        // isn't set. We use the same deposit topic as the mainnet contract if we have the deposit
+        // contract address in the genesis json.
+        let deposit_contract_block = genesis
+            .config
+            .extra_fields
+            .get(DEPOSIT_CONTRACT_BLOCK_FIELD)
+            .and_then(|block| block.as_u64())
+            .unwrap_or_default();
+        let deposit_contract =
+            genesis.config.deposit_contract_address.map(|address| DepositContract {
+                address,
+                block: deposit_contract_block,
+                topic: MAINNET_DEPOSIT_CONTRACT.topic,
+            });
 
         let hardforks = ChainHardforks::new(ordered_hardforks);
 
@@ -784,6 +868,8 @@ pub struct ChainSpecBuilder {
     chain: Option<Chain>,
     genesis: Option<Genesis>,
     hardforks: ChainHardforks,
+    deposit_contract: Option<DepositContract>,
+    blob_params: BlobScheduleBlobParams,
 }
 
 impl ChainSpecBuilder {
@@ -793,6 +879,8 @@ impl ChainSpecBuilder {
             chain: Some(MAINNET.chain),
             genesis: Some(MAINNET.genesis.clone()),
             hardforks: MAINNET.hardforks.clone(),
+            deposit_contract: None,
+            blob_params: MAINNET.blob_params.clone(),
         }
     }
 }
@@ -810,6 +898,30 @@ impl ChainSpecBuilder {
         self
     }
 
+    /// Add the given accounts to the genesis allocation.
+    ///
+    /// Starts from an empty genesis if none is set yet.
+    pub fn with_genesis_accounts(
+        mut self,
+        accounts: impl IntoIterator<Item = (Address, GenesisAccount)>,
+    ) -> Self {
+        self.genesis.get_or_insert_with(Default::default).alloc.extend(accounts);
+        self
+    }
+
+    /// Set the `PoS` deposit contract.
+    pub const fn deposit_contract(mut self, deposit_contract: DepositContract) -> Self {
+        self.deposit_contract = Some(deposit_contract);
+        self
+    }
+
+    /// Set the blob parameters of the blob-carrying hardforks and the scheduled blob parameter
+    /// changes.
+    pub fn blob_params(mut self, blob_params: BlobScheduleBlobParams) -> Self {
+        self.blob_params = blob_params;
+        self
+    }
+
     /// Add the given fork with the given activation condition to the spec.
     pub fn with_fork<H: Hardfork>(mut self, fork: H, condition: ForkCondition) -> Self {
         self.hardforks.insert(fork, condition);
@@ -838,6 +950,91 @@ impl ChainSpecBuilder {
         )
     }
 
+    /// Activate Homestead at the given block.
+    pub fn homestead_at_block(self, block: BlockNumber) -> Self {
+        self.with_fork(EthereumHardfork::Homestead, ForkCondition::Block(block))
+    }
+
+    /// Activate the DAO fork at the given block.
+    pub fn dao_at_block(self, block: BlockNumber) -> Self {
+        self.with_fork(EthereumHardfork::Dao, ForkCondition::Block(block))
+    }
+
+    /// Activate Tangerine Whistle at the given block.
+    pub fn tangerine_whistle_at_block(self, block: BlockNumber) -> Self {
+        self.with_fork(EthereumHardfork::Tangerine, ForkCondition::Block(block))
+    }
+
+    /// Activate Spurious Dragon at the given block.
+    pub fn spurious_dragon_at_block(self, block: BlockNumber) -> Self {
+        self.with_fork(EthereumHardfork::SpuriousDragon, ForkCondition::Block(block))
+    }
+
+    /// Activate Byzantium at the given block.
+    pub fn byzantium_at_block(self, block: BlockNumber) -> Self {
+        self.with_fork(EthereumHardfork::Byzantium, ForkCondition::Block(block))
+    }
+
+    /// Activate Constantinople at the given block.
+    pub fn constantinople_at_block(self, block: BlockNumber) -> Self {
+        self.with_fork(EthereumHardfork::Constantinople, ForkCondition::Block(block))
+    }
+
+    /// Activate Petersburg at the given block.
+    pub fn petersburg_at_block(self, block: BlockNumber) -> Self {
+        self.with_fork(EthereumHardfork::Petersburg, ForkCondition::Block(block))
+    }
+
+    /// Activate Istanbul at the given block.
+    pub fn istanbul_at_block(self, block: BlockNumber) -> Self {
+        self.with_fork(EthereumHardfork::Istanbul, ForkCondition::Block(block))
+    }
+
+    /// Activate Muir Glacier at the given block.
+    pub fn muir_glacier_at_block(self, block: BlockNumber) -> Self {
+        self.with_fork(EthereumHardfork::MuirGlacier, ForkCondition::Block(block))
+    }
+
+    /// Activate Berlin at the given block.
+    pub fn berlin_at_block(self, block: BlockNumber) -> Self {
+        self.with_fork(EthereumHardfork::Berlin, ForkCondition::Block(block))
+    }
+
+    /// Activate London at the given block.
+    pub fn london_at_block(self, block: BlockNumber) -> Self {
+        self.with_fork(EthereumHardfork::London, ForkCondition::Block(block))
+    }
+
+    /// Activate Arrow Glacier at the given block.
+    pub fn arrow_glacier_at_block(self, block: BlockNumber) -> Self {
+        self.with_fork(EthereumHardfork::ArrowGlacier, ForkCondition::Block(block))
+    }
+
+    /// Activate Gray Glacier at the given block.
+    pub fn gray_glacier_at_block(self, block: BlockNumber) -> Self {
+        self.with_fork(EthereumHardfork::GrayGlacier, ForkCondition::Block(block))
+    }
+
+    /// Activate Shanghai at the given timestamp.
+    pub fn shanghai_at_timestamp(self, timestamp: u64) -> Self {
+        self.with_fork(EthereumHardfork::Shanghai, ForkCondition::Timestamp(timestamp))
+    }
+
+    /// Activate Cancun at the given timestamp.
+    pub fn cancun_at_timestamp(self, timestamp: u64) -> Self {
+        self.with_fork(EthereumHardfork::Cancun, ForkCondition::Timestamp(timestamp))
+    }
+
+    /// Activate Prague at the given timestamp.
+    pub fn prague_at_timestamp(self, timestamp: u64) -> Self {
+        self.with_fork(EthereumHardfork::Prague, ForkCondition::Timestamp(timestamp))
+    }
+
+    /// Activate Osaka at the given timestamp.
+    pub fn osaka_at_timestamp(self, timestamp: u64) -> Self {
+        self.with_fork(EthereumHardfork::Osaka, ForkCondition::Timestamp(timestamp))
+    }
+
     /// Enable Frontier at genesis.
     pub fn frontier_activated(mut self) -> Self {
         self.hardforks.insert(EthereumHardfork::Frontier, ForkCondition::Block(0));
@@ -975,7 +1172,8 @@ impl ChainSpecBuilder {
             genesis,
             hardforks: self.hardforks,
             paris_block_and_final_difficulty,
-            deposit_contract: None,
+            deposit_contract: self.deposit_contract,
+            blob_params: self.blob_params,
             ..Default::default()
         }
     }
@@ -987,6 +1185,8 @@ impl From<&Arc<ChainSpec>> for ChainSpecBuilder {
             chain: Some(value.chain),
             genesis: Some(value.genesis.clone()),
             hardforks: value.hardforks.clone(),
+            deposit_contract: value.deposit_contract,
+            blob_params: value.blob_params.clone(),
         }
     }
 }
@@ -1033,7 +1233,10 @@ mod tests {
     use super::*;
     use alloy_chains::Chain;
     use alloy_consensus::constants::ETH_TO_WEI;
-    use alloy_eips::{eip4844::BLOB_TX_MIN_BLOB_GASPRICE, eip7840::BlobParams};
+    use alloy_eips::{
+        eip4844::{BLOB_TX_MIN_BLOB_GASPRICE, DATA_GAS_PER_BLOB},
+        eip7840::BlobParams,
+    };
     use alloy_evm::block::calc::{base_block_reward, block_reward};
     use alloy_genesis::{ChainConfig, GenesisAccount};
     use alloy_primitives::{b256, hex};
@@ -2540,4 +2743,87 @@ Post-merge hard forks (timestamp based):
         };
         assert_eq!(hardfork_params, expected);
     }
+
+    #[test]
+    fn custom_blob_schedule_from_genesis() {
+        let s = r#"{
+            "config": {
+                "chainId": 1337,
+                "londonBlock": 0,
+                "terminalTotalDifficulty": 0,
+                "shanghaiTime": 0,
+                "cancunTime": 0,
+                "pragueTime": 10,
+                "blobSchedule": {
+                    "cancun": {
+                        "baseFeeUpdateFraction": 3338477,
+                        "max": 6,
+                        "target": 3
+                    },
+                    "prague": {
+                        "baseFeeUpdateFraction": 8346193,
+                        "max": 32,
+                        "target": 24
+                    }
+                }
+            },
+            "alloc": {}
+        }"#;
+        let genesis: Genesis = serde_json::from_str(s).unwrap();
+        let spec = ChainSpec::from_genesis(genesis);
+
+        let cancun = spec.blob_params_at_timestamp(9).unwrap();
+        assert_eq!((cancun.target_blob_count, cancun.max_blob_count), (3, 6));
+
+        let prague = spec.blob_params_at_timestamp(10).unwrap();
+        assert_eq!((prague.target_blob_count, prague.max_blob_count), (24, 32));
+        assert_eq!(prague.update_fraction, 8346193);
+        assert_eq!(prague.max_blob_gas_per_block(), 32 * DATA_GAS_PER_BLOB);
+    }
+
+    #[test]
+    fn builder_devnet_json_roundtrip() {
+        let alloc = Address::random();
+        let deposit_contract =
+            DepositContract::new(Address::random(), 5, MAINNET_DEPOSIT_CONTRACT.topic);
+        let blob_params = BlobScheduleBlobParams {
+            prague: BlobParams { target_blob_count: 9, max_blob_count: 12, ..BlobParams::prague() },
+            scheduled: vec![(
+                30,
+                BlobParams { target_blob_count: 12, max_blob_count: 16, ..BlobParams::prague() },
+            )],
+            ..Default::default()
+        };
+        let spec = ChainSpecBuilder::default()
+            .chain(Chain::from_id(1337))
+            .with_genesis_accounts([(
+                alloc,
+                GenesisAccount::default().with_balance(U256::from(ETH_TO_WEI)),
+            )])
+            .london_activated()
+            .paris_at_ttd(U256::ZERO, 0)
+            .shanghai_at_timestamp(0)
+            .cancun_at_timestamp(10)
+            .prague_at_timestamp(20)
+            .deposit_contract(deposit_contract)
+            .blob_params(blob_params.clone())
+            .build();
+        assert_eq!(spec.fork(EthereumHardfork::Prague), ForkCondition::Timestamp(20));
+
+        let json = serde_json::to_string(&spec.to_genesis()).unwrap();
+        let decoded = ChainSpec::from_genesis(serde_json::from_str(&json).unwrap());
+
+        assert_eq!(decoded.chain, spec.chain);
+        assert_eq!(decoded.genesis_hash(), spec.genesis_hash());
+        assert!(decoded.genesis.alloc.contains_key(&alloc));
+        assert_eq!(decoded.deposit_contract, Some(deposit_contract));
+        assert_eq!(decoded.blob_params.cancun, blob_params.cancun);
+        assert_eq!(decoded.blob_params.prague, blob_params.prague);
+        assert_eq!(decoded.blob_params.scheduled, blob_params.scheduled);
+        assert_eq!(decoded.blob_params_at_timestamp(30), spec.blob_params_at_timestamp(30));
+        let forks = |spec: &ChainSpec| {
+            spec.forks_iter().map(|(fork, condition)| (fork.name(), condition)).collect::<Vec<_>>()
+        };
+        assert_eq!(forks(&decoded), forks(&spec));
+    }
 }
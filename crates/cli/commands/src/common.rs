@@ -12,6 +12,7 @@ use reth_downloaders::{bodies::noop::NoopBodiesDownloader, headers::noop::NoopHe
 use reth_eth_wire::NetPrimitivesFor;
 use reth_evm::{noop::NoopEvmConfig, ConfigureEvm};
 use reth_network::NetworkEventListenerProvider;
+use reth_nippy_jar::compression::EncryptionKey;
 use reth_node_api::FullNodeTypesAdapter;
 use reth_node_builder::{
     Node, NodeComponents, NodeComponentsBuilder, NodeTypes, NodeTypesWithDBAdapter,
@@ -26,6 +27,7 @@ use reth_provider::{
 };
 use reth_stages::{sets::DefaultStages, Pipeline, PipelineTarget};
 use reth_static_file::StaticFileProducer;
+use reth_static_file_s3::{S3Backend, S3Credentials, DEFAULT_S3_REGION};
 use std::{path::PathBuf, sync::Arc};
 use tokio::sync::watch;
 use tracing::{debug, info, warn};
@@ -92,7 +94,7 @@ impl<C: ChainSpecParser> EnvironmentArgs<C> {
         }
 
         info!(target: "reth::cli", ?db_path, ?sf_path, "Opening storage");
-        let (db, sfp) = match access {
+        let (db, mut sfp) = match access {
             AccessRights::RW => (
                 Arc::new(init_db(db_path, self.db.database_args())?),
                 StaticFileProvider::read_write(sf_path)?,
@@ -102,6 +104,24 @@ impl<C: ChainSpecParser> EnvironmentArgs<C> {
                 StaticFileProvider::read_only(sf_path, false)?,
             ),
         };
+        if let Some(key) = self.db.static_files_encryption_key()? {
+            sfp = sfp.with_encryption_key(Some(EncryptionKey::new(key.0)));
+        }
+        if let Some(max_open) = self.db.max_open_static_files {
+            sfp = sfp.with_max_open_jars(max_open);
+        }
+        let segment_directories = data_dir.static_files_segment_directories();
+        if !segment_directories.is_empty() {
+            sfp = sfp.with_segment_directories(segment_directories.iter().cloned())?;
+        }
+        if let Some(url) = data_dir.static_files_s3() {
+            let region = data_dir.static_files_s3_region().unwrap_or(DEFAULT_S3_REGION);
+            let backend = S3Backend::new(url, region)?.with_credentials(S3Credentials::from_env());
+            if let Some(size) = data_dir.static_files_s3_cache_size() {
+                sfp = sfp.with_backend_cache_size(size as u64);
+            }
+            sfp = sfp.with_backend(Arc::new(backend))?;
+        }
 
         let provider_factory = self.create_provider_factory(&config, db, sfp)?;
         if access.is_read_write() {
@@ -0,0 +1,97 @@
+use clap::Parser;
+use reth_db::{
+    create_db,
+    mdbx::DatabaseArguments,
+    migrate::{migrate_env, MigrationProgress},
+    open_db_read_only, Tables,
+};
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+/// The smallest page size supported for migrated databases.
+const MIN_PAGE_SIZE: usize = 4096;
+
+/// The largest page size supported by MDBX.
+const MAX_PAGE_SIZE: usize = 0x10000;
+
+/// The arguments for the `reth db migrate-env` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The path of the new database directory. It must not exist or be empty.
+    #[arg(long, value_name = "PATH")]
+    to: PathBuf,
+
+    /// The page size of the new database in bytes, a power of two between 4096 and 65536.
+    /// Defaults to the page size of the OS.
+    #[arg(long, value_parser = parse_page_size)]
+    page_size: Option<usize>,
+}
+
+impl Command {
+    /// Execute `db migrate-env` command
+    ///
+    /// Opens the database at `db_path` read-only and copies all tables into a new database at
+    /// `--to`, which is created with the configured page size. Static files are not part of the
+    /// database environment and can be copied as is.
+    pub fn execute(self, db_path: &Path, args: DatabaseArguments) -> eyre::Result<()> {
+        warn!("Make sure the node is not running when running `reth db migrate-env`!");
+        eyre::ensure!(
+            reth_db::is_database_empty(&self.to),
+            "Target database is not empty: {}",
+            self.to.display()
+        );
+
+        let source = open_db_read_only(db_path, args.clone())?;
+        let target = create_db(&self.to, args.with_page_size(self.page_size))?;
+        target.create_tables()?;
+
+        info!(
+            target: "reth::cli",
+            from = %db_path.display(),
+            to = %self.to.display(),
+            page_size = target.stat()?.page_size(),
+            "Migrating database"
+        );
+
+        let copied = migrate_env(&source, &target, |progress| {
+            let MigrationProgress { table, copied, total } = progress;
+            info!(target: "reth::cli", %table, copied, total, "Copied table entries");
+        })?;
+
+        info!(target: "reth::cli", copied, tables = Tables::ALL.len(), "Migrated database");
+        info!(
+            target: "reth::cli",
+            "Replace the `db` directory of the datadir with {} to use the migrated database",
+            self.to.display()
+        );
+
+        Ok(())
+    }
+}
+
+/// Parses a page size, which MDBX requires to be a power of two.
+fn parse_page_size(s: &str) -> Result<usize, String> {
+    let page_size = s.parse::<usize>().map_err(|err| err.to_string())?;
+    if !page_size.is_power_of_two() || !(MIN_PAGE_SIZE..=MAX_PAGE_SIZE).contains(&page_size) {
+        return Err(format!(
+            "page size must be a power of two between {MIN_PAGE_SIZE} and {MAX_PAGE_SIZE}"
+        ))
+    }
+    Ok(page_size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_migrate_env() {
+        let cmd =
+            Command::try_parse_from(["reth", "--to", "/tmp/db", "--page-size", "16384"]).unwrap();
+        assert_eq!(cmd.to, PathBuf::from("/tmp/db"));
+        assert_eq!(cmd.page_size, Some(16384));
+
+        assert!(Command::try_parse_from(["reth", "--to", "db", "--page-size", "5000"]).is_err());
+        assert!(Command::try_parse_from(["reth", "--to", "db", "--page-size", "1024"]).is_err());
+    }
+}
@@ -13,6 +13,11 @@ mod clear;
 mod diff;
 mod get;
 mod list;
+mod migrate_env;
+mod recompress_static_files;
+mod repair;
+mod repair_body_indices;
+mod state_size;
 mod stats;
 /// DB List TUI
 mod tui;
@@ -48,6 +53,16 @@ pub enum Subcommands {
     },
     /// Deletes all table entries
     Clear(clear::Command),
+    /// Removes orphaned entries left behind by an interrupted write, e.g. after a crash
+    Repair(repair::Command),
+    /// Re-derives block body indices from the headers and transactions in static files
+    RepairBodyIndices(repair_body_indices::Command),
+    /// Prints the state size statistics, or computes them with `--init`
+    StateSize(state_size::Command),
+    /// Copies the database into a new database with a different page size
+    MigrateEnv(migrate_env::Command),
+    /// Rewrites the existing static files with a different compression
+    RecompressStaticFiles(recompress_static_files::Command),
     /// Lists current and local database versions
     Version,
     /// Returns the full database path
@@ -135,6 +150,27 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
                 let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RW)?;
                 command.execute(provider_factory)?;
             }
+            Subcommands::Repair(command) => {
+                let access = if command.dry_run { AccessRights::RO } else { AccessRights::RW };
+                let Environment { provider_factory, .. } = self.env.init::<N>(access)?;
+                command.execute(provider_factory)?;
+            }
+            Subcommands::RepairBodyIndices(command) => {
+                let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RW)?;
+                command.execute(provider_factory)?;
+            }
+            Subcommands::StateSize(command) => {
+                let access = if command.init { AccessRights::RW } else { AccessRights::RO };
+                let Environment { provider_factory, .. } = self.env.init::<N>(access)?;
+                command.execute(provider_factory)?;
+            }
+            Subcommands::MigrateEnv(command) => {
+                command.execute(&db_path, self.env.db.database_args())?;
+            }
+            Subcommands::RecompressStaticFiles(command) => {
+                let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RW)?;
+                command.execute(provider_factory)?;
+            }
             Subcommands::Version => {
                 let local_db_version = match get_db_version(&db_path) {
                     Ok(version) => Some(version),
@@ -184,4 +220,33 @@ mod tests {
         .unwrap();
         assert_eq!(cmd.env.datadir.resolve_datadir(cmd.env.chain.chain).as_ref(), Path::new(&path));
     }
+
+    #[test]
+    fn parse_repair_body_indices() {
+        let cmd = Command::<EthereumChainSpecParser>::try_parse_from([
+            "reth",
+            "repair-body-indices",
+            "--from",
+            "5",
+            "--dry-run",
+        ])
+        .unwrap();
+        assert!(matches!(cmd.command, Subcommands::RepairBodyIndices(_)));
+    }
+
+    #[test]
+    fn parse_recompress_static_files() {
+        let cmd = Command::<EthereumChainSpecParser>::try_parse_from([
+            "reth",
+            "recompress-static-files",
+            "--compression",
+            "zstd",
+            "--zstd-level",
+            "19",
+            "--segments",
+            "headers,receipts",
+        ])
+        .unwrap();
+        assert!(matches!(cmd.command, Subcommands::RecompressStaticFiles(_)));
+    }
 }
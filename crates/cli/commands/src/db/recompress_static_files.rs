@@ -0,0 +1,143 @@
+use clap::Parser;
+use human_bytes::human_bytes;
+use reth_nippy_jar::{
+    compression::{Compressors, Encrypted, Lz4, Zstd},
+    NippyJar,
+};
+use reth_provider::{
+    providers::{ProviderNodeTypes, RECOMPRESSION_STAGING_DIR},
+    ProviderFactory, StaticFileProviderFactory,
+};
+use reth_static_file_types::{Compression, SegmentHeader, StaticFileSegment};
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+/// The arguments for the `reth db recompress-static-files` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The compression to rewrite the static files with.
+    #[arg(long, value_enum)]
+    compression: Compression,
+
+    /// The zstd compression level. A level of `0` uses zstd's default.
+    ///
+    /// Only used with `--compression zstd`.
+    #[arg(long, default_value_t = 0, allow_negative_numbers = true)]
+    zstd_level: i32,
+
+    /// Only rewrite the static files of these segments. Defaults to all segments.
+    #[arg(long, value_delimiter = ',')]
+    segments: Vec<StaticFileSegment>,
+}
+
+impl Command {
+    /// Execute `db recompress-static-files` command
+    ///
+    /// Rewrites the existing static files with the given compression. Each file is rewritten into
+    /// a staging directory first and then swapped in place of the original, so an interrupted run
+    /// leaves every static file either untouched or fully rewritten. An interrupted swap is
+    /// completed when the static files are opened again.
+    ///
+    /// If a static files encryption key is configured, the rewritten static files are encrypted
+    /// with it. Otherwise, they're rewritten unencrypted.
+    pub fn execute<N: ProviderNodeTypes>(
+        self,
+        provider_factory: ProviderFactory<N>,
+    ) -> eyre::Result<()> {
+        let static_file_provider = provider_factory.static_file_provider();
+        let compressor = |columns: usize| -> eyre::Result<Option<Compressors>> {
+            let compressor = match self.compression {
+                Compression::Lz4 => Some(Compressors::Lz4(Lz4::default())),
+                Compression::Zstd => Some(Compressors::Zstd(
+                    Zstd::new(false, 0, columns).with_level(self.zstd_level),
+                )),
+                Compression::ZstdWithDictionary => {
+                    eyre::bail!("zstd dictionaries are not supported for static files")
+                }
+                Compression::Uncompressed => None,
+            };
+            Ok(match static_file_provider.encryption_key() {
+                Some(key) => Some(Compressors::Encrypted(Encrypted::new(key, compressor))),
+                None => compressor,
+            })
+        };
+
+        let mut static_files = Vec::new();
+        for directory in static_file_provider.directories() {
+            static_files.extend(static_file_paths(directory)?.into_iter().filter(
+                |(segment, _)| static_file_provider.segment_directory(*segment) == directory,
+            ));
+        }
+
+        let (mut total_before, mut total_after) = (0, 0);
+        for (segment, path) in static_files {
+            if !self.segments.is_empty() && !self.segments.contains(&segment) {
+                continue
+            }
+            let staging_dir =
+                static_file_provider.segment_directory(segment).join(RECOMPRESSION_STAGING_DIR);
+
+            let jar = NippyJar::<SegmentHeader>::load(&path)?;
+            jar.set_encryption_key(static_file_provider.encryption_key())?;
+            let size_before = jar_size(&jar)?;
+            let jar = jar.recompress(compressor(segment.columns())?, &staging_dir)?;
+            let size_after = jar_size(&jar)?;
+
+            info!(
+                target: "reth::cli",
+                ?path,
+                before = human_bytes(size_before as f64),
+                after = human_bytes(size_after as f64),
+                "Recompressed static file"
+            );
+            total_before += size_before;
+            total_after += size_after;
+        }
+
+        for directory in static_file_provider.directories() {
+            let staging_dir = directory.join(RECOMPRESSION_STAGING_DIR);
+            if staging_dir.exists() {
+                reth_fs_util::remove_dir_all(&staging_dir)?;
+            }
+        }
+
+        // sanity check that all static files can still be loaded
+        static_file_provider.iter_static_files()?;
+
+        info!(
+            target: "reth::cli",
+            before = human_bytes(total_before as f64),
+            after = human_bytes(total_after as f64),
+            "Recompressed static files"
+        );
+
+        Ok(())
+    }
+}
+
+/// Returns the segments and data file paths of all static files in the directory.
+fn static_file_paths(static_files_path: &Path) -> eyre::Result<Vec<(StaticFileSegment, PathBuf)>> {
+    let mut paths = Vec::new();
+    for entry in reth_fs_util::read_dir(static_files_path)? {
+        let entry = entry?;
+        if !entry.file_type()?.is_file() {
+            continue
+        }
+        if let Some((segment, _)) =
+            StaticFileSegment::parse_filename(&entry.file_name().to_string_lossy())
+        {
+            paths.push((segment, entry.path()));
+        }
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+/// Returns the total size of the data, offsets and configuration files of the jar.
+fn jar_size(jar: &NippyJar<SegmentHeader>) -> eyre::Result<u64> {
+    let mut size = 0;
+    for path in [jar.data_path().to_path_buf(), jar.offsets_path(), jar.config_path()] {
+        size += reth_fs_util::metadata(path)?.len();
+    }
+    Ok(size)
+}
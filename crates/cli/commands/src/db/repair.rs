@@ -0,0 +1,386 @@
+use alloy_primitives::{Address, BlockHash, BlockNumber, TxHash, TxNumber};
+use clap::Parser;
+use reth_db_api::{
+    cursor::DbCursorRO,
+    models::BlockNumberAddress,
+    table::Table,
+    tables,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_primitives_traits::SignedTransaction;
+use reth_provider::{
+    providers::ProviderNodeTypes, DBProvider, ProviderFactory, PruneCheckpointReader,
+    StageCheckpointReader, StaticFileProviderFactory, TransactionsProvider,
+};
+use reth_prune::PruneSegment;
+use reth_stages::StageId;
+use reth_static_file_types::StaticFileSegment;
+use std::ops::RangeBounds;
+use tracing::{info, warn};
+
+/// The arguments for the `reth db repair` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// Only report the inconsistencies, without fixing them.
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Walk the whole `TransactionHashNumbers` table for entries of transactions beyond the last
+    /// block.
+    ///
+    /// By default, only the hashes of the transactions in static files beyond the last block are
+    /// looked up.
+    #[arg(long)]
+    pub full_tx_hash_scan: bool,
+}
+
+impl Command {
+    /// Execute `db repair` command
+    ///
+    /// Scans the database for inconsistencies that are left behind by an interrupted write and
+    /// aren't fixed on startup:
+    /// - canonical headers and body indices of blocks beyond the highest header in static files
+    /// - `TransactionHashNumbers` and `TransactionBlocks` entries of transactions beyond the last
+    ///   block, see [`Command::full_tx_hash_scan`]
+    /// - account and storage changesets of blocks that are already pruned or not executed
+    ///
+    /// All fixes are applied in a single database transaction.
+    pub fn execute<N: ProviderNodeTypes>(
+        self,
+        provider_factory: ProviderFactory<N>,
+    ) -> eyre::Result<()> {
+        if self.dry_run {
+            let provider = provider_factory.provider()?;
+            Inconsistencies::scan(&provider, self.full_tx_hash_scan)?.report();
+            return Ok(())
+        }
+
+        let provider_rw = provider_factory.provider_rw()?;
+        let inconsistencies = Inconsistencies::scan(&*provider_rw, self.full_tx_hash_scan)?;
+        inconsistencies.report();
+        if inconsistencies.is_repaired() {
+            return Ok(())
+        }
+
+        inconsistencies.repair(&*provider_rw)?;
+        provider_rw.commit()?;
+        info!(target: "reth::cli", "Repaired database");
+
+        Ok(())
+    }
+}
+
+/// The inconsistencies found in the database.
+#[derive(Debug, Default)]
+struct Inconsistencies {
+    /// The first block beyond the highest header in static files.
+    static_files_next_block: BlockNumber,
+    /// Canonical headers of blocks beyond the highest header in static files.
+    canonical_headers: Vec<(BlockNumber, BlockHash)>,
+    /// Number of body indices of blocks beyond the highest header in static files.
+    body_indices: usize,
+    /// The last block and its last transaction if the transaction is missing from static files.
+    ///
+    /// This can't be repaired, the block needs to be unwound.
+    missing_transactions: Option<(BlockNumber, TxNumber)>,
+    /// The first transaction beyond the last block.
+    next_tx_num: TxNumber,
+    /// Number of `TransactionBlocks` entries of transactions beyond the last block.
+    transaction_blocks: usize,
+    /// `TransactionHashNumbers` entries of transactions beyond the last block.
+    transaction_hashes: Vec<TxHash>,
+    /// Orphaned account changesets.
+    account_changesets: OrphanedChangeSets,
+    /// Orphaned storage changesets.
+    storage_changesets: OrphanedChangeSets,
+}
+
+/// Changesets that should have been removed by the pruner or an unwind.
+#[derive(Debug, Default)]
+struct OrphanedChangeSets {
+    /// The prune checkpoint and the number of changeset entries up to and including it.
+    pruned: Option<(BlockNumber, usize)>,
+    /// The execution checkpoint and the number of changeset entries beyond it.
+    unexecuted: Option<(BlockNumber, usize)>,
+}
+
+impl Inconsistencies {
+    fn scan<P>(provider: &P, full_tx_hash_scan: bool) -> eyre::Result<Self>
+    where
+        P: DBProvider + StageCheckpointReader + PruneCheckpointReader + StaticFileProviderFactory,
+    {
+        let static_file_provider = provider.static_file_provider();
+        let tx = provider.tx_ref();
+        let mut inconsistencies = Self::default();
+
+        // database entries beyond the static files
+        let highest_header = static_file_provider
+            .get_highest_static_file_block(StaticFileSegment::Headers)
+            .ok_or_else(|| eyre::eyre!("no headers in static files"))?;
+        inconsistencies.static_files_next_block = highest_header + 1;
+        for entry in tx
+            .cursor_read::<tables::CanonicalHeaders>()?
+            .walk_range(inconsistencies.static_files_next_block..)?
+        {
+            inconsistencies.canonical_headers.push(entry?);
+        }
+        inconsistencies.body_indices = count_entries::<tables::BlockBodyIndices>(
+            tx,
+            inconsistencies.static_files_next_block..,
+        )?;
+
+        // transactions beyond the last block
+        let mut cursor = tx.cursor_read::<tables::BlockBodyIndices>()?;
+        let last_block = match cursor.seek(inconsistencies.static_files_next_block)? {
+            Some(_) => cursor.prev()?,
+            None => cursor.last()?,
+        };
+        let highest_tx =
+            static_file_provider.get_highest_static_file_tx(StaticFileSegment::Transactions);
+        if let Some((number, indices)) = last_block {
+            inconsistencies.next_tx_num = indices.next_tx_num();
+            if !indices.is_empty() && highest_tx.is_none_or(|tx| tx < indices.last_tx_num()) {
+                inconsistencies.missing_transactions = Some((number, indices.last_tx_num()));
+            }
+        }
+        inconsistencies.transaction_blocks =
+            count_entries::<tables::TransactionBlocks>(tx, inconsistencies.next_tx_num..)?;
+        if full_tx_hash_scan {
+            for entry in tx.cursor_read::<tables::TransactionHashNumbers>()?.walk(None)? {
+                let (hash, tx_num) = entry?;
+                if tx_num >= inconsistencies.next_tx_num {
+                    inconsistencies.transaction_hashes.push(hash);
+                }
+            }
+        } else if let Some(highest_tx) =
+            highest_tx.filter(|highest_tx| *highest_tx >= inconsistencies.next_tx_num)
+        {
+            // only the transactions beyond the last block, instead of the whole table
+            for transaction in static_file_provider
+                .transactions_by_tx_range(inconsistencies.next_tx_num..=highest_tx)?
+            {
+                let hash = *transaction.tx_hash();
+                if tx
+                    .get::<tables::TransactionHashNumbers>(hash)?
+                    .is_some_and(|tx_num| tx_num >= inconsistencies.next_tx_num)
+                {
+                    inconsistencies.transaction_hashes.push(hash);
+                }
+            }
+        }
+
+        // changesets of pruned or unexecuted blocks
+        let executed = provider.get_stage_checkpoint(StageId::Execution)?;
+        let account_pruned = provider
+            .get_prune_checkpoint(PruneSegment::AccountHistory)?
+            .and_then(|checkpoint| checkpoint.block_number);
+        let storage_pruned = provider
+            .get_prune_checkpoint(PruneSegment::StorageHistory)?
+            .and_then(|checkpoint| checkpoint.block_number);
+
+        if let Some(block) = account_pruned {
+            let entries = count_entries::<tables::AccountChangeSets>(tx, ..=block)?;
+            inconsistencies.account_changesets.pruned = (entries > 0).then_some((block, entries));
+        }
+        if let Some(block) = storage_pruned {
+            let entries = count_entries::<tables::StorageChangeSets>(
+                tx,
+                ..BlockNumberAddress((block + 1, Address::ZERO)),
+            )?;
+            inconsistencies.storage_changesets.pruned = (entries > 0).then_some((block, entries));
+        }
+        if let Some(block) = executed.map(|checkpoint| checkpoint.block_number) {
+            let entries = count_entries::<tables::AccountChangeSets>(tx, block + 1..)?;
+            inconsistencies.account_changesets.unexecuted =
+                (entries > 0).then_some((block, entries));
+            let entries = count_entries::<tables::StorageChangeSets>(
+                tx,
+                BlockNumberAddress((block + 1, Address::ZERO))..,
+            )?;
+            inconsistencies.storage_changesets.unexecuted =
+                (entries > 0).then_some((block, entries));
+        }
+
+        Ok(inconsistencies)
+    }
+
+    /// Returns `true` if there is nothing to repair.
+    fn is_repaired(&self) -> bool {
+        self.canonical_headers.is_empty() &&
+            self.body_indices == 0 &&
+            self.transaction_blocks == 0 &&
+            self.transaction_hashes.is_empty() &&
+            self.account_changesets.is_empty() &&
+            self.storage_changesets.is_empty()
+    }
+
+    /// Logs the found inconsistencies.
+    fn report(&self) {
+        let from = self.static_files_next_block;
+        if !self.canonical_headers.is_empty() {
+            warn!(target: "reth::cli", from, entries = self.canonical_headers.len(), "Found canonical headers beyond the static files");
+        }
+        if self.body_indices > 0 {
+            warn!(target: "reth::cli", from, entries = self.body_indices, "Found body indices beyond the static files");
+        }
+        if let Some((block, tx_num)) = self.missing_transactions {
+            warn!(target: "reth::cli", block, tx_num, "Transactions of the last block are missing from static files, unwind it with `reth stage unwind`");
+        }
+
+        let from = self.next_tx_num;
+        if self.transaction_blocks > 0 {
+            warn!(target: "reth::cli", from, entries = self.transaction_blocks, "Found dangling TransactionBlocks entries");
+        }
+        if !self.transaction_hashes.is_empty() {
+            warn!(target: "reth::cli", from, entries = self.transaction_hashes.len(), "Found dangling TransactionHashNumbers entries");
+        }
+
+        for (table, changesets) in [
+            (tables::AccountChangeSets::NAME, &self.account_changesets),
+            (tables::StorageChangeSets::NAME, &self.storage_changesets),
+        ] {
+            if let Some((checkpoint, entries)) = changesets.pruned {
+                warn!(target: "reth::cli", table, checkpoint, entries, "Found changesets of pruned blocks");
+            }
+            if let Some((checkpoint, entries)) = changesets.unexecuted {
+                warn!(target: "reth::cli", table, checkpoint, entries, "Found changesets of unexecuted blocks");
+            }
+        }
+
+        if self.is_repaired() {
+            info!(target: "reth::cli", "No repairable inconsistencies found");
+        }
+    }
+
+    /// Removes the inconsistent entries.
+    fn repair<P>(&self, provider: &P) -> eyre::Result<()>
+    where
+        P: DBProvider<Tx: DbTxMut>,
+    {
+        let tx = provider.tx_ref();
+
+        for (number, hash) in &self.canonical_headers {
+            tx.delete::<tables::CanonicalHeaders>(*number, None)?;
+            tx.delete::<tables::HeaderNumbers>(*hash, None)?;
+        }
+        provider.remove::<tables::BlockBodyIndices>(self.static_files_next_block..)?;
+
+        provider.remove::<tables::TransactionBlocks>(self.next_tx_num..)?;
+        for hash in &self.transaction_hashes {
+            tx.delete::<tables::TransactionHashNumbers>(*hash, None)?;
+        }
+
+        if let Some((block, _)) = self.account_changesets.pruned {
+            provider.remove::<tables::AccountChangeSets>(..=block)?;
+        }
+        if let Some((block, _)) = self.account_changesets.unexecuted {
+            provider.remove::<tables::AccountChangeSets>(block + 1..)?;
+        }
+        if let Some((block, _)) = self.storage_changesets.pruned {
+            provider.remove::<tables::StorageChangeSets>(
+                ..BlockNumberAddress((block + 1, Address::ZERO)),
+            )?;
+        }
+        if let Some((block, _)) = self.storage_changesets.unexecuted {
+            provider.remove::<tables::StorageChangeSets>(
+                BlockNumberAddress((block + 1, Address::ZERO))..,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl OrphanedChangeSets {
+    const fn is_empty(&self) -> bool {
+        self.pruned.is_none() && self.unexecuted.is_none()
+    }
+}
+
+/// Returns the number of entries of the table in the given key range.
+fn count_entries<T: Table>(tx: &impl DbTx, range: impl RangeBounds<T::Key>) -> eyre::Result<usize> {
+    let mut entries = 0;
+    for entry in tx.cursor_read::<T>()?.walk_range(range)? {
+        entry?;
+        entries += 1;
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{SignableTransaction, TxLegacy};
+    use alloy_primitives::{Signature, B256};
+    use reth_db_api::models::AccountBeforeTx;
+    use reth_db_common::init::init_genesis;
+    use reth_ethereum_primitives::TransactionSigned;
+    use reth_provider::test_utils::create_test_provider_factory;
+
+    #[test]
+    fn scan_and_repair() {
+        let factory = create_test_provider_factory();
+        init_genesis(&factory).unwrap();
+
+        // a transaction in static files beyond the last block, left behind by an interrupted unwind
+        let transaction: TransactionSigned =
+            TxLegacy::default().into_signed(Signature::test_signature()).into();
+        let static_file_provider = factory.static_file_provider();
+        let mut writer =
+            static_file_provider.latest_writer(StaticFileSegment::Transactions).unwrap();
+        writer.append_transaction(0, &transaction).unwrap();
+        writer.commit().unwrap();
+        drop(writer);
+
+        let hash = B256::repeat_byte(1);
+        let unknown_tx_hash = B256::repeat_byte(2);
+        let provider_rw = factory.provider_rw().unwrap();
+        let tx = provider_rw.tx_ref();
+        tx.put::<tables::CanonicalHeaders>(1, hash).unwrap();
+        tx.put::<tables::HeaderNumbers>(hash, 1).unwrap();
+        tx.put::<tables::BlockBodyIndices>(1, Default::default()).unwrap();
+        tx.put::<tables::TransactionBlocks>(0, 1).unwrap();
+        tx.put::<tables::TransactionHashNumbers>(*transaction.tx_hash(), 0).unwrap();
+        tx.put::<tables::TransactionHashNumbers>(unknown_tx_hash, 1).unwrap();
+        tx.put::<tables::AccountChangeSets>(
+            1,
+            AccountBeforeTx { address: Address::ZERO, info: None },
+        )
+        .unwrap();
+        provider_rw.commit().unwrap();
+
+        let scan = |full_tx_hash_scan| {
+            Inconsistencies::scan(&factory.provider().unwrap(), full_tx_hash_scan).unwrap()
+        };
+
+        // without a full scan, only hashes of transactions in static files are found
+        let inconsistencies = scan(false);
+        assert_eq!(inconsistencies.canonical_headers, vec![(1, hash)]);
+        assert_eq!(inconsistencies.body_indices, 1);
+        assert_eq!(inconsistencies.missing_transactions, None);
+        assert_eq!(inconsistencies.transaction_blocks, 1);
+        assert_eq!(inconsistencies.transaction_hashes, vec![*transaction.tx_hash()]);
+        assert_eq!(inconsistencies.account_changesets.unexecuted, Some((0, 1)));
+        assert!(inconsistencies.storage_changesets.is_empty());
+
+        let mut hashes = scan(true).transaction_hashes;
+        hashes.sort();
+        let mut expected = vec![*transaction.tx_hash(), unknown_tx_hash];
+        expected.sort();
+        assert_eq!(hashes, expected);
+
+        // a dry run doesn't write
+        Command { dry_run: true, full_tx_hash_scan: true }.execute(factory.clone()).unwrap();
+        assert_eq!(scan(true).transaction_hashes.len(), 2);
+        assert!(!scan(true).is_repaired());
+
+        Command { dry_run: false, full_tx_hash_scan: true }.execute(factory.clone()).unwrap();
+        assert!(scan(true).is_repaired());
+
+        // the entries of the genesis block are kept
+        let provider = factory.provider().unwrap();
+        assert!(provider.tx_ref().get::<tables::CanonicalHeaders>(0).unwrap().is_some());
+        assert!(provider.tx_ref().get::<tables::BlockBodyIndices>(0).unwrap().is_some());
+        assert_eq!(provider.tx_ref().get::<tables::HeaderNumbers>(hash).unwrap(), None);
+    }
+}
@@ -0,0 +1,325 @@
+use alloy_consensus::{
+    constants::EMPTY_ROOT_HASH, proofs::calculate_transaction_root, BlockHeader, TxReceipt,
+};
+use alloy_primitives::{BlockNumber, TxNumber};
+use clap::Parser;
+use reth_db_api::{
+    cursor::DbCursorRO, models::StoredBlockBodyIndices, tables, transaction::DbTxMut,
+};
+use reth_provider::{
+    providers::ProviderNodeTypes, BlockBodyIndicesProvider, DBProvider, HeaderProvider,
+    ProviderFactory, ReceiptProvider, StaticFileProviderFactory, TransactionsProvider,
+};
+use reth_static_file_types::StaticFileSegment;
+use tracing::{info, warn};
+
+/// Minimum gas used by a transaction, which bounds the number of transactions in a block.
+const MIN_TX_GAS: u64 = 21_000;
+
+/// The arguments for the `reth db repair-body-indices` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The first block to re-derive. Its parent's body indices must be intact.
+    #[arg(long, default_value_t = 0)]
+    from: BlockNumber,
+
+    /// The last block to re-derive. Defaults to the highest header in static files.
+    #[arg(long)]
+    to: Option<BlockNumber>,
+
+    /// Only report inconsistent body indices, without rewriting them.
+    #[arg(long)]
+    dry_run: bool,
+}
+
+impl Command {
+    /// Execute `db repair-body-indices` command
+    ///
+    /// Re-derives the `BlockBodyIndices` of every block in the range by walking the transactions
+    /// static file segment and matching them against the transactions root of each header. The
+    /// cumulative gas used of the receipts, if they aren't pruned, points at the candidate
+    /// transaction count, which is then checked against the root. All
+    /// changed indices, and the `TransactionBlocks` entries derived from them, are rewritten in a
+    /// single database transaction.
+    pub fn execute<N: ProviderNodeTypes>(
+        self,
+        provider_factory: ProviderFactory<N>,
+    ) -> eyre::Result<()> {
+        let static_file_provider = provider_factory.static_file_provider();
+        let to = match self.to {
+            Some(to) => to,
+            None => static_file_provider
+                .get_highest_static_file_block(StaticFileSegment::Headers)
+                .ok_or_else(|| eyre::eyre!("no headers in static files"))?,
+        };
+        eyre::ensure!(self.from <= to, "invalid block range {}..={to}", self.from);
+
+        let provider_rw = provider_factory.provider_rw()?;
+
+        let mut next_tx_num: TxNumber = if self.from == 0 {
+            0
+        } else {
+            provider_rw
+                .block_body_indices(self.from - 1)?
+                .ok_or_else(|| {
+                    eyre::eyre!("missing body indices of parent block #{}", self.from - 1)
+                })?
+                .next_tx_num()
+        };
+        let first_tx_num = next_tx_num;
+
+        let mut repaired = Vec::new();
+        for number in self.from..=to {
+            let header = static_file_provider
+                .header_by_number(number)?
+                .ok_or_else(|| eyre::eyre!("missing header #{number} in static files"))?;
+            let existing = provider_rw.block_body_indices(number)?;
+
+            let tx_count = if header.transactions_root() == EMPTY_ROOT_HASH {
+                0
+            } else {
+                // the stored transaction count is usually still right, only derive it if it isn't
+                let hint = existing
+                    .filter(|indices| indices.first_tx_num == next_tx_num)
+                    .map(|indices| indices.tx_count);
+                derive_tx_count(&provider_rw, &static_file_provider, &header, next_tx_num, hint)?
+            };
+
+            let indices = StoredBlockBodyIndices { first_tx_num: next_tx_num, tx_count };
+            if existing != Some(indices) {
+                warn!(target: "reth::cli", block = number, ?existing, derived = ?indices, "Inconsistent body indices");
+                repaired.push((number, indices));
+            }
+            next_tx_num = indices.next_tx_num();
+        }
+
+        info!(target: "reth::cli", from = self.from, to, repaired = repaired.len(), "Derived body indices");
+        if self.dry_run || repaired.is_empty() {
+            return Ok(())
+        }
+
+        let tx = provider_rw.tx_ref();
+        for (number, indices) in &repaired {
+            tx.put::<tables::BlockBodyIndices>(*number, *indices)?;
+        }
+
+        // `TransactionBlocks` is keyed by the last transaction of each non-empty block, so stale
+        // keys are removed for the entire range before re-inserting them
+        let mut cursor = tx.cursor_write::<tables::TransactionBlocks>()?;
+        let mut walker = cursor.walk_range(first_tx_num..next_tx_num)?;
+        while walker.next().transpose()?.is_some() {
+            walker.delete_current()?;
+        }
+        for number in self.from..=to {
+            if let Some(indices) = provider_rw.block_body_indices(number)? {
+                if !indices.is_empty() {
+                    tx.put::<tables::TransactionBlocks>(indices.last_tx_num(), number)?;
+                }
+            }
+        }
+
+        provider_rw.commit()?;
+        info!(target: "reth::cli", repaired = repaired.len(), "Rewrote body indices");
+
+        Ok(())
+    }
+}
+
+/// Derives the number of transactions of the block starting at `first_tx_num`, trying the hint
+/// and the count derived from the receipts before scanning the transactions.
+fn derive_tx_count(
+    receipts: &impl ReceiptProvider<Receipt: TxReceipt>,
+    transactions: &impl TransactionsProvider,
+    header: &impl BlockHeader,
+    first_tx_num: TxNumber,
+    hint: Option<u64>,
+) -> eyre::Result<u64> {
+    if let Some(count) = hint {
+        if matches_root(transactions, header, first_tx_num, count)? {
+            return Ok(count)
+        }
+    }
+    if let Some(count) = receipts_tx_count(receipts, header, first_tx_num)? {
+        if matches_root(transactions, header, first_tx_num, count)? {
+            return Ok(count)
+        }
+    }
+    scan_tx_count(transactions, header, first_tx_num)
+}
+
+/// Returns whether the `count` transactions starting at `first_tx_num` match the transactions root
+/// of the header.
+fn matches_root(
+    provider: &impl TransactionsProvider,
+    header: &impl BlockHeader,
+    first_tx_num: TxNumber,
+    count: u64,
+) -> eyre::Result<bool> {
+    let txs = provider.transactions_by_tx_range(first_tx_num..first_tx_num + count)?;
+    Ok(txs.len() as u64 == count && calculate_transaction_root(&txs) == header.transactions_root())
+}
+
+/// Returns the number of receipts starting at `first_tx_num` whose cumulative gas used adds up to
+/// the gas used of the header, or `None` if the receipts are pruned or don't add up.
+fn receipts_tx_count(
+    provider: &impl ReceiptProvider<Receipt: TxReceipt>,
+    header: &impl BlockHeader,
+    first_tx_num: TxNumber,
+) -> eyre::Result<Option<u64>> {
+    let max_txs = header.gas_used() / MIN_TX_GAS;
+    let mut last_cumulative_gas_used = 0;
+    for count in 1..=max_txs {
+        let Some(receipt) = provider.receipt(first_tx_num + count - 1)? else { return Ok(None) };
+        let cumulative_gas_used = receipt.cumulative_gas_used();
+        if cumulative_gas_used == header.gas_used() {
+            return Ok(Some(count))
+        }
+        // the cumulative gas used resets at the first transaction of the next block
+        if cumulative_gas_used <= last_cumulative_gas_used ||
+            cumulative_gas_used > header.gas_used()
+        {
+            return Ok(None)
+        }
+        last_cumulative_gas_used = cumulative_gas_used;
+    }
+    Ok(None)
+}
+
+/// Finds the number of transactions starting at `first_tx_num` that produce the transactions root
+/// of the header.
+///
+/// This recomputes the root for every candidate count, so it's only used if the receipts of the
+/// block are pruned.
+fn scan_tx_count(
+    provider: &impl TransactionsProvider,
+    header: &impl BlockHeader,
+    first_tx_num: TxNumber,
+) -> eyre::Result<u64> {
+    let max_txs = header.gas_used() / MIN_TX_GAS;
+    let mut txs = Vec::new();
+    while (txs.len() as u64) < max_txs {
+        let tx_num = first_tx_num + txs.len() as u64;
+        let tx = provider.transaction_by_id(tx_num)?.ok_or_else(|| {
+            eyre::eyre!("missing transaction {tx_num} of block #{}", header.number())
+        })?;
+        txs.push(tx);
+        if calculate_transaction_root(&txs) == header.transactions_root() {
+            return Ok(txs.len() as u64)
+        }
+    }
+    eyre::bail!(
+        "no transactions starting at {first_tx_num} match the transactions root of block #{}",
+        header.number()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{Header, SignableTransaction, TxLegacy};
+    use alloy_primitives::{Signature, U256};
+    use reth_db_api::transaction::DbTx;
+    use reth_db_common::init::init_genesis;
+    use reth_ethereum_primitives::{Receipt, TransactionSigned, TxType};
+    use reth_provider::test_utils::create_test_provider_factory;
+
+    #[test]
+    fn repair_body_indices() {
+        let factory = create_test_provider_factory();
+        init_genesis(&factory).unwrap();
+
+        let transactions: Vec<TransactionSigned> = (0..3)
+            .map(|nonce| {
+                TxLegacy { nonce, gas_limit: MIN_TX_GAS, ..Default::default() }
+                    .into_signed(Signature::test_signature())
+                    .into()
+            })
+            .collect();
+        // block 1 has two transactions with receipts, block 2 has one with pruned receipts
+        let headers = [
+            Header {
+                number: 1,
+                gas_used: 2 * MIN_TX_GAS,
+                transactions_root: calculate_transaction_root(&transactions[..2]),
+                ..Default::default()
+            },
+            Header {
+                number: 2,
+                gas_used: MIN_TX_GAS,
+                transactions_root: calculate_transaction_root(&transactions[2..]),
+                ..Default::default()
+            },
+        ];
+
+        let static_file_provider = factory.static_file_provider();
+        let mut writer = static_file_provider.latest_writer(StaticFileSegment::Headers).unwrap();
+        for header in &headers {
+            writer.append_header(header, U256::ZERO, &header.hash_slow()).unwrap();
+        }
+        writer.commit().unwrap();
+        drop(writer);
+        let mut writer =
+            static_file_provider.latest_writer(StaticFileSegment::Transactions).unwrap();
+        for (tx_num, transaction) in transactions.iter().enumerate() {
+            writer.append_transaction(tx_num as TxNumber, transaction).unwrap();
+        }
+        writer.commit().unwrap();
+        drop(writer);
+
+        let provider_rw = factory.provider_rw().unwrap();
+        let tx = provider_rw.tx_ref();
+        for tx_num in 0..2 {
+            let receipt = Receipt {
+                tx_type: TxType::Legacy,
+                success: true,
+                cumulative_gas_used: (tx_num + 1) * MIN_TX_GAS,
+                logs: Vec::new(),
+            };
+            tx.put::<tables::Receipts<Receipt>>(tx_num, receipt).unwrap();
+        }
+        tx.put::<tables::BlockBodyIndices>(
+            1,
+            StoredBlockBodyIndices { first_tx_num: 0, tx_count: 3 },
+        )
+        .unwrap();
+        tx.put::<tables::BlockBodyIndices>(
+            2,
+            StoredBlockBodyIndices { first_tx_num: 3, tx_count: 0 },
+        )
+        .unwrap();
+        tx.put::<tables::TransactionBlocks>(2, 1).unwrap();
+        provider_rw.commit().unwrap();
+
+        // the receipts point at the transaction count of block 1 only
+        let provider = factory.provider().unwrap();
+        assert_eq!(receipts_tx_count(&provider, &headers[0], 0).unwrap(), Some(2));
+        assert_eq!(receipts_tx_count(&provider, &headers[1], 2).unwrap(), None);
+        drop(provider);
+
+        let body_indices = |number| {
+            factory.provider().unwrap().tx_ref().get::<tables::BlockBodyIndices>(number).unwrap()
+        };
+        let transaction_blocks = || {
+            factory
+                .provider()
+                .unwrap()
+                .tx_ref()
+                .cursor_read::<tables::TransactionBlocks>()
+                .unwrap()
+                .walk(None)
+                .unwrap()
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap()
+        };
+
+        // a dry run doesn't write
+        Command { from: 1, to: None, dry_run: true }.execute(factory.clone()).unwrap();
+        assert_eq!(body_indices(1), Some(StoredBlockBodyIndices { first_tx_num: 0, tx_count: 3 }));
+        assert_eq!(transaction_blocks(), vec![(2, 1)]);
+
+        Command { from: 1, to: None, dry_run: false }.execute(factory.clone()).unwrap();
+        assert_eq!(body_indices(1), Some(StoredBlockBodyIndices { first_tx_num: 0, tx_count: 2 }));
+        assert_eq!(body_indices(2), Some(StoredBlockBodyIndices { first_tx_num: 2, tx_count: 1 }));
+        assert_eq!(transaction_blocks(), vec![(1, 1), (2, 2)]);
+    }
+}
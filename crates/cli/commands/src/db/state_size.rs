@@ -0,0 +1,47 @@
+use clap::Parser;
+use reth_provider::{providers::ProviderNodeTypes, ProviderFactory, StateSize, StateSizeReader};
+use tracing::info;
+
+/// The arguments for the `reth db state-size` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// Scans the plain state to compute the statistics and maintains them from then on.
+    #[arg(long)]
+    pub init: bool,
+}
+
+impl Command {
+    /// Execute `db state-size` command
+    pub fn execute<N: ProviderNodeTypes>(
+        self,
+        provider_factory: ProviderFactory<N>,
+    ) -> eyre::Result<()> {
+        let size = if self.init {
+            let provider_rw = provider_factory.provider_rw()?;
+            info!(target: "reth::cli", "Scanning plain state");
+            let size = provider_rw.init_state_size()?;
+            provider_rw.commit()?;
+            size
+        } else {
+            let Some(size) = provider_factory.state_size()? else {
+                println!(
+                    "State size statistics are not maintained, run with --init to enable them"
+                );
+                return Ok(())
+            };
+            size
+        };
+
+        print_state_size(&size);
+        Ok(())
+    }
+}
+
+fn print_state_size(size: &StateSize) {
+    println!("Block:          {}", size.block_number);
+    println!("Accounts:       {}", size.accounts);
+    println!("Contracts:      {}", size.contracts);
+    println!("Storage slots:  {}", size.storage_slots);
+    println!("Bytecodes:      {}", size.bytecodes);
+    println!("Bytecode bytes: {}", size.bytecode_bytes);
+}
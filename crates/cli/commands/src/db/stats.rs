@@ -5,7 +5,7 @@ use eyre::WrapErr;
 use human_bytes::human_bytes;
 use itertools::Itertools;
 use reth_chainspec::EthereumHardforks;
-use reth_db::{mdbx, static_file::iter_static_files, DatabaseEnv};
+use reth_db::{mdbx, DatabaseEnv};
 use reth_db_api::{database::Database, TableViewer, Tables};
 use reth_db_common::DbTool;
 use reth_fs_util as fs;
@@ -172,9 +172,9 @@ impl Command {
             ]);
         }
 
-        let static_files = iter_static_files(&data_dir.static_files())?;
         let static_file_provider =
             StaticFileProvider::<N>::read_only(data_dir.static_files(), false)?;
+        let static_files = static_file_provider.iter_static_files()?;
 
         let mut total_data_size = 0;
         let mut total_index_size = 0;
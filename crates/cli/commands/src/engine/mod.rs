@@ -0,0 +1,28 @@
+//! `reth engine` command.
+
+use clap::{Parser, Subcommand};
+
+pub mod replay;
+
+/// `reth engine` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[command(subcommand)]
+    command: Subcommands,
+}
+
+/// `reth engine` subcommands
+#[derive(Subcommand, Debug)]
+pub enum Subcommands {
+    /// Replay engine API calls recorded with `--debug.engine-api-record` against a node.
+    Replay(replay::Command),
+}
+
+impl Command {
+    /// Execute `engine` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        match self.command {
+            Subcommands::Replay(command) => command.execute().await,
+        }
+    }
+}
@@ -0,0 +1,108 @@
+//! `reth engine replay` command.
+
+use clap::Parser;
+use eyre::WrapErr;
+use reqwest::{header::AUTHORIZATION, Client, Url};
+use reth_rpc_builder::engine_record::{read_journal, EngineApiRecord};
+use reth_rpc_layer::{secret_to_bearer_header, JwtSecret};
+use serde_json::json;
+use std::{path::PathBuf, time::Duration};
+use tracing::{info, warn};
+
+/// `reth engine replay` command
+///
+/// Sends the recorded `engine_newPayload` and `engine_forkchoiceUpdated` calls to the engine API
+/// of a node in the order they were recorded, and compares the responses to the recorded ones.
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The journal of engine API calls, recorded with `--debug.engine-api-record`.
+    #[arg(value_name = "FILE")]
+    journal: PathBuf,
+
+    /// The engine API endpoint of the node to replay the calls against.
+    #[arg(long = "engine.url", value_name = "URL", default_value = "http://localhost:8551")]
+    engine_url: Url,
+
+    /// Path to the JWT secret of the node's engine API.
+    #[arg(long = "engine.jwtsecret", value_name = "PATH")]
+    jwt_secret: PathBuf,
+
+    /// Number of recorded calls to skip.
+    #[arg(long, default_value_t = 0)]
+    skip: usize,
+
+    /// Replay the calls with the delays between them as they were recorded.
+    #[arg(long)]
+    realtime: bool,
+
+    /// Stop at the first response that differs from the recorded one.
+    #[arg(long)]
+    stop_on_mismatch: bool,
+}
+
+impl Command {
+    /// Execute `engine replay` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        let secret = JwtSecret::from_file(&self.jwt_secret).wrap_err_with(|| {
+            format!("Could not read JWT secret: {}", self.jwt_secret.display())
+        })?;
+        let records = read_journal(&self.journal)
+            .wrap_err_with(|| format!("Could not read journal: {}", self.journal.display()))?;
+        info!(target: "reth::cli", total = records.len(), skip = self.skip, "Replaying engine API calls");
+
+        let client = Client::new();
+        let (mut replayed, mut mismatches) = (0, 0);
+        let mut previous: Option<&EngineApiRecord> = None;
+        for (id, record) in records.iter().enumerate().skip(self.skip) {
+            if self.realtime {
+                if let Some(previous) = previous {
+                    let delay = record.requested_at.saturating_sub(previous.requested_at);
+                    tokio::time::sleep(Duration::from_millis(delay)).await;
+                }
+            }
+            previous = Some(record);
+
+            let response: serde_json::Value = client
+                .post(self.engine_url.clone())
+                // a fresh token for every call, tokens are only valid for a short time
+                .header(AUTHORIZATION, secret_to_bearer_header(&secret))
+                .json(&json!({
+                    "jsonrpc": "2.0",
+                    "id": id,
+                    "method": record.method,
+                    "params": record.params,
+                }))
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+            replayed += 1;
+
+            let outcome = response.get("result").or_else(|| response.get("error"));
+            if outcome == record.outcome() {
+                info!(target: "reth::cli", id, method = %record.method, "Replayed engine API call");
+                continue
+            }
+
+            mismatches += 1;
+            warn!(
+                target: "reth::cli",
+                id,
+                method = %record.method,
+                recorded = ?record.outcome(),
+                replayed = ?outcome,
+                "Response differs from the recorded response"
+            );
+            if self.stop_on_mismatch {
+                break
+            }
+        }
+
+        info!(target: "reth::cli", replayed, mismatches, "Replayed engine API calls");
+        if mismatches > 0 {
+            eyre::bail!("{mismatches} of {replayed} responses differ from the recorded responses")
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,129 @@
+//! `reth exex wal` command.
+
+use crate::common::{CliNodeTypes, EnvironmentArgs};
+use clap::{Parser, Subcommand};
+use comfy_table::{Cell, Row, Table};
+use human_bytes::human_bytes;
+use reth_chainspec::EthChainSpec;
+use reth_cli::chainspec::ChainSpecParser;
+use reth_exex::{inspect_wal, truncate_wal, WalEntryStatus};
+use std::{ops::RangeInclusive, sync::Arc};
+use tracing::{info, warn};
+
+/// `reth exex wal` command
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    #[command(subcommand)]
+    command: Subcommands,
+}
+
+/// `reth exex wal` subcommands
+#[derive(Subcommand, Debug)]
+pub enum Subcommands {
+    /// Lists all WAL entries and reports entries that are missing or can't be decoded.
+    Inspect,
+    /// Truncates the WAL at the first entry that is missing or can't be decoded.
+    ///
+    /// All entries before it are kept, so that ExExes can still be caught up from the WAL.
+    Repair {
+        /// Only report what would be removed, without removing anything.
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec>> Command<C> {
+    /// Execute `exex wal` command
+    pub async fn execute<N: CliNodeTypes<ChainSpec = C::ChainSpec>>(self) -> eyre::Result<()> {
+        let data_dir = self.env.datadir.clone().resolve_datadir(self.env.chain.chain());
+        let wal_dir = data_dir.exex_wal();
+        eyre::ensure!(wal_dir.is_dir(), "ExEx WAL does not exist: {:?}", wal_dir);
+
+        let inspection = inspect_wal::<N::Primitives>(&wal_dir)?;
+
+        match self.command {
+            Subcommands::Inspect => {
+                let mut table = Table::new();
+                table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+                table.set_header(["File ID", "Size", "Notification", "Committed", "Reverted"]);
+                for entry in &inspection.entries {
+                    let mut row = Row::new();
+                    row.add_cell(Cell::new(entry.file_id))
+                        .add_cell(Cell::new(human_bytes(entry.size as f64)));
+                    match &entry.status {
+                        WalEntryStatus::Valid { kind, committed, reverted } => {
+                            row.add_cell(Cell::new(format!("{kind:?}")))
+                                .add_cell(Cell::new(format_range(committed)))
+                                .add_cell(Cell::new(format_range(reverted)));
+                        }
+                        WalEntryStatus::Missing => {
+                            row.add_cell(Cell::new("MISSING"));
+                        }
+                        WalEntryStatus::Corrupt(err) => {
+                            row.add_cell(Cell::new(format!("CORRUPT: {err}")));
+                        }
+                    }
+                    table.add_row(row);
+                }
+                println!("{table}");
+
+                for path in &inspection.temporary_files {
+                    warn!(target: "reth::cli", ?path, "Leftover temporary file of an interrupted write");
+                }
+                info!(
+                    target: "reth::cli",
+                    entries = inspection.entries.len(),
+                    size = %human_bytes(inspection.size() as f64),
+                    committed_blocks = ?inspection.committed_block_range(),
+                    first_invalid = ?inspection.first_invalid(),
+                    "Inspected ExEx WAL"
+                );
+            }
+            Subcommands::Repair { dry_run } => {
+                let Some(first_invalid) = inspection.first_invalid() else {
+                    info!(target: "reth::cli", temporary_files = inspection.temporary_files.len(), "All WAL entries are valid");
+                    if !dry_run && !inspection.temporary_files.is_empty() {
+                        // there are no entries past the last one, this only removes the
+                        // temporary files
+                        truncate_wal::<N::Primitives>(&wal_dir, u32::MAX)?;
+                    }
+                    return Ok(())
+                };
+
+                let removed =
+                    inspection.entries.iter().filter(|entry| entry.file_id >= first_invalid);
+                warn!(
+                    target: "reth::cli",
+                    first_invalid,
+                    entries = removed.count(),
+                    committed_blocks = ?inspection.committed_block_range(),
+                    "Truncating ExEx WAL"
+                );
+                if dry_run {
+                    return Ok(())
+                }
+
+                let (removed, removed_size) =
+                    truncate_wal::<N::Primitives>(&wal_dir, first_invalid)?;
+                info!(target: "reth::cli", removed, removed_size = %human_bytes(removed_size as f64), "Truncated ExEx WAL");
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<C: ChainSpecParser> Command<C> {
+    /// Returns the underlying chain being used to run this command
+    pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        Some(&self.env.chain)
+    }
+}
+
+/// Formats an optional block range for display.
+fn format_range(range: &Option<RangeInclusive<u64>>) -> String {
+    range.as_ref().map(|range| format!("{}..={}", range.start(), range.end())).unwrap_or_default()
+}
@@ -6,7 +6,7 @@ use crate::{
 use clap::Parser;
 use reth_chainspec::{ChainSpecProvider, EthChainSpec, EthereumHardforks};
 use reth_cli::chainspec::ChainSpecParser;
-use reth_node_core::version::SHORT_VERSION;
+use reth_node_core::{args::EthashArgs, version::SHORT_VERSION};
 use std::{path::PathBuf, sync::Arc};
 use tracing::info;
 
@@ -32,6 +32,9 @@ pub struct ImportCommand<C: ChainSpecParser> {
     /// remaining stages are executed.
     #[arg(value_name = "IMPORT_PATH", verbatim_doc_comment)]
     path: PathBuf,
+
+    #[command(flatten)]
+    ethash: EthashArgs,
 }
 
 impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> ImportCommand<C> {
@@ -78,6 +81,11 @@ impl<C: ChainSpecParser> ImportCommand<C> {
     pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
         Some(&self.env.chain)
     }
+
+    /// Returns the ethash seal verification arguments
+    pub const fn ethash_args(&self) -> &EthashArgs {
+        &self.ethash
+    }
 }
 
 #[cfg(test)]
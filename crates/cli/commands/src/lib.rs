@@ -13,6 +13,8 @@ pub mod config_cmd;
 pub mod db;
 pub mod download;
 pub mod dump_genesis;
+pub mod engine;
+pub mod exex;
 pub mod import;
 pub mod import_era;
 pub mod import_op;
@@ -23,8 +25,10 @@ pub mod node;
 pub mod p2p;
 pub mod prune;
 pub mod recover;
+pub mod snapshot;
 pub mod stage;
 #[cfg(feature = "arbitrary")]
 pub mod test_vectors;
+pub mod verify_execution;
 
 pub use node::NodeCommand;
@@ -6,12 +6,12 @@ use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_cli::chainspec::ChainSpecParser;
 use reth_cli_runner::CliContext;
 use reth_cli_util::parse_socket_address;
-use reth_db::init_db;
+use reth_db::{init_db, open_db_read_only};
 use reth_node_builder::NodeBuilder;
 use reth_node_core::{
     args::{
-        DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, EngineArgs, EraArgs, NetworkArgs,
-        PayloadBuilderArgs, PruningArgs, RpcServerArgs, TxPoolArgs,
+        DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, EngineArgs, EraArgs, MemoryArgs,
+        NetworkArgs, PayloadBuilderArgs, PruningArgs, RpcServerArgs, TxPoolArgs, WebhookArgs,
     },
     node_config::NodeConfig,
     version,
@@ -69,6 +69,15 @@ pub struct NodeCommand<C: ChainSpecParser, Ext: clap::Args + fmt::Debug = NoArgs
     #[arg(long, conflicts_with = "instance", global = true)]
     pub with_unused_ports: bool,
 
+    /// Start in safe mode.
+    ///
+    /// Opens storage read-only and disables sync and pruning. If the startup consistency check
+    /// finds damaged storage, the damage is left in place instead of unwinding it: RPC keeps
+    /// serving the consistent range of the chain and returns a storage degraded error for blocks
+    /// above it. The damage report is available via `reth_repairStatus`.
+    #[arg(long)]
+    pub safe_mode: bool,
+
     /// All datadir related arguments
     #[command(flatten)]
     pub datadir: DatadirArgs,
@@ -113,6 +122,14 @@ pub struct NodeCommand<C: ChainSpecParser, Ext: clap::Args + fmt::Debug = NoArgs
     #[command(flatten, next_help_heading = "ERA")]
     pub era: EraArgs,
 
+    /// All memory budget related arguments with --memory prefix
+    #[command(flatten, next_help_heading = "Memory")]
+    pub memory: MemoryArgs,
+
+    /// All webhook notification related arguments with --webhook prefix
+    #[command(flatten, next_help_heading = "Webhook")]
+    pub webhook: WebhookArgs,
+
     /// Additional cli arguments
     #[command(flatten, next_help_heading = "Extension")]
     pub ext: Ext,
@@ -157,6 +174,7 @@ where
             metrics,
             instance,
             with_unused_ports,
+            safe_mode,
             network,
             rpc,
             txpool,
@@ -168,6 +186,8 @@ where
             ext,
             engine,
             era,
+            memory,
+            webhook,
         } = self;
 
         // set up node config
@@ -187,18 +207,30 @@ where
             pruning,
             engine,
             era,
+            memory,
+            webhook,
+            safe_mode,
         };
 
         let data_dir = node_config.datadir();
         let db_path = data_dir.db();
 
-        tracing::info!(target: "reth::cli", path = ?db_path, "Opening database");
-        let database = Arc::new(init_db(db_path.clone(), self.db.database_args())?.with_metrics());
+        let database = if safe_mode {
+            tracing::info!(target: "reth::cli", path = ?db_path, "Opening database read-only in safe mode");
+            Arc::new(open_db_read_only(&db_path, self.db.database_args())?.with_metrics())
+        } else {
+            tracing::info!(target: "reth::cli", path = ?db_path, "Opening database");
+            Arc::new(init_db(db_path.clone(), self.db.database_args())?.with_metrics())
+        };
 
         if with_unused_ports {
             node_config = node_config.with_unused_ports();
         }
 
+        if node_config.debug.task_instrumentation {
+            ctx.task_executor.task_registry().enable();
+        }
+
         let builder = NodeBuilder::new(node_config)
             .with_database(database)
             .with_launch_context(ctx.task_executor);
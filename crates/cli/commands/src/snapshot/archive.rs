@@ -0,0 +1,423 @@
+//! Creation and restoration of datadir snapshot archives.
+//!
+//! A snapshot archive is an lz4 compressed tarball with the following layout:
+//!
+//! ```text
+//! db/mdbx.dat
+//! static_files/<segment files>
+//! manifest.json
+//! ```
+//!
+//! The manifest is always the last entry and contains the size and keccak256 hash of every other
+//! file in the archive, which are validated on restore before anything is moved into the target
+//! datadir.
+
+use alloy_primitives::{Keccak256, B256};
+use eyre::{ensure, eyre, OptionExt};
+use lz4::{Decoder, EncoderBuilder};
+use reth_db::{mdbx::DatabaseArguments, open_db_read_only, DatabaseEnv};
+use reth_db_api::{database::Database, tables, transaction::DbTx};
+use reth_fs_util as fs;
+use reth_stages::StageId;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    io::{self, Read},
+    path::{Component, Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tar::{Archive, Builder, EntryType, Header};
+use tracing::{debug, info};
+
+/// Current version of the snapshot manifest format.
+pub const SNAPSHOT_MANIFEST_VERSION: u32 = 1;
+
+/// Name of the manifest entry in the archive.
+pub(super) const MANIFEST_FILE_NAME: &str = "manifest.json";
+/// Directory of the MDBX database in the archive.
+pub(super) const DB_DIR: &str = "db";
+/// Directory of the static files in the archive.
+pub(super) const STATIC_FILES_DIR: &str = "static_files";
+/// Name of the MDBX data file.
+pub(super) const MDBX_DATA_FILE: &str = "mdbx.dat";
+/// Files that are never included in a snapshot.
+const EXCLUDED_FILES: [&str; 2] = ["lock", "mdbx.lck"];
+
+/// Manifest describing the contents of a snapshot archive.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotManifest {
+    /// Version of the manifest format.
+    pub version: u32,
+    /// Chain id of the snapshotted node.
+    pub chain_id: u64,
+    /// Block number of the [`StageId::Finish`] checkpoint in the database copy, if any.
+    pub block_number: Option<u64>,
+    /// Unix timestamp in seconds at which the snapshot was created.
+    pub created_at: u64,
+    /// All files in the archive, keyed by their path relative to the datadir.
+    pub files: BTreeMap<PathBuf, SnapshotFile>,
+}
+
+impl SnapshotManifest {
+    /// Creates a manifest of the current manifest version, created now.
+    pub fn new(
+        chain_id: u64,
+        block_number: Option<u64>,
+        files: BTreeMap<PathBuf, SnapshotFile>,
+    ) -> eyre::Result<Self> {
+        let created_at = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        Ok(Self { version: SNAPSHOT_MANIFEST_VERSION, chain_id, block_number, created_at, files })
+    }
+
+    /// Checks that the manifest has a supported version and was created for the chain with
+    /// `chain_id`.
+    pub fn ensure_compatible(&self, chain_id: u64) -> eyre::Result<()> {
+        ensure!(
+            self.version == SNAPSHOT_MANIFEST_VERSION,
+            "Unsupported snapshot manifest version {}",
+            self.version
+        );
+        ensure!(
+            self.chain_id == chain_id,
+            "Snapshot was taken for chain {}, expected chain {chain_id}",
+            self.chain_id
+        );
+        Ok(())
+    }
+}
+
+/// A single file of a snapshot archive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotFile {
+    /// Size of the file in bytes.
+    pub size: u64,
+    /// Keccak256 hash of the file contents.
+    pub hash: B256,
+}
+
+/// Creates a snapshot archive at `output` from the given database and static files directory.
+///
+/// The database is copied from a single read transaction, so the node can keep running while the
+/// snapshot is taken. Static files are copied afterwards: since they are always written before the
+/// database is committed, they may be ahead of the database copy, which is healed by the regular
+/// consistency check on the first startup after a restore.
+///
+/// The archive is first written to a temporary file and only moved to `output` on success.
+pub fn create_snapshot(
+    db: &DatabaseEnv,
+    static_files: &Path,
+    chain_id: u64,
+    output: &Path,
+    compact: bool,
+) -> eyre::Result<SnapshotManifest> {
+    ensure!(!output.exists(), "Snapshot output already exists: {output:?}");
+
+    let tmp_dir = output.with_extension("tmp");
+    fs::create_dir_all(&tmp_dir)?;
+    let result = (|| {
+        // Copy the database first, the copy is consistent by itself.
+        let db_copy_dir = tmp_dir.join(DB_DIR);
+        let block_number = copy_db(db, &db_copy_dir, compact)?;
+
+        let tmp_archive = tmp_dir.join("snapshot.tar.lz4");
+        let encoder = EncoderBuilder::new().build(fs::create_file(&tmp_archive)?)?;
+        let mut builder = Builder::new(encoder);
+        let mut files = BTreeMap::new();
+
+        let db_file = Path::new(DB_DIR).join(MDBX_DATA_FILE);
+        let entry = append_file(&mut builder, &db_file, &db_copy_dir.join(MDBX_DATA_FILE))?;
+        files.insert(db_file, entry);
+
+        info!(target: "reth::cli", ?static_files, "Copying static files");
+        for (relative, path) in collect_files(static_files)? {
+            let name = Path::new(STATIC_FILES_DIR).join(relative);
+            let entry = append_file(&mut builder, &name, &path)?;
+            debug!(target: "reth::cli", ?name, size = entry.size, "Added static file to snapshot");
+            files.insert(name, entry);
+        }
+
+        let manifest = SnapshotManifest::new(chain_id, block_number, files)?;
+        let created_at = manifest.created_at;
+        let manifest_bytes = serde_json::to_vec_pretty(&manifest)?;
+        let mut header = Header::new_gnu();
+        header.set_size(manifest_bytes.len() as u64);
+        header.set_mode(0o644);
+        header.set_mtime(created_at);
+        builder.append_data(&mut header, MANIFEST_FILE_NAME, manifest_bytes.as_slice())?;
+
+        let (_, result) = builder.into_inner()?.finish();
+        result?;
+        fs::rename(&tmp_archive, output)?;
+
+        Ok(manifest)
+    })();
+    fs::remove_dir_all(&tmp_dir)?;
+
+    result
+}
+
+/// Restores a snapshot archive of the chain with `chain_id` into `data_dir`.
+///
+/// The archive is unpacked into a staging directory inside `data_dir` first. Only if every file
+/// matches the size and hash recorded in the manifest, and no file is missing or unexpected, the
+/// database and static files are moved into place.
+pub fn restore_snapshot(
+    archive: &Path,
+    data_dir: &Path,
+    chain_id: u64,
+) -> eyre::Result<SnapshotManifest> {
+    let db_dir = data_dir.join(DB_DIR);
+    let static_files_dir = data_dir.join(STATIC_FILES_DIR);
+    ensure!(
+        is_missing_or_empty(&db_dir)? && is_missing_or_empty(&static_files_dir)?,
+        "Datadir {data_dir:?} already contains a database or static files"
+    );
+
+    let staging_dir = data_dir.join(".snapshot-restore");
+    if staging_dir.exists() {
+        fs::remove_dir_all(&staging_dir)?;
+    }
+    fs::create_dir_all(&staging_dir)?;
+
+    let result = (|| {
+        let mut unpacked = BTreeMap::new();
+        let mut manifest = None;
+
+        let mut archive = Archive::new(Decoder::new(fs::open(archive)?)?);
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            if entry.header().entry_type() != EntryType::Regular {
+                continue
+            }
+
+            let name = entry.path()?.into_owned();
+            ensure!(
+                name.components().all(|component| matches!(component, Component::Normal(_))),
+                "Invalid path in snapshot archive: {name:?}"
+            );
+
+            if name == Path::new(MANIFEST_FILE_NAME) {
+                let mut bytes = Vec::new();
+                entry.read_to_end(&mut bytes)?;
+                manifest = Some(serde_json::from_slice::<SnapshotManifest>(&bytes)?);
+                continue
+            }
+
+            let path = staging_dir.join(&name);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            let mut reader = HashingReader::new(&mut entry);
+            io::copy(&mut reader, &mut fs::create_file(&path)?)?;
+            debug!(target: "reth::cli", ?name, size = reader.size, "Unpacked snapshot file");
+            unpacked.insert(name, reader.finish());
+        }
+
+        let manifest = manifest.ok_or_eyre("Snapshot archive does not contain a manifest")?;
+        manifest.ensure_compatible(chain_id)?;
+        verify_files(&manifest, &unpacked)?;
+        info!(target: "reth::cli", files = unpacked.len(), "Validated snapshot contents");
+
+        move_into_datadir(&staging_dir, data_dir)?;
+
+        Ok(manifest)
+    })();
+    fs::remove_dir_all(&staging_dir)?;
+
+    result
+}
+
+/// Copies the database into `dir` and returns the block number of its [`StageId::Finish`]
+/// checkpoint.
+pub(super) fn copy_db(db: &DatabaseEnv, dir: &Path, compact: bool) -> eyre::Result<Option<u64>> {
+    fs::create_dir_all(dir)?;
+    info!(target: "reth::cli", compact, "Copying database");
+    db.copy(&dir.join(MDBX_DATA_FILE), compact)?;
+
+    let block_number = {
+        let db_copy = open_db_read_only(dir, DatabaseArguments::default())?;
+        db_copy
+            .tx()?
+            .get::<tables::StageCheckpoints>(StageId::Finish.to_string())?
+            .map(|checkpoint| checkpoint.block_number)
+    };
+    info!(target: "reth::cli", ?block_number, "Copied database");
+
+    Ok(block_number)
+}
+
+/// Moves the database and static files directories from `staging_dir` into `data_dir`.
+pub(super) fn move_into_datadir(staging_dir: &Path, data_dir: &Path) -> eyre::Result<()> {
+    for dir in [DB_DIR, STATIC_FILES_DIR] {
+        let staged = staging_dir.join(dir);
+        if !staged.exists() {
+            continue
+        }
+        let target = data_dir.join(dir);
+        if target.exists() {
+            fs::remove_dir_all(&target)?;
+        }
+        fs::rename(&staged, &target)?;
+    }
+    Ok(())
+}
+
+/// Checks that the unpacked files exactly match the files listed in the manifest.
+pub(super) fn verify_files(
+    manifest: &SnapshotManifest,
+    unpacked: &BTreeMap<PathBuf, SnapshotFile>,
+) -> eyre::Result<()> {
+    for (name, expected) in &manifest.files {
+        let actual = unpacked
+            .get(name)
+            .ok_or_else(|| eyre!("File {name:?} is listed in the manifest but missing"))?;
+        ensure!(
+            actual == expected,
+            "File {name:?} does not match the manifest: expected {expected:?}, got {actual:?}"
+        );
+    }
+    if let Some(name) = unpacked.keys().find(|name| !manifest.files.contains_key(*name)) {
+        return Err(eyre!("File {name:?} is not listed in the manifest"))
+    }
+    Ok(())
+}
+
+/// Appends the file at `path` to the archive under `name`, returning its size and hash.
+fn append_file<W: io::Write>(
+    builder: &mut Builder<W>,
+    name: &Path,
+    path: &Path,
+) -> eyre::Result<SnapshotFile> {
+    let file = fs::open(path)?;
+    let metadata = file.metadata()?;
+
+    let mut header = Header::new_gnu();
+    header.set_metadata(&metadata);
+    // The file might still be appended to while it's being copied, so the header size is fixed
+    // up-front and only that many bytes are read.
+    let mut reader = HashingReader::new(file.take(metadata.len()));
+    builder.append_data(&mut header, name, &mut reader)?;
+
+    Ok(reader.finish())
+}
+
+/// Returns all regular files below `root` with their paths relative to `root`.
+pub(super) fn collect_files(root: &Path) -> eyre::Result<Vec<(PathBuf, PathBuf)>> {
+    let mut files = Vec::new();
+    if !root.exists() {
+        return Ok(files)
+    }
+
+    let mut dirs = vec![root.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                dirs.push(path);
+            } else if path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| !EXCLUDED_FILES.contains(&name))
+            {
+                files.push((path.strip_prefix(root)?.to_path_buf(), path));
+            }
+        }
+    }
+    files.sort();
+
+    Ok(files)
+}
+
+/// Returns `true` if the directory doesn't exist or has no entries.
+pub(super) fn is_missing_or_empty(dir: &Path) -> eyre::Result<bool> {
+    Ok(!dir.exists() || fs::read_dir(dir)?.next().is_none())
+}
+
+/// Reader adapter that computes the size and keccak256 hash of everything read through it.
+pub(super) struct HashingReader<R> {
+    reader: R,
+    hasher: Keccak256,
+    size: u64,
+}
+
+impl<R> HashingReader<R> {
+    pub(super) fn new(reader: R) -> Self {
+        Self { reader, hasher: Keccak256::new(), size: 0 }
+    }
+
+    pub(super) fn finish(self) -> SnapshotFile {
+        SnapshotFile { size: self.size, hash: self.hasher.finalize() }
+    }
+}
+
+impl<R: Read> Read for HashingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let bytes = self.reader.read(buf)?;
+        self.hasher.update(&buf[..bytes]);
+        self.size += bytes as u64;
+        Ok(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_db::init_db;
+
+    #[test]
+    fn snapshot_roundtrip() {
+        let source = tempfile::tempdir().unwrap();
+        let db = init_db(source.path().join(DB_DIR), DatabaseArguments::default()).unwrap();
+        let static_files = source.path().join(STATIC_FILES_DIR);
+        fs::create_dir_all(&static_files).unwrap();
+        fs::write(static_files.join("static_file_headers_0_499999"), b"headers").unwrap();
+        fs::write(static_files.join("lock"), b"").unwrap();
+
+        let output = source.path().join("snapshot.tar.lz4");
+        let manifest = create_snapshot(&db, &static_files, 1, &output, true).unwrap();
+        assert_eq!(manifest.files.len(), 2);
+        assert!(manifest
+            .files
+            .contains_key(Path::new("static_files/static_file_headers_0_499999")));
+
+        let target = tempfile::tempdir().unwrap();
+        let restored = restore_snapshot(&output, target.path(), 1).unwrap();
+        assert_eq!(restored, manifest);
+        assert!(target.path().join(DB_DIR).join(MDBX_DATA_FILE).exists());
+        assert_eq!(
+            fs::read(target.path().join("static_files/static_file_headers_0_499999")).unwrap(),
+            b"headers"
+        );
+
+        // Restoring a snapshot of another chain is refused
+        let other = tempfile::tempdir().unwrap();
+        assert!(restore_snapshot(&output, other.path(), 10).is_err());
+        assert!(!other.path().join(DB_DIR).exists());
+
+        // Restoring into a non-empty datadir is refused
+        assert!(restore_snapshot(&output, target.path(), 1).is_err());
+    }
+
+    #[test]
+    fn verify_files_detects_mismatch() {
+        let file = SnapshotFile { size: 1, hash: B256::ZERO };
+        let manifest = SnapshotManifest {
+            version: SNAPSHOT_MANIFEST_VERSION,
+            chain_id: 1,
+            block_number: None,
+            created_at: 0,
+            files: BTreeMap::from([(PathBuf::from("db/mdbx.dat"), file)]),
+        };
+
+        assert!(verify_files(&manifest, &manifest.files).is_ok());
+        assert!(verify_files(&manifest, &BTreeMap::new()).is_err());
+
+        let tampered =
+            BTreeMap::from([(PathBuf::from("db/mdbx.dat"), SnapshotFile { size: 2, ..file })]);
+        assert!(verify_files(&manifest, &tampered).is_err());
+
+        let mut extra = manifest.files.clone();
+        extra.insert(PathBuf::from("static_files/extra"), file);
+        assert!(verify_files(&manifest, &extra).is_err());
+    }
+}
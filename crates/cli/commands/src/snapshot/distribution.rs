@@ -0,0 +1,367 @@
+//! Publishing and fetching of unpacked datadir snapshots over HTTP.
+//!
+//! A published snapshot is a plain directory with the same layout and manifest as a snapshot
+//! archive, which can be served by any static HTTP server or CDN:
+//!
+//! ```text
+//! <base url>/manifest.json
+//! <base url>/db/mdbx.dat
+//! <base url>/static_files/<segment files>
+//! ```
+//!
+//! Files are fetched individually with HTTP range requests, so an interrupted download resumes
+//! where it stopped instead of starting over.
+
+use super::archive::{
+    collect_files, copy_db, is_missing_or_empty, move_into_datadir, verify_files, HashingReader,
+    SnapshotFile, SnapshotManifest, DB_DIR, MANIFEST_FILE_NAME, MDBX_DATA_FILE, STATIC_FILES_DIR,
+};
+use eyre::{ensure, eyre, OptionExt};
+use futures::{stream, StreamExt, TryStreamExt};
+use reqwest::{header::RANGE, Client, StatusCode};
+use reth_db::{mdbx::DatabaseArguments, open_db_read_only, DatabaseEnv};
+use reth_db_api::{database::Database, tables, transaction::DbTx};
+use reth_fs_util as fs;
+use reth_stages::StageId;
+use std::{
+    collections::BTreeMap,
+    fs::OpenOptions,
+    io::{self, Read, Write},
+    path::{Component, Path},
+};
+use tracing::{debug, info};
+
+/// Name of the directory inside the datadir that fetched files are staged in.
+///
+/// It is kept if a fetch fails, so the next attempt can resume the partial downloads.
+const FETCH_STAGING_DIR: &str = ".snapshot-fetch";
+
+/// Publishes an unpacked snapshot of the database and static files into the `output` directory.
+///
+/// The resulting directory can be served as-is over HTTP and fetched with [`fetch_snapshot`]. Like
+/// [`create_snapshot`](super::create_snapshot), this can be run while the node is running.
+pub fn publish_snapshot(
+    db: &DatabaseEnv,
+    static_files: &Path,
+    chain_id: u64,
+    output: &Path,
+    compact: bool,
+) -> eyre::Result<SnapshotManifest> {
+    ensure!(!output.exists(), "Snapshot output already exists: {output:?}");
+
+    let tmp_dir = output.with_extension("tmp");
+    if tmp_dir.exists() {
+        fs::remove_dir_all(&tmp_dir)?;
+    }
+    let result = (|| {
+        let block_number = copy_db(db, &tmp_dir.join(DB_DIR), compact)?;
+
+        let mut files = BTreeMap::new();
+        let db_file = Path::new(DB_DIR).join(MDBX_DATA_FILE);
+        files.insert(db_file.clone(), hash_file(&tmp_dir.join(&db_file))?);
+
+        info!(target: "reth::cli", ?static_files, "Copying static files");
+        for (relative, path) in collect_files(static_files)? {
+            let name = Path::new(STATIC_FILES_DIR).join(relative);
+            let target = tmp_dir.join(&name);
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+
+            let file = fs::open(&path)?;
+            // The file might still be appended to while it's being copied, so only the length
+            // at the time of opening is copied.
+            let len = file.metadata()?.len();
+            let mut reader = HashingReader::new(file.take(len));
+            io::copy(&mut reader, &mut fs::create_file(&target)?)?;
+            let entry = reader.finish();
+            debug!(target: "reth::cli", ?name, size = entry.size, "Copied static file to snapshot");
+            files.insert(name, entry);
+        }
+
+        let manifest = SnapshotManifest::new(chain_id, block_number, files)?;
+        fs::write(tmp_dir.join(MANIFEST_FILE_NAME), serde_json::to_vec_pretty(&manifest)?)?;
+        fs::rename(&tmp_dir, output)?;
+
+        Ok(manifest)
+    })();
+    if result.is_err() && tmp_dir.exists() {
+        fs::remove_dir_all(&tmp_dir)?;
+    }
+
+    result
+}
+
+/// Fetches a published snapshot of the chain with `chain_id` from `base_url` into `data_dir`.
+///
+/// Up to `concurrency` files are downloaded in parallel into a staging directory inside
+/// `data_dir`. Once all files are complete, they are validated against the manifest, moved into
+/// place and the stage checkpoints of the database are checked to be consistent with the
+/// snapshot, so the node resumes syncing from the snapshot block.
+pub async fn fetch_snapshot(
+    base_url: &str,
+    data_dir: &Path,
+    chain_id: u64,
+    concurrency: usize,
+) -> eyre::Result<SnapshotManifest> {
+    ensure!(
+        is_missing_or_empty(&data_dir.join(DB_DIR))? &&
+            is_missing_or_empty(&data_dir.join(STATIC_FILES_DIR))?,
+        "Datadir {data_dir:?} already contains a database or static files"
+    );
+
+    let base_url = base_url.trim_end_matches('/');
+    let client = Client::new();
+
+    let manifest_bytes = client
+        .get(format!("{base_url}/{MANIFEST_FILE_NAME}"))
+        .send()
+        .await?
+        .error_for_status()?
+        .bytes()
+        .await?;
+    let manifest: SnapshotManifest = serde_json::from_slice(&manifest_bytes)?;
+    manifest.ensure_compatible(chain_id)?;
+    info!(
+        target: "reth::cli",
+        block_number = ?manifest.block_number,
+        files = manifest.files.len(),
+        size = manifest.files.values().map(|file| file.size).sum::<u64>(),
+        "Fetched snapshot manifest"
+    );
+
+    let staging_dir = data_dir.join(FETCH_STAGING_DIR);
+    fs::create_dir_all(&staging_dir)?;
+
+    stream::iter(manifest.files.iter())
+        .map(|(name, file)| {
+            let client = &client;
+            let staging_dir = &staging_dir;
+            async move {
+                let url = file_url(base_url, name)?;
+                download_file(client, &url, &staging_dir.join(name), file).await
+            }
+        })
+        .buffer_unordered(concurrency.max(1))
+        .try_collect::<()>()
+        .await?;
+    info!(target: "reth::cli", "Downloaded all snapshot files");
+
+    let staging = staging_dir.clone();
+    let target = data_dir.to_path_buf();
+    let manifest = tokio::task::spawn_blocking(move || {
+        finalize_snapshot(&staging, &target, &manifest).map(|_| manifest)
+    })
+    .await??;
+    fs::remove_dir_all(&staging_dir)?;
+
+    Ok(manifest)
+}
+
+/// Downloads the file at `url` to `path`, resuming a partial download if `path` already exists.
+async fn download_file(
+    client: &Client,
+    url: &str,
+    path: &Path,
+    expected: &SnapshotFile,
+) -> eyre::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut offset = if path.exists() { fs::metadata(path)?.len() } else { 0 };
+    if offset > expected.size {
+        debug!(target: "reth::cli", ?path, offset, "Discarding oversized partial download");
+        fs::remove_file(path)?;
+        offset = 0;
+    }
+    if offset == expected.size {
+        debug!(target: "reth::cli", ?path, "Snapshot file already downloaded");
+        return Ok(())
+    }
+
+    let mut request = client.get(url);
+    if offset > 0 {
+        request = request.header(RANGE, format!("bytes={offset}-"));
+    }
+    let mut response = request.send().await?.error_for_status()?;
+
+    let mut file = match response.status() {
+        StatusCode::PARTIAL_CONTENT => {
+            debug!(target: "reth::cli", ?path, offset, "Resuming snapshot file download");
+            OpenOptions::new().append(true).open(path)?
+        }
+        // The server ignored the range request, so the file is downloaded from the start.
+        _ => {
+            offset = 0;
+            fs::create_file(path)?
+        }
+    };
+
+    while let Some(chunk) = response.chunk().await? {
+        offset += chunk.len() as u64;
+        ensure!(
+            offset <= expected.size,
+            "Snapshot file {url} is larger than the {} bytes listed in the manifest",
+            expected.size
+        );
+        file.write_all(&chunk)?;
+    }
+    file.flush()?;
+    ensure!(
+        offset == expected.size,
+        "Snapshot file {url} is incomplete: got {offset} of {} bytes",
+        expected.size
+    );
+    debug!(target: "reth::cli", ?path, size = offset, "Downloaded snapshot file");
+
+    Ok(())
+}
+
+/// Validates the files in `staging_dir` against the manifest, moves them into `data_dir` and
+/// checks the stage checkpoints of the database.
+fn finalize_snapshot(
+    staging_dir: &Path,
+    data_dir: &Path,
+    manifest: &SnapshotManifest,
+) -> eyre::Result<()> {
+    let mut staged = BTreeMap::new();
+    for (name, path) in collect_files(staging_dir)? {
+        staged.insert(name, hash_file(&path)?);
+    }
+    verify_files(manifest, &staged)?;
+    info!(target: "reth::cli", files = staged.len(), "Validated snapshot contents");
+
+    verify_checkpoints(&staging_dir.join(DB_DIR), manifest)?;
+    move_into_datadir(staging_dir, data_dir)?;
+
+    Ok(())
+}
+
+/// Checks that the [`StageId::Finish`] checkpoint of the database matches the snapshot block and
+/// that no stage is behind it.
+fn verify_checkpoints(db_dir: &Path, manifest: &SnapshotManifest) -> eyre::Result<()> {
+    let db = open_db_read_only(db_dir, DatabaseArguments::default())?;
+    let tx = db.tx()?;
+
+    let finish = tx
+        .get::<tables::StageCheckpoints>(StageId::Finish.to_string())?
+        .map(|checkpoint| checkpoint.block_number);
+    ensure!(
+        finish == manifest.block_number,
+        "Snapshot database is at block {finish:?}, but the manifest lists block {:?}",
+        manifest.block_number
+    );
+    let Some(block_number) = finish else { return Ok(()) };
+
+    for stage in StageId::ALL {
+        let checkpoint = tx
+            .get::<tables::StageCheckpoints>(stage.to_string())?
+            .ok_or_eyre(format!("Snapshot database has no checkpoint for stage {stage}"))?;
+        ensure!(
+            checkpoint.block_number >= block_number,
+            "Stage {stage} of the snapshot database is at block {}, behind the snapshot block \
+             {block_number}",
+            checkpoint.block_number
+        );
+    }
+    info!(target: "reth::cli", block_number, "Verified stage checkpoints, sync resumes from the snapshot block");
+
+    Ok(())
+}
+
+/// Returns the URL of the snapshot file `name` below `base_url`.
+fn file_url(base_url: &str, name: &Path) -> eyre::Result<String> {
+    let mut url = base_url.to_string();
+    for component in name.components() {
+        let Component::Normal(component) = component else {
+            return Err(eyre!("Invalid path in snapshot manifest: {name:?}"))
+        };
+        let component = component
+            .to_str()
+            .ok_or_else(|| eyre!("Invalid path in snapshot manifest: {name:?}"))?;
+        url.push('/');
+        url.push_str(component);
+    }
+    Ok(url)
+}
+
+/// Returns the size and hash of the file at `path`.
+fn hash_file(path: &Path) -> eyre::Result<SnapshotFile> {
+    let mut reader = HashingReader::new(fs::open(path)?);
+    io::copy(&mut reader, &mut io::sink())?;
+    Ok(reader.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_db::init_db;
+
+    #[test]
+    fn publish_and_finalize() {
+        let source = tempfile::tempdir().unwrap();
+        let db = init_db(source.path().join(DB_DIR), DatabaseArguments::default()).unwrap();
+        let static_files = source.path().join(STATIC_FILES_DIR);
+        fs::create_dir_all(&static_files).unwrap();
+        fs::write(static_files.join("static_file_headers_0_499999"), b"headers").unwrap();
+        fs::write(static_files.join("lock"), b"").unwrap();
+
+        let output = source.path().join("published");
+        let manifest = publish_snapshot(&db, &static_files, 1, &output, true).unwrap();
+        assert_eq!(manifest.files.len(), 2);
+        let published: SnapshotManifest =
+            serde_json::from_slice(&fs::read(output.join(MANIFEST_FILE_NAME)).unwrap()).unwrap();
+        assert_eq!(published, manifest);
+
+        // Simulate a completed download by copying the published files into the staging dir
+        let target = tempfile::tempdir().unwrap();
+        let staging_dir = target.path().join(FETCH_STAGING_DIR);
+        for name in manifest.files.keys() {
+            let path = staging_dir.join(name);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, fs::read(output.join(name)).unwrap()).unwrap();
+        }
+
+        finalize_snapshot(&staging_dir, target.path(), &manifest).unwrap();
+        assert!(target.path().join(DB_DIR).join(MDBX_DATA_FILE).exists());
+        assert_eq!(
+            fs::read(target.path().join("static_files/static_file_headers_0_499999")).unwrap(),
+            b"headers"
+        );
+    }
+
+    #[test]
+    fn finalize_rejects_tampered_file() {
+        let source = tempfile::tempdir().unwrap();
+        let db = init_db(source.path().join(DB_DIR), DatabaseArguments::default()).unwrap();
+        let static_files = source.path().join(STATIC_FILES_DIR);
+        fs::create_dir_all(&static_files).unwrap();
+        fs::write(static_files.join("static_file_headers_0_499999"), b"headers").unwrap();
+
+        let output = source.path().join("published");
+        let manifest = publish_snapshot(&db, &static_files, 1, &output, true).unwrap();
+
+        let target = tempfile::tempdir().unwrap();
+        let staging_dir = target.path().join(FETCH_STAGING_DIR);
+        for name in manifest.files.keys() {
+            let path = staging_dir.join(name);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, fs::read(output.join(name)).unwrap()).unwrap();
+        }
+        fs::write(staging_dir.join("static_files/static_file_headers_0_499999"), b"tampers")
+            .unwrap();
+
+        assert!(finalize_snapshot(&staging_dir, target.path(), &manifest).is_err());
+        assert!(!target.path().join(DB_DIR).exists());
+    }
+
+    #[test]
+    fn file_urls() {
+        assert_eq!(
+            file_url("https://example.com/snapshot", Path::new("static_files/segment")).unwrap(),
+            "https://example.com/snapshot/static_files/segment"
+        );
+        assert!(file_url("https://example.com", Path::new("../db/mdbx.dat")).is_err());
+    }
+}
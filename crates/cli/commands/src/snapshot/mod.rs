@@ -0,0 +1,170 @@
+//! `reth snapshot` command.
+
+use crate::common::EnvironmentArgs;
+use clap::{Parser, Subcommand};
+use reth_chainspec::EthChainSpec;
+use reth_cli::chainspec::ChainSpecParser;
+use reth_db::open_db_read_only;
+use reth_fs_util as fs;
+use std::{path::PathBuf, sync::Arc};
+use tracing::info;
+
+mod archive;
+pub use archive::{
+    create_snapshot, restore_snapshot, SnapshotFile, SnapshotManifest, SNAPSHOT_MANIFEST_VERSION,
+};
+
+mod distribution;
+pub use distribution::{fetch_snapshot, publish_snapshot};
+
+/// `reth snapshot` command
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    #[command(subcommand)]
+    command: Subcommands,
+}
+
+/// `reth snapshot` subcommands
+#[derive(Debug, Subcommand)]
+pub enum Subcommands {
+    /// Creates a compressed snapshot archive of the database and static files.
+    ///
+    /// This can be run while the node is running.
+    Create {
+        /// The path of the snapshot archive to create.
+        #[arg(long, short, value_name = "FILE")]
+        output: PathBuf,
+
+        /// Copy the database as-is instead of compacting it.
+        ///
+        /// This is faster, but the snapshot includes all free pages of the database.
+        #[arg(long)]
+        no_compact: bool,
+    },
+    /// Restores a snapshot archive into the datadir, validating all file hashes.
+    ///
+    /// The datadir must not contain a database or static files yet.
+    Restore {
+        /// The path of the snapshot archive to restore.
+        #[arg(long, short, value_name = "FILE")]
+        input: PathBuf,
+    },
+    /// Publishes an unpacked snapshot of the database and static files into a directory.
+    ///
+    /// The directory contains a manifest with the hashes of all files and can be served by any
+    /// static HTTP server for `reth snapshot fetch`. This can be run while the node is running.
+    Publish {
+        /// The directory to publish the snapshot into.
+        #[arg(long, short, value_name = "DIR")]
+        output: PathBuf,
+
+        /// Copy the database as-is instead of compacting it.
+        #[arg(long)]
+        no_compact: bool,
+    },
+    /// Fetches a published snapshot over HTTP into the datadir, validating all file hashes and
+    /// stage checkpoints.
+    ///
+    /// Interrupted downloads are resumed with HTTP range requests when the command is rerun. The
+    /// datadir must not contain a database or static files yet.
+    Fetch {
+        /// Base URL of the published snapshot, containing `manifest.json`.
+        #[arg(long, short, value_name = "URL")]
+        url: String,
+
+        /// Maximum number of files to download in parallel.
+        #[arg(long, default_value_t = 4)]
+        concurrency: usize,
+    },
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec>> Command<C> {
+    /// Execute `snapshot` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        let data_dir = self.env.datadir.clone().resolve_datadir(self.env.chain.chain());
+
+        match self.command {
+            Subcommands::Create { output, no_compact } => {
+                let db_path = data_dir.db();
+                eyre::ensure!(db_path.is_dir(), "Database does not exist: {:?}", db_path);
+
+                let db = open_db_read_only(&db_path, self.env.db.database_args())?;
+                let manifest = create_snapshot(
+                    &db,
+                    &data_dir.static_files(),
+                    self.env.chain.chain().id(),
+                    &output,
+                    !no_compact,
+                )?;
+                info!(
+                    target: "reth::cli",
+                    ?output,
+                    block_number = ?manifest.block_number,
+                    files = manifest.files.len(),
+                    "Snapshot created"
+                );
+            }
+            Subcommands::Restore { input } => {
+                fs::create_dir_all(data_dir.data_dir())?;
+                let manifest =
+                    restore_snapshot(&input, data_dir.data_dir(), self.env.chain.chain().id())?;
+                info!(
+                    target: "reth::cli",
+                    dir = ?data_dir.data_dir(),
+                    block_number = ?manifest.block_number,
+                    files = manifest.files.len(),
+                    "Snapshot restored"
+                );
+            }
+            Subcommands::Publish { output, no_compact } => {
+                let db_path = data_dir.db();
+                eyre::ensure!(db_path.is_dir(), "Database does not exist: {:?}", db_path);
+
+                let db = open_db_read_only(&db_path, self.env.db.database_args())?;
+                let manifest = publish_snapshot(
+                    &db,
+                    &data_dir.static_files(),
+                    self.env.chain.chain().id(),
+                    &output,
+                    !no_compact,
+                )?;
+                info!(
+                    target: "reth::cli",
+                    ?output,
+                    block_number = ?manifest.block_number,
+                    files = manifest.files.len(),
+                    "Snapshot published"
+                );
+            }
+            Subcommands::Fetch { url, concurrency } => {
+                fs::create_dir_all(data_dir.data_dir())?;
+                let manifest = fetch_snapshot(
+                    &url,
+                    data_dir.data_dir(),
+                    self.env.chain.chain().id(),
+                    concurrency,
+                )
+                .await?;
+                info!(
+                    target: "reth::cli",
+                    dir = ?data_dir.data_dir(),
+                    block_number = ?manifest.block_number,
+                    files = manifest.files.len(),
+                    "Snapshot fetched"
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<C: ChainSpecParser> Command<C> {
+    /// Returns the underlying chain being used to run this command
+    pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        Some(&self.env.chain)
+    }
+}
@@ -4,7 +4,7 @@ use clap::Parser;
 use itertools::Itertools;
 use reth_chainspec::EthChainSpec;
 use reth_cli::chainspec::ChainSpecParser;
-use reth_db::{mdbx::tx::Tx, static_file::iter_static_files, DatabaseError};
+use reth_db::{mdbx::tx::Tx, DatabaseError};
 use reth_db_api::{
     tables,
     transaction::{DbTx, DbTxMut},
@@ -52,7 +52,7 @@ impl<C: ChainSpecParser> Command<C> {
         // Delete static file segment data before inserting the genesis header below
         if let Some(static_file_segment) = static_file_segment {
             let static_file_provider = tool.provider_factory.static_file_provider();
-            let static_files = iter_static_files(static_file_provider.directory())?;
+            let static_files = static_file_provider.iter_static_files()?;
             if let Some(segment_static_files) = static_files.get(&static_file_segment) {
                 // Delete static files from the highest to the lowest block range
                 for (block_range, _) in segment_static_files
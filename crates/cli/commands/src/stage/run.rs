@@ -44,6 +44,7 @@ use reth_stages::{
     },
     ExecInput, ExecOutput, ExecutionStageThresholds, Stage, StageExt, UnwindInput, UnwindOutput,
 };
+use reth_trie_db::PrefixSetStore;
 use std::{any::Any, net::SocketAddr, sync::Arc, time::Instant};
 use tokio::sync::watch;
 use tracing::*;
@@ -297,10 +298,15 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
                     None,
                 ),
                 StageEnum::Merkle => (
-                    Box::new(MerkleStage::new_execution(
-                        config.stages.merkle.rebuild_threshold,
-                        config.stages.merkle.incremental_threshold,
-                    )),
+                    Box::new(
+                        MerkleStage::new_execution(
+                            config.stages.merkle.rebuild_threshold,
+                            config.stages.merkle.incremental_threshold,
+                        )
+                        .with_prefix_set_store(
+                            config.stages.merkle.prefix_sets_dir.clone().map(PrefixSetStore::new),
+                        ),
+                    ),
                     Some(Box::new(MerkleStage::default_unwind())),
                 ),
                 StageEnum::AccountHistory => (
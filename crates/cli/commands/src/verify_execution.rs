@@ -0,0 +1,402 @@
+//! `reth verify-execution` command.
+
+use crate::common::{AccessRights, CliNodeComponents, CliNodeTypes, Environment, EnvironmentArgs};
+use alloy_consensus::{BlockHeader, TxReceipt};
+use alloy_primitives::{Address, Log, B256, U256};
+use clap::Parser;
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_consensus::{ConsensusError, FullConsensus};
+use reth_evm::{execute::Executor, ConfigureEvm};
+use reth_primitives_traits::Account;
+use reth_provider::{
+    providers::ProviderNodeTypes, AccountExtReader, BlockNumReader, BlockReader, ChainSpecProvider,
+    DBProvider, ProviderFactory, ProviderResult, ReceiptProvider, StateProvider, StorageReader,
+    TryIntoHistoricalStateProvider,
+};
+use reth_revm::{database::StateProviderDatabase, db::BundleState};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    num::NonZeroUsize,
+    ops::RangeInclusive,
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+};
+use tracing::{debug, error, info};
+
+/// `reth verify-execution` command
+///
+/// Re-executes a range of blocks and compares the results with the block headers, the stored
+/// receipts, changesets and historical state, e.g. to validate the integrity of an archive node
+/// after a hardware incident.
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    /// The inclusive range of blocks to verify, e.g. `1000..=2000`.
+    #[arg(long, value_name = "FROM..=TO", value_parser = parse_block_range)]
+    range: RangeInclusive<u64>,
+
+    /// The number of consecutive blocks that are executed on top of the same historical state.
+    ///
+    /// The state changes of each batch are verified against the stored state after its last block.
+    #[arg(long, default_value_t = 1000, value_parser = clap::value_parser!(u64).range(1..))]
+    batch_size: u64,
+
+    /// The number of batches that are verified in parallel, defaults to the number of available
+    /// CPUs.
+    #[arg(long)]
+    jobs: Option<NonZeroUsize>,
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
+    /// Execute `verify-execution` command
+    pub async fn execute<N, Comp, F>(self, components: F) -> eyre::Result<()>
+    where
+        N: CliNodeTypes<ChainSpec = C::ChainSpec>,
+        Comp: CliNodeComponents<N>,
+        F: FnOnce(Arc<C::ChainSpec>) -> Comp,
+    {
+        let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RO)?;
+        let components = components(provider_factory.chain_spec());
+        let evm_config = components.evm_config().clone();
+        let consensus = components.consensus().clone();
+
+        // the genesis block is not executed
+        let from = (*self.range.start()).max(1);
+        let to = *self.range.end();
+        let best_block = provider_factory.best_block_number()?;
+        if to > best_block {
+            eyre::bail!("Block {to} is above the best block {best_block}")
+        }
+
+        let jobs = self.jobs.map_or_else(
+            || thread::available_parallelism().map_or(1, NonZeroUsize::get),
+            NonZeroUsize::get,
+        );
+        info!(target: "reth::cli", from, to, batch_size = self.batch_size, jobs, "Verifying execution");
+
+        let verify = |range| verify_batch(&provider_factory, &evm_config, &consensus, range);
+        let next_batch = AtomicU64::new(from);
+        let mismatches = AtomicUsize::new(0);
+        thread::scope(|scope| {
+            for _ in 0..jobs {
+                scope.spawn(|| loop {
+                    let start = next_batch.fetch_add(self.batch_size, Ordering::Relaxed);
+                    if start > to {
+                        break
+                    }
+                    let end = start.saturating_add(self.batch_size - 1).min(to);
+
+                    let found = match verify(start..=end) {
+                        Ok(found) => found,
+                        Err(err) => {
+                            error!(target: "reth::cli", from = start, to = end, %err, "Failed to verify blocks");
+                            1
+                        }
+                    };
+                    mismatches.fetch_add(found, Ordering::Relaxed);
+                    info!(target: "reth::cli", from = start, to = end, mismatches = found, "Verified blocks");
+                });
+            }
+        });
+
+        let mismatches = mismatches.into_inner();
+        if mismatches > 0 {
+            eyre::bail!("Found {mismatches} mismatches in blocks {from}..={to}")
+        }
+        info!(target: "reth::cli", from, to, "Execution verified");
+
+        Ok(())
+    }
+}
+
+impl<C: ChainSpecParser> Command<C> {
+    /// Returns the underlying chain being used to run this command
+    pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        Some(&self.env.chain)
+    }
+}
+
+/// Re-executes the blocks of the range on top of the historical state before the first block and
+/// returns the number of mismatches.
+///
+/// The results of each block are compared with its header and stored receipts. The state changes
+/// of the batch are compared with the stored changesets of the range and the historical state
+/// after the last block, which are both looked up through the history indices instead of
+/// reverting the state from the tip.
+fn verify_batch<N, E, Cons>(
+    provider_factory: &ProviderFactory<N>,
+    evm_config: &E,
+    consensus: &Cons,
+    range: RangeInclusive<u64>,
+) -> eyre::Result<usize>
+where
+    N: ProviderNodeTypes,
+    E: ConfigureEvm<Primitives = N::Primitives>,
+    Cons: FullConsensus<N::Primitives, Error = ConsensusError>,
+{
+    // executing a batch can take longer than the read transaction timeout, which is safe because
+    // the database is opened read-only
+    let provider = provider_factory.provider()?.disable_long_read_transaction_safety();
+    let blocks = provider.recovered_block_range(range.clone())?;
+    let Some(last_block) = blocks.last().filter(|block| block.number() == *range.end()) else {
+        eyre::bail!("Missing blocks in {range:?}")
+    };
+    let changed_accounts = provider.changed_accounts_with_range(range.clone())?;
+    let changed_storages = provider.changed_storages_with_range(range.clone())?;
+    let state_provider = provider.try_into_history_at_block(range.start() - 1)?;
+
+    let mut executor = evm_config.batch_executor(StateProviderDatabase::new(&state_provider));
+    let mut mismatches = 0;
+    for block in &blocks {
+        let result = match executor.execute_one(block) {
+            Ok(result) => result,
+            Err(err) => {
+                // the state of the following blocks is unknown
+                error!(target: "reth::cli", block = block.number(), %err, "Failed to execute block");
+                return Ok(mismatches + 1)
+            }
+        };
+
+        if let Err(err) = consensus.validate_block_post_execution(block, &result) {
+            error!(target: "reth::cli", block = block.number(), %err, "Execution result does not match the block header");
+            mismatches += 1;
+        }
+
+        match provider_factory.receipts_by_block(block.number().into())? {
+            Some(stored) => {
+                if let Some(index) = first_receipt_mismatch(&result.receipts, &stored) {
+                    error!(target: "reth::cli", block = block.number(), index, "Receipt does not match the stored receipt");
+                    mismatches += 1;
+                }
+            }
+            None => {
+                debug!(target: "reth::cli", block = block.number(), "No stored receipts to compare with")
+            }
+        }
+    }
+
+    let post_state_provider = provider_factory
+        .provider()?
+        .disable_long_read_transaction_safety()
+        .try_into_history_at_block(last_block.number())?;
+    mismatches += post_state_mismatches(
+        &executor.into_state().take_bundle(),
+        &post_state_provider,
+        &changed_accounts,
+        &changed_storages,
+    )?;
+
+    Ok(mismatches)
+}
+
+/// Returns the number of mismatches between the state changes of the executed blocks, the stored
+/// changesets of the blocks and the stored state after the last block.
+fn post_state_mismatches(
+    bundle: &BundleState,
+    post_state: &dyn StateProvider,
+    changed_accounts: &BTreeSet<Address>,
+    changed_storages: &BTreeMap<Address, BTreeSet<B256>>,
+) -> ProviderResult<usize> {
+    let mut mismatches = 0;
+
+    // every change in the stored changesets is reproduced by the execution
+    for address in changed_accounts {
+        if bundle.account(address).is_none() {
+            error!(target: "reth::cli", %address, "Stored account change was not reproduced");
+            mismatches += 1;
+        }
+    }
+    for (address, slots) in changed_storages {
+        let account = bundle.account(address);
+        for slot in slots {
+            let reproduced = account.is_some_and(|account| {
+                account.was_destroyed() ||
+                    account.storage.contains_key(&U256::from_be_bytes(slot.0))
+            });
+            if !reproduced {
+                error!(target: "reth::cli", %address, %slot, "Stored storage change was not reproduced");
+                mismatches += 1;
+            }
+        }
+    }
+
+    // every executed change matches the stored state
+    for (address, account) in &bundle.state {
+        let executed = account.info.as_ref().map(Account::from);
+        let stored = post_state.basic_account(address)?;
+        if executed != stored {
+            error!(target: "reth::cli", %address, ?executed, ?stored, "Account does not match the stored state");
+            mismatches += 1;
+        }
+
+        for (slot, value) in &account.storage {
+            let slot = B256::from(*slot);
+            let stored = post_state.storage(*address, slot)?.unwrap_or_default();
+            if value.present_value != stored {
+                error!(target: "reth::cli", %address, %slot, executed = %value.present_value, %stored, "Storage does not match the stored state");
+                mismatches += 1;
+            }
+        }
+    }
+
+    Ok(mismatches)
+}
+
+/// Returns the index of the first executed receipt that doesn't match the stored receipt.
+fn first_receipt_mismatch<R: TxReceipt<Log = Log>>(executed: &[R], stored: &[R]) -> Option<usize> {
+    executed
+        .iter()
+        .zip(stored)
+        .position(|(executed, stored)| {
+            executed.status_or_post_state() != stored.status_or_post_state() ||
+                executed.cumulative_gas_used() != stored.cumulative_gas_used() ||
+                executed.logs() != stored.logs()
+        })
+        .or_else(|| (executed.len() != stored.len()).then(|| executed.len().min(stored.len())))
+}
+
+/// Parses an inclusive block range `<FROM>..=<TO>`.
+fn parse_block_range(value: &str) -> Result<RangeInclusive<u64>, String> {
+    let (from, to) = value
+        .split_once("..=")
+        .ok_or_else(|| format!("expected a range `<FROM>..=<TO>`, got `{value}`"))?;
+    let from = from.parse::<u64>().map_err(|err| format!("invalid range start: {err}"))?;
+    let to = to.parse::<u64>().map_err(|err| format!("invalid range end: {err}"))?;
+    if from > to {
+        return Err(format!("range start {from} is after the end {to}"))
+    }
+    Ok(from..=to)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{
+        proofs::calculate_withdrawals_root, Header, Receipt, EMPTY_OMMER_ROOT_HASH, EMPTY_ROOT_HASH,
+    };
+    use alloy_eips::eip4895::{Withdrawal, Withdrawals};
+    use alloy_genesis::Genesis;
+    use alloy_primitives::LogData;
+    use reth_chainspec::{Chain, ChainSpecBuilder};
+    use reth_db_api::{tables, transaction::DbTxMut};
+    use reth_db_common::init::init_genesis;
+    use reth_ethereum_consensus::EthBeaconConsensus;
+    use reth_ethereum_primitives::{Block, BlockBody};
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_primitives_traits::{Block as _, RecoveredBlock, SealedHeader};
+    use reth_provider::{
+        test_utils::create_test_provider_factory_with_chain_spec, BlockWriter, StateProviderFactory,
+    };
+
+    const RECIPIENT: Address = Address::repeat_byte(0x42);
+
+    /// Returns blocks without transactions that withdraw to [`RECIPIENT`], so they change the
+    /// state.
+    fn withdrawal_blocks(mut parent: SealedHeader, count: u64) -> Vec<RecoveredBlock<Block>> {
+        (1..=count)
+            .map(|number| {
+                let withdrawals = Withdrawals::new(vec![Withdrawal {
+                    index: number,
+                    validator_index: 0,
+                    address: RECIPIENT,
+                    amount: number,
+                }]);
+                let header = Header {
+                    parent_hash: parent.hash(),
+                    number,
+                    timestamp: parent.timestamp + 12,
+                    gas_limit: parent.gas_limit,
+                    base_fee_per_gas: parent.base_fee_per_gas,
+                    ommers_hash: EMPTY_OMMER_ROOT_HASH,
+                    transactions_root: EMPTY_ROOT_HASH,
+                    receipts_root: EMPTY_ROOT_HASH,
+                    withdrawals_root: Some(calculate_withdrawals_root(&withdrawals)),
+                    ..Default::default()
+                };
+                let block = Block {
+                    header,
+                    body: BlockBody { withdrawals: Some(withdrawals), ..Default::default() },
+                }
+                .seal_slow();
+                parent = block.clone_sealed_header();
+                RecoveredBlock::new_sealed(block, vec![])
+            })
+            .collect()
+    }
+
+    #[test]
+    fn verify_batches() {
+        let chain_spec = Arc::new(
+            ChainSpecBuilder::default()
+                .chain(Chain::mainnet())
+                .genesis(Genesis::default().with_gas_limit(30_000_000))
+                .shanghai_activated()
+                .build(),
+        );
+        let factory = create_test_provider_factory_with_chain_spec(chain_spec.clone());
+        init_genesis(&factory).unwrap();
+        let evm_config = EthEvmConfig::new(chain_spec.clone());
+        let consensus = EthBeaconConsensus::new(chain_spec.clone());
+
+        let blocks = withdrawal_blocks(chain_spec.sealed_genesis_header(), 4);
+        let outcome = evm_config
+            .batch_executor(StateProviderDatabase::new(factory.latest().unwrap()))
+            .execute_batch(&blocks)
+            .unwrap();
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw
+            .append_blocks_with_state(blocks, &outcome, Default::default(), Default::default())
+            .unwrap();
+        provider_rw.commit().unwrap();
+
+        // the batches are verified against the historical state without reverting it
+        assert_eq!(verify_batch(&factory, &evm_config, &consensus, 1..=2).unwrap(), 0);
+        assert_eq!(verify_batch(&factory, &evm_config, &consensus, 3..=4).unwrap(), 0);
+
+        // a corrupted account in the latest state only fails the batch that ends at the tip
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw
+            .tx_ref()
+            .put::<tables::PlainAccountState>(
+                RECIPIENT,
+                Account { balance: U256::MAX, ..Default::default() },
+            )
+            .unwrap();
+        provider_rw.commit().unwrap();
+        assert_eq!(verify_batch(&factory, &evm_config, &consensus, 1..=2).unwrap(), 0);
+        assert_eq!(verify_batch(&factory, &evm_config, &consensus, 3..=4).unwrap(), 1);
+    }
+
+    #[test]
+    fn parse_range() {
+        assert_eq!(parse_block_range("1000..=2000"), Ok(1000..=2000));
+        assert_eq!(parse_block_range("5..=5"), Ok(5..=5));
+        assert!(parse_block_range("2000..=1000").is_err());
+        assert!(parse_block_range("1000..2000").is_err());
+        assert!(parse_block_range("..=2000").is_err());
+    }
+
+    #[test]
+    fn receipt_mismatch() {
+        let receipt =
+            |cumulative_gas_used, logs| Receipt { status: true.into(), cumulative_gas_used, logs };
+        let log = Log { address: Address::ZERO, data: LogData::empty() };
+
+        let stored = vec![receipt(21_000, vec![]), receipt(42_000, vec![log.clone()])];
+        assert_eq!(first_receipt_mismatch(&stored, &stored), None);
+
+        let executed = vec![receipt(21_000, vec![]), receipt(42_000, vec![])];
+        assert_eq!(first_receipt_mismatch(&executed, &stored), Some(1));
+
+        let executed = vec![receipt(21_001, vec![]), receipt(42_000, vec![log])];
+        assert_eq!(first_receipt_mismatch(&executed, &stored), Some(0));
+
+        assert_eq!(first_receipt_mismatch(&stored[..1], &stored), Some(1));
+    }
+}
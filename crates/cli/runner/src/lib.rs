@@ -14,6 +14,9 @@ use reth_tasks::{TaskExecutor, TaskManager};
 use std::{future::Future, pin::pin, sync::mpsc, time::Duration};
 use tracing::{debug, error, trace};
 
+/// The default maximum time to wait for the tasks to shut down gracefully.
+const DEFAULT_GRACEFUL_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
 /// Executes CLI commands.
 ///
 /// Provides utilities for running a cli command to completion.
@@ -21,6 +24,7 @@ use tracing::{debug, error, trace};
 #[non_exhaustive]
 pub struct CliRunner {
     tokio_runtime: tokio::runtime::Runtime,
+    graceful_shutdown_timeout: Duration,
 }
 
 impl CliRunner {
@@ -29,12 +33,21 @@ impl CliRunner {
     ///
     /// The default tokio runtime is multi-threaded, with both I/O and time drivers enabled.
     pub fn try_default_runtime() -> Result<Self, std::io::Error> {
-        Ok(Self { tokio_runtime: tokio_runtime()? })
+        Ok(Self::from_runtime(tokio_runtime()?))
     }
 
     /// Create a new [`CliRunner`] from a provided tokio [`Runtime`](tokio::runtime::Runtime).
     pub const fn from_runtime(tokio_runtime: tokio::runtime::Runtime) -> Self {
-        Self { tokio_runtime }
+        Self { tokio_runtime, graceful_shutdown_timeout: DEFAULT_GRACEFUL_SHUTDOWN_TIMEOUT }
+    }
+
+    /// Sets the maximum time to wait for the tasks of the command to shut down gracefully, see
+    /// [`CliRunner::run_command_until_exit`].
+    ///
+    /// Defaults to 5 seconds.
+    pub const fn with_graceful_shutdown_timeout(mut self, timeout: Duration) -> Self {
+        self.graceful_shutdown_timeout = timeout;
+        self
     }
 }
 
@@ -54,8 +67,9 @@ impl CliRunner {
         F: Future<Output = Result<(), E>>,
         E: Send + Sync + From<std::io::Error> + From<reth_tasks::PanickedTaskError> + 'static,
     {
+        let Self { tokio_runtime, graceful_shutdown_timeout } = self;
         let AsyncCliRunner { context, mut task_manager, tokio_runtime } =
-            AsyncCliRunner::new(self.tokio_runtime);
+            AsyncCliRunner::new(tokio_runtime);
 
         // Executes the command until it finished or ctrl-c was fired
         let command_res = tokio_runtime.block_on(run_to_completion_or_panic(
@@ -70,7 +84,7 @@ impl CliRunner {
             // after the command has finished or exit signal was received we shutdown the task
             // manager which fires the shutdown signal to all tasks spawned via the task
             // executor and awaiting on tasks spawned with graceful shutdown
-            task_manager.graceful_shutdown_with_timeout(Duration::from_secs(5));
+            task_manager.graceful_shutdown_with_timeout(graceful_shutdown_timeout);
         }
 
         // `drop(tokio_runtime)` would block the current thread until its pools
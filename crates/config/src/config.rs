@@ -1,8 +1,10 @@
 //! Configuration files.
+use alloy_primitives::Address;
 use reth_network_types::{PeersConfig, SessionsConfig};
 use reth_prune_types::PruneModes;
 use reth_stages_types::ExecutionStageThresholds;
 use std::{
+    collections::BTreeSet,
     path::{Path, PathBuf},
     time::Duration,
 };
@@ -29,6 +31,10 @@ pub struct Config {
     pub peers: PeersConfig,
     /// Configuration for peer sessions.
     pub sessions: SessionsConfig,
+    /// Configuration for the transaction pool.
+    pub txpool: TxPoolConfig,
+    /// Configuration for execution extensions.
+    pub exex: ExExConfig,
 }
 
 impl Config {
@@ -125,6 +131,10 @@ pub struct StageConfig {
     pub index_account_history: IndexHistoryConfig,
     /// Index Storage History stage configuration.
     pub index_storage_history: IndexHistoryConfig,
+    /// Index Logs stage configuration.
+    pub index_logs: IndexLogsConfig,
+    /// Gas usage index configuration.
+    pub index_gas_usage: IndexGasUsageConfig,
     /// Common ETL related configuration.
     pub etl: EtlConfig,
 }
@@ -278,6 +288,13 @@ pub struct ExecutionConfig {
         )
     )]
     pub max_duration: Option<Duration>,
+    /// The number of executed blocks after which the not yet committed blocks are saved to disk,
+    /// so they aren't executed again after a restart. `None` disables saving.
+    pub pending_batch_save_interval: Option<u64>,
+    /// Speculative execution of upcoming blocks.
+    pub prewarm: ExecutionPrewarmConfig,
+    /// Limits of the execution throughput, to share the host with other services.
+    pub governor: ExecutionGovernorConfig,
 }
 
 impl Default for ExecutionConfig {
@@ -289,10 +306,55 @@ impl Default for ExecutionConfig {
             max_cumulative_gas: Some(30_000_000 * 50_000),
             // 10 minutes
             max_duration: Some(Duration::from_secs(10 * 60)),
+            pending_batch_save_interval: None,
+            prewarm: ExecutionPrewarmConfig::default(),
+            governor: ExecutionGovernorConfig::default(),
         }
     }
 }
 
+/// Execution stage prewarming configuration.
+///
+/// Prewarming executes the transactions of upcoming blocks in parallel and discards their writes,
+/// so the state they read is already loaded once the blocks are executed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ExecutionPrewarmConfig {
+    /// The number of threads executing upcoming blocks, `0` disables prewarming.
+    pub threads: usize,
+    /// How many blocks ahead of the executed block are prewarmed.
+    pub blocks_ahead: u64,
+}
+
+impl ExecutionPrewarmConfig {
+    /// Returns true if prewarming is enabled.
+    pub const fn is_enabled(&self) -> bool {
+        self.threads > 0 && self.blocks_ahead > 0
+    }
+}
+
+impl Default for ExecutionPrewarmConfig {
+    fn default() -> Self {
+        Self { threads: 0, blocks_ahead: 16 }
+    }
+}
+
+/// Execution stage throughput limits.
+///
+/// Execution pauses once it gets ahead of a limit, so backfilling doesn't starve other services
+/// on the same host. The limits can be changed at runtime with `admin_setExecutionGovernor`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ExecutionGovernorConfig {
+    /// The maximum gas executed per second. `None` doesn't limit the gas.
+    pub max_gas_per_second: Option<u64>,
+    /// The maximum bytes the node writes to disk per second, only supported on Linux. Applies to
+    /// every pipeline stage once it commits a batch. `None` doesn't limit the writes.
+    pub max_write_bytes_per_second: Option<u64>,
+}
+
 impl From<ExecutionConfig> for ExecutionStageThresholds {
     fn from(config: ExecutionConfig) -> Self {
         Self {
@@ -338,7 +400,7 @@ impl Default for HashingConfig {
 }
 
 /// Merkle stage configuration.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(default))]
 pub struct MerkleConfig {
@@ -353,11 +415,16 @@ pub struct MerkleConfig {
     /// The threshold (in number of blocks) for switching from incremental trie building of changes
     /// to whole rebuild.
     pub rebuild_threshold: u64,
+    /// Directory where the changed keys of incremental root chunks are persisted.
+    ///
+    /// If set, the stage doesn't have to walk the changesets of a chunk again if it is restarted
+    /// before it completed. Disabled if not set.
+    pub prefix_sets_dir: Option<PathBuf>,
 }
 
 impl Default for MerkleConfig {
     fn default() -> Self {
-        Self { incremental_threshold: 7_000, rebuild_threshold: 100_000 }
+        Self { incremental_threshold: 7_000, rebuild_threshold: 100_000, prefix_sets_dir: None }
     }
 }
 
@@ -426,6 +493,38 @@ impl Default for IndexHistoryConfig {
     }
 }
 
+/// Log index stage configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct IndexLogsConfig {
+    /// Whether to maintain the index of log addresses and topics used by `eth_getLogs`.
+    pub enabled: bool,
+    /// The maximum number of blocks to process before committing progress to the database.
+    pub commit_threshold: u64,
+}
+
+impl Default for IndexLogsConfig {
+    fn default() -> Self {
+        Self { enabled: false, commit_threshold: 100_000 }
+    }
+}
+
+/// Per-contract gas usage index configuration.
+///
+/// The index is maintained during execution, there's no separate stage building it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct IndexGasUsageConfig {
+    /// Whether to maintain the index of gas used per called contract served by
+    /// `reth_topGasConsumers`.
+    ///
+    /// The index only covers blocks executed after it was enabled, and is removed when it's
+    /// disabled.
+    pub enabled: bool,
+}
+
 /// Pruning configuration.
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -487,6 +586,87 @@ impl PruneConfig {
     }
 }
 
+/// Transaction pool configuration.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct TxPoolConfig {
+    /// Rules evaluated before a transaction is validated.
+    pub firewall: TxPoolFirewallConfig,
+}
+
+/// Execution extension configuration.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ExExConfig {
+    /// Maximum number of consecutive new blocks that are merged into a single
+    /// `ChainCommitted` notification when execution extensions fall behind. Merging copies the
+    /// blocks, so it's only worth it if the extensions spend more time per notification than per
+    /// block. (0 = disabled)
+    pub max_compacted_blocks: usize,
+}
+
+/// Admission rules evaluated before a transaction is validated.
+///
+/// All rules are disabled by default. The rules can be changed at runtime with
+/// `admin_setTxPoolFirewall`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct TxPoolFirewallConfig {
+    /// Maximum size of the transaction input in bytes.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub max_calldata_size: Option<usize>,
+    /// Senders whose transactions are rejected.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "BTreeSet::is_empty"))]
+    pub blocked_senders: BTreeSet<Address>,
+    /// Recipients to which transactions are rejected.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "BTreeSet::is_empty"))]
+    pub blocked_recipients: BTreeSet<Address>,
+    /// Minimum priority fee per gas, or gas price for legacy transactions, per transaction origin.
+    pub min_priority_fee: MinPriorityFeePerOrigin,
+    /// Which transactions may create contracts.
+    pub contract_creation: ContractCreationPolicy,
+}
+
+impl TxPoolFirewallConfig {
+    /// Returns true if no rule is enabled.
+    pub fn is_disabled(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Minimum priority fee per gas for each transaction origin, in wei.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct MinPriorityFeePerOrigin {
+    /// Minimum for transactions submitted locally.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub local: Option<u64>,
+    /// Minimum for transactions received over the network.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub external: Option<u64>,
+    /// Minimum for private transactions.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub private: Option<u64>,
+}
+
+/// Which transactions may create contracts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum ContractCreationPolicy {
+    /// All transactions may create contracts.
+    #[default]
+    Allow,
+    /// Only locally submitted transactions may create contracts.
+    LocalOnly,
+    /// No transaction may create contracts.
+    Deny,
+}
+
 /// Helper type to support older versions of Duration deserialization.
 #[cfg(feature = "serde")]
 fn deserialize_duration<'de, D>(deserializer: D) -> Result<Option<Duration>, D::Error>
@@ -13,7 +13,7 @@ extern crate alloc;
 
 use alloc::{fmt::Debug, string::String, vec::Vec};
 use alloy_consensus::Header;
-use alloy_primitives::{BlockHash, BlockNumber, Bloom, B256};
+use alloy_primitives::{BlockHash, BlockNumber, Bloom, B256, U256};
 use reth_execution_types::BlockExecutionResult;
 use reth_primitives_traits::{
     constants::{MAXIMUM_GAS_LIMIT_BLOCK, MINIMUM_GAS_LIMIT},
@@ -248,6 +248,19 @@ pub enum ConsensusError {
     #[error("nonce after merge is not zero")]
     TheMergeNonceIsNotZero,
 
+    /// Error when the mix hash of a proof-of-work header doesn't match the computed mix digest.
+    #[error("mix hash mismatch: {0}")]
+    InvalidMixHash(GotExpectedBoxed<B256>),
+
+    /// Error when the proof-of-work of a header doesn't satisfy its difficulty.
+    #[error("proof-of-work {pow} does not satisfy difficulty {difficulty}")]
+    InsufficientProofOfWork {
+        /// The proof-of-work result of the header.
+        pow: B256,
+        /// The difficulty of the header.
+        difficulty: U256,
+    },
+
     /// Error when the ommer root after a merge is not empty.
     #[error("ommer root after merge is not empty")]
     TheMergeOmmerRootIsNotEmpty,
@@ -1,5 +1,7 @@
 //! Engine tree configuration.
 
+use core::time::Duration;
+
 /// Triggers persistence when the number of canonical blocks in memory exceeds this threshold.
 pub const DEFAULT_PERSISTENCE_THRESHOLD: u64 = 2;
 
@@ -14,6 +16,18 @@ pub const DEFAULT_MAX_PROOF_TASK_CONCURRENCY: u64 = 256;
 /// This will be deducted from the thread count of main reth global threadpool.
 pub const DEFAULT_RESERVED_CPU_CORES: usize = 1;
 
+/// Default number of proof targets in a multiproof chunk.
+///
+/// This is the chunk size used until proof latencies were measured, or always if adaptive chunking
+/// is disabled.
+pub const DEFAULT_MULTIPROOF_CHUNK_SIZE: usize = 10;
+
+/// Default maximum number of proof targets in a multiproof chunk.
+pub const DEFAULT_MULTIPROOF_MAX_CHUNK_SIZE: usize = 128;
+
+/// Default latency of a single multiproof chunk calculation that adaptive chunking aims for.
+pub const DEFAULT_MULTIPROOF_CHUNK_TARGET_LATENCY: Duration = Duration::from_millis(2);
+
 const DEFAULT_BLOCK_BUFFER_LIMIT: u32 = 256;
 const DEFAULT_MAX_INVALID_HEADER_CACHE_LENGTH: u32 = 256;
 const DEFAULT_MAX_EXECUTE_BLOCK_BATCH_SIZE: usize = 4;
@@ -81,6 +95,22 @@ pub struct TreeConfig {
     precompile_cache_disabled: bool,
     /// Whether to use state root fallback for testing
     state_root_fallback: bool,
+    /// Number of proof targets in a multiproof chunk, used until proof latencies were measured
+    /// or always if adaptive chunking is disabled.
+    multiproof_chunk_size: usize,
+    /// Maximum number of proof targets in a multiproof chunk.
+    multiproof_max_chunk_size: usize,
+    /// Latency of a single multiproof chunk calculation that adaptive chunking aims for.
+    multiproof_chunk_target_latency: Duration,
+    /// Whether to disable sizing multiproof chunks by the measured proof latency and the number
+    /// of worker threads.
+    disable_adaptive_multiproof_chunking: bool,
+    /// If set, every block whose number is a multiple of this interval is executed a second time
+    /// in the background and compared against the first execution.
+    determinism_check_interval: Option<u64>,
+    /// Whether to prefetch proofs of the accounts and storage slots in transaction access lists
+    /// while the payload is executed.
+    access_list_prefetch: bool,
     /// Whether to always process payload attributes and begin a payload build process
     /// even if `forkchoiceState.headBlockHash` is already the canonical head or an ancestor.
     ///
@@ -116,6 +146,12 @@ impl Default for TreeConfig {
             reserved_cpu_cores: DEFAULT_RESERVED_CPU_CORES,
             precompile_cache_disabled: false,
             state_root_fallback: false,
+            multiproof_chunk_size: DEFAULT_MULTIPROOF_CHUNK_SIZE,
+            multiproof_max_chunk_size: DEFAULT_MULTIPROOF_MAX_CHUNK_SIZE,
+            multiproof_chunk_target_latency: DEFAULT_MULTIPROOF_CHUNK_TARGET_LATENCY,
+            disable_adaptive_multiproof_chunking: false,
+            determinism_check_interval: None,
+            access_list_prefetch: false,
             always_process_payload_attributes_on_canonical_head: false,
         }
     }
@@ -141,6 +177,12 @@ impl TreeConfig {
         reserved_cpu_cores: usize,
         precompile_cache_disabled: bool,
         state_root_fallback: bool,
+        multiproof_chunk_size: usize,
+        multiproof_max_chunk_size: usize,
+        multiproof_chunk_target_latency: Duration,
+        disable_adaptive_multiproof_chunking: bool,
+        determinism_check_interval: Option<u64>,
+        access_list_prefetch: bool,
         always_process_payload_attributes_on_canonical_head: bool,
     ) -> Self {
         Self {
@@ -160,6 +202,12 @@ impl TreeConfig {
             reserved_cpu_cores,
             precompile_cache_disabled,
             state_root_fallback,
+            multiproof_chunk_size,
+            multiproof_max_chunk_size,
+            multiproof_chunk_target_latency,
+            disable_adaptive_multiproof_chunking,
+            determinism_check_interval,
+            access_list_prefetch,
             always_process_payload_attributes_on_canonical_head,
         }
     }
@@ -241,6 +289,40 @@ impl TreeConfig {
         self.state_root_fallback
     }
 
+    /// Returns the number of proof targets in a multiproof chunk, used until proof latencies were
+    /// measured or always if adaptive chunking is disabled.
+    pub const fn multiproof_chunk_size(&self) -> usize {
+        self.multiproof_chunk_size
+    }
+
+    /// Returns the maximum number of proof targets in a multiproof chunk.
+    pub const fn multiproof_max_chunk_size(&self) -> usize {
+        self.multiproof_max_chunk_size
+    }
+
+    /// Returns the latency of a single multiproof chunk calculation that adaptive chunking aims
+    /// for.
+    pub const fn multiproof_chunk_target_latency(&self) -> Duration {
+        self.multiproof_chunk_target_latency
+    }
+
+    /// Returns whether adaptive multiproof chunking is disabled.
+    pub const fn disable_adaptive_multiproof_chunking(&self) -> bool {
+        self.disable_adaptive_multiproof_chunking
+    }
+
+    /// Returns the interval of blocks that are re-executed to check execution determinism, if
+    /// enabled.
+    pub const fn determinism_check_interval(&self) -> Option<u64> {
+        self.determinism_check_interval
+    }
+
+    /// Returns whether proofs of the accounts and storage slots in transaction access lists are
+    /// prefetched during payload validation.
+    pub const fn access_list_prefetch(&self) -> bool {
+        self.access_list_prefetch
+    }
+
     /// Sets whether to always process payload attributes when the FCU head is already canonical.
     pub const fn with_always_process_payload_attributes_on_canonical_head(
         mut self,
@@ -375,6 +457,56 @@ impl TreeConfig {
         self
     }
 
+    /// Setter for the number of proof targets in a multiproof chunk.
+    pub const fn with_multiproof_chunk_size(mut self, multiproof_chunk_size: usize) -> Self {
+        self.multiproof_chunk_size = multiproof_chunk_size;
+        self
+    }
+
+    /// Setter for the maximum number of proof targets in a multiproof chunk.
+    pub const fn with_multiproof_max_chunk_size(
+        mut self,
+        multiproof_max_chunk_size: usize,
+    ) -> Self {
+        self.multiproof_max_chunk_size = multiproof_max_chunk_size;
+        self
+    }
+
+    /// Setter for the latency of a single multiproof chunk calculation that adaptive chunking
+    /// aims for.
+    pub const fn with_multiproof_chunk_target_latency(
+        mut self,
+        multiproof_chunk_target_latency: Duration,
+    ) -> Self {
+        self.multiproof_chunk_target_latency = multiproof_chunk_target_latency;
+        self
+    }
+
+    /// Setter for whether to disable adaptive multiproof chunking.
+    pub const fn without_adaptive_multiproof_chunking(
+        mut self,
+        disable_adaptive_multiproof_chunking: bool,
+    ) -> Self {
+        self.disable_adaptive_multiproof_chunking = disable_adaptive_multiproof_chunking;
+        self
+    }
+
+    /// Setter for the interval of blocks that are re-executed to check execution determinism.
+    pub const fn with_determinism_check_interval(
+        mut self,
+        determinism_check_interval: Option<u64>,
+    ) -> Self {
+        self.determinism_check_interval = determinism_check_interval;
+        self
+    }
+
+    /// Setter for whether to prefetch proofs of the accounts and storage slots in transaction
+    /// access lists.
+    pub const fn with_access_list_prefetch(mut self, access_list_prefetch: bool) -> Self {
+        self.access_list_prefetch = access_list_prefetch;
+        self
+    }
+
     /// Whether or not to use state root task
     pub const fn use_state_root_task(&self) -> bool {
         self.has_enough_parallelism && !self.legacy_state_root
@@ -4,7 +4,7 @@ use crate::ForkchoiceStatus;
 use alloc::boxed::Box;
 use alloy_consensus::BlockHeader;
 use alloy_eips::BlockNumHash;
-use alloy_primitives::B256;
+use alloy_primitives::{BlockNumber, B256};
 use alloy_rpc_types_engine::ForkchoiceState;
 use core::{
     fmt::{Display, Formatter, Result},
@@ -31,6 +31,8 @@ pub enum BeaconConsensusEngineEvent<N: NodePrimitives = EthPrimitives> {
     InvalidBlock(Box<SealedBlock<N::Block>>),
     /// The consensus engine is involved in live sync, and has specific progress
     LiveSyncProgress(ConsensusEngineLiveSyncProgress),
+    /// A canonical block was persisted, with the report of how it was processed.
+    BlockProcessed(Box<BlockProcessedReport>),
 }
 
 impl<N: NodePrimitives> BeaconConsensusEngineEvent<N> {
@@ -75,6 +77,9 @@ where
             Self::BlockReceived(num_hash) => {
                 write!(f, "BlockReceived({num_hash:?})")
             }
+            Self::BlockProcessed(report) => {
+                write!(f, "BlockProcessed({:?})", report.num_hash())
+            }
         }
     }
 }
@@ -90,3 +95,76 @@ pub enum ConsensusEngineLiveSyncProgress {
         target: B256,
     },
 }
+
+/// Report of how a canonical block was processed, from its arrival until it was persisted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlockProcessedReport {
+    /// Number of the block.
+    pub number: BlockNumber,
+    /// Hash of the block.
+    pub hash: B256,
+    /// Where the block was received from.
+    pub origin: BlockOrigin,
+    /// Number of transactions in the block.
+    pub tx_count: usize,
+    /// Gas used by the block.
+    pub gas_used: u64,
+    /// Gas limit of the block.
+    pub gas_limit: u64,
+    /// Time spent in each processing step.
+    pub timings: BlockProcessingTimings,
+}
+
+impl BlockProcessedReport {
+    /// Returns the number and hash of the block.
+    pub const fn num_hash(&self) -> BlockNumHash {
+        BlockNumHash::new(self.number, self.hash)
+    }
+}
+
+/// Time spent in each step of processing a block, `None` if the block skipped the step.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BlockProcessingTimings {
+    /// Time from requesting the download of the block, or of the range it was part of, until it
+    /// was received.
+    pub download: Option<Duration>,
+    /// Time spent validating the block against the consensus rules and its parent.
+    pub validate: Option<Duration>,
+    /// Time spent executing the block.
+    pub execute: Option<Duration>,
+    /// Time spent computing the state root of the block.
+    pub state_root: Option<Duration>,
+    /// Time spent persisting the batch of blocks the block was persisted with.
+    pub persist: Option<Duration>,
+}
+
+/// Where a block processed by the engine was received from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BlockOrigin {
+    /// The block was sent by the consensus layer as a new payload.
+    ConsensusLayer,
+    /// The block was downloaded from the network.
+    Network,
+    /// The block was built locally and inserted already executed.
+    Local,
+    /// The origin of the block is no longer known.
+    Unknown,
+}
+
+impl BlockOrigin {
+    /// Returns the name of the origin.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::ConsensusLayer => "consensus-layer",
+            Self::Network => "network",
+            Self::Local => "local",
+            Self::Unknown => "unknown",
+        }
+    }
+}
+
+impl Display for BlockOrigin {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.write_str(self.as_str())
+    }
+}
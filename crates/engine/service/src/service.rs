@@ -60,6 +60,7 @@ where
     Client: BlockClient<Block = BlockTy<N>> + 'static,
 {
     orchestrator: EngineServiceType<N, Client>,
+    persistence_handle: PersistenceHandle<N::Primitives>,
 }
 
 impl<N, Client> EngineService<N, Client>
@@ -104,7 +105,7 @@ where
             blockchain_db,
             consensus,
             payload_validator,
-            persistence_handle,
+            persistence_handle.clone(),
             payload_builder,
             canonical_in_memory_state,
             tree_config,
@@ -118,13 +119,21 @@ where
 
         let backfill_sync = PipelineSync::new(pipeline, pipeline_task_spawner);
 
-        Self { orchestrator: ChainOrchestrator::new(handler, backfill_sync) }
+        Self { orchestrator: ChainOrchestrator::new(handler, backfill_sync), persistence_handle }
     }
 
     /// Returns a mutable reference to the orchestrator.
     pub fn orchestrator_mut(&mut self) -> &mut EngineServiceType<N, Client> {
         &mut self.orchestrator
     }
+
+    /// Returns the handle to the persistence service.
+    ///
+    /// The persistence service keeps running until all handles are dropped, so a clone of the
+    /// handle can be used to persist blocks after the service is dropped.
+    pub const fn persistence_handle(&self) -> &PersistenceHandle<N::Primitives> {
+        &self.persistence_handle
+    }
 }
 
 impl<N, Client> Stream for EngineService<N, Client>
@@ -0,0 +1,185 @@
+//! Tracks how blocks are processed to emit a [`BlockProcessedReport`] per persisted block.
+
+use crate::engine::DownloadRequest;
+use alloy_consensus::BlockHeader;
+use alloy_primitives::{BlockNumber, B256};
+use reth_engine_primitives::{BlockOrigin, BlockProcessedReport, BlockProcessingTimings};
+use reth_primitives_traits::{Block, BlockBody, RecoveredBlock};
+use schnellru::{ByLength, LruMap};
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+/// The maximum number of received blocks whose origin is remembered until they are executed.
+const MAX_TRACKED_ORIGINS: u32 = 1024;
+
+/// The maximum number of in-flight block download requests that are timed.
+const MAX_TRACKED_DOWNLOADS: u32 = 1024;
+
+/// Collects the origin and processing timings of blocks until they are persisted.
+#[derive(Debug)]
+pub(crate) struct BlockReports {
+    /// Origin and download duration of received blocks that were not executed yet.
+    origins: LruMap<B256, (BlockOrigin, Option<Duration>)>,
+    /// Start of the in-flight download requests, by requested block hash.
+    downloads: LruMap<B256, Instant>,
+    /// Reports of executed blocks that were not persisted yet.
+    pending: HashMap<B256, BlockProcessedReport>,
+    /// Reports of the blocks that are currently being persisted.
+    persisting: Vec<BlockProcessedReport>,
+}
+
+impl Default for BlockReports {
+    fn default() -> Self {
+        Self {
+            origins: LruMap::new(ByLength::new(MAX_TRACKED_ORIGINS)),
+            downloads: LruMap::new(ByLength::new(MAX_TRACKED_DOWNLOADS)),
+            pending: HashMap::default(),
+            persisting: Vec::new(),
+        }
+    }
+}
+
+impl BlockReports {
+    /// Records the start of a download request.
+    pub(crate) fn on_download_requested(&mut self, request: &DownloadRequest) {
+        let now = Instant::now();
+        match request {
+            DownloadRequest::BlockSet(hashes) => {
+                for hash in hashes {
+                    self.downloads.get_or_insert(*hash, || now);
+                }
+            }
+            // a range is downloaded backwards from its highest block
+            DownloadRequest::BlockRange(hash, _) => {
+                self.downloads.get_or_insert(*hash, || now);
+            }
+        }
+    }
+
+    /// Records a batch of blocks received from the block downloader.
+    ///
+    /// Blocks of a range are received in a single batch, the download of every block in the batch
+    /// is timed from the earliest request of any of the blocks.
+    pub(crate) fn on_downloaded<B: Block>(&mut self, blocks: &[RecoveredBlock<B>]) {
+        let requested =
+            blocks.iter().filter_map(|block| self.downloads.remove(&block.hash())).min();
+        let download = requested.map(|requested| requested.elapsed());
+        for block in blocks {
+            // blocks that exceed the execution batch size are received again later
+            self.origins.get_or_insert(block.hash(), || (BlockOrigin::Network, download));
+        }
+    }
+
+    /// Records a block received from the consensus layer.
+    pub(crate) fn on_new_payload(&mut self, hash: B256) {
+        self.origins.get_or_insert(hash, || (BlockOrigin::ConsensusLayer, None));
+    }
+
+    /// Records a block that was validated and executed.
+    pub(crate) fn on_executed<B: Block>(
+        &mut self,
+        block: &RecoveredBlock<B>,
+        mut timings: BlockProcessingTimings,
+    ) {
+        let (origin, download) =
+            self.origins.remove(&block.hash()).unwrap_or((BlockOrigin::Unknown, None));
+        timings.download = download;
+        self.insert_pending(block, origin, timings);
+    }
+
+    /// Records a locally built block that was inserted already executed.
+    pub(crate) fn on_inserted_executed<B: Block>(&mut self, block: &RecoveredBlock<B>) {
+        self.insert_pending(block, BlockOrigin::Local, BlockProcessingTimings::default());
+    }
+
+    /// Records the blocks that are sent to be persisted.
+    pub(crate) fn on_persisting(&mut self, hashes: impl IntoIterator<Item = B256>) {
+        self.persisting =
+            hashes.into_iter().filter_map(|hash| self.pending.remove(&hash)).collect();
+    }
+
+    /// Returns the reports of the persisted blocks.
+    ///
+    /// Reports of blocks at or below the persisted block that were never persisted belong to
+    /// abandoned forks and are dropped.
+    pub(crate) fn on_persisted(
+        &mut self,
+        persist: Duration,
+        last_persisted: BlockNumber,
+    ) -> Vec<BlockProcessedReport> {
+        self.pending.retain(|_, report| report.number > last_persisted);
+        let mut reports = std::mem::take(&mut self.persisting);
+        for report in &mut reports {
+            report.timings.persist = Some(persist);
+        }
+        reports
+    }
+
+    fn insert_pending<B: Block>(
+        &mut self,
+        block: &RecoveredBlock<B>,
+        origin: BlockOrigin,
+        timings: BlockProcessingTimings,
+    ) {
+        self.pending.insert(
+            block.hash(),
+            BlockProcessedReport {
+                number: block.number(),
+                hash: block.hash(),
+                origin,
+                tx_count: block.body().transactions().len(),
+                gas_used: block.gas_used(),
+                gas_limit: block.gas_limit(),
+                timings,
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_chain_state::test_utils::TestBlockBuilder;
+    use std::collections::HashSet;
+
+    #[test]
+    fn reports_persisted_blocks() {
+        let blocks: Vec<_> = TestBlockBuilder::eth().get_executed_blocks(1..4).collect();
+        let [first, second, third] = [0, 1, 2].map(|i| blocks[i].recovered_block().clone());
+        let mut reports = BlockReports::default();
+
+        reports.on_download_requested(&DownloadRequest::BlockSet(HashSet::from([first.hash()])));
+        reports.on_downloaded(std::slice::from_ref(&first));
+        reports.on_new_payload(second.hash());
+
+        let timings = BlockProcessingTimings {
+            execute: Some(Duration::from_millis(10)),
+            ..Default::default()
+        };
+        reports.on_executed(&first, timings);
+        reports.on_executed(&second, timings);
+        reports.on_inserted_executed(&third);
+
+        reports.on_persisting([first.hash(), second.hash()]);
+        let persisted = reports.on_persisted(Duration::from_millis(5), second.number());
+        assert_eq!(persisted.len(), 2);
+
+        assert_eq!(persisted[0].num_hash(), first.num_hash());
+        assert_eq!(persisted[0].origin, BlockOrigin::Network);
+        assert!(persisted[0].timings.download.is_some());
+        assert_eq!(persisted[0].timings.execute, Some(Duration::from_millis(10)));
+        assert_eq!(persisted[0].timings.persist, Some(Duration::from_millis(5)));
+
+        assert_eq!(persisted[1].origin, BlockOrigin::ConsensusLayer);
+        assert_eq!(persisted[1].timings.download, None);
+
+        // the locally built block is reported once it is persisted
+        reports.on_persisting([third.hash()]);
+        let persisted = reports.on_persisted(Duration::from_millis(5), third.number());
+        assert_eq!(persisted.len(), 1);
+        assert_eq!(persisted[0].origin, BlockOrigin::Local);
+        assert!(reports.pending.is_empty());
+    }
+}
@@ -0,0 +1,205 @@
+//! Sampled re-execution of imported blocks to detect nondeterministic execution.
+
+use super::{error::InsertBlockErrorKind, StateProviderBuilder};
+use alloy_consensus::BlockHeader;
+use alloy_primitives::B256;
+use reth_chain_state::ExecutedBlock;
+use reth_evm::{execute::Executor, ConfigureEvm};
+use reth_metrics::{
+    metrics::{Counter, Histogram},
+    Metrics,
+};
+use reth_primitives_traits::{GotExpected, NodePrimitives};
+use reth_provider::{
+    BlockReader, HashedPostStateProvider, StateCommitmentProvider, StateProviderFactory,
+    StateReader, StateRootProvider,
+};
+use reth_revm::database::StateProviderDatabase;
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Instant,
+};
+use tracing::{debug, error, warn};
+
+/// Re-executes a sample of imported blocks on a separate thread and compares the result against
+/// the first execution.
+///
+/// Faulty hardware or nondeterministic code can produce a valid looking execution that differs
+/// from a second execution of the same block. The check only alerts, it never affects the
+/// validity of the block.
+#[derive(Debug)]
+pub(crate) struct DeterminismChecker {
+    /// Every block whose number is a multiple of the interval is checked.
+    interval: Option<u64>,
+    /// Whether a check is currently running. Blocks are skipped while a check is in progress.
+    in_progress: Arc<AtomicBool>,
+    /// Metrics of the determinism check.
+    metrics: DeterminismCheckMetrics,
+}
+
+impl DeterminismChecker {
+    /// Creates a new checker that checks one out of `interval` blocks, if set.
+    pub(crate) fn new(interval: Option<u64>) -> Self {
+        Self {
+            interval: interval.filter(|interval| *interval > 0),
+            in_progress: Default::default(),
+            metrics: Default::default(),
+        }
+    }
+
+    /// Returns `true` if the block with the given number should be checked.
+    pub(crate) fn should_check(&self, block_number: u64) -> bool {
+        self.interval.is_some_and(|interval| block_number % interval == 0)
+    }
+
+    /// Re-executes the block on a separate thread on top of the state of `provider_builder` and
+    /// compares the receipts, requests, hashed state and state root with the executed block.
+    ///
+    /// Does nothing if the previous check is still running.
+    pub(crate) fn spawn<N, P, C>(
+        &self,
+        evm_config: C,
+        provider_builder: StateProviderBuilder<N, P>,
+        executed: ExecutedBlock<N>,
+    ) where
+        N: NodePrimitives,
+        P: BlockReader
+            + StateProviderFactory
+            + StateReader
+            + StateCommitmentProvider
+            + Clone
+            + Send
+            + 'static,
+        C: ConfigureEvm<Primitives = N> + 'static,
+    {
+        if self.in_progress.swap(true, Ordering::AcqRel) {
+            debug!(target: "engine::tree::determinism", block = ?executed.recovered_block.num_hash(), "Skipping determinism check, previous check still running");
+            return
+        }
+
+        let in_progress = self.in_progress.clone();
+        let metrics = self.metrics.clone();
+        let spawned = std::thread::Builder::new().name("Determinism Check".to_string()).spawn(
+            move || {
+                let block = executed.recovered_block.num_hash();
+                let start = Instant::now();
+                match check_block(&evm_config, &provider_builder, &executed) {
+                    Ok(divergences) if divergences.is_empty() => {
+                        debug!(target: "engine::tree::determinism", ?block, elapsed = ?start.elapsed(), "Block re-execution matches");
+                    }
+                    Ok(divergences) => {
+                        metrics.divergences.increment(1);
+                        for divergence in divergences {
+                            error!(target: "engine::tree::determinism", ?block, %divergence, "Block re-execution diverged from the first execution, this indicates faulty hardware or nondeterministic execution");
+                        }
+                    }
+                    Err(err) => {
+                        warn!(target: "engine::tree::determinism", ?block, %err, "Failed to re-execute block for determinism check");
+                    }
+                }
+                metrics.checks.increment(1);
+                metrics.duration.record(start.elapsed());
+                in_progress.store(false, Ordering::Release);
+            },
+        );
+        if let Err(err) = spawned {
+            warn!(target: "engine::tree::determinism", %err, "Failed to spawn determinism check");
+            self.in_progress.store(false, Ordering::Release);
+        }
+    }
+}
+
+/// Re-executes the block and returns all differences to the first execution.
+fn check_block<N, P, C>(
+    evm_config: &C,
+    provider_builder: &StateProviderBuilder<N, P>,
+    executed: &ExecutedBlock<N>,
+) -> Result<Vec<ExecutionDivergence>, InsertBlockErrorKind>
+where
+    N: NodePrimitives,
+    P: BlockReader + StateProviderFactory + StateReader + StateCommitmentProvider + Clone,
+    C: ConfigureEvm<Primitives = N>,
+{
+    let block = &executed.recovered_block;
+    let state_provider = provider_builder.build()?;
+    let output = evm_config.executor(StateProviderDatabase::new(&state_provider)).execute(block)?;
+
+    let mut divergences = Vec::new();
+    if executed.execution_output.receipts.first() != Some(&output.result.receipts) {
+        divergences.push(ExecutionDivergence::Receipts);
+    }
+    if executed.execution_output.requests.first() != Some(&output.result.requests) {
+        divergences.push(ExecutionDivergence::Requests);
+    }
+
+    let hashed_state = state_provider.hashed_post_state(&output.state);
+    if hashed_state != *executed.hashed_state {
+        divergences.push(ExecutionDivergence::HashedState);
+    }
+
+    let state_root = state_provider.state_root(hashed_state)?;
+    if state_root != block.state_root() {
+        divergences.push(ExecutionDivergence::StateRoot(GotExpected {
+            got: state_root,
+            expected: block.state_root(),
+        }));
+    }
+
+    Ok(divergences)
+}
+
+/// A difference between the first execution of a block and its re-execution.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ExecutionDivergence {
+    /// The receipts differ.
+    Receipts,
+    /// The EIP-7685 requests differ.
+    Requests,
+    /// The hashed post state differs.
+    HashedState,
+    /// The state root of the re-execution differs from the block's state root.
+    StateRoot(GotExpected<B256>),
+}
+
+impl fmt::Display for ExecutionDivergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Receipts => f.write_str("receipts differ"),
+            Self::Requests => f.write_str("requests differ"),
+            Self::HashedState => f.write_str("post state differs"),
+            Self::StateRoot(diff) => write!(f, "state root differs: {diff}"),
+        }
+    }
+}
+
+/// Metrics of the execution determinism check.
+#[derive(Metrics, Clone)]
+#[metrics(scope = "sync.determinism_check")]
+struct DeterminismCheckMetrics {
+    /// Number of blocks that were re-executed.
+    checks: Counter,
+    /// Number of re-executed blocks that diverged from the first execution.
+    divergences: Counter,
+    /// Duration of a block re-execution including the state root.
+    duration: Histogram,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn samples_blocks_by_interval() {
+        let checker = DeterminismChecker::new(Some(100));
+        assert!(checker.should_check(0));
+        assert!(!checker.should_check(150));
+        assert!(checker.should_check(200));
+
+        assert!(!DeterminismChecker::new(None).should_check(100));
+        assert!(!DeterminismChecker::new(Some(0)).should_check(100));
+    }
+}
@@ -279,6 +279,15 @@ impl<S: StorageRootProvider> StorageRootProvider for InstrumentedStateProvider<S
     ) -> ProviderResult<StorageMultiProof> {
         self.state_provider.storage_multiproof(address, slots, hashed_storage)
     }
+
+    fn storage_multiproof_from_nodes(
+        &self,
+        input: TrieInput,
+        address: Address,
+        slots: &[B256],
+    ) -> ProviderResult<StorageMultiProof> {
+        self.state_provider.storage_multiproof_from_nodes(input, address, slots)
+    }
 }
 
 impl<S: BlockHashReader> BlockHashReader for InstrumentedStateProvider<S> {
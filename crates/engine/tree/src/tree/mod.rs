@@ -31,8 +31,9 @@ use reth_chain_state::{
 use reth_consensus::{Consensus, FullConsensus};
 pub use reth_engine_primitives::InvalidBlockHook;
 use reth_engine_primitives::{
-    BeaconConsensusEngineEvent, BeaconEngineMessage, BeaconOnNewPayloadError, EngineValidator,
-    ExecutionPayload, ForkchoiceStateTracker, OnForkChoiceUpdated,
+    BeaconConsensusEngineEvent, BeaconEngineMessage, BeaconOnNewPayloadError,
+    BlockProcessingTimings, EngineValidator, ExecutionPayload, ForkchoiceStateTracker,
+    OnForkChoiceUpdated,
 };
 use reth_errors::{ConsensusError, ProviderResult};
 use reth_evm::{ConfigureEvm, Evm, SpecFor};
@@ -70,7 +71,9 @@ use tokio::sync::{
 use tracing::*;
 
 mod block_buffer;
+mod block_reports;
 mod cached_state;
+mod determinism_check;
 #[cfg(test)]
 mod e2e_tests;
 pub mod error;
@@ -89,6 +92,8 @@ mod trie_updates;
 
 use crate::tree::error::AdvancePersistenceError;
 pub use block_buffer::BlockBuffer;
+use block_reports::BlockReports;
+use determinism_check::DeterminismChecker;
 pub use invalid_block_hook::{InvalidBlockHooks, NoopInvalidBlockHook};
 pub use invalid_headers::InvalidHeaderCache;
 pub use payload_processor::*;
@@ -284,6 +289,10 @@ where
     precompile_cache_map: PrecompileCacheMap<SpecFor<C>>,
     /// Metrics for precompile cache, stored per address to avoid re-allocation.
     precompile_cache_metrics: HashMap<Address, CachedPrecompileMetrics>,
+    /// Re-executes sampled blocks to detect nondeterministic execution.
+    determinism_checker: DeterminismChecker,
+    /// Collects the processing reports of blocks until they are persisted.
+    block_reports: BlockReports,
 }
 
 impl<N, P: Debug, T: PayloadTypes + Debug, V: Debug, C> std::fmt::Debug
@@ -310,6 +319,8 @@ where
             .field("engine_kind", &self.engine_kind)
             .field("payload_processor", &self.payload_processor)
             .field("evm_config", &self.evm_config)
+            .field("determinism_checker", &self.determinism_checker)
+            .field("block_reports", &self.block_reports)
             .finish()
     }
 }
@@ -357,6 +368,7 @@ where
             &config,
             precompile_cache_map.clone(),
         );
+        let determinism_checker = DeterminismChecker::new(config.determinism_check_interval());
 
         Self {
             provider,
@@ -379,6 +391,8 @@ where
             evm_config,
             precompile_cache_map,
             precompile_cache_metrics: HashMap::new(),
+            determinism_checker,
+            block_reports: BlockReports::default(),
         }
     }
 
@@ -491,6 +505,7 @@ where
         }
 
         trace!(target: "engine::tree", block_count = %blocks.len(), "received downloaded blocks");
+        self.block_reports.on_downloaded(&blocks);
         let batch = self.config.max_execute_block_batch_size().min(blocks.len());
         for block in blocks.drain(..batch) {
             if let Some(event) = self.on_downloaded_block(block)? {
@@ -585,6 +600,7 @@ where
         self.emit_event(EngineApiEvent::BeaconConsensus(engine_event));
 
         let block_hash = block.hash();
+        self.block_reports.on_new_payload(block_hash);
         let mut lowest_buffered_ancestor = self.lowest_buffered_ancestor_or(block_hash);
         if lowest_buffered_ancestor == block_hash {
             lowest_buffered_ancestor = block.parent_hash();
@@ -1022,6 +1038,8 @@ where
             .expect("Checked non-empty persisting blocks");
 
         debug!(target: "engine::tree", blocks = ?blocks_to_persist.iter().map(|block| block.recovered_block().num_hash()).collect::<Vec<_>>(), "Persisting blocks");
+        self.block_reports
+            .on_persisting(blocks_to_persist.iter().map(|block| block.recovered_block().hash()));
         let (tx, rx) = oneshot::channel();
         let _ = self.persistence.save_blocks(blocks_to_persist, tx);
 
@@ -1057,6 +1075,14 @@ where
                     debug!(target: "engine::tree", ?last_persisted_block_hash, ?last_persisted_block_number, "Finished persisting, calling finish");
                     self.persistence_state
                         .finish(last_persisted_block_hash, last_persisted_block_number);
+                    for report in self
+                        .block_reports
+                        .on_persisted(start_time.elapsed(), last_persisted_block_number)
+                    {
+                        self.emit_event(EngineApiEvent::BeaconConsensus(
+                            BeaconConsensusEngineEvent::BlockProcessed(Box::new(report)),
+                        ));
+                    }
                     self.on_new_persisted_block()?;
                 }
                 Err(TryRecvError::Closed) => return Err(TryRecvError::Closed.into()),
@@ -1116,6 +1142,7 @@ where
 
                         self.state.tree_state.insert_executed(block.clone());
                         self.metrics.engine.inserted_already_executed_blocks.increment(1);
+                        self.block_reports.on_inserted_executed(block.recovered_block());
                         self.emit_event(EngineApiEvent::BeaconConsensus(
                             BeaconConsensusEngineEvent::CanonicalBlockAdded(block, now.elapsed()),
                         ));
@@ -1347,6 +1374,7 @@ where
                 self.emit_event(EngineApiEvent::BackfillAction(action));
             }
             TreeEvent::Download(action) => {
+                self.block_reports.on_download_requested(&action);
                 self.emit_event(EngineApiEvent::Download(action));
             }
         }
@@ -2169,6 +2197,7 @@ where
             warn!(target: "engine::tree", ?block, "Failed to validate header {} against parent: {e}", block.hash());
             return Err((e.into(), block))
         }
+        let validation_elapsed = start.elapsed();
 
         let state_provider = ensure_ok!(provider_builder.build());
 
@@ -2263,6 +2292,7 @@ where
             handle.cache_metrics(),
         );
 
+        let execution_start = Instant::now();
         let (output, execution_finish) = if self.config.state_provider_metrics() {
             let state_provider = InstrumentedStateProvider::from_state_provider(&state_provider);
             let (output, execution_finish) =
@@ -2408,6 +2438,20 @@ where
             trie: trie_updates,
         };
 
+        if self.determinism_checker.should_check(block_num_hash.number) {
+            match self.state_provider_builder(executed.recovered_block().parent_hash()) {
+                Ok(Some(provider_builder)) => self.determinism_checker.spawn(
+                    self.evm_config.clone(),
+                    provider_builder,
+                    executed.block.clone(),
+                ),
+                Ok(None) => {}
+                Err(err) => {
+                    debug!(target: "engine::tree", block=?block_num_hash, %err, "Failed to create state provider for determinism check")
+                }
+            }
+        }
+
         // if the parent is the canonical head, we can insert the block as the pending block
         if self.state.tree_state.canonical_block_hash() == executed.recovered_block().parent_hash()
         {
@@ -2417,6 +2461,15 @@ where
 
         self.state.tree_state.insert_executed(executed.clone());
         self.metrics.engine.executed_blocks.set(self.state.tree_state.block_count() as f64);
+        self.block_reports.on_executed(
+            executed.recovered_block(),
+            BlockProcessingTimings {
+                validate: Some(validation_elapsed),
+                execute: Some(execution_finish.duration_since(execution_start)),
+                state_root: Some(root_elapsed),
+                ..Default::default()
+            },
+        );
 
         // emit insert event
         let elapsed = start.elapsed();
@@ -9,7 +9,7 @@ use crate::tree::{
     sparse_trie::SparseTrieTask,
     StateProviderBuilder, TreeConfig,
 };
-use alloy_consensus::{transaction::Recovered, BlockHeader};
+use alloy_consensus::{transaction::Recovered, BlockHeader, Transaction};
 use alloy_evm::block::StateChangeSource;
 use alloy_primitives::B256;
 use executor::WorkloadExecutor;
@@ -71,6 +71,8 @@ where
     /// A cleared sparse trie, kept around to be reused for the state root computation so that
     /// allocations can be minimized.
     sparse_trie: Option<SparseTrie>,
+    /// Sizes the multiproof chunks, keeps the measured proof latencies across blocks.
+    multiproof_chunk_sizer: MultiproofChunkSizer,
     _marker: std::marker::PhantomData<N>,
 }
 
@@ -96,6 +98,7 @@ where
             precompile_cache_disabled: config.precompile_cache_disabled(),
             precompile_cache_map,
             sparse_trie: None,
+            multiproof_chunk_sizer: MultiproofChunkSizer::new(config, proof_workers(config)),
             _marker: Default::default(),
         }
     }
@@ -183,11 +186,24 @@ where
             proof_task.handle(),
             to_sparse_trie,
             max_multi_proof_task_concurrency,
+            self.multiproof_chunk_sizer.clone(),
         );
 
         // wire the multiproof task to the prewarm task
         let to_multi_proof = Some(multi_proof_task.state_root_message_sender());
 
+        // the access lists hint at the accounts and storage slots the block touches, so their
+        // proofs can be fetched before the state updates arrive
+        if config.access_list_prefetch() {
+            let targets =
+                access_list_proof_targets(transactions.iter().filter_map(|tx| tx.access_list()));
+            if !targets.is_empty() {
+                let _ = multi_proof_task
+                    .state_root_message_sender()
+                    .send(MultiProofMessage::AccessListPrefetch(targets));
+            }
+        }
+
         let prewarm_handle =
             self.spawn_caching_with(header, transactions, provider_builder, to_multi_proof.clone());
 
@@ -454,6 +470,15 @@ impl ExecutionCache {
     }
 }
 
+/// Returns the number of threads available for proof calculation, that is all available threads
+/// except the ones reserved for non-reth processes.
+fn proof_workers(config: &TreeConfig) -> usize {
+    std::thread::available_parallelism()
+        .map_or(1, |num| num.get())
+        .saturating_sub(config.reserved_cpu_cores())
+        .max(1)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::tree::{
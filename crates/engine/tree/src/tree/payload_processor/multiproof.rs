@@ -1,6 +1,7 @@
 //! Multiproof task related functionality.
 
 use crate::tree::payload_processor::executor::WorkloadExecutor;
+use alloy_eips::eip2930::AccessList;
 use alloy_evm::block::StateChangeSource;
 use alloy_primitives::{
     keccak256,
@@ -9,6 +10,7 @@ use alloy_primitives::{
 };
 use derive_more::derive::Deref;
 use metrics::Histogram;
+use reth_engine_primitives::TreeConfig;
 use reth_errors::ProviderError;
 use reth_metrics::Metrics;
 use reth_provider::{
@@ -25,6 +27,7 @@ use std::{
     collections::{BTreeMap, VecDeque},
     ops::DerefMut,
     sync::{
+        atomic::{AtomicU64, Ordering},
         mpsc::{channel, Receiver, Sender},
         Arc,
     },
@@ -32,8 +35,93 @@ use std::{
 };
 use tracing::{debug, error, trace};
 
-/// The size of proof targets chunk to spawn in one calculation.
-const MULTIPROOF_TARGETS_CHUNK_SIZE: usize = 10;
+/// Sizes the proof target chunks that are spawned in one calculation.
+///
+/// With adaptive chunking, the chunk size is derived from the measured proof calculation latency
+/// per target, so that a single chunk takes about the target latency, and from the number of
+/// worker threads, so that the targets of a state update are spread over all workers. The
+/// measurements are kept across blocks.
+///
+/// This type is cheap to clone.
+#[derive(Debug, Clone)]
+pub(super) struct MultiproofChunkSizer {
+    /// Chunk size used until proof latencies were measured, or always if adaptive chunking is
+    /// disabled.
+    default_size: usize,
+    /// Maximum chunk size.
+    max_size: usize,
+    /// Latency of a single chunk calculation to aim for.
+    target_latency: Duration,
+    /// Number of worker threads the chunks are calculated on.
+    workers: usize,
+    /// Whether adaptive chunking is enabled.
+    adaptive: bool,
+    /// Moving average of the proof calculation latency per target in nanoseconds, zero if no
+    /// latencies were measured yet.
+    latency_per_target: Arc<AtomicU64>,
+}
+
+impl MultiproofChunkSizer {
+    /// Creates a new chunk sizer from the tree configuration for the given number of workers.
+    pub(super) fn new(config: &TreeConfig, workers: usize) -> Self {
+        Self {
+            default_size: config.multiproof_chunk_size().max(1),
+            max_size: config.multiproof_max_chunk_size().max(1),
+            target_latency: config.multiproof_chunk_target_latency(),
+            workers: workers.max(1),
+            adaptive: !config.disable_adaptive_multiproof_chunking(),
+            latency_per_target: Default::default(),
+        }
+    }
+
+    /// Returns the chunk size to split the given number of proof targets with.
+    pub(super) fn chunk_size(&self, targets: usize) -> usize {
+        if !self.adaptive {
+            return self.default_size
+        }
+
+        let latency_per_target = self.latency_per_target.load(Ordering::Relaxed);
+        let size = if latency_per_target == 0 {
+            self.default_size
+        } else {
+            (self.target_latency.as_nanos() / latency_per_target as u128) as usize
+        };
+
+        // Smaller chunks keep all workers busy if there are fewer targets than workers can
+        // process at the target latency.
+        size.min(targets.div_ceil(self.workers)).clamp(1, self.max_size)
+    }
+
+    /// Records the latency of a chunk calculation with the given number of proof targets.
+    pub(super) fn on_chunk_calculated(&self, targets: usize, elapsed: Duration) {
+        if targets == 0 {
+            return
+        }
+
+        let sample = (elapsed.as_nanos() / targets as u128).clamp(1, u64::MAX as u128) as u64;
+        let _ = self.latency_per_target.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |avg| {
+            // Weigh the latest sample by 1/8, so that a single outlier does not skew the size.
+            Some(if avg == 0 { sample } else { avg - avg / 8 + sample / 8 })
+        });
+    }
+}
+
+/// Returns the number of proof targets the given targets count towards the chunk size, see
+/// [`MultiProofTargets::chunks`].
+fn proof_targets_chunking_length(targets: &MultiProofTargets) -> usize {
+    targets.values().map(|slots| slots.len().max(1)).sum()
+}
+
+/// Returns the number of items the given state counts towards the chunk size, see
+/// [`HashedPostState::chunks`].
+fn hashed_state_chunking_length(state: &HashedPostState) -> usize {
+    state.accounts.len() +
+        state
+            .storages
+            .values()
+            .map(|storage| storage.storage.len() + storage.wiped as usize)
+            .sum::<usize>()
+}
 
 /// A trie update that can be applied to sparse trie alongside the proofs for touched parts of the
 /// state.
@@ -100,6 +188,11 @@ impl<Factory> MultiProofConfig<Factory> {
 pub(super) enum MultiProofMessage {
     /// Prefetch proof targets
     PrefetchProofs(MultiProofTargets),
+    /// Prefetch proof targets hinted by the access lists of the block's transactions.
+    ///
+    /// Unlike [`MultiProofMessage::PrefetchProofs`], the hinted targets are compared against the
+    /// state updates of the block to measure the hit rate.
+    AccessListPrefetch(MultiProofTargets),
     /// New state update from transaction execution with its source
     StateUpdate(StateChangeSource, EvmState),
     /// State update that can be applied to the sparse trie without any new proofs.
@@ -130,6 +223,8 @@ pub(super) struct ProofCalculated {
     sequence_number: u64,
     /// Sparse trie update
     update: SparseTrieUpdate,
+    /// The number of proof targets the proof was calculated for.
+    targets: usize,
     /// The time taken to calculate the proof.
     elapsed: Duration,
 }
@@ -243,6 +338,64 @@ pub(crate) fn evm_state_to_hashed_post_state(update: EvmState) -> HashedPostStat
     hashed_state
 }
 
+/// Returns the proof targets of the accounts and storage slots in the given access lists.
+pub(super) fn access_list_proof_targets<'a>(
+    access_lists: impl IntoIterator<Item = &'a AccessList>,
+) -> MultiProofTargets {
+    let mut targets = MultiProofTargets::default();
+    for item in access_lists.into_iter().flat_map(|access_list| access_list.iter()) {
+        targets
+            .entry(keccak256(item.address))
+            .or_default()
+            .extend(item.storage_keys.iter().map(keccak256));
+    }
+    targets
+}
+
+/// Proof targets hinted by transaction access lists, compared against the proof targets of the
+/// state updates of the block.
+#[derive(Debug, Default)]
+struct AccessListHints {
+    /// Targets hinted by the access lists.
+    hinted: MultiProofTargets,
+    /// Targets of all state updates received so far.
+    touched: MultiProofTargets,
+}
+
+impl AccessListHints {
+    /// Creates hints for the given targets.
+    fn new(hinted: MultiProofTargets) -> Self {
+        Self { hinted, touched: Default::default() }
+    }
+
+    /// Records the accounts and storage slots of a state update.
+    fn on_state_update(&mut self, update: &HashedPostState) {
+        for hashed_address in update.accounts.keys() {
+            self.touched.entry(*hashed_address).or_default();
+        }
+        for (hashed_address, storage) in &update.storages {
+            self.touched.entry(*hashed_address).or_default().extend(storage.storage.keys());
+        }
+    }
+
+    /// Returns the number of hinted targets that were touched, the number of hinted targets and
+    /// the number of touched targets, counting accounts and storage slots.
+    fn hits(&self) -> (usize, usize, usize) {
+        let count = |targets: &MultiProofTargets| {
+            targets.values().map(|slots| 1 + slots.len()).sum::<usize>()
+        };
+        let hits = self
+            .hinted
+            .iter()
+            .filter_map(|(hashed_address, slots)| {
+                let touched = self.touched.get(hashed_address)?;
+                Some(1 + slots.iter().filter(|slot| touched.contains(*slot)).count())
+            })
+            .sum();
+        (hits, count(&self.hinted), count(&self.touched))
+    }
+}
+
 /// A pending multiproof task, either [`StorageMultiproofInput`] or [`MultiproofInput`].
 #[derive(Debug)]
 enum PendingMultiproofTask<Factory> {
@@ -478,6 +631,7 @@ where
                                     proof,
                                 ),
                             },
+                            targets: storage_targets,
                             elapsed,
                         }),
                     ));
@@ -508,6 +662,7 @@ where
         self.executor.spawn_blocking(move || {
             let account_targets = proof_targets.len();
             let storage_targets = proof_targets.values().map(|slots| slots.len()).sum::<usize>();
+            let targets = proof_targets_chunking_length(&proof_targets);
 
             trace!(
                 target: "engine::root",
@@ -547,6 +702,7 @@ where
                                 state: hashed_state_update,
                                 multiproof: proof,
                             },
+                            targets,
                             elapsed,
                         }),
                     ));
@@ -584,6 +740,8 @@ pub(crate) struct MultiProofTaskMetrics {
     pub state_update_proof_targets_storages_histogram: Histogram,
     /// Histogram of the number of state update proof target chunks.
     pub state_update_proof_chunks_histogram: Histogram,
+    /// Histogram of the proof target chunk sizes.
+    pub proof_chunk_size_histogram: Histogram,
 
     /// Histogram of proof calculation durations.
     pub proof_calculation_duration_histogram: Histogram,
@@ -605,6 +763,15 @@ pub(crate) struct MultiProofTaskMetrics {
     pub first_update_wait_time_histogram: Histogram,
     /// Total time spent waiting for the last proof result.
     pub last_proof_wait_time_histogram: Histogram,
+
+    /// Histogram of the number of accounts and storage slots hinted by access lists.
+    pub access_list_hinted_targets_histogram: Histogram,
+    /// Histogram of the share of hinted accounts and storage slots that were updated by the
+    /// block.
+    pub access_list_hit_rate_histogram: Histogram,
+    /// Histogram of the share of accounts and storage slots updated by the block that were
+    /// hinted.
+    pub access_list_coverage_histogram: Histogram,
 }
 
 /// Standalone task that receives a transaction state stream and updates relevant
@@ -628,10 +795,19 @@ pub(super) struct MultiProofTask<Factory: DatabaseProviderFactory> {
     to_sparse_trie: Sender<SparseTrieUpdate>,
     /// Proof targets that have been already fetched.
     fetched_proof_targets: MultiProofTargets,
+    /// Accounts whose storage was wiped by a state update of this block.
+    ///
+    /// Their storage tries are empty after the wipe, so proofs for their storage slots would only
+    /// reveal nodes that are discarded anyway.
+    wiped_storages: B256Set,
+    /// Proof targets hinted by transaction access lists, if prefetched.
+    access_list_hints: Option<AccessListHints>,
     /// Proof sequencing handler.
     proof_sequencer: ProofSequencer,
     /// Manages calculation of multiproofs.
     multiproof_manager: MultiproofManager<Factory>,
+    /// Sizes the proof target chunks.
+    chunk_sizer: MultiproofChunkSizer,
     /// multi proof task metrics
     metrics: MultiProofTaskMetrics,
 }
@@ -648,6 +824,7 @@ where
         proof_task_handle: ProofTaskManagerHandle<FactoryTx<Factory>>,
         to_sparse_trie: Sender<SparseTrieUpdate>,
         max_concurrency: usize,
+        chunk_sizer: MultiproofChunkSizer,
     ) -> Self {
         let (tx, rx) = channel();
         let metrics = MultiProofTaskMetrics::default();
@@ -658,6 +835,8 @@ where
             tx,
             to_sparse_trie,
             fetched_proof_targets: Default::default(),
+            wiped_storages: Default::default(),
+            access_list_hints: None,
             proof_sequencer: ProofSequencer::default(),
             multiproof_manager: MultiproofManager::new(
                 executor,
@@ -665,6 +844,7 @@ where
                 proof_task_handle,
                 max_concurrency,
             ),
+            chunk_sizer,
             metrics,
         }
     }
@@ -687,8 +867,10 @@ where
             .record(proof_targets.values().map(|slots| slots.len()).sum::<usize>() as f64);
 
         // Process proof targets in chunks.
+        let chunk_size = self.chunk_sizer.chunk_size(proof_targets_chunking_length(&proof_targets));
+        self.metrics.proof_chunk_size_histogram.record(chunk_size as f64);
         let mut chunks = 0;
-        for proof_targets_chunk in proof_targets.chunks(MULTIPROOF_TARGETS_CHUNK_SIZE) {
+        for proof_targets_chunk in proof_targets.chunks(chunk_size) {
             self.multiproof_manager.spawn_or_queue(
                 MultiproofInput {
                     config: self.config.clone(),
@@ -737,6 +919,14 @@ where
         // This means we need to remove any storage slots that have already been fetched
         let mut duplicates = 0;
 
+        // Storage slots of wiped storages don't need to be revealed, only the account itself.
+        for (hashed_address, target_storage) in targets.deref_mut() {
+            if self.wiped_storages.contains(hashed_address) {
+                duplicates += target_storage.len();
+                target_storage.clear();
+            }
+        }
+
         // First remove all storage targets that are subsets of already fetched storage slots
         targets.retain(|hashed_address, target_storage| {
             let keep = self
@@ -785,6 +975,16 @@ where
     /// Returns a number of proofs that were spawned.
     fn on_state_update(&mut self, source: StateChangeSource, update: EvmState) -> u64 {
         let hashed_state_update = evm_state_to_hashed_post_state(update);
+        if let Some(hints) = &mut self.access_list_hints {
+            hints.on_state_update(&hashed_state_update);
+        }
+        self.wiped_storages.extend(
+            hashed_state_update
+                .storages
+                .iter()
+                .filter(|(_, storage)| storage.wiped)
+                .map(|(hashed_address, _)| *hashed_address),
+        );
         // Split the state update into already fetched and not fetched according to the proof
         // targets.
         let (fetched_state_update, not_fetched_state_update) =
@@ -802,10 +1002,14 @@ where
         }
 
         // Process state updates in chunks.
+        let chunk_size =
+            self.chunk_sizer.chunk_size(hashed_state_chunking_length(&not_fetched_state_update));
+        self.metrics.proof_chunk_size_histogram.record(chunk_size as f64);
         let mut chunks = 0;
         let mut spawned_proof_targets = MultiProofTargets::default();
-        for chunk in not_fetched_state_update.chunks(MULTIPROOF_TARGETS_CHUNK_SIZE) {
-            let proof_targets = get_proof_targets(&chunk, &self.fetched_proof_targets);
+        for chunk in not_fetched_state_update.chunks(chunk_size) {
+            let proof_targets =
+                get_proof_targets(&chunk, &self.fetched_proof_targets, &self.wiped_storages);
             spawned_proof_targets.extend_ref(&proof_targets);
 
             self.multiproof_manager.spawn_or_queue(
@@ -931,6 +1135,21 @@ where
                             "Prefetching proofs"
                         );
                     }
+                    MultiProofMessage::AccessListPrefetch(targets) => {
+                        trace!(target: "engine::root", "processing MultiProofMessage::AccessListPrefetch");
+                        let hints = AccessListHints::new(targets.clone());
+                        let (_, hinted, _) = hints.hits();
+                        self.metrics.access_list_hinted_targets_histogram.record(hinted as f64);
+                        self.access_list_hints = Some(hints);
+
+                        prefetch_proofs_requested += self.on_prefetch_proof(targets);
+                        debug!(
+                            target: "engine::root",
+                            hinted,
+                            prefetch_proofs_requested,
+                            "Prefetching access list proofs"
+                        );
+                    }
                     MultiProofMessage::StateUpdate(source, update) => {
                         trace!(target: "engine::root", "processing MultiProofMessage::StateUpdate");
                         if first_update_time.is_none() {
@@ -1005,6 +1224,10 @@ where
                         self.metrics
                             .proof_calculation_duration_histogram
                             .record(proof_calculated.elapsed);
+                        self.chunk_sizer.on_chunk_calculated(
+                            proof_calculated.targets,
+                            proof_calculated.elapsed,
+                        );
 
                         debug!(
                             target: "engine::root",
@@ -1074,15 +1297,29 @@ where
                 .last_proof_wait_time_histogram
                 .record(updates_finished_time.elapsed().as_secs_f64());
         }
+        if let Some(hints) = &self.access_list_hints {
+            let (hits, hinted, touched) = hints.hits();
+            debug!(target: "engine::root", hits, hinted, touched, "Access list prefetch hits");
+            if hinted > 0 {
+                self.metrics.access_list_hit_rate_histogram.record(hits as f64 / hinted as f64);
+            }
+            if touched > 0 {
+                self.metrics.access_list_coverage_histogram.record(hits as f64 / touched as f64);
+            }
+        }
     }
 }
 
 /// Returns accounts only with those storages that were not already fetched, and
 /// if there are no such storages and the account itself was already fetched, the
 /// account shouldn't be included.
+///
+/// Storage slots of accounts in `wiped_storages` are never included, because their storage tries
+/// are empty after the wipe and can be updated without revealing any nodes.
 fn get_proof_targets(
     state_update: &HashedPostState,
     fetched_proof_targets: &MultiProofTargets,
+    wiped_storages: &B256Set,
 ) -> MultiProofTargets {
     let mut targets = MultiProofTargets::default();
 
@@ -1096,17 +1333,22 @@ fn get_proof_targets(
     // then process storage slots for all accounts in the state update
     for (hashed_address, storage) in &state_update.storages {
         let fetched = fetched_proof_targets.get(hashed_address);
-        let mut changed_slots = storage
-            .storage
-            .keys()
-            .filter(|slot| !fetched.is_some_and(|f| f.contains(*slot)))
-            .peekable();
 
         // If the storage is wiped, we still need to fetch the account proof.
         if storage.wiped && fetched.is_none() {
             targets.entry(*hashed_address).or_default();
         }
 
+        if storage.wiped || wiped_storages.contains(hashed_address) {
+            continue
+        }
+
+        let mut changed_slots = storage
+            .storage
+            .keys()
+            .filter(|slot| !fetched.is_some_and(|f| f.contains(*slot)))
+            .peekable();
+
         if changed_slots.peek().is_some() {
             targets.entry(*hashed_address).or_default().extend(changed_slots);
         }
@@ -1118,7 +1360,8 @@ fn get_proof_targets(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use alloy_primitives::map::B256Set;
+    use alloy_eips::eip2930::AccessListItem;
+    use alloy_primitives::{map::B256Set, Address};
     use reth_provider::{providers::ConsistentDbView, test_utils::create_test_provider_factory};
     use reth_trie::{MultiProof, TrieInput};
     use reth_trie_parallel::proof_task::{ProofTaskCtx, ProofTaskManager};
@@ -1162,7 +1405,49 @@ mod tests {
         );
         let channel = channel();
 
-        MultiProofTask::new(config, executor, proof_task.handle(), channel.0, 1)
+        MultiProofTask::new(
+            config,
+            executor,
+            proof_task.handle(),
+            channel.0,
+            1,
+            MultiproofChunkSizer::new(&TreeConfig::default(), 1),
+        )
+    }
+
+    #[test]
+    fn test_chunk_sizer_fixed() {
+        let config = TreeConfig::default()
+            .with_multiproof_chunk_size(7)
+            .without_adaptive_multiproof_chunking(true);
+        let sizer = MultiproofChunkSizer::new(&config, 4);
+        sizer.on_chunk_calculated(10, Duration::from_micros(100));
+
+        assert_eq!(sizer.chunk_size(1000), 7);
+        assert_eq!(sizer.chunk_size(1), 7);
+    }
+
+    #[test]
+    fn test_chunk_sizer_adaptive() {
+        let config = TreeConfig::default()
+            .with_multiproof_chunk_size(10)
+            .with_multiproof_max_chunk_size(100)
+            .with_multiproof_chunk_target_latency(Duration::from_millis(1));
+        let sizer = MultiproofChunkSizer::new(&config, 4);
+
+        // no measurements yet
+        assert_eq!(sizer.chunk_size(1000), 10);
+        // spread few targets over all workers
+        assert_eq!(sizer.chunk_size(8), 2);
+
+        // 20us per target
+        sizer.on_chunk_calculated(10, Duration::from_micros(200));
+        assert_eq!(sizer.chunk_size(1000), 50);
+
+        // capped by the maximum chunk size
+        let sizer = MultiproofChunkSizer::new(&config, 1);
+        sizer.on_chunk_calculated(100, Duration::from_micros(100));
+        assert_eq!(sizer.chunk_size(1000), 100);
     }
 
     #[test]
@@ -1271,7 +1556,7 @@ mod tests {
         let state = create_get_proof_targets_state();
         let fetched = MultiProofTargets::default();
 
-        let targets = get_proof_targets(&state, &fetched);
+        let targets = get_proof_targets(&state, &fetched, &B256Set::default());
 
         // should return all accounts as targets since nothing was fetched before
         assert_eq!(targets.len(), state.accounts.len());
@@ -1285,7 +1570,7 @@ mod tests {
         let state = create_get_proof_targets_state();
         let fetched = MultiProofTargets::default();
 
-        let targets = get_proof_targets(&state, &fetched);
+        let targets = get_proof_targets(&state, &fetched, &B256Set::default());
 
         // verify storage slots are included for accounts with storage
         for (addr, storage) in &state.storages {
@@ -1313,7 +1598,7 @@ mod tests {
         // mark the account as already fetched
         fetched.insert(*fetched_addr, HashSet::default());
 
-        let targets = get_proof_targets(&state, &fetched);
+        let targets = get_proof_targets(&state, &fetched, &B256Set::default());
 
         // should not include the already fetched account since it has no storage updates
         assert!(!targets.contains_key(fetched_addr));
@@ -1333,7 +1618,7 @@ mod tests {
         fetched_slots.insert(fetched_slot);
         fetched.insert(*addr, fetched_slots);
 
-        let targets = get_proof_targets(&state, &fetched);
+        let targets = get_proof_targets(&state, &fetched, &B256Set::default());
 
         // should not include the already fetched storage slot
         let target_slots = &targets[addr];
@@ -1346,7 +1631,7 @@ mod tests {
         let state = HashedPostState::default();
         let fetched = MultiProofTargets::default();
 
-        let targets = get_proof_targets(&state, &fetched);
+        let targets = get_proof_targets(&state, &fetched, &B256Set::default());
 
         assert!(targets.is_empty());
     }
@@ -1373,13 +1658,40 @@ mod tests {
         fetched_slots.insert(slot1);
         fetched.insert(addr1, fetched_slots);
 
-        let targets = get_proof_targets(&state, &fetched);
+        let targets = get_proof_targets(&state, &fetched, &B256Set::default());
 
         assert!(targets.contains_key(&addr2));
         assert!(!targets[&addr1].contains(&slot1));
         assert!(targets[&addr1].contains(&slot2));
     }
 
+    #[test]
+    fn test_get_proof_targets_wiped_storage() {
+        let mut state = HashedPostState::default();
+        let mut fetched = MultiProofTargets::default();
+
+        let addr1 = B256::random();
+        let addr2 = B256::random();
+        let slot = B256::random();
+
+        // wiped in this update
+        let mut storage = HashedStorage::new(true);
+        storage.storage.insert(slot, U256::from(1));
+        state.storages.insert(addr1, storage);
+
+        // wiped by a previous update, so the account was already fetched
+        let mut storage = HashedStorage::default();
+        storage.storage.insert(slot, U256::ZERO);
+        state.storages.insert(addr2, storage);
+        fetched.insert(addr2, HashSet::default());
+
+        let targets = get_proof_targets(&state, &fetched, &B256Set::from_iter([addr1, addr2]));
+
+        // the account proof of the newly wiped storage is still fetched, but no slots
+        assert!(targets[&addr1].is_empty());
+        assert!(!targets.contains_key(&addr2));
+    }
+
     #[test]
     fn test_get_proof_targets_unmodified_account_with_storage() {
         let mut state = HashedPostState::default();
@@ -1399,7 +1711,7 @@ mod tests {
         assert!(!state.accounts.contains_key(&addr));
         assert!(!fetched.contains_key(&addr));
 
-        let targets = get_proof_targets(&state, &fetched);
+        let targets = get_proof_targets(&state, &fetched, &B256Set::default());
 
         // verify that we still get the storage slots for the unmodified account
         assert!(targets.contains_key(&addr));
@@ -1490,4 +1802,34 @@ mod tests {
             vec![slot2].into_iter().collect::<B256Set>()
         );
     }
+
+    #[test]
+    fn test_access_list_hints() {
+        let (addr1, addr2, addr3) = (Address::random(), Address::random(), Address::random());
+        let (slot1, slot2) = (B256::random(), B256::random());
+        let access_list = AccessList(vec![
+            AccessListItem { address: addr1, storage_keys: vec![slot1, slot2] },
+            AccessListItem { address: addr2, storage_keys: vec![] },
+        ]);
+        let targets = access_list_proof_targets([&access_list]);
+        assert_eq!(
+            targets.get(&keccak256(addr1)),
+            Some(&B256Set::from_iter([keccak256(slot1), keccak256(slot2)]))
+        );
+        assert_eq!(targets.get(&keccak256(addr2)), Some(&B256Set::default()));
+
+        // the block updates addr1 and slot1 as hinted, and addr3 that wasn't hinted
+        let mut hints = AccessListHints::new(targets);
+        let mut update = HashedPostState::default();
+        update.accounts.insert(keccak256(addr1), None);
+        update.accounts.insert(keccak256(addr3), None);
+        update.storages.insert(
+            keccak256(addr1),
+            HashedStorage::from_iter(false, [(keccak256(slot1), U256::from(1))]),
+        );
+        hints.on_state_update(&update);
+
+        // 2 of 4 hinted targets were updated, 2 of 3 updated targets were hinted
+        assert_eq!(hints.hits(), (2, 4, 3));
+    }
 }
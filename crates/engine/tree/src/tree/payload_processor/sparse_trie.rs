@@ -198,7 +198,7 @@ where
         .into_iter()
         .map(|(address, storage)| (address, storage, trie.take_storage_trie(&address)))
         .par_bridge()
-        .map(|(address, storage, storage_trie)| {
+        .map(|(address, mut storage, storage_trie)| {
             let span = trace_span!(target: "engine::root::sparse", "Storage trie", ?address);
             let _enter = span.enter();
             trace!(target: "engine::root::sparse", "Updating storage");
@@ -208,6 +208,8 @@ where
             if storage.wiped {
                 trace!(target: "engine::root::sparse", "Wiping storage");
                 storage_trie.wipe()?;
+                // The wiped trie is empty, so there are no slots to remove.
+                storage.storage.retain(|_, value| !value.is_zero());
             }
             for (slot, value) in storage.storage {
                 let slot_nibbles = Nibbles::unpack(slot);
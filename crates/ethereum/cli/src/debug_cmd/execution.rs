@@ -217,6 +217,7 @@ impl<C: ChainSpecParser<ChainSpec = ChainSpec>> Command<C> {
             reth_node_events::node::handle_events(
                 Some(Box::new(network)),
                 latest_block_number,
+                false,
                 pipeline.events().map(Into::<NodeEvent<N::Primitives>>::into),
             ),
         );
@@ -5,14 +5,15 @@ use clap::{Parser, Subcommand};
 use reth_chainspec::ChainSpec;
 use reth_cli::chainspec::ChainSpecParser;
 use reth_cli_commands::{
-    config_cmd, db, download, dump_genesis, import, import_era, init_cmd, init_state,
+    config_cmd, db, download, dump_genesis, engine, exex, import, import_era, init_cmd, init_state,
     launcher::FnLauncher,
     node::{self, NoArgs},
-    p2p, prune, recover, stage,
+    p2p, prune, recover, snapshot, stage, verify_execution,
 };
 use reth_cli_runner::CliRunner;
 use reth_db::DatabaseEnv;
-use reth_node_builder::{NodeBuilder, WithLaunchContext};
+use reth_ethereum_consensus::{Ethash, EthashConfig};
+use reth_node_builder::{shutdown::GRACEFUL_SHUTDOWN_TIMEOUT, NodeBuilder, WithLaunchContext};
 use reth_node_core::{
     args::LogArgs,
     version::{LONG_VERSION, SHORT_VERSION},
@@ -151,9 +152,11 @@ impl<C: ChainSpecParser<ChainSpec = ChainSpec>, Ext: clap::Args + fmt::Debug> Cl
             (EthEvmConfig::ethereum(spec.clone()), EthBeaconConsensus::new(spec))
         };
         match self.command {
-            Commands::Node(command) => runner.run_command_until_exit(|ctx| {
-                command.execute(ctx, FnLauncher::new::<C, Ext>(launcher))
-            }),
+            Commands::Node(command) => runner
+                .with_graceful_shutdown_timeout(GRACEFUL_SHUTDOWN_TIMEOUT)
+                .run_command_until_exit(|ctx| {
+                    command.execute(ctx, FnLauncher::new::<C, Ext>(launcher))
+                }),
             Commands::Init(command) => {
                 runner.run_blocking_until_ctrl_c(command.execute::<EthereumNode>())
             }
@@ -161,7 +164,23 @@ impl<C: ChainSpecParser<ChainSpec = ChainSpec>, Ext: clap::Args + fmt::Debug> Cl
                 runner.run_blocking_until_ctrl_c(command.execute::<EthereumNode>())
             }
             Commands::Import(command) => {
-                runner.run_blocking_until_ctrl_c(command.execute::<EthereumNode, _>(components))
+                let ethash_args = command.ethash_args();
+                let ethash = ethash_args.verify.then(|| {
+                    Arc::new(Ethash::new(EthashConfig {
+                        cache_dir: ethash_args.cache_dir.clone(),
+                        dataset_dir: ethash_args.dataset_dir.clone(),
+                    }))
+                });
+                runner.run_blocking_until_ctrl_c(command.execute::<EthereumNode, _>(
+                    |spec: Arc<ChainSpec>| {
+                        let consensus = EthBeaconConsensus::new(spec.clone());
+                        let consensus = match ethash {
+                            Some(ethash) => consensus.with_ethash(ethash),
+                            None => consensus,
+                        };
+                        (EthEvmConfig::ethereum(spec), consensus)
+                    },
+                ))
             }
             Commands::ImportEra(command) => {
                 runner.run_blocking_until_ctrl_c(command.execute::<EthereumNode>())
@@ -173,12 +192,17 @@ impl<C: ChainSpecParser<ChainSpec = ChainSpec>, Ext: clap::Args + fmt::Debug> Cl
             Commands::Download(command) => {
                 runner.run_blocking_until_ctrl_c(command.execute::<EthereumNode>())
             }
+            Commands::ExEx(command) => {
+                runner.run_blocking_until_ctrl_c(command.execute::<EthereumNode>())
+            }
+            Commands::Snapshot(command) => runner.run_blocking_until_ctrl_c(command.execute()),
             Commands::Stage(command) => runner
                 .run_command_until_exit(|ctx| command.execute::<EthereumNode, _>(ctx, components)),
             Commands::P2P(command) => runner.run_until_ctrl_c(command.execute::<EthereumNode>()),
             #[cfg(feature = "dev")]
             Commands::TestVectors(command) => runner.run_until_ctrl_c(command.execute()),
             Commands::Config(command) => runner.run_until_ctrl_c(command.execute()),
+            Commands::Engine(command) => runner.run_until_ctrl_c(command.execute()),
             Commands::Debug(command) => {
                 runner.run_command_until_exit(|ctx| command.execute::<EthereumNode>(ctx))
             }
@@ -186,6 +210,9 @@ impl<C: ChainSpecParser<ChainSpec = ChainSpec>, Ext: clap::Args + fmt::Debug> Cl
                 runner.run_command_until_exit(|ctx| command.execute::<EthereumNode>(ctx))
             }
             Commands::Prune(command) => runner.run_until_ctrl_c(command.execute::<EthereumNode>()),
+            Commands::VerifyExecution(command) => {
+                runner.run_blocking_until_ctrl_c(command.execute::<EthereumNode, _, _>(components))
+            }
         }
     }
 
@@ -226,6 +253,12 @@ pub enum Commands<C: ChainSpecParser, Ext: clap::Args + fmt::Debug> {
     /// Download public node snapshots
     #[command(name = "download")]
     Download(download::DownloadCommand<C>),
+    /// Inspect and repair ExEx state
+    #[command(name = "exex")]
+    ExEx(exex::Command<C>),
+    /// Create, restore, publish and fetch datadir snapshots
+    #[command(name = "snapshot")]
+    Snapshot(snapshot::Command<C>),
     /// Manipulate individual stages.
     #[command(name = "stage")]
     Stage(stage::Command<C>),
@@ -239,6 +272,9 @@ pub enum Commands<C: ChainSpecParser, Ext: clap::Args + fmt::Debug> {
     /// Write config to stdout
     #[command(name = "config")]
     Config(config_cmd::Command),
+    /// Engine API debugging utilities
+    #[command(name = "engine")]
+    Engine(engine::Command),
     /// Various debug routines
     #[command(name = "debug")]
     Debug(Box<debug_cmd::Command<C>>),
@@ -248,6 +284,9 @@ pub enum Commands<C: ChainSpecParser, Ext: clap::Args + fmt::Debug> {
     /// Prune according to the configuration without any limits
     #[command(name = "prune")]
     Prune(prune::PruneCommand<C>),
+    /// Re-execute a range of blocks and verify the results against the stored receipts and state
+    #[command(name = "verify-execution")]
+    VerifyExecution(verify_execution::Command<C>),
 }
 
 impl<C: ChainSpecParser, Ext: clap::Args + fmt::Debug> Commands<C, Ext> {
@@ -262,14 +301,18 @@ impl<C: ChainSpecParser, Ext: clap::Args + fmt::Debug> Commands<C, Ext> {
             Self::DumpGenesis(cmd) => cmd.chain_spec(),
             Self::Db(cmd) => cmd.chain_spec(),
             Self::Download(cmd) => cmd.chain_spec(),
+            Self::ExEx(cmd) => cmd.chain_spec(),
+            Self::Snapshot(cmd) => cmd.chain_spec(),
             Self::Stage(cmd) => cmd.chain_spec(),
             Self::P2P(cmd) => cmd.chain_spec(),
             #[cfg(feature = "dev")]
             Self::TestVectors(_) => None,
             Self::Config(_) => None,
+            Self::Engine(_) => None,
             Self::Debug(cmd) => cmd.chain_spec(),
             Self::Recover(cmd) => cmd.chain_spec(),
             Self::Prune(cmd) => cmd.chain_spec(),
+            Self::VerifyExecution(cmd) => cmd.chain_spec(),
         }
     }
 }
@@ -0,0 +1,481 @@
+//! Ethash proof-of-work seal verification for pre-merge headers.
+//!
+//! See the [ethash specification](https://ethereum.org/en/developers/docs/consensus-mechanisms/pow/mining/mining-algorithms/ethash/)
+//! for details of the algorithm.
+
+use alloy_primitives::{keccak256, B256, U256};
+use alloy_rlp::Encodable;
+use parking_lot::Mutex;
+use reth_consensus::ConsensusError;
+use reth_primitives_traits::{BlockHeader, GotExpected};
+use sha3::{Digest, Keccak512};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tracing::{debug, warn};
+
+/// Number of blocks per ethash epoch.
+pub const EPOCH_LENGTH: u64 = 30_000;
+
+const WORD_BYTES: usize = 4;
+const HASH_BYTES: usize = 64;
+const MIX_BYTES: usize = 128;
+const DATASET_BYTES_INIT: u64 = 1 << 30;
+const DATASET_BYTES_GROWTH: u64 = 1 << 23;
+const CACHE_BYTES_INIT: u64 = 1 << 24;
+const CACHE_BYTES_GROWTH: u64 = 1 << 17;
+const DATASET_PARENTS: u32 = 256;
+const CACHE_ROUNDS: usize = 3;
+const ACCESSES: usize = 64;
+const FNV_PRIME: u32 = 0x01000193;
+
+/// Number of epochs that are kept in memory.
+const MAX_EPOCHS: usize = 2;
+
+/// A 64 byte node of the ethash cache or dataset.
+type Node = [u8; HASH_BYTES];
+
+/// Configuration of the [`Ethash`] seal verifier.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EthashConfig {
+    /// Directory the verification caches are persisted in, so that they are only generated once
+    /// per epoch.
+    pub cache_dir: Option<PathBuf>,
+    /// Directory the full datasets are persisted in.
+    ///
+    /// If set, seals are verified against the full dataset of each epoch instead of deriving the
+    /// accessed dataset items from the cache. Generating a dataset is expensive, but makes
+    /// verifying many headers of the same epoch considerably faster.
+    pub dataset_dir: Option<PathBuf>,
+}
+
+/// Verifies the ethash seals of pre-merge headers.
+#[derive(Debug, Default)]
+pub struct Ethash {
+    config: EthashConfig,
+    /// Recently used epochs, most recently used last.
+    epochs: Mutex<Vec<Arc<Epoch>>>,
+}
+
+impl Ethash {
+    /// Creates a new verifier with the given configuration.
+    pub fn new(config: EthashConfig) -> Self {
+        Self { config, epochs: Default::default() }
+    }
+
+    /// Verifies that the mix hash and nonce of the header are a valid ethash seal for its
+    /// difficulty.
+    pub fn verify_seal<H: BlockHeader>(&self, header: &H) -> Result<(), ConsensusError> {
+        let difficulty = header.difficulty();
+        if difficulty.is_zero() {
+            return Err(ConsensusError::Other("proof-of-work header has zero difficulty".into()))
+        }
+        let Some(nonce) = header.nonce() else {
+            return Err(ConsensusError::Other("proof-of-work header has no nonce".into()))
+        };
+        let Some(mix_hash) = header.mix_hash() else {
+            return Err(ConsensusError::Other("proof-of-work header has no mix hash".into()))
+        };
+
+        let epoch = self.epoch(header.number() / EPOCH_LENGTH);
+        let (mix_digest, pow) = epoch.hashimoto(seal_hash(header), u64::from_be_bytes(nonce.0));
+
+        if mix_digest != mix_hash {
+            return Err(ConsensusError::InvalidMixHash(
+                GotExpected { got: mix_hash, expected: mix_digest }.into(),
+            ))
+        }
+        if U256::from_be_bytes(pow.0) > boundary(difficulty) {
+            return Err(ConsensusError::InsufficientProofOfWork { pow, difficulty })
+        }
+
+        Ok(())
+    }
+
+    /// Returns the cache and dataset of the given epoch, loading or generating them if necessary.
+    fn epoch(&self, number: u64) -> Arc<Epoch> {
+        let mut epochs = self.epochs.lock();
+        if let Some(index) = epochs.iter().position(|epoch| epoch.number == number) {
+            let epoch = epochs.remove(index);
+            epochs.push(epoch.clone());
+            return epoch
+        }
+
+        let epoch = Arc::new(Epoch::new(number, &self.config));
+        if epochs.len() == MAX_EPOCHS {
+            epochs.remove(0);
+        }
+        epochs.push(epoch.clone());
+        epoch
+    }
+}
+
+/// The verification data of a single epoch.
+#[derive(Debug)]
+struct Epoch {
+    number: u64,
+    cache: Vec<Node>,
+    /// The full dataset, if configured.
+    dataset: Option<Vec<Node>>,
+    /// Size of the full dataset in bytes.
+    dataset_size: usize,
+}
+
+impl Epoch {
+    fn new(number: u64, config: &EthashConfig) -> Self {
+        let seed = seed_hash(number);
+        let cache_size = cache_size(number) as usize;
+        let cache =
+            load_or_generate(config.cache_dir.as_deref(), "cache", number, cache_size, || {
+                make_cache(cache_size, seed)
+            });
+
+        let dataset_size = dataset_size(number) as usize;
+        let dataset = config.dataset_dir.as_deref().map(|dir| {
+            load_or_generate(Some(dir), "dataset", number, dataset_size, || {
+                make_dataset(&cache, dataset_size)
+            })
+        });
+
+        Self { number, cache, dataset, dataset_size }
+    }
+
+    /// Returns the mix digest and the proof-of-work result for the seal hash and nonce.
+    fn hashimoto(&self, seal_hash: B256, nonce: u64) -> (B256, B256) {
+        match &self.dataset {
+            Some(dataset) => hashimoto(seal_hash, nonce, self.dataset_size, |i| dataset[i]),
+            None => hashimoto(seal_hash, nonce, self.dataset_size, |i| {
+                calc_dataset_item(&self.cache, i)
+            }),
+        }
+    }
+}
+
+/// Loads the nodes of an epoch from the directory, or generates and persists them.
+///
+/// Persisting is best effort, failing to read or write the file only results in regenerating it.
+fn load_or_generate(
+    dir: Option<&Path>,
+    kind: &str,
+    epoch: u64,
+    size: usize,
+    generate: impl FnOnce() -> Vec<Node>,
+) -> Vec<Node> {
+    let Some(dir) = dir else { return generate() };
+    let path = dir.join(format!("{kind}-{epoch}"));
+
+    match std::fs::read(&path) {
+        Ok(bytes) if bytes.len() == size => {
+            debug!(target: "consensus::ethash", ?path, "Loaded ethash {kind}");
+            return bytes
+                .chunks_exact(HASH_BYTES)
+                .map(|chunk| chunk.try_into().expect("chunk is HASH_BYTES long"))
+                .collect()
+        }
+        Ok(_) => warn!(target: "consensus::ethash", ?path, "Ethash {kind} has invalid size"),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+        Err(err) => warn!(target: "consensus::ethash", ?path, %err, "Failed to read ethash {kind}"),
+    }
+
+    debug!(target: "consensus::ethash", epoch, size, "Generating ethash {kind}");
+    let nodes = generate();
+    let result = reth_fs_util::create_dir_all(dir).and_then(|_| {
+        reth_fs_util::atomic_write_file(&path, |file| {
+            std::io::Write::write_all(file, nodes.as_flattened())
+        })
+    });
+    if let Err(err) = result {
+        warn!(target: "consensus::ethash", ?path, %err, "Failed to persist ethash {kind}");
+    }
+    nodes
+}
+
+/// Returns the hash of the header without the mix hash and nonce, which is the input of the
+/// proof-of-work.
+pub fn seal_hash<H: BlockHeader>(header: &H) -> B256 {
+    let logs_bloom = header.logs_bloom();
+    let difficulty = header.difficulty();
+    let number = header.number();
+    let gas_limit = header.gas_limit();
+    let gas_used = header.gas_used();
+    let timestamp = header.timestamp();
+    let base_fee = header.base_fee_per_gas();
+
+    let fields: [&dyn Encodable; 13] = [
+        &header.parent_hash(),
+        &header.ommers_hash(),
+        &header.beneficiary(),
+        &header.state_root(),
+        &header.transactions_root(),
+        &header.receipts_root(),
+        &logs_bloom,
+        &difficulty,
+        &number,
+        &gas_limit,
+        &gas_used,
+        &timestamp,
+        header.extra_data(),
+    ];
+    let payload_length = fields.iter().map(|field| field.length()).sum::<usize>() +
+        base_fee.as_ref().map_or(0, Encodable::length);
+
+    let mut out = Vec::with_capacity(payload_length + 4);
+    alloy_rlp::Header { list: true, payload_length }.encode(&mut out);
+    for field in fields {
+        field.encode(&mut out);
+    }
+    if let Some(base_fee) = base_fee {
+        base_fee.encode(&mut out);
+    }
+    keccak256(out)
+}
+
+/// Returns the highest proof-of-work result that satisfies the difficulty, `2^256 / difficulty`.
+fn boundary(difficulty: U256) -> U256 {
+    if difficulty <= U256::from(1) {
+        return U256::MAX
+    }
+    // floor(2^256 / d) = floor((2^256 - 1) / d), plus one if d divides 2^256
+    let (quotient, remainder) = U256::MAX.div_rem(difficulty);
+    if remainder == difficulty - U256::from(1) {
+        quotient + U256::from(1)
+    } else {
+        quotient
+    }
+}
+
+/// Returns the size of the verification cache of the epoch in bytes.
+pub fn cache_size(epoch: u64) -> u64 {
+    let mut size = CACHE_BYTES_INIT + CACHE_BYTES_GROWTH * epoch - HASH_BYTES as u64;
+    while !is_prime(size / HASH_BYTES as u64) {
+        size -= 2 * HASH_BYTES as u64;
+    }
+    size
+}
+
+/// Returns the size of the full dataset of the epoch in bytes.
+pub fn dataset_size(epoch: u64) -> u64 {
+    let mut size = DATASET_BYTES_INIT + DATASET_BYTES_GROWTH * epoch - MIX_BYTES as u64;
+    while !is_prime(size / MIX_BYTES as u64) {
+        size -= 2 * MIX_BYTES as u64;
+    }
+    size
+}
+
+/// Returns the seed hash of the epoch.
+pub fn seed_hash(epoch: u64) -> B256 {
+    (0..epoch).fold(B256::ZERO, |seed, _| keccak256(seed))
+}
+
+fn is_prime(n: u64) -> bool {
+    n >= 2 && (2..).take_while(|i| i * i <= n).all(|i| n % i != 0)
+}
+
+fn fnv(a: u32, b: u32) -> u32 {
+    a.wrapping_mul(FNV_PRIME) ^ b
+}
+
+fn keccak512(data: &[u8]) -> Node {
+    Keccak512::digest(data).into()
+}
+
+/// Returns the little-endian 32-bit word at the given index of the bytes.
+fn word(bytes: &[u8], index: usize) -> u32 {
+    u32::from_le_bytes(bytes[index * WORD_BYTES..(index + 1) * WORD_BYTES].try_into().unwrap())
+}
+
+fn set_word(bytes: &mut [u8], index: usize, value: u32) {
+    bytes[index * WORD_BYTES..(index + 1) * WORD_BYTES].copy_from_slice(&value.to_le_bytes());
+}
+
+/// Generates the verification cache of the given size from the seed hash.
+fn make_cache(size: usize, seed: B256) -> Vec<Node> {
+    let n = size / HASH_BYTES;
+
+    let mut cache = Vec::with_capacity(n);
+    cache.push(keccak512(seed.as_slice()));
+    for i in 1..n {
+        cache.push(keccak512(&cache[i - 1]));
+    }
+
+    for _ in 0..CACHE_ROUNDS {
+        for i in 0..n {
+            let v = word(&cache[i], 0) as usize % n;
+            let mut node = cache[(i + n - 1) % n];
+            for (byte, other) in node.iter_mut().zip(cache[v]) {
+                *byte ^= other;
+            }
+            cache[i] = keccak512(&node);
+        }
+    }
+
+    cache
+}
+
+/// Derives the item of the full dataset at the given index from the cache.
+fn calc_dataset_item(cache: &[Node], index: usize) -> Node {
+    let n = cache.len();
+    let r = HASH_BYTES / WORD_BYTES;
+
+    let mut mix = cache[index % n];
+    set_word(&mut mix, 0, word(&mix, 0) ^ index as u32);
+    let mut mix = keccak512(&mix);
+
+    for j in 0..DATASET_PARENTS {
+        let parent = fnv(index as u32 ^ j, word(&mix, j as usize % r)) as usize % n;
+        for k in 0..r {
+            set_word(&mut mix, k, fnv(word(&mix, k), word(&cache[parent], k)));
+        }
+    }
+
+    keccak512(&mix)
+}
+
+/// Generates the full dataset of the given size from the cache, using all available cores.
+fn make_dataset(cache: &[Node], size: usize) -> Vec<Node> {
+    let mut dataset = vec![[0; HASH_BYTES]; size / HASH_BYTES];
+    let threads = std::thread::available_parallelism().map_or(1, |threads| threads.get());
+    let chunk_size = dataset.len().div_ceil(threads);
+
+    std::thread::scope(|scope| {
+        for (chunk_index, chunk) in dataset.chunks_mut(chunk_size).enumerate() {
+            scope.spawn(move || {
+                for (i, item) in chunk.iter_mut().enumerate() {
+                    *item = calc_dataset_item(cache, chunk_index * chunk_size + i);
+                }
+            });
+        }
+    });
+
+    dataset
+}
+
+/// Computes the mix digest and proof-of-work result, looking up dataset items with `lookup`.
+fn hashimoto(
+    seal_hash: B256,
+    nonce: u64,
+    dataset_size: usize,
+    lookup: impl Fn(usize) -> Node,
+) -> (B256, B256) {
+    let n = dataset_size / HASH_BYTES;
+    let w = MIX_BYTES / WORD_BYTES;
+    let mix_hashes = MIX_BYTES / HASH_BYTES;
+
+    let mut seed_input = [0; 40];
+    seed_input[..32].copy_from_slice(seal_hash.as_slice());
+    seed_input[32..].copy_from_slice(&nonce.to_le_bytes());
+    let seed = keccak512(&seed_input);
+    let seed_head = word(&seed, 0);
+
+    let mut mix = [0; MIX_BYTES];
+    for chunk in mix.chunks_exact_mut(HASH_BYTES) {
+        chunk.copy_from_slice(&seed);
+    }
+
+    for i in 0..ACCESSES {
+        let p =
+            fnv(i as u32 ^ seed_head, word(&mix, i % w)) as usize % (n / mix_hashes) * mix_hashes;
+        for j in 0..mix_hashes {
+            let item = lookup(p + j);
+            for k in 0..HASH_BYTES / WORD_BYTES {
+                let index = j * (HASH_BYTES / WORD_BYTES) + k;
+                set_word(&mut mix, index, fnv(word(&mix, index), word(&item, k)));
+            }
+        }
+    }
+
+    let mut mix_digest = B256::ZERO;
+    for i in 0..w / 4 {
+        let compressed = fnv(
+            fnv(fnv(word(&mix, 4 * i), word(&mix, 4 * i + 1)), word(&mix, 4 * i + 2)),
+            word(&mix, 4 * i + 3),
+        );
+        set_word(mix_digest.as_mut_slice(), i, compressed);
+    }
+
+    let mut result_input = [0; HASH_BYTES + 32];
+    result_input[..HASH_BYTES].copy_from_slice(&seed);
+    result_input[HASH_BYTES..].copy_from_slice(mix_digest.as_slice());
+
+    (mix_digest, keccak256(result_input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Header;
+    use alloy_primitives::{address, b256, bytes, B64};
+
+    #[test]
+    fn epoch_parameters() {
+        assert_eq!(cache_size(0), 16776896);
+        assert_eq!(cache_size(1), 16907456);
+        assert_eq!(dataset_size(0), 1073739904);
+        assert_eq!(dataset_size(1), 1082130304);
+
+        assert_eq!(seed_hash(0), B256::ZERO);
+        assert_eq!(
+            seed_hash(1),
+            b256!("0x290decd9548b62a8d60345a988386fc84ba6bc95484008f6362f93160ef3e563")
+        );
+    }
+
+    #[test]
+    fn boundary_of_difficulty() {
+        assert_eq!(boundary(U256::from(1)), U256::MAX);
+        assert_eq!(boundary(U256::from(2)), U256::from(1) << 255);
+        assert_eq!(boundary(U256::from(3)), U256::MAX / U256::from(3));
+    }
+
+    #[test]
+    fn full_and_light_hashimoto_agree() {
+        let cache = make_cache(1024 * HASH_BYTES, seed_hash(1));
+        let dataset_size = 4096 * HASH_BYTES;
+        let dataset = make_dataset(&cache, dataset_size);
+
+        let seal_hash = keccak256("header");
+        let light = hashimoto(seal_hash, 42, dataset_size, |i| calc_dataset_item(&cache, i));
+        let full = hashimoto(seal_hash, 42, dataset_size, |i| dataset[i]);
+        assert_eq!(light, full);
+        assert_ne!(light, hashimoto(seal_hash, 43, dataset_size, |i| dataset[i]));
+    }
+
+    #[test]
+    fn mainnet_block_one_seal() {
+        let mut header = Header {
+            parent_hash: b256!(
+                "0xd4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3"
+            ),
+            ommers_hash: b256!(
+                "0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347"
+            ),
+            beneficiary: address!("0x05a56e2d52c817161883f50c441c3228cfe54d9f"),
+            state_root: b256!("0xd67e4d450343046425ae4271474353857ab860dbc0a1dde64b41b5cd3a532bf3"),
+            transactions_root: b256!(
+                "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421"
+            ),
+            receipts_root: b256!(
+                "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421"
+            ),
+            difficulty: U256::from(0x3ff800000u64),
+            number: 1,
+            gas_limit: 5000,
+            timestamp: 1438269988,
+            extra_data: bytes!("0x476574682f76312e302e302f6c696e75782f676f312e342e32"),
+            mix_hash: b256!("0x969b900de27b6ac6a67742365dd65f55a0526c41fd18e1b16f1a1215c2e66f59"),
+            nonce: B64::from(0x539bd4979fef1ec4u64),
+            ..Default::default()
+        };
+        assert_eq!(
+            header.hash_slow(),
+            b256!("0x88e96d4537bea4d9c05d12549907b32561d3bf31f45aae734cdc119f13406cb6")
+        );
+
+        let ethash = Ethash::default();
+        assert_eq!(ethash.verify_seal(&header), Ok(()));
+
+        header.nonce = B64::from(0x539bd4979fef1ec5u64);
+        assert!(matches!(ethash.verify_seal(&header), Err(ConsensusError::InvalidMixHash(_))));
+    }
+}
@@ -31,6 +31,11 @@ use reth_primitives_traits::{
 mod validation;
 pub use validation::validate_block_post_execution;
 
+#[cfg(feature = "ethash")]
+pub mod ethash;
+#[cfg(feature = "ethash")]
+pub use ethash::{Ethash, EthashConfig};
+
 /// Ethereum beacon consensus
 ///
 /// This consensus engine does basic checks as outlined in the execution specs.
@@ -38,12 +43,28 @@ pub use validation::validate_block_post_execution;
 pub struct EthBeaconConsensus<ChainSpec> {
     /// Configuration
     chain_spec: Arc<ChainSpec>,
+    /// Verifier of the proof-of-work seals of pre-merge headers, if enabled.
+    #[cfg(feature = "ethash")]
+    ethash: Option<Arc<Ethash>>,
 }
 
 impl<ChainSpec: EthChainSpec + EthereumHardforks> EthBeaconConsensus<ChainSpec> {
     /// Create a new instance of [`EthBeaconConsensus`]
     pub const fn new(chain_spec: Arc<ChainSpec>) -> Self {
-        Self { chain_spec }
+        Self {
+            chain_spec,
+            #[cfg(feature = "ethash")]
+            ethash: None,
+        }
+    }
+
+    /// Enables verification of the ethash seals of pre-merge headers.
+    ///
+    /// Without it, the proof-of-work of pre-merge headers is trusted.
+    #[cfg(feature = "ethash")]
+    pub fn with_ethash(mut self, ethash: Arc<Ethash>) -> Self {
+        self.ethash = Some(ethash);
+        self
     }
 
     /// Checks the gas limit for consistency between parent and self headers.
@@ -152,6 +173,11 @@ where
                 return Err(ConsensusError::TheMergeOmmerRootIsNotEmpty);
             }
         } else {
+            #[cfg(feature = "ethash")]
+            if let Some(ethash) = &self.ethash {
+                ethash.verify_seal(header)?;
+            }
+
             #[cfg(feature = "std")]
             {
                 let present_timestamp = std::time::SystemTime::now()
@@ -279,6 +279,7 @@ where
 mod tests {
     use super::*;
     use alloy_consensus::Header;
+    use alloy_eips::eip7892::BlobScheduleBlobParams;
     use alloy_genesis::Genesis;
     use reth_chainspec::{Chain, ChainSpec};
     use reth_evm::{execute::ProviderError, EvmEnv};
@@ -314,6 +315,32 @@ mod tests {
         assert_eq!(cfg_env.chain_id, chain_spec.chain().id());
     }
 
+    #[test]
+    fn test_evm_env_custom_blob_schedule() {
+        let blob_params = BlobParams {
+            target_blob_count: 24,
+            max_blob_count: 32,
+            max_blobs_per_tx: 12,
+            ..BlobParams::prague()
+        };
+        let chain_spec = ChainSpec::builder()
+            .chain(Chain::mainnet())
+            .genesis(Genesis::default())
+            .prague_activated()
+            .blob_params(BlobScheduleBlobParams { prague: blob_params, ..Default::default() })
+            .build();
+
+        let header = Header { excess_blob_gas: Some(10_000_000), ..Default::default() };
+        let EvmEnv { cfg_env, block_env } =
+            EthEvmConfig::new(Arc::new(chain_spec)).evm_env(&header);
+
+        assert_eq!(cfg_env.max_blobs_per_tx, Some(12));
+        assert_eq!(
+            block_env.blob_excess_gas_and_price.unwrap().blob_gasprice,
+            blob_params.calc_blob_fee(10_000_000)
+        );
+    }
+
     #[test]
     fn test_evm_with_env_default_spec() {
         let evm_config = EthEvmConfig::mainnet();
@@ -34,8 +34,14 @@ use reth_node_builder::{
 };
 use reth_provider::{providers::ProviderFactoryBuilder, EthStorage};
 use reth_rpc::{eth::core::EthApiFor, ValidationApi};
-use reth_rpc_api::{eth::FullEthApiServer, servers::BlockSubmissionValidationApiServer};
-use reth_rpc_builder::{config::RethRpcServerConfig, middleware::RethRpcMiddleware};
+use reth_rpc_api::{
+    eth::FullEthApiServer,
+    servers::{BlockSubmissionValidationApiServer, RethPayloadValidationApiServer},
+};
+use reth_rpc_builder::{
+    config::RethRpcServerConfig,
+    middleware::{RethHttpMiddleware, RethRpcMiddleware},
+};
 use reth_rpc_eth_types::{error::FromEvmError, EthApiError};
 use reth_rpc_server_types::RethRpcModule;
 use reth_tracing::tracing::{debug, info};
@@ -151,6 +157,7 @@ where
         .gas_cap(ctx.config.rpc_gas_cap.into())
         .max_simulate_blocks(ctx.config.rpc_max_simulate_blocks)
         .eth_proof_window(ctx.config.eth_proof_window)
+        .max_proof_response_size(ctx.config.max_proof_response_size)
         .fee_history_cache_config(ctx.config.fee_history_cache)
         .proof_permits(ctx.config.proof_permits)
         .gas_oracle_config(ctx.config.gas_oracle)
@@ -225,6 +232,17 @@ where
         let Self { inner } = self;
         EthereumAddOns { inner: inner.with_rpc_middleware(rpc_middleware) }
     }
+
+    /// Adds a new Http middleware layer around the HTTP and WS transports of the RPC server.
+    ///
+    /// See also [`RpcAddOns::layer_http_middleware`].
+    pub fn layer_http_middleware<T>(self, layer: T) -> Self
+    where
+        T: RethHttpMiddleware,
+    {
+        let Self { inner } = self;
+        Self { inner: inner.layer_http_middleware(layer) }
+    }
 }
 
 impl<N, EthB, EV, EB, RpcMiddleware> NodeAddOns<N>
@@ -262,9 +280,13 @@ where
 
         self.inner
             .launch_add_ons_with(ctx, move |container| {
+                container.modules.merge_if_module_configured(
+                    RethRpcModule::Reth,
+                    RethPayloadValidationApiServer::into_rpc(validation_api.clone()),
+                )?;
                 container.modules.merge_if_module_configured(
                     RethRpcModule::Flashbots,
-                    validation_api.into_rpc(),
+                    BlockSubmissionValidationApiServer::into_rpc(validation_api),
                 )?;
 
                 Ok(())
@@ -3,16 +3,20 @@ use alloy_genesis::Genesis;
 use alloy_primitives::{b256, hex};
 use futures::StreamExt;
 use reth_chainspec::ChainSpec;
+use reth_engine_primitives::TreeConfig;
 use reth_node_api::{BlockBody, FullNodeComponents, FullNodePrimitives, NodeTypes};
 use reth_node_builder::{
-    rpc::RethRpcAddOns, EngineNodeLauncher, FullNode, NodeBuilder, NodeConfig, NodeHandle,
+    rpc::RethRpcAddOns, shutdown::GRACEFUL_SHUTDOWN_TIMEOUT, EngineNodeLauncher, FullNode,
+    NodeBuilder, NodeConfig, NodeHandle,
 };
 use reth_node_core::args::DevArgs;
 use reth_node_ethereum::{node::EthereumAddOns, EthereumNode};
-use reth_provider::{providers::BlockchainProvider, CanonStateSubscriptions};
+use reth_provider::{
+    providers::BlockchainProvider, BlockNumReader, CanonStateSubscriptions, DatabaseProviderFactory,
+};
 use reth_rpc_eth_api::helpers::EthTransactions;
 use reth_tasks::TaskManager;
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 #[tokio::test]
 async fn can_run_dev_node() -> eyre::Result<()> {
@@ -43,6 +47,55 @@ async fn can_run_dev_node() -> eyre::Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn dev_node_persists_in_memory_blocks_on_shutdown() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+    let tasks = TaskManager::current();
+    let exec = tasks.executor();
+
+    let node_config = NodeConfig::test().with_chain(custom_chain()).with_dev(DevArgs {
+        dev: true,
+        block_time: Some(Duration::from_millis(100)),
+        ..Default::default()
+    });
+    let NodeHandle { node, .. } = NodeBuilder::new(node_config.clone())
+        .testing_node(exec.clone())
+        .with_types_and_provider::<EthereumNode, BlockchainProvider<_>>()
+        .with_components(EthereumNode::components())
+        .with_add_ons(EthereumAddOns::default())
+        .launch_with_fn(|builder| {
+            // keep all blocks in memory until the node shuts down
+            let launcher = EngineNodeLauncher::new(
+                builder.task_executor().clone(),
+                builder.config().datadir(),
+                TreeConfig::default().with_persistence_threshold(u64::MAX),
+            );
+            builder.launch_with(launcher)
+        })
+        .await?;
+
+    let mut notifications = node.provider.canonical_state_stream();
+    let mut head = 0;
+    while head < 3 {
+        head = notifications.next().await.unwrap().tip().num_hash().number;
+    }
+    assert_eq!(node.provider.database_provider_ro()?.last_block_number()?, 0);
+
+    // the executor's shutdown signal starts the ordered shutdown of the node components
+    assert!(
+        tokio::task::spawn_blocking(move || {
+            tasks.graceful_shutdown_with_timeout(GRACEFUL_SHUTDOWN_TIMEOUT)
+        })
+        .await?
+    );
+
+    let canonical_head = node.provider.canonical_in_memory_state().get_canonical_block_number();
+    assert!(canonical_head >= head);
+    assert_eq!(node.provider.database_provider_ro()?.last_block_number()?, canonical_head);
+
+    Ok(())
+}
+
 async fn assert_chain_advances<N, AddOns>(node: FullNode<N, AddOns>)
 where
     N: FullNodeComponents<Provider: CanonStateSubscriptions>,
@@ -9,6 +9,8 @@ pub struct EthereumBuilderConfig {
     /// Waits for the first payload to be built if there is no payload built when the payload is
     /// being resolved.
     pub await_payload_on_missing: bool,
+    /// Maximum number of blobs per block, capped by the protocol's maximum.
+    pub max_blobs_per_block: Option<u64>,
 }
 
 impl Default for EthereumBuilderConfig {
@@ -20,7 +22,11 @@ impl Default for EthereumBuilderConfig {
 impl EthereumBuilderConfig {
     /// Create new payload builder config.
     pub const fn new() -> Self {
-        Self { desired_gas_limit: ETHEREUM_BLOCK_GAS_LIMIT_30M, await_payload_on_missing: true }
+        Self {
+            desired_gas_limit: ETHEREUM_BLOCK_GAS_LIMIT_30M,
+            await_payload_on_missing: true,
+            max_blobs_per_block: None,
+        }
     }
 
     /// Set desired gas limit.
@@ -35,6 +41,12 @@ impl EthereumBuilderConfig {
         self.await_payload_on_missing = await_payload_on_missing;
         self
     }
+
+    /// Set the maximum number of blobs per block.
+    pub const fn with_max_blobs_per_block(mut self, max_blobs_per_block: Option<u64>) -> Self {
+        self.max_blobs_per_block = max_blobs_per_block;
+        self
+    }
 }
 
 impl EthereumBuilderConfig {
@@ -43,6 +55,12 @@ impl EthereumBuilderConfig {
     pub fn gas_limit(&self, parent_gas_limit: u64) -> u64 {
         calculate_block_gas_limit(parent_gas_limit, self.desired_gas_limit)
     }
+
+    /// Returns the number of blobs to include at most, given the protocol's maximum blob count.
+    pub fn max_blob_count(&self, protocol_max_blob_count: u64) -> u64 {
+        self.max_blobs_per_block
+            .map_or(protocol_max_blob_count, |max| max.min(protocol_max_blob_count))
+    }
 }
 
 /// Calculate the gas limit for the next block based on parent and desired gas limits.
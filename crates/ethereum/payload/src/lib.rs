@@ -25,7 +25,7 @@ use reth_evm::{
 use reth_evm_ethereum::EthEvmConfig;
 use reth_payload_builder::{BlobSidecars, EthBuiltPayload, EthPayloadBuilderAttributes};
 use reth_payload_builder_primitives::PayloadBuilderError;
-use reth_payload_primitives::PayloadBuilderAttributes;
+use reth_payload_primitives::{PayloadBuilderAttributes, PayloadTimings};
 use reth_primitives_traits::transaction::error::InvalidTransactionError;
 use reth_revm::{database::StateProviderDatabase, db::State};
 use reth_storage_api::StateProviderFactory;
@@ -35,7 +35,10 @@ use reth_transaction_pool::{
     ValidPoolTransaction,
 };
 use revm::context_interface::Block as _;
-use std::sync::Arc;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use tracing::{debug, trace, warn};
 
 mod config;
@@ -195,9 +198,13 @@ where
     let mut block_blob_count = 0;
 
     let blob_params = chain_spec.blob_params_at_timestamp(attributes.timestamp);
-    let max_blob_count =
-        blob_params.as_ref().map(|params| params.max_blob_count).unwrap_or_default();
+    let max_blob_count = builder_config.max_blob_count(
+        blob_params.as_ref().map(|params| params.max_blob_count).unwrap_or_default(),
+    );
 
+    // everything in the loop that isn't execution is attributed to transaction selection
+    let selection_start = Instant::now();
+    let mut execution = Duration::ZERO;
     while let Some(pool_tx) = best_txs.next() {
         // ensure we still have capacity for this transaction
         if cumulative_gas_used + pool_tx.gas_limit() > block_gas_limit {
@@ -225,6 +232,27 @@ where
         if let Some(blob_tx) = tx.as_eip4844() {
             let tx_blob_count = blob_tx.tx().blob_versioned_hashes.len() as u64;
 
+            // the pool validates against the blob parameters of its tip, which can be stricter or
+            // looser than those of this payload if it is the first block after a blob parameter
+            // fork, so we need to re-check the per transaction limit here
+            if let Some(max_blobs_per_tx) =
+                blob_params.as_ref().map(|params| params.max_blobs_per_tx)
+            {
+                if tx_blob_count > max_blobs_per_tx {
+                    trace!(target: "payload_builder", tx=?tx.hash(), ?tx_blob_count, "skipping blob transaction because it exceeds the max blob count per transaction");
+                    best_txs.mark_invalid(
+                        &pool_tx,
+                        InvalidPoolTransactionError::Eip4844(
+                            Eip4844PoolTransactionError::TooManyEip4844Blobs {
+                                have: tx_blob_count,
+                                permitted: max_blobs_per_tx,
+                            },
+                        ),
+                    );
+                    continue
+                }
+            }
+
             if block_blob_count + tx_blob_count > max_blob_count {
                 // we can't fit this _blob_ transaction into the block, so we mark it as
                 // invalid, which removes its dependent transactions from
@@ -272,7 +300,10 @@ where
             };
         }
 
-        let gas_used = match builder.execute_transaction(tx.clone()) {
+        let execution_start = Instant::now();
+        let result = builder.execute_transaction(tx.clone());
+        execution += execution_start.elapsed();
+        let gas_used = match result {
             Ok(gas_used) => gas_used,
             Err(BlockExecutionError::Validation(BlockValidationError::InvalidTx {
                 error, ..
@@ -318,6 +349,7 @@ where
             blob_sidecars.push_sidecar_variant(sidecar.as_ref().clone());
         }
     }
+    let tx_selection = selection_start.elapsed().saturating_sub(execution);
 
     // check if we have a better block
     if !is_better_payload(best_payload.as_ref(), total_fees) {
@@ -327,18 +359,27 @@ where
         return Ok(BuildOutcome::Aborted { fees: total_fees, cached_reads })
     }
 
-    let BlockBuilderOutcome { execution_result, block, .. } = builder.finish(&state_provider)?;
+    let finish_start = Instant::now();
+    let BlockBuilderOutcome { execution_result, block, state_root_elapsed, .. } =
+        builder.finish(&state_provider)?;
 
     let requests = chain_spec
         .is_prague_active_at_timestamp(attributes.timestamp)
         .then_some(execution_result.requests);
 
     let sealed_block = Arc::new(block.sealed_block().clone());
-    debug!(target: "payload_builder", id=%attributes.id, sealed_block_header = ?sealed_block.sealed_header(), "sealed built block");
+    let timings = PayloadTimings {
+        tx_selection,
+        execution,
+        state_root: state_root_elapsed,
+        sealing: finish_start.elapsed().saturating_sub(state_root_elapsed),
+    };
+    debug!(target: "payload_builder", id=%attributes.id, sealed_block_header = ?sealed_block.sealed_header(), ?timings, "sealed built block");
 
     let payload = EthBuiltPayload::new(attributes.id, sealed_block, total_fees, requests)
         // add blob sidecars from the executed txs
-        .with_sidecars(blob_sidecars);
+        .with_sidecars(blob_sidecars)
+        .with_timings(timings);
 
     Ok(BuildOutcome::Better { payload, cached_reads })
 }
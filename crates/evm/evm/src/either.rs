@@ -73,6 +73,13 @@ where
         }
     }
 
+    fn state(&self) -> Option<&revm::database::State<DB>> {
+        match self {
+            Self::Left(a) => a.state(),
+            Self::Right(b) => b.state(),
+        }
+    }
+
     fn size_hint(&self) -> usize {
         match self {
             Self::Left(a) => a.size_hint(),
@@ -10,7 +10,7 @@ use alloy_evm::{
     Evm, EvmEnv, EvmFactory,
 };
 use alloy_primitives::B256;
-use core::fmt::Debug;
+use core::{fmt::Debug, time::Duration};
 pub use reth_execution_errors::{
     BlockExecutionError, BlockValidationError, InternalBlockExecutionError,
 };
@@ -133,6 +133,14 @@ pub trait Executor<DB: Database>: Sized {
     /// Consumes the executor and returns the [`State`] containing all state changes.
     fn into_state(self) -> State<DB>;
 
+    /// Returns the [`State`] containing all state changes so far, if the executor exposes it.
+    ///
+    /// This is used to save the state of a batch that isn't committed yet. Returns `None` by
+    /// default.
+    fn state(&self) -> Option<&State<DB>> {
+        None
+    }
+
     /// The size hint of the batch's tracked state size.
     ///
     /// This is used to optimize DB commits depending on the size of the state.
@@ -270,6 +278,10 @@ pub struct BlockBuilderOutcome<N: NodePrimitives> {
     pub hashed_state: HashedPostState,
     /// Trie updates collected during state root calculation.
     pub trie_updates: TrieUpdates,
+    /// Time spent calculating the state root.
+    ///
+    /// This is always zero without the `std` feature.
+    pub state_root_elapsed: Duration,
     /// The built block.
     pub block: RecoveredBlock<N::Block>,
 }
@@ -445,10 +457,16 @@ where
         db.merge_transitions(BundleRetention::Reverts);
 
         // calculate the state root
+        #[cfg(feature = "std")]
+        let state_root_start = std::time::Instant::now();
         let hashed_state = state.hashed_post_state(&db.bundle_state);
         let (state_root, trie_updates) = state
             .state_root_with_updates(hashed_state.clone())
             .map_err(BlockExecutionError::other)?;
+        #[cfg(feature = "std")]
+        let state_root_elapsed = state_root_start.elapsed();
+        #[cfg(not(feature = "std"))]
+        let state_root_elapsed = Duration::ZERO;
 
         let (transactions, senders) =
             self.transactions.into_iter().map(|tx| tx.into_parts()).unzip();
@@ -466,7 +484,13 @@ where
 
         let block = RecoveredBlock::new_unhashed(block, senders);
 
-        Ok(BlockBuilderOutcome { execution_result: result, hashed_state, trie_updates, block })
+        Ok(BlockBuilderOutcome {
+            execution_result: result,
+            hashed_state,
+            trie_updates,
+            state_root_elapsed,
+            block,
+        })
     }
 
     fn executor_mut(&mut self) -> &mut Self::Executor {
@@ -547,6 +571,10 @@ where
         self.db
     }
 
+    fn state(&self) -> Option<&State<DB>> {
+        Some(&self.db)
+    }
+
     fn size_hint(&self) -> usize {
         self.db.bundle_state.size_hint()
     }
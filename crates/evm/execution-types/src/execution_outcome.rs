@@ -1,11 +1,21 @@
 use crate::{BlockExecutionOutput, BlockExecutionResult};
 use alloc::{vec, vec::Vec};
 use alloy_eips::eip7685::Requests;
-use alloy_primitives::{logs_bloom, map::HashMap, Address, BlockNumber, Bloom, Log, B256, U256};
+use alloy_primitives::{
+    logs_bloom,
+    map::{HashMap, HashSet},
+    Address, BlockNumber, Bloom, Log, B256, U256,
+};
 use reth_primitives_traits::{Account, Bytecode, Receipt, StorageEntry};
 use reth_trie_common::{HashedPostState, KeyHasher};
 use revm::{
-    database::{states::BundleState, BundleAccount},
+    database::{
+        states::{
+            reverts::{AccountInfoRevert, RevertToSlot},
+            BundleState,
+        },
+        BundleAccount,
+    },
     state::AccountInfo,
 };
 
@@ -352,6 +362,72 @@ impl<T> ExecutionOutcome<T> {
         core::mem::swap(&mut self.bundle, &mut other)
     }
 
+    /// Removes reverts that don't record a change before the outcome is written.
+    ///
+    /// An account revert is redundant if the account info before the block equals the info after
+    /// the block, and a storage revert is redundant if the slot value before the block equals the
+    /// value after the block. Redundant reverts would still be written to the changesets and
+    /// history indices, which is common for blocks that touch the same accounts repeatedly.
+    ///
+    /// Redundant account reverts are replaced with [`AccountInfoRevert::DoNothing`] and redundant
+    /// storage reverts are removed, so reverting the bundle still yields the same state.
+    ///
+    /// Returns the number of removed account and storage reverts.
+    pub fn compact_reverts(&mut self) -> usize {
+        let bundle = &mut self.bundle;
+        // Account infos and storage values after the currently processed block, starting with the
+        // state after the last block.
+        let mut accounts = HashMap::<Address, Option<AccountInfo>>::default();
+        let mut storage = HashMap::<(Address, U256), U256>::default();
+        // Accounts with a storage wipe in a later block, their storage before the wipe is unknown.
+        let mut wiped = HashSet::<Address>::default();
+        let mut removed = 0;
+
+        for block_reverts in bundle.reverts.iter_mut().rev() {
+            for (address, revert) in block_reverts.iter_mut() {
+                let address = *address;
+                let Some(account) = bundle.state.get(&address) else { continue };
+
+                let info_after = accounts.entry(address).or_insert_with(|| account.info.clone());
+                let info_before = match &revert.account {
+                    AccountInfoRevert::DoNothing => None,
+                    AccountInfoRevert::DeleteIt => Some(None),
+                    AccountInfoRevert::RevertTo(info) => Some(Some(info.clone())),
+                };
+                if let Some(info_before) = info_before {
+                    if info_before == *info_after {
+                        revert.account = AccountInfoRevert::DoNothing;
+                        removed += 1;
+                    } else {
+                        *info_after = info_before;
+                    }
+                }
+
+                if !wiped.contains(&address) {
+                    revert.storage.retain(|slot, revert_to| {
+                        let RevertToSlot::Some(before) = *revert_to else { return true };
+                        let after = storage
+                            .get(&(address, *slot))
+                            .copied()
+                            .or_else(|| account.storage.get(slot).map(|value| value.present_value));
+                        if after == Some(before) {
+                            removed += 1;
+                            false
+                        } else {
+                            storage.insert((address, *slot), before);
+                            true
+                        }
+                    });
+                }
+                if revert.wipe_storage {
+                    wiped.insert(address);
+                }
+            }
+        }
+
+        removed
+    }
+
     /// Create a new instance with updated receipts.
     pub fn with_receipts(mut self, receipts: Vec<Vec<T>>) -> Self {
         self.receipts = receipts;
@@ -630,6 +706,44 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_compact_reverts() {
+        let address = Address::new([2; 20]);
+        let info =
+            |balance: u64| AccountInfo { balance: U256::from(balance), ..Default::default() };
+        let slot = |value: u64| U256::from(value);
+
+        let bundle = BundleState::new(
+            vec![(
+                address,
+                Some(info(2)),
+                Some(info(1)),
+                HashMap::from_iter([(slot(1), (slot(5), slot(5))), (slot(2), (slot(3), slot(7)))]),
+            )],
+            vec![
+                // The balance changes from 2 to 1, slot 1 is unchanged.
+                vec![(address, Some(Some(info(2))), vec![(slot(1), slot(5))])],
+                // The balance is unchanged, slot 1 is unchanged and slot 2 changes from 3 to 7.
+                vec![(address, Some(Some(info(1))), vec![(slot(1), slot(5)), (slot(2), slot(3))])],
+            ],
+            vec![],
+        );
+        let mut outcome =
+            ExecutionOutcome::<reth_ethereum_primitives::Receipt>::new(bundle, vec![], 0, vec![]);
+
+        assert_eq!(outcome.compact_reverts(), 3);
+
+        let (_, first) = &outcome.bundle.reverts[0][0];
+        assert_eq!(first.account, AccountInfoRevert::RevertTo(info(2)));
+        assert!(first.storage.is_empty());
+
+        let (_, second) = &outcome.bundle.reverts[1][0];
+        assert_eq!(second.account, AccountInfoRevert::DoNothing);
+        assert_eq!(second.storage, HashMap::from_iter([(slot(2), RevertToSlot::Some(slot(3)))]));
+
+        assert_eq!(outcome.compact_reverts(), 0);
+    }
+
     #[test]
     fn test_block_number_to_index() {
         // Create a Receipts object with a vector of receipt vectors
@@ -1,6 +1,7 @@
 use crate::{BackfillJobFactory, ExExNotification, StreamBackfillJob, WalHandle};
 use alloy_consensus::BlockHeader;
 use alloy_eips::BlockNumHash;
+use alloy_primitives::BlockNumber;
 use futures::{Stream, StreamExt};
 use reth_ethereum_primitives::EthPrimitives;
 use reth_evm::ConfigureEvm;
@@ -10,6 +11,7 @@ use reth_provider::{BlockReader, Chain, HeaderProvider, StateProviderFactory};
 use reth_tracing::tracing::debug;
 use std::{
     fmt::Debug,
+    ops::RangeInclusive,
     pin::Pin,
     sync::Arc,
     task::{ready, Context, Poll},
@@ -100,6 +102,21 @@ where
             )),
         }
     }
+
+    /// Returns the range of committed blocks that can be replayed from the WAL.
+    ///
+    /// See [`WalHandle::committed_block_range`] for more details.
+    pub fn wal_committed_block_range(&self) -> Option<RangeInclusive<BlockNumber>> {
+        match &self.inner {
+            ExExNotificationsInner::WithoutHead(notifications) => {
+                notifications.wal_handle.committed_block_range()
+            }
+            ExExNotificationsInner::WithHead(notifications) => {
+                notifications.wal_handle.committed_block_range()
+            }
+            ExExNotificationsInner::Invalid => unreachable!(),
+        }
+    }
 }
 
 impl<P, E> ExExNotificationsStream<E::Primitives> for ExExNotifications<P, E>
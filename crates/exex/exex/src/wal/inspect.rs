@@ -0,0 +1,242 @@
+//! Offline inspection and repair of the WAL.
+//!
+//! [`Wal::new`](super::Wal::new) fails if any notification in the WAL directory can't be read. The
+//! functions in this module work on the directory directly, so that a broken WAL can be inspected
+//! and truncated to its valid prefix without losing the position of the ExExes.
+
+use std::{
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+};
+
+use crate::wal::{Storage, WalError, WalResult};
+use alloy_primitives::BlockNumber;
+use reth_exex_types::ExExNotification;
+use reth_node_api::NodePrimitives;
+use reth_tracing::tracing::debug;
+
+/// The kind of a notification stored in the WAL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WalNotificationKind {
+    /// [`ExExNotification::ChainCommitted`]
+    ChainCommitted,
+    /// [`ExExNotification::ChainReorged`]
+    ChainReorged,
+    /// [`ExExNotification::ChainReverted`]
+    ChainReverted,
+}
+
+impl<N: NodePrimitives> From<&ExExNotification<N>> for WalNotificationKind {
+    fn from(notification: &ExExNotification<N>) -> Self {
+        match notification {
+            ExExNotification::ChainCommitted { .. } => Self::ChainCommitted,
+            ExExNotification::ChainReorged { .. } => Self::ChainReorged,
+            ExExNotification::ChainReverted { .. } => Self::ChainReverted,
+        }
+    }
+}
+
+/// The state of a single WAL entry on disk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WalEntryStatus {
+    /// The notification was decoded successfully.
+    Valid {
+        /// The kind of the notification.
+        kind: WalNotificationKind,
+        /// The range of committed blocks, if any.
+        committed: Option<RangeInclusive<BlockNumber>>,
+        /// The range of reverted blocks, if any.
+        reverted: Option<RangeInclusive<BlockNumber>>,
+    },
+    /// The file is missing from the otherwise contiguous range of file IDs.
+    Missing,
+    /// The notification can't be decoded, e.g. because the write was torn by a crash.
+    Corrupt(String),
+}
+
+/// A single entry of the WAL on disk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WalEntry {
+    /// The ID of the file the entry is stored in.
+    pub file_id: u32,
+    /// The size of the file in bytes.
+    pub size: u64,
+    /// The state of the entry.
+    pub status: WalEntryStatus,
+}
+
+impl WalEntry {
+    /// Returns `true` if the notification of the entry can be read.
+    pub const fn is_valid(&self) -> bool {
+        matches!(self.status, WalEntryStatus::Valid { .. })
+    }
+}
+
+/// The result of inspecting a WAL directory, see [`inspect_wal`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WalInspection {
+    /// All entries of the WAL, ordered by file ID.
+    pub entries: Vec<WalEntry>,
+    /// Leftover temporary files of writes that were interrupted before completion.
+    pub temporary_files: Vec<PathBuf>,
+}
+
+impl WalInspection {
+    /// Returns the file ID of the first entry that can't be read.
+    ///
+    /// The WAL is only usable up to this entry, all entries starting from it form the broken tail
+    /// that [`truncate_wal`] removes.
+    pub fn first_invalid(&self) -> Option<u32> {
+        self.entries.iter().find(|entry| !entry.is_valid()).map(|entry| entry.file_id)
+    }
+
+    /// Returns the range of blocks committed by the valid prefix of the WAL.
+    pub fn committed_block_range(&self) -> Option<RangeInclusive<BlockNumber>> {
+        self.entries
+            .iter()
+            .take_while(|entry| entry.is_valid())
+            .filter_map(|entry| match &entry.status {
+                WalEntryStatus::Valid { committed, .. } => committed.clone(),
+                _ => None,
+            })
+            .reduce(|acc, range| {
+                (*acc.start()).min(*range.start())..=(*acc.end()).max(*range.end())
+            })
+    }
+
+    /// Returns the total size of all entries in bytes.
+    pub fn size(&self) -> u64 {
+        self.entries.iter().map(|entry| entry.size).sum()
+    }
+}
+
+/// Inspects the WAL in the given directory without loading it.
+///
+/// Unlike [`Wal::new`](super::Wal::new), this doesn't fail on entries that are missing or can't be
+/// decoded, and reports them instead.
+pub fn inspect_wal<N: NodePrimitives>(directory: impl AsRef<Path>) -> WalResult<WalInspection> {
+    let storage = Storage::<N>::new(directory)?;
+
+    let mut inspection =
+        WalInspection { temporary_files: storage.temporary_files()?, ..Default::default() };
+    let Some(files_range) = storage.files_range()? else { return Ok(inspection) };
+
+    for file_id in files_range {
+        let entry = match storage.read_notification(file_id) {
+            Ok(Some((notification, size))) => WalEntry {
+                file_id,
+                size,
+                status: WalEntryStatus::Valid {
+                    kind: (&notification).into(),
+                    committed: notification.committed_chain().map(|chain| chain.range()),
+                    reverted: notification.reverted_chain().map(|chain| chain.range()),
+                },
+            },
+            Ok(None) => WalEntry { file_id, size: 0, status: WalEntryStatus::Missing },
+            Err(err @ WalError::Decode(..)) => WalEntry {
+                file_id,
+                size: storage.file_size(file_id)?,
+                status: WalEntryStatus::Corrupt(err.to_string()),
+            },
+            Err(err) => return Err(err),
+        };
+        inspection.entries.push(entry);
+    }
+
+    Ok(inspection)
+}
+
+/// Removes all entries of the WAL in the given directory starting from the given file ID, and all
+/// leftover temporary files.
+///
+/// # Returns
+///
+/// Number of removed entries and the total size of the removed files in bytes.
+pub fn truncate_wal<N: NodePrimitives>(
+    directory: impl AsRef<Path>,
+    from_file_id: u32,
+) -> WalResult<(usize, u64)> {
+    let storage = Storage::<N>::new(directory)?;
+
+    for path in storage.temporary_files()? {
+        debug!(target: "exex::wal", ?path, "Removing temporary file");
+        reth_fs_util::remove_file(&path)?;
+    }
+
+    let Some(files_range) = storage.files_range()? else { return Ok((0, 0)) };
+    if from_file_id > *files_range.end() {
+        return Ok((0, 0))
+    }
+
+    // remove the highest entries first, so that an interrupted truncation still leaves a
+    // contiguous WAL behind
+    storage
+        .remove_notifications((from_file_id.max(*files_range.start())..=*files_range.end()).rev())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wal::Wal;
+    use reth_ethereum_primitives::EthPrimitives;
+    use reth_provider::Chain;
+    use reth_testing_utils::generators::{self, random_block_range, BlockRangeParams};
+    use std::sync::Arc;
+
+    fn wal_with_notifications(directory: &Path) -> eyre::Result<()> {
+        let mut rng = generators::rng();
+        let wal = Wal::<EthPrimitives>::new(directory)?;
+        for block in random_block_range(&mut rng, 0..=2, BlockRangeParams::default()) {
+            wal.commit(&ExExNotification::ChainCommitted {
+                new: Arc::new(Chain::new(vec![block.try_recover()?], Default::default(), None)),
+            })?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn inspect_and_truncate_torn_tail() -> eyre::Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        wal_with_notifications(temp_dir.path())?;
+
+        // tear the last entry and leave a temporary file of an interrupted write behind
+        let last = temp_dir.path().join("2.wal");
+        let len = std::fs::metadata(&last)?.len();
+        std::fs::File::options().write(true).open(&last)?.set_len(len / 2)?;
+        std::fs::write(temp_dir.path().join("3.tmp"), [0x01])?;
+        assert!(Wal::<EthPrimitives>::new(temp_dir.path()).is_err());
+
+        let inspection = inspect_wal::<EthPrimitives>(temp_dir.path())?;
+        assert_eq!(inspection.entries.len(), 3);
+        assert!(matches!(inspection.entries[2].status, WalEntryStatus::Corrupt(_)));
+        assert_eq!(inspection.first_invalid(), Some(2));
+        assert_eq!(inspection.committed_block_range(), Some(0..=1));
+        assert_eq!(inspection.temporary_files.len(), 1);
+
+        let (removed, _) = truncate_wal::<EthPrimitives>(temp_dir.path(), 2)?;
+        assert_eq!(removed, 1);
+
+        let inspection = inspect_wal::<EthPrimitives>(temp_dir.path())?;
+        assert_eq!(inspection.first_invalid(), None);
+        assert!(inspection.temporary_files.is_empty());
+
+        let wal = Wal::<EthPrimitives>::new(temp_dir.path())?;
+        assert_eq!(wal.handle().committed_block_range(), Some(0..=1));
+
+        Ok(())
+    }
+
+    #[test]
+    fn inspect_missing_entry() -> eyre::Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        wal_with_notifications(temp_dir.path())?;
+        std::fs::remove_file(temp_dir.path().join("1.wal"))?;
+
+        let inspection = inspect_wal::<EthPrimitives>(temp_dir.path())?;
+        assert_eq!(inspection.entries[1].status, WalEntryStatus::Missing);
+        assert_eq!(inspection.first_invalid(), Some(1));
+        assert_eq!(inspection.committed_block_range(), Some(0..=0));
+
+        Ok(())
+    }
+}
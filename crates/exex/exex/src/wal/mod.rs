@@ -10,8 +10,13 @@ mod metrics;
 use metrics::Metrics;
 mod error;
 pub use error::{WalError, WalResult};
+mod inspect;
+pub use inspect::{
+    inspect_wal, truncate_wal, WalEntry, WalEntryStatus, WalInspection, WalNotificationKind,
+};
 
 use std::{
+    ops::RangeInclusive,
     path::Path,
     sync::{
         atomic::{AtomicU32, Ordering},
@@ -20,7 +25,7 @@ use std::{
 };
 
 use alloy_eips::BlockNumHash;
-use alloy_primitives::B256;
+use alloy_primitives::{BlockNumber, B256};
 use parking_lot::{RwLock, RwLockReadGuard};
 use reth_exex_types::ExExNotification;
 use reth_tracing::tracing::{debug, instrument};
@@ -231,6 +236,17 @@ where
             .read_notification(file_id)
             .map(|entry| entry.map(|(notification, _)| notification))
     }
+
+    /// Returns the range of committed blocks that the WAL can replay notifications for.
+    ///
+    /// An ExEx whose head is below the start of this range can't be caught up from the WAL alone.
+    pub fn committed_block_range(&self) -> Option<RangeInclusive<BlockNumber>> {
+        let block_cache = self.wal.block_cache();
+        block_cache
+            .lowest_committed_block_height
+            .zip(block_cache.highest_committed_block_height)
+            .map(|(lowest, highest)| lowest..=highest)
+    }
 }
 
 #[cfg(test)]
@@ -13,6 +13,9 @@ use tracing::instrument;
 
 static FILE_EXTENSION: &str = "wal";
 
+/// Extension of the temporary files that notifications are written to before being renamed.
+static TEMPORARY_FILE_EXTENSION: &str = "tmp";
+
 /// The underlying WAL storage backed by a directory of files.
 ///
 /// Each notification is represented by a single file that contains a MessagePack-encoded
@@ -91,6 +94,31 @@ where
         Ok(min_id.zip(max_id).map(|(min_id, max_id)| min_id..=max_id))
     }
 
+    /// Returns the paths of temporary files left behind by interrupted writes.
+    pub(super) fn temporary_files(&self) -> WalResult<Vec<PathBuf>> {
+        let mut paths = Vec::new();
+
+        for entry in reth_fs_util::read_dir(&self.path)? {
+            let entry = entry.map_err(|err| WalError::DirEntry(self.path.clone(), err))?;
+
+            if entry.path().extension() == Some(TEMPORARY_FILE_EXTENSION.as_ref()) {
+                paths.push(entry.path());
+            }
+        }
+
+        paths.sort();
+        Ok(paths)
+    }
+
+    /// Returns the size of the file with the given ID in bytes.
+    pub(super) fn file_size(&self, file_id: u32) -> WalResult<u64> {
+        Ok(self
+            .file_path(file_id)
+            .metadata()
+            .map_err(|err| WalError::FileMetadata(file_id, err))?
+            .len())
+    }
+
     /// Removes notifications from the storage according to the given list of file IDs.
     ///
     /// # Returns
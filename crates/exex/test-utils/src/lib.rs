@@ -283,6 +283,7 @@ pub async fn test_exex_context_with_chain_spec(
             consensus,
             network,
             payload_builder_handle,
+            recovery_backend: None,
         },
         task_executor,
         provider,
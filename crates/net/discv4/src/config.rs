@@ -10,6 +10,7 @@ use reth_net_nat::{NatResolver, ResolveNatInterval};
 use reth_network_peers::NodeRecord;
 use std::{
     collections::{HashMap, HashSet},
+    net::Ipv6Addr,
     time::Duration,
 };
 
@@ -64,6 +65,12 @@ pub struct Discv4Config {
     /// If configured and a `external_ip_resolver` is configured, try to resolve the external ip
     /// using this interval.
     pub resolve_external_ip_interval: Option<Duration>,
+    /// The external IPv6 address of a dual-stack node, advertised in the EIP-868 ENR along with
+    /// the IPv4 address of the local node record.
+    ///
+    /// Peers only reach the node over IPv6 if the discovery socket is bound to the unspecified
+    /// IPv6 address.
+    pub external_ipv6: Option<Ipv6Addr>,
     /// The duration after which we consider a bond expired.
     pub bond_expiration: Duration,
 }
@@ -136,6 +143,7 @@ impl Default for Discv4Config {
             external_ip_resolver: Some(Default::default()),
             // By default retry public IP using a 5min interval
             resolve_external_ip_interval: Some(Duration::from_secs(60 * 5)),
+            external_ipv6: None,
         }
     }
 }
@@ -314,6 +322,13 @@ impl Discv4ConfigBuilder {
         self
     }
 
+    /// Sets the external IPv6 address to advertise along with the IPv4 address of a dual-stack
+    /// node.
+    pub const fn external_ipv6(&mut self, external_ipv6: Option<Ipv6Addr>) -> &mut Self {
+        self.config.external_ipv6 = external_ipv6;
+        self
+    }
+
     /// Returns the configured [`Discv4Config`]
     pub fn build(&self) -> Discv4Config {
         self.config.clone()
@@ -52,6 +52,7 @@ use proto::{EnrRequest, EnrResponse};
 use reth_ethereum_forks::ForkId;
 use reth_network_peers::{pk2id, PeerId};
 use secp256k1::SecretKey;
+use socket2::{Domain, Protocol, Socket, Type};
 use std::{
     cell::RefCell,
     collections::{btree_map, hash_map::Entry, BTreeMap, HashMap, VecDeque},
@@ -220,6 +221,9 @@ impl Discv4 {
 
     /// Binds a new `UdpSocket` and creates the service
     ///
+    /// If the address is the unspecified IPv6 address, the socket is dual-stack and also serves
+    /// IPv4 peers.
+    ///
     /// ```
     /// # use std::io;
     /// use reth_discv4::{Discv4, Discv4Config};
@@ -255,7 +259,10 @@ impl Discv4 {
         secret_key: SecretKey,
         config: Discv4Config,
     ) -> io::Result<(Self, Discv4Service)> {
-        let socket = UdpSocket::bind(local_address).await?;
+        let socket = match local_address.ip() {
+            IpAddr::V6(ip) if ip.is_unspecified() => bind_dual_stack(local_address)?,
+            _ => UdpSocket::bind(local_address).await?,
+        };
         let local_addr = socket.local_addr()?;
         local_node_record.udp_port = local_addr.port();
         trace!(target: "discv4", ?local_addr,"opened UDP socket");
@@ -386,6 +393,23 @@ impl Discv4 {
         self.send_to_service(cmd);
     }
 
+    /// Sets the udp port
+    ///
+    /// This will update our [`NodeRecord`]'s udp port, e.g. to the external port mapped on the
+    /// gateway. The socket stays bound to the local port.
+    pub fn set_udp_port(&self, port: u16) {
+        let cmd = Discv4Command::SetUdpPort(port);
+        self.send_to_service(cmd);
+    }
+
+    /// Sets the external IP address
+    ///
+    /// This will update our [`NodeRecord`]'s address and the IP of the EIP-868 [`Enr`].
+    pub fn set_external_ip_addr(&self, external_ip: IpAddr) {
+        let cmd = Discv4Command::SetExternalIpAddr(external_ip);
+        self.send_to_service(cmd);
+    }
+
     /// Sets the pair in the EIP-868 [`Enr`] of the node.
     ///
     /// If the key already exists, this will update it.
@@ -568,6 +592,12 @@ impl Discv4Service {
             if local_node_record.address.is_ipv4() {
                 builder.udp4(local_node_record.udp_port);
                 builder.tcp4(local_node_record.tcp_port);
+                if let Some(ipv6) = config.external_ipv6 {
+                    // advertise both endpoints of a dual-stack node
+                    builder.ip6(ipv6);
+                    builder.udp6(local_node_record.udp_port);
+                    builder.tcp6(local_node_record.tcp_port);
+                }
             } else {
                 builder.udp6(local_node_record.udp_port);
                 builder.tcp6(local_node_record.tcp_port);
@@ -1763,14 +1793,34 @@ impl Discv4Service {
 
                         let _ = self.local_eip_868_enr.insert_raw_rlp(key, rlp, &self.secret_key);
                     }
+                    Discv4Command::SetExternalIpAddr(external_ip) => {
+                        self.set_external_ip_addr(external_ip);
+                    }
                     Discv4Command::SetTcpPort(port) => {
                         debug!(target: "discv4", %port, "Update tcp port");
                         self.local_node_record.tcp_port = port;
                         if self.local_node_record.address.is_ipv4() {
                             let _ = self.local_eip_868_enr.set_tcp4(port, &self.secret_key);
-                        } else {
+                        }
+                        if self.local_eip_868_enr.ip6().is_some() ||
+                            self.local_node_record.address.is_ipv6()
+                        {
                             let _ = self.local_eip_868_enr.set_tcp6(port, &self.secret_key);
                         }
+                        *self.shared_node_record.lock() = self.local_node_record;
+                    }
+                    Discv4Command::SetUdpPort(port) => {
+                        debug!(target: "discv4", %port, "Update udp port");
+                        self.local_node_record.udp_port = port;
+                        if self.local_node_record.address.is_ipv4() {
+                            let _ = self.local_eip_868_enr.set_udp4(port, &self.secret_key);
+                        }
+                        if self.local_eip_868_enr.ip6().is_some() ||
+                            self.local_node_record.address.is_ipv6()
+                        {
+                            let _ = self.local_eip_868_enr.set_udp6(port, &self.secret_key);
+                        }
+                        *self.shared_node_record.lock() = self.local_node_record;
                     }
 
                     Discv4Command::Terminated => {
@@ -1909,8 +1959,16 @@ pub enum Discv4Event {
 
 /// Continuously reads new messages from the channel and writes them to the socket
 pub(crate) async fn send_loop(udp: Arc<UdpSocket>, rx: EgressReceiver) {
+    let dual_stack = udp.local_addr().is_ok_and(|addr| addr.is_ipv6());
     let mut stream = ReceiverStream::new(rx);
     while let Some((payload, to)) = stream.next().await {
+        // an IPv6 socket reaches IPv4 peers on their IPv4-mapped address
+        let to = match to {
+            SocketAddr::V4(addr) if dual_stack => {
+                SocketAddr::new(addr.ip().to_ipv6_mapped().into(), addr.port())
+            }
+            to => to,
+        };
         match udp.send_to(&payload, to).await {
             Ok(size) => {
                 trace!(target: "discv4", ?to, ?size,"sent payload");
@@ -1922,6 +1980,16 @@ pub(crate) async fn send_loop(udp: Arc<UdpSocket>, rx: EgressReceiver) {
     }
 }
 
+/// Binds a socket to the unspecified IPv6 address that also serves IPv4 peers, regardless of the
+/// system default for IPv6 sockets.
+fn bind_dual_stack(addr: SocketAddr) -> io::Result<UdpSocket> {
+    let socket = Socket::new(Domain::IPV6, Type::DGRAM, Some(Protocol::UDP))?;
+    socket.set_only_v6(false)?;
+    socket.set_nonblocking(true)?;
+    socket.bind(&addr.into())?;
+    UdpSocket::from_std(socket.into())
+}
+
 /// Rate limits the number of incoming packets from individual IPs to 1 packet/second
 const MAX_INCOMING_PACKETS_PER_MINUTE_BY_IP: usize = 60usize;
 
@@ -1955,6 +2023,11 @@ pub(crate) async fn receive_loop(udp: Arc<UdpSocket>, tx: IngressSender, local_i
                 send(IngressEvent::RecvError(err)).await;
             }
             Ok((read, remote_addr)) => {
+                // a dual-stack socket reports IPv4 peers by their IPv4-mapped address, which would
+                // not match the IPv4 endpoints peers advertise and are tracked by
+                let remote_addr =
+                    SocketAddr::new(remote_addr.ip().to_canonical(), remote_addr.port());
+
                 // rate limit incoming packets by IP
                 if cache.inc_ip(remote_addr.ip()) > MAX_INCOMING_PACKETS_PER_MINUTE_BY_IP {
                     trace!(target: "discv4", ?remote_addr, "Too many incoming packets from IP.");
@@ -2053,6 +2126,8 @@ impl Default for ReceiveCache {
 enum Discv4Command {
     Add(NodeRecord),
     SetTcpPort(u16),
+    SetUdpPort(u16),
+    SetExternalIpAddr(IpAddr),
     SetEIP868RLPPair { key: Vec<u8>, rlp: Bytes },
     Ban(PeerId, IpAddr),
     BanPeer(PeerId),
@@ -2416,7 +2491,9 @@ pub enum DiscoveryUpdate {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::test_utils::{create_discv4, create_discv4_with_config, rng_endpoint, rng_record};
+    use crate::test_utils::{
+        create_discv4, create_discv4_at, create_discv4_with_config, rng_endpoint, rng_record,
+    };
     use alloy_primitives::hex;
     use alloy_rlp::{Decodable, Encodable};
     use rand_08::Rng;
@@ -2583,6 +2660,19 @@ mod tests {
         };
     }
 
+    #[tokio::test]
+    async fn test_dual_stack_enr() {
+        let ipv6: std::net::Ipv6Addr = "2001:db8::1".parse().unwrap();
+        let config = Discv4Config::builder().external_ipv6(Some(ipv6)).build();
+        let (_discv4, service) = create_discv4_with_config(config).await;
+
+        let enr = &service.local_eip_868_enr;
+        assert_eq!(enr.ip4(), Some(Ipv4Addr::UNSPECIFIED));
+        assert_eq!(enr.ip6(), Some(ipv6));
+        assert_eq!(enr.udp6(), enr.udp4());
+        assert_eq!(enr.tcp6(), enr.tcp4());
+    }
+
     #[tokio::test]
     async fn test_respect_ping_expiration() {
         reth_tracing::init_test_tracing();
@@ -2997,6 +3087,56 @@ mod tests {
         }
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_dual_stack_ping_pong() {
+        reth_tracing::init_test_tracing();
+
+        let config = Discv4Config::builder().external_ip_resolver(None).build();
+        let (_discv4, mut service_1) = create_discv4_with_config(config.clone()).await;
+        let (_discv4, mut service_2) = create_discv4_at("[::]:0".parse().unwrap(), config).await;
+
+        // ping the dual-stack node over IPv4
+        let mut record_2 = service_2.local_node_record;
+        record_2.address = Ipv4Addr::LOCALHOST.into();
+        service_1.add_node(record_2);
+
+        let event = poll_fn(|cx| service_2.poll(cx)).await;
+        assert_eq!(event, Discv4Event::Ping);
+
+        // the IPv4 peer is tracked by its IPv4 address, not the IPv4-mapped one
+        let key1 = kad_key(*service_1.local_peer_id());
+        match service_2.kbuckets.entry(&key1) {
+            kbucket::Entry::Present(entry, _) => {
+                assert_eq!(entry.value().record.address, IpAddr::from(Ipv4Addr::LOCALHOST));
+            }
+            _ => unreachable!(),
+        }
+
+        // the pong is sent back to the IPv4 peer
+        let event = poll_fn(|cx| service_1.poll(cx)).await;
+        assert_eq!(event, Discv4Event::Pong);
+
+        let key2 = kad_key(*service_2.local_peer_id());
+        match service_1.kbuckets.entry(&key2) {
+            kbucket::Entry::Present(_entry, status) => {
+                assert!(status.is_connected());
+            }
+            _ => unreachable!(),
+        }
+
+        // the ping initiated by the dual-stack node reaches the IPv4 peer and the bond is proven
+        let event = poll_fn(|cx| service_1.poll(cx)).await;
+        assert_eq!(event, Discv4Event::Ping);
+        loop {
+            match poll_fn(|cx| service_2.poll(cx)).await {
+                Discv4Event::EnrRequest => {}
+                Discv4Event::Pong => break,
+                ev => unreachable!("{ev:?}"),
+            }
+        }
+        assert!(service_2.has_bond(*service_1.local_peer_id(), Ipv4Addr::LOCALHOST.into()));
+    }
+
     #[test]
     fn test_insert() {
         let local_node_record = rng_record(&mut rand_08::thread_rng());
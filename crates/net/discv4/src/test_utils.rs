@@ -241,8 +241,12 @@ pub async fn create_discv4() -> (Discv4, Discv4Service) {
 
 /// Creates a new testing instance for [`Discv4`] and its service with the given config.
 pub async fn create_discv4_with_config(config: Discv4Config) -> (Discv4, Discv4Service) {
+    create_discv4_at(SocketAddr::from_str("0.0.0.0:0").unwrap(), config).await
+}
+
+/// Creates a new testing instance for [`Discv4`] and its service bound to the given address.
+pub async fn create_discv4_at(socket: SocketAddr, config: Discv4Config) -> (Discv4, Discv4Service) {
     let mut rng = thread_rng();
-    let socket = SocketAddr::from_str("0.0.0.0:0").unwrap();
     let (secret_key, pk) = SECP256K1.generate_keypair(&mut rng);
     let id = pk2id(&pk);
     let local_enr =
@@ -349,7 +349,7 @@ impl Discv5 {
         socket: SocketAddr,
     ) -> Result<NodeRecord, Error> {
         // ignore UDP socket advertised in ENR, use sender socket instead
-        let address = socket.ip();
+        let address = socket.ip().to_canonical();
         let udp_port = socket.port();
 
         let id = enr_to_discv4_id(enr).ok_or(Error::IncompatibleKeyType)?;
@@ -357,7 +357,9 @@ impl Discv5 {
         let tcp_port = (match self.rlpx_ip_mode {
             IpMode::Ip4 => enr.tcp4(),
             IpMode::Ip6 => enr.tcp6(),
-            IpMode::DualStack => unimplemented!("dual-stack support not implemented for rlpx"),
+            // dial the peer on the IP version it was discovered on
+            IpMode::DualStack if address.is_ipv4() => enr.tcp4(),
+            IpMode::DualStack => enr.tcp6(),
         })
         .unwrap_or(
             // tcp socket is missing from ENR, or is wrong IP version.
@@ -474,12 +476,23 @@ pub fn build_local_enr(
                 builder.ip6(ipv6);
             }
             builder.udp6(ipv6_port);
+            builder.tcp6(tcp_socket.port());
 
             (ipv6, ipv6_port).into()
         }
     };
 
-    let rlpx_ip_mode = if tcp_socket.is_ipv4() { IpMode::Ip4 } else { IpMode::Ip6 };
+    // a `RLPx` listener on the unspecified IPv6 address is dual-stack
+    let rlpx_ip_mode = match tcp_socket.ip() {
+        IpAddr::V4(_) => IpMode::Ip4,
+        IpAddr::V6(ip)
+            if ip.is_unspecified() &&
+                matches!(discv5_config.listen_config, ListenConfig::DualStack { .. }) =>
+        {
+            IpMode::DualStack
+        }
+        IpAddr::V6(_) => IpMode::Ip6,
+    };
 
     // identifies which network node is on
     let network_stack_id = fork.as_ref().map(|(network_stack_id, fork_value)| {
@@ -673,6 +686,7 @@ mod test {
     use ::enr::{CombinedKey, EnrKey};
     use rand_08::thread_rng;
     use reth_chainspec::MAINNET;
+    use std::net::{SocketAddrV4, SocketAddrV6};
     use tracing::trace;
 
     fn discv5_noop() -> Discv5 {
@@ -905,4 +919,28 @@ mod test {
         assert_eq!(fork_id, decoded_fork_id);
         assert_eq!(TCP_PORT, enr.tcp4().unwrap()); // listen config is defaulting to ip mode ipv4
     }
+
+    #[test]
+    fn build_dual_stack_enr() {
+        const TCP_PORT: u16 = 30303;
+
+        let config = Config::builder((Ipv6Addr::UNSPECIFIED, TCP_PORT).into())
+            .discv5_config(
+                discv5::ConfigBuilder::new(ListenConfig::from_two_sockets(
+                    Some(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, DEFAULT_DISCOVERY_V5_PORT)),
+                    Some(SocketAddrV6::new(Ipv6Addr::UNSPECIFIED, DEFAULT_DISCOVERY_V5_PORT, 0, 0)),
+                ))
+                .build(),
+            )
+            .build();
+
+        let sk = SecretKey::new(&mut thread_rng());
+        let (enr, _, _, ip_mode) = build_local_enr(&sk, &config);
+
+        assert!(matches!(ip_mode, IpMode::DualStack));
+        assert_eq!(enr.tcp4(), Some(TCP_PORT));
+        assert_eq!(enr.tcp6(), Some(TCP_PORT));
+        assert_eq!(enr.udp4(), Some(DEFAULT_DISCOVERY_V5_PORT));
+        assert_eq!(enr.udp6(), Some(DEFAULT_DISCOVERY_V5_PORT));
+    }
 }
@@ -13,6 +13,7 @@ use reth_primitives_traits::{Block, GotExpected, InMemorySize, SealedBlock, Seal
 use std::{
     collections::VecDeque,
     mem,
+    ops::RangeInclusive,
     pin::Pin,
     sync::Arc,
     task::{ready, Context, Poll},
@@ -106,12 +107,24 @@ where
         hashes.peek().is_some().then(|| hashes.collect())
     }
 
+    /// Retrieve the range of the blocks of the next request.
+    fn next_request_range(&self) -> Option<RangeInclusive<u64>> {
+        let mut numbers = self.pending_headers.iter().filter(|h| !h.is_empty()).map(|h| h.number());
+        let first = numbers.next()?;
+        Some(first..=numbers.last().unwrap_or(first))
+    }
+
     /// Submit the request with the given priority.
+    ///
+    /// The range of the requested blocks is passed as hint, so the request is routed to a peer
+    /// that serves the blocks.
     fn submit_request(&mut self, req: Vec<B256>, priority: Priority) {
         tracing::trace!(target: "downloaders::bodies", request_len = req.len(), "Requesting bodies");
         let client = Arc::clone(&self.client);
+        let range_hint = self.next_request_range();
         self.last_request_len = Some(req.len());
-        self.fut = Some(client.get_block_bodies_with_priority(req, priority));
+        self.fut =
+            Some(client.get_block_bodies_with_priority_and_range_hint(req, priority, range_hint));
     }
 
     /// Process block response.
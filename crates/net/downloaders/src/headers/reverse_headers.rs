@@ -104,6 +104,12 @@ pub struct ReverseHeadersDownloader<H: HeadersClient> {
     ///
     /// Note: headers are sorted from high to low
     queued_validated_headers: Vec<SealedHeader<H::Header>>,
+    /// Hashes of all headers validated for the current sync target, including the ones that were
+    /// already returned.
+    validated_hashes: ValidatedHashes,
+    /// Headers validated for a previous sync target, kept until the current sync target connects
+    /// to them.
+    retained: Option<RetainedHeaders<H::Header>>,
     /// Header downloader metrics.
     metrics: HeaderDownloaderMetrics,
 }
@@ -331,18 +337,87 @@ where
         // update tracked block info (falling block number)
         self.next_chain_tip_block_number =
             validated.last().expect("exists").number().saturating_sub(1);
-        self.queued_validated_headers.extend(validated);
+        self.queue_validated_headers(validated);
 
         Ok(())
     }
 
+    /// Queues the validated headers, sorted from high to low, to be returned.
+    ///
+    /// If the headers connect to the headers retained from a previous sync target, the retained
+    /// headers that are still canonical are spliced in, so they don't have to be downloaded again.
+    fn queue_validated_headers(&mut self, mut validated: Vec<SealedHeader<H::Header>>) {
+        let join = self.retained.as_ref().and_then(|retained| retained.find_join(&validated));
+        if let Some((_, len)) = join {
+            // the remaining headers are already retained
+            validated.truncate(len);
+        }
+
+        for header in &validated {
+            self.validated_hashes.push(header.number(), header.hash());
+        }
+        self.queued_validated_headers.extend(validated);
+
+        if let Some((join_number, _)) = join {
+            self.splice_retained(join_number);
+        } else if self.retained.as_ref().is_some_and(|retained| {
+            self.lowest_validated_header()
+                .is_some_and(|lowest| lowest.number() <= retained.lowest.number())
+        }) {
+            // the new chain can no longer connect to the retained headers
+            debug!(target: "downloaders::headers", "Discarding headers retained from previous sync target");
+            self.retained = None;
+        }
+    }
+
+    /// Splices in the headers retained from a previous sync target, starting at the given block
+    /// number, which connects to the lowest validated header.
+    fn splice_retained(&mut self, join_number: BlockNumber) {
+        let Some(RetainedHeaders { hashes, queued, lowest }) = self.retained.take() else { return };
+
+        debug!(
+            target: "downloaders::headers",
+            join_number,
+            lowest = lowest.number(),
+            "Splicing headers retained from previous sync target"
+        );
+
+        self.validated_hashes.extend_from(&hashes, join_number);
+        self.queued_validated_headers
+            .extend(queued.into_iter().filter(|header| header.number() <= join_number));
+
+        // continue below the retained headers
+        self.next_chain_tip_block_number = lowest.number().saturating_sub(1);
+        self.next_request_block_number =
+            self.next_request_block_number.min(self.next_chain_tip_block_number);
+        self.lowest_validated_header = Some(lowest);
+    }
+
+    /// Takes all headers validated for the current sync target, so they can be spliced in once the
+    /// next sync target connects to them.
+    ///
+    /// Headers retained from an earlier sync target that wasn't connected to yet take precedence,
+    /// since these were downloaded for longer.
+    fn take_retained(&mut self) -> Option<RetainedHeaders<H::Header>> {
+        if let Some(retained) = self.retained.take() {
+            return Some(retained)
+        }
+        let lowest = self.lowest_validated_header()?.clone();
+        Some(RetainedHeaders {
+            hashes: std::mem::take(&mut self.validated_hashes),
+            queued: std::mem::take(&mut self.queued_validated_headers),
+            lowest,
+        })
+    }
+
     /// Updates the state based on the given `target_block_number`
     ///
     /// There are three different outcomes:
     ///  * This is the first time this is called: current `sync_target` block is still `None`. In
     ///    which case we're initializing the request trackers to `next_block`
     ///  * The `target_block_number` is _higher_ than the current target. In which case we start
-    ///    over with a new range
+    ///    over with a new range, but retain the already validated headers so the ones that are
+    ///    still canonical can be spliced in once the new range connects to them
     ///  * The `target_block_number` is _lower_ than the current target or the _same_. In which case
     ///    we don't need to update the request trackers but need to ensure already buffered headers
     ///    are _not_ higher than the new `target_block_number`.
@@ -356,7 +431,9 @@ where
                 // request tracker and reset everything
                 self.next_request_block_number = next_block;
                 self.next_chain_tip_block_number = next_block;
+                let retained = self.take_retained();
                 self.clear();
+                self.retained = retained;
             } else {
                 // ensure already validated headers are in range
                 let skip = self
@@ -437,7 +514,7 @@ where
                 let parent_block_number = target.number().saturating_sub(1);
                 self.on_block_number_update(target.number(), parent_block_number);
 
-                self.queued_validated_headers.push(target);
+                self.queue_validated_headers(vec![target]);
 
                 // try to validate all buffered responses blocked by this successful response
                 self.try_validate_buffered()
@@ -626,6 +703,8 @@ where
     fn clear(&mut self) {
         self.lowest_validated_header.take();
         self.queued_validated_headers = Vec::new();
+        self.validated_hashes = ValidatedHashes::default();
+        self.retained = None;
         self.buffered_responses = BinaryHeap::new();
         self.in_progress_queue.clear();
 
@@ -1015,6 +1094,79 @@ impl HeadersResponseError {
     }
 }
 
+/// Hashes of a contiguous range of validated headers, from the highest block number down.
+#[derive(Debug, Default)]
+struct ValidatedHashes {
+    /// Block number of the first hash.
+    highest: BlockNumber,
+    /// Hashes with falling block numbers.
+    hashes: Vec<B256>,
+}
+
+impl ValidatedHashes {
+    /// Appends the hash of the next lower block.
+    ///
+    /// If the block doesn't extend the range, the range starts over at this block.
+    fn push(&mut self, number: BlockNumber, hash: B256) {
+        if self.hashes.is_empty() ||
+            self.highest.checked_sub(self.hashes.len() as u64) != Some(number)
+        {
+            self.highest = number;
+            self.hashes.clear();
+        }
+        self.hashes.push(hash);
+    }
+
+    /// Returns the hash of the given block, if it is in range.
+    fn get(&self, number: BlockNumber) -> Option<B256> {
+        let idx = self.highest.checked_sub(number)?;
+        self.hashes.get(idx as usize).copied()
+    }
+
+    /// Appends the hashes of `other`, starting at the given block number.
+    fn extend_from(&mut self, other: &Self, number: BlockNumber) {
+        let Some(idx) = other.highest.checked_sub(number).map(|idx| idx as usize) else { return };
+        if idx >= other.hashes.len() {
+            return
+        }
+        if self.hashes.is_empty() {
+            self.highest = number;
+        }
+        self.hashes.extend_from_slice(&other.hashes[idx..]);
+    }
+}
+
+/// Headers validated for a previous sync target.
+///
+/// When the sync target moves to a higher block, possibly on a different fork, the downloader
+/// starts over from the new target. Once the new range connects to these headers, the ones that are
+/// still canonical are spliced in instead of being downloaded again.
+#[derive(Debug)]
+struct RetainedHeaders<H> {
+    /// Hashes of all retained headers, including the ones that were already returned.
+    hashes: ValidatedHashes,
+    /// Retained headers that were not returned yet, sorted from high to low.
+    queued: Vec<SealedHeader<H>>,
+    /// The lowest retained header.
+    lowest: SealedHeader<H>,
+}
+
+impl<H: BlockHeader> RetainedHeaders<H> {
+    /// Returns the highest block number at which the given validated headers, sorted from high to
+    /// low, connect to the retained headers, and the number of given headers above it.
+    fn find_join(&self, validated: &[SealedHeader<H>]) -> Option<(BlockNumber, usize)> {
+        if let Some(pos) = validated
+            .iter()
+            .position(|header| self.hashes.get(header.number()) == Some(header.hash()))
+        {
+            return Some((validated[pos].number(), pos))
+        }
+        let last = validated.last()?;
+        let parent = last.number().checked_sub(1)?;
+        (self.hashes.get(parent) == Some(last.parent_hash())).then_some((parent, validated.len()))
+    }
+}
+
 /// The block to which we want to close the gap: (local head...sync target]
 /// This tracks the sync target block, so this could be either a block number or hash.
 #[derive(Clone, Debug)]
@@ -1230,6 +1382,8 @@ impl ReverseHeadersDownloaderBuilder {
             in_progress_queue: Default::default(),
             buffered_responses: Default::default(),
             queued_validated_headers: Default::default(),
+            validated_hashes: Default::default(),
+            retained: None,
             metrics: Default::default(),
         }
     }
@@ -1392,6 +1546,94 @@ mod tests {
         assert_eq!(request.limit, 1);
     }
 
+    /// Returns a child header of `parent` on a different fork than [`child_header`].
+    fn fork_child_header(parent: &SealedHeader) -> SealedHeader {
+        let mut child = child_header(parent).into_header();
+        child.extra_data = vec![1].into();
+        SealedHeader::seal_slow(child)
+    }
+
+    /// Tests that headers downloaded for a previous sync target are spliced in once the new sync
+    /// target connects to them.
+    #[test]
+    fn test_splice_retained_after_reorg() {
+        let client = Arc::new(TestHeadersClient::default());
+        let genesis = SealedHeader::default();
+        let mut chain = vec![child_header(&genesis)];
+        for _ in 0..5 {
+            chain.push(child_header(chain.last().unwrap()));
+        }
+        // headers 1..=6, sorted from high to low
+        chain.reverse();
+        let [a6, a5, a4, a3, _, _]: [SealedHeader; 6] = chain.try_into().unwrap();
+
+        let mut downloader = ReverseHeadersDownloaderBuilder::default()
+            .stream_batch_size(2)
+            .build(Arc::clone(&client), Arc::new(TestConsensus::default()));
+        downloader.update_local_head(genesis);
+        downloader.sync_target = Some(SyncTargetBlock::from_hash(a6.hash()));
+        downloader.on_block_number_update(6, 5);
+        downloader.queue_validated_headers(vec![a6]);
+        downloader.queue_validated_headers(vec![a5, a4.clone(), a3.clone()]);
+        downloader.next_chain_tip_block_number = 2;
+        downloader.next_request_block_number = 2;
+
+        // headers 6 and 5 are returned before the sync target moves
+        assert_eq!(downloader.split_next_batch().len(), 2);
+
+        // the new sync target forks off at block 4
+        let b5 = fork_child_header(&a4);
+        let b6 = child_header(&b5);
+        let b7 = child_header(&b6);
+        downloader.on_block_number_update(7, 6);
+        assert!(downloader.queued_validated_headers.is_empty());
+        assert!(downloader.retained.is_some());
+
+        downloader.queue_validated_headers(vec![b7.clone()]);
+        assert!(downloader.retained.is_some());
+
+        // the response overlaps with the retained headers
+        downloader.queue_validated_headers(vec![b6.clone(), b5.clone(), a4.clone()]);
+        assert!(downloader.retained.is_none());
+        assert_eq!(
+            downloader.queued_validated_headers,
+            vec![b7, b6.clone(), b5, a4.clone(), a3.clone()]
+        );
+        assert_eq!(downloader.next_chain_tip_block_number, 2);
+        assert_eq!(downloader.next_request_block_number, 2);
+        assert_eq!(downloader.validated_hashes.get(6), Some(b6.hash()));
+        assert_eq!(downloader.validated_hashes.get(4), Some(a4.hash()));
+        assert_eq!(downloader.validated_hashes.get(3), Some(a3.hash()));
+    }
+
+    /// Tests that retained headers are discarded if the new sync target doesn't connect to them.
+    #[test]
+    fn test_discard_retained_without_join() {
+        let client = Arc::new(TestHeadersClient::default());
+        let genesis = SealedHeader::default();
+        let a1 = child_header(&genesis);
+        let a2 = child_header(&a1);
+
+        let mut downloader = ReverseHeadersDownloaderBuilder::default()
+            .build(Arc::clone(&client), Arc::new(TestConsensus::default()));
+        downloader.update_local_head(genesis.clone());
+        downloader.sync_target = Some(SyncTargetBlock::from_hash(a2.hash()));
+        downloader.on_block_number_update(2, 1);
+        downloader.queue_validated_headers(vec![a2]);
+
+        // the new sync target forks off at genesis
+        let b1 = fork_child_header(&genesis);
+        let b2 = child_header(&b1);
+        let b3 = child_header(&b2);
+        downloader.on_block_number_update(3, 2);
+        assert!(downloader.retained.is_some());
+
+        downloader.queue_validated_headers(vec![b3.clone()]);
+        downloader.queue_validated_headers(vec![b2.clone()]);
+        assert!(downloader.retained.is_none());
+        assert_eq!(downloader.queued_validated_headers, vec![b3, b2]);
+    }
+
     /// Tests that request calc works
     #[test]
     fn test_next_request() {
@@ -4,6 +4,7 @@
 //!
 //! - `serde` (default): Enable serde support
 //! - `arbitrary`: Adds `proptest` and `arbitrary` support for wire types.
+//! - `test-utils`: Export utilities for testing, including an in-process mock peer.
 
 #![doc(
     html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
@@ -27,10 +28,10 @@ pub mod protocol;
 /// Handshake logic
 pub mod handshake;
 
-#[cfg(test)]
+#[cfg(any(test, feature = "test-utils"))]
 pub mod test_utils;
 
-#[cfg(test)]
+#[cfg(any(test, feature = "test-utils"))]
 pub use tokio_util::codec::{
     LengthDelimitedCodec as PassthroughCodec, LengthDelimitedCodecError as PassthroughCodecError,
 };
@@ -0,0 +1,360 @@
+//! An in-process eth peer with scriptable responses.
+//!
+//! The [`MockEthPeer`] is either connected to the code under test through an in-memory transport,
+//! without any sockets or encryption, or attached to a session of a running node like a real peer.
+//! It performs the regular `p2p` and `eth` handshakes and then reacts to every message it receives
+//! as instructed by a handler, which makes it possible to test behavior against slow, unresponsive
+//! or misbehaving peers.
+
+use crate::{
+    errors::{EthStreamError, P2PStreamError},
+    hello::DEFAULT_TCP_PORT,
+    CanDisconnect, DisconnectReason, EthMessage, EthNetworkPrimitives, EthStream, EthVersion,
+    HelloMessageWithProtocols, NetworkPrimitives, P2PStream, ProtocolVersion, UnauthedEthStream,
+    UnauthedP2PStream, UnifiedStatus,
+};
+use alloy_primitives::B512;
+use bytes::{Bytes, BytesMut};
+use futures::{Sink, SinkExt, Stream, StreamExt};
+use reth_ecies::stream::ECIESStream;
+use reth_ethereum_forks::{ForkFilter, Head};
+use reth_network_peers::{pk2id, PeerId};
+use secp256k1::{SecretKey, SECP256K1};
+use std::{fmt, io, net::SocketAddr, time::Duration};
+use tokio::{
+    io::DuplexStream,
+    net::TcpStream,
+    sync::mpsc::{self, UnboundedReceiver, UnboundedSender},
+};
+use tokio_util::codec::{Framed, LengthDelimitedCodec};
+
+/// Buffer size of the in-memory transport.
+const TRANSPORT_BUFFER_SIZE: usize = 1024 * 1024;
+
+/// The in-memory transport between the code under test and a [`MockEthPeer`].
+pub type MockTransport = Framed<DuplexStream, LengthDelimitedCodec>;
+
+/// The eth stream connected to a [`MockEthPeer`].
+pub type MockEthStream<N = EthNetworkPrimitives> = EthStream<P2PStream<MockTransport>, N>;
+
+/// How a [`MockEthPeer`] reacts to a message it received.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MockAction<N: NetworkPrimitives = EthNetworkPrimitives> {
+    /// Respond with the given message.
+    Reply(EthMessage<N>),
+    /// Respond with the given message after the delay.
+    ///
+    /// Messages are processed in order, so all subsequent messages are delayed as well.
+    ReplyAfter(Duration, EthMessage<N>),
+    /// Don't respond.
+    Ignore,
+    /// Disconnect with the given reason.
+    Disconnect(DisconnectReason),
+}
+
+/// Decides how the [`MockEthPeer`] reacts to a received message.
+type MockHandler<N> = Box<dyn FnMut(&EthMessage<N>) -> MockAction<N> + Send>;
+
+/// Commands sent from the [`MockEthPeer`] handle to its task.
+#[derive(Debug)]
+enum MockCommand<N: NetworkPrimitives> {
+    /// Send an unsolicited message.
+    Send(EthMessage<N>),
+    /// Disconnect with the given reason.
+    Disconnect(DisconnectReason),
+}
+
+/// Builder for a [`MockEthPeer`].
+pub struct MockEthPeerBuilder<N: NetworkPrimitives = EthNetworkPrimitives> {
+    /// The status sent by the code under test.
+    status: UnifiedStatus,
+    /// The status sent by the mock peer.
+    peer_status: UnifiedStatus,
+    /// The fork filter used by both sides.
+    fork_filter: ForkFilter,
+    /// Decides how the mock peer reacts to received messages.
+    handler: MockHandler<N>,
+}
+
+impl<N: NetworkPrimitives> MockEthPeerBuilder<N> {
+    /// Creates a new builder for a peer speaking the given eth version.
+    pub fn new(version: EthVersion) -> Self {
+        let genesis = UnifiedStatus::default().genesis;
+        let fork_filter = ForkFilter::new(Head::default(), genesis, 0, Vec::new());
+        let status = UnifiedStatus::builder()
+            .version(version)
+            .genesis(genesis)
+            .blockhash(genesis)
+            .forkid(fork_filter.current())
+            .build();
+        Self { status, peer_status: status, fork_filter, handler: Box::new(|_| MockAction::Ignore) }
+    }
+
+    /// Sets the status the mock peer sends during the handshake.
+    ///
+    /// This can be used to test peers on a different chain or with an incompatible fork id.
+    pub const fn peer_status(mut self, status: UnifiedStatus) -> Self {
+        self.peer_status = status;
+        self
+    }
+
+    /// Sets the fork filter the status of the other side is validated against.
+    pub fn fork_filter(mut self, fork_filter: ForkFilter) -> Self {
+        self.fork_filter = fork_filter;
+        self
+    }
+
+    /// Sets the handler that decides how the mock peer reacts to received messages.
+    ///
+    /// By default, all messages are ignored.
+    pub fn on_message<F>(mut self, handler: F) -> Self
+    where
+        F: FnMut(&EthMessage<N>) -> MockAction<N> + Send + 'static,
+    {
+        self.handler = Box::new(handler);
+        self
+    }
+
+    /// Connects the mock peer and completes the `p2p` and `eth` handshakes.
+    ///
+    /// Returns the stream for the code under test and the handle of the mock peer, which is driven
+    /// by a spawned task until it disconnects. This must be called from within a tokio runtime.
+    pub async fn connect(self) -> Result<(MockEthStream<N>, MockEthPeer<N>), EthStreamError> {
+        let Self { status, peer_status, fork_filter, handler } = self;
+        let (local, remote) = tokio::io::duplex(TRANSPORT_BUFFER_SIZE);
+
+        let (local, remote) = futures::future::join(
+            UnauthedP2PStream::new(Framed::new(local, LengthDelimitedCodec::new()))
+                .handshake(mock_hello(status.version, 1)),
+            UnauthedP2PStream::new(Framed::new(remote, LengthDelimitedCodec::new()))
+                .handshake(mock_hello(peer_status.version, 2)),
+        )
+        .await;
+        let ((local, _), (remote, _)) = (local?, remote?);
+
+        let (local, remote) = futures::future::join(
+            UnauthedEthStream::new(local).handshake::<N>(status, fork_filter.clone()),
+            UnauthedEthStream::new(remote).handshake::<N>(peer_status, fork_filter),
+        )
+        .await;
+        let ((local, _), (remote, _)) = (local?, remote?);
+
+        Ok((local, MockEthPeer::spawn(remote, handler)))
+    }
+
+    /// Connects the mock peer to the node listening on the given address and completes the
+    /// encryption, `p2p` and `eth` handshakes like a real peer.
+    ///
+    /// The mock peer is attached to a regular session of the node, so the status set with
+    /// [`peer_status`](Self::peer_status) and the [`fork_filter`](Self::fork_filter) must match the
+    /// chain of the node. This must be called from within a tokio runtime.
+    pub async fn connect_to(
+        self,
+        addr: SocketAddr,
+        remote_id: PeerId,
+        secret_key: SecretKey,
+    ) -> Result<MockEthPeer<N>, EthStreamError> {
+        let Self { peer_status, fork_filter, handler, .. } = self;
+        let stream = TcpStream::connect(addr).await.map_err(P2PStreamError::from)?;
+        let transport = ECIESStream::connect(stream, secret_key, remote_id)
+            .await
+            .map_err(|err| P2PStreamError::Io(io::Error::other(err)))?;
+
+        let hello = HelloMessageWithProtocols {
+            id: pk2id(&secret_key.public_key(SECP256K1)),
+            ..mock_hello(peer_status.version, 0)
+        };
+        let (stream, _) = UnauthedP2PStream::new(transport).handshake(hello).await?;
+        let (stream, _) =
+            UnauthedEthStream::new(stream).handshake::<N>(peer_status, fork_filter).await?;
+
+        Ok(MockEthPeer::spawn(stream, handler))
+    }
+}
+
+impl<N: NetworkPrimitives> fmt::Debug for MockEthPeerBuilder<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MockEthPeerBuilder")
+            .field("status", &self.status)
+            .field("peer_status", &self.peer_status)
+            .field("fork_filter", &self.fork_filter)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Handle to an in-process eth peer with scriptable responses.
+///
+/// See the [module docs](self) for more details.
+#[derive(Debug)]
+pub struct MockEthPeer<N: NetworkPrimitives = EthNetworkPrimitives> {
+    /// Sends commands to the mock peer task.
+    commands: UnboundedSender<MockCommand<N>>,
+    /// Messages received by the mock peer.
+    received: UnboundedReceiver<EthMessage<N>>,
+}
+
+impl<N: NetworkPrimitives> MockEthPeer<N> {
+    /// Returns a builder for a peer speaking the given eth version.
+    pub fn builder(version: EthVersion) -> MockEthPeerBuilder<N> {
+        MockEthPeerBuilder::new(version)
+    }
+
+    /// Spawns the task driving the mock peer on the given stream.
+    fn spawn<S>(stream: EthStream<P2PStream<S>, N>, handler: MockHandler<N>) -> Self
+    where
+        S: Stream<Item = io::Result<BytesMut>>
+            + Sink<Bytes, Error = io::Error>
+            + Unpin
+            + Send
+            + Sync
+            + 'static,
+    {
+        let (commands_tx, commands_rx) = mpsc::unbounded_channel();
+        let (received_tx, received_rx) = mpsc::unbounded_channel();
+        tokio::spawn(run_mock_peer(stream, handler, commands_rx, received_tx));
+        Self { commands: commands_tx, received: received_rx }
+    }
+
+    /// Sends an unsolicited message, e.g. a transaction announcement, to the code under test.
+    pub fn send(&self, msg: EthMessage<N>) {
+        let _ = self.commands.send(MockCommand::Send(msg));
+    }
+
+    /// Disconnects from the code under test with the given reason.
+    pub fn disconnect(&self, reason: DisconnectReason) {
+        let _ = self.commands.send(MockCommand::Disconnect(reason));
+    }
+
+    /// Returns the next message received by the mock peer.
+    ///
+    /// Returns `None` once the mock peer is disconnected and all received messages were returned.
+    pub async fn next_received(&mut self) -> Option<EthMessage<N>> {
+        self.received.recv().await
+    }
+}
+
+/// Returns the hello message of one side of the mock connection.
+fn mock_hello(version: EthVersion, id: u8) -> HelloMessageWithProtocols {
+    HelloMessageWithProtocols {
+        protocol_version: ProtocolVersion::V5,
+        client_version: "reth/mock".to_string(),
+        protocols: vec![version.into()],
+        port: DEFAULT_TCP_PORT,
+        id: B512::with_last_byte(id),
+    }
+}
+
+/// Drives the mock peer until it disconnects or the connection is closed.
+async fn run_mock_peer<S, N>(
+    mut stream: EthStream<P2PStream<S>, N>,
+    mut handler: MockHandler<N>,
+    mut commands: UnboundedReceiver<MockCommand<N>>,
+    received: UnboundedSender<EthMessage<N>>,
+) where
+    S: Stream<Item = io::Result<BytesMut>> + Sink<Bytes, Error = io::Error> + Unpin + Send + Sync,
+    N: NetworkPrimitives,
+{
+    loop {
+        let action = tokio::select! {
+            msg = stream.next() => {
+                let Some(Ok(msg)) = msg else { return };
+                let action = handler(&msg);
+                let _ = received.send(msg);
+                action
+            }
+            Some(command) = commands.recv() => match command {
+                MockCommand::Send(msg) => MockAction::Reply(msg),
+                MockCommand::Disconnect(reason) => MockAction::Disconnect(reason),
+            },
+        };
+
+        let res = match action {
+            MockAction::Reply(msg) => stream.send(msg).await,
+            MockAction::ReplyAfter(delay, msg) => {
+                tokio::time::sleep(delay).await;
+                stream.send(msg).await
+            }
+            MockAction::Ignore => Ok(()),
+            MockAction::Disconnect(reason) => {
+                let _ = stream.disconnect(reason).await;
+                return
+            }
+        };
+        if res.is_err() {
+            return
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BlockHeaders, GetBlockHeaders, RequestPair};
+    use alloy_consensus::Header;
+    use alloy_eips::BlockHashOrNumber;
+    use alloy_primitives::B256;
+
+    fn get_headers(request_id: u64) -> EthMessage {
+        EthMessage::GetBlockHeaders(RequestPair {
+            request_id,
+            message: GetBlockHeaders {
+                start_block: BlockHashOrNumber::Number(0),
+                limit: 1,
+                skip: 0,
+                direction: Default::default(),
+            },
+        })
+    }
+
+    #[tokio::test]
+    async fn replies_to_requests() {
+        for version in [EthVersion::Eth66, EthVersion::Eth67, EthVersion::Eth68, EthVersion::Eth69]
+        {
+            let (mut stream, mut peer) = MockEthPeer::builder(version)
+                .on_message(|msg| match msg {
+                    EthMessage::GetBlockHeaders(req) => {
+                        MockAction::Reply(EthMessage::BlockHeaders(RequestPair {
+                            request_id: req.request_id,
+                            message: BlockHeaders(vec![Header::default()]),
+                        }))
+                    }
+                    _ => MockAction::Ignore,
+                })
+                .connect()
+                .await
+                .unwrap();
+            assert_eq!(stream.version(), version);
+
+            stream.send(get_headers(7)).await.unwrap();
+            let EthMessage::BlockHeaders(res) = stream.next().await.unwrap().unwrap() else {
+                panic!("expected block headers")
+            };
+            assert_eq!(res.request_id, 7);
+            assert_eq!(res.message.0, vec![Header::default()]);
+            assert_eq!(peer.next_received().await, Some(get_headers(7)));
+        }
+    }
+
+    #[tokio::test]
+    async fn disconnects_on_request() {
+        let (mut stream, _peer) = MockEthPeer::<EthNetworkPrimitives>::builder(EthVersion::Eth68)
+            .on_message(|_| MockAction::Disconnect(DisconnectReason::UselessPeer))
+            .connect()
+            .await
+            .unwrap();
+
+        stream.send(get_headers(1)).await.unwrap();
+        assert!(matches!(stream.next().await, None | Some(Err(_))));
+    }
+
+    #[tokio::test]
+    async fn rejects_different_genesis() {
+        let status =
+            UnifiedStatus::builder().version(EthVersion::Eth68).genesis(B256::ZERO).build();
+        let res = MockEthPeer::<EthNetworkPrimitives>::builder(EthVersion::Eth68)
+            .peer_status(status)
+            .connect()
+            .await;
+        assert!(res.is_err());
+    }
+}
@@ -2,6 +2,9 @@
 
 #![allow(missing_docs)]
 
+mod mock_peer;
+pub use mock_peer::{MockAction, MockEthPeer, MockEthPeerBuilder, MockEthStream, MockTransport};
+
 use crate::{
     hello::DEFAULT_TCP_PORT, EthVersion, HelloMessageWithProtocols, P2PStream, ProtocolVersion,
     Status, StatusMessage, UnauthedP2PStream, UnifiedStatus,
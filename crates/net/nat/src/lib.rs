@@ -1,4 +1,4 @@
-//! Helpers for resolving the external IP.
+//! Helpers for resolving the external IP and mapping ports on the gateway, see [`port_mapping`].
 //!
 //! ## Feature Flags
 //!
@@ -13,8 +13,12 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
 pub mod net_if;
+pub mod port_mapping;
 
 pub use net_if::{NetInterfaceError, DEFAULT_NET_IF_NAME};
+pub use port_mapping::{
+    PortMapping, PortMappingConfig, PortMappingError, PortMappingProtocol, PortMappingService,
+};
 
 use std::{
     fmt,
@@ -37,6 +41,9 @@ use serde_with::{DeserializeFromStr, SerializeDisplay};
 const EXTERNAL_IP_APIS: &[&str] =
     &["https://ipinfo.io/ip", "https://icanhazip.com", "https://ifconfig.me"];
 
+/// The timeout of requesting the external IP from a `UPnP` gateway.
+const UPNP_TIMEOUT: Duration = Duration::from_secs(5);
+
 /// All builtin resolvers.
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Default, Hash)]
 #[cfg_attr(feature = "serde", derive(SerializeDisplay, DeserializeFromStr))]
@@ -44,7 +51,7 @@ pub enum NatResolver {
     /// Resolve with any available resolver.
     #[default]
     Any,
-    /// Resolve external IP via `UPnP`.
+    /// Resolve external IP via `UPnP`, falling back to a network request.
     Upnp,
     /// Resolve external IP via a network request.
     PublicIp,
@@ -202,7 +209,14 @@ pub async fn external_ip() -> Option<IpAddr> {
 /// Given a [`NatResolver`] attempts to produce an IP address (best effort).
 pub async fn external_addr_with(resolver: NatResolver) -> Option<IpAddr> {
     match resolver {
-        NatResolver::Any | NatResolver::Upnp | NatResolver::PublicIp => resolve_external_ip().await,
+        NatResolver::Any | NatResolver::PublicIp => resolve_external_ip().await,
+        NatResolver::Upnp => match port_mapping::upnp_external_ip(UPNP_TIMEOUT).await {
+            Ok(ip) => Some(ip),
+            Err(err) => {
+                debug!(target: "net::nat", %err, "Failed to resolve external IP via UPnP");
+                resolve_external_ip().await
+            }
+        },
         NatResolver::ExternalIp(ip) => Some(ip),
         NatResolver::NetIf => resolve_net_if_ip(DEFAULT_NET_IF_NAME)
             .inspect_err(|err| {
@@ -0,0 +1,386 @@
+//! Port mapping with `UPnP` and NAT-PMP.
+//!
+//! Nodes behind a consumer router don't receive inbound connections unless the router forwards
+//! the node's ports. The [`PortMappingService`] asks the gateway to forward the configured ports,
+//! refreshes the leases before they expire and reports the external IP address of the gateway and
+//! the external ports, so that they can be announced to other nodes.
+
+mod natpmp;
+mod upnp;
+
+use std::{
+    fmt, io,
+    net::{IpAddr, Ipv4Addr, SocketAddr, SocketAddrV4, UdpSocket},
+    time::Duration,
+};
+use tokio::{sync::mpsc, task::JoinHandle};
+use tracing::{debug, info};
+
+/// The minimum interval in which leases are refreshed.
+const MIN_REFRESH_INTERVAL: Duration = Duration::from_secs(10);
+
+/// The transport protocol of a mapped port.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PortMappingProtocol {
+    /// TCP, e.g. the `RLPx` port.
+    Tcp,
+    /// UDP, e.g. the discovery port.
+    Udp,
+}
+
+impl fmt::Display for PortMappingProtocol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tcp => f.write_str("TCP"),
+            Self::Udp => f.write_str("UDP"),
+        }
+    }
+}
+
+/// The ports mapped on the gateway, reported by the [`PortMappingService`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PortMapping {
+    /// The external IP address of the gateway.
+    pub external_ip: IpAddr,
+    /// The mapped ports, as protocol, internal port and external port.
+    pub ports: Vec<(PortMappingProtocol, u16, u16)>,
+}
+
+impl PortMapping {
+    /// Returns the external port that the internal port is mapped to, if it's mapped.
+    pub fn external_port(&self, protocol: PortMappingProtocol, internal_port: u16) -> Option<u16> {
+        self.ports.iter().find_map(|&(mapped_protocol, internal, external)| {
+            (mapped_protocol == protocol && internal == internal_port).then_some(external)
+        })
+    }
+}
+
+/// Configures the [`PortMappingService`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PortMappingConfig {
+    /// The duration of the requested leases, which are refreshed when half of it elapsed.
+    pub lease_duration: Duration,
+    /// The timeout of the gateway discovery and of each request to the gateway.
+    pub request_timeout: Duration,
+    /// The interval in which the ports are mapped again after mapping failed.
+    pub retry_interval: Duration,
+    /// The gateway to request NAT-PMP mappings from, by default the gateway of the default route.
+    pub nat_pmp_gateway: Option<Ipv4Addr>,
+}
+
+impl Default for PortMappingConfig {
+    fn default() -> Self {
+        Self {
+            lease_duration: Duration::from_secs(60 * 60),
+            request_timeout: Duration::from_secs(5),
+            retry_interval: Duration::from_secs(60 * 5),
+            nat_pmp_gateway: None,
+        }
+    }
+}
+
+/// Errors of port mapping requests.
+#[derive(Debug, thiserror::Error)]
+pub enum PortMappingError {
+    /// No gateway was found.
+    #[error("no UPnP or NAT-PMP gateway found")]
+    NoGateway,
+    /// The gateway didn't respond in time.
+    #[error("gateway request timed out")]
+    Timeout,
+    /// The gateway responded with a NAT-PMP result code other than success.
+    #[error("NAT-PMP request failed with result code {0}")]
+    NatPmp(u16),
+    /// The gateway failed to perform a `UPnP` action.
+    #[error("UPnP action {action} failed with error {code}: {description}")]
+    Upnp {
+        /// The failed action.
+        action: String,
+        /// The `UPnP` error code, or the HTTP status code if the gateway didn't return one.
+        code: u16,
+        /// The description of the error.
+        description: String,
+    },
+    /// The gateway responded with an unexpected message.
+    #[error("invalid gateway response: {0}")]
+    InvalidResponse(String),
+    /// Failed to send a request to the gateway.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    /// Failed to send a HTTP request to the gateway.
+    #[error(transparent)]
+    Http(#[from] reqwest::Error),
+}
+
+/// A gateway that maps ports.
+#[derive(Debug, Clone)]
+enum Gateway {
+    /// An Internet Gateway Device controlled over `UPnP`.
+    Upnp(upnp::Gateway),
+    /// A NAT-PMP gateway at the given address.
+    NatPmp(SocketAddrV4),
+}
+
+impl Gateway {
+    /// Searches for a `UPnP` gateway, and falls back to NAT-PMP if there is none.
+    async fn discover(config: &PortMappingConfig) -> Result<Self, PortMappingError> {
+        match upnp::Gateway::discover(config.request_timeout).await {
+            Ok(gateway) => return Ok(Self::Upnp(gateway)),
+            Err(err) => debug!(target: "net::nat", %err, "No UPnP gateway found"),
+        }
+
+        let gateway =
+            config.nat_pmp_gateway.or_else(default_gateway).ok_or(PortMappingError::NoGateway)?;
+        let gateway = SocketAddrV4::new(gateway, natpmp::NAT_PMP_PORT);
+        natpmp::external_address(gateway, config.request_timeout).await?;
+        Ok(Self::NatPmp(gateway))
+    }
+
+    /// Maps the ports, preferably to the same external ports, and returns the mapping and the
+    /// duration of the shortest lease.
+    ///
+    /// `UPnP` gateways map the ports to the same external ports, NAT-PMP gateways may grant other
+    /// external ports.
+    async fn map_ports(
+        &self,
+        ports: &[(PortMappingProtocol, u16)],
+        config: &PortMappingConfig,
+    ) -> Result<(PortMapping, Duration), PortMappingError> {
+        let timeout = config.request_timeout;
+        let mut mapped = Vec::with_capacity(ports.len());
+        match self {
+            Self::Upnp(gateway) => {
+                let local_ip = local_ip(gateway.addr)?;
+                for &(protocol, port) in ports {
+                    let internal = SocketAddrV4::new(local_ip, port);
+                    gateway
+                        .add_port_mapping(protocol, port, internal, config.lease_duration, timeout)
+                        .await?;
+                    mapped.push((protocol, port, port));
+                }
+                let external_ip = gateway.external_ip(timeout).await?.into();
+                Ok((PortMapping { external_ip, ports: mapped }, config.lease_duration))
+            }
+            Self::NatPmp(gateway) => {
+                let mut lease_duration = config.lease_duration;
+                for &(protocol, port) in ports {
+                    let (external_port, lifetime) = natpmp::map_port(
+                        *gateway,
+                        protocol,
+                        port,
+                        port,
+                        config.lease_duration,
+                        timeout,
+                    )
+                    .await?;
+                    if external_port != port {
+                        debug!(target: "net::nat", %protocol, port, external_port, "Gateway mapped port to a different external port");
+                    }
+                    mapped.push((protocol, port, external_port));
+                    lease_duration = lease_duration.min(lifetime);
+                }
+                let external_ip = natpmp::external_address(*gateway, timeout).await?.into();
+                Ok((PortMapping { external_ip, ports: mapped }, lease_duration))
+            }
+        }
+    }
+
+    /// Deletes the mappings of the ports.
+    async fn unmap_ports(&self, ports: &[(PortMappingProtocol, u16)], timeout: Duration) {
+        for &(protocol, port) in ports {
+            let res = match self {
+                Self::Upnp(gateway) => gateway.delete_port_mapping(protocol, port, timeout).await,
+                Self::NatPmp(gateway) => {
+                    natpmp::map_port(*gateway, protocol, port, 0, Duration::ZERO, timeout)
+                        .await
+                        .map(drop)
+                }
+            };
+            if let Err(err) = res {
+                debug!(target: "net::nat", %protocol, port, %err, "Failed to delete port mapping");
+            }
+        }
+    }
+}
+
+/// Maps ports on the gateway of the local network with `UPnP` or NAT-PMP.
+///
+/// The service keeps the leases alive until the receiver of the mapping updates is dropped, after
+/// which the mappings are deleted.
+#[derive(Debug)]
+#[must_use = "Service does nothing unless spawned"]
+pub struct PortMappingService {
+    /// The ports to map.
+    ports: Vec<(PortMappingProtocol, u16)>,
+    /// The configuration of the service.
+    config: PortMappingConfig,
+    /// The gateway that mapped the ports, if any.
+    gateway: Option<Gateway>,
+    /// The last reported mapping.
+    mapping: Option<PortMapping>,
+    /// Reports changes of the mapping.
+    updates: mpsc::Sender<PortMapping>,
+}
+
+impl PortMappingService {
+    /// Creates a new service that maps the given ports, and the receiver of the changes of the
+    /// mapping, i.e. the external IP address of the gateway and the external ports.
+    pub fn new(
+        ports: Vec<(PortMappingProtocol, u16)>,
+        config: PortMappingConfig,
+    ) -> (Self, mpsc::Receiver<PortMapping>) {
+        let (updates, rx) = mpsc::channel(1);
+        (Self { ports, config, gateway: None, mapping: None, updates }, rx)
+    }
+
+    /// Spawns the service onto a new task.
+    pub fn spawn(self) -> JoinHandle<()> {
+        tokio::task::spawn(self.run())
+    }
+
+    /// Maps the ports and refreshes the leases until the receiver of the updates is dropped.
+    pub async fn run(mut self) {
+        loop {
+            let delay = match self.map_ports().await {
+                Ok((mapping, lease_duration)) => {
+                    if self.mapping.as_ref() != Some(&mapping) {
+                        info!(target: "net::nat", external_ip = %mapping.external_ip, ports = ?mapping.ports, "Mapped ports on gateway");
+                        self.mapping = Some(mapping.clone());
+                        if self.updates.send(mapping).await.is_err() {
+                            break
+                        }
+                    }
+                    // guard against gateways that grant very short leases
+                    (lease_duration / 2).max(MIN_REFRESH_INTERVAL)
+                }
+                Err(err) => {
+                    debug!(target: "net::nat", %err, "Failed to map ports");
+                    self.config.retry_interval
+                }
+            };
+
+            tokio::select! {
+                _ = tokio::time::sleep(delay) => {}
+                _ = self.updates.closed() => break,
+            }
+        }
+
+        if let Some(gateway) = &self.gateway {
+            gateway.unmap_ports(&self.ports, self.config.request_timeout).await;
+        }
+    }
+
+    /// Maps the ports on the known gateway, or on a newly discovered one if there is none or
+    /// mapping on it failed previously.
+    async fn map_ports(&mut self) -> Result<(PortMapping, Duration), PortMappingError> {
+        let gateway = match self.gateway.take() {
+            Some(gateway) => gateway,
+            None => Gateway::discover(&self.config).await?,
+        };
+        let res = gateway.map_ports(&self.ports, &self.config).await;
+        if res.is_ok() {
+            self.gateway = Some(gateway);
+        }
+        res
+    }
+}
+
+/// Requests the external IP address from a `UPnP` gateway of the local network.
+pub async fn upnp_external_ip(timeout: Duration) -> Result<IpAddr, PortMappingError> {
+    let gateway = upnp::Gateway::discover(timeout).await?;
+    Ok(gateway.external_ip(timeout).await?.into())
+}
+
+/// Returns the local IPv4 address that the gateway is reached from.
+fn local_ip(gateway: SocketAddr) -> io::Result<Ipv4Addr> {
+    // connecting a UDP socket only selects the route, nothing is sent
+    let socket = UdpSocket::bind(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, 0))?;
+    socket.connect(gateway)?;
+    match socket.local_addr()?.ip() {
+        IpAddr::V4(ip) => Ok(ip),
+        IpAddr::V6(ip) => Err(io::Error::other(format!("unexpected IPv6 address {ip}"))),
+    }
+}
+
+/// Returns the gateway of the default IPv4 route, only supported on Linux.
+fn default_gateway() -> Option<Ipv4Addr> {
+    std::fs::read_to_string("/proc/net/route").ok().as_deref().and_then(parse_route_table)
+}
+
+/// Parses the gateway of the default route from the route table in `/proc/net/route`.
+fn parse_route_table(table: &str) -> Option<Ipv4Addr> {
+    table.lines().skip(1).find_map(|line| {
+        let mut columns = line.split_whitespace();
+        let destination = columns.nth(1)?;
+        let gateway = columns.next()?;
+        if destination != "00000000" {
+            return None
+        }
+        // the address is printed as integer in host byte order
+        let gateway = u32::from_str_radix(gateway, 16).ok()?;
+        Some(Ipv4Addr::from(gateway.to_ne_bytes())).filter(|gateway| !gateway.is_unspecified())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(target_endian = "little")]
+    fn parse_default_gateway() {
+        let table =
+            "Iface\tDestination\tGateway \tFlags\tRefCnt\tUse\tMetric\tMask\t\tMTU\tWindow\tIRTT
+eth0\t0000A8C0\t00000000\t0001\t0\t0\t100\t00FFFFFF\t0\t0\t0
+eth0\t00000000\t0100A8C0\t0003\t0\t0\t100\t00000000\t0\t0\t0
+";
+        assert_eq!(parse_route_table(table), Some(Ipv4Addr::new(192, 168, 0, 1)));
+        assert_eq!(
+            parse_route_table(table.lines().take(2).collect::<Vec<_>>().join("\n").as_str()),
+            None
+        );
+    }
+
+    /// Spawns a NAT-PMP gateway that maps every port to the next port.
+    async fn spawn_mock_nat_pmp_gateway() -> SocketAddrV4 {
+        let socket = tokio::net::UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let SocketAddr::V4(addr) = socket.local_addr().unwrap() else { unreachable!() };
+        tokio::spawn(async move {
+            let mut buf = [0u8; 12];
+            loop {
+                let (len, from) = socket.recv_from(&mut buf).await.unwrap();
+                let mut response = vec![0, buf[1] + 128, 0, 0, 0, 0, 0, 1];
+                if buf[1] == 0 {
+                    response.extend_from_slice(&[203, 0, 113, 7]);
+                } else {
+                    assert_eq!(len, 12);
+                    let internal_port = u16::from_be_bytes([buf[4], buf[5]]);
+                    response.extend_from_slice(&internal_port.to_be_bytes());
+                    response.extend_from_slice(&(internal_port + 1).to_be_bytes());
+                    response.extend_from_slice(&buf[8..12]);
+                }
+                socket.send_to(&response, from).await.unwrap();
+            }
+        });
+        addr
+    }
+
+    #[tokio::test]
+    async fn nat_pmp_reports_external_ports() {
+        let gateway = Gateway::NatPmp(spawn_mock_nat_pmp_gateway().await);
+        let ports = [
+            (PortMappingProtocol::Tcp, 30303),
+            (PortMappingProtocol::Udp, 30303),
+            (PortMappingProtocol::Udp, 9200),
+        ];
+
+        let (mapping, lease_duration) =
+            gateway.map_ports(&ports, &PortMappingConfig::default()).await.unwrap();
+        assert_eq!(mapping.external_ip, IpAddr::V4(Ipv4Addr::new(203, 0, 113, 7)));
+        assert_eq!(lease_duration, PortMappingConfig::default().lease_duration);
+        assert_eq!(mapping.external_port(PortMappingProtocol::Tcp, 30303), Some(30304));
+        assert_eq!(mapping.external_port(PortMappingProtocol::Udp, 30303), Some(30304));
+        assert_eq!(mapping.external_port(PortMappingProtocol::Udp, 9200), Some(9201));
+        assert_eq!(mapping.external_port(PortMappingProtocol::Tcp, 9200), None);
+    }
+}
@@ -0,0 +1,150 @@
+//! NAT Port Mapping Protocol, see [RFC 6886](https://datatracker.ietf.org/doc/html/rfc6886).
+
+use super::{PortMappingError, PortMappingProtocol};
+use std::{
+    net::{Ipv4Addr, SocketAddrV4},
+    time::{Duration, Instant},
+};
+use tokio::net::UdpSocket;
+
+/// The port NAT-PMP gateways listen on.
+pub(super) const NAT_PMP_PORT: u16 = 5351;
+
+/// The opcode of external address requests.
+const OPCODE_EXTERNAL_ADDRESS: u8 = 0;
+
+/// Responses carry the opcode of the request plus this offset.
+const RESPONSE_OPCODE_OFFSET: u8 = 128;
+
+/// The initial retransmission interval, which is doubled after every attempt.
+const INITIAL_RETRANSMISSION_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Requests the external address of the gateway.
+pub(super) async fn external_address(
+    gateway: SocketAddrV4,
+    timeout: Duration,
+) -> Result<Ipv4Addr, PortMappingError> {
+    let response = request(gateway, &[0, OPCODE_EXTERNAL_ADDRESS], timeout).await?;
+    decode_external_address(&response)
+}
+
+/// Requests a mapping of the internal port to the external port for the given lifetime, a zero
+/// lifetime deletes the mapping.
+///
+/// Returns the mapped external port and the lifetime granted by the gateway.
+pub(super) async fn map_port(
+    gateway: SocketAddrV4,
+    protocol: PortMappingProtocol,
+    internal_port: u16,
+    external_port: u16,
+    lifetime: Duration,
+    timeout: Duration,
+) -> Result<(u16, Duration), PortMappingError> {
+    let packet = encode_map_request(protocol, internal_port, external_port, lifetime);
+    let response = request(gateway, &packet, timeout).await?;
+    decode_map_response(protocol, &response)
+}
+
+/// Sends the request to the gateway, retransmitting it with a doubling interval until a response
+/// is received or the timeout elapsed.
+async fn request(
+    gateway: SocketAddrV4,
+    packet: &[u8],
+    timeout: Duration,
+) -> Result<Vec<u8>, PortMappingError> {
+    let socket = UdpSocket::bind(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, 0)).await?;
+    socket.connect(gateway).await?;
+
+    let deadline = Instant::now() + timeout;
+    let mut interval = INITIAL_RETRANSMISSION_INTERVAL;
+    let mut buf = [0u8; 16];
+    loop {
+        socket.send(packet).await?;
+        let wait = interval.min(deadline.saturating_duration_since(Instant::now()));
+        match tokio::time::timeout(wait, socket.recv(&mut buf)).await {
+            Ok(len) => return Ok(buf[..len?].to_vec()),
+            Err(_) if Instant::now() >= deadline => return Err(PortMappingError::Timeout),
+            Err(_) => interval *= 2,
+        }
+    }
+}
+
+/// Returns the opcode of map requests of the protocol.
+const fn map_opcode(protocol: PortMappingProtocol) -> u8 {
+    match protocol {
+        PortMappingProtocol::Udp => 1,
+        PortMappingProtocol::Tcp => 2,
+    }
+}
+
+/// Encodes a request to map the internal port.
+fn encode_map_request(
+    protocol: PortMappingProtocol,
+    internal_port: u16,
+    external_port: u16,
+    lifetime: Duration,
+) -> [u8; 12] {
+    let lifetime = u32::try_from(lifetime.as_secs()).unwrap_or(u32::MAX);
+    let mut request = [0u8; 12];
+    request[1] = map_opcode(protocol);
+    request[4..6].copy_from_slice(&internal_port.to_be_bytes());
+    request[6..8].copy_from_slice(&external_port.to_be_bytes());
+    request[8..12].copy_from_slice(&lifetime.to_be_bytes());
+    request
+}
+
+/// Checks the version, opcode, length and result code of the response.
+fn check_response(response: &[u8], opcode: u8, len: usize) -> Result<(), PortMappingError> {
+    if response.len() < len || response[0] != 0 || response[1] != opcode + RESPONSE_OPCODE_OFFSET {
+        return Err(PortMappingError::InvalidResponse(format!(
+            "unexpected NAT-PMP response {response:?}"
+        )))
+    }
+    match u16::from_be_bytes([response[2], response[3]]) {
+        0 => Ok(()),
+        code => Err(PortMappingError::NatPmp(code)),
+    }
+}
+
+/// Decodes the response to an external address request.
+fn decode_external_address(response: &[u8]) -> Result<Ipv4Addr, PortMappingError> {
+    check_response(response, OPCODE_EXTERNAL_ADDRESS, 12)?;
+    Ok(Ipv4Addr::new(response[8], response[9], response[10], response[11]))
+}
+
+/// Decodes the response to a map request, returning the external port and lifetime.
+fn decode_map_response(
+    protocol: PortMappingProtocol,
+    response: &[u8],
+) -> Result<(u16, Duration), PortMappingError> {
+    check_response(response, map_opcode(protocol), 16)?;
+    let external_port = u16::from_be_bytes([response[10], response[11]]);
+    let lifetime = u32::from_be_bytes([response[12], response[13], response[14], response[15]]);
+    Ok((external_port, Duration::from_secs(lifetime.into())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_decode() {
+        let request =
+            encode_map_request(PortMappingProtocol::Tcp, 30303, 30303, Duration::from_secs(3600));
+        assert_eq!(request, [0, 2, 0, 0, 0x76, 0x5f, 0x76, 0x5f, 0, 0, 0x0e, 0x10]);
+
+        let response = [0, 130, 0, 0, 0, 0, 0, 1, 0x76, 0x5f, 0x76, 0x60, 0, 0, 0x07, 0x08];
+        assert_eq!(
+            decode_map_response(PortMappingProtocol::Tcp, &response).unwrap(),
+            (30304, Duration::from_secs(1800))
+        );
+        assert!(decode_map_response(PortMappingProtocol::Udp, &response).is_err());
+
+        let response = [0, 128, 0, 0, 0, 0, 0, 1, 203, 0, 113, 7];
+        assert_eq!(decode_external_address(&response).unwrap(), Ipv4Addr::new(203, 0, 113, 7));
+
+        // not authorized
+        let response = [0, 128, 0, 2, 0, 0, 0, 1, 0, 0, 0, 0];
+        assert!(matches!(decode_external_address(&response), Err(PortMappingError::NatPmp(2))));
+    }
+}
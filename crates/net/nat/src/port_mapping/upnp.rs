@@ -0,0 +1,292 @@
+//! Port mapping on `UPnP` Internet Gateway Devices.
+
+use super::{PortMappingError, PortMappingProtocol};
+use reqwest::{Client, Url};
+use std::{
+    net::{Ipv4Addr, SocketAddr, SocketAddrV4},
+    time::{Duration, Instant},
+};
+use tokio::net::UdpSocket;
+use tracing::debug;
+
+/// The SSDP multicast address gateways are searched on.
+const SSDP_ADDR: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(239, 255, 255, 250), 1900);
+
+/// The device type of Internet Gateway Devices.
+const SEARCH_TARGET: &str = "urn:schemas-upnp-org:device:InternetGatewayDevice:1";
+
+/// The services that control the WAN connection of a gateway, in order of preference.
+const WAN_CONNECTION_SERVICES: &[&str] = &[
+    "urn:schemas-upnp-org:service:WANIPConnection:1",
+    "urn:schemas-upnp-org:service:WANIPConnection:2",
+    "urn:schemas-upnp-org:service:WANPPPConnection:1",
+];
+
+/// The error code of gateways that only support permanent leases.
+const ONLY_PERMANENT_LEASES_SUPPORTED: u16 = 725;
+
+/// The description of the port mappings.
+const PORT_MAPPING_DESCRIPTION: &str = "reth";
+
+/// An Internet Gateway Device that was discovered over SSDP.
+#[derive(Debug, Clone)]
+pub(super) struct Gateway {
+    /// The address the gateway responded to the search from.
+    pub(super) addr: SocketAddr,
+    /// The URL of the WAN connection service.
+    control_url: Url,
+    /// The type of the WAN connection service.
+    service: &'static str,
+}
+
+impl Gateway {
+    /// Searches the local network for a gateway, returning the first that responds with a WAN
+    /// connection service.
+    pub(super) async fn discover(timeout: Duration) -> Result<Self, PortMappingError> {
+        let socket = UdpSocket::bind(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, 0)).await?;
+        let search = format!(
+            "M-SEARCH * HTTP/1.1\r\nHOST: {SSDP_ADDR}\r\nST: {SEARCH_TARGET}\r\nMAN: \"ssdp:discover\"\r\nMX: 2\r\n\r\n"
+        );
+        socket.send_to(search.as_bytes(), SSDP_ADDR).await?;
+
+        let deadline = Instant::now() + timeout;
+        let mut buf = [0u8; 2048];
+        loop {
+            let wait = deadline.saturating_duration_since(Instant::now());
+            let (len, addr) = tokio::time::timeout(wait, socket.recv_from(&mut buf))
+                .await
+                .map_err(|_| PortMappingError::Timeout)??;
+            let Some(location) = std::str::from_utf8(&buf[..len]).ok().and_then(parse_location)
+            else {
+                continue
+            };
+
+            match Self::from_description(addr, location, timeout).await {
+                Ok(gateway) => return Ok(gateway),
+                Err(err) => {
+                    debug!(target: "net::nat", %addr, %err, "Skipping UPnP device");
+                }
+            }
+        }
+    }
+
+    /// Fetches the description of the device and looks up its WAN connection service.
+    async fn from_description(
+        addr: SocketAddr,
+        location: Url,
+        timeout: Duration,
+    ) -> Result<Self, PortMappingError> {
+        let description =
+            client(timeout)?.get(location.clone()).send().await?.error_for_status()?.text().await?;
+        let (service, control_url) = parse_control_url(&description).ok_or_else(|| {
+            PortMappingError::InvalidResponse(format!("no WAN connection service at {location}"))
+        })?;
+        let control_url = location.join(control_url).map_err(|err| {
+            PortMappingError::InvalidResponse(format!("invalid control URL {control_url}: {err}"))
+        })?;
+        Ok(Self { addr, control_url, service })
+    }
+
+    /// Maps the external port to the internal address for the lease duration.
+    ///
+    /// Falls back to a permanent mapping if the gateway only supports those.
+    pub(super) async fn add_port_mapping(
+        &self,
+        protocol: PortMappingProtocol,
+        external_port: u16,
+        internal: SocketAddrV4,
+        lease_duration: Duration,
+        timeout: Duration,
+    ) -> Result<(), PortMappingError> {
+        let args = |lease_duration: u64| {
+            [
+                ("NewRemoteHost", String::new()),
+                ("NewExternalPort", external_port.to_string()),
+                ("NewProtocol", protocol.to_string()),
+                ("NewInternalPort", internal.port().to_string()),
+                ("NewInternalClient", internal.ip().to_string()),
+                ("NewEnabled", "1".to_string()),
+                ("NewPortMappingDescription", PORT_MAPPING_DESCRIPTION.to_string()),
+                ("NewLeaseDuration", lease_duration.to_string()),
+            ]
+        };
+
+        match self.call("AddPortMapping", &args(lease_duration.as_secs()), timeout).await {
+            Err(PortMappingError::Upnp { code: ONLY_PERMANENT_LEASES_SUPPORTED, .. }) => {
+                self.call("AddPortMapping", &args(0), timeout).await.map(drop)
+            }
+            res => res.map(drop),
+        }
+    }
+
+    /// Deletes the mapping of the external port.
+    pub(super) async fn delete_port_mapping(
+        &self,
+        protocol: PortMappingProtocol,
+        external_port: u16,
+        timeout: Duration,
+    ) -> Result<(), PortMappingError> {
+        self.call(
+            "DeletePortMapping",
+            &[
+                ("NewRemoteHost", String::new()),
+                ("NewExternalPort", external_port.to_string()),
+                ("NewProtocol", protocol.to_string()),
+            ],
+            timeout,
+        )
+        .await
+        .map(drop)
+    }
+
+    /// Requests the external IP address of the gateway.
+    pub(super) async fn external_ip(
+        &self,
+        timeout: Duration,
+    ) -> Result<Ipv4Addr, PortMappingError> {
+        let response = self.call("GetExternalIPAddress", &[], timeout).await?;
+        xml_element(&response, "NewExternalIPAddress")
+            .and_then(|ip| ip.trim().parse().ok())
+            .ok_or_else(|| {
+                PortMappingError::InvalidResponse(format!("no external IP address in {response}"))
+            })
+    }
+
+    /// Invokes the SOAP action on the WAN connection service and returns the response body.
+    async fn call(
+        &self,
+        action: &str,
+        args: &[(&str, String)],
+        timeout: Duration,
+    ) -> Result<String, PortMappingError> {
+        let args: String =
+            args.iter().map(|(name, value)| format!("<{name}>{value}</{name}>")).collect();
+        let body = format!(
+            r#"<?xml version="1.0"?><s:Envelope xmlns:s="http://schemas.xmlsoap.org/soap/envelope/" s:encodingStyle="http://schemas.xmlsoap.org/soap/encoding/"><s:Body><u:{action} xmlns:u="{}">{args}</u:{action}></s:Body></s:Envelope>"#,
+            self.service
+        );
+
+        let response = client(timeout)?
+            .post(self.control_url.clone())
+            .header("Content-Type", "text/xml; charset=\"utf-8\"")
+            .header("SOAPAction", format!("\"{}#{action}\"", self.service))
+            .body(body)
+            .send()
+            .await?;
+        let status = response.status();
+        let text = response.text().await?;
+        if !status.is_success() {
+            return Err(PortMappingError::Upnp {
+                action: action.to_string(),
+                code: xml_element(&text, "errorCode")
+                    .and_then(|code| code.trim().parse().ok())
+                    .unwrap_or(status.as_u16()),
+                description: xml_element(&text, "errorDescription").unwrap_or_default().to_string(),
+            })
+        }
+        Ok(text)
+    }
+}
+
+/// Returns a HTTP client with the given timeout.
+fn client(timeout: Duration) -> Result<Client, PortMappingError> {
+    Ok(Client::builder().timeout(timeout).build()?)
+}
+
+/// Parses the description URL from the `LOCATION` header of a search response.
+fn parse_location(response: &str) -> Option<Url> {
+    response.lines().find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        name.trim().eq_ignore_ascii_case("location").then(|| value.trim().parse().ok()).flatten()
+    })
+}
+
+/// Returns the preferred WAN connection service of the device description and its control URL.
+fn parse_control_url(description: &str) -> Option<(&'static str, &str)> {
+    let services = description
+        .split("<service>")
+        .skip(1)
+        .filter_map(|service| {
+            let service = service.split("</service>").next()?;
+            Some((xml_element(service, "serviceType")?.trim(), xml_element(service, "controlURL")?))
+        })
+        .collect::<Vec<_>>();
+
+    WAN_CONNECTION_SERVICES.iter().find_map(|wanted| {
+        services
+            .iter()
+            .find(|(service, _)| service == wanted)
+            .map(|(_, control_url)| (*wanted, control_url.trim()))
+    })
+}
+
+/// Returns the text of the first element with the given tag, ignoring namespace prefixes.
+fn xml_element<'a>(body: &'a str, tag: &str) -> Option<&'a str> {
+    let open = format!("{tag}>");
+    let (index, _) =
+        body.match_indices(&open).find(|(index, _)| body[..*index].ends_with(['<', ':']))?;
+    let start = index + open.len();
+    let end = start + body[start..].find("</")?;
+    Some(&body[start..end])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_search_response() {
+        let response = "HTTP/1.1 200 OK\r\nCACHE-CONTROL: max-age=120\r\nST: urn:schemas-upnp-org:device:InternetGatewayDevice:1\r\nLocation: http://192.168.1.1:5000/rootDesc.xml\r\n\r\n";
+        assert_eq!(
+            parse_location(response),
+            Some("http://192.168.1.1:5000/rootDesc.xml".parse().unwrap())
+        );
+        assert_eq!(parse_location("HTTP/1.1 200 OK\r\n\r\n"), None);
+    }
+
+    #[test]
+    fn parse_description() {
+        let description = r#"<?xml version="1.0"?>
+<root xmlns="urn:schemas-upnp-org:device-1-0">
+  <device>
+    <deviceType>urn:schemas-upnp-org:device:InternetGatewayDevice:1</deviceType>
+    <serviceList>
+      <service>
+        <serviceType>urn:schemas-upnp-org:service:Layer3Forwarding:1</serviceType>
+        <controlURL>/ctl/L3F</controlURL>
+      </service>
+    </serviceList>
+    <deviceList>
+      <device>
+        <serviceList>
+          <service>
+            <serviceType>urn:schemas-upnp-org:service:WANPPPConnection:1</serviceType>
+            <controlURL>/ctl/PPPConn</controlURL>
+          </service>
+          <service>
+            <serviceType>urn:schemas-upnp-org:service:WANIPConnection:1</serviceType>
+            <controlURL>/ctl/IPConn</controlURL>
+          </service>
+        </serviceList>
+      </device>
+    </deviceList>
+  </device>
+</root>"#;
+        assert_eq!(
+            parse_control_url(description),
+            Some(("urn:schemas-upnp-org:service:WANIPConnection:1", "/ctl/IPConn"))
+        );
+        assert_eq!(parse_control_url("<root></root>"), None);
+    }
+
+    #[test]
+    fn parse_soap_response() {
+        let response = r#"<?xml version="1.0"?>
+<s:Envelope xmlns:s="http://schemas.xmlsoap.org/soap/envelope/"><s:Body><u:GetExternalIPAddressResponse xmlns:u="urn:schemas-upnp-org:service:WANIPConnection:1"><NewExternalIPAddress>203.0.113.7</NewExternalIPAddress></u:GetExternalIPAddressResponse></s:Body></s:Envelope>"#;
+        assert_eq!(xml_element(response, "NewExternalIPAddress"), Some("203.0.113.7"));
+
+        let fault = "<s:Fault><detail><UPnPError><errorCode>725</errorCode><errorDescription>OnlyPermanentLeasesSupported</errorDescription></UPnPError></detail></s:Fault>";
+        assert_eq!(xml_element(fault, "errorCode"), Some("725"));
+        assert_eq!(xml_element(fault, "ExternalIPAddress"), None);
+    }
+}
@@ -9,7 +9,7 @@ use reth_eth_wire_types::{
 use reth_ethereum_forks::ForkId;
 use reth_network_p2p::error::{RequestError, RequestResult};
 use reth_network_peers::PeerId;
-use reth_network_types::{PeerAddr, PeerKind};
+use reth_network_types::{PeerAddr, PeerKind, PeerQuality};
 use reth_tokio_util::EventStream;
 use std::{
     fmt,
@@ -68,6 +68,8 @@ pub struct SessionInfo {
     pub version: EthVersion,
     /// The kind of peer this session represents
     pub peer_kind: PeerKind,
+    /// The quality of the peer's responses, shared by all components that request from the peer.
+    pub quality: PeerQuality,
 }
 
 /// (Non-exhaustive) List of the different events emitted by the network that are of interest for
@@ -244,6 +244,18 @@ pub struct PeerInfo {
     pub session_established: Instant,
     /// The peer's connection kind
     pub kind: PeerKind,
+    /// The ASN and country of the peer's address, if GeoIP enrichment is enabled
+    pub geo: Option<PeerGeo>,
+}
+
+/// The autonomous system and country of a peer's IP address.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PeerGeo {
+    /// The autonomous system number.
+    pub asn: Option<u32>,
+    /// The ISO 3166-1 alpha-2 country code.
+    pub country: Option<String>,
 }
 
 /// The direction of the connection.
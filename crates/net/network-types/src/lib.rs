@@ -25,6 +25,7 @@ pub use backoff::BackoffKind;
 pub use peers::{
     addr::PeerAddr,
     kind::PeerKind,
+    quality::PeerQuality,
     reputation::{
         is_banned_reputation, is_connection_failed_reputation, ReputationChangeOutcome,
         DEFAULT_REPUTATION,
@@ -1,10 +1,12 @@
 pub mod addr;
 pub mod config;
 pub mod kind;
+pub mod quality;
 pub mod reputation;
 pub mod state;
 
 pub use config::{ConnectionsConfig, PeersConfig};
+pub use quality::PeerQuality;
 pub use reputation::{Reputation, ReputationChange, ReputationChangeKind, ReputationChangeWeights};
 
 use alloy_eip2124::ForkId;
@@ -0,0 +1,238 @@
+//! Peer quality scoring
+
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, PoisonError,
+    },
+    time::Duration,
+};
+
+/// The weight of a new sample in the moving averages of a [`PeerQuality`].
+///
+/// The weight of a sample halves about every five samples, so the score follows the current
+/// behaviour of the peer.
+const SAMPLE_WEIGHT: f64 = 0.125;
+
+/// The response latency a peer is assumed to have until it responded.
+///
+/// New peers are ranked like an average peer, below proven fast peers but above proven slow ones.
+const PRIOR_LATENCY: Duration = Duration::from_millis(500);
+
+/// Tracks the quality of the responses of a connected peer.
+///
+/// While the [`Reputation`](super::Reputation) of a peer decides whether the peer is banned, the
+/// quality is a continuous score that ranks the connected peers, so that requests are sent to the
+/// peers that serve them best.
+///
+/// The metrics are shared by all components that send requests to the peer, e.g. the block
+/// downloaders and the transaction fetcher.
+#[derive(Debug, Clone, Default)]
+pub struct PeerQuality {
+    inner: Arc<PeerQualityInner>,
+}
+
+impl PeerQuality {
+    /// Records a response of the peer that was received after the given latency.
+    ///
+    /// `useful_bytes` is the size of the data that was requested and served, zero for empty
+    /// responses.
+    pub fn on_response(&self, latency: Duration, useful_bytes: u64) {
+        let inner = &self.inner;
+        inner.responses.fetch_add(1, Ordering::Relaxed);
+        inner.useful_bytes.fetch_add(useful_bytes, Ordering::Relaxed);
+        inner.update(|averages| {
+            averages.timeout_rate.add(0.0);
+            averages.invalid_response_rate.add(0.0);
+            averages.useful_response_rate.add(if useful_bytes > 0 { 1.0 } else { 0.0 });
+            averages.latency_secs.add(latency.as_secs_f64());
+        });
+    }
+
+    /// Records a request that the peer failed to respond to in time.
+    pub fn on_timeout(&self) {
+        self.inner.timeouts.fetch_add(1, Ordering::Relaxed);
+        self.inner.update(|averages| {
+            averages.timeout_rate.add(1.0);
+            averages.invalid_response_rate.add(0.0);
+        });
+    }
+
+    /// Records an invalid response of the peer.
+    pub fn on_invalid_response(&self) {
+        self.inner.invalid_responses.fetch_add(1, Ordering::Relaxed);
+        self.inner.update(|averages| {
+            averages.timeout_rate.add(0.0);
+            averages.invalid_response_rate.add(1.0);
+        });
+    }
+
+    /// Returns the number of requests that were answered, timed out or answered invalidly.
+    pub fn requests(&self) -> u64 {
+        self.responses() +
+            self.inner.timeouts.load(Ordering::Relaxed) +
+            self.inner.invalid_responses.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of valid responses.
+    pub fn responses(&self) -> u64 {
+        self.inner.responses.load(Ordering::Relaxed)
+    }
+
+    /// Returns the moving average share of requests that timed out.
+    pub fn timeout_rate(&self) -> f64 {
+        self.inner.averages().timeout_rate.0
+    }
+
+    /// Returns the moving average share of requests that were answered invalidly.
+    pub fn invalid_response_rate(&self) -> f64 {
+        self.inner.averages().invalid_response_rate.0
+    }
+
+    /// Returns the moving average share of valid responses that included useful data.
+    pub fn useful_response_rate(&self) -> f64 {
+        self.inner.averages().useful_response_rate.0
+    }
+
+    /// Returns the moving average of the response latency, `None` until the peer responded.
+    pub fn latency(&self) -> Option<Duration> {
+        (self.responses() > 0)
+            .then(|| Duration::from_secs_f64(self.inner.averages().latency_secs.0))
+    }
+
+    /// Returns the total number of useful bytes the peer served.
+    pub fn useful_bytes(&self) -> u64 {
+        self.inner.useful_bytes.load(Ordering::Relaxed)
+    }
+
+    /// Returns the quality score of the peer in `[0, 1]`, higher is better.
+    ///
+    /// The score is the share of requests that were answered validly and with useful data,
+    /// discounted by the response latency in seconds. All of them are moving averages that start
+    /// at the values of an average peer, see [`PRIOR_LATENCY`].
+    pub fn score(&self) -> f64 {
+        let averages = self.inner.averages();
+        let reliability =
+            (1.0 - averages.timeout_rate.0) * (1.0 - averages.invalid_response_rate.0);
+        reliability * averages.useful_response_rate.0 / (1.0 + averages.latency_secs.0)
+    }
+}
+
+/// The shared metrics of a [`PeerQuality`].
+#[derive(Debug, Default)]
+struct PeerQualityInner {
+    /// Number of valid responses.
+    responses: AtomicU64,
+    /// Number of requests that timed out.
+    timeouts: AtomicU64,
+    /// Number of invalid responses.
+    invalid_responses: AtomicU64,
+    /// Total size of the useful data served.
+    useful_bytes: AtomicU64,
+    /// The moving averages the score is computed from.
+    averages: Mutex<Averages>,
+}
+
+impl PeerQualityInner {
+    fn averages(&self) -> Averages {
+        *self.averages.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn update(&self, f: impl FnOnce(&mut Averages)) {
+        f(&mut self.averages.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+}
+
+/// The moving averages of a [`PeerQuality`].
+#[derive(Debug, Clone, Copy)]
+struct Averages {
+    /// Share of requests that timed out.
+    timeout_rate: Ewma,
+    /// Share of requests that were answered invalidly.
+    invalid_response_rate: Ewma,
+    /// Share of valid responses with useful data.
+    useful_response_rate: Ewma,
+    /// Response latency in seconds.
+    latency_secs: Ewma,
+}
+
+impl Default for Averages {
+    fn default() -> Self {
+        Self {
+            timeout_rate: Ewma(0.0),
+            invalid_response_rate: Ewma(0.0),
+            useful_response_rate: Ewma(1.0),
+            latency_secs: Ewma(PRIOR_LATENCY.as_secs_f64()),
+        }
+    }
+}
+
+/// An exponentially weighted moving average, see [`SAMPLE_WEIGHT`].
+#[derive(Debug, Clone, Copy)]
+struct Ewma(f64);
+
+impl Ewma {
+    fn add(&mut self, sample: f64) {
+        self.0 += (sample - self.0) * SAMPLE_WEIGHT;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn score_ranks_peers() {
+        let new = PeerQuality::default();
+        assert_eq!(new.latency(), None);
+        assert_eq!(new.score(), 1.0 / (1.0 + PRIOR_LATENCY.as_secs_f64()));
+
+        let fast = PeerQuality::default();
+        let slow = PeerQuality::default();
+        for _ in 0..4 {
+            fast.on_response(Duration::from_millis(100), 1024);
+            slow.on_response(Duration::from_secs(2), 1024);
+        }
+        assert!(fast.latency().unwrap() < PRIOR_LATENCY);
+        assert!(fast.score() > slow.score());
+        // new peers rank between proven fast and slow peers
+        assert!(fast.score() > new.score());
+        assert!(new.score() > slow.score());
+
+        // timeouts and invalid responses lower the score
+        let unreliable = PeerQuality::default();
+        for _ in 0..2 {
+            unreliable.on_response(Duration::from_millis(100), 1024);
+        }
+        unreliable.on_timeout();
+        unreliable.on_invalid_response();
+        assert_eq!(unreliable.requests(), 4);
+        assert!(unreliable.timeout_rate() > 0.0);
+        assert!(unreliable.score() < fast.score());
+
+        // as do empty responses
+        let useless = PeerQuality::default();
+        useless.on_response(Duration::from_millis(100), 1024);
+        useless.on_response(Duration::from_millis(100), 0);
+        assert_eq!(useless.useful_bytes(), 1024);
+        assert!(useless.useful_response_rate() < 1.0);
+        assert!(useless.score() < fast.score());
+    }
+
+    #[test]
+    fn score_follows_current_behaviour() {
+        let quality = PeerQuality::default();
+        for _ in 0..10 {
+            quality.on_timeout();
+        }
+        let unreliable = quality.score();
+
+        // the timeouts are forgotten once the peer responds reliably again
+        for _ in 0..50 {
+            quality.on_response(Duration::from_millis(100), 1024);
+        }
+        assert!(quality.timeout_rate() < 0.01);
+        assert!(quality.score() > unreliable);
+        assert!(quality.score() > PeerQuality::default().score());
+    }
+}
@@ -0,0 +1,89 @@
+#![allow(missing_docs)]
+use alloy_primitives::B256;
+use criterion::*;
+use rand::SeedableRng;
+use reth_ethereum_primitives::Block;
+use reth_network::{
+    test_utils::{NetworkEventStream, Testnet},
+    BlockDownloaderProvider, LinkConditions, NetworkEventListenerProvider,
+};
+use reth_network_api::{NetworkInfo, Peers};
+use reth_network_p2p::bodies::client::BodiesClient;
+use reth_provider::test_utils::MockEthProvider;
+use reth_transaction_pool::test_utils::TransactionGenerator;
+use std::{sync::Arc, time::Duration};
+use tokio::runtime::Runtime as TokioRuntime;
+
+criterion_group!(
+    name = shaped_links_benches;
+    config = Criterion::default();
+    targets = fetch_bodies_bench
+);
+
+/// Number of blocks requested per iteration.
+const NUM_BLOCKS: usize = 16;
+
+/// Number of transactions per block.
+const TXS_PER_BLOCK: usize = 20;
+
+/// Fetches block bodies from a peer over links with different simulated conditions.
+pub fn fetch_bodies_bench(c: &mut Criterion) {
+    let rt = TokioRuntime::new().unwrap();
+    let mut group = c.benchmark_group("Fetch Bodies");
+    group.sample_size(10);
+
+    let links = [
+        ("unshaped", None),
+        ("latency_50ms", Some(LinkConditions::default().with_latency(Duration::from_millis(50)))),
+        (
+            "latency_50ms_10mbit_loss_1pct",
+            Some(
+                LinkConditions::default()
+                    .with_latency(Duration::from_millis(50))
+                    .with_bandwidth(10_000_000 / 8)
+                    .with_loss(0.01),
+            ),
+        ),
+    ];
+
+    for (name, conditions) in links {
+        let (fetch, hashes, _net) = rt.block_on(async {
+            let provider = Arc::new(MockEthProvider::default());
+            let mut tx_gen = TransactionGenerator::new(rand::rngs::StdRng::seed_from_u64(0));
+            let hashes = (0..NUM_BLOCKS)
+                .map(|i| {
+                    let mut block = Block::default();
+                    for _ in 0..TXS_PER_BLOCK {
+                        block.body.transactions.push(tx_gen.gen_eip1559());
+                    }
+                    let hash = B256::with_last_byte(i as u8);
+                    provider.add_block(hash, block);
+                    hash
+                })
+                .collect::<Vec<_>>();
+
+            let mut net = Testnet::create_with(2, provider).await;
+            net.for_each_mut(|peer| peer.install_request_handler());
+            let handle0 = net.peers()[0].handle();
+            let handle1 = net.peers()[1].handle();
+            let mut events0 = NetworkEventStream::new(handle0.event_listener());
+            let net = net.spawn();
+
+            let fetch = handle0.fetch_client().await.unwrap();
+            handle0.add_peer(*handle1.peer_id(), handle1.local_addr());
+            events0.next_session_established().await.unwrap();
+            handle0.shape_links(conditions);
+
+            (fetch, hashes, net)
+        });
+
+        group.bench_function(name, |b| {
+            b.to_async(&rt).iter(|| async {
+                let bodies = fetch.get_block_bodies(hashes.clone()).await.unwrap().1;
+                assert_eq!(bodies.len(), NUM_BLOCKS);
+            })
+        });
+    }
+}
+
+criterion_main!(shaped_links_benches);
@@ -2,6 +2,7 @@
 
 use crate::{
     error::NetworkError,
+    geo::GeoIpConfig,
     import::{BlockImport, ProofOfStakeBlockImport},
     transactions::TransactionsManagerConfig,
     NetworkHandle, NetworkManager,
@@ -16,12 +17,20 @@ use reth_eth_wire::{
     UnifiedStatus,
 };
 use reth_ethereum_forks::{ForkFilter, Head};
+use reth_net_nat::PortMappingConfig;
 use reth_network_peers::{mainnet_nodes, pk2id, sepolia_nodes, PeerId, TrustedPeer};
 use reth_network_types::{PeersConfig, SessionsConfig};
 use reth_storage_api::{noop::NoopProvider, BlockNumReader, BlockReader, HeaderProvider};
-use reth_tasks::{TaskSpawner, TokioTaskExecutor};
+use reth_tasks::{
+    time::{SharedClock, SystemClock},
+    TaskSpawner, TokioTaskExecutor,
+};
 use secp256k1::SECP256K1;
-use std::{collections::HashSet, net::SocketAddr, sync::Arc};
+use std::{
+    collections::HashSet,
+    net::{Ipv6Addr, SocketAddr},
+    sync::Arc,
+};
 
 // re-export for convenience
 use crate::protocol::{IntoRlpxSubProtocol, RlpxSubProtocols};
@@ -85,11 +94,17 @@ pub struct NetworkConfig<C, N: NetworkPrimitives = EthNetworkPrimitives> {
     pub transactions_manager_config: TransactionsManagerConfig,
     /// The NAT resolver for external IP
     pub nat: Option<NatResolver>,
+    /// How to map the discovery and `RLPx` ports on the gateway, if enabled.
+    pub port_mapping: Option<PortMappingConfig>,
+    /// Enrichment of peers with ASN and country information, if enabled.
+    pub geoip: Option<GeoIpConfig>,
     /// The Ethereum P2P handshake, see also:
     /// <https://github.com/ethereum/devp2p/blob/master/rlpx.md#initial-handshake>.
     /// This can be overridden to support custom handshake logic via the
     /// [`NetworkConfigBuilder`].
     pub handshake: Arc<dyn EthRlpxHandshake>,
+    /// The clock that drives the timers of the sessions.
+    pub clock: SharedClock,
 }
 
 // === impl NetworkConfig ===
@@ -214,9 +229,15 @@ pub struct NetworkConfigBuilder<N: NetworkPrimitives = EthNetworkPrimitives> {
     transactions_manager_config: TransactionsManagerConfig,
     /// The NAT resolver for external IP
     nat: Option<NatResolver>,
+    /// How to map the discovery and `RLPx` ports on the gateway.
+    port_mapping: Option<PortMappingConfig>,
+    /// Enrichment of peers with ASN and country information.
+    geoip: Option<GeoIpConfig>,
     /// The Ethereum P2P handshake, see also:
     /// <https://github.com/ethereum/devp2p/blob/master/rlpx.md#initial-handshake>.
     handshake: Arc<dyn EthRlpxHandshake>,
+    /// The clock that drives the timers of the sessions.
+    clock: SharedClock,
 }
 
 impl NetworkConfigBuilder<EthNetworkPrimitives> {
@@ -256,7 +277,10 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
             block_import: None,
             transactions_manager_config: Default::default(),
             nat: None,
+            port_mapping: None,
+            geoip: None,
             handshake: Arc::new(EthHandshake::default()),
+            clock: SystemClock::shared(),
         }
     }
 
@@ -419,6 +443,17 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
         self
     }
 
+    /// Sets the external IPv6 address that discovery v4 advertises along with the IPv4 address of
+    /// a dual-stack node.
+    ///
+    /// This has no effect if discovery v4 is disabled.
+    pub fn external_ipv6(mut self, external_ipv6: Option<Ipv6Addr>) -> Self {
+        if let Some(builder) = self.discovery_v4_builder.as_mut() {
+            builder.external_ipv6(external_ipv6);
+        }
+        self
+    }
+
     /// Sets the discv4 config to use.
     pub fn discovery(mut self, builder: Discv4ConfigBuilder) -> Self {
         self.discovery_v4_builder = Some(builder);
@@ -559,12 +594,35 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
         self
     }
 
+    /// Enables mapping the discovery and `RLPx` ports on the gateway with `UPnP` or NAT-PMP.
+    ///
+    /// The external IP address of the gateway is announced in the discovery v4 ENR.
+    pub const fn port_mapping(mut self, port_mapping: Option<PortMappingConfig>) -> Self {
+        self.port_mapping = port_mapping;
+        self
+    }
+
+    /// Enables the enrichment of peers with ASN and country information.
+    pub fn geoip(mut self, geoip: Option<GeoIpConfig>) -> Self {
+        self.geoip = geoip;
+        self
+    }
+
     /// Overrides the default Eth `RLPx` handshake.
     pub fn eth_rlpx_handshake(mut self, handshake: Arc<dyn EthRlpxHandshake>) -> Self {
         self.handshake = handshake;
         self
     }
 
+    /// Overrides the clock that drives the timers of the sessions, e.g. the interval of block
+    /// range updates.
+    ///
+    /// Defaults to the [`SystemClock`].
+    pub fn clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
     /// Consumes the type and creates the actual [`NetworkConfig`]
     /// for the given client type that can interact with the chain.
     ///
@@ -596,7 +654,10 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
             block_import,
             transactions_manager_config,
             nat,
+            port_mapping,
+            geoip,
             handshake,
+            clock,
         } = self;
 
         let head = head.unwrap_or_else(|| Head {
@@ -664,7 +725,10 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
             tx_gossip_disabled,
             transactions_manager_config,
             nat,
+            port_mapping,
+            geoip,
             handshake,
+            clock,
         }
     }
 }
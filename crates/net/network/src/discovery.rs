@@ -12,6 +12,7 @@ use reth_dns_discovery::{
     DnsDiscoveryConfig, DnsDiscoveryHandle, DnsDiscoveryService, DnsNodeRecordUpdate, DnsResolver,
 };
 use reth_ethereum_forks::{EnrForkIdEntry, ForkId};
+use reth_net_nat::{PortMapping, PortMappingConfig, PortMappingProtocol, PortMappingService};
 use reth_network_api::{DiscoveredEvent, DiscoveryEvent};
 use reth_network_peers::{NodeRecord, PeerId};
 use reth_network_types::PeerAddr;
@@ -60,6 +61,13 @@ pub struct Discovery {
     dns_discovery_updates: Option<ReceiverStream<DnsNodeRecordUpdate>>,
     /// The handle to the spawned DNS discovery service
     _dns_disc_service: Option<JoinHandle<()>>,
+    /// The internal ports of the `RLPx`, discv4 and discv5 listeners that are mapped on the
+    /// gateway.
+    port_mapping_ports: MappedPorts,
+    /// Updates of the mapping from the port mapping service.
+    port_mapping_updates: Option<mpsc::Receiver<PortMapping>>,
+    /// The handle to the spawned port mapping service
+    _port_mapping_service: Option<JoinHandle<()>>,
     /// Events buffered until polled.
     queued_events: VecDeque<DiscoveryEvent>,
     /// List of listeners subscribed to discovery events.
@@ -134,9 +142,72 @@ impl Discovery {
             _dns_disc_service,
             _dns_discovery,
             dns_discovery_updates,
+            port_mapping_ports: Default::default(),
+            port_mapping_updates: None,
+            _port_mapping_service: None,
         })
     }
 
+    /// Spawns the [`PortMappingService`] that maps the `RLPx` port, the discv4 port and the discv5
+    /// port on the gateway, and announces the external IP address of the gateway and the external
+    /// ports in the discv4 and discv5 ENRs.
+    ///
+    /// The mappings are deleted when this type is dropped.
+    pub(crate) fn spawn_port_mapping(&mut self, config: PortMappingConfig) {
+        let mapped = MappedPorts {
+            tcp: self.local_enr.tcp_port,
+            discv4: self.discv4.as_ref().map(|discv4| discv4.node_record().udp_port),
+            discv5: self.discv5.as_ref().and_then(|discv5| {
+                discv5.with_discv5(|discv5| {
+                    let enr = discv5.local_enr();
+                    enr.udp4().or_else(|| enr.udp6())
+                })
+            }),
+        };
+        let mut ports = vec![(PortMappingProtocol::Tcp, mapped.tcp)];
+        for port in [mapped.discv4, mapped.discv5].into_iter().flatten() {
+            if !ports.contains(&(PortMappingProtocol::Udp, port)) {
+                ports.push((PortMappingProtocol::Udp, port));
+            }
+        }
+        let (service, updates) = PortMappingService::new(ports, config);
+        self.port_mapping_ports = mapped;
+        self.port_mapping_updates = Some(updates);
+        self._port_mapping_service = Some(service.spawn());
+    }
+
+    /// Announces the external IP address and the external ports of the mapping in the discv4 and
+    /// discv5 ENRs.
+    fn on_port_mapping(&self, mapping: &PortMapping) {
+        let ip = mapping.external_ip;
+        let tcp_port = mapping
+            .external_port(PortMappingProtocol::Tcp, self.port_mapping_ports.tcp)
+            .unwrap_or(self.port_mapping_ports.tcp);
+        if let Some(discv4) = &self.discv4 {
+            discv4.set_external_ip_addr(ip);
+            discv4.set_tcp_port(tcp_port);
+            if let Some(udp_port) = self
+                .port_mapping_ports
+                .discv4
+                .and_then(|port| mapping.external_port(PortMappingProtocol::Udp, port))
+            {
+                discv4.set_udp_port(udp_port);
+            }
+        }
+        if let Some(discv5) = &self.discv5 {
+            discv5.with_discv5(|discv5| {
+                if let Some(udp_port) = self
+                    .port_mapping_ports
+                    .discv5
+                    .and_then(|port| mapping.external_port(PortMappingProtocol::Udp, port))
+                {
+                    discv5.update_local_enr_socket(SocketAddr::new(ip, udp_port), false);
+                }
+                discv5.update_local_enr_socket(SocketAddr::new(ip, tcp_port), true);
+            });
+        }
+    }
+
     /// Registers a listener for receiving [`DiscoveryEvent`] updates.
     pub(crate) fn add_listener(&mut self, tx: mpsc::UnboundedSender<DiscoveryEvent>) {
         self.discovery_listeners.push(tx);
@@ -276,6 +347,13 @@ impl Discovery {
                 }
             }
 
+            // announce the external address and ports of the gateway
+            while let Some(Poll::Ready(Some(mapping))) =
+                self.port_mapping_updates.as_mut().map(|updates| updates.poll_recv(cx))
+            {
+                self.on_port_mapping(&mapping);
+            }
+
             // drain the dns update stream
             while let Some(Poll::Ready(Some(update))) =
                 self.dns_discovery_updates.as_mut().map(|updates| updates.poll_next_unpin(cx))
@@ -297,6 +375,17 @@ impl Discovery {
     }
 }
 
+/// The internal ports that are mapped on the gateway.
+#[derive(Debug, Default, Clone, Copy)]
+struct MappedPorts {
+    /// The `RLPx` port.
+    tcp: u16,
+    /// The discv4 port.
+    discv4: Option<u16>,
+    /// The discv5 port.
+    discv5: Option<u16>,
+}
+
 impl Stream for Discovery {
     type Item = DiscoveryEvent;
 
@@ -331,6 +420,9 @@ impl Discovery {
             _dns_discovery: None,
             dns_discovery_updates: None,
             _dns_disc_service: None,
+            port_mapping_ports: Default::default(),
+            port_mapping_updates: None,
+            _port_mapping_service: None,
             discovery_listeners: Default::default(),
         }
     }
@@ -388,6 +480,41 @@ mod tests {
         .expect("should build discv5 with discv4 downgrade")
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn port_mapping_updates_enrs() {
+        let mut node = start_discovery_node(40034, 40035).await;
+        node.port_mapping_ports =
+            MappedPorts { tcp: 40034, discv4: Some(40034), discv5: Some(40035) };
+        let external_ip = IpAddr::V4(Ipv4Addr::new(203, 0, 113, 7));
+        node.on_port_mapping(&PortMapping {
+            external_ip,
+            ports: vec![
+                (PortMappingProtocol::Tcp, 40034, 50034),
+                (PortMappingProtocol::Udp, 40034, 50034),
+                (PortMappingProtocol::Udp, 40035, 50035),
+            ],
+        });
+
+        // the discv4 commands are applied by the service
+        let discv4 = node.discv4.as_ref().unwrap();
+        let mut record = discv4.node_record();
+        for _ in 0..100 {
+            if record.udp_port == 50034 {
+                break
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            record = discv4.node_record();
+        }
+        assert_eq!(record.address, external_ip);
+        assert_eq!(record.tcp_port, 50034);
+        assert_eq!(record.udp_port, 50034);
+
+        let enr = node.discv5.as_ref().unwrap().with_discv5(|discv5| discv5.local_enr());
+        assert_eq!(enr.ip4(), Some(Ipv4Addr::new(203, 0, 113, 7)));
+        assert_eq!(enr.udp4(), Some(50035));
+        assert_eq!(enr.tcp4(), Some(50034));
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn discv5_and_discv4_same_pk() {
         reth_tracing::init_test_tracing();
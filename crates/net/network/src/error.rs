@@ -8,7 +8,7 @@ use reth_eth_wire::{
     DisconnectReason,
 };
 use reth_network_types::BackoffKind;
-use std::{fmt, io, io::ErrorKind, net::SocketAddr};
+use std::{fmt, io, io::ErrorKind, net::SocketAddr, path::PathBuf};
 
 /// Service kind.
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
@@ -63,6 +63,9 @@ pub enum NetworkError {
     /// See also [`DnsResolver`](reth_dns_discovery::DnsResolver::from_system_conf)
     #[error("failed to configure DNS resolver: {0}")]
     DnsResolver(#[from] ResolveError),
+    /// Error when opening a GeoIP database failed
+    #[error("failed to open GeoIP database {0}: {1}")]
+    GeoIp(PathBuf, maxminddb::MaxMindDbError),
 }
 
 impl NetworkError {
@@ -6,6 +6,7 @@ pub use client::FetchClient;
 
 use crate::{message::BlockRequest, session::BlockRangeInfo};
 use alloy_primitives::B256;
+use alloy_rlp::Encodable;
 use futures::StreamExt;
 use reth_eth_wire::{EthNetworkPrimitives, GetBlockBodies, GetBlockHeaders, NetworkPrimitives};
 use reth_network_api::test_utils::PeersHandle;
@@ -15,7 +16,7 @@ use reth_network_p2p::{
     priority::Priority,
 };
 use reth_network_peers::PeerId;
-use reth_network_types::ReputationChangeKind;
+use reth_network_types::{PeerQuality, ReputationChangeKind};
 use std::{
     collections::{HashMap, VecDeque},
     ops::RangeInclusive,
@@ -24,12 +25,39 @@ use std::{
         Arc,
     },
     task::{Context, Poll},
+    time::{Duration, Instant},
 };
 use tokio::sync::{mpsc, mpsc::UnboundedSender, oneshot};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 
 type InflightHeadersRequest<H> = Request<HeadersRequest, PeerRequestResult<Vec<H>>>;
-type InflightBodiesRequest<B> = Request<Vec<B256>, PeerRequestResult<Vec<B>>>;
+type InflightBodiesRequest<B> = Request<BodiesRequest, PeerRequestResult<Vec<B>>>;
+
+/// The number of bodies requested at once from a peer that hasn't served bodies yet.
+const INITIAL_BODIES_BATCH_SIZE: usize = 64;
+
+/// The lower bound of the adaptive bodies batch size of a peer.
+const MIN_BODIES_BATCH_SIZE: usize = 4;
+
+/// The upper bound of the adaptive bodies batch size of a peer.
+const MAX_BODIES_BATCH_SIZE: usize = 1024;
+
+/// Bodies responses faster than this grow the batch size of the peer, slower ones shrink it.
+const TARGET_BODIES_RESPONSE_LATENCY: Duration = Duration::from_secs(1);
+
+/// Peers whose average bodies response latency exceeds this are only requested from if no faster
+/// peer is idle.
+const SLOW_PEER_BODIES_RESPONSE_LATENCY: Duration = Duration::from_secs(4);
+
+/// The number of consecutive empty bodies responses for blocks a peer announced to serve, after
+/// which the peer is penalized.
+///
+/// A peer on another fork legitimately has no bodies for some of the requested hashes, so single
+/// misses are not penalized.
+const MAX_ANNOUNCED_RANGE_MISSES: u8 = 3;
+
+/// Peers whose [`PeerQuality::score`] differs by less than this are ranked by their timeout.
+const QUALITY_SCORE_TOLERANCE: f64 = 0.1;
 
 /// Manages data fetching operations.
 ///
@@ -82,6 +110,7 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
         best_number: u64,
         timeout: Arc<AtomicU64>,
         range_info: Option<BlockRangeInfo>,
+        quality: PeerQuality,
     ) {
         self.peers.insert(
             peer_id,
@@ -91,7 +120,10 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
                 best_number,
                 timeout,
                 last_response_likely_bad: false,
+                announced_range_misses: 0,
+                bodies_batch: Default::default(),
                 range_info,
+                quality,
             },
         );
     }
@@ -134,10 +166,16 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
     }
 
     /// Returns the _next_ idle peer that's ready to accept a request,
-    /// prioritizing those with the lowest timeout/latency and those that recently responded with
-    /// adequate data.
-    fn next_best_peer(&self) -> Option<PeerId> {
-        let mut idle = self.peers.iter().filter(|(_, peer)| peer.state.is_idle());
+    /// prioritizing those with the lowest timeout/latency, those that recently responded with
+    /// adequate data and those that serve bodies in time.
+    ///
+    /// If a range hint is given, only peers that serve the blocks of the range are considered, see
+    /// [`Peer::serves`]. If no connected peer announced the range, any idle peer is returned.
+    fn next_best_peer(&self, range_hint: Option<&RangeInclusive<u64>>) -> Option<PeerId> {
+        let range_hint =
+            range_hint.filter(|range| self.peers.values().any(|peer| peer.serves(Some(*range))));
+        let mut idle =
+            self.peers.iter().filter(|(_, peer)| peer.state.is_idle() && peer.serves(range_hint));
 
         let mut best_peer = idle.next()?;
 
@@ -148,6 +186,30 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
                 continue
             }
 
+            // replace best peer if it's slow to serve bodies and this one isn't
+            if best_peer.1.bodies_batch.is_slow() &&
+                !maybe_better.1.bodies_batch.is_slow() &&
+                !maybe_better.1.last_response_likely_bad
+            {
+                best_peer = maybe_better;
+                continue
+            }
+            if maybe_better.1.bodies_batch.is_slow() && !best_peer.1.bodies_batch.is_slow() {
+                continue
+            }
+
+            // replace best peer if this peer served previous requests considerably better
+            let (score, best_score) = (maybe_better.1.quality.score(), best_peer.1.quality.score());
+            if score > best_score + QUALITY_SCORE_TOLERANCE &&
+                !maybe_better.1.last_response_likely_bad
+            {
+                best_peer = maybe_better;
+                continue
+            }
+            if score + QUALITY_SCORE_TOLERANCE < best_score {
+                continue
+            }
+
             // replace best peer if this peer has better rtt
             if maybe_better.1.timeout() < best_peer.1.timeout() &&
                 !maybe_better.1.last_response_likely_bad
@@ -166,7 +228,10 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
             return PollAction::NoRequests
         }
 
-        let Some(peer_id) = self.next_best_peer() else { return PollAction::NoPeersAvailable };
+        let range_hint = self.queued_requests.front().and_then(DownloadRequest::range_hint);
+        let Some(peer_id) = self.next_best_peer(range_hint) else {
+            return PollAction::NoPeersAvailable
+        };
 
         let request = self.queued_requests.pop_front().expect("not empty");
         let request = self.prepare_block_request(peer_id, request);
@@ -217,16 +282,28 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
 
     /// Handles a new request to a peer.
     ///
+    /// Bodies requests are truncated to the batch size of the peer, the requester is expected to
+    /// request the remaining bodies again.
+    ///
     /// Caution: this assumes the peer exists and is idle
-    fn prepare_block_request(&mut self, peer_id: PeerId, req: DownloadRequest<N>) -> BlockRequest {
+    fn prepare_block_request(
+        &mut self,
+        peer_id: PeerId,
+        mut req: DownloadRequest<N>,
+    ) -> BlockRequest {
         // update the peer's state
         if let Some(peer) = self.peers.get_mut(&peer_id) {
             peer.state = req.peer_state();
+            if let DownloadRequest::GetBlockBodies { request, .. } = &mut req {
+                request.truncate(peer.bodies_batch.size);
+                peer.bodies_batch.inflight_since = Some(Instant::now());
+            }
         }
 
         match req {
             DownloadRequest::GetBlockHeaders { request, response, .. } => {
-                let inflight = Request { request: request.clone(), response };
+                let inflight =
+                    Request { request: request.clone(), response, started: Instant::now() };
                 self.inflight_headers_requests.insert(peer_id, inflight);
                 let HeadersRequest { start, limit, direction } = request;
                 BlockRequest::GetBlockHeaders(GetBlockHeaders {
@@ -236,18 +313,29 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
                     direction,
                 })
             }
-            DownloadRequest::GetBlockBodies { request, response, .. } => {
-                let inflight = Request { request: request.clone(), response };
+            DownloadRequest::GetBlockBodies { request, response, range_hint, .. } => {
+                let inflight = Request {
+                    request: BodiesRequest { hashes: request.clone(), range_hint },
+                    response,
+                    started: Instant::now(),
+                };
                 self.inflight_bodies_requests.insert(peer_id, inflight);
                 BlockRequest::GetBlockBodies(GetBlockBodies(request))
             }
         }
     }
 
-    /// Returns a new followup request for the peer.
+    /// Returns a new followup request for the peer, if it serves the next queued request.
     ///
     /// Caution: this expects that the peer is _not_ closed.
     fn followup_request(&mut self, peer_id: PeerId) -> Option<BlockResponseOutcome> {
+        let range_hint = self.queued_requests.front()?.range_hint();
+        if !self.peers.get(&peer_id)?.serves(range_hint) &&
+            self.peers.values().any(|peer| peer.serves(range_hint))
+        {
+            // leave the request to a peer that serves the range
+            return None
+        }
         let req = self.queued_requests.pop_front()?;
         let req = self.prepare_block_request(peer_id, req);
         Some(BlockResponseOutcome::Request(peer_id, req))
@@ -270,6 +358,14 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
 
         let is_likely_bad_response =
             resp.as_ref().is_some_and(|r| res.is_likely_bad_headers_response(&r.request));
+        let useful_bytes = match &res {
+            Ok(headers) if !is_likely_bad_response => {
+                headers.iter().map(|h| h.length() as u64).sum()
+            }
+            _ => 0,
+        };
+        let started = resp.as_ref().map(|resp| resp.started);
+        let outcome = res.as_ref().map(|_| useful_bytes).map_err(Clone::clone);
 
         if let Some(resp) = resp {
             // delegate the response
@@ -279,6 +375,9 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
         if let Some(peer) = self.peers.get_mut(&peer_id) {
             // update the peer's response state
             peer.last_response_likely_bad = is_likely_bad_response;
+            if let Some(started) = started {
+                peer.on_response_outcome(started, outcome);
+            }
 
             // If the peer is still ready to accept new requests, we try to send a followup
             // request immediately.
@@ -294,21 +393,65 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
     }
 
     /// Called on a `GetBlockBodies` response from a peer
+    ///
+    /// Returns a [`BlockResponseOutcome::BadResponse`] if the peer repeatedly served no bodies of a
+    /// range it announced to serve, see [`MAX_ANNOUNCED_RANGE_MISSES`]. Only requests with a range
+    /// hint are considered, which the bodies downloader sends for the canonical chain it syncs.
     pub(crate) fn on_block_bodies_response(
         &mut self,
         peer_id: PeerId,
         res: RequestResult<Vec<N::BlockBody>>,
     ) -> Option<BlockResponseOutcome> {
         let is_likely_bad_response = res.as_ref().map_or(true, |bodies| bodies.is_empty());
-
-        if let Some(resp) = self.inflight_bodies_requests.remove(&peer_id) {
+        let received = res.as_ref().map(Vec::len).ok();
+        let outcome = res
+            .as_ref()
+            .map(|bodies| bodies.iter().map(|body| body.length() as u64).sum())
+            .map_err(Clone::clone);
+
+        let resp = self.inflight_bodies_requests.remove(&peer_id);
+        let requested = resp.as_ref().map(|resp| resp.request.hashes.len());
+        let range_hint = resp.as_ref().and_then(|resp| resp.request.range_hint.clone());
+        let started = resp.as_ref().map(|resp| resp.started);
+        if let Some(resp) = resp {
             let _ = resp.response.send(res.map(|b| (peer_id, b).into()));
         }
         if let Some(peer) = self.peers.get_mut(&peer_id) {
             // update the peer's response state
             peer.last_response_likely_bad = is_likely_bad_response;
+            if let Some(started) = started {
+                peer.on_response_outcome(started, outcome);
+            }
+
+            // adapt the batch size of the peer to how fast it served this request
+            if let (Some(requested), Some(started)) =
+                (requested, peer.bodies_batch.inflight_since.take())
+            {
+                let elapsed = started.elapsed();
+                match received {
+                    Some(received) => peer.bodies_batch.on_response(requested, received, elapsed),
+                    None => peer.bodies_batch.on_failure(elapsed),
+                }
+            }
+
+            let is_ready = peer.state.on_request_finished();
+
+            if let Some(received) = received.filter(|_| peer.announced(range_hint.as_ref())) {
+                if received == 0 {
+                    peer.announced_range_misses += 1;
+                } else {
+                    peer.announced_range_misses = 0;
+                }
+                if peer.announced_range_misses >= MAX_ANNOUNCED_RANGE_MISSES {
+                    peer.announced_range_misses = 0;
+                    return Some(BlockResponseOutcome::BadResponse(
+                        peer_id,
+                        ReputationChangeKind::BadAnnouncement,
+                    ))
+                }
+            }
 
-            if peer.state.on_request_finished() && !is_likely_bad_response {
+            if is_ready && !is_likely_bad_response {
                 return self.followup_request(peer_id)
             }
         }
@@ -350,15 +493,110 @@ struct Peer {
     /// downloaded), but we still want to avoid requesting from the same peer again if it has the
     /// lowest timeout.
     last_response_likely_bad: bool,
+    /// Number of consecutive empty bodies responses for blocks of the canonical chain that the
+    /// peer announced to serve.
+    announced_range_misses: u8,
+    /// Tracks how many bodies to request from the peer at once.
+    bodies_batch: BodiesBatch,
     /// Tracks the range info for the peer.
-    #[allow(dead_code)]
     range_info: Option<BlockRangeInfo>,
+    /// Tracks the quality of the peer's responses, shared with the other components that request
+    /// from the peer.
+    quality: PeerQuality,
 }
 
 impl Peer {
     fn timeout(&self) -> u64 {
         self.timeout.load(Ordering::Relaxed)
     }
+
+    /// Records the outcome of a request that was sent at `started` in the peer's quality.
+    ///
+    /// The outcome is the number of useful bytes the peer responded with, or the request error.
+    fn on_response_outcome(&self, started: Instant, outcome: Result<u64, RequestError>) {
+        match outcome {
+            Ok(useful_bytes) => self.quality.on_response(started.elapsed(), useful_bytes),
+            Err(RequestError::Timeout) => self.quality.on_timeout(),
+            Err(RequestError::BadResponse) => self.quality.on_invalid_response(),
+            Err(_) => {}
+        }
+    }
+
+    /// Returns `true` if the peer may serve the blocks of the range.
+    ///
+    /// Peers that didn't announce the range of blocks they serve, e.g. before `eth69`, may serve
+    /// any range.
+    fn serves(&self, range: Option<&RangeInclusive<u64>>) -> bool {
+        self.range_info.is_none() || range.is_none() || self.announced(range)
+    }
+
+    /// Returns `true` if the peer announced to serve all blocks of the range.
+    fn announced(&self, range: Option<&RangeInclusive<u64>>) -> bool {
+        match (&self.range_info, range) {
+            (Some(info), Some(range)) => {
+                info.contains(*range.start()) && info.contains(*range.end())
+            }
+            _ => false,
+        }
+    }
+}
+
+/// The adaptive size of bodies requests to a peer, based on the latency and length of its
+/// responses.
+///
+/// Peers that serve bodies quickly get larger requests, while slow peers get smaller requests and
+/// are demoted in favor of faster ones before their requests time out.
+#[derive(Debug)]
+struct BodiesBatch {
+    /// The maximum number of bodies to request from the peer at once.
+    size: usize,
+    /// Moving average of the peer's bodies response latency, `None` until it served a request.
+    latency: Option<Duration>,
+    /// When the inflight bodies request was sent to the peer.
+    inflight_since: Option<Instant>,
+}
+
+impl BodiesBatch {
+    /// Adapts the batch size to a response with `received` out of `requested` bodies.
+    fn on_response(&mut self, requested: usize, received: usize, elapsed: Duration) {
+        // empty responses are not representative, the peer likely doesn't have the bodies
+        if received == 0 {
+            return
+        }
+        self.record_latency(elapsed);
+
+        if elapsed > TARGET_BODIES_RESPONSE_LATENCY {
+            self.size = received / 2;
+        } else if received < requested {
+            // the peer hit its response size limit, don't ask for more than it serves
+            self.size = received;
+        } else if requested >= self.size && elapsed < TARGET_BODIES_RESPONSE_LATENCY / 2 {
+            // the request was limited by the batch size and served quickly, ask for more
+            self.size = self.size.saturating_mul(2);
+        }
+        self.size = self.size.clamp(MIN_BODIES_BATCH_SIZE, MAX_BODIES_BATCH_SIZE);
+    }
+
+    /// Shrinks the batch size after a failed request, e.g. a timeout.
+    fn on_failure(&mut self, elapsed: Duration) {
+        self.record_latency(elapsed);
+        self.size = (self.size / 2).max(MIN_BODIES_BATCH_SIZE);
+    }
+
+    fn record_latency(&mut self, elapsed: Duration) {
+        self.latency = Some(self.latency.map_or(elapsed, |latency| (latency * 3 + elapsed) / 4));
+    }
+
+    /// Returns `true` if the peer is slow to serve bodies.
+    fn is_slow(&self) -> bool {
+        self.latency.is_some_and(|latency| latency > SLOW_PEER_BODIES_RESPONSE_LATENCY)
+    }
+}
+
+impl Default for BodiesBatch {
+    fn default() -> Self {
+        Self { size: INITIAL_BODIES_BATCH_SIZE, latency: None, inflight_since: None }
+    }
 }
 
 /// Tracks the state of an individual peer
@@ -404,6 +642,17 @@ struct Request<Req, Resp> {
     // TODO: this can be attached to the response in error case
     request: Req,
     response: oneshot::Sender<Resp>,
+    /// When the request was sent.
+    started: Instant,
+}
+
+/// An inflight `GetBlockBodies` request.
+#[derive(Debug)]
+struct BodiesRequest {
+    /// The hashes of the requested bodies.
+    hashes: Vec<B256>,
+    /// The range of the requested blocks, if known.
+    range_hint: Option<RangeInclusive<u64>>,
 }
 
 /// Requests that can be sent to the Syncer from a [`FetchClient`]
@@ -420,7 +669,7 @@ pub(crate) enum DownloadRequest<N: NetworkPrimitives> {
         request: Vec<B256>,
         response: oneshot::Sender<PeerRequestResult<Vec<N::BlockBody>>>,
         priority: Priority,
-        #[allow(dead_code)]
+        /// The range of the requested blocks, used to route the request to a peer that serves it.
         range_hint: Option<RangeInclusive<u64>>,
     },
 }
@@ -449,6 +698,14 @@ impl<N: NetworkPrimitives> DownloadRequest<N> {
     const fn is_normal_priority(&self) -> bool {
         self.get_priority().is_normal()
     }
+
+    /// Returns the range of the requested blocks, if known.
+    const fn range_hint(&self) -> Option<&RangeInclusive<u64>> {
+        match self {
+            Self::GetBlockHeaders { .. } => None,
+            Self::GetBlockBodies { range_hint, .. } => range_hint.as_ref(),
+        }
+    }
 }
 
 /// An action the syncer can emit.
@@ -511,20 +768,34 @@ mod tests {
         // Add a few random peers
         let peer1 = B512::random();
         let peer2 = B512::random();
-        fetcher.new_active_peer(peer1, B256::random(), 1, Arc::new(AtomicU64::new(1)), None);
-        fetcher.new_active_peer(peer2, B256::random(), 2, Arc::new(AtomicU64::new(1)), None);
+        fetcher.new_active_peer(
+            peer1,
+            B256::random(),
+            1,
+            Arc::new(AtomicU64::new(1)),
+            None,
+            Default::default(),
+        );
+        fetcher.new_active_peer(
+            peer2,
+            B256::random(),
+            2,
+            Arc::new(AtomicU64::new(1)),
+            None,
+            Default::default(),
+        );
 
-        let first_peer = fetcher.next_best_peer().unwrap();
+        let first_peer = fetcher.next_best_peer(None).unwrap();
         assert!(first_peer == peer1 || first_peer == peer2);
         // Pending disconnect for first_peer
         fetcher.on_pending_disconnect(&first_peer);
         // first_peer now isn't idle, so we should get other peer
-        let second_peer = fetcher.next_best_peer().unwrap();
+        let second_peer = fetcher.next_best_peer(None).unwrap();
         assert!(first_peer == peer1 || first_peer == peer2);
         assert_ne!(first_peer, second_peer);
         // without idle peers, returns None
         fetcher.on_pending_disconnect(&second_peer);
-        assert_eq!(fetcher.next_best_peer(), None);
+        assert_eq!(fetcher.next_best_peer(None), None);
     }
 
     #[tokio::test]
@@ -539,18 +810,242 @@ mod tests {
 
         let peer2_timeout = Arc::new(AtomicU64::new(300));
 
-        fetcher.new_active_peer(peer1, B256::random(), 1, Arc::new(AtomicU64::new(30)), None);
-        fetcher.new_active_peer(peer2, B256::random(), 2, Arc::clone(&peer2_timeout), None);
-        fetcher.new_active_peer(peer3, B256::random(), 3, Arc::new(AtomicU64::new(50)), None);
+        fetcher.new_active_peer(
+            peer1,
+            B256::random(),
+            1,
+            Arc::new(AtomicU64::new(30)),
+            None,
+            Default::default(),
+        );
+        fetcher.new_active_peer(
+            peer2,
+            B256::random(),
+            2,
+            Arc::clone(&peer2_timeout),
+            None,
+            Default::default(),
+        );
+        fetcher.new_active_peer(
+            peer3,
+            B256::random(),
+            3,
+            Arc::new(AtomicU64::new(50)),
+            None,
+            Default::default(),
+        );
 
         // Must always get peer1 (lowest timeout)
-        assert_eq!(fetcher.next_best_peer(), Some(peer1));
-        assert_eq!(fetcher.next_best_peer(), Some(peer1));
+        assert_eq!(fetcher.next_best_peer(None), Some(peer1));
+        assert_eq!(fetcher.next_best_peer(None), Some(peer1));
         // peer2's timeout changes below peer1's
         peer2_timeout.store(10, Ordering::Relaxed);
         // Then we get peer 2 always (now lowest)
-        assert_eq!(fetcher.next_best_peer(), Some(peer2));
-        assert_eq!(fetcher.next_best_peer(), Some(peer2));
+        assert_eq!(fetcher.next_best_peer(None), Some(peer2));
+        assert_eq!(fetcher.next_best_peer(None), Some(peer2));
+    }
+
+    #[test]
+    fn test_bodies_batch_adapts() {
+        let mut batch = BodiesBatch::default();
+
+        // fast full responses grow the batch
+        batch.on_response(INITIAL_BODIES_BATCH_SIZE, INITIAL_BODIES_BATCH_SIZE, Duration::ZERO);
+        assert_eq!(batch.size, INITIAL_BODIES_BATCH_SIZE * 2);
+
+        // smaller requests don't
+        batch.on_response(10, 10, Duration::ZERO);
+        assert_eq!(batch.size, INITIAL_BODIES_BATCH_SIZE * 2);
+
+        // truncated responses cap the batch at what the peer serves
+        batch.on_response(batch.size, 40, Duration::ZERO);
+        assert_eq!(batch.size, 40);
+
+        // empty responses are ignored
+        batch.on_response(batch.size, 0, Duration::from_secs(10));
+        assert_eq!(batch.size, 40);
+        assert!(!batch.is_slow());
+
+        // slow responses shrink the batch and eventually mark the peer as slow
+        batch.on_response(batch.size, 40, Duration::from_secs(8));
+        assert_eq!(batch.size, 20);
+        batch.on_failure(Duration::from_secs(20));
+        assert_eq!(batch.size, 10);
+        assert!(batch.is_slow());
+
+        for _ in 0..10 {
+            batch.on_failure(Duration::from_secs(20));
+        }
+        assert_eq!(batch.size, MIN_BODIES_BATCH_SIZE);
+    }
+
+    #[tokio::test]
+    async fn test_slow_peer_demoted() {
+        let manager = PeersManager::new(PeersConfig::default());
+        let mut fetcher =
+            StateFetcher::<EthNetworkPrimitives>::new(manager.handle(), Default::default());
+        let peer1 = B512::random();
+        let peer2 = B512::random();
+        fetcher.new_active_peer(
+            peer1,
+            B256::random(),
+            1,
+            Arc::new(AtomicU64::new(10)),
+            None,
+            Default::default(),
+        );
+        fetcher.new_active_peer(
+            peer2,
+            B256::random(),
+            2,
+            Arc::new(AtomicU64::new(100)),
+            None,
+            Default::default(),
+        );
+        assert_eq!(fetcher.next_best_peer(None), Some(peer1));
+
+        // bodies requests are truncated to the batch size of the peer
+        let (tx, _rx) = oneshot::channel();
+        let request = fetcher.prepare_block_request(
+            peer1,
+            DownloadRequest::GetBlockBodies {
+                request: (0..INITIAL_BODIES_BATCH_SIZE * 2).map(|_| B256::random()).collect(),
+                response: tx,
+                priority: Priority::default(),
+                range_hint: None,
+            },
+        );
+        let BlockRequest::GetBlockBodies(GetBlockBodies(hashes)) = request else { unreachable!() };
+        assert_eq!(hashes.len(), INITIAL_BODIES_BATCH_SIZE);
+
+        // the peer with the lower timeout is demoted once it's slow to serve bodies
+        let peer = fetcher.peers.get_mut(&peer1).unwrap();
+        assert!(peer.state.on_request_finished());
+        peer.bodies_batch.on_failure(Duration::from_secs(30));
+        assert_eq!(fetcher.next_best_peer(None), Some(peer2));
+    }
+
+    #[tokio::test]
+    async fn test_range_routing() {
+        let manager = PeersManager::new(PeersConfig::default());
+        let mut fetcher =
+            StateFetcher::<EthNetworkPrimitives>::new(manager.handle(), Default::default());
+        let peer1 = B512::random();
+        let peer2 = B512::random();
+        let range_info = |latest| Some(BlockRangeInfo::new(0, latest, B256::random()));
+        fetcher.new_active_peer(
+            peer1,
+            B256::random(),
+            100,
+            Arc::new(AtomicU64::new(10)),
+            range_info(100),
+            Default::default(),
+        );
+        fetcher.new_active_peer(
+            peer2,
+            B256::random(),
+            1000,
+            Arc::new(AtomicU64::new(100)),
+            range_info(1000),
+            Default::default(),
+        );
+
+        // requests are routed to the peers that announced the range
+        assert_eq!(fetcher.next_best_peer(Some(&(10..=20))), Some(peer1));
+        assert_eq!(fetcher.next_best_peer(Some(&(500..=600))), Some(peer2));
+        assert_eq!(fetcher.next_best_peer(Some(&(90..=110))), Some(peer2));
+        // any peer is requested from if no peer announced the range
+        assert_eq!(fetcher.next_best_peer(Some(&(2000..=2100))), Some(peer1));
+
+        let request_bodies = |fetcher: &mut StateFetcher, peer_id, range_hint| {
+            let (tx, _rx) = oneshot::channel();
+            fetcher.prepare_block_request(
+                peer_id,
+                DownloadRequest::GetBlockBodies {
+                    request: vec![B256::random()],
+                    response: tx,
+                    priority: Priority::default(),
+                    range_hint: Some(range_hint),
+                },
+            );
+        };
+
+        // a peer on another fork may not have some of the bodies of an announced range
+        request_bodies(&mut fetcher, peer1, 10..=20);
+        assert_eq!(fetcher.on_block_bodies_response(peer1, Ok(vec![])), None);
+        request_bodies(&mut fetcher, peer1, 10..=20);
+        assert_eq!(fetcher.on_block_bodies_response(peer1, Ok(vec![Default::default()])), None);
+
+        // peers that repeatedly don't serve an announced range are penalized
+        for _ in 1..MAX_ANNOUNCED_RANGE_MISSES {
+            request_bodies(&mut fetcher, peer1, 10..=20);
+            assert_eq!(fetcher.on_block_bodies_response(peer1, Ok(vec![])), None);
+        }
+        request_bodies(&mut fetcher, peer1, 10..=20);
+        assert_eq!(
+            fetcher.on_block_bodies_response(peer1, Ok(vec![])),
+            Some(BlockResponseOutcome::BadResponse(peer1, ReputationChangeKind::BadAnnouncement))
+        );
+
+        // ranges the peer didn't announce are not counted
+        for _ in 0..MAX_ANNOUNCED_RANGE_MISSES {
+            request_bodies(&mut fetcher, peer1, 2000..=2100);
+            assert_eq!(fetcher.on_block_bodies_response(peer1, Ok(vec![])), None);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_quality_ranking() {
+        let manager = PeersManager::new(PeersConfig::default());
+        let mut fetcher =
+            StateFetcher::<EthNetworkPrimitives>::new(manager.handle(), Default::default());
+        let peer1 = B512::random();
+        let peer2 = B512::random();
+        let quality1 = PeerQuality::default();
+        let quality2 = PeerQuality::default();
+        fetcher.new_active_peer(
+            peer1,
+            B256::random(),
+            1,
+            Arc::new(AtomicU64::new(10)),
+            None,
+            quality1.clone(),
+        );
+        fetcher.new_active_peer(
+            peer2,
+            B256::random(),
+            2,
+            Arc::new(AtomicU64::new(100)),
+            None,
+            quality2.clone(),
+        );
+        assert_eq!(fetcher.next_best_peer(None), Some(peer1));
+
+        // responses are recorded in the quality of the peer
+        let (tx, _rx) = oneshot::channel();
+        fetcher.prepare_block_request(
+            peer1,
+            DownloadRequest::GetBlockHeaders {
+                request: HeadersRequest {
+                    start: 0u64.into(),
+                    limit: 1,
+                    direction: Default::default(),
+                },
+                response: tx,
+                priority: Priority::default(),
+            },
+        );
+        let header = Header { number: 0, ..Default::default() };
+        fetcher.on_block_headers_response(peer1, Ok(vec![header]));
+        assert_eq!(quality1.responses(), 1);
+        assert!(quality1.useful_bytes() > 0);
+        // peers with similar scores are ranked by their timeout
+        assert_eq!(fetcher.next_best_peer(None), Some(peer1));
+
+        // the peer is demoted once requests of other components time out
+        quality1.on_timeout();
+        quality1.on_timeout();
+        assert_eq!(fetcher.next_best_peer(None), Some(peer2));
     }
 
     #[tokio::test]
@@ -600,6 +1095,7 @@ mod tests {
                     direction: Default::default(),
                 },
                 response: tx,
+                started: Instant::now(),
             };
             let header = Header { number: 0, ..Default::default() };
             (req, header)
@@ -611,6 +1107,7 @@ mod tests {
             Default::default(),
             Default::default(),
             None,
+            Default::default(),
         );
 
         let (req, header) = request_pair();
@@ -0,0 +1,262 @@
+//! Enrichment of peers with ASN and country information from local MMDB files.
+
+use crate::error::NetworkError;
+use maxminddb::{geoip2, Reader};
+use reth_metrics::{metrics::Gauge, Metrics};
+use reth_network_api::PeerGeo;
+use reth_network_peers::PeerId;
+use std::{
+    collections::{HashMap, HashSet},
+    net::IpAddr,
+    path::PathBuf,
+};
+use tracing::trace;
+
+/// Maximum number of distinct ASNs that are used as metric labels.
+///
+/// Connected peers in any other ASN are reported under the [`OTHER_LABEL`].
+const MAX_ASN_LABELS: usize = 64;
+
+/// Label for peers without known ASN or country, or with an ASN that exceeds
+/// [`MAX_ASN_LABELS`].
+const OTHER_LABEL: &str = "other";
+
+/// Configuration for the enrichment of peers with ASN and country information.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GeoIpConfig {
+    /// Paths to MMDB files, for example `GeoLite2-ASN.mmdb` and `GeoLite2-Country.mmdb`.
+    pub databases: Vec<PathBuf>,
+    /// Maximum number of connected peers within the same ASN.
+    ///
+    /// Trusted and static peers are exempt from this limit.
+    pub max_peers_per_asn: Option<usize>,
+}
+
+impl GeoIpConfig {
+    /// Creates a new config that reads the given MMDB files.
+    pub const fn new(databases: Vec<PathBuf>) -> Self {
+        Self { databases, max_peers_per_asn: None }
+    }
+
+    /// Sets the maximum number of connected peers within the same ASN.
+    pub const fn with_max_peers_per_asn(mut self, max_peers_per_asn: Option<usize>) -> Self {
+        self.max_peers_per_asn = max_peers_per_asn;
+        self
+    }
+}
+
+/// Resolves the ASN and country of IP addresses using MMDB files.
+pub struct GeoIpResolver {
+    /// The opened databases.
+    readers: Vec<Reader<Vec<u8>>>,
+}
+
+impl GeoIpResolver {
+    /// Opens the given MMDB files.
+    pub fn open(databases: &[PathBuf]) -> Result<Self, NetworkError> {
+        let readers = databases
+            .iter()
+            .map(|path| {
+                Reader::open_readfile(path).map_err(|err| NetworkError::GeoIp(path.clone(), err))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { readers })
+    }
+
+    /// Returns the ASN and country of the given IP address, merged from all databases.
+    ///
+    /// Returns `None` if none of the databases has an entry for the address.
+    pub fn lookup(&self, ip: IpAddr) -> Option<PeerGeo> {
+        let mut geo = PeerGeo::default();
+        for reader in &self.readers {
+            if reader.metadata.database_type.contains("ASN") {
+                if let Ok(Some(asn)) = reader.lookup::<geoip2::Asn<'_>>(ip) {
+                    geo.asn = geo.asn.or(asn.autonomous_system_number);
+                }
+            } else if let Ok(Some(country)) = reader.lookup::<geoip2::Country<'_>>(ip) {
+                geo.country = geo
+                    .country
+                    .or_else(|| country.country.and_then(|c| c.iso_code).map(Into::into));
+            }
+        }
+        (geo != PeerGeo::default()).then_some(geo)
+    }
+}
+
+impl std::fmt::Debug for GeoIpResolver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GeoIpResolver")
+            .field(
+                "databases",
+                &self.readers.iter().map(|r| &r.metadata.database_type).collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+/// Tracks the ASN and country of connected peers and enforces the per-ASN peer limit.
+#[derive(Debug)]
+pub(crate) struct PeerGeoTracker {
+    /// Resolves peer IP addresses.
+    resolver: GeoIpResolver,
+    /// Maximum number of connected peers within the same ASN.
+    max_peers_per_asn: Option<usize>,
+    /// Enrichment of all connected peers.
+    peers: HashMap<PeerId, PeerGeo>,
+    /// Number of connected peers per ASN.
+    peers_per_asn: HashMap<u32, usize>,
+    /// Peers whose session was rejected, until the session is closed.
+    rejected: HashSet<PeerId>,
+    /// ASNs that have their own metric label.
+    labeled_asns: HashSet<u32>,
+    /// Metrics per ASN label.
+    asn_metrics: HashMap<String, GeoPeerMetrics>,
+    /// Metrics per country label.
+    country_metrics: HashMap<String, GeoPeerMetrics>,
+}
+
+impl PeerGeoTracker {
+    /// Creates a new tracker from the given config.
+    pub(crate) fn new(config: GeoIpConfig) -> Result<Self, NetworkError> {
+        Ok(Self::with_resolver(GeoIpResolver::open(&config.databases)?, config.max_peers_per_asn))
+    }
+
+    /// Creates a new tracker with the given resolver.
+    fn with_resolver(resolver: GeoIpResolver, max_peers_per_asn: Option<usize>) -> Self {
+        Self {
+            resolver,
+            max_peers_per_asn,
+            peers: Default::default(),
+            peers_per_asn: Default::default(),
+            rejected: Default::default(),
+            labeled_asns: Default::default(),
+            asn_metrics: Default::default(),
+            country_metrics: Default::default(),
+        }
+    }
+
+    /// Returns the enrichment of a connected peer.
+    pub(crate) fn get(&self, peer_id: &PeerId) -> Option<&PeerGeo> {
+        self.peers.get(peer_id)
+    }
+
+    /// Resolves and records the established session.
+    ///
+    /// Returns `false` if the peer exceeds the per-ASN limit and should be disconnected, in which
+    /// case it's not recorded. Exempt peers are always accepted.
+    pub(crate) fn on_session_established(
+        &mut self,
+        peer_id: PeerId,
+        ip: IpAddr,
+        exempt: bool,
+    ) -> bool {
+        let geo = self.resolver.lookup(ip).unwrap_or_default();
+        trace!(target: "net::geo", ?peer_id, ?ip, ?geo, "Resolved peer");
+        self.insert(peer_id, geo, exempt)
+    }
+
+    /// Records a peer, see [`Self::on_session_established`].
+    fn insert(&mut self, peer_id: PeerId, geo: PeerGeo, exempt: bool) -> bool {
+        if let Some(asn) = geo.asn {
+            let count = self.peers_per_asn.get(&asn).copied().unwrap_or_default();
+            if !exempt && self.max_peers_per_asn.is_some_and(|max| count >= max) {
+                self.rejected.insert(peer_id);
+                return false
+            }
+            self.peers_per_asn.insert(asn, count + 1);
+        }
+
+        self.update_metrics(&geo, 1.0);
+        self.rejected.remove(&peer_id);
+        self.peers.insert(peer_id, geo);
+        true
+    }
+
+    /// Removes the peer of the closed session.
+    ///
+    /// Returns `false` if the session was rejected by [`Self::on_session_established`].
+    pub(crate) fn on_session_closed(&mut self, peer_id: &PeerId) -> bool {
+        if self.rejected.remove(peer_id) {
+            return false
+        }
+        let Some(geo) = self.peers.remove(peer_id) else { return true };
+        if let Some(asn) = geo.asn {
+            if let Some(count) = self.peers_per_asn.get_mut(&asn) {
+                *count -= 1;
+                if *count == 0 {
+                    self.peers_per_asn.remove(&asn);
+                }
+            }
+        }
+        self.update_metrics(&geo, -1.0);
+        true
+    }
+
+    /// Updates the peer gauges of the peer's ASN and country by `delta`.
+    fn update_metrics(&mut self, geo: &PeerGeo, delta: f64) {
+        let asn_label = match geo.asn {
+            Some(asn)
+                if self.labeled_asns.contains(&asn) || self.labeled_asns.len() < MAX_ASN_LABELS =>
+            {
+                self.labeled_asns.insert(asn);
+                asn.to_string()
+            }
+            _ => OTHER_LABEL.to_string(),
+        };
+        self.asn_metrics
+            .entry(asn_label)
+            .or_insert_with_key(|label| GeoPeerMetrics::new_with_labels(&[("asn", label.clone())]))
+            .connected_peers
+            .increment(delta);
+
+        // country codes are bounded by the ISO 3166 list
+        let country_label = geo.country.clone().unwrap_or_else(|| OTHER_LABEL.to_string());
+        self.country_metrics
+            .entry(country_label)
+            .or_insert_with_key(|label| {
+                GeoPeerMetrics::new_with_labels(&[("country", label.clone())])
+            })
+            .connected_peers
+            .increment(delta);
+    }
+}
+
+/// Metrics of connected peers per ASN or country.
+#[derive(Metrics, Clone)]
+#[metrics(scope = "network.geo")]
+struct GeoPeerMetrics {
+    /// Number of connected peers.
+    connected_peers: Gauge,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn geo(asn: u32) -> PeerGeo {
+        PeerGeo { asn: Some(asn), country: Some("DE".to_string()) }
+    }
+
+    #[test]
+    fn enforce_max_peers_per_asn() {
+        let resolver = GeoIpResolver::open(&[]).unwrap();
+        let mut tracker = PeerGeoTracker::with_resolver(resolver, Some(1));
+
+        let (first, second, third) = (PeerId::random(), PeerId::random(), PeerId::random());
+        assert!(tracker.insert(first, geo(1), false));
+        assert!(!tracker.insert(second, geo(1), false));
+        assert!(tracker.get(&second).is_none());
+        assert!(!tracker.on_session_closed(&second));
+        assert!(!tracker.insert(second, geo(1), false));
+
+        // exempt peers and peers in other ASNs are accepted
+        assert!(tracker.insert(second, geo(1), true));
+        assert!(tracker.insert(third, geo(2), false));
+        assert!(tracker.insert(PeerId::random(), PeerGeo::default(), false));
+
+        assert!(tracker.on_session_closed(&first));
+        assert!(tracker.on_session_closed(&second));
+        assert!(tracker.insert(first, geo(1), false));
+        assert_eq!(tracker.get(&first), Some(&geo(1)));
+    }
+}
@@ -144,6 +144,7 @@ mod builder;
 mod discovery;
 mod fetch;
 mod flattened_response;
+mod geo;
 mod listener;
 mod manager;
 mod metrics;
@@ -157,7 +158,8 @@ pub use reth_eth_wire::{DisconnectReason, HelloMessageWithProtocols};
 pub use reth_eth_wire_types::{primitives, EthNetworkPrimitives, NetworkPrimitives};
 pub use reth_network_api::{
     events, BlockDownloaderProvider, DiscoveredEvent, DiscoveryEvent, NetworkEvent,
-    NetworkEventListenerProvider, NetworkInfo, PeerRequest, PeerRequestSender, Peers, PeersInfo,
+    NetworkEventListenerProvider, NetworkInfo, PeerGeo, PeerRequest, PeerRequestSender, Peers,
+    PeersInfo,
 };
 pub use reth_network_p2p::sync::{NetworkSyncUpdater, SyncState};
 pub use reth_network_types::{PeersConfig, SessionsConfig};
@@ -167,11 +169,15 @@ pub use session::{
     SessionEvent, SessionId, SessionManager,
 };
 
+#[cfg(any(test, feature = "test-utils"))]
+pub use session::{LinkConditions, SessionFault};
+
 pub use builder::NetworkBuilder;
 pub use config::{NetworkConfig, NetworkConfigBuilder};
 pub use discovery::Discovery;
 pub use fetch::FetchClient;
 pub use flattened_response::FlattenedResponse;
+pub use geo::{GeoIpConfig, GeoIpResolver};
 pub use manager::NetworkManager;
 pub use metrics::TxTypesCounter;
 pub use network::{NetworkHandle, NetworkProtocols};
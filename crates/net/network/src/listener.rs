@@ -1,9 +1,10 @@
 //! Contains connection-oriented interfaces.
 
 use futures::{ready, Stream, StreamExt};
+use socket2::{Domain, Protocol, Socket, Type};
 use std::{
     io,
-    net::SocketAddr,
+    net::{IpAddr, SocketAddr},
     pin::Pin,
     task::{Context, Poll},
 };
@@ -23,8 +24,14 @@ pub struct ConnectionListener {
 
 impl ConnectionListener {
     /// Creates a new [`TcpListener`] that listens for incoming connections.
+    ///
+    /// If the address is the unspecified IPv6 address, the listener is dual-stack and also accepts
+    /// IPv4 connections.
     pub async fn bind(addr: SocketAddr) -> io::Result<Self> {
-        let listener = TcpListener::bind(addr).await?;
+        let listener = match addr.ip() {
+            IpAddr::V6(ip) if ip.is_unspecified() => bind_dual_stack(addr)?,
+            _ => TcpListener::bind(addr).await?,
+        };
         let local_addr = listener.local_addr()?;
         Ok(Self::new(listener, local_addr))
     }
@@ -39,6 +46,9 @@ impl ConnectionListener {
         let this = self.get_mut();
         match ready!(this.incoming.poll_next_unpin(cx)) {
             Some(Ok((stream, remote_addr))) => {
+                // IPv4 peers of a dual-stack listener have IPv4-mapped IPv6 addresses
+                let remote_addr =
+                    SocketAddr::new(remote_addr.ip().to_canonical(), remote_addr.port());
                 if let Err(err) = stream.set_nodelay(true) {
                     tracing::warn!(target: "net", "set nodelay failed: {:?}", err);
                 }
@@ -57,6 +67,20 @@ impl ConnectionListener {
     }
 }
 
+/// Binds a listener to the unspecified IPv6 address that also accepts IPv4 connections, regardless
+/// of the system default for IPv6 sockets.
+fn bind_dual_stack(addr: SocketAddr) -> io::Result<TcpListener> {
+    let socket = Socket::new(Domain::IPV6, Type::STREAM, Some(Protocol::TCP))?;
+    socket.set_only_v6(false)?;
+    // same as `TcpListener::bind`
+    #[cfg(unix)]
+    socket.set_reuse_address(true)?;
+    socket.set_nonblocking(true)?;
+    socket.bind(&addr.into())?;
+    socket.listen(1024)?;
+    TcpListener::from_std(socket.into())
+}
+
 /// Event type produced by the [`TcpListenerStream`].
 pub enum ListenerEvent {
     /// Received a new incoming.
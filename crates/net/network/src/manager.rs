@@ -21,6 +21,7 @@ use crate::{
     discovery::Discovery,
     error::{NetworkError, ServiceKind},
     eth_requests::IncomingEthRequest,
+    geo::PeerGeoTracker,
     import::{BlockImport, BlockImportEvent, BlockImportOutcome, BlockValidation, NewBlockEvent},
     listener::ConnectionListener,
     message::{NewBlockMessage, PeerMessage},
@@ -44,12 +45,11 @@ use reth_metrics::common::mpsc::UnboundedMeteredSender;
 use reth_network_api::{
     events::{PeerEvent, SessionInfo},
     test_utils::PeersHandle,
-    EthProtocolInfo, NetworkEvent, NetworkStatus, PeerInfo, PeerRequest,
+    EthProtocolInfo, NetworkEvent, NetworkStatus, PeerGeo, PeerInfo, PeerRequest,
 };
 use reth_network_peers::{NodeRecord, PeerId};
 use reth_network_types::ReputationChangeKind;
 use reth_storage_api::BlockNumReader;
-use reth_tasks::shutdown::GracefulShutdown;
 use reth_tokio_util::EventSender;
 use secp256k1::SecretKey;
 use std::{
@@ -146,6 +146,8 @@ pub struct NetworkManager<N: NetworkPrimitives = EthNetworkPrimitives> {
     metrics: NetworkMetrics,
     /// Disconnect metrics for the Network
     disconnect_metrics: DisconnectMetrics,
+    /// Tracks the ASN and country of connected peers, if GeoIP enrichment is enabled.
+    geo: Option<PeerGeoTracker>,
 }
 
 impl NetworkManager {
@@ -257,9 +259,14 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             tx_gossip_disabled,
             transactions_manager_config: _,
             nat,
+            port_mapping,
+            geoip,
             handshake,
+            clock,
         } = config;
 
+        let geo = geoip.map(PeerGeoTracker::new).transpose()?;
+
         let peers_manager = PeersManager::new(peers_config);
         let peers_handle = peers_manager.handle();
 
@@ -287,7 +294,7 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             discv5.extend_unsigned_boot_nodes(resolved_boot_nodes)
         }
 
-        let discovery = Discovery::new(
+        let mut discovery = Discovery::new(
             listener_addr,
             discovery_v4_addr,
             secret_key,
@@ -296,6 +303,9 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             dns_discovery_config,
         )
         .await?;
+        if let Some(port_mapping) = port_mapping {
+            discovery.spawn_port_mapping(port_mapping);
+        }
         // need to retrieve the addr here since provided port could be `0`
         let local_peer_id = discovery.local_id();
         let discv4 = discovery.discv4();
@@ -312,6 +322,7 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             fork_filter,
             extra_protocols,
             handshake,
+            clock,
         );
 
         let state = NetworkState::new(
@@ -354,6 +365,7 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             num_active_peers,
             metrics: Default::default(),
             disconnect_metrics: Default::default(),
+            geo,
         })
     }
 
@@ -683,6 +695,14 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             NetworkHandleMessage::DisconnectPeer(peer_id, reason) => {
                 self.swarm.sessions_mut().disconnect(peer_id, reason);
             }
+            #[cfg(any(test, feature = "test-utils"))]
+            NetworkHandleMessage::InjectSessionFault(peer_id, fault) => {
+                self.swarm.sessions().inject_fault(&peer_id, fault);
+            }
+            #[cfg(any(test, feature = "test-utils"))]
+            NetworkHandleMessage::ShapeLinks(conditions) => {
+                self.swarm.sessions_mut().shape_links(conditions);
+            }
             NetworkHandleMessage::ConnectPeer(peer_id, kind, addr) => {
                 self.swarm.state_mut().add_and_connect(peer_id, kind, addr);
             }
@@ -776,6 +796,7 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
                 messages,
                 status,
                 direction,
+                quality,
             } => {
                 let total_active = self.num_active_peers.fetch_add(1, Ordering::Relaxed) + 1;
                 self.metrics.connected_peers.set(total_active as f64);
@@ -810,6 +831,19 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
                     .peer_by_id(peer_id)
                     .map(|(_, kind)| kind)
                     .unwrap_or_default();
+
+                if let Some(geo) = &mut self.geo {
+                    let exempt = peer_kind.is_trusted() || peer_kind.is_static();
+                    if !geo.on_session_established(peer_id, remote_addr.ip(), exempt) {
+                        debug!(target: "net", ?peer_id, ?remote_addr, "Disconnecting peer, too many peers in the same ASN");
+                        self.swarm
+                            .sessions_mut()
+                            .disconnect(peer_id, Some(DisconnectReason::TooManyPeers));
+                        // the session is neither announced nor is its closing
+                        return
+                    }
+                }
+
                 let session_info = SessionInfo {
                     peer_id,
                     remote_addr,
@@ -818,6 +852,7 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
                     status,
                     version,
                     peer_kind,
+                    quality,
                 };
 
                 self.event_sender
@@ -836,6 +871,7 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             SwarmEvent::SessionClosed { peer_id, remote_addr, error } => {
                 let total_active = self.num_active_peers.fetch_sub(1, Ordering::Relaxed) - 1;
                 self.metrics.connected_peers.set(total_active as f64);
+                let announced = self.geo.as_mut().is_none_or(|geo| geo.on_session_closed(&peer_id));
                 trace!(
                     target: "net",
                     ?remote_addr,
@@ -873,8 +909,10 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
                             .saturating_sub(1)
                             as f64,
                     );
-                self.event_sender
-                    .notify(NetworkEvent::Peer(PeerEvent::SessionClosed { peer_id, reason }));
+                if announced {
+                    self.event_sender
+                        .notify(NetworkEvent::Peer(PeerEvent::SessionClosed { peer_id, reason }));
+                }
             }
             SwarmEvent::IncomingPendingSessionClosed { remote_addr, error } => {
                 trace!(
@@ -990,6 +1028,11 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
         }
     }
 
+    /// Returns the ASN and country of a connected peer, if GeoIP enrichment is enabled.
+    fn peer_geo(&self, peer_id: &PeerId) -> Option<PeerGeo> {
+        self.geo.as_ref().and_then(|geo| geo.get(peer_id).cloned())
+    }
+
     /// Returns [`PeerInfo`] for all connected peers
     fn get_peer_infos(&self) -> Vec<PeerInfo> {
         self.swarm
@@ -1001,7 +1044,7 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
                     .state()
                     .peers()
                     .peer_by_id(peer_id)
-                    .map(|(record, kind)| session.peer_info(&record, kind))
+                    .map(|(record, kind)| session.peer_info(&record, kind, self.peer_geo(&peer_id)))
             })
             .collect()
     }
@@ -1015,7 +1058,7 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
                 .state()
                 .peers()
                 .peer_by_id(peer_id)
-                .map(|(record, kind)| session.peer_info(&record, kind))
+                .map(|(record, kind)| session.peer_info(&record, kind, self.peer_geo(&peer_id)))
         })
     }
 
@@ -1048,15 +1091,18 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             .set(self.swarm.sessions().num_pending_connections() as f64);
     }
 
-    /// Drives the [`NetworkManager`] future until a [`GracefulShutdown`] signal is received.
+    /// Drives the [`NetworkManager`] future until the `shutdown` future resolves, e.g. a
+    /// [`GracefulShutdown`](reth_tasks::shutdown::GracefulShutdown) signal is received.
     ///
-    /// This invokes the given function `shutdown_hook` while holding the graceful shutdown guard.
-    pub async fn run_until_graceful_shutdown<F, R>(
+    /// This invokes the given function `shutdown_hook` while holding the guard the `shutdown`
+    /// future resolved to.
+    pub async fn run_until_graceful_shutdown<S, G, F, R>(
         mut self,
-        shutdown: GracefulShutdown,
+        shutdown: S,
         shutdown_hook: F,
     ) -> R
     where
+        S: Future<Output = G>,
         F: FnOnce(Self) -> R,
     {
         let mut graceful_guard = None;
@@ -119,6 +119,13 @@ pub struct TransactionsManagerMetrics {
     /// Total number of times a transaction is sent that is already in the local pool.
     pub(crate) occurrences_transactions_already_in_pool: Counter,
 
+    /* -- Replaced local txns -- */
+    /// Total number of times gossip of a recently replaced local transaction was suppressed.
+    pub(crate) suppressed_replaced_transactions: Counter,
+    /// Total number of times a peer that announced a replaced transaction was sent the hash of
+    /// the replacement.
+    pub(crate) replacement_announcements: Counter,
+
     /* ================ POOL IMPORTS ================ */
     /// Number of transactions about to be imported into the pool.
     pub(crate) pending_pool_imports: Gauge,
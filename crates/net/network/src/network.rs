@@ -190,6 +190,24 @@ impl<N: NetworkPrimitives> NetworkHandle<N> {
     pub fn secret_key(&self) -> &SecretKey {
         &self.inner.secret_key
     }
+
+    /// Injects a fault into the active session with the given peer, as if the peer misbehaved.
+    ///
+    /// This is intended for resilience tests of the session's protocol violation and request
+    /// timeout handling.
+    #[cfg(any(test, feature = "test-utils"))]
+    pub fn inject_session_fault(&self, peer_id: PeerId, fault: crate::session::SessionFault) {
+        self.send_message(NetworkHandleMessage::InjectSessionFault(peer_id, fault))
+    }
+
+    /// Simulates the given link conditions for the messages received from all peers, or removes
+    /// the simulation if `None`.
+    ///
+    /// This is intended for benchmarks that measure sync under controlled network conditions.
+    #[cfg(any(test, feature = "test-utils"))]
+    pub fn shape_links(&self, conditions: Option<crate::session::LinkConditions>) {
+        self.send_message(NetworkHandleMessage::ShapeLinks(conditions))
+    }
 }
 
 // === API Implementations ===
@@ -548,4 +566,10 @@ pub(crate) enum NetworkHandleMessage<N: NetworkPrimitives = EthNetworkPrimitives
     ConnectPeer(PeerId, PeerKind, PeerAddr),
     /// Message to update the node's advertised block range information.
     InternalBlockRangeUpdate(BlockRangeUpdate),
+    /// Injects a fault into the session with the given peer.
+    #[cfg(any(test, feature = "test-utils"))]
+    InjectSessionFault(PeerId, crate::session::SessionFault),
+    /// Simulates link conditions for the messages received from all peers.
+    #[cfg(any(test, feature = "test-utils"))]
+    ShapeLinks(Option<crate::session::LinkConditions>),
 }
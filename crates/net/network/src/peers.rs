@@ -723,8 +723,13 @@ impl PeersManager {
     }
 
     /// Marks the given peer as trusted.
+    ///
+    /// If the peer is already tracked, it's trusted immediately.
     pub(crate) fn add_trusted_peer_id(&mut self, peer_id: PeerId) {
         self.trusted_peer_ids.insert(peer_id);
+        if let Some(peer) = self.peers.get_mut(&peer_id) {
+            peer.kind = PeerKind::Trusted;
+        }
     }
 
     /// Called for a newly discovered trusted peer.
@@ -864,6 +869,9 @@ impl PeersManager {
 
     /// Removes the tracked node from the trusted set.
     pub(crate) fn remove_peer_from_trusted_set(&mut self, peer_id: PeerId) {
+        // the peer id may have been trusted without a tracked peer
+        self.trusted_peer_ids.remove(&peer_id);
+
         let Entry::Occupied(mut entry) = self.peers.entry(peer_id) else { return };
         if !entry.get().is_trusted() {
             return
@@ -871,8 +879,6 @@ impl PeersManager {
 
         let peer = entry.get_mut();
         peer.kind = PeerKind::Basic;
-
-        self.trusted_peer_ids.remove(&peer_id);
     }
 
     /// Returns the idle peer with the highest reputation.
@@ -1998,6 +2004,27 @@ mod tests {
         assert_eq!(peers.connection_info.num_inbound, 1);
     }
 
+    #[tokio::test]
+    async fn test_trusted_peer_id() {
+        let peer = PeerId::random();
+        let socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        let mut peers = PeersManager::default();
+
+        // an untracked peer id is removed from the trusted set
+        peers.add_trusted_peer_id(peer);
+        assert!(peers.trusted_peer_ids.contains(&peer));
+        peers.remove_peer_from_trusted_set(peer);
+        assert!(!peers.trusted_peer_ids.contains(&peer));
+
+        // a tracked peer is trusted immediately
+        peers.add_peer(peer, PeerAddr::from_tcp(socket_addr), None);
+        peers.add_trusted_peer_id(peer);
+        assert!(peers.peers.get(&peer).unwrap().is_trusted());
+        peers.remove_peer_from_trusted_set(peer);
+        assert!(!peers.peers.get(&peer).unwrap().is_trusted());
+        assert!(!peers.trusted_peer_ids.contains(&peer));
+    }
+
     #[tokio::test]
     async fn test_reputation_change_trusted_peer() {
         let peer = PeerId::random();
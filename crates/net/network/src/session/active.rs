@@ -43,6 +43,9 @@ use tokio_stream::wrappers::ReceiverStream;
 use tokio_util::sync::PollSender;
 use tracing::{debug, trace};
 
+#[cfg(any(test, feature = "test-utils"))]
+use crate::session::{fault::SessionFaults, shaping::LinkShaper};
+
 /// The recommended interval at which a new range update should be sent to the remote peer.
 ///
 /// This is set to 120 seconds (2 minutes) as per the Ethereum specification for eth69.
@@ -127,7 +130,13 @@ pub(crate) struct ActiveSession<N: NetworkPrimitives> {
     pub(crate) local_range_info: BlockRangeInfo,
     /// Optional interval for sending periodic range updates to the remote peer (eth69+)
     /// Recommended frequency is ~2 minutes per spec
-    pub(crate) range_update_interval: Option<Interval>,
+    pub(crate) range_update_interval: Option<reth_tasks::time::Interval>,
+    /// Faults injected into this session for testing.
+    #[cfg(any(test, feature = "test-utils"))]
+    pub(crate) faults: SessionFaults<N>,
+    /// Simulated link conditions of this session for testing.
+    #[cfg(any(test, feature = "test-utils"))]
+    pub(crate) link: LinkShaper<N>,
 }
 
 impl<N: NetworkPrimitives> ActiveSession<N> {
@@ -517,6 +526,45 @@ impl<N: NetworkPrimitives> ActiveSession<N> {
         self.internal_request_timeout_interval = tokio::time::interval(request_timeout);
     }
 
+    /// Reads the next message from the connection.
+    #[cfg(not(any(test, feature = "test-utils")))]
+    fn poll_next_message(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<EthMessage<N>, EthStreamError>>> {
+        self.conn.poll_next_unpin(cx)
+    }
+
+    /// Reads the next message from the connection, applying the injected faults and the
+    /// simulated link conditions.
+    #[cfg(any(test, feature = "test-utils"))]
+    fn poll_next_message(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<EthMessage<N>, EthStreamError>>> {
+        let version = self.conn.version();
+        loop {
+            if let Poll::Ready(msg) = self.link.poll_released(cx) {
+                return Poll::Ready(Some(Ok(msg)))
+            }
+            if let Poll::Ready(res) = self.faults.poll_injected(cx, version) {
+                return Poll::Ready(Some(res))
+            }
+            match ready!(self.conn.poll_next_unpin(cx)) {
+                Some(Ok(msg)) => match self.faults.on_message(msg, version) {
+                    Some(Ok(msg)) => {
+                        if let Some(msg) = self.link.on_message(msg) {
+                            return Poll::Ready(Some(Ok(msg)))
+                        }
+                    }
+                    Some(Err(err)) => return Poll::Ready(Some(Err(err))),
+                    None => {}
+                },
+                res => return Poll::Ready(res),
+            }
+        }
+    }
+
     /// If a termination message is queued this will try to send it
     fn poll_terminate_message(&mut self, cx: &mut Context<'_>) -> Option<Poll<()>> {
         let (mut tx, msg) = self.terminate_message.take()?;
@@ -590,6 +638,16 @@ impl<N: NetworkPrimitives> Future for ActiveSession<N> {
                             SessionCommand::Message(msg) => {
                                 this.on_internal_peer_message(msg);
                             }
+                            #[cfg(any(test, feature = "test-utils"))]
+                            SessionCommand::InjectFault(fault) => {
+                                debug!(target: "net::session", ?fault, remote_peer_id=?this.remote_peer_id, "Injecting session fault");
+                                this.faults.inject(fault);
+                            }
+                            #[cfg(any(test, feature = "test-utils"))]
+                            SessionCommand::ShapeLink(conditions) => {
+                                debug!(target: "net::session", ?conditions, remote_peer_id=?this.remote_peer_id, "Shaping session link");
+                                this.link.set_conditions(conditions);
+                            }
                         }
                     }
                 }
@@ -686,7 +744,7 @@ impl<N: NetworkPrimitives> Future for ActiveSession<N> {
                     break 'receive
                 }
 
-                match this.conn.poll_next_unpin(cx) {
+                match this.poll_next_message(cx) {
                     Poll::Pending => break,
                     Poll::Ready(None) => {
                         if this.is_disconnecting() {
@@ -901,12 +959,14 @@ impl<N: NetworkPrimitives> QueuedOutgoingMessages<N> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::session::{handle::PendingSessionEvent, start_pending_incoming_session};
+    use crate::session::{
+        handle::PendingSessionEvent, start_pending_incoming_session, SessionFault,
+    };
     use alloy_eips::eip2124::ForkFilter;
     use reth_chainspec::MAINNET;
     use reth_ecies::stream::ECIESStream;
     use reth_eth_wire::{
-        handshake::EthHandshake, EthNetworkPrimitives, EthStream, GetBlockBodies,
+        handshake::EthHandshake, BlockBodies, EthNetworkPrimitives, EthStream, GetBlockBodies,
         HelloMessageWithProtocols, P2PStream, StatusBuilder, UnauthedEthStream, UnauthedP2PStream,
         UnifiedStatus,
     };
@@ -1046,6 +1106,8 @@ mod tests {
                             alloy_primitives::B256::ZERO,
                         ),
                         range_update_interval: None,
+                        faults: Default::default(),
+                        link: LinkShaper::new(None),
                     }
                 }
                 ev => {
@@ -1291,6 +1353,76 @@ mod tests {
         }
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_injected_malformed_frame() {
+        let mut builder = SessionBuilder::default();
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let local_addr = listener.local_addr().unwrap();
+
+        let fut = builder.with_client_stream(local_addr, move |client_stream| async move {
+            let _client_stream = client_stream;
+            tokio::time::sleep(Duration::from_secs(60)).await;
+        });
+        tokio::task::spawn(fut);
+
+        let (incoming, _) = listener.accept().await.unwrap();
+        let session = builder.connect_incoming(incoming).await;
+        builder.to_sessions[0]
+            .send(SessionCommand::InjectFault(SessionFault::MalformedFrame(
+                alloy_primitives::Bytes::from_static(&[0x06, 0xff]),
+            )))
+            .await
+            .unwrap();
+        tokio::spawn(session);
+
+        let msg = builder.active_session_rx.next().await.unwrap();
+        match msg {
+            ActiveSessionMessage::ClosedOnConnectionError {
+                error: EthStreamError::InvalidMessage(_),
+                ..
+            } => {}
+            ev => unreachable!("{ev:?}"),
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_injected_dropped_responses() {
+        reth_tracing::init_test_tracing();
+
+        let mut builder = SessionBuilder::default();
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let local_addr = listener.local_addr().unwrap();
+
+        // the remote answers all requests, but the answers never reach the session
+        let fut = builder.with_client_stream(local_addr, move |mut client_stream| async move {
+            while let Some(Ok(EthMessage::GetBlockBodies(req))) = client_stream.next().await {
+                let resp = RequestPair { request_id: req.request_id, message: BlockBodies(vec![]) };
+                client_stream.send(EthMessage::BlockBodies(resp)).await.unwrap();
+            }
+        });
+        tokio::task::spawn(fut);
+
+        let request_timeout = Duration::from_millis(100);
+
+        let (incoming, _) = listener.accept().await.unwrap();
+        let mut session = builder.connect_incoming(incoming).await;
+        session.faults.inject(SessionFault::DropResponses);
+        session
+            .internal_request_timeout
+            .store(request_timeout.as_millis() as u64, Ordering::Relaxed);
+        session.internal_request_timeout_interval =
+            tokio::time::interval_at(tokio::time::Instant::now(), request_timeout);
+        let (tx, rx) = oneshot::channel();
+        let req = PeerRequest::GetBlockBodies { request: GetBlockBodies(vec![]), response: tx };
+        session.on_internal_peer_request(req, Instant::now() + request_timeout);
+        tokio::spawn(session);
+
+        let err = rx.await.unwrap().unwrap_err();
+        assert_eq!(err, RequestError::Timeout);
+    }
+
     #[test]
     fn timeout_calculation_sanity_tests() {
         let rtt = Duration::from_secs(5);
@@ -0,0 +1,194 @@
+//! Fault injection for active sessions.
+//!
+//! This is only available for testing and allows to exercise the protocol violation and request
+//! timeout paths of a session without a misbehaving remote peer.
+
+use alloy_primitives::Bytes;
+use alloy_rlp::Encodable;
+use reth_eth_wire::{errors::EthStreamError, EthMessage, EthVersion, NetworkPrimitives};
+use reth_eth_wire_types::ProtocolMessage;
+use std::{
+    collections::VecDeque,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::time::{Instant, Sleep};
+
+/// A fault that is injected into an active session, as if the remote peer misbehaved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SessionFault {
+    /// Handles the given bytes as if they were an `eth` message read from the connection.
+    MalformedFrame(Bytes),
+    /// Truncates the next message read from the connection to the given number of bytes before
+    /// decoding it.
+    TruncateNext(usize),
+    /// Holds back responses read from the connection for the given duration.
+    DelayResponses(Duration),
+    /// Discards responses read from the connection, as if the peer never answered.
+    DropResponses,
+    /// Removes all previously injected faults, discarding held back responses.
+    Clear,
+}
+
+/// The faults that are currently injected into an active session.
+#[derive(Debug)]
+pub(crate) struct SessionFaults<N: NetworkPrimitives> {
+    /// Frames to handle before reading from the connection.
+    frames: VecDeque<Bytes>,
+    /// Truncate the next message read from the connection.
+    truncate_next: Option<usize>,
+    /// Delay for responses read from the connection.
+    response_delay: Option<Duration>,
+    /// Whether responses read from the connection are discarded.
+    drop_responses: bool,
+    /// Responses that are held back, in order of their release.
+    delayed: VecDeque<(Instant, EthMessage<N>)>,
+    /// Wakes the session when the next delayed response is due.
+    timer: Option<Pin<Box<Sleep>>>,
+}
+
+impl<N: NetworkPrimitives> SessionFaults<N> {
+    /// Applies the given fault to the session.
+    pub(crate) fn inject(&mut self, fault: SessionFault) {
+        match fault {
+            SessionFault::MalformedFrame(frame) => self.frames.push_back(frame),
+            SessionFault::TruncateNext(len) => self.truncate_next = Some(len),
+            SessionFault::DelayResponses(delay) => self.response_delay = Some(delay),
+            SessionFault::DropResponses => self.drop_responses = true,
+            SessionFault::Clear => *self = Self::default(),
+        }
+    }
+
+    /// Returns the next injected frame or the next delayed response that is due.
+    pub(crate) fn poll_injected(
+        &mut self,
+        cx: &mut Context<'_>,
+        version: EthVersion,
+    ) -> Poll<Result<EthMessage<N>, EthStreamError>> {
+        if let Some(frame) = self.frames.pop_front() {
+            return Poll::Ready(decode(version, &frame))
+        }
+
+        let Some((release, _)) = self.delayed.front() else { return Poll::Pending };
+        let release = *release;
+        let timer = self.timer.get_or_insert_with(|| Box::pin(tokio::time::sleep_until(release)));
+        if timer.deadline() != release {
+            timer.as_mut().reset(release);
+        }
+        if timer.as_mut().poll(cx).is_pending() {
+            return Poll::Pending
+        }
+
+        self.timer = None;
+        let (_, msg) = self.delayed.pop_front().expect("exists");
+        Poll::Ready(Ok(msg))
+    }
+
+    /// Applies the injected faults to a message read from the connection.
+    ///
+    /// Returns `None` if the message was held back or discarded.
+    pub(crate) fn on_message(
+        &mut self,
+        msg: EthMessage<N>,
+        version: EthVersion,
+    ) -> Option<Result<EthMessage<N>, EthStreamError>> {
+        if let Some(len) = self.truncate_next.take() {
+            let mut buf = Vec::new();
+            ProtocolMessage::from(msg).encode(&mut buf);
+            buf.truncate(len);
+            return Some(decode(version, &buf))
+        }
+
+        if msg.is_response() {
+            if self.drop_responses {
+                return None
+            }
+            if let Some(delay) = self.response_delay {
+                self.delayed.push_back((Instant::now() + delay, msg));
+                return None
+            }
+        }
+
+        Some(Ok(msg))
+    }
+}
+
+impl<N: NetworkPrimitives> Default for SessionFaults<N> {
+    fn default() -> Self {
+        Self {
+            frames: Default::default(),
+            truncate_next: None,
+            response_delay: None,
+            drop_responses: false,
+            delayed: Default::default(),
+            timer: None,
+        }
+    }
+}
+
+/// Decodes a raw `eth` message the same way the connection does.
+fn decode<N: NetworkPrimitives>(
+    version: EthVersion,
+    mut buf: &[u8],
+) -> Result<EthMessage<N>, EthStreamError> {
+    ProtocolMessage::decode_message(version, &mut buf)
+        .map(|msg| msg.message)
+        .map_err(EthStreamError::InvalidMessage)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_eth_wire::{BlockBodies, EthNetworkPrimitives, GetBlockBodies, RequestPair};
+
+    fn response() -> EthMessage<EthNetworkPrimitives> {
+        EthMessage::BlockBodies(RequestPair { request_id: 1, message: BlockBodies(vec![]) })
+    }
+
+    #[test]
+    fn truncates_next_message() {
+        let mut faults = SessionFaults::<EthNetworkPrimitives>::default();
+        faults.inject(SessionFault::TruncateNext(1));
+
+        assert!(faults.on_message(response(), EthVersion::Eth68).unwrap().is_err());
+        // only the next message is truncated
+        assert!(faults.on_message(response(), EthVersion::Eth68).unwrap().is_ok());
+    }
+
+    #[test]
+    fn drops_only_responses() {
+        let mut faults = SessionFaults::<EthNetworkPrimitives>::default();
+        faults.inject(SessionFault::DropResponses);
+
+        assert!(faults.on_message(response(), EthVersion::Eth68).is_none());
+        let request = EthMessage::GetBlockBodies(RequestPair {
+            request_id: 1,
+            message: GetBlockBodies(vec![]),
+        });
+        assert!(faults.on_message(request, EthVersion::Eth68).is_some());
+
+        faults.inject(SessionFault::Clear);
+        assert!(faults.on_message(response(), EthVersion::Eth68).is_some());
+    }
+
+    #[tokio::test]
+    async fn releases_delayed_responses() {
+        let mut faults = SessionFaults::<EthNetworkPrimitives>::default();
+        faults.inject(SessionFault::DelayResponses(Duration::from_millis(10)));
+        assert!(faults.on_message(response(), EthVersion::Eth68).is_none());
+
+        let msg = std::future::poll_fn(|cx| faults.poll_injected(cx, EthVersion::Eth68)).await;
+        assert_eq!(msg.unwrap(), response());
+    }
+
+    #[tokio::test]
+    async fn decodes_malformed_frame() {
+        let mut faults = SessionFaults::<EthNetworkPrimitives>::default();
+        faults.inject(SessionFault::MalformedFrame(Bytes::from_static(&[0x06, 0xff])));
+
+        let msg = std::future::poll_fn(|cx| faults.poll_injected(cx, EthVersion::Eth68)).await;
+        assert!(matches!(msg, Err(EthStreamError::InvalidMessage(_))));
+    }
+}
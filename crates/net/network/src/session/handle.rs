@@ -10,7 +10,7 @@ use reth_eth_wire::{
     errors::EthStreamError, Capabilities, DisconnectReason, EthVersion, NetworkPrimitives,
     UnifiedStatus,
 };
-use reth_network_api::PeerInfo;
+use reth_network_api::{PeerGeo, PeerInfo};
 use reth_network_peers::{NodeRecord, PeerId};
 use reth_network_types::PeerKind;
 use std::{io, net::SocketAddr, sync::Arc, time::Instant};
@@ -95,6 +95,22 @@ impl<N: NetworkPrimitives> ActiveSessionHandle<N> {
         self.commands_to_session.clone().send(SessionCommand::Disconnect { reason }).await
     }
 
+    /// Injects a fault into the session, as if the remote peer misbehaved.
+    ///
+    /// Returns `false` if the command could not be delivered to the session.
+    #[cfg(any(test, feature = "test-utils"))]
+    pub fn inject_fault(&self, fault: crate::session::SessionFault) -> bool {
+        self.commands_to_session.clone().try_send(SessionCommand::InjectFault(fault)).is_ok()
+    }
+
+    /// Simulates the given link conditions for the messages received in the session.
+    ///
+    /// Returns `false` if the command could not be delivered to the session.
+    #[cfg(any(test, feature = "test-utils"))]
+    pub fn shape_link(&self, conditions: Option<crate::session::LinkConditions>) -> bool {
+        self.commands_to_session.clone().try_send(SessionCommand::ShapeLink(conditions)).is_ok()
+    }
+
     /// Returns the direction of the active session (inbound or outbound).
     pub const fn direction(&self) -> Direction {
         self.direction
@@ -136,7 +152,12 @@ impl<N: NetworkPrimitives> ActiveSessionHandle<N> {
     }
 
     /// Extracts the [`PeerInfo`] from the session handle.
-    pub(crate) fn peer_info(&self, record: &NodeRecord, kind: PeerKind) -> PeerInfo {
+    pub(crate) fn peer_info(
+        &self,
+        record: &NodeRecord,
+        kind: PeerKind,
+        geo: Option<PeerGeo>,
+    ) -> PeerInfo {
         PeerInfo {
             remote_id: self.remote_id,
             direction: self.direction,
@@ -150,6 +171,7 @@ impl<N: NetworkPrimitives> ActiveSessionHandle<N> {
             status: self.status.clone(),
             session_established: self.established,
             kind,
+            geo,
         }
     }
 }
@@ -228,6 +250,12 @@ pub enum SessionCommand<N: NetworkPrimitives> {
     },
     /// Sends a message to the peer
     Message(PeerMessage<N>),
+    /// Injects a fault into the session
+    #[cfg(any(test, feature = "test-utils"))]
+    InjectFault(crate::session::SessionFault),
+    /// Simulates link conditions for the messages received in the session
+    #[cfg(any(test, feature = "test-utils"))]
+    ShapeLink(Option<crate::session::LinkConditions>),
 }
 
 /// Message variants an active session can produce and send back to the
@@ -3,7 +3,11 @@
 mod active;
 mod conn;
 mod counter;
+#[cfg(any(test, feature = "test-utils"))]
+mod fault;
 mod handle;
+#[cfg(any(test, feature = "test-utils"))]
+mod shaping;
 mod types;
 pub use types::BlockRangeInfo;
 
@@ -28,7 +32,10 @@ use reth_metrics::common::mpsc::MeteredPollSender;
 use reth_network_api::{PeerRequest, PeerRequestSender};
 use reth_network_peers::PeerId;
 use reth_network_types::SessionsConfig;
-use reth_tasks::TaskSpawner;
+use reth_tasks::{
+    time::{Interval, MissedTickBehavior, SharedClock},
+    TaskSpawner,
+};
 use rustc_hash::FxHashMap;
 use secp256k1::SecretKey;
 use std::{
@@ -56,6 +63,11 @@ pub use handle::{
 };
 pub use reth_network_api::{Direction, PeerInfo};
 
+#[cfg(any(test, feature = "test-utils"))]
+pub use fault::SessionFault;
+#[cfg(any(test, feature = "test-utils"))]
+pub use shaping::LinkConditions;
+
 /// Internal identifier for active sessions.
 #[derive(Debug, Clone, Copy, PartialOrd, PartialEq, Eq, Hash)]
 pub struct SessionId(usize);
@@ -117,9 +129,14 @@ pub struct SessionManager<N: NetworkPrimitives> {
     metrics: SessionManagerMetrics,
     /// The [`EthRlpxHandshake`] is used to perform the initial handshake with the peer.
     handshake: Arc<dyn EthRlpxHandshake>,
+    /// The clock that drives the timers of active sessions.
+    clock: SharedClock,
     /// Shared local range information that gets propagated to active sessions.
     /// This represents the range of blocks that this node can serve to other peers.
     local_range_info: BlockRangeInfo,
+    /// Conditions of the simulated links to all peers.
+    #[cfg(any(test, feature = "test-utils"))]
+    link_conditions: Option<LinkConditions>,
 }
 
 // === impl SessionManager ===
@@ -136,6 +153,7 @@ impl<N: NetworkPrimitives> SessionManager<N> {
         fork_filter: ForkFilter,
         extra_protocols: RlpxSubProtocols,
         handshake: Arc<dyn EthRlpxHandshake>,
+        clock: SharedClock,
     ) -> Self {
         let (pending_sessions_tx, pending_sessions_rx) = mpsc::channel(config.session_event_buffer);
         let (active_session_tx, active_session_rx) = mpsc::channel(config.session_event_buffer);
@@ -170,7 +188,10 @@ impl<N: NetworkPrimitives> SessionManager<N> {
             disconnections_counter: Default::default(),
             metrics: Default::default(),
             handshake,
+            clock,
             local_range_info,
+            #[cfg(any(test, feature = "test-utils"))]
+            link_conditions: None,
         }
     }
 
@@ -225,11 +246,13 @@ impl<N: NetworkPrimitives> SessionManager<N> {
 
     /// Spawns the given future onto a new task that is tracked in the `spawned_tasks`
     /// [`JoinSet`](tokio::task::JoinSet).
-    fn spawn<F>(&self, f: F)
+    ///
+    /// The name identifies the task in diagnostics.
+    fn spawn<F>(&self, name: &'static str, f: F)
     where
         F: Future<Output = ()> + Send + 'static,
     {
-        self.executor.spawn(f.boxed());
+        self.executor.spawn_named(name, f.boxed());
     }
 
     /// Invoked on a received status update.
@@ -273,7 +296,7 @@ impl<N: NetworkPrimitives> SessionManager<N> {
         let status = self.status;
         let fork_filter = self.fork_filter.clone();
         let extra_handlers = self.extra_protocols.on_incoming(remote_addr);
-        self.spawn(pending_session_with_timeout(
+        let session = pending_session_with_timeout(
             self.pending_session_timeout,
             session_id,
             remote_addr,
@@ -292,7 +315,8 @@ impl<N: NetworkPrimitives> SessionManager<N> {
                 fork_filter,
                 extra_handlers,
             ),
-        ));
+        );
+        self.spawn("pending session", session);
 
         let handle = PendingSessionHandle {
             disconnect_tx: Some(disconnect_tx),
@@ -315,7 +339,7 @@ impl<N: NetworkPrimitives> SessionManager<N> {
             let fork_filter = self.fork_filter.clone();
             let status = self.status;
             let extra_handlers = self.extra_protocols.on_outgoing(remote_addr, remote_peer_id);
-            self.spawn(pending_session_with_timeout(
+            let session = pending_session_with_timeout(
                 self.pending_session_timeout,
                 session_id,
                 remote_addr,
@@ -334,7 +358,8 @@ impl<N: NetworkPrimitives> SessionManager<N> {
                     fork_filter,
                     extra_handlers,
                 ),
-            ));
+            );
+            self.spawn("pending session", session);
 
             let handle = PendingSessionHandle {
                 disconnect_tx: Some(disconnect_tx),
@@ -390,6 +415,26 @@ impl<N: NetworkPrimitives> SessionManager<N> {
         }
     }
 
+    /// Injects a fault into the peer's session.
+    ///
+    /// Returns `false` if there's no active session with the peer.
+    #[cfg(any(test, feature = "test-utils"))]
+    pub fn inject_fault(&self, peer_id: &PeerId, fault: SessionFault) -> bool {
+        self.active_sessions.get(peer_id).is_some_and(|session| session.inject_fault(fault))
+    }
+
+    /// Simulates the given link conditions for the messages received from all peers, or removes
+    /// the simulation if `None`.
+    ///
+    /// This applies to the active sessions and to all sessions established afterwards.
+    #[cfg(any(test, feature = "test-utils"))]
+    pub fn shape_links(&mut self, conditions: Option<LinkConditions>) {
+        self.link_conditions = conditions;
+        for session in self.active_sessions.values() {
+            session.shape_link(conditions);
+        }
+    }
+
     /// Removes the [`PendingSessionHandle`] if it exists.
     fn remove_pending_session(&mut self, id: &SessionId) -> Option<PendingSessionHandle> {
         let session = self.pending_sessions.remove(id)?;
@@ -420,7 +465,7 @@ impl<N: NetworkPrimitives> SessionManager<N> {
         let guard = self.disconnections_counter.clone();
         let secret_key = self.secret_key;
 
-        self.spawn(async move {
+        self.spawn("session disconnect", async move {
             trace!(
                 target: "net::session",
                 "gracefully disconnecting incoming connection"
@@ -512,7 +557,7 @@ impl<N: NetworkPrimitives> SessionManager<N> {
                         "already connected"
                     );
 
-                    self.spawn(async move {
+                    self.spawn("session disconnect", async move {
                         // send a disconnect message
                         let _ =
                             conn.into_inner().disconnect(DisconnectReason::AlreadyConnected).await;
@@ -541,8 +586,8 @@ impl<N: NetworkPrimitives> SessionManager<N> {
                 // Configure the interval at which the range information is updated, starting with
                 // ETH69
                 let range_update_interval = (conn.version() >= EthVersion::Eth69).then(|| {
-                    let mut interval = tokio::time::interval(RANGE_UPDATE_INTERVAL);
-                    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+                    let mut interval = Interval::new(self.clock.clone(), RANGE_UPDATE_INTERVAL);
+                    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
                     interval
                 });
 
@@ -571,9 +616,13 @@ impl<N: NetworkPrimitives> SessionManager<N> {
                     range_info: None,
                     local_range_info: self.local_range_info.clone(),
                     range_update_interval,
+                    #[cfg(any(test, feature = "test-utils"))]
+                    faults: Default::default(),
+                    #[cfg(any(test, feature = "test-utils"))]
+                    link: shaping::LinkShaper::new(self.link_conditions),
                 };
 
-                self.spawn(session);
+                self.spawn("active session", session);
 
                 let client_version = client_id.into();
                 let handle = ActiveSessionHandle {
@@ -0,0 +1,235 @@
+//! Simulation of slow and lossy links for active sessions.
+//!
+//! This is only available for tests and benchmarks. Messages read from the connection are held
+//! back as if they were transmitted over a link with the configured latency, bandwidth and packet
+//! loss, so that sync can be measured in-process under controlled network conditions.
+
+use alloy_rlp::Encodable;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use reth_eth_wire::{EthMessage, NetworkPrimitives};
+use std::{
+    collections::VecDeque,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::time::{Instant, Sleep};
+
+/// Size of a simulated TCP segment, packets are lost per segment.
+const SEGMENT_SIZE: usize = 1460;
+
+/// Minimum time until a lost segment is retransmitted, see RFC 6298.
+const MIN_RETRANSMISSION_TIMEOUT: Duration = Duration::from_millis(200);
+
+/// Conditions of a simulated link from a remote peer to the local node.
+///
+/// Only the messages received by the local node are shaped, to shape both directions of a session
+/// the conditions must be applied on both nodes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LinkConditions {
+    /// One-way delay of every message.
+    pub latency: Duration,
+    /// Bandwidth of the link in bytes per second, unlimited if `None`.
+    pub bandwidth: Option<u64>,
+    /// Probability that a segment is lost and has to be retransmitted, between `0` and `1`.
+    pub loss: f64,
+    /// Seed for the simulated packet loss, so that runs are reproducible.
+    pub seed: u64,
+}
+
+impl Default for LinkConditions {
+    fn default() -> Self {
+        Self { latency: Duration::ZERO, bandwidth: None, loss: 0.0, seed: 0 }
+    }
+}
+
+impl LinkConditions {
+    /// Sets the one-way delay of every message.
+    pub const fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency = latency;
+        self
+    }
+
+    /// Sets the bandwidth of the link in bytes per second.
+    pub const fn with_bandwidth(mut self, bytes_per_sec: u64) -> Self {
+        self.bandwidth = Some(bytes_per_sec);
+        self
+    }
+
+    /// Sets the probability that a segment is lost, clamped to `0..=1`.
+    pub fn with_loss(mut self, loss: f64) -> Self {
+        self.loss = loss.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Sets the seed for the simulated packet loss.
+    pub const fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Returns the time until a lost segment is retransmitted.
+    fn retransmission_timeout(&self) -> Duration {
+        (self.latency * 2).max(MIN_RETRANSMISSION_TIMEOUT)
+    }
+
+    /// Returns the time it takes to put a message of the given size on the link.
+    fn transmission_time(&self, size: usize) -> Duration {
+        self.bandwidth
+            .map(|bandwidth| Duration::from_secs_f64(size as f64 / bandwidth.max(1) as f64))
+            .unwrap_or_default()
+    }
+}
+
+/// Holds back messages read from the connection according to the [`LinkConditions`].
+///
+/// Messages are released in the order they were received, like on a TCP connection.
+#[derive(Debug)]
+pub(crate) struct LinkShaper<N: NetworkPrimitives> {
+    /// Conditions of the link, messages are passed through if `None`.
+    conditions: Option<LinkConditions>,
+    /// Randomness of the packet loss.
+    rng: StdRng,
+    /// Time at which the link finished transmitting the previous message.
+    link_free_at: Instant,
+    /// Release time of the previous message.
+    last_release: Instant,
+    /// Messages that are held back, in order of their release.
+    delayed: VecDeque<(Instant, EthMessage<N>)>,
+    /// Wakes the session when the next message is due.
+    timer: Option<Pin<Box<Sleep>>>,
+}
+
+impl<N: NetworkPrimitives> LinkShaper<N> {
+    /// Creates a shaper for the given link conditions.
+    pub(crate) fn new(conditions: Option<LinkConditions>) -> Self {
+        let now = Instant::now();
+        Self {
+            conditions,
+            rng: StdRng::seed_from_u64(conditions.map(|c| c.seed).unwrap_or_default()),
+            link_free_at: now,
+            last_release: now,
+            delayed: Default::default(),
+            timer: None,
+        }
+    }
+
+    /// Changes the link conditions of subsequent messages.
+    ///
+    /// Messages that are already held back keep their release time.
+    pub(crate) fn set_conditions(&mut self, conditions: Option<LinkConditions>) {
+        if let Some(conditions) = conditions {
+            self.rng = StdRng::seed_from_u64(conditions.seed);
+        }
+        self.conditions = conditions;
+    }
+
+    /// Returns the next held back message that is due.
+    pub(crate) fn poll_released(&mut self, cx: &mut Context<'_>) -> Poll<EthMessage<N>> {
+        let Some((release, _)) = self.delayed.front() else { return Poll::Pending };
+        let release = *release;
+        let timer = self.timer.get_or_insert_with(|| Box::pin(tokio::time::sleep_until(release)));
+        if timer.deadline() != release {
+            timer.as_mut().reset(release);
+        }
+        if timer.as_mut().poll(cx).is_pending() {
+            return Poll::Pending
+        }
+
+        self.timer = None;
+        let (_, msg) = self.delayed.pop_front().expect("exists");
+        Poll::Ready(msg)
+    }
+
+    /// Applies the link conditions to a message read from the connection.
+    ///
+    /// Returns `None` if the message was held back.
+    pub(crate) fn on_message(&mut self, msg: EthMessage<N>) -> Option<EthMessage<N>> {
+        let Some(conditions) = self.conditions else {
+            // messages must not overtake messages that are still held back
+            if self.delayed.is_empty() {
+                return Some(msg)
+            }
+            let release = self.last_release;
+            self.delayed.push_back((release, msg));
+            return None
+        };
+
+        let release = self.release_time(&conditions, msg.length());
+        self.delayed.push_back((release, msg));
+        None
+    }
+
+    /// Returns the time at which a message of the given size that is received now is released.
+    fn release_time(&mut self, conditions: &LinkConditions, size: usize) -> Instant {
+        let sent = Instant::now().max(self.link_free_at) + conditions.transmission_time(size);
+        self.link_free_at = sent;
+
+        let mut release = sent + conditions.latency;
+        if conditions.loss > 0.0 {
+            let segments = size.div_ceil(SEGMENT_SIZE).max(1);
+            let lost = (0..segments).filter(|_| self.rng.random_bool(conditions.loss)).count();
+            release += conditions.retransmission_timeout() * lost as u32;
+        }
+
+        let release = release.max(self.last_release);
+        self.last_release = release;
+        release
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_eth_wire::{BlockBodies, EthNetworkPrimitives, RequestPair};
+
+    fn response(request_id: u64) -> EthMessage<EthNetworkPrimitives> {
+        EthMessage::BlockBodies(RequestPair { request_id, message: BlockBodies(vec![]) })
+    }
+
+    #[test]
+    fn passes_through_without_conditions() {
+        let mut shaper = LinkShaper::<EthNetworkPrimitives>::new(None);
+        assert_eq!(shaper.on_message(response(1)), Some(response(1)));
+    }
+
+    #[tokio::test]
+    async fn releases_messages_in_order() {
+        let conditions = LinkConditions::default().with_latency(Duration::from_millis(20));
+        let mut shaper = LinkShaper::<EthNetworkPrimitives>::new(Some(conditions));
+        let start = Instant::now();
+        assert!(shaper.on_message(response(1)).is_none());
+
+        // lifting the conditions doesn't let messages overtake held back ones
+        shaper.set_conditions(None);
+        assert!(shaper.on_message(response(2)).is_none());
+
+        let first = std::future::poll_fn(|cx| shaper.poll_released(cx)).await;
+        assert_eq!(first, response(1));
+        assert!(start.elapsed() >= Duration::from_millis(20));
+        let second = std::future::poll_fn(|cx| shaper.poll_released(cx)).await;
+        assert_eq!(second, response(2));
+
+        assert_eq!(shaper.on_message(response(3)), Some(response(3)));
+    }
+
+    #[test]
+    fn delays_by_bandwidth_and_loss() {
+        let mut shaper = LinkShaper::<EthNetworkPrimitives>::new(None);
+        let now = Instant::now();
+
+        // one second to transmit the message, and the next one queues behind it
+        let conditions = LinkConditions::default().with_bandwidth(SEGMENT_SIZE as u64);
+        assert!(shaper.release_time(&conditions, SEGMENT_SIZE) >= now + Duration::from_secs(1));
+        assert!(shaper.release_time(&conditions, SEGMENT_SIZE) >= now + Duration::from_secs(2));
+
+        // every segment is lost once
+        let mut shaper = LinkShaper::<EthNetworkPrimitives>::new(None);
+        let conditions = LinkConditions::default().with_loss(1.0);
+        assert!(
+            shaper.release_time(&conditions, 2 * SEGMENT_SIZE) >=
+                now + MIN_RETRANSMISSION_TIMEOUT * 2
+        );
+    }
+}
@@ -19,7 +19,7 @@ use reth_eth_wire::{
 use reth_ethereum_forks::ForkId;
 use reth_network_api::{DiscoveredEvent, DiscoveryEvent, PeerRequest, PeerRequestSender};
 use reth_network_peers::PeerId;
-use reth_network_types::{PeerAddr, PeerKind};
+use reth_network_types::{PeerAddr, PeerKind, PeerQuality};
 use reth_primitives_traits::Block;
 use std::{
     collections::{HashMap, VecDeque},
@@ -151,6 +151,7 @@ impl<N: NetworkPrimitives> NetworkState<N> {
         request_tx: PeerRequestSender<PeerRequest<N>>,
         timeout: Arc<AtomicU64>,
         range_info: Option<BlockRangeInfo>,
+        quality: PeerQuality,
     ) {
         debug_assert!(!self.active_peers.contains_key(&peer), "Already connected; not possible");
 
@@ -163,6 +164,7 @@ impl<N: NetworkPrimitives> NetworkState<N> {
             block_number,
             timeout,
             range_info,
+            quality,
         );
 
         self.active_peers.insert(
@@ -622,6 +624,7 @@ mod tests {
             peer_tx,
             Arc::new(AtomicU64::new(1)),
             None,
+            Default::default(),
         );
 
         assert!(state.active_peers.contains_key(&peer_id));
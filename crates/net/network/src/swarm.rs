@@ -13,6 +13,7 @@ use reth_eth_wire::{
 };
 use reth_network_api::{PeerRequest, PeerRequestSender};
 use reth_network_peers::PeerId;
+use reth_network_types::PeerQuality;
 use std::{
     io,
     net::SocketAddr,
@@ -124,6 +125,7 @@ impl<N: NetworkPrimitives> Swarm<N> {
                 timeout,
                 range_info,
             } => {
+                let quality = PeerQuality::default();
                 self.state.on_session_activated(
                     peer_id,
                     capabilities.clone(),
@@ -131,6 +133,7 @@ impl<N: NetworkPrimitives> Swarm<N> {
                     messages.clone(),
                     timeout,
                     range_info,
+                    quality.clone(),
                 );
                 Some(SwarmEvent::SessionEstablished {
                     peer_id,
@@ -141,6 +144,7 @@ impl<N: NetworkPrimitives> Swarm<N> {
                     messages,
                     status,
                     direction,
+                    quality,
                 })
             }
             SessionEvent::AlreadyConnected { peer_id, remote_addr, direction } => {
@@ -387,6 +391,8 @@ pub(crate) enum SwarmEvent<N: NetworkPrimitives = EthNetworkPrimitives> {
         messages: PeerRequestSender<PeerRequest<N>>,
         status: Arc<UnifiedStatus>,
         direction: Direction,
+        /// The quality of the peer's responses, shared with the request handlers.
+        quality: PeerQuality,
     },
     SessionClosed {
         peer_id: PeerId,
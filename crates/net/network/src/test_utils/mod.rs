@@ -8,5 +8,8 @@ pub use init::{
     enr_to_peer_id, unused_port, unused_tcp_addr, unused_tcp_and_udp_port, unused_tcp_udp,
     unused_udp_addr, unused_udp_port,
 };
+pub use reth_eth_wire::test_utils::{
+    MockAction, MockEthPeer, MockEthPeerBuilder, MockEthStream, MockTransport,
+};
 pub use testnet::{NetworkEventStream, Peer, PeerConfig, PeerHandle, Testnet, TestnetHandle};
 pub use transactions::{buffer_hash_to_tx_fetcher, new_mock_session, new_tx_manager};
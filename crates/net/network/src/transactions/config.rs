@@ -1,4 +1,9 @@
-use std::{fmt::Debug, marker::PhantomData, str::FromStr};
+use std::{
+    collections::HashSet,
+    fmt::{self, Debug},
+    marker::PhantomData,
+    str::FromStr,
+};
 
 use super::{
     PeerMetadata, DEFAULT_MAX_COUNT_TRANSACTIONS_SEEN_BY_PEER,
@@ -13,6 +18,7 @@ use alloy_primitives::B256;
 use derive_more::{Constructor, Display};
 use reth_eth_wire::NetworkPrimitives;
 use reth_ethereum_primitives::TxType;
+use reth_network_peers::PeerId;
 
 /// Configuration for managing transactions within the network.
 #[derive(Debug, Clone)]
@@ -25,6 +31,16 @@ pub struct TransactionsManagerConfig {
     /// How new pending transactions are propagated.
     #[cfg_attr(feature = "serde", serde(default))]
     pub propagation_mode: TransactionPropagationMode,
+    /// Max number of blob transaction hashes announced to a peer in a single
+    /// [`NewPooledTransactionHashes`](reth_eth_wire::NewPooledTransactionHashes) message.
+    ///
+    /// Blob transactions over the limit are not announced to the peer. If `None`, blob transaction
+    /// hashes are only limited by the soft limit of the message.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub max_blob_hashes_per_announcement: Option<usize>,
+    /// Peers that new pending transactions are never propagated to.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub propagation_disabled_peers: HashSet<PeerId>,
 }
 
 impl Default for TransactionsManagerConfig {
@@ -33,12 +49,23 @@ impl Default for TransactionsManagerConfig {
             transaction_fetcher_config: TransactionFetcherConfig::default(),
             max_transactions_seen_by_peer_history: DEFAULT_MAX_COUNT_TRANSACTIONS_SEEN_BY_PEER,
             propagation_mode: TransactionPropagationMode::default(),
+            max_blob_hashes_per_announcement: None,
+            propagation_disabled_peers: HashSet::default(),
         }
     }
 }
 
+impl TransactionsManagerConfig {
+    /// Returns `true` if new pending transactions may be propagated to the given peer.
+    pub fn is_propagation_enabled(&self, peer_id: &PeerId) -> bool {
+        !self.propagation_disabled_peers.contains(peer_id)
+    }
+}
+
 /// Determines how new pending transactions are propagated to other peers in full.
-#[derive(Debug, Clone, Default)]
+///
+/// All remaining peers only receive announcements of the transaction hashes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TransactionPropagationMode {
     /// Send full transactions to sqrt of current peers.
@@ -48,6 +75,10 @@ pub enum TransactionPropagationMode {
     All,
     /// Send full transactions to a maximum number of peers
     Max(usize),
+    /// Send full transactions to a percentage of current peers, rounded up.
+    Percent(u8),
+    /// Never send transactions in full, only announce their hashes.
+    HashesOnly,
 }
 
 impl TransactionPropagationMode {
@@ -57,6 +88,43 @@ impl TransactionPropagationMode {
             Self::Sqrt => (peer_count as f64).sqrt().round() as usize,
             Self::All => peer_count,
             Self::Max(max) => peer_count.min(*max),
+            Self::Percent(percent) => (peer_count * (*percent).min(100) as usize).div_ceil(100),
+            Self::HashesOnly => 0,
+        }
+    }
+}
+
+impl fmt::Display for TransactionPropagationMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Sqrt => f.write_str("sqrt"),
+            Self::All => f.write_str("all"),
+            Self::Max(max) => write!(f, "max:{max}"),
+            Self::Percent(percent) => write!(f, "percent:{percent}"),
+            Self::HashesOnly => f.write_str("hashes-only"),
+        }
+    }
+}
+
+impl FromStr for TransactionPropagationMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid transaction propagation mode: {s}");
+        match s {
+            "sqrt" => Ok(Self::Sqrt),
+            "all" => Ok(Self::All),
+            "hashes-only" => Ok(Self::HashesOnly),
+            _ => match s.split_once(':') {
+                Some(("max", max)) => max.parse().map(Self::Max).map_err(|_| invalid()),
+                Some(("percent", percent)) => percent
+                    .parse::<u8>()
+                    .ok()
+                    .filter(|percent| *percent <= 100)
+                    .map(Self::Percent)
+                    .ok_or_else(invalid),
+                _ => Err(invalid()),
+            },
         }
     }
 }
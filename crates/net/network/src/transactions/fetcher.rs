@@ -36,6 +36,7 @@ use crate::{
     metrics::TransactionFetcherMetrics,
 };
 use alloy_consensus::transaction::PooledTransaction;
+use alloy_eips::eip2718::Encodable2718;
 use alloy_primitives::TxHash;
 use derive_more::{Constructor, Deref};
 use futures::{stream::FuturesUnordered, Future, FutureExt, Stream, StreamExt};
@@ -48,13 +49,14 @@ use reth_eth_wire_types::{EthNetworkPrimitives, NetworkPrimitives};
 use reth_network_api::PeerRequest;
 use reth_network_p2p::error::{RequestError, RequestResult};
 use reth_network_peers::PeerId;
+use reth_network_types::PeerQuality;
 use reth_primitives_traits::SignedTransaction;
 use schnellru::ByLength;
 use std::{
     collections::HashMap,
     pin::Pin,
     task::{ready, Context, Poll},
-    time::Duration,
+    time::{Duration, Instant},
 };
 use tokio::sync::{mpsc::error::TrySendError, oneshot, oneshot::error::RecvError};
 use tracing::trace;
@@ -84,14 +86,23 @@ pub struct TransactionFetcher<N: NetworkPrimitives = EthNetworkPrimitives> {
     pub hashes_fetch_inflight_and_pending_fetch: LruMap<TxHash, TxFetchMetadata, ByLength>,
     /// Info on capacity of the transaction fetcher.
     pub info: TransactionFetcherInfo,
+    /// The quality of the responses of the connected peers, used to pick the fallback peer to
+    /// request from.
+    peer_quality: HashMap<PeerId, PeerQuality>,
     #[doc(hidden)]
     metrics: TransactionFetcherMetrics,
 }
 
 impl<N: NetworkPrimitives> TransactionFetcher<N> {
+    /// Tracks the quality of the responses of a newly connected peer.
+    pub(crate) fn add_peer(&mut self, peer_id: PeerId, quality: PeerQuality) {
+        self.peer_quality.insert(peer_id, quality);
+    }
+
     /// Removes the peer from the active set.
     pub(crate) fn remove_peer(&mut self, peer_id: &PeerId) {
         self.active_peers.remove(peer_id);
+        self.peer_quality.remove(peer_id);
     }
 
     /// Updates metrics.
@@ -183,18 +194,22 @@ impl<N: NetworkPrimitives> TransactionFetcher<N> {
         false
     }
 
-    /// Returns any idle peer for the given hash.
+    /// Returns the idle peer for the given hash with the best [`PeerQuality::score`].
+    ///
+    /// Peers with equal scores are picked in lru order.
     pub fn get_idle_peer_for(&self, hash: TxHash) -> Option<&PeerId> {
         let TxFetchMetadata { fallback_peers, .. } =
             self.hashes_fetch_inflight_and_pending_fetch.peek(&hash)?;
 
-        for peer_id in fallback_peers.iter() {
-            if self.is_idle(peer_id) {
-                return Some(peer_id)
+        let mut best_peer: Option<(&PeerId, f64)> = None;
+        for peer_id in fallback_peers.iter().filter(|peer_id| self.is_idle(peer_id)) {
+            let score = self.peer_quality.get(peer_id).cloned().unwrap_or_default().score();
+            if best_peer.is_none_or(|(_, best_score)| score > best_score) {
+                best_peer = Some((peer_id, score));
             }
         }
 
-        None
+        best_peer.map(|(peer_id, _)| peer_id)
     }
 
     /// Returns any idle peer for any hash pending fetch. If one is found, the corresponding
@@ -680,8 +695,8 @@ impl<N: NetworkPrimitives> TransactionFetcher<N> {
     /// Loops through hashes pending fetch and does:
     ///
     /// 1. Check if a hash pending fetch is seen by peer.
-    /// 2. Optimistically include the hash in the request.
-    /// 3. Accumulate expected total response size.
+    /// 2. Check if the transaction fits into the expected response, if not skip it.
+    /// 3. Include the hash in the request and accumulate expected total response size.
     /// 4. Check if acc size and hashes count is at limit, if so stop looping.
     /// 5. Remove hashes to request from cache of hashes pending fetch.
     pub fn fill_request_from_hashes_pending_fetch(
@@ -713,16 +728,22 @@ impl<N: NetworkPrimitives> TransactionFetcher<N> {
                 continue
             };
 
-            // 2. Optimistically include the hash in the request.
-            hashes_to_request.insert(*hash);
-
-            // 3. Accumulate expected total response size.
+            // 2. Check if the transaction fits into the expected response. Large transactions,
+            // e.g. blob transactions with their sidecars, are left pending for another request.
             let size = self
                 .hashes_fetch_inflight_and_pending_fetch
                 .get(hash)
                 .and_then(|entry| entry.tx_encoded_len())
                 .unwrap_or(AVERAGE_BYTE_SIZE_TX_ENCODED);
 
+            if acc_size_response + size >
+                DEFAULT_SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESPONSE_ON_FETCH_PENDING_HASHES
+            {
+                continue
+            }
+
+            // 3. Include the hash in the request and accumulate expected total response size.
+            hashes_to_request.insert(*hash);
             acc_size_response += size;
 
             // 4. Check if acc size or hashes count is at limit, if so stop looping.
@@ -849,9 +870,10 @@ impl<N: NetworkPrimitives> TransactionFetcher<N> {
     ) -> FetchEvent<N::PooledTransaction> {
         // update peer activity, requests for buffered hashes can only be made to idle
         // fallback peers
-        let GetPooledTxResponse { peer_id, mut requested_hashes, result } = response;
+        let GetPooledTxResponse { peer_id, mut requested_hashes, result, started } = response;
 
         self.decrement_inflight_request_count_for(&peer_id);
+        let quality = self.peer_quality.get(&peer_id);
 
         match result {
             Ok(Ok(transactions)) => {
@@ -866,6 +888,9 @@ impl<N: NetworkPrimitives> TransactionFetcher<N> {
                         "received empty `PooledTransactions` response from peer, peer failed to serve hashes it announced"
                     );
 
+                    if let Some(quality) = quality {
+                        quality.on_response(started.elapsed(), 0);
+                    }
                     return FetchEvent::EmptyResponse { peer_id }
                 }
 
@@ -897,6 +922,9 @@ impl<N: NetworkPrimitives> TransactionFetcher<N> {
 
                 // peer has only sent hashes that we didn't request
                 if verified_payload.is_empty() {
+                    if let Some(quality) = quality {
+                        quality.on_invalid_response();
+                    }
                     return FetchEvent::FetchError { peer_id, error: RequestError::BadResponse }
                 }
 
@@ -954,11 +982,24 @@ impl<N: NetworkPrimitives> TransactionFetcher<N> {
                 //
                 self.try_buffer_hashes_for_retry(requested_hashes, &peer_id);
 
+                if let Some(quality) = self.peer_quality.get(&peer_id) {
+                    let useful_bytes =
+                        valid_payload.values().map(|tx| tx.encode_2718_len() as u64).sum();
+                    quality.on_response(started.elapsed(), useful_bytes);
+                }
+
                 let transactions = valid_payload.into_data().into_values().collect();
 
                 FetchEvent::TransactionsFetched { peer_id, transactions, report_peer }
             }
             Ok(Err(req_err)) => {
+                if let Some(quality) = quality {
+                    match req_err {
+                        RequestError::Timeout => quality.on_timeout(),
+                        RequestError::BadResponse => quality.on_invalid_response(),
+                        _ => {}
+                    }
+                }
                 self.try_buffer_hashes_for_retry(requested_hashes, &peer_id);
                 FetchEvent::FetchError { peer_id, error: req_err }
             }
@@ -1000,6 +1041,7 @@ impl<T: NetworkPrimitives> Default for TransactionFetcher<T> {
                 DEFAULT_MAX_CAPACITY_CACHE_INFLIGHT_AND_PENDING_FETCH,
             ),
             info: TransactionFetcherInfo::default(),
+            peer_quality: Default::default(),
             metrics: Default::default(),
         }
     }
@@ -1068,6 +1110,8 @@ pub struct GetPooledTxRequest<T = PooledTransaction> {
     /// Transaction hashes that were requested, for cleanup purposes
     requested_hashes: RequestTxHashes,
     response: oneshot::Receiver<RequestResult<PooledTransactions<T>>>,
+    /// When the request was sent.
+    started: Instant,
 }
 
 /// Upon reception of a response, a [`GetPooledTxRequest`] is deconstructed to form a
@@ -1079,6 +1123,8 @@ pub struct GetPooledTxResponse<T = PooledTransaction> {
     /// subset of requested hashes.
     requested_hashes: RequestTxHashes,
     result: Result<RequestResult<PooledTransactions<T>>, RecvError>,
+    /// When the request was sent.
+    started: Instant,
 }
 
 /// Stores the response receiver made by sending a [`GetPooledTransactions`] request to a peer's
@@ -1093,12 +1139,13 @@ pub struct GetPooledTxRequestFut<T = PooledTransaction> {
 
 impl<T> GetPooledTxRequestFut<T> {
     #[inline]
-    const fn new(
+    fn new(
         peer_id: PeerId,
         requested_hashes: RequestTxHashes,
         response: oneshot::Receiver<RequestResult<PooledTransactions<T>>>,
     ) -> Self {
-        Self { inner: Some(GetPooledTxRequest { peer_id, requested_hashes, response }) }
+        let started = Instant::now();
+        Self { inner: Some(GetPooledTxRequest { peer_id, requested_hashes, response, started }) }
     }
 }
 
@@ -1112,6 +1159,7 @@ impl<T> Future for GetPooledTxRequestFut<T> {
                 peer_id: req.peer_id,
                 requested_hashes: req.requested_hashes,
                 result,
+                started: req.started,
             }),
             Poll::Pending => {
                 self.project().inner.set(Some(req));
@@ -1455,6 +1503,67 @@ mod test {
         )
     }
 
+    #[test]
+    fn test_fill_request_skips_oversized_pending_hashes() {
+        let tx_fetcher = &mut TransactionFetcher::default();
+        let peer = PeerId::new([1; 64]);
+
+        let small = B256::from_slice(&[1; 32]);
+        let large = B256::from_slice(&[2; 32]);
+        let other_small = B256::from_slice(&[3; 32]);
+        let requested = B256::from_slice(&[4; 32]);
+
+        // a blob transaction with sidecar doesn't fit into the expected response
+        let large_size =
+            DEFAULT_SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESPONSE_ON_FETCH_PENDING_HASHES;
+        buffer_hash_to_tx_fetcher(tx_fetcher, small, peer, 0, Some(120));
+        buffer_hash_to_tx_fetcher(tx_fetcher, large, peer, 0, Some(large_size));
+        buffer_hash_to_tx_fetcher(tx_fetcher, other_small, peer, 0, Some(158));
+        buffer_hash_to_tx_fetcher(tx_fetcher, requested, peer, 0, Some(116));
+
+        let mut seen_hashes = LruCache::new(10);
+        for hash in [small, large, other_small, requested] {
+            seen_hashes.insert(hash);
+        }
+
+        let mut hashes_to_request = RequestTxHashes::default();
+        hashes_to_request.insert(requested);
+        tx_fetcher.fill_request_from_hashes_pending_fetch(
+            &mut hashes_to_request,
+            &seen_hashes,
+            None,
+        );
+
+        assert_eq!(
+            hashes_to_request.into_iter().collect::<HashSet<_>>(),
+            HashSet::from_iter([small, other_small, requested])
+        );
+        assert!(tx_fetcher.hashes_pending_fetch.contains(&large));
+    }
+
+    #[test]
+    fn idle_peer_with_best_quality() {
+        let tx_fetcher = &mut TransactionFetcher::<EthNetworkPrimitives>::default();
+        let hash = B256::from_slice(&[1; 32]);
+        let peer_1 = PeerId::new([1; 64]);
+        let peer_2 = PeerId::new([2; 64]);
+        let quality_1 = PeerQuality::default();
+        tx_fetcher.add_peer(peer_1, quality_1.clone());
+        tx_fetcher.add_peer(peer_2, PeerQuality::default());
+        buffer_hash_to_tx_fetcher(tx_fetcher, hash, peer_2, 0, None);
+        buffer_hash_to_tx_fetcher(tx_fetcher, hash, peer_1, 0, None);
+
+        // lru fallback peer is picked if the scores are equal
+        assert_eq!(tx_fetcher.get_idle_peer_for(hash), Some(&peer_1));
+
+        quality_1.on_timeout();
+        assert_eq!(tx_fetcher.get_idle_peer_for(hash), Some(&peer_2));
+
+        // peers are no longer ranked once disconnected
+        tx_fetcher.remove_peer(&peer_2);
+        assert_eq!(tx_fetcher.get_idle_peer_for(hash), Some(&peer_1));
+    }
+
     #[test]
     fn verify_response_hashes() {
         let input = hex!(
@@ -36,6 +36,7 @@ use crate::{
     },
     NetworkHandle, TxTypesCounter,
 };
+use alloy_consensus::Typed2718;
 use alloy_primitives::{TxHash, B256};
 use constants::SOFT_LIMIT_COUNT_HASHES_IN_NEW_POOLED_TRANSACTIONS_BROADCAST_MESSAGE;
 use futures::{stream::FuturesUnordered, Future, StreamExt};
@@ -574,6 +575,31 @@ impl<Pool: TransactionPool, N: NetworkPrimitives, PBundle: TransactionPolicies>
         }
     }
 
+    /// Announces the replacements of local transactions to a peer that sent us the replaced
+    /// versions.
+    ///
+    /// Replaced transactions are no longer in the pool and would only be rejected as underpriced
+    /// on import. Instead the peer is sent the hashes of the replacements, so it can fetch the
+    /// latest version.
+    fn announce_replacements(&self, peer_id: PeerId, mut replacements: Vec<TxHash>) {
+        if replacements.is_empty() {
+            return
+        }
+
+        trace!(target: "net::tx",
+            peer_id=format!("{peer_id:#}"),
+            ?replacements,
+            "peer sent replaced transactions, announcing replacements"
+        );
+
+        self.metrics.suppressed_replaced_transactions.increment(replacements.len() as u64);
+        self.metrics.replacement_announcements.increment(replacements.len() as u64);
+
+        replacements.sort_unstable();
+        replacements.dedup();
+        let _ = self.command_tx.send(TransactionsCommand::PropagateHashesTo(replacements, peer_id));
+    }
+
     /// Request handler for an incoming `NewPooledTransactionHashes`
     fn on_new_pooled_transaction_hashes(
         &mut self,
@@ -660,12 +686,17 @@ impl<Pool: TransactionPool, N: NetworkPrimitives, PBundle: TransactionPolicies>
                 .increment(already_known_hashes_count as u64);
         }
 
+        // 4. filter out replaced versions of local transactions, the peer is sent the hash of the
+        // replacement instead
+        let replacements = take_replaced_transactions(&self.pool, &mut partially_valid_msg);
+        self.announce_replacements(peer_id, replacements);
+
         if partially_valid_msg.is_empty() {
             // nothing to request
             return
         }
 
-        // 4. filter out invalid entries (spam)
+        // 5. filter out invalid entries (spam)
         //
         // validates messages with respect to the given network, e.g. allowed tx types
         //
@@ -735,7 +766,7 @@ impl<Pool: TransactionPool, N: NetworkPrimitives, PBundle: TransactionPolicies>
             return
         }
 
-        // 5. filter out already seen unknown hashes
+        // 6. filter out already seen unknown hashes
         //
         // seen hashes are already in the tx fetcher, pending fetch.
         //
@@ -862,6 +893,18 @@ where
 
         trace!(target: "net::tx", num_hashes=?hashes.len(), "Start propagating transactions");
 
+        // don't gossip versions of local transactions that have already been replaced
+        let num_hashes = hashes.len();
+        let hashes = hashes
+            .into_iter()
+            .filter(|hash| self.pool.replacement_of(hash).is_none())
+            .collect::<Vec<_>>();
+        if hashes.len() < num_hashes {
+            self.metrics
+                .suppressed_replaced_transactions
+                .increment((num_hashes - hashes.len()) as u64);
+        }
+
         self.propagate_all(hashes);
     }
 
@@ -1021,33 +1064,45 @@ where
 
         // send full transactions to a set of the connected peers based on the configured mode
         let max_num_full = self.config.propagation_mode.full_peer_count(self.peers.len());
+        let max_blob_hashes = self.config.max_blob_hashes_per_announcement.unwrap_or(usize::MAX);
+        let mut num_full = 0;
 
         // Note: Assuming ~random~ order due to random state of the peers map hasher
-        for (peer_idx, (peer_id, peer)) in self.peers.iter_mut().enumerate() {
-            if !self.policies.propagation_policy().can_propagate(peer) {
+        for (peer_id, peer) in &mut self.peers {
+            if !self.config.is_propagation_enabled(peer_id) ||
+                !self.policies.propagation_policy().can_propagate(peer)
+            {
                 // skip peers we should not propagate to
                 continue
             }
             // determine whether to send full tx objects or hashes.
-            let mut builder = if peer_idx > max_num_full {
-                PropagateTransactionsBuilder::pooled(peer.version)
-            } else {
+            let mut builder = if num_full < max_num_full {
+                num_full += 1;
                 PropagateTransactionsBuilder::full(peer.version)
+            } else {
+                PropagateTransactionsBuilder::pooled(peer.version)
             };
 
-            if propagation_mode.is_forced() {
-                builder.extend(to_propagate.iter());
-            } else {
-                // Iterate through the transactions to propagate and fill the hashes and full
-                // transaction lists, before deciding whether or not to send full transactions to
-                // the peer.
-                for tx in &to_propagate {
-                    // Only proceed if the transaction is not in the peer's list of seen
-                    // transactions
-                    if !peer.seen_transactions.contains(tx.tx_hash()) {
-                        builder.push(tx);
+            // Iterate through the transactions to propagate and fill the hashes and full
+            // transaction lists, before deciding whether or not to send full transactions to
+            // the peer.
+            let mut num_blob_hashes = 0;
+            for tx in &to_propagate {
+                // Only proceed if the transaction is not in the peer's list of seen
+                // transactions, unless propagation is forced
+                if !propagation_mode.is_forced() && peer.seen_transactions.contains(tx.tx_hash()) {
+                    continue
+                }
+                // Blob transactions are only ever announced as hashes, the ones over the limit are
+                // not announced to this peer at all, it can still learn about them from other
+                // peers.
+                if tx.transaction.is_eip4844() {
+                    if num_blob_hashes >= max_blob_hashes {
+                        continue
                     }
+                    num_blob_hashes += 1;
                 }
+                builder.push(tx);
             }
 
             if builder.is_empty() {
@@ -1215,6 +1270,7 @@ where
         };
 
         self.policies.propagation_policy_mut().on_session_established(peer);
+        self.transaction_fetcher.add_peer(peer_id, info.quality);
 
         // Send a `NewPooledTransactionHashes` to the peer with up to
         // `SOFT_LIMIT_COUNT_HASHES_IN_NEW_POOLED_TRANSACTIONS_BROADCAST_MESSAGE`
@@ -1223,6 +1279,10 @@ where
             trace!(target: "net::tx", ?peer_id, "Skipping transaction broadcast: node syncing or gossip disabled");
             return
         }
+        if !self.config.is_propagation_enabled(&peer_id) {
+            trace!(target: "net::tx", ?peer_id, "Skipping transaction broadcast: propagation disabled for peer");
+            return
+        }
 
         // Get transactions to broadcast
         let pooled_txs = self.pool.pooled_transactions_max(
@@ -1234,8 +1294,16 @@ where
         }
 
         // Build and send transaction hashes message
+        let max_blob_hashes = self.config.max_blob_hashes_per_announcement.unwrap_or(usize::MAX);
+        let mut num_blob_hashes = 0;
         let mut msg_builder = PooledTransactionsHashesBuilder::new(version);
         for pooled_tx in pooled_txs {
+            if pooled_tx.transaction.is_eip4844() {
+                if num_blob_hashes >= max_blob_hashes {
+                    continue
+                }
+                num_blob_hashes += 1;
+            }
             peer.seen_transactions.insert(*pooled_tx.hash());
             msg_builder.push_pooled(pooled_tx);
         }
@@ -1355,6 +1423,9 @@ where
                 .increment(already_known_txns_count as u64);
         }
 
+        // filter out replaced versions of local transactions
+        let replacements = take_replaced_transactions(&self.pool, &mut transactions);
+
         // tracks the quality of the given transactions
         let mut has_bad_transactions = false;
 
@@ -1451,6 +1522,8 @@ where
         if num_already_seen_by_peer > 0 {
             self.report_already_seen(peer_id);
         }
+
+        self.announce_replacements(peer_id, replacements);
     }
 
     /// Processes a [`FetchEvent`].
@@ -1473,6 +1546,26 @@ where
     }
 }
 
+/// Removes the replaced versions of local transactions from the given data and returns the hashes
+/// of the transactions that replaced them.
+fn take_replaced_transactions<Pool: TransactionPool, D: HandleMempoolData>(
+    pool: &Pool,
+    data: &mut D,
+) -> Vec<TxHash> {
+    let mut replacements = Vec::new();
+    if data.is_empty() {
+        return replacements
+    }
+    data.retain_by_hash(|hash| match pool.replacement_of(hash) {
+        Some(replacement) => {
+            replacements.push(replacement);
+            false
+        }
+        None => true,
+    });
+    replacements
+}
+
 /// An endless future. Preemption ensure that future is non-blocking, nonetheless. See
 /// [`crate::NetworkManager`] for more context on the design pattern.
 ///
@@ -1736,13 +1829,6 @@ impl<T> PropagateTransactionsBuilder<T> {
 }
 
 impl<T: SignedTransaction> PropagateTransactionsBuilder<T> {
-    /// Appends all transactions
-    fn extend<'a>(&mut self, txs: impl IntoIterator<Item = &'a PropagateTransaction<T>>) {
-        for tx in txs {
-            self.push(tx);
-        }
-    }
-
     /// Appends a transaction to the list.
     fn push(&mut self, transaction: &PropagateTransaction<T>) {
         match self {
@@ -2830,6 +2916,7 @@ mod tests {
             status: Arc::new(Default::default()),
             version: EthVersion::Eth68,
             peer_kind: PeerKind::Basic,
+            quality: Default::default(),
         };
         let messages: PeerRequestSender<PeerRequest> = PeerRequestSender::new(peer_id, tx);
         tx_manager
@@ -2862,6 +2949,77 @@ mod tests {
         assert!(propagated.0.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_propagation_policy_config() {
+        reth_tracing::init_test_tracing();
+
+        let (mut tx_manager, network) = new_tx_manager().await;
+        tx_manager.config.propagation_mode = TransactionPropagationMode::HashesOnly;
+        tx_manager.config.max_blob_hashes_per_announcement = Some(1);
+        let peer_id = PeerId::random();
+        let disabled_peer_id = PeerId::random();
+        tx_manager.config.propagation_disabled_peers.insert(disabled_peer_id);
+
+        // ensure not syncing
+        network.handle().update_sync_state(SyncState::Idle);
+
+        // mock peers
+        for peer_id in [peer_id, disabled_peer_id] {
+            let (tx, _rx) = mpsc::channel::<PeerRequest>(1);
+            let session_info = SessionInfo {
+                peer_id,
+                remote_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0),
+                client_version: Arc::from(""),
+                capabilities: Arc::new(vec![].into()),
+                status: Arc::new(Default::default()),
+                version: EthVersion::Eth68,
+                peer_kind: PeerKind::Basic,
+                quality: Default::default(),
+            };
+            let messages: PeerRequestSender<PeerRequest> = PeerRequestSender::new(peer_id, tx);
+            tx_manager
+                .on_network_event(NetworkEvent::ActivePeerSession { info: session_info, messages });
+        }
+
+        let mut factory = MockTransactionFactory::default();
+        let eip1559_tx = Arc::new(factory.create_eip1559());
+        let propagate = vec![
+            PropagateTransaction::pool_tx(eip1559_tx.clone()),
+            PropagateTransaction::pool_tx(Arc::new(factory.create_eip4844())),
+            PropagateTransaction::pool_tx(Arc::new(factory.create_eip4844())),
+        ];
+
+        let propagated = tx_manager.propagate_transactions(propagate, PropagationMode::Basic);
+
+        // only one of the blob transactions is announced, and nothing is sent in full
+        assert_eq!(propagated.0.len(), 2);
+        let prop_txs = propagated.0.get(eip1559_tx.transaction.hash()).unwrap();
+        assert_eq!(prop_txs, &vec![PropagateKind::Hash(peer_id)]);
+        assert!(propagated.0.values().flatten().all(|kind| kind.is_hash()));
+
+        // nothing is propagated to the disabled peer
+        let peer = tx_manager.peers.get(&disabled_peer_id).unwrap();
+        assert!(!peer.seen_transactions.contains(eip1559_tx.transaction.hash()));
+    }
+
+    #[test]
+    fn test_parse_propagation_mode() {
+        for mode in [
+            TransactionPropagationMode::Sqrt,
+            TransactionPropagationMode::All,
+            TransactionPropagationMode::Max(3),
+            TransactionPropagationMode::Percent(25),
+            TransactionPropagationMode::HashesOnly,
+        ] {
+            assert_eq!(mode.to_string().parse::<TransactionPropagationMode>().unwrap(), mode);
+        }
+        assert!("percent:150".parse::<TransactionPropagationMode>().is_err());
+        assert!("max".parse::<TransactionPropagationMode>().is_err());
+
+        assert_eq!(TransactionPropagationMode::Percent(25).full_peer_count(10), 3);
+        assert_eq!(TransactionPropagationMode::HashesOnly.full_peer_count(10), 0);
+    }
+
     #[tokio::test]
     async fn test_relaxed_filter_ignores_unknown_tx_types() {
         reth_tracing::init_test_tracing();
@@ -3,10 +3,14 @@
 
 use alloy_consensus::Header;
 use rand::Rng;
-use reth_eth_wire::{EthVersion, HeadersDirection};
+use reth_chainspec::EthChainSpec;
+use reth_eth_wire::{
+    BlockHeaders, EthMessage, EthVersion, HeadersDirection, RequestPair, UnifiedStatus,
+};
+use reth_ethereum_forks::Head;
 use reth_ethereum_primitives::Block;
 use reth_network::{
-    test_utils::{NetworkEventStream, PeerConfig, Testnet},
+    test_utils::{MockAction, MockEthPeer, NetworkEventStream, PeerConfig, Testnet},
     BlockDownloaderProvider, NetworkEventListenerProvider,
 };
 use reth_network_api::{NetworkInfo, Peers};
@@ -14,8 +18,10 @@ use reth_network_p2p::{
     bodies::client::BodiesClient,
     headers::client::{HeadersClient, HeadersRequest},
 };
+use reth_network_peers::pk2id;
 use reth_provider::test_utils::MockEthProvider;
 use reth_transaction_pool::test_utils::{TestPool, TransactionGenerator};
+use secp256k1::{SecretKey, SECP256K1};
 use std::sync::Arc;
 use tokio::sync::oneshot;
 
@@ -108,6 +114,59 @@ async fn test_get_header() {
     }
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_get_header_from_mock_peer() {
+    reth_tracing::init_test_tracing();
+    let mock_provider = MockEthProvider::default();
+    let chain_spec = mock_provider.chain_spec.clone();
+
+    let net = Testnet::create_with(1, Arc::new(mock_provider)).await;
+    let handle = net.peers()[0].handle();
+    let mut events = NetworkEventStream::new(handle.event_listener());
+    let _net = net.spawn();
+
+    // the mock peer is on the chain of the node
+    let genesis = chain_spec.genesis();
+    let head = Head {
+        hash: chain_spec.genesis_hash(),
+        number: 0,
+        timestamp: genesis.timestamp,
+        difficulty: genesis.difficulty,
+        total_difficulty: genesis.difficulty,
+    };
+    let mut status = UnifiedStatus::spec_builder(&*chain_spec, &head);
+    status.set_eth_version(EthVersion::Eth68);
+
+    let header = Header { number: 7, ..Default::default() };
+    let response = header.clone();
+    let secret_key = SecretKey::new(&mut rand_08::thread_rng());
+    let mut peer = MockEthPeer::builder(EthVersion::Eth68)
+        .peer_status(status)
+        .fork_filter(chain_spec.fork_filter(head))
+        .on_message(move |msg| match msg {
+            EthMessage::GetBlockHeaders(req) => {
+                MockAction::Reply(EthMessage::BlockHeaders(RequestPair {
+                    request_id: req.request_id,
+                    message: BlockHeaders(vec![response.clone()]),
+                }))
+            }
+            _ => MockAction::Ignore,
+        })
+        .connect_to(handle.local_addr(), *handle.peer_id(), secret_key)
+        .await
+        .unwrap();
+
+    let connected = events.next_session_established().await.unwrap();
+    assert_eq!(connected, pk2id(&secret_key.public_key(SECP256K1)));
+
+    // requests of the node are served by the mock peer
+    let fetch = handle.fetch_client().await.unwrap();
+    let req = HeadersRequest { start: 7u64.into(), limit: 1, direction: HeadersDirection::Rising };
+    let res = fetch.get_headers(req).await.unwrap();
+    assert_eq!(res.1, vec![header]);
+    assert!(matches!(peer.next_received().await, Some(EthMessage::GetBlockHeaders(_))));
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_eth68_get_receipts() {
     reth_tracing::init_test_tracing();
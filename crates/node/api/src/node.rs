@@ -11,8 +11,9 @@ use reth_network_api::FullNetwork;
 use reth_node_core::node_config::NodeConfig;
 use reth_node_types::{NodeTypes, NodeTypesWithDBAdapter, TxTy};
 use reth_payload_builder::PayloadBuilderHandle;
-use reth_provider::FullProvider;
-use reth_tasks::TaskExecutor;
+use reth_provider::{providers::StorageDamage, FullProvider};
+use reth_rpc_layer::JwtRejections;
+use reth_tasks::{governor::ThroughputGovernor, memory::MemoryBudget, TaskExecutor};
 use reth_tokio_util::EventSender;
 use reth_transaction_pool::{PoolTransaction, TransactionPool};
 use std::{fmt::Debug, future::Future, marker::PhantomData};
@@ -112,12 +113,22 @@ pub struct AddOnsContext<'a, N: FullNodeComponents> {
     pub node: N,
     /// Node configuration.
     pub config: &'a NodeConfig<<N::Types as NodeTypes>::ChainSpec>,
+    /// Node configuration loaded from the config file, with command line overrides applied.
+    pub toml_config: &'a reth_config::Config,
     /// Handle to the beacon consensus engine.
     pub beacon_engine_handle: BeaconConsensusEngineHandle<<N::Types as NodeTypes>::Payload>,
     /// Notification channel for engine API events
     pub engine_events: EventSender<BeaconConsensusEngineEvent<<N::Types as NodeTypes>::Primitives>>,
     /// JWT secret for the node.
     pub jwt_secret: JwtSecret,
+    /// Storage damage the node was started with in safe mode, if any.
+    pub storage_damage: Option<StorageDamage>,
+    /// Memory budget shared by the in-memory caches of the node.
+    pub memory_budget: MemoryBudget,
+    /// Records requests rejected by the JWT validation of the auth server.
+    pub jwt_rejections: JwtRejections,
+    /// Limits the throughput of the execution stage.
+    pub execution_governor: ThroughputGovernor,
 }
 
 /// Customizable node add-on types.
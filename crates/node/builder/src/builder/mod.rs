@@ -7,12 +7,16 @@ use crate::{
     components::NodeComponentsBuilder,
     node::FullNode,
     rpc::{RethRpcAddOns, RethRpcServerHandles, RpcContext},
+    shutdown::{NodeComponent, ShutdownCoordinator},
     BlockReaderFor, DebugNode, DebugNodeLauncher, EngineNodeLauncher, LaunchNode, Node,
 };
 use alloy_eips::eip4844::env_settings::EnvKzgSettings;
 use futures::Future;
 use reth_chainspec::{EthChainSpec, EthereumHardforks, Hardforks};
 use reth_cli_util::get_secret_key;
+use reth_config::config::{
+    ContractCreationPolicy as ConfigContractCreationPolicy, TxPoolFirewallConfig,
+};
 use reth_db_api::{database::Database, database_metrics::DatabaseMetrics};
 use reth_exex::ExExContext;
 use reth_network::{
@@ -35,7 +39,10 @@ use reth_provider::{
     ChainSpecProvider, FullProvider,
 };
 use reth_tasks::TaskExecutor;
-use reth_transaction_pool::{PoolConfig, PoolTransaction, TransactionPool};
+use reth_transaction_pool::{
+    firewall::{ContractCreationPolicy, FirewallConfig, MinPriorityFeePerOrigin},
+    PoolConfig, PoolTransaction, TransactionPool,
+};
 use secp256k1::SecretKey;
 use std::{fmt::Debug, sync::Arc};
 use tracing::{info, trace, warn};
@@ -670,17 +677,23 @@ pub struct BuilderContext<Node: FullNodeTypes> {
     pub(crate) executor: TaskExecutor,
     /// Config container
     pub(crate) config_container: WithConfigs<<Node::Types as NodeTypes>::ChainSpec>,
+    /// Shuts the components of the node down in order.
+    pub(crate) shutdown: ShutdownCoordinator,
 }
 
 impl<Node: FullNodeTypes> BuilderContext<Node> {
     /// Create a new instance of [`BuilderContext`]
+    ///
+    /// Components shut down in the order of the given [`ShutdownCoordinator`], which must be
+    /// spawned to shut them down gracefully.
     pub const fn new(
         head: Head,
         provider: Node::Provider,
         executor: TaskExecutor,
         config_container: WithConfigs<<Node::Types as NodeTypes>::ChainSpec>,
+        shutdown: ShutdownCoordinator,
     ) -> Self {
-        Self { head, provider, executor, config_container }
+        Self { head, provider, executor, config_container, shutdown }
     }
 
     /// Returns the configured provider to interact with the blockchain.
@@ -710,6 +723,11 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
         &self.executor
     }
 
+    /// Returns the coordinator that shuts the components of the node down in order.
+    pub const fn shutdown_coordinator(&self) -> &ShutdownCoordinator {
+        &self.shutdown
+    }
+
     /// Returns the chain spec of the node.
     pub fn chain_spec(&self) -> Arc<<Node::Types as NodeTypes>::ChainSpec> {
         self.provider().chain_spec()
@@ -720,9 +738,13 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
         self.config().dev.dev
     }
 
-    /// Returns the transaction pool config of the node.
+    /// Returns the transaction pool config of the node, including the firewall rules of the
+    /// configuration file.
     pub fn pool_config(&self) -> PoolConfig {
-        self.config().txpool.pool_config()
+        PoolConfig {
+            firewall: firewall_config(&self.reth_config().txpool.firewall),
+            ..self.config().txpool.pool_config()
+        }
     }
 
     /// Loads `EnvKzgSettings::Default`.
@@ -798,9 +820,11 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
 
         let default_peers_path = self.config().datadir().known_peers();
         let known_peers_file = self.config().network.persistent_peers_file(default_peers_path);
-        self.executor.spawn_critical_with_graceful_shutdown_signal(
+        // the network is shut down after the engine, see `NodeComponent`
+        let shutdown = self.shutdown.on_shutdown(NodeComponent::Network);
+        self.executor.spawn_critical_with_shutdown_signal(
             "p2p network task",
-            |shutdown| {
+            |_| {
                 network.run_until_graceful_shutdown(shutdown, |network| {
                     if let Some(peers_file) = known_peers_file {
                         let num_known_peers = network.num_known_peers();
@@ -895,3 +919,22 @@ impl<Node: FullNodeTypes> std::fmt::Debug for BuilderContext<Node> {
             .finish()
     }
 }
+
+/// Converts the firewall rules of the configuration file into the firewall config of the pool.
+fn firewall_config(config: &TxPoolFirewallConfig) -> FirewallConfig {
+    FirewallConfig {
+        max_calldata_size: config.max_calldata_size,
+        blocked_senders: config.blocked_senders.clone(),
+        blocked_recipients: config.blocked_recipients.clone(),
+        min_priority_fee: MinPriorityFeePerOrigin {
+            local: config.min_priority_fee.local,
+            external: config.min_priority_fee.external,
+            private: config.min_priority_fee.private,
+        },
+        contract_creation: match config.contract_creation {
+            ConfigContractCreationPolicy::Allow => ContractCreationPolicy::Allow,
+            ConfigContractCreationPolicy::LocalOnly => ContractCreationPolicy::LocalOnly,
+            ConfigContractCreationPolicy::Deny => ContractCreationPolicy::Deny,
+        },
+    }
+}
@@ -11,8 +11,9 @@ use reth_consensus::{ConsensusError, FullConsensus};
 use reth_network::types::NetPrimitivesFor;
 use reth_network_api::FullNetwork;
 use reth_node_api::{PrimitivesTy, TxTy};
+use reth_primitives_traits::crypto::RecoveryBackend;
 use reth_transaction_pool::{PoolPooledTx, PoolTransaction, TransactionPool};
-use std::{future::Future, marker::PhantomData};
+use std::{future::Future, marker::PhantomData, sync::Arc};
 
 /// A generic, general purpose and customizable [`NodeComponentsBuilder`] implementation.
 ///
@@ -40,6 +41,7 @@ pub struct ComponentsBuilder<Node, PoolB, PayloadB, NetworkB, ExecB, ConsB> {
     network_builder: NetworkB,
     executor_builder: ExecB,
     consensus_builder: ConsB,
+    recovery_backend: Option<Arc<dyn RecoveryBackend>>,
     _marker: PhantomData<Node>,
 }
 
@@ -59,6 +61,7 @@ impl<Node, PoolB, PayloadB, NetworkB, ExecB, ConsB>
             network_builder,
             executor_builder: evm_builder,
             consensus_builder,
+            recovery_backend,
             _marker,
         } = self;
         ComponentsBuilder {
@@ -67,6 +70,7 @@ impl<Node, PoolB, PayloadB, NetworkB, ExecB, ConsB>
             payload_builder,
             network_builder,
             consensus_builder,
+            recovery_backend,
             _marker: Default::default(),
         }
     }
@@ -79,6 +83,7 @@ impl<Node, PoolB, PayloadB, NetworkB, ExecB, ConsB>
             network_builder: self.network_builder,
             executor_builder: self.executor_builder,
             consensus_builder: self.consensus_builder,
+            recovery_backend: self.recovery_backend,
             _marker: self._marker,
         }
     }
@@ -91,6 +96,7 @@ impl<Node, PoolB, PayloadB, NetworkB, ExecB, ConsB>
             network_builder: self.network_builder,
             executor_builder: self.executor_builder,
             consensus_builder: self.consensus_builder,
+            recovery_backend: self.recovery_backend,
             _marker: self._marker,
         }
     }
@@ -103,6 +109,7 @@ impl<Node, PoolB, PayloadB, NetworkB, ExecB, ConsB>
             network_builder: f(self.network_builder),
             executor_builder: self.executor_builder,
             consensus_builder: self.consensus_builder,
+            recovery_backend: self.recovery_backend,
             _marker: self._marker,
         }
     }
@@ -115,6 +122,7 @@ impl<Node, PoolB, PayloadB, NetworkB, ExecB, ConsB>
             network_builder: self.network_builder,
             executor_builder: f(self.executor_builder),
             consensus_builder: self.consensus_builder,
+            recovery_backend: self.recovery_backend,
             _marker: self._marker,
         }
     }
@@ -127,9 +135,20 @@ impl<Node, PoolB, PayloadB, NetworkB, ExecB, ConsB>
             network_builder: self.network_builder,
             executor_builder: self.executor_builder,
             consensus_builder: f(self.consensus_builder),
+            recovery_backend: self.recovery_backend,
             _marker: self._marker,
         }
     }
+
+    /// Configures the backend the sender recovery stage recovers signers with.
+    ///
+    /// Signers are recovered with secp256k1 on the CPU by default. Alternative backends, e.g.
+    /// batch recovery on a GPU or a remote signer service, only replace the recovery during sync;
+    /// the transaction pool, RPC and consensus keep recovering with secp256k1.
+    pub fn recovery_backend(mut self, backend: Arc<dyn RecoveryBackend>) -> Self {
+        self.recovery_backend = Some(backend);
+        self
+    }
 }
 
 impl<Node, PoolB, PayloadB, NetworkB, ExecB, ConsB>
@@ -154,6 +173,7 @@ where
             network_builder,
             executor_builder: evm_builder,
             consensus_builder,
+            recovery_backend,
             _marker,
         } = self;
         ComponentsBuilder {
@@ -162,6 +182,7 @@ where
             network_builder,
             executor_builder: evm_builder,
             consensus_builder,
+            recovery_backend,
             _marker,
         }
     }
@@ -190,6 +211,7 @@ where
             network_builder: _,
             executor_builder: evm_builder,
             consensus_builder,
+            recovery_backend,
             _marker,
         } = self;
         ComponentsBuilder {
@@ -198,6 +220,7 @@ where
             network_builder,
             executor_builder: evm_builder,
             consensus_builder,
+            recovery_backend,
             _marker,
         }
     }
@@ -220,6 +243,7 @@ where
             network_builder,
             executor_builder: evm_builder,
             consensus_builder,
+            recovery_backend,
             _marker,
         } = self;
         ComponentsBuilder {
@@ -228,6 +252,7 @@ where
             network_builder,
             executor_builder: evm_builder,
             consensus_builder,
+            recovery_backend,
             _marker,
         }
     }
@@ -249,6 +274,7 @@ where
             network_builder,
             executor_builder: _,
             consensus_builder,
+            recovery_backend,
             _marker,
         } = self;
         ComponentsBuilder {
@@ -257,6 +283,7 @@ where
             network_builder,
             executor_builder,
             consensus_builder,
+            recovery_backend,
             _marker,
         }
     }
@@ -265,6 +292,12 @@ where
     ///
     /// This accepts a [`ConsensusBuilder`] instance that will be used to create the node's
     /// components for consensus.
+    ///
+    /// The consensus implementation validates headers, block bodies and execution results. Custom
+    /// chain rules can be enforced by replacing it, while reusing all other components of the
+    /// node, e.g. `EthereumNode::components().consensus(MyConsensusBuilder::default())`. A custom
+    /// implementation can wrap the default one and only override the rules that differ, see the
+    /// `custom-consensus` example.
     pub fn consensus<CB>(
         self,
         consensus_builder: CB,
@@ -278,7 +311,7 @@ where
             network_builder,
             executor_builder,
             consensus_builder: _,
-
+            recovery_backend,
             _marker,
         } = self;
         ComponentsBuilder {
@@ -287,6 +320,7 @@ where
             network_builder,
             executor_builder,
             consensus_builder,
+            recovery_backend,
             _marker,
         }
     }
@@ -324,6 +358,7 @@ where
             network_builder,
             executor_builder: evm_builder,
             consensus_builder,
+            recovery_backend,
             _marker,
         } = self;
 
@@ -341,6 +376,7 @@ where
             network,
             payload_builder_handle,
             consensus,
+            recovery_backend,
         })
     }
 }
@@ -353,6 +389,7 @@ impl Default for ComponentsBuilder<(), (), (), (), (), ()> {
             network_builder: (),
             executor_builder: (),
             consensus_builder: (),
+            recovery_backend: None,
             _marker: Default::default(),
         }
     }
@@ -4,6 +4,8 @@
 //!  - The transaction pool.
 //!  - The network implementation.
 //!  - The payload builder service.
+//!  - The consensus implementation.
+//!  - The backend that recovers transaction senders during sync.
 //!
 //! Components depend on a fully type configured node: [FullNodeTypes](crate::node::FullNodeTypes).
 
@@ -27,8 +29,9 @@ use reth_network::types::NetPrimitivesFor;
 use reth_network_api::FullNetwork;
 use reth_node_api::{NodeTypes, PrimitivesTy, TxTy};
 use reth_payload_builder::PayloadBuilderHandle;
+use reth_primitives_traits::crypto::RecoveryBackend;
 use reth_transaction_pool::{PoolPooledTx, PoolTransaction, TransactionPool};
-use std::fmt::Debug;
+use std::{fmt::Debug, sync::Arc};
 
 /// An abstraction over the components of a node, consisting of:
 ///  - evm and executor
@@ -66,6 +69,11 @@ pub trait NodeComponents<T: FullNodeTypes>: Clone + Debug + Unpin + Send + Sync
     /// Returns the handle to the payload builder service handling payload building requests from
     /// the engine.
     fn payload_builder_handle(&self) -> &PayloadBuilderHandle<<T::Types as NodeTypes>::Payload>;
+
+    /// Returns the backend the sender recovery stage recovers signers with, if not secp256k1.
+    fn recovery_backend(&self) -> Option<&Arc<dyn RecoveryBackend>> {
+        None
+    }
 }
 
 /// All the components of the node.
@@ -83,6 +91,8 @@ pub struct Components<Node: FullNodeTypes, Network, Pool, EVM, Consensus> {
     pub network: Network,
     /// The handle to the payload builder service.
     pub payload_builder_handle: PayloadBuilderHandle<<Node::Types as NodeTypes>::Payload>,
+    /// The backend the sender recovery stage recovers signers with, if not secp256k1.
+    pub recovery_backend: Option<Arc<dyn RecoveryBackend>>,
 }
 
 impl<Node, Pool, EVM, Cons, Network> NodeComponents<Node>
@@ -126,6 +136,10 @@ where
     fn payload_builder_handle(&self) -> &PayloadBuilderHandle<<Node::Types as NodeTypes>::Payload> {
         &self.payload_builder_handle
     }
+
+    fn recovery_backend(&self) -> Option<&Arc<dyn RecoveryBackend>> {
+        self.recovery_backend.as_ref()
+    }
 }
 
 impl<Node, N, Pool, EVM, Cons> Clone for Components<Node, N, Pool, EVM, Cons>
@@ -143,6 +157,7 @@ where
             consensus: self.consensus.clone(),
             network: self.network.clone(),
             payload_builder_handle: self.payload_builder_handle.clone(),
+            recovery_backend: self.recovery_backend.clone(),
         }
     }
 }
@@ -1,7 +1,9 @@
 //! Payload service component for the node builder.
 
 use crate::{BuilderContext, FullNodeTypes};
-use reth_basic_payload_builder::{BasicPayloadJobGenerator, BasicPayloadJobGeneratorConfig};
+use reth_basic_payload_builder::{
+    BasicPayloadJobGenerator, BasicPayloadJobGeneratorConfig, PayloadBuildStrategy,
+};
 use reth_chain_state::CanonStateSubscriptions;
 use reth_node_api::{NodeTypes, PayloadBuilderFor};
 use reth_payload_builder::{PayloadBuilderHandle, PayloadBuilderService, PayloadServiceCommand};
@@ -93,10 +95,17 @@ where
 
         let conf = ctx.config().builder.clone();
 
+        let strategy = if conf.until_resolved {
+            PayloadBuildStrategy::UntilResolved
+        } else {
+            PayloadBuildStrategy::Interval
+        };
         let payload_job_config = BasicPayloadJobGeneratorConfig::default()
             .interval(conf.interval)
             .deadline(conf.deadline)
-            .max_payload_tasks(conf.max_payload_tasks);
+            .max_payload_tasks(conf.max_payload_tasks)
+            .max_iterations(conf.max_iterations)
+            .strategy(strategy);
 
         let payload_generator = BasicPayloadJobGenerator::with_builder(
             ctx.provider().clone(),
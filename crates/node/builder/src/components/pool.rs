@@ -5,7 +5,7 @@ use reth_chain_state::CanonStateSubscriptions;
 use reth_node_api::TxTy;
 use reth_transaction_pool::{
     blobstore::DiskFileBlobStore, CoinbaseTipOrdering, PoolConfig, PoolTransaction, SubPoolLimit,
-    TransactionPool, TransactionValidationTaskExecutor, TransactionValidator,
+    TransactionOrdering, TransactionPool, TransactionValidationTaskExecutor, TransactionValidator,
 };
 use std::{collections::HashSet, future::Future};
 
@@ -145,15 +145,34 @@ where
             DiskFileBlobStore,
         >,
     > {
+        self.build_and_spawn_maintenance_task_with_ordering(
+            CoinbaseTipOrdering::default(),
+            blob_store,
+            pool_config,
+        )
+    }
+
+    /// Build the transaction pool with a custom [`TransactionOrdering`] and spawn its maintenance
+    /// tasks.
+    ///
+    /// The ordering determines the priority of pending transactions, e.g. a
+    /// [`PolicyOrdering`](reth_transaction_pool::PolicyOrdering) that ranks certain senders first.
+    pub fn build_and_spawn_maintenance_task_with_ordering<O>(
+        self,
+        ordering: O,
+        blob_store: DiskFileBlobStore,
+        pool_config: PoolConfig,
+    ) -> eyre::Result<
+        reth_transaction_pool::Pool<TransactionValidationTaskExecutor<V>, O, DiskFileBlobStore>,
+    >
+    where
+        O: TransactionOrdering<Transaction = V::Transaction>,
+    {
         // Destructure self to avoid partial move issues
         let TxPoolBuilder { ctx, validator, .. } = self;
 
-        let transaction_pool = reth_transaction_pool::Pool::new(
-            validator,
-            CoinbaseTipOrdering::default(),
-            blob_store,
-            pool_config.clone(),
-        );
+        let transaction_pool =
+            reth_transaction_pool::Pool::new(validator, ordering, blob_store, pool_config.clone());
 
         // Spawn maintenance tasks using standalone functions
         spawn_maintenance_tasks(ctx, transaction_pool.clone(), &pool_config)?;
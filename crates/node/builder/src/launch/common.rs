@@ -32,6 +32,7 @@
 use crate::{
     components::{NodeComponents, NodeComponentsBuilder},
     hooks::OnComponentInitializedHook,
+    shutdown::ShutdownCoordinator,
     BuilderContext, ExExLauncher, NodeAdapter, PrimitivesTy,
 };
 use alloy_consensus::BlockHeader as _;
@@ -39,11 +40,14 @@ use alloy_eips::eip2124::Head;
 use alloy_primitives::{BlockNumber, B256};
 use eyre::{Context, OptionExt};
 use rayon::ThreadPoolBuilder;
-use reth_chainspec::{Chain, EthChainSpec, EthereumHardfork, EthereumHardforks};
-use reth_config::{config::EtlConfig, PruneConfig};
+use reth_chainspec::{Chain, EthChainSpec, EthereumHardforks};
+use reth_config::{
+    config::{EtlConfig, ExecutionGovernorConfig},
+    PruneConfig,
+};
 use reth_consensus::noop::NoopConsensus;
 use reth_db_api::{database::Database, database_metrics::DatabaseMetrics};
-use reth_db_common::init::{init_genesis, InitStorageError};
+use reth_db_common::init::{init_genesis, verify_genesis, InitStorageError};
 use reth_downloaders::{bodies::noop::NoopBodiesDownloader, headers::noop::NoopHeaderDownloader};
 use reth_engine_local::MiningMode;
 use reth_engine_tree::tree::{InvalidBlockHook, InvalidBlockHooks, NoopInvalidBlockHook};
@@ -52,6 +56,7 @@ use reth_exex::ExExManagerHandle;
 use reth_fs_util as fs;
 use reth_invalid_block_hooks::InvalidBlockWitnessHook;
 use reth_network_p2p::headers::client::HeadersClient;
+use reth_nippy_jar::compression::EncryptionKey;
 use reth_node_api::{FullNodeTypes, NodeTypes, NodeTypesWithDB, NodeTypesWithDBAdapter};
 use reth_node_core::{
     args::{DefaultEraHost, InvalidBlockHookType},
@@ -71,24 +76,28 @@ use reth_node_metrics::{
     version::VersionInfo,
 };
 use reth_provider::{
-    providers::{NodeTypesForProvider, ProviderNodeTypes, StaticFileProvider},
-    BlockHashReader, BlockNumReader, BlockReaderIdExt, ChainSpecProvider, ProviderError,
-    ProviderFactory, ProviderResult, StageCheckpointReader, StateProviderFactory,
+    providers::{
+        BytecodeCache, NodeTypesForProvider, ProviderNodeTypes, QueryStats, StaticFileProvider,
+        StorageDamage,
+    },
+    BlockHashReader, BlockNumReader, BlockReaderIdExt, ChainSpecProvider, GasUsageWriter,
+    ProviderError, ProviderFactory, ProviderResult, StageCheckpointReader, StateProviderFactory,
     StaticFileProviderFactory,
 };
 use reth_prune::{PruneModes, PrunerBuilder};
 use reth_rpc_api::clients::EthApiClient;
 use reth_rpc_builder::config::RethRpcServerConfig;
-use reth_rpc_layer::JwtSecret;
+use reth_rpc_layer::{JwtRejections, JwtSecret};
 use reth_stages::{
     sets::DefaultStages, stages::EraImportSource, MetricEvent, PipelineBuilder, PipelineTarget,
     StageId,
 };
 use reth_static_file::StaticFileProducer;
-use reth_tasks::TaskExecutor;
+use reth_static_file_s3::{S3Backend, S3Credentials, DEFAULT_S3_REGION};
+use reth_tasks::{governor::ThroughputGovernor, memory::MemoryBudget, TaskExecutor};
 use reth_tracing::tracing::{debug, error, info, warn};
 use reth_transaction_pool::TransactionPool;
-use std::{sync::Arc, thread::available_parallelism};
+use std::{sync::Arc, thread::available_parallelism, time::Duration};
 use tokio::sync::{
     mpsc::{unbounded_channel, UnboundedSender},
     oneshot, watch,
@@ -97,6 +106,9 @@ use tokio::sync::{
 use futures::{future::Either, stream, Stream, StreamExt};
 use reth_node_events::{cl::ConsensusLayerHealthEvents, node::NodeEvent};
 
+/// The interval in which the query statistics are persisted.
+const QUERY_STATS_SAVE_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
 /// Reusable setup for launching a node.
 ///
 /// This is the entry point for the node launch process. It implements a builder
@@ -121,12 +133,14 @@ pub struct LaunchContext {
     pub task_executor: TaskExecutor,
     /// The data directory for the node.
     pub data_dir: ChainPath<DataDirPath>,
+    /// Shuts the components of the node down in order.
+    pub shutdown: ShutdownCoordinator,
 }
 
 impl LaunchContext {
     /// Create a new instance of the default node launcher.
-    pub const fn new(task_executor: TaskExecutor, data_dir: ChainPath<DataDirPath>) -> Self {
-        Self { task_executor, data_dir }
+    pub fn new(task_executor: TaskExecutor, data_dir: ChainPath<DataDirPath>) -> Self {
+        Self { task_executor, data_dir, shutdown: ShutdownCoordinator::new() }
     }
 
     /// Create launch context with attachment.
@@ -271,6 +285,11 @@ impl<T> LaunchContextWith<T> {
         &self.inner.task_executor
     }
 
+    /// Returns the coordinator that shuts the components of the node down in order.
+    pub const fn shutdown_coordinator(&self) -> &ShutdownCoordinator {
+        &self.inner.shutdown
+    }
+
     /// Attaches another value to the launch context.
     pub fn attach<A>(self, attachment: A) -> LaunchContextWith<Attached<T, A>> {
         LaunchContextWith {
@@ -404,6 +423,11 @@ impl<R, ChainSpec: EthChainSpec> LaunchContextWith<Attached<WithConfigs<ChainSpe
         self.node_config().dev.dev
     }
 
+    /// Returns true if the node is configured as --safe-mode
+    pub const fn is_safe_mode(&self) -> bool {
+        self.node_config().safe_mode
+    }
+
     /// Returns the configured [`PruneConfig`]
     ///
     /// Any configuration set in CLI will take precedence over those set in toml
@@ -464,18 +488,58 @@ where
     /// Returns the [`ProviderFactory`] for the attached storage after executing a consistent check
     /// between the database and static files. **It may execute a pipeline unwind if it fails this
     /// check.**
+    ///
+    /// In safe mode static files are opened read-only and no unwind is executed. Instead, the
+    /// damage is recorded on the returned factory, see [`StorageDamage`].
     pub async fn create_provider_factory<N, Evm>(&self) -> eyre::Result<ProviderFactory<N>>
     where
         N: ProviderNodeTypes<DB = DB, ChainSpec = ChainSpec>,
         Evm: ConfigureEvm<Primitives = N::Primitives> + 'static,
     {
-        let factory = ProviderFactory::new(
-            self.right().clone(),
-            self.chain_spec(),
-            StaticFileProvider::read_write(self.data_dir().static_files())?,
-        )
-        .with_prune_modes(self.prune_modes())
-        .with_static_files_metrics();
+        let mut static_file_provider = if self.is_safe_mode() {
+            StaticFileProvider::read_only(self.data_dir().static_files(), false)?
+        } else {
+            StaticFileProvider::read_write(self.data_dir().static_files())?
+        };
+        if let Some(key) = self.node_config().db.static_files_encryption_key()? {
+            static_file_provider =
+                static_file_provider.with_encryption_key(Some(EncryptionKey::new(key.0)));
+        }
+        if let Some(max_open) = self.node_config().db.max_open_static_files {
+            static_file_provider = static_file_provider.with_max_open_jars(max_open);
+        }
+        let segment_directories = self.data_dir().static_files_segment_directories();
+        if !segment_directories.is_empty() {
+            static_file_provider = static_file_provider
+                .with_segment_directories(segment_directories.iter().cloned())?;
+        }
+        if let Some(url) = self.data_dir().static_files_s3() {
+            let region = self.data_dir().static_files_s3_region().unwrap_or(DEFAULT_S3_REGION);
+            let backend = S3Backend::new(url, region)?.with_credentials(S3Credentials::from_env());
+            if let Some(size) = self.data_dir().static_files_s3_cache_size() {
+                static_file_provider = static_file_provider.with_backend_cache_size(size as u64);
+            }
+            static_file_provider = static_file_provider.with_backend(Arc::new(backend))?;
+        }
+        let mut factory =
+            ProviderFactory::new(self.right().clone(), self.chain_spec(), static_file_provider)
+                .with_prune_modes(self.prune_modes())
+                .with_static_files_metrics();
+        if let Some(budget) = self.node_config().rpc.rpc_proof_effort_budget {
+            factory = factory.with_proof_effort_budget(budget);
+        }
+        let bytecode_cache_args = &self.node_config().rpc.rpc_state_cache;
+        if bytecode_cache_args.max_bytecodes > 0 {
+            factory =
+                factory.with_bytecode_cache(BytecodeCache::new(bytecode_cache_args.max_bytecodes));
+        }
+        if self.node_config().rpc.rpc_state_cache.warmup {
+            let stats = QueryStats::load(&self.data_dir().query_stats()).unwrap_or_else(|err| {
+                warn!(target: "reth::cli", %err, "Failed to load query statistics");
+                QueryStats::new()
+            });
+            factory = factory.with_query_stats(stats);
+        }
 
         let has_receipt_pruning =
             self.toml_config().prune.as_ref().is_some_and(|a| a.has_receipts_pruning());
@@ -486,6 +550,20 @@ where
             .static_file_provider()
             .check_consistency(&factory.provider()?, has_receipt_pruning)?
         {
+            if self.is_safe_mode() {
+                let damage = StorageDamage {
+                    consistent_tip: unwind_target.unwind_target().unwrap_or_default(),
+                    highest_block: factory.provider()?.last_block_number()?,
+                };
+                warn!(
+                    target: "reth::cli",
+                    consistent_tip = damage.consistent_tip,
+                    highest_block = damage.highest_block,
+                    "Storage consistency check failed, serving the consistent range in safe mode"
+                );
+                return Ok(factory.with_storage_damage(damage))
+            }
+
             // Highly unlikely to happen, and given its destructive nature, it's better to panic
             // instead.
             assert_ne!(
@@ -544,6 +622,81 @@ where
         Evm: ConfigureEvm<Primitives = N::Primitives> + 'static,
     {
         let factory = self.create_provider_factory::<N, Evm>().await?;
+
+        // Start maintaining the gas usage index after the executed blocks, if enabled. The index
+        // is extended by execution, which may be ahead of the last finished pipeline run.
+        let provider_rw = factory.provider_rw()?;
+        if self.toml_config().stages.index_gas_usage.enabled {
+            let executed = provider_rw
+                .get_stage_checkpoint(StageId::Execution)?
+                .unwrap_or_default()
+                .block_number;
+            provider_rw.init_gas_usage_index(executed)?;
+        } else if provider_rw.gas_usage_indexed_to()?.is_some() {
+            info!(target: "reth::cli", "Removing the disabled gas usage index");
+            provider_rw.remove_gas_usage_index()?;
+        }
+        provider_rw.commit()?;
+
+        // Pre-warm the bytecode cache in the background, it only speeds up later reads.
+        let prewarm_bytecodes = self.node_config().rpc.rpc_state_cache.prewarm_bytecodes;
+        if factory.bytecode_cache().is_some() && prewarm_bytecodes > 0 {
+            let factory = factory.clone();
+            self.task_executor().spawn_blocking(Box::pin(async move {
+                match factory.prewarm_bytecode_cache(prewarm_bytecodes) {
+                    Ok(bytecodes) => {
+                        debug!(target: "reth::cli", bytecodes, "Pre-warmed bytecode cache")
+                    }
+                    Err(err) => {
+                        warn!(target: "reth::cli", %err, "Failed to pre-warm bytecode cache")
+                    }
+                }
+            }));
+        }
+
+        // Warm the caches with the recorded query patterns in the background, and persist the
+        // statistics periodically and on shutdown.
+        if let Some(stats) = factory.query_stats().cloned() {
+            let state_cache_args = &self.node_config().rpc.rpc_state_cache;
+            let (contracts, ranges) =
+                (state_cache_args.warmup_contracts, state_cache_args.warmup_ranges);
+            let warmup_factory = factory.clone();
+            self.task_executor().spawn_blocking(Box::pin(async move {
+                match warmup_factory.warm_from_query_stats(contracts, ranges) {
+                    Ok((contracts, entries)) => {
+                        debug!(target: "reth::cli", contracts, entries, "Warmed caches with query statistics")
+                    }
+                    Err(err) => {
+                        warn!(target: "reth::cli", %err, "Failed to warm caches with query statistics")
+                    }
+                }
+            }));
+
+            let path = self.data_dir().query_stats();
+            self.task_executor().spawn_critical_with_graceful_shutdown_signal(
+                "query stats persistence",
+                |shutdown| async move {
+                    let save = || {
+                        if let Err(err) = stats.save(&path) {
+                            warn!(target: "reth::cli", %err, "Failed to save query statistics");
+                        }
+                    };
+                    let mut interval = tokio::time::interval(QUERY_STATS_SAVE_INTERVAL);
+                    // the first tick completes immediately
+                    interval.tick().await;
+                    tokio::pin!(shutdown);
+                    let guard = loop {
+                        tokio::select! {
+                            _ = interval.tick() => save(),
+                            guard = &mut shutdown => break guard,
+                        }
+                    };
+                    save();
+                    drop(guard);
+                },
+            );
+        }
+
         let ctx = LaunchContextWith {
             inner: self.inner,
             attachment: self.attachment.map_right(|_| factory),
@@ -613,6 +766,14 @@ where
                             }
                         }
                     })
+                    .with_hook({
+                        let factory = self.provider_factory().clone();
+                        move || {
+                            if let Err(error) = factory.report_state_size_metrics() {
+                                error!(%error, "Failed to report state size metrics");
+                            }
+                        }
+                    })
                     .build(),
             );
 
@@ -624,12 +785,17 @@ where
 
     /// Convenience function to [`Self::init_genesis`]
     pub fn with_genesis(self) -> Result<Self, InitStorageError> {
-        init_genesis(self.provider_factory())?;
+        self.init_genesis()?;
         Ok(self)
     }
 
     /// Write the genesis block and state if it has not already been written
+    ///
+    /// In safe mode the storage isn't written to, so this only verifies the genesis block.
     pub fn init_genesis(&self) -> Result<B256, InitStorageError> {
+        if self.is_safe_mode() {
+            return verify_genesis(self.provider_factory())
+        }
         init_genesis(self.provider_factory())
     }
 
@@ -760,11 +926,15 @@ where
         // fetch the head block from the database
         let head = self.lookup_head()?;
 
+        // components register with the coordinator while they're built
+        self.shutdown_coordinator().spawn(self.task_executor());
+
         let builder_ctx = BuilderContext::new(
             head,
             self.blockchain_db().clone(),
             self.task_executor().clone(),
             self.configs().clone(),
+            self.shutdown_coordinator().clone(),
         );
 
         debug!(target: "reth::cli", "creating components");
@@ -949,34 +1119,34 @@ where
         Ok(None)
     }
 
-    /// Expire the pre-merge transactions if the node is configured to do so and the chain has a
-    /// merge block.
+    /// Expires the transaction history below the configured retention boundary and returns the
+    /// earliest block whose history is still available.
     ///
-    /// If the node is configured to prune pre-merge transactions and it has synced past the merge
-    /// block, it will delete the pre-merge transaction static files if they still exist.
-    pub fn expire_pre_merge_transactions(&self) -> eyre::Result<()>
+    /// The retention boundary is configured with `--history.expire-before`, or is the merge block
+    /// with `--prune.bodies.pre-merge`. History is only expired once the node has synced past the
+    /// boundary, and only whole transaction static files below it are deleted.
+    pub fn expire_history(&self) -> eyre::Result<BlockNumber>
     where
         T: FullNodeTypes<Provider: StaticFileProviderFactory>,
     {
-        if self.node_config().pruning.bodies_pre_merge {
-            if let Some(merge_block) =
-                self.chain_spec().ethereum_fork_activation(EthereumHardfork::Paris).block_number()
+        let provider = self.blockchain_db().static_file_provider();
+        if let Some(boundary) = self.node_config().history_expiry() {
+            // Ensure we only expire history after we synced past the retention boundary.
+            if self
+                .blockchain_db()
+                .latest_header()?
+                .is_some_and(|latest| latest.number() > boundary)
             {
-                // Ensure we only expire transactions after we synced past the merge block.
-                let Some(latest) = self.blockchain_db().latest_header()? else { return Ok(()) };
-                if latest.number() > merge_block {
-                    let provider = self.blockchain_db().static_file_provider();
-                    if provider.get_lowest_transaction_static_file_block() < Some(merge_block) {
-                        info!(target: "reth::cli", merge_block, "Expiring pre-merge transactions");
-                        provider.delete_transactions_below(merge_block)?;
-                    } else {
-                        debug!(target: "reth::cli", merge_block, "No pre-merge transactions to expire");
-                    }
+                if provider.get_lowest_transaction_static_file_block() < Some(boundary) {
+                    info!(target: "reth::cli", boundary, "Expiring transaction history");
+                    provider.delete_transactions_below(boundary)?;
+                } else {
+                    debug!(target: "reth::cli", boundary, "No transaction history to expire");
                 }
             }
         }
 
-        Ok(())
+        Ok(provider.earliest_history_height())
     }
 
     /// Returns the metrics sender.
@@ -1003,6 +1173,7 @@ where
             self.node_adapter().clone(),
             installed_exex,
             self.configs().clone(),
+            self.shutdown_coordinator().clone(),
         )
         .launch()
         .await
@@ -1025,6 +1196,35 @@ where
         )
     }
 
+    /// Creates the memory budget shared by the in-memory caches of the node.
+    ///
+    /// If a budget is configured, this spawns a task that enforces it periodically. Otherwise the
+    /// budget only tracks the memory reported by the caches.
+    pub fn spawn_memory_budget(&self) -> MemoryBudget {
+        let args = &self.node_config().memory;
+        let Some(limit) = args.budget_bytes() else { return MemoryBudget::unlimited() };
+
+        info!(target: "reth::cli", limit, interval = ?args.reclaim_interval, "Enforcing memory budget");
+        let budget = MemoryBudget::new(limit);
+        self.task_executor().spawn_critical(
+            "memory budget task",
+            Box::pin(budget.clone().reclaim_task(args.reclaim_interval)),
+        );
+        budget
+    }
+
+    /// Returns the governor that limits the throughput of the execution stage, with the limits of
+    /// the configuration.
+    ///
+    /// The limits can be changed at runtime with `admin_setExecutionGovernor`.
+    pub fn execution_governor(&self) -> ThroughputGovernor {
+        let config = self.toml_config().stages.execution.governor;
+        if config != ExecutionGovernorConfig::default() {
+            info!(target: "reth::cli", ?config, "Limiting execution throughput");
+        }
+        ThroughputGovernor::new(config.max_gas_per_second, config.max_write_bytes_per_second)
+    }
+
     /// Creates consensus layer health events stream based on node configuration.
     ///
     /// Returns a stream that monitors consensus layer health if:
@@ -1032,15 +1232,23 @@ where
     /// - Not running in dev mode
     ///
     /// Otherwise returns an empty stream.
+    ///
+    /// Requests rejected by the auth server are recorded in `jwt_rejections` and used to diagnose
+    /// why no updates are received from the consensus layer.
     pub fn consensus_layer_events(
         &self,
+        jwt_rejections: JwtRejections,
     ) -> impl Stream<Item = NodeEvent<PrimitivesTy<T::Types>>> + 'static
     where
         T::Provider: reth_provider::CanonChainTracker,
     {
         if self.node_config().debug.tip.is_none() && !self.is_dev() {
+            let engine = &self.node_config().engine;
             Either::Left(
                 ConsensusLayerHealthEvents::new(Box::new(self.blockchain_db().clone()))
+                    .with_no_update_period(engine.cl_health_window)
+                    .with_jwt_rejections(jwt_rejections)
+                    .with_cl_endpoint(engine.cl_endpoint.clone())
                     .map(Into::into),
             )
         } else {
@@ -1266,6 +1474,7 @@ mod tests {
                     bodies_distance: None,
                     receipts_log_filter: None,
                     bodies_before: None,
+                    history_expire_before: None,
                 },
                 ..NodeConfig::test()
             };
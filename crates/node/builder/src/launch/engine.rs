@@ -5,17 +5,21 @@ use crate::{
     hooks::NodeHooks,
     rpc::{EngineValidatorAddOn, RethRpcAddOns, RpcHandle},
     setup::build_networked_pipeline,
+    shutdown::{ComponentTaskSpawner, NodeComponent},
     AddOns, AddOnsContext, FullNode, LaunchContext, LaunchNode, NodeAdapter,
     NodeBuilderWithComponents, NodeComponents, NodeComponentsBuilder, NodeHandle, NodeTypesAdapter,
 };
 use alloy_consensus::BlockHeader;
-use futures::{stream_select, StreamExt};
+use alloy_eips::{BlockNumHash, Typed2718};
+use futures::{future, stream_select, StreamExt};
+use reth_chain_state::CanonicalInMemoryState;
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_db_api::{database_metrics::DatabaseMetrics, Database};
 use reth_engine_local::{LocalMiner, LocalPayloadAttributesBuilder};
 use reth_engine_service::service::{ChainEvent, EngineService};
 use reth_engine_tree::{
     engine::{EngineApiRequest, EngineRequestHandler},
+    persistence::PersistenceHandle,
     tree::TreeConfig,
 };
 use reth_engine_util::EngineMessageStreamExt;
@@ -23,23 +27,32 @@ use reth_exex::ExExManagerHandle;
 use reth_network::{types::BlockRangeUpdate, NetworkSyncUpdater, SyncState};
 use reth_network_api::BlockDownloaderProvider;
 use reth_node_api::{
-    BeaconConsensusEngineHandle, BuiltPayload, FullNodeTypes, NodeTypes, NodeTypesWithDBAdapter,
-    PayloadAttributesBuilder, PayloadTypes,
+    BeaconConsensusEngineHandle, BlockBody, BuiltPayload, FullNodeTypes, NodePrimitives, NodeTypes,
+    NodeTypesWithDBAdapter, PayloadAttributesBuilder, PayloadTypes,
 };
 use reth_node_core::{
     dirs::{ChainPath, DataDirPath},
     exit::NodeExitFuture,
     primitives::Head,
 };
-use reth_node_events::node;
+use reth_node_events::{
+    node,
+    webhook::{self, WebhookConfig},
+};
+use reth_primitives_traits::SignedTransaction;
 use reth_provider::{
-    providers::{BlockchainProvider, NodeTypesForProvider},
-    BlockNumReader,
+    providers::{BlobSidecarStore, BlockchainProvider, NodeTypesForProvider, ProviderNodeTypes},
+    BlobSidecarWriter, BlockNumReader, CanonStateNotificationStream, CanonStateSubscriptions,
+    ProviderError, ProviderFactory, ProviderResult, StaticFileProviderFactory, StaticFileWriter,
+    StoredBlobSidecars,
 };
-use reth_tasks::TaskExecutor;
+use reth_rpc_layer::JwtRejections;
+use reth_static_file_exchange::StaticFileExchangeClient;
+use reth_tasks::{governor::ThroughputGovernor, memory::MemoryBudget, TaskExecutor};
 use reth_tokio_util::EventSender;
-use reth_tracing::tracing::{debug, error, info};
-use std::sync::Arc;
+use reth_tracing::tracing::{debug, error, info, warn};
+use reth_transaction_pool::TransactionPool;
+use std::{sync::Arc, time::Duration};
 use tokio::sync::{mpsc::unbounded_channel, oneshot};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 
@@ -52,16 +65,32 @@ pub struct EngineNodeLauncher {
     /// Temporary configuration for engine tree.
     /// After engine is stabilized, this should be configured through node builder.
     pub engine_tree_config: TreeConfig,
+
+    /// The client of the static file exchange protocol, used to sync headers from chunks.
+    pub static_file_exchange: Option<StaticFileExchangeClient>,
 }
 
 impl EngineNodeLauncher {
     /// Create a new instance of the ethereum node launcher.
-    pub const fn new(
+    pub fn new(
         task_executor: TaskExecutor,
         data_dir: ChainPath<DataDirPath>,
         engine_tree_config: TreeConfig,
     ) -> Self {
-        Self { ctx: LaunchContext::new(task_executor, data_dir), engine_tree_config }
+        Self {
+            ctx: LaunchContext::new(task_executor, data_dir),
+            engine_tree_config,
+            static_file_exchange: None,
+        }
+    }
+
+    /// Syncs headers from the chunks served to the given static file exchange client.
+    ///
+    /// The client only learns about chunks once the static file exchange subprotocol is installed
+    /// and forwards its events to it.
+    pub fn with_static_file_exchange(mut self, client: StaticFileExchangeClient) -> Self {
+        self.static_file_exchange = Some(client);
+        self
     }
 }
 
@@ -87,7 +116,7 @@ where
         self,
         target: NodeBuilderWithComponents<T, CB, AO>,
     ) -> eyre::Result<Self::Node> {
-        let Self { ctx, engine_tree_config } = self;
+        let Self { ctx, engine_tree_config, static_file_exchange } = self;
         let NodeBuilderWithComponents {
             adapter: NodeTypesAdapter { database },
             components_builder,
@@ -95,6 +124,12 @@ where
             config,
         } = target;
         let NodeHooks { on_component_initialized, on_node_started, .. } = hooks;
+        let hot_state_cache_entries = config.engine.hot_state_cache_entries;
+        let blob_sidecar_store = config
+            .rpc
+            .rpc_blob_sidecars_retention
+            .map(|_| BlobSidecarStore::open(ctx.data_dir.blob_sidecars()))
+            .transpose()?;
 
         // setup the launch context
         let ctx = ctx
@@ -123,13 +158,72 @@ where
             .with_metrics_task()
             // passing FullNodeTypes as type parameter here so that we can build
             // later the components.
-            .with_blockchain_db::<T, _>(move |provider_factory| {
-                Ok(BlockchainProvider::new(provider_factory)?)
+            .with_blockchain_db::<T, _>({
+                let blob_sidecar_store = blob_sidecar_store.clone();
+                move |provider_factory| {
+                    let mut provider = BlockchainProvider::new(provider_factory)?;
+                    if let Some(store) = blob_sidecar_store {
+                        provider = provider.with_blob_sidecar_store(store);
+                    }
+                    if hot_state_cache_entries == 0 {
+                        return Ok(provider)
+                    }
+                    Ok(provider.with_hot_state_cache(hot_state_cache_entries))
+                }
             })?
             .with_components(components_builder, on_component_initialized).await?;
 
-        // Try to expire pre-merge transaction history if configured
-        ctx.expire_pre_merge_transactions()?;
+        if ctx.is_safe_mode() {
+            let storage_damage = ctx.provider_factory().storage_damage().copied();
+            warn!(target: "reth::cli", ?storage_damage, "Starting in safe mode, sync and pruning are disabled");
+
+            // There is no consensus engine in safe mode, so engine API requests fail right away.
+            let (consensus_engine_tx, _) = unbounded_channel();
+            let add_ons_ctx = AddOnsContext {
+                node: ctx.node_adapter().clone(),
+                config: ctx.node_config(),
+                toml_config: ctx.toml_config(),
+                beacon_engine_handle: BeaconConsensusEngineHandle::new(consensus_engine_tx),
+                jwt_secret: ctx.auth_jwt_secret()?,
+                engine_events: EventSender::default(),
+                storage_damage,
+                memory_budget: ctx.spawn_memory_budget(),
+                jwt_rejections: JwtRejections::default(),
+                execution_governor: ThroughputGovernor::unlimited(),
+            };
+            let add_ons_handle = add_ons.launch_add_ons(add_ons_ctx).await?;
+
+            let rpc_server_handles = add_ons_handle.rpc_server_handles.clone();
+            ctx.shutdown_coordinator().spawn_on_shutdown(
+                ctx.task_executor(),
+                NodeComponent::Rpc,
+                move || rpc_server_handles.shutdown(),
+            );
+
+            let full_node = FullNode {
+                evm_config: ctx.components().evm_config().clone(),
+                pool: ctx.components().pool().clone(),
+                network: ctx.components().network().clone(),
+                provider: ctx.node_adapter().provider.clone(),
+                payload_builder_handle: ctx.components().payload_builder_handle().clone(),
+                task_executor: ctx.task_executor().clone(),
+                config: ctx.node_config().clone(),
+                data_dir: ctx.data_dir().clone(),
+                add_ons_handle,
+            };
+            on_node_started.on_event(FullNode::clone(&full_node))?;
+
+            return Ok(NodeHandle {
+                node_exit_future: NodeExitFuture::new(
+                    future::pending(),
+                    full_node.config.debug.terminate,
+                ),
+                node: full_node,
+            })
+        }
+
+        // Try to expire transaction history below the retention boundary if configured
+        let earliest_block = ctx.expire_history()?;
 
         // spawn exexs if any
         let maybe_exex_manager_handle = ctx.launch_exex(installed_exex).await?;
@@ -144,6 +238,14 @@ where
         // We always assume that node is syncing after a restart
         network_handle.update_sync_state(SyncState::Syncing);
 
+        // Advertise the earliest block we still serve history for
+        let head = ctx.head();
+        network_handle.update_block_range(BlockRangeUpdate {
+            earliest: earliest_block,
+            latest: head.number,
+            latest_hash: head.hash,
+        });
+
         let max_block = ctx.max_block(network_client.clone()).await?;
 
         let static_file_producer = ctx.static_file_producer();
@@ -152,6 +254,7 @@ where
 
         let consensus = Arc::new(ctx.components().consensus().clone());
 
+        let execution_governor = ctx.execution_governor();
         let pipeline = build_networked_pipeline(
             &ctx.toml_config().stages,
             network_client.clone(),
@@ -165,12 +268,17 @@ where
             ctx.components().evm_config().clone(),
             maybe_exex_manager_handle.clone().unwrap_or_else(ExExManagerHandle::empty),
             ctx.era_import_source(),
+            Some(ctx.data_dir().execution_pending_batch()),
+            Some(execution_governor.clone()),
+            ctx.components().recovery_backend().cloned(),
+            static_file_exchange,
         )?;
 
         // The new engine writes directly to static files. This ensures that they're up to the tip.
         pipeline.move_to_static_files()?;
 
         let pipeline_events = pipeline.events();
+        let pipeline_run_state = pipeline.run_state();
 
         let mut pruner_builder = ctx.pruner_builder();
         if let Some(exex_manager_handle) = &maybe_exex_manager_handle {
@@ -179,6 +287,8 @@ where
         }
         let pruner = pruner_builder.build_with_provider_factory(ctx.provider_factory().clone());
         let pruner_events = pruner.events();
+        let webhook_events =
+            ctx.node_config().webhook.url.is_some().then(|| (pipeline.events(), pruner.events()));
         info!(target: "reth::cli", prune_config=?ctx.prune_config().unwrap_or_default(), "Pruner initialized");
 
         let event_sender = EventSender::default();
@@ -187,13 +297,43 @@ where
 
         // extract the jwt secret from the args if possible
         let jwt_secret = ctx.auth_jwt_secret()?;
+        let jwt_rejections = JwtRejections::default();
+
+        let memory_budget = ctx.spawn_memory_budget();
+        track_memory_usage(
+            &memory_budget,
+            ctx.task_executor(),
+            ctx.components().pool().clone(),
+            ctx.blockchain_db().canonical_in_memory_state(),
+            engine_tree_config.cross_block_cache_size(),
+            ctx.node_config().memory.reclaim_interval,
+        );
+
+        if let (Some(store), Some(retention)) =
+            (blob_sidecar_store, ctx.node_config().rpc.rpc_blob_sidecars_retention)
+        {
+            ctx.task_executor().spawn_named(
+                "blob sidecar persistence",
+                persist_blob_sidecars(
+                    store,
+                    ctx.components().pool().clone(),
+                    ctx.blockchain_db().canonical_state_stream(),
+                    retention,
+                ),
+            );
+        }
 
         let add_ons_ctx = AddOnsContext {
             node: ctx.node_adapter().clone(),
             config: ctx.node_config(),
+            toml_config: ctx.toml_config(),
             beacon_engine_handle: beacon_engine_handle.clone(),
             jwt_secret,
             engine_events: event_sender.clone(),
+            storage_damage: None,
+            memory_budget,
+            jwt_rejections: jwt_rejections.clone(),
+            execution_governor,
         };
         let engine_payload_validator = add_ons.engine_validator(&add_ons_ctx).await?;
 
@@ -218,7 +358,8 @@ where
             network_client.clone(),
             Box::pin(consensus_engine_stream),
             pipeline,
-            Box::new(ctx.task_executor().clone()),
+            // the pipeline is stopped by the shutdown coordinator instead of the shutdown signal
+            Box::new(ComponentTaskSpawner::new(ctx.task_executor().clone())),
             ctx.provider_factory().clone(),
             ctx.blockchain_db().clone(),
             pruner,
@@ -229,6 +370,7 @@ where
             ctx.sync_metrics_tx(),
             ctx.components().evm_config().clone(),
         );
+        let persistence_handle = engine_service.persistence_handle().clone();
 
         if ctx.is_dev() {
             ctx.task_executor().spawn_critical(
@@ -249,7 +391,7 @@ where
         let events = stream_select!(
             event_sender.new_listener().map(Into::into),
             pipeline_events.map(Into::into),
-            ctx.consensus_layer_events(),
+            ctx.consensus_layer_events(jwt_rejections),
             pruner_events.map(Into::into),
             static_file_producer_events.map(Into::into),
         );
@@ -259,13 +401,98 @@ where
             node::handle_events(
                 Some(Box::new(ctx.components().network().clone())),
                 Some(ctx.head().number),
+                ctx.node_config().engine.block_reports_json,
                 events,
             ),
         );
 
+        if let (Some(url), Some((pipeline_events, pruner_events))) =
+            (&ctx.node_config().webhook.url, webhook_events)
+        {
+            let args = &ctx.node_config().webhook;
+            let config = WebhookConfig {
+                url: url.parse().map_err(|err| eyre::eyre!("Invalid webhook URL: {err}"))?,
+                secret: args
+                    .secret()
+                    .map_err(|err| eyre::eyre!("Failed to read webhook secret: {err}"))?,
+                reorg_depth: args.reorg_depth,
+                sync_stall: args.sync_stall,
+                finality_lag: args.finality_lag,
+                max_retries: args.max_retries,
+            };
+            info!(target: "reth::cli", url = %config.url.origin().ascii_serialization(), "Sending webhook notifications");
+            ctx.task_executor().spawn(webhook::notify_webhooks(
+                config,
+                ctx.blockchain_db().clone(),
+                ctx.task_executor().clone(),
+                ctx.blockchain_db().canonical_state_stream(),
+                pipeline_events,
+                pruner_events,
+            ));
+        }
+
         let RpcHandle { rpc_server_handles, rpc_registry, engine_events, beacon_engine_handle } =
             add_ons.launch_add_ons(add_ons_ctx).await?;
 
+        // Shut the components down in order once the node exits, the engine, the network and the
+        // exexes register themselves
+        let shutdown = ctx.shutdown_coordinator();
+        let handles = rpc_server_handles.clone();
+        shutdown
+            .spawn_on_shutdown(ctx.task_executor(), NodeComponent::Rpc, move || handles.shutdown());
+        // the running pipeline commits its current batch and returns
+        shutdown.spawn_on_shutdown(
+            ctx.task_executor(),
+            NodeComponent::Pipeline,
+            move || async move {
+                pipeline_run_state.request_stop();
+                pipeline_run_state.idle().await
+            },
+        );
+        let provider_factory = ctx.provider_factory().clone();
+        let in_memory_state = ctx.blockchain_db().canonical_in_memory_state();
+        shutdown.spawn_on_shutdown(
+            ctx.task_executor(),
+            NodeComponent::Persistence,
+            move || async move {
+                match persist_in_memory_blocks(
+                    &persistence_handle,
+                    &provider_factory,
+                    &in_memory_state,
+                )
+                .await
+                {
+                    Ok(Some(block)) => {
+                        info!(target: "reth::cli", ?block, "Persisted in-memory blocks")
+                    }
+                    Ok(None) => debug!(target: "reth::cli", "No in-memory blocks to persist"),
+                    Err(err) => {
+                        error!(target: "reth::cli", %err, "Failed to persist in-memory blocks")
+                    }
+                }
+                // dropping the last handle stops the persistence service
+                drop(persistence_handle);
+            },
+        );
+        let static_file_provider = ctx.provider_factory().static_file_provider();
+        shutdown.spawn_on_shutdown(
+            ctx.task_executor(),
+            NodeComponent::Database,
+            move || async move {
+                // database transactions are durable once committed, only the static file
+                // writers may hold unflushed data
+                let res = tokio::task::spawn_blocking(move || static_file_provider.commit()).await;
+                match res {
+                    Ok(Ok(())) => debug!(target: "reth::cli", "Flushed static files"),
+                    Ok(Err(err)) => {
+                        error!(target: "reth::cli", %err, "Failed to flush static files")
+                    }
+                    Err(err) => error!(target: "reth::cli", %err, "Failed to flush static files"),
+                }
+            },
+        );
+        let engine_shutdown = shutdown.on_shutdown(NodeComponent::Engine);
+
         // Run consensus engine to completion
         let initial_target = ctx.initial_backfill_target()?;
         let mut built_payloads = ctx
@@ -283,7 +510,11 @@ where
         let terminate_after_backfill = ctx.terminate_after_initial_backfill();
 
         info!(target: "reth::cli", "Starting consensus engine");
-        ctx.task_executor().spawn_critical("consensus engine", async move {
+        // the engine is stopped by the shutdown coordinator instead of the shutdown signal
+        ctx.task_executor().spawn_critical_with_shutdown_signal("consensus engine", |_| async move {
+            let mut engine_shutdown = engine_shutdown;
+            let mut shutdown_guard = None;
+
             if let Some(initial_target) = initial_target {
                 debug!(target: "reth::cli", %initial_target,  "start backfill sync");
                 engine_service.orchestrator_mut().start_backfill_sync(initial_target);
@@ -294,6 +525,11 @@ where
             // advance the chain and await payloads built locally to add into the engine api tree handler to prevent re-execution if that block is received as payload from the CL
             loop {
                 tokio::select! {
+                    guard = &mut engine_shutdown => {
+                        debug!(target: "reth::cli", "Shutting down consensus engine");
+                        shutdown_guard = Some(guard);
+                        break
+                    }
                     payload = built_payloads.select_next_some() => {
                         if let Some(executed_block) = payload.executed_block() {
                             debug!(target: "reth::cli", block=?executed_block.recovered_block().num_hash(),  "inserting built payload");
@@ -345,6 +581,9 @@ where
                 }
             }
 
+            // dropping the service closes the channels of the engine tree and persistence tasks
+            drop(engine_service);
+            drop(shutdown_guard);
             let _ = exit.send(res);
         });
 
@@ -378,3 +617,148 @@ where
         Ok(handle)
     }
 }
+
+/// Persists the blocks of the in-memory canonical chain that are not on disk yet, and returns the
+/// last persisted block.
+///
+/// Waits for the actions the engine already sent to the persistence service first, so that no
+/// block is saved twice.
+async fn persist_in_memory_blocks<N: ProviderNodeTypes>(
+    persistence: &PersistenceHandle<N::Primitives>,
+    provider_factory: &ProviderFactory<N>,
+    in_memory_state: &CanonicalInMemoryState<N::Primitives>,
+) -> eyre::Result<Option<BlockNumHash>> {
+    // saving no blocks resolves once the actions queued before are done
+    let (tx, rx) = oneshot::channel();
+    persistence
+        .save_blocks(Vec::new(), tx)
+        .map_err(|_| eyre::eyre!("Persistence service stopped"))?;
+    rx.await?;
+
+    let last_persisted = provider_factory.provider()?.last_block_number()?;
+    // the canonical chain is iterated from the head
+    let mut blocks = in_memory_state
+        .canonical_chain()
+        .take_while(|block| block.number() > last_persisted)
+        .map(|block| block.block())
+        .collect::<Vec<_>>();
+    if blocks.is_empty() {
+        return Ok(None)
+    }
+    blocks.reverse();
+
+    let (tx, rx) = oneshot::channel();
+    persistence.save_blocks(blocks, tx).map_err(|_| eyre::eyre!("Persistence service stopped"))?;
+    Ok(rx.await?)
+}
+
+/// Registers the transaction pool, the in-memory canonical chain and the engine's cross-block cache
+/// with the memory budget.
+///
+/// These components enforce their own limits and don't release memory on request, so they are
+/// only tracked. The cross-block cache is tracked at its configured size.
+fn track_memory_usage<Pool, N>(
+    budget: &MemoryBudget,
+    task_executor: &TaskExecutor,
+    pool: Pool,
+    in_memory_state: CanonicalInMemoryState<N>,
+    cross_block_cache_size: u64,
+    interval: Duration,
+) where
+    Pool: TransactionPool + 'static,
+    N: NodePrimitives,
+{
+    let pool_consumer = budget.register("transaction_pool", 0, |_| 0);
+    task_executor.spawn_named(
+        "transaction pool memory",
+        pool_consumer.report_task(interval, move || {
+            let size = pool.pool_size();
+            size.pending_size + size.basefee_size + size.queued_size + size.blob_size
+        }),
+    );
+
+    let in_memory_consumer = budget.register("in_memory_blocks", 0, |_| 0);
+    task_executor.spawn_named(
+        "in-memory blocks memory",
+        in_memory_consumer.report_task(interval, move || in_memory_state.memory_usage()),
+    );
+
+    let cross_block_cache_consumer = budget.register("engine_cross_block_cache", 0, |_| 0);
+    task_executor.spawn_named(
+        "engine cross-block cache memory",
+        cross_block_cache_consumer.report_task(interval, move || cross_block_cache_size as usize),
+    );
+}
+
+/// Persists the blob sidecars of newly canonical blocks and prunes the ones of blocks older than
+/// the retention window.
+///
+/// Sidecars are taken from the transaction pool's blob store, which keeps the sidecars of included
+/// transactions until their block is finalized. Sidecars of reorged out blocks are removed, and
+/// sidecars that are no longer in the pool are recorded as missing.
+async fn persist_blob_sidecars<N, Pool>(
+    store: BlobSidecarStore,
+    pool: Pool,
+    mut canonical_state: CanonStateNotificationStream<N>,
+    retention: u64,
+) where
+    N: NodePrimitives,
+    Pool: TransactionPool + 'static,
+{
+    while let Some(notification) = canonical_state.next().await {
+        let store = store.clone();
+        let pool = pool.clone();
+        let res = tokio::task::spawn_blocking(move || -> ProviderResult<()> {
+            if let Some(reverted) = notification.reverted() {
+                for block in reverted.blocks_iter() {
+                    store.remove_blob_sidecars(block.number())?;
+                }
+            }
+
+            let committed = notification.committed();
+            for block in committed.blocks_iter() {
+                let hashes = block
+                    .body()
+                    .transactions_iter()
+                    .filter(|tx| tx.is_eip4844())
+                    .map(|tx| *tx.tx_hash())
+                    .collect::<Vec<_>>();
+                if hashes.is_empty() {
+                    store.remove_blob_sidecars(block.number())?;
+                    continue
+                }
+
+                let sidecars = pool
+                    .get_all_blobs(hashes.clone())
+                    .map_err(ProviderError::other)?
+                    .into_iter()
+                    .map(|(tx_hash, sidecar)| (tx_hash, Arc::unwrap_or_clone(sidecar)))
+                    .collect::<Vec<_>>();
+                let missing = hashes
+                    .into_iter()
+                    .filter(|hash| !sidecars.iter().any(|(tx_hash, _)| tx_hash == hash))
+                    .collect::<Vec<_>>();
+                if !missing.is_empty() {
+                    warn!(
+                        target: "reth::cli",
+                        block = block.number(),
+                        missing = missing.len(),
+                        "Blob sidecars of canonical block are not available"
+                    );
+                }
+
+                store.insert_blob_sidecars(
+                    block.number(),
+                    &StoredBlobSidecars { block_hash: block.hash(), sidecars, missing },
+                )?;
+            }
+            store.prune_blob_sidecars(committed.tip().number().saturating_sub(retention) + 1)
+        })
+        .await;
+        match res {
+            Ok(Ok(())) => {}
+            Ok(Err(err)) => warn!(target: "reth::cli", %err, "Failed to persist blob sidecars"),
+            Err(err) => error!(target: "reth::cli", %err, "Blob sidecar persistence task panicked"),
+        }
+    }
+}
@@ -1,7 +1,7 @@
 //! Support for launching execution extensions.
 
 use alloy_eips::{eip2124::Head, BlockNumHash};
-use futures::future;
+use futures::{future, StreamExt};
 use reth_chain_state::ForkChoiceSubscriptions;
 use reth_chainspec::EthChainSpec;
 use reth_exex::{
@@ -10,11 +10,16 @@ use reth_exex::{
 };
 use reth_node_api::{FullNodeComponents, NodeTypes, PrimitivesTy};
 use reth_provider::CanonStateSubscriptions;
+use reth_tasks::registry::set_task_label;
 use reth_tracing::tracing::{debug, info};
 use std::{fmt, fmt::Debug};
 use tracing::Instrument;
 
-use crate::{common::WithConfigs, exex::BoxedLaunchExEx};
+use crate::{
+    common::WithConfigs,
+    exex::BoxedLaunchExEx,
+    shutdown::{NodeComponent, ShutdownCoordinator},
+};
 
 /// Can launch execution extensions.
 pub struct ExExLauncher<Node: FullNodeComponents> {
@@ -22,17 +27,21 @@ pub struct ExExLauncher<Node: FullNodeComponents> {
     extensions: Vec<(String, Box<dyn BoxedLaunchExEx<Node>>)>,
     components: Node,
     config_container: WithConfigs<<Node::Types as NodeTypes>::ChainSpec>,
+    shutdown: ShutdownCoordinator,
 }
 
 impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
     /// Create a new `ExExLauncher` with the given extensions.
+    ///
+    /// The extensions and the `ExEx` manager are stopped by the given [`ShutdownCoordinator`].
     pub const fn new(
         head: Head,
         components: Node,
         extensions: Vec<(String, Box<dyn BoxedLaunchExEx<Node>>)>,
         config_container: WithConfigs<<Node::Types as NodeTypes>::ChainSpec>,
+        shutdown: ShutdownCoordinator,
     ) -> Self {
-        Self { head, extensions, components, config_container }
+        Self { head, extensions, components, config_container, shutdown }
     }
 
     /// Launches all execution extensions.
@@ -42,7 +51,7 @@ impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
     pub async fn launch(
         self,
     ) -> eyre::Result<Option<ExExManagerHandle<PrimitivesTy<Node::Types>>>> {
-        let Self { head, extensions, components, config_container } = self;
+        let Self { head, extensions, components, config_container, shutdown } = self;
         let head = BlockNumHash::new(head.number, head.hash);
 
         if extensions.is_empty() {
@@ -85,6 +94,7 @@ impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
             };
 
             let executor = components.task_executor().clone();
+            let shutdown = shutdown.clone();
             exexes.push(async move {
                 debug!(target: "reth::cli", id, "spawning exex");
                 let span = reth_tracing::tracing::info_span!("exex", id);
@@ -92,10 +102,13 @@ impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
                 // init the exex
                 let exex = exex.launch(context).instrument(span.clone()).await.unwrap();
 
-                // spawn it as a crit task
-                executor.spawn_critical(
+                // spawn it as a crit task that runs until the exexes are shut down
+                shutdown.spawn_critical_until_shutdown(
+                    &executor,
+                    NodeComponent::ExEx,
                     "exex",
                     async move {
+                        set_task_label(format_args!("exex {id}"));
                         info!(target: "reth::cli", "ExEx started");
                         match exex.await {
                             Ok(_) => panic!("ExEx {id} finished. ExExes should run indefinitely"),
@@ -119,25 +132,60 @@ impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
             components.provider().finalized_block_stream(),
         );
         let exex_manager_handle = exex_manager.handle();
-        components.task_executor().spawn_critical("exex manager", async move {
-            exex_manager.await.expect("exex manager crashed");
-        });
-
-        // send notifications from the blockchain tree to exex manager
-        let mut canon_state_notifications = components.provider().subscribe_to_canonical_state();
-        let mut handle = exex_manager_handle.clone();
-        components.task_executor().spawn_critical(
-            "exex manager blockchain tree notifications",
+        shutdown.spawn_critical_until_shutdown(
+            components.task_executor(),
+            NodeComponent::ExEx,
+            "exex manager",
             async move {
-                while let Ok(notification) = canon_state_notifications.recv().await {
-                    handle
-                        .send_async(ExExNotificationSource::BlockchainTree, notification.into())
-                        .await
-                        .expect("blockchain tree notification could not be sent to exex manager");
-                }
+                exex_manager.await.expect("exex manager crashed");
             },
         );
 
+        // send notifications from the blockchain tree to exex manager
+        let mut handle = exex_manager_handle.clone();
+        let max_compacted_blocks = config_container.toml_config.exex.max_compacted_blocks;
+        if max_compacted_blocks > 0 {
+            // bursts of commits that are buffered while the manager is busy are merged
+            let mut canon_state_notifications =
+                components.provider().compact_canonical_state_stream(max_compacted_blocks);
+            shutdown.spawn_critical_until_shutdown(
+                components.task_executor(),
+                NodeComponent::ExEx,
+                "exex manager blockchain tree notifications",
+                async move {
+                    while let Some(batch) = canon_state_notifications.next().await {
+                        handle
+                            .send_async(
+                                ExExNotificationSource::BlockchainTree,
+                                batch.into_merged().into(),
+                            )
+                            .await
+                            .expect(
+                                "blockchain tree notification could not be sent to exex manager",
+                            );
+                    }
+                },
+            );
+        } else {
+            let mut canon_state_notifications =
+                components.provider().subscribe_to_canonical_state();
+            shutdown.spawn_critical_until_shutdown(
+                components.task_executor(),
+                NodeComponent::ExEx,
+                "exex manager blockchain tree notifications",
+                async move {
+                    while let Ok(notification) = canon_state_notifications.recv().await {
+                        handle
+                            .send_async(ExExNotificationSource::BlockchainTree, notification.into())
+                            .await
+                            .expect(
+                                "blockchain tree notification could not be sent to exex manager",
+                            );
+                    }
+                },
+            );
+        }
+
         info!(target: "reth::cli", "ExEx Manager started");
 
         Ok(Some(exex_manager_handle))
@@ -151,6 +199,7 @@ impl<Node: FullNodeComponents> Debug for ExExLauncher<Node> {
             .field("extensions", &self.extensions.iter().map(|(id, _)| id).collect::<Vec<_>>())
             .field("components", &"...")
             .field("config_container", &self.config_container)
+            .field("shutdown", &self.shutdown)
             .finish()
     }
 }
@@ -43,6 +43,8 @@ pub mod rpc;
 
 pub mod setup;
 
+pub mod shutdown;
+
 /// Type aliases for traits that are often used together
 pub mod aliases;
 pub use aliases::*;
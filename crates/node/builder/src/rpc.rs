@@ -1,39 +1,60 @@
 //! Builder support for rpc components.
 
 pub use jsonrpsee::server::middleware::rpc::{RpcService, RpcServiceBuilder};
-pub use reth_rpc_builder::{middleware::RethRpcMiddleware, Identity};
+pub use reth_rpc_builder::{
+    middleware::{HttpMiddleware, RethHttpMiddleware, RethRpcMiddleware},
+    Identity,
+};
 
 use crate::{BeaconConsensusEngineEvent, BeaconConsensusEngineHandle};
 use alloy_rpc_types::engine::ClientVersionV1;
 use alloy_rpc_types_engine::ExecutionData;
 use jsonrpsee::{core::middleware::layer::Either, RpcModule};
 use reth_chain_state::CanonStateSubscriptions;
-use reth_chainspec::{ChainSpecProvider, EthereumHardforks};
+use reth_chainspec::{ChainSpecProvider, EthChainSpec, EthereumHardforks};
+use reth_network_api::EthVersion;
 use reth_node_api::{
     AddOnsContext, BlockTy, EngineTypes, EngineValidator, FullNodeComponents, FullNodeTypes,
     NodeAddOns, NodeTypes, PayloadTypes, ReceiptTy,
 };
 use reth_node_core::{
     node_config::NodeConfig,
-    version::{CARGO_PKG_VERSION, CLIENT_CODE, NAME_CLIENT, VERGEN_GIT_SHA},
+    version::{
+        BUILD_PROFILE_NAME, CARGO_PKG_VERSION, CLIENT_CODE, NAME_CLIENT, SHORT_VERSION,
+        VERGEN_BUILD_TIMESTAMP, VERGEN_CARGO_FEATURES, VERGEN_CARGO_TARGET_TRIPLE, VERGEN_GIT_SHA,
+        VERGEN_GIT_SHA_LONG,
+    },
 };
 use reth_payload_builder::{PayloadBuilderHandle, PayloadStore};
-use reth_rpc::eth::{EthApiTypes, FullEthApiServer};
-use reth_rpc_api::{eth::helpers::AddDevSigners, IntoEngineApiRpcModule};
+use reth_rpc::{
+    eth::{EthApiTypes, FullEthApiServer},
+    redact_args, ExecutionGovernorApi, LogFilterApi, NodeConfigApi, PayloadTimingsApi, SafeModeApi,
+    TaskDumpApi,
+};
+use reth_rpc_api::{
+    eth::helpers::AddDevSigners, AdminExecutionGovernorApiServer, DebugLogFilterApiServer,
+    DebugPayloadTimingsApiServer, DebugTaskDumpApiServer, IntoEngineApiRpcModule,
+    NodeConfigApiServer, NodeConfigInfo, RepairStatus, SafeModeApiServer,
+};
 use reth_rpc_builder::{
     auth::{AuthRpcModule, AuthServerHandle},
     config::RethRpcServerConfig,
-    RpcModuleBuilder, RpcRegistryInner, RpcServerConfig, RpcServerHandle, Stack,
+    engine_record::EngineApiRecorder,
+    RethRpcModule, RpcModuleBuilder, RpcRegistryInner, RpcServerConfig, RpcServerHandle, Stack,
     TransportRpcModules,
 };
 use reth_rpc_engine_api::{capabilities::EngineCapabilities, EngineApi};
+use reth_rpc_eth_api::helpers::LoadFee;
 use reth_rpc_eth_types::{cache::cache_new_blocks_task, EthConfig, EthStateCache};
 use reth_tokio_util::EventSender;
 use reth_tracing::tracing::{debug, info};
 use std::{
+    collections::BTreeMap,
     fmt::{self, Debug},
     future::Future,
+    net::SocketAddr,
     ops::{Deref, DerefMut},
+    sync::Arc,
 };
 
 /// Contains the handles to the spawned RPC servers.
@@ -47,6 +68,18 @@ pub struct RethRpcServerHandles {
     pub auth: AuthServerHandle,
 }
 
+impl RethRpcServerHandles {
+    /// Stops the servers and waits until they stopped.
+    pub async fn shutdown(self) {
+        let Self { rpc, auth } = self;
+        // the servers may have been stopped already
+        let _ = rpc.clone().stop();
+        let _ = auth.clone().stop();
+        rpc.stopped().await;
+        auth.stopped().await;
+    }
+}
+
 /// Contains hooks that are called during the rpc setup.
 pub struct RpcHooks<Node: FullNodeComponents, EthApi> {
     /// Hooks to run once RPC server is running.
@@ -436,6 +469,11 @@ pub struct RpcAddOns<
     /// This middleware is applied to all RPC requests across all transports (HTTP, WS, IPC).
     /// See [`RpcAddOns::with_rpc_middleware`] for more details.
     rpc_middleware: RpcMiddleware,
+    /// Configurable Http middleware.
+    ///
+    /// This middleware is applied to the Http requests of the HTTP and WS transports.
+    /// See [`RpcAddOns::layer_http_middleware`] for more details.
+    http_middleware: HttpMiddleware,
 }
 
 impl<Node, EthB, EV, EB, RpcMiddleware> Debug for RpcAddOns<Node, EthB, EV, EB, RpcMiddleware>
@@ -452,6 +490,7 @@ where
             .field("engine_validator_builder", &self.engine_validator_builder)
             .field("engine_api_builder", &self.engine_api_builder)
             .field("rpc_middleware", &"...")
+            .field("http_middleware", &self.http_middleware)
             .finish()
     }
 }
@@ -474,6 +513,7 @@ where
             engine_validator_builder,
             engine_api_builder,
             rpc_middleware,
+            http_middleware: HttpMiddleware::default(),
         }
     }
 
@@ -482,13 +522,21 @@ where
         self,
         engine_api_builder: T,
     ) -> RpcAddOns<Node, EthB, EV, T, RpcMiddleware> {
-        let Self { hooks, eth_api_builder, engine_validator_builder, rpc_middleware, .. } = self;
+        let Self {
+            hooks,
+            eth_api_builder,
+            engine_validator_builder,
+            rpc_middleware,
+            http_middleware,
+            ..
+        } = self;
         RpcAddOns {
             hooks,
             eth_api_builder,
             engine_validator_builder,
             engine_api_builder,
             rpc_middleware,
+            http_middleware,
         }
     }
 
@@ -497,13 +545,21 @@ where
         self,
         engine_validator_builder: T,
     ) -> RpcAddOns<Node, EthB, T, EB, RpcMiddleware> {
-        let Self { hooks, eth_api_builder, engine_api_builder, rpc_middleware, .. } = self;
+        let Self {
+            hooks,
+            eth_api_builder,
+            engine_api_builder,
+            rpc_middleware,
+            http_middleware,
+            ..
+        } = self;
         RpcAddOns {
             hooks,
             eth_api_builder,
             engine_validator_builder,
             engine_api_builder,
             rpc_middleware,
+            http_middleware,
         }
     }
 
@@ -546,14 +602,21 @@ where
     /// - The default middleware is `Identity` (no-op), which passes through requests unchanged
     /// - Middleware layers are applied in the order they are added via `.layer()`
     pub fn with_rpc_middleware<T>(self, rpc_middleware: T) -> RpcAddOns<Node, EthB, EV, EB, T> {
-        let Self { hooks, eth_api_builder, engine_validator_builder, engine_api_builder, .. } =
-            self;
+        let Self {
+            hooks,
+            eth_api_builder,
+            engine_validator_builder,
+            engine_api_builder,
+            http_middleware,
+            ..
+        } = self;
         RpcAddOns {
             hooks,
             eth_api_builder,
             engine_validator_builder,
             engine_api_builder,
             rpc_middleware,
+            http_middleware,
         }
     }
 
@@ -568,6 +631,7 @@ where
             engine_validator_builder,
             engine_api_builder,
             rpc_middleware,
+            http_middleware,
         } = self;
         let rpc_middleware = Stack::new(rpc_middleware, layer);
         RpcAddOns {
@@ -576,6 +640,7 @@ where
             engine_validator_builder,
             engine_api_builder,
             rpc_middleware,
+            http_middleware,
         }
     }
 
@@ -588,6 +653,32 @@ where
         self.layer_rpc_middleware(layer)
     }
 
+    /// Adds a new Http middleware layer `T` around the HTTP and WS transports of the RPC server.
+    ///
+    /// Unlike [`RpcAddOns::with_rpc_middleware`], which is applied to the individual RPC calls,
+    /// these layers operate on the raw Http requests and responses. This allows e.g. checking an
+    /// `Authorization` header or mutating request headers before the request is handled by the
+    /// RPC server.
+    ///
+    /// Layers are applied in the order they are added, the first layer being the outermost. All
+    /// layers run after the builtin CORS and JWT layers.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use reth_rpc_layer::{AuthLayer, JwtAuthValidator};
+    ///
+    /// let with_auth =
+    ///     rpc_addons.layer_http_middleware(AuthLayer::new(JwtAuthValidator::new(secret)));
+    /// ```
+    pub fn layer_http_middleware<T>(mut self, layer: T) -> Self
+    where
+        T: RethHttpMiddleware,
+    {
+        self.http_middleware = self.http_middleware.layer(layer);
+        self
+    }
+
     /// Sets the hook that is run once the rpc server is started.
     pub fn on_rpc_started<F>(mut self, hook: F) -> Self
     where
@@ -644,6 +735,7 @@ where
         F: FnOnce(RpcModuleContainer<'_, N, EthB::EthApi>) -> eyre::Result<()>,
     {
         let rpc_middleware = self.rpc_middleware.clone();
+        let http_middleware = self.http_middleware.clone();
         let setup_ctx = self.setup_rpc_components(ctx, ext).await?;
         let RpcSetupContext {
             node,
@@ -657,7 +749,11 @@ where
             engine_handle,
         } = setup_ctx;
 
-        let server_config = config.rpc.rpc_server_config().set_rpc_middleware(rpc_middleware);
+        let server_config = config
+            .rpc
+            .rpc_server_config()
+            .with_http_middleware(http_middleware)
+            .set_rpc_middleware(rpc_middleware);
         let rpc_server_handle = Self::launch_rpc_server_internal(server_config, &modules).await?;
 
         let handles =
@@ -691,6 +787,7 @@ where
         F: FnOnce(RpcModuleContainer<'_, N, EthB::EthApi>) -> eyre::Result<()>,
     {
         let rpc_middleware = self.rpc_middleware.clone();
+        let http_middleware = self.http_middleware.clone();
         let setup_ctx = self.setup_rpc_components(ctx, ext).await?;
         let RpcSetupContext {
             node,
@@ -704,7 +801,11 @@ where
             engine_handle,
         } = setup_ctx;
 
-        let server_config = config.rpc.rpc_server_config().set_rpc_middleware(rpc_middleware);
+        let server_config = config
+            .rpc
+            .rpc_server_config()
+            .with_http_middleware(http_middleware)
+            .set_rpc_middleware(rpc_middleware);
         let auth_module_clone = auth_module.clone();
 
         // launch servers concurrently
@@ -746,7 +847,18 @@ where
         let Self { eth_api_builder, engine_api_builder, hooks, .. } = self;
 
         let engine_api = engine_api_builder.build_engine_api(&ctx).await?;
-        let AddOnsContext { node, config, beacon_engine_handle, jwt_secret, engine_events } = ctx;
+        let AddOnsContext {
+            node,
+            config,
+            toml_config,
+            beacon_engine_handle,
+            jwt_secret,
+            engine_events,
+            storage_damage,
+            memory_budget,
+            jwt_rejections,
+            execution_governor,
+        } = ctx;
 
         info!(target: "reth::cli", "Engine API handler initialized");
 
@@ -755,6 +867,7 @@ where
             config.rpc.eth_config().cache,
             node.task_executor().clone(),
         );
+        cache.register_memory_budget(&memory_budget, 1);
 
         let new_canonical_blocks = node.provider().canonical_state_stream();
         let c = cache.clone();
@@ -767,9 +880,26 @@ where
 
         let ctx = EthApiCtx { components: &node, config: config.rpc.eth_config(), cache };
         let eth_api = eth_api_builder.build_eth_api(ctx).await?;
+        eth_api.fee_history_cache().register_memory_budget(&memory_budget, 1);
 
-        let auth_config = config.rpc.auth_server_config(jwt_secret)?;
-        let module_config = config.rpc.transport_rpc_module_config();
+        let mut auth_config =
+            config.rpc.auth_server_config(jwt_secret)?.with_jwt_rejections(jwt_rejections);
+        if let Some(path) = &config.debug.engine_api_record {
+            info!(target: "reth::cli", ?path, "Recording engine API calls");
+            auth_config = auth_config.with_engine_api_recorder(EngineApiRecorder::open(path)?);
+        }
+        let mut module_config = config.rpc.transport_rpc_module_config();
+        if let Some(abi_registry) = config.rpc.abi_registry()? {
+            info!(target: "reth::cli", events = abi_registry.len(), "Loaded ABI registry");
+            if let Some(config) = module_config.config_mut() {
+                *config.abi_registry_mut() = Some(Arc::new(abi_registry));
+            }
+        }
+        if config.rpc.rpc_persist_filters {
+            if let Some(module_config) = module_config.config_mut() {
+                *module_config.filter_persistence_path_mut() = Some(config.datadir().eth_filters());
+            }
+        }
         debug!(target: "reth::cli", http=?module_config.http(), ws=?module_config.ws(), "Using RPC module config");
 
         let (mut modules, mut auth_module, registry) = RpcModuleBuilder::default()
@@ -786,6 +916,35 @@ where
             registry.eth_api().with_dev_accounts();
         }
 
+        // in safe mode we expose the storage repair status
+        if config.safe_mode {
+            let status = storage_damage.map_or_else(RepairStatus::healthy, |damage| {
+                RepairStatus::degraded(damage.consistent_tip, damage.highest_block)
+            });
+            modules.merge_configured(SafeModeApi::new(status).into_rpc())?;
+        }
+
+        modules.merge_if_module_configured(
+            RethRpcModule::Reth,
+            NodeConfigApi::new(node_config_info(config, toml_config)?).into_rpc(),
+        )?;
+
+        modules.merge_if_module_configured(
+            RethRpcModule::Admin,
+            ExecutionGovernorApi::new(execution_governor).into_rpc(),
+        )?;
+
+        // payload build timings are served alongside the other `debug` endpoints
+        modules.merge_if_module_configured(
+            RethRpcModule::Debug,
+            PayloadTimingsApi::new(node.payload_builder_handle().clone()).into_rpc(),
+        )?;
+        modules.merge_if_module_configured(
+            RethRpcModule::Debug,
+            TaskDumpApi::new(node.task_executor().clone()).into_rpc(),
+        )?;
+        modules.merge_if_module_configured(RethRpcModule::Debug, LogFilterApi::new().into_rpc())?;
+
         let mut registry = RpcRegistry { registry };
         let ctx = RpcContext {
             node: node.clone(),
@@ -1107,3 +1266,65 @@ impl IntoEngineApiRpcModule for NoopEngineApi {
         RpcModule::new(())
     }
 }
+
+/// Collects the effective configuration of the node that is served by `reth_nodeConfig`.
+fn node_config_info<ChainSpec: EthChainSpec>(
+    config: &NodeConfig<ChainSpec>,
+    toml_config: &reth_config::Config,
+) -> eyre::Result<NodeConfigInfo> {
+    let eth_protocols = EthVersion::ALL_VERSIONS
+        .iter()
+        .map(|version| <&str>::from(*version))
+        .collect::<Vec<_>>()
+        .join(",");
+    let versions = [
+        ("client", SHORT_VERSION),
+        ("commit", VERGEN_GIT_SHA_LONG),
+        ("buildTimestamp", VERGEN_BUILD_TIMESTAMP),
+        ("buildProfile", BUILD_PROFILE_NAME),
+        ("target", VERGEN_CARGO_TARGET_TRIPLE),
+        ("ethProtocols", &eth_protocols),
+    ]
+    .into_iter()
+    .map(|(name, version)| (name.to_string(), version.to_string()))
+    .collect::<BTreeMap<_, _>>();
+
+    let rpc = &config.rpc;
+    let network = &config.network;
+    let node = serde_json::json!({
+        "chain": config.chain.chain().to_string(),
+        "datadir": config.datadir().data_dir(),
+        "dev": config.dev.dev,
+        "fullNode": config.pruning.full,
+        "safeMode": config.safe_mode,
+        "rpc": {
+            "http": rpc.http.then(|| SocketAddr::new(rpc.http_addr, rpc.http_port)),
+            "httpApi": rpc.http_api.as_ref().map(ToString::to_string),
+            "ws": rpc.ws.then(|| SocketAddr::new(rpc.ws_addr, rpc.ws_port)),
+            "wsApi": rpc.ws_api.as_ref().map(ToString::to_string),
+            "ipc": (!rpc.ipcdisable).then_some(&rpc.ipcpath),
+            "authrpc": SocketAddr::new(rpc.auth_addr, rpc.auth_port),
+            "eth": rpc.eth_config(),
+        },
+        "network": {
+            "listener": SocketAddr::new(network.addr, network.port),
+            "discovery": !network.discovery.disable_discovery,
+            "maxOutboundPeers": network.max_outbound_peers,
+            "maxInboundPeers": network.max_inbound_peers,
+        },
+    });
+
+    Ok(NodeConfigInfo {
+        versions,
+        features: VERGEN_CARGO_FEATURES
+            .split(',')
+            .filter(|feature| !feature.is_empty())
+            .map(String::from)
+            .collect(),
+        args: redact_args(std::env::args()),
+        config: serde_json::json!({
+            "node": node,
+            "file": serde_json::to_value(toml_config)?,
+        }),
+    })
+}
@@ -1,6 +1,6 @@
 //! Helpers for setting up parts of the node.
 
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc};
 
 use crate::BlockTy;
 use alloy_primitives::{BlockNumber, B256};
@@ -8,7 +8,7 @@ use reth_config::{config::StageConfig, PruneConfig};
 use reth_consensus::{ConsensusError, FullConsensus};
 use reth_downloaders::{
     bodies::bodies::BodiesDownloaderBuilder,
-    headers::reverse_headers::ReverseHeadersDownloaderBuilder,
+    headers::{reverse_headers::ReverseHeadersDownloaderBuilder, task::TaskDownloader},
 };
 use reth_evm::ConfigureEvm;
 use reth_exex::ExExManagerHandle;
@@ -16,18 +16,23 @@ use reth_network_p2p::{
     bodies::downloader::BodyDownloader, headers::downloader::HeaderDownloader, BlockClient,
 };
 use reth_node_api::HeaderTy;
+use reth_primitives_traits::crypto::RecoveryBackend;
 use reth_provider::{providers::ProviderNodeTypes, ProviderFactory};
 use reth_stages::{
     prelude::DefaultStages,
-    stages::{EraImportSource, ExecutionStage},
+    stages::{EraImportSource, ExecutionPrewarm, ExecutionStage, SenderRecoveryStage},
     Pipeline, StageSet,
 };
 use reth_static_file::StaticFileProducer;
-use reth_tasks::TaskExecutor;
+use reth_static_file_exchange::{StaticFileExchangeClient, StaticFileHeaderDownloader};
+use reth_tasks::{governor::ThroughputGovernor, TaskExecutor};
 use reth_tracing::tracing::debug;
 use tokio::sync::watch;
 
 /// Constructs a [Pipeline] that's wired to the network
+///
+/// If a [`StaticFileExchangeClient`] is given, the headers chunks its peers serve are used to sync
+/// large header gaps.
 #[expect(clippy::too_many_arguments)]
 pub fn build_networked_pipeline<N, Client, Evm>(
     config: &StageConfig,
@@ -42,6 +47,10 @@ pub fn build_networked_pipeline<N, Client, Evm>(
     evm_config: Evm,
     exex_manager_handle: ExExManagerHandle<N::Primitives>,
     era_import_source: Option<EraImportSource>,
+    execution_pending_batch: Option<PathBuf>,
+    execution_governor: Option<ThroughputGovernor>,
+    recovery_backend: Option<Arc<dyn RecoveryBackend>>,
+    static_file_exchange: Option<StaticFileExchangeClient>,
 ) -> eyre::Result<Pipeline<N>>
 where
     N: ProviderNodeTypes,
@@ -50,8 +59,16 @@ where
 {
     // building network downloaders using the fetch client
     let header_downloader = ReverseHeadersDownloaderBuilder::new(config.headers)
-        .build(client.clone(), consensus.clone())
-        .into_task_with(task_executor);
+        .build(client.clone(), consensus.clone());
+    let header_downloader = match static_file_exchange {
+        Some(exchange) => {
+            let mut header_downloader =
+                StaticFileHeaderDownloader::new(header_downloader, exchange, consensus.clone());
+            header_downloader.set_batch_size(config.headers.commit_threshold as usize);
+            TaskDownloader::spawn_with(header_downloader, task_executor)
+        }
+        None => header_downloader.into_task_with(task_executor),
+    };
 
     let body_downloader = BodiesDownloaderBuilder::new(config.bodies)
         .build(client, consensus.clone(), provider_factory.clone())
@@ -70,6 +87,9 @@ where
         evm_config,
         exex_manager_handle,
         era_import_source,
+        execution_pending_batch,
+        execution_governor,
+        recovery_backend,
     )?;
 
     Ok(pipeline)
@@ -90,6 +110,9 @@ pub fn build_pipeline<N, H, B, Evm>(
     evm_config: Evm,
     exex_manager_handle: ExExManagerHandle<N::Primitives>,
     era_import_source: Option<EraImportSource>,
+    execution_pending_batch: Option<PathBuf>,
+    execution_governor: Option<ThroughputGovernor>,
+    recovery_backend: Option<Arc<dyn RecoveryBackend>>,
 ) -> eyre::Result<Pipeline<N>>
 where
     N: ProviderNodeTypes,
@@ -108,6 +131,35 @@ where
 
     let prune_modes = prune_config.map(|prune| prune.segments).unwrap_or_default();
 
+    let mut sender_recovery_stage = SenderRecoveryStage::new(stage_config.sender_recovery);
+    if let Some(backend) = recovery_backend {
+        sender_recovery_stage = sender_recovery_stage.with_backend(backend);
+    }
+
+    let mut execution_stage = ExecutionStage::new(
+        evm_config.clone(),
+        Arc::clone(&consensus),
+        stage_config.execution.into(),
+        stage_config.execution_external_clean_threshold(),
+        exex_manager_handle,
+    );
+    if stage_config.execution.prewarm.is_enabled() {
+        let factory = provider_factory.clone();
+        execution_stage = execution_stage.with_prewarm(ExecutionPrewarm::new(
+            move || factory.latest(),
+            stage_config.execution.prewarm,
+        ));
+    }
+    if let Some((path, save_interval)) =
+        execution_pending_batch.zip(stage_config.execution.pending_batch_save_interval)
+    {
+        execution_stage = execution_stage.with_pending_batch(path, save_interval);
+    }
+    if let Some(governor) = execution_governor {
+        builder = builder.with_governor(governor.clone());
+        execution_stage = execution_stage.with_governor(governor);
+    }
+
     let pipeline = builder
         .with_tip_sender(tip_tx)
         .with_metrics_tx(metrics_tx)
@@ -115,21 +167,16 @@ where
             DefaultStages::new(
                 provider_factory.clone(),
                 tip_rx,
-                Arc::clone(&consensus),
+                consensus,
                 header_downloader,
                 body_downloader,
-                evm_config.clone(),
+                evm_config,
                 stage_config.clone(),
                 prune_modes,
                 era_import_source,
             )
-            .set(ExecutionStage::new(
-                evm_config,
-                consensus,
-                stage_config.execution.into(),
-                stage_config.execution_external_clean_threshold(),
-                exex_manager_handle,
-            )),
+            .set(sender_recovery_stage)
+            .set(execution_stage),
         )
         .build(provider_factory, static_file_producer);
 
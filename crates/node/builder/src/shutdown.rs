@@ -0,0 +1,409 @@
+//! Ordered shutdown of the node components.
+//!
+//! On shutdown, the [`ShutdownCoordinator`] stops the components of the node one after another in
+//! dependency order, see [`NodeComponent`]. Each component is given a timeout to shut down, after
+//! which the coordinator moves on to the next one, so that a stuck component doesn't prevent the
+//! database from being flushed.
+
+use futures::future::{self, BoxFuture, Either};
+use reth_tasks::{
+    shutdown::{signal, Shutdown, Signal},
+    TaskExecutor, TaskSpawner,
+};
+use std::{
+    fmt,
+    future::Future,
+    pin::{pin, Pin},
+    sync::{Arc, Mutex},
+    task::{ready, Context, Poll},
+    time::{Duration, Instant},
+};
+use tokio::{sync::mpsc, task::JoinHandle};
+use tracing::{debug, info, warn};
+
+/// A component of the node that is shut down by the [`ShutdownCoordinator`].
+///
+/// Components are shut down in the order of the variants: the RPC servers stop accepting requests
+/// first, then the engine stops processing them, then the network disconnects the peers, then the
+/// running pipeline finishes its batch, then the execution extensions are stopped, then the blocks
+/// of the in-memory tree are persisted and finally the static files are flushed.
+///
+/// The sum of the default timeouts is the [`GRACEFUL_SHUTDOWN_TIMEOUT`] of the node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum NodeComponent {
+    /// The RPC and engine API servers.
+    Rpc,
+    /// The consensus engine.
+    Engine,
+    /// The p2p network.
+    Network,
+    /// The staged sync pipeline.
+    Pipeline,
+    /// The execution extensions and the `ExEx` manager.
+    ExEx,
+    /// The persistence service, which also runs the pruner.
+    Persistence,
+    /// The database and static files.
+    Database,
+}
+
+impl NodeComponent {
+    /// All components, in shutdown order.
+    pub const ALL: [Self; 7] = [
+        Self::Rpc,
+        Self::Engine,
+        Self::Network,
+        Self::Pipeline,
+        Self::ExEx,
+        Self::Persistence,
+        Self::Database,
+    ];
+
+    /// Returns the default duration the component is given to shut down.
+    pub const fn default_timeout(&self) -> Duration {
+        match self {
+            Self::Rpc | Self::Network => Duration::from_secs(5),
+            Self::ExEx | Self::Database => Duration::from_secs(10),
+            Self::Engine => Duration::from_secs(15),
+            Self::Pipeline => Duration::from_secs(30),
+            Self::Persistence => Duration::from_secs(45),
+        }
+    }
+}
+
+/// The time the node is given to shut down once it exits, the sum of the
+/// [`NodeComponent::default_timeout`]s.
+pub const GRACEFUL_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(120);
+
+impl fmt::Display for NodeComponent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Rpc => f.write_str("rpc"),
+            Self::Engine => f.write_str("engine"),
+            Self::Network => f.write_str("network"),
+            Self::Pipeline => f.write_str("pipeline"),
+            Self::ExEx => f.write_str("exex"),
+            Self::Persistence => f.write_str("persistence"),
+            Self::Database => f.write_str("database"),
+        }
+    }
+}
+
+/// Shuts the node components down in dependency order.
+///
+/// Components register with [`ShutdownCoordinator::on_shutdown`] instead of listening to the
+/// shutdown signal of the [`TaskExecutor`], and are only signaled once all components before them
+/// finished shutting down, or ran out of time.
+#[derive(Debug, Clone)]
+pub struct ShutdownCoordinator {
+    components: Arc<Mutex<Vec<ComponentState>>>,
+}
+
+impl ShutdownCoordinator {
+    /// Creates a new coordinator with the default timeouts.
+    pub fn new() -> Self {
+        let components = NodeComponent::ALL.map(ComponentState::new).into();
+        Self { components: Arc::new(Mutex::new(components)) }
+    }
+
+    /// Sets the duration the component is given to shut down.
+    pub fn set_timeout(&self, component: NodeComponent, timeout: Duration) {
+        self.with_component(component, |state| state.timeout = timeout);
+    }
+
+    /// Returns a future that resolves once it's the component's turn to shut down.
+    ///
+    /// The coordinator waits until the returned [`ComponentShutdownGuard`] is dropped, or the
+    /// timeout of the component elapsed, before shutting down the next component.
+    pub fn on_shutdown(&self, component: NodeComponent) -> ComponentShutdown {
+        self.with_component(component, |state| ComponentShutdown {
+            shutdown: state.on_shutdown.clone(),
+            guard: Some(ComponentShutdownGuard { _done_tx: state.done_tx.clone() }),
+        })
+    }
+
+    /// Spawns a task that runs the given shutdown routine once it's the component's turn to shut
+    /// down.
+    ///
+    /// Unlike tasks spawned by the [`TaskExecutor`], the task isn't cancelled by the shutdown
+    /// signal of the executor.
+    pub fn spawn_on_shutdown<F, Fut>(
+        &self,
+        executor: &TaskExecutor,
+        component: NodeComponent,
+        shutdown: F,
+    ) where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let on_shutdown = self.on_shutdown(component);
+        executor.spawn_with_signal(|_| async move {
+            let guard = on_shutdown.await;
+            shutdown().await;
+            drop(guard);
+        });
+    }
+
+    /// Spawns a critical task that runs until it's the component's turn to shut down.
+    ///
+    /// Unlike tasks spawned by the [`TaskExecutor`], the task isn't cancelled by the shutdown
+    /// signal of the executor. The future is dropped before the component is considered shut down.
+    pub fn spawn_critical_until_shutdown<F>(
+        &self,
+        executor: &TaskExecutor,
+        component: NodeComponent,
+        name: &'static str,
+        fut: F,
+    ) where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let on_shutdown = self.on_shutdown(component);
+        executor.spawn_critical_with_shutdown_signal(name, |_| async move {
+            let fut = pin!(fut);
+            if let Either::Left((guard, fut)) = future::select(on_shutdown, fut).await {
+                drop(fut);
+                drop(guard);
+            }
+        });
+    }
+
+    /// Spawns the coordinator, which shuts down the components once the shutdown signal of the
+    /// executor fires.
+    ///
+    /// The executor's graceful shutdown waits for the coordinator to finish.
+    pub fn spawn(&self, executor: &TaskExecutor) {
+        let this = self.clone();
+        executor.spawn_critical_with_graceful_shutdown_signal(
+            "shutdown coordinator",
+            |shutdown| async move {
+                let guard = shutdown.await;
+                this.shutdown().await;
+                drop(guard);
+            },
+        );
+    }
+
+    /// Shuts down the components in order.
+    pub async fn shutdown(&self) {
+        let started_at = Instant::now();
+        for component in NodeComponent::ALL {
+            let (signal, done_rx, timeout) = self.with_component(component, |state| {
+                // drop the coordinator's sender, so that the channel closes once all guards of
+                // the component are dropped
+                state.done_tx = None;
+                (state.signal.take(), state.done_rx.take(), state.timeout)
+            });
+            let (Some(signal), Some(mut done_rx)) = (signal, done_rx) else {
+                debug!(target: "reth::cli", %component, "Component already shut down");
+                continue
+            };
+
+            info!(target: "reth::cli", %component, "Shutting down component");
+            let component_started_at = Instant::now();
+            signal.fire();
+            match tokio::time::timeout(timeout, done_rx.recv()).await {
+                Ok(_) => {
+                    info!(target: "reth::cli", %component, elapsed = ?component_started_at.elapsed(), "Component shut down");
+                }
+                Err(_) => {
+                    warn!(target: "reth::cli", %component, ?timeout, "Component did not shut down in time");
+                }
+            }
+        }
+        info!(target: "reth::cli", elapsed = ?started_at.elapsed(), "Node components shut down");
+    }
+
+    fn with_component<R>(
+        &self,
+        component: NodeComponent,
+        f: impl FnOnce(&mut ComponentState) -> R,
+    ) -> R {
+        let mut components = self.components.lock().unwrap_or_else(|err| err.into_inner());
+        f(&mut components[component as usize])
+    }
+}
+
+impl Default for ShutdownCoordinator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The shutdown state of a [`NodeComponent`].
+#[derive(Debug)]
+struct ComponentState {
+    /// The duration the component is given to shut down.
+    timeout: Duration,
+    /// Signals the component to shut down, taken when the component is shut down.
+    signal: Option<Signal>,
+    /// Resolves once the component is signaled.
+    on_shutdown: Shutdown,
+    /// Held by the guards of the component, dropped when the component is shut down.
+    done_tx: Option<mpsc::Sender<()>>,
+    /// Closes once the component and the coordinator dropped their senders.
+    done_rx: Option<mpsc::Receiver<()>>,
+}
+
+impl ComponentState {
+    fn new(component: NodeComponent) -> Self {
+        let (signal, on_shutdown) = signal();
+        let (done_tx, done_rx) = mpsc::channel(1);
+        Self {
+            timeout: component.default_timeout(),
+            signal: Some(signal),
+            on_shutdown,
+            done_tx: Some(done_tx),
+            done_rx: Some(done_rx),
+        }
+    }
+}
+
+/// A future that resolves once it's the component's turn to shut down, see
+/// [`ShutdownCoordinator::on_shutdown`].
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled"]
+pub struct ComponentShutdown {
+    shutdown: Shutdown,
+    guard: Option<ComponentShutdownGuard>,
+}
+
+impl Future for ComponentShutdown {
+    type Output = ComponentShutdownGuard;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        ready!(Pin::new(&mut self.shutdown).poll(cx));
+        Poll::Ready(self.guard.take().expect("Future polled after completion"))
+    }
+}
+
+/// A guard that signals the [`ShutdownCoordinator`] that the component shut down once dropped.
+#[derive(Debug)]
+#[must_use = "the component is considered shut down once the guard is dropped"]
+pub struct ComponentShutdownGuard {
+    _done_tx: Option<mpsc::Sender<()>>,
+}
+
+/// A [`TaskSpawner`] for the tasks of a component that is shut down by the
+/// [`ShutdownCoordinator`].
+///
+/// The tasks are spawned on the [`TaskExecutor`], but aren't cancelled by its shutdown signal, so
+/// that the component can finish its work when it's its turn to shut down.
+#[derive(Debug, Clone)]
+pub struct ComponentTaskSpawner {
+    executor: TaskExecutor,
+}
+
+impl ComponentTaskSpawner {
+    /// Creates a new spawner that spawns the tasks on the given executor.
+    pub const fn new(executor: TaskExecutor) -> Self {
+        Self { executor }
+    }
+}
+
+impl TaskSpawner for ComponentTaskSpawner {
+    fn spawn(&self, fut: BoxFuture<'static, ()>) -> JoinHandle<()> {
+        self.executor.spawn_with_signal(|_| fut)
+    }
+
+    fn spawn_critical(&self, name: &'static str, fut: BoxFuture<'static, ()>) -> JoinHandle<()> {
+        self.executor.spawn_critical_with_shutdown_signal(name, |_| fut)
+    }
+
+    fn spawn_blocking(&self, fut: BoxFuture<'static, ()>) -> JoinHandle<()> {
+        self.executor.spawn_blocking_with_signal(|_| fut)
+    }
+
+    fn spawn_critical_blocking(
+        &self,
+        name: &'static str,
+        fut: BoxFuture<'static, ()>,
+    ) -> JoinHandle<()> {
+        self.executor.spawn_critical_blocking_with_shutdown_signal(name, |_| fut)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_tasks::TaskManager;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn shuts_down_in_order() {
+        let manager = TaskManager::current();
+        let executor = manager.executor();
+        let coordinator = ShutdownCoordinator::new();
+        let order = Arc::new(Mutex::new(Vec::new()));
+
+        // register in reverse order to make sure the registration order doesn't matter
+        for component in NodeComponent::ALL.into_iter().rev() {
+            let order = order.clone();
+            coordinator.spawn_on_shutdown(&executor, component, move || async move {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                order.lock().unwrap().push(component);
+            });
+        }
+
+        coordinator.shutdown().await;
+        assert_eq!(*order.lock().unwrap(), NodeComponent::ALL);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn skips_stuck_component() {
+        let manager = TaskManager::current();
+        let executor = manager.executor();
+        let coordinator = ShutdownCoordinator::new();
+        coordinator.set_timeout(NodeComponent::Engine, Duration::from_millis(10));
+
+        // the engine never finishes its shutdown
+        coordinator.spawn_on_shutdown(&executor, NodeComponent::Engine, std::future::pending::<()>);
+
+        let network_shut_down = Arc::new(Mutex::new(false));
+        let shut_down = network_shut_down.clone();
+        coordinator.spawn_on_shutdown(&executor, NodeComponent::Network, move || async move {
+            *shut_down.lock().unwrap() = true;
+        });
+
+        coordinator.shutdown().await;
+        assert!(*network_shut_down.lock().unwrap());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn component_tasks_outlive_executor_shutdown() {
+        let manager = TaskManager::current();
+        let executor = manager.executor();
+        let coordinator = ShutdownCoordinator::new();
+
+        let (exex_tx, mut exex_rx) = mpsc::channel::<()>(1);
+        coordinator.spawn_critical_until_shutdown(
+            &executor,
+            NodeComponent::ExEx,
+            "exex",
+            async move {
+                let _exex_tx = exex_tx;
+                std::future::pending::<()>().await
+            },
+        );
+        let (pipeline_tx, mut pipeline_rx) = mpsc::channel::<()>(1);
+        let (stop_pipeline, pipeline_stopped) = tokio::sync::oneshot::channel::<()>();
+        ComponentTaskSpawner::new(executor.clone()).spawn_critical_blocking(
+            "pipeline",
+            Box::pin(async move {
+                let _pipeline_tx = pipeline_tx;
+                let _ = pipeline_stopped.await;
+            }),
+        );
+
+        // the executor's shutdown signal doesn't cancel the tasks
+        assert!(manager.graceful_shutdown_with_timeout(Duration::from_secs(1)));
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert_eq!(exex_rx.try_recv(), Err(mpsc::error::TryRecvError::Empty));
+        assert_eq!(pipeline_rx.try_recv(), Err(mpsc::error::TryRecvError::Empty));
+
+        // the coordinator drops the exex once it's its turn
+        coordinator.shutdown().await;
+        assert_eq!(exex_rx.recv().await, None);
+
+        let _ = stop_pipeline.send(());
+        assert_eq!(pipeline_rx.recv().await, None);
+    }
+}
@@ -1,8 +1,9 @@
 //! clap [Args](clap::Args) for database configuration
 
-use std::{fmt, str::FromStr, time::Duration};
+use std::{fmt, path::PathBuf, process, str::FromStr, time::Duration};
 
 use crate::version::default_client_version;
+use alloy_primitives::B256;
 use clap::{
     builder::{PossibleValue, TypedValueParser},
     error::ErrorKind,
@@ -12,7 +13,7 @@ use reth_db::{mdbx::MaxReadTransactionDuration, ClientVersion};
 use reth_storage_errors::db::LogLevel;
 
 /// Parameters for database configuration
-#[derive(Debug, Args, PartialEq, Eq, Default, Clone, Copy)]
+#[derive(Debug, Args, PartialEq, Eq, Default, Clone)]
 #[command(next_help_heading = "Database")]
 pub struct DatabaseArgs {
     /// Database logging level. Levels higher than "notice" require a debug build.
@@ -31,6 +32,28 @@ pub struct DatabaseArgs {
     /// Read transaction timeout in seconds, 0 means no timeout.
     #[arg(long = "db.read-transaction-timeout")]
     pub read_transaction_timeout: Option<u64>,
+    /// Maximum number of static files that are memory mapped at the same time. Unbounded if not
+    /// set.
+    #[arg(long = "db.max-open-static-files", value_name = "COUNT")]
+    pub max_open_static_files: Option<usize>,
+    /// Path to a file with the hex encoded 32-byte key to encrypt static files with.
+    ///
+    /// New static files are encrypted with AES-256-GCM. Existing static files can be encrypted
+    /// with `reth db recompress-static-files`. Encrypted static files can't be read without the
+    /// key.
+    #[arg(
+        long = "db.static-files-encryption-key",
+        value_name = "PATH",
+        conflicts_with = "static_files_encryption_key_cmd"
+    )]
+    pub static_files_encryption_key: Option<PathBuf>,
+    /// Shell command that prints the hex encoded 32-byte key to encrypt static files with, e.g. to
+    /// fetch it from a key management service.
+    ///
+    /// Same as `--db.static-files-encryption-key`, but the key is read from the output of the
+    /// command.
+    #[arg(long = "db.static-files-encryption-key-cmd", value_name = "COMMAND")]
+    pub static_files_encryption_key_cmd: Option<String>,
 }
 
 impl DatabaseArgs {
@@ -58,6 +81,32 @@ impl DatabaseArgs {
             .with_geometry_max_size(self.max_size)
             .with_growth_step(self.growth_step)
     }
+
+    /// Returns the static files encryption key, read from the configured key file or the output
+    /// of the configured key command.
+    pub fn static_files_encryption_key(&self) -> eyre::Result<Option<B256>> {
+        let key = if let Some(path) = &self.static_files_encryption_key {
+            reth_fs_util::read_to_string(path)?
+        } else if let Some(cmd) = &self.static_files_encryption_key_cmd {
+            let output = process::Command::new("sh").arg("-c").arg(cmd).output()?;
+            if !output.status.success() {
+                eyre::bail!(
+                    "Static files encryption key command failed with {}: {}",
+                    output.status,
+                    String::from_utf8_lossy(&output.stderr).trim()
+                )
+            }
+            String::from_utf8(output.stdout)?
+        } else {
+            return Ok(None)
+        };
+
+        let key = key
+            .trim()
+            .parse()
+            .map_err(|err| eyre::eyre!("Invalid static files encryption key: {err}"))?;
+        Ok(Some(key))
+    }
 }
 
 /// clap value parser for [`LogLevel`].
@@ -167,7 +216,7 @@ impl fmt::Display for ByteSize {
 }
 
 /// Value parser function that supports various formats.
-fn parse_byte_size(s: &str) -> Result<usize, String> {
+pub(crate) fn parse_byte_size(s: &str) -> Result<usize, String> {
     s.parse::<ByteSize>().map(Into::into)
 }
 
@@ -316,6 +365,47 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_command_parser_with_max_open_static_files() {
+        let cmd = CommandParser::<DatabaseArgs>::try_parse_from([
+            "reth",
+            "--db.max-open-static-files",
+            "64",
+        ])
+        .unwrap();
+        assert_eq!(cmd.args.max_open_static_files, Some(64));
+    }
+
+    #[test]
+    fn test_static_files_encryption_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("static-files.key");
+        let key = B256::repeat_byte(0x42);
+        std::fs::write(&path, format!("{key}\n")).unwrap();
+
+        let cmd = CommandParser::<DatabaseArgs>::try_parse_from([
+            "reth",
+            "--db.static-files-encryption-key",
+            path.to_str().unwrap(),
+        ])
+        .unwrap();
+        assert_eq!(cmd.args.static_files_encryption_key().unwrap(), Some(key));
+
+        std::fs::write(&path, "0x42").unwrap();
+        assert!(cmd.args.static_files_encryption_key().is_err());
+
+        assert_eq!(DatabaseArgs::default().static_files_encryption_key().unwrap(), None);
+
+        let result = CommandParser::<DatabaseArgs>::try_parse_from([
+            "reth",
+            "--db.static-files-encryption-key",
+            path.to_str().unwrap(),
+            "--db.static-files-encryption-key-cmd",
+            "cat key",
+        ]);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_command_parser_with_valid_log_level() {
         let cmd =
@@ -1,9 +1,12 @@
 //! clap [Args](clap::Args) for datadir config
 
+use super::database::parse_byte_size;
 use crate::dirs::{ChainPath, DataDirPath, MaybePlatformPath};
 use clap::Args;
 use reth_chainspec::Chain;
-use std::path::PathBuf;
+use reth_static_file_types::StaticFileSegment;
+use std::{path::PathBuf, str::FromStr};
+use url::Url;
 
 /// Parameters for datadir configuration
 #[derive(Debug, Args, PartialEq, Eq, Default, Clone)]
@@ -27,6 +30,53 @@ pub struct DatadirArgs {
         verbatim_doc_comment
     )]
     pub static_files_path: Option<PathBuf>,
+
+    /// Store the static files of a segment in a separate directory, e.g. to keep history on a
+    /// cheaper volume. Can be repeated.
+    ///
+    /// The directories are remembered, so they only need to be configured once. The static files
+    /// need to be moved to the new directory before a segment is relocated.
+    ///
+    /// Example: `--datadir.static-files-segment transactions=/mnt/hdd/transactions`
+    #[arg(
+        long = "datadir.static-files-segment",
+        value_name = "SEGMENT=PATH",
+        value_parser = parse_static_files_segment_directory
+    )]
+    pub static_files_segment_directories: Vec<(StaticFileSegment, PathBuf)>,
+
+    /// Read the static files that don't exist locally from an S3-compatible bucket, and cache them
+    /// in the static files directory.
+    ///
+    /// The bucket is addressed with a path-style URL. Requests are signed with the credentials of
+    /// the `AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY` and `AWS_SESSION_TOKEN` environment
+    /// variables, or anonymous if they're not set. Static files are never uploaded to the bucket.
+    ///
+    /// Example: `--datadir.static-files-s3 https://s3.us-east-1.amazonaws.com/<BUCKET>/<PREFIX>`
+    #[arg(long = "datadir.static-files-s3", value_name = "URL")]
+    pub static_files_s3: Option<Url>,
+
+    /// The region that requests to the static files bucket are signed for.
+    ///
+    /// Defaults to `us-east-1`.
+    #[arg(
+        long = "datadir.static-files-s3-region",
+        value_name = "REGION",
+        requires = "static_files_s3"
+    )]
+    pub static_files_s3_region: Option<String>,
+
+    /// Maximum size of the static files downloaded from the static files bucket that are kept
+    /// locally (e.g., 500GB). The least recently downloaded static files are removed first.
+    ///
+    /// Defaults to 100GB.
+    #[arg(
+        long = "datadir.static-files-s3-cache-size",
+        value_name = "SIZE",
+        value_parser = parse_byte_size,
+        requires = "static_files_s3"
+    )]
+    pub static_files_s3_cache_size: Option<usize>,
 }
 
 impl DatadirArgs {
@@ -37,6 +87,15 @@ impl DatadirArgs {
     }
 }
 
+/// Parses a `<SEGMENT>=<PATH>` pair.
+fn parse_static_files_segment_directory(s: &str) -> Result<(StaticFileSegment, PathBuf), String> {
+    let (segment, path) =
+        s.split_once('=').ok_or_else(|| format!("expected <SEGMENT>=<PATH>, got {s}"))?;
+    let segment = StaticFileSegment::from_str(segment)
+        .map_err(|_| format!("unknown static file segment: {segment}"))?;
+    Ok((segment, path.into()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -55,4 +114,30 @@ mod tests {
         let args = CommandParser::<DatadirArgs>::parse_from(["reth"]).args;
         assert_eq!(args, default_args);
     }
+
+    #[test]
+    fn test_parse_static_files_segment_directories() {
+        let args = CommandParser::<DatadirArgs>::parse_from([
+            "reth",
+            "--datadir.static-files-segment",
+            "transactions=/mnt/hdd/transactions",
+            "--datadir.static-files-segment",
+            "receipts=/mnt/hdd/receipts",
+        ])
+        .args;
+        assert_eq!(
+            args.static_files_segment_directories,
+            vec![
+                (StaticFileSegment::Transactions, "/mnt/hdd/transactions".into()),
+                (StaticFileSegment::Receipts, "/mnt/hdd/receipts".into()),
+            ]
+        );
+
+        assert!(CommandParser::<DatadirArgs>::try_parse_from([
+            "reth",
+            "--datadir.static-files-segment",
+            "/mnt/hdd/transactions",
+        ])
+        .is_err());
+    }
 }
@@ -68,6 +68,14 @@ pub struct DebugArgs {
     #[arg(long = "debug.engine-api-store", help_heading = "Debug", value_name = "PATH")]
     pub engine_api_store: Option<PathBuf>,
 
+    /// The file to record engine API calls to.
+    ///
+    /// If specified, every `engine_newPayload` and `engine_forkchoiceUpdated` request served by
+    /// the auth server is appended to the file together with its response and timestamps. The
+    /// recording can be fed back into a node with `reth engine replay`.
+    #[arg(long = "debug.engine-api-record", help_heading = "Debug", value_name = "FILE")]
+    pub engine_api_record: Option<PathBuf>,
+
     /// Determines which type of invalid block hook to install
     ///
     /// Example: `witness,prestate`
@@ -92,6 +100,12 @@ pub struct DebugArgs {
         verbatim_doc_comment
     )]
     pub healthy_node_rpc_url: Option<String>,
+
+    /// Track the name, age and poll statistics of every spawned task.
+    ///
+    /// The live tasks are served by the `debug_taskDump` RPC method.
+    #[arg(long = "debug.task-instrumentation", help_heading = "Debug")]
+    pub task_instrumentation: bool,
 }
 
 impl Default for DebugArgs {
@@ -107,8 +121,10 @@ impl Default for DebugArgs {
             reorg_frequency: None,
             reorg_depth: None,
             engine_api_store: None,
+            engine_api_record: None,
             invalid_block_hook: Some(InvalidBlockSelection::default()),
             healthy_node_rpc_url: None,
+            task_instrumentation: false,
         }
     }
 }
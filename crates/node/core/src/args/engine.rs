@@ -1,11 +1,15 @@
 //! clap [Args](clap::Args) for engine purposes
 
 use clap::Args;
+use humantime::parse_duration;
 use reth_engine_primitives::TreeConfig;
+use std::time::Duration;
 
 use crate::node_config::{
-    DEFAULT_CROSS_BLOCK_CACHE_SIZE_MB, DEFAULT_MAX_PROOF_TASK_CONCURRENCY,
-    DEFAULT_MEMORY_BLOCK_BUFFER_TARGET, DEFAULT_PERSISTENCE_THRESHOLD, DEFAULT_RESERVED_CPU_CORES,
+    DEFAULT_CROSS_BLOCK_CACHE_SIZE_MB, DEFAULT_HOT_STATE_CACHE_ENTRIES,
+    DEFAULT_MAX_PROOF_TASK_CONCURRENCY, DEFAULT_MEMORY_BLOCK_BUFFER_TARGET,
+    DEFAULT_MULTIPROOF_CHUNK_SIZE, DEFAULT_MULTIPROOF_CHUNK_TARGET_LATENCY,
+    DEFAULT_MULTIPROOF_MAX_CHUNK_SIZE, DEFAULT_PERSISTENCE_THRESHOLD, DEFAULT_RESERVED_CPU_CORES,
 };
 
 /// Parameters for configuring the engine driver.
@@ -61,6 +65,30 @@ pub struct EngineArgs {
     #[arg(long = "engine.reserved-cpu-cores", default_value_t = DEFAULT_RESERVED_CPU_CORES)]
     pub reserved_cpu_cores: usize,
 
+    /// Configure the number of proof targets in a multiproof chunk, used until proof latencies
+    /// were measured or always if adaptive chunking is disabled
+    #[arg(long = "engine.multiproof-chunk-size", default_value_t = DEFAULT_MULTIPROOF_CHUNK_SIZE)]
+    pub multiproof_chunk_size: usize,
+
+    /// Configure the maximum number of proof targets in a multiproof chunk
+    #[arg(long = "engine.multiproof-max-chunk-size", default_value_t = DEFAULT_MULTIPROOF_MAX_CHUNK_SIZE)]
+    pub multiproof_max_chunk_size: usize,
+
+    /// Configure the latency of a single multiproof chunk calculation that adaptive chunking aims
+    /// for
+    #[arg(
+        long = "engine.multiproof-chunk-target-latency",
+        value_name = "DURATION",
+        value_parser = parse_duration,
+        default_value = "2ms"
+    )]
+    pub multiproof_chunk_target_latency: Duration,
+
+    /// Disable sizing multiproof chunks by the measured proof latency and the number of worker
+    /// threads
+    #[arg(long = "engine.disable-adaptive-multiproof-chunking")]
+    pub adaptive_multiproof_chunking_disabled: bool,
+
     /// CAUTION: This CLI flag has no effect anymore, use --engine.disable-precompile-cache
     /// if you want to disable precompile cache
     #[arg(long = "engine.precompile-cache", default_value = "true", hide = true)]
@@ -85,6 +113,42 @@ pub struct EngineArgs {
         default_value = "false"
     )]
     pub always_process_payload_attributes_on_canonical_head: bool,
+
+    /// Period without a forkchoice update or new payload from the consensus layer after which
+    /// the consensus layer is considered unhealthy and diagnosed.
+    #[arg(
+        long = "engine.cl-health-window",
+        value_name = "DURATION",
+        value_parser = parse_duration,
+        default_value = "120s"
+    )]
+    pub cl_health_window: Duration,
+
+    /// Address of the consensus layer client, probed when no updates are received from it to
+    /// tell whether it is down.
+    #[arg(long = "engine.cl-endpoint", value_name = "HOST:PORT")]
+    pub cl_endpoint: Option<String>,
+
+    /// Re-execute every block whose number is a multiple of this interval on a separate thread
+    /// and alert if the result differs from the first execution.
+    ///
+    /// This detects nondeterministic execution, e.g. caused by faulty hardware.
+    #[arg(long = "engine.determinism-check-interval", value_name = "BLOCKS", value_parser = clap::value_parser!(u64).range(1..))]
+    pub determinism_check_interval: Option<u64>,
+
+    /// Prefetch proofs of the accounts and storage slots in transaction access lists while the
+    /// payload is executed, so the state root computation finds them already revealed.
+    #[arg(long = "engine.access-list-prefetch", default_value = "false")]
+    pub access_list_prefetch: bool,
+
+    /// Additionally log the report of every processed canonical block as a JSON object.
+    #[arg(long = "engine.block-reports-json", default_value = "false")]
+    pub block_reports_json: bool,
+
+    /// Number of accounts and storage slots of the canonical head to cache each for payload
+    /// building and RPC calls. Set to 0 to disable the cache.
+    #[arg(long = "engine.hot-state-cache-entries", default_value_t = DEFAULT_HOT_STATE_CACHE_ENTRIES)]
+    pub hot_state_cache_entries: usize,
 }
 
 #[allow(deprecated)]
@@ -102,10 +166,20 @@ impl Default for EngineArgs {
             accept_execution_requests_hash: false,
             max_proof_task_concurrency: DEFAULT_MAX_PROOF_TASK_CONCURRENCY,
             reserved_cpu_cores: DEFAULT_RESERVED_CPU_CORES,
+            multiproof_chunk_size: DEFAULT_MULTIPROOF_CHUNK_SIZE,
+            multiproof_max_chunk_size: DEFAULT_MULTIPROOF_MAX_CHUNK_SIZE,
+            multiproof_chunk_target_latency: DEFAULT_MULTIPROOF_CHUNK_TARGET_LATENCY,
+            adaptive_multiproof_chunking_disabled: false,
             precompile_cache_enabled: true,
             precompile_cache_disabled: false,
             state_root_fallback: false,
             always_process_payload_attributes_on_canonical_head: false,
+            cl_health_window: Duration::from_secs(120),
+            cl_endpoint: None,
+            determinism_check_interval: None,
+            access_list_prefetch: false,
+            block_reports_json: false,
+            hot_state_cache_entries: DEFAULT_HOT_STATE_CACHE_ENTRIES,
         }
     }
 }
@@ -123,11 +197,17 @@ impl EngineArgs {
             .with_cross_block_cache_size(self.cross_block_cache_size * 1024 * 1024)
             .with_max_proof_task_concurrency(self.max_proof_task_concurrency)
             .with_reserved_cpu_cores(self.reserved_cpu_cores)
+            .with_multiproof_chunk_size(self.multiproof_chunk_size)
+            .with_multiproof_max_chunk_size(self.multiproof_max_chunk_size)
+            .with_multiproof_chunk_target_latency(self.multiproof_chunk_target_latency)
+            .without_adaptive_multiproof_chunking(self.adaptive_multiproof_chunking_disabled)
             .without_precompile_cache(self.precompile_cache_disabled)
             .with_state_root_fallback(self.state_root_fallback)
             .with_always_process_payload_attributes_on_canonical_head(
                 self.always_process_payload_attributes_on_canonical_head,
             )
+            .with_determinism_check_interval(self.determinism_check_interval)
+            .with_access_list_prefetch(self.access_list_prefetch)
     }
 }
 
@@ -149,4 +229,65 @@ mod tests {
         let args = CommandParser::<EngineArgs>::parse_from(["reth"]).args;
         assert_eq!(args, default_args);
     }
+
+    #[test]
+    fn test_parse_cl_health_args() {
+        let args = CommandParser::<EngineArgs>::parse_from([
+            "reth",
+            "--engine.cl-health-window",
+            "5m",
+            "--engine.cl-endpoint",
+            "localhost:5052",
+        ])
+        .args;
+        assert_eq!(args.cl_health_window, Duration::from_secs(300));
+        assert_eq!(args.cl_endpoint.as_deref(), Some("localhost:5052"));
+    }
+
+    #[test]
+    fn test_parse_determinism_check_interval() {
+        let args = CommandParser::<EngineArgs>::parse_from([
+            "reth",
+            "--engine.determinism-check-interval",
+            "1000",
+        ])
+        .args;
+        assert_eq!(args.tree_config().determinism_check_interval(), Some(1000));
+
+        assert!(CommandParser::<EngineArgs>::try_parse_from([
+            "reth",
+            "--engine.determinism-check-interval",
+            "0"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_parse_access_list_prefetch() {
+        assert!(!EngineArgs::default().tree_config().access_list_prefetch());
+
+        let args =
+            CommandParser::<EngineArgs>::parse_from(["reth", "--engine.access-list-prefetch"]).args;
+        assert!(args.tree_config().access_list_prefetch());
+    }
+
+    #[test]
+    fn test_parse_block_reports_json() {
+        assert!(!EngineArgs::default().block_reports_json);
+
+        let args =
+            CommandParser::<EngineArgs>::parse_from(["reth", "--engine.block-reports-json"]).args;
+        assert!(args.block_reports_json);
+    }
+
+    #[test]
+    fn test_parse_hot_state_cache_entries() {
+        let args = CommandParser::<EngineArgs>::parse_from([
+            "reth",
+            "--engine.hot-state-cache-entries",
+            "0",
+        ])
+        .args;
+        assert_eq!(args.hot_state_cache_entries, 0);
+    }
 }
@@ -0,0 +1,62 @@
+//! clap [Args](clap::Args) for ethash seal verification
+
+use clap::Args;
+use std::path::PathBuf;
+
+/// Parameters for verifying the proof-of-work seals of pre-merge headers
+#[derive(Debug, Args, PartialEq, Eq, Default, Clone)]
+#[command(next_help_heading = "Ethash")]
+pub struct EthashArgs {
+    /// Verify the ethash seals of pre-merge headers instead of trusting them.
+    #[arg(long = "ethash.verify")]
+    pub verify: bool,
+
+    /// Directory to persist ethash verification caches in, so that they are only generated once
+    /// per epoch.
+    #[arg(long = "ethash.cache-dir", value_name = "PATH", requires = "verify")]
+    pub cache_dir: Option<PathBuf>,
+
+    /// Directory to persist full ethash datasets in.
+    ///
+    /// If set, seals are verified against the full dataset of each epoch, which is faster when
+    /// verifying many headers. Each dataset is larger than 1GB and takes minutes to generate.
+    #[arg(long = "ethash.dataset-dir", value_name = "PATH", requires = "verify")]
+    pub dataset_dir: Option<PathBuf>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn test_parse_ethash_args() {
+        let args = CommandParser::<EthashArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, EthashArgs::default());
+
+        let args = CommandParser::<EthashArgs>::parse_from([
+            "reth",
+            "--ethash.verify",
+            "--ethash.cache-dir",
+            "/tmp/ethash",
+        ])
+        .args;
+        assert!(args.verify);
+        assert_eq!(args.cache_dir, Some(PathBuf::from("/tmp/ethash")));
+
+        // directories require verification to be enabled
+        assert!(CommandParser::<EthashArgs>::try_parse_from([
+            "reth",
+            "--ethash.cache-dir",
+            "/tmp/ethash"
+        ])
+        .is_err());
+    }
+}
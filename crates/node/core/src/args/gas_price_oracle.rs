@@ -3,7 +3,7 @@ use clap::Args;
 use reth_rpc_eth_types::GasPriceOracleConfig;
 use reth_rpc_server_types::constants::gas_oracle::{
     DEFAULT_GAS_PRICE_BLOCKS, DEFAULT_GAS_PRICE_PERCENTILE, DEFAULT_IGNORE_GAS_PRICE,
-    DEFAULT_MAX_GAS_PRICE,
+    DEFAULT_MAX_GAS_PRICE, DEFAULT_MEMPOOL_WEIGHT,
 };
 
 /// Parameters to configure Gas Price Oracle
@@ -25,17 +25,35 @@ pub struct GasPriceOracleArgs {
     /// The percentile of gas prices to use for the estimate
     #[arg(long = "gpo.percentile", default_value_t = DEFAULT_GAS_PRICE_PERCENTILE)]
     pub percentile: u32,
+
+    /// The percentile of pending mempool tips to use for the estimate
+    #[arg(long = "gpo.mempool-percentile", default_value_t = DEFAULT_GAS_PRICE_PERCENTILE)]
+    pub mempool_percentile: u32,
+
+    /// Weight (in %) of the mempool percentile in the priority fee suggestion, blended with the
+    /// percentile of recently included tips. 0 disables the blend
+    #[arg(long = "gpo.mempool-weight", default_value_t = DEFAULT_MEMPOOL_WEIGHT)]
+    pub mempool_weight: u32,
 }
 
 impl GasPriceOracleArgs {
     /// Returns a [`GasPriceOracleConfig`] from the arguments.
     pub fn gas_price_oracle_config(&self) -> GasPriceOracleConfig {
-        let Self { blocks, ignore_price, max_price, percentile } = self;
+        let Self {
+            blocks,
+            ignore_price,
+            max_price,
+            percentile,
+            mempool_percentile,
+            mempool_weight,
+        } = self;
         GasPriceOracleConfig {
             max_price: Some(U256::from(*max_price)),
             ignore_price: Some(U256::from(*ignore_price)),
             percentile: *percentile,
             blocks: *blocks,
+            mempool_percentile: *mempool_percentile,
+            mempool_weight: *mempool_weight,
             ..Default::default()
         }
     }
@@ -48,6 +66,8 @@ impl Default for GasPriceOracleArgs {
             ignore_price: DEFAULT_IGNORE_GAS_PRICE.to(),
             max_price: DEFAULT_MAX_GAS_PRICE.to(),
             percentile: DEFAULT_GAS_PRICE_PERCENTILE,
+            mempool_percentile: DEFAULT_GAS_PRICE_PERCENTILE,
+            mempool_weight: DEFAULT_MEMPOOL_WEIGHT,
         }
     }
 }
@@ -73,6 +93,8 @@ mod tests {
                 ignore_price: DEFAULT_IGNORE_GAS_PRICE.to(),
                 max_price: DEFAULT_MAX_GAS_PRICE.to(),
                 percentile: DEFAULT_GAS_PRICE_PERCENTILE,
+                mempool_percentile: DEFAULT_GAS_PRICE_PERCENTILE,
+                mempool_weight: DEFAULT_MEMPOOL_WEIGHT,
             }
         );
     }
@@ -2,11 +2,12 @@
 
 use crate::dirs::{LogsDir, PlatformPath};
 use clap::{ArgAction, Args, ValueEnum};
+use eyre::OptionExt;
 use reth_tracing::{
     tracing_subscriber::filter::Directive, FileInfo, FileWorkerGuard, LayerInfo, Layers, LogFormat,
     RethTracer, Tracer,
 };
-use std::{fmt, fmt::Display};
+use std::{fmt, fmt::Display, path::PathBuf};
 use tracing::{level_filters::LevelFilter, Level};
 /// Constant to convert megabytes to bytes
 const MB_TO_BYTES: u64 = 1024 * 1024;
@@ -57,6 +58,20 @@ pub struct LogArgs {
     )]
     pub journald_filter: String,
 
+    /// Serve the task instrumentation of the runtime to `tokio-console`.
+    ///
+    /// Requires a binary built with the `tokio-console` feature and `--cfg tokio_unstable`.
+    #[arg(long = "log.tokio-console", global = true)]
+    pub tokio_console: bool,
+
+    /// Watch a file for log filter directives that are applied on top of the stdout and file
+    /// filters while the node is running.
+    ///
+    /// The file contains comma-separated directives, e.g. `engine=trace,net=debug`, and is
+    /// re-read whenever it changes. Removing the file resets the filters.
+    #[arg(long = "log.filter-file", value_name = "PATH", global = true)]
+    pub log_filter_file: Option<PathBuf>,
+
     /// Sets whether or not the formatter emits ANSI terminal escape codes for colors and other
     /// text formatting.
     #[arg(
@@ -123,7 +138,27 @@ impl LogArgs {
             tracer = tracer.with_file(file, info);
         }
 
+        #[cfg(feature = "tokio-console")]
+        let layers = {
+            let mut layers = layers;
+            if self.tokio_console {
+                layers.tokio_console();
+            }
+            layers
+        };
+        #[cfg(not(feature = "tokio-console"))]
+        if self.tokio_console {
+            eyre::bail!("`--log.tokio-console` requires a build with the `tokio-console` feature");
+        }
+
         let guard = tracer.init_with_layers(layers)?;
+
+        if let Some(path) = &self.log_filter_file {
+            reth_tracing::log_filter_handle()
+                .ok_or_eyre("log filters can't be reloaded, tracing was already initialized")?
+                .watch_file(path.clone())?;
+        }
+
         Ok(guard)
     }
 }
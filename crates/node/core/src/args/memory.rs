@@ -0,0 +1,76 @@
+//! clap [Args](clap::Args) for the global memory budget
+
+use clap::Args;
+use humantime::parse_duration;
+use std::time::Duration;
+
+/// Default interval at which the memory budget is enforced.
+const DEFAULT_RECLAIM_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Parameters for the memory budget shared by in-memory caches
+#[derive(Debug, Args, PartialEq, Eq, Clone)]
+#[command(next_help_heading = "Memory")]
+pub struct MemoryArgs {
+    /// Maximum memory in megabytes that registered caches may use in total.
+    ///
+    /// If the memory reported by all caches exceeds the budget, they are asked to evict entries.
+    /// Without a budget the memory is only tracked.
+    #[arg(long = "memory.budget", value_name = "MB")]
+    pub budget: Option<u64>,
+
+    /// Interval at which the memory budget is enforced.
+    #[arg(
+        long = "memory.reclaim-interval",
+        value_name = "DURATION",
+        value_parser = parse_duration,
+        default_value = "1s"
+    )]
+    pub reclaim_interval: Duration,
+}
+
+impl MemoryArgs {
+    /// Returns the memory budget in bytes, if configured.
+    pub const fn budget_bytes(&self) -> Option<usize> {
+        match self.budget {
+            Some(budget) => Some(budget as usize * 1024 * 1024),
+            None => None,
+        }
+    }
+}
+
+impl Default for MemoryArgs {
+    fn default() -> Self {
+        Self { budget: None, reclaim_interval: DEFAULT_RECLAIM_INTERVAL }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn test_parse_memory_args() {
+        let args = CommandParser::<MemoryArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, MemoryArgs::default());
+        assert_eq!(args.budget_bytes(), None);
+
+        let args = CommandParser::<MemoryArgs>::parse_from([
+            "reth",
+            "--memory.budget",
+            "4096",
+            "--memory.reclaim-interval",
+            "500ms",
+        ])
+        .args;
+        assert_eq!(args.budget_bytes(), Some(4096 * 1024 * 1024));
+        assert_eq!(args.reclaim_interval, Duration::from_millis(500));
+    }
+}
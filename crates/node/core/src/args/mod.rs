@@ -64,9 +64,25 @@ pub use engine::EngineArgs;
 mod ress_args;
 pub use ress_args::RessArgs;
 
+/// `StaticFileExchangeArgs` for configuring the static file exchange subprotocol.
+mod static_file_exchange;
+pub use static_file_exchange::StaticFileExchangeArgs;
+
 /// `EraArgs` for configuring ERA files import.
 mod era;
 pub use era::{DefaultEraHost, EraArgs, EraSourceArgs};
 
+/// `EthashArgs` for configuring ethash seal verification.
+mod ethash;
+pub use ethash::EthashArgs;
+
+/// `MemoryArgs` for configuring the memory budget of in-memory caches.
+mod memory;
+pub use memory::MemoryArgs;
+
+/// `WebhookArgs` for configuring webhook notifications.
+mod webhook;
+pub use webhook::WebhookArgs;
+
 mod error;
 pub mod types;
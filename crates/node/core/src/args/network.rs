@@ -14,10 +14,10 @@ use reth_discv5::{
     discv5::ListenConfig, DEFAULT_COUNT_BOOTSTRAP_LOOKUPS, DEFAULT_DISCOVERY_V5_PORT,
     DEFAULT_SECONDS_BOOTSTRAP_LOOKUP_INTERVAL, DEFAULT_SECONDS_LOOKUP_INTERVAL,
 };
-use reth_net_nat::{NatResolver, DEFAULT_NET_IF_NAME};
+use reth_net_nat::{NatResolver, PortMappingConfig, DEFAULT_NET_IF_NAME};
 use reth_network::{
     transactions::{
-        config::TransactionPropagationKind,
+        config::{TransactionPropagationKind, TransactionPropagationMode},
         constants::{
             tx_fetcher::{
                 DEFAULT_MAX_CAPACITY_CACHE_PENDING_FETCH, DEFAULT_MAX_COUNT_CONCURRENT_REQUESTS,
@@ -31,9 +31,10 @@ use reth_network::{
         DEFAULT_SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESP_ON_PACK_GET_POOLED_TRANSACTIONS_REQ,
         SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESPONSE,
     },
-    HelloMessageWithProtocols, NetworkConfigBuilder, NetworkPrimitives, SessionsConfig,
+    GeoIpConfig, HelloMessageWithProtocols, NetworkConfigBuilder, NetworkPrimitives,
+    SessionsConfig,
 };
-use reth_network_peers::{mainnet_nodes, TrustedPeer};
+use reth_network_peers::{mainnet_nodes, PeerId, TrustedPeer};
 use secp256k1::SecretKey;
 use tracing::error;
 
@@ -92,6 +93,19 @@ pub struct NetworkArgs {
     #[arg(long, default_value = "any")]
     pub nat: NatResolver,
 
+    /// External IPv6 address of a dual-stack node, advertised along with the IPv4 address.
+    ///
+    /// Peers only reach the node over IPv6 if it listens on the unspecified IPv6 address, e.g.
+    /// `--addr :: --discovery.addr ::`.
+    #[arg(long = "nat.ipv6", value_name = "IPV6")]
+    pub nat_ipv6: Option<Ipv6Addr>,
+
+    /// Map the p2p and discovery ports on the gateway with `UPnP` or NAT-PMP.
+    ///
+    /// Allows inbound connections to nodes behind consumer routers without manual port forwarding.
+    #[arg(long)]
+    pub port_mapping: bool,
+
     /// Network listening address
     #[arg(long = "addr", value_name = "ADDR", default_value_t = DEFAULT_DISCOVERY_ADDR)]
     pub addr: IpAddr,
@@ -161,6 +175,33 @@ pub struct NetworkArgs {
     /// The policy determines which peers transactions are gossiped to.
     #[arg(long = "tx-propagation-policy", default_value_t = TransactionPropagationKind::All)]
     pub tx_propagation_policy: TransactionPropagationKind,
+
+    /// Transaction Propagation Mode
+    ///
+    /// Determines how many peers new pending transactions are sent to in full, all other peers
+    /// only receive announcements of the transaction hashes. One of `sqrt`, `all`, `hashes-only`,
+    /// `max:<COUNT>` or `percent:<0-100>`.
+    #[arg(long = "tx-propagation-mode", value_name = "MODE", default_value_t = TransactionPropagationMode::Sqrt)]
+    pub tx_propagation_mode: TransactionPropagationMode,
+
+    /// Max number of blob transaction hashes announced to a peer in a single message.
+    #[arg(long = "max-blob-tx-hashes-per-announcement", value_name = "COUNT")]
+    pub max_blob_hashes_per_announcement: Option<usize>,
+
+    /// Comma separated ids of peers that new pending transactions are never propagated to.
+    #[arg(long = "tx-propagation-disabled-peers", value_name = "PEER_ID", value_delimiter = ',')]
+    pub tx_propagation_disabled_peers: Vec<PeerId>,
+
+    /// Comma separated paths to MMDB files, e.g. `GeoLite2-ASN.mmdb` and
+    /// `GeoLite2-Country.mmdb`, used to resolve the ASN and country of connected peers.
+    #[arg(long = "geoip-db", value_name = "PATH", value_delimiter = ',')]
+    pub geoip_databases: Vec<PathBuf>,
+
+    /// Max number of connected peers within the same ASN. Trusted and static peers are exempt.
+    ///
+    /// Requires `--geoip-db` with an ASN database.
+    #[arg(long = "max-peers-per-asn", value_name = "COUNT", requires = "geoip_databases")]
+    pub max_peers_per_asn: Option<usize>,
 }
 
 impl NetworkArgs {
@@ -202,7 +243,13 @@ impl NetworkArgs {
                 self.max_capacity_cache_txns_pending_fetch,
             ),
             max_transactions_seen_by_peer_history: self.max_seen_tx_history,
-            propagation_mode: Default::default(),
+            propagation_mode: self.tx_propagation_mode,
+            max_blob_hashes_per_announcement: self.max_blob_hashes_per_announcement,
+            propagation_disabled_peers: self
+                .tx_propagation_disabled_peers
+                .iter()
+                .copied()
+                .collect(),
         }
     }
 
@@ -243,12 +290,15 @@ impl NetworkArgs {
                 self.persistent_peers_file(peers_file).as_deref(),
             ))
             .external_ip_resolver(self.nat)
+            .external_ipv6(self.nat_ipv6)
+            .port_mapping(self.port_mapping.then(PortMappingConfig::default))
             .sessions_config(
                 SessionsConfig::default().with_upscaled_event_buffer(peers_config.max_peers()),
             )
             .peer_config(peers_config)
             .boot_nodes(chain_bootnodes.clone())
             .transactions_manager_config(self.transactions_manager_config())
+            .geoip(self.geoip_config())
             // Configure node identity
             .apply(|builder| {
                 let peer_id = builder.get_peer_id();
@@ -274,6 +324,14 @@ impl NetworkArgs {
             ))
     }
 
+    /// Returns the [`GeoIpConfig`] if any GeoIP database is configured.
+    pub fn geoip_config(&self) -> Option<GeoIpConfig> {
+        (!self.geoip_databases.is_empty()).then(|| {
+            GeoIpConfig::new(self.geoip_databases.clone())
+                .with_max_peers_per_asn(self.max_peers_per_asn)
+        })
+    }
+
     /// If `no_persist_peers` is false then this returns the path to the persistent peers file path.
     pub fn persistent_peers_file(&self, peers_file: PathBuf) -> Option<PathBuf> {
         self.no_persist_peers.not().then_some(peers_file)
@@ -329,6 +387,8 @@ impl Default for NetworkArgs {
             p2p_secret_key: None,
             no_persist_peers: false,
             nat: NatResolver::Any,
+            nat_ipv6: None,
+            port_mapping: false,
             addr: DEFAULT_DISCOVERY_ADDR,
             port: DEFAULT_DISCOVERY_PORT,
             max_outbound_peers: None,
@@ -342,7 +402,12 @@ impl Default for NetworkArgs {
             max_seen_tx_history: DEFAULT_MAX_COUNT_TRANSACTIONS_SEEN_BY_PEER,
             max_capacity_cache_txns_pending_fetch: DEFAULT_MAX_CAPACITY_CACHE_PENDING_FETCH,
             net_if: None,
-            tx_propagation_policy: TransactionPropagationKind::default()
+            tx_propagation_policy: TransactionPropagationKind::default(),
+            tx_propagation_mode: TransactionPropagationMode::default(),
+            max_blob_hashes_per_announcement: None,
+            tx_propagation_disabled_peers: vec![],
+            geoip_databases: vec![],
+            max_peers_per_asn: None,
         }
     }
 }
@@ -469,6 +534,8 @@ impl DiscoveryArgs {
         // Use rlpx address if none given
         let discv5_addr_ipv4 = discv5_addr.or(match rlpx_tcp_socket {
             SocketAddr::V4(addr) => Some(*addr.ip()),
+            // listening on the unspecified IPv6 address is dual-stack
+            SocketAddr::V6(addr) if addr.ip().is_unspecified() => Some(Ipv4Addr::UNSPECIFIED),
             SocketAddr::V6(_) => None,
         });
         let discv5_addr_ipv6 = discv5_addr_ipv6.or(match rlpx_tcp_socket {
@@ -561,6 +628,13 @@ mod tests {
         let args =
             CommandParser::<NetworkArgs>::parse_from(["reth", "--nat", "extip:0.0.0.0"]).args;
         assert_eq!(args.nat, NatResolver::ExternalIp("0.0.0.0".parse().unwrap()));
+
+        let args = CommandParser::<NetworkArgs>::parse_from(["reth", "--port-mapping"]).args;
+        assert!(args.port_mapping);
+
+        let args =
+            CommandParser::<NetworkArgs>::parse_from(["reth", "--nat.ipv6", "2001:db8::1"]).args;
+        assert_eq!(args.nat_ipv6, Some("2001:db8::1".parse().unwrap()));
     }
 
     #[test]
@@ -617,6 +691,56 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_tx_propagation_args() {
+        let peer_id = PeerId::random();
+        let args = CommandParser::<NetworkArgs>::parse_from([
+            "reth",
+            "--tx-propagation-mode",
+            "percent:25",
+            "--max-blob-tx-hashes-per-announcement",
+            "16",
+            "--tx-propagation-disabled-peers",
+            &peer_id.to_string(),
+        ])
+        .args;
+        assert_eq!(args.tx_propagation_mode, TransactionPropagationMode::Percent(25));
+
+        let config = args.transactions_manager_config();
+        assert_eq!(config.max_blob_hashes_per_announcement, Some(16));
+        assert!(!config.is_propagation_enabled(&peer_id));
+
+        assert!(CommandParser::<NetworkArgs>::try_parse_from([
+            "reth",
+            "--tx-propagation-mode",
+            "percent:101"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn parse_geoip_args() {
+        let args = CommandParser::<NetworkArgs>::parse_from([
+            "reth",
+            "--geoip-db",
+            "asn.mmdb,country.mmdb",
+            "--max-peers-per-asn",
+            "5",
+        ])
+        .args;
+        assert_eq!(
+            args.geoip_config(),
+            Some(
+                GeoIpConfig::new(vec!["asn.mmdb".into(), "country.mmdb".into()])
+                    .with_max_peers_per_asn(Some(5))
+            )
+        );
+
+        assert!(CommandParser::<NetworkArgs>::try_parse_from(["reth", "--max-peers-per-asn", "5"])
+            .is_err());
+        assert_eq!(NetworkArgs::default().geoip_config(), None);
+    }
+
     #[test]
     fn network_args_default_sanity_test() {
         let default_args = NetworkArgs::default();
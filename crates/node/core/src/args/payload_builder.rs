@@ -35,6 +35,25 @@ pub struct PayloadBuilderArgs {
     /// Maximum number of tasks to spawn for building a payload.
     #[arg(long = "builder.max-tasks", default_value = "3", value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
     pub max_payload_tasks: usize,
+
+    /// Maximum number of times a payload is rebuilt before it is resolved.
+    ///
+    /// If not set, the payload is rebuilt until it is resolved or the deadline is reached.
+    #[arg(long = "builder.max-iterations", value_name = "COUNT", value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    pub max_iterations: Option<usize>,
+
+    /// Keep rebuilding the payload back to back until it is requested via `engine_getPayload`,
+    /// instead of once per `--builder.interval`.
+    ///
+    /// A build that doesn't improve the payload waits for the next `--builder.interval` tick.
+    #[arg(long = "builder.until-resolved")]
+    pub until_resolved: bool,
+
+    /// Maximum number of blobs to include in built blocks.
+    ///
+    /// If not set, blocks are filled up to the protocol's maximum blob count.
+    #[arg(long = "builder.max-blobs", value_name = "COUNT")]
+    pub max_blobs_per_block: Option<u64>,
 }
 
 impl Default for PayloadBuilderArgs {
@@ -45,6 +64,9 @@ impl Default for PayloadBuilderArgs {
             gas_limit: None,
             deadline: SLOT_DURATION,
             max_payload_tasks: 3,
+            max_iterations: None,
+            until_resolved: false,
+            max_blobs_per_block: None,
         }
     }
 }
@@ -69,6 +91,18 @@ impl PayloadBuilderConfig for PayloadBuilderArgs {
     fn max_payload_tasks(&self) -> usize {
         self.max_payload_tasks
     }
+
+    fn max_iterations(&self) -> Option<usize> {
+        self.max_iterations
+    }
+
+    fn until_resolved(&self) -> bool {
+        self.until_resolved
+    }
+
+    fn max_blobs_per_block(&self) -> Option<u64> {
+        self.max_blobs_per_block
+    }
 }
 
 #[derive(Clone, Debug, Default)]
@@ -166,6 +200,29 @@ mod tests {
         assert_eq!(args.interval, Duration::from_secs(50));
     }
 
+    #[test]
+    fn test_args_with_build_strategy() {
+        let args = CommandParser::<PayloadBuilderArgs>::parse_from([
+            "reth",
+            "--builder.max-iterations",
+            "5",
+            "--builder.until-resolved",
+            "--builder.max-blobs",
+            "3",
+        ])
+        .args;
+        assert_eq!(args.max_iterations, Some(5));
+        assert!(args.until_resolved);
+        assert_eq!(args.max_blobs_per_block, Some(3));
+
+        assert!(CommandParser::<PayloadBuilderArgs>::try_parse_from([
+            "reth",
+            "--builder.max-iterations",
+            "0"
+        ])
+        .is_err());
+    }
+
     #[test]
     fn test_args_with_ms_interval() {
         let args =
@@ -100,6 +100,14 @@ pub struct PruningArgs {
     /// pruned.
     #[arg(long = "prune.bodies.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["bodies_distance", "bodies_pre_merge"])]
     pub bodies_before: Option<BlockNumber>,
+
+    // History Expiry
+    /// Expire the transaction history before the specified block number, expired history is no
+    /// longer served. The specified block number is not expired.
+    ///
+    /// Defaults to the merge block with `--prune.bodies.pre-merge`.
+    #[arg(long = "history.expire-before", value_name = "BLOCK_NUMBER")]
+    pub history_expire_before: Option<BlockNumber>,
 }
 
 impl PruningArgs {
@@ -162,6 +170,20 @@ impl PruningArgs {
         Some(config)
     }
 
+    /// Returns the block below which the transaction history is expired.
+    pub fn history_expiry<ChainSpec>(&self, chain_spec: &ChainSpec) -> Option<BlockNumber>
+    where
+        ChainSpec: EthereumHardforks,
+    {
+        self.history_expire_before.or_else(|| {
+            self.bodies_pre_merge
+                .then(|| {
+                    chain_spec.ethereum_fork_activation(EthereumHardfork::Paris).block_number()
+                })
+                .flatten()
+        })
+    }
+
     fn bodies_prune_mode<ChainSpec>(&self, chain_spec: &ChainSpec) -> Option<PruneMode>
     where
         ChainSpec: EthereumHardforks,
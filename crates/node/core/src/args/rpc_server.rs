@@ -58,6 +58,10 @@ pub struct RpcServerArgs {
     #[arg(long = "http.disable-compression", default_value_t = false)]
     pub http_disable_compression: bool,
 
+    /// Serve HTTP responses as CBOR to clients that send `Accept: application/cbor`
+    #[arg(long = "http.binary-encoding", default_value_t = false)]
+    pub http_binary_encoding: bool,
+
     /// Rpc Modules to be configured for the HTTP server
     #[arg(long = "http.api", value_parser = RpcModuleSelectionValueParser::default())]
     pub http_api: Option<RpcModuleSelection>,
@@ -119,6 +123,13 @@ pub struct RpcServerArgs {
     #[arg(long = "auth-ipc.path", default_value_t = constants::DEFAULT_ENGINE_API_IPC_ENDPOINT.to_string())]
     pub auth_ipc_path: String,
 
+    /// File permissions of the auth IPC socket in octal, e.g. `600`.
+    ///
+    /// Access to the auth IPC socket is not authenticated with a JWT, so the file permissions are
+    /// the trust boundary. Only applies to unix domain sockets.
+    #[arg(long = "auth-ipc.permissions", value_name = "MODE", value_parser = parse_socket_permissions, requires = "auth_ipc")]
+    pub auth_ipc_permissions: Option<u32>,
+
     /// Hex encoded JWT secret to authenticate the regular RPC server(s), see `--http.api` and
     /// `--ws.api`.
     ///
@@ -156,6 +167,25 @@ pub struct RpcServerArgs {
     #[arg(long = "rpc.max-trace-filter-blocks", alias = "rpc-max-trace-filter-blocks", value_name = "COUNT", default_value_t = constants::DEFAULT_MAX_TRACE_FILTER_BLOCKS)]
     pub rpc_max_trace_filter_blocks: u64,
 
+    /// Maximum number of traces that can be returned in a single `trace_filter` response. (0 = no
+    /// limit)
+    #[arg(long = "rpc.max-trace-filter-results", value_name = "COUNT", default_value_t = ZeroAsNoneU64::new(constants::DEFAULT_MAX_TRACE_FILTER_RESULTS as u64))]
+    pub rpc_max_trace_filter_results: ZeroAsNoneU64,
+
+    /// Number of blocks that are replayed concurrently by a single `trace_filter` request.
+    #[arg(
+        long = "rpc.trace-filter-concurrency",
+        value_name = "COUNT",
+        default_value_t = constants::DEFAULT_TRACE_FILTER_CONCURRENCY,
+        value_parser = RangedU64ValueParser::<usize>::new().range(1..)
+    )]
+    pub rpc_trace_filter_concurrency: usize,
+
+    /// Maximum size in megabytes of the cached `debug_traceBlock*` results, keyed by block hash
+    /// and tracing options. (0 = disabled)
+    #[arg(long = "rpc.trace-cache-size", value_name = "MB", default_value_t = 0)]
+    pub rpc_trace_cache_size: u64,
+
     /// Maximum number of blocks that could be scanned per filter request. (0 = entire chain)
     #[arg(long = "rpc.max-blocks-per-filter", alias = "rpc-max-blocks-per-filter", value_name = "COUNT", default_value_t = ZeroAsNoneU64::new(constants::DEFAULT_MAX_BLOCKS_PER_FILTER))]
     pub rpc_max_blocks_per_filter: ZeroAsNoneU64,
@@ -164,6 +194,53 @@ pub struct RpcServerArgs {
     #[arg(long = "rpc.max-logs-per-response", alias = "rpc-max-logs-per-response", value_name = "COUNT", default_value_t = ZeroAsNoneU64::new(constants::DEFAULT_MAX_LOGS_PER_RESPONSE as u64))]
     pub rpc_max_logs_per_response: ZeroAsNoneU64,
 
+    /// Maximum size in megabytes buffered while serializing large `eth_getLogs` responses in
+    /// parallel.
+    #[arg(long = "rpc.serialization-memory-budget", value_name = "MB", default_value_t = constants::DEFAULT_SERIALIZATION_MEMORY_BUDGET_MB)]
+    pub rpc_serialization_memory_budget: usize,
+
+    /// Time in seconds after which a filter that has not been polled is removed.
+    #[arg(long = "rpc.filter-ttl", value_name = "SECONDS", default_value_t = constants::DEFAULT_FILTER_TTL_SECS)]
+    pub rpc_filter_ttl: u64,
+
+    /// Maximum number of new blocks processed per `eth_getFilterChanges` call, the remaining
+    /// blocks are returned by subsequent polls. (0 = no limit)
+    #[arg(long = "rpc.filter-max-blocks-per-poll", value_name = "COUNT", default_value_t = ZeroAsNoneU64(None))]
+    pub rpc_filter_max_blocks_per_poll: ZeroAsNoneU64,
+
+    /// Number of most recently polled blocks a filter tracks to report logs of reorged blocks
+    /// with `removed: true`. (0 = disabled)
+    #[arg(long = "rpc.filter-reorg-window", value_name = "COUNT", default_value_t = constants::DEFAULT_FILTER_REORG_WINDOW)]
+    pub rpc_filter_reorg_window: u64,
+
+    /// Maximum number of consecutive new blocks that `newHeads` and `logs` subscriptions process
+    /// at once, so that subscribers keep up when a large backlog of blocks is committed at once.
+    /// Reorgs are never batched. (0 = disabled)
+    #[arg(
+        long = "rpc.subscription-max-compacted-blocks",
+        value_name = "COUNT",
+        default_value_t = 0
+    )]
+    pub rpc_subscription_max_compacted_blocks: usize,
+
+    /// Persists installed log and block filters to the datadir, so they survive node restarts.
+    #[arg(long = "rpc.persist-filters")]
+    pub rpc_persist_filters: bool,
+
+    /// Number of recent blocks whose blob sidecars are persisted, so `eth_getBlobSidecars` can
+    /// serve them after the transaction pool dropped them.
+    ///
+    /// Disabled by default.
+    #[arg(long = "rpc.blob-sidecars-retention", value_name = "BLOCKS")]
+    pub rpc_blob_sidecars_retention: Option<u64>,
+
+    /// Path to a JSON ABI file, or a directory of JSON ABI files, used to decode logs in `reth`
+    /// namespace responses.
+    ///
+    /// Can be specified multiple times.
+    #[arg(long = "rpc.abi", value_name = "PATH")]
+    pub rpc_abi: Vec<PathBuf>,
+
     /// Maximum gas limit for `eth_call` and call tracing RPC methods.
     #[arg(
         long = "rpc.gascap",
@@ -202,6 +279,21 @@ pub struct RpcServerArgs {
     )]
     pub rpc_eth_proof_window: u64,
 
+    /// Maximum number of state history entries that are looked up to generate a historical
+    /// proof.
+    ///
+    /// Historical proofs are generated by reverting state from the tip, which becomes expensive
+    /// for old blocks. With a budget, proofs that would look up more entries are rejected, and the
+    /// proof window defaults to its maximum unless `--rpc.eth-proof-window` is set. Account proofs
+    /// only look up the history of the proven account and storage slots, so they count far fewer
+    /// entries against the budget. Proofs for blocks whose history is pruned remain unavailable.
+    #[arg(long = "rpc.proof-effort-budget", value_name = "ENTRIES")]
+    pub rpc_proof_effort_budget: Option<usize>,
+
+    /// Maximum size in megabytes of the proof nodes in an `eth_getProof` response.
+    #[arg(long = "rpc.max-proof-response-size", value_name = "MB", default_value_t = constants::DEFAULT_MAX_PROOF_RESPONSE_SIZE_MB)]
+    pub rpc_max_proof_response_size: usize,
+
     /// Maximum number of concurrent getproof requests.
     #[arg(long = "rpc.proof-permits", alias = "rpc-proof-permits", value_name = "COUNT", default_value_t = constants::DEFAULT_PROOF_PERMITS)]
     pub rpc_proof_permits: usize,
@@ -221,6 +313,19 @@ pub struct RpcServerArgs {
 }
 
 impl RpcServerArgs {
+    /// Returns the proof window for historical proof generation.
+    ///
+    /// If a proof effort budget is configured, historical proofs are bounded by the budget instead
+    /// of the distance to the tip, so the default window is lifted to its maximum.
+    pub const fn eth_proof_window(&self) -> u64 {
+        if self.rpc_proof_effort_budget.is_some() &&
+            self.rpc_eth_proof_window == constants::DEFAULT_ETH_PROOF_WINDOW
+        {
+            return constants::MAX_ETH_PROOF_WINDOW
+        }
+        self.rpc_eth_proof_window
+    }
+
     /// Enables the HTTP-RPC server.
     pub const fn with_http(mut self) -> Self {
         self.http = true;
@@ -245,6 +350,18 @@ impl RpcServerArgs {
         self
     }
 
+    /// Configures the filename of the Auth IPC socket.
+    pub fn with_auth_ipc_path(mut self, path: impl Into<String>) -> Self {
+        self.auth_ipc_path = path.into();
+        self
+    }
+
+    /// Configures the file permissions of the Auth IPC socket.
+    pub const fn with_auth_ipc_permissions(mut self, mode: u32) -> Self {
+        self.auth_ipc_permissions = Some(mode);
+        self
+    }
+
     /// Change rpc port numbers based on the instance number, if provided.
     /// * The `auth_port` is scaled by a factor of `instance * 100`
     /// * The `http_port` is scaled by a factor of `-instance`
@@ -321,6 +438,7 @@ impl Default for RpcServerArgs {
             http_addr: Ipv4Addr::LOCALHOST.into(),
             http_port: constants::DEFAULT_HTTP_RPC_PORT,
             http_disable_compression: false,
+            http_binary_encoding: false,
             http_api: None,
             http_corsdomain: None,
             ws: false,
@@ -335,6 +453,7 @@ impl Default for RpcServerArgs {
             auth_jwtsecret: None,
             auth_ipc: false,
             auth_ipc_path: constants::DEFAULT_ENGINE_API_IPC_ENDPOINT.to_string(),
+            auth_ipc_permissions: None,
             rpc_jwtsecret: None,
             rpc_max_request_size: RPC_DEFAULT_MAX_REQUEST_SIZE_MB.into(),
             rpc_max_response_size: RPC_DEFAULT_MAX_RESPONSE_SIZE_MB.into(),
@@ -342,12 +461,27 @@ impl Default for RpcServerArgs {
             rpc_max_connections: RPC_DEFAULT_MAX_CONNECTIONS.into(),
             rpc_max_tracing_requests: constants::default_max_tracing_requests(),
             rpc_max_trace_filter_blocks: constants::DEFAULT_MAX_TRACE_FILTER_BLOCKS,
+            rpc_max_trace_filter_results: ZeroAsNoneU64::new(
+                constants::DEFAULT_MAX_TRACE_FILTER_RESULTS as u64,
+            ),
+            rpc_trace_filter_concurrency: constants::DEFAULT_TRACE_FILTER_CONCURRENCY,
+            rpc_trace_cache_size: 0,
             rpc_max_blocks_per_filter: constants::DEFAULT_MAX_BLOCKS_PER_FILTER.into(),
             rpc_max_logs_per_response: (constants::DEFAULT_MAX_LOGS_PER_RESPONSE as u64).into(),
+            rpc_serialization_memory_budget: constants::DEFAULT_SERIALIZATION_MEMORY_BUDGET_MB,
+            rpc_filter_ttl: constants::DEFAULT_FILTER_TTL_SECS,
+            rpc_filter_max_blocks_per_poll: ZeroAsNoneU64(None),
+            rpc_filter_reorg_window: constants::DEFAULT_FILTER_REORG_WINDOW,
+            rpc_subscription_max_compacted_blocks: 0,
+            rpc_persist_filters: false,
+            rpc_blob_sidecars_retention: None,
+            rpc_abi: Vec::new(),
             rpc_gas_cap: constants::gas_oracle::RPC_DEFAULT_GAS_CAP,
             rpc_tx_fee_cap: constants::DEFAULT_TX_FEE_CAP_WEI,
             rpc_max_simulate_blocks: constants::DEFAULT_MAX_SIMULATE_BLOCKS,
             rpc_eth_proof_window: constants::DEFAULT_ETH_PROOF_WINDOW,
+            rpc_proof_effort_budget: None,
+            rpc_max_proof_response_size: constants::DEFAULT_MAX_PROOF_RESPONSE_SIZE_MB,
             gas_price_oracle: GasPriceOracleArgs::default(),
             rpc_state_cache: RpcStateCacheArgs::default(),
             rpc_proof_permits: constants::DEFAULT_PROOF_PERMITS,
@@ -356,6 +490,11 @@ impl Default for RpcServerArgs {
     }
 }
 
+/// Parses octal file permissions, e.g. `600`, `0600` or `0o600`.
+fn parse_socket_permissions(value: &str) -> Result<u32, std::num::ParseIntError> {
+    u32::from_str_radix(value.strip_prefix("0o").unwrap_or(value), 8)
+}
+
 /// clap value parser for [`RpcModuleSelection`].
 #[derive(Clone, Debug, Default)]
 #[non_exhaustive]
@@ -440,6 +579,34 @@ mod tests {
         assert_eq!(args, default_args);
     }
 
+    #[test]
+    fn test_auth_ipc_permissions() {
+        for mode in ["600", "0600", "0o600"] {
+            let args = CommandParser::<RpcServerArgs>::parse_from([
+                "reth",
+                "--auth-ipc",
+                "--auth-ipc.permissions",
+                mode,
+            ])
+            .args;
+            assert_eq!(args.auth_ipc_permissions, Some(0o600));
+        }
+
+        assert!(CommandParser::<RpcServerArgs>::try_parse_from([
+            "reth",
+            "--auth-ipc",
+            "--auth-ipc.permissions",
+            "800",
+        ])
+        .is_err());
+        assert!(CommandParser::<RpcServerArgs>::try_parse_from([
+            "reth",
+            "--auth-ipc.permissions",
+            "600",
+        ])
+        .is_err());
+    }
+
     #[test]
     fn test_rpc_tx_fee_cap_parse_integer() {
         let args = CommandParser::<RpcServerArgs>::parse_from(["reth", "--rpc.txfeecap", "2"]).args;
@@ -467,4 +634,108 @@ mod tests {
         let expected = 1_000_000_000_000_000_000u128;
         assert_eq!(args.rpc_tx_fee_cap, expected); // 1 ETH default cap
     }
+
+    #[test]
+    fn test_rpc_trace_filter_args() {
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.max-trace-filter-results",
+            "0",
+            "--rpc.trace-filter-concurrency",
+            "4",
+            "--rpc.trace-cache-size",
+            "32",
+        ])
+        .args;
+        assert_eq!(args.rpc_max_trace_filter_results.unwrap_or_max(), u64::MAX); // 0 = no limit
+        assert_eq!(args.rpc_trace_filter_concurrency, 4);
+        assert_eq!(args.rpc_trace_cache_size, 32);
+
+        assert!(CommandParser::<RpcServerArgs>::try_parse_from([
+            "reth",
+            "--rpc.trace-filter-concurrency",
+            "0",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_rpc_serialization_memory_budget_args() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert_eq!(
+            args.rpc_serialization_memory_budget,
+            constants::DEFAULT_SERIALIZATION_MEMORY_BUDGET_MB
+        );
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.serialization-memory-budget",
+            "64",
+        ])
+        .args;
+        assert_eq!(args.rpc_serialization_memory_budget, 64);
+    }
+
+    #[test]
+    fn test_rpc_filter_args() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert_eq!(args.rpc_filter_ttl, constants::DEFAULT_FILTER_TTL_SECS);
+        assert_eq!(args.rpc_filter_max_blocks_per_poll, ZeroAsNoneU64(None));
+        assert_eq!(args.rpc_filter_reorg_window, constants::DEFAULT_FILTER_REORG_WINDOW);
+        assert!(!args.rpc_persist_filters);
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.filter-ttl",
+            "3600",
+            "--rpc.filter-max-blocks-per-poll",
+            "100",
+            "--rpc.filter-reorg-window",
+            "0",
+            "--rpc.persist-filters",
+        ])
+        .args;
+        assert_eq!(args.rpc_filter_ttl, 3600);
+        assert_eq!(args.rpc_filter_max_blocks_per_poll, ZeroAsNoneU64::new(100));
+        assert_eq!(args.rpc_filter_reorg_window, 0);
+        assert!(args.rpc_persist_filters);
+    }
+
+    #[test]
+    fn test_rpc_abi_args() {
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.abi",
+            "erc20.json",
+            "--rpc.abi",
+            "abis",
+        ])
+        .args;
+        assert_eq!(args.rpc_abi, vec![PathBuf::from("erc20.json"), PathBuf::from("abis")]);
+    }
+
+    #[test]
+    fn test_rpc_proof_effort_budget_lifts_default_window() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert_eq!(args.eth_proof_window(), constants::DEFAULT_ETH_PROOF_WINDOW);
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.proof-effort-budget",
+            "1000000",
+        ])
+        .args;
+        assert_eq!(args.rpc_proof_effort_budget, Some(1_000_000));
+        assert_eq!(args.eth_proof_window(), constants::MAX_ETH_PROOF_WINDOW);
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.proof-effort-budget",
+            "1000000",
+            "--rpc.eth-proof-window",
+            "64",
+        ])
+        .args;
+        assert_eq!(args.eth_proof_window(), 64);
+    }
 }
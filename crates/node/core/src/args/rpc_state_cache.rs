@@ -1,7 +1,8 @@
 use clap::Args;
 use reth_rpc_server_types::constants::cache::{
-    DEFAULT_BLOCK_CACHE_MAX_LEN, DEFAULT_CONCURRENT_DB_REQUESTS, DEFAULT_HEADER_CACHE_MAX_LEN,
-    DEFAULT_RECEIPT_CACHE_MAX_LEN,
+    DEFAULT_BLOCK_CACHE_MAX_LEN, DEFAULT_BYTECODE_CACHE_MAX_LEN, DEFAULT_BYTECODE_CACHE_PREWARM,
+    DEFAULT_CONCURRENT_DB_REQUESTS, DEFAULT_HEADER_CACHE_MAX_LEN, DEFAULT_RECEIPT_CACHE_MAX_LEN,
+    DEFAULT_WARMUP_CONTRACTS, DEFAULT_WARMUP_RANGES,
 };
 
 /// Parameters to configure RPC state cache.
@@ -36,6 +37,47 @@ pub struct RpcStateCacheArgs {
         default_value_t = DEFAULT_CONCURRENT_DB_REQUESTS,
     )]
     pub max_concurrent_db_requests: usize,
+
+    /// Max number of decompressed bytecodes in cache.
+    ///
+    /// The cache is shared by all state providers, 0 disables it.
+    #[arg(
+        long = "rpc-cache.max-bytecodes",
+        default_value_t = DEFAULT_BYTECODE_CACHE_MAX_LEN,
+    )]
+    pub max_bytecodes: u32,
+
+    /// Number of recently most used contracts whose bytecodes are loaded into the cache on
+    /// startup.
+    #[arg(
+        long = "rpc-cache.prewarm-bytecodes",
+        default_value_t = DEFAULT_BYTECODE_CACHE_PREWARM,
+    )]
+    pub prewarm_bytecodes: usize,
+
+    /// Record aggregate statistics of historical state queries and warm the caches with them.
+    ///
+    /// Only the number of queries per block range and state reads per contract are recorded,
+    /// and persisted in the data directory. On startup, the state history of the most read
+    /// contracts in the most queried block ranges is warmed in the background.
+    #[arg(long = "rpc-cache.warmup")]
+    pub warmup: bool,
+
+    /// Number of most read contracts whose state is warmed on startup.
+    #[arg(
+        long = "rpc-cache.warmup-contracts",
+        default_value_t = DEFAULT_WARMUP_CONTRACTS,
+        requires = "warmup",
+    )]
+    pub warmup_contracts: usize,
+
+    /// Number of most queried block ranges whose state history is warmed on startup.
+    #[arg(
+        long = "rpc-cache.warmup-ranges",
+        default_value_t = DEFAULT_WARMUP_RANGES,
+        requires = "warmup",
+    )]
+    pub warmup_ranges: usize,
 }
 
 impl RpcStateCacheArgs {
@@ -44,6 +86,7 @@ impl RpcStateCacheArgs {
         self.max_blocks = 0;
         self.max_receipts = 0;
         self.max_headers = 0;
+        self.max_bytecodes = 0;
     }
 }
 
@@ -54,6 +97,11 @@ impl Default for RpcStateCacheArgs {
             max_receipts: DEFAULT_RECEIPT_CACHE_MAX_LEN,
             max_headers: DEFAULT_HEADER_CACHE_MAX_LEN,
             max_concurrent_db_requests: DEFAULT_CONCURRENT_DB_REQUESTS,
+            max_bytecodes: DEFAULT_BYTECODE_CACHE_MAX_LEN,
+            prewarm_bytecodes: DEFAULT_BYTECODE_CACHE_PREWARM,
+            warmup: false,
+            warmup_contracts: DEFAULT_WARMUP_CONTRACTS,
+            warmup_ranges: DEFAULT_WARMUP_RANGES,
         }
     }
 }
@@ -0,0 +1,29 @@
+use clap::Args;
+
+/// The default number of maximum active connections.
+const MAX_ACTIVE_CONNECTIONS_DEFAULT: u64 = 10;
+
+/// Parameters for configuring the static file exchange subprotocol.
+#[derive(Debug, Clone, Args, PartialEq, Eq)]
+#[command(next_help_heading = "Static file exchange")]
+pub struct StaticFileExchangeArgs {
+    /// Enable the static file exchange subprotocol.
+    ///
+    /// Finalized static files are served to peers, and large header gaps are synced from the
+    /// headers static files of peers.
+    #[arg(long = "static-file-exchange.enable", default_value_t = false)]
+    pub enabled: bool,
+
+    /// The maximum number of active connections for the static file exchange subprotocol.
+    #[arg(
+        long = "static-file-exchange.max-active-connections",
+        default_value_t = MAX_ACTIVE_CONNECTIONS_DEFAULT
+    )]
+    pub max_active_connections: u64,
+}
+
+impl Default for StaticFileExchangeArgs {
+    fn default() -> Self {
+        Self { enabled: false, max_active_connections: MAX_ACTIVE_CONNECTIONS_DEFAULT }
+    }
+}
@@ -10,7 +10,8 @@ use reth_transaction_pool::{
     maintain::MAX_QUEUED_TRANSACTION_LIFETIME,
     pool::{NEW_TX_LISTENER_BUFFER_SIZE, PENDING_TX_LISTENER_BUFFER_SIZE},
     validate::DEFAULT_MAX_TX_INPUT_BYTES,
-    LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_PRICE_BUMP,
+    LocalTransactionConfig, PoolConfig, PoolQuotas, PriceBumpConfig, QuotaEvictionPolicy,
+    SubPoolLimit, DEFAULT_PRICE_BUMP, DEFAULT_REPLACEMENT_SUPPRESSION_WINDOW,
     DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS, MAX_NEW_PENDING_TXS_NOTIFICATIONS,
     REPLACE_BLOB_PRICE_BUMP, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
     TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT, TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
@@ -121,6 +122,31 @@ pub struct TxPoolArgs {
     #[arg(long = "txpool.lifetime", value_parser = parse_duration_from_secs_or_ms, default_value = "10800", value_name = "DURATION")]
     pub max_queued_lifetime: Duration,
 
+    /// How long a replaced local transaction is tracked to suppress gossip of the replaced
+    /// version. Set to 0 to disable.
+    #[arg(long = "txpool.replacement-suppression-window", value_parser = parse_duration_from_secs_or_ms, default_value = "12", value_name = "DURATION")]
+    pub replacement_suppression_window: Duration,
+
+    /// Max number of transactions a single sender may have in the pool, including local senders.
+    #[arg(long = "txpool.sender-quota", value_name = "COUNT")]
+    pub sender_quota: Option<usize>,
+
+    /// Max number of local transactions in the pool.
+    #[arg(long = "txpool.local-quota", value_name = "COUNT")]
+    pub local_quota: Option<usize>,
+
+    /// Max number of external transactions in the pool.
+    #[arg(long = "txpool.external-quota", value_name = "COUNT")]
+    pub external_quota: Option<usize>,
+
+    /// Max number of private transactions in the pool.
+    #[arg(long = "txpool.private-quota", value_name = "COUNT")]
+    pub private_quota: Option<usize>,
+
+    /// Evict the cheapest transactions of an origin over its quota instead of rejecting new ones.
+    #[arg(long = "txpool.quota-evict-cheapest")]
+    pub quota_evict_cheapest: bool,
+
     /// Path to store the local transaction backup at, to survive node restarts.
     #[arg(long = "txpool.transactions-backup", alias = "txpool.journal", value_name = "PATH")]
     pub transactions_backup_path: Option<std::path::PathBuf>,
@@ -163,6 +189,12 @@ impl Default for TxPoolArgs {
             new_tx_listener_buffer_size: NEW_TX_LISTENER_BUFFER_SIZE,
             max_new_pending_txs_notifications: MAX_NEW_PENDING_TXS_NOTIFICATIONS,
             max_queued_lifetime: MAX_QUEUED_TRANSACTION_LIFETIME,
+            replacement_suppression_window: DEFAULT_REPLACEMENT_SUPPRESSION_WINDOW,
+            sender_quota: None,
+            local_quota: None,
+            external_quota: None,
+            private_quota: None,
+            quota_evict_cheapest: false,
             transactions_backup_path: None,
             disable_transactions_backup: false,
         }
@@ -207,6 +239,19 @@ impl RethTransactionPoolConfig for TxPoolArgs {
             new_tx_listener_buffer_size: self.new_tx_listener_buffer_size,
             max_new_pending_txs_notifications: self.max_new_pending_txs_notifications,
             max_queued_lifetime: self.max_queued_lifetime,
+            replacement_suppression_window: self.replacement_suppression_window,
+            quotas: PoolQuotas {
+                per_sender: self.sender_quota,
+                local: self.local_quota,
+                external: self.external_quota,
+                private: self.private_quota,
+                eviction: if self.quota_evict_cheapest {
+                    QuotaEvictionPolicy::EvictCheapest
+                } else {
+                    QuotaEvictionPolicy::RejectNew
+                },
+            },
+            firewall: Default::default(),
         }
     }
 }
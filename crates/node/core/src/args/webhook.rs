@@ -0,0 +1,132 @@
+//! clap [Args](clap::Args) for webhook notifications
+
+use clap::Args;
+use humantime::parse_duration;
+use reth_fs_util::FsPathError;
+use std::{path::PathBuf, time::Duration};
+
+/// Default minimum depth of a reorg to notify about.
+const DEFAULT_REORG_DEPTH: u64 = 2;
+
+/// Default duration without a new canonical block after which the sync is considered stalled.
+const DEFAULT_SYNC_STALL: Duration = Duration::from_secs(120);
+
+/// Default number of blocks the finalized block may lag behind the head.
+const DEFAULT_FINALITY_LAG: u64 = 128;
+
+/// Default number of delivery retries per notification.
+const DEFAULT_MAX_RETRIES: u32 = 5;
+
+/// Parameters for webhook notifications on significant node events
+#[derive(Debug, Args, PartialEq, Eq, Clone)]
+#[command(next_help_heading = "Webhook")]
+pub struct WebhookArgs {
+    /// URL to POST webhook notifications to.
+    ///
+    /// Notifications are sent for deep reorgs, a stalled sync, a lagging finalized block and
+    /// pruner failures. Disabled if not set.
+    #[arg(long = "webhook.url", value_name = "URL")]
+    pub url: Option<String>,
+
+    /// Path to a file with the secret to sign webhook payloads with.
+    ///
+    /// If set, the hex encoded HMAC-SHA256 of the payload is sent in the `X-Reth-Signature`
+    /// header. Leading and trailing whitespace of the file is ignored.
+    #[arg(long = "webhook.secret-file", value_name = "PATH", requires = "url")]
+    pub secret_file: Option<PathBuf>,
+
+    /// Notify about reorgs that revert at least this many blocks.
+    #[arg(long = "webhook.reorg-depth", value_name = "BLOCKS", default_value_t = DEFAULT_REORG_DEPTH)]
+    pub reorg_depth: u64,
+
+    /// Notify if no new canonical block was received for this long.
+    #[arg(
+        long = "webhook.sync-stall",
+        value_name = "DURATION",
+        value_parser = parse_duration,
+        default_value = "2m"
+    )]
+    pub sync_stall: Duration,
+
+    /// Notify if the finalized block lags behind the head by more than this many blocks.
+    #[arg(long = "webhook.finality-lag", value_name = "BLOCKS", default_value_t = DEFAULT_FINALITY_LAG)]
+    pub finality_lag: u64,
+
+    /// Maximum number of retries to deliver a notification, with exponential backoff.
+    #[arg(long = "webhook.max-retries", value_name = "RETRIES", default_value_t = DEFAULT_MAX_RETRIES)]
+    pub max_retries: u32,
+}
+
+impl WebhookArgs {
+    /// Reads the secret from the [`WebhookArgs::secret_file`], if set.
+    pub fn secret(&self) -> Result<Option<String>, FsPathError> {
+        self.secret_file
+            .as_ref()
+            .map(|path| reth_fs_util::read_to_string(path).map(|secret| secret.trim().to_string()))
+            .transpose()
+    }
+}
+
+impl Default for WebhookArgs {
+    fn default() -> Self {
+        Self {
+            url: None,
+            secret_file: None,
+            reorg_depth: DEFAULT_REORG_DEPTH,
+            sync_stall: DEFAULT_SYNC_STALL,
+            finality_lag: DEFAULT_FINALITY_LAG,
+            max_retries: DEFAULT_MAX_RETRIES,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn test_parse_webhook_args() {
+        let args = CommandParser::<WebhookArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, WebhookArgs::default());
+
+        let args = CommandParser::<WebhookArgs>::parse_from([
+            "reth",
+            "--webhook.url",
+            "http://localhost:8080/hook",
+            "--webhook.secret-file",
+            "/run/secrets/webhook",
+            "--webhook.sync-stall",
+            "5m",
+        ])
+        .args;
+        assert_eq!(args.url.as_deref(), Some("http://localhost:8080/hook"));
+        assert_eq!(args.secret_file, Some(PathBuf::from("/run/secrets/webhook")));
+        assert_eq!(args.sync_stall, Duration::from_secs(300));
+
+        assert!(CommandParser::<WebhookArgs>::try_parse_from([
+            "reth",
+            "--webhook.secret-file",
+            "/run/secrets/webhook"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn read_secret_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("secret");
+        std::fs::write(&path, "s3cret\n").unwrap();
+
+        assert_eq!(WebhookArgs::default().secret().unwrap(), None);
+        let args = WebhookArgs { secret_file: Some(path), ..Default::default() };
+        assert_eq!(args.secret().unwrap().as_deref(), Some("s3cret"));
+    }
+}
@@ -35,6 +35,15 @@ pub trait PayloadBuilderConfig {
     /// Maximum number of tasks to spawn for building a payload.
     fn max_payload_tasks(&self) -> usize;
 
+    /// Maximum number of times a payload is rebuilt, unlimited if `None`.
+    fn max_iterations(&self) -> Option<usize>;
+
+    /// Whether the payload is rebuilt back to back until it is resolved.
+    fn until_resolved(&self) -> bool;
+
+    /// Maximum number of blobs to include in built blocks.
+    fn max_blobs_per_block(&self) -> Option<u64>;
+
     /// Returns the configured gas limit if set, or a chain-specific default.
     fn gas_limit_for(&self, chain: Chain) -> u64 {
         if let Some(limit) = self.gas_limit() {
@@ -2,12 +2,14 @@
 
 use crate::{args::DatadirArgs, utils::parse_path};
 use reth_chainspec::Chain;
+use reth_static_file_types::StaticFileSegment;
 use std::{
     env::VarError,
     fmt::{Debug, Display, Formatter},
     path::{Path, PathBuf},
     str::FromStr,
 };
+use url::Url;
 
 /// Constructs a string to be used as a path for configuration and db paths.
 pub fn config_path_prefix(chain: Chain) -> String {
@@ -301,6 +303,28 @@ impl<D> ChainPath<D> {
         }
     }
 
+    /// Returns the configured directories of the static file segments that are stored outside of
+    /// the static files directory.
+    pub fn static_files_segment_directories(&self) -> &[(StaticFileSegment, PathBuf)] {
+        &self.2.static_files_segment_directories
+    }
+
+    /// Returns the URL of the bucket that missing static files are read from, if configured.
+    pub fn static_files_s3(&self) -> Option<&Url> {
+        self.2.static_files_s3.as_ref()
+    }
+
+    /// Returns the region that requests to the static files bucket are signed for, if configured.
+    pub fn static_files_s3_region(&self) -> Option<&str> {
+        self.2.static_files_s3_region.as_deref()
+    }
+
+    /// Returns the maximum size of the static files downloaded from the static files bucket that
+    /// are kept locally, if configured.
+    pub const fn static_files_s3_cache_size(&self) -> Option<usize> {
+        self.2.static_files_s3_cache_size
+    }
+
     /// Returns the path to the reth p2p secret key for this chain.
     ///
     /// `<DIR>/<CHAIN_ID>/discovery-secret`
@@ -323,6 +347,22 @@ impl<D> ChainPath<D> {
         self.data_dir().join("blobstore")
     }
 
+    /// Returns the path to the directory for this chain where the blob sidecars of recent
+    /// canonical blocks are persisted.
+    ///
+    /// `<DIR>/<CHAIN_ID>/blob-sidecars`
+    pub fn blob_sidecars(&self) -> PathBuf {
+        self.data_dir().join("blob-sidecars")
+    }
+
+    /// Returns the path to the directory for this chain where static file chunks of peers are
+    /// downloaded to before they're verified.
+    ///
+    /// `<DIR>/<CHAIN_ID>/static-file-exchange`
+    pub fn static_file_exchange(&self) -> PathBuf {
+        self.data_dir().join("static-file-exchange")
+    }
+
     /// Returns the path to the local transactions backup file
     ///
     /// `<DIR>/<CHAIN_ID>/txpool-transactions-backup.rlp`
@@ -330,6 +370,28 @@ impl<D> ChainPath<D> {
         self.data_dir().join("txpool-transactions-backup.rlp")
     }
 
+    /// Returns the path to the executed, but not yet committed blocks of the execution stage
+    ///
+    /// `<DIR>/<CHAIN_ID>/execution-pending-batch`
+    pub fn execution_pending_batch(&self) -> PathBuf {
+        self.data_dir().join("execution-pending-batch")
+    }
+
+    /// Returns the path to the file the installed `eth` filters are persisted to.
+    ///
+    /// `<DIR>/<CHAIN_ID>/eth-filters.json`
+    pub fn eth_filters(&self) -> PathBuf {
+        self.data_dir().join("eth-filters.json")
+    }
+
+    /// Returns the path to the file the aggregate statistics of historical state queries are
+    /// persisted to.
+    ///
+    /// `<DIR>/<CHAIN_ID>/query-stats`
+    pub fn query_stats(&self) -> PathBuf {
+        self.data_dir().join("query-stats")
+    }
+
     /// Returns the path to the config file for this chain.
     ///
     /// `<DIR>/<CHAIN_ID>/reth.toml`
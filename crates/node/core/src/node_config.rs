@@ -31,10 +31,11 @@ use std::{
 };
 use tracing::*;
 
-use crate::args::EraArgs;
+use crate::args::{EraArgs, MemoryArgs, WebhookArgs};
 pub use reth_engine_primitives::{
     DEFAULT_MAX_PROOF_TASK_CONCURRENCY, DEFAULT_MEMORY_BLOCK_BUFFER_TARGET,
-    DEFAULT_RESERVED_CPU_CORES,
+    DEFAULT_MULTIPROOF_CHUNK_SIZE, DEFAULT_MULTIPROOF_CHUNK_TARGET_LATENCY,
+    DEFAULT_MULTIPROOF_MAX_CHUNK_SIZE, DEFAULT_RESERVED_CPU_CORES,
 };
 
 /// Triggers persistence when the number of canonical blocks in memory exceeds this threshold.
@@ -43,6 +44,9 @@ pub const DEFAULT_PERSISTENCE_THRESHOLD: u64 = 2;
 /// Default size of cross-block cache in megabytes.
 pub const DEFAULT_CROSS_BLOCK_CACHE_SIZE_MB: u64 = 4 * 1024;
 
+/// Default number of accounts, storage slots and bytecodes in the hot state cache.
+pub const DEFAULT_HOT_STATE_CACHE_ENTRIES: usize = 100_000;
+
 /// This includes all necessary configuration to launch the node.
 /// The individual configuration options can be overwritten before launching the node.
 ///
@@ -152,6 +156,18 @@ pub struct NodeConfig<ChainSpec> {
 
     /// All ERA import related arguments with --era prefix
     pub era: EraArgs,
+
+    /// All memory budget related arguments with --memory prefix
+    pub memory: MemoryArgs,
+
+    /// All webhook notification related arguments with --webhook prefix
+    pub webhook: WebhookArgs,
+
+    /// Start in safe mode if storage is damaged.
+    ///
+    /// Opens storage read-only, disables sync and pruning and serves RPC for the consistent range
+    /// of the chain.
+    pub safe_mode: bool,
 }
 
 impl NodeConfig<ChainSpec> {
@@ -182,6 +198,9 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
             datadir: DatadirArgs::default(),
             engine: EngineArgs::default(),
             era: EraArgs::default(),
+            memory: MemoryArgs::default(),
+            webhook: WebhookArgs::default(),
+            safe_mode: false,
         }
     }
 
@@ -270,7 +289,7 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
     }
 
     /// Set the database args for the node
-    pub const fn with_db(mut self, db: DatabaseArgs) -> Self {
+    pub fn with_db(mut self, db: DatabaseArgs) -> Self {
         self.db = db;
         self
     }
@@ -287,6 +306,24 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
         self
     }
 
+    /// Set the memory budget args for the node
+    pub const fn with_memory(mut self, memory: MemoryArgs) -> Self {
+        self.memory = memory;
+        self
+    }
+
+    /// Set the webhook notification args for the node
+    pub fn with_webhook(mut self, webhook: WebhookArgs) -> Self {
+        self.webhook = webhook;
+        self
+    }
+
+    /// Set whether the node should start in safe mode
+    pub const fn with_safe_mode(mut self, safe_mode: bool) -> Self {
+        self.safe_mode = safe_mode;
+        self
+    }
+
     /// Returns pruning configuration.
     pub fn prune_config(&self) -> Option<PruneConfig>
     where
@@ -295,6 +332,14 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
         self.pruning.prune_config(&self.chain)
     }
 
+    /// Returns the block below which the transaction history is expired.
+    pub fn history_expiry(&self) -> Option<BlockNumber>
+    where
+        ChainSpec: EthereumHardforks,
+    {
+        self.pruning.history_expiry(&self.chain)
+    }
+
     /// Returns the max block that the node should run to, looking it up from the network if
     /// necessary
     pub async fn max_block<Provider, Client>(
@@ -488,6 +533,9 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
             pruning: self.pruning,
             engine: self.engine,
             era: self.era,
+            memory: self.memory,
+            webhook: self.webhook,
+            safe_mode: self.safe_mode,
         }
     }
 }
@@ -510,12 +558,15 @@ impl<ChainSpec> Clone for NodeConfig<ChainSpec> {
             txpool: self.txpool.clone(),
             builder: self.builder.clone(),
             debug: self.debug.clone(),
-            db: self.db,
+            db: self.db.clone(),
             dev: self.dev,
             pruning: self.pruning.clone(),
             datadir: self.datadir.clone(),
             engine: self.engine.clone(),
             era: self.era.clone(),
+            memory: self.memory.clone(),
+            webhook: self.webhook.clone(),
+            safe_mode: self.safe_mode,
         }
     }
 }
@@ -1,15 +1,23 @@
 //! Events related to Consensus Layer health.
 
 use alloy_consensus::Header;
-use futures::Stream;
+use futures::{future::BoxFuture, FutureExt, Stream};
+use reth_metrics::{
+    metrics::{Counter, Gauge},
+    Metrics,
+};
+use reth_rpc_layer::{JwtRejectionReason, JwtRejections};
 use reth_storage_api::CanonChainTracker;
 use std::{
-    fmt,
+    fmt, io,
     pin::Pin,
     task::{ready, Context, Poll},
     time::Duration,
 };
-use tokio::time::{Instant, Interval};
+use tokio::{
+    net::TcpStream,
+    time::{Instant, Interval},
+};
 
 /// Interval of checking Consensus Layer client health.
 const CHECK_INTERVAL: Duration = Duration::from_secs(300);
@@ -18,24 +26,84 @@ const CHECK_INTERVAL: Duration = Duration::from_secs(300);
 /// after which the warning is issued.
 const NO_FORKCHOICE_UPDATE_RECEIVED_PERIOD: Duration = Duration::from_secs(120);
 
+/// Timeout of connecting to the Consensus Layer client endpoint.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
 /// A Stream of [`ConsensusLayerHealthEvent`].
+///
+/// If no updates were received from the Consensus Layer client within the configured period, the
+/// stream tries to diagnose why: requests rejected by the JWT validation of the auth server point
+/// to a mismatching secret or a skewed clock, and a failing connection to the configured endpoint
+/// points to a Consensus Layer client that is down.
 pub struct ConsensusLayerHealthEvents<H = Header> {
     interval: Interval,
     canon_chain: Box<dyn CanonChainTracker<Header = H>>,
+    /// Period of not receiving updates after which the Consensus Layer client is unhealthy.
+    no_update_period: Duration,
+    /// Requests rejected by the auth server.
+    jwt_rejections: Option<JwtRejections>,
+    /// Address of the Consensus Layer client that is probed when it is unhealthy.
+    cl_endpoint: Option<String>,
+    /// The pending probe of the Consensus Layer client endpoint.
+    probe: Option<BoxFuture<'static, ConsensusLayerHealthEvent>>,
+    metrics: ConsensusLayerHealthMetrics,
 }
 
 impl<H> fmt::Debug for ConsensusLayerHealthEvents<H> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("ConsensusLayerHealthEvents").field("interval", &self.interval).finish()
+        f.debug_struct("ConsensusLayerHealthEvents")
+            .field("interval", &self.interval)
+            .field("no_update_period", &self.no_update_period)
+            .field("cl_endpoint", &self.cl_endpoint)
+            .finish_non_exhaustive()
     }
 }
 
 impl<H> ConsensusLayerHealthEvents<H> {
     /// Creates a new [`ConsensusLayerHealthEvents`] with the given canonical chain tracker.
     pub fn new(canon_chain: Box<dyn CanonChainTracker<Header = H>>) -> Self {
-        // Skip the first tick to prevent the false `ConsensusLayerHealthEvent::NeverSeen` event.
-        let interval = tokio::time::interval_at(Instant::now() + CHECK_INTERVAL, CHECK_INTERVAL);
-        Self { interval, canon_chain }
+        Self {
+            interval: check_interval(NO_FORKCHOICE_UPDATE_RECEIVED_PERIOD),
+            canon_chain,
+            no_update_period: NO_FORKCHOICE_UPDATE_RECEIVED_PERIOD,
+            jwt_rejections: None,
+            cl_endpoint: None,
+            probe: None,
+            metrics: Default::default(),
+        }
+    }
+
+    /// Sets the period of not receiving updates after which the Consensus Layer client is
+    /// considered unhealthy.
+    pub fn with_no_update_period(mut self, period: Duration) -> Self {
+        self.interval = check_interval(period);
+        self.no_update_period = period;
+        self
+    }
+
+    /// Sets the requests rejected by the auth server, used to diagnose JWT issues.
+    pub fn with_jwt_rejections(mut self, rejections: JwtRejections) -> Self {
+        self.jwt_rejections = Some(rejections);
+        self
+    }
+
+    /// Sets the address of the Consensus Layer client that is probed when it is unhealthy.
+    pub fn with_cl_endpoint(mut self, endpoint: Option<String>) -> Self {
+        self.cl_endpoint = endpoint;
+        self
+    }
+
+    /// Returns the diagnosis of requests rejected by the auth server within the no update
+    /// period, if any.
+    fn jwt_diagnosis(&self) -> Option<ConsensusLayerHealthEvent> {
+        let since = std::time::Instant::now().checked_sub(self.no_update_period)?;
+        match self.jwt_rejections.as_ref()?.last_since(since)? {
+            JwtRejectionReason::SecretMismatch => {
+                Some(ConsensusLayerHealthEvent::JwtSecretMismatch)
+            }
+            JwtRejectionReason::ClockSkew => Some(ConsensusLayerHealthEvent::ClockSkew),
+            JwtRejectionReason::MissingToken | JwtRejectionReason::Other => None,
+        }
     }
 }
 
@@ -46,30 +114,74 @@ impl<H: Send + Sync> Stream for ConsensusLayerHealthEvents<H> {
         let this = self.get_mut();
 
         loop {
+            if let Some(probe) = &mut this.probe {
+                let event = ready!(probe.poll_unpin(cx));
+                this.probe = None;
+                return Poll::Ready(Some(event))
+            }
+
             ready!(this.interval.poll_tick(cx));
 
-            if let Some(fork_choice) = this.canon_chain.last_received_update_timestamp() {
-                if fork_choice.elapsed() <= NO_FORKCHOICE_UPDATE_RECEIVED_PERIOD {
+            let event = if let Some(fork_choice) = this.canon_chain.last_received_update_timestamp()
+            {
+                let elapsed = fork_choice.elapsed();
+                this.metrics.seconds_since_last_update.set(elapsed.as_secs_f64());
+                if elapsed <= this.no_update_period {
                     // We had an FCU, and it's recent. CL is healthy.
                     continue
                 }
                 // We had an FCU, but it's too old.
-                return Poll::Ready(Some(
-                    ConsensusLayerHealthEvent::HaveNotReceivedUpdatesForAWhile(
-                        fork_choice.elapsed(),
-                    ),
-                ))
+                ConsensusLayerHealthEvent::HaveNotReceivedUpdatesForAWhile(elapsed)
+            } else {
+                // We never had both FCU and transition config exchange.
+                ConsensusLayerHealthEvent::NeverSeen
+            };
+            this.metrics.unhealthy_checks.increment(1);
+
+            if let Some(diagnosis) = this.jwt_diagnosis() {
+                return Poll::Ready(Some(diagnosis))
+            }
+
+            if let Some(endpoint) = this.cl_endpoint.clone() {
+                this.probe = Some(
+                    async move {
+                        match probe(&endpoint).await {
+                            // The CL is up, but does not send any updates.
+                            Ok(()) => event,
+                            Err(err) => ConsensusLayerHealthEvent::Unreachable {
+                                endpoint,
+                                error: err.to_string(),
+                            },
+                        }
+                    }
+                    .boxed(),
+                );
+                continue
             }
 
-            // We never had both FCU and transition config exchange.
-            return Poll::Ready(Some(ConsensusLayerHealthEvent::NeverSeen))
+            return Poll::Ready(Some(event))
         }
     }
 }
 
+/// Returns the interval of checking Consensus Layer client health for the given no update period.
+fn check_interval(no_update_period: Duration) -> Interval {
+    let period = CHECK_INTERVAL.min(no_update_period);
+    // Skip the first tick to prevent the false `ConsensusLayerHealthEvent::NeverSeen` event.
+    tokio::time::interval_at(Instant::now() + period, period)
+}
+
+/// Connects to the given Consensus Layer client endpoint.
+async fn probe(endpoint: &str) -> io::Result<()> {
+    tokio::time::timeout(PROBE_TIMEOUT, TcpStream::connect(endpoint))
+        .await
+        .map_err(|_| io::Error::from(io::ErrorKind::TimedOut))??;
+    Ok(())
+}
+
 /// Event that is triggered when Consensus Layer health is degraded from the
 /// Execution Layer point of view.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug)]
 pub enum ConsensusLayerHealthEvent {
     /// Consensus Layer client was never seen.
     NeverSeen,
@@ -79,4 +191,27 @@ pub enum ConsensusLayerHealthEvent {
     NeverReceivedUpdates,
     /// Updates from the Consensus Layer client have not been received for a while.
     HaveNotReceivedUpdatesForAWhile(Duration),
+    /// Requests of the Consensus Layer client are rejected, because its JWT was not signed with
+    /// the configured secret.
+    JwtSecretMismatch,
+    /// Requests of the Consensus Layer client are rejected, because the issuance timestamp of its
+    /// JWT is too far from the local time.
+    ClockSkew,
+    /// The Consensus Layer client endpoint can not be reached.
+    Unreachable {
+        /// The configured endpoint.
+        endpoint: String,
+        /// The error of the connection attempt.
+        error: String,
+    },
+}
+
+/// Consensus Layer health metrics
+#[derive(Metrics)]
+#[metrics(scope = "consensus_layer")]
+struct ConsensusLayerHealthMetrics {
+    /// Seconds since the last update was received from the Consensus Layer client
+    seconds_since_last_update: Gauge,
+    /// Number of health checks that found no recent updates from the Consensus Layer client
+    unhealthy_checks: Counter,
 }
@@ -10,3 +10,4 @@
 
 pub mod cl;
 pub mod node;
+pub mod webhook;
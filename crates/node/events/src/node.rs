@@ -6,7 +6,8 @@ use alloy_primitives::{BlockNumber, B256};
 use alloy_rpc_types_engine::ForkchoiceState;
 use futures::Stream;
 use reth_engine_primitives::{
-    BeaconConsensusEngineEvent, ConsensusEngineLiveSyncProgress, ForkchoiceStatus,
+    BeaconConsensusEngineEvent, BlockProcessedReport, ConsensusEngineLiveSyncProgress,
+    ForkchoiceStatus,
 };
 use reth_network_api::PeersInfo;
 use reth_primitives_traits::{format_gas, format_gas_throughput, BlockBody, NodePrimitives};
@@ -45,12 +46,15 @@ struct NodeState {
     safe_block_hash: Option<B256>,
     /// Hash of finalized block last set by fork choice update
     finalized_block_hash: Option<B256>,
+    /// Whether block processed reports are additionally logged as JSON objects.
+    json_block_reports: bool,
 }
 
 impl NodeState {
     const fn new(
         peers_info: Option<Box<dyn PeersInfo>>,
         latest_block: Option<BlockNumber>,
+        json_block_reports: bool,
     ) -> Self {
         Self {
             peers_info,
@@ -60,6 +64,7 @@ impl NodeState {
             head_block_hash: None,
             safe_block_hash: None,
             finalized_block_hash: None,
+            json_block_reports,
         }
     }
 
@@ -252,7 +257,7 @@ impl NodeState {
             }
             BeaconConsensusEngineEvent::CanonicalBlockAdded(executed, elapsed) => {
                 let block = executed.sealed_block();
-                info!(
+                debug!(
                     number=block.number(),
                     hash=?block.hash(),
                     peers=self.num_connected_peers(),
@@ -284,6 +289,52 @@ impl NodeState {
             BeaconConsensusEngineEvent::BlockReceived(num_hash) => {
                 info!(number=num_hash.number, hash=?num_hash.hash, "Received block from consensus engine");
             }
+            BeaconConsensusEngineEvent::BlockProcessed(report) => {
+                self.handle_block_processed_report(&report);
+            }
+        }
+    }
+
+    fn handle_block_processed_report(&self, report: &BlockProcessedReport) {
+        let BlockProcessedReport { number, hash, origin, tx_count, gas_used, gas_limit, timings } =
+            report;
+        info!(
+            number,
+            ?hash,
+            %origin,
+            peers=self.num_connected_peers(),
+            txs=tx_count,
+            gas=%format_gas(*gas_used),
+            gas_throughput=?timings.execute.map(|execute| format_gas_throughput(*gas_used, execute)),
+            gas_limit=%format_gas(*gas_limit),
+            download=?timings.download,
+            validate=?timings.validate,
+            execute=?timings.execute,
+            state_root=?timings.state_root,
+            persist=?timings.persist,
+            "Block processed"
+        );
+
+        if self.json_block_reports {
+            let as_millis = |duration: Option<Duration>| {
+                duration.map(|duration| duration.as_secs_f64() * 1000.0)
+            };
+            let json = serde_json::json!({
+                "number": number,
+                "hash": hash,
+                "origin": origin.as_str(),
+                "tx_count": tx_count,
+                "gas_used": gas_used,
+                "gas_limit": gas_limit,
+                "timings_ms": {
+                    "download": as_millis(timings.download),
+                    "validate": as_millis(timings.validate),
+                    "execute": as_millis(timings.execute),
+                    "state_root": as_millis(timings.state_root),
+                    "persist": as_millis(timings.persist),
+                },
+            });
+            info!(target: "reth::block_report", report = %json, "Block processed report");
         }
     }
 
@@ -314,6 +365,23 @@ impl NodeState {
                         "Beacon client online, but no consensus updates received for a while. This may be because of a reth error, or an error in the beacon client! Please investigate reth and beacon client logs!"
                     )
                 }
+                ConsensusLayerHealthEvent::JwtSecretMismatch => {
+                    warn!(
+                        "No consensus updates received, beacon client requests are rejected because of an invalid JWT signature. Please ensure reth and the beacon client use the same JWT secret!"
+                    )
+                }
+                ConsensusLayerHealthEvent::ClockSkew => {
+                    warn!(
+                        "No consensus updates received, beacon client requests are rejected because the JWT issuance timestamp is too far from the local time. Please ensure the clocks of reth and the beacon client are synchronized!"
+                    )
+                }
+                ConsensusLayerHealthEvent::Unreachable { endpoint, error } => {
+                    warn!(
+                        %endpoint,
+                        %error,
+                        "No consensus updates received and the beacon client can not be reached. Please ensure your beacon client is running!"
+                    )
+                }
             }
         }
     }
@@ -330,6 +398,9 @@ impl NodeState {
                 );
                 debug!(tip_block_number, ?elapsed, pruned_segments = %stats, "Pruner finished");
             }
+            PrunerEvent::Failed { tip_block_number, error } => {
+                warn!(tip_block_number, %error, "Pruner failed");
+            }
         }
     }
 
@@ -392,14 +463,18 @@ pub enum NodeEvent<N: NodePrimitives> {
 
 /// Displays relevant information to the user from components of the node, and periodically
 /// displays the high-level status of the node.
+///
+/// If `json_block_reports` is set, the report of every processed block is additionally logged as
+/// a JSON object.
 pub async fn handle_events<E, N: NodePrimitives>(
     peers_info: Option<Box<dyn PeersInfo>>,
     latest_block_number: Option<BlockNumber>,
+    json_block_reports: bool,
     events: E,
 ) where
     E: Stream<Item = NodeEvent<N>> + Unpin,
 {
-    let state = NodeState::new(peers_info, latest_block_number);
+    let state = NodeState::new(peers_info, latest_block_number, json_block_reports);
 
     let start = tokio::time::Instant::now() + Duration::from_secs(3);
     let mut info_interval = tokio::time::interval_at(start, INFO_MESSAGE_INTERVAL);
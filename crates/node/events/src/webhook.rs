@@ -0,0 +1,505 @@
+//! Webhook notifications on significant node events.
+//!
+//! Notifications are POSTed as JSON to a configured URL for reorgs deeper than a threshold, a
+//! stalled sync, a finalized block lagging behind the head and pruner failures. Failed deliveries
+//! are retried with exponential backoff, and at most [`MAX_PENDING_DELIVERIES`] notifications are
+//! delivered at once.
+
+use alloy_eips::BlockNumHash;
+use alloy_primitives::{hex, BlockNumber};
+use futures::{Stream, StreamExt};
+use hmac::{Hmac, Mac};
+use reth_chain_state::CanonStateNotification;
+use reth_primitives_traits::NodePrimitives;
+use reth_prune_types::PrunerEvent;
+use reth_stages::PipelineEvent;
+use reth_storage_api::BlockIdReader;
+use reth_tasks::TaskExecutor;
+use serde_json::json;
+use sha2::Sha256;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+use tokio::sync::Semaphore;
+use tracing::{debug, warn};
+
+pub use reqwest::Url;
+
+/// The header that carries the hex encoded HMAC-SHA256 signature of the payload.
+pub const SIGNATURE_HEADER: &str = "X-Reth-Signature";
+
+/// Interval at which the sync is checked for a stall.
+const STALL_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Delay before the first retry of a failed delivery, doubled on every retry.
+const INITIAL_RETRY_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Timeout of a single delivery attempt.
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Maximum number of notifications that are delivered at once, further notifications are dropped.
+pub const MAX_PENDING_DELIVERIES: usize = 32;
+
+/// Configuration of the webhook notifications.
+#[derive(Debug, Clone)]
+pub struct WebhookConfig {
+    /// The URL to POST notifications to.
+    pub url: Url,
+    /// The secret to sign payloads with.
+    pub secret: Option<String>,
+    /// Minimum number of reverted blocks of a reorg to notify about.
+    pub reorg_depth: u64,
+    /// Duration without a new canonical block after which the sync is considered stalled.
+    pub sync_stall: Duration,
+    /// Maximum number of blocks the finalized block may lag behind the head.
+    pub finality_lag: u64,
+    /// Maximum number of retries to deliver a notification.
+    pub max_retries: u32,
+}
+
+/// A significant node event that is notified about.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WebhookEvent {
+    /// The canonical chain was reorged.
+    Reorg {
+        /// Number of reverted blocks.
+        depth: u64,
+        /// The tip of the reverted chain.
+        old_tip: BlockNumHash,
+        /// The tip of the new canonical chain, if any blocks were committed.
+        new_tip: Option<BlockNumHash>,
+    },
+    /// No new canonical block was received for a while.
+    SyncStalled {
+        /// The last canonical head.
+        head: BlockNumber,
+        /// The time since the last canonical head was received.
+        stalled_for: Duration,
+    },
+    /// The finalized block lags behind the head.
+    FinalityLag {
+        /// The canonical head.
+        head: BlockNumber,
+        /// The finalized block.
+        finalized: BlockNumber,
+    },
+    /// The pruner failed.
+    PrunerFailed {
+        /// The tip block number the pruner ran with.
+        tip_block_number: BlockNumber,
+        /// The error the pruner failed with.
+        error: String,
+    },
+}
+
+impl WebhookEvent {
+    /// Returns the event for a [`PrunerEvent`] worth notifying about.
+    pub fn from_pruner_event(event: &PrunerEvent) -> Option<Self> {
+        match event {
+            PrunerEvent::Failed { tip_block_number, error } => Some(Self::PrunerFailed {
+                tip_block_number: *tip_block_number,
+                error: error.clone(),
+            }),
+            PrunerEvent::Started { .. } | PrunerEvent::Finished { .. } => None,
+        }
+    }
+
+    /// Returns the name of the event.
+    pub const fn kind(&self) -> &'static str {
+        match self {
+            Self::Reorg { .. } => "reorg",
+            Self::SyncStalled { .. } => "sync_stalled",
+            Self::FinalityLag { .. } => "finality_lag",
+            Self::PrunerFailed { .. } => "pruner_failed",
+        }
+    }
+
+    /// Returns the JSON payload of the event, sent at the given unix timestamp.
+    pub fn payload(&self, timestamp: u64) -> serde_json::Value {
+        let data = match self {
+            Self::Reorg { depth, old_tip, new_tip } => json!({
+                "depth": depth,
+                "oldTip": { "number": old_tip.number, "hash": old_tip.hash },
+                "newTip": new_tip.map(|tip| json!({ "number": tip.number, "hash": tip.hash })),
+            }),
+            Self::SyncStalled { head, stalled_for } => json!({
+                "head": head,
+                "stalledForSecs": stalled_for.as_secs(),
+            }),
+            Self::FinalityLag { head, finalized } => json!({
+                "head": head,
+                "finalized": finalized,
+                "lag": head.saturating_sub(*finalized),
+            }),
+            Self::PrunerFailed { tip_block_number, error } => json!({
+                "tipBlockNumber": tip_block_number,
+                "error": error,
+            }),
+        };
+        json!({ "event": self.kind(), "timestamp": timestamp, "data": data })
+    }
+}
+
+/// Decides which events are notified about.
+///
+/// A stalled sync and a lagging finalized block are only notified about once, until the
+/// condition clears.
+#[derive(Debug)]
+pub struct WebhookTriggers {
+    reorg_depth: u64,
+    sync_stall: Duration,
+    finality_lag: u64,
+    /// The last canonical head and when it was received.
+    head: Option<(BlockNumber, Instant)>,
+    stalled: bool,
+    lagging: bool,
+}
+
+impl WebhookTriggers {
+    /// Creates the triggers with the thresholds of the config.
+    pub const fn new(config: &WebhookConfig) -> Self {
+        Self {
+            reorg_depth: config.reorg_depth,
+            sync_stall: config.sync_stall,
+            finality_lag: config.finality_lag,
+            head: None,
+            stalled: false,
+            lagging: false,
+        }
+    }
+
+    /// Returns the event for a reorg if it reverted at least the configured number of blocks.
+    pub fn on_reorg(
+        &self,
+        depth: u64,
+        old_tip: BlockNumHash,
+        new_tip: Option<BlockNumHash>,
+    ) -> Option<WebhookEvent> {
+        (depth >= self.reorg_depth).then_some(WebhookEvent::Reorg { depth, old_tip, new_tip })
+    }
+
+    /// Records progress of the sync that doesn't advance the canonical head, e.g. of a pipeline
+    /// backfill, so that it isn't reported as stalled.
+    pub fn on_sync_progress(&mut self, now: Instant) {
+        if let Some((_, received_at)) = &mut self.head {
+            *received_at = now;
+            self.stalled = false;
+        }
+    }
+
+    /// Records a new canonical head and returns the event for a lagging finalized block.
+    pub fn on_head(
+        &mut self,
+        head: BlockNumber,
+        finalized: Option<BlockNumber>,
+        now: Instant,
+    ) -> Option<WebhookEvent> {
+        self.head = Some((head, now));
+        self.stalled = false;
+
+        let finalized = finalized?;
+        let lagging = head.saturating_sub(finalized) > self.finality_lag;
+        let notify = lagging && !self.lagging;
+        self.lagging = lagging;
+        notify.then_some(WebhookEvent::FinalityLag { head, finalized })
+    }
+
+    /// Returns the event for a stalled sync.
+    ///
+    /// The sync can only stall after the first canonical head was received, so the initial sync
+    /// is not reported as stalled.
+    pub fn on_tick(&mut self, now: Instant) -> Option<WebhookEvent> {
+        let (head, received_at) = self.head?;
+        let stalled_for = now.saturating_duration_since(received_at);
+        if self.stalled || stalled_for < self.sync_stall {
+            return None
+        }
+        self.stalled = true;
+        Some(WebhookEvent::SyncStalled { head, stalled_for })
+    }
+}
+
+/// Returns the hex encoded HMAC-SHA256 of the payload.
+pub fn sign_payload(secret: &[u8], payload: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts keys of any size");
+    mac.update(payload);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Sends webhook notifications for the canonical state notifications and pruner events until the
+/// canonical state stream ends.
+///
+/// The pipeline events keep the sync from being reported as stalled while the pipeline backfills,
+/// which doesn't advance the canonical head. Deliveries are spawned on the executor.
+pub async fn notify_webhooks<N, Provider>(
+    config: WebhookConfig,
+    provider: Provider,
+    executor: TaskExecutor,
+    mut canon_state: impl Stream<Item = CanonStateNotification<N>> + Unpin,
+    mut pipeline_events: impl Stream<Item = PipelineEvent> + Unpin,
+    mut pruner_events: impl Stream<Item = PrunerEvent> + Unpin,
+) where
+    N: NodePrimitives,
+    Provider: BlockIdReader,
+{
+    let client = match reqwest::Client::builder().timeout(DELIVERY_TIMEOUT).build() {
+        Ok(client) => client,
+        Err(err) => {
+            warn!(target: "reth::webhook", %err, "Failed to create webhook client");
+            return
+        }
+    };
+    let deliveries = Arc::new(Semaphore::new(MAX_PENDING_DELIVERIES));
+    let mut triggers = WebhookTriggers::new(&config);
+    let mut interval = tokio::time::interval(STALL_CHECK_INTERVAL);
+
+    loop {
+        let mut events = Vec::new();
+        tokio::select! {
+            notification = canon_state.next() => {
+                let Some(notification) = notification else { return };
+                let committed = notification.committed();
+                let new_tip = committed.blocks().values().next_back().map(|block| block.num_hash());
+
+                if let Some(reverted) = notification.reverted() {
+                    let old_tip = reverted.tip().num_hash();
+                    events.extend(triggers.on_reorg(reverted.len() as u64, old_tip, new_tip));
+                }
+                if let Some(new_tip) = new_tip {
+                    let finalized = provider.finalized_block_number().ok().flatten();
+                    events.extend(triggers.on_head(new_tip.number, finalized, Instant::now()));
+                }
+            }
+            Some(_) = pipeline_events.next() => {
+                triggers.on_sync_progress(Instant::now());
+            }
+            Some(event) = pruner_events.next() => {
+                events.extend(WebhookEvent::from_pruner_event(&event));
+            }
+            _ = interval.tick() => {
+                events.extend(triggers.on_tick(Instant::now()));
+            }
+        }
+
+        for event in events {
+            let Ok(permit) = deliveries.clone().try_acquire_owned() else {
+                warn!(target: "reth::webhook", kind = event.kind(), "Dropping webhook notification, too many pending deliveries");
+                continue
+            };
+            debug!(target: "reth::webhook", kind = event.kind(), "Sending webhook notification");
+            let delivery = deliver(client.clone(), config.clone(), event);
+            executor.spawn(async move {
+                delivery.await;
+                drop(permit);
+            });
+        }
+    }
+}
+
+/// Delivers the notification, retrying failed deliveries with exponential backoff.
+///
+/// Returns `true` if the notification was delivered.
+async fn deliver(client: reqwest::Client, config: WebhookConfig, event: WebhookEvent) -> bool {
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+    let body = event.payload(timestamp).to_string();
+    let signature =
+        config.secret.as_ref().map(|secret| sign_payload(secret.as_bytes(), body.as_bytes()));
+
+    let mut backoff = INITIAL_RETRY_BACKOFF;
+    for attempt in 0..=config.max_retries {
+        let mut request = client
+            .post(config.url.clone())
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body.clone());
+        if let Some(signature) = &signature {
+            request = request.header(SIGNATURE_HEADER, signature);
+        }
+
+        match request.send().await.and_then(|response| response.error_for_status()) {
+            Ok(_) => return true,
+            Err(err) => {
+                debug!(target: "reth::webhook", %err, attempt, kind = event.kind(), "Failed to deliver webhook notification");
+            }
+        }
+
+        if attempt < config.max_retries {
+            tokio::time::sleep(backoff).await;
+            backoff *= 2;
+        }
+    }
+
+    warn!(target: "reth::webhook", kind = event.kind(), retries = config.max_retries, "Dropping undeliverable webhook notification");
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+
+    fn config() -> WebhookConfig {
+        WebhookConfig {
+            url: "http://localhost:8080".parse().unwrap(),
+            secret: None,
+            reorg_depth: 2,
+            sync_stall: Duration::from_secs(60),
+            finality_lag: 64,
+            max_retries: 0,
+        }
+    }
+
+    #[test]
+    fn reorg_depth_threshold() {
+        let triggers = WebhookTriggers::new(&config());
+        let old_tip = BlockNumHash::new(10, B256::with_last_byte(1));
+        let new_tip = Some(BlockNumHash::new(10, B256::with_last_byte(2)));
+
+        assert_eq!(triggers.on_reorg(1, old_tip, new_tip), None);
+        assert_eq!(
+            triggers.on_reorg(2, old_tip, new_tip),
+            Some(WebhookEvent::Reorg { depth: 2, old_tip, new_tip })
+        );
+    }
+
+    #[test]
+    fn sync_stall_notified_once() {
+        let mut triggers = WebhookTriggers::new(&config());
+        let start = Instant::now();
+
+        // no head received yet
+        assert_eq!(triggers.on_tick(start + Duration::from_secs(120)), None);
+
+        triggers.on_head(100, None, start);
+        assert_eq!(triggers.on_tick(start + Duration::from_secs(30)), None);
+        assert_eq!(
+            triggers.on_tick(start + Duration::from_secs(60)),
+            Some(WebhookEvent::SyncStalled { head: 100, stalled_for: Duration::from_secs(60) })
+        );
+        assert_eq!(triggers.on_tick(start + Duration::from_secs(90)), None);
+
+        // a new head clears the stall
+        triggers.on_head(101, None, start + Duration::from_secs(100));
+        assert!(triggers.on_tick(start + Duration::from_secs(160)).is_some());
+    }
+
+    #[test]
+    fn backfill_is_not_stalled() {
+        let mut triggers = WebhookTriggers::new(&config());
+        let start = Instant::now();
+        triggers.on_head(100, None, start);
+
+        // the pipeline makes progress without advancing the canonical head
+        triggers.on_sync_progress(start + Duration::from_secs(50));
+        assert_eq!(triggers.on_tick(start + Duration::from_secs(90)), None);
+
+        // until it stops making progress
+        assert_eq!(
+            triggers.on_tick(start + Duration::from_secs(110)),
+            Some(WebhookEvent::SyncStalled { head: 100, stalled_for: Duration::from_secs(60) })
+        );
+    }
+
+    /// Serves the given response statuses in order and returns the received requests.
+    async fn serve(statuses: Vec<u16>) -> (Url, tokio::task::JoinHandle<Vec<String>>) {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap()).parse().unwrap();
+        let server = tokio::spawn(async move {
+            let mut requests = Vec::new();
+            for status in statuses {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut request = Vec::new();
+                let mut buf = [0; 1024];
+                // the payload is a JSON object, so the request ends with its closing brace
+                while !request.ends_with(b"}") {
+                    let read = stream.read(&mut buf).await.unwrap();
+                    assert_ne!(read, 0);
+                    request.extend_from_slice(&buf[..read]);
+                }
+                let response = format!(
+                    "HTTP/1.1 {status} X\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                );
+                stream.write_all(response.as_bytes()).await.unwrap();
+                requests.push(String::from_utf8(request).unwrap());
+            }
+            requests
+        });
+        (url, server)
+    }
+
+    #[tokio::test]
+    async fn deliver_with_retry() {
+        let (url, server) = serve(vec![500, 200]).await;
+        let config =
+            WebhookConfig { url, secret: Some("secret".to_string()), max_retries: 1, ..config() };
+        let event = WebhookEvent::FinalityLag { head: 200, finalized: 100 };
+
+        let client = reqwest::Client::new();
+        assert!(deliver(client, config, event).await);
+
+        let requests = server.await.unwrap();
+        assert_eq!(requests.len(), 2);
+        for request in requests {
+            let request = request.to_lowercase();
+            assert!(request.starts_with("post / http/1.1"));
+            assert!(request.contains(&SIGNATURE_HEADER.to_lowercase()));
+            assert!(request.contains(r#""event":"finality_lag""#));
+        }
+    }
+
+    #[tokio::test]
+    async fn deliver_gives_up() {
+        let (url, server) = serve(vec![503]).await;
+        let config = WebhookConfig { url, max_retries: 0, ..config() };
+        let event = WebhookEvent::FinalityLag { head: 200, finalized: 100 };
+
+        assert!(!deliver(reqwest::Client::new(), config, event).await);
+        assert_eq!(server.await.unwrap().len(), 1);
+    }
+
+    #[test]
+    fn finality_lag_notified_once() {
+        let mut triggers = WebhookTriggers::new(&config());
+        let now = Instant::now();
+
+        assert_eq!(triggers.on_head(100, Some(50), now), None);
+        assert_eq!(
+            triggers.on_head(200, Some(100), now),
+            Some(WebhookEvent::FinalityLag { head: 200, finalized: 100 })
+        );
+        assert_eq!(triggers.on_head(201, Some(100), now), None);
+
+        // the lag recovers and lags again
+        assert_eq!(triggers.on_head(202, Some(200), now), None);
+        assert!(triggers.on_head(300, Some(200), now).is_some());
+    }
+
+    #[test]
+    fn pruner_failure() {
+        let event = PrunerEvent::Failed { tip_block_number: 5, error: "boom".to_string() };
+        let event = WebhookEvent::from_pruner_event(&event).unwrap();
+        assert_eq!(
+            event.payload(1),
+            json!({
+                "event": "pruner_failed",
+                "timestamp": 1,
+                "data": { "tipBlockNumber": 5, "error": "boom" },
+            })
+        );
+        assert_eq!(
+            WebhookEvent::from_pruner_event(&PrunerEvent::Started { tip_block_number: 5 }),
+            None
+        );
+    }
+
+    #[test]
+    fn hmac_signature() {
+        // RFC 4231 test case 2
+        assert_eq!(
+            sign_payload(b"Jefe", b"what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+}
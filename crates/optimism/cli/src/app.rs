@@ -3,6 +3,7 @@ use eyre::{eyre, Result};
 use reth_cli::chainspec::ChainSpecParser;
 use reth_cli_commands::launcher::Launcher;
 use reth_cli_runner::CliRunner;
+use reth_node_builder::shutdown::GRACEFUL_SHUTDOWN_TIMEOUT;
 use reth_node_metrics::recorder::install_prometheus_recorder;
 use reth_optimism_chainspec::OpChainSpec;
 use reth_optimism_consensus::OpBeaconConsensus;
@@ -66,9 +67,9 @@ where
         let _ = install_prometheus_recorder();
 
         match self.cli.command {
-            Commands::Node(command) => {
-                runner.run_command_until_exit(|ctx| command.execute(ctx, launcher))
-            }
+            Commands::Node(command) => runner
+                .with_graceful_shutdown_timeout(GRACEFUL_SHUTDOWN_TIMEOUT)
+                .run_command_until_exit(|ctx| command.execute(ctx, launcher)),
             Commands::Init(command) => {
                 runner.run_blocking_until_ctrl_c(command.execute::<OpNode>())
             }
@@ -90,6 +91,7 @@ where
             }),
             Commands::P2P(command) => runner.run_until_ctrl_c(command.execute::<OpNode>()),
             Commands::Config(command) => runner.run_until_ctrl_c(command.execute()),
+            Commands::Engine(command) => runner.run_until_ctrl_c(command.execute()),
             Commands::Recover(command) => {
                 runner.run_command_until_exit(|ctx| command.execute::<OpNode>(ctx))
             }
@@ -113,7 +113,12 @@ impl<C: ChainSpecParser<ChainSpec = OpChainSpec>> ImportOpCommand<C> {
 
             let latest_block_number =
                 provider.get_stage_checkpoint(StageId::Finish)?.map(|ch| ch.block_number);
-            tokio::spawn(reth_node_events::node::handle_events(None, latest_block_number, events));
+            tokio::spawn(reth_node_events::node::handle_events(
+                None,
+                latest_block_number,
+                false,
+                events,
+            ));
 
             // Run pipeline
             info!(target: "reth::cli", "Starting sync pipeline");
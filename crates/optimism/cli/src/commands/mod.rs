@@ -5,7 +5,7 @@ use import_receipts::ImportReceiptsOpCommand;
 use reth_chainspec::{EthChainSpec, EthereumHardforks, Hardforks};
 use reth_cli::chainspec::ChainSpecParser;
 use reth_cli_commands::{
-    config_cmd, db, dump_genesis, init_cmd,
+    config_cmd, db, dump_genesis, engine, init_cmd,
     node::{self, NoArgs},
     p2p, prune, recover, stage,
 };
@@ -52,6 +52,9 @@ pub enum Commands<Spec: ChainSpecParser = OpChainSpecParser, Ext: clap::Args + f
     /// Write config to stdout
     #[command(name = "config")]
     Config(config_cmd::Command),
+    /// Engine API debugging utilities
+    #[command(name = "engine")]
+    Engine(engine::Command),
     /// Scripts for node recovery
     #[command(name = "recover")]
     Recover(recover::Command<Spec>),
@@ -80,6 +83,7 @@ impl<
             Self::Stage(cmd) => cmd.chain_spec(),
             Self::P2P(cmd) => cmd.chain_spec(),
             Self::Config(_) => None,
+            Self::Engine(_) => None,
             Self::Recover(cmd) => cmd.chain_spec(),
             Self::Prune(cmd) => cmd.chain_spec(),
             Self::ImportOp(cmd) => cmd.chain_spec(),
@@ -27,7 +27,8 @@ use reth_node_builder::{
     node::{FullNodeTypes, NodeTypes},
     rpc::{
         EngineApiBuilder, EngineValidatorAddOn, EngineValidatorBuilder, EthApiBuilder, Identity,
-        RethRpcAddOns, RethRpcMiddleware, RethRpcServerHandles, RpcAddOns, RpcContext, RpcHandle,
+        RethHttpMiddleware, RethRpcAddOns, RethRpcMiddleware, RethRpcServerHandles, RpcAddOns,
+        RpcContext, RpcHandle,
     },
     BuilderContext, DebugNode, Node, NodeAdapter, NodeComponentsBuilder,
 };
@@ -398,6 +399,17 @@ where
         }
     }
 
+    /// Adds a new Http middleware layer around the HTTP and WS transports of the RPC server.
+    ///
+    /// See also [`RpcAddOns::layer_http_middleware`].
+    pub fn layer_http_middleware<T>(mut self, layer: T) -> Self
+    where
+        T: RethHttpMiddleware,
+    {
+        self.rpc_add_ons = self.rpc_add_ons.layer_http_middleware(layer);
+        self
+    }
+
     /// Sets the hook that is run once the rpc server is started.
     pub fn on_rpc_started<F>(mut self, hook: F) -> Self
     where
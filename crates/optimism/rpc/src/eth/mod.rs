@@ -266,6 +266,11 @@ where
     fn max_proof_window(&self) -> u64 {
         self.inner.eth_api.eth_proof_window()
     }
+
+    #[inline]
+    fn max_proof_response_size(&self) -> usize {
+        self.inner.eth_api.max_proof_response_size()
+    }
 }
 
 impl<N, NetworkT> EthFees for OpEthApi<N, NetworkT>
@@ -417,6 +422,7 @@ where
         .gas_cap(ctx.config.rpc_gas_cap.into())
         .max_simulate_blocks(ctx.config.rpc_max_simulate_blocks)
         .eth_proof_window(ctx.config.eth_proof_window)
+        .max_proof_response_size(ctx.config.max_proof_response_size)
         .fee_history_cache_config(ctx.config.fee_history_cache)
         .proof_permits(ctx.config.proof_permits)
         .gas_oracle_config(ctx.config.gas_oracle)
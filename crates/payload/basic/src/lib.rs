@@ -16,11 +16,16 @@ use futures_util::FutureExt;
 use reth_chain_state::CanonStateNotification;
 use reth_payload_builder::{KeepPayloadJobAlive, PayloadId, PayloadJob, PayloadJobGenerator};
 use reth_payload_builder_primitives::PayloadBuilderError;
-use reth_payload_primitives::{BuiltPayload, PayloadBuilderAttributes, PayloadKind};
+use reth_payload_primitives::{
+    BuiltPayload, PayloadBuilderAttributes, PayloadKind, PayloadTimings,
+};
 use reth_primitives_traits::{HeaderTy, NodePrimitives, SealedHeader};
 use reth_revm::{cached::CachedReads, cancelled::CancelOnDrop};
 use reth_storage_api::{BlockReaderIdExt, StateProviderFactory};
-use reth_tasks::TaskSpawner;
+use reth_tasks::{
+    time::{Interval, SharedClock, Sleep, SystemClock},
+    TaskSpawner,
+};
 use std::{
     fmt,
     future::Future,
@@ -28,12 +33,9 @@ use std::{
     pin::Pin,
     sync::Arc,
     task::{Context, Poll},
-    time::{Duration, SystemTime, UNIX_EPOCH},
-};
-use tokio::{
-    sync::{oneshot, Semaphore},
-    time::{Interval, Sleep},
+    time::{Duration, Instant},
 };
+use tokio::sync::{oneshot, Semaphore};
 use tracing::{debug, trace, warn};
 
 mod better_payload_emitter;
@@ -63,6 +65,8 @@ pub struct BasicPayloadJobGenerator<Client, Tasks, Builder> {
     builder: Builder,
     /// Stored `cached_reads` for new payload jobs.
     pre_cached: Option<PrecachedState>,
+    /// The clock that drives the interval and deadline of payload jobs.
+    clock: SharedClock,
 }
 
 // === impl BasicPayloadJobGenerator ===
@@ -83,9 +87,16 @@ impl<Client, Tasks, Builder> BasicPayloadJobGenerator<Client, Tasks, Builder> {
             config,
             builder,
             pre_cached: None,
+            clock: SystemClock::shared(),
         }
     }
 
+    /// Sets the clock that drives the interval and deadline of new payload jobs.
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
     /// Returns the maximum duration a job should be allowed to run.
     ///
     /// This adheres to the following specification:
@@ -96,7 +107,7 @@ impl<Client, Tasks, Builder> BasicPayloadJobGenerator<Client, Tasks, Builder> {
     /// See also <https://github.com/ethereum/execution-apis/blob/431cf72fd3403d946ca3e3afc36b973fc87e0e89/src/engine/paris.md?plain=1#L137>
     #[inline]
     fn max_job_duration(&self, unix_timestamp: u64) -> Duration {
-        let duration_until_timestamp = duration_until(unix_timestamp);
+        let duration_until_timestamp = duration_until(self.clock.unix_now(), unix_timestamp);
 
         // safety in case clocks are bad
         let duration_until_timestamp = duration_until_timestamp.min(self.config.deadline * 3);
@@ -104,11 +115,11 @@ impl<Client, Tasks, Builder> BasicPayloadJobGenerator<Client, Tasks, Builder> {
         self.config.deadline + duration_until_timestamp
     }
 
-    /// Returns the [Instant](tokio::time::Instant) at which the job should be terminated because it
-    /// is considered timed out.
+    /// Returns the [Instant] at which the job should be terminated because it is considered timed
+    /// out.
     #[inline]
-    fn job_deadline(&self, unix_timestamp: u64) -> tokio::time::Instant {
-        tokio::time::Instant::now() + self.max_job_duration(unix_timestamp)
+    fn job_deadline(&self, unix_timestamp: u64) -> Instant {
+        self.clock.now() + self.max_job_duration(unix_timestamp)
     }
 
     /// Returns a reference to the tasks type
@@ -161,7 +172,7 @@ where
         let config = PayloadConfig::new(Arc::new(parent_header.clone()), attributes);
 
         let until = self.job_deadline(config.attributes.timestamp());
-        let deadline = Box::pin(tokio::time::sleep_until(until));
+        let deadline = self.clock.sleep_until(until);
 
         let cached_reads = self.maybe_pre_cached(parent_header.hash());
 
@@ -170,7 +181,11 @@ where
             executor: self.executor.clone(),
             deadline,
             // ticks immediately
-            interval: tokio::time::interval(self.config.interval),
+            interval: Interval::new(self.clock.clone(), self.config.interval),
+            strategy: self.config.strategy,
+            wait_for_tick: false,
+            max_iterations: self.config.max_iterations,
+            iterations: 0,
             best_payload: PayloadState::Missing,
             pending_block: None,
             cached_reads,
@@ -248,6 +263,10 @@ pub struct BasicPayloadJobGeneratorConfig {
     deadline: Duration,
     /// Maximum number of tasks to spawn for building a payload.
     max_payload_tasks: usize,
+    /// Maximum number of payload builds per job, unlimited if `None`.
+    max_iterations: Option<usize>,
+    /// How new payload builds are scheduled.
+    strategy: PayloadBuildStrategy,
 }
 
 // === impl BasicPayloadJobGeneratorConfig ===
@@ -275,6 +294,18 @@ impl BasicPayloadJobGeneratorConfig {
         self.max_payload_tasks = max_payload_tasks;
         self
     }
+
+    /// Sets the maximum number of payload builds per job, `None` for no limit.
+    pub const fn max_iterations(mut self, max_iterations: Option<usize>) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Sets the strategy for scheduling new payload builds.
+    pub const fn strategy(mut self, strategy: PayloadBuildStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
 }
 
 impl Default for BasicPayloadJobGeneratorConfig {
@@ -284,10 +315,27 @@ impl Default for BasicPayloadJobGeneratorConfig {
             // 12s slot time
             deadline: SLOT_DURATION,
             max_payload_tasks: 3,
+            max_iterations: None,
+            strategy: PayloadBuildStrategy::default(),
         }
     }
 }
 
+/// Determines when a [`BasicPayloadJob`] starts a new payload build.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PayloadBuildStrategy {
+    /// Starts a new build on every tick of the configured interval.
+    #[default]
+    Interval,
+    /// Starts a new build as soon as the previous one improved the payload, so the payload keeps
+    /// improving until it is resolved.
+    ///
+    /// After a build that didn't improve the payload or failed, the next build starts on the next
+    /// tick of the configured interval. The job's deadline still applies if the payload is never
+    /// resolved.
+    UntilResolved,
+}
+
 /// A basic payload job that continuously builds a payload with the best transactions from the pool.
 ///
 /// This type is a [`PayloadJob`] and [`Future`] that terminates when the deadline is reached or
@@ -308,9 +356,18 @@ where
     /// How to spawn building tasks
     executor: Tasks,
     /// The deadline when this job should resolve.
-    deadline: Pin<Box<Sleep>>,
+    deadline: Sleep,
     /// The interval at which the job should build a new payload after the last.
     interval: Interval,
+    /// How new payload builds are scheduled.
+    strategy: PayloadBuildStrategy,
+    /// Whether the next build waits for the interval to tick, see
+    /// [`PayloadBuildStrategy::UntilResolved`].
+    wait_for_tick: bool,
+    /// Maximum number of payload builds, unlimited if `None`.
+    max_iterations: Option<usize>,
+    /// Number of payload builds spawned so far.
+    iterations: usize,
     /// The best payload so far and its state.
     best_payload: PayloadState<Builder::BuiltPayload>,
     /// Receiver for the block that is currently being built.
@@ -337,6 +394,13 @@ where
     Builder::Attributes: Unpin + Clone,
     Builder::BuiltPayload: Unpin + Clone,
 {
+    /// Returns true if a new payload build can be started.
+    fn can_build(&self) -> bool {
+        self.pending_block.is_none() &&
+            !self.best_payload.is_frozen() &&
+            self.max_iterations.is_none_or(|max| self.iterations < max)
+    }
+
     /// Spawns a new payload build task.
     fn spawn_build_job(&mut self) {
         trace!(target: "payload_builder", id = %self.config.payload_id(), "spawn new payload build task");
@@ -347,6 +411,7 @@ where
         let payload_config = self.config.clone();
         let best_payload = self.best_payload.payload().cloned();
         self.metrics.inc_initiated_payload_builds();
+        self.iterations += 1;
         let cached_reads = self.cached_reads.take().unwrap_or_default();
         let builder = self.builder.clone();
         self.executor.spawn_blocking(Box::pin(async move {
@@ -382,46 +447,62 @@ where
 
         // check if the interval is reached
         while this.interval.poll_tick(cx).is_ready() {
-            // start a new job if there is no pending block, we haven't reached the deadline,
-            // and the payload isn't frozen
-            if this.pending_block.is_none() && !this.best_payload.is_frozen() {
-                this.spawn_build_job();
+            match this.strategy {
+                // start a new job if there is no pending block, we haven't reached the deadline
+                // or the maximum number of builds, and the payload isn't frozen
+                PayloadBuildStrategy::Interval if this.can_build() => this.spawn_build_job(),
+                PayloadBuildStrategy::Interval => {}
+                PayloadBuildStrategy::UntilResolved => this.wait_for_tick = false,
             }
         }
 
-        // poll the pending block
-        if let Some(mut fut) = this.pending_block.take() {
-            match fut.poll_unpin(cx) {
-                Poll::Ready(Ok(outcome)) => match outcome {
-                    BuildOutcome::Better { payload, cached_reads } => {
-                        this.cached_reads = Some(cached_reads);
-                        debug!(target: "payload_builder", value = %payload.fees(), "built better payload");
-                        this.best_payload = PayloadState::Best(payload);
-                    }
-                    BuildOutcome::Freeze(payload) => {
-                        debug!(target: "payload_builder", "payload frozen, no further building will occur");
-                        this.best_payload = PayloadState::Frozen(payload);
-                    }
-                    BuildOutcome::Aborted { fees, cached_reads } => {
-                        this.cached_reads = Some(cached_reads);
-                        trace!(target: "payload_builder", worse_fees = %fees, "skipped payload build of worse block");
+        loop {
+            // poll the pending block
+            if let Some(mut fut) = this.pending_block.take() {
+                match fut.poll_unpin(cx) {
+                    Poll::Ready(Ok(outcome)) => match outcome {
+                        BuildOutcome::Better { payload, cached_reads } => {
+                            this.cached_reads = Some(cached_reads);
+                            debug!(target: "payload_builder", value = %payload.fees(), "built better payload");
+                            this.best_payload = PayloadState::Best(payload);
+                        }
+                        BuildOutcome::Freeze(payload) => {
+                            debug!(target: "payload_builder", "payload frozen, no further building will occur");
+                            this.best_payload = PayloadState::Frozen(payload);
+                        }
+                        BuildOutcome::Aborted { fees, cached_reads } => {
+                            this.cached_reads = Some(cached_reads);
+                            this.wait_for_tick = true;
+                            trace!(target: "payload_builder", worse_fees = %fees, "skipped payload build of worse block");
+                        }
+                        BuildOutcome::Cancelled => {
+                            unreachable!("the cancel signal never fired")
+                        }
+                    },
+                    Poll::Ready(Err(error)) => {
+                        // job failed, but we simply try again next interval
+                        debug!(target: "payload_builder", %error, "payload build attempt failed");
+                        this.metrics.inc_failed_payload_builds();
+                        this.wait_for_tick = true;
                     }
-                    BuildOutcome::Cancelled => {
-                        unreachable!("the cancel signal never fired")
+                    Poll::Pending => {
+                        this.pending_block = Some(fut);
                     }
-                },
-                Poll::Ready(Err(error)) => {
-                    // job failed, but we simply try again next interval
-                    debug!(target: "payload_builder", %error, "payload build attempt failed");
-                    this.metrics.inc_failed_payload_builds();
-                }
-                Poll::Pending => {
-                    this.pending_block = Some(fut);
                 }
             }
-        }
 
-        Poll::Pending
+            // keep improving the payload until it is resolved, and poll the new build so it wakes
+            // the job when it's done
+            if this.strategy == PayloadBuildStrategy::UntilResolved &&
+                !this.wait_for_tick &&
+                this.can_build()
+            {
+                this.spawn_build_job();
+                continue
+            }
+
+            return Poll::Pending
+        }
     }
 }
 
@@ -451,6 +532,10 @@ where
         }
     }
 
+    fn best_payload_timings(&self) -> Option<PayloadTimings> {
+        self.best_payload.payload().and_then(|payload| payload.timings())
+    }
+
     fn payload_attributes(&self) -> Result<Self::PayloadAttributes, PayloadBuilderError> {
         Ok(self.config.attributes.clone())
     }
@@ -891,11 +976,11 @@ pub fn is_better_payload<T: BuiltPayload>(best_payload: Option<&T>, new_fees: U2
     }
 }
 
-/// Returns the duration until the given unix timestamp in seconds.
+/// Returns the duration from the given current unix time until the given unix timestamp in
+/// seconds.
 ///
 /// Returns `Duration::ZERO` if the given timestamp is in the past.
-fn duration_until(unix_timestamp_secs: u64) -> Duration {
-    let unix_now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+fn duration_until(unix_now: Duration, unix_timestamp_secs: u64) -> Duration {
     let timestamp = Duration::from_secs(unix_timestamp_secs);
     timestamp.saturating_sub(unix_now)
 }
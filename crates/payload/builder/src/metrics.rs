@@ -1,9 +1,10 @@
 //! Payload builder service metrics.
 
 use reth_metrics::{
-    metrics::{Counter, Gauge},
+    metrics::{Counter, Gauge, Histogram},
     Metrics,
 };
+use reth_payload_primitives::PayloadTimings;
 
 /// Payload builder service metrics
 #[derive(Metrics, Clone)]
@@ -23,6 +24,14 @@ pub(crate) struct PayloadBuilderServiceMetrics {
     pub(crate) resolved_revenue: Gauge,
     /// Current block returned as the resolved payload
     pub(crate) resolved_block: Gauge,
+    /// Time spent selecting transactions for resolved payloads
+    pub(crate) resolved_tx_selection_duration: Histogram,
+    /// Time spent executing transactions for resolved payloads
+    pub(crate) resolved_execution_duration: Histogram,
+    /// Time spent calculating the state root for resolved payloads
+    pub(crate) resolved_state_root_duration: Histogram,
+    /// Time spent sealing resolved payloads
+    pub(crate) resolved_sealing_duration: Histogram,
 }
 
 impl PayloadBuilderServiceMetrics {
@@ -47,4 +56,11 @@ impl PayloadBuilderServiceMetrics {
         self.resolved_block.set(block as f64);
         self.resolved_revenue.set(value)
     }
+
+    pub(crate) fn record_resolved_timings(&self, timings: &PayloadTimings) {
+        self.resolved_tx_selection_duration.record(timings.tx_selection);
+        self.resolved_execution_duration.record(timings.execution);
+        self.resolved_state_root_duration.record(timings.state_root);
+        self.resolved_sealing_duration.record(timings.sealing);
+    }
 }
@@ -10,11 +10,15 @@ use crate::{
 use alloy_consensus::BlockHeader;
 use alloy_rpc_types::engine::PayloadId;
 use futures_util::{future::FutureExt, Stream, StreamExt};
+use parking_lot::Mutex;
 use reth_chain_state::CanonStateNotification;
 use reth_payload_builder_primitives::{Events, PayloadBuilderError, PayloadEvents};
-use reth_payload_primitives::{BuiltPayload, PayloadBuilderAttributes, PayloadKind, PayloadTypes};
+use reth_payload_primitives::{
+    BuiltPayload, PayloadBuilderAttributes, PayloadKind, PayloadTimings, PayloadTypes,
+};
 use reth_primitives_traits::NodePrimitives;
 use std::{
+    collections::VecDeque,
     fmt,
     future::Future,
     pin::Pin,
@@ -158,6 +162,15 @@ impl<T: PayloadTypes> PayloadBuilderHandle<T> {
         }
     }
 
+    /// Returns the timing breakdown of the best payload for the given identifier.
+    ///
+    /// Note: this also returns the timings of recently resolved payloads.
+    pub async fn payload_timings(&self, id: PayloadId) -> Option<PayloadTimings> {
+        let (tx, rx) = oneshot::channel();
+        self.to_service.send(PayloadServiceCommand::PayloadTimings(id, tx)).ok()?;
+        rx.await.ok()?
+    }
+
     /// Sends a message to the service to subscribe to payload events.
     /// Returns a receiver that will receive them.
     pub async fn subscribe(&self) -> Result<PayloadEvents<T>, PayloadBuilderError> {
@@ -218,10 +231,15 @@ where
     chain_events: St,
     /// Payload events handler, used to broadcast and subscribe to payload events.
     payload_events: broadcast::Sender<Events<T>>,
+    /// Timings of the most recently resolved payloads.
+    resolved_timings: Arc<Mutex<VecDeque<(PayloadId, PayloadTimings)>>>,
 }
 
 const PAYLOAD_EVENTS_BUFFER_SIZE: usize = 20;
 
+/// Number of resolved payloads for which timings are retained.
+const RESOLVED_TIMINGS_LIMIT: usize = 64;
+
 // === impl PayloadBuilderService ===
 
 impl<Gen, St, T> PayloadBuilderService<Gen, St, T>
@@ -249,6 +267,7 @@ where
             metrics: Default::default(),
             chain_events,
             payload_events,
+            resolved_timings: Default::default(),
         };
 
         let handle = service.handle();
@@ -279,6 +298,21 @@ where
         res
     }
 
+    /// Returns the timings of the best payload for the given identifier, falling back to recently
+    /// resolved payloads.
+    fn payload_timings(&self, id: PayloadId) -> Option<PayloadTimings> {
+        if let Some((job, _)) = self.payload_jobs.iter().find(|(_, job_id)| *job_id == id) {
+            return job.best_payload_timings()
+        }
+
+        self.resolved_timings
+            .lock()
+            .iter()
+            .rev()
+            .find(|(resolved_id, _)| *resolved_id == id)
+            .map(|(_, timings)| *timings)
+    }
+
     /// Returns the best payload for the given identifier that has been built so far and terminates
     /// the job if requested.
     fn resolve(
@@ -300,6 +334,7 @@ where
         // the future in a new future that will update the metrics.
         let resolved_metrics = self.metrics.clone();
         let payload_events = self.payload_events.clone();
+        let resolved_timings = self.resolved_timings.clone();
 
         let fut = async move {
             let res = fut.await;
@@ -310,6 +345,16 @@ where
 
                 resolved_metrics
                     .set_resolved_revenue(payload.block().number(), f64::from(payload.fees()));
+
+                if let Some(timings) = payload.timings() {
+                    resolved_metrics.record_resolved_timings(&timings);
+
+                    let mut resolved_timings = resolved_timings.lock();
+                    if resolved_timings.len() == RESOLVED_TIMINGS_LIMIT {
+                        resolved_timings.pop_front();
+                    }
+                    resolved_timings.push_back((id, timings));
+                }
             }
             res.map(|p| p.into())
         };
@@ -432,6 +477,9 @@ where
                     PayloadServiceCommand::Resolve(id, strategy, tx) => {
                         let _ = tx.send(this.resolve(id, strategy));
                     }
+                    PayloadServiceCommand::PayloadTimings(id, tx) => {
+                        let _ = tx.send(this.payload_timings(id));
+                    }
                     PayloadServiceCommand::Subscribe(tx) => {
                         let new_rx = this.payload_events.subscribe();
                         let _ = tx.send(new_rx);
@@ -466,6 +514,8 @@ pub enum PayloadServiceCommand<T: PayloadTypes> {
         /* kind: */ PayloadKind,
         oneshot::Sender<Option<PayloadFuture<T::BuiltPayload>>>,
     ),
+    /// Get the timing breakdown of the given payload
+    PayloadTimings(PayloadId, oneshot::Sender<Option<PayloadTimings>>),
     /// Payload service events
     Subscribe(oneshot::Sender<broadcast::Receiver<Events<T>>>),
 }
@@ -486,6 +536,9 @@ where
                 f.debug_tuple("PayloadAttributes").field(&f0).field(&f1).finish()
             }
             Self::Resolve(f0, f1, _f2) => f.debug_tuple("Resolve").field(&f0).field(&f1).finish(),
+            Self::PayloadTimings(f0, f1) => {
+                f.debug_tuple("PayloadTimings").field(&f0).field(&f1).finish()
+            }
             Self::Subscribe(f0) => f.debug_tuple("Subscribe").field(&f0).finish(),
         }
     }
@@ -2,7 +2,9 @@
 
 use reth_chain_state::CanonStateNotification;
 use reth_payload_builder_primitives::PayloadBuilderError;
-use reth_payload_primitives::{BuiltPayload, PayloadBuilderAttributes, PayloadKind};
+use reth_payload_primitives::{
+    BuiltPayload, PayloadBuilderAttributes, PayloadKind, PayloadTimings,
+};
 use reth_primitives_traits::NodePrimitives;
 use std::future::Future;
 
@@ -33,6 +35,14 @@ pub trait PayloadJob: Future<Output = Result<(), PayloadBuilderError>> + Send +
     /// Note: This is never called by the CL.
     fn best_payload(&self) -> Result<Self::BuiltPayload, PayloadBuilderError>;
 
+    /// Returns the timings of the best payload that has been built so far.
+    ///
+    /// Unlike [`PayloadJob::best_payload`] this must not build a payload, because it is called
+    /// from the payload builder service loop. Returns `None` if nothing has been built yet.
+    fn best_payload_timings(&self) -> Option<PayloadTimings> {
+        None
+    }
+
     /// Returns the payload attributes for the payload being built.
     fn payload_attributes(&self) -> Result<Self::PayloadAttributes, PayloadBuilderError>;
 
@@ -32,6 +32,9 @@ pub use traits::{
 mod payload;
 pub use payload::{ExecutionPayload, PayloadOrAttributes};
 
+mod timings;
+pub use timings::PayloadTimings;
+
 /// Core trait that defines the associated types for working with execution payloads.
 pub trait PayloadTypes: Send + Sync + Unpin + core::fmt::Debug + Clone + 'static {
     /// The format for execution payload data that can be processed and validated.
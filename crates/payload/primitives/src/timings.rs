@@ -0,0 +1,76 @@
+//! Timing breakdown of a built payload.
+
+use core::time::Duration;
+use serde::{Deserialize, Serialize};
+
+/// Breakdown of the time spent building a payload.
+///
+/// Durations are serialized as microseconds.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PayloadTimings {
+    /// Time spent selecting transactions from the pool.
+    #[serde(with = "duration_micros")]
+    pub tx_selection: Duration,
+    /// Time spent executing the selected transactions.
+    #[serde(with = "duration_micros")]
+    pub execution: Duration,
+    /// Time spent calculating the state root.
+    #[serde(with = "duration_micros")]
+    pub state_root: Duration,
+    /// Time spent assembling and sealing the block.
+    #[serde(with = "duration_micros")]
+    pub sealing: Duration,
+}
+
+impl PayloadTimings {
+    /// Returns the total time spent building the payload.
+    pub fn total(&self) -> Duration {
+        self.tx_selection + self.execution + self.state_root + self.sealing
+    }
+}
+
+mod duration_micros {
+    use core::time::Duration;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub(super) fn serialize<S: Serializer>(
+        value: &Duration,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(value.as_micros() as u64)
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Duration, D::Error> {
+        u64::deserialize(deserializer).map(Duration::from_micros)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_payload_timings() {
+        let timings = PayloadTimings {
+            tx_selection: Duration::from_micros(10),
+            execution: Duration::from_millis(2),
+            state_root: Duration::from_millis(5),
+            sealing: Duration::from_micros(300),
+        };
+        let json = serde_json::to_value(timings).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "txSelection": 10,
+                "execution": 2000,
+                "stateRoot": 5000,
+                "sealing": 300
+            })
+        );
+        assert_eq!(serde_json::from_value::<PayloadTimings>(json).unwrap(), timings);
+        assert_eq!(timings.total(), Duration::from_micros(7310));
+    }
+}
@@ -11,6 +11,8 @@ use core::fmt;
 use reth_chain_state::ExecutedBlockWithTrieUpdates;
 use reth_primitives_traits::{NodePrimitives, SealedBlock};
 
+use crate::PayloadTimings;
+
 /// Represents a successfully built execution payload (block).
 ///
 /// Provides access to the underlying block data, execution results, and associated metadata
@@ -38,6 +40,13 @@ pub trait BuiltPayload: Send + Sync + fmt::Debug {
     /// These are requests generated by the execution layer that need to be
     /// processed by the consensus layer (e.g., validator deposits, withdrawals).
     fn requests(&self) -> Option<Requests>;
+
+    /// Returns the breakdown of the time spent building this payload.
+    ///
+    /// Returns `None` if the builder does not track timings.
+    fn timings(&self) -> Option<PayloadTimings> {
+        None
+    }
 }
 
 /// Attributes used to guide the construction of a new execution payload.
@@ -1,3 +1,88 @@
 //! Crypto utilities.
 
 pub use alloy_consensus::crypto::*;
+
+use alloc::vec::Vec;
+use alloy_primitives::{Address, Signature, B256};
+use core::fmt::Debug;
+
+/// A backend that recovers the signers of signature hashes.
+///
+/// The default backend is [`Secp256k1Recovery`], which recovers on the CPU. Alternative backends,
+/// e.g. batch recovery on a GPU or a remote signer service, are configured as a component of the
+/// node and used by the sender recovery stage. Signers of single transactions, e.g. in the
+/// transaction pool or RPC, are always recovered with secp256k1.
+pub trait RecoveryBackend: Debug + Send + Sync + 'static {
+    /// Recovers the signer of the signature hash _without ensuring that the signature has a low
+    /// `s` value_.
+    fn recover_signer_unchecked(
+        &self,
+        signature: &Signature,
+        hash: B256,
+    ) -> Result<Address, RecoveryError>;
+
+    /// Recovers the signer of the signature hash.
+    ///
+    /// Returns an error if the signature has a high `s` value, see
+    /// [EIP-2](https://github.com/ethereum/EIPs/blob/master/EIPS/eip-2.md).
+    fn recover_signer(&self, signature: &Signature, hash: B256) -> Result<Address, RecoveryError> {
+        if signature.s() > SECP256K1N_HALF {
+            return Err(RecoveryError::new())
+        }
+        self.recover_signer_unchecked(signature, hash)
+    }
+
+    /// Recovers the signers of a batch of signature hashes _without ensuring that the signatures
+    /// have a low `s` value_.
+    ///
+    /// Returns one result per entry of the batch, in the same order. Backends that benefit from
+    /// batching, e.g. GPU offloading, should override this.
+    fn recover_signers_unchecked(
+        &self,
+        batch: &[(Signature, B256)],
+    ) -> Vec<Result<Address, RecoveryError>> {
+        batch
+            .iter()
+            .map(|(signature, hash)| self.recover_signer_unchecked(signature, *hash))
+            .collect()
+    }
+}
+
+/// The default [`RecoveryBackend`] that recovers signers on the CPU with secp256k1.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Secp256k1Recovery;
+
+impl RecoveryBackend for Secp256k1Recovery {
+    fn recover_signer_unchecked(
+        &self,
+        signature: &Signature,
+        hash: B256,
+    ) -> Result<Address, RecoveryError> {
+        secp256k1::recover_signer_unchecked(signature, hash)
+    }
+
+    fn recover_signer(&self, signature: &Signature, hash: B256) -> Result<Address, RecoveryError> {
+        secp256k1::recover_signer(signature, hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn secp256k1_batch_recovery() {
+        let secret = B256::with_last_byte(1);
+        let hashes = [B256::with_last_byte(2), B256::with_last_byte(3)];
+        let batch = hashes
+            .iter()
+            .map(|hash| (secp256k1::sign_message(secret, *hash).unwrap(), *hash))
+            .collect::<Vec<_>>();
+
+        let signers = Secp256k1Recovery.recover_signers_unchecked(&batch);
+        assert_eq!(signers.len(), batch.len());
+        for ((signature, hash), signer) in batch.iter().zip(signers) {
+            assert_eq!(signer.unwrap(), secp256k1::recover_signer(signature, *hash).unwrap());
+        }
+    }
+}
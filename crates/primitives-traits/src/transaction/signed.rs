@@ -59,6 +59,15 @@ pub trait SignedTransaction:
         !self.is_eip4844()
     }
 
+    /// Returns the signature and the signature hash the signer is recovered from.
+    ///
+    /// Returns `None` if the signer isn't recovered from a signature, e.g. for deposit
+    /// transactions, or if the transaction doesn't support recovery through a
+    /// [`RecoveryBackend`](crate::crypto::RecoveryBackend).
+    fn signature_with_hash(&self) -> Option<(Signature, B256)> {
+        None
+    }
+
     /// Recover signer from signature and hash.
     ///
     /// Returns an error if the transaction's signature is invalid.
@@ -139,6 +148,16 @@ where
             Self::Eip4844(tx) => tx.hash(),
         }
     }
+
+    fn signature_with_hash(&self) -> Option<(Signature, B256)> {
+        Some(match self {
+            Self::Legacy(tx) => (*tx.signature(), tx.signature_hash()),
+            Self::Eip2930(tx) => (*tx.signature(), tx.signature_hash()),
+            Self::Eip1559(tx) => (*tx.signature(), tx.signature_hash()),
+            Self::Eip7702(tx) => (*tx.signature(), tx.signature_hash()),
+            Self::Eip4844(tx) => (*tx.signature(), tx.signature_hash()),
+        })
+    }
 }
 
 #[cfg(feature = "op")]
@@ -155,6 +174,15 @@ mod op {
                 Self::Eip7702(tx) => tx.hash(),
             }
         }
+
+        fn signature_with_hash(&self) -> Option<(Signature, B256)> {
+            Some(match self {
+                Self::Legacy(tx) => (*tx.signature(), tx.signature_hash()),
+                Self::Eip2930(tx) => (*tx.signature(), tx.signature_hash()),
+                Self::Eip1559(tx) => (*tx.signature(), tx.signature_hash()),
+                Self::Eip7702(tx) => (*tx.signature(), tx.signature_hash()),
+            })
+        }
     }
 
     impl SignedTransaction for OpTxEnvelope {
@@ -167,5 +195,16 @@ mod op {
                 Self::Deposit(tx) => tx.hash_ref(),
             }
         }
+
+        fn signature_with_hash(&self) -> Option<(Signature, B256)> {
+            match self {
+                Self::Legacy(tx) => Some((*tx.signature(), tx.signature_hash())),
+                Self::Eip2930(tx) => Some((*tx.signature(), tx.signature_hash())),
+                Self::Eip1559(tx) => Some((*tx.signature(), tx.signature_hash())),
+                Self::Eip7702(tx) => Some((*tx.signature(), tx.signature_hash())),
+                // the signer of deposit transactions isn't recovered from a signature
+                Self::Deposit(_) => None,
+            }
+        }
     }
 }
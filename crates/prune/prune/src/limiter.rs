@@ -1,4 +1,5 @@
 use reth_prune_types::{PruneInterruptReason, PruneProgress};
+use reth_tasks::time::{SharedClock, SystemClock};
 use std::{
     num::NonZeroUsize,
     time::{Duration, Instant},
@@ -38,15 +39,17 @@ struct PruneTimeLimit {
     limit: Duration,
     /// Time when the prune run has started.
     start: Instant,
+    /// The clock the run time is measured with.
+    clock: SharedClock,
 }
 
 impl PruneTimeLimit {
-    fn new(limit: Duration) -> Self {
-        Self { limit, start: Instant::now() }
+    fn new(limit: Duration, clock: SharedClock) -> Self {
+        Self { limit, start: clock.now(), clock }
     }
 
     fn is_limit_reached(&self) -> bool {
-        self.start.elapsed() > self.limit
+        self.clock.elapsed(self.start) > self.limit
     }
 }
 
@@ -105,8 +108,13 @@ impl PruneLimiter {
     }
 
     /// Sets the time limit.
-    pub fn set_time_limit(mut self, limit: Duration) -> Self {
-        self.time_limit = Some(PruneTimeLimit::new(limit));
+    pub fn set_time_limit(self, limit: Duration) -> Self {
+        self.set_time_limit_with_clock(limit, SystemClock::shared())
+    }
+
+    /// Sets the time limit, measured with the given clock.
+    pub fn set_time_limit_with_clock(mut self, limit: Duration, clock: SharedClock) -> Self {
+        self.time_limit = Some(PruneTimeLimit::new(limit, clock));
 
         self
     }
@@ -149,7 +157,8 @@ impl PruneLimiter {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::thread::sleep;
+    use reth_tasks::time::ManualClock;
+    use std::{sync::Arc, thread::sleep};
 
     #[test]
     fn test_prune_deleted_entries_limit_initial_state() {
@@ -179,7 +188,7 @@ mod tests {
 
     #[test]
     fn test_prune_time_limit_initial_state() {
-        let time_limit = PruneTimeLimit::new(Duration::from_secs(10));
+        let time_limit = PruneTimeLimit::new(Duration::from_secs(10), SystemClock::shared());
         // The limit should be set correctly
         assert_eq!(time_limit.limit, Duration::from_secs(10));
         // The elapsed time should be very small right after creation
@@ -190,14 +199,15 @@ mod tests {
 
     #[test]
     fn test_prune_time_limit_is_limit_reached() {
-        let time_limit = PruneTimeLimit::new(Duration::from_millis(50));
+        let clock = ManualClock::new();
+        let time_limit = PruneTimeLimit::new(Duration::from_millis(50), Arc::new(clock.clone()));
 
-        // Simulate waiting for some time (less than the limit)
-        std::thread::sleep(Duration::from_millis(30));
+        // Advance the time by less than the limit
+        clock.advance(Duration::from_millis(30));
         assert!(!time_limit.is_limit_reached());
 
-        // Simulate waiting for time greater than the limit
-        std::thread::sleep(Duration::from_millis(30));
+        // Advance the time beyond the limit
+        clock.advance(Duration::from_millis(30));
         assert!(time_limit.is_limit_reached());
     }
 
@@ -10,8 +10,9 @@ use reth_provider::{
     DBProvider, DatabaseProviderFactory, PruneCheckpointReader, PruneCheckpointWriter,
 };
 use reth_prune_types::{PruneProgress, PrunedSegmentInfo, PrunerOutput};
+use reth_tasks::time::{SharedClock, SystemClock};
 use reth_tokio_util::{EventSender, EventStream};
-use std::time::{Duration, Instant};
+use std::time::Duration;
 use tokio::sync::watch;
 use tracing::debug;
 
@@ -47,6 +48,8 @@ pub struct Pruner<Provider, PF> {
     delete_limit: usize,
     /// Maximum time for a one pruner run.
     timeout: Option<Duration>,
+    /// The clock the run time is measured with.
+    clock: SharedClock,
     /// The finished height of all `ExEx`'s.
     finished_exex_height: watch::Receiver<FinishedExExHeight>,
     #[doc(hidden)]
@@ -70,6 +73,7 @@ impl<Provider> Pruner<Provider, ()> {
             previous_tip_block_number: None,
             delete_limit,
             timeout,
+            clock: SystemClock::shared(),
             finished_exex_height,
             metrics: Metrics::default(),
             event_sender: Default::default(),
@@ -97,6 +101,7 @@ where
             previous_tip_block_number: None,
             delete_limit,
             timeout,
+            clock: SystemClock::shared(),
             finished_exex_height,
             metrics: Metrics::default(),
             event_sender: Default::default(),
@@ -108,6 +113,12 @@ impl<Provider, S> Pruner<Provider, S>
 where
     Provider: PruneCheckpointReader + PruneCheckpointWriter,
 {
+    /// Sets the clock the run time and the timeout of the pruner are measured with.
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
     /// Listen for events on the pruner.
     pub fn events(&self) -> EventStream<PrunerEvent> {
         self.event_sender.new_listener()
@@ -138,19 +149,22 @@ where
         self.event_sender.notify(PrunerEvent::Started { tip_block_number });
 
         debug!(target: "pruner", %tip_block_number, "Pruner started");
-        let start = Instant::now();
+        let start = self.clock.now();
 
         let mut limiter = PruneLimiter::default().set_deleted_entries_limit(self.delete_limit);
         if let Some(timeout) = self.timeout {
-            limiter = limiter.set_time_limit(timeout);
+            limiter = limiter.set_time_limit_with_clock(timeout, self.clock.clone());
         };
 
         let (stats, deleted_entries, output) =
-            self.prune_segments(provider, tip_block_number, &mut limiter)?;
+            self.prune_segments(provider, tip_block_number, &mut limiter).inspect_err(|err| {
+                self.event_sender
+                    .notify(PrunerEvent::Failed { tip_block_number, error: err.to_string() })
+            })?;
 
         self.previous_tip_block_number = Some(tip_block_number);
 
-        let elapsed = start.elapsed();
+        let elapsed = self.clock.elapsed(start);
         self.metrics.duration_seconds.record(elapsed);
 
         let message = match output.progress {
@@ -215,7 +229,7 @@ where
                     "Segment pruning started"
                 );
 
-                let segment_start = Instant::now();
+                let segment_start = self.clock.now();
                 let previous_checkpoint = provider.get_prune_checkpoint(segment.segment())?;
                 let segment_output = segment.prune(
                     provider,
@@ -228,7 +242,7 @@ where
                 self.metrics
                     .get_prune_segment_metrics(segment.segment())
                     .duration_seconds
-                    .record(segment_start.elapsed());
+                    .record(self.clock.elapsed(segment_start));
                 if let Some(highest_pruned_block) =
                     segment_output.checkpoint.and_then(|checkpoint| checkpoint.block_number)
                 {
@@ -1,5 +1,5 @@
 use crate::PrunedSegmentInfo;
-use alloc::vec::Vec;
+use alloc::{string::String, vec::Vec};
 use alloy_primitives::BlockNumber;
 use core::time::Duration;
 
@@ -20,4 +20,11 @@ pub enum PrunerEvent {
         /// Collected pruning stats.
         stats: Vec<PrunedSegmentInfo>,
     },
+    /// Emitted when pruner failed.
+    Failed {
+        /// The tip block number before pruning.
+        tip_block_number: BlockNumber,
+        /// The error the pruner failed with.
+        error: String,
+    },
 }
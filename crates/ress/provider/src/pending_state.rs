@@ -124,7 +124,8 @@ pub async fn maintain_pending_state<P>(
             // ignore
             BeaconConsensusEngineEvent::CanonicalChainCommitted(_, _) |
             BeaconConsensusEngineEvent::BlockReceived(_) |
-            BeaconConsensusEngineEvent::LiveSyncProgress(_) => (),
+            BeaconConsensusEngineEvent::LiveSyncProgress(_) |
+            BeaconConsensusEngineEvent::BlockProcessed(_) => (),
         }
     }
 }
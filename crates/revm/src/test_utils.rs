@@ -119,6 +119,15 @@ impl StorageRootProvider for StateProviderTest {
     ) -> ProviderResult<StorageMultiProof> {
         unimplemented!("proof generation is not supported")
     }
+
+    fn storage_multiproof_from_nodes(
+        &self,
+        _input: TrieInput,
+        _address: Address,
+        _slots: &[B256],
+    ) -> ProviderResult<StorageMultiProof> {
+        unimplemented!("proof generation is not supported")
+    }
 }
 
 impl StateProofProvider for StateProviderTest {
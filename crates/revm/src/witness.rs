@@ -1,7 +1,7 @@
 use alloc::vec::Vec;
 use alloy_primitives::{keccak256, Bytes, B256};
 use reth_trie::{HashedPostState, HashedStorage};
-use revm::database::State;
+use revm::{database::State, state::EvmState};
 
 /// Tracks state changes during execution.
 #[derive(Debug, Clone, Default)]
@@ -74,6 +74,43 @@ impl ExecutionWitnessRecord {
         self.lowest_block_number = statedb.block_hashes.keys().next().copied()
     }
 
+    /// Records the state accessed by a single transaction, i.e. the accounts, storage slots and
+    /// contract codes loaded during its execution, with their values after the transaction.
+    ///
+    /// Unlike [`Self::record_executed_state`], this does not record the accessed block hashes.
+    pub fn record_transaction_state(&mut self, state: &EvmState) {
+        for (address, account) in state {
+            let hashed_address = keccak256(address);
+            let destroyed = account.is_selfdestructed();
+            let exists = !destroyed && !account.info.is_empty();
+            self.hashed_state
+                .accounts
+                .insert(hashed_address, exists.then(|| (&account.info).into()));
+
+            let storage = self
+                .hashed_state
+                .storages
+                .entry(hashed_address)
+                .or_insert_with(|| HashedStorage::new(destroyed));
+
+            if let Some(code) = account.info.code.as_ref().filter(|code| !code.is_empty()) {
+                self.codes.push(code.original_bytes());
+            }
+
+            if exists {
+                self.keys.push(address.to_vec().into());
+
+                for (slot, value) in &account.storage {
+                    let slot = B256::from(*slot);
+                    let hashed_slot = keccak256(slot);
+                    storage.storage.insert(hashed_slot, value.present_value);
+
+                    self.keys.push(slot.into());
+                }
+            }
+        }
+    }
+
     /// Creates the record from the state after execution.
     pub fn from_executed_state<DB>(state: &State<DB>) -> Self {
         let mut record = Self::default();
@@ -118,6 +118,20 @@ where
         Ok(server_handle)
     }
 
+    /// Returns the options used to create the listener.
+    ///
+    /// On unix the configured socket permissions are applied while the socket is created, so
+    /// there is no window in which the socket is reachable with the default permissions.
+    fn listener_options(&self) -> ListenerOptions<'_> {
+        let options = ListenerOptions::new();
+        #[cfg(unix)]
+        if let Some(mode) = self.cfg.socket_permissions {
+            use interprocess::os::unix::local_socket::ListenerOptionsExt;
+            return options.mode(mode as _)
+        }
+        options
+    }
+
     async fn start_inner(
         self,
         methods: Methods,
@@ -137,7 +151,7 @@ where
             .endpoint
             .as_str()
             .to_fs_name::<GenericFilePath>()
-            .and_then(|name| ListenerOptions::new().name(name).create_tokio())
+            .and_then(|name| self.listener_options().name(name).create_tokio())
         {
             Ok(listener) => listener,
             Err(err) => {
@@ -550,6 +564,8 @@ pub struct Settings {
     message_buffer_capacity: u32,
     /// Custom tokio runtime to run the server on.
     tokio_runtime: Option<tokio::runtime::Handle>,
+    /// File permissions of the unix socket.
+    socket_permissions: Option<u32>,
 }
 
 impl Default for Settings {
@@ -562,6 +578,7 @@ impl Default for Settings {
             max_subscriptions_per_connection: 1024,
             message_buffer_capacity: 1024,
             tokio_runtime: None,
+            socket_permissions: None,
         }
     }
 }
@@ -640,6 +657,18 @@ impl<HttpMiddleware, RpcMiddleware> Builder<HttpMiddleware, RpcMiddleware> {
         self
     }
 
+    /// Sets the file permissions of the unix socket, e.g. `0o600` to only allow the owner to
+    /// connect.
+    ///
+    /// The permissions are applied as part of creating the socket, so it is never reachable with
+    /// the default permissions. This has no effect on Windows named pipes.
+    ///
+    /// Default: permissions derived from the process umask.
+    pub const fn set_socket_permissions(mut self, mode: Option<u32>) -> Self {
+        self.settings.socket_permissions = mode;
+        self
+    }
+
     /// Configure a custom [`tokio::runtime::Handle`] to run the server on.
     ///
     /// Default: [`tokio::spawn`]
@@ -888,6 +917,42 @@ mod tests {
         assert!(response4.is_ok());
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn can_set_socket_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let endpoint = &dummy_name();
+        let server = Builder::default().set_socket_permissions(Some(0o600)).build(endpoint.clone());
+        let handle = server.start(RpcModule::new(())).await.unwrap();
+        tokio::spawn(handle.stopped());
+
+        let mode = std::fs::metadata(endpoint).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+
+        // the owner can still connect
+        let client = IpcClientBuilder::default().build(endpoint).await;
+        assert!(client.is_ok());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn socket_permissions_replace_stale_socket() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let endpoint = &dummy_name();
+        // a stale, world accessible socket file from a previous run
+        std::fs::write(endpoint, []).unwrap();
+        std::fs::set_permissions(endpoint, std::fs::Permissions::from_mode(0o777)).unwrap();
+
+        let server = Builder::default().set_socket_permissions(Some(0o700)).build(endpoint.clone());
+        let handle = server.start(RpcModule::new(())).await.unwrap();
+        tokio::spawn(handle.stopped());
+
+        let mode = std::fs::metadata(endpoint).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o700);
+    }
+
     #[tokio::test]
     async fn test_rpc_request() {
         init_test_tracing();
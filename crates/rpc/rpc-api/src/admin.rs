@@ -1,22 +1,29 @@
 use alloy_rpc_types_admin::{NodeInfo, PeerInfo};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
-use reth_network_peers::{AnyNode, NodeRecord};
+use reth_config::config::ExecutionGovernorConfig;
+use reth_network_peers::AnyNode;
+use reth_transaction_pool::firewall::FirewallConfig;
 
 /// Admin namespace rpc interface that gives access to several non-standard RPC methods.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "admin"))]
 #[cfg_attr(feature = "client", rpc(server, client, namespace = "admin"))]
 pub trait AdminApi {
-    /// Adds the given node record to the peerset.
+    /// Adds the given node, in enode or ENR form, to the static peerset and connects to it.
+    ///
+    /// Static peers are reconnected if the connection drops.
     #[method(name = "addPeer")]
-    fn add_peer(&self, record: NodeRecord) -> RpcResult<bool>;
+    fn add_peer(&self, record: AnyNode) -> RpcResult<bool>;
 
-    /// Disconnects from a remote node if the connection exists.
+    /// Removes a remote node from the static peerset and disconnects from it if the connection
+    /// exists.
     ///
     /// Returns true if the peer was successfully removed.
     #[method(name = "removePeer")]
     fn remove_peer(&self, record: AnyNode) -> RpcResult<bool>;
 
-    /// Adds the given node record to the trusted peerset.
+    /// Adds the given node to the trusted peerset.
+    ///
+    /// Trusted peers are always accepted, even if the inbound slots are full.
     #[method(name = "addTrustedPeer")]
     fn add_trusted_peer(&self, record: AnyNode) -> RpcResult<bool>;
 
@@ -42,7 +49,32 @@ pub trait AdminApi {
     )]
     async fn subscribe_peer_events(&self) -> jsonrpsee::core::SubscriptionResult;
 
-    /// Returns the ENR of the node.
+    /// Returns the identity of the node: its id, enode, ENR, listen address and protocols.
     #[method(name = "nodeInfo")]
     async fn node_info(&self) -> RpcResult<NodeInfo>;
+
+    /// Returns the rules of the transaction pool's admission firewall.
+    #[method(name = "txPoolFirewall")]
+    fn txpool_firewall(&self) -> RpcResult<FirewallConfig>;
+
+    /// Replaces the rules of the transaction pool's admission firewall.
+    ///
+    /// Transactions already in the pool are not affected.
+    #[method(name = "setTxPoolFirewall")]
+    fn set_txpool_firewall(&self, config: FirewallConfig) -> RpcResult<bool>;
+}
+
+/// An API to adjust the throughput limits of the execution stage at runtime.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "admin"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "admin"))]
+pub trait AdminExecutionGovernorApi {
+    /// Returns the throughput limits of the execution stage.
+    #[method(name = "executionGovernor")]
+    fn execution_governor(&self) -> RpcResult<ExecutionGovernorConfig>;
+
+    /// Replaces the throughput limits of the execution stage.
+    ///
+    /// The new limits apply to the next executed block.
+    #[method(name = "setExecutionGovernor")]
+    fn set_execution_governor(&self, config: ExecutionGovernorConfig) -> RpcResult<bool>;
 }
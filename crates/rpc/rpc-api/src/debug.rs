@@ -2,11 +2,14 @@ use alloy_eips::{BlockId, BlockNumberOrTag};
 use alloy_genesis::ChainConfig;
 use alloy_primitives::{Address, Bytes, B256};
 use alloy_rpc_types_debug::ExecutionWitness;
+use alloy_rpc_types_engine::PayloadId;
 use alloy_rpc_types_eth::{transaction::TransactionRequest, Block, Bundle, StateContext};
 use alloy_rpc_types_trace::geth::{
     BlockTraceResult, GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace, TraceResult,
 };
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_payload_primitives::PayloadTimings;
+use reth_tasks::registry::TaskInfo;
 use reth_trie_common::{updates::TrieUpdates, HashedPostState};
 
 /// Debug rpc interface.
@@ -155,6 +158,18 @@ pub trait DebugApi {
         hash: B256,
     ) -> RpcResult<ExecutionWitness>;
 
+    /// The `debug_transactionWitness` method replays the block containing the given transaction
+    /// with the purpose of generating the execution witness of that single transaction. The
+    /// witness comprises of the accounts, storage slots, contract codes and trie nodes that are
+    /// required to re-execute the transaction.
+    ///
+    /// The trie nodes prove the accessed state against the state the transaction is executed on,
+    /// i.e. the state of the parent block with the preceding transactions of the block applied.
+    ///
+    /// The first argument is the transaction hash.
+    #[method(name = "transactionWitness")]
+    async fn debug_transaction_witness(&self, tx_hash: B256) -> RpcResult<ExecutionWitness>;
+
     /// Sets the logging backtrace location. When a backtrace location is set and a log message is
     /// emitted at that location, the stack of the goroutine executing the log statement will
     /// be printed to stderr.
@@ -444,3 +459,41 @@ pub trait DebugExecutionWitnessApi<Attributes> {
         attributes: Attributes,
     ) -> RpcResult<ExecutionWitness>;
 }
+
+/// An API to inspect the payload builder.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "debug"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "debug"))]
+pub trait DebugPayloadTimingsApi {
+    /// Returns the time spent on each stage of building the payload with the given id.
+    ///
+    /// Returns `None` if the payload is unknown, has not been built yet, or was resolved too long
+    /// ago.
+    #[method(name = "getPayloadTimings")]
+    async fn get_payload_timings(&self, payload_id: PayloadId)
+        -> RpcResult<Option<PayloadTimings>>;
+}
+
+/// An API to inspect the tasks spawned by the node.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "debug"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "debug"))]
+pub trait DebugTaskDumpApi {
+    /// Returns the live tasks of the node with their poll statistics, in the order they were
+    /// spawned.
+    ///
+    /// Requires the node to run with `--debug.task-instrumentation`.
+    #[method(name = "taskDump")]
+    async fn task_dump(&self) -> RpcResult<Vec<TaskInfo>>;
+}
+
+/// An API to change the log filters of the node at runtime.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "debug"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "debug"))]
+pub trait DebugLogFilterApi {
+    /// Applies the comma-separated filter directives, e.g. `engine=trace`, on top of the
+    /// configured stdout and file log filters, replacing the previously applied directives.
+    ///
+    /// An empty filter resets the log filters. If `duration_secs` is set, the previous directives
+    /// are restored once the duration elapsed, the duration is limited to one hour.
+    #[method(name = "setLogFilter")]
+    async fn set_log_filter(&self, filter: String, duration_secs: Option<u64>) -> RpcResult<()>;
+}
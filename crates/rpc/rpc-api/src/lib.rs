@@ -23,29 +23,45 @@ mod hardhat;
 mod mev;
 mod miner;
 mod net;
+mod node_config;
 mod otterscan;
 mod reth;
 mod rpc;
+mod safe_mode;
 mod trace;
 mod txpool;
 mod validation;
 mod web3;
 
+pub use node_config::NodeConfigInfo;
+pub use reth::{
+    AccountHistory, BlockIncome, CallManyOptions, CallManyResult, DecodedEvent, DecodedEventParam,
+    DecodedLog, GasConsumer, IncomeRange, OmmerEraStats, PayloadValidation, ProofBundle,
+    ProofBundleTarget, StateSize, TopGasConsumers, ValidatePayloadRequest, ValidatorIncome,
+};
+pub use safe_mode::RepairStatus;
+pub use txpool::TxpoolContentPage;
+
 /// re-export of all server traits
 pub use servers::*;
 
 /// Aggregates all server traits.
 pub mod servers {
     pub use crate::{
-        admin::AdminApiServer,
-        debug::{DebugApiServer, DebugExecutionWitnessApiServer},
+        admin::{AdminApiServer, AdminExecutionGovernorApiServer},
+        debug::{
+            DebugApiServer, DebugExecutionWitnessApiServer, DebugLogFilterApiServer,
+            DebugPayloadTimingsApiServer, DebugTaskDumpApiServer,
+        },
         engine::{EngineApiServer, EngineEthApiServer, IntoEngineApiRpcModule},
         mev::{MevFullApiServer, MevSimApiServer},
         miner::MinerApiServer,
         net::NetApiServer,
+        node_config::NodeConfigApiServer,
         otterscan::OtterscanServer,
-        reth::RethApiServer,
+        reth::{RethApiServer, RethCallApiServer, RethPayloadValidationApiServer},
         rpc::RpcApiServer,
+        safe_mode::SafeModeApiServer,
         trace::TraceApiServer,
         txpool::TxPoolApiServer,
         validation::BlockSubmissionValidationApiServer,
@@ -65,18 +81,23 @@ pub use clients::*;
 #[cfg(feature = "client")]
 pub mod clients {
     pub use crate::{
-        admin::AdminApiClient,
+        admin::{AdminApiClient, AdminExecutionGovernorApiClient},
         anvil::AnvilApiClient,
-        debug::{DebugApiClient, DebugExecutionWitnessApiClient},
+        debug::{
+            DebugApiClient, DebugExecutionWitnessApiClient, DebugLogFilterApiClient,
+            DebugPayloadTimingsApiClient, DebugTaskDumpApiClient,
+        },
         engine::{EngineApiClient, EngineEthApiClient},
         ganache::GanacheApiClient,
         hardhat::HardhatApiClient,
         mev::{MevFullApiClient, MevSimApiClient},
         miner::MinerApiClient,
         net::NetApiClient,
+        node_config::NodeConfigApiClient,
         otterscan::OtterscanClient,
-        reth::RethApiClient,
+        reth::{RethApiClient, RethCallApiClient, RethPayloadValidationApiClient},
         rpc::RpcApiServer,
+        safe_mode::SafeModeApiClient,
         trace::TraceApiClient,
         txpool::TxPoolApiClient,
         validation::BlockSubmissionValidationApiClient,
@@ -0,0 +1,28 @@
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Node configuration introspection rpc interface.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "reth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "reth"))]
+pub trait NodeConfigApi {
+    /// Returns the effective configuration the node is running with, with secrets redacted.
+    #[method(name = "nodeConfig")]
+    async fn node_config(&self) -> RpcResult<NodeConfigInfo>;
+}
+
+/// Effective configuration of a running node, see `reth_nodeConfig`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeConfigInfo {
+    /// Versions of the client and its key subsystems, by name.
+    pub versions: BTreeMap<String, String>,
+    /// Cargo features the client was built with.
+    pub features: Vec<String>,
+    /// Command line arguments the node was started with, with the values of arguments that are
+    /// not known to be public redacted.
+    pub args: Vec<String>,
+    /// Effective configuration after applying the defaults, the config file and the command line
+    /// arguments.
+    pub config: serde_json::Value,
+}
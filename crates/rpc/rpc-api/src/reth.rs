@@ -1,12 +1,20 @@
-use alloy_eips::BlockId;
-use alloy_primitives::{Address, U256};
+use alloy_eips::{eip7685::Requests, BlockId};
+use alloy_primitives::{Address, BlockHash, BlockNumber, Bytes, B256, U256};
+use alloy_rpc_types_engine::ExecutionPayload;
+use alloy_rpc_types_eth::{EIP1186AccountProofResponse, Log, TransactionRequest};
+use alloy_serde::JsonStorageKey;
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
-use std::collections::HashMap;
+use reth_storage_api::RelativeBlockNumberOrTag;
+pub use reth_storage_api::StateSize;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 
 // Required for the subscription attribute below
 use reth_chain_state as _;
 
 /// Reth API namespace for reth-specific methods
+///
+/// Blocks of ranges can be given relative to a block number or tag, e.g. `latest-128`.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "reth"))]
 #[cfg_attr(feature = "client", rpc(server, client, namespace = "reth"))]
 pub trait RethApi {
@@ -17,6 +25,89 @@ pub trait RethApi {
         block_id: BlockId,
     ) -> RpcResult<HashMap<Address, U256>>;
 
+    /// Returns the header of the given block together with account and storage proofs for all
+    /// requested accounts, all anchored to the same state root.
+    #[method(name = "getProofBundle")]
+    async fn reth_get_proof_bundle(
+        &self,
+        block_id: BlockId,
+        accounts: Vec<ProofBundleTarget>,
+    ) -> RpcResult<ProofBundle>;
+
+    /// Returns ommer statistics for the given inclusive block range, grouped by hardfork era.
+    #[method(name = "getOmmerStats")]
+    async fn reth_get_ommer_stats(
+        &self,
+        from_block: RelativeBlockNumberOrTag,
+        to_block: RelativeBlockNumberOrTag,
+    ) -> RpcResult<Vec<OmmerEraStats>>;
+
+    /// Returns the priority fees and withdrawals credited to the given addresses within the block
+    /// or timestamp range, per block.
+    #[method(name = "validatorIncome")]
+    async fn reth_validator_income(
+        &self,
+        range: IncomeRange,
+        addresses: Vec<Address>,
+    ) -> RpcResult<ValidatorIncome>;
+
+    /// Returns all logs of the given block, decoded with the configured ABI registry where a
+    /// matching event is known.
+    #[method(name = "getDecodedLogs")]
+    async fn reth_get_decoded_logs(&self, block_id: BlockId) -> RpcResult<Option<Vec<DecodedLog>>>;
+
+    /// Returns the blocks in the given inclusive range in which the balance, nonce or code of the
+    /// account changed, in ascending order.
+    ///
+    /// At most `limit` blocks are returned, if there are more the response contains the block to
+    /// pass as `fromBlock` to request the next page.
+    #[method(name = "getAccountHistory")]
+    async fn reth_get_account_history(
+        &self,
+        address: Address,
+        from_block: RelativeBlockNumberOrTag,
+        to_block: RelativeBlockNumberOrTag,
+        limit: usize,
+    ) -> RpcResult<AccountHistory>;
+
+    /// Returns the code of the given account, with an EIP-7702 delegation resolved.
+    ///
+    /// The code of a delegated account is its delegation designator, the code of the delegate is
+    /// returned instead. Delegations of the delegate are not followed, matching the EVM.
+    #[method(name = "getResolvedCode")]
+    async fn reth_get_resolved_code(
+        &self,
+        address: Address,
+        block_id: Option<BlockId>,
+    ) -> RpcResult<Bytes>;
+
+    /// Returns the address the given account delegates to with an EIP-7702 delegation
+    /// designator, or `null` if the account has no delegation.
+    #[method(name = "getDelegation")]
+    async fn reth_get_delegation(
+        &self,
+        address: Address,
+        block_id: Option<BlockId>,
+    ) -> RpcResult<Option<Address>>;
+
+    /// Returns the size of the latest state per category, or `null` if the node doesn't maintain
+    /// state size statistics.
+    #[method(name = "stateSize")]
+    async fn reth_state_size(&self) -> RpcResult<Option<StateSize>>;
+
+    /// Returns the `to` addresses whose transactions used the most gas in the given inclusive
+    /// block range, or `null` if the node doesn't maintain the gas usage index for the range.
+    ///
+    /// Gas usage is aggregated per epoch, the range of the response is widened to whole epochs
+    /// and narrowed to the blocks covered by the index.
+    #[method(name = "topGasConsumers")]
+    async fn reth_top_gas_consumers(
+        &self,
+        from_block: RelativeBlockNumberOrTag,
+        to_block: RelativeBlockNumberOrTag,
+        limit: usize,
+    ) -> RpcResult<Option<TopGasConsumers>>;
+
     /// Subscribe to json `ChainNotifications`
     #[subscription(
         name = "subscribeChainNotifications",
@@ -25,3 +116,301 @@ pub trait RethApi {
     )]
     async fn reth_subscribe_chain_notifications(&self) -> jsonrpsee::core::SubscriptionResult;
 }
+
+/// Reth API namespace for executing calls against historical state.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "reth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "reth"))]
+pub trait RethCallApi {
+    /// Executes all calls on top of the state of the given block, using a single state snapshot,
+    /// and returns the result and gas used of every call, in the same order as requested.
+    ///
+    /// A failing call doesn't abort the batch, its error is returned in its result. The gas used
+    /// by all calls together is limited by the configured call gas limit.
+    #[method(name = "callMany")]
+    async fn reth_call_many(
+        &self,
+        block_id: BlockId,
+        calls: Vec<TransactionRequest>,
+        options: Option<CallManyOptions>,
+    ) -> RpcResult<Vec<CallManyResult>>;
+}
+
+/// Reth API namespace for validating blocks without importing them.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "reth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "reth"))]
+pub trait RethPayloadValidationApi {
+    /// Fully validates the execution payload on top of its parent, including its execution and
+    /// state root, without inserting it into the tree.
+    ///
+    /// Invalid payloads are reported in the response, an error is only returned if the payload
+    /// can't be validated, e.g. because its parent is unknown.
+    #[method(name = "validatePayload")]
+    async fn reth_validate_payload(
+        &self,
+        request: ValidatePayloadRequest,
+    ) -> RpcResult<PayloadValidation>;
+}
+
+/// Request of `reth_validatePayload`, with the same fields as the `engine_newPayload` call of the
+/// payload's fork.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatePayloadRequest {
+    /// The execution payload.
+    pub execution_payload: ExecutionPayload,
+    /// Versioned hashes of the blobs of the payload, required since Cancun.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected_blob_versioned_hashes: Option<Vec<B256>>,
+    /// Root of the parent beacon block, required since Cancun.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_beacon_block_root: Option<B256>,
+    /// Execution layer requests of the payload, required since Prague.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub execution_requests: Option<Requests>,
+}
+
+/// Response of `reth_validatePayload`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PayloadValidation {
+    /// Block hash of the payload.
+    pub block_hash: BlockHash,
+    /// Whether the payload is valid.
+    pub valid: bool,
+    /// The reason the payload is invalid.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub validation_error: Option<String>,
+}
+
+/// Options of `reth_callMany`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallManyOptions {
+    /// Whether the calls are independent of each other.
+    ///
+    /// Independent calls are executed in parallel, all against the unmodified state of the block,
+    /// otherwise every call observes the state changes of the previous calls.
+    #[serde(default)]
+    pub independent: bool,
+}
+
+/// Result of a single call of `reth_callMany`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallManyResult {
+    /// Return data of the call, if it succeeded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<Bytes>,
+    /// Error message, if the call failed, reverted or halted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Gas used by the call.
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas_used: u64,
+}
+
+impl CallManyResult {
+    /// Creates the result of a failed call.
+    pub fn error(error: impl ToString, gas_used: u64) -> Self {
+        Self { value: None, error: Some(error.to_string()), gas_used }
+    }
+}
+
+/// An account and the storage slots to prove as part of a [`ProofBundle`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProofBundleTarget {
+    /// The account address.
+    pub address: Address,
+    /// The storage slots of the account to prove.
+    #[serde(default)]
+    pub storage_keys: Vec<JsonStorageKey>,
+}
+
+/// Response of `reth_getProofBundle`.
+///
+/// The header is returned in its canonical RLP encoding so that verifiers can check
+/// `keccak256(header) == block_hash` and then verify every proof against the `stateRoot` field of
+/// that header.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProofBundle {
+    /// Hash of the block the proofs were generated for.
+    pub block_hash: BlockHash,
+    /// Number of the block the proofs were generated for.
+    #[serde(with = "alloy_serde::quantity")]
+    pub block_number: BlockNumber,
+    /// State root of the block, all account proofs are rooted here.
+    pub state_root: B256,
+    /// RLP encoded block header.
+    pub header: Bytes,
+    /// Account proofs, in the same order as requested.
+    pub account_proofs: Vec<EIP1186AccountProofResponse>,
+}
+
+/// Ommer statistics of a block range within a single hardfork era, see `reth_getOmmerStats`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OmmerEraStats {
+    /// Name of the hardfork that started the era.
+    pub era: String,
+    /// First block of the era within the requested range.
+    #[serde(with = "alloy_serde::quantity")]
+    pub from_block: BlockNumber,
+    /// Last block of the era within the requested range.
+    #[serde(with = "alloy_serde::quantity")]
+    pub to_block: BlockNumber,
+    /// Number of blocks.
+    #[serde(with = "alloy_serde::quantity")]
+    pub blocks: u64,
+    /// Number of blocks that included at least one ommer.
+    #[serde(with = "alloy_serde::quantity")]
+    pub blocks_with_ommers: u64,
+    /// Total number of included ommers.
+    #[serde(with = "alloy_serde::quantity")]
+    pub ommers: u64,
+    /// Average number of ommers per block.
+    pub ommer_rate: f64,
+}
+
+/// Inclusive range of `reth_validatorIncome`, either by block or by block timestamp.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged, rename_all = "camelCase")]
+pub enum IncomeRange {
+    /// Blocks from `fromBlock` to `toBlock`.
+    #[serde(rename_all = "camelCase")]
+    Blocks {
+        /// First block of the range.
+        from_block: RelativeBlockNumberOrTag,
+        /// Last block of the range.
+        to_block: RelativeBlockNumberOrTag,
+    },
+    /// Blocks with a timestamp from `fromTimestamp` to `toTimestamp`.
+    #[serde(rename_all = "camelCase")]
+    Timestamps {
+        /// Earliest block timestamp, in seconds.
+        #[serde(with = "alloy_serde::quantity")]
+        from_timestamp: u64,
+        /// Latest block timestamp, in seconds.
+        #[serde(with = "alloy_serde::quantity")]
+        to_timestamp: u64,
+    },
+}
+
+/// Response of `reth_validatorIncome`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatorIncome {
+    /// First block of the resolved range.
+    #[serde(with = "alloy_serde::quantity")]
+    pub from_block: BlockNumber,
+    /// Last block of the resolved range.
+    #[serde(with = "alloy_serde::quantity")]
+    pub to_block: BlockNumber,
+    /// Priority fees received by the addresses as fee recipient, in wei.
+    pub total_priority_fees: U256,
+    /// Withdrawals credited to the addresses, in wei.
+    pub total_withdrawals: U256,
+    /// Blocks in the range that credited any of the addresses.
+    pub blocks: Vec<BlockIncome>,
+}
+
+/// Income credited to the requested addresses by a single block, see `reth_validatorIncome`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockIncome {
+    /// Block number.
+    #[serde(with = "alloy_serde::quantity")]
+    pub block_number: BlockNumber,
+    /// Block hash.
+    pub block_hash: BlockHash,
+    /// Block timestamp.
+    #[serde(with = "alloy_serde::quantity")]
+    pub timestamp: u64,
+    /// Fee recipient of the block.
+    pub fee_recipient: Address,
+    /// Priority fees paid to the fee recipient, zero unless it is one of the addresses.
+    pub priority_fees: U256,
+    /// Withdrawals credited to each of the addresses, in wei.
+    pub withdrawals: BTreeMap<Address, U256>,
+}
+
+/// A page of the blocks in which an account changed, see `reth_getAccountHistory`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountHistory {
+    /// Blocks in which the account changed, in ascending order.
+    #[serde(with = "alloy_serde::quantity::vec")]
+    pub blocks: Vec<BlockNumber>,
+    /// The first block of the next page if there are more blocks in the range, pass it as
+    /// `fromBlock` to request the next page.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub next_block: Option<BlockNumber>,
+}
+
+/// Response of `reth_topGasConsumers`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TopGasConsumers {
+    /// First block covered by the response.
+    #[serde(with = "alloy_serde::quantity")]
+    pub from_block: BlockNumber,
+    /// Last block covered by the response.
+    #[serde(with = "alloy_serde::quantity")]
+    pub to_block: BlockNumber,
+    /// The addresses, ordered by gas used in descending order.
+    pub consumers: Vec<GasConsumer>,
+}
+
+/// Gas used by transactions sent to an address, see [`TopGasConsumers`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GasConsumer {
+    /// The `to` address of the transactions.
+    pub address: Address,
+    /// Total gas used by the transactions.
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas_used: u64,
+}
+
+/// A log of `reth_getDecodedLogs`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecodedLog {
+    /// The log.
+    #[serde(flatten)]
+    pub log: Log,
+    /// The decoded event, if the event of the log is known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decoded: Option<DecodedEvent>,
+}
+
+/// An event decoded with a known ABI.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecodedEvent {
+    /// Name of the event.
+    pub name: String,
+    /// Signature of the event, e.g. `Transfer(address,address,uint256)`.
+    pub signature: String,
+    /// Parameters of the event, in declaration order.
+    pub params: Vec<DecodedEventParam>,
+}
+
+/// A decoded parameter of a [`DecodedEvent`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecodedEventParam {
+    /// Name of the parameter, may be empty.
+    pub name: String,
+    /// Solidity type of the parameter.
+    #[serde(rename = "type")]
+    pub ty: String,
+    /// Whether the parameter is indexed.
+    pub indexed: bool,
+    /// Decoded value.
+    ///
+    /// Integers are decimal strings, byte values are hex strings. Indexed parameters of dynamic
+    /// types are the hash of the value.
+    pub value: serde_json::Value,
+}
@@ -0,0 +1,53 @@
+use alloy_primitives::BlockNumber;
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use serde::{Deserialize, Serialize};
+
+/// Safe mode rpc interface, only available when the node was started with `--safe-mode`.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "reth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "reth"))]
+pub trait SafeModeApi {
+    /// Returns the storage damage report the node was started with.
+    #[method(name = "repairStatus")]
+    async fn repair_status(&self) -> RpcResult<RepairStatus>;
+}
+
+/// Storage repair status of a node running in safe mode, see `reth_repairStatus`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RepairStatus {
+    /// Whether the startup consistency check found damaged storage.
+    pub degraded: bool,
+    /// The highest block that is consistent between the database and static files. Blocks above
+    /// it are answered with a storage degraded error.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub consistent_tip: Option<BlockNumber>,
+    /// The highest block that was stored before the damage was detected.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub highest_block: Option<BlockNumber>,
+    /// Suggested recovery action.
+    pub recovery: String,
+}
+
+impl RepairStatus {
+    /// Status of a node in safe mode whose storage passed the consistency check.
+    pub fn healthy() -> Self {
+        Self {
+            degraded: false,
+            consistent_tip: None,
+            highest_block: None,
+            recovery: "storage is consistent, restart without --safe-mode".to_string(),
+        }
+    }
+
+    /// Status of a node in safe mode whose storage is consistent up to `consistent_tip` only.
+    pub fn degraded(consistent_tip: BlockNumber, highest_block: BlockNumber) -> Self {
+        Self {
+            degraded: true,
+            consistent_tip: Some(consistent_tip),
+            highest_block: Some(highest_block),
+            recovery: format!(
+                "restart without --safe-mode to unwind to block {consistent_tip}, or run `reth stage unwind to-block {consistent_tip}`"
+            ),
+        }
+    }
+}
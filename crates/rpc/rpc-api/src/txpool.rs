@@ -2,6 +2,8 @@ use alloy_json_rpc::RpcObject;
 use alloy_primitives::Address;
 use alloy_rpc_types_txpool::{TxpoolContent, TxpoolContentFrom, TxpoolInspect, TxpoolStatus};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Txpool rpc interface.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "txpool"))]
@@ -34,4 +36,36 @@ pub trait TxPoolApi<T: RpcObject> {
     /// See [here](https://geth.ethereum.org/docs/rpc/ns-txpool#txpool_content) for more details
     #[method(name = "content")]
     async fn txpool_content(&self) -> RpcResult<TxpoolContent<T>>;
+
+    /// Returns a page of the transactions currently in the txpool, grouped by sender and nonce.
+    ///
+    /// Senders are returned in ascending order, starting with the first sender after `after`. All
+    /// transactions of a sender are part of the same page, so a page contains at most `limit`
+    /// transactions unless its first sender has more.
+    #[method(name = "contentPage")]
+    async fn txpool_content_page(
+        &self,
+        after: Option<Address>,
+        limit: usize,
+    ) -> RpcResult<TxpoolContentPage<T>>;
+}
+
+/// A page of the txpool content, see `txpool_contentPage`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxpoolContentPage<T> {
+    /// Pending transactions of the senders of this page, grouped by sender and nonce.
+    pub pending: BTreeMap<Address, BTreeMap<String, T>>,
+    /// Queued transactions of the senders of this page, grouped by sender and nonce.
+    pub queued: BTreeMap<Address, BTreeMap<String, T>>,
+    /// The last sender of this page if there are more senders, pass it as `after` to request the
+    /// next page.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next: Option<Address>,
+}
+
+impl<T> Default for TxpoolContentPage<T> {
+    fn default() -> Self {
+        Self { pending: BTreeMap::new(), queued: BTreeMap::new(), next: None }
+    }
 }
@@ -1,15 +1,19 @@
-use crate::error::{RpcError, ServerKind};
+use crate::{
+    engine_record::EngineApiRecorder,
+    error::{RpcError, ServerKind},
+    IpcRpcServiceBuilder,
+};
 use http::header::AUTHORIZATION;
 use jsonrpsee::{
-    core::{client::SubscriptionClientT, RegisterMethodError},
+    core::{client::SubscriptionClientT, middleware::layer::Either, RegisterMethodError},
     http_client::HeaderMap,
-    server::{AlreadyStoppedError, RpcModule},
+    server::{middleware::rpc::RpcServiceBuilder, AlreadyStoppedError, RpcModule},
     Methods,
 };
 use reth_rpc_api::servers::*;
 use reth_rpc_eth_types::EthSubscriptionIdProvider;
 use reth_rpc_layer::{
-    secret_to_bearer_header, AuthClientLayer, AuthLayer, JwtAuthValidator, JwtSecret,
+    secret_to_bearer_header, AuthClientLayer, AuthLayer, JwtAuthValidator, JwtRejections, JwtSecret,
 };
 use reth_rpc_server_types::constants;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
@@ -32,6 +36,10 @@ pub struct AuthServerConfig {
     pub(crate) ipc_server_config: Option<IpcServerBuilder<Identity, Identity>>,
     /// IPC endpoint
     pub(crate) ipc_endpoint: Option<String>,
+    /// Records requests rejected by the auth layer.
+    pub(crate) jwt_rejections: Option<JwtRejections>,
+    /// Records engine API calls to a journal.
+    pub(crate) engine_api_recorder: Option<EngineApiRecorder>,
 }
 
 // === impl AuthServerConfig ===
@@ -47,18 +55,43 @@ impl AuthServerConfig {
         self.socket_addr
     }
 
+    /// Records all requests rejected by the auth layer in the given [`JwtRejections`].
+    pub fn with_jwt_rejections(mut self, rejections: JwtRejections) -> Self {
+        self.jwt_rejections = Some(rejections);
+        self
+    }
+
+    /// Records all `engine_newPayload` and `engine_forkchoiceUpdated` calls with the given
+    /// [`EngineApiRecorder`].
+    pub fn with_engine_api_recorder(mut self, recorder: EngineApiRecorder) -> Self {
+        self.engine_api_recorder = Some(recorder);
+        self
+    }
+
     /// Convenience function to start a server in one step.
     pub async fn start(self, module: AuthRpcModule) -> Result<AuthServerHandle, RpcError> {
-        let Self { socket_addr, secret, server_config, ipc_server_config, ipc_endpoint } = self;
+        let Self {
+            socket_addr,
+            secret,
+            server_config,
+            ipc_server_config,
+            ipc_endpoint,
+            jwt_rejections,
+            engine_api_recorder,
+        } = self;
 
         // Create auth middleware.
-        let middleware =
-            tower::ServiceBuilder::new().layer(AuthLayer::new(JwtAuthValidator::new(secret)));
+        let mut validator = JwtAuthValidator::new(secret);
+        if let Some(rejections) = jwt_rejections {
+            validator = validator.with_rejections(rejections);
+        }
+        let middleware = tower::ServiceBuilder::new().layer(AuthLayer::new(validator));
 
         // By default, both http and ws are enabled.
         let server = ServerBuilder::new()
             .set_config(server_config.build())
             .set_http_middleware(middleware)
+            .set_rpc_middleware(RpcServiceBuilder::new().option_layer(engine_api_recorder.clone()))
             .build(socket_addr)
             .await
             .map_err(|err| RpcError::server_error(err, ServerKind::Auth(socket_addr)))?;
@@ -74,7 +107,11 @@ impl AuthServerConfig {
             let ipc_endpoint_str = ipc_endpoint
                 .clone()
                 .unwrap_or_else(|| constants::DEFAULT_ENGINE_API_IPC_ENDPOINT.to_string());
-            let ipc_server = ipc_server_config.build(ipc_endpoint_str);
+            let ipc_server = ipc_server_config
+                .set_rpc_middleware(IpcRpcServiceBuilder::new().layer(
+                    engine_api_recorder.map(Either::Left).unwrap_or(Either::Right(Identity::new())),
+                ))
+                .build(ipc_endpoint_str);
             let res = ipc_server.start(module.inner).await?;
             ipc_handle = Some(res);
         }
@@ -181,6 +218,8 @@ impl AuthServerConfigBuilder {
                     .set_id_provider(EthSubscriptionIdProvider::default())
             }),
             ipc_endpoint: self.ipc_endpoint,
+            jwt_rejections: None,
+            engine_api_recorder: None,
         }
     }
 }
@@ -289,6 +328,13 @@ impl AuthServerHandle {
         handle.stop()
     }
 
+    /// Waits until the server stopped, see [`AuthServerHandle::stop`].
+    pub async fn stopped(self) {
+        if let Some(handle) = self.handle {
+            handle.stopped().await;
+        }
+    }
+
     /// Returns the url to the http server
     pub fn http_url(&self) -> String {
         format!("http://{}", self.local_addr)
@@ -1,10 +1,10 @@
 use jsonrpsee::server::ServerConfigBuilder;
 use reth_node_core::{args::RpcServerArgs, utils::get_or_create_jwt_secret_from_path};
-use reth_rpc::ValidationApiConfig;
+use reth_rpc::{AbiRegistry, AbiRegistryError, ValidationApiConfig};
 use reth_rpc_eth_types::{EthConfig, EthStateCacheConfig, GasPriceOracleConfig};
 use reth_rpc_layer::{JwtError, JwtSecret};
 use reth_rpc_server_types::RpcModuleSelection;
-use std::{net::SocketAddr, path::PathBuf};
+use std::{net::SocketAddr, path::PathBuf, time::Duration};
 use tower::layer::util::Identity;
 use tracing::{debug, warn};
 
@@ -30,6 +30,10 @@ pub trait RethRpcServerConfig {
     /// The configured ethereum RPC settings.
     fn flashbots_config(&self) -> ValidationApiConfig;
 
+    /// Loads the [`AbiRegistry`] used to decode logs in the `reth` namespace, if any ABIs are
+    /// configured.
+    fn abi_registry(&self) -> Result<Option<AbiRegistry>, AbiRegistryError>;
+
     /// Returns state cache configuration.
     fn state_cache_config(&self) -> EthStateCacheConfig;
 
@@ -95,9 +99,20 @@ impl RethRpcServerConfig for RpcServerArgs {
         EthConfig::default()
             .max_tracing_requests(self.rpc_max_tracing_requests)
             .max_trace_filter_blocks(self.rpc_max_trace_filter_blocks)
+            .max_trace_filter_results(self.rpc_max_trace_filter_results.unwrap_or_max() as usize)
+            .trace_filter_concurrency(self.rpc_trace_filter_concurrency)
+            .trace_cache_size(self.rpc_trace_cache_size * 1024 * 1024)
             .max_blocks_per_filter(self.rpc_max_blocks_per_filter.unwrap_or_max())
             .max_logs_per_response(self.rpc_max_logs_per_response.unwrap_or_max() as usize)
-            .eth_proof_window(self.rpc_eth_proof_window)
+            .serialization_memory_budget(
+                self.rpc_serialization_memory_budget.saturating_mul(1024 * 1024),
+            )
+            .stale_filter_ttl(Duration::from_secs(self.rpc_filter_ttl))
+            .filter_max_blocks_per_poll(self.rpc_filter_max_blocks_per_poll.unwrap_or_max())
+            .filter_reorg_window(self.rpc_filter_reorg_window)
+            .subscription_max_compacted_blocks(self.rpc_subscription_max_compacted_blocks)
+            .eth_proof_window(self.eth_proof_window())
+            .max_proof_response_size(self.rpc_max_proof_response_size.saturating_mul(1024 * 1024))
             .rpc_gas_cap(self.rpc_gas_cap)
             .rpc_max_simulate_blocks(self.rpc_max_simulate_blocks)
             .state_cache(self.state_cache_config())
@@ -112,6 +127,13 @@ impl RethRpcServerConfig for RpcServerArgs {
         }
     }
 
+    fn abi_registry(&self) -> Result<Option<AbiRegistry>, AbiRegistryError> {
+        if self.rpc_abi.is_empty() {
+            return Ok(None)
+        }
+        AbiRegistry::load(&self.rpc_abi).map(Some)
+    }
+
     fn state_cache_config(&self) -> EthStateCacheConfig {
         EthStateCacheConfig {
             max_blocks: self.rpc_state_cache.max_blocks,
@@ -193,6 +215,7 @@ impl RethRpcServerConfig for RpcServerArgs {
                 .with_http(self.http_ws_server_builder())
                 .with_http_cors(self.http_corsdomain.clone())
                 .with_http_disable_compression(self.http_disable_compression)
+                .with_http_binary_encoding(self.http_binary_encoding)
                 .with_ws_cors(self.ws_allowed_origins.clone());
         }
 
@@ -214,9 +237,9 @@ impl RethRpcServerConfig for RpcServerArgs {
 
         let mut builder = AuthServerConfig::builder(jwt_secret).socket_addr(address);
         if self.auth_ipc {
-            builder = builder
-                .ipc_endpoint(self.auth_ipc_path.clone())
-                .with_ipc_config(self.ipc_server_builder());
+            builder = builder.ipc_endpoint(self.auth_ipc_path.clone()).with_ipc_config(
+                self.ipc_server_builder().set_socket_permissions(self.auth_ipc_permissions),
+            );
         }
         Ok(builder.build())
     }
@@ -242,7 +265,10 @@ mod tests {
     use reth_node_core::args::RpcServerArgs;
     use reth_rpc_eth_types::RPC_DEFAULT_GAS_CAP;
     use reth_rpc_server_types::{constants, RethRpcModule, RpcModuleSelection};
-    use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
+    use std::{
+        net::{Ipv4Addr, SocketAddr, SocketAddrV4},
+        time::Duration,
+    };
 
     use crate::config::RethRpcServerConfig;
 
@@ -385,4 +411,23 @@ mod tests {
         assert_eq!(config.max_blocks_per_filter, Some(100));
         assert_eq!(config.max_logs_per_response, Some(200));
     }
+
+    #[test]
+    fn test_filter_poll_config() {
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.filter-ttl",
+            "60",
+            "--rpc.filter-max-blocks-per-poll",
+            "10",
+            "--rpc.filter-reorg-window",
+            "128",
+        ])
+        .args;
+
+        let config = args.eth_config().filter_config();
+        assert_eq!(config.stale_filter_ttl, Duration::from_secs(60));
+        assert_eq!(config.max_blocks_per_poll, Some(10));
+        assert_eq!(config.reorg_window, 128);
+    }
 }
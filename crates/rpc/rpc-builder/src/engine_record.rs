@@ -0,0 +1,295 @@
+//! Records engine API requests and responses to a journal for later replay.
+//!
+//! The journal is a file of newline delimited JSON [`EngineApiRecord`]s, one for every
+//! `engine_newPayload` and `engine_forkchoiceUpdated` call served by the auth server, in the
+//! order the responses were sent. The journal is written by a background thread, so recording
+//! doesn't block the engine API.
+
+use jsonrpsee::{
+    core::middleware::{Batch, Notification},
+    server::middleware::rpc::RpcServiceT,
+    types::Request,
+    MethodResponse,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{File, OpenOptions},
+    future::Future,
+    io::{self, BufRead, BufReader, BufWriter, Write},
+    path::Path,
+    sync::mpsc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tower::Layer;
+use tracing::error;
+
+/// A recorded engine API call.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EngineApiRecord {
+    /// The called method, e.g. `engine_newPayloadV4`.
+    pub method: String,
+    /// The params of the request.
+    pub params: Option<serde_json::Value>,
+    /// The JSON-RPC response object sent for the request.
+    pub response: serde_json::Value,
+    /// Unix timestamp in milliseconds at which the request was received.
+    pub requested_at: u64,
+    /// Unix timestamp in milliseconds at which the response was sent.
+    pub responded_at: u64,
+}
+
+impl EngineApiRecord {
+    /// Returns `true` if calls of the method are recorded.
+    pub fn is_recorded_method(method: &str) -> bool {
+        method.starts_with("engine_newPayload") || method.starts_with("engine_forkchoiceUpdated")
+    }
+
+    /// Returns the `result` or `error` of the recorded response.
+    pub fn outcome(&self) -> Option<&serde_json::Value> {
+        self.response.get("result").or_else(|| self.response.get("error"))
+    }
+}
+
+/// Reads all records of the journal at the given path.
+pub fn read_journal(path: impl AsRef<Path>) -> io::Result<Vec<EngineApiRecord>> {
+    let mut records = Vec::new();
+    for line in BufReader::new(File::open(path)?).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue
+        }
+        records.push(serde_json::from_str(&line)?);
+    }
+    Ok(records)
+}
+
+/// A [`RpcServiceT`] middleware layer that appends engine API calls to a journal.
+///
+/// See [`EngineApiRecord::is_recorded_method`] for the recorded calls. The records are queued and
+/// written in batches by a background thread, which exits once all clones of the recorder are
+/// dropped.
+#[derive(Debug, Clone)]
+pub struct EngineApiRecorder {
+    journal: mpsc::Sender<JournalMessage>,
+}
+
+impl EngineApiRecorder {
+    /// Opens the journal at the given path, appending to it if it already exists, and spawns the
+    /// thread that writes it.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let journal = OpenOptions::new().create(true).append(true).open(path)?;
+        let (tx, rx) = mpsc::channel();
+        std::thread::Builder::new()
+            .name("engine-api-recorder".to_string())
+            .spawn(move || write_journal(journal, rx))?;
+        Ok(Self { journal: tx })
+    }
+
+    /// Queues the record to be appended to the journal.
+    pub fn record(&self, record: EngineApiRecord) {
+        self.send(JournalMessage::Record(record));
+    }
+
+    /// Blocks until all queued records are written to the journal.
+    pub fn flush(&self) -> io::Result<()> {
+        let (tx, rx) = mpsc::sync_channel(1);
+        self.send(JournalMessage::Flush(tx));
+        rx.recv().map_err(|_| io::Error::other("engine API journal writer stopped"))?
+    }
+
+    fn send(&self, message: JournalMessage) {
+        // the writer only stops once all senders are dropped
+        let _ = self.journal.send(message);
+    }
+}
+
+/// A message to the journal writer.
+#[derive(Debug)]
+enum JournalMessage {
+    /// A served call, parsed by the writer so that the engine API isn't delayed by it.
+    Call(RecordedCall),
+    /// A record to append.
+    Record(EngineApiRecord),
+    /// Flushes the journal and reports the result.
+    Flush(mpsc::SyncSender<io::Result<()>>),
+}
+
+/// A served engine API call with its raw params and response.
+#[derive(Debug)]
+struct RecordedCall {
+    method: String,
+    params: Option<String>,
+    response: String,
+    requested_at: u64,
+    responded_at: u64,
+}
+
+impl RecordedCall {
+    fn into_record(self) -> EngineApiRecord {
+        let Self { method, params, response, requested_at, responded_at } = self;
+        EngineApiRecord {
+            method,
+            params: params.and_then(|params| serde_json::from_str(&params).ok()),
+            response: serde_json::from_str(&response)
+                .unwrap_or(serde_json::Value::String(response)),
+            requested_at,
+            responded_at,
+        }
+    }
+}
+
+/// Appends the records received on the channel to the journal, until all senders are dropped.
+///
+/// All queued records are written before the journal is flushed, so bursts of calls result in a
+/// single write.
+fn write_journal(journal: File, rx: mpsc::Receiver<JournalMessage>) {
+    let mut journal = BufWriter::new(journal);
+    while let Ok(message) = rx.recv() {
+        for message in std::iter::once(message).chain(rx.try_iter()) {
+            let record = match message {
+                JournalMessage::Call(call) => call.into_record(),
+                JournalMessage::Record(record) => record,
+                JournalMessage::Flush(tx) => {
+                    let _ = tx.send(journal.flush());
+                    continue
+                }
+            };
+            if let Err(err) = serde_json::to_writer(&mut journal, &record)
+                .map_err(io::Error::from)
+                .and_then(|_| journal.write_all(b"\n"))
+            {
+                error!(target: "rpc::engine", method = %record.method, %err, "Failed to record engine API call");
+            }
+        }
+        if let Err(err) = journal.flush() {
+            error!(target: "rpc::engine", %err, "Failed to write engine API journal");
+        }
+    }
+}
+
+impl<S> Layer<S> for EngineApiRecorder {
+    type Service = EngineApiRecorderService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        EngineApiRecorderService { inner, recorder: self.clone() }
+    }
+}
+
+/// A [`RpcServiceT`] middleware that records engine API calls, see [`EngineApiRecorder`].
+#[derive(Debug, Clone)]
+pub struct EngineApiRecorderService<S> {
+    inner: S,
+    recorder: EngineApiRecorder,
+}
+
+impl<S> RpcServiceT for EngineApiRecorderService<S>
+where
+    S: RpcServiceT<MethodResponse = MethodResponse> + Send + Sync + Clone + 'static,
+{
+    type MethodResponse = S::MethodResponse;
+    type NotificationResponse = S::NotificationResponse;
+    type BatchResponse = S::BatchResponse;
+
+    fn call<'a>(&self, req: Request<'a>) -> impl Future<Output = S::MethodResponse> + Send + 'a {
+        let call = EngineApiRecord::is_recorded_method(req.method_name()).then(|| {
+            let params = req.params().as_str().map(ToString::to_string);
+            (req.method_name().to_string(), params, unix_millis())
+        });
+        let recorder = self.recorder.clone();
+        let fut = self.inner.call(req);
+
+        async move {
+            let response = fut.await;
+            if let Some((method, params, requested_at)) = call {
+                recorder.send(JournalMessage::Call(RecordedCall {
+                    method,
+                    params,
+                    response: response.to_json().get().to_string(),
+                    requested_at,
+                    responded_at: unix_millis(),
+                }));
+            }
+            response
+        }
+    }
+
+    fn batch<'a>(&self, req: Batch<'a>) -> impl Future<Output = Self::BatchResponse> + Send + 'a {
+        self.inner.batch(req)
+    }
+
+    fn notification<'a>(
+        &self,
+        n: Notification<'a>,
+    ) -> impl Future<Output = Self::NotificationResponse> + Send + 'a {
+        self.inner.notification(n)
+    }
+}
+
+/// Returns the current unix timestamp in milliseconds.
+fn unix_millis() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recorded_methods() {
+        assert!(EngineApiRecord::is_recorded_method("engine_newPayloadV4"));
+        assert!(EngineApiRecord::is_recorded_method("engine_forkchoiceUpdatedV3"));
+        assert!(!EngineApiRecord::is_recorded_method("engine_getPayloadV4"));
+        assert!(!EngineApiRecord::is_recorded_method("eth_blockNumber"));
+    }
+
+    #[test]
+    fn journal_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("engine").join("journal.jsonl");
+
+        let records = vec![
+            EngineApiRecord {
+                method: "engine_forkchoiceUpdatedV3".to_string(),
+                params: Some(serde_json::json!([{ "headBlockHash": "0x01" }, null])),
+                response: serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "result": { "payloadStatus": { "status": "VALID" } }
+                }),
+                requested_at: 1,
+                responded_at: 2,
+            },
+            EngineApiRecord {
+                method: "engine_newPayloadV4".to_string(),
+                params: None,
+                response: serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "id": 2,
+                    "error": { "code": -32602, "message": "Invalid params" }
+                }),
+                requested_at: 3,
+                responded_at: 4,
+            },
+        ];
+
+        let recorder = EngineApiRecorder::open(&path).unwrap();
+        recorder.record(records[0].clone());
+        recorder.flush().unwrap();
+        // reopening appends to the journal
+        let recorder = EngineApiRecorder::open(&path).unwrap();
+        recorder.record(records[1].clone());
+        recorder.flush().unwrap();
+
+        assert_eq!(read_journal(&path).unwrap(), records);
+        assert_eq!(
+            records[0].outcome(),
+            Some(&serde_json::json!({ "payloadStatus": { "status": "VALID" } }))
+        );
+        assert_eq!(records[1].outcome().and_then(|err| err.get("code")), Some(&(-32602).into()));
+    }
+}
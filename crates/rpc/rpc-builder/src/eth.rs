@@ -1,6 +1,6 @@
 use reth_rpc::{EthFilter, EthPubSub};
 use reth_rpc_eth_api::EthApiTypes;
-use reth_rpc_eth_types::EthConfig;
+use reth_rpc_eth_types::EthFilterConfig;
 use reth_tasks::TaskSpawner;
 
 /// Handlers for core, filter and pubsub `eth` namespace APIs.
@@ -22,13 +22,15 @@ where
     ///
     /// This will spawn all necessary tasks for the additional handlers.
     pub fn bootstrap(
-        config: EthConfig,
+        filter_config: EthFilterConfig,
+        subscription_max_compacted_blocks: usize,
         executor: Box<dyn TaskSpawner + 'static>,
         eth_api: EthApi,
     ) -> Self {
-        let filter = EthFilter::new(eth_api.clone(), config.filter_config(), executor.clone());
+        let filter = EthFilter::new(eth_api.clone(), filter_config, executor.clone());
 
-        let pubsub = EthPubSub::with_spawner(eth_api.clone(), executor);
+        let pubsub = EthPubSub::with_spawner(eth_api.clone(), executor)
+            .with_max_compacted_blocks(subscription_max_compacted_blocks);
 
         Self { api: eth_api, filter, pubsub }
     }
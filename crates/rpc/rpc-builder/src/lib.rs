@@ -42,8 +42,9 @@ use reth_evm::ConfigureEvm;
 use reth_network_api::{noop::NoopNetwork, NetworkInfo, Peers};
 use reth_primitives_traits::NodePrimitives;
 use reth_rpc::{
-    AdminApi, DebugApi, EngineEthApi, EthApi, EthApiBuilder, EthBundle, MinerApi, NetApi,
-    OtterscanApi, RPCApi, RethApi, TraceApi, TxPoolApi, ValidationApiConfig, Web3Api,
+    AbiRegistry, AdminApi, DebugApi, EngineEthApi, EthApi, EthApiBuilder, EthBundle, MinerApi,
+    NetApi, OtterscanApi, RPCApi, RethApi, RethCall, TraceApi, TxPoolApi, ValidationApiConfig,
+    Web3Api,
 };
 use reth_rpc_api::servers::*;
 use reth_rpc_eth_api::{
@@ -51,11 +52,13 @@ use reth_rpc_eth_api::{
     EthApiServer, EthApiTypes, FullEthApiServer, RpcBlock, RpcHeader, RpcReceipt, RpcTransaction,
     RpcTxReq,
 };
-use reth_rpc_eth_types::{EthConfig, EthSubscriptionIdProvider};
-use reth_rpc_layer::{AuthLayer, Claims, CompressionLayer, JwtAuthValidator, JwtSecret};
+use reth_rpc_eth_types::{EthConfig, EthFilterConfig, EthSubscriptionIdProvider};
+use reth_rpc_layer::{
+    AuthLayer, BinaryEncodingLayer, Claims, CompressionLayer, JwtAuthValidator, JwtSecret,
+};
 use reth_storage_api::{
-    AccountReader, BlockReader, BlockReaderIdExt, ChangeSetReader, FullRpcProvider, ProviderBlock,
-    StateProviderFactory,
+    AccountReader, BlockReader, BlockReaderIdExt, ChangeSetReader, FullRpcProvider, GasUsageReader,
+    ProviderBlock, StateProviderFactory, StateSizeReader,
 };
 use reth_tasks::{pool::BlockingTaskGuard, TaskSpawner, TokioTaskExecutor};
 use reth_transaction_pool::{noop::NoopTransactionPool, TransactionPool};
@@ -64,6 +67,8 @@ use std::{
     collections::HashMap,
     fmt::Debug,
     net::{Ipv4Addr, SocketAddr, SocketAddrV4},
+    path::{Path, PathBuf},
+    sync::Arc,
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
 use tower_http::cors::CorsLayer;
@@ -88,6 +93,9 @@ pub mod config;
 /// Utils for installing Rpc middleware
 pub mod middleware;
 
+/// Engine API call recording.
+pub mod engine_record;
+
 /// Cors utilities.
 mod cors;
 
@@ -100,7 +108,7 @@ pub use eth::EthHandlers;
 
 // Rpc server metrics
 mod metrics;
-use crate::middleware::RethRpcMiddleware;
+use crate::middleware::{HttpMiddleware, RethRpcMiddleware};
 pub use metrics::{MeteredRequestFuture, RpcRequestMetricsService};
 use reth_chain_state::CanonStateSubscriptions;
 use reth_rpc::eth::sim_bundle::EthSimBundle;
@@ -300,7 +308,9 @@ where
     Provider: FullRpcProvider<Block = N::Block, Receipt = N::Receipt, Header = N::BlockHeader>
         + CanonStateSubscriptions<Primitives = N>
         + AccountReader
-        + ChangeSetReader,
+        + ChangeSetReader
+        + StateSizeReader
+        + GasUsageReader,
     Pool: TransactionPool + 'static,
     Network: NetworkInfo + Peers + Clone + 'static,
     EvmConfig: ConfigureEvm<Primitives = N> + 'static,
@@ -407,6 +417,11 @@ pub struct RpcModuleConfig {
     eth: EthConfig,
     /// `flashbots` namespace settings
     flashbots: ValidationApiConfig,
+    /// ABIs used to decode logs in the `reth` namespace
+    #[serde(skip)]
+    abi_registry: Option<Arc<AbiRegistry>>,
+    /// File the installed `eth` filters are persisted to
+    filter_persistence_path: Option<PathBuf>,
 }
 
 // === impl RpcModuleConfig ===
@@ -419,7 +434,19 @@ impl RpcModuleConfig {
 
     /// Returns a new RPC module config given the eth namespace config
     pub const fn new(eth: EthConfig, flashbots: ValidationApiConfig) -> Self {
-        Self { eth, flashbots }
+        Self { eth, flashbots, abi_registry: None, filter_persistence_path: None }
+    }
+
+    /// Configures the [`AbiRegistry`] used to decode logs in the `reth` namespace
+    pub fn with_abi_registry(mut self, abi_registry: Arc<AbiRegistry>) -> Self {
+        self.abi_registry = Some(abi_registry);
+        self
+    }
+
+    /// Configures the file the installed `eth` filters are persisted to
+    pub fn with_filter_persistence_path(mut self, path: PathBuf) -> Self {
+        self.filter_persistence_path = Some(path);
+        self
     }
 
     /// Get a reference to the eth namespace config
@@ -431,6 +458,35 @@ impl RpcModuleConfig {
     pub const fn eth_mut(&mut self) -> &mut EthConfig {
         &mut self.eth
     }
+
+    /// Get the [`AbiRegistry`] used to decode logs in the `reth` namespace, if any
+    pub const fn abi_registry(&self) -> Option<&Arc<AbiRegistry>> {
+        self.abi_registry.as_ref()
+    }
+
+    /// Get a mutable reference to the [`AbiRegistry`] used to decode logs in the `reth` namespace
+    pub const fn abi_registry_mut(&mut self) -> &mut Option<Arc<AbiRegistry>> {
+        &mut self.abi_registry
+    }
+
+    /// Get the file the installed `eth` filters are persisted to, if any
+    pub fn filter_persistence_path(&self) -> Option<&Path> {
+        self.filter_persistence_path.as_deref()
+    }
+
+    /// Get a mutable reference to the file the installed `eth` filters are persisted to
+    pub const fn filter_persistence_path_mut(&mut self) -> &mut Option<PathBuf> {
+        &mut self.filter_persistence_path
+    }
+
+    /// Returns the config for the `eth` filter handler.
+    pub fn filter_config(&self) -> EthFilterConfig {
+        let config = self.eth.filter_config();
+        match &self.filter_persistence_path {
+            Some(path) => config.persistence_path(path.clone()),
+            None => config,
+        }
+    }
 }
 
 /// Configures [`RpcModuleConfig`]
@@ -438,6 +494,8 @@ impl RpcModuleConfig {
 pub struct RpcModuleConfigBuilder {
     eth: Option<EthConfig>,
     flashbots: Option<ValidationApiConfig>,
+    abi_registry: Option<Arc<AbiRegistry>>,
+    filter_persistence_path: Option<PathBuf>,
 }
 
 // === impl RpcModuleConfigBuilder ===
@@ -455,10 +513,27 @@ impl RpcModuleConfigBuilder {
         self
     }
 
+    /// Configures the [`AbiRegistry`] used to decode logs in the `reth` namespace
+    pub fn abi_registry(mut self, abi_registry: Arc<AbiRegistry>) -> Self {
+        self.abi_registry = Some(abi_registry);
+        self
+    }
+
+    /// Configures the file the installed `eth` filters are persisted to
+    pub fn filter_persistence_path(mut self, path: PathBuf) -> Self {
+        self.filter_persistence_path = Some(path);
+        self
+    }
+
     /// Consumes the type and creates the [`RpcModuleConfig`]
     pub fn build(self) -> RpcModuleConfig {
-        let Self { eth, flashbots } = self;
-        RpcModuleConfig { eth: eth.unwrap_or_default(), flashbots: flashbots.unwrap_or_default() }
+        let Self { eth, flashbots, abi_registry, filter_persistence_path } = self;
+        RpcModuleConfig {
+            eth: eth.unwrap_or_default(),
+            flashbots: flashbots.unwrap_or_default(),
+            abi_registry,
+            filter_persistence_path,
+        }
     }
 
     /// Get a reference to the eth namespace config, if any
@@ -502,6 +577,8 @@ pub struct RpcRegistryInner<
     modules: HashMap<RethRpcModule, Methods>,
     /// eth config settings
     eth_config: EthConfig,
+    /// ABIs used to decode logs in the `reth` namespace
+    abi_registry: Option<Arc<AbiRegistry>>,
 }
 
 // === impl RpcRegistryInner ===
@@ -538,7 +615,12 @@ where
     {
         let blocking_pool_guard = BlockingTaskGuard::new(config.eth.max_tracing_requests);
 
-        let eth = EthHandlers::bootstrap(config.eth, executor.clone(), eth_api);
+        let eth = EthHandlers::bootstrap(
+            config.filter_config(),
+            config.eth.subscription_max_compacted_blocks,
+            executor.clone(),
+            eth_api,
+        );
 
         Self {
             provider,
@@ -550,6 +632,7 @@ where
             modules: Default::default(),
             blocking_pool_guard,
             eth_config: config.eth,
+            abi_registry: config.abi_registry,
             evm_config,
         }
     }
@@ -613,8 +696,10 @@ where
     pub fn admin_api(&self) -> AdminApi<Network, Provider::ChainSpec>
     where
         Network: Peers,
+        Pool: TransactionPool,
     {
         AdminApi::new(self.network.clone(), self.provider.chain_spec())
+            .with_txpool_firewall(self.pool.firewall())
     }
 
     /// Instantiates `Web3Api`
@@ -626,6 +711,7 @@ where
     pub fn register_admin(&mut self) -> &mut Self
     where
         Network: Peers,
+        Pool: TransactionPool,
     {
         let adminapi = self.admin_api();
         self.modules.insert(RethRpcModule::Admin, adminapi.into_rpc().into());
@@ -651,6 +737,8 @@ where
             Transaction = N::SignedTx,
         > + AccountReader
         + ChangeSetReader
+        + StateSizeReader
+        + GasUsageReader
         + CanonStateSubscriptions,
     Network: NetworkInfo + Peers + Clone + 'static,
     EthApi: EthApiServer<
@@ -766,7 +854,9 @@ where
             Transaction = N::SignedTx,
             Receipt = N::Receipt,
         > + AccountReader
-        + ChangeSetReader,
+        + ChangeSetReader
+        + StateSizeReader
+        + GasUsageReader,
     Network: NetworkInfo + Peers + Clone + 'static,
     EthApi: EthApiTypes,
     EvmConfig: ConfigureEvm<Primitives = N>,
@@ -806,10 +896,11 @@ where
         EthApi: EthApiSpec + EthTransactions + TraceExt,
         EvmConfig::Primitives: NodePrimitives<Block = ProviderBlock<EthApi::Provider>>,
     {
-        DebugApi::new(
+        DebugApi::with_trace_cache(
             self.eth_api().clone(),
             self.blocking_pool_guard.clone(),
             self.evm_config.clone(),
+            self.eth_config.trace_cache_size,
         )
     }
 
@@ -828,7 +919,12 @@ where
 
     /// Instantiates `RethApi`
     pub fn reth_api(&self) -> RethApi<Provider> {
-        RethApi::new(self.provider.clone(), self.executor.clone())
+        RethApi::with_abi_registry(
+            self.provider.clone(),
+            self.executor.clone(),
+            self.abi_registry.clone(),
+        )
+        .with_eth_proof_window(self.eth_config.eth_proof_window)
     }
 }
 
@@ -839,7 +935,9 @@ where
     Provider: FullRpcProvider<Block = N::Block>
         + CanonStateSubscriptions<Primitives = N>
         + AccountReader
-        + ChangeSetReader,
+        + ChangeSetReader
+        + StateSizeReader
+        + GasUsageReader,
     Pool: TransactionPool + 'static,
     Network: NetworkInfo + Peers + Clone + 'static,
     EthApi: FullEthApiServer<Provider = Provider, Pool = Pool>,
@@ -924,13 +1022,15 @@ where
                     .or_insert_with(|| match namespace {
                         RethRpcModule::Admin => {
                             AdminApi::new(self.network.clone(), self.provider.chain_spec())
+                                .with_txpool_firewall(self.pool.firewall())
                                 .into_rpc()
                                 .into()
                         }
-                        RethRpcModule::Debug => DebugApi::new(
+                        RethRpcModule::Debug => DebugApi::with_trace_cache(
                             eth_api.clone(),
                             self.blocking_pool_guard.clone(),
                             self.evm_config.clone(),
+                            self.eth_config.trace_cache_size,
                         )
                         .into_rpc()
                         .into(),
@@ -978,9 +1078,17 @@ where
                         .into(),
                         RethRpcModule::Ots => OtterscanApi::new(eth_api.clone()).into_rpc().into(),
                         RethRpcModule::Reth => {
-                            RethApi::new(self.provider.clone(), self.executor.clone())
-                                .into_rpc()
-                                .into()
+                            let mut module = RethApi::with_abi_registry(
+                                self.provider.clone(),
+                                self.executor.clone(),
+                                self.abi_registry.clone(),
+                            )
+                            .with_eth_proof_window(self.eth_config.eth_proof_window)
+                            .into_rpc();
+                            module
+                                .merge(RethCall::new(eth_api.clone()).into_rpc())
+                                .expect("No conflicts");
+                            module.into()
                         }
                         // only relevant for Ethereum and configured in `EthereumAddOns`
                         // implementation
@@ -1020,6 +1128,8 @@ pub struct RpcServerConfig<RpcMiddleware = Identity> {
     http_addr: Option<SocketAddr>,
     /// Control whether http responses should be compressed
     http_disable_compression: bool,
+    /// Whether HTTP responses can be served in a binary encoding
+    http_binary_encoding: bool,
     /// Configs for WS server
     ws_server_config: Option<ServerConfigBuilder>,
     /// Allowed CORS Domains for ws.
@@ -1032,6 +1142,8 @@ pub struct RpcServerConfig<RpcMiddleware = Identity> {
     ipc_endpoint: Option<String>,
     /// JWT secret for authentication
     jwt_secret: Option<JwtSecret>,
+    /// Configurable Http middleware for the http and ws servers
+    http_middleware: HttpMiddleware,
     /// Configurable RPC middleware
     rpc_middleware: RpcMiddleware,
 }
@@ -1046,12 +1158,14 @@ impl Default for RpcServerConfig<Identity> {
             http_cors_domains: None,
             http_addr: None,
             http_disable_compression: false,
+            http_binary_encoding: false,
             ws_server_config: None,
             ws_cors_domains: None,
             ws_addr: None,
             ipc_server_config: None,
             ipc_endpoint: None,
             jwt_secret: None,
+            http_middleware: Default::default(),
             rpc_middleware: Default::default(),
         }
     }
@@ -1110,12 +1224,14 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
             http_cors_domains: self.http_cors_domains,
             http_addr: self.http_addr,
             http_disable_compression: self.http_disable_compression,
+            http_binary_encoding: self.http_binary_encoding,
             ws_server_config: self.ws_server_config,
             ws_cors_domains: self.ws_cors_domains,
             ws_addr: self.ws_addr,
             ipc_server_config: self.ipc_server_config,
             ipc_endpoint: self.ipc_endpoint,
             jwt_secret: self.jwt_secret,
+            http_middleware: self.http_middleware,
             rpc_middleware,
         }
     }
@@ -1137,6 +1253,12 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         self
     }
 
+    /// Configure whether HTTP responses can be served in a binary encoding
+    pub const fn with_http_binary_encoding(mut self, http_binary_encoding: bool) -> Self {
+        self.http_binary_encoding = http_binary_encoding;
+        self
+    }
+
     /// Configure the cors domains for HTTP
     pub fn with_http_cors(mut self, cors_domain: Option<String>) -> Self {
         self.http_cors_domains = cors_domain;
@@ -1195,6 +1317,12 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         self
     }
 
+    /// Configures the [`HttpMiddleware`] of the http and ws servers.
+    pub fn with_http_middleware(mut self, http_middleware: HttpMiddleware) -> Self {
+        self.http_middleware = http_middleware;
+        self
+    }
+
     /// Returns true if any server is configured.
     ///
     /// If no server is configured, no server will be launched on [`RpcServerConfig::start`].
@@ -1229,6 +1357,11 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         jwt_secret.map(|secret| AuthLayer::new(JwtAuthValidator::new(secret)))
     }
 
+    /// Returns the [`HttpMiddleware`] if any layers are configured
+    fn maybe_http_middleware(http_middleware: &HttpMiddleware) -> Option<HttpMiddleware> {
+        (!http_middleware.is_empty()).then(|| http_middleware.clone())
+    }
+
     /// Returns a [`CompressionLayer`] that adds compression support (gzip, deflate, brotli, zstd)
     /// based on the client's `Accept-Encoding` header
     fn maybe_compression_layer(disable_compression: bool) -> Option<CompressionLayer> {
@@ -1239,6 +1372,12 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         }
     }
 
+    /// Returns a [`BinaryEncodingLayer`] that serves responses as CBOR based on the client's
+    /// `Accept` header
+    fn maybe_binary_encoding_layer(binary_encoding: bool) -> Option<BinaryEncodingLayer> {
+        binary_encoding.then(BinaryEncodingLayer::new)
+    }
+
     /// Builds and starts the configured server(s): http, ws, ipc.
     ///
     /// If both http and ws are on the same port, they are combined into one server.
@@ -1304,7 +1443,11 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
                             .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
                             .option_layer(Self::maybe_compression_layer(
                                 self.http_disable_compression,
-                            )),
+                            ))
+                            .option_layer(Self::maybe_binary_encoding_layer(
+                                self.http_binary_encoding,
+                            ))
+                            .option_layer(Self::maybe_http_middleware(&self.http_middleware)),
                     )
                     .set_rpc_middleware(
                         RpcServiceBuilder::default()
@@ -1355,7 +1498,8 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
                 .set_http_middleware(
                     tower::ServiceBuilder::new()
                         .option_layer(Self::maybe_cors_layer(self.ws_cors_domains.clone())?)
-                        .option_layer(Self::maybe_jwt_layer(self.jwt_secret)),
+                        .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
+                        .option_layer(Self::maybe_http_middleware(&self.http_middleware)),
                 )
                 .set_rpc_middleware(
                     RpcServiceBuilder::default()
@@ -1381,7 +1525,9 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
                     tower::ServiceBuilder::new()
                         .option_layer(Self::maybe_cors_layer(self.http_cors_domains.clone())?)
                         .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
-                        .option_layer(Self::maybe_compression_layer(self.http_disable_compression)),
+                        .option_layer(Self::maybe_compression_layer(self.http_disable_compression))
+                        .option_layer(Self::maybe_binary_encoding_layer(self.http_binary_encoding))
+                        .option_layer(Self::maybe_http_middleware(&self.http_middleware)),
                 )
                 .set_rpc_middleware(
                     RpcServiceBuilder::default()
@@ -2025,6 +2171,13 @@ impl RpcServerHandle {
         Ok(())
     }
 
+    /// Waits until the servers stopped, see [`RpcServerHandle::stop`].
+    pub async fn stopped(self) {
+        for handle in [self.http, self.ws, self.ipc].into_iter().flatten() {
+            handle.stopped().await;
+        }
+    }
+
     /// Returns the endpoint of the launched IPC server, if any
     pub fn ipc_endpoint(&self) -> Option<String> {
         self.ipc_endpoint.clone()
@@ -8,6 +8,7 @@ use reth_metrics::{
     metrics::{Counter, Histogram},
     Metrics,
 };
+use reth_storage_api::ReadScope;
 use std::{
     collections::HashMap,
     future::Future,
@@ -170,7 +171,11 @@ impl<F: Future<Output = MethodResponse>> Future for MeteredRequestFuture<F> {
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         let this = self.project();
 
-        let res = this.fut.poll(cx);
+        // attribute the provider reads of the call to the method
+        let res = {
+            let _scope = this.method.map(ReadScope::enter);
+            this.fut.poll(cx)
+        };
         if let Poll::Ready(resp) = &res {
             let elapsed = this.started_at.elapsed().as_secs_f64();
 
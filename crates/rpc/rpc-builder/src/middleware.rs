@@ -1,5 +1,6 @@
-use jsonrpsee::server::middleware::rpc::RpcService;
-use tower::Layer;
+use jsonrpsee::server::{middleware::rpc::RpcService, HttpRequest, HttpResponse};
+use std::{fmt, sync::Arc};
+use tower::{util::BoxCloneService, BoxError, Layer, Service};
 
 /// A Helper alias trait for the RPC middleware supported by the server.
 pub trait RethRpcMiddleware:
@@ -35,3 +36,89 @@ impl<T> RethRpcMiddleware for T where
         + 'static
 {
 }
+
+/// A type-erased Http service of the RPC server.
+pub type BoxedHttpService = BoxCloneService<HttpRequest, HttpResponse, BoxError>;
+
+/// A Helper alias trait for the Http middleware layers supported by [`HttpMiddleware`].
+pub trait RethHttpMiddleware:
+    Layer<
+        BoxedHttpService,
+        Service: Service<
+            HttpRequest,
+            Response = HttpResponse,
+            Error = BoxError,
+            Future: Send + 'static,
+        > + Clone
+                     + Send
+                     + 'static,
+    > + Send
+    + Sync
+    + 'static
+{
+}
+
+impl<T> RethHttpMiddleware for T where
+    T: Layer<
+            BoxedHttpService,
+            Service: Service<
+                HttpRequest,
+                Response = HttpResponse,
+                Error = BoxError,
+                Future: Send + 'static,
+            > + Clone
+                         + Send
+                         + 'static,
+        > + Send
+        + Sync
+        + 'static
+{
+}
+
+/// Type-erased Http middleware of the RPC server.
+///
+/// This wraps the Http transport of the RPC server, unlike [`RethRpcMiddleware`] which wraps the
+/// processing of individual RPC calls. This allows middleware that operates on raw Http requests,
+/// e.g. checking an `Authorization` header or mutating request headers, for both the HTTP and the
+/// WS transport.
+///
+/// Layers are applied in the order they were added, the first layer being the outermost. All
+/// layers run after the builtin CORS and JWT layers.
+#[derive(Clone, Default)]
+pub struct HttpMiddleware {
+    layers: Vec<Arc<dyn Fn(BoxedHttpService) -> BoxedHttpService + Send + Sync>>,
+}
+
+impl HttpMiddleware {
+    /// Adds a new [`Layer`] to the middleware.
+    pub fn layer<L>(mut self, layer: L) -> Self
+    where
+        L: RethHttpMiddleware,
+    {
+        self.layers.push(Arc::new(move |service| BoxCloneService::new(layer.layer(service))));
+        self
+    }
+
+    /// Returns `true` if no layers are configured.
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+}
+
+impl fmt::Debug for HttpMiddleware {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HttpMiddleware").field("layers", &self.layers.len()).finish()
+    }
+}
+
+impl<S> Layer<S> for HttpMiddleware
+where
+    S: Service<HttpRequest, Response = HttpResponse, Error = BoxError> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Service = BoxedHttpService;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        self.layers.iter().rev().fold(BoxCloneService::new(inner), |service, layer| layer(service))
+    }
+}
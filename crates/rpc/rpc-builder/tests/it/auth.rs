@@ -1,6 +1,6 @@
 //! Auth server tests
 
-use crate::utils::launch_auth;
+use crate::utils::{launch_auth, launch_auth_with_config, test_address};
 use alloy_primitives::U64;
 use alloy_rpc_types_engine::{
     ExecutionPayloadInputV2, ExecutionPayloadV1, ForkchoiceState, PayloadId,
@@ -10,6 +10,10 @@ use reth_ethereum_engine_primitives::EthEngineTypes;
 use reth_ethereum_primitives::{Block, TransactionSigned};
 use reth_primitives_traits::block::Block as _;
 use reth_rpc_api::clients::EngineApiClient;
+use reth_rpc_builder::{
+    auth::AuthServerConfig,
+    engine_record::{read_journal, EngineApiRecorder},
+};
 use reth_rpc_layer::JwtSecret;
 
 #[expect(unused_must_use)]
@@ -58,3 +62,34 @@ async fn test_auth_endpoints_ws() {
     let client = handle.ws_client().await;
     test_basic_engine_calls(&client).await
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_auth_engine_api_journal() {
+    reth_tracing::init_test_tracing();
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("journal.jsonl");
+    let recorder = EngineApiRecorder::open(&path).unwrap();
+
+    let secret = JwtSecret::random();
+    let config = AuthServerConfig::builder(secret)
+        .socket_addr(test_address())
+        .build()
+        .with_engine_api_recorder(recorder.clone());
+    let handle = launch_auth_with_config(config).await;
+    let client = handle.http_client();
+    test_basic_engine_calls(&client).await;
+    recorder.flush().unwrap();
+
+    // only new payload and forkchoice updates are recorded, in the order they were served
+    let records = read_journal(&path).unwrap();
+    let methods = records.iter().map(|record| record.method.as_str()).collect::<Vec<_>>();
+    assert_eq!(
+        methods,
+        ["engine_newPayloadV1", "engine_newPayloadV2", "engine_forkchoiceUpdatedV1"]
+    );
+    for record in &records {
+        assert!(record.params.is_some());
+        assert!(record.outcome().is_some());
+        assert!(record.requested_at <= record.responded_at);
+    }
+}
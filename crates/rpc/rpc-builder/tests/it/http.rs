@@ -26,6 +26,7 @@ use reth_rpc_api::{
     TraceApiClient, Web3ApiClient,
 };
 use reth_rpc_server_types::RethRpcModule;
+use reth_transaction_pool::firewall::FirewallConfig;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashSet;
@@ -141,11 +142,15 @@ where
     let url = "enode://6f8a80d14311c39f35f516fa664deaaaa13e85b2f7493f37f6144d86991ec012937307647bd3b9a82abe2974e1407241d54947bbb39763a4cac9f77166ad92a0@10.3.58.6:30303?discport=30301";
     let node: NodeRecord = url.parse().unwrap();
 
-    AdminApiClient::add_peer(client, node).await.unwrap();
+    AdminApiClient::add_peer(client, node.into()).await.unwrap();
     AdminApiClient::remove_peer(client, node.into()).await.unwrap();
     AdminApiClient::add_trusted_peer(client, node.into()).await.unwrap();
     AdminApiClient::remove_trusted_peer(client, node.into()).await.unwrap();
     AdminApiClient::node_info(client).await.unwrap();
+
+    let firewall = FirewallConfig { max_calldata_size: Some(1024), ..Default::default() };
+    assert!(AdminApiClient::set_txpool_firewall(client, firewall.clone()).await.unwrap());
+    assert_eq!(AdminApiClient::txpool_firewall(client).await.unwrap(), firewall);
 }
 
 async fn test_basic_eth_calls<C>(client: &C)
@@ -256,6 +261,12 @@ where
     )
     .await
     .unwrap();
+    EthApiClient::<TransactionRequest, Transaction, Block, Receipt, Header>::blob_sidecars(
+        client,
+        block_number.into(),
+    )
+    .await
+    .unwrap();
     EthApiClient::<TransactionRequest, Transaction, Block, Receipt, Header>::block_uncles_count_by_hash(client, hash)
         .await
         .unwrap();
@@ -294,6 +305,12 @@ where
     )
     .await
     .unwrap();
+    EthApiClient::<TransactionRequest, Transaction, Block, Receipt, Header>::transaction_receipts_by_hashes(
+        client,
+        vec![tx_hash],
+    )
+    .await
+    .unwrap();
     EthApiClient::<TransactionRequest, Transaction, Block, Receipt, Header>::transaction_by_block_hash_and_index(
         client, hash, index,
     )
@@ -2,10 +2,10 @@ use crate::utils::{test_address, test_rpc_builder};
 use alloy_rpc_types_eth::{Block, Header, Receipt, Transaction, TransactionRequest};
 use jsonrpsee::{
     core::middleware::{Batch, Notification},
-    server::middleware::rpc::RpcServiceT,
+    server::{middleware::rpc::RpcServiceT, HttpRequest},
     types::Request,
 };
-use reth_rpc_builder::{RpcServerConfig, TransportRpcModuleConfig};
+use reth_rpc_builder::{middleware::HttpMiddleware, RpcServerConfig, TransportRpcModuleConfig};
 use reth_rpc_eth_api::EthApiClient;
 use reth_rpc_server_types::RpcModuleSelection;
 use std::{
@@ -15,7 +15,7 @@ use std::{
         Arc,
     },
 };
-use tower::Layer;
+use tower::{util::MapRequestLayer, Layer};
 
 #[derive(Clone, Default)]
 struct MyMiddlewareLayer {
@@ -93,3 +93,34 @@ async fn test_rpc_middleware() {
     let count = mylayer.count.load(Ordering::Relaxed);
     assert_eq!(count, 1);
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_http_middleware() {
+    let builder = test_rpc_builder();
+    let eth_api = builder.bootstrap_eth_api();
+    let modules =
+        builder.build(TransportRpcModuleConfig::set_http(RpcModuleSelection::All), eth_api);
+
+    let count = Arc::new(AtomicUsize::new(0));
+    let counter = count.clone();
+    let http_middleware =
+        HttpMiddleware::default().layer(MapRequestLayer::new(move |req: HttpRequest| {
+            counter.fetch_add(1, Ordering::Relaxed);
+            req
+        }));
+
+    let handle = RpcServerConfig::http(Default::default())
+        .with_http_address(test_address())
+        .with_http_middleware(http_middleware)
+        .start(&modules)
+        .await
+        .unwrap();
+
+    let client = handle.http_client().unwrap();
+    EthApiClient::<TransactionRequest, Transaction, Block, Receipt, Header>::protocol_version(
+        &client,
+    )
+    .await
+    .unwrap();
+    assert_eq!(count.load(Ordering::Relaxed), 1);
+}
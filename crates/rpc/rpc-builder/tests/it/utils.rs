@@ -33,7 +33,12 @@ pub const fn test_address() -> SocketAddr {
 
 /// Launches a new server for the auth module
 pub async fn launch_auth(secret: JwtSecret) -> AuthServerHandle {
-    let config = AuthServerConfig::builder(secret).socket_addr(test_address()).build();
+    launch_auth_with_config(AuthServerConfig::builder(secret).socket_addr(test_address()).build())
+        .await
+}
+
+/// Launches a new server for the auth module with the given config
+pub async fn launch_auth_with_config(config: AuthServerConfig) -> AuthServerHandle {
     let (tx, _rx) = unbounded_channel();
     let beacon_engine_handle = BeaconConsensusEngineHandle::<EthEngineTypes>::new(tx);
     let client = ClientVersionV1 {
@@ -17,6 +17,7 @@ use alloy_rpc_types_eth::{
 use alloy_serde::JsonStorageKey;
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use reth_rpc_convert::RpcTxReq;
+use reth_rpc_eth_types::BlockBlobSidecar;
 use reth_rpc_server_types::{result::internal_rpc_err, ToRpcResult};
 use tracing::trace;
 
@@ -108,6 +109,11 @@ pub trait EthApi<TxReq: RpcObject, T: RpcObject, B: RpcObject, R: RpcObject, H:
     #[method(name = "getBlockReceipts")]
     async fn block_receipts(&self, block_id: BlockId) -> RpcResult<Option<Vec<R>>>;
 
+    /// Returns the blob sidecars of the blob transactions in the given block that are still
+    /// available in the blob store.
+    #[method(name = "getBlobSidecars")]
+    async fn blob_sidecars(&self, block_id: BlockId) -> RpcResult<Option<Vec<BlockBlobSidecar>>>;
+
     /// Returns an uncle block of the given block and index.
     #[method(name = "getUncleByBlockHashAndIndex")]
     async fn uncle_by_block_hash_and_index(&self, hash: B256, index: Index)
@@ -176,6 +182,11 @@ pub trait EthApi<TxReq: RpcObject, T: RpcObject, B: RpcObject, R: RpcObject, H:
     #[method(name = "getTransactionReceipt")]
     async fn transaction_receipt(&self, hash: B256) -> RpcResult<Option<R>>;
 
+    /// Returns the receipts of many transactions by transaction hash, in the order of the given
+    /// hashes. Unknown transactions have a `null` receipt.
+    #[method(name = "getTransactionReceiptsByHashes")]
+    async fn transaction_receipts_by_hashes(&self, hashes: Vec<B256>) -> RpcResult<Vec<Option<R>>>;
+
     /// Returns the balance of the account of given address.
     #[method(name = "getBalance")]
     async fn balance(&self, address: Address, block_number: Option<BlockId>) -> RpcResult<U256>;
@@ -468,12 +479,7 @@ where
     /// Handler for: `eth_getUncleCountByBlockHash`
     async fn block_uncles_count_by_hash(&self, hash: B256) -> RpcResult<Option<U256>> {
         trace!(target: "rpc::eth", ?hash, "Serving eth_getUncleCountByBlockHash");
-
-        if let Some(block) = self.block_by_hash(hash, false).await? {
-            Ok(Some(U256::from(block.uncles.len())))
-        } else {
-            Ok(None)
-        }
+        Ok(EthBlocks::ommers(self, hash.into()).await?.map(|ommers| U256::from(ommers.len())))
     }
 
     /// Handler for: `eth_getUncleCountByBlockNumber`
@@ -482,12 +488,7 @@ where
         number: BlockNumberOrTag,
     ) -> RpcResult<Option<U256>> {
         trace!(target: "rpc::eth", ?number, "Serving eth_getUncleCountByBlockNumber");
-
-        if let Some(block) = self.block_by_number(number, false).await? {
-            Ok(Some(U256::from(block.uncles.len())))
-        } else {
-            Ok(None)
-        }
+        Ok(EthBlocks::ommers(self, number.into()).await?.map(|ommers| U256::from(ommers.len())))
     }
 
     /// Handler for: `eth_getBlockReceipts`
@@ -499,6 +500,12 @@ where
         Ok(EthBlocks::block_receipts(self, block_id).await?)
     }
 
+    /// Handler for: `eth_getBlobSidecars`
+    async fn blob_sidecars(&self, block_id: BlockId) -> RpcResult<Option<Vec<BlockBlobSidecar>>> {
+        trace!(target: "rpc::eth", ?block_id, "Serving eth_getBlobSidecars");
+        Ok(EthBlocks::blob_sidecars(self, block_id).await?)
+    }
+
     /// Handler for: `eth_getUncleByBlockHashAndIndex`
     async fn uncle_by_block_hash_and_index(
         &self,
@@ -605,6 +612,15 @@ where
         Ok(EthTransactions::transaction_receipt(self, hash).await?)
     }
 
+    /// Handler for: `eth_getTransactionReceiptsByHashes`
+    async fn transaction_receipts_by_hashes(
+        &self,
+        hashes: Vec<B256>,
+    ) -> RpcResult<Vec<Option<RpcReceipt<T::NetworkTypes>>>> {
+        trace!(target: "rpc::eth", count = hashes.len(), "Serving eth_getTransactionReceiptsByHashes");
+        Ok(EthTransactions::transaction_receipts_by_hashes(self, hashes).await?)
+    }
+
     /// Handler for: `eth_getBalance`
     async fn balance(&self, address: Address, block_number: Option<BlockId>) -> RpcResult<U256> {
         trace!(target: "rpc::eth", ?address, ?block_number, "Serving eth_getBalance");
@@ -3,6 +3,7 @@
 use alloy_json_rpc::RpcObject;
 use alloy_rpc_types_eth::{Filter, FilterChanges, FilterId, Log, PendingTransactionFilterKind};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_rpc_server_types::ParallelJsonArray;
 use std::future::Future;
 
 /// Rpc Interface for poll-based ethereum filter API.
@@ -30,15 +31,17 @@ pub trait EthFilterApi<T: RpcObject> {
 
     /// Returns all logs matching given filter (in a range 'from' - 'to').
     #[method(name = "getFilterLogs")]
-    async fn filter_logs(&self, id: FilterId) -> RpcResult<Vec<Log>>;
+    async fn filter_logs(&self, id: FilterId) -> RpcResult<ParallelJsonArray<Log>>;
 
     /// Uninstalls filter.
     #[method(name = "uninstallFilter")]
     async fn uninstall_filter(&self, id: FilterId) -> RpcResult<bool>;
 
     /// Returns logs matching given filter object.
+    ///
+    /// Large responses are serialized in parallel, see [`ParallelJsonArray`].
     #[method(name = "getLogs")]
-    async fn logs(&self, filter: Filter) -> RpcResult<Vec<Log>>;
+    async fn logs(&self, filter: Filter) -> RpcResult<ParallelJsonArray<Log>>;
 }
 
 /// Limits for logs queries
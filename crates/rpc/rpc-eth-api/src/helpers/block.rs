@@ -5,18 +5,20 @@ use crate::{
     node::RpcNodeCoreExt, EthApiTypes, FromEthApiError, FullEthApiTypes, RpcBlock, RpcNodeCore,
     RpcReceipt,
 };
-use alloy_eips::BlockId;
+use alloy_consensus::BlockHeader;
+use alloy_eips::{BlockId, BlockNumHash, Typed2718};
 use alloy_primitives::{Sealable, U256};
 use alloy_rlp::Encodable;
 use alloy_rpc_types_eth::{Block, BlockTransactions, Header, Index};
 use futures::Future;
 use reth_evm::ConfigureEvm;
 use reth_node_api::BlockBody;
-use reth_primitives_traits::{NodePrimitives, RecoveredBlock};
+use reth_primitives_traits::{NodePrimitives, RecoveredBlock, SignedTransaction};
 use reth_rpc_convert::RpcConvert;
+use reth_rpc_eth_types::{BlockBlobSidecar, EthApiError};
 use reth_storage_api::{BlockIdReader, BlockReader, ProviderHeader, ProviderReceipt, ProviderTx};
 use reth_transaction_pool::{PoolTransaction, TransactionPool};
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 /// Result type of the fetched block receipts.
 pub type BlockReceiptsResult<N, E> = Result<Option<Vec<RpcReceipt<N>>>, E>;
@@ -159,6 +161,9 @@ pub trait EthBlocks: LoadBlock {
     /// Returns uncle headers of given block.
     ///
     /// Returns an empty vec if there are none.
+    ///
+    /// Ommers of non-pending blocks are read from storage directly, without loading the block's
+    /// transactions.
     #[expect(clippy::type_complexity)]
     fn ommers(
         &self,
@@ -166,11 +171,23 @@ pub trait EthBlocks: LoadBlock {
     ) -> impl Future<Output = Result<Option<Vec<ProviderHeader<Self::Provider>>>, Self::Error>> + Send
     {
         async move {
-            if let Some(block) = self.recovered_block(block_id).await? {
-                Ok(block.body().ommers().map(|o| o.to_vec()))
-            } else {
-                Ok(None)
+            if block_id.is_pending() {
+                return Ok(self
+                    .recovered_block(block_id)
+                    .await?
+                    .and_then(|block| block.body().ommers().map(|o| o.to_vec())))
             }
+
+            let Some(num_hash) =
+                self.provider().resolve_block_id(block_id).map_err(Self::Error::from_eth_err)?
+            else {
+                return Ok(None)
+            };
+
+            self.spawn_blocking_io(move |this| {
+                this.provider().ommers(num_hash.hash.into()).map_err(Self::Error::from_eth_err)
+            })
+            .await
         }
     }
 
@@ -191,9 +208,7 @@ pub trait EthBlocks: LoadBlock {
                     .map_err(Self::Error::from_eth_err)?
                     .and_then(|block| block.body().ommers().map(|o| o.to_vec()))
             } else {
-                self.recovered_block(block_id)
-                    .await?
-                    .map(|block| block.body().ommers().map(|o| o.to_vec()).unwrap_or_default())
+                self.ommers(block_id).await?
             }
             .unwrap_or_default();
 
@@ -209,6 +224,75 @@ pub trait EthBlocks: LoadBlock {
             }))
         }
     }
+
+    /// Returns the blob sidecars of the EIP-4844 transactions in the given block.
+    ///
+    /// Sidecars are served from the transaction pool's blob store, which keeps the sidecars of
+    /// included transactions until their block is finalized, and then from the sidecars persisted
+    /// for recent canonical blocks. Transactions whose sidecar is no longer available are skipped.
+    ///
+    /// Returns `None` if the block does not exist.
+    fn blob_sidecars(
+        &self,
+        block_id: BlockId,
+    ) -> impl Future<Output = Result<Option<Vec<BlockBlobSidecar>>, Self::Error>> + Send {
+        async move {
+            let Some(block) = self.recovered_block(block_id).await? else { return Ok(None) };
+
+            let blob_txs = block
+                .body()
+                .transactions_iter()
+                .enumerate()
+                .filter(|(_, tx)| tx.is_eip4844())
+                .map(|(idx, tx)| (*tx.tx_hash(), idx as u64))
+                .collect::<HashMap<_, _>>();
+            if blob_txs.is_empty() {
+                return Ok(Some(Vec::new()))
+            }
+
+            let (block_hash, block_number) = (block.hash(), block.header().number());
+            let hashes = blob_txs.keys().copied().collect::<Vec<_>>();
+            let blobs = self
+                .spawn_blocking_io(move |this| {
+                    let mut blobs = this
+                        .pool()
+                        .get_all_blobs(hashes.clone())
+                        .map_err(|err| Self::Error::from_eth_err(EthApiError::from(err)))?
+                        .into_iter()
+                        .map(|(tx_hash, sidecar)| (tx_hash, Arc::unwrap_or_clone(sidecar)))
+                        .collect::<HashMap<_, _>>();
+                    if blobs.len() < hashes.len() {
+                        // ignore sidecars stored for a block that was reorged out at this height
+                        if let Some(stored) = this
+                            .provider()
+                            .block_blob_sidecars(block_number)
+                            .map_err(Self::Error::from_eth_err)?
+                            .filter(|stored| stored.block_hash == block_hash)
+                        {
+                            for (tx_hash, sidecar) in stored.sidecars {
+                                blobs.entry(tx_hash).or_insert(sidecar);
+                            }
+                        }
+                    }
+                    Ok(blobs)
+                })
+                .await?;
+
+            let mut sidecars = blobs
+                .into_iter()
+                .map(|(tx_hash, sidecar)| BlockBlobSidecar {
+                    block_hash,
+                    block_number,
+                    transaction_hash: tx_hash,
+                    transaction_index: blob_txs[&tx_hash],
+                    sidecar,
+                })
+                .collect::<Vec<_>>();
+            sidecars.sort_unstable_by_key(|sidecar| sidecar.transaction_index);
+
+            Ok(Some(sidecars))
+        }
+    }
 }
 
 /// Loads a block from database.
@@ -250,13 +334,27 @@ pub trait LoadBlock:
                 };
             }
 
-            let block_hash = match self
-                .provider()
-                .block_hash_for_id(block_id)
-                .map_err(Self::Error::from_eth_err)?
-            {
+            // Blocks that must be canonical can be checked against the canonical hash index,
+            // which avoids looking up the number of the block.
+            let indexed = match (block_id, self.canonical_hash_index()) {
+                (BlockId::Hash(hash), Some(index)) if hash.require_canonical == Some(true) => {
+                    let info = self.provider().chain_info().map_err(Self::Error::from_eth_err)?;
+                    let head = BlockNumHash::new(info.best_number, info.best_hash);
+                    index.number(&hash.block_hash, head).map(|_| hash.block_hash)
+                }
+                _ => None,
+            };
+
+            let block_hash = match indexed {
                 Some(block_hash) => block_hash,
-                None => return Ok(None),
+                None => match self
+                    .provider()
+                    .block_hash_for_id(block_id)
+                    .map_err(Self::Error::from_eth_err)?
+                {
+                    Some(block_hash) => block_hash,
+                    None => return Ok(None),
+                },
             };
 
             self.cache().get_recovered_block(block_hash).await.map_err(Self::Error::from_eth_err)
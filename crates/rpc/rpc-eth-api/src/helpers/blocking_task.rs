@@ -3,6 +3,7 @@
 
 use futures::Future;
 use reth_rpc_eth_types::EthApiError;
+use reth_storage_api::ReadScope;
 use reth_tasks::{
     pool::{BlockingTaskGuard, BlockingTaskPool},
     TaskSpawner,
@@ -52,7 +53,9 @@ pub trait SpawnBlocking: EthApiTypes + Clone + Send + Sync + 'static {
     {
         let (tx, rx) = oneshot::channel();
         let this = self.clone();
+        let scope = ReadScope::current();
         self.io_task_spawner().spawn_blocking(Box::pin(async move {
+            let _scope = scope.map(ReadScope::enter);
             let res = f(this);
             let _ = tx.send(res);
         }));
@@ -71,7 +74,11 @@ pub trait SpawnBlocking: EthApiTypes + Clone + Send + Sync + 'static {
         R: Send + 'static,
     {
         let this = self.clone();
-        let fut = self.tracing_task_pool().spawn(move || f(this));
+        let scope = ReadScope::current();
+        let fut = self.tracing_task_pool().spawn(move || {
+            let _scope = scope.map(ReadScope::enter);
+            f(this)
+        });
         async move { fut.await.map_err(|_| EthApiError::InternalBlockingTaskError)? }
     }
 }
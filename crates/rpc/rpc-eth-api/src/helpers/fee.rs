@@ -2,7 +2,7 @@
 
 use super::LoadBlock;
 use crate::FromEthApiError;
-use alloy_consensus::BlockHeader;
+use alloy_consensus::{BlockHeader, Transaction};
 use alloy_eips::eip7840::BlobParams;
 use alloy_primitives::U256;
 use alloy_rpc_types_eth::{BlockNumberOrTag, FeeHistory};
@@ -13,7 +13,9 @@ use reth_rpc_eth_types::{
     fee_history::calculate_reward_percentiles_for_block, EthApiError, FeeHistoryCache,
     FeeHistoryEntry, GasPriceOracle, RpcInvalidTransactionError,
 };
+use reth_rpc_server_types::constants::gas_oracle::MEMPOOL_SAMPLE_NUMBER;
 use reth_storage_api::{BlockIdReader, BlockReaderIdExt, HeaderProvider, ProviderHeader};
+use reth_transaction_pool::TransactionPool;
 use tracing::debug;
 
 /// Fee related functions for the [`EthApiServer`](crate::EthApiServer) trait in the
@@ -371,10 +373,25 @@ where
     }
 
     /// Returns a suggestion for the priority fee (the tip)
+    ///
+    /// This blends recently included tips with the tips of pending pool transactions, see
+    /// [`GasPriceOracle::suggest_blended_tip_cap`].
     fn suggested_priority_fee(&self) -> impl Future<Output = Result<U256, Self::Error>> + Send
     where
         Self: 'static,
     {
-        async move { self.gas_oracle().suggest_tip_cap().await.map_err(Self::Error::from_eth_err) }
+        async move {
+            self.gas_oracle()
+                .suggest_blended_tip_cap(|| {
+                    let base_fee = self.pool().block_info().pending_basefee;
+                    self.pool()
+                        .pending_transactions_sample(MEMPOOL_SAMPLE_NUMBER)
+                        .iter()
+                        .filter_map(|tx| tx.transaction.effective_tip_per_gas(base_fee))
+                        .collect()
+                })
+                .await
+                .map_err(Self::Error::from_eth_err)
+        }
     }
 }
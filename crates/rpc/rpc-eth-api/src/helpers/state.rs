@@ -7,21 +7,36 @@ use alloy_eips::BlockId;
 use alloy_primitives::{Address, Bytes, B256, U256};
 use alloy_rpc_types_eth::{Account, AccountInfo, EIP1186AccountProofResponse};
 use alloy_serde::JsonStorageKey;
-use futures::Future;
+use futures::{future::try_join_all, Future};
 use reth_chainspec::{ChainSpecProvider, EthChainSpec, EthereumHardforks};
-use reth_errors::RethError;
+use reth_errors::{ProviderError, RethError};
 use reth_evm::{ConfigureEvm, EvmEnvFor};
 use reth_rpc_eth_types::{EthApiError, PendingBlockEnv, RpcInvalidTransactionError};
 use reth_storage_api::{
     BlockIdReader, BlockNumReader, StateProvider, StateProviderBox, StateProviderFactory,
 };
 use reth_transaction_pool::TransactionPool;
+use reth_trie_common::{AccountProof, StorageProof};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+/// The minimum number of storage keys of an `eth_getProof` request that are proven in one
+/// blocking task. Requests with fewer keys are proven in a single task.
+const MIN_PROOF_STORAGE_KEYS_PER_TASK: usize = 32;
+
+/// The maximum number of blocking tasks a single `eth_getProof` request is split into.
+const MAX_PROOF_TASKS: usize = 8;
 
 /// Helper methods for `eth_` methods relating to state (accounts).
 pub trait EthState: LoadState + SpawnBlocking {
     /// Returns the maximum number of blocks into the past for generating state proofs.
     fn max_proof_window(&self) -> u64;
 
+    /// Returns the maximum size in bytes of the proof nodes in an `eth_getProof` response.
+    fn max_proof_response_size(&self) -> usize;
+
     /// Returns the number of transactions sent from an address at the given block identifier.
     ///
     /// If this is [`BlockNumberOrTag::Pending`](alloy_eips::BlockNumberOrTag) then this will
@@ -110,15 +125,76 @@ pub trait EthState: LoadState + SpawnBlocking {
                 return Err(EthApiError::ExceedsMaxProofWindow.into())
             }
 
-            self.spawn_blocking_io(move |this| {
-                let state = this.state_at_block_id(block_id)?;
-                let storage_keys = keys.iter().map(|key| key.as_b256()).collect::<Vec<_>>();
-                let proof = state
-                    .proof(Default::default(), address, &storage_keys)
-                    .map_err(Self::Error::from_eth_err)?;
-                Ok(proof.into_eip1186_response(keys))
-            })
-            .await
+            let storage_keys = keys.iter().map(|key| key.as_b256()).collect::<Vec<_>>();
+            let chunk_size =
+                storage_keys.len().div_ceil(MAX_PROOF_TASKS).max(MIN_PROOF_STORAGE_KEYS_PER_TASK);
+            // Pin the state to the block hash, so that all tasks prove against the same state.
+            let pinned_block_id = if block_id.is_pending() || storage_keys.len() <= chunk_size {
+                None
+            } else {
+                self.provider()
+                    .block_hash_for_id(block_id)
+                    .map_err(Self::Error::from_eth_err)?
+                    .map(BlockId::from)
+            };
+
+            // The size of the proof nodes is checked as soon as each part of the proof is built,
+            // so that chunks which are still queued aren't proven once the limit is exceeded.
+            let budget = Arc::new(ProofSizeBudget::new(self.max_proof_response_size()));
+            let proof = if let Some(block_id) = pinned_block_id {
+                // The storage keys are split into chunks that are proven in parallel. The first
+                // task also proves the account, every other task proves its keys with a single
+                // storage multiproof, so the upper storage trie nodes are only walked once per
+                // chunk.
+                let mut chunks = storage_keys.chunks(chunk_size).map(<[B256]>::to_vec);
+                let first_chunk = chunks.next().unwrap_or_default();
+                let account_budget = budget.clone();
+                let account_proof = self.spawn_blocking_io(move |this| {
+                    let state = this.state_at_block_id(block_id)?;
+                    let proof = state
+                        .proof(Default::default(), address, &first_chunk)
+                        .map_err(Self::Error::from_eth_err)?;
+                    account_budget.consume(account_proof_size(&proof))?;
+                    Ok(proof)
+                });
+                let storage_proofs = try_join_all(chunks.map(|chunk| {
+                    let budget = budget.clone();
+                    self.spawn_blocking_io(move |this| {
+                        budget.check()?;
+                        let state = this.state_at_block_id(block_id)?;
+                        let multiproof = state
+                            .storage_multiproof(address, &chunk, Default::default())
+                            .map_err(Self::Error::from_eth_err)?;
+                        let proofs = chunk
+                            .into_iter()
+                            .map(|slot| {
+                                multiproof.storage_proof(slot).map_err(|err| {
+                                    Self::Error::from_eth_err(ProviderError::from(err))
+                                })
+                            })
+                            .collect::<Result<Vec<_>, _>>()?;
+                        budget.consume(storage_proofs_size(&proofs))?;
+                        Ok(proofs)
+                    })
+                }));
+
+                let (mut proof, storage_proofs) =
+                    futures::try_join!(account_proof, storage_proofs)?;
+                proof.storage_proofs.extend(storage_proofs.into_iter().flatten());
+                proof
+            } else {
+                self.spawn_blocking_io(move |this| {
+                    let state = this.state_at_block_id(block_id)?;
+                    let proof = state
+                        .proof(Default::default(), address, &storage_keys)
+                        .map_err(Self::Error::from_eth_err)?;
+                    budget.consume(account_proof_size(&proof))?;
+                    Ok(proof)
+                })
+                .await?
+            };
+
+            Ok(proof.into_eip1186_response(keys))
         })
     }
 
@@ -207,10 +283,21 @@ pub trait LoadState:
 
     /// Returns the state at the given [`BlockId`] enum.
     ///
+    /// Except for the latest and pending state, the block is resolved with
+    /// [`BlockIdReader::resolve_block_id`](reth_storage_api::BlockIdReader::resolve_block_id).
+    ///
     /// Note: if not [`BlockNumberOrTag::Pending`](alloy_eips::BlockNumberOrTag) then this
     /// will only return canonical state. See also <https://github.com/paradigmxyz/reth/issues/4515>
     fn state_at_block_id(&self, at: BlockId) -> Result<StateProviderBox, Self::Error> {
-        self.provider().state_by_block_id(at).map_err(Self::Error::from_eth_err)
+        if at.is_latest() || at.is_pending() {
+            return self.provider().state_by_block_id(at).map_err(Self::Error::from_eth_err)
+        }
+        let num_hash = self
+            .provider()
+            .resolve_block_id(at)
+            .map_err(Self::Error::from_eth_err)?
+            .ok_or(EthApiError::HeaderNotFound(at))?;
+        self.state_at_hash(num_hash.hash)
     }
 
     /// Returns the _latest_ state
@@ -368,3 +455,49 @@ pub trait LoadState:
         })
     }
 }
+
+/// Tracks the size of the proof nodes built for an `eth_getProof` response against the configured
+/// limit, shared by all tasks that build parts of the proof.
+#[derive(Debug)]
+struct ProofSizeBudget {
+    /// Size in bytes of the proof nodes built so far.
+    size: AtomicUsize,
+    /// Maximum size in bytes of the proof nodes.
+    limit: usize,
+}
+
+impl ProofSizeBudget {
+    const fn new(limit: usize) -> Self {
+        Self { size: AtomicUsize::new(0), limit }
+    }
+
+    /// Returns an error if the proof nodes built so far exceed the limit.
+    fn check(&self) -> Result<(), EthApiError> {
+        let size = self.size.load(Ordering::Relaxed);
+        if size > self.limit {
+            return Err(EthApiError::ExceedsMaxProofResponseSize { size, limit: self.limit })
+        }
+        Ok(())
+    }
+
+    /// Records the size of newly built proof nodes and checks the limit.
+    fn consume(&self, size: usize) -> Result<(), EthApiError> {
+        self.size.fetch_add(size, Ordering::Relaxed);
+        self.check()
+    }
+}
+
+/// Returns the size in bytes of the given proof nodes.
+fn nodes_size(nodes: &[Bytes]) -> usize {
+    nodes.iter().map(|node| node.len()).sum()
+}
+
+/// Returns the size in bytes of the proof nodes in the given storage proofs.
+fn storage_proofs_size(proofs: &[StorageProof]) -> usize {
+    proofs.iter().map(|StorageProof { proof, .. }| nodes_size(proof)).sum()
+}
+
+/// Returns the size in bytes of the proof nodes in the given account proof.
+fn account_proof_size(proof: &AccountProof) -> usize {
+    nodes_size(&proof.proof) + storage_proofs_size(&proof.storage_proofs)
+}
@@ -11,7 +11,9 @@ use alloy_consensus::{
     BlockHeader, Transaction,
 };
 use alloy_dyn_abi::TypedData;
-use alloy_eips::{eip2718::Encodable2718, BlockId};
+use alloy_eips::{
+    eip2718::Encodable2718, eip7702::constants::EIP7702_DELEGATION_DESIGNATOR, BlockId,
+};
 use alloy_network::TransactionBuilder;
 use alloy_primitives::{Address, Bytes, TxHash, B256};
 use alloy_rpc_types_eth::{transaction::TransactionRequest, BlockNumberOrTag, TransactionInfo};
@@ -24,6 +26,7 @@ use reth_rpc_eth_types::{
     utils::binary_search, EthApiError, EthApiError::TransactionConfirmationTimeout, SignError,
     TransactionSource,
 };
+use reth_rpc_server_types::constants::MAX_RECEIPTS_BY_HASHES;
 use reth_storage_api::{
     BlockNumReader, BlockReaderIdExt, ProviderBlock, ProviderReceipt, ProviderTx, ReceiptProvider,
     TransactionsProvider,
@@ -242,6 +245,124 @@ pub trait EthTransactions: LoadTransaction<Provider: BlockReaderIdExt> {
         })
     }
 
+    /// Returns the receipts of the given transactions, `None` for transactions that are not
+    /// found, in the order of the given hashes.
+    fn transaction_receipts_by_hashes(
+        &self,
+        hashes: Vec<B256>,
+    ) -> impl Future<Output = Result<Vec<Option<RpcReceipt<Self::NetworkTypes>>>, Self::Error>> + Send
+    where
+        Self: LoadReceipt + 'static,
+    {
+        async move {
+            if hashes.len() > MAX_RECEIPTS_BY_HASHES {
+                return Err(EthApiError::InvalidParams(format!(
+                    "too many transaction hashes: {} > {MAX_RECEIPTS_BY_HASHES}",
+                    hashes.len()
+                ))
+                .into_eth_err())
+            }
+
+            let mut receipts = Vec::with_capacity(hashes.len());
+            for loaded in self.load_transactions_and_receipts(hashes).await? {
+                let receipt = match loaded {
+                    Some((tx, meta, receipt)) => {
+                        Some(self.build_transaction_receipt(tx, meta, receipt).await?)
+                    }
+                    None => None,
+                };
+                receipts.push(receipt);
+            }
+            Ok(receipts)
+        }
+    }
+
+    /// Helper method that loads the transactions and receipts of many transaction hashes.
+    ///
+    /// All hashes are resolved to transaction numbers first, the transactions and receipts are
+    /// then read in transaction number order, so transactions of the same block or of nearby
+    /// blocks are read sequentially from static files and share their block lookups.
+    #[expect(clippy::complexity)]
+    fn load_transactions_and_receipts(
+        &self,
+        hashes: Vec<TxHash>,
+    ) -> impl Future<
+        Output = Result<
+            Vec<
+                Option<(
+                    ProviderTx<Self::Provider>,
+                    TransactionMeta,
+                    ProviderReceipt<Self::Provider>,
+                )>,
+            >,
+            Self::Error,
+        >,
+    > + Send
+    where
+        Self: 'static,
+    {
+        let provider = self.provider().clone();
+        self.spawn_blocking_io(move |_| {
+            let mut ids = Vec::with_capacity(hashes.len());
+            for (position, hash) in hashes.iter().enumerate() {
+                if let Some(id) =
+                    provider.transaction_id(*hash).map_err(Self::Error::from_eth_err)?
+                {
+                    ids.push((id, position));
+                }
+            }
+            ids.sort_unstable();
+
+            let mut loaded = (0..hashes.len()).map(|_| None).collect::<Vec<_>>();
+            let mut block = None;
+            for (id, position) in ids {
+                let tx_hash = hashes[position];
+                // the transaction can be reorged out between the lookups
+                let Some(tx) = provider
+                    .transaction_by_id(id)
+                    .map_err(Self::Error::from_eth_err)?
+                    .filter(|tx| *tx.tx_hash() == tx_hash)
+                else {
+                    continue
+                };
+                let Some(block_number) =
+                    provider.transaction_block(id).map_err(Self::Error::from_eth_err)?
+                else {
+                    continue
+                };
+
+                if block.as_ref().is_none_or(|(number, _, _)| *number != block_number) {
+                    let header =
+                        provider.sealed_header(block_number).map_err(Self::Error::from_eth_err)?;
+                    let indices = provider
+                        .block_body_indices(block_number)
+                        .map_err(Self::Error::from_eth_err)?;
+                    block = header
+                        .zip(indices)
+                        .map(|(header, indices)| (block_number, header, indices.first_tx_num()));
+                }
+                let Some((_, header, first_tx_num)) = &block else { continue };
+
+                let Some(receipt) = provider.receipt(id).map_err(Self::Error::from_eth_err)? else {
+                    continue
+                };
+
+                let meta = TransactionMeta {
+                    tx_hash,
+                    index: id - first_tx_num,
+                    block_hash: header.hash(),
+                    block_number,
+                    base_fee: header.base_fee_per_gas(),
+                    excess_blob_gas: header.excess_blob_gas(),
+                    timestamp: header.timestamp(),
+                };
+                loaded[position] = Some((tx, meta, receipt));
+            }
+
+            Ok(loaded)
+        })
+    }
+
     /// Get transaction by [`BlockId`] and index of transaction within that block.
     ///
     /// Returns `Ok(None)` if the block does not exist, or index is out of range.
@@ -298,8 +419,10 @@ pub trait EthTransactions: LoadTransaction<Provider: BlockReaderIdExt> {
                 }
             }
 
-            // Check if the sender is a contract
-            if !self.get_code(sender, None).await?.is_empty() {
+            // Check if the sender is a contract, EIP-7702 delegated accounts can still send
+            // transactions
+            let code = self.get_code(sender, None).await?;
+            if !code.is_empty() && !code.starts_with(&EIP7702_DELEGATION_DESIGNATOR) {
                 return Ok(None);
             }
 
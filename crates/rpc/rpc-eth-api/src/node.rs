@@ -2,7 +2,7 @@
 
 use reth_node_api::{FullNodeComponents, NodeTypes, PrimitivesTy};
 use reth_payload_builder::PayloadBuilderHandle;
-use reth_rpc_eth_types::EthStateCache;
+use reth_rpc_eth_types::{CanonicalHashIndex, EthStateCache};
 use reth_storage_api::{BlockReader, ProviderBlock, ProviderReceipt};
 
 /// Helper trait that provides the same interface as [`FullNodeComponents`] but without requiring
@@ -89,4 +89,9 @@ pub trait RpcNodeCoreExt: RpcNodeCore<Provider: BlockReader> {
     fn cache(
         &self,
     ) -> &EthStateCache<ProviderBlock<Self::Provider>, ProviderReceipt<Self::Provider>>;
+
+    /// Returns the index of recent canonical block hashes, if one is maintained.
+    fn canonical_hash_index(&self) -> Option<&CanonicalHashIndex> {
+        None
+    }
 }
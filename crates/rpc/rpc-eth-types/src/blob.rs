@@ -0,0 +1,23 @@
+//! Blob sidecar types served by `eth_getBlobSidecars`.
+
+use alloy_eips::eip7594::BlobTransactionSidecarVariant;
+use alloy_primitives::{BlockHash, BlockNumber, TxHash};
+use serde::{Deserialize, Serialize};
+
+/// The blob sidecar of an EIP-4844 transaction included in a block.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockBlobSidecar {
+    /// Hash of the block the transaction is included in.
+    pub block_hash: BlockHash,
+    /// Number of the block the transaction is included in.
+    #[serde(with = "alloy_serde::quantity")]
+    pub block_number: BlockNumber,
+    /// Hash of the blob transaction.
+    pub transaction_hash: TxHash,
+    /// Index of the blob transaction in the block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub transaction_index: u64,
+    /// The blobs, commitments and proofs of the transaction.
+    pub sidecar: BlobTransactionSidecarVariant,
+}
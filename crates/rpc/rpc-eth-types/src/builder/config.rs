@@ -1,19 +1,21 @@
 //! Configuration for `eth` namespace APIs.
 
-use std::time::Duration;
+use std::{path::PathBuf, time::Duration};
 
 use crate::{
     EthStateCacheConfig, FeeHistoryCacheConfig, GasPriceOracleConfig, RPC_DEFAULT_GAS_CAP,
 };
 use reth_rpc_server_types::constants::{
-    default_max_tracing_requests, DEFAULT_ETH_PROOF_WINDOW, DEFAULT_MAX_BLOCKS_PER_FILTER,
-    DEFAULT_MAX_LOGS_PER_RESPONSE, DEFAULT_MAX_SIMULATE_BLOCKS, DEFAULT_MAX_TRACE_FILTER_BLOCKS,
-    DEFAULT_PROOF_PERMITS,
+    default_max_tracing_requests, DEFAULT_ETH_PROOF_WINDOW, DEFAULT_FILTER_REORG_WINDOW,
+    DEFAULT_FILTER_TTL_SECS, DEFAULT_MAX_BLOCKS_PER_FILTER, DEFAULT_MAX_LOGS_PER_RESPONSE,
+    DEFAULT_MAX_PROOF_RESPONSE_SIZE, DEFAULT_MAX_SIMULATE_BLOCKS, DEFAULT_MAX_TRACE_FILTER_BLOCKS,
+    DEFAULT_MAX_TRACE_FILTER_RESULTS, DEFAULT_PROOF_PERMITS, DEFAULT_SERIALIZATION_MEMORY_BUDGET,
+    DEFAULT_TRACE_FILTER_CONCURRENCY,
 };
 use serde::{Deserialize, Serialize};
 
 /// Default value for stale filter ttl
-pub const DEFAULT_STALE_FILTER_TTL: Duration = Duration::from_secs(5 * 60);
+pub const DEFAULT_STALE_FILTER_TTL: Duration = Duration::from_secs(DEFAULT_FILTER_TTL_SECS);
 
 /// Additional config values for the eth namespace.
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
@@ -24,14 +26,37 @@ pub struct EthConfig {
     pub gas_oracle: GasPriceOracleConfig,
     /// The maximum number of blocks into the past for generating state proofs.
     pub eth_proof_window: u64,
+    /// The maximum size in bytes of the proof nodes in an `eth_getProof` response.
+    pub max_proof_response_size: usize,
     /// The maximum number of tracing calls that can be executed in concurrently.
     pub max_tracing_requests: usize,
     /// Maximum number of blocks for `trace_filter` requests.
     pub max_trace_filter_blocks: u64,
+    /// Maximum number of traces that can be returned in a single `trace_filter` response.
+    pub max_trace_filter_results: usize,
+    /// Number of blocks that are replayed concurrently by a single `trace_filter` request.
+    pub trace_filter_concurrency: usize,
+    /// Maximum size in bytes of the cached `debug_traceBlock` results, keyed by block hash and
+    /// tracing options.
+    ///
+    /// Caching is disabled if zero.
+    pub trace_cache_size: u64,
     /// Maximum number of blocks that could be scanned per filter request in `eth_getLogs` calls.
     pub max_blocks_per_filter: u64,
     /// Maximum number of logs that can be returned in a single response in `eth_getLogs` calls.
     pub max_logs_per_response: usize,
+    /// Maximum number of bytes buffered while serializing large `eth_getLogs` responses in
+    /// parallel.
+    pub serialization_memory_budget: usize,
+    /// Maximum number of new blocks that are processed per `eth_getFilterChanges` call.
+    pub filter_max_blocks_per_poll: u64,
+    /// Number of most recently polled blocks a filter tracks to detect reorgs.
+    pub filter_reorg_window: u64,
+    /// Maximum number of consecutive new blocks that `eth_subscribe` subscriptions process at
+    /// once.
+    ///
+    /// Batching is disabled if zero.
+    pub subscription_max_compacted_blocks: usize,
     /// Gas limit for `eth_call` and call tracing RPC methods.
     ///
     /// Defaults to [`RPC_DEFAULT_GAS_CAP`]
@@ -54,6 +79,9 @@ impl EthConfig {
             .max_blocks_per_filter(self.max_blocks_per_filter)
             .max_logs_per_response(self.max_logs_per_response)
             .stale_filter_ttl(self.stale_filter_ttl)
+            .serialization_memory_budget(self.serialization_memory_budget)
+            .max_blocks_per_poll(self.filter_max_blocks_per_poll)
+            .reorg_window(self.filter_reorg_window)
     }
 }
 
@@ -63,10 +91,18 @@ impl Default for EthConfig {
             cache: EthStateCacheConfig::default(),
             gas_oracle: GasPriceOracleConfig::default(),
             eth_proof_window: DEFAULT_ETH_PROOF_WINDOW,
+            max_proof_response_size: DEFAULT_MAX_PROOF_RESPONSE_SIZE,
             max_tracing_requests: default_max_tracing_requests(),
             max_trace_filter_blocks: DEFAULT_MAX_TRACE_FILTER_BLOCKS,
+            max_trace_filter_results: DEFAULT_MAX_TRACE_FILTER_RESULTS,
+            trace_filter_concurrency: DEFAULT_TRACE_FILTER_CONCURRENCY,
+            trace_cache_size: 0,
             max_blocks_per_filter: DEFAULT_MAX_BLOCKS_PER_FILTER,
             max_logs_per_response: DEFAULT_MAX_LOGS_PER_RESPONSE,
+            serialization_memory_budget: DEFAULT_SERIALIZATION_MEMORY_BUDGET,
+            filter_max_blocks_per_poll: u64::MAX,
+            filter_reorg_window: DEFAULT_FILTER_REORG_WINDOW,
+            subscription_max_compacted_blocks: 0,
             rpc_gas_cap: RPC_DEFAULT_GAS_CAP.into(),
             rpc_max_simulate_blocks: DEFAULT_MAX_SIMULATE_BLOCKS,
             stale_filter_ttl: DEFAULT_STALE_FILTER_TTL,
@@ -107,12 +143,60 @@ impl EthConfig {
         self
     }
 
+    /// Configures the maximum number of traces per `trace_filter` response
+    pub const fn max_trace_filter_results(mut self, max_results: usize) -> Self {
+        self.max_trace_filter_results = max_results;
+        self
+    }
+
+    /// Configures the number of blocks replayed concurrently per `trace_filter` request
+    pub const fn trace_filter_concurrency(mut self, concurrency: usize) -> Self {
+        self.trace_filter_concurrency = concurrency;
+        self
+    }
+
+    /// Configures the maximum size in bytes of the cached `debug_traceBlock` results
+    pub const fn trace_cache_size(mut self, size: u64) -> Self {
+        self.trace_cache_size = size;
+        self
+    }
+
     /// Configures the maximum number of logs per response
     pub const fn max_logs_per_response(mut self, max_logs: usize) -> Self {
         self.max_logs_per_response = max_logs;
         self
     }
 
+    /// Configures the maximum number of bytes buffered while serializing large responses
+    pub const fn serialization_memory_budget(mut self, budget: usize) -> Self {
+        self.serialization_memory_budget = budget;
+        self
+    }
+
+    /// Configures the maximum number of new blocks processed per `eth_getFilterChanges` call
+    pub const fn filter_max_blocks_per_poll(mut self, max_blocks: u64) -> Self {
+        self.filter_max_blocks_per_poll = max_blocks;
+        self
+    }
+
+    /// Configures the number of recently polled blocks a filter tracks to detect reorgs
+    pub const fn filter_reorg_window(mut self, window: u64) -> Self {
+        self.filter_reorg_window = window;
+        self
+    }
+
+    /// Configures the maximum number of new blocks subscriptions process at once
+    pub const fn subscription_max_compacted_blocks(mut self, max_blocks: usize) -> Self {
+        self.subscription_max_compacted_blocks = max_blocks;
+        self
+    }
+
+    /// Configures how long a filter remains valid after the last poll
+    pub const fn stale_filter_ttl(mut self, duration: Duration) -> Self {
+        self.stale_filter_ttl = duration;
+        self
+    }
+
     /// Configures the maximum gas limit for `eth_call` and call tracing RPC methods
     pub const fn rpc_gas_cap(mut self, rpc_gas_cap: u64) -> Self {
         self.rpc_gas_cap = rpc_gas_cap;
@@ -131,6 +215,12 @@ impl EthConfig {
         self
     }
 
+    /// Configures the maximum size in bytes of the proof nodes in an `eth_getProof` response.
+    pub const fn max_proof_response_size(mut self, size: usize) -> Self {
+        self.max_proof_response_size = size;
+        self
+    }
+
     /// Configures the number of getproof requests
     pub const fn proof_permits(mut self, permits: usize) -> Self {
         self.proof_permits = permits;
@@ -154,6 +244,25 @@ pub struct EthFilterConfig {
     /// A filter is considered stale if it has not been polled for longer than this duration and
     /// will be removed.
     pub stale_filter_ttl: Duration,
+    /// Maximum number of bytes buffered while serializing large `eth_getLogs` and
+    /// `eth_getFilterLogs` responses in parallel.
+    pub serialization_memory_budget: usize,
+    /// Maximum number of new blocks that are processed per `eth_getFilterChanges` call.
+    ///
+    /// Blocks beyond this limit are returned by subsequent polls. If `None` then all new blocks
+    /// are processed at once.
+    pub max_blocks_per_poll: Option<u64>,
+    /// Number of most recently polled blocks a log or block filter tracks to detect reorgs.
+    ///
+    /// If a tracked block is reorged, logs that were returned for it are returned again with
+    /// `removed: true` and the filter continues from the new canonical chain. Reorgs deeper than
+    /// the window are not detected. Zero disables reorg tracking.
+    pub reorg_window: u64,
+    /// File the installed log and block filters are persisted to, so they survive node
+    /// restarts.
+    ///
+    /// If `None` then filters are not persisted.
+    pub persistence_path: Option<PathBuf>,
 }
 
 impl EthFilterConfig {
@@ -175,6 +284,30 @@ impl EthFilterConfig {
         self.stale_filter_ttl = duration;
         self
     }
+
+    /// Sets the maximum number of bytes buffered while serializing large responses in parallel.
+    pub const fn serialization_memory_budget(mut self, budget: usize) -> Self {
+        self.serialization_memory_budget = budget;
+        self
+    }
+
+    /// Sets the maximum number of new blocks that are processed per `eth_getFilterChanges` call.
+    pub const fn max_blocks_per_poll(mut self, num: u64) -> Self {
+        self.max_blocks_per_poll = Some(num);
+        self
+    }
+
+    /// Sets the number of recently polled blocks a filter tracks to detect reorgs.
+    pub const fn reorg_window(mut self, window: u64) -> Self {
+        self.reorg_window = window;
+        self
+    }
+
+    /// Sets the file the installed filters are persisted to.
+    pub fn persistence_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.persistence_path = Some(path.into());
+        self
+    }
 }
 
 impl Default for EthFilterConfig {
@@ -184,6 +317,10 @@ impl Default for EthFilterConfig {
             max_logs_per_response: None,
             // 5min
             stale_filter_ttl: Duration::from_secs(5 * 60),
+            serialization_memory_budget: DEFAULT_SERIALIZATION_MEMORY_BUDGET,
+            max_blocks_per_poll: None,
+            reorg_window: DEFAULT_FILTER_REORG_WINDOW,
+            persistence_path: None,
         }
     }
 }
@@ -78,6 +78,15 @@ impl reth_storage_api::StorageRootProvider for StateProviderTraitObjWrapper<'_>
     ) -> ProviderResult<reth_trie::StorageMultiProof> {
         self.0.storage_multiproof(address, slots, hashed_storage)
     }
+
+    fn storage_multiproof_from_nodes(
+        &self,
+        input: reth_trie::TrieInput,
+        address: Address,
+        slots: &[B256],
+    ) -> ProviderResult<reth_trie::StorageMultiProof> {
+        self.0.storage_multiproof_from_nodes(input, address, slots)
+    }
 }
 
 impl reth_storage_api::StateProofProvider for StateProviderTraitObjWrapper<'_> {
@@ -9,8 +9,11 @@ use reth_chain_state::CanonStateNotification;
 use reth_errors::{ProviderError, ProviderResult};
 use reth_execution_types::Chain;
 use reth_primitives_traits::{Block, BlockBody, NodePrimitives, RecoveredBlock};
-use reth_storage_api::{BlockReader, TransactionVariant};
-use reth_tasks::{TaskSpawner, TokioTaskExecutor};
+use reth_storage_api::{BlockReader, ReadScope, TransactionVariant};
+use reth_tasks::{
+    memory::{MemoryBudget, MemoryConsumer},
+    TaskSpawner, TokioTaskExecutor,
+};
 use schnellru::{ByLength, Limiter};
 use std::{
     future::Future,
@@ -103,6 +106,7 @@ impl<B: Block, R: Send + Sync> EthStateCache<B, R> {
             action_rx: UnboundedReceiverStream::new(rx),
             action_task_spawner,
             rate_limiter: Arc::new(Semaphore::new(max_concurrent_db_operations)),
+            memory_consumer: None,
         };
         let cache = Self { to_service };
         (cache, service)
@@ -150,6 +154,18 @@ impl<B: Block, R: Send + Sync> EthStateCache<B, R> {
         this
     }
 
+    /// Registers the cache with the given [`MemoryBudget`].
+    ///
+    /// The cache reports the memory of all cached blocks, receipts and headers to the budget and
+    /// evicts the least recently used entries if the budget asks it to release memory.
+    pub fn register_memory_budget(&self, budget: &MemoryBudget, weight: u32) {
+        let to_service = self.to_service.clone();
+        let consumer = budget.register("eth_state_cache", weight, move |bytes| {
+            to_service.send(CacheAction::Reclaim { bytes }).map_or(0, |_| bytes)
+        });
+        let _ = self.to_service.send(CacheAction::SetMemoryConsumer { consumer });
+    }
+
     /// Requests the  [`RecoveredBlock`] for the block hash
     ///
     /// Returns `None` if the block does not exist.
@@ -158,7 +174,12 @@ impl<B: Block, R: Send + Sync> EthStateCache<B, R> {
         block_hash: B256,
     ) -> ProviderResult<Option<Arc<RecoveredBlock<B>>>> {
         let (response_tx, rx) = oneshot::channel();
-        let _ = self.to_service.send(CacheAction::GetBlockWithSenders { block_hash, response_tx });
+        let scope = ReadScope::current();
+        let _ = self.to_service.send(CacheAction::GetBlockWithSenders {
+            block_hash,
+            scope,
+            response_tx,
+        });
         rx.await.map_err(|_| CacheServiceUnavailable)?
     }
 
@@ -167,7 +188,8 @@ impl<B: Block, R: Send + Sync> EthStateCache<B, R> {
     /// Returns `None` if the block was not found.
     pub async fn get_receipts(&self, block_hash: B256) -> ProviderResult<Option<Arc<Vec<R>>>> {
         let (response_tx, rx) = oneshot::channel();
-        let _ = self.to_service.send(CacheAction::GetReceipts { block_hash, response_tx });
+        let scope = ReadScope::current();
+        let _ = self.to_service.send(CacheAction::GetReceipts { block_hash, scope, response_tx });
         rx.await.map_err(|_| CacheServiceUnavailable)?
     }
 
@@ -229,7 +251,8 @@ impl<B: Block, R: Send + Sync> EthStateCache<B, R> {
     /// Returns an error if the header is not found.
     pub async fn get_header(&self, block_hash: B256) -> ProviderResult<B::Header> {
         let (response_tx, rx) = oneshot::channel();
-        let _ = self.to_service.send(CacheAction::GetHeader { block_hash, response_tx });
+        let scope = ReadScope::current();
+        let _ = self.to_service.send(CacheAction::GetHeader { block_hash, scope, response_tx });
         rx.await.map_err(|_| CacheServiceUnavailable)?
     }
 
@@ -321,6 +344,8 @@ pub(crate) struct EthStateCacheService<
     ///
     /// This restricts the max concurrent fetch tasks at the same time.
     rate_limiter: Arc<Semaphore>,
+    /// Registration with the memory budget, if any.
+    memory_consumer: Option<MemoryConsumer>,
 }
 
 impl<Provider, Tasks> EthStateCacheService<Provider, Tasks>
@@ -417,10 +442,34 @@ where
         self.headers_cache.shrink_to(min_capacity);
     }
 
+    /// Evicts the least recently used entries of all caches, proportionally to their memory
+    /// usage, until at least `bytes` are freed.
+    fn reclaim(&mut self, bytes: usize) {
+        let blocks = self.full_block_cache.memory_usage();
+        let receipts = self.receipts_cache.memory_usage();
+        let total = blocks + self.headers_cache.memory_usage() + receipts;
+        if total == 0 {
+            return
+        }
+
+        let share = |usage: usize| (bytes as u128 * usage as u128 / total as u128) as usize;
+        let mut freed = self.full_block_cache.evict(share(blocks));
+        freed += self.receipts_cache.evict(share(receipts));
+        self.headers_cache.evict(bytes.saturating_sub(freed));
+    }
+
     fn update_cached_metrics(&self) {
         self.full_block_cache.update_cached_metrics();
         self.receipts_cache.update_cached_metrics();
         self.headers_cache.update_cached_metrics();
+
+        if let Some(consumer) = &self.memory_consumer {
+            consumer.report(
+                self.full_block_cache.memory_usage() +
+                    self.receipts_cache.memory_usage() +
+                    self.headers_cache.memory_usage(),
+            );
+        }
     }
 }
 
@@ -456,7 +505,7 @@ where
                             let receipts = this.receipts_cache.get(&block_hash).cloned();
                             let _ = response_tx.send((block, receipts));
                         }
-                        CacheAction::GetBlockWithSenders { block_hash, response_tx } => {
+                        CacheAction::GetBlockWithSenders { block_hash, scope, response_tx } => {
                             if let Some(block) = this.full_block_cache.get(&block_hash).cloned() {
                                 let _ = response_tx.send(Ok(Some(block)));
                                 continue
@@ -472,6 +521,7 @@ where
                                 this.action_task_spawner.spawn_blocking(Box::pin(async move {
                                     // Acquire permit
                                     let _permit = rate_limiter.acquire().await;
+                                    let _scope = scope.map(ReadScope::enter);
                                     // Only look in the database to prevent situations where we
                                     // looking up the tree is blocking
                                     let block_sender = provider
@@ -484,7 +534,7 @@ where
                                 }));
                             }
                         }
-                        CacheAction::GetReceipts { block_hash, response_tx } => {
+                        CacheAction::GetReceipts { block_hash, scope, response_tx } => {
                             // check if block is cached
                             if let Some(receipts) = this.receipts_cache.get(&block_hash).cloned() {
                                 let _ = response_tx.send(Ok(Some(receipts)));
@@ -501,6 +551,7 @@ where
                                 this.action_task_spawner.spawn_blocking(Box::pin(async move {
                                     // Acquire permit
                                     let _permit = rate_limiter.acquire().await;
+                                    let _scope = scope.map(ReadScope::enter);
                                     let res = provider
                                         .receipts_by_block(block_hash.into())
                                         .map(|maybe_receipts| maybe_receipts.map(Arc::new));
@@ -509,7 +560,7 @@ where
                                 }));
                             }
                         }
-                        CacheAction::GetHeader { block_hash, response_tx } => {
+                        CacheAction::GetHeader { block_hash, scope, response_tx } => {
                             // check if the header is cached
                             if let Some(header) = this.headers_cache.get(&block_hash).cloned() {
                                 let _ = response_tx.send(Ok(header));
@@ -533,6 +584,7 @@ where
                                 this.action_task_spawner.spawn_blocking(Box::pin(async move {
                                     // Acquire permit
                                     let _permit = rate_limiter.acquire().await;
+                                    let _scope = scope.map(ReadScope::enter);
                                     let header = provider.header(&block_hash).and_then(|header| {
                                         header.ok_or_else(|| {
                                             ProviderError::HeaderNotFound(block_hash.into())
@@ -618,6 +670,10 @@ where
 
                             let _ = response_tx.send(blocks);
                         }
+                        CacheAction::SetMemoryConsumer { consumer } => {
+                            this.memory_consumer = Some(consumer);
+                        }
+                        CacheAction::Reclaim { bytes } => this.reclaim(bytes),
                     };
                     this.update_cached_metrics();
                 }
@@ -630,14 +686,18 @@ where
 enum CacheAction<B: Block, R> {
     GetBlockWithSenders {
         block_hash: B256,
+        /// The read scope of the requester, the database reads are attributed to.
+        scope: Option<&'static str>,
         response_tx: BlockWithSendersResponseSender<B>,
     },
     GetHeader {
         block_hash: B256,
+        scope: Option<&'static str>,
         response_tx: HeaderResponseSender<B::Header>,
     },
     GetReceipts {
         block_hash: B256,
+        scope: Option<&'static str>,
         response_tx: ReceiptsResponseSender<R>,
     },
     GetCachedBlock {
@@ -671,6 +731,12 @@ enum CacheAction<B: Block, R> {
         max_blocks: usize,
         response_tx: CachedParentBlocksResponseSender<B>,
     },
+    SetMemoryConsumer {
+        consumer: MemoryConsumer,
+    },
+    Reclaim {
+        bytes: usize,
+    },
 }
 
 struct BlockReceipts<R> {
@@ -115,6 +115,30 @@ where
         }
     }
 
+    /// Evicts the least recently used entries until at least `bytes` of tracked memory are freed
+    /// or the cache is empty.
+    ///
+    /// Returns the number of freed bytes.
+    pub fn evict(&mut self, bytes: usize) -> usize
+    where
+        V: InMemorySize,
+    {
+        let mut freed = 0;
+        while freed < bytes {
+            let Some((_, evicted)) = self.cache.pop_oldest() else { break };
+            let size = evicted.size();
+            self.memory_usage = self.memory_usage.saturating_sub(size);
+            freed += size;
+        }
+        freed
+    }
+
+    /// Returns the tracked heap usage of the cached values in bytes.
+    #[inline]
+    pub const fn memory_usage(&self) -> usize {
+        self.memory_usage
+    }
+
     /// Shrinks the capacity of the queue with a lower limit.
     #[inline]
     pub fn shrink_to(&mut self, min_capacity: usize) {
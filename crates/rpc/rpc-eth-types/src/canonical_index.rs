@@ -0,0 +1,320 @@
+//! In-memory index of recent canonical block hashes.
+
+use alloy_eips::BlockNumHash;
+use alloy_primitives::{map::B256Map, BlockNumber, B256};
+use futures::{Stream, StreamExt};
+use parking_lot::RwLock;
+use reth_chain_state::CanonStateNotification;
+use reth_primitives_traits::NodePrimitives;
+use std::{collections::VecDeque, ops::Range, sync::Arc};
+
+/// Default number of recent canonical blocks tracked by the [`CanonicalHashIndex`].
+pub const DEFAULT_CANONICAL_HASH_INDEX_SIZE: usize = 8192;
+
+/// Index of the most recent canonical block hashes and their numbers.
+///
+/// This is kept up to date with canonical state notifications and allows answering
+/// number-to-hash, hash-to-number and ancestry queries for recent blocks without a database
+/// lookup, which are common in hot RPC paths like filter polling and reorg checks.
+///
+/// The index is updated asynchronously and can lag behind the chain, so lookups require the
+/// current canonical head and only succeed if the index is in sync with it. All lookups return
+/// `None` if the index is behind or the block is outside of the tracked window, in which case
+/// callers are expected to fall back to the provider.
+#[derive(Debug, Clone)]
+pub struct CanonicalHashIndex {
+    inner: Arc<RwLock<CanonicalHashIndexInner>>,
+}
+
+impl CanonicalHashIndex {
+    /// Creates a new index that tracks at most `max_blocks` recent canonical blocks.
+    pub fn new(max_blocks: usize) -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(CanonicalHashIndexInner {
+                lowest: 0,
+                hashes: VecDeque::with_capacity(max_blocks),
+                numbers: Default::default(),
+                max_blocks,
+            })),
+        }
+    }
+
+    /// Returns the number of blocks currently tracked.
+    pub fn len(&self) -> usize {
+        self.inner.read().hashes.len()
+    }
+
+    /// Returns `true` if no blocks are tracked.
+    pub fn is_empty(&self) -> bool {
+        self.inner.read().hashes.is_empty()
+    }
+
+    /// Returns the highest tracked canonical block.
+    pub fn tip(&self) -> Option<BlockNumHash> {
+        self.inner.read().tip()
+    }
+
+    /// Returns the number of the canonical block with the given hash, if tracked and the index is
+    /// in sync with the given canonical head.
+    pub fn number(&self, hash: &B256, head: BlockNumHash) -> Option<BlockNumber> {
+        let inner = self.inner.read();
+        if inner.tip() != Some(head) {
+            return None
+        }
+        inner.numbers.get(hash).copied()
+    }
+
+    /// Returns the hash of the canonical block with the given number, if tracked and the index is
+    /// in sync with the given canonical head.
+    pub fn hash(&self, number: BlockNumber, head: BlockNumHash) -> Option<B256> {
+        let inner = self.inner.read();
+        if inner.tip() != Some(head) {
+            return None
+        }
+        inner.hash(number)
+    }
+
+    /// Returns the canonical hashes for the given block range, if the entire range is tracked and
+    /// the index is in sync with the given canonical head.
+    pub fn canonical_hashes_range(
+        &self,
+        range: Range<BlockNumber>,
+        head: BlockNumHash,
+    ) -> Option<Vec<B256>> {
+        let inner = self.inner.read();
+        if inner.tip() != Some(head) ||
+            range.start < inner.lowest ||
+            range.end > inner.lowest + inner.hashes.len() as u64
+        {
+            return None
+        }
+        let start = (range.start - inner.lowest) as usize;
+        let end = (range.end.max(range.start) - inner.lowest) as usize;
+        Some(inner.hashes.range(start..end).copied().collect())
+    }
+
+    /// Returns whether the block with the given hash is canonical and at most `depth` blocks below
+    /// the given canonical head.
+    ///
+    /// A `depth` of zero only matches the head itself. Returns `None` if the index is not in sync
+    /// with the head, or if the block isn't tracked and `depth` reaches beyond the tracked window.
+    pub fn is_canonical_ancestor(
+        &self,
+        hash: &B256,
+        depth: u64,
+        head: BlockNumHash,
+    ) -> Option<bool> {
+        let inner = self.inner.read();
+        if inner.tip() != Some(head) {
+            return None
+        }
+        match inner.numbers.get(hash) {
+            Some(number) => Some(head.number - number <= depth),
+            None if depth < inner.hashes.len() as u64 => Some(false),
+            None => None,
+        }
+    }
+
+    /// Returns whether both blocks are canonical and `descendant` is not lower than `ancestor`.
+    ///
+    /// Returns `None` if the index is not in sync with the given canonical head or one of the
+    /// blocks isn't tracked.
+    pub fn is_canonical_descendant(
+        &self,
+        ancestor: &B256,
+        descendant: &B256,
+        head: BlockNumHash,
+    ) -> Option<bool> {
+        let inner = self.inner.read();
+        if inner.tip() != Some(head) {
+            return None
+        }
+        Some(inner.numbers.get(ancestor)? <= inner.numbers.get(descendant)?)
+    }
+
+    /// Inserts a new canonical block.
+    ///
+    /// If the block doesn't extend the current tip, all tracked blocks at or above its number are
+    /// considered reorged out and are removed. If there's a gap to the current tip, the index is
+    /// reset.
+    pub fn insert(&self, block: BlockNumHash) {
+        self.inner.write().insert(block)
+    }
+
+    /// Updates the index with the blocks of a canonical state notification.
+    pub fn on_canon_state_notification<N: NodePrimitives>(
+        &self,
+        notification: &CanonStateNotification<N>,
+    ) {
+        let committed = notification.committed();
+        let mut inner = self.inner.write();
+        for block in committed.blocks_iter() {
+            inner.insert(block.num_hash());
+        }
+    }
+}
+
+impl Default for CanonicalHashIndex {
+    fn default() -> Self {
+        Self::new(DEFAULT_CANONICAL_HASH_INDEX_SIZE)
+    }
+}
+
+/// Container type for shared state in [`CanonicalHashIndex`]
+#[derive(Debug)]
+struct CanonicalHashIndexInner {
+    /// Number of the first block in `hashes`.
+    lowest: BlockNumber,
+    /// Consecutive canonical hashes starting at `lowest`.
+    hashes: VecDeque<B256>,
+    /// Reverse lookup of the tracked hashes.
+    numbers: B256Map<BlockNumber>,
+    /// Maximum number of tracked blocks.
+    max_blocks: usize,
+}
+
+impl CanonicalHashIndexInner {
+    fn tip(&self) -> Option<BlockNumHash> {
+        let hash = *self.hashes.back()?;
+        Some(BlockNumHash::new(self.lowest + self.hashes.len() as u64 - 1, hash))
+    }
+
+    fn hash(&self, number: BlockNumber) -> Option<B256> {
+        let idx = number.checked_sub(self.lowest)?;
+        self.hashes.get(idx as usize).copied()
+    }
+
+    fn insert(&mut self, block: BlockNumHash) {
+        if self.max_blocks == 0 {
+            return
+        }
+
+        let next = self.lowest + self.hashes.len() as u64;
+        if self.hashes.is_empty() || block.number > next || block.number < self.lowest {
+            // Not connected to the tracked chain, start over.
+            self.hashes.clear();
+            self.numbers.clear();
+            self.lowest = block.number;
+        } else {
+            // Drop everything that's replaced by the new block.
+            for hash in self.hashes.drain((block.number - self.lowest) as usize..) {
+                self.numbers.remove(&hash);
+            }
+        }
+
+        self.hashes.push_back(block.hash);
+        self.numbers.insert(block.hash, block.number);
+
+        while self.hashes.len() > self.max_blocks {
+            if let Some(hash) = self.hashes.pop_front() {
+                self.numbers.remove(&hash);
+            }
+            self.lowest += 1;
+        }
+    }
+}
+
+/// Awaits for new chain events and inserts the new canonical blocks into the index.
+pub async fn canonical_hash_index_new_blocks_task<St, N>(index: CanonicalHashIndex, mut events: St)
+where
+    St: Stream<Item = CanonStateNotification<N>> + Unpin + 'static,
+    N: NodePrimitives,
+{
+    while let Some(event) = events.next().await {
+        index.on_canon_state_notification(&event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(number: u64, fork: u8) -> BlockNumHash {
+        let mut hash = B256::with_last_byte(fork);
+        hash[..8].copy_from_slice(&number.to_be_bytes());
+        BlockNumHash::new(number, hash)
+    }
+
+    #[test]
+    fn tracks_bounded_window() {
+        let index = CanonicalHashIndex::new(3);
+        for number in 10..15 {
+            index.insert(block(number, 0));
+        }
+
+        let head = block(14, 0);
+        assert_eq!(index.len(), 3);
+        assert_eq!(index.tip(), Some(head));
+        assert_eq!(index.hash(11, head), None);
+        assert_eq!(index.hash(12, head), Some(block(12, 0).hash));
+        assert_eq!(index.number(&block(11, 0).hash, head), None);
+        assert_eq!(index.number(&block(13, 0).hash, head), Some(13));
+        assert_eq!(
+            index.canonical_hashes_range(12..15, head),
+            Some(vec![block(12, 0).hash, block(13, 0).hash, block(14, 0).hash])
+        );
+        assert_eq!(index.canonical_hashes_range(11..14, head), None);
+        assert_eq!(index.canonical_hashes_range(13..16, head), None);
+    }
+
+    #[test]
+    fn handles_reorgs() {
+        let index = CanonicalHashIndex::new(10);
+        for number in 0..5 {
+            index.insert(block(number, 0));
+        }
+
+        // reorg of the last two blocks
+        index.insert(block(3, 1));
+        let head = block(3, 1);
+        assert_eq!(index.tip(), Some(head));
+        assert_eq!(index.len(), 4);
+        assert_eq!(index.hash(2, head), Some(block(2, 0).hash));
+        assert_eq!(index.hash(3, head), Some(block(3, 1).hash));
+        assert_eq!(index.hash(4, head), None);
+        assert_eq!(index.number(&block(4, 0).hash, head), None);
+        assert_eq!(index.number(&block(3, 0).hash, head), None);
+        assert_eq!(index.is_canonical_ancestor(&block(2, 0).hash, 1, head), Some(true));
+        assert_eq!(index.is_canonical_ancestor(&block(1, 0).hash, 1, head), Some(false));
+        assert_eq!(index.is_canonical_ancestor(&block(3, 0).hash, 3, head), Some(false));
+        assert_eq!(index.is_canonical_ancestor(&block(4, 0).hash, 10, head), None);
+        assert_eq!(
+            index.is_canonical_descendant(&block(1, 0).hash, &block(3, 1).hash, head),
+            Some(true)
+        );
+        assert_eq!(
+            index.is_canonical_descendant(&block(3, 1).hash, &block(1, 0).hash, head),
+            Some(false)
+        );
+        assert_eq!(index.is_canonical_descendant(&block(1, 0).hash, &block(4, 0).hash, head), None);
+
+        // gap resets the index
+        index.insert(block(10, 0));
+        assert_eq!(index.len(), 1);
+        assert_eq!(index.hash(2, block(10, 0)), None);
+        assert_eq!(index.number(&block(2, 0).hash, block(10, 0)), None);
+    }
+
+    #[test]
+    fn lagging_index_is_not_trusted() {
+        let index = CanonicalHashIndex::new(10);
+        for number in 0..5 {
+            index.insert(block(number, 0));
+        }
+
+        // the chain reorged to a new head that the index hasn't caught up with yet
+        let head = block(4, 1);
+        assert_eq!(index.hash(3, head), None);
+        assert_eq!(index.canonical_hashes_range(0..3, head), None);
+        assert_eq!(index.number(&block(3, 0).hash, head), None);
+        assert_eq!(index.is_canonical_ancestor(&block(3, 0).hash, 1, head), None);
+
+        // or the chain advanced past the tracked tip
+        let head = block(5, 0);
+        assert_eq!(index.hash(3, head), None);
+
+        index.insert(head);
+        assert_eq!(index.hash(3, head), Some(block(3, 0).hash));
+        assert_eq!(index.canonical_hashes_range(3..6, head).map(|hashes| hashes.len()), Some(3));
+    }
+}
@@ -15,9 +15,13 @@ use reth_rpc_convert::{CallFeesError, EthTxEnvError, TransactionConversionError}
 use reth_rpc_server_types::result::{
     block_id_to_str, internal_rpc_err, invalid_params_rpc_err, rpc_err, rpc_error_with_code,
 };
-use reth_transaction_pool::error::{
-    Eip4844PoolTransactionError, Eip7702PoolTransactionError, InvalidPoolTransactionError,
-    PoolError, PoolErrorKind, PoolTransactionError,
+use reth_transaction_pool::{
+    blobstore::BlobStoreError,
+    error::{
+        Eip4844PoolTransactionError, Eip7702PoolTransactionError, InvalidPoolTransactionError,
+        PoolError, PoolErrorKind, PoolTransactionError,
+    },
+    firewall::FirewallRejection,
 };
 use revm::context_interface::result::{
     EVMError, ExecutionResult, HaltReason, InvalidHeader, InvalidTransaction, OutOfGasError,
@@ -41,6 +45,9 @@ impl ToRpcError for jsonrpsee_types::ErrorObject<'static> {
 /// Result alias
 pub type EthResult<T> = Result<T, EthApiError>;
 
+/// Error code returned for requests that hit damaged storage while the node runs in safe mode.
+pub const STORAGE_DEGRADED_CODE: i32 = -32010;
+
 /// Errors that can occur when interacting with the `eth_` namespace
 #[derive(Debug, thiserror::Error)]
 pub enum EthApiError {
@@ -70,6 +77,15 @@ pub enum EthApiError {
     /// See also <https://eips.ethereum.org/EIPS/eip-4444>
     #[error("pruned history unavailable")]
     PrunedHistoryUnavailable,
+    /// Thrown when a block above the consistent range of damaged storage is requested while the
+    /// node runs in safe mode.
+    #[error("block #{block} is unavailable, storage is degraded above block #{consistent_tip}")]
+    StorageDegraded {
+        /// The requested block number.
+        block: u64,
+        /// The highest block that can be served.
+        consistent_tip: u64,
+    },
     /// Receipts not found for block hash/number/tag
     #[error("receipts not found")]
     ReceiptsNotFound(BlockId),
@@ -82,6 +98,25 @@ pub enum EthApiError {
     /// Thrown when the target block for proof computation exceeds the maximum configured window.
     #[error("distance to target block exceeds maximum proof window")]
     ExceedsMaxProofWindow,
+    /// Thrown when reconstructing the state for a historical proof exceeds the configured effort
+    /// budget.
+    #[error(
+        "state at block #{block} exceeds the proof effort budget of {budget} changeset entries"
+    )]
+    ExceedsProofEffortBudget {
+        /// The requested block number.
+        block: u64,
+        /// The maximum number of changeset entries that may be replayed.
+        budget: usize,
+    },
+    /// Thrown when the proof nodes of an `eth_getProof` response exceed the configured size limit.
+    #[error("proof response size of {size} bytes exceeds the limit of {limit} bytes")]
+    ExceedsMaxProofResponseSize {
+        /// The size of the proof nodes in bytes.
+        size: usize,
+        /// The maximum size of the proof nodes in bytes.
+        limit: usize,
+    },
     /// An internal error where prevrandao is not set in the evm's environment
     #[error("prevrandao not in the EVM's environment after merge")]
     PrevrandaoNotSet,
@@ -212,6 +247,8 @@ impl From<EthApiError> for jsonrpsee_types::error::ErrorObject<'static> {
             EthApiError::EmptyRawTransactionData |
             EthApiError::InvalidBlockRange |
             EthApiError::ExceedsMaxProofWindow |
+            EthApiError::ExceedsProofEffortBudget { .. } |
+            EthApiError::ExceedsMaxProofResponseSize { .. } |
             EthApiError::ConflictingFeeFieldsInRequest |
             EthApiError::Signing(_) |
             EthApiError::BothStateAndStateDiffInOverride(_) |
@@ -264,6 +301,9 @@ impl From<EthApiError> for jsonrpsee_types::error::ErrorObject<'static> {
             }
             err @ EthApiError::TransactionInputError(_) => invalid_params_rpc_err(err.to_string()),
             EthApiError::PrunedHistoryUnavailable => rpc_error_with_code(4444, error.to_string()),
+            err @ EthApiError::StorageDegraded { .. } => {
+                rpc_error_with_code(STORAGE_DEGRADED_CODE, err.to_string())
+            }
             EthApiError::Other(err) => err.to_rpc_error(),
             EthApiError::MuxTracerError(msg) => internal_rpc_err(msg.to_string()),
         }
@@ -356,6 +396,12 @@ impl From<RethError> for EthApiError {
     }
 }
 
+impl From<BlobStoreError> for EthApiError {
+    fn from(error: BlobStoreError) -> Self {
+        Self::Internal(RethError::other(error))
+    }
+}
+
 impl From<BlockExecutionError> for EthApiError {
     fn from(error: BlockExecutionError) -> Self {
         Self::Internal(error.into())
@@ -375,6 +421,15 @@ impl From<reth_errors::ProviderError> for EthApiError {
             ProviderError::TotalDifficultyNotFound(num) => Self::HeaderNotFound(num.into()),
             ProviderError::FinalizedBlockNotFound => Self::HeaderNotFound(BlockId::finalized()),
             ProviderError::SafeBlockNotFound => Self::HeaderNotFound(BlockId::safe()),
+            ProviderError::StorageDegraded { block, consistent_tip } => {
+                Self::StorageDegraded { block, consistent_tip }
+            }
+            ProviderError::HistoryExpired { .. } | ProviderError::StateAtBlockPruned(_) => {
+                Self::PrunedHistoryUnavailable
+            }
+            ProviderError::ProofEffortExceeded { block, budget } => {
+                Self::ExceedsProofEffortBudget { block, budget }
+            }
             err => Self::Internal(err.into()),
         }
     }
@@ -846,6 +901,9 @@ pub enum RpcPoolError {
     /// constraint (blob vs normal tx)
     #[error("address already reserved")]
     AddressAlreadyReserved,
+    /// Thrown if a rule of the pool's admission firewall rejected the transaction
+    #[error(transparent)]
+    FirewallRejected(FirewallRejection),
     /// Other unspecified error
     #[error(transparent)]
     Other(Box<dyn core::error::Error + Send + Sync>),
@@ -855,7 +913,7 @@ impl From<RpcPoolError> for jsonrpsee_types::error::ErrorObject<'static> {
     fn from(error: RpcPoolError) -> Self {
         match error {
             RpcPoolError::Invalid(err) => err.into(),
-            RpcPoolError::TxPoolOverflow => {
+            RpcPoolError::TxPoolOverflow | RpcPoolError::FirewallRejected(_) => {
                 rpc_error_with_code(EthRpcErrorCode::TransactionRejected.code(), error.to_string())
             }
             RpcPoolError::AlreadyKnown |
@@ -884,10 +942,11 @@ impl From<PoolError> for RpcPoolError {
         match err.kind {
             PoolErrorKind::ReplacementUnderpriced => Self::ReplaceUnderpriced,
             PoolErrorKind::FeeCapBelowMinimumProtocolFeeCap(_) => Self::Underpriced,
-            PoolErrorKind::SpammerExceededCapacity(_) | PoolErrorKind::DiscardedOnInsert => {
-                Self::TxPoolOverflow
-            }
+            PoolErrorKind::SpammerExceededCapacity(_) |
+            PoolErrorKind::OriginExceededCapacity(_) |
+            PoolErrorKind::DiscardedOnInsert => Self::TxPoolOverflow,
             PoolErrorKind::InvalidTransaction(err) => err.into(),
+            PoolErrorKind::FirewallRejected(err) => Self::FirewallRejected(err),
             PoolErrorKind::Other(err) => Self::Other(err),
             PoolErrorKind::AlreadyImported => Self::AlreadyKnown,
             PoolErrorKind::ExistingConflictingTransactionType(_, _) => Self::AddressAlreadyReserved,
@@ -1023,4 +1082,20 @@ mod tests {
         let msg = err.to_string();
         assert_eq!(msg, "execution reverted: test_revert_reason");
     }
+
+    #[test]
+    fn history_expired_error() {
+        let err: jsonrpsee_types::error::ErrorObject<'static> =
+            EthApiError::from(reth_errors::ProviderError::HistoryExpired {
+                block: 1,
+                earliest: 15537394,
+            })
+            .into();
+        assert_eq!(err.code(), 4444);
+        assert_eq!(err.message(), "pruned history unavailable");
+
+        let err: jsonrpsee_types::error::ErrorObject<'static> =
+            EthApiError::from(reth_errors::ProviderError::StateAtBlockPruned(1)).into();
+        assert_eq!(err.code(), 4444);
+    }
 }
@@ -3,7 +3,7 @@
 use std::{
     collections::{BTreeMap, VecDeque},
     fmt::Debug,
-    sync::{atomic::Ordering::SeqCst, Arc},
+    sync::{atomic::Ordering::SeqCst, Arc, OnceLock},
 };
 
 use alloy_consensus::{BlockHeader, Header, Transaction, TxReceipt};
@@ -19,6 +19,7 @@ use reth_chainspec::{ChainSpecProvider, EthChainSpec};
 use reth_primitives_traits::{Block, BlockBody, NodePrimitives, SealedBlock};
 use reth_rpc_server_types::constants::gas_oracle::MAX_HEADER_HISTORY;
 use reth_storage_api::BlockReaderIdExt;
+use reth_tasks::memory::{MemoryBudget, MemoryConsumer};
 use serde::{Deserialize, Serialize};
 use tracing::trace;
 
@@ -43,10 +44,26 @@ where
             upper_bound: Default::default(),
             config,
             entries: Default::default(),
+            memory_consumer: OnceLock::new(),
         };
         Self { inner: Arc::new(inner) }
     }
 
+    /// Registers the cache with the given [`MemoryBudget`].
+    ///
+    /// The cache reports the memory of its entries to the budget and drops the oldest entries if
+    /// the budget asks it to release memory. A cache can only be registered once.
+    pub fn register_memory_budget(&self, budget: &MemoryBudget, weight: u32)
+    where
+        H: Send + Sync + 'static,
+    {
+        let inner = Arc::downgrade(&self.inner);
+        let consumer = budget.register("fee_history_cache", weight, move |bytes| {
+            inner.upgrade().map_or(0, |inner| inner.reclaim(bytes))
+        });
+        let _ = self.inner.memory_consumer.set(consumer);
+    }
+
     /// How the cache is configured.
     #[inline]
     pub fn config(&self) -> &FeeHistoryCacheConfig {
@@ -122,6 +139,7 @@ where
         let lower_bound = *entries.first_entry().expect("Contains at least one entry").key();
         self.inner.upper_bound.store(upper_bound, SeqCst);
         self.inner.lower_bound.store(lower_bound, SeqCst);
+        self.inner.report_memory_usage(&entries);
     }
 
     /// Get `UpperBound` value for `FeeHistoryCache`
@@ -210,6 +228,38 @@ struct FeeHistoryCacheInner<H> {
     config: FeeHistoryCacheConfig,
     /// Stores the entries of the cache
     entries: tokio::sync::RwLock<BTreeMap<u64, FeeHistoryEntry<H>>>,
+    /// Registration with the memory budget, if any.
+    memory_consumer: OnceLock<MemoryConsumer>,
+}
+
+impl<H> FeeHistoryCacheInner<H> {
+    /// Drops the oldest entries until at least `bytes` are freed.
+    ///
+    /// Nothing is freed if the entries are currently locked, the budget asks again on its next
+    /// check.
+    fn reclaim(&self, bytes: usize) -> usize {
+        let Ok(mut entries) = self.entries.try_write() else { return 0 };
+        let mut freed = 0;
+        while freed < bytes {
+            let Some((_, entry)) = entries.pop_first() else { break };
+            freed += entry.size();
+        }
+
+        let lower_bound = entries.first_key_value().map_or(0, |(number, _)| *number);
+        if entries.is_empty() {
+            self.upper_bound.store(0, SeqCst);
+        }
+        self.lower_bound.store(lower_bound, SeqCst);
+        self.report_memory_usage(&entries);
+        freed
+    }
+
+    /// Reports the memory of the given entries to the memory budget, if registered.
+    fn report_memory_usage(&self, entries: &BTreeMap<u64, FeeHistoryEntry<H>>) {
+        if let Some(consumer) = self.memory_consumer.get() {
+            consumer.report(entries.values().map(FeeHistoryEntry::size).sum());
+        }
+    }
 }
 
 /// Awaits for new chain events and directly inserts them into the cache so they're available
@@ -358,6 +408,13 @@ pub struct FeeHistoryEntry<H = Header> {
     pub blob_params: Option<BlobParams>,
 }
 
+impl<H> FeeHistoryEntry<H> {
+    /// Returns an estimate of the memory used by the entry in bytes.
+    fn size(&self) -> usize {
+        size_of::<u64>() + size_of::<Self>() + self.rewards.len() * size_of::<u128>()
+    }
+}
+
 impl<H> FeeHistoryEntry<H>
 where
     H: BlockHeader + Clone,
@@ -409,3 +466,46 @@ where
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reclaims_oldest_entries() {
+        let cache = FeeHistoryCache::<Header>::new(FeeHistoryCacheConfig::default());
+        let budget = MemoryBudget::new(0);
+        cache.register_memory_budget(&budget, 1);
+
+        let entry = FeeHistoryEntry {
+            header: Header::default(),
+            gas_used_ratio: 0.0,
+            base_fee_per_blob_gas: None,
+            blob_gas_used_ratio: 0.0,
+            rewards: vec![0; 401],
+            blob_params: None,
+        };
+        let entry_size = entry.size();
+        {
+            let mut entries = cache.inner.entries.try_write().unwrap();
+            entries.extend((1..=4).map(|number| (number, entry.clone())));
+            cache.inner.report_memory_usage(&entries);
+        }
+        cache.inner.lower_bound.store(1, SeqCst);
+        cache.inner.upper_bound.store(4, SeqCst);
+        assert_eq!(budget.tracked(), 4 * entry_size);
+
+        // the entries are locked, so nothing is released
+        let entries = cache.inner.entries.try_read().unwrap();
+        assert_eq!(cache.inner.reclaim(entry_size), 0);
+        drop(entries);
+
+        assert_eq!(cache.inner.reclaim(entry_size + 1), 2 * entry_size);
+        assert_eq!(budget.tracked(), 2 * entry_size);
+        assert_eq!((cache.lower_bound(), cache.upper_bound()), (3, 4));
+
+        assert_eq!(budget.reclaim(), 2 * entry_size);
+        assert_eq!(budget.tracked(), 0);
+        assert_eq!((cache.lower_bound(), cache.upper_bound()), (0, 0));
+    }
+}
@@ -12,7 +12,8 @@ use reth_rpc_server_types::{
     constants,
     constants::gas_oracle::{
         DEFAULT_GAS_PRICE_BLOCKS, DEFAULT_GAS_PRICE_PERCENTILE, DEFAULT_IGNORE_GAS_PRICE,
-        DEFAULT_MAX_GAS_PRICE, MAX_HEADER_HISTORY, MAX_REWARD_PERCENTILE_COUNT, SAMPLE_NUMBER,
+        DEFAULT_MAX_GAS_PRICE, DEFAULT_MEMPOOL_WEIGHT, MAX_HEADER_HISTORY,
+        MAX_REWARD_PERCENTILE_COUNT, SAMPLE_NUMBER,
     },
 };
 use reth_storage_api::{BlockReader, BlockReaderIdExt};
@@ -56,6 +57,19 @@ pub struct GasPriceOracleConfig {
 
     /// The minimum gas price, under which the sample will be ignored
     pub ignore_price: Option<U256>,
+
+    /// The percentile of pending mempool tips to use for the estimate
+    #[serde(default = "default_mempool_percentile")]
+    pub mempool_percentile: u32,
+
+    /// The weight (in %) of the mempool percentile when blended with the percentile of recently
+    /// included tips. Zero disables the blend.
+    #[serde(default)]
+    pub mempool_weight: u32,
+}
+
+const fn default_mempool_percentile() -> u32 {
+    DEFAULT_GAS_PRICE_PERCENTILE
 }
 
 impl Default for GasPriceOracleConfig {
@@ -69,6 +83,8 @@ impl Default for GasPriceOracleConfig {
             default: None,
             max_price: Some(DEFAULT_MAX_GAS_PRICE),
             ignore_price: Some(DEFAULT_IGNORE_GAS_PRICE),
+            mempool_percentile: DEFAULT_GAS_PRICE_PERCENTILE,
+            mempool_weight: DEFAULT_MEMPOOL_WEIGHT,
         }
     }
 }
@@ -107,12 +123,21 @@ where
             warn!(prev_percentile = ?oracle_config.percentile, "Invalid configured gas price percentile, assuming 100.");
             oracle_config.percentile = 100;
         }
+        if oracle_config.mempool_percentile > 100 {
+            warn!(prev_percentile = ?oracle_config.mempool_percentile, "Invalid configured mempool gas price percentile, assuming 100.");
+            oracle_config.mempool_percentile = 100;
+        }
+        if oracle_config.mempool_weight > 100 {
+            warn!(prev_weight = ?oracle_config.mempool_weight, "Invalid configured mempool weight, assuming 100.");
+            oracle_config.mempool_weight = 100;
+        }
         let ignore_price = oracle_config.ignore_price.map(|price| price.saturating_to());
 
         // this is the number of blocks that we will cache the values for
         let cached_values = (oracle_config.blocks * 5).max(oracle_config.max_block_history as u32);
         let inner = Mutex::new(GasPriceOracleInner {
             last_price: Default::default(),
+            last_price_stale: false,
             lowest_effective_tip_cache: EffectiveTipLruCache(LruMap::new(ByLength::new(
                 cached_values,
             ))),
@@ -128,6 +153,45 @@ where
 
     /// Suggests a gas price estimate based on recent blocks, using the configured percentile.
     pub async fn suggest_tip_cap(&self) -> EthResult<U256> {
+        self.suggest_included_tip_cap().await.map(|(price, _)| price)
+    }
+
+    /// Suggests a gas price estimate that blends the percentile of recently included tips with the
+    /// configured percentile of pending mempool tips, weighted by
+    /// [`GasPriceOracleConfig::mempool_weight`].
+    ///
+    /// If none of the recently checked blocks included a transaction, the included percentile is
+    /// considered stale and only the mempool tips are used.
+    ///
+    /// The mempool tips are only requested if the blend is enabled.
+    pub async fn suggest_blended_tip_cap(
+        &self,
+        mempool_tips: impl FnOnce() -> Vec<u128>,
+    ) -> EthResult<U256> {
+        let (included, stale) = self.suggest_included_tip_cap().await?;
+        if self.oracle_config.mempool_weight == 0 {
+            return Ok(included)
+        }
+
+        let mut tips = mempool_tips();
+        if let Some(ignore_under) = self.ignore_price {
+            tips.retain(|tip| *tip >= ignore_under);
+        }
+        let mut price = blend_tips(included, stale, tips, &self.oracle_config);
+
+        // constrain to the max price
+        if let Some(max_price) = self.oracle_config.max_price {
+            if price > max_price {
+                price = max_price;
+            }
+        }
+
+        Ok(price)
+    }
+
+    /// Returns the percentile of recently included tips and whether it is stale, because none of
+    /// the checked blocks included a transaction.
+    async fn suggest_included_tip_cap(&self) -> EthResult<(U256, bool)> {
         let header = self
             .provider
             .sealed_header_by_number_or_tag(BlockNumberOrTag::Latest)?
@@ -137,7 +201,7 @@ where
 
         // if we have stored a last price, then we check whether or not it was for the same head
         if inner.last_price.block_hash == header.hash() {
-            return Ok(inner.last_price.price)
+            return Ok((inner.last_price.price, inner.last_price_stale))
         }
 
         // if all responses are empty, then we can return a maximum of 2*check_block blocks' worth
@@ -206,8 +270,9 @@ where
         }
 
         inner.last_price = GasPriceOracleResult { block_hash: header.hash(), price };
+        inner.last_price_stale = populated_blocks == 0;
 
-        Ok(price)
+        Ok((price, inner.last_price_stale))
     }
 
     /// Get the `limit` lowest effective tip values for the given block. If the oracle has a
@@ -341,6 +406,7 @@ where
         }
 
         inner.last_price = GasPriceOracleResult { block_hash: header.hash(), price: suggestion };
+        inner.last_price_stale = false;
 
         Ok(suggestion)
     }
@@ -382,10 +448,33 @@ where
         Ok(median)
     }
 }
+/// Blends the included tip percentile with the configured percentile of the mempool tips.
+///
+/// A stale included percentile is replaced by the mempool percentile.
+fn blend_tips(
+    included: U256,
+    stale: bool,
+    mut mempool_tips: Vec<u128>,
+    config: &GasPriceOracleConfig,
+) -> U256 {
+    if mempool_tips.is_empty() {
+        return included
+    }
+    mempool_tips.sort_unstable();
+    let mempool = U256::from(
+        mempool_tips[(mempool_tips.len() - 1) * config.mempool_percentile as usize / 100],
+    );
+
+    let weight = if stale { 100 } else { config.mempool_weight };
+    (included * U256::from(100 - weight) + mempool * U256::from(weight)) / U256::from(100)
+}
+
 /// Container type for mutable inner state of the [`GasPriceOracle`]
 #[derive(Debug)]
 struct GasPriceOracleInner {
     last_price: GasPriceOracleResult,
+    /// Whether `last_price` was computed without any populated block.
+    last_price_stale: bool,
     lowest_effective_tip_cache: EffectiveTipLruCache,
 }
 
@@ -441,4 +530,21 @@ mod tests {
     fn ignore_price_sanity() {
         assert_eq!(DEFAULT_IGNORE_GAS_PRICE, U256::from(2u64));
     }
+
+    #[test]
+    fn blend_mempool_tips() {
+        let config = GasPriceOracleConfig {
+            mempool_percentile: 50,
+            mempool_weight: 25,
+            ..Default::default()
+        };
+        let tips = vec![300, 100, 200];
+
+        // 75% of the included percentile and 25% of the mempool median
+        assert_eq!(blend_tips(U256::from(400), false, tips.clone(), &config), U256::from(350));
+        // a stale included percentile is ignored
+        assert_eq!(blend_tips(U256::from(400), true, tips, &config), U256::from(200));
+        // no mempool tips keeps the included percentile
+        assert_eq!(blend_tips(U256::from(400), true, Vec::new(), &config), U256::from(400));
+    }
 }
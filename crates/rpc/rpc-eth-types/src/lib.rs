@@ -8,8 +8,10 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 
+pub mod blob;
 pub mod builder;
 pub mod cache;
+pub mod canonical_index;
 pub mod error;
 pub mod fee_history;
 pub mod gas_oracle;
@@ -21,11 +23,13 @@ pub mod simulate;
 pub mod transaction;
 pub mod utils;
 
+pub use blob::BlockBlobSidecar;
 pub use builder::config::{EthConfig, EthFilterConfig};
 pub use cache::{
     config::EthStateCacheConfig, db::StateCacheDb, multi_consumer::MultiConsumerLruCache,
     EthStateCache,
 };
+pub use canonical_index::{CanonicalHashIndex, DEFAULT_CANONICAL_HASH_INDEX_SIZE};
 pub use error::{EthApiError, EthResult, RevertError, RpcInvalidTransactionError, SignError};
 pub use fee_history::{FeeHistoryCache, FeeHistoryCacheConfig, FeeHistoryEntry};
 pub use gas_oracle::{
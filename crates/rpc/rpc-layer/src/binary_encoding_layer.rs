@@ -0,0 +1,428 @@
+use http::{
+    header::{ACCEPT, CONTENT_LENGTH, CONTENT_TYPE, VARY},
+    HeaderMap, HeaderValue, StatusCode,
+};
+use http_body_util::BodyExt;
+use jsonrpsee_http_client::{HttpBody, HttpRequest, HttpResponse};
+use serde::de::{DeserializeSeed, Deserializer, Error, MapAccess, SeqAccess, Visitor};
+use std::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tower::{Layer, Service};
+use tracing::{debug, trace};
+
+/// Media type of CBOR encoded responses.
+pub const CBOR_MEDIA_TYPE: &str = "application/cbor";
+
+/// Latest version of the CBOR response schema.
+///
+/// Version 1 encodes JSON objects and arrays as indefinite-length CBOR maps and arrays, numbers,
+/// booleans and `null` as their CBOR equivalents, and all `0x` prefixed hex string values
+/// (quantities and data) as big-endian CBOR byte strings. Hex strings with an odd number of digits
+/// are left-padded with a zero. Object keys are always encoded as text strings.
+pub const CBOR_SCHEMA_VERSION: u8 = 1;
+
+/// This layer serves HTTP responses in a compact binary encoding if the client asks for it.
+///
+/// If the `Accept` header of a request contains `application/cbor` (optionally with a `v=<schema
+/// version>` parameter), the JSON response is transcoded to CBOR using the schema described in
+/// [`CBOR_SCHEMA_VERSION`]. Requests that don't accept CBOR, or ask for an unsupported schema
+/// version, are served as JSON.
+///
+/// Responses are transcoded while the JSON is parsed, without building an intermediate document.
+/// They are still serialized to JSON first, so this mainly reduces the size of heavy responses like
+/// receipts, logs and traces on the wire and the decoding cost on the client.
+#[expect(missing_debug_implementations)]
+#[derive(Clone, Default)]
+pub struct BinaryEncodingLayer;
+
+impl BinaryEncodingLayer {
+    /// Creates a new binary encoding layer.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl<S> Layer<S> for BinaryEncodingLayer {
+    type Service = BinaryEncodingService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        BinaryEncodingService { inner }
+    }
+}
+
+/// Service that transcodes JSON responses to a binary encoding.
+///
+/// Created by [`BinaryEncodingLayer`].
+#[expect(missing_debug_implementations)]
+#[derive(Clone)]
+pub struct BinaryEncodingService<S> {
+    inner: S,
+}
+
+impl<S> Service<HttpRequest> for BinaryEncodingService<S>
+where
+    S: Service<HttpRequest, Response = HttpResponse>,
+    S::Future: Send + 'static,
+{
+    type Response = HttpResponse;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: HttpRequest) -> Self::Future {
+        let version = accepted_cbor_version(req.headers());
+        let fut = self.inner.call(req);
+
+        Box::pin(async move {
+            let resp = fut.await?;
+            let Some(version) = version else { return Ok(resp) };
+            if !is_json(resp.headers()) {
+                return Ok(resp)
+            }
+
+            let (mut parts, body) = resp.into_parts();
+            let bytes = match body.collect().await {
+                Ok(collected) => collected.to_bytes(),
+                Err(err) => {
+                    debug!(target: "rpc::layer", %err, "Failed to collect response body");
+                    let mut resp = HttpResponse::new(HttpBody::empty());
+                    *resp.status_mut() = StatusCode::INTERNAL_SERVER_ERROR;
+                    return Ok(resp)
+                }
+            };
+
+            let encoded = match encode_cbor(&bytes) {
+                Ok(encoded) => encoded,
+                Err(err) => {
+                    trace!(target: "rpc::layer", %err, "Response is not valid JSON, skipping CBOR encoding");
+                    return Ok(HttpResponse::from_parts(parts, HttpBody::from(bytes.to_vec())))
+                }
+            };
+            trace!(target: "rpc::layer", json = bytes.len(), cbor = encoded.len(), "Encoded response as CBOR");
+
+            parts.headers.insert(
+                CONTENT_TYPE,
+                HeaderValue::from_str(&format!("{CBOR_MEDIA_TYPE}; v={version}"))
+                    .expect("valid header value"),
+            );
+            parts.headers.insert(CONTENT_LENGTH, HeaderValue::from(encoded.len()));
+            parts.headers.append(VARY, HeaderValue::from_static("accept"));
+
+            Ok(HttpResponse::from_parts(parts, HttpBody::from(encoded)))
+        })
+    }
+}
+
+/// Returns the CBOR schema version the client accepts, if any.
+///
+/// A media range without a `v` parameter accepts the latest version. Media ranges with a quality
+/// of zero or an unsupported version are ignored.
+fn accepted_cbor_version(headers: &HeaderMap) -> Option<u8> {
+    headers
+        .get_all(ACCEPT)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .find_map(|range| {
+            let mut params = range.split(';').map(str::trim);
+            if !params.next()?.eq_ignore_ascii_case(CBOR_MEDIA_TYPE) {
+                return None
+            }
+
+            let mut version = CBOR_SCHEMA_VERSION;
+            for param in params {
+                let (name, value) = param.split_once('=')?;
+                match name.trim() {
+                    "v" => version = value.trim().parse().ok()?,
+                    "q" if value.trim().parse::<f32>().ok()? == 0.0 => return None,
+                    _ => {}
+                }
+            }
+            (version == CBOR_SCHEMA_VERSION).then_some(version)
+        })
+}
+
+/// Returns true if the response has a JSON content type.
+fn is_json(headers: &HeaderMap) -> bool {
+    headers
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/json"))
+}
+
+/// Transcodes a JSON document to CBOR using the schema described in [`CBOR_SCHEMA_VERSION`].
+pub fn encode_cbor(json: &[u8]) -> serde_json::Result<Vec<u8>> {
+    let mut out = Vec::with_capacity(json.len() / 2);
+    let mut deserializer = serde_json::Deserializer::from_slice(json);
+    CborTranscoder(&mut out).deserialize(&mut deserializer)?;
+    deserializer.end()?;
+    Ok(out)
+}
+
+const MAJOR_UNSIGNED: u8 = 0;
+const MAJOR_NEGATIVE: u8 = 1;
+const MAJOR_BYTES: u8 = 2;
+const MAJOR_TEXT: u8 = 3;
+
+const SIMPLE_FALSE: u8 = 0xf4;
+const SIMPLE_TRUE: u8 = 0xf5;
+const SIMPLE_NULL: u8 = 0xf6;
+const FLOAT_64: u8 = 0xfb;
+const INDEFINITE_ARRAY: u8 = 0x9f;
+const INDEFINITE_MAP: u8 = 0xbf;
+const BREAK: u8 = 0xff;
+
+/// Writes the visited JSON value to the buffer as CBOR.
+struct CborTranscoder<'a>(&'a mut Vec<u8>);
+
+impl<'de> DeserializeSeed<'de> for CborTranscoder<'_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for CborTranscoder<'_> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a JSON value")
+    }
+
+    fn visit_unit<E: Error>(self) -> Result<(), E> {
+        self.0.push(SIMPLE_NULL);
+        Ok(())
+    }
+
+    fn visit_bool<E: Error>(self, value: bool) -> Result<(), E> {
+        self.0.push(if value { SIMPLE_TRUE } else { SIMPLE_FALSE });
+        Ok(())
+    }
+
+    fn visit_u64<E: Error>(self, value: u64) -> Result<(), E> {
+        write_head(self.0, MAJOR_UNSIGNED, value);
+        Ok(())
+    }
+
+    fn visit_i64<E: Error>(self, value: i64) -> Result<(), E> {
+        if value >= 0 {
+            write_head(self.0, MAJOR_UNSIGNED, value as u64);
+        } else {
+            // negative integers are encoded as `-1 - n`
+            write_head(self.0, MAJOR_NEGATIVE, !(value as u64));
+        }
+        Ok(())
+    }
+
+    fn visit_f64<E: Error>(self, value: f64) -> Result<(), E> {
+        self.0.push(FLOAT_64);
+        self.0.extend_from_slice(&value.to_be_bytes());
+        Ok(())
+    }
+
+    fn visit_str<E: Error>(self, value: &str) -> Result<(), E> {
+        if !write_hex(self.0, value) {
+            write_text(self.0, value);
+        }
+        Ok(())
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        let out = self.0;
+        out.push(INDEFINITE_ARRAY);
+        while seq.next_element_seed(CborTranscoder(&mut *out))?.is_some() {}
+        out.push(BREAK);
+        Ok(())
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        let out = self.0;
+        out.push(INDEFINITE_MAP);
+        while map.next_key_seed(KeyTranscoder(&mut *out))?.is_some() {
+            map.next_value_seed(CborTranscoder(&mut *out))?;
+        }
+        out.push(BREAK);
+        Ok(())
+    }
+}
+
+/// Writes the visited JSON object key to the buffer as a CBOR text string.
+struct KeyTranscoder<'a>(&'a mut Vec<u8>);
+
+impl<'de> DeserializeSeed<'de> for KeyTranscoder<'_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_str(self)
+    }
+}
+
+impl Visitor<'_> for KeyTranscoder<'_> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("an object key")
+    }
+
+    fn visit_str<E: Error>(self, value: &str) -> Result<(), E> {
+        write_text(self.0, value);
+        Ok(())
+    }
+}
+
+/// Writes a text string.
+fn write_text(out: &mut Vec<u8>, value: &str) {
+    write_head(out, MAJOR_TEXT, value.len() as u64);
+    out.extend_from_slice(value.as_bytes());
+}
+
+/// Writes the initial byte of a data item with the given major type and argument.
+fn write_head(out: &mut Vec<u8>, major: u8, arg: u64) {
+    let major = major << 5;
+    if arg < 24 {
+        out.push(major | arg as u8);
+    } else if arg <= u8::MAX as u64 {
+        out.extend_from_slice(&[major | 24, arg as u8]);
+    } else if arg <= u16::MAX as u64 {
+        out.push(major | 25);
+        out.extend_from_slice(&(arg as u16).to_be_bytes());
+    } else if arg <= u32::MAX as u64 {
+        out.push(major | 26);
+        out.extend_from_slice(&(arg as u32).to_be_bytes());
+    } else {
+        out.push(major | 27);
+        out.extend_from_slice(&arg.to_be_bytes());
+    }
+}
+
+/// Writes a `0x` prefixed hex string as a byte string, left-padding odd length strings with a
+/// zero.
+///
+/// Returns false and leaves the buffer untouched if the string is not hex.
+fn write_hex(out: &mut Vec<u8>, value: &str) -> bool {
+    let Some(digits) = value.strip_prefix("0x").map(str::as_bytes) else { return false };
+    if !digits.iter().all(u8::is_ascii_hexdigit) {
+        return false
+    }
+    let nibble = |c: u8| (c as char).to_digit(16).expect("hex digit") as u8;
+
+    write_head(out, MAJOR_BYTES, digits.len().div_ceil(2) as u64);
+    let (head, rest) = digits.split_at(digits.len() % 2);
+    if let Some(&c) = head.first() {
+        out.push(nibble(c));
+    }
+    for pair in rest.chunks_exact(2) {
+        out.push((nibble(pair[0]) << 4) | nibble(pair[1]));
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{convert::Infallible, future::ready};
+
+    const TEST_RESPONSE: &str = r#"{"jsonrpc":"2.0","id":1,"result":[{"logIndex":"0x1f","data":"0xdeadbeef","removed":false}]}"#;
+
+    #[derive(Clone)]
+    struct MockRequestService;
+
+    impl Service<HttpRequest> for MockRequestService {
+        type Response = HttpResponse;
+        type Error = Infallible;
+        type Future = std::future::Ready<Result<Self::Response, Self::Error>>;
+
+        fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _: HttpRequest) -> Self::Future {
+            let response = HttpResponse::builder()
+                .header(CONTENT_TYPE, "application/json")
+                .body(HttpBody::from(TEST_RESPONSE.to_string()))
+                .unwrap();
+            ready(Ok(response))
+        }
+    }
+
+    async fn call_with_accept(accept: Option<&str>) -> HttpResponse {
+        let mut service = BinaryEncodingLayer::new().layer(MockRequestService);
+        let mut request = HttpRequest::builder();
+        if let Some(accept) = accept {
+            request = request.header(ACCEPT, accept);
+        }
+        service.call(request.body(HttpBody::empty()).unwrap()).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_cbor_encoding() {
+        let response = call_with_accept(Some("application/json;q=0.5, application/cbor")).await;
+        assert_eq!(response.headers().get(CONTENT_TYPE).unwrap(), "application/cbor; v=1");
+        assert_eq!(response.headers().get(VARY).unwrap(), "accept");
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let expected = encode_cbor(TEST_RESPONSE.as_bytes()).unwrap();
+        assert_eq!(body.as_ref(), expected.as_slice());
+        assert!(body.len() < TEST_RESPONSE.len());
+    }
+
+    #[tokio::test]
+    async fn test_json_when_not_requested() {
+        for accept in [None, Some("application/json"), Some("application/cbor; v=2")] {
+            let response = call_with_accept(accept).await;
+            assert_eq!(response.headers().get(CONTENT_TYPE).unwrap(), "application/json");
+
+            let body = response.into_body().collect().await.unwrap().to_bytes();
+            assert_eq!(body.as_ref(), TEST_RESPONSE.as_bytes());
+        }
+    }
+
+    #[test]
+    fn test_accepted_cbor_version() {
+        let accept = |value: &'static str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(ACCEPT, HeaderValue::from_static(value));
+            accepted_cbor_version(&headers)
+        };
+
+        assert_eq!(accept("application/cbor"), Some(1));
+        assert_eq!(accept("application/cbor; v=1"), Some(1));
+        assert_eq!(accept("Application/CBOR;q=0.9"), Some(1));
+        assert_eq!(accept("application/cbor; v=2"), None);
+        assert_eq!(accept("application/cbor; q=0"), None);
+        assert_eq!(accept("application/json"), None);
+    }
+
+    #[test]
+    fn test_encode_cbor() {
+        let encode = |json: &str| encode_cbor(json.as_bytes()).unwrap();
+        assert_eq!(encode("null"), [0xf6]);
+        assert_eq!(encode("true"), [0xf5]);
+        assert_eq!(encode("10"), [0x0a]);
+        assert_eq!(encode("500"), [0x19, 0x01, 0xf4]);
+        assert_eq!(encode("-1"), [0x20]);
+        assert_eq!(encode("-500"), [0x39, 0x01, 0xf3]);
+        assert_eq!(encode("1.5"), [0xfb, 0x3f, 0xf8, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(encode(r#""a""#), [0x61, b'a']);
+        assert_eq!(encode(r#""\u0061""#), [0x61, b'a']);
+        assert_eq!(encode(r#""0x""#), [0x40]);
+        assert_eq!(encode(r#""0x1f""#), [0x41, 0x1f]);
+        assert_eq!(encode(r#""0x100""#), [0x42, 0x01, 0x00]);
+        assert_eq!(encode(r#""0xzz""#), [0x64, b'0', b'x', b'z', b'z']);
+        assert_eq!(encode("[1, 2]"), [0x9f, 0x01, 0x02, 0xff]);
+        assert_eq!(encode(r#"{"a": 1}"#), [0xbf, 0x61, b'a', 0x01, 0xff]);
+        assert_eq!(
+            encode(r#"{"0x1f": "0x1f"}"#),
+            [0xbf, 0x64, b'0', b'x', b'1', b'f', 0x41, 0x1f, 0xff]
+        );
+        assert!(encode_cbor(b"{\"a\": 1} x").is_err());
+    }
+}
@@ -1,6 +1,10 @@
 use crate::{AuthValidator, JwtError, JwtSecret};
 use http::{header, HeaderMap, Response, StatusCode};
 use jsonrpsee_http_client::{HttpBody, HttpResponse};
+use std::{
+    sync::{Arc, Mutex},
+    time::Instant,
+};
 use tracing::error;
 
 /// Implements JWT validation logics and integrates
@@ -9,6 +13,7 @@ use tracing::error;
 #[derive(Debug, Clone)]
 pub struct JwtAuthValidator {
     secret: JwtSecret,
+    rejections: Option<JwtRejections>,
 }
 
 impl JwtAuthValidator {
@@ -16,7 +21,21 @@ impl JwtAuthValidator {
     /// Validation logics are implemented by the `secret`
     /// argument (see [`JwtSecret`]).
     pub const fn new(secret: JwtSecret) -> Self {
-        Self { secret }
+        Self { secret, rejections: None }
+    }
+
+    /// Records every rejected request in the given [`JwtRejections`].
+    pub fn with_rejections(mut self, rejections: JwtRejections) -> Self {
+        self.rejections = Some(rejections);
+        self
+    }
+
+    fn reject(&self, err: JwtError) -> HttpResponse {
+        error!(target: "engine::jwt-validator", "Invalid JWT: {err}");
+        if let Some(rejections) = &self.rejections {
+            rejections.record(JwtRejectionReason::from(&err));
+        }
+        err_response(err)
     }
 }
 
@@ -25,22 +44,64 @@ impl AuthValidator for JwtAuthValidator {
         match get_bearer(headers) {
             Some(jwt) => match self.secret.validate(&jwt) {
                 Ok(_) => Ok(()),
-                Err(e) => {
-                    error!(target: "engine::jwt-validator", "Invalid JWT: {e}");
-                    let response = err_response(e);
-                    Err(response)
-                }
+                Err(e) => Err(self.reject(e)),
             },
-            None => {
-                let e = JwtError::MissingOrInvalidAuthorizationHeader;
-                error!(target: "engine::jwt-validator", "Invalid JWT: {e}");
-                let response = err_response(e);
-                Err(response)
-            }
+            None => Err(self.reject(JwtError::MissingOrInvalidAuthorizationHeader)),
         }
     }
 }
 
+/// The reason a request was rejected by the [`JwtAuthValidator`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JwtRejectionReason {
+    /// The token was not signed with the configured secret.
+    SecretMismatch,
+    /// The issuance timestamp of the token is too far from the local time.
+    ClockSkew,
+    /// The request did not carry a bearer token.
+    MissingToken,
+    /// The token was rejected for any other reason.
+    Other,
+}
+
+impl From<&JwtError> for JwtRejectionReason {
+    fn from(err: &JwtError) -> Self {
+        match err {
+            JwtError::InvalidSignature => Self::SecretMismatch,
+            JwtError::InvalidIssuanceTimestamp => Self::ClockSkew,
+            JwtError::MissingOrInvalidAuthorizationHeader => Self::MissingToken,
+            _ => Self::Other,
+        }
+    }
+}
+
+/// Keeps track of the last request that was rejected by a [`JwtAuthValidator`].
+///
+/// This is used to diagnose why the consensus layer fails to reach the engine API.
+///
+/// This type is cheap to clone.
+#[derive(Debug, Clone, Default)]
+pub struct JwtRejections {
+    last: Arc<Mutex<Option<(Instant, JwtRejectionReason)>>>,
+}
+
+impl JwtRejections {
+    /// Records a rejection with the given reason.
+    pub fn record(&self, reason: JwtRejectionReason) {
+        *self.last.lock().unwrap() = Some((Instant::now(), reason));
+    }
+
+    /// Returns the time and reason of the last rejection, if any.
+    pub fn last(&self) -> Option<(Instant, JwtRejectionReason)> {
+        *self.last.lock().unwrap()
+    }
+
+    /// Returns the reason of the last rejection if it happened after `since`.
+    pub fn last_since(&self, since: Instant) -> Option<JwtRejectionReason> {
+        self.last().filter(|(at, _)| *at >= since).map(|(_, reason)| reason)
+    }
+}
+
 /// This is an utility function that retrieves a bearer
 /// token from an authorization Http header.
 fn get_bearer(headers: &HeaderMap) -> Option<String> {
@@ -64,7 +125,8 @@ fn err_response(err: JwtError) -> HttpResponse {
 
 #[cfg(test)]
 mod tests {
-    use crate::jwt_validator::get_bearer;
+    use super::*;
+    use crate::secret_to_bearer_header;
     use http::{header, HeaderMap};
 
     #[test]
@@ -93,4 +155,20 @@ mod tests {
         let token = get_bearer(&headers);
         assert!(token.is_none());
     }
+
+    #[test]
+    fn records_rejections() {
+        let rejections = JwtRejections::default();
+        let validator =
+            JwtAuthValidator::new(JwtSecret::random()).with_rejections(rejections.clone());
+        let start = Instant::now();
+
+        assert!(validator.validate(&HeaderMap::new()).is_err());
+        assert_eq!(rejections.last_since(start), Some(JwtRejectionReason::MissingToken));
+
+        let mut headers = HeaderMap::new();
+        headers.insert(header::AUTHORIZATION, secret_to_bearer_header(&JwtSecret::random()));
+        assert!(validator.validate(&headers).is_err());
+        assert_eq!(rejections.last_since(start), Some(JwtRejectionReason::SecretMismatch));
+    }
 }
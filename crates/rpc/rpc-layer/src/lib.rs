@@ -13,10 +13,14 @@ use jsonrpsee_http_client::HttpResponse;
 
 mod auth_client_layer;
 mod auth_layer;
+mod binary_encoding_layer;
 mod compression_layer;
 mod jwt_validator;
 
 pub use auth_layer::{AuthService, ResponseFuture};
+pub use binary_encoding_layer::{
+    encode_cbor, BinaryEncodingLayer, BinaryEncodingService, CBOR_MEDIA_TYPE, CBOR_SCHEMA_VERSION,
+};
 pub use compression_layer::CompressionLayer;
 
 // Export alloy JWT types
@@ -24,7 +28,7 @@ pub use alloy_rpc_types_engine::{Claims, JwtError, JwtSecret};
 
 pub use auth_client_layer::{secret_to_bearer_header, AuthClientLayer, AuthClientService};
 pub use auth_layer::AuthLayer;
-pub use jwt_validator::JwtAuthValidator;
+pub use jwt_validator::{JwtAuthValidator, JwtRejectionReason, JwtRejections};
 
 /// General purpose trait to validate Http Authorization headers. It's supposed to be integrated as
 /// a validator trait into an [`AuthLayer`].
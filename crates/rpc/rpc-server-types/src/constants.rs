@@ -15,9 +15,21 @@ pub const DEFAULT_MAX_BLOCKS_PER_FILTER: u64 = 100_000;
 /// The default maximum of logs in a single response.
 pub const DEFAULT_MAX_LOGS_PER_RESPONSE: usize = 20_000;
 
+/// The default time in seconds after which a filter that has not been polled is removed.
+pub const DEFAULT_FILTER_TTL_SECS: u64 = 5 * 60;
+
+/// The default number of most recently polled blocks a filter tracks to detect reorgs.
+pub const DEFAULT_FILTER_REORG_WINDOW: u64 = 64;
+
 /// The default maximum number of blocks for `trace_filter` requests.
 pub const DEFAULT_MAX_TRACE_FILTER_BLOCKS: u64 = 100;
 
+/// The default maximum number of traces in a single `trace_filter` response.
+pub const DEFAULT_MAX_TRACE_FILTER_RESULTS: usize = 100_000;
+
+/// The default number of blocks that are replayed concurrently by a single `trace_filter` request.
+pub const DEFAULT_TRACE_FILTER_CONCURRENCY: usize = 8;
+
 /// The default maximum number tracing requests we're allowing concurrently.
 /// Tracing is mostly CPU bound so we're limiting the number of concurrent requests to something
 /// lower that the number of cores, in order to minimize the impact on the rest of the system.
@@ -51,6 +63,54 @@ pub const DEFAULT_ENGINE_API_IPC_ENDPOINT: &str = "/tmp/reth_engine_api.ipc";
 /// The default limit for blocks count in `eth_simulateV1`.
 pub const DEFAULT_MAX_SIMULATE_BLOCKS: u64 = 256;
 
+/// The maximum number of proof targets (accounts plus storage slots) that can be requested in a
+/// single `reth_getProofBundle` call.
+pub const MAX_PROOF_BUNDLE_TARGETS: usize = 1024;
+
+/// The maximum number of transactions in a single `txpool_contentPage` response.
+pub const MAX_TXPOOL_CONTENT_PAGE_SIZE: usize = 10_000;
+
+/// The minimum number of elements of a response array to serialize it in parallel.
+pub const PARALLEL_SERIALIZATION_THRESHOLD: usize = 1024;
+
+/// The number of elements in the first batch of a parallel serialized response array.
+pub const PARALLEL_SERIALIZATION_BATCH_SIZE: usize = 1024;
+
+/// The default maximum size in megabytes buffered while serializing a response array in
+/// parallel.
+pub const DEFAULT_SERIALIZATION_MEMORY_BUDGET_MB: usize = 16;
+
+/// The default maximum number of bytes buffered while serializing a response array in parallel.
+pub const DEFAULT_SERIALIZATION_MEMORY_BUDGET: usize =
+    DEFAULT_SERIALIZATION_MEMORY_BUDGET_MB * 1024 * 1024;
+
+/// The maximum number of blocks that can be queried in a single `reth_getOmmerStats` call.
+pub const MAX_OMMER_STATS_BLOCK_RANGE: u64 = 100_000;
+
+/// The maximum number of blocks that can be queried in a single `reth_validatorIncome` call.
+pub const MAX_VALIDATOR_INCOME_BLOCK_RANGE: u64 = 10_000;
+
+/// The maximum number of addresses that can be queried in a single `reth_validatorIncome` call.
+pub const MAX_VALIDATOR_INCOME_ADDRESSES: usize = 1024;
+
+/// The maximum number of blocks in a single `reth_getAccountHistory` response.
+pub const MAX_ACCOUNT_HISTORY_PAGE_SIZE: usize = 10_000;
+
+/// The maximum number of addresses in a single `reth_topGasConsumers` response.
+pub const MAX_TOP_GAS_CONSUMERS: usize = 1_000;
+
+/// The maximum number of calls that can be executed in a single `reth_callMany` call.
+pub const MAX_CALL_MANY_CALLS: usize = 1024;
+
+/// The maximum number of transaction hashes in a single `eth_getTransactionReceiptsByHashes` call.
+pub const MAX_RECEIPTS_BY_HASHES: usize = 1024;
+
+/// The default maximum size in megabytes of the proof nodes in an `eth_getProof` response.
+pub const DEFAULT_MAX_PROOF_RESPONSE_SIZE_MB: usize = 16;
+
+/// The default maximum size in bytes of the proof nodes in an `eth_getProof` response.
+pub const DEFAULT_MAX_PROOF_RESPONSE_SIZE: usize = DEFAULT_MAX_PROOF_RESPONSE_SIZE_MB * 1024 * 1024;
+
 /// The default eth historical proof window.
 pub const DEFAULT_ETH_PROOF_WINDOW: u64 = 0;
 
@@ -80,6 +140,14 @@ pub mod gas_oracle {
     /// The percentile of gas prices to use for the estimate
     pub const DEFAULT_GAS_PRICE_PERCENTILE: u32 = 60;
 
+    /// The default weight (in %) of the mempool percentile in the priority fee suggestion.
+    ///
+    /// Zero disables the mempool blend.
+    pub const DEFAULT_MEMPOOL_WEIGHT: u32 = 0;
+
+    /// The maximum number of pending mempool transactions sampled for the priority fee suggestion
+    pub const MEMPOOL_SAMPLE_NUMBER: usize = 1_024;
+
     /// Maximum transaction priority fee (or gas price before London Fork) to be recommended by the
     /// gas price oracle
     pub const DEFAULT_MAX_GAS_PRICE: U256 = U256::from_limbs([500_000_000_000u64, 0, 0, 0]);
@@ -127,4 +195,16 @@ pub mod cache {
 
     /// Default number of concurrent database requests.
     pub const DEFAULT_CONCURRENT_DB_REQUESTS: usize = 512;
+
+    /// Default cache size for the bytecode cache: 10000 bytecodes.
+    pub const DEFAULT_BYTECODE_CACHE_MAX_LEN: u32 = 10_000;
+
+    /// Default number of recently used contracts whose bytecodes are pre-warmed on startup.
+    pub const DEFAULT_BYTECODE_CACHE_PREWARM: usize = 1_000;
+
+    /// Default number of most read contracts whose state is warmed with the query statistics.
+    pub const DEFAULT_WARMUP_CONTRACTS: usize = 1_000;
+
+    /// Default number of most queried block ranges that are read ahead with the query statistics.
+    pub const DEFAULT_WARMUP_RANGES: usize = 8;
 }
@@ -0,0 +1,185 @@
+//! Parallel JSON serialization of large responses.
+
+use crate::constants::{
+    DEFAULT_SERIALIZATION_MEMORY_BUDGET, PARALLEL_SERIALIZATION_BATCH_SIZE,
+    PARALLEL_SERIALIZATION_THRESHOLD,
+};
+use rayon::prelude::*;
+use serde::{ser::SerializeSeq, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::value::RawValue;
+use std::ops::{Deref, DerefMut};
+use tokio::runtime::{Handle, RuntimeFlavor};
+
+/// A JSON array whose elements are serialized in parallel if the array is large.
+///
+/// Serializes and deserializes exactly like a `Vec<T>`. Arrays with at least
+/// [`PARALLEL_SERIALIZATION_THRESHOLD`] elements are serialized in batches: the elements of a
+/// batch are serialized concurrently on the rayon thread pool, and the serialized elements are
+/// then written to the serializer in order. This keeps multi-MB responses, like large log sets,
+/// from being serialized on a single thread.
+///
+/// At most one batch of serialized elements is buffered at a time. Batches are sized to stay
+/// within the memory budget, based on the average size of the elements serialized so far.
+///
+/// Responses are serialized on the tokio worker of the request, so while waiting for the rayon
+/// pool the worker is handed over to the blocking pool and its other tasks move to other workers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParallelJsonArray<T> {
+    /// The elements of the array.
+    items: Vec<T>,
+    /// Maximum number of bytes of serialized elements buffered at a time.
+    memory_budget: usize,
+}
+
+impl<T> ParallelJsonArray<T> {
+    /// Creates a new array with the [`DEFAULT_SERIALIZATION_MEMORY_BUDGET`].
+    pub const fn new(items: Vec<T>) -> Self {
+        Self::with_memory_budget(items, DEFAULT_SERIALIZATION_MEMORY_BUDGET)
+    }
+
+    /// Creates a new array that buffers at most `memory_budget` bytes of serialized elements.
+    pub const fn with_memory_budget(items: Vec<T>, memory_budget: usize) -> Self {
+        Self { items, memory_budget }
+    }
+
+    /// Returns the elements of the array.
+    pub fn into_inner(self) -> Vec<T> {
+        self.items
+    }
+}
+
+impl<T> Default for ParallelJsonArray<T> {
+    fn default() -> Self {
+        Self::new(Vec::new())
+    }
+}
+
+impl<T> Deref for ParallelJsonArray<T> {
+    type Target = Vec<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.items
+    }
+}
+
+impl<T> DerefMut for ParallelJsonArray<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.items
+    }
+}
+
+impl<T> From<Vec<T>> for ParallelJsonArray<T> {
+    fn from(items: Vec<T>) -> Self {
+        Self::new(items)
+    }
+}
+
+impl<T> From<ParallelJsonArray<T>> for Vec<T> {
+    fn from(array: ParallelJsonArray<T>) -> Self {
+        array.items
+    }
+}
+
+impl<T> IntoIterator for ParallelJsonArray<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.into_iter()
+    }
+}
+
+impl<T: Serialize + Sync> Serialize for ParallelJsonArray<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.items.len() < PARALLEL_SERIALIZATION_THRESHOLD {
+            return self.items.serialize(serializer)
+        }
+
+        block_in_place(|| {
+            let mut seq = serializer.serialize_seq(Some(self.items.len()))?;
+            let mut remaining = self.items.as_slice();
+            let mut batch_size = PARALLEL_SERIALIZATION_BATCH_SIZE;
+            while !remaining.is_empty() {
+                let (batch, rest) = remaining.split_at(batch_size.min(remaining.len()));
+                remaining = rest;
+
+                let serialized = batch
+                    .par_iter()
+                    .map(serde_json::value::to_raw_value)
+                    .collect::<Result<Vec<Box<RawValue>>, _>>()
+                    .map_err(serde::ser::Error::custom)?;
+
+                let mut batch_bytes = 0;
+                for element in &serialized {
+                    batch_bytes += element.get().len();
+                    seq.serialize_element(element)?;
+                }
+
+                let average_size = batch_bytes.div_ceil(batch.len()).max(1);
+                batch_size = (self.memory_budget / average_size).max(1);
+            }
+            seq.end()
+        })
+    }
+}
+
+/// Runs the closure on the blocking pool if called from a multi-threaded tokio runtime, so that
+/// the other tasks of the current worker are not blocked by it.
+fn block_in_place<R>(f: impl FnOnce() -> R) -> R {
+    match Handle::try_current() {
+        Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => {
+            tokio::task::block_in_place(f)
+        }
+        _ => f(),
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for ParallelJsonArray<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::deserialize(deserializer).map(Self::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serializes_like_vec() {
+        let items = (0..PARALLEL_SERIALIZATION_THRESHOLD * 3)
+            .map(|i| (i, format!("item {i}")))
+            .collect::<Vec<_>>();
+        let expected = serde_json::to_string(&items).unwrap();
+
+        for budget in [1, 64, DEFAULT_SERIALIZATION_MEMORY_BUDGET] {
+            let array = ParallelJsonArray::with_memory_budget(items.clone(), budget);
+            assert_eq!(serde_json::to_string(&array).unwrap(), expected);
+        }
+
+        let small = ParallelJsonArray::new(vec![1, 2, 3]);
+        assert_eq!(serde_json::to_string(&small).unwrap(), "[1,2,3]");
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn serializes_in_runtime() {
+        let items = (0..PARALLEL_SERIALIZATION_THRESHOLD * 2).collect::<Vec<_>>();
+        let expected = serde_json::to_string(&items).unwrap();
+
+        let array = ParallelJsonArray::with_memory_budget(items, 64);
+        let serialized = tokio::spawn(async move { serde_json::to_string(&array).unwrap() });
+        assert_eq!(serialized.await.unwrap(), expected);
+    }
+
+    #[tokio::test]
+    async fn serializes_in_current_thread_runtime() {
+        let items = (0..PARALLEL_SERIALIZATION_THRESHOLD * 2).collect::<Vec<_>>();
+        let array = ParallelJsonArray::new(items.clone());
+        assert_eq!(serde_json::to_string(&array).unwrap(), serde_json::to_string(&items).unwrap());
+    }
+
+    #[test]
+    fn deserializes_like_vec() {
+        let array: ParallelJsonArray<u64> = serde_json::from_str("[1,2,3]").unwrap();
+        assert_eq!(array.into_inner(), vec![1, 2, 3]);
+    }
+}
@@ -10,9 +10,11 @@
 
 /// Common RPC constants.
 pub mod constants;
+pub mod json;
 pub mod result;
 
 mod module;
 pub use module::{RethRpcModule, RpcModuleSelection};
 
+pub use json::ParallelJsonArray;
 pub use result::ToRpcResult;
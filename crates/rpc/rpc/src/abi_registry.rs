@@ -0,0 +1,234 @@
+//! Registry of contract ABIs used to decode logs.
+
+use alloy_dyn_abi::{DynSolValue, EventExt};
+use alloy_json_abi::{Event, JsonAbi};
+use alloy_primitives::{map::B256HashMap, Bytes, LogData};
+use reth_rpc_api::{DecodedEvent, DecodedEventParam};
+use std::path::{Path, PathBuf};
+
+/// Errors that can occur when loading an [`AbiRegistry`].
+#[derive(Debug, thiserror::Error)]
+pub enum AbiRegistryError {
+    /// Failed to read an ABI file or directory.
+    #[error("failed to read {}: {source}", path.display())]
+    Io {
+        /// Path of the file or directory.
+        path: PathBuf,
+        /// The underlying error.
+        #[source]
+        source: std::io::Error,
+    },
+    /// Failed to parse an ABI file.
+    #[error("failed to parse ABI file {}: {source}", path.display())]
+    Json {
+        /// Path of the file.
+        path: PathBuf,
+        /// The underlying error.
+        #[source]
+        source: serde_json::Error,
+    },
+}
+
+/// A registry of event definitions, indexed by their selector, that is used to decode logs.
+///
+/// Anonymous events have no selector and are ignored.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AbiRegistry {
+    /// Events by selector.
+    ///
+    /// Events can share a selector if they only differ in which parameters are indexed, e.g. the
+    /// ERC-20 and ERC-721 `Transfer` events.
+    events: B256HashMap<Vec<Event>>,
+}
+
+impl AbiRegistry {
+    /// Loads all ABIs from the given paths.
+    ///
+    /// A path is either a JSON ABI file or a directory, in which case all `.json` files in the
+    /// directory are loaded. A file contains either a plain JSON ABI or a compiler artifact with an
+    /// `abi` field.
+    pub fn load<P: AsRef<Path>>(
+        paths: impl IntoIterator<Item = P>,
+    ) -> Result<Self, AbiRegistryError> {
+        let mut registry = Self::default();
+        for path in paths {
+            let path = path.as_ref();
+            let io_err = |source| AbiRegistryError::Io { path: path.to_path_buf(), source };
+            if path.is_dir() {
+                let mut files = Vec::new();
+                for entry in std::fs::read_dir(path).map_err(io_err)? {
+                    let file = entry.map_err(io_err)?.path();
+                    if file.is_file() && file.extension().is_some_and(|ext| ext == "json") {
+                        files.push(file);
+                    }
+                }
+                files.sort();
+                for file in files {
+                    registry.load_file(&file)?;
+                }
+            } else {
+                registry.load_file(path)?;
+            }
+        }
+        Ok(registry)
+    }
+
+    /// Loads the ABI of a single file.
+    fn load_file(&mut self, path: &Path) -> Result<(), AbiRegistryError> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|source| AbiRegistryError::Io { path: path.to_path_buf(), source })?;
+        let json_err = |source| AbiRegistryError::Json { path: path.to_path_buf(), source };
+        let mut value = serde_json::from_str::<serde_json::Value>(&contents).map_err(json_err)?;
+        if let Some(abi) = value.get_mut("abi") {
+            value = abi.take();
+        }
+        self.extend(&serde_json::from_value::<JsonAbi>(value).map_err(json_err)?);
+        Ok(())
+    }
+
+    /// Adds all non-anonymous events of the ABI to the registry.
+    pub fn extend(&mut self, abi: &JsonAbi) {
+        for event in abi.events().filter(|event| !event.anonymous) {
+            let events = self.events.entry(event.selector()).or_default();
+            if !events.contains(event) {
+                events.push(event.clone());
+            }
+        }
+    }
+
+    /// Returns the number of events in the registry.
+    pub fn len(&self) -> usize {
+        self.events.values().map(Vec::len).sum()
+    }
+
+    /// Returns `true` if the registry contains no events.
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Decodes the log with the first known event that matches its selector and layout.
+    ///
+    /// Returns `None` if the log is anonymous or no known event matches.
+    pub fn decode(&self, log: &LogData) -> Option<DecodedEvent> {
+        let events = self.events.get(log.topics().first()?)?;
+        events.iter().find_map(|event| {
+            let indexed_inputs = event.inputs.iter().filter(|input| input.indexed).count();
+            if indexed_inputs + 1 != log.topics().len() {
+                return None
+            }
+            let decoded = event.decode_log(log).ok()?;
+            let (mut indexed, mut body) = (decoded.indexed.into_iter(), decoded.body.into_iter());
+            let params = event
+                .inputs
+                .iter()
+                .map(|input| {
+                    let value = if input.indexed { indexed.next() } else { body.next() }?;
+                    Some(DecodedEventParam {
+                        name: input.name.clone(),
+                        ty: input.selector_type().into_owned(),
+                        indexed: input.indexed,
+                        value: value_to_json(value),
+                    })
+                })
+                .collect::<Option<Vec<_>>>()?;
+            Some(DecodedEvent { name: event.name.clone(), signature: event.signature(), params })
+        })
+    }
+}
+
+/// Converts a decoded value to JSON.
+///
+/// Integers are encoded as decimal strings to not lose precision, byte values as hex strings.
+/// Indexed parameters of dynamic types are only available as the hash of their value.
+fn value_to_json(value: DynSolValue) -> serde_json::Value {
+    match value {
+        DynSolValue::Bool(value) => value.into(),
+        DynSolValue::Int(value, _) => value.to_string().into(),
+        DynSolValue::Uint(value, _) => value.to_string().into(),
+        DynSolValue::FixedBytes(word, size) => {
+            Bytes::copy_from_slice(&word[..size]).to_string().into()
+        }
+        DynSolValue::Address(address) => address.to_checksum(None).into(),
+        DynSolValue::Function(function) => function.to_string().into(),
+        DynSolValue::Bytes(bytes) => Bytes::from(bytes).to_string().into(),
+        DynSolValue::String(value) => value.into(),
+        DynSolValue::Array(values) |
+        DynSolValue::FixedArray(values) |
+        DynSolValue::Tuple(values) => values.into_iter().map(value_to_json).collect(),
+        DynSolValue::CustomStruct { prop_names, tuple, .. } => {
+            prop_names.into_iter().zip(tuple.into_iter().map(value_to_json)).collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, b256, Address, U256};
+
+    const ERC20: &str = r#"[{"type":"event","name":"Transfer","anonymous":false,"inputs":[
+        {"name":"from","type":"address","indexed":true},
+        {"name":"to","type":"address","indexed":true},
+        {"name":"value","type":"uint256","indexed":false}]}]"#;
+
+    const ERC721: &str = r#"{"abi":[{"type":"event","name":"Transfer","anonymous":false,"inputs":[
+        {"name":"from","type":"address","indexed":true},
+        {"name":"to","type":"address","indexed":true},
+        {"name":"tokenId","type":"uint256","indexed":true}]}]}"#;
+
+    fn transfer_log(indexed_value: bool) -> LogData {
+        let from = address!("0x0000000000000000000000000000000000000001");
+        let to = address!("0x0000000000000000000000000000000000000002");
+        let value = U256::from(42).to_be_bytes::<32>();
+        let mut topics = vec![
+            b256!("0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"),
+            from.into_word(),
+            to.into_word(),
+        ];
+        let data = if indexed_value {
+            topics.push(value.into());
+            Bytes::new()
+        } else {
+            Bytes::copy_from_slice(&value)
+        };
+        LogData::new_unchecked(topics, data)
+    }
+
+    #[test]
+    fn load_and_decode() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("erc20.json"), ERC20).unwrap();
+        std::fs::write(dir.path().join("erc721.json"), ERC721).unwrap();
+        std::fs::write(dir.path().join("README.md"), "not an abi").unwrap();
+
+        let registry = AbiRegistry::load([dir.path()]).unwrap();
+        assert_eq!(registry.len(), 2);
+
+        let decoded = registry.decode(&transfer_log(false)).unwrap();
+        assert_eq!(decoded.name, "Transfer");
+        assert_eq!(decoded.signature, "Transfer(address,address,uint256)");
+        assert_eq!(decoded.params[0].name, "from");
+        assert_eq!(
+            decoded.params[1].value,
+            serde_json::json!("0x0000000000000000000000000000000000000002")
+        );
+        assert_eq!(decoded.params[2].name, "value");
+        assert_eq!(decoded.params[2].value, serde_json::json!("42"));
+        assert!(!decoded.params[2].indexed);
+
+        let decoded = registry.decode(&transfer_log(true)).unwrap();
+        assert_eq!(decoded.params[2].name, "tokenId");
+        assert!(decoded.params[2].indexed);
+
+        let unknown = LogData::new_unchecked(vec![Address::ZERO.into_word()], Bytes::new());
+        assert_eq!(registry.decode(&unknown), None);
+    }
+
+    #[test]
+    fn load_invalid_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("invalid.json");
+        std::fs::write(&path, "{").unwrap();
+        assert!(matches!(AbiRegistry::load([&path]), Err(AbiRegistryError::Json { .. })));
+    }
+}
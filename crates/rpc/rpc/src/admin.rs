@@ -9,10 +9,14 @@ use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
 use reth_chainspec::{EthChainSpec, EthereumHardfork, EthereumHardforks, ForkCondition};
 use reth_network_api::{NetworkInfo, Peers};
-use reth_network_peers::{id2pk, AnyNode, NodeRecord};
+use reth_network_peers::{id2pk, AnyNode};
 use reth_network_types::PeerKind;
 use reth_rpc_api::AdminApiServer;
-use reth_rpc_server_types::ToRpcResult;
+use reth_rpc_server_types::{
+    result::{internal_rpc_err, invalid_params_rpc_err},
+    ToRpcResult,
+};
+use reth_transaction_pool::{firewall::FirewallConfig, PoolFirewall};
 
 /// `admin` API implementation.
 ///
@@ -22,12 +26,27 @@ pub struct AdminApi<N, ChainSpec> {
     network: N,
     /// The specification of the blockchain's configuration.
     chain_spec: Arc<ChainSpec>,
+    /// The admission firewall of the transaction pool, if the pool has one.
+    txpool_firewall: Option<PoolFirewall>,
 }
 
 impl<N, ChainSpec> AdminApi<N, ChainSpec> {
     /// Creates a new instance of `AdminApi`.
     pub const fn new(network: N, chain_spec: Arc<ChainSpec>) -> Self {
-        Self { network, chain_spec }
+        Self { network, chain_spec, txpool_firewall: None }
+    }
+
+    /// Sets the transaction pool firewall managed by `admin_setTxPoolFirewall`.
+    pub fn with_txpool_firewall(mut self, firewall: Option<PoolFirewall>) -> Self {
+        self.txpool_firewall = firewall;
+        self
+    }
+
+    /// Returns the transaction pool firewall or an error if there is none.
+    fn txpool_firewall_or_err(&self) -> RpcResult<&PoolFirewall> {
+        self.txpool_firewall
+            .as_ref()
+            .ok_or_else(|| internal_rpc_err("transaction pool has no firewall"))
     }
 }
 
@@ -38,14 +57,22 @@ where
     ChainSpec: EthChainSpec + EthereumHardforks + Send + Sync + 'static,
 {
     /// Handler for `admin_addPeer`
-    fn add_peer(&self, record: NodeRecord) -> RpcResult<bool> {
-        self.network.add_peer_with_udp(record.id, record.tcp_addr(), record.udp_addr());
+    fn add_peer(&self, record: AnyNode) -> RpcResult<bool> {
+        let record = record
+            .node_record()
+            .ok_or_else(|| invalid_params_rpc_err("node has no ip address and ports"))?;
+        self.network.connect_peer_kind(
+            record.id,
+            PeerKind::Static,
+            record.tcp_addr(),
+            Some(record.udp_addr()),
+        );
         Ok(true)
     }
 
     /// Handler for `admin_removePeer`
     fn remove_peer(&self, record: AnyNode) -> RpcResult<bool> {
-        self.network.remove_peer(record.peer_id(), PeerKind::Basic);
+        self.network.remove_peer(record.peer_id(), PeerKind::Static);
         Ok(true)
     }
 
@@ -167,7 +194,7 @@ where
             enr: self.network.local_enr().to_string(),
             ip: enode.address,
             ports: Ports { discovery: enode.udp_port, listener: enode.tcp_port },
-            listen_addr: enode.tcp_addr(),
+            listen_addr: self.network.local_addr(),
             #[expect(deprecated)]
             protocols: ProtocolInfo {
                 eth: Some(EthProtocolInfo {
@@ -182,6 +209,17 @@ where
         })
     }
 
+    /// Handler for `admin_txPoolFirewall`
+    fn txpool_firewall(&self) -> RpcResult<FirewallConfig> {
+        Ok(self.txpool_firewall_or_err()?.config())
+    }
+
+    /// Handler for `admin_setTxPoolFirewall`
+    fn set_txpool_firewall(&self, config: FirewallConfig) -> RpcResult<bool> {
+        self.txpool_firewall_or_err()?.set_config(config);
+        Ok(true)
+    }
+
     /// Handler for `admin_peerEvents`
     async fn subscribe_peer_events(
         &self,
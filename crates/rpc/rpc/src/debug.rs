@@ -15,6 +15,7 @@ use alloy_rpc_types_trace::geth::{
 };
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
+use parking_lot::Mutex;
 use reth_chainspec::{ChainSpecProvider, EthChainSpec, EthereumHardforks};
 use reth_evm::{execute::Executor, ConfigureEvm, EvmEnvFor, TxEnvFor};
 use reth_primitives_traits::{
@@ -22,7 +23,7 @@ use reth_primitives_traits::{
 };
 use reth_revm::{
     database::StateProviderDatabase,
-    db::{CacheDB, State},
+    db::{states::bundle_state::BundleRetention, CacheDB, State},
     witness::ExecutionWitnessRecord,
 };
 use reth_rpc_api::DebugApiServer;
@@ -37,11 +38,12 @@ use reth_storage_api::{
     StateProofProvider, StateProviderFactory, StateRootProvider, TransactionVariant,
 };
 use reth_tasks::pool::BlockingTaskGuard;
-use reth_trie_common::{updates::TrieUpdates, HashedPostState};
+use reth_trie_common::{updates::TrieUpdates, HashedPostState, KeccakKeyHasher, TrieInput};
 use revm::{context_interface::Transaction, state::EvmState, DatabaseCommit};
 use revm_inspectors::tracing::{
     FourByteInspector, MuxInspector, TracingInspector, TracingInspectorConfig, TransactionContext,
 };
+use schnellru::{LruMap, Unlimited};
 use std::sync::Arc;
 use tokio::sync::{AcquireError, OwnedSemaphorePermit};
 
@@ -57,7 +59,22 @@ pub struct DebugApi<Eth, BlockExecutor> {
 impl<Eth, Evm> DebugApi<Eth, Evm> {
     /// Create a new instance of the [`DebugApi`]
     pub fn new(eth: Eth, blocking_task_guard: BlockingTaskGuard, evm_config: Evm) -> Self {
-        let inner = Arc::new(DebugApiInner { eth_api: eth, blocking_task_guard, evm_config });
+        Self::with_trace_cache(eth, blocking_task_guard, evm_config, 0)
+    }
+
+    /// Create a new instance of the [`DebugApi`] that caches block traces up to a total size of
+    /// `trace_cache_size` bytes.
+    ///
+    /// Caching is disabled if `trace_cache_size` is zero.
+    pub fn with_trace_cache(
+        eth: Eth,
+        blocking_task_guard: BlockingTaskGuard,
+        evm_config: Evm,
+        trace_cache_size: u64,
+    ) -> Self {
+        let trace_cache = (trace_cache_size > 0).then(|| BlockTraceCache::new(trace_cache_size));
+        let inner =
+            Arc::new(DebugApiInner { eth_api: eth, blocking_task_guard, evm_config, trace_cache });
         Self { inner }
     }
 
@@ -190,6 +207,14 @@ where
             .map_err(Eth::Error::from_eth_err)?
             .ok_or(EthApiError::HeaderNotFound(block_id))?;
 
+        let cache = self.inner.trace_cache.as_ref().and_then(|cache| {
+            let key = (block_hash, serde_json::to_string(&opts).ok()?);
+            Some((cache, key))
+        });
+        if let Some(traces) = cache.as_ref().and_then(|(cache, key)| cache.get(key)) {
+            return Ok(traces)
+        }
+
         let ((evm_env, _), block) = futures::try_join!(
             self.eth_api().evm_env_at(block_hash.into()),
             self.eth_api().recovered_block(block_hash.into()),
@@ -197,7 +222,11 @@ where
 
         let block = block.ok_or(EthApiError::HeaderNotFound(block_id))?;
 
-        self.trace_block(block, evm_env, opts).await
+        let traces = self.trace_block(block, evm_env, opts).await?;
+        if let Some((cache, key)) = cache {
+            cache.insert(key, traces.clone());
+        }
+        Ok(traces)
     }
 
     /// Trace the transaction according to the provided options.
@@ -653,6 +682,88 @@ where
             })
             .await?;
 
+        exec_witness.headers = self.execution_witness_headers(block_number, lowest_block_number)?;
+
+        Ok(exec_witness)
+    }
+
+    /// The `debug_transactionWitness` method replays the block containing the given transaction
+    /// with the purpose of generating the execution witness of that single transaction.
+    ///
+    /// The trie nodes of the witness prove the state accessed by the transaction against the state
+    /// the transaction is executed on, i.e. the state of the parent block with the preceding
+    /// transactions of the block applied.
+    pub async fn debug_transaction_witness(
+        &self,
+        tx_hash: B256,
+    ) -> Result<ExecutionWitness, Eth::Error> {
+        let (transaction, block) = match self.eth_api().transaction_and_block(tx_hash).await? {
+            None => return Err(EthApiError::TransactionNotFound.into()),
+            Some(res) => res,
+        };
+        let (evm_env, _) = self.eth_api().evm_env_at(block.hash().into()).await?;
+        let block_number = block.header().number();
+
+        let this = self.clone();
+        let (mut exec_witness, lowest_block_number) = self
+            .eth_api()
+            .spawn_with_state_at_block(block.parent_hash().into(), move |state_provider| {
+                let mut db = State::builder()
+                    .with_database(StateProviderDatabase::new(&state_provider))
+                    .with_bundle_update()
+                    .build();
+
+                this.eth_api().apply_pre_execution_changes(&block, &mut db, &evm_env)?;
+
+                // replay all transactions prior to the targeted transaction
+                let tx = transaction.into_recovered();
+                this.eth_api().replay_transactions_until(
+                    &mut db,
+                    evm_env.clone(),
+                    block.transactions_recovered(),
+                    *tx.tx_hash(),
+                )?;
+
+                // the changes of the preceding transactions, which the witness is generated on top
+                // of
+                db.merge_transitions(BundleRetention::PlainState);
+                let preceding_state =
+                    HashedPostState::from_bundle_state::<KeccakKeyHasher>(&db.bundle_state.state);
+
+                // only the block hashes accessed by the targeted transaction are required
+                db.block_hashes.clear();
+
+                let tx_env = this.eth_api().evm_config().tx_env(&tx);
+                let res = this.eth_api().transact(&mut db, evm_env, tx_env)?;
+
+                let mut witness_record = ExecutionWitnessRecord::default();
+                witness_record.record_transaction_state(&res.state);
+                let ExecutionWitnessRecord { hashed_state, codes, keys, .. } = witness_record;
+                // BTreeMap keys are ordered, so the first key is the smallest
+                let lowest_block_number = db.block_hashes.keys().next().copied();
+
+                let state = state_provider
+                    .witness(TrieInput::from_state(preceding_state), hashed_state)
+                    .map_err(EthApiError::from)?;
+                Ok((
+                    ExecutionWitness { state, codes, keys, ..Default::default() },
+                    lowest_block_number,
+                ))
+            })
+            .await?;
+
+        exec_witness.headers = self.execution_witness_headers(block_number, lowest_block_number)?;
+
+        Ok(exec_witness)
+    }
+
+    /// Returns the RLP encoded headers of an execution witness of the given block, from the lowest
+    /// block whose hash was accessed through the BLOCKHASH opcode to the parent block.
+    fn execution_witness_headers(
+        &self,
+        block_number: u64,
+        lowest_block_number: Option<u64>,
+    ) -> Result<Vec<Bytes>, Eth::Error> {
         let smallest = match lowest_block_number {
             Some(smallest) => smallest,
             None => {
@@ -664,7 +775,7 @@ where
 
         let range = smallest..block_number;
         // TODO: Check if headers_range errors when one of the headers in the range is missing
-        exec_witness.headers = self
+        Ok(self
             .provider()
             .headers_range(range)
             .map_err(EthApiError::from)?
@@ -674,9 +785,7 @@ where
                 header.encode(&mut serialized_header);
                 serialized_header.into()
             })
-            .collect();
-
-        Ok(exec_witness)
+            .collect())
     }
 
     /// Returns the code associated with a given hash at the specified block ID. If no code is
@@ -892,18 +1001,9 @@ where
 {
     /// Handler for `debug_getRawHeader`
     async fn raw_header(&self, block_id: BlockId) -> RpcResult<Bytes> {
-        let header = match block_id {
-            BlockId::Hash(hash) => self.provider().header(&hash.into()).to_rpc_result()?,
-            BlockId::Number(number_or_tag) => {
-                let number = self
-                    .provider()
-                    .convert_block_number(number_or_tag)
-                    .to_rpc_result()?
-                    .ok_or_else(|| {
-                    internal_rpc_err("Pending block not supported".to_string())
-                })?;
-                self.provider().header_by_number(number).to_rpc_result()?
-            }
+        let header = match self.provider().resolve_block_id(block_id).to_rpc_result()? {
+            Some(num_hash) => self.provider().header(&num_hash.hash).to_rpc_result()?,
+            None => None,
         };
 
         let mut res = Vec::new();
@@ -1061,6 +1161,12 @@ where
         Self::debug_execution_witness_by_block_hash(self, hash).await.map_err(Into::into)
     }
 
+    /// Handler for `debug_transactionWitness`
+    async fn debug_transaction_witness(&self, tx_hash: B256) -> RpcResult<ExecutionWitness> {
+        let _permit = self.acquire_trace_permit().await;
+        Self::debug_transaction_witness(self, tx_hash).await.map_err(Into::into)
+    }
+
     async fn debug_backtrace_at(&self, _location: &str) -> RpcResult<()> {
         Ok(())
     }
@@ -1312,4 +1418,256 @@ struct DebugApiInner<Eth, Evm> {
     blocking_task_guard: BlockingTaskGuard,
     /// block executor for debug & trace apis
     evm_config: Evm,
+    /// Cache of block traces, if enabled.
+    trace_cache: Option<BlockTraceCache>,
+}
+
+/// LRU cache of block traces, keyed by block hash and the serialized tracing options.
+///
+/// The options include the tracer and its config, so the same block traced with different
+/// tracers is cached separately. The cache is bounded by the serialized size of the traces, the
+/// least recently used traces are evicted once the total exceeds the maximum.
+struct BlockTraceCache {
+    inner: Mutex<BlockTraceCacheInner>,
+}
+
+struct BlockTraceCacheInner {
+    /// The traces and their size in bytes.
+    traces: LruMap<(B256, String), (Vec<TraceResult>, u64), Unlimited>,
+    /// Total size of the cached traces in bytes.
+    size: u64,
+    max_size: u64,
+}
+
+impl BlockTraceCache {
+    fn new(max_size: u64) -> Self {
+        Self {
+            inner: Mutex::new(BlockTraceCacheInner {
+                traces: LruMap::new(Unlimited),
+                size: 0,
+                max_size,
+            }),
+        }
+    }
+
+    /// Returns the cached traces of the key and marks them as recently used.
+    fn get(&self, key: &(B256, String)) -> Option<Vec<TraceResult>> {
+        self.inner.lock().traces.get(key).map(|(traces, _)| traces.clone())
+    }
+
+    /// Caches the traces, unless they alone exceed the maximum size.
+    fn insert(&self, key: (B256, String), traces: Vec<TraceResult>) {
+        let Ok(serialized) = serde_json::to_vec(&traces) else { return };
+        let size = (key.1.len() + serialized.len()) as u64;
+
+        let mut inner = self.inner.lock();
+        if size > inner.max_size {
+            return
+        }
+        if let Some((_, replaced)) = inner.traces.remove(&key) {
+            inner.size -= replaced;
+        }
+        while inner.size + size > inner.max_size {
+            let Some((_, (_, evicted))) = inner.traces.pop_oldest() else { break };
+            inner.size -= evicted;
+        }
+        inner.traces.insert(key, (traces, size));
+        inner.size += size;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EthApi;
+    use alloy_consensus::{transaction::Recovered, Header, SignableTransaction, TxLegacy};
+    use alloy_genesis::{Genesis, GenesisAccount};
+    use alloy_primitives::{bytes, map::B256Map, TxKind, U256};
+    use alloy_rpc_types_trace::geth::GethDebugTracerConfig;
+    use alloy_signer::SignerSync;
+    use alloy_signer_local::PrivateKeySigner;
+    use reth_chainspec::{ChainSpecBuilder, MAINNET};
+    use reth_db_common::init::init_genesis;
+    use reth_errors::ProviderError;
+    use reth_ethereum_primitives::{Block, BlockBody, TransactionSigned};
+    use reth_evm::Evm;
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_network_api::noop::NoopNetwork;
+    use reth_provider::{
+        providers::BlockchainProvider, test_utils::create_test_provider_factory_with_chain_spec,
+        StaticFileProviderFactory, StaticFileSegment,
+    };
+    use reth_stateless::trie::StatelessSparseTrie;
+    use reth_transaction_pool::test_utils::testing_pool;
+    use revm::{bytecode::Bytecode, state::AccountInfo, Database};
+    use std::collections::BTreeMap;
+
+    /// Serves the state proven by an execution witness.
+    #[derive(Debug)]
+    struct WitnessDb<'a> {
+        trie: &'a StatelessSparseTrie,
+        bytecodes: B256Map<Bytecode>,
+    }
+
+    impl Database for WitnessDb<'_> {
+        type Error = ProviderError;
+
+        fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+            Ok(self.trie.account(address)?.map(|account| AccountInfo {
+                balance: account.balance,
+                nonce: account.nonce,
+                code_hash: account.code_hash,
+                code: self.bytecodes.get(&account.code_hash).cloned(),
+            }))
+        }
+
+        fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+            self.bytecodes
+                .get(&code_hash)
+                .cloned()
+                .ok_or_else(|| ProviderError::TrieWitnessError(format!("missing code {code_hash}")))
+        }
+
+        fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+            self.trie.storage(address, index)
+        }
+
+        fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error> {
+            Err(ProviderError::HeaderNotFound(number.into()))
+        }
+    }
+
+    #[test]
+    fn block_trace_cache() {
+        let traces =
+            vec![TraceResult::Success { result: NoopFrame::default().into(), tx_hash: None }];
+        let call_tracer = GethDebugTracingOptions {
+            tracer: Some(GethDebugBuiltInTracerType::CallTracer.into()),
+            ..Default::default()
+        };
+        let with_config = GethDebugTracingOptions {
+            tracer_config: GethDebugTracerConfig(serde_json::json!({"onlyTopCall": true})),
+            ..call_tracer.clone()
+        };
+        let call_tracer = serde_json::to_string(&call_tracer).unwrap();
+        let with_config = serde_json::to_string(&with_config).unwrap();
+        let size =
+            |options: &str| (options.len() + serde_json::to_vec(&traces).unwrap().len()) as u64;
+
+        // room for the traces of both options
+        let cache = BlockTraceCache::new(size(&call_tracer) + size(&with_config));
+        cache.insert((B256::ZERO, call_tracer.clone()), traces.clone());
+        assert_eq!(cache.get(&(B256::ZERO, call_tracer.clone())), Some(traces.clone()));
+        assert_eq!(cache.get(&(B256::ZERO, with_config.clone())), None);
+        cache.insert((B256::ZERO, with_config.clone()), traces.clone());
+        assert_eq!(cache.get(&(B256::ZERO, call_tracer.clone())), Some(traces.clone()));
+
+        // the least recently used traces are evicted once the size is exceeded
+        let other_block = B256::with_last_byte(1);
+        cache.insert((other_block, call_tracer.clone()), traces.clone());
+        assert_eq!(cache.get(&(B256::ZERO, with_config)), None);
+        assert_eq!(cache.get(&(B256::ZERO, call_tracer.clone())), Some(traces.clone()));
+        assert_eq!(cache.get(&(other_block, call_tracer.clone())), Some(traces.clone()));
+
+        // traces larger than the cache are not cached
+        let cache = BlockTraceCache::new(size(&call_tracer) - 1);
+        cache.insert((B256::ZERO, call_tracer.clone()), traces);
+        assert_eq!(cache.get(&(B256::ZERO, call_tracer)), None);
+    }
+
+    #[tokio::test]
+    async fn transaction_witness_proves_pre_state() {
+        // increments the counter in slot 0 and returns it
+        let counter = Address::with_last_byte(0xc0);
+        let code = bytes!("6000546001018060005560005260206000f3");
+        let signer = PrivateKeySigner::random();
+        let genesis = Genesis::default().extend_accounts([
+            (signer.address(), GenesisAccount::default().with_balance(U256::from(1))),
+            (
+                counter,
+                GenesisAccount::default()
+                    .with_code(Some(code))
+                    .with_storage(Some(BTreeMap::from([(B256::ZERO, B256::with_last_byte(5))]))),
+            ),
+        ]);
+        let chain_spec = Arc::new(
+            ChainSpecBuilder::default()
+                .chain(MAINNET.chain)
+                .genesis(genesis)
+                .berlin_activated()
+                .build(),
+        );
+
+        let factory = create_test_provider_factory_with_chain_spec(chain_spec.clone());
+        init_genesis(&factory).unwrap();
+
+        // two transactions of the block increment the counter, the second one on top of the first
+        let transactions = (0..2)
+            .map(|nonce| {
+                let tx = TxLegacy {
+                    nonce,
+                    gas_limit: 100_000,
+                    to: TxKind::Call(counter),
+                    ..Default::default()
+                };
+                let signature = signer.sign_hash_sync(&tx.signature_hash()).unwrap();
+                TransactionSigned::from(tx.into_signed(signature))
+            })
+            .collect::<Vec<_>>();
+        let block = Block {
+            header: Header {
+                parent_hash: chain_spec.genesis_hash(),
+                number: 1,
+                gas_limit: 1_000_000,
+                difficulty: U256::from(1),
+                ..Default::default()
+            },
+            body: BlockBody { transactions: transactions.clone(), ..Default::default() },
+        }
+        .try_into_recovered()
+        .unwrap();
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw.insert_historical_block(block.clone()).unwrap();
+        provider_rw
+            .static_file_provider()
+            .latest_writer(StaticFileSegment::Headers)
+            .unwrap()
+            .commit()
+            .unwrap();
+        provider_rw.commit().unwrap();
+
+        let evm_config = EthEvmConfig::new(chain_spec.clone());
+        let eth_api = EthApi::builder(
+            BlockchainProvider::new(factory).unwrap(),
+            testing_pool(),
+            NoopNetwork::default(),
+            evm_config.clone(),
+        )
+        .build();
+        let debug_api = DebugApi::new(eth_api, BlockingTaskGuard::new(1), evm_config.clone());
+
+        // each transaction is re-executed against the pre-state proven by its witness, starting
+        // with the state root of the parent block
+        let mut pre_state_root = chain_spec.genesis_header().state_root;
+        for (transaction, expected) in transactions.iter().zip([6u8, 7]) {
+            let witness =
+                debug_api.debug_transaction_witness(*transaction.tx_hash()).await.unwrap();
+            let (mut trie, bytecodes) = StatelessSparseTrie::new(&witness, pre_state_root).unwrap();
+
+            let db = WitnessDb { trie: &trie, bytecodes };
+            let mut evm = evm_config.evm_with_env(db, evm_config.evm_env(block.header()));
+            let tx = Recovered::new_unchecked(transaction.clone(), signer.address());
+            let res = evm.transact(evm_config.tx_env(&tx)).unwrap();
+            drop(evm);
+            assert_eq!(
+                res.result.output(),
+                Some(&Bytes::copy_from_slice(B256::with_last_byte(expected).as_slice()))
+            );
+
+            // the witness also covers the state changed by the transaction
+            let mut record = ExecutionWitnessRecord::default();
+            record.record_transaction_state(&res.state);
+            pre_state_root = trie.calculate_state_root(record.hashed_state).unwrap();
+        }
+    }
 }
@@ -5,11 +5,13 @@ use reth_chain_state::CanonStateSubscriptions;
 use reth_chainspec::ChainSpecProvider;
 use reth_node_api::NodePrimitives;
 use reth_rpc_eth_types::{
+    canonical_index::canonical_hash_index_new_blocks_task,
     fee_history::fee_history_cache_new_blocks_task, EthStateCache, EthStateCacheConfig,
     FeeHistoryCache, FeeHistoryCacheConfig, GasCap, GasPriceOracle, GasPriceOracleConfig,
 };
 use reth_rpc_server_types::constants::{
-    DEFAULT_ETH_PROOF_WINDOW, DEFAULT_MAX_SIMULATE_BLOCKS, DEFAULT_PROOF_PERMITS,
+    DEFAULT_ETH_PROOF_WINDOW, DEFAULT_MAX_PROOF_RESPONSE_SIZE, DEFAULT_MAX_SIMULATE_BLOCKS,
+    DEFAULT_PROOF_PERMITS,
 };
 use reth_storage_api::{BlockReaderIdExt, StateProviderFactory};
 use reth_tasks::{pool::BlockingTaskPool, TaskSpawner, TokioTaskExecutor};
@@ -31,6 +33,7 @@ where
     gas_cap: GasCap,
     max_simulate_blocks: u64,
     eth_proof_window: u64,
+    max_proof_response_size: usize,
     fee_history_cache_config: FeeHistoryCacheConfig,
     proof_permits: usize,
     eth_state_cache_config: EthStateCacheConfig,
@@ -60,6 +63,7 @@ where
             gas_cap: GasCap::default(),
             max_simulate_blocks: DEFAULT_MAX_SIMULATE_BLOCKS,
             eth_proof_window: DEFAULT_ETH_PROOF_WINDOW,
+            max_proof_response_size: DEFAULT_MAX_PROOF_RESPONSE_SIZE,
             blocking_task_pool: None,
             fee_history_cache_config: FeeHistoryCacheConfig::default(),
             proof_permits: DEFAULT_PROOF_PERMITS,
@@ -125,6 +129,12 @@ where
         self
     }
 
+    /// Sets the maximum size in bytes of the proof nodes in an `eth_getProof` response.
+    pub const fn max_proof_response_size(mut self, max_proof_response_size: usize) -> Self {
+        self.max_proof_response_size = max_proof_response_size;
+        self
+    }
+
     /// Sets the blocking task pool.
     pub fn blocking_task_pool(mut self, blocking_task_pool: BlockingTaskPool) -> Self {
         self.blocking_task_pool = Some(blocking_task_pool);
@@ -181,6 +191,7 @@ where
             gas_cap,
             max_simulate_blocks,
             eth_proof_window,
+            max_proof_response_size,
             blocking_task_pool,
             fee_history_cache_config,
             proof_permits,
@@ -204,8 +215,8 @@ where
             }),
         );
 
-        EthApiInner::new(
-            provider,
+        let inner = EthApiInner::new(
+            provider.clone(),
             pool,
             network,
             eth_cache,
@@ -221,6 +232,18 @@ where
             task_spawner,
             proof_permits,
         )
+        .with_max_proof_response_size(max_proof_response_size);
+
+        let new_canonical_blocks = provider.canonical_state_stream();
+        let index = inner.canonical_hash_index().clone();
+        inner.task_spawner().spawn_critical(
+            "canonical hash index task",
+            Box::pin(async move {
+                canonical_hash_index_new_blocks_task(index, new_canonical_blocks).await;
+            }),
+        );
+
+        inner
     }
 
     /// Builds the [`EthApi`] instance.
@@ -16,8 +16,10 @@ use reth_rpc_eth_api::{
     EthApiTypes, RpcNodeCore,
 };
 use reth_rpc_eth_types::{
-    EthApiError, EthStateCache, FeeHistoryCache, GasCap, GasPriceOracle, PendingBlock,
+    CanonicalHashIndex, EthApiError, EthStateCache, FeeHistoryCache, GasCap, GasPriceOracle,
+    PendingBlock,
 };
+use reth_rpc_server_types::constants::DEFAULT_MAX_PROOF_RESPONSE_SIZE;
 use reth_storage_api::{
     BlockReader, BlockReaderIdExt, NodePrimitivesProvider, ProviderBlock, ProviderHeader,
     ProviderReceipt,
@@ -220,6 +222,11 @@ where
     fn cache(&self) -> &EthStateCache<ProviderBlock<Provider>, ProviderReceipt<Provider>> {
         self.inner.cache()
     }
+
+    #[inline]
+    fn canonical_hash_index(&self) -> Option<&CanonicalHashIndex> {
+        Some(self.inner.canonical_hash_index())
+    }
 }
 
 impl<Provider, Pool, Network, EvmConfig> std::fmt::Debug
@@ -275,6 +282,8 @@ pub struct EthApiInner<Provider: BlockReader, Pool, Network, EvmConfig> {
     max_simulate_blocks: u64,
     /// The maximum number of blocks into the past for generating state proofs.
     eth_proof_window: u64,
+    /// The maximum size in bytes of the proof nodes in an `eth_getProof` response.
+    max_proof_response_size: usize,
     /// The block number at which the node started
     starting_block: U256,
     /// The type that can spawn tasks which would otherwise block.
@@ -285,6 +294,8 @@ pub struct EthApiInner<Provider: BlockReader, Pool, Network, EvmConfig> {
     blocking_task_pool: BlockingTaskPool,
     /// Cache for block fees history
     fee_history_cache: FeeHistoryCache<ProviderHeader<Provider>>,
+    /// Index of recent canonical block hashes
+    canonical_hash_index: CanonicalHashIndex,
     /// The type that defines how to configure the EVM
     evm_config: EvmConfig,
 
@@ -339,16 +350,24 @@ where
             gas_cap: gas_cap.into().into(),
             max_simulate_blocks,
             eth_proof_window,
+            max_proof_response_size: DEFAULT_MAX_PROOF_RESPONSE_SIZE,
             starting_block,
             task_spawner,
             pending_block: Default::default(),
             blocking_task_pool,
             fee_history_cache,
+            canonical_hash_index: CanonicalHashIndex::default(),
             evm_config,
             blocking_task_guard: BlockingTaskGuard::new(proof_permits),
             raw_tx_sender,
         }
     }
+
+    /// Sets the maximum size in bytes of the proof nodes in an `eth_getProof` response.
+    pub const fn with_max_proof_response_size(mut self, max_proof_response_size: usize) -> Self {
+        self.max_proof_response_size = max_proof_response_size;
+        self
+    }
 }
 
 impl<Provider, Pool, Network, EvmConfig> EthApiInner<Provider, Pool, Network, EvmConfig>
@@ -423,6 +442,12 @@ where
         &self.fee_history_cache
     }
 
+    /// Returns a handle to the index of recent canonical block hashes.
+    #[inline]
+    pub const fn canonical_hash_index(&self) -> &CanonicalHashIndex {
+        &self.canonical_hash_index
+    }
+
     /// Returns a handle to the signers.
     #[inline]
     pub const fn signers(
@@ -449,6 +474,12 @@ where
         self.eth_proof_window
     }
 
+    /// The maximum size in bytes of the proof nodes in an `eth_getProof` response.
+    #[inline]
+    pub const fn max_proof_response_size(&self) -> usize {
+        self.max_proof_response_size
+    }
+
     /// Returns reference to [`BlockingTaskGuard`].
     #[inline]
     pub const fn blocking_task_guard(&self) -> &BlockingTaskGuard {
@@ -1,7 +1,7 @@
 //! `eth_` `Filter` RPC handler implementation
 
 use alloy_consensus::BlockHeader;
-use alloy_primitives::TxHash;
+use alloy_primitives::{TxHash, B256};
 use alloy_rpc_types_eth::{
     BlockNumHash, Filter, FilterBlockOption, FilterChanges, FilterId, Log,
     PendingTransactionFilterKind,
@@ -19,19 +19,21 @@ use reth_rpc_eth_types::{
     logs_utils::{self, append_matching_block_logs, ProviderOrBlock},
     EthApiError, EthFilterConfig, EthStateCache, EthSubscriptionIdProvider,
 };
-use reth_rpc_server_types::{result::rpc_error_with_code, ToRpcResult};
+use reth_rpc_server_types::{result::rpc_error_with_code, ParallelJsonArray, ToRpcResult};
 use reth_storage_api::{
     BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, HeaderProvider, ProviderBlock,
-    ProviderReceipt, TransactionsProvider,
+    ProviderReceipt, ReceiptProvider, TransactionsProvider,
 };
 use reth_tasks::TaskSpawner;
 use reth_transaction_pool::{NewSubpoolTransactionStream, PoolTransaction, TransactionPool};
+use serde::{Deserialize, Serialize};
 use std::{
-    collections::HashMap,
+    collections::{HashMap, VecDeque},
     fmt,
     future::Future,
     iter::StepBy,
     ops::RangeInclusive,
+    path::{Path, PathBuf},
     sync::Arc,
     time::{Duration, Instant},
 };
@@ -39,7 +41,7 @@ use tokio::{
     sync::{mpsc::Receiver, oneshot, Mutex},
     time::MissedTickBehavior,
 };
-use tracing::{error, trace};
+use tracing::{debug, error, trace, warn};
 
 impl<Eth> EngineEthFilter for EthFilter<Eth>
 where
@@ -89,6 +91,10 @@ where
     ///
     /// This also spawns a task that periodically clears stale filters.
     ///
+    /// If a [`EthFilterConfig::persistence_path`] is configured, the log and block filters
+    /// persisted to it are restored and the installed filters are persisted whenever filters are
+    /// installed, uninstalled or cleared.
+    ///
     /// # Create a new instance with [`EthApi`](crate::EthApi)
     ///
     /// ```no_run
@@ -108,16 +114,30 @@ where
     /// let filter = EthFilter::new(eth_api, Default::default(), TokioTaskExecutor::default().boxed());
     /// ```
     pub fn new(eth_api: Eth, config: EthFilterConfig, task_spawner: Box<dyn TaskSpawner>) -> Self {
-        let EthFilterConfig { max_blocks_per_filter, max_logs_per_response, stale_filter_ttl } =
-            config;
+        let EthFilterConfig {
+            max_blocks_per_filter,
+            max_logs_per_response,
+            stale_filter_ttl,
+            serialization_memory_budget,
+            max_blocks_per_poll,
+            reorg_window,
+            persistence_path,
+        } = config;
+        let active_filters =
+            persistence_path.as_deref().map(ActiveFilters::load).unwrap_or_else(ActiveFilters::new);
         let inner = EthFilterInner {
             eth_api,
-            active_filters: ActiveFilters::new(),
+            active_filters,
             id_provider: Arc::new(EthSubscriptionIdProvider::default()),
             max_headers_range: MAX_HEADERS_RANGE,
             task_spawner,
             stale_filter_ttl,
             query_limits: QueryLimits { max_blocks_per_filter, max_logs_per_response },
+            serialization_memory_budget,
+            max_blocks_per_poll,
+            reorg_window,
+            persistence: persistence_path
+                .map(|path| FilterPersistence { path, lock: Mutex::new(()) }),
         };
 
         let eth_filter = Self { inner: Arc::new(inner) };
@@ -154,6 +174,8 @@ where
 
     /// Clears all filters that have not been polled for longer than the configured
     /// `stale_filter_ttl` at the given instant.
+    ///
+    /// This also persists the remaining filters, if persistence is enabled.
     pub async fn clear_stale_filters(&self, now: Instant) {
         trace!(target: "rpc::eth", "clear stale filters");
         self.active_filters().inner.lock().await.retain(|id, filter| {
@@ -164,7 +186,8 @@ where
             }
 
             is_valid
-        })
+        });
+        self.inner.persist_filters().await;
     }
 }
 
@@ -192,35 +215,53 @@ where
 
         // start_block is the block from which we should start fetching changes, the next block from
         // the last time changes were polled, in other words the best block at last poll + 1
-        let (start_block, kind) = {
+        let (start_block, end_block, reorged, kind) = {
             let mut filters = self.inner.active_filters.inner.lock().await;
-            let filter = filters.get_mut(&id).ok_or(EthFilterError::FilterNotFound(id))?;
+            let filter =
+                filters.get_mut(&id).ok_or_else(|| EthFilterError::FilterNotFound(id.clone()))?;
+
+            // blocks returned by previous polls that are no longer canonical, the filter is
+            // rewound to the first of them
+            let reorged = self.inner.unwind_reorged_blocks(filter)?;
 
-            if filter.block > best_number {
+            if filter.block > best_number && reorged.is_empty() {
                 // no new blocks since the last poll
                 return Ok(FilterChanges::Empty)
             }
 
             // update filter
-            // we fetch all changes from [filter.block..best_block], so we advance the filter's
-            // block to `best_block +1`, the next from which we should start fetching changes again
-            let mut block = best_number + 1;
-            std::mem::swap(&mut filter.block, &mut block);
+            // we fetch all changes from [filter.block..end_block], so we advance the filter's
+            // block to `end_block + 1`, the next from which we should start fetching changes
+            // again. Unless the blocks per poll are limited, the end block is the best block.
+            let end_block = self.inner.max_blocks_per_poll.map_or(best_number, |max| {
+                best_number.min(filter.block.saturating_add(max.saturating_sub(1)))
+            });
+            let block = std::mem::replace(&mut filter.block, end_block + 1);
             filter.last_poll_timestamp = Instant::now();
 
-            (block, filter.kind.clone())
+            (block, end_block, reorged, filter.kind.clone())
         };
 
         match kind {
             FilterKind::PendingTransaction(filter) => Ok(filter.drain().await),
             FilterKind::Block => {
                 // Note: we need to fetch the block hashes from inclusive range
-                // [start_block..best_block]
-                let end_block = best_number + 1;
-                let block_hashes =
-                    self.provider().canonical_hashes_range(start_block, end_block).map_err(
-                        |_| EthApiError::HeaderRangeNotFound(start_block.into(), end_block.into()),
-                    )?;
+                // [start_block..end_block]
+                let block_hashes = if start_block <= end_block {
+                    self.inner.canonical_hashes_range(start_block, end_block + 1)?
+                } else {
+                    Vec::new()
+                };
+
+                let tracked_from = self.inner.tracked_from(start_block, end_block);
+                let tracked = block_hashes
+                    .iter()
+                    .zip(start_block..)
+                    .filter(|(_, number)| *number >= tracked_from)
+                    .map(|(hash, number)| TrackedBlock::new(BlockNumHash::new(number, *hash)))
+                    .collect();
+                self.inner.track_blocks(&id, tracked).await;
+
                 Ok(FilterChanges::Hashes(block_hashes))
             }
             FilterKind::Log(filter) => {
@@ -243,17 +284,53 @@ where
                         (start_block, best_number)
                     }
                 };
-                let logs = self
-                    .inner
-                    .clone()
-                    .get_logs_in_block_range(
-                        *filter,
-                        from_block_number,
-                        to_block_number,
-                        self.inner.query_limits,
-                    )
-                    .await?;
-                Ok(FilterChanges::Logs(logs))
+
+                let logs = if start_block <= end_block {
+                    self.inner
+                        .clone()
+                        .get_logs_in_block_range(
+                            *filter,
+                            from_block_number,
+                            to_block_number.min(end_block),
+                            self.inner.query_limits,
+                        )
+                        .await?
+                } else {
+                    Vec::new()
+                };
+
+                let tracked_from = self.inner.tracked_from(start_block, end_block);
+                if tracked_from <= end_block {
+                    let mut tracked = self
+                        .inner
+                        .canonical_hashes_range(tracked_from, end_block + 1)?
+                        .into_iter()
+                        .zip(tracked_from..)
+                        .map(|(hash, number)| TrackedBlock::new(BlockNumHash::new(number, hash)))
+                        .collect::<Vec<_>>();
+                    for log in &logs {
+                        let Some(block) = log
+                            .block_number
+                            .and_then(|number| number.checked_sub(tracked_from))
+                            .and_then(|idx| tracked.get_mut(idx as usize))
+                        else {
+                            continue
+                        };
+                        // track the block the log was returned for
+                        if let Some(hash) = log.block_hash {
+                            block.num_hash.hash = hash;
+                        }
+                        block.logs.push(log.clone());
+                    }
+                    self.inner.track_blocks(&id, tracked).await;
+                }
+
+                // logs of reorged blocks are returned again, marked as removed
+                let removed = reorged.into_iter().flat_map(|block| block.logs).map(|mut log| {
+                    log.removed = true;
+                    log
+                });
+                Ok(FilterChanges::Logs(removed.chain(logs).collect()))
             }
         }
     }
@@ -359,29 +436,30 @@ where
     /// Returns an error if no matching log filter exists.
     ///
     /// Handler for `eth_getFilterLogs`
-    async fn filter_logs(&self, id: FilterId) -> RpcResult<Vec<Log>> {
+    async fn filter_logs(&self, id: FilterId) -> RpcResult<ParallelJsonArray<Log>> {
         trace!(target: "rpc::eth", "Serving eth_getFilterLogs");
-        Ok(Self::filter_logs(self, id).await?)
+        let logs = Self::filter_logs(self, id).await?;
+        Ok(ParallelJsonArray::with_memory_budget(logs, self.inner.serialization_memory_budget))
     }
 
     /// Handler for `eth_uninstallFilter`
     async fn uninstall_filter(&self, id: FilterId) -> RpcResult<bool> {
         trace!(target: "rpc::eth", "Serving eth_uninstallFilter");
-        let mut filters = self.inner.active_filters.inner.lock().await;
-        if filters.remove(&id).is_some() {
+        let removed = self.inner.active_filters.inner.lock().await.remove(&id).is_some();
+        if removed {
             trace!(target: "rpc::eth::filter", ?id, "uninstalled filter");
-            Ok(true)
-        } else {
-            Ok(false)
+            self.inner.persist_filters().await;
         }
+        Ok(removed)
     }
 
     /// Returns logs matching given filter object.
     ///
     /// Handler for `eth_getLogs`
-    async fn logs(&self, filter: Filter) -> RpcResult<Vec<Log>> {
+    async fn logs(&self, filter: Filter) -> RpcResult<ParallelJsonArray<Log>> {
         trace!(target: "rpc::eth", "Serving eth_getLogs");
-        Ok(self.logs_for_filter(filter, self.inner.query_limits).await?)
+        let logs = self.logs_for_filter(filter, self.inner.query_limits).await?;
+        Ok(ParallelJsonArray::with_memory_budget(logs, self.inner.serialization_memory_budget))
     }
 }
 
@@ -411,6 +489,42 @@ struct EthFilterInner<Eth: EthApiTypes> {
     task_spawner: Box<dyn TaskSpawner>,
     /// Duration since the last filter poll, after which the filter is considered stale
     stale_filter_ttl: Duration,
+    /// Maximum number of bytes buffered while serializing large log responses
+    serialization_memory_budget: usize,
+    /// Maximum number of new blocks processed per filter poll
+    max_blocks_per_poll: Option<u64>,
+    /// Number of most recently polled blocks tracked per filter to detect reorgs
+    reorg_window: u64,
+    /// Persists the installed filters, if enabled
+    persistence: Option<FilterPersistence>,
+}
+
+impl<Eth: EthApiTypes> EthFilterInner<Eth> {
+    /// Persists the installed log and block filters, if persistence is enabled.
+    async fn persist_filters(&self) {
+        let Some(persistence) = &self.persistence else { return };
+
+        // serialize writes, so that the last snapshot is written last
+        let _guard = persistence.lock.lock().await;
+        let filters = self.active_filters.persisted().await;
+
+        // the file is written on the blocking pool to not stall the rpc task
+        let (tx, rx) = oneshot::channel();
+        let path = persistence.path.clone();
+        self.task_spawner.spawn_blocking(Box::pin(async move {
+            let res = reth_fs_util::atomic_write_file(&path, |file| {
+                serde_json::to_writer(file, &filters)
+            })
+            .map(|()| filters.len());
+            let _ = tx.send(res);
+        }));
+
+        match rx.await {
+            Ok(Ok(filters)) => trace!(target: "rpc::eth::filter", filters, "persisted filters"),
+            Ok(Err(err)) => warn!(target: "rpc::eth::filter", %err, "failed to persist filters"),
+            Err(_) => warn!(target: "rpc::eth::filter", "filter persistence task dropped"),
+        }
+    }
 }
 
 impl<Eth> EthFilterInner<Eth>
@@ -505,18 +619,116 @@ where
             jsonrpsee_types::SubscriptionId::Num(n) => FilterId::Num(n),
             jsonrpsee_types::SubscriptionId::Str(s) => FilterId::Str(s.into_owned()),
         };
-        let mut filters = self.active_filters.inner.lock().await;
-        filters.insert(
+        self.active_filters.inner.lock().await.insert(
             id.clone(),
             ActiveFilter {
                 block: last_poll_block_number,
                 last_poll_timestamp: Instant::now(),
                 kind,
+                tracked: VecDeque::new(),
             },
         );
+        self.persist_filters().await;
         Ok(id)
     }
 
+    /// Returns the canonical head of the provider, which the canonical hash index must be in sync
+    /// with to be used.
+    fn canonical_head(&self) -> Result<BlockNumHash, EthFilterError> {
+        let info = self.provider().chain_info()?;
+        Ok(BlockNumHash::new(info.best_number, info.best_hash))
+    }
+
+    /// Returns the canonical hash of the block with the given number, if any.
+    fn canonical_hash(&self, number: u64) -> Result<Option<B256>, EthFilterError> {
+        let indexed = match self.eth_api.canonical_hash_index() {
+            Some(index) => index.hash(number, self.canonical_head()?),
+            None => None,
+        };
+        match indexed {
+            Some(hash) => Ok(Some(hash)),
+            None => Ok(self.provider().block_hash(number)?),
+        }
+    }
+
+    /// Returns `true` if the given block is still part of the canonical chain.
+    fn is_canonical(&self, block: BlockNumHash) -> Result<bool, EthFilterError> {
+        if let Some(index) = self.eth_api.canonical_hash_index() {
+            let head = self.canonical_head()?;
+            if let Some(is_canonical) = head
+                .number
+                .checked_sub(block.number)
+                .and_then(|depth| index.is_canonical_ancestor(&block.hash, depth, head))
+            {
+                return Ok(is_canonical)
+            }
+        }
+        Ok(self.provider().block_hash(block.number)? == Some(block.hash))
+    }
+
+    /// Returns the canonical hashes of the blocks in the range `[start..end)`.
+    fn canonical_hashes_range(&self, start: u64, end: u64) -> Result<Vec<B256>, EthFilterError> {
+        let indexed = match self.eth_api.canonical_hash_index() {
+            Some(index) => index.canonical_hashes_range(start..end, self.canonical_head()?),
+            None => None,
+        };
+        match indexed {
+            Some(block_hashes) => Ok(block_hashes),
+            None => self
+                .provider()
+                .canonical_hashes_range(start, end)
+                .map_err(|_| EthApiError::HeaderRangeNotFound(start.into(), end.into()).into()),
+        }
+    }
+
+    /// Returns the first block of the inclusive range `[start_block..end_block]` returned by a
+    /// poll that is tracked to detect reorgs.
+    ///
+    /// Returns a block after `end_block` if reorg tracking is disabled.
+    fn tracked_from(&self, start_block: u64, end_block: u64) -> u64 {
+        if self.reorg_window == 0 {
+            return end_block + 1
+        }
+        start_block.max((end_block + 1).saturating_sub(self.reorg_window))
+    }
+
+    /// Removes the tracked blocks of the filter that are no longer canonical and rewinds the
+    /// filter to the first of them, so that the next changes start at the new canonical chain.
+    ///
+    /// Returns the removed blocks, most recent first.
+    fn unwind_reorged_blocks(
+        &self,
+        filter: &mut ActiveFilter<RpcTransaction<Eth::NetworkTypes>>,
+    ) -> Result<Vec<TrackedBlock>, EthFilterError> {
+        let mut reorged = Vec::new();
+        while let Some(block) = filter.tracked.back() {
+            if self.is_canonical(block.num_hash)? {
+                break
+            }
+            reorged.extend(filter.tracked.pop_back());
+        }
+
+        if let Some(first) = reorged.last() {
+            trace!(target: "rpc::eth::filter", block = first.num_hash.number, reorged = reorged.len(), "rewinding reorged filter");
+            filter.block = first.num_hash.number;
+        }
+        Ok(reorged)
+    }
+
+    /// Tracks the blocks returned by a poll of the filter with the given id, keeping at most the
+    /// `reorg_window` most recent blocks.
+    async fn track_blocks(&self, id: &FilterId, blocks: Vec<TrackedBlock>) {
+        if blocks.is_empty() {
+            return
+        }
+
+        let mut filters = self.active_filters.inner.lock().await;
+        let Some(filter) = filters.get_mut(id) else { return };
+        filter.tracked.extend(blocks);
+        let excess = filter.tracked.len().saturating_sub(self.reorg_window as usize);
+        filter.tracked.drain(..excess);
+    }
+
     /// Returns all logs in the given _inclusive_ range that match the filter
     ///
     /// Returns an error if:
@@ -559,6 +771,9 @@ where
     /// ranges and utilizes the rpc cache for optimistically fetching receipts and blocks.
     /// This function is considered blocking and should thus be spawned on a blocking task.
     ///
+    /// If the optional log index is maintained, block ranges it covers are only scanned for blocks
+    /// that may contain matching logs.
+    ///
     /// Returns an error if:
     ///  - underlying database error
     async fn get_logs_in_block_range_inner(
@@ -570,24 +785,37 @@ where
     ) -> Result<Vec<Log>, EthFilterError> {
         let mut all_logs = Vec::new();
 
+        // consult the log index, if available, to skip blocks without matching logs
+        let indexed = self.provider().indexed_log_blocks(
+            from_block..=to_block,
+            &filter.address.iter().copied().collect::<Vec<_>>(),
+            &filter
+                .topics
+                .iter()
+                .map(|topics| topics.iter().copied().collect())
+                .collect::<Vec<_>>(),
+        )?;
+
         // loop over the range of new blocks and check logs if the filter matches the log's bloom
         // filter
         for (from, to) in
             BlockRangeInclusiveIter::new(from_block..=to_block, self.max_headers_range)
         {
+            if indexed.as_ref().is_some_and(|indexed| !indexed.may_contain_any(from..=to)) {
+                continue
+            }
+
             let headers = self.provider().headers_range(from..=to)?;
-            for (idx, header) in headers
-                .iter()
-                .enumerate()
-                .filter(|(_, header)| filter.matches_bloom(header.logs_bloom()))
-            {
+            for (idx, header) in headers.iter().enumerate().filter(|(_, header)| {
+                indexed.as_ref().is_none_or(|indexed| indexed.may_contain(header.number())) &&
+                    filter.matches_bloom(header.logs_bloom())
+            }) {
                 // these are consecutive headers, so we can use the parent hash of the next
                 // block to get the current header's hash
                 let block_hash = match headers.get(idx + 1) {
                     Some(child) => child.parent_hash(),
                     None => self
-                        .provider()
-                        .block_hash(header.number())?
+                        .canonical_hash(header.number())?
                         .ok_or_else(|| ProviderError::HeaderNotFound(header.number().into()))?,
                 };
 
@@ -638,6 +866,61 @@ impl<T> ActiveFilters<T> {
     pub fn new() -> Self {
         Self { inner: Arc::new(Mutex::new(HashMap::default())) }
     }
+
+    /// Returns the filters persisted to the given file.
+    ///
+    /// Returns an empty instance if the file does not exist or can't be read.
+    fn load(path: &Path) -> Self {
+        if !path.exists() {
+            return Self::new()
+        }
+
+        let persisted = match reth_fs_util::read_json_file::<Vec<PersistedFilter>>(path) {
+            Ok(persisted) => persisted,
+            Err(err) => {
+                warn!(target: "rpc::eth::filter", %err, "failed to load persisted filters");
+                return Self::new()
+            }
+        };
+
+        // restored filters are considered polled at startup
+        let now = Instant::now();
+        let filters = persisted
+            .into_iter()
+            .map(|PersistedFilter { id, block, filter }| {
+                let kind = filter.map_or(FilterKind::Block, FilterKind::Log);
+                let filter = ActiveFilter {
+                    block,
+                    last_poll_timestamp: now,
+                    kind,
+                    tracked: VecDeque::new(),
+                };
+                (id, filter)
+            })
+            .collect::<HashMap<_, _>>();
+        debug!(target: "rpc::eth::filter", ?path, filters = filters.len(), "loaded persisted filters");
+
+        Self { inner: Arc::new(Mutex::new(filters)) }
+    }
+
+    /// Returns all log and block filters in their persisted form.
+    ///
+    /// Pending transaction filters can't be restored and are skipped.
+    async fn persisted(&self) -> Vec<PersistedFilter> {
+        self.inner
+            .lock()
+            .await
+            .iter()
+            .filter_map(|(id, filter)| {
+                let log_filter = match &filter.kind {
+                    FilterKind::Log(filter) => Some(filter.clone()),
+                    FilterKind::Block => None,
+                    FilterKind::PendingTransaction(_) => return None,
+                };
+                Some(PersistedFilter { id: id.clone(), block: filter.block, filter: log_filter })
+            })
+            .collect()
+    }
 }
 
 /// An installed filter
@@ -649,6 +932,46 @@ struct ActiveFilter<T> {
     last_poll_timestamp: Instant,
     /// What kind of filter it is.
     kind: FilterKind<T>,
+    /// The most recent blocks returned by polls of the filter, oldest first.
+    ///
+    /// Used to detect reorgs of blocks the filter already returned.
+    tracked: VecDeque<TrackedBlock>,
+}
+
+/// A block returned by a poll of a filter.
+#[derive(Debug)]
+struct TrackedBlock {
+    /// Number and hash of the block.
+    num_hash: BlockNumHash,
+    /// The logs of the block returned by the filter.
+    logs: Vec<Log>,
+}
+
+impl TrackedBlock {
+    /// Creates a new tracked block without logs.
+    const fn new(num_hash: BlockNumHash) -> Self {
+        Self { num_hash, logs: Vec::new() }
+    }
+}
+
+/// A log or block filter in the form it is persisted across restarts.
+#[derive(Debug, Serialize, Deserialize)]
+struct PersistedFilter {
+    /// Identifier of the filter.
+    id: FilterId,
+    /// The block from which the next poll returns changes.
+    block: u64,
+    /// The log filter, or `None` for a block filter.
+    filter: Option<Box<Filter>>,
+}
+
+/// Persists the installed filters to a file.
+#[derive(Debug)]
+struct FilterPersistence {
+    /// The file the filters are persisted to.
+    path: PathBuf,
+    /// Serializes writes to the file.
+    lock: Mutex<()>,
 }
 
 /// A receiver for pending transactions that returns all new transactions since the last poll.
@@ -844,8 +1167,14 @@ impl From<ProviderError> for EthFilterError {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::EthApi;
     use rand::Rng;
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_network_api::noop::NoopNetwork;
+    use reth_provider::test_utils::NoopProvider;
+    use reth_tasks::TokioTaskExecutor;
     use reth_testing_utils::generators;
+    use reth_transaction_pool::test_utils::testing_pool;
 
     #[test]
     fn test_block_range_iter() {
@@ -868,4 +1197,70 @@ mod tests {
 
         assert_eq!(end, *range.end());
     }
+
+    #[tokio::test]
+    async fn test_persisted_filters_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("eth-filters.json");
+        assert!(ActiveFilters::<()>::load(&path).inner.lock().await.is_empty());
+
+        let log_filter = Filter::new().from_block(10u64);
+        let persisted = vec![
+            PersistedFilter {
+                id: FilterId::Str("0x1".to_string()),
+                block: 5,
+                filter: Some(Box::new(log_filter.clone())),
+            },
+            PersistedFilter { id: FilterId::Str("0x2".to_string()), block: 7, filter: None },
+        ];
+        reth_fs_util::write_json_file(&path, &persisted).unwrap();
+
+        let filters = ActiveFilters::<()>::load(&path);
+        let mut restored = filters.persisted().await;
+        restored.sort_by_key(|filter| filter.block);
+        assert_eq!(restored.len(), 2);
+        assert_eq!(restored[0].id, FilterId::Str("0x1".to_string()));
+        assert_eq!(restored[0].filter.as_deref(), Some(&log_filter));
+        assert_eq!(restored[1].block, 7);
+        assert!(restored[1].filter.is_none());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_persists_installed_filters() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("eth-filters.json");
+        let new_filter = || {
+            let eth_api = EthApi::builder(
+                NoopProvider::default(),
+                testing_pool(),
+                NoopNetwork::default(),
+                EthEvmConfig::mainnet(),
+            )
+            .build();
+            EthFilter::new(
+                eth_api,
+                EthFilterConfig::default().persistence_path(&path),
+                TokioTaskExecutor::default().boxed(),
+            )
+        };
+        let persisted_ids = || {
+            reth_fs_util::read_json_file::<Vec<PersistedFilter>>(&path)
+                .unwrap()
+                .into_iter()
+                .map(|filter| filter.id)
+                .collect::<Vec<_>>()
+        };
+
+        let filter = new_filter();
+        let id = filter.new_block_filter().await.unwrap();
+        filter.new_pending_transaction_filter(None).await.unwrap();
+        assert_eq!(persisted_ids(), vec![id.clone()]);
+
+        // a restarted instance restores the filter
+        let restarted = new_filter();
+        assert!(restarted.active_filters().inner.lock().await.contains_key(&id));
+
+        assert!(filter.uninstall_filter(id).await.unwrap());
+        assert!(persisted_ids().is_empty());
+    }
 }
@@ -19,6 +19,10 @@ where
     fn max_proof_window(&self) -> u64 {
         self.inner.eth_proof_window()
     }
+
+    fn max_proof_response_size(&self) -> usize {
+        self.inner.max_proof_response_size()
+    }
 }
 
 impl<Provider, Pool, Network, EvmConfig> LoadState for EthApi<Provider, Pool, Network, EvmConfig>
@@ -38,20 +42,33 @@ mod tests {
     use super::*;
     use alloy_consensus::Header;
     use alloy_eips::eip1559::ETHEREUM_BLOCK_GAS_LIMIT_30M;
-    use alloy_primitives::{Address, StorageKey, StorageValue, U256};
+    use alloy_genesis::{Genesis, GenesisAccount};
+    use alloy_primitives::{Address, StorageKey, StorageValue, B256, U256};
+    use alloy_rpc_types_eth::EIP1186AccountProofResponse;
+    use reth_chainspec::ChainSpec;
+    use reth_db_common::init::init_genesis;
     use reth_evm_ethereum::EthEvmConfig;
     use reth_network_api::noop::NoopNetwork;
-    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider, NoopProvider};
+    use reth_provider::{
+        providers::BlockchainProvider,
+        test_utils::{
+            create_test_provider_factory_with_chain_spec, ExtendedAccount, MockEthProvider,
+            MockNodeTypesWithDB, NoopProvider,
+        },
+    };
     use reth_rpc_eth_api::helpers::EthState;
     use reth_rpc_eth_types::{
-        EthStateCache, FeeHistoryCache, FeeHistoryCacheConfig, GasPriceOracle,
+        EthApiError, EthStateCache, FeeHistoryCache, FeeHistoryCacheConfig, GasPriceOracle,
     };
     use reth_rpc_server_types::constants::{
         DEFAULT_ETH_PROOF_WINDOW, DEFAULT_MAX_SIMULATE_BLOCKS, DEFAULT_PROOF_PERMITS,
     };
     use reth_tasks::pool::BlockingTaskPool;
     use reth_transaction_pool::test_utils::{testing_pool, TestPool};
-    use std::collections::HashMap;
+    use std::{
+        collections::{BTreeMap, HashMap},
+        sync::Arc,
+    };
 
     fn noop_eth_api() -> EthApi<NoopProvider, TestPool, NoopNetwork, EthEvmConfig> {
         let pool = testing_pool();
@@ -100,6 +117,72 @@ mod tests {
         )
     }
 
+    /// Returns an [`EthApi`] over a database whose genesis allocates `slots` storage slots to
+    /// `address`.
+    fn proof_eth_api(
+        address: Address,
+        slots: u64,
+        max_proof_response_size: usize,
+    ) -> EthApi<BlockchainProvider<MockNodeTypesWithDB>, TestPool, NoopNetwork, EthEvmConfig> {
+        let storage =
+            (1..=slots).map(|slot| (B256::from(U256::from(slot)), B256::with_last_byte(1)));
+        let genesis = Genesis::default().extend_accounts([(
+            address,
+            GenesisAccount::default().with_storage(Some(BTreeMap::from_iter(storage))),
+        )]);
+        let chain_spec = Arc::new(ChainSpec::from(genesis));
+
+        let factory = create_test_provider_factory_with_chain_spec(chain_spec.clone());
+        init_genesis(&factory).unwrap();
+        let provider = BlockchainProvider::new(factory).unwrap();
+
+        EthApi::builder(
+            provider,
+            testing_pool(),
+            NoopNetwork::default(),
+            EthEvmConfig::new(chain_spec),
+        )
+        .max_proof_response_size(max_proof_response_size)
+        .build()
+    }
+
+    /// Returns the size in bytes of the proof nodes in the given response.
+    fn proof_nodes_size(proof: &EIP1186AccountProofResponse) -> usize {
+        proof.account_proof.iter().map(|node| node.len()).sum::<usize>() +
+            proof
+                .storage_proof
+                .iter()
+                .flat_map(|proof| &proof.proof)
+                .map(|node| node.len())
+                .sum::<usize>()
+    }
+
+    #[tokio::test]
+    async fn test_get_proof_max_response_size() {
+        let address = Address::random();
+        // Enough keys to split the proof into parallel chunks, and few enough to prove them in a
+        // single task.
+        for num_keys in [100, 4] {
+            let keys =
+                (1..=num_keys).map(|slot| B256::from(U256::from(slot)).into()).collect::<Vec<_>>();
+
+            let eth_api = proof_eth_api(address, 100, usize::MAX);
+            let proof = eth_api.get_proof(address, keys.clone(), None).unwrap().await.unwrap();
+            assert_eq!(proof.storage_proof.len(), num_keys as usize);
+            let size = proof_nodes_size(&proof);
+
+            let eth_api = proof_eth_api(address, 100, size);
+            assert!(eth_api.get_proof(address, keys.clone(), None).unwrap().await.is_ok());
+
+            let eth_api = proof_eth_api(address, 100, size - 1);
+            let err = eth_api.get_proof(address, keys, None).unwrap().await.unwrap_err();
+            assert!(
+                matches!(err, EthApiError::ExceedsMaxProofResponseSize { limit, .. } if limit == size - 1),
+                "{err:?}"
+            );
+        }
+    }
+
     #[tokio::test]
     async fn test_storage() {
         // === Noop ===
@@ -56,12 +56,21 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloy_consensus::{Header, SignableTransaction, TxLegacy, TxType};
     use alloy_eips::eip1559::ETHEREUM_BLOCK_GAS_LIMIT_30M;
-    use alloy_primitives::{hex_literal::hex, Bytes};
+    use alloy_primitives::{hex_literal::hex, Address, Bytes, Signature};
     use reth_chainspec::ChainSpecProvider;
+    use reth_db_common::init::init_genesis;
+    use reth_ethereum_primitives::{Block, BlockBody, Receipt, TransactionSigned};
     use reth_evm_ethereum::EthEvmConfig;
     use reth_network_api::noop::NoopNetwork;
-    use reth_provider::test_utils::NoopProvider;
+    use reth_primitives_traits::{RecoveredBlock, SignedTransaction};
+    use reth_provider::{
+        providers::BlockchainProvider,
+        test_utils::{create_test_provider_factory, NoopProvider},
+        ExecutionOutcome, OriginalValuesKnown, StateWriter, StaticFileProviderFactory,
+        StaticFileSegment, StorageLocation,
+    };
     use reth_rpc_eth_api::helpers::EthTransactions;
     use reth_rpc_eth_types::{
         EthStateCache, FeeHistoryCache, FeeHistoryCacheConfig, GasPriceOracle,
@@ -126,4 +135,87 @@ mod tests {
         assert!(pool.get(&tx_1_result).is_some(), "tx1 not found in the pool");
         assert!(pool.get(&tx_2_result).is_some(), "tx2 not found in the pool");
     }
+
+    #[tokio::test]
+    async fn load_transactions_and_receipts() {
+        let factory = create_test_provider_factory();
+        init_genesis(&factory).unwrap();
+
+        let transactions = (0..3)
+            .map(|nonce| {
+                TransactionSigned::from(
+                    TxLegacy { nonce, ..Default::default() }
+                        .into_signed(Signature::test_signature()),
+                )
+            })
+            .collect::<Vec<_>>();
+        let receipts = [1, 2, 1]
+            .map(|count| Receipt {
+                tx_type: TxType::Legacy,
+                success: true,
+                cumulative_gas_used: count * 21_000,
+                logs: Vec::new(),
+            })
+            .to_vec();
+
+        // block 1 has the first two transactions, block 2 the last one
+        let provider_rw = factory.provider_rw().unwrap();
+        let mut parent_hash = factory.chain_spec().genesis_hash();
+        let mut blocks = Vec::new();
+        for (number, transactions) in [(1, &transactions[..2]), (2, &transactions[2..])] {
+            let block = Block {
+                header: Header { parent_hash, number, ..Default::default() },
+                body: BlockBody { transactions: transactions.to_vec(), ..Default::default() },
+            };
+            let block =
+                RecoveredBlock::new_unhashed(block, vec![Address::ZERO; transactions.len()]);
+            parent_hash = block.hash();
+            provider_rw.insert_historical_block(block.clone()).unwrap();
+            blocks.push(block);
+        }
+        provider_rw
+            .static_file_provider()
+            .latest_writer(StaticFileSegment::Headers)
+            .unwrap()
+            .commit()
+            .unwrap();
+        let outcome = ExecutionOutcome::new(
+            Default::default(),
+            vec![receipts[..2].to_vec(), receipts[2..].to_vec()],
+            1,
+            Vec::new(),
+        );
+        provider_rw
+            .write_state(&outcome, OriginalValuesKnown::Yes, StorageLocation::Database)
+            .unwrap();
+        provider_rw.commit().unwrap();
+
+        let eth_api = EthApi::builder(
+            BlockchainProvider::new(factory).unwrap(),
+            testing_pool(),
+            NoopNetwork::default(),
+            EthEvmConfig::mainnet(),
+        )
+        .build();
+
+        // results are in the order of the hashes, regardless of the order they're read in
+        let hashes = vec![
+            *transactions[2].tx_hash(),
+            B256::ZERO,
+            *transactions[1].tx_hash(),
+            *transactions[0].tx_hash(),
+        ];
+        let loaded = eth_api.load_transactions_and_receipts(hashes.clone()).await.unwrap();
+        assert_eq!(loaded.len(), 4);
+        assert!(loaded[1].is_none());
+        for (position, tx, block, index) in [(0, 2, 1, 0), (2, 1, 0, 1), (3, 0, 0, 0)] {
+            let (transaction, meta, receipt) = loaded[position].clone().unwrap();
+            assert_eq!(transaction, transactions[tx]);
+            assert_eq!(receipt, receipts[tx]);
+            assert_eq!(meta.tx_hash, hashes[position]);
+            assert_eq!(meta.index, index);
+            assert_eq!(meta.block_hash, blocks[block].hash());
+            assert_eq!(meta.block_number, blocks[block].header().number);
+        }
+    }
 }
@@ -11,7 +11,7 @@ use futures::StreamExt;
 use jsonrpsee::{
     server::SubscriptionMessage, types::ErrorObject, PendingSubscriptionSink, SubscriptionSink,
 };
-use reth_chain_state::CanonStateSubscriptions;
+use reth_chain_state::{CanonStateSubscriptions, CompactCanonStateNotificationStream};
 use reth_network_api::NetworkInfo;
 use reth_primitives_traits::NodePrimitives;
 use reth_rpc_eth_api::{
@@ -50,9 +50,21 @@ impl<Eth> EthPubSub<Eth> {
 
     /// Creates a new, shareable instance.
     pub fn with_spawner(eth_api: Eth, subscription_task_spawner: Box<dyn TaskSpawner>) -> Self {
-        let inner = EthPubSubInner { eth_api, subscription_task_spawner };
+        let inner = EthPubSubInner { eth_api, subscription_task_spawner, max_compacted_blocks: 0 };
         Self { inner: Arc::new(inner) }
     }
+
+    /// Batches bursts of consecutive new blocks up to `max_blocks` blocks before they're processed
+    /// by `newHeads` and `logs` subscriptions. Disabled if zero.
+    ///
+    /// See [`CompactCanonStateNotificationStream`].
+    pub fn with_max_compacted_blocks(mut self, max_blocks: usize) -> Self
+    where
+        Eth: Clone,
+    {
+        Arc::make_mut(&mut self.inner).max_compacted_blocks = max_blocks;
+        self
+    }
 }
 
 impl<N: NodePrimitives, Eth> EthPubSub<Eth>
@@ -300,6 +312,8 @@ struct EthPubSubInner<EthApi> {
     eth_api: EthApi,
     /// The type that's used to spawn subscription tasks.
     subscription_task_spawner: Box<dyn TaskSpawner>,
+    /// Maximum number of consecutive new blocks that are processed at once.
+    max_compacted_blocks: usize,
 }
 
 // == impl EthPubSubInner ===
@@ -350,29 +364,37 @@ impl<N: NodePrimitives, Eth> EthPubSubInner<Eth>
 where
     Eth: RpcNodeCore<Provider: CanonStateSubscriptions<Primitives = N>>,
 {
+    /// Returns a stream of the canonical state notifications, batching bursts of consecutive new
+    /// blocks if configured.
+    fn canonical_state_stream(&self) -> CompactCanonStateNotificationStream<N> {
+        self.eth_api.provider().compact_canonical_state_stream(self.max_compacted_blocks)
+    }
+
     /// Returns a stream that yields all new RPC blocks.
     fn new_headers_stream(&self) -> impl Stream<Item = Header<N::BlockHeader>> {
-        self.eth_api.provider().canonical_state_stream().flat_map(|new_chain| {
-            let headers = new_chain
-                .committed()
-                .blocks_iter()
-                .map(|block| {
+        self.canonical_state_stream().flat_map(|batch| {
+            let mut headers = Vec::new();
+            for new_chain in batch.iter() {
+                headers.extend(new_chain.committed().blocks_iter().map(|block| {
                     Header::from_consensus(
                         block.clone_sealed_header().into(),
                         None,
                         Some(U256::from(block.rlp_length())),
                     )
-                })
-                .collect::<Vec<_>>();
+                }));
+            }
             futures::stream::iter(headers)
         })
     }
 
     /// Returns a stream that yields all logs that match the given filter.
     fn log_stream(&self, filter: Filter) -> impl Stream<Item = Log> {
-        BroadcastStream::new(self.eth_api.provider().subscribe_to_canonical_state())
-            .map(move |canon_state| {
-                canon_state.expect("new block subscription never ends").block_receipts()
+        self.canonical_state_stream()
+            .map(|batch| {
+                batch
+                    .iter()
+                    .flat_map(|canon_state| canon_state.block_receipts())
+                    .collect::<Vec<_>>()
             })
             .flat_map(futures::stream::iter)
             .flat_map(move |(block_receipts, removed)| {
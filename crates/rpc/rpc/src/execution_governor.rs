@@ -0,0 +1,37 @@
+use jsonrpsee::core::RpcResult;
+use reth_config::config::ExecutionGovernorConfig;
+use reth_rpc_api::AdminExecutionGovernorApiServer;
+use reth_tasks::governor::ThroughputGovernor;
+
+/// `admin` execution governor API implementation.
+///
+/// Adjusts the throughput limits of the execution stage at runtime.
+#[derive(Debug)]
+pub struct ExecutionGovernorApi {
+    /// The governor of the execution stage.
+    governor: ThroughputGovernor,
+}
+
+impl ExecutionGovernorApi {
+    /// Creates a new instance of `ExecutionGovernorApi`.
+    pub const fn new(governor: ThroughputGovernor) -> Self {
+        Self { governor }
+    }
+}
+
+impl AdminExecutionGovernorApiServer for ExecutionGovernorApi {
+    /// Handler for `admin_executionGovernor`
+    fn execution_governor(&self) -> RpcResult<ExecutionGovernorConfig> {
+        Ok(ExecutionGovernorConfig {
+            max_gas_per_second: self.governor.max_gas_per_second(),
+            max_write_bytes_per_second: self.governor.max_write_bytes_per_second(),
+        })
+    }
+
+    /// Handler for `admin_setExecutionGovernor`
+    fn set_execution_governor(&self, config: ExecutionGovernorConfig) -> RpcResult<bool> {
+        self.governor.set_max_gas_per_second(config.max_gas_per_second);
+        self.governor.set_max_write_bytes_per_second(config.max_write_bytes_per_second);
+        Ok(true)
+    }
+}
@@ -38,29 +38,45 @@ use jsonwebtoken as _;
 use pin_project as _;
 use tower as _;
 
+mod abi_registry;
 mod admin;
 mod debug;
 mod engine;
 pub mod eth;
+mod execution_governor;
+mod log_filter;
 mod miner;
 mod net;
+mod node_config;
 mod otterscan;
+mod payload_timings;
 mod reth;
+mod reth_call;
 mod rpc;
+mod safe_mode;
+mod task_dump;
 mod trace;
 mod txpool;
 mod validation;
 mod web3;
 
+pub use abi_registry::{AbiRegistry, AbiRegistryError};
 pub use admin::AdminApi;
 pub use debug::DebugApi;
 pub use engine::{EngineApi, EngineEthApi};
 pub use eth::{helpers::SyncListener, EthApi, EthApiBuilder, EthBundle, EthFilter, EthPubSub};
+pub use execution_governor::ExecutionGovernorApi;
+pub use log_filter::LogFilterApi;
 pub use miner::MinerApi;
 pub use net::NetApi;
+pub use node_config::{redact_args, NodeConfigApi};
 pub use otterscan::OtterscanApi;
+pub use payload_timings::PayloadTimingsApi;
 pub use reth::RethApi;
+pub use reth_call::RethCall;
 pub use rpc::RPCApi;
+pub use safe_mode::SafeModeApi;
+pub use task_dump::TaskDumpApi;
 pub use trace::TraceApi;
 pub use txpool::TxPoolApi;
 pub use validation::{ValidationApi, ValidationApiConfig};
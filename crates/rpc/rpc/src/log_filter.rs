@@ -0,0 +1,34 @@
+use async_trait::async_trait;
+use jsonrpsee::core::RpcResult;
+use reth_rpc_api::DebugLogFilterApiServer;
+use reth_rpc_server_types::result::{internal_rpc_err, invalid_params_rpc_err};
+use std::time::Duration;
+
+/// `debug` log filter API implementation.
+///
+/// Changes the log filters of the global subscriber installed by `reth_tracing`.
+#[derive(Debug, Default)]
+pub struct LogFilterApi;
+
+impl LogFilterApi {
+    /// Creates a new instance of `LogFilterApi`.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl DebugLogFilterApiServer for LogFilterApi {
+    /// Handler for `debug_setLogFilter`
+    async fn set_log_filter(&self, filter: String, duration_secs: Option<u64>) -> RpcResult<()> {
+        let handle = reth_tracing::log_filter_handle()
+            .ok_or_else(|| internal_rpc_err("log filters can't be reloaded"))?;
+        let res = match duration_secs {
+            Some(secs) => handle.set_filter_for(&filter, Duration::from_secs(secs)),
+            None => handle.set_filter(&filter),
+        };
+        res.map_err(|err| invalid_params_rpc_err(err.to_string()))?;
+        tracing::info!(target: "rpc::debug", %filter, ?duration_secs, "Changed log filter");
+        Ok(())
+    }
+}
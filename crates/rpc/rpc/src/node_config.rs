@@ -0,0 +1,207 @@
+use async_trait::async_trait;
+use jsonrpsee::core::RpcResult;
+use reth_rpc_api::{NodeConfigApiServer, NodeConfigInfo};
+
+/// Placeholder for redacted values.
+const REDACTED: &str = "<redacted>";
+
+/// Arguments whose values are reported as is.
+///
+/// The values of all other arguments are redacted, so newly added arguments that take secrets,
+/// URLs with credentials or commands can't leak through the config API.
+const PUBLIC_ARGS: &[&str] = &[
+    "--chain",
+    "--config",
+    "--datadir",
+    "--datadir.static-files",
+    "--instance",
+    "--addr",
+    "--port",
+    "--discovery.addr",
+    "--discovery.port",
+    "--discovery.v5.addr",
+    "--discovery.v5.port",
+    "--max-outbound-peers",
+    "--max-inbound-peers",
+    "--http.addr",
+    "--http.port",
+    "--http.api",
+    "--ws.addr",
+    "--ws.port",
+    "--ws.api",
+    "--authrpc.addr",
+    "--authrpc.port",
+    "--rpc.gascap",
+    "--rpc.max-connections",
+    "--rpc.max-request-size",
+    "--rpc.max-response-size",
+    "--rpc.max-subscriptions-per-connection",
+    "--rpc.max-tracing-requests",
+    "--rpc.max-blocks-per-filter",
+    "--rpc.max-logs-per-response",
+    "--db.log-level",
+    "--db.max-size",
+    "--db.growth-step",
+    "--dev.block-time",
+    "--dev.block-max-transactions",
+    "--log.stdout.format",
+    "--log.stdout.filter",
+    "--log.file.format",
+    "--log.file.filter",
+    "--log.file.max-size",
+    "--log.file.max-files",
+    "--color",
+    "--verbosity",
+];
+
+/// Argument namespaces that only take numeric or enum values, whose values are reported as is.
+const PUBLIC_ARG_PREFIXES: &[&str] =
+    &["--builder.", "--engine.", "--gpo.", "--prune.", "--rpc-cache.", "--txpool."];
+
+/// `reth` node config API implementation.
+///
+/// Serves the effective configuration the node was started with.
+#[derive(Debug, Clone)]
+pub struct NodeConfigApi {
+    /// The configuration determined at startup.
+    info: NodeConfigInfo,
+}
+
+impl NodeConfigApi {
+    /// Creates a new instance of `NodeConfigApi`.
+    pub const fn new(info: NodeConfigInfo) -> Self {
+        Self { info }
+    }
+}
+
+#[async_trait]
+impl NodeConfigApiServer for NodeConfigApi {
+    /// Handler for `reth_nodeConfig`
+    async fn node_config(&self) -> RpcResult<NodeConfigInfo> {
+        Ok(self.info.clone())
+    }
+}
+
+/// Redacts the values of all arguments that are not known to be public.
+///
+/// Flags without values and the values of [`PUBLIC_ARGS`] and [`PUBLIC_ARG_PREFIXES`] are kept,
+/// both in the `--name=value` and the `--name value` form. Positional arguments before the first
+/// flag, i.e. the binary and subcommands, are kept as well.
+pub fn redact_args(args: impl IntoIterator<Item = String>) -> Vec<String> {
+    let is_public = |name: &str| {
+        PUBLIC_ARGS.contains(&name) ||
+            PUBLIC_ARG_PREFIXES.iter().any(|prefix| name.starts_with(prefix))
+    };
+
+    let mut redacted = Vec::new();
+    let mut redact_next = false;
+    for arg in args {
+        if !arg.starts_with('-') {
+            if std::mem::take(&mut redact_next) {
+                redacted.push(REDACTED.to_string());
+            } else {
+                redacted.push(arg);
+            }
+            continue
+        }
+
+        if let Some((name, _)) = arg.split_once('=') {
+            redact_next = false;
+            if is_public(name) {
+                redacted.push(arg);
+            } else {
+                redacted.push(format!("{name}={REDACTED}"));
+            }
+        } else {
+            // the next argument is this flag's value, unless it is another flag
+            redact_next = !is_public(&arg);
+            redacted.push(arg);
+        }
+    }
+    redacted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redacts_secret_args() {
+        let args = [
+            "reth",
+            "node",
+            "--rpc.jwtsecret",
+            "0xdeadbeef",
+            "--p2p-secret-key=/keys/p2p",
+            "--ethstats",
+            "node:hunter2@stats.example.com:3000",
+            "--http",
+            "--http.port",
+            "8545",
+        ]
+        .map(String::from);
+
+        assert_eq!(
+            redact_args(args),
+            [
+                "reth",
+                "node",
+                "--rpc.jwtsecret",
+                "<redacted>",
+                "--p2p-secret-key=<redacted>",
+                "--ethstats",
+                "<redacted>",
+                "--http",
+                "--http.port",
+                "8545",
+            ]
+        );
+    }
+
+    #[test]
+    fn redacts_args_not_known_to_be_public() {
+        let args = [
+            "reth",
+            "node",
+            "--rollup.sequencer-headers",
+            "x-api-key: abc",
+            "--webhook.url=https://hooks.example.com/T000/B000/XXXX",
+            "--webhook.secret-file",
+            "/run/secrets/webhook",
+            "--db.static-files-encryption-key-cmd",
+            "pass show reth",
+            "--rpc.forwarder=https://mainnet.example.com/v3/apikey",
+            "--datadir=/data/reth",
+            "--txpool.pending-max-count",
+            "10000",
+            "-vvv",
+        ]
+        .map(String::from);
+
+        assert_eq!(
+            redact_args(args),
+            [
+                "reth",
+                "node",
+                "--rollup.sequencer-headers",
+                "<redacted>",
+                "--webhook.url=<redacted>",
+                "--webhook.secret-file",
+                "<redacted>",
+                "--db.static-files-encryption-key-cmd",
+                "<redacted>",
+                "--rpc.forwarder=<redacted>",
+                "--datadir=/data/reth",
+                "--txpool.pending-max-count",
+                "10000",
+                "-vvv",
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_flags_without_values() {
+        let args = ["reth", "node", "--dev", "--http", "--chain", "sepolia"].map(String::from);
+        assert_eq!(redact_args(args.clone()), args);
+    }
+}
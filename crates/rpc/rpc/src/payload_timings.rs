@@ -0,0 +1,33 @@
+use alloy_rpc_types_engine::PayloadId;
+use async_trait::async_trait;
+use jsonrpsee::core::RpcResult;
+use reth_payload_builder::PayloadBuilderHandle;
+use reth_payload_primitives::{PayloadTimings, PayloadTypes};
+use reth_rpc_api::DebugPayloadTimingsApiServer;
+
+/// `debug` payload timings API implementation.
+///
+/// Serves the timing breakdown of payloads built by the node's payload builder.
+#[derive(Debug)]
+pub struct PayloadTimingsApi<T: PayloadTypes> {
+    /// Handle to the payload builder service.
+    payload_builder: PayloadBuilderHandle<T>,
+}
+
+impl<T: PayloadTypes> PayloadTimingsApi<T> {
+    /// Creates a new instance of `PayloadTimingsApi`.
+    pub const fn new(payload_builder: PayloadBuilderHandle<T>) -> Self {
+        Self { payload_builder }
+    }
+}
+
+#[async_trait]
+impl<T: PayloadTypes> DebugPayloadTimingsApiServer for PayloadTimingsApi<T> {
+    /// Handler for `debug_getPayloadTimings`
+    async fn get_payload_timings(
+        &self,
+        payload_id: PayloadId,
+    ) -> RpcResult<Option<PayloadTimings>> {
+        Ok(self.payload_builder.payload_timings(payload_id).await)
+    }
+}
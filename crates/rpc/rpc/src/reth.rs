@@ -1,26 +1,58 @@
-use std::{collections::HashMap, future::Future, sync::Arc};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    future::Future,
+    sync::Arc,
+};
 
+use alloy_consensus::{
+    constants::{EMPTY_WITHDRAWALS, GWEI_TO_WEI},
+    BlockHeader, Transaction, TxReceipt,
+};
 use alloy_eips::BlockId;
-use alloy_primitives::{Address, U256};
+use alloy_primitives::{keccak256, Address, Bytes, U256};
+use alloy_rlp::Encodable;
+use alloy_rpc_types_eth::Log;
 use async_trait::async_trait;
 use futures::StreamExt;
 use jsonrpsee::{core::RpcResult, PendingSubscriptionSink, SubscriptionMessage, SubscriptionSink};
 use jsonrpsee_types::ErrorObject;
 use reth_chain_state::{CanonStateNotificationStream, CanonStateSubscriptions};
-use reth_errors::RethResult;
-use reth_primitives_traits::NodePrimitives;
-use reth_rpc_api::RethApiServer;
+use reth_chainspec::{
+    ChainSpecProvider, EthereumHardfork, EthereumHardforks, ForkCondition, Hardfork,
+};
+use reth_errors::{ProviderError, RethResult};
+use reth_primitives_traits::{Block, BlockBody, Bytecode, NodePrimitives, SignedTransaction};
+use reth_rpc_api::{
+    AccountHistory, BlockIncome, DecodedLog, GasConsumer, IncomeRange, OmmerEraStats, ProofBundle,
+    ProofBundleTarget, RethApiServer, StateSize, TopGasConsumers, ValidatorIncome,
+};
 use reth_rpc_eth_types::{EthApiError, EthResult};
-use reth_rpc_server_types::result::internal_rpc_err;
-use reth_storage_api::{BlockReaderIdExt, ChangeSetReader, StateProviderFactory};
+use reth_rpc_server_types::{
+    constants::{
+        DEFAULT_ETH_PROOF_WINDOW, MAX_ACCOUNT_HISTORY_PAGE_SIZE, MAX_OMMER_STATS_BLOCK_RANGE,
+        MAX_PROOF_BUNDLE_TARGETS, MAX_TOP_GAS_CONSUMERS, MAX_VALIDATOR_INCOME_ADDRESSES,
+        MAX_VALIDATOR_INCOME_BLOCK_RANGE,
+    },
+    result::internal_rpc_err,
+};
+use reth_storage_api::{
+    BlockReaderIdExt, ChangeSetReader, GasUsageReader, RelativeBlockNumberOrTag,
+    StateProviderFactory, StateSizeReader,
+};
 use reth_tasks::TaskSpawner;
+use reth_trie_common::{MultiProofTargets, TrieInput};
+use revm::bytecode::Bytecode as RevmBytecode;
 use tokio::sync::oneshot;
 
+use crate::AbiRegistry;
+
 /// `reth` API implementation.
 ///
 /// This type provides the functionality for handling `reth` prototype RPC requests.
 pub struct RethApi<Provider> {
     inner: Arc<RethApiInner<Provider>>,
+    /// The maximum number of blocks into the past for which proofs can be generated.
+    eth_proof_window: u64,
 }
 
 // === impl RethApi ===
@@ -33,14 +65,36 @@ impl<Provider> RethApi<Provider> {
 
     /// Create a new instance of the [`RethApi`]
     pub fn new(provider: Provider, task_spawner: Box<dyn TaskSpawner>) -> Self {
-        let inner = Arc::new(RethApiInner { provider, task_spawner });
-        Self { inner }
+        Self::with_abi_registry(provider, task_spawner, None)
+    }
+
+    /// Create a new instance of the [`RethApi`] that decodes logs with the given [`AbiRegistry`].
+    pub fn with_abi_registry(
+        provider: Provider,
+        task_spawner: Box<dyn TaskSpawner>,
+        abi_registry: Option<Arc<AbiRegistry>>,
+    ) -> Self {
+        let inner = Arc::new(RethApiInner { provider, task_spawner, abi_registry });
+        Self { inner, eth_proof_window: DEFAULT_ETH_PROOF_WINDOW }
+    }
+
+    /// Configures the maximum number of blocks into the past for which proofs can be generated,
+    /// see also `eth_getProof`.
+    pub const fn with_eth_proof_window(mut self, eth_proof_window: u64) -> Self {
+        self.eth_proof_window = eth_proof_window;
+        self
     }
 }
 
 impl<Provider> RethApi<Provider>
 where
-    Provider: BlockReaderIdExt + ChangeSetReader + StateProviderFactory + 'static,
+    Provider: BlockReaderIdExt
+        + ChangeSetReader
+        + StateProviderFactory
+        + StateSizeReader
+        + GasUsageReader
+        + ChainSpecProvider<ChainSpec: EthereumHardforks>
+        + 'static,
 {
     /// Executes the future on a new blocking task.
     async fn on_blocking_task<C, F, R>(&self, c: C) -> EthResult<R>
@@ -88,6 +142,528 @@ where
         )?;
         Ok(hash_map)
     }
+
+    /// Returns all logs of the block, decoded with the configured [`AbiRegistry`].
+    pub async fn decoded_logs(&self, block_id: BlockId) -> EthResult<Option<Vec<DecodedLog>>> {
+        let Some(abi_registry) = self.inner.abi_registry.clone() else {
+            return Err(EthApiError::Unsupported("no ABI registry configured"))
+        };
+        self.on_blocking_task(|this| async move { this.try_decoded_logs(block_id, &abi_registry) })
+            .await
+    }
+
+    fn try_decoded_logs(
+        &self,
+        block_id: BlockId,
+        abi_registry: &AbiRegistry,
+    ) -> EthResult<Option<Vec<DecodedLog>>> {
+        let Some(header) = self.provider().sealed_header_by_id(block_id)? else { return Ok(None) };
+        let (Some(block), Some(receipts)) = (
+            self.provider().block(header.hash().into())?,
+            self.provider().receipts_by_block(header.hash().into())?,
+        ) else {
+            return Ok(None)
+        };
+
+        let mut logs = Vec::new();
+        for (tx_index, (tx, receipt)) in
+            block.body().transactions().iter().zip(receipts.iter()).enumerate()
+        {
+            for log in receipt.logs() {
+                logs.push(DecodedLog {
+                    decoded: abi_registry.decode(&log.data),
+                    log: Log {
+                        inner: log.clone(),
+                        block_hash: Some(header.hash()),
+                        block_number: Some(header.number()),
+                        block_timestamp: Some(header.timestamp()),
+                        transaction_hash: Some(*tx.tx_hash()),
+                        transaction_index: Some(tx_index as u64),
+                        log_index: Some(logs.len() as u64),
+                        removed: false,
+                    },
+                });
+            }
+        }
+        Ok(Some(logs))
+    }
+
+    /// Returns the header of the block together with the proofs of all requested accounts and
+    /// storage slots.
+    pub async fn proof_bundle(
+        &self,
+        block_id: BlockId,
+        targets: Vec<ProofBundleTarget>,
+    ) -> EthResult<ProofBundle> {
+        let target_count =
+            targets.iter().map(|target| target.storage_keys.len().max(1)).sum::<usize>();
+        if target_count > MAX_PROOF_BUNDLE_TARGETS {
+            return Err(EthApiError::InvalidParams(format!(
+                "too many proof targets: {target_count} > {MAX_PROOF_BUNDLE_TARGETS}"
+            )))
+        }
+
+        self.on_blocking_task(|this| async move { this.try_proof_bundle(block_id, targets) }).await
+    }
+
+    fn try_proof_bundle(
+        &self,
+        block_id: BlockId,
+        targets: Vec<ProofBundleTarget>,
+    ) -> EthResult<ProofBundle> {
+        let Some(header) = self.provider().sealed_header_by_id(block_id)? else {
+            return Err(EthApiError::HeaderNotFound(block_id))
+        };
+
+        // Same as `eth_getProof`, proofs are only served for recent blocks.
+        let best_number = self.provider().best_block_number()?;
+        if best_number.saturating_sub(header.number()) > self.eth_proof_window {
+            return Err(EthApiError::ExceedsMaxProofWindow)
+        }
+
+        let state = self.provider().history_by_block_hash(header.hash())?;
+
+        // All targets are proven with a single multiproof so that shared trie nodes are only
+        // computed once.
+        let mut proof_targets = MultiProofTargets::with_capacity(targets.len());
+        for target in &targets {
+            proof_targets
+                .entry(keccak256(target.address))
+                .or_default()
+                .extend(target.storage_keys.iter().map(|key| keccak256(key.as_b256())));
+        }
+        let multiproof = state.multiproof(TrieInput::default(), proof_targets)?;
+
+        let mut account_proofs = Vec::with_capacity(targets.len());
+        for ProofBundleTarget { address, storage_keys } in targets {
+            let slots = storage_keys.iter().map(|key| key.as_b256()).collect::<Vec<_>>();
+            let proof = multiproof.account_proof(address, &slots).map_err(ProviderError::from)?;
+            account_proofs.push(proof.into_eip1186_response(storage_keys));
+        }
+
+        let mut encoded_header = Vec::with_capacity(header.length());
+        header.header().encode(&mut encoded_header);
+
+        Ok(ProofBundle {
+            block_hash: header.hash(),
+            block_number: header.number(),
+            state_root: header.state_root(),
+            header: encoded_header.into(),
+            account_proofs,
+        })
+    }
+
+    /// Returns ommer statistics for the given inclusive block range, grouped by hardfork era.
+    pub async fn ommer_stats(
+        &self,
+        from_block: RelativeBlockNumberOrTag,
+        to_block: RelativeBlockNumberOrTag,
+    ) -> EthResult<Vec<OmmerEraStats>> {
+        self.on_blocking_task(|this| async move { this.try_ommer_stats(from_block, to_block) })
+            .await
+    }
+
+    fn try_ommer_stats(
+        &self,
+        from_block: RelativeBlockNumberOrTag,
+        to_block: RelativeBlockNumberOrTag,
+    ) -> EthResult<Vec<OmmerEraStats>> {
+        let best_block = self.provider().best_block_number()?;
+        let (from, to) = (
+            self.resolve_block_number(from_block, best_block)?,
+            self.resolve_block_number(to_block, best_block)?,
+        );
+        if from > to {
+            return Err(EthApiError::InvalidBlockRange)
+        }
+        if to - from >= MAX_OMMER_STATS_BLOCK_RANGE {
+            return Err(EthApiError::InvalidParams(format!(
+                "block range too large, max is {MAX_OMMER_STATS_BLOCK_RANGE}"
+            )))
+        }
+
+        let chain_spec = self.provider().chain_spec();
+        let eras = ommer_eras(&*chain_spec);
+
+        let mut stats = Vec::<OmmerEraStats>::new();
+        for number in from..=to {
+            // there are no ommers after the merge, no need to hit the database
+            let ommers = if chain_spec.is_paris_active_at_block(number) {
+                0
+            } else {
+                self.provider()
+                    .ommers(number.into())?
+                    .map(|ommers| ommers.len() as u64)
+                    .unwrap_or_default()
+            };
+
+            let era = eras
+                .iter()
+                .rev()
+                .find(|(_, activation)| *activation <= number)
+                .map(|(name, _)| *name)
+                .unwrap_or(EthereumHardfork::Frontier.name());
+
+            let entry = match stats.last_mut() {
+                Some(entry) if entry.era == era => entry,
+                _ => {
+                    stats.push(OmmerEraStats {
+                        era: era.to_string(),
+                        from_block: number,
+                        to_block: number,
+                        blocks: 0,
+                        blocks_with_ommers: 0,
+                        ommers: 0,
+                        ommer_rate: 0.0,
+                    });
+                    stats.last_mut().expect("just pushed")
+                }
+            };
+            entry.to_block = number;
+            entry.blocks += 1;
+            entry.ommers += ommers;
+            if ommers > 0 {
+                entry.blocks_with_ommers += 1;
+            }
+        }
+
+        for entry in &mut stats {
+            entry.ommer_rate = entry.ommers as f64 / entry.blocks as f64;
+        }
+
+        Ok(stats)
+    }
+
+    /// Returns the priority fees and withdrawals credited to the given addresses in the given
+    /// block or timestamp range.
+    pub async fn validator_income(
+        &self,
+        range: IncomeRange,
+        addresses: Vec<Address>,
+    ) -> EthResult<ValidatorIncome> {
+        if addresses.len() > MAX_VALIDATOR_INCOME_ADDRESSES {
+            return Err(EthApiError::InvalidParams(format!(
+                "too many addresses: {} > {MAX_VALIDATOR_INCOME_ADDRESSES}",
+                addresses.len()
+            )))
+        }
+
+        self.on_blocking_task(|this| async move { this.try_validator_income(range, addresses) })
+            .await
+    }
+
+    fn try_validator_income(
+        &self,
+        range: IncomeRange,
+        addresses: Vec<Address>,
+    ) -> EthResult<ValidatorIncome> {
+        let best_block = self.provider().best_block_number()?;
+        let (from, to) = match range {
+            IncomeRange::Blocks { from_block, to_block } => (
+                self.resolve_block_number(from_block, best_block)?,
+                self.resolve_block_number(to_block, best_block)?,
+            ),
+            IncomeRange::Timestamps { from_timestamp, to_timestamp } => {
+                if from_timestamp > to_timestamp {
+                    return Err(EthApiError::InvalidBlockRange)
+                }
+                let from = self.first_block_at_or_after(from_timestamp, best_block)?;
+                let end =
+                    self.first_block_at_or_after(to_timestamp.saturating_add(1), best_block)?;
+                if from == end {
+                    return Err(EthApiError::InvalidParams(
+                        "no blocks in timestamp range".to_string(),
+                    ))
+                }
+                (from, end - 1)
+            }
+        };
+        if from > to {
+            return Err(EthApiError::InvalidBlockRange)
+        }
+        if to - from >= MAX_VALIDATOR_INCOME_BLOCK_RANGE {
+            return Err(EthApiError::InvalidParams(format!(
+                "block range too large, max is {MAX_VALIDATOR_INCOME_BLOCK_RANGE}"
+            )))
+        }
+
+        let addresses = addresses.into_iter().collect::<HashSet<_>>();
+        let headers = self.provider().sealed_headers_range(from..=to)?;
+        if headers.len() as u64 != to - from + 1 {
+            return Err(EthApiError::HeaderRangeNotFound(from.into(), to.into()))
+        }
+
+        let mut income = ValidatorIncome { from_block: from, to_block: to, ..Default::default() };
+        for header in headers {
+            let number = header.number();
+            let is_recipient = addresses.contains(&header.beneficiary());
+            // the body is only read for the priority fees of a recipient or for withdrawals
+            let has_withdrawals =
+                header.withdrawals_root().is_some_and(|root| root != EMPTY_WITHDRAWALS);
+            if !is_recipient && !has_withdrawals {
+                continue
+            }
+            let Some(block) = self.provider().block(number.into())? else {
+                return Err(EthApiError::HeaderNotFound(number.into()))
+            };
+
+            let mut withdrawals = BTreeMap::<Address, U256>::new();
+            for withdrawal in block.body().withdrawals().into_iter().flatten() {
+                if addresses.contains(&withdrawal.address) {
+                    *withdrawals.entry(withdrawal.address).or_default() +=
+                        U256::from(withdrawal.amount) * U256::from(GWEI_TO_WEI);
+                }
+            }
+
+            if !is_recipient && withdrawals.is_empty() {
+                continue
+            }
+
+            let priority_fees = if is_recipient {
+                self.priority_fees(number, header.base_fee_per_gas(), block.body().transactions())?
+            } else {
+                U256::ZERO
+            };
+
+            income.total_priority_fees += priority_fees;
+            income.total_withdrawals += withdrawals.values().copied().sum::<U256>();
+            income.blocks.push(BlockIncome {
+                block_number: number,
+                block_hash: header.hash(),
+                timestamp: header.timestamp(),
+                fee_recipient: header.beneficiary(),
+                priority_fees,
+                withdrawals,
+            });
+        }
+
+        Ok(income)
+    }
+
+    /// Returns the blocks in the given inclusive range in which the account changed.
+    pub async fn account_history(
+        &self,
+        address: Address,
+        from_block: RelativeBlockNumberOrTag,
+        to_block: RelativeBlockNumberOrTag,
+        limit: usize,
+    ) -> EthResult<AccountHistory> {
+        self.on_blocking_task(|this| async move {
+            this.try_account_history(address, from_block, to_block, limit)
+        })
+        .await
+    }
+
+    fn try_account_history(
+        &self,
+        address: Address,
+        from_block: RelativeBlockNumberOrTag,
+        to_block: RelativeBlockNumberOrTag,
+        limit: usize,
+    ) -> EthResult<AccountHistory> {
+        if limit == 0 || limit > MAX_ACCOUNT_HISTORY_PAGE_SIZE {
+            return Err(EthApiError::InvalidParams(format!(
+                "limit must be between 1 and {MAX_ACCOUNT_HISTORY_PAGE_SIZE}"
+            )))
+        }
+        let best_block = self.provider().best_block_number()?;
+        let (from, to) = (
+            self.resolve_block_number(from_block, best_block)?,
+            self.resolve_block_number(to_block, best_block)?,
+        );
+        if from > to {
+            return Err(EthApiError::InvalidBlockRange)
+        }
+
+        // one more block than requested tells whether there is a next page
+        let mut blocks = self.provider().account_change_blocks(address, from..=to, limit + 1)?;
+        let next_block = if blocks.len() > limit { blocks.pop() } else { None };
+        Ok(AccountHistory { blocks, next_block })
+    }
+
+    /// Returns the code of the account, with an EIP-7702 delegation resolved to the code of the
+    /// delegate.
+    pub async fn resolved_code(&self, address: Address, block_id: BlockId) -> EthResult<Bytes> {
+        self.on_blocking_task(|this| async move { this.try_resolved_code(address, block_id) }).await
+    }
+
+    fn try_resolved_code(&self, address: Address, block_id: BlockId) -> EthResult<Bytes> {
+        let state = self.provider().state_by_block_id(block_id)?;
+        let mut code = state.account_code(&address)?;
+        if let Some(delegate) = code.as_ref().and_then(delegation_address) {
+            code = state.account_code(&delegate)?;
+        }
+        Ok(code.unwrap_or_default().original_bytes())
+    }
+
+    /// Returns the address the account delegates to with an EIP-7702 delegation designator.
+    pub async fn delegation(
+        &self,
+        address: Address,
+        block_id: BlockId,
+    ) -> EthResult<Option<Address>> {
+        self.on_blocking_task(|this| async move { this.try_delegation(address, block_id) }).await
+    }
+
+    fn try_delegation(&self, address: Address, block_id: BlockId) -> EthResult<Option<Address>> {
+        let code = self.provider().state_by_block_id(block_id)?.account_code(&address)?;
+        Ok(code.as_ref().and_then(delegation_address))
+    }
+
+    /// Returns the state size statistics, if they are maintained.
+    pub async fn state_size(&self) -> EthResult<Option<StateSize>> {
+        self.on_blocking_task(|this| async move { this.try_state_size() }).await
+    }
+
+    fn try_state_size(&self) -> EthResult<Option<StateSize>> {
+        Ok(self.provider().state_size()?)
+    }
+
+    /// Returns the addresses whose transactions used the most gas in the given inclusive range, if
+    /// the gas usage index covers it.
+    pub async fn top_gas_consumers(
+        &self,
+        from_block: RelativeBlockNumberOrTag,
+        to_block: RelativeBlockNumberOrTag,
+        limit: usize,
+    ) -> EthResult<Option<TopGasConsumers>> {
+        self.on_blocking_task(|this| async move {
+            this.try_top_gas_consumers(from_block, to_block, limit)
+        })
+        .await
+    }
+
+    fn try_top_gas_consumers(
+        &self,
+        from_block: RelativeBlockNumberOrTag,
+        to_block: RelativeBlockNumberOrTag,
+        limit: usize,
+    ) -> EthResult<Option<TopGasConsumers>> {
+        if limit == 0 || limit > MAX_TOP_GAS_CONSUMERS {
+            return Err(EthApiError::InvalidParams(format!(
+                "limit must be between 1 and {MAX_TOP_GAS_CONSUMERS}"
+            )))
+        }
+        let best_block = self.provider().best_block_number()?;
+        let (from, to) = (
+            self.resolve_block_number(from_block, best_block)?,
+            self.resolve_block_number(to_block, best_block)?,
+        );
+        if from > to {
+            return Err(EthApiError::InvalidBlockRange)
+        }
+
+        Ok(self.provider().top_gas_consumers(from..=to, limit)?.map(|top| TopGasConsumers {
+            from_block: top.from_block,
+            to_block: top.to_block,
+            consumers: top
+                .consumers
+                .into_iter()
+                .map(|consumer| GasConsumer {
+                    address: consumer.address,
+                    gas_used: consumer.gas_used,
+                })
+                .collect(),
+        }))
+    }
+
+    /// Resolves the block to the number of an existing block, at most `best_block`.
+    fn resolve_block_number(
+        &self,
+        block: RelativeBlockNumberOrTag,
+        best_block: u64,
+    ) -> EthResult<u64> {
+        match self.provider().convert_relative_block_number(block)? {
+            Some(number) if number <= best_block => Ok(number),
+            _ if block.offset == 0 => Err(EthApiError::HeaderNotFound(block.base.into())),
+            _ => Err(EthApiError::InvalidParams(format!("block not found: {block}"))),
+        }
+    }
+
+    /// Returns the first block with a timestamp of at least `timestamp`, or `best_block + 1` if
+    /// there is none.
+    fn first_block_at_or_after(&self, timestamp: u64, best_block: u64) -> EthResult<u64> {
+        let (mut low, mut high) = (0, best_block + 1);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            let Some(header) = self.provider().header_by_number(mid)? else {
+                return Err(EthApiError::HeaderNotFound(mid.into()))
+            };
+            if header.timestamp() < timestamp {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        Ok(low)
+    }
+
+    /// Returns the priority fees paid by the transactions of the block to its fee recipient.
+    ///
+    /// Before London there is no base fee and the entire gas price is paid to the fee recipient.
+    fn priority_fees<T: Transaction>(
+        &self,
+        number: u64,
+        base_fee: Option<u64>,
+        transactions: &[T],
+    ) -> EthResult<U256> {
+        if transactions.is_empty() {
+            return Ok(U256::ZERO)
+        }
+        let Some(receipts) = self.provider().receipts_by_block(number.into())? else {
+            return Err(EthApiError::ReceiptsNotFound(number.into()))
+        };
+
+        let mut fees = U256::ZERO;
+        let mut cumulative_gas_used = 0;
+        for (tx, receipt) in transactions.iter().zip(&receipts) {
+            let gas_used = receipt.cumulative_gas_used() - cumulative_gas_used;
+            cumulative_gas_used = receipt.cumulative_gas_used();
+            let tip = tx.effective_tip_per_gas(base_fee.unwrap_or_default()).unwrap_or_default();
+            fees += U256::from(tip) * U256::from(gas_used);
+        }
+        Ok(fees)
+    }
+}
+
+/// Returns the delegate of the EIP-7702 delegation designator, if the code is one.
+fn delegation_address(code: &Bytecode) -> Option<Address> {
+    match &code.0 {
+        RevmBytecode::Eip7702(code) => Some(code.address()),
+        _ => None,
+    }
+}
+
+/// Returns the block activated hardforks up to and including the merge, together with their
+/// activation block, in activation order.
+fn ommer_eras(chain_spec: &impl EthereumHardforks) -> Vec<(&'static str, u64)> {
+    [
+        EthereumHardfork::Frontier,
+        EthereumHardfork::Homestead,
+        EthereumHardfork::Dao,
+        EthereumHardfork::Tangerine,
+        EthereumHardfork::SpuriousDragon,
+        EthereumHardfork::Byzantium,
+        EthereumHardfork::Constantinople,
+        EthereumHardfork::Petersburg,
+        EthereumHardfork::Istanbul,
+        EthereumHardfork::MuirGlacier,
+        EthereumHardfork::Berlin,
+        EthereumHardfork::London,
+        EthereumHardfork::ArrowGlacier,
+        EthereumHardfork::GrayGlacier,
+        EthereumHardfork::Paris,
+    ]
+    .into_iter()
+    .filter_map(|fork| {
+        let activation = match chain_spec.ethereum_fork_activation(fork) {
+            ForkCondition::Block(block) => block,
+            ForkCondition::TTD { activation_block_number, .. } => activation_block_number,
+            _ => return None,
+        };
+        Some((fork.name(), activation))
+    })
+    .collect()
 }
 
 #[async_trait]
@@ -96,6 +672,9 @@ where
     Provider: BlockReaderIdExt
         + ChangeSetReader
         + StateProviderFactory
+        + StateSizeReader
+        + GasUsageReader
+        + ChainSpecProvider<ChainSpec: EthereumHardforks>
         + CanonStateSubscriptions
         + 'static,
 {
@@ -107,6 +686,82 @@ where
         Ok(Self::balance_changes_in_block(self, block_id).await?)
     }
 
+    /// Handler for `reth_getProofBundle`
+    async fn reth_get_proof_bundle(
+        &self,
+        block_id: BlockId,
+        accounts: Vec<ProofBundleTarget>,
+    ) -> RpcResult<ProofBundle> {
+        Ok(Self::proof_bundle(self, block_id, accounts).await?)
+    }
+
+    /// Handler for `reth_getOmmerStats`
+    async fn reth_get_ommer_stats(
+        &self,
+        from_block: RelativeBlockNumberOrTag,
+        to_block: RelativeBlockNumberOrTag,
+    ) -> RpcResult<Vec<OmmerEraStats>> {
+        Ok(Self::ommer_stats(self, from_block, to_block).await?)
+    }
+
+    /// Handler for `reth_validatorIncome`
+    async fn reth_validator_income(
+        &self,
+        range: IncomeRange,
+        addresses: Vec<Address>,
+    ) -> RpcResult<ValidatorIncome> {
+        Ok(Self::validator_income(self, range, addresses).await?)
+    }
+
+    /// Handler for `reth_getAccountHistory`
+    async fn reth_get_account_history(
+        &self,
+        address: Address,
+        from_block: RelativeBlockNumberOrTag,
+        to_block: RelativeBlockNumberOrTag,
+        limit: usize,
+    ) -> RpcResult<AccountHistory> {
+        Ok(Self::account_history(self, address, from_block, to_block, limit).await?)
+    }
+
+    /// Handler for `reth_getDecodedLogs`
+    async fn reth_get_decoded_logs(&self, block_id: BlockId) -> RpcResult<Option<Vec<DecodedLog>>> {
+        Ok(Self::decoded_logs(self, block_id).await?)
+    }
+
+    /// Handler for `reth_getResolvedCode`
+    async fn reth_get_resolved_code(
+        &self,
+        address: Address,
+        block_id: Option<BlockId>,
+    ) -> RpcResult<Bytes> {
+        Ok(Self::resolved_code(self, address, block_id.unwrap_or_default()).await?)
+    }
+
+    /// Handler for `reth_getDelegation`
+    async fn reth_get_delegation(
+        &self,
+        address: Address,
+        block_id: Option<BlockId>,
+    ) -> RpcResult<Option<Address>> {
+        Ok(Self::delegation(self, address, block_id.unwrap_or_default()).await?)
+    }
+
+    /// Handler for `reth_stateSize`
+    async fn reth_state_size(&self) -> RpcResult<Option<StateSize>> {
+        Ok(Self::state_size(self).await?)
+    }
+
+    /// Handler for `reth_topGasConsumers`
+    async fn reth_top_gas_consumers(
+        &self,
+        from_block: RelativeBlockNumberOrTag,
+        to_block: RelativeBlockNumberOrTag,
+        limit: usize,
+    ) -> RpcResult<Option<TopGasConsumers>> {
+        Ok(Self::top_gas_consumers(self, from_block, to_block, limit).await?)
+    }
+
     /// Handler for `reth_subscribeChainNotifications`
     async fn reth_subscribe_chain_notifications(
         &self,
@@ -160,7 +815,7 @@ impl<Provider> std::fmt::Debug for RethApi<Provider> {
 
 impl<Provider> Clone for RethApi<Provider> {
     fn clone(&self) -> Self {
-        Self { inner: Arc::clone(&self.inner) }
+        Self { inner: Arc::clone(&self.inner), eth_proof_window: self.eth_proof_window }
     }
 }
 
@@ -169,4 +824,200 @@ struct RethApiInner<Provider> {
     provider: Provider,
     /// The type that can spawn tasks which would otherwise block.
     task_spawner: Box<dyn TaskSpawner>,
+    /// Known ABIs used to decode logs.
+    abi_registry: Option<Arc<AbiRegistry>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{BlockBody, Header, TxEip1559};
+    use alloy_eips::{
+        eip4895::{Withdrawal, Withdrawals},
+        eip7702::constants::EIP7702_DELEGATION_DESIGNATOR,
+    };
+    use alloy_genesis::{Genesis, GenesisAccount};
+    use alloy_primitives::{Signature, B256};
+    use reth_chainspec::ChainSpec;
+    use reth_db_common::init::init_genesis;
+    use reth_ethereum_primitives::{Block, Receipt, TransactionSigned};
+    use reth_provider::{
+        providers::BlockchainProvider,
+        test_utils::{
+            create_test_provider_factory_with_chain_spec, ExtendedAccount, MockEthProvider,
+        },
+    };
+    use reth_storage_api::{HeaderProvider, StateProofProvider};
+    use reth_tasks::TokioTaskExecutor;
+
+    #[tokio::test]
+    async fn resolves_delegations() {
+        let provider = MockEthProvider::default();
+        let (eoa, delegate, contract) =
+            (Address::with_last_byte(1), Address::with_last_byte(2), Address::with_last_byte(3));
+        let designator = Bytes::from([&EIP7702_DELEGATION_DESIGNATOR[..], &delegate[..]].concat());
+        let delegate_code = Bytes::from_static(&[0x60, 0x00]);
+        provider.add_account(eoa, ExtendedAccount::new(0, U256::ZERO).with_bytecode(designator));
+        provider.add_account(
+            delegate,
+            ExtendedAccount::new(0, U256::ZERO).with_bytecode(delegate_code.clone()),
+        );
+        provider.add_account(
+            contract,
+            ExtendedAccount::new(0, U256::ZERO).with_bytecode(Bytes::from_static(&[0x60, 0x01])),
+        );
+        let api = RethApi::new(provider, TokioTaskExecutor::default().boxed());
+
+        assert_eq!(api.reth_get_delegation(eoa, None).await.unwrap(), Some(delegate));
+        assert_eq!(api.reth_get_resolved_code(eoa, None).await.unwrap(), delegate_code);
+
+        // accounts without a delegation are returned as is
+        assert_eq!(api.reth_get_delegation(contract, None).await.unwrap(), None);
+        assert_eq!(
+            api.reth_get_resolved_code(contract, None).await.unwrap(),
+            Bytes::from_static(&[0x60, 0x01])
+        );
+        assert_eq!(api.reth_get_resolved_code(Address::ZERO, None).await.unwrap(), Bytes::new());
+    }
+
+    #[tokio::test]
+    async fn validator_income() {
+        let provider = MockEthProvider::default();
+        let validator = Address::with_last_byte(1);
+        let other = Address::with_last_byte(2);
+
+        // tip of 2 wei per gas at a base fee of 1
+        let tx = TransactionSigned::new_unhashed(
+            TxEip1559 { max_fee_per_gas: 3, max_priority_fee_per_gas: 2, ..Default::default() }
+                .into(),
+            Signature::test_signature(),
+        );
+        let withdrawal = |address, amount| Withdrawal { address, amount, ..Default::default() };
+        let blocks = [
+            // neither fee recipient nor withdrawals
+            Block {
+                header: Header { beneficiary: other, ..Default::default() },
+                body: BlockBody::default(),
+            },
+            // fee recipient
+            Block {
+                header: Header {
+                    number: 1,
+                    beneficiary: validator,
+                    base_fee_per_gas: Some(1),
+                    ..Default::default()
+                },
+                body: BlockBody { transactions: vec![tx], ..Default::default() },
+            },
+            // withdrawals to the validator and another account
+            Block {
+                header: Header {
+                    number: 2,
+                    beneficiary: other,
+                    withdrawals_root: Some(B256::with_last_byte(1)),
+                    ..Default::default()
+                },
+                body: BlockBody {
+                    withdrawals: Some(Withdrawals::new(vec![
+                        withdrawal(validator, 5),
+                        withdrawal(other, 7),
+                    ])),
+                    ..Default::default()
+                },
+            },
+        ];
+        for block in blocks {
+            provider.add_block(block.header.hash_slow(), block);
+        }
+        provider
+            .add_receipts(1, vec![Receipt { cumulative_gas_used: 21_000, ..Default::default() }]);
+        let api = RethApi::new(provider, TokioTaskExecutor::default().boxed());
+
+        let range = IncomeRange::Blocks { from_block: 0.into(), to_block: 2.into() };
+        let income = api.validator_income(range, vec![validator]).await.unwrap();
+        assert_eq!((income.from_block, income.to_block), (0, 2));
+        assert_eq!(income.total_priority_fees, U256::from(42_000));
+        assert_eq!(income.total_withdrawals, U256::from(5 * GWEI_TO_WEI));
+        assert_eq!(
+            income.blocks.iter().map(|block| block.block_number).collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert_eq!(
+            income.blocks[1].withdrawals,
+            BTreeMap::from([(validator, U256::from(5 * GWEI_TO_WEI))])
+        );
+
+        // ranges beyond the best block are rejected
+        let range = IncomeRange::Blocks { from_block: 0.into(), to_block: 3.into() };
+        assert!(api.validator_income(range, vec![validator]).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn proof_bundle() {
+        let (address, other) = (Address::with_last_byte(1), Address::with_last_byte(2));
+        let storage =
+            (1..=3u64).map(|slot| (B256::from(U256::from(slot)), B256::with_last_byte(1)));
+        let genesis = Genesis::default().extend_accounts([
+            (address, GenesisAccount::default().with_storage(Some(BTreeMap::from_iter(storage)))),
+            (other, GenesisAccount::default().with_balance(U256::from(10))),
+        ]);
+        let factory =
+            create_test_provider_factory_with_chain_spec(Arc::new(ChainSpec::from(genesis)));
+        init_genesis(&factory).unwrap();
+        let provider = BlockchainProvider::new(factory).unwrap();
+        let api = RethApi::new(provider.clone(), TokioTaskExecutor::default().boxed());
+
+        let storage_keys = vec![B256::from(U256::from(1)).into(), B256::from(U256::from(4)).into()];
+        let targets = vec![
+            ProofBundleTarget { address, storage_keys: storage_keys.clone() },
+            ProofBundleTarget { address: other, storage_keys: Vec::new() },
+        ];
+        let bundle = api.proof_bundle(BlockId::latest(), targets).await.unwrap();
+
+        let header = provider.sealed_header(0).unwrap().unwrap();
+        assert_eq!((bundle.block_hash, bundle.block_number), (header.hash(), 0));
+        assert_eq!(bundle.state_root, header.state_root());
+        assert_eq!(keccak256(&bundle.header), bundle.block_hash);
+
+        // every proof matches the single account proof and verifies against the state root
+        let state = provider.history_by_block_hash(header.hash()).unwrap();
+        for (proof, (address, keys)) in
+            bundle.account_proofs.into_iter().zip([(address, storage_keys), (other, Vec::new())])
+        {
+            let slots = keys.iter().map(|key| key.as_b256()).collect::<Vec<_>>();
+            let expected = state.proof(TrieInput::default(), address, &slots).unwrap();
+            expected.verify(header.state_root()).unwrap();
+            assert_eq!(proof, expected.into_eip1186_response(keys));
+        }
+    }
+
+    #[tokio::test]
+    async fn proof_bundle_limits() {
+        let provider = MockEthProvider::default();
+        for number in 0..=1 {
+            let block = Block {
+                header: Header { number, ..Default::default() },
+                body: BlockBody::default(),
+            };
+            provider.add_block(block.header.hash_slow(), block);
+        }
+        let api = RethApi::new(provider, TokioTaskExecutor::default().boxed());
+
+        // too many targets
+        let storage_keys = (0..=MAX_PROOF_BUNDLE_TARGETS as u64)
+            .map(|slot| B256::from(U256::from(slot)).into())
+            .collect();
+        let targets = vec![ProofBundleTarget { address: Address::ZERO, storage_keys }];
+        let err = api.proof_bundle(BlockId::latest(), targets).await.unwrap_err();
+        assert!(matches!(err, EthApiError::InvalidParams(_)), "{err:?}");
+
+        // blocks outside of the proof window
+        let targets = vec![ProofBundleTarget { address: Address::ZERO, storage_keys: Vec::new() }];
+        let err = api.proof_bundle(BlockId::number(0), targets.clone()).await.unwrap_err();
+        assert!(matches!(err, EthApiError::ExceedsMaxProofWindow), "{err:?}");
+        assert!(api.proof_bundle(BlockId::number(1), targets.clone()).await.is_ok());
+
+        let api = api.with_eth_proof_window(1);
+        assert!(api.proof_bundle(BlockId::number(0), targets).await.is_ok());
+    }
 }
@@ -0,0 +1,304 @@
+//! `reth_callMany` implementation.
+
+use alloy_eips::BlockId;
+use alloy_rpc_types_eth::{state::EvmOverrides, TransactionRequest};
+use async_trait::async_trait;
+use jsonrpsee::core::RpcResult;
+use reth_evm::EvmEnvFor;
+use reth_revm::{database::StateProviderDatabase, db::CacheDB};
+use reth_rpc_api::{CallManyOptions, CallManyResult, RethCallApiServer};
+use reth_rpc_eth_api::{
+    helpers::{Call, LoadPendingBlock, LoadState, SpawnBlocking},
+    FromEthApiError,
+};
+use reth_rpc_eth_types::{
+    cache::db::StateProviderTraitObjWrapper, error::ensure_success, EthApiError,
+};
+use reth_rpc_server_types::constants::MAX_CALL_MANY_CALLS;
+use revm::DatabaseCommit;
+use std::{
+    num::NonZeroUsize,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+/// `reth_callMany` implementation.
+///
+/// Executes a batch of calls against the state of a single block, so that the historical state is
+/// only resolved once instead of once per call.
+pub struct RethCall<Eth> {
+    inner: Arc<RethCallInner<Eth>>,
+}
+
+impl<Eth> RethCall<Eth> {
+    /// Create a new `RethCall` instance.
+    pub fn new(eth_api: Eth) -> Self {
+        Self { inner: Arc::new(RethCallInner { eth_api }) }
+    }
+
+    /// Access the underlying `Eth` API.
+    pub fn eth_api(&self) -> &Eth {
+        &self.inner.eth_api
+    }
+}
+
+impl<Eth> RethCall<Eth>
+where
+    Eth: Call + LoadPendingBlock + 'static,
+{
+    /// Executes all calls on top of the state of the given block.
+    ///
+    /// Unless the calls are declared independent, every call observes the state changes of the
+    /// previous calls, like the transactions of a block, and all calls share a single state
+    /// provider and its read cache. Independent calls are executed in parallel against the
+    /// unmodified state of the block.
+    ///
+    /// Calls that fail are reported in their result and don't abort the batch. The gas of all
+    /// calls is limited by the configured call gas limit.
+    pub async fn call_many(
+        &self,
+        block_id: BlockId,
+        calls: Vec<TransactionRequest>,
+        options: CallManyOptions,
+    ) -> Result<Vec<CallManyResult>, Eth::Error> {
+        if calls.is_empty() {
+            return Err(EthApiError::InvalidParams("calls are empty".to_string()).into_eth_err())
+        }
+        if calls.len() > MAX_CALL_MANY_CALLS {
+            return Err(EthApiError::InvalidParams(format!(
+                "too many calls: {} > {MAX_CALL_MANY_CALLS}",
+                calls.len()
+            ))
+            .into_eth_err())
+        }
+
+        let (evm_env, at) = self.eth_api().evm_env_at(block_id).await?;
+        let budget = Arc::new(GasBudget(AtomicU64::new(self.eth_api().call_gas_limit())));
+        let CallManyOptions { independent } = options;
+
+        if !independent {
+            return self
+                .eth_api()
+                .spawn_blocking_io(move |this| {
+                    execute_calls(&this, at, evm_env, calls, &budget, true)
+                })
+                .await
+        }
+
+        // the boxed state provider is not `Sync`, so every task gets its own provider
+        let parallelism = std::thread::available_parallelism().map_or(1, NonZeroUsize::get);
+        let chunk_size = calls.len().div_ceil(parallelism);
+        let tasks = calls.chunks(chunk_size).map(|chunk| {
+            let (calls, evm_env, budget) = (chunk.to_vec(), evm_env.clone(), budget.clone());
+            self.eth_api().spawn_blocking_io(move |this| {
+                execute_calls(&this, at, evm_env, calls, &budget, false)
+            })
+        });
+
+        Ok(futures::future::try_join_all(tasks).await?.into_iter().flatten().collect())
+    }
+}
+
+/// Executes the calls on top of the state of the given block, committing the state changes of
+/// every call if requested.
+fn execute_calls<Eth: Call>(
+    eth_api: &Eth,
+    at: BlockId,
+    evm_env: EvmEnvFor<Eth::Evm>,
+    calls: Vec<TransactionRequest>,
+    budget: &GasBudget,
+    commit: bool,
+) -> Result<Vec<CallManyResult>, Eth::Error> {
+    let state = eth_api.state_at_block_id(at)?;
+    let mut db = CacheDB::new(StateProviderDatabase::new(StateProviderTraitObjWrapper(&state)));
+    let block_gas_limit = evm_env.block_env.gas_limit.min(eth_api.call_gas_limit());
+
+    let mut results = Vec::with_capacity(calls.len());
+    for mut request in calls {
+        let gas_limit = budget.reserve(request.gas.unwrap_or(block_gas_limit));
+        if gas_limit == 0 {
+            results.push(CallManyResult::error("gas limit of the batch exhausted", 0));
+            continue
+        }
+        request.gas = Some(gas_limit);
+
+        let res = eth_api
+            .prepare_call_env(evm_env.clone(), request, &mut db, EvmOverrides::default())
+            .and_then(|(evm_env, tx_env)| eth_api.transact(&mut db, evm_env, tx_env));
+        let res = match res {
+            Ok(res) => res,
+            Err(err) => {
+                budget.refund(gas_limit);
+                results.push(CallManyResult::error(err, 0));
+                continue
+            }
+        };
+
+        let gas_used = res.result.gas_used();
+        budget.refund(gas_limit.saturating_sub(gas_used));
+        results.push(match ensure_success::<_, Eth::Error>(res.result) {
+            Ok(output) => CallManyResult { value: Some(output), error: None, gas_used },
+            Err(err) => CallManyResult::error(err, gas_used),
+        });
+
+        if commit {
+            db.commit(res.state);
+        }
+    }
+
+    Ok(results)
+}
+
+/// Gas that is left for the calls of a batch.
+#[derive(Debug)]
+struct GasBudget(AtomicU64);
+
+impl GasBudget {
+    /// Reserves up to the given amount of gas, returns the reserved gas.
+    fn reserve(&self, gas: u64) -> u64 {
+        let mut reserved = 0;
+        let _ = self.0.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |left| {
+            reserved = left.min(gas);
+            Some(left - reserved)
+        });
+        reserved
+    }
+
+    /// Returns unused gas to the budget.
+    fn refund(&self, gas: u64) {
+        self.0.fetch_add(gas, Ordering::Relaxed);
+    }
+}
+
+#[async_trait]
+impl<Eth> RethCallApiServer for RethCall<Eth>
+where
+    Eth: Call + LoadPendingBlock + 'static,
+{
+    /// Handler for `reth_callMany`
+    async fn reth_call_many(
+        &self,
+        block_id: BlockId,
+        calls: Vec<TransactionRequest>,
+        options: Option<CallManyOptions>,
+    ) -> RpcResult<Vec<CallManyResult>> {
+        Self::call_many(self, block_id, calls, options.unwrap_or_default())
+            .await
+            .map_err(Into::into)
+    }
+}
+
+impl<Eth> std::fmt::Debug for RethCall<Eth> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RethCall").finish_non_exhaustive()
+    }
+}
+
+impl<Eth> Clone for RethCall<Eth> {
+    fn clone(&self) -> Self {
+        Self { inner: Arc::clone(&self.inner) }
+    }
+}
+
+struct RethCallInner<Eth> {
+    /// Access to commonly used code of the `eth` namespace
+    eth_api: Eth,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EthApi;
+    use alloy_consensus::Header;
+    use alloy_eips::eip1559::ETHEREUM_BLOCK_GAS_LIMIT_30M;
+    use alloy_primitives::{Address, B256, U256};
+    use reth_chainspec::ChainSpecProvider;
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+    use reth_rpc_eth_types::{
+        EthStateCache, FeeHistoryCache, FeeHistoryCacheConfig, GasPriceOracle,
+    };
+    use reth_rpc_server_types::constants::{
+        DEFAULT_ETH_PROOF_WINDOW, DEFAULT_MAX_SIMULATE_BLOCKS, DEFAULT_PROOF_PERMITS,
+    };
+    use reth_tasks::pool::BlockingTaskPool;
+    use reth_transaction_pool::test_utils::{testing_pool, TestPool};
+
+    const ALICE: Address = Address::with_last_byte(1);
+    const BOB: Address = Address::with_last_byte(2);
+    const CAROL: Address = Address::with_last_byte(3);
+    const BLOCK: B256 = B256::with_last_byte(1);
+
+    fn reth_call(gas_cap: u64) -> RethCall<EthApi<MockEthProvider, TestPool, (), EthEvmConfig>> {
+        let provider = MockEthProvider::default();
+        provider.add_account(ALICE, ExtendedAccount::new(0, U256::from(10)));
+        provider.add_header(
+            BLOCK,
+            Header { gas_limit: ETHEREUM_BLOCK_GAS_LIMIT_30M, ..Default::default() },
+        );
+
+        let cache = EthStateCache::spawn(provider.clone(), Default::default());
+        RethCall::new(EthApi::new(
+            provider.clone(),
+            testing_pool(),
+            (),
+            cache.clone(),
+            GasPriceOracle::new(provider.clone(), Default::default(), cache),
+            gas_cap,
+            DEFAULT_MAX_SIMULATE_BLOCKS,
+            DEFAULT_ETH_PROOF_WINDOW,
+            BlockingTaskPool::build().expect("failed to build tracing pool"),
+            FeeHistoryCache::<Header>::new(FeeHistoryCacheConfig::default()),
+            EthEvmConfig::new(provider.chain_spec()),
+            DEFAULT_PROOF_PERMITS,
+        ))
+    }
+
+    fn transfer(from: Address, to: Address, value: u64) -> TransactionRequest {
+        TransactionRequest::default().from(from).to(to).value(U256::from(value))
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn reports_errors_per_call() {
+        let reth_call = reth_call(ETHEREUM_BLOCK_GAS_LIMIT_30M);
+        let calls =
+            vec![transfer(ALICE, BOB, 5), transfer(BOB, CAROL, 3), transfer(ALICE, CAROL, 1)];
+
+        // bob can only pay carol with the funds he received from alice in the first call
+        let results = reth_call
+            .call_many(BLOCK.into(), calls.clone(), CallManyOptions { independent: false })
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 3);
+        assert!(results.iter().all(|result| result.error.is_none() && result.gas_used == 21_000));
+
+        let results = reth_call
+            .call_many(BLOCK.into(), calls, CallManyOptions { independent: true })
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 3);
+        assert!(results[0].error.is_none());
+        assert!(results[1].error.is_some());
+        assert_eq!(results[1].gas_used, 0);
+        assert!(results[2].error.is_none());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn limits_gas_of_the_batch() {
+        let reth_call = reth_call(50_000);
+        let calls = vec![transfer(ALICE, BOB, 1); 4];
+
+        let results = reth_call
+            .call_many(BLOCK.into(), calls, CallManyOptions { independent: false })
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 4);
+        assert_eq!(results[0].gas_used, 21_000);
+        assert_eq!(results[1].gas_used, 21_000);
+        // only 8_000 gas are left for the remaining calls
+        assert!(results[2].error.is_some());
+        assert!(results[3].error.is_some());
+    }
+}
@@ -0,0 +1,27 @@
+use async_trait::async_trait;
+use jsonrpsee::core::RpcResult;
+use reth_rpc_api::{RepairStatus, SafeModeApiServer};
+
+/// `reth` safe mode API implementation.
+///
+/// Serves the storage repair status of a node that was started with `--safe-mode`.
+#[derive(Debug, Clone)]
+pub struct SafeModeApi {
+    /// The repair status determined at startup.
+    status: RepairStatus,
+}
+
+impl SafeModeApi {
+    /// Creates a new instance of `SafeModeApi`.
+    pub const fn new(status: RepairStatus) -> Self {
+        Self { status }
+    }
+}
+
+#[async_trait]
+impl SafeModeApiServer for SafeModeApi {
+    /// Handler for `reth_repairStatus`
+    async fn repair_status(&self) -> RpcResult<RepairStatus> {
+        Ok(self.status.clone())
+    }
+}
@@ -0,0 +1,35 @@
+use async_trait::async_trait;
+use jsonrpsee::core::RpcResult;
+use reth_rpc_api::DebugTaskDumpApiServer;
+use reth_rpc_server_types::result::internal_rpc_err;
+use reth_tasks::{registry::TaskInfo, TaskExecutor};
+
+/// `debug` task dump API implementation.
+///
+/// Serves the live tasks tracked by the task registry of the node's executor.
+#[derive(Debug)]
+pub struct TaskDumpApi {
+    /// The executor that spawns the node's tasks.
+    executor: TaskExecutor,
+}
+
+impl TaskDumpApi {
+    /// Creates a new instance of `TaskDumpApi`.
+    pub const fn new(executor: TaskExecutor) -> Self {
+        Self { executor }
+    }
+}
+
+#[async_trait]
+impl DebugTaskDumpApiServer for TaskDumpApi {
+    /// Handler for `debug_taskDump`
+    async fn task_dump(&self) -> RpcResult<Vec<TaskInfo>> {
+        let registry = self.executor.task_registry();
+        if !registry.is_enabled() {
+            return Err(internal_rpc_err(
+                "task instrumentation is disabled, enable it with --debug.task-instrumentation",
+            ))
+        }
+        Ok(registry.tasks())
+    }
+}
@@ -14,6 +14,7 @@ use alloy_rpc_types_trace::{
     tracerequest::TraceCallRequest,
 };
 use async_trait::async_trait;
+use futures::{StreamExt, TryStreamExt};
 use jsonrpsee::core::RpcResult;
 use reth_chainspec::{ChainSpecProvider, EthChainSpec, EthereumHardfork, MAINNET, SEPOLIA};
 use reth_evm::ConfigureEvm;
@@ -21,7 +22,7 @@ use reth_primitives_traits::{BlockBody, BlockHeader};
 use reth_revm::{database::StateProviderDatabase, db::CacheDB};
 use reth_rpc_api::TraceApiServer;
 use reth_rpc_eth_api::{
-    helpers::{Call, LoadPendingBlock, LoadTransaction, Trace, TraceExt},
+    helpers::{Call, LoadBlock, LoadPendingBlock, LoadTransaction, Trace, TraceExt},
     FromEthApiError, RpcNodeCore,
 };
 use reth_rpc_eth_types::{error::EthApiError, utils::recover_raw_transaction, EthConfig};
@@ -378,53 +379,60 @@ where
         // ensure that the range is not too large, since we need to fetch all blocks in the range
         let distance = end.saturating_sub(start);
         if distance > self.inner.eth_config.max_trace_filter_blocks {
-            return Err(EthApiError::InvalidParams(
-                "Block range too large; currently limited to 100 blocks".to_string(),
-            )
+            return Err(EthApiError::InvalidParams(format!(
+                "Block range too large; currently limited to {} blocks",
+                self.inner.eth_config.max_trace_filter_blocks
+            ))
             .into())
         }
 
-        // fetch all blocks in that range
-        let blocks = self
-            .provider()
-            .recovered_block_range(start..=end)
-            .map_err(Eth::Error::from_eth_err)?
-            .into_iter()
-            .map(Arc::new)
-            .collect::<Vec<_>>();
-
-        // trace all blocks
-        let mut block_traces = Vec::with_capacity(blocks.len());
-        for block in &blocks {
-            let matcher = matcher.clone();
-            let traces = self.eth_api().trace_block_until(
-                block.hash().into(),
-                Some(block.clone()),
-                None,
-                TracingInspectorConfig::default_parity(),
-                move |tx_info, ctx| {
-                    let mut traces = ctx
-                        .inspector
-                        .into_parity_builder()
-                        .into_localized_transaction_traces(tx_info);
-                    traces.retain(|trace| matcher.matches(&trace.trace));
-                    Ok(Some(traces))
-                },
-            );
-            block_traces.push(traces);
-        }
+        let after = after.map_or(0, |after| after as usize);
+        let count = count.map(|count| count as usize);
+        let max_results = self.inner.eth_config.max_trace_filter_results;
+        let concurrency = self.inner.eth_config.trace_filter_concurrency.max(1);
+
+        // replay the blocks of the range in parallel, but consume their traces in block order so
+        // that `after` and `count` can be applied while streaming and replay stops as soon as
+        // enough traces were collected
+        let mut block_traces = futures::stream::iter(start..=end)
+            .map(|block_number| {
+                let matcher = matcher.clone();
+                async move {
+                    let block = self
+                        .eth_api()
+                        .recovered_block(block_number.into())
+                        .await?
+                        .ok_or(EthApiError::HeaderNotFound(block_number.into()))?;
+                    let traces = self
+                        .eth_api()
+                        .trace_block_until(
+                            block.hash().into(),
+                            Some(block.clone()),
+                            None,
+                            TracingInspectorConfig::default_parity(),
+                            move |tx_info, ctx| {
+                                let mut traces = ctx
+                                    .inspector
+                                    .into_parity_builder()
+                                    .into_localized_transaction_traces(tx_info);
+                                traces.retain(|trace| matcher.matches(&trace.trace));
+                                Ok(Some(traces))
+                            },
+                        )
+                        .await?;
+                    Ok::<_, Eth::Error>((block, traces))
+                }
+            })
+            .buffered(concurrency);
 
-        let block_traces = futures::future::try_join_all(block_traces).await?;
-        let mut all_traces = block_traces
-            .into_iter()
-            .flatten()
-            .flat_map(|traces| traces.into_iter().flatten().flat_map(|traces| traces.into_iter()))
-            .collect::<Vec<_>>();
+        let mut skipped = 0;
+        let mut all_traces = Vec::new();
+        while let Some((block, traces)) = block_traces.try_next().await? {
+            let mut matching = traces.into_iter().flatten().flatten().flatten().collect::<Vec<_>>();
 
-        // add reward traces for all blocks
-        for block in &blocks {
+            // add reward traces of the block, there are no rewards after the Paris hardfork
             if let Some(base_block_reward) = self.calculate_base_block_reward(block.header())? {
-                all_traces.extend(
+                matching.extend(
                     self.extract_reward_traces(
                         block.header(),
                         block.body().ommers(),
@@ -433,31 +441,32 @@ where
                     .into_iter()
                     .filter(|trace| matcher.matches(&trace.trace)),
                 );
-            } else {
-                // no block reward, means we're past the Paris hardfork and don't expect any rewards
-                // because the blocks in ascending order
-                break
             }
-        }
 
-        // Skips the first `after` number of matching traces.
-        // If `after` is greater than or equal to the number of matched traces, it returns an empty
-        // array.
-        if let Some(after) = after.map(|a| a as usize) {
-            if after < all_traces.len() {
-                all_traces.drain(..after);
-            } else {
-                return Ok(vec![])
+            // Skips the first `after` number of matching traces.
+            let skip = (after - skipped).min(matching.len());
+            skipped += skip;
+            all_traces.extend(matching.into_iter().skip(skip));
+
+            // Return at most `count` of traces
+            if let Some(count) = count {
+                if all_traces.len() >= count {
+                    all_traces.truncate(count);
+                    break
+                }
             }
-        }
 
-        // Return at most `count` of traces
-        if let Some(count) = count {
-            let count = count as usize;
-            if count < all_traces.len() {
-                all_traces.truncate(count);
+            // the suggested retry range ends at the last block that was fully collected, so a
+            // single block is always returned in full
+            let block_number = block.header().number();
+            if block_number > start && all_traces.len() > max_results {
+                return Err(EthApiError::InvalidParams(format!(
+                    "query exceeds max results {max_results}, retry with the range {start}-{}",
+                    block_number - 1
+                ))
+                .into())
             }
-        };
+        }
 
         Ok(all_traces)
     }
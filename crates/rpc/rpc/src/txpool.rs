@@ -9,9 +9,12 @@ use alloy_rpc_types_txpool::{
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
 use reth_primitives_traits::NodePrimitives;
-use reth_rpc_api::TxPoolApiServer;
+use reth_rpc_api::{TxPoolApiServer, TxpoolContentPage};
 use reth_rpc_convert::{RpcConvert, RpcTypes};
 use reth_rpc_eth_api::RpcTransaction;
+use reth_rpc_server_types::{
+    constants::MAX_TXPOOL_CONTENT_PAGE_SIZE, result::invalid_params_rpc_err,
+};
 use reth_transaction_pool::{
     AllPoolTransactions, PoolConsensusTx, PoolTransaction, TransactionPool,
 };
@@ -40,27 +43,6 @@ where
     Eth: RpcConvert<Primitives: NodePrimitives<SignedTx = PoolConsensusTx<Pool>>>,
 {
     fn content(&self) -> Result<TxpoolContent<RpcTransaction<Eth::Network>>, Eth::Error> {
-        #[inline]
-        fn insert<Tx, RpcTxB>(
-            tx: &Tx,
-            content: &mut BTreeMap<
-                Address,
-                BTreeMap<String, <RpcTxB::Network as RpcTypes>::TransactionResponse>,
-            >,
-            resp_builder: &RpcTxB,
-        ) -> Result<(), RpcTxB::Error>
-        where
-            Tx: PoolTransaction,
-            RpcTxB: RpcConvert<Primitives: NodePrimitives<SignedTx = Tx::Consensus>>,
-        {
-            content.entry(tx.sender()).or_default().insert(
-                tx.nonce().to_string(),
-                resp_builder.fill_pending(tx.clone_into_consensus())?,
-            );
-
-            Ok(())
-        }
-
         let AllPoolTransactions { pending, queued } = self.pool.all_transactions();
 
         let mut content = TxpoolContent::default();
@@ -73,6 +55,85 @@ where
 
         Ok(content)
     }
+
+    fn content_from(
+        &self,
+        from: Address,
+    ) -> Result<TxpoolContentFrom<RpcTransaction<Eth::Network>>, Eth::Error> {
+        let mut content = TxpoolContent::default();
+        for pending in self.pool.get_pending_transactions_by_sender(from) {
+            insert::<_, Eth>(&pending.transaction, &mut content.pending, &self.tx_resp_builder)?;
+        }
+        for queued in self.pool.get_queued_transactions_by_sender(from) {
+            insert::<_, Eth>(&queued.transaction, &mut content.queued, &self.tx_resp_builder)?;
+        }
+
+        Ok(content.remove_from(&from))
+    }
+
+    fn content_page(
+        &self,
+        after: Option<Address>,
+        limit: usize,
+    ) -> Result<TxpoolContentPage<RpcTransaction<Eth::Network>>, Eth::Error> {
+        let AllPoolTransactions { pending, queued } = self.pool.all_transactions();
+
+        // group the transactions of all senders after the cursor, only the transactions of the
+        // page are converted
+        let mut senders = BTreeMap::<Address, (Vec<_>, Vec<_>)>::new();
+        let after_cursor = |sender: Address| after.is_none_or(|after| sender > after);
+        for tx in pending.into_iter().filter(|tx| after_cursor(tx.sender())) {
+            senders.entry(tx.sender()).or_default().0.push(tx);
+        }
+        for tx in queued.into_iter().filter(|tx| after_cursor(tx.sender())) {
+            senders.entry(tx.sender()).or_default().1.push(tx);
+        }
+
+        let mut page = TxpoolContentPage::default();
+        let mut len = 0;
+        let mut last = None;
+        for (sender, (pending, queued)) in senders {
+            let count = pending.len() + queued.len();
+            if len > 0 && len + count > limit {
+                page.next = last;
+                break
+            }
+            len += count;
+            last = Some(sender);
+
+            for pending in pending {
+                insert::<_, Eth>(&pending.transaction, &mut page.pending, &self.tx_resp_builder)?;
+            }
+            for queued in queued {
+                insert::<_, Eth>(&queued.transaction, &mut page.queued, &self.tx_resp_builder)?;
+            }
+        }
+
+        Ok(page)
+    }
+}
+
+/// Inserts the RPC representation of the transaction into the content, grouped by sender and
+/// nonce.
+#[inline]
+fn insert<Tx, RpcTxB>(
+    tx: &Tx,
+    content: &mut BTreeMap<
+        Address,
+        BTreeMap<String, <RpcTxB::Network as RpcTypes>::TransactionResponse>,
+    >,
+    resp_builder: &RpcTxB,
+) -> Result<(), RpcTxB::Error>
+where
+    Tx: PoolTransaction,
+    RpcTxB: RpcConvert<Primitives: NodePrimitives<SignedTx = Tx::Consensus>>,
+{
+    content
+        .entry(tx.sender())
+        .or_default()
+        .insert(tx.nonce().to_string(), resp_builder.fill_pending(tx.clone_into_consensus())?);
+
+    Ok(())
 }
 
 #[async_trait]
@@ -135,7 +196,7 @@ where
         from: Address,
     ) -> RpcResult<TxpoolContentFrom<RpcTransaction<Eth::Network>>> {
         trace!(target: "rpc::eth", ?from, "Serving txpool_contentFrom");
-        Ok(self.content().map_err(Into::into)?.remove_from(&from))
+        Ok(self.content_from(from).map_err(Into::into)?)
     }
 
     /// Returns the details of all transactions currently pending for inclusion in the next
@@ -147,6 +208,23 @@ where
         trace!(target: "rpc::eth", "Serving txpool_content");
         Ok(self.content().map_err(Into::into)?)
     }
+
+    /// Returns a page of the transactions currently in the txpool, grouped by sender and nonce.
+    ///
+    /// Handler for `txpool_contentPage`
+    async fn txpool_content_page(
+        &self,
+        after: Option<Address>,
+        limit: usize,
+    ) -> RpcResult<TxpoolContentPage<RpcTransaction<Eth::Network>>> {
+        trace!(target: "rpc::eth", ?after, limit, "Serving txpool_contentPage");
+        if limit == 0 || limit > MAX_TXPOOL_CONTENT_PAGE_SIZE {
+            return Err(invalid_params_rpc_err(format!(
+                "limit must be between 1 and {MAX_TXPOOL_CONTENT_PAGE_SIZE}"
+            )))
+        }
+        Ok(self.content_page(after, limit).map_err(Into::into)?)
+    }
 }
 
 impl<Pool, Eth> fmt::Debug for TxPoolApi<Pool, Eth> {
@@ -154,3 +232,43 @@ impl<Pool, Eth> fmt::Debug for TxPoolApi<Pool, Eth> {
         f.debug_struct("TxpoolApi").finish_non_exhaustive()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eth::helpers::types::EthRpcConverter;
+    use reth_transaction_pool::test_utils::{testing_pool, MockTransaction};
+
+    #[tokio::test]
+    async fn content_page() {
+        let pool = testing_pool();
+        let [a, b, c] = [1, 2, 3].map(Address::with_last_byte);
+        for (sender, nonce) in [(a, 0), (a, 1), (b, 0), (b, 5), (c, 0)] {
+            let tx = MockTransaction::eip1559().with_sender(sender).with_nonce(nonce);
+            pool.add_external_transaction(tx).await.unwrap();
+        }
+        let api = TxPoolApi::new(pool, EthRpcConverter::default());
+        let senders = |content: &BTreeMap<Address, _>| content.keys().copied().collect::<Vec<_>>();
+
+        // the transactions of a sender are never split across pages
+        let page = api.content_page(None, 3).unwrap();
+        assert_eq!(senders(&page.pending), vec![a]);
+        assert_eq!(page.pending[&a].len(), 2);
+        assert!(page.queued.is_empty());
+        assert_eq!(page.next, Some(a));
+
+        let page = api.content_page(page.next, 3).unwrap();
+        assert_eq!(senders(&page.pending), vec![b, c]);
+        assert_eq!(senders(&page.queued), vec![b]);
+        assert!(page.queued[&b].contains_key("5"));
+        assert_eq!(page.next, None);
+
+        // a sender with more transactions than the limit still fills a page on its own
+        let page = api.content_page(None, 1).unwrap();
+        assert_eq!(page.pending[&a].len(), 2);
+        assert_eq!(page.next, Some(a));
+
+        assert!(api.txpool_content_page(None, 0).await.is_err());
+        assert!(api.txpool_content_page(None, MAX_TXPOOL_CONTENT_PAGE_SIZE + 1).await.is_err());
+    }
+}
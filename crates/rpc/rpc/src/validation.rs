@@ -32,7 +32,10 @@ use reth_primitives_traits::{
     SealedBlock, SealedHeaderFor,
 };
 use reth_revm::{cached::CachedReads, database::StateProviderDatabase};
-use reth_rpc_api::BlockSubmissionValidationApiServer;
+use reth_rpc_api::{
+    BlockSubmissionValidationApiServer, PayloadValidation, RethPayloadValidationApiServer,
+    ValidatePayloadRequest,
+};
 use reth_rpc_server_types::result::{internal_rpc_err, invalid_params_rpc_err};
 use reth_storage_api::{BlockReaderIdExt, StateProviderFactory};
 use reth_tasks::TaskSpawner;
@@ -173,7 +176,72 @@ where
 
         self.consensus.validate_header_against_parent(block.sealed_header(), &parent_header)?;
         self.validate_gas_limit(registered_gas_limit, &parent_header, block.sealed_header())?;
-        let parent_header_hash = parent_header.hash();
+
+        let output = self.execute_and_validate(&block, parent_header.hash(), true).await?;
+
+        self.ensure_payment(&block, &output, &message)?;
+
+        Ok(())
+    }
+
+    /// Fully validates the payload of the request on top of its parent, without inserting it.
+    pub async fn validate_payload(
+        &self,
+        request: ValidatePayloadRequest,
+    ) -> Result<(), ValidationApiError> {
+        let ValidatePayloadRequest {
+            execution_payload,
+            expected_blob_versioned_hashes,
+            parent_beacon_block_root,
+            execution_requests,
+        } = request;
+
+        let sidecar = match (parent_beacon_block_root, execution_requests) {
+            (Some(parent_beacon_block_root), requests) => {
+                let cancun = CancunPayloadFields {
+                    parent_beacon_block_root,
+                    versioned_hashes: expected_blob_versioned_hashes.unwrap_or_default(),
+                };
+                match requests {
+                    Some(requests) => ExecutionPayloadSidecar::v4(
+                        cancun,
+                        PraguePayloadFields { requests: RequestsOrHash::Requests(requests) },
+                    ),
+                    None => ExecutionPayloadSidecar::v3(cancun),
+                }
+            }
+            (None, Some(_)) => return Err(ValidationApiError::MissingParentBeaconBlockRoot),
+            (None, None) => ExecutionPayloadSidecar::none(),
+        };
+        let block = self
+            .payload_validator
+            .ensure_well_formed_payload(ExecutionData { payload: execution_payload, sidecar })?;
+
+        self.consensus.validate_header(block.sealed_header())?;
+        self.consensus.validate_block_pre_execution(block.sealed_block())?;
+
+        let parent_header = self
+            .provider
+            .sealed_header_by_hash(block.parent_hash())?
+            .ok_or_else(|| ValidationApiError::MissingParentBlock)?;
+        self.consensus.validate_header_against_parent(block.sealed_header(), &parent_header)?;
+
+        self.execute_and_validate(&block, parent_header.hash(), false).await?;
+
+        Ok(())
+    }
+
+    /// Executes the block on top of the state of its parent and validates the execution output
+    /// and the state root.
+    ///
+    /// If `check_disallow` is set, the block must not access any disallowed address.
+    async fn execute_and_validate(
+        &self,
+        block: &RecoveredBlock<<E::Primitives as NodePrimitives>::Block>,
+        parent_header_hash: B256,
+        check_disallow: bool,
+    ) -> Result<BlockExecutionOutput<<E::Primitives as NodePrimitives>::Receipt>, ValidationApiError>
+    {
         let state_provider = self.provider.state_by_block_hash(parent_header_hash)?;
 
         let mut request_cache = self.cached_reads(parent_header_hash).await;
@@ -182,8 +250,8 @@ where
         let executor = self.evm_config.batch_executor(cached_db);
 
         let mut accessed_blacklisted = None;
-        let output = executor.execute_with_state_closure(&block, |state| {
-            if !self.disallow.is_empty() {
+        let output = executor.execute_with_state_closure(block, |state| {
+            if check_disallow && !self.disallow.is_empty() {
                 // Check whether the submission interacted with any blacklisted account by scanning
                 // the `State`'s cache that records everything read from database during execution.
                 for account in state.cache.accounts.keys() {
@@ -201,9 +269,7 @@ where
         // update the cached reads
         self.update_cached_reads(parent_header_hash, request_cache).await;
 
-        self.consensus.validate_block_post_execution(&block, &output)?;
-
-        self.ensure_payment(&block, &output, &message)?;
+        self.consensus.validate_block_post_execution(block, &output)?;
 
         let state_root =
             state_provider.state_root(state_provider.hashed_post_state(&output.state))?;
@@ -215,7 +281,7 @@ where
             .into())
         }
 
-        Ok(())
+        Ok(output)
     }
 
     /// Ensures that fields of [`BidTrace`] match the fields of the [`SealedHeaderFor`].
@@ -545,6 +611,41 @@ where
     }
 }
 
+#[async_trait]
+impl<Provider, E> RethPayloadValidationApiServer for ValidationApi<Provider, E>
+where
+    Provider: BlockReaderIdExt<Header = <E::Primitives as NodePrimitives>::BlockHeader>
+        + ChainSpecProvider<ChainSpec: EthereumHardforks>
+        + StateProviderFactory
+        + Clone
+        + 'static,
+    E: ConfigureEvm + 'static,
+{
+    /// Validates a payload without inserting it
+    async fn reth_validate_payload(
+        &self,
+        request: ValidatePayloadRequest,
+    ) -> RpcResult<PayloadValidation> {
+        let block_hash = request.execution_payload.block_hash();
+        let this = self.clone();
+        let (tx, rx) = oneshot::channel();
+
+        self.task_spawner.spawn_blocking(Box::pin(async move {
+            let _ = tx.send(this.validate_payload(request).await);
+        }));
+
+        match rx.await.map_err(|_| internal_rpc_err("Internal blocking task error"))? {
+            Ok(()) => Ok(PayloadValidation { block_hash, valid: true, validation_error: None }),
+            Err(err) if err.is_invalid_block() => Ok(PayloadValidation {
+                block_hash,
+                valid: false,
+                validation_error: Some(err.to_string()),
+            }),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
 pub struct ValidationApiInner<Provider, E: ConfigureEvm> {
     /// The provider that can interact with the chain.
     provider: Provider,
@@ -639,6 +740,8 @@ pub enum ValidationApiError {
     InvalidBlobsBundle,
     #[error("block accesses blacklisted address: {_0}")]
     Blacklist(Address),
+    #[error("execution requests require a parent beacon block root")]
+    MissingParentBeaconBlockRoot,
     #[error(transparent)]
     Blob(#[from] BlobTransactionValidationError),
     #[error(transparent)]
@@ -651,6 +754,23 @@ pub enum ValidationApiError {
     Payload(#[from] NewPayloadError),
 }
 
+impl ValidationApiError {
+    /// Returns `true` if the error is caused by an invalid block, rather than by a failure to
+    /// validate it.
+    pub const fn is_invalid_block(&self) -> bool {
+        !matches!(
+            self,
+            Self::MissingLatestBlock |
+                Self::MissingParentBlock |
+                Self::BlockTooOld |
+                Self::MissingParentBeaconBlockRoot |
+                Self::Provider(_) |
+                Self::Execution(BlockExecutionError::Internal(_)) |
+                Self::Payload(NewPayloadError::Other(_))
+        )
+    }
+}
+
 impl From<ValidationApiError> for ErrorObject<'static> {
     fn from(error: ValidationApiError) -> Self {
         match error {
@@ -661,6 +781,7 @@ impl From<ValidationApiError> for ErrorObject<'static> {
             ValidationApiError::Blacklist(_) |
             ValidationApiError::ProposerPayment |
             ValidationApiError::InvalidBlobsBundle |
+            ValidationApiError::MissingParentBeaconBlockRoot |
             ValidationApiError::Blob(_) => invalid_params_rpc_err(error.to_string()),
 
             ValidationApiError::MissingLatestBlock |
@@ -692,9 +813,119 @@ pub(crate) struct ValidationMetrics {
 
 #[cfg(test)]
 mod tests {
-    use super::hash_disallow_list;
-    use revm_primitives::Address;
-    use std::collections::HashSet;
+    use super::*;
+    use alloy_consensus::{Header, EMPTY_OMMER_ROOT_HASH};
+    use alloy_eips::eip7685::Requests;
+    use alloy_rpc_types_engine::{ExecutionPayloadV1, PayloadError};
+    use jsonrpsee_types::error::{INTERNAL_ERROR_CODE, INVALID_PARAMS_CODE};
+    use reth_consensus::noop::NoopConsensus;
+    use reth_ethereum_primitives::{Block, BlockBody, TransactionSigned};
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_provider::test_utils::MockEthProvider;
+    use reth_tasks::TokioTaskExecutor;
+
+    /// Converts payloads into blocks without checking any fork specific fields.
+    struct TestPayloadValidator;
+
+    impl PayloadValidator for TestPayloadValidator {
+        type Block = Block;
+        type ExecutionData = ExecutionData;
+
+        fn ensure_well_formed_payload(
+            &self,
+            payload: ExecutionData,
+        ) -> Result<RecoveredBlock<Block>, NewPayloadError> {
+            let expected = payload.payload.block_hash();
+            let block = SealedBlock::seal_slow(
+                payload
+                    .payload
+                    .try_into_block_with_sidecar::<TransactionSigned>(&payload.sidecar)
+                    .map_err(NewPayloadError::Eth)?,
+            );
+            if block.hash() != expected {
+                return Err(NewPayloadError::Eth(PayloadError::BlockHash {
+                    execution: block.hash(),
+                    consensus: expected,
+                }))
+            }
+            block.try_recover().map_err(|err| NewPayloadError::Other(err.into()))
+        }
+    }
+
+    fn validation_api() -> (ValidationApi<MockEthProvider, EthEvmConfig>, Header) {
+        let provider = MockEthProvider::default();
+        let parent = Header { gas_limit: 30_000_000, ..Default::default() };
+        provider.add_header(parent.hash_slow(), parent.clone());
+        let api = ValidationApi::new(
+            provider.clone(),
+            NoopConsensus::arc(),
+            EthEvmConfig::new(provider.chain_spec()),
+            ValidationApiConfig::default(),
+            Box::new(TokioTaskExecutor::default()),
+            Arc::new(TestPayloadValidator),
+        );
+        (api, parent)
+    }
+
+    /// Returns a request for an empty child of the given parent.
+    fn payload_request(parent: &Header, state_root: B256) -> ValidatePayloadRequest {
+        let block = Block {
+            header: Header {
+                parent_hash: parent.hash_slow(),
+                ommers_hash: EMPTY_OMMER_ROOT_HASH,
+                number: parent.number + 1,
+                gas_limit: parent.gas_limit,
+                timestamp: parent.timestamp + 12,
+                state_root,
+                ..Default::default()
+            },
+            body: BlockBody::default(),
+        };
+        ValidatePayloadRequest {
+            execution_payload: ExecutionPayload::V1(ExecutionPayloadV1::from_block_slow(&block)),
+            expected_blob_versioned_hashes: None,
+            parent_beacon_block_root: None,
+            execution_requests: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn validates_payload() {
+        let (api, parent) = validation_api();
+
+        // the mock provider computes an empty state root
+        let request = payload_request(&parent, B256::ZERO);
+        let block_hash = request.execution_payload.block_hash();
+        assert_eq!(
+            api.reth_validate_payload(request).await.unwrap(),
+            PayloadValidation { block_hash, valid: true, validation_error: None }
+        );
+
+        let request = payload_request(&parent, B256::with_last_byte(1));
+        let validation = api.reth_validate_payload(request).await.unwrap();
+        assert!(!validation.valid);
+        assert!(validation.validation_error.is_some());
+    }
+
+    #[tokio::test]
+    async fn rejects_payload_that_cannot_be_validated() {
+        let (api, parent) = validation_api();
+
+        let request = payload_request(&Header { number: 1, ..parent.clone() }, B256::ZERO);
+        assert_eq!(
+            api.reth_validate_payload(request).await.unwrap_err().code(),
+            INTERNAL_ERROR_CODE
+        );
+
+        let request = ValidatePayloadRequest {
+            execution_requests: Some(Requests::default()),
+            ..payload_request(&parent, B256::ZERO)
+        };
+        assert_eq!(
+            api.reth_validate_payload(request).await.unwrap_err().code(),
+            INVALID_PARAMS_CODE
+        );
+    }
 
     #[test]
     fn test_hash_disallow_list_deterministic() {
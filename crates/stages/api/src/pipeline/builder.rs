@@ -2,6 +2,7 @@ use crate::{pipeline::BoxedStage, MetricEventsSender, Pipeline, Stage, StageId,
 use alloy_primitives::{BlockNumber, B256};
 use reth_provider::{providers::ProviderNodeTypes, DatabaseProviderFactory, ProviderFactory};
 use reth_static_file::StaticFileProducer;
+use reth_tasks::governor::ThroughputGovernor;
 use tokio::sync::watch;
 
 /// Builds a [`Pipeline`].
@@ -15,6 +16,8 @@ pub struct PipelineBuilder<Provider> {
     tip_tx: Option<watch::Sender<B256>>,
     metrics_tx: Option<MetricEventsSender>,
     fail_on_unwind: bool,
+    /// Limits the bytes written per second, checked after every commit.
+    governor: Option<ThroughputGovernor>,
 }
 
 impl<Provider> PipelineBuilder<Provider> {
@@ -69,6 +72,15 @@ impl<Provider> PipelineBuilder<Provider> {
         self
     }
 
+    /// Set the governor that limits the bytes written to disk per second.
+    ///
+    /// The writes are throttled after each stage commits its batch, so the database transaction
+    /// is not held while waiting.
+    pub fn with_governor(mut self, governor: ThroughputGovernor) -> Self {
+        self.governor = Some(governor);
+        self
+    }
+
     /// Builds the final [`Pipeline`] using the given database.
     pub fn build<N>(
         self,
@@ -79,7 +91,7 @@ impl<Provider> PipelineBuilder<Provider> {
         N: ProviderNodeTypes,
         ProviderFactory<N>: DatabaseProviderFactory<ProviderRW = Provider>,
     {
-        let Self { stages, max_block, tip_tx, metrics_tx, fail_on_unwind } = self;
+        let Self { stages, max_block, tip_tx, metrics_tx, fail_on_unwind, governor } = self;
         Pipeline {
             provider_factory,
             stages,
@@ -90,8 +102,10 @@ impl<Provider> PipelineBuilder<Provider> {
             progress: Default::default(),
             metrics_tx,
             fail_on_unwind,
+            governor,
             last_detached_head_unwind_target: None,
             detached_head_attempts: 0,
+            run_state: Default::default(),
         }
     }
 }
@@ -104,6 +118,7 @@ impl<Provider> Default for PipelineBuilder<Provider> {
             tip_tx: None,
             metrics_tx: None,
             fail_on_unwind: false,
+            governor: None,
         }
     }
 }
@@ -114,6 +129,7 @@ impl<Provider> std::fmt::Debug for PipelineBuilder<Provider> {
             .field("stages", &self.stages.iter().map(|stage| stage.id()).collect::<Vec<StageId>>())
             .field("max_block", &self.max_block)
             .field("fail_on_unwind", &self.fail_on_unwind)
+            .field("governor", &self.governor)
             .finish()
     }
 }
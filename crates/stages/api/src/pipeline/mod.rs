@@ -1,7 +1,7 @@
 mod ctrl;
 mod event;
 pub use crate::pipeline::ctrl::ControlFlow;
-use crate::{PipelineTarget, StageCheckpoint, StageId};
+use crate::{PipelineTarget, StageCheckpoint, StageId, StageRegistry};
 use alloy_primitives::{BlockNumber, B256};
 pub use event::*;
 use futures_util::Future;
@@ -13,6 +13,7 @@ use reth_provider::{
 };
 use reth_prune::PrunerBuilder;
 use reth_static_file::StaticFileProducer;
+use reth_tasks::{governor::ThroughputGovernor, registry::set_task_label};
 use reth_tokio_util::{EventSender, EventStream};
 use std::pin::Pin;
 use tokio::sync::watch;
@@ -20,6 +21,7 @@ use tracing::*;
 
 mod builder;
 mod progress;
+mod run_state;
 mod set;
 
 use crate::{
@@ -29,6 +31,7 @@ use crate::{
 pub use builder::*;
 use progress::*;
 use reth_errors::RethResult;
+pub use run_state::PipelineRunState;
 pub use set::*;
 
 /// A container for a queued stage.
@@ -89,12 +92,16 @@ pub struct Pipeline<N: ProviderNodeTypes> {
     /// Whether an unwind should fail the syncing process. Should only be set when downloading
     /// blocks from trusted sources and expecting them to be valid.
     fail_on_unwind: bool,
+    /// Limits the bytes written per second, checked after every commit.
+    governor: Option<ThroughputGovernor>,
     /// Block that was chosen as a target of the last unwind triggered by
     /// [`StageError::DetachedHead`] error.
     last_detached_head_unwind_target: Option<B256>,
     /// Number of consecutive unwind attempts due to [`StageError::DetachedHead`] for the current
     /// fork.
     detached_head_attempts: u64,
+    /// Whether the pipeline is running.
+    run_state: PipelineRunState,
 }
 
 impl<N: ProviderNodeTypes> Pipeline<N> {
@@ -123,6 +130,12 @@ impl<N: ProviderNodeTypes> Pipeline<N> {
         self.event_sender.new_listener()
     }
 
+    /// Returns a handle that tracks whether the pipeline is running, which remains valid after the
+    /// pipeline was moved into a task.
+    pub fn run_state(&self) -> PipelineRunState {
+        self.run_state.clone()
+    }
+
     /// Get a mutable reference to a stage by index.
     pub fn stage(
         &mut self,
@@ -130,6 +143,15 @@ impl<N: ProviderNodeTypes> Pipeline<N> {
     ) -> &mut dyn Stage<<ProviderFactory<N> as DatabaseProviderFactory>::ProviderRW> {
         &mut self.stages[idx]
     }
+
+    /// Returns the registry of all built-in stages and the custom stages of this pipeline.
+    pub fn stage_registry(&self) -> StageRegistry {
+        let mut registry = StageRegistry::default();
+        for stage in &self.stages {
+            registry.register(stage.id());
+        }
+        registry
+    }
 }
 
 impl<N: ProviderNodeTypes> Pipeline<N> {
@@ -149,12 +171,34 @@ impl<N: ProviderNodeTypes> Pipeline<N> {
         Ok(())
     }
 
+    /// Returns the names of stored checkpoints that don't belong to any stage known to this
+    /// pipeline.
+    pub fn unknown_checkpoints(&self) -> Result<Vec<String>, PipelineError> {
+        let registry = self.stage_registry();
+        Ok(self
+            .provider_factory
+            .provider()?
+            .get_all_checkpoints()?
+            .into_iter()
+            .map(|(name, _)| name)
+            .filter(|name| !registry.contains(name))
+            .collect())
+    }
+
     /// Consume the pipeline and run it until it reaches the provided tip, if set. Return the
     /// pipeline and its result as a future.
     #[track_caller]
     pub fn run_as_fut(mut self, target: Option<PipelineTarget>) -> PipelineFut<N> {
         let _ = self.register_metrics();
+        if let Ok(unknown) = self.unknown_checkpoints() {
+            if !unknown.is_empty() {
+                warn!(target: "sync::pipeline", ?unknown, "Found checkpoints of unknown stages");
+            }
+        }
+        let run_state = self.run_state.clone();
         Box::pin(async move {
+            let _running = run_state.start();
+
             // NOTE: the tip should only be None if we are in continuous sync mode.
             if let Some(target) = target {
                 match target {
@@ -183,8 +227,14 @@ impl<N: ProviderNodeTypes> Pipeline<N> {
     /// a `max_block` in the pipeline.
     pub async fn run(&mut self) -> Result<(), PipelineError> {
         let _ = self.register_metrics(); // ignore error
+        let _running = self.run_state.start();
 
         loop {
+            if self.run_state.is_stop_requested() {
+                trace!(target: "sync::pipeline", "Pipeline stop requested, terminating");
+                return Ok(())
+            }
+
             let next_action = self.run_loop().await?;
 
             if next_action.is_unwind() && self.fail_on_unwind {
@@ -230,6 +280,11 @@ impl<N: ProviderNodeTypes> Pipeline<N> {
             let stage = &self.stages[stage_index];
             let stage_id = stage.id();
 
+            if self.run_state.is_stop_requested() {
+                trace!(target: "sync::pipeline", stage = %stage_id, "Pipeline stop requested, skipping stage");
+                break
+            }
+
             trace!(target: "sync::pipeline", stage = %stage_id, "Executing stage");
             let next = self.execute_stage_to_completion(previous_stage, stage_index).await?;
 
@@ -320,6 +375,7 @@ impl<N: ProviderNodeTypes> Pipeline<N> {
 
         for stage in unwind_pipeline {
             let stage_id = stage.id();
+            set_task_label(format_args!("unwind {stage_id}"));
             let span = info_span!("Unwinding", stage = %stage_id);
             let _enter = span.enter();
 
@@ -413,6 +469,7 @@ impl<N: ProviderNodeTypes> Pipeline<N> {
         let total_stages = self.stages.len();
 
         let stage_id = self.stage(stage_index).id();
+        set_task_label(stage_id);
         let mut made_progress = false;
         let target = self.max_block.or(previous_stage);
 
@@ -495,6 +552,11 @@ impl<N: ProviderNodeTypes> Pipeline<N> {
 
                     UnifiedStorageWriter::commit(provider_rw)?;
 
+                    // the batch is only written on commit, wait once the transaction is released
+                    if let Some(governor) = &self.governor {
+                        governor.throttle_writes();
+                    }
+
                     self.stage(stage_index).post_execute_commit()?;
 
                     if done {
@@ -505,6 +567,14 @@ impl<N: ProviderNodeTypes> Pipeline<N> {
                             ControlFlow::NoProgress { block_number: Some(block_number) }
                         })
                     }
+
+                    // the batch is committed, so the stage can resume from its checkpoint
+                    if self.run_state.is_stop_requested() {
+                        trace!(target: "sync::pipeline", stage = %stage_id, ?checkpoint, "Pipeline stop requested, stopping stage");
+                        return Ok(ControlFlow::NoProgress {
+                            block_number: Some(checkpoint.block_number),
+                        })
+                    }
                 }
                 Err(err) => {
                     drop(provider_rw);
@@ -677,6 +747,25 @@ mod tests {
         assert_eq!(progress.next_ctrl(), ControlFlow::Continue { block_number: 1 });
     }
 
+    #[test]
+    fn unknown_checkpoints() {
+        let provider_factory = create_test_provider_factory();
+        let provider_rw = provider_factory.provider_rw().unwrap();
+        provider_rw.save_stage_checkpoint(StageId::Headers, Default::default()).unwrap();
+        provider_rw.save_stage_checkpoint(StageId::Other("A"), Default::default()).unwrap();
+        provider_rw.save_stage_checkpoint(StageId::Other("Typo"), Default::default()).unwrap();
+        provider_rw.commit().unwrap();
+
+        let pipeline = Pipeline::<MockNodeTypesWithDB>::builder()
+            .add_stage(TestStage::new(StageId::Other("A")))
+            .build(
+                provider_factory.clone(),
+                StaticFileProducer::new(provider_factory.clone(), PruneModes::default()),
+            );
+
+        assert_eq!(pipeline.unknown_checkpoints().unwrap(), vec!["Typo".to_string()]);
+    }
+
     /// Runs a simple pipeline.
     #[tokio::test]
     async fn run_pipeline() {
@@ -755,6 +844,45 @@ mod tests {
         assert_eq!(post_unwind_commit_counter_b.load(Ordering::Relaxed), 0);
     }
 
+    /// Stops a running pipeline after the committed batch.
+    #[tokio::test]
+    async fn stop_pipeline() {
+        let provider_factory = create_test_provider_factory();
+
+        let stage_a = TestStage::new(StageId::Other("A"))
+            .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(5), done: false }))
+            .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(10), done: true }));
+        let (stage_a, post_execute_commit_counter_a) = stage_a.with_post_execute_commit_counter();
+
+        let stage_b = TestStage::new(StageId::Other("B"))
+            .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(10), done: true }));
+        let (stage_b, post_execute_commit_counter_b) = stage_b.with_post_execute_commit_counter();
+
+        let mut pipeline = Pipeline::<MockNodeTypesWithDB>::builder()
+            .add_stage(stage_a)
+            .add_stage(stage_b)
+            .with_max_block(10)
+            .build(
+                provider_factory.clone(),
+                StaticFileProducer::new(provider_factory.clone(), PruneModes::default()),
+            );
+        pipeline.run_state().request_stop();
+
+        // the batch in flight is committed, the next one and the remaining stages are not run
+        assert_eq!(pipeline.run_loop().await.unwrap(), ControlFlow::Continue { block_number: 5 });
+        assert_eq!(post_execute_commit_counter_a.load(Ordering::Relaxed), 1);
+        assert_eq!(post_execute_commit_counter_b.load(Ordering::Relaxed), 0);
+        assert_eq!(
+            provider_factory.get_stage_checkpoint(StageId::Other("A")).unwrap(),
+            Some(StageCheckpoint::new(5))
+        );
+        assert_eq!(provider_factory.get_stage_checkpoint(StageId::Other("B")).unwrap(), None);
+
+        // a stopped pipeline doesn't start another run
+        pipeline.run().await.unwrap();
+        assert_eq!(post_execute_commit_counter_a.load(Ordering::Relaxed), 1);
+    }
+
     /// Unwinds a simple pipeline.
     #[tokio::test]
     async fn unwind_pipeline() {
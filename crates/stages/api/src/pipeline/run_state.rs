@@ -0,0 +1,96 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+use tokio::sync::watch;
+
+/// Tracks whether a [`Pipeline`](super::Pipeline) is running.
+///
+/// The pipeline commits its progress after every batch, so once it's idle there are no pending
+/// writes of the pipeline to the database. This is used to stop an in-flight pipeline run on
+/// shutdown: [`PipelineRunState::request_stop`] makes the pipeline return after the batch it's
+/// executing, and [`PipelineRunState::idle`] resolves once it did.
+#[derive(Debug, Clone)]
+pub struct PipelineRunState {
+    running: Arc<watch::Sender<bool>>,
+    stop_requested: Arc<AtomicBool>,
+}
+
+impl PipelineRunState {
+    /// Returns `true` if the pipeline is running.
+    pub fn is_running(&self) -> bool {
+        *self.running.borrow()
+    }
+
+    /// Resolves once the pipeline is not running.
+    pub async fn idle(&self) {
+        let mut running = self.running.subscribe();
+        let _ = running.wait_for(|running| !running).await;
+    }
+
+    /// Requests the pipeline to stop after committing the batch it's executing.
+    ///
+    /// The request is not reset, so the pipeline doesn't start new runs afterwards.
+    pub fn request_stop(&self) {
+        self.stop_requested.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns `true` if the pipeline was requested to stop.
+    pub fn is_stop_requested(&self) -> bool {
+        self.stop_requested.load(Ordering::Relaxed)
+    }
+
+    /// Marks the pipeline as running until the returned guard is dropped.
+    pub(crate) fn start(&self) -> RunningGuard {
+        self.running.send_replace(true);
+        RunningGuard(Arc::clone(&self.running))
+    }
+}
+
+impl Default for PipelineRunState {
+    fn default() -> Self {
+        Self { running: Arc::new(watch::Sender::new(false)), stop_requested: Default::default() }
+    }
+}
+
+/// Marks the pipeline as idle when dropped, including when the run is cancelled.
+#[derive(Debug)]
+pub(crate) struct RunningGuard(Arc<watch::Sender<bool>>);
+
+impl Drop for RunningGuard {
+    fn drop(&mut self) {
+        self.0.send_replace(false);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn idle_after_run() {
+        let state = PipelineRunState::default();
+        assert!(!state.is_running());
+        state.idle().await;
+
+        let guard = state.start();
+        assert!(state.is_running());
+        let idle = tokio::spawn({
+            let state = state.clone();
+            async move { state.idle().await }
+        });
+        drop(guard);
+        idle.await.unwrap();
+        assert!(!state.is_running());
+    }
+
+    #[test]
+    fn stop_request_is_shared() {
+        let state = PipelineRunState::default();
+        let other = state.clone();
+        assert!(!other.is_stop_requested());
+
+        state.request_stop();
+        assert!(other.is_stop_requested());
+    }
+}
@@ -3,7 +3,7 @@
 
 use alloy_primitives::BlockNumber;
 use criterion::{criterion_main, measurement::WallTime, BenchmarkGroup, Criterion};
-use reth_config::config::{EtlConfig, TransactionLookupConfig};
+use reth_config::config::{EtlConfig, SenderRecoveryConfig, TransactionLookupConfig};
 use reth_db::{test_utils::TempDatabase, Database, DatabaseEnv};
 use reth_provider::{test_utils::MockNodeTypesWithDB, DatabaseProvider, DatabaseProviderFactory};
 use reth_stages::{
@@ -70,7 +70,8 @@ fn senders(c: &mut Criterion, runtime: &Runtime) {
 
     let db = setup::txs_testdata(DEFAULT_NUM_BLOCKS);
 
-    let stage = SenderRecoveryStage { commit_threshold: DEFAULT_NUM_BLOCKS };
+    let stage =
+        SenderRecoveryStage::new(SenderRecoveryConfig { commit_threshold: DEFAULT_NUM_BLOCKS });
 
     measure_stage(
         runtime,
@@ -113,7 +114,11 @@ fn merkle(c: &mut Criterion, runtime: &Runtime) {
 
     let db = setup::txs_testdata(DEFAULT_NUM_BLOCKS);
 
-    let stage = MerkleStage::Both { rebuild_threshold: u64::MAX, incremental_threshold: u64::MAX };
+    let stage = MerkleStage::Both {
+        rebuild_threshold: u64::MAX,
+        incremental_threshold: u64::MAX,
+        prefix_set_store: None,
+    };
     measure_stage(
         runtime,
         &mut group,
@@ -124,7 +129,11 @@ fn merkle(c: &mut Criterion, runtime: &Runtime) {
         "Merkle-incremental".to_string(),
     );
 
-    let stage = MerkleStage::Both { rebuild_threshold: 0, incremental_threshold: 0 };
+    let stage = MerkleStage::Both {
+        rebuild_threshold: 0,
+        incremental_threshold: 0,
+        prefix_set_store: None,
+    };
     measure_stage(
         runtime,
         &mut group,
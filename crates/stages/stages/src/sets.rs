@@ -39,9 +39,9 @@
 use crate::{
     stages::{
         AccountHashingStage, BodyStage, EraImportSource, EraStage, ExecutionStage, FinishStage,
-        HeaderStage, IndexAccountHistoryStage, IndexStorageHistoryStage, MerkleStage,
-        PruneSenderRecoveryStage, PruneStage, SenderRecoveryStage, StorageHashingStage,
-        TransactionLookupStage,
+        HeaderStage, IndexAccountHistoryStage, IndexLogsStage, IndexStorageHistoryStage,
+        MerkleStage, PruneSenderRecoveryStage, PruneStage, SenderRecoveryStage,
+        StorageHashingStage, TransactionLookupStage,
     },
     StageSet, StageSetBuilder,
 };
@@ -54,6 +54,7 @@ use reth_primitives_traits::{Block, NodePrimitives};
 use reth_provider::HeaderSyncGapProvider;
 use reth_prune_types::PruneModes;
 use reth_stages_api::Stage;
+use reth_trie_db::PrefixSetStore;
 use std::{ops::Not, sync::Arc};
 use tokio::sync::watch;
 
@@ -411,10 +412,15 @@ where
                 self.stages_config.storage_hashing,
                 self.stages_config.etl.clone(),
             ))
-            .add_stage(MerkleStage::new_execution(
-                self.stages_config.merkle.rebuild_threshold,
-                self.stages_config.merkle.incremental_threshold,
-            ))
+            .add_stage(
+                MerkleStage::new_execution(
+                    self.stages_config.merkle.rebuild_threshold,
+                    self.stages_config.merkle.incremental_threshold,
+                )
+                .with_prefix_set_store(
+                    self.stages_config.merkle.prefix_sets_dir.clone().map(PrefixSetStore::new),
+                ),
+            )
     }
 }
 
@@ -433,6 +439,7 @@ where
     TransactionLookupStage: Stage<Provider>,
     IndexStorageHistoryStage: Stage<Provider>,
     IndexAccountHistoryStage: Stage<Provider>,
+    IndexLogsStage: Stage<Provider>,
 {
     fn builder(self) -> StageSetBuilder<Provider> {
         StageSetBuilder::default()
@@ -451,5 +458,8 @@ where
                 self.stages_config.etl.clone(),
                 self.prune_modes.storage_history,
             ))
+            .add_stage_opt(self.stages_config.index_logs.enabled.then(|| {
+                IndexLogsStage::new(self.stages_config.index_logs, self.stages_config.etl.clone())
+            }))
     }
 }
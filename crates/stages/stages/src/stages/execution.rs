@@ -1,6 +1,10 @@
-use crate::stages::MERKLE_STAGE_DEFAULT_INCREMENTAL_THRESHOLD;
+use crate::stages::{
+    pending_batch::{PendingBatch, PendingBatchFile, PendingBatchRange},
+    prewarm::{PrewarmCache, PrewarmedState},
+    ExecutionPrewarm, MERKLE_STAGE_DEFAULT_INCREMENTAL_THRESHOLD,
+};
 use alloy_consensus::{BlockHeader, Header};
-use alloy_primitives::BlockNumber;
+use alloy_primitives::{Address, BlockNumber};
 use num_traits::Zero;
 use reth_config::config::ExecutionConfig;
 use reth_consensus::{ConsensusError, FullConsensus};
@@ -8,10 +12,13 @@ use reth_db::{static_file::HeaderMask, tables};
 use reth_evm::{execute::Executor, metrics::ExecutorMetrics, ConfigureEvm};
 use reth_execution_types::Chain;
 use reth_exex::{ExExManagerHandle, ExExNotification, ExExNotificationSource};
-use reth_primitives_traits::{format_gas_throughput, Block, BlockBody, NodePrimitives};
+use reth_primitives_traits::{
+    format_gas_throughput, Block, BlockBody, NodePrimitives, RecoveredBlock,
+};
 use reth_provider::{
+    block_gas_usage,
     providers::{StaticFileProvider, StaticFileWriter},
-    BlockHashReader, BlockReader, DBProvider, ExecutionOutcome, HeaderProvider,
+    BlockHashReader, BlockReader, DBProvider, ExecutionOutcome, GasUsageWriter, HeaderProvider,
     LatestStateProviderRef, OriginalValuesKnown, ProviderError, StateCommitmentProvider,
     StateWriter, StaticFileProviderFactory, StatsReader, StorageLocation, TransactionVariant,
 };
@@ -22,9 +29,12 @@ use reth_stages_api::{
     UnwindInput, UnwindOutput,
 };
 use reth_static_file_types::StaticFileSegment;
+use reth_tasks::governor::ThroughputGovernor;
 use std::{
     cmp::Ordering,
+    collections::VecDeque,
     ops::RangeInclusive,
+    path::PathBuf,
     sync::Arc,
     task::{ready, Context, Poll},
     time::{Duration, Instant},
@@ -96,6 +106,12 @@ where
     exex_manager_handle: ExExManagerHandle<E::Primitives>,
     /// Executor metrics.
     metrics: ExecutorMetrics,
+    /// Speculative execution of upcoming blocks, if enabled.
+    prewarm: Option<ExecutionPrewarm>,
+    /// The file the executed blocks of the current batch are saved to, if enabled.
+    pending_batch: Option<PendingBatchFile>,
+    /// Limits the execution throughput, if set.
+    governor: Option<ThroughputGovernor>,
 }
 
 impl<E> ExecutionStage<E>
@@ -119,9 +135,36 @@ where
             post_unwind_commit_input: None,
             exex_manager_handle,
             metrics: ExecutorMetrics::default(),
+            prewarm: None,
+            pending_batch: None,
+            governor: None,
         }
     }
 
+    /// Enables speculative execution of upcoming blocks to warm their state reads.
+    pub fn with_prewarm(mut self, prewarm: ExecutionPrewarm) -> Self {
+        self.prewarm = Some(prewarm);
+        self
+    }
+
+    /// Saves the executed blocks of a batch to the given file every `save_interval` blocks.
+    ///
+    /// If the node stops before the batch is written, the saved blocks are written on the next run
+    /// instead of being executed again.
+    pub fn with_pending_batch(mut self, path: PathBuf, save_interval: u64) -> Self {
+        self.pending_batch = Some(PendingBatchFile::new(path, save_interval));
+        self
+    }
+
+    /// Limits the gas executed per second with the given governor.
+    ///
+    /// The written bytes are throttled by the pipeline once the batch is committed, see
+    /// `PipelineBuilder::with_governor`.
+    pub fn with_governor(mut self, governor: ThroughputGovernor) -> Self {
+        self.governor = Some(governor);
+        self
+    }
+
     /// Create an execution stage with the provided executor.
     ///
     /// The commit threshold will be set to [`MERKLE_STAGE_DEFAULT_INCREMENTAL_THRESHOLD`].
@@ -177,6 +220,107 @@ where
             provider.count_entries::<tables::AccountsTrie>()?.is_zero())
     }
 
+    /// Loads the saved pending batch, if it continues the checkpoint of the stage.
+    ///
+    /// A batch that doesn't, e.g. because the chain was unwound since it was saved, is removed.
+    fn load_pending_batch<Provider>(
+        &self,
+        provider: &Provider,
+        start_block: u64,
+        max_block: u64,
+        index_gas_usage: bool,
+    ) -> Result<Option<PendingBatch<<E::Primitives as NodePrimitives>::Receipt>>, StageError>
+    where
+        Provider: BlockHashReader,
+    {
+        let Some(file) = &self.pending_batch else { return Ok(None) };
+        let Some(batch) = file.load() else { return Ok(None) };
+
+        let range = batch.range;
+        let is_valid = range.first_block == start_block &&
+            (start_block..=max_block).contains(&range.last_block) &&
+            batch.outcome.receipts.len() as u64 == range.last_block - start_block + 1 &&
+            index_gas_usage == (batch.gas_usage.len() == batch.outcome.receipts.len()) &&
+            provider.block_hash(range.last_block)? == Some(range.last_block_hash);
+        if is_valid {
+            return Ok(Some(batch))
+        }
+
+        debug!(target: "sync::stages::execution", ?range, start = start_block, end = max_block, "Discarding pending batch");
+        file.remove();
+        Ok(None)
+    }
+
+    /// Writes the outcome of the executed blocks, starting at `start_block`.
+    ///
+    /// Returns the number of removed reverts that didn't record a change.
+    fn write_outcome<Provider>(
+        &mut self,
+        provider: &Provider,
+        start_block: u64,
+        max_block: u64,
+        mut state: ExecutionOutcome<<E::Primitives as NodePrimitives>::Receipt>,
+        gas_usage: &[Vec<(Address, u64)>],
+        blocks: Vec<RecoveredBlock<<E::Primitives as NodePrimitives>::Block>>,
+    ) -> Result<usize, StageError>
+    where
+        Provider: DBProvider
+            + StatsReader
+            + StateWriter<Receipt = <E::Primitives as NodePrimitives>::Receipt>
+            + GasUsageWriter,
+    {
+        // Prepare the input for post execute commit hook, where an `ExExNotification` will be sent.
+        //
+        // Note: Since we only write to `blocks` if there are any ExExes, we don't need to perform
+        // the `has_exexs` check here as well
+        if !blocks.is_empty() {
+            let previous_input =
+                self.post_execute_commit_input.replace(Chain::new(blocks, state.clone(), None));
+
+            if previous_input.is_some() {
+                // Not processing the previous post execute commit input is a critical error, as it
+                // means that we didn't send the notification to ExExes
+                return Err(StageError::PostExecuteCommit(
+                    "Previous post execute commit input wasn't processed",
+                ))
+            }
+        }
+
+        if self.can_prune_changesets(provider, start_block, max_block)? {
+            let prune_modes = provider.prune_modes_ref();
+
+            // Iterate over all reverts and clear them if pruning is configured.
+            for block_number in start_block..=max_block {
+                let Some(reverts) =
+                    state.bundle.reverts.get_mut((block_number - start_block) as usize)
+                else {
+                    break
+                };
+
+                // If both account history and storage history pruning is configured, clear reverts
+                // for this block.
+                if prune_modes
+                    .account_history
+                    .is_some_and(|m| m.should_prune(block_number, max_block)) &&
+                    prune_modes
+                        .storage_history
+                        .is_some_and(|m| m.should_prune(block_number, max_block))
+                {
+                    reverts.clear();
+                }
+            }
+        }
+
+        // Remove reverts that don't record a change, so they aren't written to the changesets.
+        let compacted_reverts = state.compact_reverts();
+
+        // write output
+        provider.write_state(&state, OriginalValuesKnown::Yes, StorageLocation::StaticFiles)?;
+        provider.index_gas_usage(start_block, gas_usage)?;
+
+        Ok(compacted_reverts)
+    }
+
     /// Performs consistency check on static files.
     ///
     /// This function compares the highest receipt number recorded in the database with that in the
@@ -266,7 +410,8 @@ where
         + StatsReader
         + BlockHashReader
         + StateWriter<Receipt = <E::Primitives as NodePrimitives>::Receipt>
-        + StateCommitmentProvider,
+        + StateCommitmentProvider
+        + GasUsageWriter,
 {
     /// Return the id of the stage
     fn id(&self) -> StageId {
@@ -295,11 +440,9 @@ where
 
         self.ensure_consistency(provider, input.checkpoint().block_number, None)?;
 
-        let db = StateProviderDatabase(LatestStateProviderRef::new(provider));
-        let mut executor = self.evm_config.batch_executor(db);
+        // Only collect the per-contract gas usage if the index is maintained
+        let index_gas_usage = provider.gas_usage_indexed_to()?.is_some();
 
-        // Progress tracking
-        let mut stage_progress = start_block;
         let mut stage_checkpoint = execution_checkpoint(
             &static_file_provider,
             start_block,
@@ -307,6 +450,41 @@ where
             input.checkpoint(),
         )?;
 
+        // Write the blocks that were executed and saved before the node stopped, instead of
+        // executing them again.
+        if let Some(PendingBatch { range, outcome, gas_usage }) =
+            self.load_pending_batch(provider, start_block, max_block, index_gas_usage)?
+        {
+            info!(target: "sync::stages::execution", start = start_block, end = range.last_block, "Writing pending batch");
+
+            let blocks = if self.exex_manager_handle.has_exexs() {
+                provider.recovered_block_range(start_block..=range.last_block)?
+            } else {
+                Vec::new()
+            };
+            self.write_outcome(provider, start_block, max_block, outcome, &gas_usage, blocks)?;
+
+            stage_checkpoint.progress.processed += range.gas_used;
+            return Ok(ExecOutput {
+                checkpoint: StageCheckpoint::new(range.last_block)
+                    .with_execution_stage_checkpoint(stage_checkpoint),
+                done: range.last_block == max_block,
+            })
+        }
+
+        // The workers only run for this batch, so the values they read can't outlive the state
+        // they were read from.
+        let prewarm_cache = self.prewarm.as_ref().map(|_| Arc::new(PrewarmCache::default()));
+        let db = StateProviderDatabase(PrewarmedState::new(
+            LatestStateProviderRef::new(provider),
+            prewarm_cache.clone(),
+        ));
+        let mut executor = self.evm_config.batch_executor(db);
+        let blocks_ahead = self.prewarm.as_ref().map_or(0, |prewarm| prewarm.blocks_ahead());
+
+        // Progress tracking
+        let mut stage_progress = start_block;
+
         let mut fetch_block_duration = Duration::default();
         let mut execution_duration = Duration::default();
 
@@ -322,82 +500,133 @@ where
         let mut cumulative_gas = 0;
         let batch_start = Instant::now();
 
+        let mut gas_usage = Vec::new();
+
         let mut blocks = Vec::new();
         let mut results = Vec::new();
-        for block_number in start_block..=max_block {
-            // Fetch the block
-            let fetch_block_start = Instant::now();
+        let mut pending_batch = self.pending_batch.as_ref().map(PendingBatchFile::writer);
+        std::thread::scope(|scope| {
+            let mut prewarm = self
+                .prewarm
+                .as_ref()
+                .zip(prewarm_cache)
+                .map(|(prewarm, cache)| prewarm.spawn(scope, &self.evm_config, cache));
+            let mut pending = VecDeque::new();
+            let mut next_fetch = start_block;
+
+            for block_number in start_block..=max_block {
+                // Fetch the block, and the upcoming blocks if they are prewarmed
+                let fetch_block_start = Instant::now();
+
+                while next_fetch <= max_block.min(block_number + blocks_ahead) {
+                    // we need the block's transactions but we don't need the transaction hashes
+                    let block = provider
+                        .recovered_block(next_fetch.into(), TransactionVariant::NoHash)?
+                        .ok_or_else(|| ProviderError::HeaderNotFound(next_fetch.into()))?;
+                    if let Some(prewarm) = prewarm.as_mut().filter(|_| next_fetch > block_number) {
+                        prewarm.prewarm(block.clone());
+                    }
+                    pending.push_back(block);
+                    next_fetch += 1;
+                }
+                let block = pending.pop_front().expect("block is fetched");
 
-            // we need the block's transactions but we don't need the transaction hashes
-            let block = provider
-                .recovered_block(block_number.into(), TransactionVariant::NoHash)?
-                .ok_or_else(|| ProviderError::HeaderNotFound(block_number.into()))?;
+                fetch_block_duration += fetch_block_start.elapsed();
 
-            fetch_block_duration += fetch_block_start.elapsed();
+                cumulative_gas += block.header().gas_used();
 
-            cumulative_gas += block.header().gas_used();
+                // Configure the executor to use the current state.
+                trace!(target: "sync::stages::execution", number = block_number, txs = block.body().transactions().len(), "Executing block");
 
-            // Configure the executor to use the current state.
-            trace!(target: "sync::stages::execution", number = block_number, txs = block.body().transactions().len(), "Executing block");
+                // Execute the block
+                let execute_start = Instant::now();
 
-            // Execute the block
-            let execute_start = Instant::now();
+                let result = self.metrics.metered_one(&block, |input| {
+                    executor.execute_one(input).map_err(|error| StageError::Block {
+                        block: Box::new(block.block_with_parent()),
+                        error: BlockErrorKind::Execution(error),
+                    })
+                })?;
 
-            let result = self.metrics.metered_one(&block, |input| {
-                executor.execute_one(input).map_err(|error| StageError::Block {
-                    block: Box::new(block.block_with_parent()),
-                    error: BlockErrorKind::Execution(error),
-                })
-            })?;
+                if let Err(err) = self.consensus.validate_block_post_execution(&block, &result) {
+                    return Err(StageError::Block {
+                        block: Box::new(block.block_with_parent()),
+                        error: BlockErrorKind::Validation(err),
+                    })
+                }
+                if index_gas_usage {
+                    gas_usage.push(block_gas_usage(block.body().transactions(), &result.receipts));
+                }
+                results.push(result);
 
-            if let Err(err) = self.consensus.validate_block_post_execution(&block, &result) {
-                return Err(StageError::Block {
-                    block: Box::new(block.block_with_parent()),
-                    error: BlockErrorKind::Validation(err),
-                })
-            }
-            results.push(result);
-
-            execution_duration += execute_start.elapsed();
-
-            // Log execution throughput
-            if last_log_instant.elapsed() >= log_duration {
-                info!(
-                    target: "sync::stages::execution",
-                    start = last_block,
-                    end = block_number,
-                    throughput = format_gas_throughput(cumulative_gas - last_cumulative_gas, execution_duration - last_execution_duration),
-                    "Executed block range"
-                );
-
-                last_block = block_number + 1;
-                last_execution_duration = execution_duration;
-                last_cumulative_gas = cumulative_gas;
-                last_log_instant = Instant::now();
-            }
+                execution_duration += execute_start.elapsed();
 
-            stage_progress = block_number;
-            stage_checkpoint.progress.processed += block.header().gas_used();
+                if let Some(prewarm) = &prewarm {
+                    prewarm.on_executed(block_number);
+                }
 
-            // If we have ExExes we need to save the block in memory for later
-            if self.exex_manager_handle.has_exexs() {
-                blocks.push(block);
-            }
+                // Pause if execution is ahead of the configured throughput
+                if let Some(governor) = &self.governor {
+                    governor.throttle_gas(block.header().gas_used());
+                }
+
+                // Log execution throughput
+                if last_log_instant.elapsed() >= log_duration {
+                    info!(
+                        target: "sync::stages::execution",
+                        start = last_block,
+                        end = block_number,
+                        throughput = format_gas_throughput(cumulative_gas - last_cumulative_gas, execution_duration - last_execution_duration),
+                        "Executed block range"
+                    );
+
+                    last_block = block_number + 1;
+                    last_execution_duration = execution_duration;
+                    last_cumulative_gas = cumulative_gas;
+                    last_log_instant = Instant::now();
+                }
+
+                stage_progress = block_number;
+                stage_checkpoint.progress.processed += block.header().gas_used();
+
+                // Save the executed blocks, so they aren't executed again if the node stops
+                if let Some((writer, state)) = pending_batch
+                    .as_mut()
+                    .filter(|writer| writer.should_save(block_number - start_block + 1))
+                    .zip(executor.state())
+                {
+                    let range = PendingBatchRange {
+                        first_block: start_block,
+                        last_block: block_number,
+                        last_block_hash: block.hash(),
+                        gas_used: cumulative_gas,
+                    };
+                    writer.save(range, &state.bundle_state, &results, &gas_usage);
+                }
+
+                // If we have ExExes we need to save the block in memory for later
+                if self.exex_manager_handle.has_exexs() {
+                    blocks.push(block);
+                }
 
-            // Check if we should commit now
-            if self.thresholds.is_end_of_batch(
-                block_number - start_block,
-                executor.size_hint() as u64,
-                cumulative_gas,
-                batch_start.elapsed(),
-            ) {
-                break
+                // Check if we should commit now
+                if self.thresholds.is_end_of_batch(
+                    block_number - start_block,
+                    executor.size_hint() as u64,
+                    cumulative_gas,
+                    batch_start.elapsed(),
+                ) {
+                    break
+                }
             }
-        }
+
+            Ok::<_, StageError>(())
+        })?;
+        drop(pending_batch);
 
         // prepare execution output for writing
         let time = Instant::now();
-        let mut state = ExecutionOutcome::from_blocks(
+        let state = ExecutionOutcome::from_blocks(
             start_block,
             executor.into_state().take_bundle(),
             results,
@@ -413,53 +642,9 @@ where
             "Finished executing block range"
         );
 
-        // Prepare the input for post execute commit hook, where an `ExExNotification` will be sent.
-        //
-        // Note: Since we only write to `blocks` if there are any ExExes, we don't need to perform
-        // the `has_exexs` check here as well
-        if !blocks.is_empty() {
-            let previous_input =
-                self.post_execute_commit_input.replace(Chain::new(blocks, state.clone(), None));
-
-            if previous_input.is_some() {
-                // Not processing the previous post execute commit input is a critical error, as it
-                // means that we didn't send the notification to ExExes
-                return Err(StageError::PostExecuteCommit(
-                    "Previous post execute commit input wasn't processed",
-                ))
-            }
-        }
-
         let time = Instant::now();
-
-        if self.can_prune_changesets(provider, start_block, max_block)? {
-            let prune_modes = provider.prune_modes_ref();
-
-            // Iterate over all reverts and clear them if pruning is configured.
-            for block_number in start_block..=max_block {
-                let Some(reverts) =
-                    state.bundle.reverts.get_mut((block_number - start_block) as usize)
-                else {
-                    break
-                };
-
-                // If both account history and storage history pruning is configured, clear reverts
-                // for this block.
-                if prune_modes
-                    .account_history
-                    .is_some_and(|m| m.should_prune(block_number, max_block)) &&
-                    prune_modes
-                        .storage_history
-                        .is_some_and(|m| m.should_prune(block_number, max_block))
-                {
-                    reverts.clear();
-                }
-            }
-        }
-
-        // write output
-        provider.write_state(&state, OriginalValuesKnown::Yes, StorageLocation::StaticFiles)?;
-
+        let compacted_reverts =
+            self.write_outcome(provider, start_block, max_block, state, &gas_usage, blocks)?;
         let db_write_duration = time.elapsed();
         debug!(
             target: "sync::stages::execution",
@@ -467,6 +652,7 @@ where
             execution = ?execution_duration,
             write_preparation = ?write_preparation_duration,
             write = ?db_write_duration,
+            compacted_reverts,
             "Execution time"
         );
 
@@ -479,6 +665,11 @@ where
     }
 
     fn post_execute_commit(&mut self) -> Result<(), StageError> {
+        // The executed blocks are committed, so they don't need to be written again
+        if let Some(file) = &self.pending_batch {
+            file.remove();
+        }
+
         let Some(chain) = self.post_execute_commit_input.take() else { return Ok(()) };
 
         // NOTE: We can ignore the error here, since an error means that the channel is closed,
@@ -507,6 +698,13 @@ where
 
         self.ensure_consistency(provider, input.checkpoint.block_number, Some(unwind_to))?;
 
+        if let Some(file) = &self.pending_batch {
+            file.remove();
+        }
+
+        // Unwind the gas usage index while the receipts of the unwound blocks are still available.
+        provider.unwind_gas_usage_above(unwind_to)?;
+
         // Unwind account and storage changesets, as well as receipts.
         //
         // This also updates `PlainStorageState` and `PlainAccountState`.
@@ -675,8 +873,9 @@ mod tests {
     use reth_evm_ethereum::EthEvmConfig;
     use reth_primitives_traits::{Account, Bytecode, SealedBlock, StorageEntry};
     use reth_provider::{
-        test_utils::create_test_provider_factory, AccountReader, DatabaseProviderFactory,
-        ReceiptProvider, StaticFileProviderFactory,
+        test_utils::{create_test_provider_factory, MockNodeTypesWithDB},
+        AccountReader, DatabaseProviderFactory, ProviderFactory, ReceiptProvider,
+        StaticFileProviderFactory,
     };
     use reth_prune::PruneModes;
     use reth_prune_types::{PruneMode, ReceiptsLogPruneConfig};
@@ -985,6 +1184,151 @@ mod tests {
         }
     }
 
+    /// Inserts the genesis block and block 1 with the state block 1 is executed on, and returns
+    /// block 1.
+    fn insert_block_with_pre_state(
+        factory: &ProviderFactory<MockNodeTypesWithDB>,
+    ) -> SealedBlock<Block> {
+        let provider = factory.provider_rw().unwrap();
+        let mut genesis_rlp = hex!("f901faf901f5a00000000000000000000000000000000000000000000000000000000000000000a01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347942adc25665018aa1fe0e6bc666dac8fc2697ff9baa045571b40ae66ca7480791bbb2887286e4e4c4b1b298b191c889d6959023a32eda056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421a056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421b901000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000083020000808502540be400808000a00000000000000000000000000000000000000000000000000000000000000000880000000000000000c0c0").as_slice();
+        let genesis = SealedBlock::<Block>::decode(&mut genesis_rlp).unwrap();
+        let mut block_rlp = hex!("f90262f901f9a075c371ba45999d87f4542326910a11af515897aebce5265d3f6acd1f1161f82fa01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347942adc25665018aa1fe0e6bc666dac8fc2697ff9baa098f2dcd87c8ae4083e7017a05456c14eea4b1db2032126e27b3b1563d57d7cc0a08151d548273f6683169524b66ca9fe338b9ce42bc3540046c828fd939ae23bcba03f4e5c2ec5b2170b711d97ee755c160457bb58d8daa338e835ec02ae6860bbabb901000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000083020000018502540be40082a8798203e800a00000000000000000000000000000000000000000000000000000000000000000880000000000000000f863f861800a8405f5e10094100000000000000000000000000000000000000080801ba07e09e26678ed4fac08a249ebe8ed680bf9051a5e14ad223e4b2b9d26e0208f37a05f6e3f188e3e6eab7d7d3b6568f5eac7d687b08d307d3154ccd8c87b4630509bc0").as_slice();
+        let block = SealedBlock::<Block>::decode(&mut block_rlp).unwrap();
+        provider.insert_historical_block(genesis.try_recover().unwrap()).unwrap();
+        provider.insert_historical_block(block.clone().try_recover().unwrap()).unwrap();
+        provider
+            .static_file_provider()
+            .latest_writer(StaticFileSegment::Headers)
+            .unwrap()
+            .commit()
+            .unwrap();
+        {
+            let static_file_provider = provider.static_file_provider();
+            let mut receipts_writer =
+                static_file_provider.latest_writer(StaticFileSegment::Receipts).unwrap();
+            receipts_writer.increment_block(0).unwrap();
+            receipts_writer.commit().unwrap();
+        }
+        provider.commit().unwrap();
+
+        // insert pre state
+        let provider = factory.provider_rw().unwrap();
+
+        let db_tx = provider.tx_ref();
+        let acc1 = address!("0x1000000000000000000000000000000000000000");
+        let acc2 = address!("0xa94f5374fce5edbc8e2a8697c15331677e6ebf0b");
+        let code = hex!("5a465a905090036002900360015500");
+        let balance = U256::from(0x3635c9adc5dea00000u128);
+        let code_hash = keccak256(code);
+        db_tx
+            .put::<tables::PlainAccountState>(
+                acc1,
+                Account { nonce: 0, balance: U256::ZERO, bytecode_hash: Some(code_hash) },
+            )
+            .unwrap();
+        db_tx
+            .put::<tables::PlainAccountState>(
+                acc2,
+                Account { nonce: 0, balance, bytecode_hash: None },
+            )
+            .unwrap();
+        db_tx.put::<tables::Bytecodes>(code_hash, Bytecode::new_raw(code.to_vec().into())).unwrap();
+        provider.commit().unwrap();
+
+        block
+    }
+
+    #[tokio::test]
+    async fn execution_is_throttled_by_governor() {
+        let factory = create_test_provider_factory();
+        let block = insert_block_with_pre_state(&factory);
+
+        // at this rate the block takes a fifth of a second to execute
+        let started = Instant::now();
+        let governor = ThroughputGovernor::new(Some(block.gas_used * 5), None);
+
+        let provider = factory.database_provider_rw().unwrap();
+        let mut execution_stage = stage().with_governor(governor);
+        let output = execution_stage
+            .execute(&provider, ExecInput { target: Some(1), checkpoint: None })
+            .unwrap();
+        provider.commit().unwrap();
+
+        assert!(output.done);
+        assert_eq!(output.checkpoint.block_number, 1);
+        assert!(started.elapsed() >= Duration::from_millis(200));
+    }
+
+    #[tokio::test]
+    async fn resuming_pending_batch_matches_execution() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("pending-batch");
+        let input = ExecInput { target: Some(1), checkpoint: None };
+
+        // the node stops after saving the executed block, before the batch is committed
+        let crashed_db = TestStageDB::default();
+        insert_block_with_pre_state(&crashed_db.factory);
+        let provider = crashed_db.factory.database_provider_rw().unwrap();
+        stage().with_pending_batch(path.clone(), 1).execute(&provider, input).unwrap();
+        drop(provider);
+        assert!(path.exists());
+
+        // the block can't be executed with pre-Berlin rules, so the saved batch has to be written
+        let chain_spec = Arc::new(ChainSpecBuilder::mainnet().build());
+        let mut resumed_stage = ExecutionStage::new(
+            EthEvmConfig::new(chain_spec.clone()),
+            Arc::new(EthBeaconConsensus::new(chain_spec)),
+            ExecutionStageThresholds {
+                max_blocks: Some(100),
+                max_changes: None,
+                max_cumulative_gas: None,
+                max_duration: None,
+            },
+            MERKLE_STAGE_DEFAULT_REBUILD_THRESHOLD,
+            ExExManagerHandle::empty(),
+        )
+        .with_pending_batch(path.clone(), 1);
+        let resumed_db = TestStageDB::default();
+        insert_block_with_pre_state(&resumed_db.factory);
+        let provider = resumed_db.factory.database_provider_rw().unwrap();
+        let resumed_output = resumed_stage.execute(&provider, input).unwrap();
+        provider.commit().unwrap();
+        resumed_stage.post_execute_commit().unwrap();
+        assert!(!path.exists());
+
+        let executed_db = TestStageDB::default();
+        insert_block_with_pre_state(&executed_db.factory);
+        let provider = executed_db.factory.database_provider_rw().unwrap();
+        let executed_output = stage().execute(&provider, input).unwrap();
+        provider.commit().unwrap();
+
+        assert_eq!(resumed_output, executed_output);
+        assert_eq!(
+            resumed_db.table::<tables::PlainAccountState>().unwrap(),
+            executed_db.table::<tables::PlainAccountState>().unwrap()
+        );
+        assert_eq!(
+            resumed_db.table::<tables::PlainStorageState>().unwrap(),
+            executed_db.table::<tables::PlainStorageState>().unwrap()
+        );
+        assert_eq!(
+            resumed_db.table::<tables::AccountChangeSets>().unwrap(),
+            executed_db.table::<tables::AccountChangeSets>().unwrap()
+        );
+        assert_eq!(
+            resumed_db.table::<tables::StorageChangeSets>().unwrap(),
+            executed_db.table::<tables::StorageChangeSets>().unwrap()
+        );
+        assert_eq!(
+            resumed_db.table::<tables::Bytecodes>().unwrap(),
+            executed_db.table::<tables::Bytecodes>().unwrap()
+        );
+        assert_eq!(
+            resumed_db.factory.provider().unwrap().receipts_by_block(1.into()).unwrap(),
+            executed_db.factory.provider().unwrap().receipts_by_block(1.into()).unwrap()
+        );
+    }
+
     #[tokio::test]
     async fn sanity_execute_unwind() {
         let factory = create_test_provider_factory();
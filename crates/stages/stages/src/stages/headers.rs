@@ -25,7 +25,11 @@ use reth_stages_api::{
 };
 use reth_static_file_types::StaticFileSegment;
 use reth_storage_errors::provider::ProviderError;
-use std::task::{ready, Context, Poll};
+use std::{
+    collections::{HashMap, HashSet},
+    ops::RangeInclusive,
+    task::{ready, Context, Poll},
+};
 
 use tokio::sync::watch;
 use tracing::*;
@@ -63,6 +67,8 @@ pub struct HeaderStage<Provider, Downloader: HeaderDownloader> {
     hash_collector: Collector<BlockHash, BlockNumber>,
     /// ETL collector with `BlockNumber` -> `BincodeSealedHeader`
     header_collector: Collector<BlockNumber, Bytes>,
+    /// Tracks the headers pushed to the ETL collectors.
+    collected: CollectedHeaders,
     /// Returns true if the ETL collector has all necessary headers to fill the gap.
     is_etl_ready: bool,
 }
@@ -87,6 +93,7 @@ where
             sync_gap: None,
             hash_collector: Collector::new(etl_config.file_size / 2, etl_config.dir.clone()),
             header_collector: Collector::new(etl_config.file_size / 2, etl_config.dir),
+            collected: CollectedHeaders::default(),
             is_etl_ready: false,
         }
     }
@@ -122,6 +129,7 @@ where
         // order
         let mut writer = static_file_provider.latest_writer(StaticFileSegment::Headers)?;
         let interval = (total_headers / 10).max(1);
+        let mut superseded_hashes = HashSet::new();
         for (index, header) in self.header_collector.iter()?.enumerate() {
             let (_, header_buf) = header?;
 
@@ -138,6 +146,16 @@ where
             if header.number() == 0 {
                 continue
             }
+
+            // Skip headers that were collected more than once, because the sync target changed
+            // while downloading.
+            if self.collected.is_superseded(header.number(), *header_hash) {
+                superseded_hashes.insert(*header_hash);
+                continue
+            }
+            if header.number() <= last_header_number {
+                continue
+            }
             last_header_number = header.number();
 
             // Increase total difficulty
@@ -167,6 +185,7 @@ where
 
         // Since ETL sorts all entries by hashes, we are either appending (first sync) or inserting
         // in order (further syncs).
+        let mut previous_hash = None;
         for (index, hash_to_number) in self.hash_collector.iter()?.enumerate() {
             let (hash, number) = hash_to_number?;
 
@@ -174,6 +193,16 @@ where
                 info!(target: "sync::stages::headers", progress = %format!("{:.2}%", (index as f64 / total_headers as f64) * 100.0), "Writing headers hash index");
             }
 
+            if self.collected.has_duplicates() {
+                // Duplicate hashes are sorted next to each other.
+                if previous_hash.as_ref() == Some(&hash) ||
+                    superseded_hashes.contains(&B256::from_slice(&hash))
+                {
+                    continue
+                }
+                previous_hash = Some(hash.clone());
+            }
+
             if first_sync {
                 cursor_header_numbers.append(
                     RawKey::<BlockHash>::from_vec(hash),
@@ -251,6 +280,7 @@ where
                     for header in headers {
                         let header_number = header.number();
 
+                        self.collected.insert(header_number, header.hash());
                         self.hash_collector.insert(header.hash(), header_number)?;
                         self.header_collector.insert(
                             header_number,
@@ -306,12 +336,13 @@ where
         self.is_etl_ready = false;
 
         // Write the headers and related tables to DB from ETL space
-        let to_be_processed = self.hash_collector.len() as u64;
+        let to_be_processed = self.hash_collector.len() as u64 - self.collected.duplicates;
         let last_header_number = self.write_headers(provider)?;
 
         // Clear ETL collectors
         self.hash_collector.clear();
         self.header_collector.clear();
+        self.collected = CollectedHeaders::default();
 
         Ok(ExecOutput {
             checkpoint: StageCheckpoint::new(last_header_number).with_headers_stage_checkpoint(
@@ -400,6 +431,45 @@ where
     }
 }
 
+/// Tracks the headers pushed to the ETL collectors of the [`HeaderStage`].
+///
+/// If the sync target changes while downloading, the downloader starts over from the new target
+/// and can return headers for block numbers that were already collected. The headers collected
+/// last supersede the earlier ones.
+#[derive(Debug, Default)]
+struct CollectedHeaders {
+    /// Lowest and highest collected block number.
+    range: Option<RangeInclusive<BlockNumber>>,
+    /// Hash of the header collected last, for block numbers that were collected more than once.
+    latest: HashMap<BlockNumber, BlockHash>,
+    /// Number of headers collected for block numbers that were already collected.
+    duplicates: u64,
+}
+
+impl CollectedHeaders {
+    /// Records a collected header.
+    fn insert(&mut self, number: BlockNumber, hash: BlockHash) {
+        match &mut self.range {
+            Some(range) if range.contains(&number) => {
+                self.latest.insert(number, hash);
+                self.duplicates += 1;
+            }
+            Some(range) => *range = (*range.start()).min(number)..=(*range.end()).max(number),
+            None => self.range = Some(number..=number),
+        }
+    }
+
+    /// Returns true if any block number was collected more than once.
+    const fn has_duplicates(&self) -> bool {
+        self.duplicates > 0
+    }
+
+    /// Returns true if a different header was collected for the same block number later.
+    fn is_superseded(&self, number: BlockNumber, hash: BlockHash) -> bool {
+        self.latest.get(&number).is_some_and(|latest| *latest != hash)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -717,4 +787,22 @@ mod tests {
         assert!(runner.stage().hash_collector.is_empty());
         assert!(runner.stage().header_collector.is_empty());
     }
+
+    #[test]
+    fn collected_headers_superseded() {
+        let mut collected = CollectedHeaders::default();
+        let (stale, latest) = (B256::random(), B256::random());
+        collected.insert(3, stale);
+        collected.insert(2, B256::random());
+        assert!(!collected.has_duplicates());
+
+        // the downloader started over from a new sync target
+        collected.insert(4, B256::random());
+        collected.insert(3, latest);
+        assert!(collected.has_duplicates());
+        assert_eq!(collected.duplicates, 1);
+        assert!(collected.is_superseded(3, stale));
+        assert!(!collected.is_superseded(3, latest));
+        assert!(!collected.is_superseded(4, stale));
+    }
 }
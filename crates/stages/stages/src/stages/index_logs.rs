@@ -0,0 +1,382 @@
+use super::load_history_indices;
+use alloy_consensus::TxReceipt;
+use alloy_primitives::{Address, BlockNumber, B256};
+use reth_config::config::{EtlConfig, IndexLogsConfig};
+use reth_db_api::{
+    models::ShardedKey,
+    table::{Decode, Key},
+    tables,
+    transaction::DbTxMut,
+    BlockNumberList,
+};
+use reth_etl::Collector;
+use reth_provider::{BlockReader, DBProvider, HistoryWriter, PruneCheckpointReader};
+use reth_prune_types::PruneSegment;
+use reth_stages_api::{
+    ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput,
+};
+use std::{collections::HashMap, hash::Hash};
+use tracing::info;
+
+/// Number of blocks whose receipts are read at once.
+const RECEIPTS_BATCH_SIZE: u64 = 1_000;
+
+/// Number of blocks before pushing indices from cache to [`Collector`].
+const CACHE_THRESHOLD: u64 = 100_000;
+
+/// Stage is indexing the addresses and topics of the logs in the receipts generated in
+/// [`ExecutionStage`][crate::stages::ExecutionStage], so `eth_getLogs` can skip blocks without
+/// matching logs. For more information on index sharding take a look at
+/// [`tables::LogAddressIndex`] and [`tables::LogTopicIndex`].
+///
+/// The stage is optional and identified by [`StageId::INDEX_LOGS`]. The index always covers all
+/// blocks up to the stage checkpoint, blocks with pruned receipts are not indexed.
+#[derive(Debug)]
+pub struct IndexLogsStage {
+    /// Number of blocks after which the control
+    /// flow will be returned to the pipeline for commit.
+    pub commit_threshold: u64,
+    /// ETL configuration
+    pub etl_config: EtlConfig,
+}
+
+impl IndexLogsStage {
+    /// Create new instance of [`IndexLogsStage`].
+    pub const fn new(config: IndexLogsConfig, etl_config: EtlConfig) -> Self {
+        Self { commit_threshold: config.commit_threshold, etl_config }
+    }
+}
+
+impl Default for IndexLogsStage {
+    fn default() -> Self {
+        Self { commit_threshold: 100_000, etl_config: EtlConfig::default() }
+    }
+}
+
+impl<Provider> Stage<Provider> for IndexLogsStage
+where
+    Provider: DBProvider<Tx: DbTxMut> + BlockReader + HistoryWriter + PruneCheckpointReader,
+{
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::INDEX_LOGS
+    }
+
+    /// Execute the stage.
+    fn execute(&mut self, provider: &Provider, input: ExecInput) -> Result<ExecOutput, StageError> {
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        let (mut range, is_final_range) =
+            input.next_block_range_with_threshold(self.commit_threshold);
+        let first_sync = input.checkpoint().block_number == 0;
+
+        // On first sync we clear the tables, the index is required to cover all blocks up to the
+        // checkpoint.
+        if first_sync {
+            provider.tx_ref().clear::<tables::LogAddressIndex>()?;
+            provider.tx_ref().clear::<tables::LogTopicIndex>()?;
+            range = 0..=*range.end();
+        }
+
+        // Blocks with pruned receipts have no logs to index.
+        let receipts_range =
+            (*range.start()).max(first_unpruned_receipts(provider)?)..=*range.end();
+
+        info!(target: "sync::stages::index_logs::exec", ?first_sync, ?range, "Collecting indices");
+        let mut addresses = LogIndexCollector::new(&self.etl_config);
+        let mut topics = LogIndexCollector::new(&self.etl_config);
+        let mut flush_counter = 0;
+        for start in receipts_range.step_by(RECEIPTS_BATCH_SIZE as usize) {
+            let end = (start + RECEIPTS_BATCH_SIZE - 1).min(*range.end());
+            for (block_number, receipts) in
+                (start..=end).zip(provider.receipts_by_block_range(start..=end)?)
+            {
+                for log in receipts.iter().flat_map(|receipt| receipt.logs()) {
+                    addresses.insert(log.address, block_number);
+                    for topic in log.topics() {
+                        topics.insert(*topic, block_number);
+                    }
+                }
+            }
+
+            // Make sure we only flush the cache every CACHE_THRESHOLD blocks.
+            flush_counter += end - start + 1;
+            if flush_counter >= CACHE_THRESHOLD {
+                addresses.flush()?;
+                topics.flush()?;
+                flush_counter = 0;
+            }
+        }
+
+        info!(target: "sync::stages::index_logs::exec", "Loading indices into database");
+        load_history_indices::<_, tables::LogAddressIndex, _>(
+            provider,
+            addresses.finish()?,
+            first_sync,
+            ShardedKey::new,
+            ShardedKey::<Address>::decode_owned,
+            |key| key.key,
+        )?;
+        load_history_indices::<_, tables::LogTopicIndex, _>(
+            provider,
+            topics.finish()?,
+            first_sync,
+            ShardedKey::new,
+            ShardedKey::<B256>::decode_owned,
+            |key| key.key,
+        )?;
+
+        Ok(ExecOutput { checkpoint: StageCheckpoint::new(*range.end()), done: is_final_range })
+    }
+
+    /// Unwind the stage.
+    fn unwind(
+        &mut self,
+        provider: &Provider,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        let (range, unwind_progress, _) =
+            input.unwind_block_range_with_threshold(self.commit_threshold);
+
+        let receipts_range =
+            (*range.start()).max(first_unpruned_receipts(provider)?)..=*range.end();
+        if !receipts_range.is_empty() {
+            let receipts = provider.receipts_by_block_range(receipts_range)?;
+            provider.unwind_log_indices(
+                receipts.iter().flatten().flat_map(|receipt| receipt.logs()),
+                *range.start(),
+            )?;
+        }
+
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(unwind_progress) })
+    }
+}
+
+/// Returns the first block whose receipts haven't been pruned.
+fn first_unpruned_receipts<Provider: PruneCheckpointReader>(
+    provider: &Provider,
+) -> Result<BlockNumber, StageError> {
+    Ok(provider
+        .get_prune_checkpoint(PruneSegment::Receipts)?
+        .and_then(|checkpoint| checkpoint.block_number)
+        .map_or(0, |block_number| block_number + 1))
+}
+
+/// Caches the blocks of each indexed key and moves them to a [`Collector`] in shards, like
+/// [`collect_history_indices`](super::collect_history_indices) does for changesets.
+struct LogIndexCollector<K> {
+    /// Blocks of each key since the last flush.
+    cache: HashMap<K, Vec<BlockNumber>>,
+    /// Collected shards.
+    collector: Collector<ShardedKey<K>, BlockNumberList>,
+}
+
+impl<K> LogIndexCollector<K>
+where
+    K: Copy + Eq + Hash,
+    ShardedKey<K>: Key,
+{
+    fn new(etl_config: &EtlConfig) -> Self {
+        Self {
+            cache: HashMap::default(),
+            collector: Collector::new(etl_config.file_size, etl_config.dir.clone()),
+        }
+    }
+
+    /// Records that the block contains a log with the key.
+    fn insert(&mut self, key: K, block_number: BlockNumber) {
+        let blocks = self.cache.entry(key).or_default();
+        // a block usually contains many logs with the same key
+        if blocks.last() != Some(&block_number) {
+            blocks.push(block_number);
+        }
+    }
+
+    /// Moves the cached blocks to the collector.
+    fn flush(&mut self) -> Result<(), StageError> {
+        for (key, blocks) in self.cache.drain() {
+            let last = *blocks.last().expect("qed");
+            self.collector
+                .insert(ShardedKey::new(key, last), BlockNumberList::new_pre_sorted(blocks))?;
+        }
+        Ok(())
+    }
+
+    /// Flushes the cache and returns the collector.
+    fn finish(mut self) -> Result<Collector<ShardedKey<K>, BlockNumberList>, StageError> {
+        self.flush()?;
+        Ok(self.collector)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TestStageDB;
+    use alloy_primitives::{address, b256, Log};
+    use reth_db_api::models::StoredBlockBodyIndices;
+    use reth_ethereum_primitives::Receipt;
+    use reth_provider::{
+        DatabaseProviderFactory, PruneCheckpointWriter, ReceiptProvider, StageCheckpointReader,
+        StageCheckpointWriter,
+    };
+    use reth_prune_types::{PruneCheckpoint, PruneMode};
+    use std::collections::BTreeMap;
+
+    const ADDRESS: Address = address!("0x0000000000000000000000000000000000000001");
+    const OTHER_ADDRESS: Address = address!("0x0000000000000000000000000000000000000002");
+    const TOPIC: B256 = b256!("0x0000000000000000000000000000000000000000000000000000000000000003");
+    const OTHER_TOPIC: B256 =
+        b256!("0x0000000000000000000000000000000000000000000000000000000000000004");
+
+    /// Inserts one receipt per block with the given logs.
+    fn setup(db: &TestStageDB, blocks: &[Vec<(Address, Vec<B256>)>]) {
+        db.commit(|tx| {
+            for (block, logs) in blocks.iter().enumerate() {
+                tx.put::<tables::BlockBodyIndices>(
+                    block as BlockNumber,
+                    StoredBlockBodyIndices { first_tx_num: block as u64, tx_count: 1 },
+                )?;
+                let logs = logs
+                    .iter()
+                    .map(|(address, topics)| {
+                        Log::new_unchecked(*address, topics.clone(), Default::default())
+                    })
+                    .collect();
+                tx.put::<tables::Receipts>(block as u64, Receipt { logs, ..Default::default() })?;
+            }
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    fn address_index(db: &TestStageDB) -> BTreeMap<Address, Vec<BlockNumber>> {
+        db.table::<tables::LogAddressIndex>()
+            .unwrap()
+            .into_iter()
+            .map(|(key, list)| (key.key, list.iter().collect()))
+            .collect()
+    }
+
+    fn topic_index(db: &TestStageDB) -> BTreeMap<B256, Vec<BlockNumber>> {
+        db.table::<tables::LogTopicIndex>()
+            .unwrap()
+            .into_iter()
+            .map(|(key, list)| (key.key, list.iter().collect()))
+            .collect()
+    }
+
+    #[test]
+    fn index_and_unwind_logs() {
+        let db = TestStageDB::default();
+        setup(
+            &db,
+            &[
+                vec![],
+                vec![(ADDRESS, vec![TOPIC]), (ADDRESS, vec![TOPIC, OTHER_TOPIC])],
+                vec![(OTHER_ADDRESS, vec![OTHER_TOPIC])],
+                vec![(OTHER_ADDRESS, vec![TOPIC])],
+                vec![],
+            ],
+        );
+
+        let mut stage = IndexLogsStage::default();
+        let provider = db.factory.database_provider_rw().unwrap();
+        let out =
+            stage.execute(&provider, ExecInput { target: Some(4), checkpoint: None }).unwrap();
+        assert_eq!(out, ExecOutput { checkpoint: StageCheckpoint::new(4), done: true });
+        provider.save_stage_checkpoint(StageId::INDEX_LOGS, out.checkpoint).unwrap();
+        provider.commit().unwrap();
+
+        assert_eq!(
+            address_index(&db),
+            BTreeMap::from([(ADDRESS, vec![1]), (OTHER_ADDRESS, vec![2, 3])])
+        );
+        assert_eq!(
+            topic_index(&db),
+            BTreeMap::from([(TOPIC, vec![1, 3]), (OTHER_TOPIC, vec![1, 2])])
+        );
+
+        // addresses and topics are intersected, the blocks above the checkpoint are not covered
+        let provider = db.factory.provider().unwrap();
+        let indexed = provider
+            .indexed_log_blocks(0..=10, &[OTHER_ADDRESS], &[vec![TOPIC, OTHER_TOPIC], vec![]])
+            .unwrap()
+            .unwrap();
+        assert_eq!(indexed.indexed_to, 4);
+        assert_eq!(indexed.blocks, vec![2, 3]);
+        assert!(!indexed.may_contain_any(0..=1));
+        assert!(indexed.may_contain_any(3..=4));
+        assert!(indexed.may_contain(5));
+        assert_eq!(
+            provider.indexed_log_blocks(2..=4, &[ADDRESS], &[]).unwrap().unwrap().blocks,
+            Vec::<BlockNumber>::new()
+        );
+        // unconstrained filters don't use the index
+        assert_eq!(provider.indexed_log_blocks(0..=4, &[], &[vec![]]).unwrap(), None);
+        drop(provider);
+
+        let provider = db.factory.database_provider_rw().unwrap();
+        let input =
+            UnwindInput { checkpoint: StageCheckpoint::new(4), unwind_to: 2, ..Default::default() };
+        let out = stage.unwind(&provider, input).unwrap();
+        assert_eq!(out, UnwindOutput { checkpoint: StageCheckpoint::new(2) });
+        provider.commit().unwrap();
+
+        assert_eq!(
+            address_index(&db),
+            BTreeMap::from([(ADDRESS, vec![1]), (OTHER_ADDRESS, vec![2])])
+        );
+        assert_eq!(topic_index(&db), BTreeMap::from([(TOPIC, vec![1]), (OTHER_TOPIC, vec![1, 2])]));
+    }
+
+    #[test]
+    fn index_saved_blocks_and_skip_pruned_receipts() {
+        let db = TestStageDB::default();
+        setup(&db, &[vec![], vec![(ADDRESS, vec![TOPIC])], vec![]]);
+
+        let mut stage = IndexLogsStage::default();
+        let provider = db.factory.database_provider_rw().unwrap();
+        let out =
+            stage.execute(&provider, ExecInput { target: Some(2), checkpoint: None }).unwrap();
+        provider.save_stage_checkpoint(StageId::INDEX_LOGS, out.checkpoint).unwrap();
+
+        // blocks that don't extend the index are ignored
+        let log = Log::new_unchecked(ADDRESS, vec![OTHER_TOPIC], Default::default());
+        provider.index_logs(4..=4, [(4, &log)]).unwrap();
+        assert_eq!(
+            provider.get_stage_checkpoint(StageId::INDEX_LOGS).unwrap(),
+            Some(out.checkpoint)
+        );
+
+        // blocks saved by the engine extend the index
+        provider.index_logs(3..=4, [(3, &log), (3, &log), (4, &log)]).unwrap();
+        assert_eq!(
+            provider.get_stage_checkpoint(StageId::INDEX_LOGS).unwrap(),
+            Some(StageCheckpoint::new(4))
+        );
+        provider.commit().unwrap();
+        assert_eq!(address_index(&db), BTreeMap::from([(ADDRESS, vec![1, 3, 4])]));
+        assert_eq!(topic_index(&db), BTreeMap::from([(TOPIC, vec![1]), (OTHER_TOPIC, vec![3, 4])]));
+
+        // blocks with pruned receipts are not covered by the index
+        let provider = db.factory.database_provider_rw().unwrap();
+        provider
+            .save_prune_checkpoint(
+                PruneSegment::Receipts,
+                PruneCheckpoint {
+                    block_number: Some(1),
+                    tx_number: Some(1),
+                    prune_mode: PruneMode::Before(2),
+                },
+            )
+            .unwrap();
+        assert_eq!(provider.indexed_log_blocks(0..=4, &[ADDRESS], &[]).unwrap(), None);
+        assert_eq!(
+            provider.indexed_log_blocks(2..=4, &[ADDRESS], &[]).unwrap().unwrap().blocks,
+            vec![3, 4]
+        );
+    }
+}
@@ -15,9 +15,11 @@ use reth_stages_api::{
     BlockErrorKind, EntitiesCheckpoint, ExecInput, ExecOutput, MerkleCheckpoint, Stage,
     StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput,
 };
-use reth_trie::{IntermediateStateRootState, StateRoot, StateRootProgress, StoredSubNode};
-use reth_trie_db::DatabaseStateRoot;
-use std::fmt::Debug;
+use reth_trie::{
+    IntermediateStateRootState, KeccakKeyHasher, StateRoot, StateRootProgress, StoredSubNode,
+};
+use reth_trie_db::{ChangedKeys, DatabaseStateRoot, PrefixSetLoader, PrefixSetStore};
+use std::{fmt::Debug, ops::RangeInclusive};
 use tracing::*;
 
 // TODO: automate the process outlined below so the user can just send in a debugging package
@@ -82,6 +84,8 @@ pub enum MerkleStage {
         /// incremental mode will calculate the state root by calculating the new state root for
         /// some number of blocks, repeating until we reach the desired block number.
         incremental_threshold: u64,
+        /// Store for the changed keys of incremental chunks, if enabled.
+        prefix_set_store: Option<PrefixSetStore>,
     },
     /// The unwind portion of the merkle stage.
     Unwind,
@@ -95,6 +99,8 @@ pub enum MerkleStage {
         /// incremental mode will calculate the state root by calculating the new state root for
         /// some number of blocks, repeating until we reach the desired block number.
         incremental_threshold: u64,
+        /// Store for the changed keys of incremental chunks, if enabled.
+        prefix_set_store: Option<PrefixSetStore>,
     },
 }
 
@@ -104,6 +110,7 @@ impl MerkleStage {
         Self::Execution {
             rebuild_threshold: MERKLE_STAGE_DEFAULT_REBUILD_THRESHOLD,
             incremental_threshold: MERKLE_STAGE_DEFAULT_INCREMENTAL_THRESHOLD,
+            prefix_set_store: None,
         }
     }
 
@@ -114,7 +121,71 @@ impl MerkleStage {
 
     /// Create new instance of [`MerkleStage::Execution`].
     pub const fn new_execution(rebuild_threshold: u64, incremental_threshold: u64) -> Self {
-        Self::Execution { rebuild_threshold, incremental_threshold }
+        Self::Execution { rebuild_threshold, incremental_threshold, prefix_set_store: None }
+    }
+
+    /// Sets the store for the changed keys of incremental chunks.
+    ///
+    /// If set, the changed keys of every chunk are persisted before its root is computed, so that
+    /// the changesets of the chunk don't have to be walked again if the stage is restarted before
+    /// it completed. This has no effect for [`MerkleStage::Unwind`].
+    pub fn with_prefix_set_store(mut self, store: Option<PrefixSetStore>) -> Self {
+        match &mut self {
+            Self::Execution { prefix_set_store, .. } => *prefix_set_store = store,
+            Self::Unwind => {}
+            #[cfg(any(test, feature = "test-utils"))]
+            Self::Both { prefix_set_store, .. } => *prefix_set_store = store,
+        }
+        self
+    }
+
+    /// Returns the store for the changed keys of incremental chunks, if enabled.
+    pub const fn prefix_set_store(&self) -> Option<&PrefixSetStore> {
+        match self {
+            Self::Execution { prefix_set_store, .. } => prefix_set_store.as_ref(),
+            Self::Unwind => None,
+            #[cfg(any(test, feature = "test-utils"))]
+            Self::Both { prefix_set_store, .. } => prefix_set_store.as_ref(),
+        }
+    }
+
+    /// Loads the hashed keys changed in the given block range.
+    ///
+    /// If a [`PrefixSetStore`] is set, the keys are read from it, or written to it after they
+    /// were loaded from the changesets.
+    fn load_changed_keys<Provider>(
+        &self,
+        provider: &Provider,
+        range: RangeInclusive<BlockNumber>,
+    ) -> Result<ChangedKeys, StageError>
+    where
+        Provider: DBProvider + HeaderProvider,
+    {
+        let loader = PrefixSetLoader::<_, KeccakKeyHasher>::new(provider.tx_ref());
+        let Some(store) = self.prefix_set_store() else {
+            return Ok(loader.load_changed_keys(range)?)
+        };
+
+        let last_block_hash = provider
+            .sealed_header(*range.end())?
+            .ok_or_else(|| ProviderError::HeaderNotFound((*range.end()).into()))?
+            .hash();
+        match store.get(&range, last_block_hash) {
+            Ok(Some(changed_keys)) => {
+                debug!(target: "sync::stages::merkle::exec", ?range, "Loaded stored prefix sets");
+                return Ok(changed_keys)
+            }
+            Ok(None) => {}
+            Err(err) => {
+                warn!(target: "sync::stages::merkle::exec", %err, ?range, "Failed to read stored prefix sets")
+            }
+        }
+
+        let changed_keys = loader.load_changed_keys(range.clone())?;
+        if let Err(err) = store.insert(&range, last_block_hash, &changed_keys) {
+            warn!(target: "sync::stages::merkle::exec", %err, ?range, "Failed to store prefix sets");
+        }
+        Ok(changed_keys)
     }
 
     /// Gets the hashing progress
@@ -178,11 +249,11 @@ where
                 info!(target: "sync::stages::merkle::unwind", "Stage is always skipped");
                 return Ok(ExecOutput::done(StageCheckpoint::new(input.target())))
             }
-            Self::Execution { rebuild_threshold, incremental_threshold } => {
+            Self::Execution { rebuild_threshold, incremental_threshold, .. } => {
                 (*rebuild_threshold, *incremental_threshold)
             }
             #[cfg(any(test, feature = "test-utils"))]
-            Self::Both { rebuild_threshold, incremental_threshold } => {
+            Self::Both { rebuild_threshold, incremental_threshold, .. } => {
                 (*rebuild_threshold, *incremental_threshold)
             }
         };
@@ -287,8 +358,13 @@ where
                     chunk_range = ?chunk_range,
                     "Processing chunk"
                 );
-                let (root, updates) =
-                StateRoot::incremental_root_with_updates(provider.tx_ref(), chunk_range)
+                let changed_keys = self.load_changed_keys(provider, chunk_range)?;
+                let tx = provider.tx_ref();
+                let prefix_sets =
+                    PrefixSetLoader::<_, KeccakKeyHasher>::new(tx).prefix_sets(changed_keys)?;
+                let (root, updates) = StateRoot::from_tx(tx)
+                    .with_prefix_sets(prefix_sets)
+                    .root_with_updates()
                     .map_err(|e| {
                         error!(target: "sync::stages::merkle", %e, ?current_block_number, ?to_block, "Incremental state root failed! {INVALID_STATE_ROOT_ERROR_MESSAGE}");
                         StageError::Fatal(Box::new(e))
@@ -322,6 +398,13 @@ where
 
         validate_state_root(trie_root, SealedHeader::seal_slow(target_block), to_block)?;
 
+        // The stored prefix sets of the executed range are not needed anymore
+        if let Some(store) = self.prefix_set_store() {
+            if let Err(err) = store.remove_until(to_block) {
+                warn!(target: "sync::stages::merkle::exec", %err, "Failed to remove stored prefix sets");
+            }
+        }
+
         Ok(ExecOutput {
             checkpoint: StageCheckpoint::new(to_block)
                 .with_entities_stage_checkpoint(entities_checkpoint),
@@ -596,6 +679,7 @@ mod tests {
             Self::S::Both {
                 rebuild_threshold: self.clean_threshold,
                 incremental_threshold: self.incremental_threshold,
+                prefix_set_store: None,
             }
         }
     }
@@ -12,10 +12,16 @@ mod hashing_storage;
 mod headers;
 /// Index history of account changes
 mod index_account_history;
+/// Index of log addresses and topics
+mod index_logs;
 /// Index history of storage changes
 mod index_storage_history;
 /// Stage for computing state root.
 mod merkle;
+/// Pending batches of the execution stage.
+mod pending_batch;
+/// Prewarming of the execution stage.
+mod prewarm;
 mod prune;
 /// The s3 download stage
 mod s3;
@@ -32,8 +38,10 @@ pub use hashing_account::*;
 pub use hashing_storage::*;
 pub use headers::*;
 pub use index_account_history::*;
+pub use index_logs::*;
 pub use index_storage_history::*;
 pub use merkle::*;
+pub use prewarm::{ExecutionPrewarm, LatestStateFactory};
 pub use prune::*;
 pub use s3::*;
 pub use sender_recovery::*;
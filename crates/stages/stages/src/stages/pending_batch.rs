@@ -0,0 +1,352 @@
+//! Persistence of the executed, but not yet written blocks of an
+//! [`ExecutionStage`](super::ExecutionStage) batch.
+//!
+//! The execution stage only writes its state once the whole batch is executed. The pending batch is
+//! saved to a file in regular intervals, so that after a crash the stage can write the saved blocks
+//! instead of executing them again.
+
+use alloy_eips::eip7685::Requests;
+use alloy_primitives::{map::HashSet, Address, BlockHash, BlockNumber, B256};
+use reth_execution_types::BlockExecutionResult;
+use reth_primitives_traits::serde_bincode_compat::SerdeBincodeCompat;
+use reth_provider::ExecutionOutcome;
+use reth_revm::{
+    bytecode::Bytecode,
+    db::{states::reverts::AccountRevert, BundleAccount, BundleState},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, BufWriter, Write},
+    path::PathBuf,
+};
+use tracing::*;
+
+/// A record appended to the file each time the batch is saved, with the blocks executed since the
+/// previous record: the range of the batch so far, the accounts and contracts changed by the new
+/// blocks, and the reverts, receipts, requests and per-contract gas usage of the new blocks.
+type PendingBatchRecord<R> = (
+    PendingBatchRange,
+    Vec<(Address, BundleAccount)>,
+    Vec<(B256, Bytecode)>,
+    Vec<Vec<(Address, AccountRevert)>>,
+    Vec<Vec<R>>,
+    Vec<Requests>,
+    Vec<Vec<(Address, u64)>>,
+);
+
+/// The range of a saved batch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct PendingBatchRange {
+    /// The first block of the batch.
+    pub(crate) first_block: BlockNumber,
+    /// The last executed block of the batch.
+    pub(crate) last_block: BlockNumber,
+    /// The hash of the last executed block, to detect that the chain changed since.
+    pub(crate) last_block_hash: BlockHash,
+    /// The gas used by the executed blocks.
+    pub(crate) gas_used: u64,
+}
+
+/// A batch loaded from the file.
+#[derive(Debug)]
+pub(crate) struct PendingBatch<R> {
+    /// The range of the batch.
+    pub(crate) range: PendingBatchRange,
+    /// The execution outcome of the batch.
+    pub(crate) outcome: ExecutionOutcome<R>,
+    /// The per-contract gas usage of the blocks, see [`reth_provider::block_gas_usage`].
+    pub(crate) gas_usage: Vec<Vec<(Address, u64)>>,
+}
+
+/// The file the pending batch is saved to.
+///
+/// The file is a sequence of records, each saving only the blocks executed since the previous
+/// one, so that saving a batch doesn't get slower the more blocks it has.
+#[derive(Debug, Clone)]
+pub(crate) struct PendingBatchFile {
+    /// The path of the file.
+    path: PathBuf,
+    /// The number of executed blocks after which the batch is saved again.
+    save_interval: u64,
+}
+
+impl PendingBatchFile {
+    /// Creates a new instance for the given path.
+    pub(crate) const fn new(path: PathBuf, save_interval: u64) -> Self {
+        Self { path, save_interval }
+    }
+
+    /// Returns true if the batch should be saved after executing the given number of blocks.
+    pub(crate) const fn should_save(&self, executed_blocks: u64) -> bool {
+        self.save_interval > 0 && executed_blocks % self.save_interval == 0
+    }
+
+    /// Returns a writer that saves a new batch, replacing the previously saved one on its first
+    /// save.
+    pub(crate) fn writer(&self) -> PendingBatchWriter<'_> {
+        PendingBatchWriter {
+            file: self,
+            writer: None,
+            failed: false,
+            saved_blocks: 0,
+            saved_contracts: HashSet::default(),
+        }
+    }
+
+    /// Loads the saved batch, if any.
+    ///
+    /// A record that was only partially written, because the node stopped while saving it, is
+    /// ignored. A file without any readable record is removed.
+    pub(crate) fn load<R: SerdeBincodeCompat>(&self) -> Option<PendingBatch<R>> {
+        if !self.path.exists() {
+            return None
+        }
+
+        let mut reader = match File::open(&self.path) {
+            Ok(file) => BufReader::new(file),
+            Err(err) => {
+                warn!(target: "sync::stages::execution", %err, path = ?self.path, "Failed to open pending batch");
+                return None
+            }
+        };
+
+        let mut range = None;
+        let mut bundle = BundleState::default();
+        let mut receipts = Vec::new();
+        let mut requests = Vec::new();
+        let mut gas_usage = Vec::new();
+        loop {
+            match reader.fill_buf() {
+                Ok([]) => break,
+                Ok(_) => {}
+                Err(err) => {
+                    warn!(target: "sync::stages::execution", %err, path = ?self.path, "Failed to read pending batch");
+                    break
+                }
+            }
+
+            match bincode::deserialize_from::<_, PendingBatchRecord<R::BincodeRepr<'static>>>(
+                &mut reader,
+            ) {
+                Ok((
+                    record_range,
+                    record_state,
+                    record_contracts,
+                    record_reverts,
+                    record_receipts,
+                    record_requests,
+                    record_gas_usage,
+                )) => {
+                    range = Some(record_range);
+                    bundle.state.extend(record_state);
+                    bundle.contracts.extend(record_contracts);
+                    bundle.reverts.extend(record_reverts);
+                    receipts.extend(record_receipts.into_iter().map(|receipts| {
+                        receipts.into_iter().map(R::from_repr).collect::<Vec<_>>()
+                    }));
+                    requests.extend(record_requests);
+                    gas_usage.extend(record_gas_usage);
+                }
+                Err(err) => {
+                    warn!(target: "sync::stages::execution", %err, path = ?self.path, "Ignoring unreadable pending batch record");
+                    break
+                }
+            }
+        }
+
+        let Some(range) = range else {
+            self.remove();
+            return None
+        };
+        bundle.state_size = bundle.state.values().map(BundleAccount::size_hint).sum();
+        bundle.reverts_size =
+            bundle.reverts.iter().flatten().map(|(_, revert)| revert.size_hint()).sum();
+
+        Some(PendingBatch {
+            range,
+            outcome: ExecutionOutcome::new(bundle, receipts, range.first_block, requests),
+            gas_usage,
+        })
+    }
+
+    /// Removes the saved batch, if any.
+    pub(crate) fn remove(&self) {
+        if self.path.exists() {
+            if let Err(err) = reth_fs_util::remove_file(&self.path) {
+                warn!(target: "sync::stages::execution", %err, path = ?self.path, "Failed to remove pending batch");
+            }
+        }
+    }
+}
+
+/// Saves the blocks of a batch to a [`PendingBatchFile`] while they're executed.
+#[derive(Debug)]
+pub(crate) struct PendingBatchWriter<'a> {
+    /// The file the batch is saved to.
+    file: &'a PendingBatchFile,
+    /// The opened file, `None` before the first save.
+    writer: Option<BufWriter<File>>,
+    /// Whether saving failed, in which case the batch isn't saved anymore.
+    failed: bool,
+    /// The number of blocks saved so far.
+    saved_blocks: usize,
+    /// The hashes of the contracts saved so far.
+    saved_contracts: HashSet<B256>,
+}
+
+impl PendingBatchWriter<'_> {
+    /// Returns true if the batch should be saved after executing the given number of blocks.
+    pub(crate) const fn should_save(&self, executed_blocks: u64) -> bool {
+        !self.failed && self.file.should_save(executed_blocks)
+    }
+
+    /// Appends the blocks executed since the last save to the file.
+    ///
+    /// `bundle`, `results` and `gas_usage` contain all executed blocks of the batch. Failures are
+    /// only logged, the blocks that aren't saved are executed again after a crash in that case.
+    pub(crate) fn save<R: SerdeBincodeCompat>(
+        &mut self,
+        range: PendingBatchRange,
+        bundle: &BundleState,
+        results: &[BlockExecutionResult<R>],
+        gas_usage: &[Vec<(Address, u64)>],
+    ) {
+        let new_blocks = self.saved_blocks..results.len();
+        let reverts = bundle.reverts.get(new_blocks.clone()).unwrap_or_default();
+
+        // the accounts changed by the new blocks are the ones they have reverts for
+        let mut changed = HashSet::<&Address>::default();
+        let mut state = Vec::new();
+        let mut contracts = Vec::new();
+        for (address, _) in reverts.iter().flatten() {
+            let Some(account) = bundle.state.get(address).filter(|_| changed.insert(address))
+            else {
+                continue
+            };
+            state.push((address, account));
+
+            let code_hash = account.info.as_ref().map(|info| info.code_hash);
+            if let Some((code_hash, code)) = code_hash
+                .and_then(|code_hash| bundle.contracts.get_key_value(&code_hash))
+                .filter(|(code_hash, _)| self.saved_contracts.insert(**code_hash))
+            {
+                contracts.push((code_hash, code));
+            }
+        }
+        let receipts = results[new_blocks.clone()]
+            .iter()
+            .map(|result| result.receipts.iter().map(R::as_repr).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let requests =
+            results[new_blocks.clone()].iter().map(|result| &result.requests).collect::<Vec<_>>();
+        let gas_usage = gas_usage.get(new_blocks).unwrap_or_default();
+
+        let result = self.writer().and_then(|writer| {
+            bincode::serialize_into(
+                &mut *writer,
+                &(range, state, contracts, reverts, receipts, requests, gas_usage),
+            )?;
+            writer.flush()?;
+            writer.get_ref().sync_data().map_err(bincode::Error::from)
+        });
+        match result {
+            Ok(()) => {
+                self.saved_blocks = results.len();
+                debug!(target: "sync::stages::execution", ?range, path = ?self.file.path, "Saved pending batch")
+            }
+            Err(err) => {
+                // the blocks that were saved before are still readable
+                self.failed = true;
+                warn!(target: "sync::stages::execution", %err, path = ?self.file.path, "Failed to save pending batch")
+            }
+        }
+    }
+
+    /// Returns the opened file, replacing the previously saved batch on first use.
+    fn writer(&mut self) -> Result<&mut BufWriter<File>, bincode::Error> {
+        if self.writer.is_none() {
+            self.writer = Some(BufWriter::new(File::create(&self.file.path)?));
+        }
+        Ok(self.writer.as_mut().expect("file is opened"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_ethereum_primitives::Receipt;
+    use std::fs::OpenOptions;
+
+    #[test]
+    fn save_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = PendingBatchFile::new(dir.path().join("pending-batch"), 2);
+        assert!(file.load::<Receipt>().is_none());
+        assert!(!file.should_save(1));
+        assert!(file.should_save(2));
+
+        let range = |last_block| PendingBatchRange {
+            first_block: 1,
+            last_block,
+            last_block_hash: B256::with_last_byte(last_block as u8),
+            gas_used: last_block * 21_000,
+        };
+        let receipts = vec![
+            vec![Receipt { cumulative_gas_used: 21_000, success: true, ..Default::default() }],
+            vec![Receipt { cumulative_gas_used: 21_000, success: true, ..Default::default() }],
+        ];
+        let results = receipts
+            .iter()
+            .map(|receipts| BlockExecutionResult {
+                receipts: receipts.clone(),
+                requests: Requests::default(),
+                gas_used: 21_000,
+            })
+            .collect::<Vec<_>>();
+        let gas_usage = vec![vec![(Address::random(), 21_000)], vec![]];
+
+        // each save appends the blocks executed since the previous one
+        let mut writer = file.writer();
+        writer.save(range(1), &BundleState::default(), &results[..1], &gas_usage[..1]);
+        writer.save(range(2), &BundleState::default(), &results, &gas_usage);
+        drop(writer);
+
+        let batch = file.load::<Receipt>().unwrap();
+        assert_eq!(batch.range, range(2));
+        assert_eq!(batch.outcome.first_block, 1);
+        assert_eq!(batch.outcome.receipts, receipts);
+        assert_eq!(batch.gas_usage, gas_usage);
+
+        // a partially written record is ignored
+        OpenOptions::new()
+            .append(true)
+            .open(dir.path().join("pending-batch"))
+            .unwrap()
+            .write_all(&[1, 2, 3])
+            .unwrap();
+        assert_eq!(file.load::<Receipt>().unwrap().range, range(2));
+
+        // a new batch replaces the saved one
+        let mut writer = file.writer();
+        writer.save(range(1), &BundleState::default(), &results[..1], &gas_usage[..1]);
+        drop(writer);
+        let batch = file.load::<Receipt>().unwrap();
+        assert_eq!(batch.range, range(1));
+        assert_eq!(batch.outcome.receipts, receipts[..1]);
+
+        file.remove();
+        assert!(file.load::<Receipt>().is_none());
+    }
+
+    #[test]
+    fn remove_corrupted() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("pending-batch");
+        std::fs::write(&path, [1, 2, 3]).unwrap();
+
+        let file = PendingBatchFile::new(path.clone(), 1);
+        assert!(file.load::<Receipt>().is_none());
+        assert!(!path.exists());
+    }
+}
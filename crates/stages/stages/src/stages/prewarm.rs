@@ -0,0 +1,488 @@
+//! Speculative execution of upcoming blocks to warm the state reads of the
+//! [`ExecutionStage`](super::ExecutionStage).
+//!
+//! Prewarm workers execute the transactions of blocks ahead of the executed block against their own
+//! read-only view of the latest committed state and discard all writes. The values they read are
+//! collected in a [`PrewarmCache`] that the stage's executor takes them from. Values the executor
+//! didn't take by the time the block they were read for is executed are evicted.
+//!
+//! This is sound because the execution stage only writes state at the end of a batch: until then,
+//! every account or storage slot the executor has not loaded yet still has its committed value.
+//! Values the executor loaded once are cached by its own state and are never requested again.
+
+use alloy_consensus::BlockHeader;
+use alloy_primitives::{Address, BlockNumber, StorageKey, StorageValue, B256};
+use dashmap::DashMap;
+use reth_config::config::ExecutionPrewarmConfig;
+use reth_evm::{ConfigureEvm, Evm};
+use reth_primitives_traits::{Account, Block, Bytecode, NodePrimitives, RecoveredBlock};
+use reth_provider::{ProviderResult, StateProviderBox};
+use reth_revm::database::{EvmStateProvider, StateProviderDatabase};
+use std::{
+    fmt,
+    hash::Hash,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        mpsc::{channel, Receiver, Sender},
+        Arc,
+    },
+    thread::Scope,
+};
+use tracing::*;
+
+/// The maximum number of prewarmed values that are kept until the executor takes them.
+const MAX_PREWARMED_ENTRIES: usize = 4_000_000;
+
+/// Opens a read-only view of the latest committed state.
+pub type LatestStateFactory = Arc<dyn Fn() -> ProviderResult<StateProviderBox> + Send + Sync>;
+
+/// Prewarms the state of upcoming blocks for the [`ExecutionStage`](super::ExecutionStage).
+#[derive(Clone)]
+pub struct ExecutionPrewarm {
+    /// Opens the state the workers execute against.
+    latest_state: LatestStateFactory,
+    /// The number of workers and how far ahead they execute.
+    config: ExecutionPrewarmConfig,
+}
+
+impl ExecutionPrewarm {
+    /// Creates a new instance that opens the state of the workers with the given function.
+    pub fn new(
+        latest_state: impl Fn() -> ProviderResult<StateProviderBox> + Send + Sync + 'static,
+        config: ExecutionPrewarmConfig,
+    ) -> Self {
+        Self { latest_state: Arc::new(latest_state), config }
+    }
+
+    /// Returns how many blocks ahead of the executed block are fetched and prewarmed.
+    pub(crate) const fn blocks_ahead(&self) -> u64 {
+        self.config.blocks_ahead
+    }
+
+    /// Spawns the workers in the given scope.
+    ///
+    /// The workers stop once the returned handle is dropped.
+    pub(crate) fn spawn<'scope, 'env, E>(
+        &'env self,
+        scope: &'scope Scope<'scope, 'env>,
+        evm_config: &'env E,
+        cache: Arc<PrewarmCache>,
+    ) -> PrewarmHandle<<E::Primitives as NodePrimitives>::Block>
+    where
+        E: ConfigureEvm,
+    {
+        let progress = Arc::new(PrewarmProgress::default());
+        let mut senders = Vec::with_capacity(self.config.threads);
+        for _ in 0..self.config.threads {
+            let (tx, rx) = channel();
+            senders.push(tx);
+
+            let cache = Arc::clone(&cache);
+            let progress = Arc::clone(&progress);
+            scope.spawn(move || {
+                let state = match (self.latest_state)() {
+                    Ok(state) => state,
+                    Err(err) => {
+                        debug!(target: "sync::stages::execution::prewarm", %err, "Failed to open state for prewarming");
+                        return
+                    }
+                };
+                prewarm_blocks(evm_config, state, &cache, rx, &progress);
+            });
+        }
+
+        PrewarmHandle { senders, next: 0, progress, cache }
+    }
+}
+
+impl fmt::Debug for ExecutionPrewarm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExecutionPrewarm").field("config", &self.config).finish_non_exhaustive()
+    }
+}
+
+/// Handle to the prewarm workers of a batch.
+#[derive(Debug)]
+pub(crate) struct PrewarmHandle<B: Block> {
+    /// Senders of the workers, blocks are distributed round-robin.
+    senders: Vec<Sender<RecoveredBlock<B>>>,
+    /// The worker that receives the next block.
+    next: usize,
+    /// Progress shared with the workers.
+    progress: Arc<PrewarmProgress>,
+    /// Values read by the workers.
+    cache: Arc<PrewarmCache>,
+}
+
+impl<B: Block> PrewarmHandle<B> {
+    /// Sends an upcoming block to the workers.
+    pub(crate) fn prewarm(&mut self, block: RecoveredBlock<B>) {
+        if self.senders.is_empty() {
+            return
+        }
+        let _ = self.senders[self.next].send(block);
+        self.next = (self.next + 1) % self.senders.len();
+    }
+
+    /// Records that the given block was executed, the workers skip it if they didn't reach it yet.
+    ///
+    /// Values that were only read for this block or earlier blocks are evicted.
+    pub(crate) fn on_executed(&self, block_number: BlockNumber) {
+        self.progress.executed.store(block_number, Ordering::Relaxed);
+        self.cache.evict(block_number);
+    }
+}
+
+impl<B: Block> Drop for PrewarmHandle<B> {
+    fn drop(&mut self) {
+        self.progress.terminate.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Progress of the stage shared with the workers.
+#[derive(Debug, Default)]
+struct PrewarmProgress {
+    /// The highest executed block.
+    executed: AtomicU64,
+    /// Set once the batch is done.
+    terminate: AtomicBool,
+}
+
+impl PrewarmProgress {
+    /// Returns true if the block should no longer be prewarmed.
+    fn is_stale(&self, block_number: BlockNumber) -> bool {
+        self.terminate.load(Ordering::Relaxed) ||
+            block_number <= self.executed.load(Ordering::Relaxed)
+    }
+}
+
+/// Executes the received blocks and discards their writes.
+fn prewarm_blocks<E: ConfigureEvm>(
+    evm_config: &E,
+    state: StateProviderBox,
+    cache: &PrewarmCache,
+    blocks: Receiver<RecoveredBlock<<E::Primitives as NodePrimitives>::Block>>,
+    progress: &PrewarmProgress,
+) {
+    let mut db =
+        StateProviderDatabase::new(PrewarmingState { inner: state, cache, block_number: 0 });
+    for block in blocks {
+        let block_number = block.header().number();
+        if progress.terminate.load(Ordering::Relaxed) {
+            break
+        }
+        db.block_number = block_number;
+
+        let mut evm_env = evm_config.evm_env(block.header());
+        // transactions are executed without the writes of the previous ones, so the nonce may not
+        // match
+        evm_env.cfg_env.disable_nonce_check = true;
+        let mut evm = evm_config.evm_with_env(&mut db, evm_env);
+
+        for tx in block.transactions_recovered() {
+            if progress.is_stale(block_number) {
+                break
+            }
+            if let Err(err) = evm.transact(evm_config.tx_env(tx)) {
+                trace!(target: "sync::stages::execution::prewarm", %err, number = block_number, "Failed to prewarm transaction");
+                break
+            }
+        }
+    }
+}
+
+/// Values read by the prewarm workers that the executor didn't load yet.
+///
+/// Every value is tagged with the highest block it was read for and is evicted once that block is
+/// executed.
+#[derive(Debug)]
+pub(crate) struct PrewarmCache {
+    accounts: DashMap<Address, (BlockNumber, Option<Account>)>,
+    storage: DashMap<(Address, StorageKey), (BlockNumber, Option<StorageValue>)>,
+    bytecodes: DashMap<B256, (BlockNumber, Option<Bytecode>)>,
+    /// The keys of the values read for each block that isn't executed yet.
+    reads: DashMap<BlockNumber, Vec<PrewarmKey>>,
+    /// The number of values in all maps.
+    len: AtomicUsize,
+    /// The maximum number of values in all maps.
+    max_entries: usize,
+}
+
+impl Default for PrewarmCache {
+    fn default() -> Self {
+        Self {
+            accounts: Default::default(),
+            storage: Default::default(),
+            bytecodes: Default::default(),
+            reads: Default::default(),
+            len: Default::default(),
+            max_entries: MAX_PREWARMED_ENTRIES,
+        }
+    }
+}
+
+impl PrewarmCache {
+    /// Returns the value read for the given block, the value is read from the inner state if it's
+    /// not cached yet.
+    fn get_or_read<K, V>(
+        &self,
+        map: &DashMap<K, (BlockNumber, V)>,
+        key: K,
+        block_number: BlockNumber,
+        read: impl FnOnce() -> ProviderResult<V>,
+    ) -> ProviderResult<V>
+    where
+        K: Eq + Hash + Copy + Into<PrewarmKey>,
+        V: Clone,
+    {
+        if let Some(mut entry) = map.get_mut(&key) {
+            let value = entry.1.clone();
+            // keep the value until the later block is executed
+            let bumped = entry.0 < block_number;
+            if bumped {
+                entry.0 = block_number;
+            }
+            drop(entry);
+            if bumped {
+                self.reads.entry(block_number).or_default().push(key.into());
+            }
+            return Ok(value)
+        }
+
+        let value = read()?;
+        if self.len.load(Ordering::Relaxed) < self.max_entries &&
+            map.insert(key, (block_number, value.clone())).is_none()
+        {
+            self.len.fetch_add(1, Ordering::Relaxed);
+            self.reads.entry(block_number).or_default().push(key.into());
+        }
+        Ok(value)
+    }
+
+    /// Evicts the values that were only read for the given block or earlier blocks.
+    fn evict(&self, executed: BlockNumber) {
+        let blocks = self
+            .reads
+            .iter()
+            .map(|entry| *entry.key())
+            .filter(|block_number| *block_number <= executed)
+            .collect::<Vec<_>>();
+        for (_, keys) in
+            blocks.into_iter().filter_map(|block_number| self.reads.remove(&block_number))
+        {
+            for key in keys {
+                let evicted = match key {
+                    PrewarmKey::Account(address) => {
+                        self.accounts.remove_if(&address, |_, (n, _)| *n <= executed).is_some()
+                    }
+                    PrewarmKey::Storage(address, slot) => self
+                        .storage
+                        .remove_if(&(address, slot), |_, (n, _)| *n <= executed)
+                        .is_some(),
+                    PrewarmKey::Bytecode(code_hash) => {
+                        self.bytecodes.remove_if(&code_hash, |_, (n, _)| *n <= executed).is_some()
+                    }
+                };
+                if evicted {
+                    self.len.fetch_sub(1, Ordering::Relaxed);
+                }
+            }
+        }
+    }
+
+    /// Records a value taken by the executor.
+    fn on_take<T>(&self, taken: Option<T>) -> Option<T> {
+        if taken.is_some() {
+            self.len.fetch_sub(1, Ordering::Relaxed);
+        }
+        taken
+    }
+}
+
+/// The key of a value in the [`PrewarmCache`].
+#[derive(Debug, Clone, Copy)]
+enum PrewarmKey {
+    Account(Address),
+    Storage(Address, StorageKey),
+    Bytecode(B256),
+}
+
+impl From<Address> for PrewarmKey {
+    fn from(address: Address) -> Self {
+        Self::Account(address)
+    }
+}
+
+impl From<(Address, StorageKey)> for PrewarmKey {
+    fn from((address, slot): (Address, StorageKey)) -> Self {
+        Self::Storage(address, slot)
+    }
+}
+
+impl From<B256> for PrewarmKey {
+    fn from(code_hash: B256) -> Self {
+        Self::Bytecode(code_hash)
+    }
+}
+
+/// The state of a prewarm worker, values read from the inner state are inserted into the cache.
+struct PrewarmingState<'a, DB> {
+    inner: DB,
+    cache: &'a PrewarmCache,
+    /// The block that is prewarmed.
+    block_number: BlockNumber,
+}
+
+impl<DB: EvmStateProvider> EvmStateProvider for PrewarmingState<'_, DB> {
+    fn basic_account(&self, address: &Address) -> ProviderResult<Option<Account>> {
+        self.cache.get_or_read(&self.cache.accounts, *address, self.block_number, || {
+            self.inner.basic_account(address)
+        })
+    }
+
+    fn block_hash(&self, number: BlockNumber) -> ProviderResult<Option<B256>> {
+        self.inner.block_hash(number)
+    }
+
+    fn bytecode_by_hash(&self, code_hash: &B256) -> ProviderResult<Option<Bytecode>> {
+        self.cache.get_or_read(&self.cache.bytecodes, *code_hash, self.block_number, || {
+            self.inner.bytecode_by_hash(code_hash)
+        })
+    }
+
+    fn storage(
+        &self,
+        account: Address,
+        storage_key: StorageKey,
+    ) -> ProviderResult<Option<StorageValue>> {
+        self.cache.get_or_read(
+            &self.cache.storage,
+            (account, storage_key),
+            self.block_number,
+            || self.inner.storage(account, storage_key),
+        )
+    }
+}
+
+/// The state of the executor, values are taken from the cache if the workers read them already.
+pub(crate) struct PrewarmedState<DB> {
+    inner: DB,
+    cache: Option<Arc<PrewarmCache>>,
+}
+
+impl<DB> PrewarmedState<DB> {
+    /// Creates a new instance, reads go to the inner state only if there is no cache.
+    pub(crate) const fn new(inner: DB, cache: Option<Arc<PrewarmCache>>) -> Self {
+        Self { inner, cache }
+    }
+}
+
+impl<DB: EvmStateProvider> EvmStateProvider for PrewarmedState<DB> {
+    fn basic_account(&self, address: &Address) -> ProviderResult<Option<Account>> {
+        if let Some(cache) = &self.cache {
+            if let Some((_, (_, account))) = cache.on_take(cache.accounts.remove(address)) {
+                return Ok(account)
+            }
+        }
+        self.inner.basic_account(address)
+    }
+
+    fn block_hash(&self, number: BlockNumber) -> ProviderResult<Option<B256>> {
+        self.inner.block_hash(number)
+    }
+
+    fn bytecode_by_hash(&self, code_hash: &B256) -> ProviderResult<Option<Bytecode>> {
+        if let Some(cache) = &self.cache {
+            if let Some((_, (_, bytecode))) = cache.on_take(cache.bytecodes.remove(code_hash)) {
+                return Ok(bytecode)
+            }
+        }
+        self.inner.bytecode_by_hash(code_hash)
+    }
+
+    fn storage(
+        &self,
+        account: Address,
+        storage_key: StorageKey,
+    ) -> ProviderResult<Option<StorageValue>> {
+        if let Some(cache) = &self.cache {
+            if let Some((_, (_, value))) =
+                cache.on_take(cache.storage.remove(&(account, storage_key)))
+            {
+                return Ok(value)
+            }
+        }
+        self.inner.storage(account, storage_key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::U256;
+    use reth_db::tables;
+    use reth_db_api::transaction::DbTxMut;
+    use reth_provider::{test_utils::create_test_provider_factory, DBProvider};
+
+    #[test]
+    fn executor_takes_prewarmed_values() {
+        let factory = create_test_provider_factory();
+        let address = Address::with_last_byte(1);
+        let account = Account { nonce: 1, balance: U256::from(10), bytecode_hash: None };
+        let provider = factory.provider_rw().unwrap();
+        provider.tx_ref().put::<tables::PlainAccountState>(address, account).unwrap();
+        provider.commit().unwrap();
+
+        let cache = Arc::new(PrewarmCache::default());
+        let worker =
+            PrewarmingState { inner: factory.latest().unwrap(), cache: &cache, block_number: 1 };
+        assert_eq!(worker.basic_account(&address).unwrap(), Some(account));
+        assert_eq!(worker.basic_account(&Address::ZERO).unwrap(), None);
+        assert_eq!(cache.len.load(Ordering::Relaxed), 2);
+
+        // the executor reads the prewarmed value even though the state changed since
+        let changed = Account { nonce: 2, ..account };
+        let provider = factory.provider_rw().unwrap();
+        provider.tx_ref().put::<tables::PlainAccountState>(address, changed).unwrap();
+        provider.commit().unwrap();
+
+        let executor = PrewarmedState::new(factory.latest().unwrap(), Some(Arc::clone(&cache)));
+        assert_eq!(executor.basic_account(&address).unwrap(), Some(account));
+        assert_eq!(cache.len.load(Ordering::Relaxed), 1);
+
+        // taken values are read from the inner state again
+        assert_eq!(executor.basic_account(&address).unwrap(), Some(changed));
+    }
+
+    #[test]
+    fn evicts_values_of_executed_blocks() {
+        let factory = create_test_provider_factory();
+        let cache = PrewarmCache { max_entries: 2, ..Default::default() };
+        let mut worker =
+            PrewarmingState { inner: factory.latest().unwrap(), cache: &cache, block_number: 1 };
+        let (first, second, third) =
+            (Address::with_last_byte(1), Address::with_last_byte(2), Address::with_last_byte(3));
+
+        // the cache is filled with values of block 1, one of them is read for block 2 as well
+        worker.basic_account(&first).unwrap();
+        worker.basic_account(&second).unwrap();
+        worker.block_number = 2;
+        worker.basic_account(&second).unwrap();
+        worker.basic_account(&third).unwrap();
+        assert!(!cache.accounts.contains_key(&third));
+
+        // executing block 1 evicts the values the executor didn't take
+        cache.evict(1);
+        assert!(!cache.accounts.contains_key(&first));
+        assert!(cache.accounts.contains_key(&second));
+        assert_eq!(cache.len.load(Ordering::Relaxed), 1);
+
+        // the freed space is used for later blocks
+        worker.basic_account(&third).unwrap();
+        assert!(cache.accounts.contains_key(&third));
+
+        cache.evict(2);
+        assert_eq!(cache.len.load(Ordering::Relaxed), 0);
+        assert!(cache.reads.is_empty());
+    }
+}
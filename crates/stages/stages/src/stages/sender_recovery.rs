@@ -9,7 +9,9 @@ use reth_db_api::{
     transaction::{DbTx, DbTxMut},
     DbTxUnwindExt, RawValue,
 };
-use reth_primitives_traits::{GotExpected, NodePrimitives, SignedTransaction};
+use reth_primitives_traits::{
+    crypto::RecoveryBackend, GotExpected, NodePrimitives, SignedTransaction,
+};
 use reth_provider::{
     BlockReader, DBProvider, HeaderProvider, ProviderError, PruneCheckpointReader,
     StaticFileProviderFactory, StatsReader,
@@ -20,7 +22,11 @@ use reth_stages_api::{
     StageId, UnwindInput, UnwindOutput,
 };
 use reth_static_file_types::StaticFileSegment;
-use std::{fmt::Debug, ops::Range, sync::mpsc};
+use std::{
+    fmt::Debug,
+    ops::Range,
+    sync::{mpsc, Arc},
+};
 use thiserror::Error;
 use tracing::*;
 
@@ -38,23 +44,34 @@ type RecoveryResultSender = mpsc::Sender<Result<(u64, Address), Box<SenderRecove
 /// The sender recovery stage iterates over existing transactions,
 /// recovers the transaction signer and stores them
 /// in [`TransactionSenders`][reth_db_api::tables::TransactionSenders] table.
+///
+/// Signers are recovered with secp256k1 on the CPU, unless a [`RecoveryBackend`] is configured
+/// with [`SenderRecoveryStage::with_backend`], which then recovers them in batches.
 #[derive(Clone, Debug)]
 pub struct SenderRecoveryStage {
     /// The size of inserted items after which the control
     /// flow will be returned to the pipeline for commit
     pub commit_threshold: u64,
+    /// The backend signers are recovered with, if not secp256k1.
+    backend: Option<Arc<dyn RecoveryBackend>>,
 }
 
 impl SenderRecoveryStage {
     /// Create new instance of [`SenderRecoveryStage`].
     pub const fn new(config: SenderRecoveryConfig) -> Self {
-        Self { commit_threshold: config.commit_threshold }
+        Self { commit_threshold: config.commit_threshold, backend: None }
+    }
+
+    /// Recovers the signers with the given backend instead of secp256k1.
+    pub fn with_backend(mut self, backend: Arc<dyn RecoveryBackend>) -> Self {
+        self.backend = Some(backend);
+        self
     }
 }
 
 impl Default for SenderRecoveryStage {
     fn default() -> Self {
-        Self { commit_threshold: 5_000_000 }
+        Self { commit_threshold: 5_000_000, backend: None }
     }
 }
 
@@ -106,7 +123,7 @@ where
             .map(|start| start..std::cmp::min(start + BATCH_SIZE as u64, tx_range.end))
             .collect::<Vec<Range<u64>>>();
 
-        let tx_batch_sender = setup_range_recovery(provider);
+        let tx_batch_sender = setup_range_recovery(provider, self.backend.clone());
 
         for range in batch {
             recover_range(range, provider, tx_batch_sender.clone(), &mut senders_cursor)?;
@@ -234,6 +251,7 @@ where
 /// transactions in parallel using global rayon pool
 fn setup_range_recovery<Provider>(
     provider: &Provider,
+    backend: Option<Arc<dyn RecoveryBackend>>,
 ) -> mpsc::Sender<Vec<(Range<u64>, RecoveryResultSender)>>
 where
     Provider: DBProvider
@@ -277,8 +295,23 @@ where
 
                 // Spawn the task onto the global rayon pool
                 // This task will send the results through the channel after it has read the
-                // transaction and calculated the sender.
+                // transactions and calculated the senders.
+                let backend = backend.clone();
                 rayon::spawn(move || {
+                    if let Some(backend) = backend {
+                        for res in recover_senders(chunk, &*backend) {
+                            let is_err = res.is_err();
+
+                            let _ = recovered_senders_tx.send(res);
+
+                            // Finish early
+                            if is_err {
+                                break
+                            }
+                        }
+                        return
+                    }
+
                     let mut rlp_buf = Vec::with_capacity(128);
                     for (number, tx) in chunk {
                         let res = tx
@@ -322,6 +355,50 @@ fn recover_sender<T: SignedTransaction>(
     Ok((tx_id, sender))
 }
 
+/// Decodes the transactions of the chunk and recovers their senders with a single batch call to
+/// the backend.
+///
+/// Transactions whose signer isn't recovered from a signature, e.g. deposit transactions, are
+/// recovered by the transaction itself. The results are returned in the order of the chunk.
+fn recover_senders<T: SignedTransaction>(
+    chunk: Vec<(TxNumber, RawValue<T>)>,
+    backend: &dyn RecoveryBackend,
+) -> Vec<Result<(u64, Address), Box<SenderRecoveryStageError>>> {
+    let txs = match chunk
+        .into_iter()
+        .map(|(tx_id, tx)| tx.value().map(|tx| (tx_id, tx)))
+        .collect::<Result<Vec<_>, _>>()
+    {
+        Ok(txs) => txs,
+        Err(err) => return vec![Err(Box::new(SenderRecoveryStageError::StageError(err.into())))],
+    };
+
+    let signatures = txs.iter().map(|(_, tx)| tx.signature_with_hash()).collect::<Vec<_>>();
+    let batch = signatures.iter().flatten().copied().collect::<Vec<_>>();
+    // We recover unchecked because transactions run in the pipeline are known to be valid - this
+    // means that we do not need to check whether or not the `s` value is greater than
+    // `secp256k1n / 2` if past EIP-2. There are transactions pre-homestead which have large `s`
+    // values, so checking the `s` value here would not be backwards-compatible.
+    let mut recovered = backend.recover_signers_unchecked(&batch).into_iter();
+
+    let mut rlp_buf = Vec::with_capacity(128);
+    txs.into_iter()
+        .zip(signatures)
+        .map(|((tx_id, tx), signature)| {
+            let sender = if signature.is_some() {
+                recovered.next().expect("one result per signature")
+            } else {
+                rlp_buf.clear();
+                tx.recover_unchecked_with_buf(&mut rlp_buf)
+            };
+            let sender = sender.map_err(|_| {
+                SenderRecoveryStageError::FailedRecovery(FailedSenderRecoveryError { tx: tx_id })
+            })?;
+            Ok((tx_id, sender))
+        })
+        .collect()
+}
+
 fn stage_checkpoint<Provider>(provider: &Provider) -> Result<EntitiesCheckpoint, StageError>
 where
     Provider: StatsReader + StaticFileProviderFactory + PruneCheckpointReader,
@@ -372,11 +449,14 @@ mod tests {
         stage_test_suite_ext, ExecuteStageTestRunner, StageTestRunner, StorageKind,
         TestRunnerError, TestStageDB, UnwindStageTestRunner,
     };
-    use alloy_primitives::{BlockNumber, B256};
+    use alloy_primitives::{BlockNumber, Signature, B256};
     use assert_matches::assert_matches;
     use reth_db_api::cursor::DbCursorRO;
     use reth_ethereum_primitives::{Block, TransactionSigned};
-    use reth_primitives_traits::{SealedBlock, SignerRecoverable};
+    use reth_primitives_traits::{
+        crypto::{RecoveryError, Secp256k1Recovery},
+        SealedBlock, SignerRecoverable,
+    };
     use reth_provider::{
         providers::StaticFileWriter, BlockBodyIndicesProvider, DatabaseProviderFactory,
         PruneCheckpointWriter, StaticFileProviderFactory, TransactionsProvider,
@@ -386,6 +466,7 @@ mod tests {
     use reth_testing_utils::generators::{
         self, random_block, random_block_range, BlockParams, BlockRangeParams,
     };
+    use std::sync::atomic::{AtomicUsize, Ordering};
 
     stage_test_suite_ext!(SenderRecoveryTestRunner, sender_recovery);
 
@@ -520,6 +601,39 @@ mod tests {
         assert!(runner.validate_execution(first_input, result.ok()).is_ok(), "validation failed");
     }
 
+    /// Execute the stage with a custom recovery backend
+    #[tokio::test]
+    async fn execute_with_backend() {
+        let mut rng = generators::rng();
+
+        let backend = Arc::new(CountingRecoveryBackend::default());
+        let mut runner = SenderRecoveryTestRunner::default();
+        runner.backend = Some(backend.clone());
+        let (stage_progress, previous_stage) = (100, 200);
+
+        let seed = random_block_range(
+            &mut rng,
+            stage_progress + 1..=previous_stage,
+            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 1..4, ..Default::default() },
+        );
+        runner
+            .db
+            .insert_blocks(seed.iter(), StorageKind::Static)
+            .expect("failed to seed execution");
+        let total_transactions = seed.iter().map(|block| block.transaction_count()).sum::<usize>();
+
+        let input = ExecInput {
+            target: Some(previous_stage),
+            checkpoint: Some(StageCheckpoint::new(stage_progress)),
+        };
+        let result = runner.execute(input).await.unwrap();
+        assert_matches!(result, Ok(ExecOutput { done: true, .. }));
+        assert!(runner.validate_execution(input, result.ok()).is_ok(), "validation failed");
+
+        // all senders were recovered by the backend
+        assert_eq!(backend.recovered.load(Ordering::Relaxed), total_transactions);
+    }
+
     #[test]
     fn stage_checkpoint_pruned() {
         let db = TestStageDB::default();
@@ -579,14 +693,32 @@ mod tests {
         );
     }
 
+    /// A [`RecoveryBackend`] that counts the recovered signers.
+    #[derive(Debug, Default)]
+    struct CountingRecoveryBackend {
+        recovered: AtomicUsize,
+    }
+
+    impl RecoveryBackend for CountingRecoveryBackend {
+        fn recover_signer_unchecked(
+            &self,
+            signature: &Signature,
+            hash: B256,
+        ) -> Result<Address, RecoveryError> {
+            self.recovered.fetch_add(1, Ordering::Relaxed);
+            Secp256k1Recovery.recover_signer_unchecked(signature, hash)
+        }
+    }
+
     struct SenderRecoveryTestRunner {
         db: TestStageDB,
         threshold: u64,
+        backend: Option<Arc<dyn RecoveryBackend>>,
     }
 
     impl Default for SenderRecoveryTestRunner {
         fn default() -> Self {
-            Self { threshold: 1000, db: TestStageDB::default() }
+            Self { threshold: 1000, db: TestStageDB::default(), backend: None }
         }
     }
 
@@ -630,7 +762,7 @@ mod tests {
         }
 
         fn stage(&self) -> Self::S {
-            SenderRecoveryStage { commit_threshold: self.threshold }
+            SenderRecoveryStage { commit_threshold: self.threshold, backend: self.backend.clone() }
         }
     }
 
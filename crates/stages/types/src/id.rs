@@ -1,3 +1,6 @@
+use alloc::{string::String, vec::Vec};
+use core::str::FromStr;
+
 /// Stage IDs for all known stages.
 ///
 /// For custom stages, use [`StageId::Other`]
@@ -60,6 +63,62 @@ impl StageId {
         Self::Prune,
     ];
 
+    /// Identifiers of stages that were removed from the pipeline.
+    ///
+    /// Checkpoints of these stages may still be present in existing databases and are removed on
+    /// startup.
+    pub const RETIRED: [&'static str; 2] = ["StaticFile", "TotalDifficulty"];
+
+    /// Identifier of the optional stage that indexes log addresses and topics.
+    ///
+    /// The stage is custom, so it's not part of [`StageId::ALL`].
+    pub const INDEX_LOGS: Self = Self::Other("IndexLogs");
+
+    /// Returns the position of the stage in [`StageId::ALL`], or `None` for retired and custom
+    /// stages.
+    ///
+    /// The match is exhaustive, so adding a new stage fails to compile until it's assigned a
+    /// position here, and the position is checked against [`StageId::ALL`] at compile time.
+    pub const fn position(&self) -> Option<usize> {
+        match self {
+            #[expect(deprecated)]
+            Self::StaticFile => None,
+            Self::Era => Some(0),
+            Self::Headers => Some(1),
+            Self::Bodies => Some(2),
+            Self::SenderRecovery => Some(3),
+            Self::Execution => Some(4),
+            Self::PruneSenderRecovery => Some(5),
+            Self::MerkleUnwind => Some(6),
+            Self::AccountHashing => Some(7),
+            Self::StorageHashing => Some(8),
+            Self::MerkleExecute => Some(9),
+            Self::TransactionLookup => Some(10),
+            Self::IndexStorageHistory => Some(11),
+            Self::IndexAccountHistory => Some(12),
+            Self::Prune => Some(13),
+            Self::Finish => Some(14),
+            Self::Other(_) => None,
+        }
+    }
+
+    /// Returns the built-in stage with the given name, if any.
+    ///
+    /// Custom stages are resolved through a [`StageRegistry`].
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|id| id.as_str() == name)
+    }
+
+    /// Returns `true` if it's a custom stage [`StageId::Other`].
+    pub const fn is_custom(&self) -> bool {
+        matches!(self, Self::Other(_))
+    }
+
+    /// Returns `true` if the given name belongs to a stage that was removed from the pipeline.
+    pub fn is_retired(name: &str) -> bool {
+        Self::RETIRED.contains(&name)
+    }
+
     /// Return stage id formatted as string.
     pub const fn as_str(&self) -> &str {
         match self {
@@ -100,12 +159,87 @@ impl StageId {
     }
 }
 
+// Ensures that every stage in `StageId::ALL` is at the position returned by `StageId::position`.
+const _: () = {
+    let mut i = 0;
+    while i < StageId::ALL.len() {
+        assert!(matches!(StageId::ALL[i].position(), Some(position) if position == i));
+        i += 1;
+    }
+};
+
 impl core::fmt::Display for StageId {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}", self.as_str())
     }
 }
 
+impl FromStr for StageId {
+    type Err = UnknownStageId;
+
+    /// Parses a built-in stage. Custom stages are resolved through a [`StageRegistry`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_name(s).ok_or_else(|| UnknownStageId(s.into()))
+    }
+}
+
+/// Error returned when a stage name doesn't belong to any known stage.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownStageId(pub String);
+
+impl core::fmt::Display for UnknownStageId {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "unknown stage: {}", self.0)
+    }
+}
+
+impl core::error::Error for UnknownStageId {}
+
+/// Registry of all stages known to a pipeline: the built-in stages and any registered custom
+/// stages.
+///
+/// Checkpoints are stored by stage name, so the registry is used to resolve stored names back to
+/// stage IDs and to detect checkpoints that don't belong to any known stage.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StageRegistry {
+    /// Registered custom stages.
+    custom: Vec<&'static str>,
+}
+
+impl StageRegistry {
+    /// Registers a stage. Built-in stages are always known and are ignored.
+    pub fn register(&mut self, id: StageId) {
+        if let StageId::Other(name) = id {
+            if !self.custom.contains(&name) {
+                self.custom.push(name);
+            }
+        }
+    }
+
+    /// Registers a stage and returns the registry.
+    pub fn with_stage(mut self, id: StageId) -> Self {
+        self.register(id);
+        self
+    }
+
+    /// Resolves the stage with the given name.
+    pub fn resolve(&self, name: &str) -> Option<StageId> {
+        StageId::from_name(name).or_else(|| {
+            self.custom.iter().copied().find(|custom| *custom == name).map(StageId::Other)
+        })
+    }
+
+    /// Returns `true` if the stage with the given name is known.
+    pub fn contains(&self, name: &str) -> bool {
+        self.resolve(name).is_some()
+    }
+
+    /// Returns an iterator over all known stages, built-in stages first.
+    pub fn iter(&self) -> impl Iterator<Item = StageId> + '_ {
+        StageId::ALL.into_iter().chain(self.custom.iter().copied().map(StageId::Other))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -137,4 +271,28 @@ mod tests {
 
         assert!(!StageId::Execution.is_downloading_stage());
     }
+
+    #[test]
+    fn parse_stage_id() {
+        for stage in StageId::ALL {
+            assert_eq!(stage.as_str().parse::<StageId>(), Ok(stage));
+        }
+
+        assert_eq!("Foo".parse::<StageId>(), Err(UnknownStageId("Foo".into())));
+        assert_eq!(StageId::from_name("StaticFile"), None);
+        assert!(StageId::is_retired("StaticFile"));
+    }
+
+    #[test]
+    fn stage_registry() {
+        let registry = StageRegistry::default()
+            .with_stage(StageId::Headers)
+            .with_stage(StageId::Other("Foo"))
+            .with_stage(StageId::Other("Foo"));
+
+        assert_eq!(registry.resolve("Headers"), Some(StageId::Headers));
+        assert_eq!(registry.resolve("Foo"), Some(StageId::Other("Foo")));
+        assert!(!registry.contains("Bar"));
+        assert_eq!(registry.iter().count(), StageId::ALL.len() + 1);
+    }
 }
@@ -13,7 +13,7 @@ extern crate alloc;
 
 mod id;
 use alloy_primitives::{BlockHash, BlockNumber};
-pub use id::StageId;
+pub use id::{StageId, StageRegistry, UnknownStageId};
 
 mod checkpoints;
 pub use checkpoints::{
@@ -0,0 +1,204 @@
+use crate::{ProtocolEvent, StaticFileChunk, StaticFilePeerRequest};
+use alloy_primitives::BlockNumber;
+use parking_lot::Mutex;
+use reth_network_api::PeerId;
+use reth_static_file_types::StaticFileSegment;
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tokio::sync::mpsc::UnboundedSender;
+use tracing::*;
+
+/// Keeps track of the peers of the static file exchange protocol and the chunks they serve.
+///
+/// Downloaded chunks are written to a separate download directory, where they're only read to
+/// verify them.
+#[derive(Debug, Clone)]
+pub struct StaticFileExchangeClient {
+    inner: Arc<StaticFileExchangeClientInner>,
+}
+
+#[derive(Debug)]
+struct StaticFileExchangeClientInner {
+    /// The directory chunks are downloaded to.
+    directory: PathBuf,
+    /// The connected peers.
+    peers: Mutex<HashMap<PeerId, ExchangePeer>>,
+    /// Peers that served invalid chunks.
+    banned: Mutex<HashSet<PeerId>>,
+}
+
+#[derive(Debug)]
+struct ExchangePeer {
+    /// Sender part for forwarding commands to the connection.
+    to_connection: UnboundedSender<StaticFilePeerRequest>,
+    /// The chunks the peer serves.
+    chunks: Vec<StaticFileChunk>,
+}
+
+/// A chunk and the peer that serves it.
+#[derive(Debug, Clone)]
+pub struct ChunkSource {
+    /// Peer ID.
+    pub peer_id: PeerId,
+    /// Sender part for forwarding commands to the connection of the peer.
+    pub to_connection: UnboundedSender<StaticFilePeerRequest>,
+    /// The chunk.
+    pub chunk: StaticFileChunk,
+}
+
+impl StaticFileExchangeClient {
+    /// Creates a new client that downloads chunks to the given directory.
+    ///
+    /// Leftovers of previous downloads in the directory are removed.
+    pub fn new(directory: impl Into<PathBuf>) -> reth_fs_util::Result<Self> {
+        let directory = directory.into();
+        if directory.exists() {
+            reth_fs_util::remove_dir_all(&directory)?;
+        }
+        reth_fs_util::create_dir_all(&directory)?;
+        Ok(Self {
+            inner: Arc::new(StaticFileExchangeClientInner {
+                directory,
+                peers: Default::default(),
+                banned: Default::default(),
+            }),
+        })
+    }
+
+    /// Returns the directory chunks are downloaded to.
+    pub fn directory(&self) -> &Path {
+        &self.inner.directory
+    }
+
+    /// Updates the peers with an event of the protocol.
+    pub fn on_event(&self, event: ProtocolEvent) {
+        match event {
+            ProtocolEvent::Established { peer_id, to_connection, .. } => {
+                self.inner
+                    .peers
+                    .lock()
+                    .insert(peer_id, ExchangePeer { to_connection, chunks: Vec::new() });
+            }
+            ProtocolEvent::Manifest { peer_id, chunks } => {
+                if let Some(peer) = self.inner.peers.lock().get_mut(&peer_id) {
+                    peer.chunks = chunks;
+                }
+            }
+            ProtocolEvent::MaxActiveConnectionsExceeded { .. } => {}
+        }
+    }
+
+    /// Announces the manifest of this node to all peers, e.g. after new chunks were finalized.
+    pub fn announce_manifest(&self) {
+        let mut peers = self.inner.peers.lock();
+        peers.retain(|_, peer| {
+            peer.to_connection.send(StaticFilePeerRequest::AnnounceManifest).is_ok()
+        });
+    }
+
+    /// Returns consecutive headers chunks served by peers, starting with the chunk that contains
+    /// the `first` block and ending before the `below` block.
+    ///
+    /// The chunks are ordered by their block range and the list is empty if no peer serves the
+    /// first chunk.
+    pub fn header_chunks(&self, first: BlockNumber, below: BlockNumber) -> Vec<ChunkSource> {
+        let banned = self.inner.banned.lock();
+        let mut peers = self.inner.peers.lock();
+        peers.retain(|_, peer| !peer.to_connection.is_closed());
+
+        let mut chunks = BTreeMap::new();
+        for (peer_id, peer) in peers.iter().filter(|(peer_id, _)| !banned.contains(*peer_id)) {
+            for chunk in &peer.chunks {
+                if chunk.segment == StaticFileSegment::Headers &&
+                    chunk.block_range.start() <= chunk.block_range.end() &&
+                    chunk.block_range.end() < below
+                {
+                    chunks.entry(chunk.block_range.start()).or_insert_with(|| ChunkSource {
+                        peer_id: *peer_id,
+                        to_connection: peer.to_connection.clone(),
+                        chunk: chunk.clone(),
+                    });
+                }
+            }
+        }
+
+        let mut sources = Vec::new();
+        let Some(mut next) = chunks
+            .range(..=first)
+            .next_back()
+            .filter(|(_, source)| source.chunk.block_range.end() >= first)
+            .map(|(start, _)| *start)
+        else {
+            return sources
+        };
+        while let Some(source) = chunks.remove(&next) {
+            next = source.chunk.block_range.end() + 1;
+            sources.push(source);
+        }
+        sources
+    }
+
+    /// Stops downloading chunks from the peer, because it served an invalid chunk.
+    pub fn ban(&self, peer_id: PeerId) {
+        debug!(target: "static_file::exchange", %peer_id, "Banning peer that served an invalid chunk");
+        self.inner.banned.lock().insert(peer_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_static_file_types::SegmentRangeInclusive;
+    use tokio::sync::mpsc;
+
+    fn chunk(segment: StaticFileSegment, start: BlockNumber, end: BlockNumber) -> StaticFileChunk {
+        StaticFileChunk {
+            segment,
+            block_range: SegmentRangeInclusive::new(start, end),
+            files: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn consecutive_header_chunks() {
+        let dir = tempfile::tempdir().unwrap();
+        let client = StaticFileExchangeClient::new(dir.path().join("exchange")).unwrap();
+        let (first_peer, second_peer) = (PeerId::repeat_byte(1), PeerId::repeat_byte(2));
+        let (tx, _rx) = mpsc::unbounded_channel();
+        for peer_id in [first_peer, second_peer] {
+            client.on_event(ProtocolEvent::Established {
+                direction: reth_network_api::Direction::Outgoing(peer_id),
+                peer_id,
+                to_connection: tx.clone(),
+            });
+        }
+        client.on_event(ProtocolEvent::Manifest {
+            peer_id: first_peer,
+            chunks: vec![
+                chunk(StaticFileSegment::Headers, 0, 9),
+                chunk(StaticFileSegment::Receipts, 10, 19),
+                chunk(StaticFileSegment::Headers, 30, 39),
+            ],
+        });
+        client.on_event(ProtocolEvent::Manifest {
+            peer_id: second_peer,
+            chunks: vec![chunk(StaticFileSegment::Headers, 10, 19)],
+        });
+
+        let ranges = |sources: Vec<ChunkSource>| {
+            sources.into_iter().map(|source| source.chunk.block_range).collect::<Vec<_>>()
+        };
+        assert_eq!(
+            ranges(client.header_chunks(5, 100)),
+            vec![SegmentRangeInclusive::new(0, 9), SegmentRangeInclusive::new(10, 19)]
+        );
+        assert_eq!(ranges(client.header_chunks(5, 19)), vec![SegmentRangeInclusive::new(0, 9)]);
+        assert_eq!(ranges(client.header_chunks(20, 100)), vec![]);
+
+        client.ban(second_peer);
+        assert_eq!(ranges(client.header_chunks(5, 100)), vec![SegmentRangeInclusive::new(0, 9)]);
+    }
+}
@@ -0,0 +1,221 @@
+use crate::{
+    GetChunkData, ProtocolEvent, StaticFileExchangeMessage, StaticFileExchangeMessageKind,
+    StaticFileExchangeProvider,
+};
+use alloy_primitives::{bytes::BytesMut, Bytes};
+use futures::{Stream, StreamExt};
+use reth_eth_wire::multiplex::ProtocolConnection;
+use reth_network_api::{test_utils::PeersHandle, PeerId, ReputationChangeKind};
+use std::{
+    collections::HashMap,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+};
+use tokio::sync::{mpsc, oneshot};
+use tokio_stream::wrappers::UnboundedReceiverStream;
+use tracing::*;
+
+/// The connection handler for the static file exchange `RLPx` protocol.
+#[derive(Debug)]
+pub struct StaticFileExchangeConnection<P> {
+    /// Provider.
+    provider: P,
+    /// Peers handle.
+    peers_handle: PeersHandle,
+    /// Peer ID.
+    peer_id: PeerId,
+    /// Protocol connection.
+    conn: ProtocolConnection,
+    /// Stream of incoming commands.
+    commands: UnboundedReceiverStream<StaticFilePeerRequest>,
+    /// Protocol event sender, used to forward the manifests of the peer.
+    events_sender: mpsc::UnboundedSender<ProtocolEvent>,
+    /// The total number of active connections.
+    active_connections: Arc<AtomicU64>,
+    /// Flag indicating whether the manifest was sent to the peer.
+    manifest_sent: bool,
+    /// Flag indicating whether this stream has previously been terminated.
+    terminated: bool,
+    /// Incremental counter for request ids.
+    next_id: u64,
+    /// Collection of inflight requests.
+    inflight_requests: HashMap<u64, StaticFilePeerRequest>,
+}
+
+impl<P> StaticFileExchangeConnection<P> {
+    /// Create new connection.
+    pub fn new(
+        provider: P,
+        peers_handle: PeersHandle,
+        peer_id: PeerId,
+        conn: ProtocolConnection,
+        commands: UnboundedReceiverStream<StaticFilePeerRequest>,
+        events_sender: mpsc::UnboundedSender<ProtocolEvent>,
+        active_connections: Arc<AtomicU64>,
+    ) -> Self {
+        Self {
+            provider,
+            peers_handle,
+            peer_id,
+            conn,
+            commands,
+            events_sender,
+            active_connections,
+            manifest_sent: false,
+            terminated: false,
+            next_id: 0,
+            inflight_requests: HashMap::default(),
+        }
+    }
+
+    /// Returns the next request id
+    const fn next_id(&mut self) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        id
+    }
+
+    /// Report bad message from current peer.
+    fn report_bad_message(&self) {
+        self.peers_handle.reputation_change(self.peer_id, ReputationChangeKind::BadMessage);
+    }
+}
+
+impl<P> StaticFileExchangeConnection<P>
+where
+    P: StaticFileExchangeProvider,
+{
+    fn on_command(&mut self, command: StaticFilePeerRequest) -> StaticFileExchangeMessage {
+        match command {
+            StaticFilePeerRequest::AnnounceManifest => {
+                StaticFileExchangeMessage::manifest(self.provider.manifest())
+            }
+            StaticFilePeerRequest::GetChunkData { request, .. } => {
+                let next_id = self.next_id();
+                self.inflight_requests.insert(next_id, command);
+                StaticFileExchangeMessage::get_chunk_data(next_id, request)
+            }
+        }
+    }
+
+    fn on_chunk_data_request(&self, request: GetChunkData) -> Bytes {
+        match self.provider.chunk_data(request) {
+            Ok(data) => data,
+            Err(error) => {
+                trace!(target: "static_file::exchange::connection", peer_id = %self.peer_id, ?request, %error, "error retrieving chunk data");
+                Default::default()
+            }
+        }
+    }
+
+    fn on_message(&mut self, msg: StaticFileExchangeMessage) -> Option<BytesMut> {
+        match msg.message {
+            StaticFileExchangeMessageKind::Manifest(chunks) => {
+                trace!(target: "static_file::exchange::connection", peer_id = %self.peer_id, chunks = chunks.len(), "received manifest");
+                let _ = self
+                    .events_sender
+                    .send(ProtocolEvent::Manifest { peer_id: self.peer_id, chunks });
+            }
+            StaticFileExchangeMessageKind::GetChunkData(req) => {
+                let request = req.message;
+                trace!(target: "static_file::exchange::connection", peer_id = %self.peer_id, ?request, "serving chunk data");
+                let data = self.on_chunk_data_request(request);
+                let response = StaticFileExchangeMessage::chunk_data(req.request_id, data);
+                return Some(response.encoded())
+            }
+            StaticFileExchangeMessageKind::ChunkData(res) => {
+                if let Some(StaticFilePeerRequest::GetChunkData { tx, .. }) =
+                    self.inflight_requests.remove(&res.request_id)
+                {
+                    let _ = tx.send(res.message);
+                } else {
+                    self.report_bad_message();
+                }
+            }
+        };
+        None
+    }
+}
+
+impl<P> Drop for StaticFileExchangeConnection<P> {
+    fn drop(&mut self) {
+        let _ = self
+            .active_connections
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |c| Some(c.saturating_sub(1)));
+    }
+}
+
+impl<P> Stream for StaticFileExchangeConnection<P>
+where
+    P: StaticFileExchangeProvider + Unpin,
+{
+    type Item = BytesMut;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        if this.terminated {
+            return Poll::Ready(None)
+        }
+
+        if !this.manifest_sent {
+            this.manifest_sent = true;
+            let manifest = StaticFileExchangeMessage::manifest(this.provider.manifest());
+            return Poll::Ready(Some(manifest.encoded()))
+        }
+
+        'conn: loop {
+            if let Poll::Ready(Some(cmd)) = this.commands.poll_next_unpin(cx) {
+                let message = this.on_command(cmd);
+                trace!(target: "static_file::exchange::connection", peer_id = %this.peer_id, message = ?message.message_type, "Sending peer command");
+                return Poll::Ready(Some(message.encoded()));
+            }
+
+            if let Poll::Ready(maybe_msg) = this.conn.poll_next_unpin(cx) {
+                let Some(next) = maybe_msg else { break 'conn };
+                let msg = match StaticFileExchangeMessage::decode_message(&mut &next[..]) {
+                    Ok(msg) => {
+                        trace!(target: "static_file::exchange::connection", peer_id = %this.peer_id, message = ?msg.message_type, "Processing message");
+                        msg
+                    }
+                    Err(error) => {
+                        trace!(target: "static_file::exchange::connection", peer_id = %this.peer_id, %error, "Error decoding peer message");
+                        this.report_bad_message();
+                        continue;
+                    }
+                };
+
+                if let Some(response) = this.on_message(msg) {
+                    return Poll::Ready(Some(response))
+                }
+
+                continue;
+            }
+
+            return Poll::Pending;
+        }
+
+        // Terminating the connection.
+        this.terminated = true;
+        Poll::Ready(None)
+    }
+}
+
+/// Static file exchange peer request.
+#[derive(Debug)]
+pub enum StaticFilePeerRequest {
+    /// Announce the current manifest of the provider to the peer, e.g. after new chunks were
+    /// finalized.
+    AnnounceManifest,
+    /// Get a part of a chunk file.
+    GetChunkData {
+        /// The request for chunk data.
+        request: GetChunkData,
+        /// The sender for the response.
+        tx: oneshot::Sender<Bytes>,
+    },
+}
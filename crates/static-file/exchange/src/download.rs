@@ -0,0 +1,204 @@
+use crate::{
+    ChunkFileKind, GetChunkData, StaticFileChunk, StaticFilePeerRequest, MAX_CHUNK_DATA_SERVE,
+};
+use alloy_primitives::B256;
+use std::{io, path::Path, time::Duration};
+use tokio::{
+    fs,
+    io::AsyncWriteExt,
+    sync::{mpsc::UnboundedSender, oneshot},
+};
+use tracing::*;
+
+/// The time to wait for a part of a chunk file before the download is aborted.
+pub const CHUNK_DATA_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Errors that can occur while downloading a chunk from a peer.
+#[derive(Debug, thiserror::Error)]
+pub enum ChunkDownloadError {
+    /// The connection to the peer was closed.
+    #[error("connection to the peer was closed")]
+    ChannelClosed,
+    /// The peer didn't respond in time.
+    #[error("peer didn't respond within {CHUNK_DATA_TIMEOUT:?}")]
+    Timeout,
+    /// The peer does not advertise all files of the chunk.
+    #[error("chunk file {0:?} is not advertised")]
+    MissingFile(ChunkFileKind),
+    /// The peer stopped serving the file before its advertised size was reached.
+    #[error("peer stopped serving {file} at {received} of {size} bytes")]
+    Truncated {
+        /// The name of the file.
+        file: String,
+        /// The number of bytes received.
+        received: u64,
+        /// The advertised size of the file.
+        size: u64,
+    },
+    /// The checksum of the received file does not match the advertised checksum.
+    #[error("checksum mismatch of {file}: expected {expected}, got {got}")]
+    ChecksumMismatch {
+        /// The name of the file.
+        file: String,
+        /// The advertised checksum.
+        expected: B256,
+        /// The checksum of the received file.
+        got: B256,
+    },
+    /// I/O error while writing the file.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+/// Downloads all files of the chunk from the peer of the given connection into the given
+/// directory.
+///
+/// Every file is written to a temporary file first and only moved into place after its size and
+/// checksum were verified against the manifest, so an interrupted or corrupt download never leaves
+/// a partial file behind. The manifest comes from the same peer though, so this doesn't make the
+/// chunk trustworthy: it must be downloaded into a separate directory and verified before it's
+/// used, see [`read_header_chunk`](crate::read_header_chunk).
+pub async fn fetch_chunk(
+    to_connection: &UnboundedSender<StaticFilePeerRequest>,
+    chunk: &StaticFileChunk,
+    directory: &Path,
+) -> Result<(), ChunkDownloadError> {
+    let mut files = Vec::with_capacity(ChunkFileKind::ALL.len());
+    for kind in ChunkFileKind::ALL {
+        let file = chunk.file(kind).ok_or(ChunkDownloadError::MissingFile(kind))?;
+        let name = kind.file_name(chunk.segment, &chunk.block_range);
+        let tmp_path = directory.join(format!("{name}.tmp"));
+
+        let result = fetch_file(to_connection, chunk, kind, file.size, &name, &tmp_path).await;
+        match result {
+            Ok(checksum) if checksum == file.checksum => files.push((tmp_path, name)),
+            Ok(got) => {
+                let _ = fs::remove_file(&tmp_path).await;
+                return Err(ChunkDownloadError::ChecksumMismatch {
+                    file: name,
+                    expected: file.checksum,
+                    got,
+                })
+            }
+            Err(err) => {
+                let _ = fs::remove_file(&tmp_path).await;
+                return Err(err)
+            }
+        }
+    }
+
+    // the data file is moved last so the static file is only discovered once it's complete
+    for (tmp_path, name) in files.into_iter().rev() {
+        fs::rename(tmp_path, directory.join(name)).await?;
+    }
+    debug!(target: "static_file::exchange", segment = %chunk.segment, block_range = %chunk.block_range, size = chunk.size(), "Downloaded static file chunk");
+
+    Ok(())
+}
+
+/// Downloads a file of the chunk to the given path and returns its checksum.
+async fn fetch_file(
+    to_connection: &UnboundedSender<StaticFilePeerRequest>,
+    chunk: &StaticFileChunk,
+    kind: ChunkFileKind,
+    size: u64,
+    name: &str,
+    path: &Path,
+) -> Result<B256, ChunkDownloadError> {
+    let mut writer = fs::File::create(path).await?;
+    let mut hasher = blake3::Hasher::new();
+
+    let mut offset = 0;
+    while offset < size {
+        let request = GetChunkData {
+            segment: chunk.segment,
+            block_start: chunk.block_range.start(),
+            kind,
+            offset,
+            limit: MAX_CHUNK_DATA_SERVE.min(size - offset),
+        };
+        let (tx, rx) = oneshot::channel();
+        to_connection
+            .send(StaticFilePeerRequest::GetChunkData { request, tx })
+            .map_err(|_| ChunkDownloadError::ChannelClosed)?;
+        let data = tokio::time::timeout(CHUNK_DATA_TIMEOUT, rx)
+            .await
+            .map_err(|_| ChunkDownloadError::Timeout)?
+            .map_err(|_| ChunkDownloadError::ChannelClosed)?;
+
+        // an empty response means the peer no longer serves the chunk
+        if data.is_empty() || data.len() as u64 > request.limit {
+            return Err(ChunkDownloadError::Truncated {
+                file: name.to_string(),
+                received: offset,
+                size,
+            })
+        }
+
+        hasher.update(&data);
+        writer.write_all(&data).await?;
+        offset += data.len() as u64;
+    }
+    writer.sync_all().await?;
+
+    Ok(B256::from(*hasher.finalize().as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{StaticFileDirectory, StaticFileExchangeProvider};
+    use reth_static_file_types::{HighestStaticFiles, SegmentRangeInclusive, StaticFileSegment};
+    use tokio::sync::mpsc;
+
+    /// Serves the requests of the returned sender from the provider.
+    fn serve(provider: StaticFileDirectory) -> UnboundedSender<StaticFilePeerRequest> {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            while let Some(request) = rx.recv().await {
+                if let StaticFilePeerRequest::GetChunkData { request, tx } = request {
+                    let _ = tx.send(provider.chunk_data(request).unwrap());
+                }
+            }
+        });
+        tx
+    }
+
+    #[tokio::test]
+    async fn fetch_chunk_from_peer() {
+        let remote = tempfile::tempdir().unwrap();
+        let local = tempfile::tempdir().unwrap();
+
+        let segment = StaticFileSegment::Headers;
+        let block_range = SegmentRangeInclusive::new(0, 499_999);
+        let data = (0..MAX_CHUNK_DATA_SERVE * 2 + 10).map(|i| i as u8).collect::<Vec<_>>();
+        for kind in ChunkFileKind::ALL {
+            let contents = if kind == ChunkFileKind::Data { &data[..] } else { &[kind as u8; 16] };
+            std::fs::write(remote.path().join(kind.file_name(segment, &block_range)), contents)
+                .unwrap();
+        }
+
+        let provider = StaticFileDirectory::new(remote.path());
+        let highest = HighestStaticFiles { headers: Some(499_999), ..Default::default() };
+        provider.refresh(highest, 499_999).unwrap();
+        let chunk = provider.manifest().pop().unwrap();
+
+        let to_connection = serve(provider);
+        fetch_chunk(&to_connection, &chunk, local.path()).await.unwrap();
+        for kind in ChunkFileKind::ALL {
+            let name = kind.file_name(segment, &block_range);
+            assert_eq!(
+                std::fs::read(local.path().join(&name)).unwrap(),
+                std::fs::read(remote.path().join(&name)).unwrap()
+            );
+        }
+
+        // a corrupt manifest is rejected without leaving files behind
+        let local = tempfile::tempdir().unwrap();
+        let mut corrupt = chunk;
+        corrupt.files[0].checksum = B256::ZERO;
+        let err = fetch_chunk(&to_connection, &corrupt, local.path()).await.unwrap_err();
+        assert!(matches!(err, ChunkDownloadError::ChecksumMismatch { .. }));
+        assert_eq!(std::fs::read_dir(local.path()).unwrap().count(), 0);
+    }
+}
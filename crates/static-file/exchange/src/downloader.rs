@@ -0,0 +1,459 @@
+use crate::{
+    fetch_chunk, read_header_chunk, remove_chunk, ChunkDownloadError, ChunkSource,
+    HeaderChunkError, StaticFileExchangeClient,
+};
+use alloy_primitives::BlockNumber;
+use futures::{FutureExt, Stream, StreamExt};
+use reth_consensus::HeaderValidator;
+use reth_db::table::Decompress;
+use reth_network_p2p::headers::{
+    downloader::{HeaderDownloader, SyncTarget},
+    error::{HeadersDownloaderError, HeadersDownloaderResult},
+};
+use reth_primitives_traits::{BlockHeader, SealedHeader};
+use std::{
+    collections::VecDeque,
+    io,
+    pin::Pin,
+    sync::Arc,
+    task::{ready, Context, Poll},
+};
+use tokio::task::JoinHandle;
+use tracing::*;
+
+/// The minimum number of headers that are taken from chunks.
+///
+/// Chunks are large, so they're not worth downloading for small gaps.
+pub const DEFAULT_MIN_CHUNK_HEADERS: u64 = 100_000;
+
+/// A [`HeaderDownloader`] that fills the lower part of the sync gap with headers chunks of the
+/// static file exchange protocol.
+///
+/// Headers are downloaded in reverse, starting at the sync target. Once the inner downloader
+/// returned the header above the consecutive chunks that reach down to the local head, the headers
+/// below it are read from the chunks instead. They're trusted because they extend that header and
+/// the lowest one attaches to the local head. If a chunk can't be downloaded or is invalid, the
+/// rest of the gap is downloaded by the inner downloader.
+pub struct StaticFileHeaderDownloader<D: HeaderDownloader> {
+    /// The downloader for the headers that aren't taken from chunks.
+    inner: D,
+    /// The client of the static file exchange protocol.
+    client: StaticFileExchangeClient,
+    /// Consensus client used to validate headers.
+    consensus: Arc<dyn HeaderValidator<D::Header>>,
+    /// The number of headers that are returned at once.
+    batch_size: usize,
+    /// The minimum number of headers that are taken from chunks.
+    min_chunk_headers: u64,
+    /// The local head of the sync gap.
+    local_head: Option<SealedHeader<D::Header>>,
+    /// The chunks that are synced.
+    sync: Option<ChunkSync<D::Header>>,
+    /// Whether no more chunks are used for the current sync gap.
+    chunks_done: bool,
+    /// Headers from this block on were already returned, so they're skipped in the batches of
+    /// the inner downloader.
+    skip_from: Option<BlockNumber>,
+}
+
+impl<D: HeaderDownloader> StaticFileHeaderDownloader<D> {
+    /// Creates a new downloader that takes headers from the chunks served to the given client.
+    pub fn new(
+        inner: D,
+        client: StaticFileExchangeClient,
+        consensus: Arc<dyn HeaderValidator<D::Header>>,
+    ) -> Self {
+        Self {
+            inner,
+            client,
+            consensus,
+            batch_size: 10_000,
+            min_chunk_headers: DEFAULT_MIN_CHUNK_HEADERS,
+            local_head: None,
+            sync: None,
+            chunks_done: false,
+            skip_from: None,
+        }
+    }
+
+    /// Sets the minimum number of headers that are taken from chunks.
+    pub const fn with_min_chunk_headers(mut self, min_chunk_headers: u64) -> Self {
+        self.min_chunk_headers = min_chunk_headers;
+        self
+    }
+
+    /// Resets the chunk sync for a new sync gap.
+    fn reset(&mut self) {
+        self.sync = None;
+        self.chunks_done = false;
+        self.skip_from = None;
+    }
+}
+
+impl<D> StaticFileHeaderDownloader<D>
+where
+    D: HeaderDownloader<Header: BlockHeader + Decompress>,
+{
+    /// Starts the chunk sync if the chunks reach up to a header of the batch, which is then split
+    /// after that header.
+    fn start_chunk_sync(&mut self, headers: &mut Vec<SealedHeader<D::Header>>) {
+        let (Some(local_head), Some(highest), Some(lowest)) =
+            (&self.local_head, headers.first(), headers.last())
+        else {
+            return
+        };
+        let mut chunks = self.client.header_chunks(local_head.number() + 1, highest.number());
+        let Some(end) = chunks.last().map(|source| source.chunk.block_range.end()) else { return };
+        if end + 1 < lowest.number() || end - local_head.number() < self.min_chunk_headers {
+            return
+        }
+
+        let pending = headers.split_off((highest.number() - end) as usize);
+        let anchor = headers.last().expect("contains the header above the chunks").clone();
+        debug!(target: "static_file::exchange", chunks = chunks.len(), from = local_head.number() + 1, to = end, "Syncing headers from chunks");
+        chunks.reverse();
+        self.sync = Some(ChunkSync {
+            anchor,
+            chunks: chunks.into(),
+            fetch: None,
+            queued: VecDeque::new(),
+            pending,
+        });
+    }
+
+    /// Stops using chunks for the rest of the sync gap and returns the headers of the inner
+    /// downloader that were held back.
+    fn fall_back(&mut self) -> Vec<SealedHeader<D::Header>> {
+        self.chunks_done = true;
+        let Some(sync) = self.sync.take() else { return Vec::new() };
+        let skip_from = sync.anchor.number();
+        self.skip_from = Some(skip_from);
+        let mut pending = sync.pending;
+        pending.retain(|header| header.number() < skip_from);
+        pending
+    }
+}
+
+impl<D> HeaderDownloader for StaticFileHeaderDownloader<D>
+where
+    D: HeaderDownloader<Header: BlockHeader + Decompress>,
+{
+    type Header = D::Header;
+
+    fn update_local_head(&mut self, head: SealedHeader<Self::Header>) {
+        self.reset();
+        self.local_head = Some(head.clone());
+        self.inner.update_local_head(head);
+    }
+
+    fn update_sync_target(&mut self, target: SyncTarget) {
+        self.reset();
+        self.inner.update_sync_target(target);
+    }
+
+    fn set_batch_size(&mut self, limit: usize) {
+        self.batch_size = limit;
+        self.inner.set_batch_size(limit);
+    }
+}
+
+impl<D> Stream for StaticFileHeaderDownloader<D>
+where
+    D: HeaderDownloader<Header: BlockHeader + Decompress>,
+{
+    type Item = HeadersDownloaderResult<Vec<SealedHeader<D::Header>>, D::Header>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if let Some(sync) = &mut this.sync {
+                if !sync.queued.is_empty() {
+                    let len = this.batch_size.clamp(1, sync.queued.len());
+                    return Poll::Ready(Some(Ok(sync.queued.drain(..len).collect())))
+                }
+
+                let local_head = this.local_head.as_ref().expect("sync gap is set");
+                if sync.fetch.is_none() {
+                    let Some(source) = sync.chunks.pop_front() else {
+                        // all headers of the chunks were returned
+                        this.sync = None;
+                        continue
+                    };
+                    let fetch = tokio::task::spawn(fetch_headers(
+                        this.client.clone(),
+                        source.clone(),
+                        sync.anchor.clone(),
+                        local_head.number() + 1,
+                        this.consensus.clone(),
+                    ));
+                    sync.fetch = Some((source, fetch));
+                }
+
+                let (_, fetch) = sync.fetch.as_mut().expect("fetch is in progress");
+                let result = ready!(fetch.poll_unpin(cx))
+                    .map_err(|err| {
+                        HeaderChunkError::from(ChunkDownloadError::Io(io::Error::other(err)))
+                    })
+                    .and_then(|result| result);
+                let (source, _) = sync.fetch.take().expect("fetch is in progress");
+                match result {
+                    Ok(headers) => {
+                        let Some(lowest) = headers.last().cloned() else { continue };
+                        sync.anchor = lowest.clone();
+                        sync.queued = headers.into();
+
+                        if sync.chunks.is_empty() {
+                            // the chunks reached the local head
+                            sync.pending.clear();
+                            this.chunks_done = true;
+                            this.skip_from = Some(lowest.number());
+                            if let Err(error) =
+                                this.consensus.validate_header_against_parent(&lowest, local_head)
+                            {
+                                error!(target: "static_file::exchange", %error, number = lowest.number(), hash = ?lowest.hash(), "Header cannot be attached to known canonical chain");
+                                let local_head = local_head.clone();
+                                this.sync = None;
+                                return Poll::Ready(Some(Err(
+                                    HeadersDownloaderError::DetachedHead {
+                                        local_head: Box::new(local_head),
+                                        header: Box::new(lowest),
+                                        error: Box::new(error),
+                                    },
+                                )))
+                            }
+                        }
+                    }
+                    Err(error) => {
+                        warn!(target: "static_file::exchange", peer_id = %source.peer_id, block_range = %source.chunk.block_range, %error, "Failed to sync headers chunk");
+                        this.client.ban(source.peer_id);
+                        let pending = this.fall_back();
+                        if !pending.is_empty() {
+                            return Poll::Ready(Some(Ok(pending)))
+                        }
+                    }
+                }
+                continue
+            }
+
+            let mut headers = match this.inner.poll_next_unpin(cx) {
+                Poll::Ready(Some(Ok(headers))) => headers,
+                other => return other,
+            };
+            if let Some(skip_from) = this.skip_from {
+                headers.retain(|header| header.number() < skip_from);
+                if headers.is_empty() {
+                    continue
+                }
+            } else if !this.chunks_done {
+                this.start_chunk_sync(&mut headers);
+            }
+            return Poll::Ready(Some(Ok(headers)))
+        }
+    }
+}
+
+impl<D: HeaderDownloader> std::fmt::Debug for StaticFileHeaderDownloader<D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StaticFileHeaderDownloader")
+            .field("client", &self.client)
+            .field("batch_size", &self.batch_size)
+            .field("min_chunk_headers", &self.min_chunk_headers)
+            .field("chunks_done", &self.chunks_done)
+            .field("skip_from", &self.skip_from)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The state of the chunks that fill the lower part of the sync gap.
+struct ChunkSync<H> {
+    /// The lowest trusted header, the chunks must extend it.
+    anchor: SealedHeader<H>,
+    /// The chunks that are left, in reverse order.
+    chunks: VecDeque<ChunkSource>,
+    /// The chunk that is currently downloaded.
+    fetch: Option<(ChunkSource, JoinHandle<Result<Vec<SealedHeader<H>>, HeaderChunkError>>)>,
+    /// Verified headers that weren't returned yet.
+    queued: VecDeque<SealedHeader<H>>,
+    /// Headers of the inner downloader below the anchor, they're returned if the chunks fail.
+    pending: Vec<SealedHeader<H>>,
+}
+
+impl<H> Drop for ChunkSync<H> {
+    fn drop(&mut self) {
+        if let Some((_, fetch)) = &self.fetch {
+            fetch.abort();
+        }
+    }
+}
+
+/// Downloads the chunk and returns its headers below the trusted `anchor`, down to `lowest`.
+///
+/// The files of the chunk are removed once they were read.
+async fn fetch_headers<H>(
+    client: StaticFileExchangeClient,
+    source: ChunkSource,
+    anchor: SealedHeader<H>,
+    lowest: BlockNumber,
+    consensus: Arc<dyn HeaderValidator<H>>,
+) -> Result<Vec<SealedHeader<H>>, HeaderChunkError>
+where
+    H: BlockHeader + Decompress + 'static,
+{
+    let directory = client.directory().to_path_buf();
+    let result = fetch_chunk(&source.to_connection, &source.chunk, &directory).await;
+    if let Err(err) = result {
+        remove_chunk(&directory, &source.chunk)?;
+        return Err(err.into())
+    }
+
+    // reading the headers of a chunk takes a while
+    tokio::task::spawn_blocking(move || {
+        let headers = read_header_chunk(&directory, &source.chunk, &anchor, lowest, &*consensus);
+        remove_chunk(&directory, &source.chunk)?;
+        headers
+    })
+    .await
+    .map_err(|err| ChunkDownloadError::Io(io::Error::other(err)))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ProtocolEvent, StaticFileDirectory, StaticFileExchangeProvider, StaticFilePeerRequest,
+    };
+    use alloy_consensus::Header;
+    use alloy_primitives::{B256, U256};
+    use reth_consensus::test_utils::TestConsensus;
+    use reth_ethereum_primitives::EthPrimitives;
+    use reth_network_api::{Direction, PeerId};
+    use reth_provider::{providers::StaticFileProvider, StaticFileWriter};
+    use reth_static_file_types::{HighestStaticFiles, StaticFileSegment};
+    use reth_testing_utils::generators::{self, random_header, random_header_range};
+    use std::path::Path;
+    use tokio::sync::mpsc;
+
+    /// Returns the given batches of headers.
+    struct BatchDownloader(VecDeque<Vec<SealedHeader>>);
+
+    impl HeaderDownloader for BatchDownloader {
+        type Header = Header;
+
+        fn update_local_head(&mut self, _head: SealedHeader) {}
+
+        fn update_sync_target(&mut self, _target: SyncTarget) {}
+
+        fn set_batch_size(&mut self, _limit: usize) {}
+    }
+
+    impl Stream for BatchDownloader {
+        type Item = HeadersDownloaderResult<Vec<SealedHeader>, Header>;
+
+        fn poll_next(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            Poll::Ready(self.get_mut().0.pop_front().map(Ok))
+        }
+    }
+
+    /// Writes the headers to static files of 10 blocks and serves them to the returned client.
+    fn serve_headers(directory: &Path, headers: &[SealedHeader]) -> StaticFileExchangeClient {
+        let remote = directory.join("remote");
+        {
+            let provider = StaticFileProvider::<EthPrimitives>::read_write(&remote)
+                .unwrap()
+                .with_custom_blocks_per_file(10);
+            let mut writer = provider.latest_writer(StaticFileSegment::Headers).unwrap();
+            for header in headers {
+                writer.append_header(header.header(), U256::ZERO, &header.hash()).unwrap();
+            }
+            writer.commit().unwrap();
+        }
+
+        let provider = StaticFileDirectory::new(remote);
+        let highest = HighestStaticFiles {
+            headers: headers.last().map(|header| header.number),
+            ..Default::default()
+        };
+        provider.refresh(highest, u64::MAX).unwrap();
+        let chunks = provider.manifest();
+
+        let (to_connection, mut rx) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            while let Some(request) = rx.recv().await {
+                if let StaticFilePeerRequest::GetChunkData { request, tx } = request {
+                    let _ = tx.send(provider.chunk_data(request).unwrap());
+                }
+            }
+        });
+
+        let client = StaticFileExchangeClient::new(directory.join("download")).unwrap();
+        let peer_id = PeerId::repeat_byte(1);
+        client.on_event(ProtocolEvent::Established {
+            direction: Direction::Outgoing(peer_id),
+            peer_id,
+            to_connection,
+        });
+        client.on_event(ProtocolEvent::Manifest { peer_id, chunks });
+        client
+    }
+
+    /// Syncs the gap above the local head and returns the headers.
+    async fn sync(
+        client: StaticFileExchangeClient,
+        headers: &[SealedHeader],
+        local_head: &SealedHeader,
+    ) -> Vec<SealedHeader> {
+        let batches = headers[local_head.number as usize + 1..]
+            .rchunks(10)
+            .map(|batch| batch.iter().rev().cloned().collect())
+            .collect();
+        let mut downloader = StaticFileHeaderDownloader::new(
+            BatchDownloader(batches),
+            client,
+            Arc::new(TestConsensus::default()),
+        )
+        .with_min_chunk_headers(0);
+        downloader.set_batch_size(7);
+        let tip = headers.last().unwrap().hash();
+        downloader.update_sync_gap(local_head.clone(), SyncTarget::Tip(tip));
+
+        let mut synced = Vec::new();
+        while synced
+            .last()
+            .is_none_or(|header: &SealedHeader| header.number > local_head.number + 1)
+        {
+            synced.extend(downloader.next().await.unwrap().unwrap());
+        }
+        synced
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn sync_headers_from_chunks() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut rng = generators::rng();
+        let headers = random_header_range(&mut rng, 0..60, B256::ZERO);
+        let client = serve_headers(dir.path(), &headers);
+
+        let synced = sync(client.clone(), &headers, &headers[5]).await;
+        assert!(synced.iter().eq(headers[6..].iter().rev()));
+        // the chunks were removed after reading them
+        assert_eq!(std::fs::read_dir(client.directory()).unwrap().count(), 0);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn forged_chunk_falls_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut rng = generators::rng();
+        let headers = random_header_range(&mut rng, 0..60, B256::ZERO);
+        // the peer serves a chunk that doesn't extend the chain
+        let mut forged = headers.clone();
+        for header in &mut forged[20..30] {
+            *header = random_header(&mut rng, header.number, Some(header.parent_hash));
+        }
+        let client = serve_headers(dir.path(), &forged);
+
+        let synced = sync(client.clone(), &headers, &headers[5]).await;
+        assert!(synced.iter().eq(headers[6..].iter().rev()));
+        assert!(client.header_chunks(6, 60).is_empty());
+    }
+}
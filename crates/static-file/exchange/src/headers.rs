@@ -0,0 +1,115 @@
+use crate::{ChunkDownloadError, ChunkFileKind, StaticFileChunk};
+use alloy_primitives::{BlockNumber, B256};
+use reth_consensus::HeaderValidator;
+use reth_db::{
+    static_file::{HeaderMask, StaticFileCursor},
+    table::Decompress,
+};
+use reth_network_p2p::{error::DownloadError, headers::downloader::validate_header_download};
+use reth_nippy_jar::NippyJar;
+use reth_primitives_traits::{BlockHeader, SealedHeader};
+use reth_static_file_types::{SegmentHeader, SegmentRangeInclusive, StaticFileSegment};
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
+use std::{path::Path, sync::Arc};
+
+/// Errors of a downloaded headers chunk.
+#[derive(Debug, thiserror::Error)]
+pub enum HeaderChunkError {
+    /// The chunk couldn't be downloaded.
+    #[error(transparent)]
+    Download(#[from] ChunkDownloadError),
+    /// The static file of the chunk isn't the advertised one.
+    #[error("static file of {segment} {block_range} doesn't match the chunk")]
+    UnexpectedStaticFile {
+        /// The advertised segment.
+        segment: StaticFileSegment,
+        /// The advertised block range.
+        block_range: SegmentRangeInclusive,
+    },
+    /// A header is missing from the chunk.
+    #[error("header {0} is missing from the chunk")]
+    MissingHeader(BlockNumber),
+    /// A header of the chunk is not the parent of the next header.
+    #[error("header {number} has hash {got}, expected {expected}")]
+    HashMismatch {
+        /// The block number of the header.
+        number: BlockNumber,
+        /// The parent hash of the next header.
+        expected: B256,
+        /// The hash of the header of the chunk.
+        got: B256,
+    },
+    /// A header of the chunk is invalid.
+    #[error(transparent)]
+    Validation(#[from] DownloadError),
+    /// The static file of the chunk couldn't be read.
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+}
+
+/// Reads the headers of a headers chunk in the given directory that precede the trusted `child`
+/// header, down to `lowest`.
+///
+/// The chunk is only trusted as far as it extends `child`: every header must hash to the parent
+/// hash of the header above it and is validated against it, so a peer can't serve forged
+/// headers. The headers are returned in reverse order, like the headers downloader returns them.
+pub fn read_header_chunk<H>(
+    directory: &Path,
+    chunk: &StaticFileChunk,
+    child: &SealedHeader<H>,
+    lowest: BlockNumber,
+    consensus: &dyn HeaderValidator<H>,
+) -> Result<Vec<SealedHeader<H>>, HeaderChunkError>
+where
+    H: BlockHeader + Decompress,
+{
+    let path = directory.join(ChunkFileKind::Data.file_name(chunk.segment, &chunk.block_range));
+    let jar = NippyJar::<SegmentHeader>::load(&path).map_err(ProviderError::other)?;
+    if chunk.segment != StaticFileSegment::Headers ||
+        jar.user_header().segment() != chunk.segment ||
+        jar.user_header().block_range() != Some(&chunk.block_range)
+    {
+        return Err(HeaderChunkError::UnexpectedStaticFile {
+            segment: chunk.segment,
+            block_range: chunk.block_range,
+        })
+    }
+    let mut cursor = StaticFileCursor::new(
+        &jar,
+        Arc::new(jar.open_data_reader().map_err(ProviderError::other)?),
+    )?;
+
+    let lowest = lowest.max(chunk.block_range.start());
+    let mut headers = Vec::with_capacity(child.number().saturating_sub(lowest) as usize);
+    let mut child = child.clone();
+    for number in (lowest..child.number()).rev() {
+        let header = cursor
+            .get_one::<HeaderMask<H>>(number.into())?
+            .ok_or(HeaderChunkError::MissingHeader(number))?;
+        let header = SealedHeader::seal_slow(header);
+        if header.hash() != child.parent_hash() {
+            return Err(HeaderChunkError::HashMismatch {
+                number,
+                expected: child.parent_hash(),
+                got: header.hash(),
+            })
+        }
+        validate_header_download(consensus, &child, &header)?;
+
+        headers.push(header.clone());
+        child = header;
+    }
+
+    Ok(headers)
+}
+
+/// Removes the files of a downloaded chunk from the given directory.
+pub fn remove_chunk(directory: &Path, chunk: &StaticFileChunk) -> ProviderResult<()> {
+    for kind in ChunkFileKind::ALL {
+        let path = directory.join(kind.file_name(chunk.segment, &chunk.block_range));
+        if path.exists() {
+            reth_fs_util::remove_file(&path).map_err(ProviderError::other)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,49 @@
+//! Static file exchange is an `RLPx` subprotocol between reth nodes.
+//! following [RLPx specs](https://github.com/ethereum/devp2p/blob/master/rlpx.md)
+//!
+//! Peers advertise the finalized static file chunks they can serve in a [`StaticFileChunk`]
+//! manifest. A chunk is downloaded as a whole by requesting its files in parts into a separate
+//! download directory, see [`fetch_chunk`].
+//!
+//! During historical backfill, the [`StaticFileHeaderDownloader`] fills the lower part of the
+//! headers sync gap with headers chunks instead of downloading the headers one by one. The
+//! headers of a chunk are only used if they extend the headers that were already downloaded from
+//! the sync target and attach to the local head.
+//!
+//! The protocol is opt-in, it's enabled by installing the [`StaticFileExchangeHandler`] as an
+//! additional `RLPx` subprotocol of the network.
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+mod types;
+pub use types::*;
+
+mod message;
+pub use message::*;
+
+mod provider;
+pub use provider::*;
+
+mod handlers;
+pub use handlers::*;
+
+mod connection;
+pub use connection::{StaticFileExchangeConnection, StaticFilePeerRequest};
+
+mod download;
+pub use download::{fetch_chunk, ChunkDownloadError, CHUNK_DATA_TIMEOUT};
+
+mod headers;
+pub use headers::{read_header_chunk, remove_chunk, HeaderChunkError};
+
+mod client;
+pub use client::{ChunkSource, StaticFileExchangeClient};
+
+mod downloader;
+pub use downloader::{StaticFileHeaderDownloader, DEFAULT_MIN_CHUNK_HEADERS};
@@ -0,0 +1,212 @@
+//! Implements the static file exchange protocol.
+//! Defines structs/enums for messages, request-response pairs.
+
+use crate::{GetChunkData, StaticFileChunk};
+use alloy_primitives::{
+    bytes::{Buf, BufMut},
+    Bytes,
+};
+use alloy_rlp::{BytesMut, Decodable, Encodable};
+use reth_eth_wire::{message::RequestPair, protocol::Protocol, Capability};
+
+/// A static file exchange protocol message, containing a message ID and payload.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct StaticFileExchangeMessage {
+    /// The unique identifier representing the type of the message.
+    pub message_type: StaticFileExchangeMessageID,
+    /// The content of the message, including specific data based on the message type.
+    pub message: StaticFileExchangeMessageKind,
+}
+
+impl StaticFileExchangeMessage {
+    /// Returns the capability for the static file exchange protocol.
+    pub const fn capability() -> Capability {
+        Capability::new_static("rethsf", 1)
+    }
+
+    /// Returns the protocol for the static file exchange protocol.
+    pub const fn protocol() -> Protocol {
+        Protocol::new(Self::capability(), 3)
+    }
+
+    /// Manifest of the chunks this node serves.
+    pub const fn manifest(chunks: Vec<StaticFileChunk>) -> Self {
+        StaticFileExchangeMessageKind::Manifest(chunks).into_protocol_message()
+    }
+
+    /// Chunk data request.
+    pub const fn get_chunk_data(request_id: u64, request: GetChunkData) -> Self {
+        StaticFileExchangeMessageKind::GetChunkData(RequestPair { request_id, message: request })
+            .into_protocol_message()
+    }
+
+    /// Chunk data response.
+    pub const fn chunk_data(request_id: u64, data: Bytes) -> Self {
+        StaticFileExchangeMessageKind::ChunkData(RequestPair { request_id, message: data })
+            .into_protocol_message()
+    }
+
+    /// Return RLP encoded message.
+    pub fn encoded(&self) -> BytesMut {
+        let mut buf = BytesMut::with_capacity(self.length());
+        self.encode(&mut buf);
+        buf
+    }
+
+    /// Decodes a `StaticFileExchangeMessage` from the given message buffer.
+    pub fn decode_message(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let message_type = StaticFileExchangeMessageID::decode(buf)?;
+        let message = match message_type {
+            StaticFileExchangeMessageID::Manifest => {
+                StaticFileExchangeMessageKind::Manifest(Vec::decode(buf)?)
+            }
+            StaticFileExchangeMessageID::GetChunkData => {
+                StaticFileExchangeMessageKind::GetChunkData(RequestPair::decode(buf)?)
+            }
+            StaticFileExchangeMessageID::ChunkData => {
+                StaticFileExchangeMessageKind::ChunkData(RequestPair::decode(buf)?)
+            }
+        };
+        Ok(Self { message_type, message })
+    }
+}
+
+impl Encodable for StaticFileExchangeMessage {
+    fn encode(&self, out: &mut dyn BufMut) {
+        self.message_type.encode(out);
+        self.message.encode(out);
+    }
+
+    fn length(&self) -> usize {
+        self.message_type.length() + self.message.length()
+    }
+}
+
+/// Represents message IDs for static file exchange protocol messages.
+#[repr(u8)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum StaticFileExchangeMessageID {
+    /// Manifest message.
+    Manifest = 0x00,
+
+    /// Chunk data request message.
+    GetChunkData = 0x01,
+    /// Chunk data response message.
+    ChunkData = 0x02,
+}
+
+impl Encodable for StaticFileExchangeMessageID {
+    fn encode(&self, out: &mut dyn BufMut) {
+        out.put_u8(*self as u8);
+    }
+
+    fn length(&self) -> usize {
+        1
+    }
+}
+
+impl Decodable for StaticFileExchangeMessageID {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let id = match buf.first().ok_or(alloy_rlp::Error::InputTooShort)? {
+            0x00 => Self::Manifest,
+            0x01 => Self::GetChunkData,
+            0x02 => Self::ChunkData,
+            _ => return Err(alloy_rlp::Error::Custom("Invalid message type")),
+        };
+        buf.advance(1);
+        Ok(id)
+    }
+}
+
+/// Represents a message in the static file exchange protocol.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum StaticFileExchangeMessageKind {
+    /// Represents the manifest of the chunks a peer serves, sent after the connection is
+    /// established and whenever it changes.
+    Manifest(Vec<StaticFileChunk>),
+
+    /// Represents a chunk data request message.
+    GetChunkData(RequestPair<GetChunkData>),
+    /// Represents a chunk data response message.
+    ChunkData(RequestPair<Bytes>),
+}
+
+impl StaticFileExchangeMessageKind {
+    /// Return [`StaticFileExchangeMessageID`] that corresponds to the given message.
+    pub const fn message_id(&self) -> StaticFileExchangeMessageID {
+        match self {
+            Self::Manifest(_) => StaticFileExchangeMessageID::Manifest,
+            Self::GetChunkData(_) => StaticFileExchangeMessageID::GetChunkData,
+            Self::ChunkData(_) => StaticFileExchangeMessageID::ChunkData,
+        }
+    }
+
+    /// Convert message into [`StaticFileExchangeMessage`].
+    pub const fn into_protocol_message(self) -> StaticFileExchangeMessage {
+        let message_type = self.message_id();
+        StaticFileExchangeMessage { message_type, message: self }
+    }
+}
+
+impl From<StaticFileExchangeMessageKind> for StaticFileExchangeMessage {
+    fn from(value: StaticFileExchangeMessageKind) -> Self {
+        value.into_protocol_message()
+    }
+}
+
+impl Encodable for StaticFileExchangeMessageKind {
+    fn encode(&self, out: &mut dyn BufMut) {
+        match self {
+            Self::Manifest(chunks) => chunks.encode(out),
+            Self::GetChunkData(request) => request.encode(out),
+            Self::ChunkData(data) => data.encode(out),
+        }
+    }
+
+    fn length(&self) -> usize {
+        match self {
+            Self::Manifest(chunks) => chunks.length(),
+            Self::GetChunkData(request) => request.length(),
+            Self::ChunkData(data) => data.length(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ChunkFile, ChunkFileKind};
+    use alloy_primitives::B256;
+    use reth_static_file_types::{SegmentRangeInclusive, StaticFileSegment};
+
+    #[test]
+    fn message_roundtrip() {
+        let messages = [
+            StaticFileExchangeMessage::manifest(vec![StaticFileChunk {
+                segment: StaticFileSegment::Headers,
+                block_range: SegmentRangeInclusive::new(0, 499_999),
+                files: vec![ChunkFile {
+                    kind: ChunkFileKind::Data,
+                    size: 42,
+                    checksum: B256::random(),
+                }],
+            }]),
+            StaticFileExchangeMessage::get_chunk_data(
+                1,
+                GetChunkData {
+                    segment: StaticFileSegment::Receipts,
+                    block_start: 500_000,
+                    kind: ChunkFileKind::Config,
+                    offset: 0,
+                    limit: 1024,
+                },
+            ),
+            StaticFileExchangeMessage::chunk_data(1, Bytes::from_static(&[1, 2, 3])),
+        ];
+        for message in messages {
+            let encoded = message.encoded();
+            let decoded = StaticFileExchangeMessage::decode_message(&mut &encoded[..]);
+            assert_eq!(Ok(message), decoded);
+        }
+    }
+}
@@ -0,0 +1,223 @@
+use crate::{ChunkFile, ChunkFileKind, GetChunkData, StaticFileChunk};
+use alloy_primitives::{BlockNumber, Bytes, B256};
+use parking_lot::RwLock;
+use reth_nippy_jar::{compression::Compressors, NippyJar};
+use reth_static_file_types::{HighestStaticFiles, SegmentHeader, StaticFileSegment};
+use std::{
+    fs::File,
+    io::{self, BufReader, Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tracing::*;
+
+/// The maximum number of bytes of a chunk file that is served per request.
+pub const MAX_CHUNK_DATA_SERVE: u64 = 2 * 1024 * 1024;
+
+/// A provider trait for the static file exchange protocol.
+pub trait StaticFileExchangeProvider: Send + Sync {
+    /// Return the chunks this node serves.
+    fn manifest(&self) -> Vec<StaticFileChunk>;
+
+    /// Return the requested part of a chunk file.
+    ///
+    /// At most [`MAX_CHUNK_DATA_SERVE`] bytes are returned, the response is empty if the chunk is
+    /// not served.
+    fn chunk_data(&self, request: GetChunkData) -> io::Result<Bytes>;
+}
+
+/// Serves the complete, finalized static files of a static files directory.
+#[derive(Debug, Clone)]
+pub struct StaticFileDirectory {
+    inner: Arc<StaticFileDirectoryInner>,
+}
+
+#[derive(Debug)]
+struct StaticFileDirectoryInner {
+    /// The static files directory.
+    directory: PathBuf,
+    /// The chunks that are served.
+    manifest: RwLock<Vec<StaticFileChunk>>,
+}
+
+impl StaticFileDirectory {
+    /// Creates a new instance serving the given static files directory.
+    ///
+    /// No chunks are served until [`Self::refresh`] is called.
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            inner: Arc::new(StaticFileDirectoryInner {
+                directory: directory.into(),
+                manifest: Default::default(),
+            }),
+        }
+    }
+
+    /// Returns the static files directory.
+    pub fn directory(&self) -> &Path {
+        &self.inner.directory
+    }
+
+    /// Updates the served chunks to the static files that are complete and finalized.
+    ///
+    /// A static file is complete once the highest static file block of its segment reached the end
+    /// of its block range. Encrypted static files are not served. Computing the checksums of new
+    /// chunks reads their files entirely, so this should be called on a blocking thread.
+    ///
+    /// Returns `true` if the served chunks changed.
+    pub fn refresh(&self, highest: HighestStaticFiles, finalized: BlockNumber) -> io::Result<bool> {
+        let previous = self.manifest();
+
+        let mut chunks = Vec::new();
+        for entry in std::fs::read_dir(self.directory())? {
+            let entry = entry?;
+            let Some((segment, block_range)) =
+                entry.file_name().to_str().and_then(StaticFileSegment::parse_filename)
+            else {
+                continue
+            };
+            if block_range.end() > finalized ||
+                highest.highest(segment).is_none_or(|highest| highest < block_range.end())
+            {
+                continue
+            }
+
+            if let Some(chunk) = previous
+                .iter()
+                .find(|chunk| chunk.segment == segment && chunk.block_range == block_range)
+            {
+                chunks.push(chunk.clone());
+                continue
+            }
+
+            // other nodes can't read encrypted static files
+            if NippyJar::<SegmentHeader>::load(
+                &self.directory().join(segment.filename(&block_range)),
+            )
+            .is_ok_and(|jar| matches!(jar.compressor(), Some(Compressors::Encrypted(_))))
+            {
+                continue
+            }
+
+            let mut files = Vec::with_capacity(ChunkFileKind::ALL.len());
+            for kind in ChunkFileKind::ALL {
+                let path = self.directory().join(kind.file_name(segment, &block_range));
+                match chunk_file(&path, kind) {
+                    Ok(file) => files.push(file),
+                    Err(err) => {
+                        debug!(target: "static_file::exchange", ?path, %err, "Failed to read chunk file");
+                        break
+                    }
+                }
+            }
+            if files.len() == ChunkFileKind::ALL.len() {
+                chunks.push(StaticFileChunk { segment, block_range, files });
+            }
+        }
+        chunks.sort_unstable_by_key(|chunk| (chunk.block_range.start(), chunk.segment));
+
+        let changed = chunks != previous;
+        if changed {
+            debug!(target: "static_file::exchange", chunks = chunks.len(), "Updated served static file chunks");
+            *self.inner.manifest.write() = chunks;
+        }
+        Ok(changed)
+    }
+}
+
+impl StaticFileExchangeProvider for StaticFileDirectory {
+    fn manifest(&self) -> Vec<StaticFileChunk> {
+        self.inner.manifest.read().clone()
+    }
+
+    fn chunk_data(&self, request: GetChunkData) -> io::Result<Bytes> {
+        let manifest = self.inner.manifest.read();
+        let Some((chunk, file)) = manifest
+            .iter()
+            .find(|chunk| {
+                chunk.segment == request.segment && chunk.block_range.start() == request.block_start
+            })
+            .and_then(|chunk| Some((chunk, chunk.file(request.kind)?)))
+        else {
+            return Ok(Bytes::new())
+        };
+
+        let len =
+            request.limit.min(MAX_CHUNK_DATA_SERVE).min(file.size.saturating_sub(request.offset));
+        if len == 0 {
+            return Ok(Bytes::new())
+        }
+
+        let mut file = File::open(
+            self.directory().join(request.kind.file_name(chunk.segment, &chunk.block_range)),
+        )?;
+        file.seek(SeekFrom::Start(request.offset))?;
+        let mut data = Vec::with_capacity(len as usize);
+        file.take(len).read_to_end(&mut data)?;
+        Ok(data.into())
+    }
+}
+
+/// Returns the size and checksum of the file at the given path.
+fn chunk_file(path: &Path, kind: ChunkFileKind) -> io::Result<ChunkFile> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut hasher = blake3::Hasher::new();
+    let size = io::copy(&mut reader, &mut hasher)?;
+    Ok(ChunkFile { kind, size, checksum: B256::from(*hasher.finalize().as_bytes()) })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_static_file_types::SegmentRangeInclusive;
+
+    /// Writes the files of a chunk with the given contents to the directory.
+    fn write_chunk(
+        directory: &Path,
+        segment: StaticFileSegment,
+        block_range: SegmentRangeInclusive,
+        contents: &[u8],
+    ) {
+        for kind in ChunkFileKind::ALL {
+            std::fs::write(directory.join(kind.file_name(segment, &block_range)), contents)
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn serves_complete_finalized_chunks() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = SegmentRangeInclusive::new(0, 499_999);
+        let second = SegmentRangeInclusive::new(500_000, 999_999);
+        write_chunk(dir.path(), StaticFileSegment::Headers, first, &[1; 1000]);
+        write_chunk(dir.path(), StaticFileSegment::Headers, second, &[2; 10]);
+
+        let provider = StaticFileDirectory::new(dir.path());
+        let highest = HighestStaticFiles { headers: Some(600_000), ..Default::default() };
+        assert!(provider.refresh(highest, 1_000_000).unwrap());
+
+        // the second file is still being written
+        let manifest = provider.manifest();
+        assert_eq!(manifest.len(), 1);
+        assert_eq!(manifest[0].block_range, first);
+        assert_eq!(manifest[0].size(), 3000);
+        assert!(!provider.refresh(highest, 1_000_000).unwrap());
+
+        let request = GetChunkData {
+            segment: StaticFileSegment::Headers,
+            block_start: 0,
+            kind: ChunkFileKind::Data,
+            offset: 900,
+            limit: 1000,
+        };
+        assert_eq!(provider.chunk_data(request).unwrap().len(), 100);
+        let request = GetChunkData { block_start: 500_000, ..request };
+        assert!(provider.chunk_data(request).unwrap().is_empty());
+
+        // not finalized
+        let highest = HighestStaticFiles { headers: Some(1_000_000), ..Default::default() };
+        assert!(!provider.refresh(highest, 999_998).unwrap());
+        assert!(provider.refresh(highest, 999_999).unwrap());
+        assert_eq!(provider.manifest().len(), 2);
+    }
+}
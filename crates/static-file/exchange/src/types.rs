@@ -0,0 +1,274 @@
+use alloy_primitives::{
+    bytes::{Buf, BufMut},
+    BlockNumber, B256,
+};
+use alloy_rlp::{Decodable, Encodable, RlpDecodable, RlpEncodable};
+use reth_static_file_types::{SegmentRangeInclusive, StaticFileSegment};
+
+/// A file of a static file chunk.
+#[repr(u8)]
+#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug)]
+pub enum ChunkFileKind {
+    /// The data file.
+    Data = 0x00,
+    /// The offsets of the rows in the data file.
+    Offsets,
+    /// The configuration of the static file.
+    Config,
+}
+
+impl ChunkFileKind {
+    /// All files of a chunk.
+    pub const ALL: [Self; 3] = [Self::Data, Self::Offsets, Self::Config];
+
+    /// Returns the name of the file of the given chunk in the static files directory.
+    pub fn file_name(
+        &self,
+        segment: StaticFileSegment,
+        block_range: &SegmentRangeInclusive,
+    ) -> String {
+        let data = segment.filename(block_range);
+        match self {
+            Self::Data => data,
+            Self::Offsets => format!("{data}.off"),
+            Self::Config => format!("{data}.conf"),
+        }
+    }
+}
+
+impl Encodable for ChunkFileKind {
+    fn encode(&self, out: &mut dyn BufMut) {
+        out.put_u8(*self as u8);
+    }
+
+    fn length(&self) -> usize {
+        1
+    }
+}
+
+impl Decodable for ChunkFileKind {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let kind = match buf.first().ok_or(alloy_rlp::Error::InputTooShort)? {
+            0x00 => Self::Data,
+            0x01 => Self::Offsets,
+            0x02 => Self::Config,
+            _ => return Err(alloy_rlp::Error::Custom("Invalid chunk file kind")),
+        };
+        buf.advance(1);
+        Ok(kind)
+    }
+}
+
+/// A file of a chunk advertised in the manifest.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, RlpEncodable, RlpDecodable)]
+pub struct ChunkFile {
+    /// The kind of the file.
+    pub kind: ChunkFileKind,
+    /// The size of the file in bytes.
+    pub size: u64,
+    /// The blake3 checksum of the file.
+    pub checksum: B256,
+}
+
+/// A finalized static file that a peer can serve as a whole.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct StaticFileChunk {
+    /// The segment of the static file.
+    pub segment: StaticFileSegment,
+    /// The block range of the static file.
+    pub block_range: SegmentRangeInclusive,
+    /// The files of the static file.
+    pub files: Vec<ChunkFile>,
+}
+
+impl StaticFileChunk {
+    /// Returns the advertised file of the given kind.
+    pub fn file(&self, kind: ChunkFileKind) -> Option<&ChunkFile> {
+        self.files.iter().find(|file| file.kind == kind)
+    }
+
+    /// Returns the total size of the files in bytes.
+    pub fn size(&self) -> u64 {
+        self.files.iter().map(|file| file.size).sum()
+    }
+
+    fn payload_length(&self) -> usize {
+        segment_id(self.segment).length() +
+            self.block_range.start().length() +
+            self.block_range.end().length() +
+            self.files.length()
+    }
+}
+
+impl Encodable for StaticFileChunk {
+    fn encode(&self, out: &mut dyn BufMut) {
+        alloy_rlp::Header { list: true, payload_length: self.payload_length() }.encode(out);
+        segment_id(self.segment).encode(out);
+        self.block_range.start().encode(out);
+        self.block_range.end().encode(out);
+        self.files.encode(out);
+    }
+
+    fn length(&self) -> usize {
+        let payload_length = self.payload_length();
+        payload_length + alloy_rlp::length_of_length(payload_length)
+    }
+}
+
+impl Decodable for StaticFileChunk {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        decode_list(buf, |buf| {
+            Ok(Self {
+                segment: decode_segment(buf)?,
+                block_range: SegmentRangeInclusive::new(
+                    BlockNumber::decode(buf)?,
+                    BlockNumber::decode(buf)?,
+                ),
+                files: Vec::decode(buf)?,
+            })
+        })
+    }
+}
+
+/// A request for a part of a file of a chunk.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct GetChunkData {
+    /// The segment of the static file.
+    pub segment: StaticFileSegment,
+    /// The first block of the static file.
+    pub block_start: BlockNumber,
+    /// The requested file.
+    pub kind: ChunkFileKind,
+    /// The offset in the file to read from.
+    pub offset: u64,
+    /// The maximum number of bytes to return.
+    pub limit: u64,
+}
+
+impl GetChunkData {
+    fn payload_length(&self) -> usize {
+        segment_id(self.segment).length() +
+            self.block_start.length() +
+            self.kind.length() +
+            self.offset.length() +
+            self.limit.length()
+    }
+}
+
+impl Encodable for GetChunkData {
+    fn encode(&self, out: &mut dyn BufMut) {
+        alloy_rlp::Header { list: true, payload_length: self.payload_length() }.encode(out);
+        segment_id(self.segment).encode(out);
+        self.block_start.encode(out);
+        self.kind.encode(out);
+        self.offset.encode(out);
+        self.limit.encode(out);
+    }
+
+    fn length(&self) -> usize {
+        let payload_length = self.payload_length();
+        payload_length + alloy_rlp::length_of_length(payload_length)
+    }
+}
+
+impl Decodable for GetChunkData {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        decode_list(buf, |buf| {
+            Ok(Self {
+                segment: decode_segment(buf)?,
+                block_start: BlockNumber::decode(buf)?,
+                kind: ChunkFileKind::decode(buf)?,
+                offset: u64::decode(buf)?,
+                limit: u64::decode(buf)?,
+            })
+        })
+    }
+}
+
+/// Returns the wire identifier of the segment.
+const fn segment_id(segment: StaticFileSegment) -> u8 {
+    match segment {
+        StaticFileSegment::Headers => 0x00,
+        StaticFileSegment::Transactions => 0x01,
+        StaticFileSegment::Receipts => 0x02,
+        StaticFileSegment::BlockMeta => 0x03,
+    }
+}
+
+fn decode_segment(buf: &mut &[u8]) -> alloy_rlp::Result<StaticFileSegment> {
+    Ok(match u8::decode(buf)? {
+        0x00 => StaticFileSegment::Headers,
+        0x01 => StaticFileSegment::Transactions,
+        0x02 => StaticFileSegment::Receipts,
+        0x03 => StaticFileSegment::BlockMeta,
+        _ => return Err(alloy_rlp::Error::Custom("Invalid static file segment")),
+    })
+}
+
+/// Decodes an RLP list with the given function, which must consume the whole payload.
+fn decode_list<T>(
+    buf: &mut &[u8],
+    f: impl FnOnce(&mut &[u8]) -> alloy_rlp::Result<T>,
+) -> alloy_rlp::Result<T> {
+    let header = alloy_rlp::Header::decode(buf)?;
+    if !header.list {
+        return Err(alloy_rlp::Error::UnexpectedString)
+    }
+    let started_len = buf.len();
+    let value = f(buf)?;
+    let consumed = started_len - buf.len();
+    if consumed != header.payload_length {
+        return Err(alloy_rlp::Error::ListLengthMismatch {
+            expected: header.payload_length,
+            got: consumed,
+        })
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunk_roundtrip() {
+        let chunk = StaticFileChunk {
+            segment: StaticFileSegment::Transactions,
+            block_range: SegmentRangeInclusive::new(500_000, 999_999),
+            files: ChunkFileKind::ALL
+                .into_iter()
+                .map(|kind| ChunkFile { kind, size: 1024, checksum: B256::random() })
+                .collect(),
+        };
+        let encoded = alloy_rlp::encode(&chunk);
+        assert_eq!(encoded.len(), chunk.length());
+        assert_eq!(StaticFileChunk::decode(&mut &encoded[..]), Ok(chunk));
+
+        let request = GetChunkData {
+            segment: StaticFileSegment::Headers,
+            block_start: 0,
+            kind: ChunkFileKind::Offsets,
+            offset: 4096,
+            limit: 1024,
+        };
+        let encoded = alloy_rlp::encode(request);
+        assert_eq!(GetChunkData::decode(&mut &encoded[..]), Ok(request));
+    }
+
+    #[test]
+    fn file_names() {
+        let range = SegmentRangeInclusive::new(0, 499_999);
+        assert_eq!(
+            ChunkFileKind::Data.file_name(StaticFileSegment::Headers, &range),
+            "static_file_headers_0_499999"
+        );
+        assert_eq!(
+            ChunkFileKind::Offsets.file_name(StaticFileSegment::Headers, &range),
+            "static_file_headers_0_499999.off"
+        );
+        assert_eq!(
+            ChunkFileKind::Config.file_name(StaticFileSegment::Headers, &range),
+            "static_file_headers_0_499999.conf"
+        );
+    }
+}
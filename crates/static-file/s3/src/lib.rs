@@ -0,0 +1,346 @@
+//! S3-compatible object storage backend for static files.
+//!
+//! [`S3Backend`] implements [`StaticFileBackend`], so that the static file provider reads the
+//! static files that don't exist locally from a bucket and caches them in the local segment
+//! directories. This allows keeping the archive history in object storage, and sharing one copy
+//! of it between multiple nodes, e.g. stateless RPC replicas.
+//!
+//! The backend only reads from the bucket, static files are uploaded with external tools, e.g.
+//! `aws s3 sync`.
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+mod sign;
+pub use sign::S3Credentials;
+
+use reqwest::{Client, Response};
+use reth_provider::providers::StaticFileBackend;
+use std::{
+    fs::File,
+    future::Future,
+    io::{self, Write},
+    path::Path,
+    sync::{mpsc, Arc},
+    time::{Duration, SystemTime},
+};
+use tokio::{runtime::Handle, sync::oneshot};
+use tracing::debug;
+use url::Url;
+
+/// The region requests are signed for if none is configured.
+pub const DEFAULT_S3_REGION: &str = "us-east-1";
+
+/// The timeout for connecting to the endpoint.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The timeout for receiving the next chunk of a response. Downloads of large static files take
+/// long in total, so only stalled responses time out.
+const READ_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A [`StaticFileBackend`] that reads static files from an S3-compatible bucket.
+///
+/// The bucket is addressed with a path-style URL, e.g.
+/// `https://s3.us-east-1.amazonaws.com/<bucket>/<prefix>`, where the static files are located
+/// directly below the optional prefix. Requests are signed with AWS Signature Version 4 if
+/// credentials are configured, and anonymous otherwise.
+///
+/// Requests are sent from a runtime on a dedicated thread that is shared by all clones of the
+/// backend, so the threads reading static files only wait for the responses.
+#[derive(Debug, Clone)]
+pub struct S3Backend {
+    /// Scheme, host and port of the endpoint.
+    origin: String,
+    /// The bucket name.
+    bucket: String,
+    /// The key prefix of the static files, empty or ending with `/`.
+    prefix: String,
+    /// Region requests are signed for.
+    region: String,
+    /// Credentials requests are signed with, if any.
+    credentials: Option<S3Credentials>,
+    /// The client requests are sent with.
+    client: Client,
+    /// Handle of the runtime requests are sent from.
+    runtime: Handle,
+    /// Stops the runtime when the last clone of the backend is dropped.
+    _runtime_shutdown: Arc<oneshot::Sender<()>>,
+}
+
+impl S3Backend {
+    /// Creates a new backend for the bucket at the given path-style URL, without credentials.
+    pub fn new(url: &Url, region: impl Into<String>) -> io::Result<Self> {
+        let host =
+            url.host_str().ok_or_else(|| invalid_input(format!("S3 URL without host: {url}")))?;
+        let origin = match url.port() {
+            Some(port) => format!("{}://{host}:{port}", url.scheme()),
+            None => format!("{}://{host}", url.scheme()),
+        };
+
+        let mut segments = url.path().split('/').filter(|segment| !segment.is_empty());
+        let bucket = segments
+            .next()
+            .ok_or_else(|| invalid_input(format!("S3 URL without bucket: {url}")))?
+            .to_string();
+        let prefix = segments.map(|segment| format!("{segment}/")).collect();
+
+        let client = Client::builder()
+            .connect_timeout(CONNECT_TIMEOUT)
+            .read_timeout(READ_TIMEOUT)
+            .build()
+            .map_err(io::Error::other)?;
+        let (runtime, runtime_shutdown) = spawn_runtime()?;
+
+        Ok(Self {
+            origin,
+            bucket,
+            prefix,
+            region: region.into(),
+            credentials: None,
+            client,
+            runtime,
+            _runtime_shutdown: Arc::new(runtime_shutdown),
+        })
+    }
+
+    /// Signs requests with the given credentials.
+    pub fn with_credentials(mut self, credentials: Option<S3Credentials>) -> Self {
+        self.credentials = credentials;
+        self
+    }
+
+    /// Sends a signed `GET` request for the given key of the bucket, or the bucket itself if
+    /// `None`.
+    async fn get(&self, key: Option<&str>, query: &[(&str, &str)]) -> io::Result<Response> {
+        let mut path = format!("/{}", sign::uri_encode(&self.bucket, true));
+        if let Some(key) = key {
+            path.push('/');
+            path.push_str(&sign::uri_encode(key, false));
+        }
+        let query = sign::canonical_query(query);
+        let url = if query.is_empty() {
+            format!("{}{path}", self.origin)
+        } else {
+            format!("{}{path}?{query}", self.origin)
+        };
+
+        let mut request = self.client.get(&url);
+        if let Some(credentials) = &self.credentials {
+            let host = self.origin.split_once("://").map_or(&*self.origin, |(_, host)| host);
+            let headers =
+                credentials.sign_get(host, &path, &query, &self.region, SystemTime::now());
+            for (name, value) in headers {
+                request = request.header(name, value);
+            }
+        }
+
+        let response = request.send().await.map_err(io::Error::other)?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(io::Error::other(format!("S3 request for {url} failed: {status} {body}")))
+        }
+        Ok(response)
+    }
+
+    /// Runs the future on the runtime of the backend and waits for its output.
+    ///
+    /// The static file provider is synchronous and may be called from within an async runtime,
+    /// which can't be blocked on.
+    fn run<T: Send + 'static>(
+        &self,
+        fut: impl Future<Output = io::Result<T>> + Send + 'static,
+    ) -> io::Result<T> {
+        let (tx, rx) = mpsc::channel();
+        self.runtime.spawn(async move {
+            let _ = tx.send(fut.await);
+        });
+        rx.recv().map_err(|_| io::Error::other("S3 runtime stopped"))?
+    }
+}
+
+impl StaticFileBackend for S3Backend {
+    fn list(&self) -> io::Result<Vec<String>> {
+        let this = self.clone();
+        self.run(async move {
+            let mut names = Vec::new();
+            let mut continuation_token = None;
+            loop {
+                let mut query = vec![("list-type", "2"), ("prefix", this.prefix.as_str())];
+                if let Some(token) = &continuation_token {
+                    query.push(("continuation-token", token.as_str()));
+                }
+                let body = this
+                    .get(None, &query)
+                    .await?
+                    .text()
+                    .await
+                    .map_err(io::Error::other)?;
+
+                let page = ListPage::parse(&body);
+                // only the files directly below the prefix
+                names.extend(page.keys.iter().filter_map(|key| {
+                    key.strip_prefix(&this.prefix)
+                        .filter(|name| !name.is_empty() && !name.contains('/'))
+                        .map(str::to_string)
+                }));
+
+                match page.next_continuation_token {
+                    Some(token) if page.is_truncated => continuation_token = Some(token),
+                    _ => break,
+                }
+            }
+
+            debug!(target: "static_file::s3", bucket = %this.bucket, prefix = %this.prefix, files = names.len(), "Listed static files");
+            Ok(names)
+        })
+    }
+
+    fn fetch(&self, name: &str, path: &Path) -> io::Result<()> {
+        let key = format!("{}{name}", self.prefix);
+        let path = path.to_path_buf();
+        let this = self.clone();
+        self.run(async move {
+            let mut response = this.get(Some(&key), &[]).await?;
+            let content_length = response.content_length();
+
+            // blocking writes are fine on the dedicated runtime
+            let mut file = File::create(&path)?;
+            let mut len = 0;
+            while let Some(chunk) = response.chunk().await.map_err(io::Error::other)? {
+                file.write_all(&chunk)?;
+                len += chunk.len() as u64;
+            }
+            if content_length.is_some_and(|content_length| content_length != len) {
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    format!("S3 object {key} is truncated: {len} of {content_length:?} bytes"),
+                ))
+            }
+            file.sync_all()?;
+
+            debug!(target: "static_file::s3", bucket = %this.bucket, %key, len, "Fetched static file");
+            Ok(())
+        })
+    }
+}
+
+/// A page of a `ListObjectsV2` response.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct ListPage {
+    /// The keys of the listed objects.
+    keys: Vec<String>,
+    /// Whether there are more objects to list.
+    is_truncated: bool,
+    /// The token to list the next page with.
+    next_continuation_token: Option<String>,
+}
+
+impl ListPage {
+    /// Parses the XML body of a `ListObjectsV2` response.
+    fn parse(body: &str) -> Self {
+        Self {
+            keys: xml_elements(body, "Key").map(xml_unescape).collect(),
+            is_truncated: xml_elements(body, "IsTruncated").next() == Some("true"),
+            next_continuation_token: xml_elements(body, "NextContinuationToken")
+                .next()
+                .map(xml_unescape),
+        }
+    }
+}
+
+/// Returns the text of all elements with the given tag.
+fn xml_elements<'a>(body: &'a str, tag: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+    let open = format!("<{tag}>");
+    let close = format!("</{tag}>");
+    let mut rest = body;
+    std::iter::from_fn(move || {
+        let start = rest.find(&open)? + open.len();
+        let end = start + rest[start..].find(&close)?;
+        let text = &rest[start..end];
+        rest = &rest[end + close.len()..];
+        Some(text)
+    })
+}
+
+/// Replaces the predefined XML entities.
+fn xml_unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Spawns a thread that runs a runtime until the returned sender is dropped.
+///
+/// The runtime is dropped on its own thread, so the backend can be dropped from within another
+/// runtime.
+fn spawn_runtime() -> io::Result<(Handle, oneshot::Sender<()>)> {
+    let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build()?;
+    let handle = runtime.handle().clone();
+    let (shutdown_tx, shutdown_rx) = oneshot::channel();
+    std::thread::Builder::new().name("static-file-s3".to_string()).spawn(move || {
+        let _ = runtime.block_on(shutdown_rx);
+    })?;
+    Ok((handle, shutdown_tx))
+}
+
+fn invalid_input(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_url() {
+        let url =
+            Url::parse("https://s3.eu-west-1.amazonaws.com/archive/mainnet/static_files/").unwrap();
+        let backend = S3Backend::new(&url, "eu-west-1").unwrap();
+        assert_eq!(backend.origin, "https://s3.eu-west-1.amazonaws.com");
+        assert_eq!(backend.bucket, "archive");
+        assert_eq!(backend.prefix, "mainnet/static_files/");
+
+        let url = Url::parse("http://localhost:9000/archive").unwrap();
+        let backend = S3Backend::new(&url, DEFAULT_S3_REGION).unwrap();
+        assert_eq!(backend.origin, "http://localhost:9000");
+        assert_eq!(backend.prefix, "");
+
+        assert!(S3Backend::new(&Url::parse("http://localhost:9000").unwrap(), "").is_err());
+    }
+
+    #[test]
+    fn parse_list_page() {
+        let body = r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+    <Name>archive</Name>
+    <Prefix>mainnet/</Prefix>
+    <IsTruncated>true</IsTruncated>
+    <Contents><Key>mainnet/static_file_headers_0_499999</Key><Size>1</Size></Contents>
+    <Contents><Key>mainnet/static_file_headers_0_499999.conf</Key><Size>1</Size></Contents>
+    <NextContinuationToken>1ueGcxLPRx1Tr/XYExHnhbYLgveDs2J/wm36Hy4vbOwM=</NextContinuationToken>
+</ListBucketResult>"#;
+
+        assert_eq!(
+            ListPage::parse(body),
+            ListPage {
+                keys: vec![
+                    "mainnet/static_file_headers_0_499999".to_string(),
+                    "mainnet/static_file_headers_0_499999.conf".to_string(),
+                ],
+                is_truncated: true,
+                next_continuation_token: Some(
+                    "1ueGcxLPRx1Tr/XYExHnhbYLgveDs2J/wm36Hy4vbOwM=".to_string()
+                ),
+            }
+        );
+        assert_eq!(xml_unescape("a&amp;lt;b"), "a&lt;b");
+    }
+}
@@ -0,0 +1,199 @@
+//! AWS Signature Version 4 for `GET` requests.
+
+use alloy_primitives::hex;
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The payload hash of requests whose payload is not signed.
+const UNSIGNED_PAYLOAD: &str = "UNSIGNED-PAYLOAD";
+
+/// Credentials that requests to the bucket are signed with.
+#[derive(Clone)]
+pub struct S3Credentials {
+    /// The access key id.
+    pub access_key_id: String,
+    /// The secret access key.
+    pub secret_access_key: String,
+    /// The session token of temporary credentials.
+    pub session_token: Option<String>,
+}
+
+impl S3Credentials {
+    /// Reads the credentials from the `AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY` and
+    /// `AWS_SESSION_TOKEN` environment variables. Returns `None` if the access key isn't set.
+    pub fn from_env() -> Option<Self> {
+        Some(Self {
+            access_key_id: std::env::var("AWS_ACCESS_KEY_ID").ok()?,
+            secret_access_key: std::env::var("AWS_SECRET_ACCESS_KEY").ok()?,
+            session_token: std::env::var("AWS_SESSION_TOKEN").ok(),
+        })
+    }
+
+    /// Returns the headers that sign a `GET` request of the URI encoded path and canonical query
+    /// string, see [`canonical_query`].
+    pub(crate) fn sign_get(
+        &self,
+        host: &str,
+        path: &str,
+        query: &str,
+        region: &str,
+        now: SystemTime,
+    ) -> Vec<(&'static str, String)> {
+        let (date, timestamp) = format_timestamp(now);
+
+        let mut headers = vec![
+            ("host", host.to_string()),
+            ("x-amz-content-sha256", UNSIGNED_PAYLOAD.to_string()),
+            ("x-amz-date", timestamp.clone()),
+        ];
+        if let Some(token) = &self.session_token {
+            headers.push(("x-amz-security-token", token.clone()));
+        }
+
+        let signed_headers = headers.iter().map(|(name, _)| *name).collect::<Vec<_>>().join(";");
+        let canonical_headers: String =
+            headers.iter().map(|(name, value)| format!("{name}:{}\n", value.trim())).collect();
+        let canonical_request = format!(
+            "GET\n{path}\n{query}\n{canonical_headers}\n{signed_headers}\n{UNSIGNED_PAYLOAD}"
+        );
+
+        let scope = format!("{date}/{region}/s3/aws4_request");
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{timestamp}\n{scope}\n{}",
+            hex::encode(Sha256::digest(canonical_request.as_bytes()))
+        );
+        let signature = hex::encode(hmac_sha256(
+            &signing_key(&self.secret_access_key, &date, region, "s3"),
+            string_to_sign.as_bytes(),
+        ));
+
+        // the host header is set by the client
+        headers.remove(0);
+        headers.push((
+            "authorization",
+            format!(
+                "AWS4-HMAC-SHA256 Credential={}/{scope}, SignedHeaders={signed_headers}, Signature={signature}",
+                self.access_key_id
+            ),
+        ));
+        headers
+    }
+}
+
+impl std::fmt::Debug for S3Credentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("S3Credentials")
+            .field("access_key_id", &self.access_key_id)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Percent-encodes everything but unreserved characters, and `/` unless `encode_slash` is set.
+pub(crate) fn uri_encode(value: &str, encode_slash: bool) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            b'/' if !encode_slash => encoded.push('/'),
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+/// Returns the encoded query string with the parameters sorted by name.
+pub(crate) fn canonical_query(params: &[(&str, &str)]) -> String {
+    let mut params: Vec<_> = params
+        .iter()
+        .map(|(name, value)| (uri_encode(name, true), uri_encode(value, true)))
+        .collect();
+    params.sort();
+    params.iter().map(|(name, value)| format!("{name}={value}")).collect::<Vec<_>>().join("&")
+}
+
+/// Derives the key that requests of the day are signed with.
+fn signing_key(secret_access_key: &str, date: &str, region: &str, service: &str) -> [u8; 32] {
+    let key = hmac_sha256(format!("AWS4{secret_access_key}").as_bytes(), date.as_bytes());
+    let key = hmac_sha256(&key, region.as_bytes());
+    let key = hmac_sha256(&key, service.as_bytes());
+    hmac_sha256(&key, b"aws4_request")
+}
+
+/// Returns the HMAC-SHA256 of the message.
+fn hmac_sha256(key: &[u8], message: &[u8]) -> [u8; 32] {
+    let mut mac =
+        <Hmac<Sha256> as Mac>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(message);
+    mac.finalize().into_bytes().into()
+}
+
+/// Returns the UTC date `YYYYMMDD` and timestamp `YYYYMMDDTHHMMSSZ` of the time.
+fn format_timestamp(time: SystemTime) -> (String, String) {
+    let secs = time.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+    let (days, secs_of_day) = (secs / 86400, secs % 86400);
+
+    // civil date from days since the epoch, see
+    // <https://howardhinnant.github.io/date_algorithms.html>
+    let z = days + 719468;
+    let era = z / 146097;
+    let doe = z % 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+
+    let date = format!("{year:04}{month:02}{day:02}");
+    let timestamp = format!(
+        "{date}T{:02}{:02}{:02}Z",
+        secs_of_day / 3600,
+        secs_of_day % 3600 / 60,
+        secs_of_day % 60
+    );
+    (date, timestamp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn timestamps() {
+        let time = |secs| UNIX_EPOCH + Duration::from_secs(secs);
+        assert_eq!(format_timestamp(time(0)), ("19700101".into(), "19700101T000000Z".into()));
+        assert_eq!(
+            format_timestamp(time(951_782_400)),
+            ("20000229".into(), "20000229T000000Z".into())
+        );
+        assert_eq!(
+            format_timestamp(time(1_700_000_000)),
+            ("20231114".into(), "20231114T221320Z".into())
+        );
+    }
+
+    #[test]
+    fn derives_signing_key() {
+        // example of the AWS documentation
+        let key =
+            signing_key("wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY", "20120215", "us-east-1", "iam");
+        assert_eq!(
+            hex::encode(key),
+            "f4780e2d9f65fa895f9c67b32ce1baf0b0d8a43505a000a1a9e090d414db404d"
+        );
+    }
+
+    #[test]
+    fn encodes_query() {
+        assert_eq!(uri_encode("a b/c~", false), "a%20b/c~");
+        assert_eq!(uri_encode("a b/c~", true), "a%20b%2Fc~");
+        assert_eq!(
+            canonical_query(&[("prefix", "mainnet/"), ("list-type", "2")]),
+            "list-type=2&prefix=mainnet%2F"
+        );
+    }
+}
@@ -0,0 +1,93 @@
+//! Derive macros for the database abstraction used in reth.
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![allow(unreachable_pub, missing_docs)]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned};
+use syn::{parse_macro_input, spanned::Spanned, Data, DeriveInput, Error, Member, Result};
+
+/// Derives `FixedWidth`, `Encode` and `Decode` for a struct of fixed-width fields, see
+/// `reth_db_api::FixedWidthKey`.
+#[proc_macro_derive(FixedWidthKey)]
+pub fn derive_fixed_width_key(input: TokenStream) -> TokenStream {
+    fixed_width_key(parse_macro_input!(input as DeriveInput))
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn fixed_width_key(input: DeriveInput) -> Result<TokenStream2> {
+    let name = &input.ident;
+    if !input.generics.params.is_empty() {
+        return Err(Error::new(
+            input.generics.span(),
+            "`FixedWidthKey` cannot be derived for generic structs",
+        ))
+    }
+    let fields = match &input.data {
+        Data::Struct(data) if !data.fields.is_empty() => &data.fields,
+        Data::Struct(_) => {
+            return Err(Error::new(name.span(), "`FixedWidthKey` requires at least one field"))
+        }
+        _ => return Err(Error::new(name.span(), "`FixedWidthKey` can only be derived for structs")),
+    };
+
+    let fixed_width = quote!(reth_db_api::table::FixedWidth);
+    let mut width = quote!(0);
+    let mut encode_fields = Vec::with_capacity(fields.len());
+    let mut decode_fields = Vec::with_capacity(fields.len());
+    for (index, field) in fields.iter().enumerate() {
+        let member = field.ident.clone().map_or_else(|| Member::from(index), Member::Named);
+        // spanned by the field type, so that unsupported fields are reported at their type
+        let ty = &field.ty;
+        let field_width = quote_spanned!(ty.span()=> <#ty as #fixed_width>::WIDTH);
+
+        encode_fields.push(quote_spanned! {ty.span()=>
+            <#ty as #fixed_width>::encode_to(&self.#member, buf);
+        });
+        decode_fields.push(quote_spanned! {ty.span()=>
+            #member: <#ty as #fixed_width>::decode_from(&buf[#width..#width + #field_width])?,
+        });
+        width = quote!(#width + #field_width);
+    }
+
+    Ok(quote! {
+        impl #fixed_width for #name {
+            const WIDTH: usize = #width;
+
+            fn encode_to(&self, buf: &mut Vec<u8>) {
+                #(#encode_fields)*
+            }
+
+            fn decode_from(buf: &[u8]) -> Result<Self, reth_db_api::DatabaseError> {
+                if buf.len() != <Self as #fixed_width>::WIDTH {
+                    return Err(reth_db_api::DatabaseError::Decode)
+                }
+                Ok(Self { #(#decode_fields)* })
+            }
+        }
+
+        impl reth_db_api::table::Encode for #name {
+            type Encoded = Vec<u8>;
+
+            fn encode(self) -> Self::Encoded {
+                let mut buf = Vec::with_capacity(<Self as #fixed_width>::WIDTH);
+                <Self as #fixed_width>::encode_to(&self, &mut buf);
+                buf
+            }
+        }
+
+        impl reth_db_api::table::Decode for #name {
+            fn decode(value: &[u8]) -> Result<Self, reth_db_api::DatabaseError> {
+                <Self as #fixed_width>::decode_from(value)
+            }
+        }
+    })
+}
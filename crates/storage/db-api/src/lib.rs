@@ -85,6 +85,36 @@ pub mod transaction;
 /// Re-exports
 pub use reth_storage_errors::db::{DatabaseError, DatabaseWriteOperation};
 
+/// Derives [`FixedWidth`](table::FixedWidth), [`Encode`](table::Encode) and
+/// [`Decode`](table::Decode) for a struct of [`FixedWidth`](table::FixedWidth) fields.
+///
+/// The fields are encoded in declaration order, so the encoding sorts like the struct if it
+/// derives [`Ord`]. This makes the struct usable as a table key or
+/// [`DupSort`](table::DupSort) subkey. Fields without a fixed-width encoding that sorts like
+/// the type, like `Option` or `Vec`, are rejected at compile time. The generated code refers
+/// to this crate as `reth_db_api`.
+///
+/// ```
+/// use alloy_primitives::{Address, B256};
+/// use reth_db_api::{
+///     table::{Decode, Encode},
+///     FixedWidthKey,
+/// };
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, FixedWidthKey)]
+/// struct LogSubKey {
+///     topic: B256,
+///     emitter: Address,
+///     block_number: u64,
+/// }
+///
+/// let key = LogSubKey { topic: B256::ZERO, emitter: Address::ZERO, block_number: 1 };
+/// let encoded = key.encode();
+/// assert_eq!(encoded.len(), 32 + 20 + 8);
+/// assert_eq!(LogSubKey::decode(&encoded).unwrap(), key);
+/// ```
+pub use reth_db_api_derive::FixedWidthKey;
+
 pub mod models;
 mod scale;
 
@@ -94,3 +124,10 @@ pub use database::Database;
 
 mod unwind;
 pub use unwind::DbTxUnwindExt;
+
+// Used by exported macros. Not public API.
+#[doc(hidden)]
+pub mod __private {
+    pub use bytes;
+    pub use reth_codecs::Compact;
+}
@@ -1,12 +1,12 @@
 //! Implements data structures specific to the database
 
 use crate::{
-    table::{Compress, Decode, Decompress, Encode},
+    table::{Compress, Decode, Decompress, Encode, FixedWidth},
     DatabaseError,
 };
 use alloy_consensus::Header;
 use alloy_genesis::GenesisAccount;
-use alloy_primitives::{Address, Bytes, Log, B256, U256};
+use alloy_primitives::{Address, Bytes, FixedBytes, Log, B256, U256};
 use reth_codecs::{add_arbitrary_tests, Compact};
 use reth_ethereum_primitives::{Receipt, TransactionSigned, TxType};
 use reth_primitives_traits::{Account, Bytecode, StorageEntry};
@@ -51,6 +51,18 @@ macro_rules! impl_uints {
                     )
                 }
             }
+
+            impl FixedWidth for $name {
+                const WIDTH: usize = std::mem::size_of::<$name>();
+
+                fn encode_to(&self, buf: &mut Vec<u8>) {
+                    buf.extend_from_slice(&self.to_be_bytes())
+                }
+
+                fn decode_from(buf: &[u8]) -> Result<Self, $crate::DatabaseError> {
+                    Self::decode(buf)
+                }
+            }
         )+
     };
 }
@@ -103,6 +115,30 @@ impl Decode for B256 {
     }
 }
 
+impl FixedWidth for Address {
+    const WIDTH: usize = 20;
+
+    fn encode_to(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(self.as_slice())
+    }
+
+    fn decode_from(buf: &[u8]) -> Result<Self, DatabaseError> {
+        Ok(Self::new(buf.try_into().map_err(|_| DatabaseError::Decode)?))
+    }
+}
+
+impl<const N: usize> FixedWidth for FixedBytes<N> {
+    const WIDTH: usize = N;
+
+    fn encode_to(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(self.as_slice())
+    }
+
+    fn decode_from(buf: &[u8]) -> Result<Self, DatabaseError> {
+        Ok(Self::new(buf.try_into().map_err(|_| DatabaseError::Decode)?))
+    }
+}
+
 impl Encode for String {
     type Encoded = Vec<u8>;
 
@@ -188,21 +224,23 @@ impl Decode for ClientVersion {
     }
 }
 
-/// Implements compression for Compact type.
+/// Implements [`Compress`] and [`Decompress`] for types implementing [`Compact`], so they can be
+/// used as table values.
+#[macro_export]
 macro_rules! impl_compression_for_compact {
     ($($name:ident$(<$($generic:ident),*>)?),+) => {
         $(
-            impl$(<$($generic: core::fmt::Debug + Send + Sync + Compact),*>)? Compress for $name$(<$($generic),*>)? {
+            impl$(<$($generic: ::core::fmt::Debug + Send + Sync + $crate::__private::Compact),*>)? $crate::table::Compress for $name$(<$($generic),*>)? {
                 type Compressed = Vec<u8>;
 
-                fn compress_to_buf<B: bytes::BufMut + AsMut<[u8]>>(&self, buf: &mut B) {
-                    let _ = Compact::to_compact(self, buf);
+                fn compress_to_buf<B: $crate::__private::bytes::BufMut + AsMut<[u8]>>(&self, buf: &mut B) {
+                    let _ = $crate::__private::Compact::to_compact(self, buf);
                 }
             }
 
-            impl$(<$($generic: core::fmt::Debug + Send + Sync + Compact),*>)? Decompress for $name$(<$($generic),*>)? {
+            impl$(<$($generic: ::core::fmt::Debug + Send + Sync + $crate::__private::Compact),*>)? $crate::table::Decompress for $name$(<$($generic),*>)? {
                 fn decompress(value: &[u8]) -> Result<$name$(<$($generic),*>)?, $crate::DatabaseError> {
-                    let (obj, _) = Compact::from_compact(value, value.len());
+                    let (obj, _) = $crate::__private::Compact::from_compact(value, value.len());
                     Ok(obj)
                 }
             }
@@ -66,6 +66,27 @@ pub trait Decode: Send + Sync + Sized + Debug {
     }
 }
 
+/// A type with a fixed-width encoding that sorts in the same order as the type, e.g. big-endian
+/// integers and fixed-size byte arrays.
+///
+/// Structs of such fields can derive [`Encode`] and [`Decode`] with
+/// [`FixedWidthKey`](crate::FixedWidthKey).
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` does not have a fixed-width encoding",
+    label = "not a fixed-width key field",
+    note = "key fields must have a fixed-width encoding that sorts like the type, unlike e.g. the `Compact` encoding of integers or `Option`"
+)]
+pub trait FixedWidth: Sized {
+    /// The length of the encoding in bytes.
+    const WIDTH: usize;
+
+    /// Appends the encoding of the value to the buffer.
+    fn encode_to(&self, buf: &mut Vec<u8>);
+
+    /// Decodes the value from exactly [`Self::WIDTH`] bytes.
+    fn decode_from(buf: &[u8]) -> Result<Self, DatabaseError>;
+}
+
 /// Generic trait that enforces the database key to implement [`Encode`] and [`Decode`].
 pub trait Key: Encode + Decode + Ord + Clone + Serialize + for<'a> Deserialize<'a> {}
 
@@ -114,6 +135,20 @@ pub type TableRow<T> = (<T as Table>::Key, <T as Table>::Value);
 
 /// `DupSort` allows for keys to be repeated in the database.
 ///
+/// The values of a key are sorted by their compressed bytes, and looked up by the encoded
+/// [`DupSort::SubKey`]. For this to work, the subkey and value have to uphold the following
+/// invariants, which can't be checked by the type system:
+///
+/// - The [`Compress`]ed value starts with the [`Encode`]d subkey of the value, e.g.
+///   [`StorageEntry`](reth_primitives_traits::StorageEntry) is compressed as its slot followed by
+///   its value.
+/// - The encoded subkeys sort in the same order as the subkeys, i.e. the encoding is big-endian and
+///   either has a fixed length or is prefix-free.
+///
+/// Structs of [`FixedWidth`] fields, e.g. `B256`, `Address` and integers, can derive [`Encode`] and
+/// [`Decode`] with [`FixedWidthKey`](crate::FixedWidthKey), which upholds the latter invariant.
+/// Tables are declared with the [`tables`](crate::tables) macro.
+///
 /// Upstream docs: <https://libmdbx.dqdkfa.ru/usage.html#autotoc_md48>
 pub trait DupSort: Table {
     /// The table subkey. This type must implement [`Encode`] and [`Decode`].
@@ -42,7 +42,6 @@ use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_stages_types::StageCheckpoint;
 use reth_trie_common::{BranchNodeCompact, StorageTrieEntry, StoredNibbles, StoredNibblesSubKey};
 use serde::{Deserialize, Serialize};
-use std::fmt;
 
 /// Enum for the types of tables present in libmdbx.
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
@@ -112,7 +111,33 @@ pub trait TableSet {
     fn tables() -> Box<dyn Iterator<Item = Box<dyn TableInfo>>>;
 }
 
-/// Defines all the tables in the database.
+/// Defines a set of tables.
+///
+/// This generates a marker type implementing [`Table`] for each table, and [`DupSort`] for tables
+/// with a `SubKey`, as well as a `Tables` enum of all the tables that implements [`TableSet`], so
+/// the tables can be created with `init_db_for`. The macro can be used by external crates, e.g. to
+/// declare the tables of an `ExEx` in a separate database:
+///
+/// ```
+/// use alloy_primitives::{Address, B256};
+/// use reth_db_api::{table::DupSort, tables};
+/// use reth_primitives_traits::StorageEntry;
+///
+/// tables! {
+///     /// Storage slots of an address, sorted by slot.
+///     table AddressSlots {
+///         type Key = Address;
+///         type Value = StorageEntry;
+///         type SubKey = B256;
+///     }
+/// }
+///
+/// fn assert_dupsort<T: DupSort<SubKey = B256>>() {}
+/// assert_dupsort::<AddressSlots>();
+/// assert!(Tables::AddressSlots.is_dupsort());
+/// ```
+///
+/// See [`DupSort`] for the requirements on the subkey and value of `DUPSORT` tables.
 #[macro_export]
 macro_rules! tables {
     (@bool) => { false };
@@ -129,23 +154,64 @@ macro_rules! tables {
         concat!("`", stringify!($value), "`")
     };
 
+    // The tables of reth, which additionally define the `tables_to_generic` macro.
+    (@reth $($(#[$attr:meta])* table $name:ident$(<$($generic:ident $(= $default:ty)?),*>)? { type Key = $key:ty; type Value = $value:ty; $(type SubKey = $subkey:ty;)? } )*) => {
+        $crate::tables! {
+            $(
+                $(#[$attr])*
+                table $name$(<$($generic $(= $default)?),*>)? {
+                    type Key = $key;
+                    type Value = $value;
+                    $(type SubKey = $subkey;)?
+                }
+            )*
+        }
+
+        /// Maps a run-time [`Tables`] enum value to its corresponding compile-time [`Table`] type.
+        ///
+        /// This is a simpler alternative to [`TableViewer`].
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use reth_db_api::{table::Table, Tables, tables_to_generic};
+        ///
+        /// let table = Tables::Headers;
+        /// let result = tables_to_generic!(table, |GenericTable| <GenericTable as Table>::NAME);
+        /// assert_eq!(result, table.name());
+        /// ```
+        #[macro_export]
+        macro_rules! tables_to_generic {
+            ($table:expr, |$generic_name:ident| $e:expr) => {
+                match $table {
+                    $(
+                        Tables::$name => {
+                            use $crate::tables::$name as $generic_name;
+                            $e
+                        },
+                    )*
+                }
+            };
+        }
+    };
+
     ($($(#[$attr:meta])* table $name:ident$(<$($generic:ident $(= $default:ty)?),*>)? { type Key = $key:ty; type Value = $value:ty; $(type SubKey = $subkey:ty;)? } )*) => {
         // Table marker types.
         $(
             $(#[$attr])*
             ///
-            #[doc = concat!("Marker type representing a database table mapping [`", stringify!($key), "`] to ", tables!(@value_doc $key, $value, $($($generic),*)?), ".")]
+            #[doc = concat!("Marker type representing a database table mapping [`", stringify!($key), "`] to ", $crate::tables!(@value_doc $key, $value, $($($generic),*)?), ".")]
             $(
                 #[doc = concat!("\n\nThis table's `DUPSORT` subkey is [`", stringify!($subkey), "`].")]
             )?
             pub struct $name$(<$($generic $( = $default)?),*>)? {
-                _private: std::marker::PhantomData<($($($generic,)*)?)>,
+                _private: ::core::marker::PhantomData<($($($generic,)*)?)>,
             }
 
             // Ideally this implementation wouldn't exist, but it is necessary to derive `Debug`
             // when a type is generic over `T: Table`. See: https://github.com/rust-lang/rust/issues/26925
-            impl$(<$($generic),*>)? fmt::Debug for $name$(<$($generic),*>)? {
-                fn fmt(&self, _: &mut fmt::Formatter<'_>) -> fmt::Result {
+            impl$(<$($generic),*>)? ::core::fmt::Debug for $name$(<$($generic),*>)? {
+                fn fmt(&self, _: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
                     unreachable!("this type cannot be instantiated")
                 }
             }
@@ -156,14 +222,18 @@ macro_rules! tables {
                 $($(,$generic: Send + Sync)*)?
             {
                 const NAME: &'static str = table_names::$name;
-                const DUPSORT: bool = tables!(@bool $($subkey)?);
+                const DUPSORT: bool = $crate::tables!(@bool $($subkey)?);
 
                 type Key = $key;
                 type Value = $value;
             }
 
             $(
-                impl DupSort for $name {
+                impl$(<$($generic),*>)? $crate::table::DupSort for $name$(<$($generic),*>)?
+                where
+                    $value: $crate::table::Value + 'static
+                    $($(,$generic: Send + Sync)*)?
+                {
                     type SubKey = $subkey;
                 }
             )?
@@ -200,48 +270,48 @@ macro_rules! tables {
             pub const fn is_dupsort(&self) -> bool {
                 match self {
                     $(
-                        Self::$name => tables!(@bool $($subkey)?),
+                        Self::$name => $crate::tables!(@bool $($subkey)?),
                     )*
                 }
             }
 
             /// The type of the given table in database.
-            pub const fn table_type(&self) -> TableType {
+            pub const fn table_type(&self) -> $crate::TableType {
                 if self.is_dupsort() {
-                    TableType::DupSort
+                    $crate::TableType::DupSort
                 } else {
-                    TableType::Table
+                    $crate::TableType::Table
                 }
             }
 
             /// Allows to operate on specific table type
             pub fn view<T, R>(&self, visitor: &T) -> Result<R, T::Error>
             where
-                T: ?Sized + TableViewer<R>,
+                T: ?Sized + $crate::TableViewer<R>,
             {
                 match self {
                     $(
-                        Self::$name => tables!(@view $name visitor $($subkey)?),
+                        Self::$name => $crate::tables!(@view $name visitor $($subkey)?),
                     )*
                 }
             }
         }
 
-        impl fmt::Debug for Tables {
+        impl ::core::fmt::Debug for Tables {
             #[inline]
-            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
                 f.write_str(self.name())
             }
         }
 
-        impl fmt::Display for Tables {
+        impl ::core::fmt::Display for Tables {
             #[inline]
-            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-                self.name().fmt(f)
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                ::core::fmt::Display::fmt(self.name(), f)
             }
         }
 
-        impl std::str::FromStr for Tables {
+        impl ::core::str::FromStr for Tables {
             type Err = String;
 
             fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -254,7 +324,7 @@ macro_rules! tables {
             }
         }
 
-        impl TableInfo for Tables {
+        impl $crate::table::TableInfo for Tables {
             fn name(&self) -> &'static str {
                 self.name()
             }
@@ -264,9 +334,9 @@ macro_rules! tables {
             }
         }
 
-        impl TableSet for Tables {
-            fn tables() -> Box<dyn Iterator<Item = Box<dyn TableInfo>>> {
-                Box::new(Self::ALL.iter().map(|table| Box::new(*table) as Box<dyn TableInfo>))
+        impl $crate::TableSet for Tables {
+            fn tables() -> Box<dyn Iterator<Item = Box<dyn $crate::table::TableInfo>>> {
+                Box::new(Self::ALL.iter().map(|table| Box::new(*table) as Box<dyn $crate::table::TableInfo>))
             }
         }
 
@@ -277,37 +347,12 @@ macro_rules! tables {
                 pub(super) const $name: &'static str = stringify!($name);
             )*
         }
-
-        /// Maps a run-time [`Tables`] enum value to its corresponding compile-time [`Table`] type.
-        ///
-        /// This is a simpler alternative to [`TableViewer`].
-        ///
-        /// # Examples
-        ///
-        /// ```
-        /// use reth_db_api::{table::Table, Tables, tables_to_generic};
-        ///
-        /// let table = Tables::Headers;
-        /// let result = tables_to_generic!(table, |GenericTable| <GenericTable as Table>::NAME);
-        /// assert_eq!(result, table.name());
-        /// ```
-        #[macro_export]
-        macro_rules! tables_to_generic {
-            ($table:expr, |$generic_name:ident| $e:expr) => {
-                match $table {
-                    $(
-                        Tables::$name => {
-                            use $crate::tables::$name as $generic_name;
-                            $e
-                        },
-                    )*
-                }
-            };
-        }
     };
 }
 
 tables! {
+    @reth
+
     /// Stores the header hashes belonging to the canonical chain.
     // LESSON 7: Canonical Chain Tracking
     // This table maps block number → hash for the canonical (main) chain.
@@ -491,6 +536,33 @@ tables! {
         type Value = BlockNumberList;
     }
 
+    /// Stores pointers to the blocks that contain a log emitted by each address.
+    ///
+    /// Sharded like [`AccountsHistory`], the last shard of an address has the `u64::MAX` key.
+    /// Only populated if the optional log index stage is enabled.
+    table LogAddressIndex {
+        type Key = ShardedKey<Address>;
+        type Value = BlockNumberList;
+    }
+
+    /// Stores pointers to the blocks that contain a log with each topic, at any position.
+    ///
+    /// Sharded like [`AccountsHistory`], the last shard of a topic has the `u64::MAX` key.
+    /// Only populated if the optional log index stage is enabled.
+    table LogTopicIndex {
+        type Key = ShardedKey<B256>;
+        type Value = BlockNumberList;
+    }
+
+    /// Stores the gas used by transactions sent to each address, aggregated per epoch.
+    ///
+    /// The key is the first block of the epoch and the `to` address of the transactions.
+    /// Only populated if the optional gas usage index is enabled.
+    table ContractGasUsage {
+        type Key = BlockNumberAddress;
+        type Value = u64;
+    }
+
     /// Stores the state of an account before a certain transaction changed it.
     /// Change on state can be: account is created, selfdestructed, touched while empty
     /// or changed balance,nonce.
@@ -573,7 +645,8 @@ tables! {
         type Value = ClientVersion;
     }
 
-    /// Stores generic chain state info, like the last finalized block.
+    /// Stores generic chain state info, like the last finalized block and the size statistics of
+    /// the plain state.
     table ChainState {
         type Key = ChainStateKey;
         type Value = BlockNumber;
@@ -587,6 +660,20 @@ pub enum ChainStateKey {
     LastFinalizedBlock,
     /// Last finalized block key
     LastSafeBlockBlock,
+    /// Number of accounts in the plain state
+    StateSizeAccounts,
+    /// Number of accounts with code in the plain state
+    StateSizeContracts,
+    /// Number of storage slots in the plain state
+    StateSizeStorageSlots,
+    /// Number of bytecodes
+    StateSizeBytecodes,
+    /// Total size of the bytecodes in bytes
+    StateSizeBytecodeBytes,
+    /// First block covered by the gas usage index
+    GasUsageIndexedFrom,
+    /// Last block covered by the gas usage index
+    GasUsageIndexedTo,
 }
 
 impl Encode for ChainStateKey {
@@ -596,6 +683,13 @@ impl Encode for ChainStateKey {
         match self {
             Self::LastFinalizedBlock => [0],
             Self::LastSafeBlockBlock => [1],
+            Self::StateSizeAccounts => [2],
+            Self::StateSizeContracts => [3],
+            Self::StateSizeStorageSlots => [4],
+            Self::StateSizeBytecodes => [5],
+            Self::StateSizeBytecodeBytes => [6],
+            Self::GasUsageIndexedFrom => [7],
+            Self::GasUsageIndexedTo => [8],
         }
     }
 }
@@ -605,6 +699,13 @@ impl Decode for ChainStateKey {
         match value {
             [0] => Ok(Self::LastFinalizedBlock),
             [1] => Ok(Self::LastSafeBlockBlock),
+            [2] => Ok(Self::StateSizeAccounts),
+            [3] => Ok(Self::StateSizeContracts),
+            [4] => Ok(Self::StateSizeStorageSlots),
+            [5] => Ok(Self::StateSizeBytecodes),
+            [6] => Ok(Self::StateSizeBytecodeBytes),
+            [7] => Ok(Self::GasUsageIndexedFrom),
+            [8] => Ok(Self::GasUsageIndexedTo),
             _ => Err(crate::DatabaseError::Decode),
         }
     }
@@ -0,0 +1,69 @@
+//! Tests for the `FixedWidthKey` derive.
+
+use alloy_primitives::{Address, B256};
+use reth_db_api::{
+    table::{Decode, Encode, FixedWidth},
+    DatabaseError, FixedWidthKey,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, FixedWidthKey)]
+struct LogSubKey {
+    topic: B256,
+    emitter: Address,
+    block_number: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, FixedWidthKey)]
+struct NestedKey(u8, LogSubKey);
+
+#[test]
+fn encoding_sorts_like_key() {
+    let key = |topic, emitter, block_number| LogSubKey {
+        topic: B256::with_last_byte(topic),
+        emitter: Address::with_last_byte(emitter),
+        block_number,
+    };
+    let mut keys = vec![
+        key(1, 1, 256),
+        key(1, 1, 1),
+        key(1, 2, 0),
+        key(2, 0, 0),
+        key(1, 1, u64::MAX),
+        key(0, 3, 2),
+    ];
+    let mut encoded = keys.iter().map(|key| key.encode()).collect::<Vec<_>>();
+    keys.sort();
+    encoded.sort();
+
+    for (key, encoded) in keys.into_iter().zip(encoded) {
+        assert_eq!(encoded.len(), LogSubKey::WIDTH);
+        assert_eq!(LogSubKey::decode(&encoded).unwrap(), key);
+    }
+}
+
+#[test]
+fn nested_key_roundtrip() {
+    assert_eq!(NestedKey::WIDTH, 1 + 32 + 20 + 8);
+
+    let key = NestedKey(
+        7,
+        LogSubKey {
+            topic: B256::repeat_byte(1),
+            emitter: Address::repeat_byte(2),
+            block_number: 3,
+        },
+    );
+    let encoded = key.encode();
+    assert_eq!(encoded[0], 7);
+    assert_eq!(NestedKey::decode(&encoded).unwrap(), key);
+
+    // encodings of another width are rejected
+    assert_eq!(NestedKey::decode(&encoded[1..]), Err(DatabaseError::Decode));
+    assert_eq!(NestedKey::decode(&[encoded.as_slice(), &[0]].concat()), Err(DatabaseError::Decode));
+}
+
+#[test]
+fn rejects_unsupported_types() {
+    let tests = trybuild::TestCases::new();
+    tests.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,9 @@
+use reth_db_api::FixedWidthKey;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, FixedWidthKey)]
+enum Key {
+    A(u64),
+    B(u64),
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use reth_db_api::FixedWidthKey;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, FixedWidthKey)]
+struct Key<T> {
+    value: T,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use alloy_primitives::Address;
+use reth_db_api::FixedWidthKey;
+
+// `Option` fields have no fixed width.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, FixedWidthKey)]
+struct Key {
+    address: Address,
+    block_number: Option<u64>,
+}
+
+fn main() {}
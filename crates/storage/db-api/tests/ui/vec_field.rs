@@ -0,0 +1,7 @@
+use reth_db_api::FixedWidthKey;
+
+// Variable-length fields are not prefix-free, so they don't sort like the type.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, FixedWidthKey)]
+struct Key(Vec<u8>, u64);
+
+fn main() {}
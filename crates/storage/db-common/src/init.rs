@@ -49,6 +49,9 @@ pub enum InitStorageError {
         "static files found, but the database is uninitialized. If attempting to re-syncing, delete both."
     )]
     UninitializedDatabase,
+    /// The genesis block hasn't been written, and the storage must not be written to.
+    #[error("genesis block has not been written to the storage")]
+    MissingGenesis,
     /// An existing genesis block was found in the database, and its hash did not match the hash of
     /// the chainspec.
     #[error(
@@ -91,39 +94,23 @@ where
         + AsRef<PF::ProviderRW>,
     PF::ChainSpec: EthChainSpec<Header = <PF::Primitives as NodePrimitives>::BlockHeader>,
 {
+    if let Some(hash) = genesis_written(factory)? {
+        let provider_rw = factory.database_provider_rw()?;
+        let removed = provider_rw.migrate_stage_checkpoints()?;
+        if !removed.is_empty() {
+            info!(target: "reth::storage", ?removed, "Removed checkpoints of retired stages");
+            UnifiedStorageWriter::commit(provider_rw)?;
+        }
+
+        debug!("Genesis already written, skipping.");
+        return Ok(hash)
+    }
+
     let chain = factory.chain_spec();
 
     let genesis = chain.genesis();
     let hash = chain.genesis_hash();
 
-    // Check if we already have the genesis header or if we have the wrong one.
-    match factory.block_hash(0) {
-        Ok(None) | Err(ProviderError::MissingStaticFileBlock(StaticFileSegment::Headers, 0)) => {}
-        Ok(Some(block_hash)) => {
-            if block_hash == hash {
-                // Some users will at times attempt to re-sync from scratch by just deleting the
-                // database. Since `factory.block_hash` will only query the static files, we need to
-                // make sure that our database has been written to, and throw error if it's empty.
-                if factory.get_stage_checkpoint(StageId::Headers)?.is_none() {
-                    error!(target: "reth::storage", "Genesis header found on static files, but database is uninitialized.");
-                    return Err(InitStorageError::UninitializedDatabase)
-                }
-
-                debug!("Genesis already written, skipping.");
-                return Ok(hash)
-            }
-
-            return Err(InitStorageError::GenesisHashMismatch {
-                chainspec_hash: hash,
-                storage_hash: block_hash,
-            })
-        }
-        Err(e) => {
-            debug!(?e);
-            return Err(e.into());
-        }
-    }
-
     debug!("Writing genesis block.");
 
     let alloc = &genesis.alloc;
@@ -158,6 +145,56 @@ where
     Ok(hash)
 }
 
+/// Checks that the genesis block matches the chain spec without writing to the storage, e.g. when
+/// the node runs in safe mode.
+///
+/// Unlike [`init_genesis`], this neither writes a missing genesis block nor removes the checkpoints
+/// of retired stages.
+pub fn verify_genesis<PF>(factory: &PF) -> Result<B256, InitStorageError>
+where
+    PF: ChainSpecProvider + StageCheckpointReader + BlockHashReader,
+{
+    genesis_written(factory)?.ok_or(InitStorageError::MissingGenesis)
+}
+
+/// Returns the genesis hash if the genesis block was already written to the storage, or `None` if
+/// it still has to be written.
+fn genesis_written<PF>(factory: &PF) -> Result<Option<B256>, InitStorageError>
+where
+    PF: ChainSpecProvider + StageCheckpointReader + BlockHashReader,
+{
+    let hash = factory.chain_spec().genesis_hash();
+
+    // Check if we already have the genesis header or if we have the wrong one.
+    match factory.block_hash(0) {
+        Ok(None) | Err(ProviderError::MissingStaticFileBlock(StaticFileSegment::Headers, 0)) => {
+            Ok(None)
+        }
+        Ok(Some(block_hash)) => {
+            if block_hash != hash {
+                return Err(InitStorageError::GenesisHashMismatch {
+                    chainspec_hash: hash,
+                    storage_hash: block_hash,
+                })
+            }
+
+            // Some users will at times attempt to re-sync from scratch by just deleting the
+            // database. Since `factory.block_hash` will only query the static files, we need to
+            // make sure that our database has been written to, and throw error if it's empty.
+            if factory.get_stage_checkpoint(StageId::Headers)?.is_none() {
+                error!(target: "reth::storage", "Genesis header found on static files, but database is uninitialized.");
+                return Err(InitStorageError::UninitializedDatabase)
+            }
+
+            Ok(Some(hash))
+        }
+        Err(e) => {
+            debug!(?e);
+            Err(e.into())
+        }
+    }
+}
+
 /// Inserts the genesis state into the database.
 pub fn insert_genesis_state<'a, 'b, Provider>(
     provider: &Provider,
@@ -702,6 +739,32 @@ mod tests {
         ))
     }
 
+    #[test]
+    fn verify_genesis_does_not_write() {
+        let factory = create_test_provider_factory_with_chain_spec(SEPOLIA.clone());
+        assert!(matches!(verify_genesis(&factory), Err(InitStorageError::MissingGenesis)));
+        assert_eq!(factory.provider().unwrap().block_hash(0).unwrap(), None);
+
+        init_genesis(&factory).unwrap();
+        let retired = StageId::RETIRED[0].to_string();
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw
+            .tx_ref()
+            .put::<tables::StageCheckpoints>(retired.clone(), Default::default())
+            .unwrap();
+        provider_rw.commit().unwrap();
+
+        // the checkpoint of the retired stage is kept
+        assert_eq!(verify_genesis(&factory).unwrap(), SEPOLIA_GENESIS_HASH);
+        let tx = factory.provider().unwrap().into_tx();
+        assert!(tx.get::<tables::StageCheckpoints>(retired.clone()).unwrap().is_some());
+        drop(tx);
+
+        assert_eq!(init_genesis(&factory).unwrap(), SEPOLIA_GENESIS_HASH);
+        let tx = factory.provider().unwrap().into_tx();
+        assert!(tx.get::<tables::StageCheckpoints>(retired).unwrap().is_none());
+    }
+
     #[test]
     fn init_genesis_history() {
         let address_with_balance = Address::with_last_byte(1);
@@ -71,6 +71,19 @@ impl<K: TransactionKind, T: Table> Cursor<K, T> {
             f(self)
         }
     }
+
+    /// If table latency metrics are enabled and `self.metrics` is `Some(...)`, record the latency
+    /// of the read.
+    ///
+    /// Otherwise, just execute the closure.
+    fn execute_with_read_metric<R>(&mut self, f: impl FnOnce(&mut Self) -> R) -> R {
+        #[cfg(feature = "table-latency-metrics")]
+        if let Some(metrics) = self.metrics.clone() {
+            return metrics.record_cursor_read(T::NAME, || f(self))
+        }
+
+        f(self)
+    }
 }
 
 /// Decodes a `(key, value)` pair from the database.
@@ -109,33 +122,35 @@ macro_rules! compress_to_buf_or_ref {
 impl<K: TransactionKind, T: Table> DbCursorRO<T> for Cursor<K, T> {
     // Jump to the first key in the table
     fn first(&mut self) -> PairResult<T> {
-        decode::<T>(self.inner.first())
+        self.execute_with_read_metric(|this| decode::<T>(this.inner.first()))
     }
 
     // Find exact key match (returns None if not found)
     fn seek_exact(&mut self, key: <T as Table>::Key) -> PairResult<T> {
-        decode::<T>(self.inner.set_key(key.encode().as_ref()))
+        self.execute_with_read_metric(|this| decode::<T>(this.inner.set_key(key.encode().as_ref())))
     }
 
     // Find key or next greater key (for range queries)
     fn seek(&mut self, key: <T as Table>::Key) -> PairResult<T> {
-        decode::<T>(self.inner.set_range(key.encode().as_ref()))
+        self.execute_with_read_metric(|this| {
+            decode::<T>(this.inner.set_range(key.encode().as_ref()))
+        })
     }
 
     fn next(&mut self) -> PairResult<T> {
-        decode::<T>(self.inner.next())
+        self.execute_with_read_metric(|this| decode::<T>(this.inner.next()))
     }
 
     fn prev(&mut self) -> PairResult<T> {
-        decode::<T>(self.inner.prev())
+        self.execute_with_read_metric(|this| decode::<T>(this.inner.prev()))
     }
 
     fn last(&mut self) -> PairResult<T> {
-        decode::<T>(self.inner.last())
+        self.execute_with_read_metric(|this| decode::<T>(this.inner.last()))
     }
 
     fn current(&mut self) -> PairResult<T> {
-        decode::<T>(self.inner.get_current())
+        self.execute_with_read_metric(|this| decode::<T>(this.inner.get_current()))
     }
 
     fn walk(&mut self, start_key: Option<T::Key>) -> Result<Walker<'_, T, Self>, DatabaseError> {
@@ -181,21 +196,23 @@ impl<K: TransactionKind, T: Table> DbCursorRO<T> for Cursor<K, T> {
 impl<K: TransactionKind, T: DupSort> DbDupCursorRO<T> for Cursor<K, T> {
     /// Returns the next `(key, value)` pair of a DUPSORT table.
     fn next_dup(&mut self) -> PairResult<T> {
-        decode::<T>(self.inner.next_dup())
+        self.execute_with_read_metric(|this| decode::<T>(this.inner.next_dup()))
     }
 
     /// Returns the next `(key, value)` pair skipping the duplicates.
     fn next_no_dup(&mut self) -> PairResult<T> {
-        decode::<T>(self.inner.next_nodup())
+        self.execute_with_read_metric(|this| decode::<T>(this.inner.next_nodup()))
     }
 
     /// Returns the next `value` of a duplicate `key`.
     fn next_dup_val(&mut self) -> ValueOnlyResult<T> {
-        self.inner
-            .next_dup()
-            .map_err(|e| DatabaseError::Read(e.into()))?
-            .map(decode_value::<T>)
-            .transpose()
+        self.execute_with_read_metric(|this| {
+            this.inner
+                .next_dup()
+                .map_err(|e| DatabaseError::Read(e.into()))?
+                .map(decode_value::<T>)
+                .transpose()
+        })
     }
 
     fn seek_by_key_subkey(
@@ -203,11 +220,13 @@ impl<K: TransactionKind, T: DupSort> DbDupCursorRO<T> for Cursor<K, T> {
         key: <T as Table>::Key,
         subkey: <T as DupSort>::SubKey,
     ) -> ValueOnlyResult<T> {
-        self.inner
-            .get_both_range(key.encode().as_ref(), subkey.encode().as_ref())
-            .map_err(|e| DatabaseError::Read(e.into()))?
-            .map(decode_one::<T>)
-            .transpose()
+        self.execute_with_read_metric(|this| {
+            this.inner
+                .get_both_range(key.encode().as_ref(), subkey.encode().as_ref())
+                .map_err(|e| DatabaseError::Read(e.into()))?
+                .map(decode_one::<T>)
+                .transpose()
+        })
     }
 
     /// Depending on its arguments, returns an iterator starting at:
@@ -124,6 +124,9 @@ pub struct DatabaseArguments {
     ///
     /// This flag affects only at environment opening but can't be changed after.
     exclusive: Option<bool>,
+    /// Whether the database was written by a newer, read compatible version, see
+    /// [`DatabaseCompat::ReadCompat`](crate::version::DatabaseCompat).
+    read_compat: bool,
 }
 
 impl Default for DatabaseArguments {
@@ -153,6 +156,7 @@ impl DatabaseArguments {
             log_level: None,
             max_read_transaction_duration: None,
             exclusive: None,
+            read_compat: false,
         }
     }
 
@@ -172,6 +176,17 @@ impl DatabaseArguments {
         self
     }
 
+    /// Sets the page size of a newly created db environment in bytes.
+    ///
+    /// The page size of an existing environment can't be changed, see
+    /// [`migrate_env`](crate::migrate::migrate_env) to copy it into a new environment instead.
+    pub const fn with_page_size(mut self, page_size: Option<usize>) -> Self {
+        if let Some(page_size) = page_size {
+            self.geometry.page_size = Some(PageSize::Set(page_size));
+        }
+        self
+    }
+
     /// Set the log level.
     pub const fn with_log_level(mut self, log_level: Option<LogLevel>) -> Self {
         self.log_level = log_level;
@@ -193,6 +208,12 @@ impl DatabaseArguments {
         self
     }
 
+    /// Set whether the database was written by a newer, read compatible version.
+    pub const fn with_read_compat(mut self, read_compat: bool) -> Self {
+        self.read_compat = read_compat;
+        self
+    }
+
     /// Returns the client version if any.
     pub const fn client_version(&self) -> &ClientVersion {
         &self.client_version
@@ -208,6 +229,8 @@ pub struct DatabaseEnv {
     metrics: Option<Arc<DatabaseEnvMetrics>>,
     /// Write lock for when dealing with a read-write environment.
     _lock_file: Option<StorageLock>,
+    /// Whether the database was written by a newer, read compatible version.
+    read_compat: bool,
 }
 
 impl Database for DatabaseEnv {
@@ -244,7 +267,12 @@ impl DatabaseMetrics for DatabaseEnv {
         let _ = self
             .view(|tx| {
                 for table in Tables::ALL.iter().map(Tables::name) {
-                    let table_db = tx.inner.open_db(Some(table)).wrap_err("Could not open db.")?;
+                    let table_db = match tx.inner.open_db(Some(table)) {
+                        Ok(table_db) => table_db,
+                        // Tables may be dropped by the newer version that wrote the database.
+                        Err(reth_libmdbx::Error::NotFound) if self.read_compat => continue,
+                        Err(err) => return Err(err).wrap_err("Could not open db."),
+                    };
 
                     let stats = tx
                         .inner
@@ -461,6 +489,7 @@ impl DatabaseEnv {
             inner: inner_env.open(path).map_err(|e| DatabaseError::Open(e.into()))?,
             metrics: None,
             _lock_file,
+            read_compat: args.read_compat,
         };
 
         Ok(env)
@@ -472,6 +501,11 @@ impl DatabaseEnv {
         self
     }
 
+    /// Returns `true` if the database was written by a newer, read compatible version.
+    pub const fn is_read_compat(&self) -> bool {
+        self.read_compat
+    }
+
     /// Creates all the tables defined in [`Tables`], if necessary.
     pub fn create_tables(&self) -> Result<(), DatabaseError> {
         self.create_tables_for::<Tables>()
@@ -1449,4 +1483,164 @@ mod tests {
             assert_eq!(list400, list);
         }
     }
+
+    #[test]
+    #[cfg(feature = "table-latency-metrics")]
+    fn db_table_latency_metrics() {
+        use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+        let path = TempDir::new().expect(ERROR_TEMPDIR);
+        let env = metrics::with_local_recorder(&recorder, || {
+            create_test_db_with_path(DatabaseEnvKind::RW, path.path()).with_metrics()
+        });
+
+        env.update(|tx| tx.put::<CanonicalHeaders>(1, B256::ZERO).expect(ERROR_PUT)).unwrap();
+        env.view(|tx| tx.get::<CanonicalHeaders>(1).expect(ERROR_GET)).unwrap();
+        env.view(|tx| {
+            tx.cursor_read::<PlainAccountState>().unwrap().first().unwrap();
+        })
+        .unwrap();
+
+        // number of samples of the histogram of the table
+        let snapshot = snapshotter.snapshot().into_hashmap();
+        let samples = |name: &str, table: &str| {
+            snapshot
+                .iter()
+                .find(|(key, _)| {
+                    key.key().name() == format!("database.table.{name}") &&
+                        key.key().labels().any(|label| label.value() == table)
+                })
+                .map_or(0, |(_, (_, _, value))| match value {
+                    DebugValue::Histogram(values) => values.len(),
+                    _ => unreachable!("table latency metrics are histograms"),
+                })
+        };
+
+        let headers = CanonicalHeaders::NAME;
+        assert_eq!(samples("write_duration_seconds", headers), 1);
+        assert_eq!(samples("read_duration_seconds", headers), 1);
+        assert_eq!(samples("transaction_commit_duration_seconds", headers), 1);
+
+        // cursor reads are recorded, commits only for the written tables
+        let accounts = PlainAccountState::NAME;
+        assert_eq!(samples("read_duration_seconds", accounts), 1);
+        assert_eq!(samples("write_duration_seconds", accounts), 0);
+        assert_eq!(samples("transaction_commit_duration_seconds", accounts), 0);
+    }
+
+    #[test]
+    fn db_external_dup_sort() {
+        use external::{AddressLogs, LogEntry, LogSubKey};
+
+        let path = TempDir::new().expect(ERROR_TEMPDIR);
+        let env = DatabaseEnv::open(
+            path.path(),
+            DatabaseEnvKind::RW,
+            DatabaseArguments::new(ClientVersion::default()),
+        )
+        .expect(ERROR_DB_CREATION);
+        env.create_tables_for::<external::Tables>().expect(ERROR_TABLE_CREATION);
+        assert!(external::Tables::AddressLogs.is_dupsort());
+
+        let address = Address::with_last_byte(1);
+        let entry = |topic, emitter, block_number| LogEntry {
+            key: LogSubKey {
+                topic: B256::with_last_byte(topic),
+                emitter: Address::with_last_byte(emitter),
+            },
+            block_number,
+        };
+        let entries = [entry(2, 1, 10), entry(1, 2, 20), entry(1, 1, 0)];
+        env.update(|tx| {
+            for entry in entries {
+                tx.put::<AddressLogs>(address, entry).expect(ERROR_PUT);
+            }
+        })
+        .unwrap();
+
+        let tx = env.tx().expect(ERROR_INIT_TX);
+        let mut cursor = tx.cursor_dup_read::<AddressLogs>().unwrap();
+
+        // Values are sorted by their subkey.
+        let values = cursor
+            .walk_dup(Some(address), None)
+            .unwrap()
+            .map(|entry| entry.unwrap().1)
+            .collect::<Vec<_>>();
+        assert_eq!(values, vec![entries[2], entries[1], entries[0]]);
+
+        // Values are found by their subkey.
+        assert_eq!(cursor.seek_by_key_subkey(address, entries[1].key).unwrap(), Some(entries[1]));
+        assert_eq!(
+            cursor.seek_by_key_subkey(address, entry(1, 3, 0).key).unwrap(),
+            Some(entries[0])
+        );
+    }
+
+    /// Tables declared like an external crate, e.g. an `ExEx`, would.
+    #[allow(unreachable_pub)]
+    mod external {
+        use alloy_primitives::{Address, B256};
+        use reth_codecs::Compact;
+        use reth_db_api::{table::FixedWidth, FixedWidthKey};
+        use serde::{Deserialize, Serialize};
+
+        /// Subkey of fixed-width fields, whose encoding sorts like the type.
+        #[derive(
+            Debug,
+            Clone,
+            Copy,
+            Default,
+            PartialEq,
+            Eq,
+            PartialOrd,
+            Ord,
+            Serialize,
+            Deserialize,
+            FixedWidthKey,
+        )]
+        pub(super) struct LogSubKey {
+            pub(super) topic: B256,
+            pub(super) emitter: Address,
+        }
+
+        /// Value that is compressed as its subkey followed by the block number.
+        #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+        pub(super) struct LogEntry {
+            pub(super) key: LogSubKey,
+            pub(super) block_number: u64,
+        }
+
+        impl Compact for LogEntry {
+            fn to_compact<B>(&self, buf: &mut B) -> usize
+            where
+                B: bytes::BufMut + AsMut<[u8]>,
+            {
+                let mut key = Vec::with_capacity(LogSubKey::WIDTH);
+                self.key.encode_to(&mut key);
+                buf.put_slice(&key);
+                key.len() + self.block_number.to_compact(buf)
+            }
+
+            fn from_compact(buf: &[u8], len: usize) -> (Self, &[u8]) {
+                let (key, buf) = buf.split_at(LogSubKey::WIDTH);
+                let key = LogSubKey::decode_from(key).expect("subkey has a fixed width");
+                let (block_number, buf) = u64::from_compact(buf, len - LogSubKey::WIDTH);
+                (Self { key, block_number }, buf)
+            }
+        }
+
+        reth_db_api::impl_compression_for_compact!(LogEntry);
+
+        reth_db_api::tables! {
+            /// Logs of an address, sorted by topic and emitter.
+            table AddressLogs {
+                type Key = Address;
+                type Value = LogEntry;
+                type SubKey = LogSubKey;
+            }
+        }
+    }
 }
@@ -19,6 +19,8 @@ mod implementation;
 pub mod lockfile;
 #[cfg(feature = "mdbx")]
 mod metrics;
+#[cfg(feature = "mdbx")]
+pub mod migrate;
 pub mod static_file;
 #[cfg(feature = "mdbx")]
 mod utils;
@@ -214,8 +216,8 @@ mod tests {
     use crate::{
         init_db,
         mdbx::DatabaseArguments,
-        open_db, tables,
-        version::{db_version_file_path, DatabaseVersionError},
+        open_db, open_db_read_only, tables,
+        version::{db_version_file_path, DatabaseVersionError, DB_VERSION},
     };
     use assert_matches::assert_matches;
     use reth_db_api::{
@@ -259,13 +261,43 @@ mod tests {
         // Database is not empty, version file contains not matching version
         {
             reth_fs_util::write(path.path().join(db_version_file_path(&path)), "0").unwrap();
-            let db = init_db(&path, args);
+            let db = init_db(&path, args.clone());
             assert!(db.is_err());
             assert_matches!(
                 db.unwrap_err().downcast_ref::<DatabaseVersionError>(),
                 Some(DatabaseVersionError::VersionMismatch { version: 0 })
             )
         }
+
+        // Database is written by a newer version that is read compatible with the current one
+        {
+            let version = DB_VERSION + 1;
+            reth_fs_util::write(
+                path.path().join(db_version_file_path(&path)),
+                format!("{version}\n{DB_VERSION}"),
+            )
+            .unwrap();
+            let db = init_db(&path, args.clone());
+            assert_matches!(
+                db.unwrap_err().downcast_ref::<DatabaseVersionError>(),
+                Some(DatabaseVersionError::ReadOnlyCompatible { version: v }) if *v == version
+            );
+
+            let db = open_db_read_only(&path, args.clone()).unwrap();
+            assert!(db.is_read_compat());
+        }
+
+        // Database is written by a newer version that is not read compatible with the current one
+        {
+            let version = DB_VERSION + 1;
+            reth_fs_util::write(path.path().join(db_version_file_path(&path)), version.to_string())
+                .unwrap();
+            let db = open_db_read_only(&path, args);
+            assert_matches!(
+                db.unwrap_err().downcast_ref::<DatabaseVersionError>(),
+                Some(DatabaseVersionError::NewerVersion { version: v, .. }) if *v == version
+            );
+        }
     }
 
     #[test]
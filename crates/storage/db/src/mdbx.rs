@@ -1,7 +1,12 @@
 //! Helper functions for initializing and opening a database.
 
-use crate::{is_database_empty, TableSet, Tables};
+use crate::{
+    is_database_empty,
+    version::{check_db_version_compat, DatabaseCompat, DatabaseVersionError, DB_VERSION},
+    TableSet, Tables,
+};
 use eyre::Context;
+use reth_tracing::tracing::warn;
 use std::path::Path;
 
 pub use crate::implementation::mdbx::*;
@@ -10,7 +15,7 @@ pub use reth_libmdbx::*;
 /// Creates a new database at the specified path if it doesn't exist. Does NOT create tables. Check
 /// [`init_db`].
 pub fn create_db<P: AsRef<Path>>(path: P, args: DatabaseArguments) -> eyre::Result<DatabaseEnv> {
-    use crate::version::{check_db_version_file, create_db_version_file, DatabaseVersionError};
+    use crate::version::{check_db_version_file, create_db_version_file};
 
     let rpath = path.as_ref();
     if is_database_empty(rpath) {
@@ -48,11 +53,30 @@ pub fn init_db_for<P: AsRef<Path>, TS: TableSet>(
 }
 
 /// Opens up an existing database. Read only mode. It doesn't create it or create tables if missing.
+///
+/// A database written by a newer version is only opened if that version declared this one as read
+/// compatible, see [`check_db_version_compat`]. Otherwise, a
+/// [`DatabaseVersionError::NewerVersion`] error is returned.
 pub fn open_db_read_only(
     path: impl AsRef<Path>,
-    args: DatabaseArguments,
+    mut args: DatabaseArguments,
 ) -> eyre::Result<DatabaseEnv> {
     let path = path.as_ref();
+    match check_db_version_compat(path) {
+        Ok(DatabaseCompat::Current) => (),
+        Ok(DatabaseCompat::ReadCompat { version }) => {
+            warn!(
+                target: "storage::db",
+                version,
+                current_version = DB_VERSION,
+                "Database was written by a newer, read compatible version, opening it read-only"
+            );
+            args = args.with_read_compat(true);
+        }
+        Err(err @ DatabaseVersionError::NewerVersion { .. }) => return Err(err.into()),
+        // Older or unversioned databases are opened as is.
+        Err(_) => (),
+    }
     DatabaseEnv::open(path, DatabaseEnvKind::RO, args)
         .with_context(|| format!("Could not open database at path: {}", path.display()))
 }
@@ -2,6 +2,8 @@ use crate::Tables;
 use metrics::Histogram;
 use reth_metrics::{metrics::Counter, Metrics};
 use rustc_hash::FxHashMap;
+#[cfg(feature = "table-latency-metrics")]
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::{Duration, Instant};
 use strum::{EnumCount, EnumIter, IntoEnumIterator};
 
@@ -23,6 +25,9 @@ pub(crate) struct DatabaseEnvMetrics {
     /// outcome. Can only be updated at tx close, as outcome is only known at that point.
     transaction_outcomes:
         FxHashMap<(TransactionMode, TransactionOutcome), TransactionOutcomeMetrics>,
+    /// Caches `TableLatencyMetrics` handles for each table.
+    #[cfg(feature = "table-latency-metrics")]
+    tables: FxHashMap<&'static str, TableLatencyMetrics>,
 }
 
 impl DatabaseEnvMetrics {
@@ -33,6 +38,8 @@ impl DatabaseEnvMetrics {
             operations: Self::generate_operation_handles(),
             transactions: Self::generate_transaction_handles(),
             transaction_outcomes: Self::generate_transaction_outcome_handles(),
+            #[cfg(feature = "table-latency-metrics")]
+            tables: Self::generate_table_handles(),
         }
     }
 
@@ -95,6 +102,22 @@ impl DatabaseEnvMetrics {
         transaction_outcomes
     }
 
+    /// Generate a map of all tables to latency metric handles.
+    /// Used for tracking the read and write latency of each table, and the commit latency of the
+    /// transactions that wrote to it.
+    #[cfg(feature = "table-latency-metrics")]
+    fn generate_table_handles() -> FxHashMap<&'static str, TableLatencyMetrics> {
+        Tables::ALL
+            .iter()
+            .map(|table| {
+                (
+                    table.name(),
+                    TableLatencyMetrics::new_with_labels(&[(Labels::Table.as_str(), table.name())]),
+                )
+            })
+            .collect()
+    }
+
     /// Record a metric for database operation executed in `f`.
     /// Panics if a metric recorder is not found for the given table and operation.
     pub(crate) fn record_operation<R>(
@@ -104,6 +127,13 @@ impl DatabaseEnvMetrics {
         value_size: Option<usize>,
         f: impl FnOnce() -> R,
     ) -> R {
+        #[cfg(feature = "table-latency-metrics")]
+        let f = || match self.tables.get(table) {
+            Some(metrics) if operation.is_write() => metrics.record_write(f),
+            Some(metrics) => metrics.record_read(f),
+            None => f(),
+        };
+
         if let Some(metrics) = self.operations.get(&(table, operation)) {
             metrics.record(value_size, f)
         } else {
@@ -111,6 +141,16 @@ impl DatabaseEnvMetrics {
         }
     }
 
+    /// Record the latency of a cursor read executed in `f`.
+    #[cfg(feature = "table-latency-metrics")]
+    pub(crate) fn record_cursor_read<R>(&self, table: &'static str, f: impl FnOnce() -> R) -> R {
+        if let Some(metrics) = self.tables.get(table) {
+            metrics.record_read(f)
+        } else {
+            f()
+        }
+    }
+
     /// Record metrics for opening a database transaction.
     pub(crate) fn record_opened_transaction(&self, mode: TransactionMode) {
         self.transactions
@@ -138,6 +178,14 @@ impl DatabaseEnvMetrics {
             .get(&(mode, outcome))
             .expect("transaction outcome metric handle not found")
             .record(open_duration, close_duration, commit_latency);
+
+        #[cfg(feature = "table-latency-metrics")]
+        if !mode.is_read_only() {
+            let commit_duration = outcome.is_commit().then_some(close_duration).flatten();
+            for metrics in self.tables.values() {
+                metrics.record_transaction_closed(commit_duration);
+            }
+        }
     }
 }
 
@@ -230,6 +278,12 @@ impl Operation {
             Self::CursorDeleteCurrentDuplicates => "cursor-delete-current-duplicates",
         }
     }
+
+    /// Returns `true` if the operation modifies the table.
+    #[cfg(feature = "table-latency-metrics")]
+    pub(crate) const fn is_write(&self) -> bool {
+        !matches!(self, Self::Get)
+    }
 }
 
 /// Enum defining labels for various aspects used in metrics.
@@ -360,3 +414,57 @@ impl OperationMetrics {
         }
     }
 }
+
+/// Latency of the operations on a table, recorded for every operation rather than only for large
+/// values.
+///
+/// Timing every operation has a noticeable overhead, so these metrics are only recorded with the
+/// `table-latency-metrics` feature.
+#[cfg(feature = "table-latency-metrics")]
+#[derive(Metrics)]
+#[metrics(scope = "database.table")]
+pub(crate) struct TableLatencyMetrics {
+    /// The time it took to read from the table, with a get or a cursor operation
+    read_duration_seconds: Histogram,
+    /// The time it took to write to the table, with a put, delete or a cursor operation
+    write_duration_seconds: Histogram,
+    /// The time it took to commit read-write transactions that wrote to the table.
+    ///
+    /// This is the commit latency of the whole transaction, labelled by every table it wrote to,
+    /// so it can't attribute a slow commit to a single table.
+    transaction_commit_duration_seconds: Histogram,
+    /// Whether the open read-write transaction wrote to the table. There is at most one
+    /// read-write transaction at a time, so this is reset when it's closed.
+    #[metric(skip)]
+    written: AtomicBool,
+}
+
+#[cfg(feature = "table-latency-metrics")]
+impl TableLatencyMetrics {
+    /// Record the duration it took to execute the read in `f`.
+    pub(crate) fn record_read<R>(&self, f: impl FnOnce() -> R) -> R {
+        let start = Instant::now();
+        let result = f();
+        self.read_duration_seconds.record(start.elapsed());
+        result
+    }
+
+    /// Record the duration it took to execute the write in `f`, and mark the table as written by
+    /// the open read-write transaction.
+    pub(crate) fn record_write<R>(&self, f: impl FnOnce() -> R) -> R {
+        let start = Instant::now();
+        let result = f();
+        self.write_duration_seconds.record(start.elapsed());
+        self.written.store(true, Ordering::Relaxed);
+        result
+    }
+
+    /// Record the commit duration of the closed read-write transaction if it wrote to the table.
+    pub(crate) fn record_transaction_closed(&self, commit_duration: Option<Duration>) {
+        if self.written.swap(false, Ordering::Relaxed) {
+            if let Some(commit_duration) = commit_duration {
+                self.transaction_commit_duration_seconds.record(commit_duration);
+            }
+        }
+    }
+}
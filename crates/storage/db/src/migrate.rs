@@ -0,0 +1,186 @@
+//! Migration of a database environment into a new one.
+//!
+//! The page size of an MDBX environment is fixed when it's created and defaults to the page size of
+//! the OS, so a datadir can't always be copied to a machine with a different page size or platform
+//! layout. Instead, [`migrate_env`] copies the contents of an environment into a new environment,
+//! which can be created with any supported page size.
+
+use crate::{DatabaseEnv, DatabaseError, Tables};
+use reth_db_api::{
+    cursor::{DbCursorRO, DbCursorRW, DbDupCursorRW},
+    database::Database,
+    table::{DupSort, Table, TableRow},
+    transaction::{DbTx, DbTxMut},
+    RawDupSort, RawTable, TableViewer,
+};
+use std::cell::RefCell;
+
+/// Number of entries that are copied in a single write transaction.
+const COMMIT_THRESHOLD: usize = 100_000;
+
+/// Progress of a [`migrate_env`] call, reported after every committed batch of a table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MigrationProgress {
+    /// The table that is being copied.
+    pub table: Tables,
+    /// Number of entries of the table that were copied so far.
+    pub copied: usize,
+    /// Total number of entries of the table.
+    pub total: usize,
+}
+
+/// Copies all tables of the `source` environment into the `target` environment.
+///
+/// Tables are copied one by one as raw key-value pairs in batches of [`COMMIT_THRESHOLD`] entries.
+/// Since all entries are encoded in a platform independent format, the `target` environment can use
+/// a different page size than the `source`.
+///
+/// All tables must already exist in the `target` environment and must be empty. Returns the total
+/// number of copied entries.
+pub fn migrate_env(
+    source: &DatabaseEnv,
+    target: &DatabaseEnv,
+    on_progress: impl FnMut(MigrationProgress),
+) -> Result<usize, DatabaseError> {
+    let on_progress = RefCell::new(on_progress);
+    let mut copied = 0;
+    for table in Tables::ALL {
+        copied += table.view(&MigrateViewer {
+            source,
+            target,
+            table: *table,
+            on_progress: &on_progress,
+        })?;
+    }
+    Ok(copied)
+}
+
+/// Copies a single table from the source to the target environment.
+struct MigrateViewer<'a, F> {
+    source: &'a DatabaseEnv,
+    target: &'a DatabaseEnv,
+    table: Tables,
+    on_progress: &'a RefCell<F>,
+}
+
+impl<F: FnMut(MigrationProgress)> MigrateViewer<'_, F> {
+    /// Copies the raw entries of the table in batches, writing each batch with `write`.
+    fn copy_table<T: Table>(
+        &self,
+        write: impl Fn(
+            &<DatabaseEnv as Database>::TXMut,
+            Vec<TableRow<RawTable<T>>>,
+        ) -> Result<(), DatabaseError>,
+    ) -> Result<usize, DatabaseError> {
+        if self.target.tx()?.entries::<T>()? != 0 {
+            return Err(DatabaseError::Other(format!(
+                "table {} of the target database is not empty",
+                T::NAME
+            )))
+        }
+
+        let mut source_tx = self.source.tx()?;
+        // copying a large table takes a while
+        source_tx.disable_long_read_transaction_safety();
+        let total = source_tx.entries::<T>()?;
+
+        let mut cursor = source_tx.cursor_read::<RawTable<T>>()?;
+        let mut walker = cursor.walk(None)?;
+        let mut copied = 0;
+        loop {
+            let batch = walker.by_ref().take(COMMIT_THRESHOLD).collect::<Result<Vec<_>, _>>()?;
+            if batch.is_empty() {
+                break
+            }
+            copied += batch.len();
+
+            let target_tx = self.target.tx_mut()?;
+            write(&target_tx, batch)?;
+            target_tx.commit()?;
+
+            (self.on_progress.borrow_mut())(MigrationProgress { table: self.table, copied, total });
+        }
+
+        Ok(copied)
+    }
+}
+
+impl<F: FnMut(MigrationProgress)> TableViewer<usize> for MigrateViewer<'_, F> {
+    type Error = DatabaseError;
+
+    fn view<T: Table>(&self) -> Result<usize, Self::Error> {
+        self.copy_table::<T>(|tx, batch| {
+            let mut cursor = tx.cursor_write::<RawTable<T>>()?;
+            for (key, value) in batch {
+                cursor.append(key, &value)?;
+            }
+            Ok(())
+        })
+    }
+
+    fn view_dupsort<T: DupSort>(&self) -> Result<usize, Self::Error> {
+        self.copy_table::<T>(|tx, batch| {
+            let mut cursor = tx.cursor_dup_write::<RawDupSort<T>>()?;
+            for (key, value) in batch {
+                cursor.append_dup(key, value)?;
+            }
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        mdbx::{DatabaseArguments, DatabaseEnvKind},
+        tables, ClientVersion,
+    };
+    use alloy_primitives::{Address, B256, U256};
+    use reth_db_api::cursor::DbDupCursorRO;
+    use reth_primitives_traits::StorageEntry;
+
+    fn create_db(page_size: usize) -> (tempfile::TempDir, DatabaseEnv) {
+        let dir = tempfile::TempDir::new().unwrap();
+        let args = DatabaseArguments::new(ClientVersion::default()).with_page_size(Some(page_size));
+        let db = DatabaseEnv::open(dir.path(), DatabaseEnvKind::RW, args).unwrap();
+        db.create_tables().unwrap();
+        (dir, db)
+    }
+
+    #[test]
+    fn migrate_to_different_page_size() {
+        let (_source_dir, source) = create_db(4096);
+        let (_target_dir, target) = create_db(16384);
+
+        let tx = source.tx_mut().unwrap();
+        for number in 0..10u64 {
+            tx.put::<tables::CanonicalHeaders>(number, B256::with_last_byte(number as u8)).unwrap();
+        }
+        for slot in 0..5u8 {
+            let entry = StorageEntry { key: B256::with_last_byte(slot), value: U256::from(slot) };
+            tx.put::<tables::PlainStorageState>(Address::ZERO, entry).unwrap();
+        }
+        tx.commit().unwrap();
+
+        let mut progress = Vec::new();
+        let copied = migrate_env(&source, &target, |p| progress.push(p)).unwrap();
+        assert_eq!(copied, 15);
+        assert!(progress.contains(&MigrationProgress {
+            table: Tables::PlainStorageState,
+            copied: 5,
+            total: 5
+        }));
+
+        assert_eq!(target.stat().unwrap().page_size(), 16384);
+        let tx = target.tx().unwrap();
+        assert_eq!(tx.entries::<tables::CanonicalHeaders>().unwrap(), 10);
+        assert_eq!(tx.get::<tables::CanonicalHeaders>(7).unwrap(), Some(B256::with_last_byte(7)));
+        let mut cursor = tx.cursor_dup_read::<tables::PlainStorageState>().unwrap();
+        let storage = cursor.walk_dup(Some(Address::ZERO), None).unwrap().count();
+        assert_eq!(storage, 5);
+
+        // the target is no longer empty
+        assert!(migrate_env(&source, &target, |_| {}).is_err());
+    }
+}
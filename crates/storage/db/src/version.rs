@@ -10,6 +10,15 @@ pub const DB_VERSION_FILE_NAME: &str = "database.version";
 /// The version of the database stored in the [`DB_VERSION_FILE_NAME`] file in the same directory as
 /// database.
 pub const DB_VERSION: u64 = 2;
+/// The oldest database version that is able to read a database written with [`DB_VERSION`] in
+/// read-only mode.
+///
+/// Only lower this below [`DB_VERSION`] if the older versions can decode all tables they read
+/// as is, there are no compatibility shims for reading newer encodings.
+///
+/// It's stored on the second line of the [`DB_VERSION_FILE_NAME`] file if it differs from
+/// [`DB_VERSION`].
+pub const DB_READ_COMPAT_VERSION: u64 = 2;
 
 /// Error when checking a database version using [`check_db_version_file`]
 #[derive(thiserror::Error, Debug)]
@@ -31,6 +40,26 @@ pub enum DatabaseVersionError {
         /// The detected version in the database.
         version: u64,
     },
+    /// The database was written by a newer version that this version can't read.
+    #[error(
+        "the database was written by a newer version (v{version}) that can only be read by \
+         database versions v{read_compat_version} and newer, this version is v{DB_VERSION}"
+    )]
+    NewerVersion {
+        /// The detected version in the database.
+        version: u64,
+        /// The oldest version that is able to read the database.
+        read_compat_version: u64,
+    },
+    /// The database was written by a newer version and can only be opened read-only.
+    #[error(
+        "the database was written by a newer version (v{version}) and can only be opened \
+         read-only by this version (v{DB_VERSION})"
+    )]
+    ReadOnlyCompatible {
+        /// The detected version in the database.
+        version: u64,
+    },
     /// IO error occurred while reading the database version file.
     #[error("IO error occurred while reading {path}: {err}")]
     IORead {
@@ -41,17 +70,84 @@ pub enum DatabaseVersionError {
     },
 }
 
+/// Version information stored in the [`DB_VERSION_FILE_NAME`] file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DatabaseVersion {
+    /// The version of the database.
+    pub version: u64,
+    /// The oldest version that is able to read the database in read-only mode.
+    ///
+    /// Equals to `version` if the file doesn't specify it.
+    pub read_compat_version: u64,
+}
+
+impl DatabaseVersion {
+    /// Parses the contents of the [`DB_VERSION_FILE_NAME`] file.
+    ///
+    /// The first line contains the version, the optional second line contains the read
+    /// compatibility version.
+    fn parse(raw: &str) -> Option<Self> {
+        let mut lines = raw.lines().map(str::trim);
+        let version = lines.next()?.parse().ok()?;
+        let read_compat_version = match lines.next() {
+            Some(line) if !line.is_empty() => line.parse().ok()?,
+            _ => version,
+        };
+        if lines.any(|line| !line.is_empty()) {
+            return None
+        }
+        Some(Self { version, read_compat_version })
+    }
+}
+
+/// Compatibility of this version with an existing database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DatabaseCompat {
+    /// The database has the current [`DB_VERSION`].
+    Current,
+    /// The database was written by a newer version, but can still be read by this version in
+    /// read-only mode.
+    ///
+    /// No data is translated when reading: the newer version only declares this version as read
+    /// compatible, see [`DB_READ_COMPAT_VERSION`], if it didn't change the encoding of any table
+    /// this version reads.
+    ReadCompat {
+        /// The detected version in the database.
+        version: u64,
+    },
+}
+
 /// Checks the database version file with [`DB_VERSION_FILE_NAME`] name.
 ///
-/// Returns [Ok] if file is found and has one line which equals to [`DB_VERSION`].
+/// Returns [Ok] if file is found and has the version which equals to [`DB_VERSION`].
 /// Otherwise, returns different [`DatabaseVersionError`] error variants.
 pub fn check_db_version_file<P: AsRef<Path>>(db_path: P) -> Result<(), DatabaseVersionError> {
-    let version = get_db_version(db_path)?;
-    if version != DB_VERSION {
-        return Err(DatabaseVersionError::VersionMismatch { version })
+    match check_db_version_compat(db_path)? {
+        DatabaseCompat::Current => Ok(()),
+        DatabaseCompat::ReadCompat { version } => {
+            Err(DatabaseVersionError::ReadOnlyCompatible { version })
+        }
     }
+}
 
-    Ok(())
+/// Checks whether this version is able to open the database in read-only mode.
+///
+/// Returns [`DatabaseCompat::ReadCompat`] if the database was written by a newer version that
+/// declared this version as read compatible, and [`DatabaseVersionError::NewerVersion`] if it
+/// didn't.
+pub fn check_db_version_compat<P: AsRef<Path>>(
+    db_path: P,
+) -> Result<DatabaseCompat, DatabaseVersionError> {
+    let DatabaseVersion { version, read_compat_version } = read_db_version_file(db_path)?;
+    if version == DB_VERSION {
+        Ok(DatabaseCompat::Current)
+    } else if version < DB_VERSION {
+        Err(DatabaseVersionError::VersionMismatch { version })
+    } else if read_compat_version <= DB_VERSION {
+        Ok(DatabaseCompat::ReadCompat { version })
+    } else {
+        Err(DatabaseVersionError::NewerVersion { version, read_compat_version })
+    }
 }
 
 /// Returns the database version from file with [`DB_VERSION_FILE_NAME`] name.
@@ -59,10 +155,20 @@ pub fn check_db_version_file<P: AsRef<Path>>(db_path: P) -> Result<(), DatabaseV
 /// Returns [Ok] if file is found and contains a valid version.
 /// Otherwise, returns different [`DatabaseVersionError`] error variants.
 pub fn get_db_version<P: AsRef<Path>>(db_path: P) -> Result<u64, DatabaseVersionError> {
+    read_db_version_file(db_path).map(|version| version.version)
+}
+
+/// Returns the [`DatabaseVersion`] from file with [`DB_VERSION_FILE_NAME`] name.
+///
+/// Returns [Ok] if file is found and contains a valid version.
+/// Otherwise, returns different [`DatabaseVersionError`] error variants.
+pub fn read_db_version_file<P: AsRef<Path>>(
+    db_path: P,
+) -> Result<DatabaseVersion, DatabaseVersionError> {
     let version_file_path = db_version_file_path(db_path);
     match fs::read_to_string(&version_file_path) {
         Ok(raw_version) => {
-            Ok(raw_version.parse::<u64>().map_err(|_| DatabaseVersionError::MalformedFile)?)
+            DatabaseVersion::parse(&raw_version).ok_or(DatabaseVersionError::MalformedFile)
         }
         Err(err) if err.kind() == io::ErrorKind::NotFound => Err(DatabaseVersionError::MissingFile),
         Err(err) => Err(DatabaseVersionError::IORead { err, path: version_file_path }),
@@ -70,12 +176,17 @@ pub fn get_db_version<P: AsRef<Path>>(db_path: P) -> Result<u64, DatabaseVersion
 }
 
 /// Creates a database version file with [`DB_VERSION_FILE_NAME`] name containing [`DB_VERSION`]
-/// string.
+/// string, followed by [`DB_READ_COMPAT_VERSION`] on the second line if it differs.
 ///
 /// This function will create a file if it does not exist,
 /// and will entirely replace its contents if it does.
 pub fn create_db_version_file<P: AsRef<Path>>(db_path: P) -> io::Result<()> {
-    fs::write(db_version_file_path(db_path), DB_VERSION.to_string())
+    let contents = if DB_READ_COMPAT_VERSION == DB_VERSION {
+        DB_VERSION.to_string()
+    } else {
+        format!("{DB_VERSION}\n{DB_READ_COMPAT_VERSION}")
+    };
+    fs::write(db_version_file_path(db_path), contents)
 }
 
 /// Returns a database version file path.
@@ -85,7 +196,10 @@ pub fn db_version_file_path<P: AsRef<Path>>(db_path: P) -> PathBuf {
 
 #[cfg(test)]
 mod tests {
-    use super::{check_db_version_file, db_version_file_path, DatabaseVersionError};
+    use super::{
+        check_db_version_compat, check_db_version_file, db_version_file_path, DatabaseCompat,
+        DatabaseVersionError, DB_VERSION,
+    };
     use assert_matches::assert_matches;
     use std::fs;
     use tempfile::tempdir;
@@ -115,4 +229,44 @@ mod tests {
         let result = check_db_version_file(&dir);
         assert_matches!(result, Err(DatabaseVersionError::VersionMismatch { version: 0 }));
     }
+
+    #[test]
+    fn newer_version() {
+        let dir = tempdir().unwrap();
+
+        // Newer version that is still readable by this version.
+        fs::write(db_version_file_path(&dir), format!("{}\n{DB_VERSION}", DB_VERSION + 1)).unwrap();
+        let version = DB_VERSION + 1;
+        assert_matches!(
+            check_db_version_compat(&dir),
+            Ok(DatabaseCompat::ReadCompat { version: v }) if v == version
+        );
+        assert_matches!(
+            check_db_version_file(&dir),
+            Err(DatabaseVersionError::ReadOnlyCompatible { version: v }) if v == version
+        );
+
+        // Newer version without an explicit read compatibility version.
+        fs::write(db_version_file_path(&dir), version.to_string()).unwrap();
+        assert_matches!(
+            check_db_version_compat(&dir),
+            Err(DatabaseVersionError::NewerVersion { version: v, read_compat_version: r })
+                if v == version && r == version
+        );
+
+        // Malformed read compatibility version.
+        fs::write(db_version_file_path(&dir), format!("{version}\ninvalid")).unwrap();
+        assert_matches!(check_db_version_compat(&dir), Err(DatabaseVersionError::MalformedFile));
+    }
+
+    #[test]
+    fn current_version() {
+        let dir = tempdir().unwrap();
+        super::create_db_version_file(&dir).unwrap();
+        assert_matches!(check_db_version_compat(&dir), Ok(DatabaseCompat::Current));
+
+        // Trailing newline is accepted.
+        fs::write(db_version_file_path(&dir), format!("{DB_VERSION}\n")).unwrap();
+        assert_matches!(check_db_version_file(&dir), Ok(()));
+    }
 }
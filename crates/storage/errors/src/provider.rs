@@ -100,6 +100,17 @@ pub enum ProviderError {
     /// State is not available for the given block number because it is pruned.
     #[error("state at block #{_0} is pruned")]
     StateAtBlockPruned(BlockNumber),
+    /// Reconstructing the state at the given block requires replaying more changeset entries than
+    /// the configured proof effort budget allows.
+    #[error(
+        "state at block #{block} exceeds the proof effort budget of {budget} changeset entries"
+    )]
+    ProofEffortExceeded {
+        /// The requested block number.
+        block: BlockNumber,
+        /// The maximum number of changeset entries that may be replayed.
+        budget: usize,
+    },
     /// Provider does not support this particular request.
     #[error("this provider does not support this request")]
     UnsupportedProvider,
@@ -107,6 +118,19 @@ pub enum ProviderError {
     #[cfg(feature = "std")]
     #[error("not able to find {_0} static file at {_1:?}")]
     MissingStaticFilePath(StaticFileSegment, std::path::PathBuf),
+    /// Static files of a segment are located in a different directory than the configured one.
+    #[cfg(feature = "std")]
+    #[error(
+        "{segment} static files are located in {current:?}, move them to {configured:?} first"
+    )]
+    StaticFileSegmentDirectory {
+        /// The static file segment.
+        segment: StaticFileSegment,
+        /// The directory the static files are located in.
+        current: std::path::PathBuf,
+        /// The configured directory.
+        configured: std::path::PathBuf,
+    },
     /// Static File is not found for requested block.
     #[error("not able to find {_0} static file for block number {_1}")]
     MissingStaticFileBlock(StaticFileSegment, BlockNumber),
@@ -137,6 +161,24 @@ pub enum ProviderError {
     /// Missing trie updates.
     #[error("missing trie updates for block {0}")]
     MissingTrieUpdates(B256),
+    /// The requested block is above the last block that is consistent across storage, which can
+    /// only happen when running in safe mode on damaged storage.
+    #[error("block #{block} is unavailable, storage is degraded above block #{consistent_tip}")]
+    StorageDegraded {
+        /// The requested block number.
+        block: BlockNumber,
+        /// The highest block that is consistent across database and static files.
+        consistent_tip: BlockNumber,
+    },
+    /// The requested block is below the history expiry boundary, its bodies and receipts are no
+    /// longer stored.
+    #[error("block #{block} history is expired, the earliest available block is #{earliest}")]
+    HistoryExpired {
+        /// The requested block number.
+        block: BlockNumber,
+        /// The earliest block with available history.
+        earliest: BlockNumber,
+    },
     /// Any other error type wrapped into a cloneable [`AnyError`].
     #[error(transparent)]
     Other(#[from] AnyError),
@@ -182,6 +182,23 @@ impl Environment {
         }
     }
 
+    /// Copies the environment to a new file at `dest`.
+    ///
+    /// The copy is made from a consistent read-only snapshot, so it can be taken while other
+    /// processes keep writing to the environment. If `compact` is set, free pages are omitted and
+    /// all pages are renumbered sequentially in the output.
+    ///
+    /// The destination file must not exist yet, but its parent directory must be writable.
+    pub fn copy(&self, dest: &Path, compact: bool) -> Result<()> {
+        let dest = CString::new(path_to_bytes(dest)).map_err(|_| Error::Invalid)?;
+        let mut flags = ffi::MDBX_CP_THROTTLE_MVCC;
+        if compact {
+            flags |= ffi::MDBX_CP_COMPACT;
+        }
+        mdbx_result(unsafe { ffi::mdbx_env_copy(self.env_ptr(), dest.as_ptr(), flags) })?;
+        Ok(())
+    }
+
     /// Retrieves the total number of pages on the freelist.
     ///
     /// Along with [`Environment::info()`], this can be used to calculate the exact number
@@ -690,20 +707,6 @@ impl EnvironmentBuilder {
                     ))?;
                 }
 
-                #[cfg(unix)]
-                fn path_to_bytes<P: AsRef<Path>>(path: P) -> Vec<u8> {
-                    use std::os::unix::ffi::OsStrExt;
-                    path.as_ref().as_os_str().as_bytes().to_vec()
-                }
-
-                #[cfg(windows)]
-                fn path_to_bytes<P: AsRef<Path>>(path: P) -> Vec<u8> {
-                    // On Windows, could use std::os::windows::ffi::OsStrExt to encode_wide(),
-                    // but we end up with a Vec<u16> instead of a Vec<u8>, so that doesn't
-                    // really help.
-                    path.as_ref().to_string_lossy().to_string().into_bytes()
-                }
-
                 let path = match CString::new(path_to_bytes(path)) {
                     Ok(path) => path,
                     Err(_) => return Err(Error::Invalid),
@@ -918,6 +921,20 @@ fn convert_hsr_fn(callback: Option<HandleSlowReadersCallback>) -> ffi::MDBX_hsr_
     unsafe { std::mem::transmute(callback) }
 }
 
+#[cfg(unix)]
+fn path_to_bytes<P: AsRef<Path>>(path: P) -> Vec<u8> {
+    use std::os::unix::ffi::OsStrExt;
+    path.as_ref().as_os_str().as_bytes().to_vec()
+}
+
+#[cfg(windows)]
+fn path_to_bytes<P: AsRef<Path>>(path: P) -> Vec<u8> {
+    // On Windows, could use std::os::windows::ffi::OsStrExt to encode_wide(),
+    // but we end up with a Vec<u16> instead of a Vec<u8>, so that doesn't
+    // really help.
+    path.as_ref().to_string_lossy().to_string().into_bytes()
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{Environment, Error, Geometry, HandleSlowReadersReturnCode, PageSize, WriteFlags};
@@ -38,6 +38,25 @@ fn test_begin_txn() {
     }
 }
 
+#[test]
+fn test_copy() {
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+
+    let txn = env.begin_rw_txn().unwrap();
+    let db = txn.open_db(None).unwrap();
+    txn.put(db.dbi(), b"key", b"val", WriteFlags::empty()).unwrap();
+    txn.commit().unwrap();
+
+    let copy_dir = tempdir().unwrap();
+    env.copy(&copy_dir.path().join("mdbx.dat"), true).unwrap();
+
+    let copy = Environment::builder().open(copy_dir.path()).unwrap();
+    let txn = copy.begin_ro_txn().unwrap();
+    let db = txn.open_db(None).unwrap();
+    assert_eq!(txn.get(db.dbi(), b"key").unwrap(), Some(*b"val"));
+}
+
 #[test]
 fn test_open_db() {
     let dir = tempdir().unwrap();
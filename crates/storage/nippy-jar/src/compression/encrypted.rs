@@ -0,0 +1,249 @@
+use crate::{
+    compression::{Compression, Compressors},
+    NippyJarError,
+};
+use aes_gcm::{
+    aead::{Aead, KeyInit, Payload},
+    Aes256Gcm, Nonce,
+};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::sync::OnceLock;
+
+/// Length of the random nonce prepended to every encrypted value.
+const NONCE_LEN: usize = 12;
+
+/// Length of the authentication tag appended to every encrypted value.
+const TAG_LEN: usize = 16;
+
+/// Message that is authenticated with the file key to check whether the right key is used.
+const KEY_CHECK_MESSAGE: &[u8] = b"nippy-jar-key-check";
+
+/// A 256-bit master key for [`Encrypted`] jars.
+#[derive(Clone, PartialEq, Eq)]
+pub struct EncryptionKey([u8; 32]);
+
+impl EncryptionKey {
+    /// Creates a new key from raw bytes.
+    pub const fn new(key: [u8; 32]) -> Self {
+        Self(key)
+    }
+
+    /// Derives the key of a single file from the master key and the salt of the file.
+    fn derive(&self, salt: &[u8; 32]) -> [u8; 32] {
+        hmac_sha256(&self.0, salt)
+    }
+}
+
+impl std::fmt::Debug for EncryptionKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("EncryptionKey").field(&"<redacted>").finish()
+    }
+}
+
+/// Encrypts column values with AES-256-GCM after compressing them with an optional inner
+/// compressor.
+///
+/// Every jar has its own key, derived from the master key and a random salt that is stored in the
+/// jar configuration. Every value is encrypted with a random nonce, which is stored in front of
+/// the ciphertext, followed by the authentication tag. This adds 28 bytes to every value.
+///
+/// The row and column of a value and the salt of the jar are authenticated along with it, so
+/// values can't be swapped between positions or files without failing decryption. Values are
+/// therefore only encrypted and decrypted by the jar writer and cursor, which know their
+/// position, and not through [`Compression`].
+///
+/// Jars that are loaded from disk need their key set with [`Encrypted::set_key`].
+///
+/// Zstd with dictionaries is not supported as the inner compressor.
+#[derive(Serialize, Deserialize)]
+pub struct Encrypted {
+    /// Compressor applied to values before they're encrypted.
+    compressor: Option<Box<Compressors>>,
+    /// Salt the file key is derived with.
+    salt: [u8; 32],
+    /// Checks whether the master key used for decryption is the one used for encryption.
+    key_check: [u8; 8],
+    /// Cipher with the file key, set on creation or with [`Encrypted::set_key`].
+    #[serde(skip)]
+    cipher: OnceLock<Aes256Gcm>,
+}
+
+impl Encrypted {
+    /// Creates a new [`Encrypted`] with a random salt, that compresses values with the given
+    /// compressor before encrypting them with a key derived from `key`.
+    pub fn new(key: &EncryptionKey, compressor: Option<Compressors>) -> Self {
+        let salt = rand::random();
+        let file_key = key.derive(&salt);
+        Self {
+            compressor: compressor.map(Box::new),
+            salt,
+            key_check: key_check(&file_key),
+            cipher: OnceLock::from(Aes256Gcm::new(&file_key.into())),
+        }
+    }
+
+    /// Returns the compressor applied to values before they're encrypted.
+    pub fn compressor(&self) -> Option<&Compressors> {
+        self.compressor.as_deref()
+    }
+
+    /// Derives the file key from the master key and checks that it's the key the jar was
+    /// encrypted with.
+    pub fn set_key(&self, key: &EncryptionKey) -> Result<(), NippyJarError> {
+        let file_key = key.derive(&self.salt);
+        if key_check(&file_key) != self.key_check {
+            return Err(NippyJarError::WrongEncryptionKey)
+        }
+        self.cipher.get_or_init(|| Aes256Gcm::new(&file_key.into()));
+        Ok(())
+    }
+
+    /// Returns the cipher with the file key, if the key is set.
+    fn cipher(&self) -> Result<&Aes256Gcm, NippyJarError> {
+        self.cipher.get().ok_or(NippyJarError::MissingEncryptionKey)
+    }
+
+    /// Returns the additional data that is authenticated with the value at the given position.
+    fn aad(&self, row: u64, column: usize) -> [u8; 48] {
+        let mut aad = [0; 48];
+        aad[..8].copy_from_slice(&row.to_le_bytes());
+        aad[8..16].copy_from_slice(&(column as u64).to_le_bytes());
+        aad[16..].copy_from_slice(&self.salt);
+        aad
+    }
+
+    /// Compresses and encrypts the value at the given row and column, and appends
+    /// `nonce || ciphertext || tag` to `dest`.
+    ///
+    /// Returns the number of bytes appended.
+    pub fn encrypt_to(
+        &self,
+        value: &[u8],
+        row: u64,
+        column: usize,
+        dest: &mut Vec<u8>,
+    ) -> Result<usize, NippyJarError> {
+        let compressed;
+        let value = match self.compressor() {
+            Some(compressor) => {
+                compressed = compressor.compress(value)?;
+                compressed.as_slice()
+            }
+            None => value,
+        };
+
+        let nonce: [u8; NONCE_LEN] = rand::random();
+        let ciphertext = self
+            .cipher()?
+            .encrypt(Nonce::from_slice(&nonce), Payload { msg: value, aad: &self.aad(row, column) })
+            .map_err(|_| NippyJarError::Encryption)?;
+
+        dest.extend_from_slice(&nonce);
+        dest.extend_from_slice(&ciphertext);
+        Ok(NONCE_LEN + ciphertext.len())
+    }
+
+    /// Decrypts a value returned by [`Self::encrypt_to`] for the same row and column, verifying
+    /// its authentication tag, and appends the decompressed value to `dest`.
+    pub fn decrypt_to(
+        &self,
+        value: &[u8],
+        row: u64,
+        column: usize,
+        dest: &mut Vec<u8>,
+    ) -> Result<(), NippyJarError> {
+        if value.len() < NONCE_LEN + TAG_LEN {
+            return Err(NippyJarError::Decryption)
+        }
+        let (nonce, ciphertext) = value.split_at(NONCE_LEN);
+        let decrypted = self
+            .cipher()?
+            .decrypt(
+                Nonce::from_slice(nonce),
+                Payload { msg: ciphertext, aad: &self.aad(row, column) },
+            )
+            .map_err(|_| NippyJarError::Decryption)?;
+
+        match self.compressor() {
+            Some(compressor) => compressor.decompress_to(&decrypted, dest),
+            None => {
+                dest.extend_from_slice(&decrypted);
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Values are bound to their position, see [`Encrypted::encrypt_to`] and
+/// [`Encrypted::decrypt_to`].
+impl Compression for Encrypted {
+    fn decompress_to(&self, _value: &[u8], _dest: &mut Vec<u8>) -> Result<(), NippyJarError> {
+        Err(NippyJarError::Decryption)
+    }
+
+    fn decompress(&self, _value: &[u8]) -> Result<Vec<u8>, NippyJarError> {
+        Err(NippyJarError::Decryption)
+    }
+
+    fn compress_to(&self, _src: &[u8], _dest: &mut Vec<u8>) -> Result<usize, NippyJarError> {
+        Err(NippyJarError::Encryption)
+    }
+
+    fn compress(&self, _src: &[u8]) -> Result<Vec<u8>, NippyJarError> {
+        Err(NippyJarError::Encryption)
+    }
+
+    fn is_ready(&self) -> bool {
+        match self.compressor() {
+            Some(Compressors::Zstd(zstd)) if zstd.use_dict => false,
+            Some(compressor) => compressor.is_ready(),
+            None => true,
+        }
+    }
+
+    #[cfg(test)]
+    fn prepare_compression(
+        &mut self,
+        columns: Vec<impl IntoIterator<Item = Vec<u8>>>,
+    ) -> Result<(), NippyJarError> {
+        match self.compressor.as_deref_mut() {
+            Some(compressor) => compressor.prepare_compression(columns),
+            None => Ok(()),
+        }
+    }
+}
+
+impl std::fmt::Debug for Encrypted {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Encrypted")
+            .field("compressor", &self.compressor)
+            .field("salt", &self.salt)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+impl PartialEq for Encrypted {
+    fn eq(&self, other: &Self) -> bool {
+        self.compressor == other.compressor &&
+            self.salt == other.salt &&
+            self.key_check == other.key_check
+    }
+}
+
+/// Returns the check value of a file key, which is stored to detect a wrong master key.
+fn key_check(file_key: &[u8; 32]) -> [u8; 8] {
+    let mut check = [0; 8];
+    check.copy_from_slice(&hmac_sha256(file_key, KEY_CHECK_MESSAGE)[..8]);
+    check
+}
+
+/// Returns the HMAC-SHA256 of the message.
+fn hmac_sha256(key: &[u8], message: &[u8]) -> [u8; 32] {
+    let mut mac =
+        <Hmac<Sha256> as Mac>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(message);
+    mac.finalize().into_bytes().into()
+}
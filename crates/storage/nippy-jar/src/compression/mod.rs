@@ -5,6 +5,8 @@ mod zstd;
 pub use self::zstd::{DecoderDictionary, Decompressor, Zstd, ZstdState};
 mod lz4;
 pub use self::lz4::Lz4;
+mod encrypted;
+pub use self::encrypted::{Encrypted, EncryptionKey};
 
 /// Trait that will compress column values
 pub trait Compression: Serialize + for<'a> Deserialize<'a> {
@@ -48,6 +50,8 @@ pub enum Compressors {
     Zstd(Zstd),
     /// LZ4 compression algorithm with custom settings.
     Lz4(Lz4),
+    /// AES-256-GCM encryption of values compressed with an optional inner compressor.
+    Encrypted(Encrypted),
 }
 
 impl Compression for Compressors {
@@ -55,12 +59,14 @@ impl Compression for Compressors {
         match self {
             Self::Zstd(zstd) => zstd.decompress_to(value, dest),
             Self::Lz4(lz4) => lz4.decompress_to(value, dest),
+            Self::Encrypted(encrypted) => encrypted.decompress_to(value, dest),
         }
     }
     fn decompress(&self, value: &[u8]) -> Result<Vec<u8>, NippyJarError> {
         match self {
             Self::Zstd(zstd) => zstd.decompress(value),
             Self::Lz4(lz4) => lz4.decompress(value),
+            Self::Encrypted(encrypted) => encrypted.decompress(value),
         }
     }
 
@@ -70,6 +76,7 @@ impl Compression for Compressors {
             let result = match self {
                 Self::Zstd(zstd) => zstd.compress_to(src, dest),
                 Self::Lz4(lz4) => lz4.compress_to(src, dest),
+                Self::Encrypted(encrypted) => encrypted.compress_to(src, dest),
             };
 
             match result {
@@ -88,6 +95,7 @@ impl Compression for Compressors {
         match self {
             Self::Zstd(zstd) => zstd.compress(src),
             Self::Lz4(lz4) => lz4.compress(src),
+            Self::Encrypted(encrypted) => encrypted.compress(src),
         }
     }
 
@@ -95,6 +103,7 @@ impl Compression for Compressors {
         match self {
             Self::Zstd(zstd) => zstd.is_ready(),
             Self::Lz4(lz4) => lz4.is_ready(),
+            Self::Encrypted(encrypted) => encrypted.is_ready(),
         }
     }
 
@@ -106,6 +115,7 @@ impl Compression for Compressors {
         match self {
             Self::Zstd(zstd) => zstd.prepare_compression(columns),
             Self::Lz4(lz4) => lz4.prepare_compression(columns),
+            Self::Encrypted(encrypted) => encrypted.prepare_compression(columns),
         }
     }
 }
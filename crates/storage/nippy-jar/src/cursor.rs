@@ -179,6 +179,15 @@ impl<'a, H: NippyJarHeader> NippyJarCursor<'a, H> {
                         &mut decompressor,
                     )?;
                 }
+                Compressors::Encrypted(encrypted) => {
+                    // Encrypted values are bound to their position
+                    encrypted.decrypt_to(
+                        self.reader.data(column_offset_range),
+                        self.row,
+                        column,
+                        &mut self.internal_buffer,
+                    )?;
+                }
                 _ => {
                     // Uses the chosen default decompressor
                     compression.decompress_to(
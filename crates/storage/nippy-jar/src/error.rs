@@ -92,4 +92,20 @@ pub enum NippyJarError {
     /// A specified file is missing.
     #[error("Missing file: {}", .0.display())]
     MissingFile(PathBuf),
+
+    /// The jar is encrypted, but no encryption key is set.
+    #[error("jar is encrypted, but no encryption key is set")]
+    MissingEncryptionKey,
+
+    /// The jar was encrypted with a different key than the one that is set.
+    #[error("jar was encrypted with a different encryption key")]
+    WrongEncryptionKey,
+
+    /// A value could not be encrypted.
+    #[error("failed to encrypt value")]
+    Encryption,
+
+    /// A value could not be decrypted, because it was modified or is corrupted.
+    #[error("failed to decrypt value, it was modified or is corrupted")]
+    Decryption,
 }
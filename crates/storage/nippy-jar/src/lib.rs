@@ -25,9 +25,7 @@ use tracing::*;
 
 /// Compression algorithms supported by `NippyJar`.
 pub mod compression;
-#[cfg(test)]
-use compression::Compression;
-use compression::Compressors;
+use compression::{Compression, Compressors};
 
 /// empty enum for backwards compatibility
 #[derive(Debug, Serialize, Deserialize)]
@@ -56,7 +54,9 @@ const NIPPY_JAR_VERSION: usize = 1;
 /// The file extension used for index files.
 const INDEX_FILE_EXTENSION: &str = "idx";
 /// The file extension used for offsets files.
-const OFFSETS_FILE_EXTENSION: &str = "off";
+pub const OFFSETS_FILE_EXTENSION: &str = "off";
+/// The file extension of the marker file of a recompressed jar that is being swapped in.
+const SWAP_FILE_EXTENSION: &str = "swap";
 /// The file extension used for configuration files.
 pub const CONFIG_FILE_EXTENSION: &str = "conf";
 
@@ -168,6 +168,28 @@ impl<H: NippyJarHeader> NippyJar<H> {
         self
     }
 
+    /// Encrypts the values with [`compression::Encrypted`] after compressing them with the
+    /// current compressor, if any.
+    pub fn with_encryption(mut self, key: &compression::EncryptionKey) -> Self {
+        self.compressor =
+            Some(Compressors::Encrypted(compression::Encrypted::new(key, self.compressor.take())));
+        self
+    }
+
+    /// Sets the key to encrypt and decrypt the values of an encrypted jar with. Does nothing if
+    /// the jar is not encrypted.
+    ///
+    /// Returns [`NippyJarError::WrongEncryptionKey`] if the jar was encrypted with another key.
+    pub fn set_encryption_key(
+        &self,
+        key: Option<&compression::EncryptionKey>,
+    ) -> Result<(), NippyJarError> {
+        match (&self.compressor, key) {
+            (Some(Compressors::Encrypted(encrypted)), Some(key)) => encrypted.set_key(key),
+            _ => Ok(()),
+        }
+    }
+
     /// Gets a reference to the user header.
     pub const fn user_header(&self) -> &H {
         &self.user_header
@@ -248,6 +270,168 @@ impl<H: NippyJarHeader> NippyJar<H> {
         Ok(())
     }
 
+    /// Rewrites all rows of the jar with the given compressor and swaps the rewritten files in
+    /// place of the original ones.
+    ///
+    /// The jar is first rewritten into `staging_dir`, which needs to be on the same filesystem as
+    /// the jar. Once the rewrite is committed, a marker file is created before the files are moved,
+    /// so an interrupted swap can be completed with [`NippyJar::finish_recompressions`].
+    ///
+    /// **The jar must not be written to or read from by anyone else while it's being rewritten.**
+    pub fn recompress(
+        self,
+        compressor: Option<Compressors>,
+        staging_dir: &Path,
+    ) -> Result<Self, NippyJarError> {
+        if compressor.as_ref().is_some_and(|compressor| !compressor.is_ready()) {
+            return Err(NippyJarError::CompressorNotReady)
+        }
+
+        let staging_path = Self::staging_path(self.data_path(), staging_dir)?;
+        if staging_path.with_extension(SWAP_FILE_EXTENSION).exists() {
+            return Err(NippyJarError::Custom(format!(
+                "interrupted recompression of {} needs to be finished first",
+                self.data_path().display()
+            )))
+        }
+        reth_fs_util::create_dir_all(staging_dir)?;
+        // Discards the leftovers of a previously interrupted rewrite that wasn't committed
+        Self::remove_staged(&staging_path)?;
+
+        // The user-defined header is not required to be `Clone`, so we load a second copy of it.
+        let mut staged = Self::load(self.data_path())?;
+        staged.path = staging_path.clone();
+        staged.rows = 0;
+        staged.max_row_size = 0;
+        staged.compressor = compressor;
+
+        debug!(target: "nippy-jar", path=?self.data_path(), ?staging_path, rows=self.rows, "Recompressing jar.");
+
+        let mut writer = NippyJarWriter::new(staged)?;
+        if self.rows > 0 {
+            let mut cursor = NippyJarCursor::new(&self)?;
+            while let Some(row) = cursor.next_row()? {
+                for value in row {
+                    writer.append_column(Some(Ok::<_, Box<dyn StdError + Send + Sync>>(value)))?;
+                }
+            }
+        }
+        writer.commit()?;
+
+        let staged = writer.into_jar();
+        if staged.rows != self.rows {
+            return Err(NippyJarError::Custom(format!(
+                "recompressed jar has {} rows, expected {}",
+                staged.rows, self.rows
+            )))
+        }
+
+        let path = self.path.clone();
+        drop(self);
+
+        reth_fs_util::write(staging_path.with_extension(SWAP_FILE_EXTENSION), [])?;
+        Self::swap_staged(&staging_path, &path)?;
+
+        Self::load(&path)
+    }
+
+    /// Completes or discards a [`NippyJar::recompress`] of the jar at `path` that was interrupted.
+    ///
+    /// If the rewritten files were committed, the swap is completed. Otherwise, the rewritten files
+    /// are removed from `staging_dir`.
+    ///
+    /// Returns `true` if an interrupted swap was completed.
+    pub fn finish_recompression(path: &Path, staging_dir: &Path) -> Result<bool, NippyJarError> {
+        let staging_path = Self::staging_path(path, staging_dir)?;
+
+        if staging_path.with_extension(SWAP_FILE_EXTENSION).exists() {
+            debug!(target: "nippy-jar", ?path, "Completing interrupted recompression.");
+            Self::swap_staged(&staging_path, path)?;
+            return Ok(true)
+        }
+
+        Self::remove_staged(&staging_path)?;
+        Ok(false)
+    }
+
+    /// Completes or discards the interrupted [`NippyJar::recompress`] of every jar in `directory`
+    /// that was rewritten into `staging_dir`, and removes `staging_dir`.
+    ///
+    /// This is meant to be called before the jars of `directory` are opened, so that none of them
+    /// is read while its swap is incomplete.
+    ///
+    /// Returns the paths of the jars whose swap was completed.
+    pub fn finish_recompressions(
+        directory: &Path,
+        staging_dir: &Path,
+    ) -> Result<Vec<PathBuf>, NippyJarError> {
+        let mut completed = Vec::new();
+        if !staging_dir.exists() {
+            return Ok(completed)
+        }
+
+        for entry in reth_fs_util::read_dir(staging_dir)? {
+            let marker = entry?.path();
+            if marker.extension().is_none_or(|extension| extension != SWAP_FILE_EXTENSION) {
+                continue
+            }
+            let Some(file_name) = marker.file_stem() else { continue };
+
+            let path = directory.join(file_name);
+            debug!(target: "nippy-jar", ?path, "Completing interrupted recompression.");
+            Self::swap_staged(&marker.with_extension(""), &path)?;
+            completed.push(path);
+        }
+
+        // Everything left was never committed
+        reth_fs_util::remove_dir_all(staging_dir)?;
+        Ok(completed)
+    }
+
+    /// Returns the path of the rewritten data file of the jar at `path` in `staging_dir`.
+    fn staging_path(path: &Path, staging_dir: &Path) -> Result<PathBuf, NippyJarError> {
+        let file_name = path.file_name().ok_or_else(|| {
+            NippyJarError::Custom(format!("invalid jar path: {}", path.display()))
+        })?;
+        Ok(staging_dir.join(file_name))
+    }
+
+    /// Moves the rewritten files at `staging_path` over the files of the jar at `path` and removes
+    /// the swap marker.
+    ///
+    /// Files that were already moved by an interrupted swap are skipped.
+    fn swap_staged(staging_path: &Path, path: &Path) -> Result<(), NippyJarError> {
+        // The configuration is moved last, since it determines how the data is decompressed
+        for extension in [OFFSETS_FILE_EXTENSION, "", CONFIG_FILE_EXTENSION] {
+            let (from, to) = if extension.is_empty() {
+                (staging_path.to_path_buf(), path.to_path_buf())
+            } else {
+                (staging_path.with_extension(extension), path.with_extension(extension))
+            };
+            if from.exists() {
+                reth_fs_util::rename(from, to)?;
+            }
+        }
+
+        reth_fs_util::remove_file(staging_path.with_extension(SWAP_FILE_EXTENSION))?;
+        Ok(())
+    }
+
+    /// Removes all rewritten files at `staging_path`, if any.
+    fn remove_staged(staging_path: &Path) -> Result<(), NippyJarError> {
+        for path in [
+            staging_path.to_path_buf(),
+            staging_path.with_extension(OFFSETS_FILE_EXTENSION),
+            staging_path.with_extension(CONFIG_FILE_EXTENSION),
+            staging_path.with_extension(SWAP_FILE_EXTENSION),
+        ] {
+            if path.exists() {
+                reth_fs_util::remove_file(path)?;
+            }
+        }
+        Ok(())
+    }
+
     /// Returns a [`DataReader`] of the data and offset file
     pub fn open_data_reader(&self) -> Result<DataReader, NippyJarError> {
         DataReader::new(self.data_path())
@@ -421,7 +605,6 @@ impl DataReader {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use compression::Compression;
     use rand::{rngs::SmallRng, seq::SliceRandom, RngCore, SeedableRng};
     use std::{fs::OpenOptions, io::Read};
 
@@ -589,6 +772,178 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_encryption() {
+        let (col1, col2) = test_data(None);
+        let num_rows = col1.len() as u64;
+        let num_columns = 2;
+        let file_path = tempfile::NamedTempFile::new().unwrap();
+        let key = compression::EncryptionKey::new([7; 32]);
+
+        let nippy = NippyJar::new_without_header(num_columns, file_path.path())
+            .with_lz4()
+            .with_encryption(&key)
+            .freeze(vec![clone_with_result(&col1), clone_with_result(&col2)], num_rows)
+            .unwrap();
+
+        let loaded_nippy = NippyJar::load_without_header(file_path.path()).unwrap();
+        assert_eq!(nippy, loaded_nippy);
+        let Some(Compressors::Encrypted(encrypted)) = loaded_nippy.compressor() else {
+            panic!("Expected encrypted jar")
+        };
+        assert!(matches!(encrypted.compressor(), Some(Compressors::Lz4(_))));
+
+        // the key is required to read the loaded jar
+        let mut cursor = NippyJarCursor::new(&loaded_nippy).unwrap();
+        assert!(matches!(cursor.next_row(), Err(NippyJarError::MissingEncryptionKey)));
+
+        let wrong_key = compression::EncryptionKey::new([8; 32]);
+        assert!(matches!(
+            loaded_nippy.set_encryption_key(Some(&wrong_key)),
+            Err(NippyJarError::WrongEncryptionKey)
+        ));
+
+        loaded_nippy.set_encryption_key(Some(&key)).unwrap();
+        let mut cursor = NippyJarCursor::new(&loaded_nippy).unwrap();
+        let mut row_index = 0usize;
+        while let Some(row) = cursor.next_row().unwrap() {
+            assert_eq!((row[0], row[1]), (col1[row_index].as_slice(), col2[row_index].as_slice()));
+            row_index += 1;
+        }
+        assert_eq!(row_index, col1.len());
+
+        // modified values fail the integrity check
+        let mut data = std::fs::read(file_path.path()).unwrap();
+        data[20] ^= 1;
+        std::fs::write(file_path.path(), data).unwrap();
+        let loaded_nippy = NippyJar::load_without_header(file_path.path()).unwrap();
+        loaded_nippy.set_encryption_key(Some(&key)).unwrap();
+        let mut cursor = NippyJarCursor::new(&loaded_nippy).unwrap();
+        assert!(matches!(cursor.next_row(), Err(NippyJarError::Decryption)));
+    }
+
+    #[test]
+    fn test_encryption_binds_position() {
+        let key = compression::EncryptionKey::new([7; 32]);
+        let encrypted = compression::Encrypted::new(&key, None);
+
+        let mut value = Vec::new();
+        encrypted.encrypt_to(b"value", 1, 0, &mut value).unwrap();
+
+        let mut decrypted = Vec::new();
+        encrypted.decrypt_to(&value, 1, 0, &mut decrypted).unwrap();
+        assert_eq!(decrypted, b"value");
+
+        // values can't be moved to another row, column or jar
+        for (row, column) in [(2, 0), (1, 1)] {
+            assert!(matches!(
+                encrypted.decrypt_to(&value, row, column, &mut Vec::new()),
+                Err(NippyJarError::Decryption)
+            ));
+        }
+        let other = compression::Encrypted::new(&key, None);
+        assert!(matches!(
+            other.decrypt_to(&value, 1, 0, &mut Vec::new()),
+            Err(NippyJarError::Decryption)
+        ));
+    }
+
+    #[test]
+    fn test_recompress() {
+        let (col1, col2) = test_data(None);
+        let num_rows = col1.len() as u64;
+        let num_columns = 2;
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file_path = temp_dir.path().join("jar");
+        let staging_dir = temp_dir.path().join("staging");
+
+        NippyJar::new_without_header(num_columns, &file_path)
+            .with_lz4()
+            .freeze(vec![clone_with_result(&col1), clone_with_result(&col2)], num_rows)
+            .unwrap();
+
+        let nippy = NippyJar::load_without_header(&file_path).unwrap();
+        let nippy = nippy
+            .recompress(
+                Some(Compressors::Zstd(Zstd::new(false, 0, num_columns).with_level(19))),
+                &staging_dir,
+            )
+            .unwrap();
+        assert!(matches!(nippy.compressor(), Some(Compressors::Zstd(_))));
+        assert_eq!(nippy.rows(), num_rows as usize);
+
+        // the staging directory only contains the rewritten files while the rewrite is in progress
+        assert_eq!(std::fs::read_dir(&staging_dir).unwrap().count(), 0);
+
+        let loaded_nippy = NippyJar::load_without_header(&file_path).unwrap();
+        assert_eq!(nippy, loaded_nippy);
+
+        let mut cursor = NippyJarCursor::new(&loaded_nippy).unwrap();
+        let mut row_index = 0usize;
+        while let Some(row) = cursor.next_row().unwrap() {
+            assert_eq!((row[0], row[1]), (col1[row_index].as_slice(), col2[row_index].as_slice()));
+            row_index += 1;
+        }
+        assert_eq!(row_index, col1.len());
+
+        // an uncommitted rewrite is discarded
+        std::fs::write(staging_dir.join("jar"), [1, 2, 3]).unwrap();
+        assert!(!NippyJar::<()>::finish_recompression(&file_path, &staging_dir).unwrap());
+        assert!(!staging_dir.join("jar").exists());
+    }
+
+    #[test]
+    fn test_finish_interrupted_recompressions() {
+        let (col1, col2) = test_data(None);
+        let num_rows = col1.len() as u64;
+        let num_columns = 2;
+        let temp_dir = tempfile::tempdir().unwrap();
+        let staging_dir = temp_dir.path().join("staging");
+        std::fs::create_dir(&staging_dir).unwrap();
+
+        // both jars were rewritten with zstd, but only the rewrite of the first one was committed
+        // and its swap was interrupted after the offsets were moved
+        let (committed, uncommitted) = (temp_dir.path().join("a"), temp_dir.path().join("b"));
+        for path in [&committed, &uncommitted] {
+            NippyJar::new_without_header(num_columns, path)
+                .with_lz4()
+                .freeze(vec![clone_with_result(&col1), clone_with_result(&col2)], num_rows)
+                .unwrap();
+            NippyJar::new_without_header(num_columns, &staging_dir.join(path.file_name().unwrap()))
+                .with_zstd(false, 5000)
+                .freeze(vec![clone_with_result(&col1), clone_with_result(&col2)], num_rows)
+                .unwrap();
+        }
+        std::fs::write(staging_dir.join("a.swap"), []).unwrap();
+        std::fs::rename(
+            staging_dir.join("a").with_extension(OFFSETS_FILE_EXTENSION),
+            committed.with_extension(OFFSETS_FILE_EXTENSION),
+        )
+        .unwrap();
+
+        assert_eq!(
+            NippyJar::<()>::finish_recompressions(temp_dir.path(), &staging_dir).unwrap(),
+            vec![committed.clone()]
+        );
+        assert!(!staging_dir.exists());
+
+        for (path, zstd) in [(&committed, true), (&uncommitted, false)] {
+            let nippy = NippyJar::load_without_header(path).unwrap();
+            assert_eq!(matches!(nippy.compressor(), Some(Compressors::Zstd(_))), zstd);
+
+            let mut cursor = NippyJarCursor::new(&nippy).unwrap();
+            let mut row_index = 0usize;
+            while let Some(row) = cursor.next_row().unwrap() {
+                assert_eq!(
+                    (row[0], row[1]),
+                    (col1[row_index].as_slice(), col2[row_index].as_slice())
+                );
+                row_index += 1;
+            }
+            assert_eq!(row_index, col1.len());
+        }
+    }
+
     #[test]
     fn test_zstd_no_dictionaries() {
         let (col1, col2) = test_data(None);
@@ -1,6 +1,6 @@
 use crate::{
-    compression::Compression, ColumnResult, NippyJar, NippyJarChecker, NippyJarError,
-    NippyJarHeader,
+    compression::{Compression, Compressors},
+    ColumnResult, NippyJar, NippyJarChecker, NippyJarError, NippyJarHeader,
 };
 use std::{
     fs::{File, OpenOptions},
@@ -229,7 +229,16 @@ impl<H: NippyJarHeader> NippyJarWriter<H> {
         self.uncompressed_row_size += value.len();
         let len = if let Some(compression) = &self.jar.compressor {
             let before = self.tmp_buf.len();
-            let len = compression.compress_to(value, &mut self.tmp_buf)?;
+            let len = match compression {
+                // encrypted values are bound to their position
+                Compressors::Encrypted(encrypted) => encrypted.encrypt_to(
+                    value,
+                    self.jar.rows as u64,
+                    self.column,
+                    &mut self.tmp_buf,
+                )?,
+                _ => compression.compress_to(value, &mut self.tmp_buf)?,
+            };
             self.data_file.write_all(&self.tmp_buf[before..before + len])?;
             len
         } else {
@@ -0,0 +1,135 @@
+use alloy_primitives::BlockNumber;
+use reth_storage_api::{BlobSidecarWriter, StoredBlobSidecars};
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// Stores the blob sidecars of recent canonical blocks in a directory, one file per block.
+///
+/// The sidecars are kept outside of the database, so writing them doesn't compete with the engine
+/// for the database write transaction and also works if the database is opened read-only.
+#[derive(Debug, Clone)]
+pub struct BlobSidecarStore {
+    dir: Arc<PathBuf>,
+}
+
+impl BlobSidecarStore {
+    /// Opens the store in the given directory, creating it if it doesn't exist.
+    pub fn open(dir: impl Into<PathBuf>) -> ProviderResult<Self> {
+        let dir = dir.into();
+        reth_fs_util::create_dir_all(&dir).map_err(ProviderError::other)?;
+        Ok(Self { dir: Arc::new(dir) })
+    }
+
+    /// Returns the directory of the store.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Returns the stored blob sidecars of the given block.
+    pub fn get(&self, block: BlockNumber) -> ProviderResult<Option<StoredBlobSidecars>> {
+        let path = self.path(block);
+        if !path.exists() {
+            return Ok(None)
+        }
+        let data = reth_fs_util::read(&path).map_err(ProviderError::other)?;
+        Ok(Some(StoredBlobSidecars::decode(&data)?))
+    }
+
+    fn path(&self, block: BlockNumber) -> PathBuf {
+        self.dir.join(block.to_string())
+    }
+}
+
+impl BlobSidecarWriter for BlobSidecarStore {
+    fn insert_blob_sidecars(
+        &self,
+        block: BlockNumber,
+        sidecars: &StoredBlobSidecars,
+    ) -> ProviderResult<()> {
+        reth_fs_util::atomic_write_file(&self.path(block), |file| {
+            file.write_all(&sidecars.encode())
+        })
+        .map_err(ProviderError::other)
+    }
+
+    fn remove_blob_sidecars(&self, block: BlockNumber) -> ProviderResult<()> {
+        let path = self.path(block);
+        if path.exists() {
+            reth_fs_util::remove_file(&path).map_err(ProviderError::other)?;
+        }
+        Ok(())
+    }
+
+    fn prune_blob_sidecars(&self, below: BlockNumber) -> ProviderResult<()> {
+        for entry in reth_fs_util::read_dir(self.dir.as_path()).map_err(ProviderError::other)? {
+            let path = entry.map_err(ProviderError::other)?.path();
+            let Some(block) = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.parse::<BlockNumber>().ok())
+            else {
+                continue
+            };
+            if block < below {
+                reth_fs_util::remove_file(&path).map_err(ProviderError::other)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_eips::{
+        eip4844::{Blob, BlobTransactionSidecar, Bytes48},
+        eip7594::BlobTransactionSidecarVariant,
+    };
+    use alloy_primitives::B256;
+
+    #[test]
+    fn blob_sidecars() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = BlobSidecarStore::open(dir.path()).unwrap();
+        let sidecar = |commitment: u8| {
+            BlobTransactionSidecarVariant::Eip4844(BlobTransactionSidecar {
+                blobs: vec![Blob::repeat_byte(commitment)],
+                commitments: vec![Bytes48::repeat_byte(commitment)],
+                proofs: vec![Bytes48::ZERO],
+            })
+        };
+        let first = StoredBlobSidecars {
+            block_hash: B256::with_last_byte(1),
+            sidecars: vec![
+                (B256::with_last_byte(1), sidecar(1)),
+                (B256::with_last_byte(2), sidecar(2)),
+            ],
+            missing: vec![B256::with_last_byte(3)],
+        };
+        let second = StoredBlobSidecars {
+            block_hash: B256::with_last_byte(2),
+            sidecars: vec![(B256::with_last_byte(4), sidecar(4))],
+            missing: Vec::new(),
+        };
+
+        store.insert_blob_sidecars(1, &first).unwrap();
+        store.insert_blob_sidecars(2, &second).unwrap();
+        store.insert_blob_sidecars(3, &StoredBlobSidecars::default()).unwrap();
+        assert_eq!(store.get(1).unwrap(), Some(first));
+        assert_eq!(store.get(2).unwrap(), Some(second.clone()));
+        assert_eq!(store.get(3).unwrap(), Some(StoredBlobSidecars::default()));
+        assert_eq!(store.get(4).unwrap(), None);
+
+        // reorged out blocks are removed
+        store.remove_blob_sidecars(3).unwrap();
+        assert_eq!(store.get(3).unwrap(), None);
+
+        store.prune_blob_sidecars(2).unwrap();
+        assert_eq!(store.get(1).unwrap(), None);
+        assert_eq!(store.get(2).unwrap(), Some(second));
+    }
+}
@@ -1,6 +1,6 @@
 #![allow(unused)]
 use crate::{
-    providers::{ConsistentProvider, ProviderNodeTypes, StaticFileProvider},
+    providers::{BlobSidecarStore, ConsistentProvider, ProviderNodeTypes, StaticFileProvider},
     AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
     BlockSource, CanonChainTracker, CanonStateNotifications, CanonStateSubscriptions,
     ChainSpecProvider, ChainStateBlockReader, ChangeSetReader, DatabaseProvider,
@@ -18,7 +18,7 @@ use alloy_primitives::{Address, BlockHash, BlockNumber, Sealable, TxHash, TxNumb
 use alloy_rpc_types_engine::ForkchoiceState;
 use reth_chain_state::{
     BlockState, CanonicalInMemoryState, ForkChoiceNotifications, ForkChoiceSubscriptions,
-    MemoryOverlayStateProvider,
+    HotStateCache, MemoryOverlayStateProvider,
 };
 use reth_chainspec::{ChainInfo, EthereumHardforks};
 use reth_db_api::{
@@ -36,8 +36,9 @@ use reth_primitives_traits::{
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_storage_api::{
-    BlockBodyIndicesProvider, DBProvider, NodePrimitivesProvider, StateCommitmentProvider,
-    StorageChangeSetReader,
+    BlockBodyIndicesProvider, DBProvider, GasUsageReader, IndexedLogBlocks, NodePrimitivesProvider,
+    StateCommitmentProvider, StateSize, StateSizeReader, StorageChangeSetReader, StorageSlotChange,
+    StoredBlobSidecars, TopGasConsumers,
 };
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::HashedPostState;
@@ -62,6 +63,10 @@ pub struct BlockchainProvider<N: NodeTypesWithDB> {
     /// Tracks the chain info wrt forkchoice updates and in memory canonical
     /// state.
     pub(crate) canonical_in_memory_state: CanonicalInMemoryState<N::Primitives>,
+    /// Cache of hot state at the canonical head, shared by all state providers of the head.
+    pub(crate) hot_state: Option<HotStateCache<N::Primitives>>,
+    /// Store of the blob sidecars of recent canonical blocks, if enabled.
+    pub(crate) blob_sidecars: Option<BlobSidecarStore>,
 }
 
 impl<N: NodeTypesWithDB> Clone for BlockchainProvider<N> {
@@ -69,6 +74,8 @@ impl<N: NodeTypesWithDB> Clone for BlockchainProvider<N> {
         Self {
             database: self.database.clone(),
             canonical_in_memory_state: self.canonical_in_memory_state.clone(),
+            hot_state: self.hot_state.clone(),
+            blob_sidecars: self.blob_sidecars.clone(),
         }
     }
 }
@@ -120,9 +127,37 @@ impl<N: ProviderNodeTypes> BlockchainProvider<N> {
                 finalized_header,
                 safe_header,
             ),
+            hot_state: None,
+            blob_sidecars: None,
         })
     }
 
+    /// Caches up to `max_entries` accounts, storage slots and bytecodes read from the state of
+    /// the canonical head, shared by all clones of this provider.
+    ///
+    /// Payload building and RPC calls against the head block then only read hot state from the
+    /// database once per block.
+    pub fn with_hot_state_cache(mut self, max_entries: usize) -> Self {
+        let head = self.canonical_in_memory_state.get_canonical_head().hash();
+        let notifications = self.canonical_in_memory_state.subscribe_canon_state();
+        self.hot_state = Some(HotStateCache::new(head, notifications, max_entries));
+        self
+    }
+
+    /// Serves the blob sidecars of recent canonical blocks from the given store.
+    pub fn with_blob_sidecar_store(mut self, store: BlobSidecarStore) -> Self {
+        self.blob_sidecars = Some(store);
+        self
+    }
+
+    /// Wraps the state provider of the given block with the hot state cache, if configured.
+    fn with_hot_state(&self, block_hash: B256, state: StateProviderBox) -> StateProviderBox {
+        match &self.hot_state {
+            Some(cache) => cache.wrap(block_hash, state),
+            None => state,
+        }
+    }
+
     /// Gets a clone of `canonical_in_memory_state`.
     pub fn canonical_in_memory_state(&self) -> CanonicalInMemoryState<N::Primitives> {
         self.canonical_in_memory_state.clone()
@@ -299,6 +334,10 @@ impl<N: ProviderNodeTypes> BlockReader for BlockchainProvider<N> {
         self.consistent_provider()?.block(id)
     }
 
+    fn ommers(&self, id: BlockHashOrNumber) -> ProviderResult<Option<Vec<Self::Header>>> {
+        self.consistent_provider()?.ommers(id)
+    }
+
     fn pending_block(&self) -> ProviderResult<Option<RecoveredBlock<Self::Block>>> {
         Ok(self.canonical_in_memory_state.pending_recovered_block())
     }
@@ -331,6 +370,13 @@ impl<N: ProviderNodeTypes> BlockReader for BlockchainProvider<N> {
         self.consistent_provider()?.sealed_block_with_senders(id, transaction_kind)
     }
 
+    fn block_blob_sidecars(
+        &self,
+        number: BlockNumber,
+    ) -> ProviderResult<Option<StoredBlobSidecars>> {
+        self.blob_sidecars.as_ref().map_or(Ok(None), |store| store.get(number))
+    }
+
     fn block_range(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<Vec<Self::Block>> {
         self.consistent_provider()?.block_range(range)
     }
@@ -447,6 +493,15 @@ impl<N: ProviderNodeTypes> ReceiptProvider for BlockchainProvider<N> {
     ) -> ProviderResult<Vec<Vec<Self::Receipt>>> {
         self.consistent_provider()?.receipts_by_block_range(block_range)
     }
+
+    fn indexed_log_blocks(
+        &self,
+        block_range: RangeInclusive<BlockNumber>,
+        addresses: &[Address],
+        topics: &[Vec<B256>],
+    ) -> ProviderResult<Option<IndexedLogBlocks>> {
+        self.consistent_provider()?.indexed_log_blocks(block_range, addresses, topics)
+    }
 }
 
 impl<N: ProviderNodeTypes> ReceiptProviderIdExt for BlockchainProvider<N> {
@@ -513,7 +568,7 @@ impl<N: ProviderNodeTypes> StateProviderFactory for BlockchainProvider<N> {
         // use latest state provider if the head state exists
         if let Some(state) = self.canonical_in_memory_state.head_state() {
             trace!(target: "providers::blockchain", "Using head state for latest state provider");
-            Ok(self.block_state_provider(&state)?.boxed())
+            Ok(self.with_hot_state(state.hash(), self.block_state_provider(&state)?.boxed()))
         } else {
             trace!(target: "providers::blockchain", "Using database state for latest state provider");
             self.database.latest()
@@ -542,7 +597,7 @@ impl<N: ProviderNodeTypes> StateProviderFactory for BlockchainProvider<N> {
         trace!(target: "providers::blockchain", ?hash, "Getting state by block hash");
         if let Ok(state) = self.history_by_block_hash(hash) {
             // This could be tracked by a historical block
-            Ok(state)
+            Ok(self.with_hot_state(hash, state))
         } else if let Ok(Some(pending)) = self.pending_state_by_hash(hash) {
             // .. or this could be the pending state
             Ok(pending)
@@ -703,6 +758,14 @@ impl<N: ProviderNodeTypes> StorageChangeSetReader for BlockchainProvider<N> {
     ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
         self.consistent_provider()?.storage_changeset(block_number)
     }
+
+    fn storage_changes_in_range(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<StorageSlotChange>> {
+        self.consistent_provider()?.storage_changes_in_range(address, range)
+    }
 }
 
 impl<N: ProviderNodeTypes> ChangeSetReader for BlockchainProvider<N> {
@@ -712,6 +775,15 @@ impl<N: ProviderNodeTypes> ChangeSetReader for BlockchainProvider<N> {
     ) -> ProviderResult<Vec<AccountBeforeTx>> {
         self.consistent_provider()?.account_block_changeset(block_number)
     }
+
+    fn account_change_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+        limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.consistent_provider()?.account_change_blocks(address, range, limit)
+    }
 }
 
 impl<N: ProviderNodeTypes> AccountReader for BlockchainProvider<N> {
@@ -721,6 +793,22 @@ impl<N: ProviderNodeTypes> AccountReader for BlockchainProvider<N> {
     }
 }
 
+impl<N: ProviderNodeTypes> StateSizeReader for BlockchainProvider<N> {
+    fn state_size(&self) -> ProviderResult<Option<StateSize>> {
+        self.database.state_size()
+    }
+}
+
+impl<N: ProviderNodeTypes> GasUsageReader for BlockchainProvider<N> {
+    fn top_gas_consumers(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        limit: usize,
+    ) -> ProviderResult<Option<TopGasConsumers>> {
+        self.database.top_gas_consumers(range, limit)
+    }
+}
+
 impl<N: ProviderNodeTypes> StateReader for BlockchainProvider<N> {
     type Receipt = ReceiptTy<N>;
 
@@ -753,7 +841,7 @@ mod tests {
         BlockWriter, CanonChainTracker, ProviderFactory, StaticFileProviderFactory,
         StaticFileWriter,
     };
-    use alloy_eips::{BlockHashOrNumber, BlockNumHash, BlockNumberOrTag};
+    use alloy_eips::{BlockHashOrNumber, BlockId, BlockNumHash, BlockNumberOrTag};
     use alloy_primitives::{BlockNumber, TxNumber, B256};
     use itertools::Itertools;
     use rand::Rng;
@@ -781,8 +869,8 @@ mod tests {
     use reth_storage_api::{
         BlockBodyIndicesProvider, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader,
         BlockReaderIdExt, BlockSource, ChangeSetReader, DatabaseProviderFactory, HeaderProvider,
-        ReceiptProvider, ReceiptProviderIdExt, StateProviderFactory, TransactionVariant,
-        TransactionsProvider,
+        ReceiptProvider, ReceiptProviderIdExt, RelativeBlockNumberOrTag, StateProviderFactory,
+        TransactionVariant, TransactionsProvider,
     };
     use reth_testing_utils::generators::{
         self, random_block, random_block_range, random_changeset_range, random_eoa_accounts,
@@ -1985,6 +2073,39 @@ mod tests {
             Some(BlockNumHash { number: finalized_block.number, hash: finalized_block.hash() })
         );
 
+        // Verify that all block ids resolve consistently
+        assert_eq!(
+            provider.resolve_block_id(BlockId::safe())?,
+            Some(BlockNumHash { number: safe_block.number, hash: safe_block.hash() })
+        );
+        assert_eq!(
+            provider.resolve_block_id(BlockId::hash_canonical(finalized_block.hash()))?,
+            Some(BlockNumHash { number: finalized_block.number, hash: finalized_block.hash() })
+        );
+        assert_eq!(provider.resolve_block_id(BlockId::hash_canonical(B256::random()))?, None);
+        assert_eq!(provider.block_hash_for_id(BlockId::hash_canonical(B256::random()))?, None);
+        assert_eq!(provider.resolve_block_id(BlockId::number(pending_block.number + 1))?, None);
+        assert_eq!(
+            provider.resolve_block_id(BlockId::number(safe_block.number))?,
+            Some(BlockNumHash { number: safe_block.number, hash: safe_block.hash() })
+        );
+
+        // Verify relative block numbers
+        assert_eq!(
+            provider.convert_relative_block_number(RelativeBlockNumberOrTag::new(
+                BlockNumberOrTag::Finalized,
+                1
+            ))?,
+            Some(finalized_block.number - 1)
+        );
+        assert_eq!(
+            provider.convert_relative_block_number(RelativeBlockNumberOrTag::new(
+                BlockNumberOrTag::Earliest,
+                1
+            ))?,
+            None
+        );
+
         Ok(())
     }
 
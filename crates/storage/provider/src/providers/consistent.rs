@@ -24,8 +24,10 @@ use reth_primitives_traits::{Account, BlockBody, RecoveredBlock, SealedHeader, S
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_storage_api::{
-    BlockBodyIndicesProvider, DatabaseProviderFactory, NodePrimitivesProvider, StateProvider,
-    StorageChangeSetReader, TryIntoHistoricalStateProvider,
+    record_read, BlockBodyIndicesProvider, DatabaseProviderFactory, GasUsageReader,
+    IndexedLogBlocks, NodePrimitivesProvider, ReadSource, StateProvider, StateSize,
+    StateSizeReader, StorageChangeSetReader, StorageSlotChange, TopGasConsumers,
+    TryIntoHistoricalStateProvider,
 };
 use reth_storage_errors::provider::ProviderResult;
 use revm_database::states::PlainStorageRevert;
@@ -378,6 +380,7 @@ impl<N: ProviderNodeTypes> ConsistentProvider<N> {
         }
 
         if let Some((in_memory_chain, in_memory_range)) = in_memory {
+            record_read(ReadSource::InMemory);
             for (num, block) in in_memory_range.zip(in_memory_chain.into_iter().rev()) {
                 debug_assert!(num == block.number());
                 if let Some(item) = map_block_state_item(block, &mut predicate) {
@@ -486,6 +489,8 @@ impl<N: ProviderNodeTypes> ConsistentProvider<N> {
             // This should only be more than 0 once, in case of a partial range inside a block.
             let skip = (tx_range.start() - in_memory_tx_num) as usize;
 
+            record_read(ReadSource::InMemory);
+
             items.extend(fetch_from_block_state(
                 skip..=skip + (remaining.min(block_tx_count - skip) - 1),
                 block_state,
@@ -551,11 +556,13 @@ impl<N: ProviderNodeTypes> ConsistentProvider<N> {
                 match id {
                     HashOrNumber::Hash(tx_hash) => {
                         if tx_hash == block.body().transactions()[tx_index].trie_hash() {
+                            record_read(ReadSource::InMemory);
                             return fetch_from_block_state(tx_index, in_memory_tx_num, block_state)
                         }
                     }
                     HashOrNumber::Number(id) => {
                         if id == in_memory_tx_num {
+                            record_read(ReadSource::InMemory);
                             return fetch_from_block_state(tx_index, in_memory_tx_num, block_state)
                         }
                     }
@@ -585,6 +592,7 @@ impl<N: ProviderNodeTypes> ConsistentProvider<N> {
         M: Fn(&BlockState<N::Primitives>) -> ProviderResult<R>,
     {
         if let Some(Some(block_state)) = self.head_block.as_ref().map(|b| b.block_on_chain(id)) {
+            record_read(ReadSource::InMemory);
             return fetch_from_block_state(block_state)
         }
         fetch_from_db(&self.storage_provider)
@@ -845,6 +853,17 @@ impl<N: ProviderNodeTypes> BlockReader for ConsistentProvider<N> {
         )
     }
 
+    fn ommers(&self, id: BlockHashOrNumber) -> ProviderResult<Option<Vec<Self::Header>>> {
+        self.get_in_memory_or_storage_by_block(
+            id,
+            |db_provider| db_provider.ommers(id),
+            |block_state| {
+                let body = block_state.block_ref().recovered_block().body();
+                Ok(Some(body.ommers().map(|ommers| ommers.to_vec()).unwrap_or_default()))
+            },
+        )
+    }
+
     fn pending_block(&self) -> ProviderResult<Option<RecoveredBlock<Self::Block>>> {
         Ok(self.canonical_in_memory_state.pending_recovered_block())
     }
@@ -1126,6 +1145,15 @@ impl<N: ProviderNodeTypes> ReceiptProvider for ConsistentProvider<N> {
     ) -> ProviderResult<Vec<Vec<Self::Receipt>>> {
         self.storage_provider.receipts_by_block_range(block_range)
     }
+
+    fn indexed_log_blocks(
+        &self,
+        block_range: RangeInclusive<BlockNumber>,
+        addresses: &[Address],
+        topics: &[Vec<B256>],
+    ) -> ProviderResult<Option<IndexedLogBlocks>> {
+        self.storage_provider.indexed_log_blocks(block_range, addresses, topics)
+    }
 }
 
 impl<N: ProviderNodeTypes> ReceiptProviderIdExt for ConsistentProvider<N> {
@@ -1232,6 +1260,22 @@ impl<N: ProviderNodeTypes> PruneCheckpointReader for ConsistentProvider<N> {
     }
 }
 
+impl<N: ProviderNodeTypes> StateSizeReader for ConsistentProvider<N> {
+    fn state_size(&self) -> ProviderResult<Option<StateSize>> {
+        self.storage_provider.state_size()
+    }
+}
+
+impl<N: ProviderNodeTypes> GasUsageReader for ConsistentProvider<N> {
+    fn top_gas_consumers(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        limit: usize,
+    ) -> ProviderResult<Option<TopGasConsumers>> {
+        self.storage_provider.top_gas_consumers(range, limit)
+    }
+}
+
 impl<N: ProviderNodeTypes> ChainSpecProvider for ConsistentProvider<N> {
     type ChainSpec = N::ChainSpec;
 
@@ -1368,6 +1412,46 @@ impl<N: ProviderNodeTypes> StorageChangeSetReader for ConsistentProvider<N> {
             self.storage_provider.storage_changeset(block_number)
         }
     }
+
+    fn storage_changes_in_range(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<StorageSlotChange>> {
+        let start = *range.start();
+        let end = (*range.end()).min(self.best_block_number()?);
+        let last_persisted = self.storage_provider.last_block_number()?;
+        if start > end {
+            return Ok(Vec::new())
+        }
+        if end <= last_persisted {
+            return self.storage_provider.storage_changes_in_range(address, start..=end)
+        }
+
+        // the in-memory blocks build on top of the database state, so the values after the
+        // persisted changes are correct
+        let mut changes = if start <= last_persisted {
+            self.storage_provider.storage_changes_in_range(address, start..=last_persisted)?
+        } else {
+            Vec::new()
+        };
+
+        let mut changesets = Vec::new();
+        for block_number in start.max(last_persisted + 1)..=end {
+            changesets.extend(
+                self.storage_changeset(block_number)?
+                    .into_iter()
+                    .filter(|(index, _)| index.address() == address)
+                    .map(|(_, entry)| (block_number, entry)),
+            );
+        }
+
+        let state = self.state_by_block_number_ref(end)?;
+        changes.extend(StorageSlotChange::from_changesets(changesets, |key| {
+            Ok(state.storage(address, key)?.unwrap_or_default())
+        })?);
+        Ok(changes)
+    }
 }
 
 impl<N: ProviderNodeTypes> ChangeSetReader for ConsistentProvider<N> {
@@ -1414,6 +1498,44 @@ impl<N: ProviderNodeTypes> ChangeSetReader for ConsistentProvider<N> {
             self.storage_provider.account_block_changeset(block_number)
         }
     }
+
+    fn account_change_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+        limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        let start = *range.start();
+        let end = (*range.end()).min(self.best_block_number()?);
+        let last_persisted = self.storage_provider.last_block_number()?;
+        if start > end {
+            return Ok(Vec::new())
+        }
+        if end <= last_persisted {
+            return self.storage_provider.account_change_blocks(address, start..=end, limit)
+        }
+
+        let mut blocks = if start <= last_persisted {
+            self.storage_provider.account_change_blocks(address, start..=last_persisted, limit)?
+        } else {
+            Vec::new()
+        };
+
+        // the in-memory blocks are not indexed, so their changesets are scanned
+        for block_number in start.max(last_persisted + 1)..=end {
+            if blocks.len() >= limit {
+                break
+            }
+            if self
+                .account_block_changeset(block_number)?
+                .iter()
+                .any(|change| change.address == address)
+            {
+                blocks.push(block_number);
+            }
+        }
+        Ok(blocks)
+    }
 }
 
 impl<N: ProviderNodeTypes> AccountReader for ConsistentProvider<N> {
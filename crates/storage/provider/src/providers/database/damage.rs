@@ -0,0 +1,24 @@
+use alloy_primitives::BlockNumber;
+
+/// Storage damage that was found by the startup consistency check but left in place, because the
+/// node was started in safe mode.
+///
+/// A [`ProviderFactory`](super::ProviderFactory) that carries a [`StorageDamage`] caps the chain at
+/// [`StorageDamage::consistent_tip`] and answers requests for blocks above it with
+/// [`ProviderError::StorageDegraded`](reth_storage_errors::provider::ProviderError::StorageDegraded).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StorageDamage {
+    /// The highest block that is consistent between the database and static files.
+    ///
+    /// This is the target a repair unwind would use.
+    pub consistent_tip: BlockNumber,
+    /// The highest block that was stored before the damage was detected.
+    pub highest_block: BlockNumber,
+}
+
+impl StorageDamage {
+    /// Returns `true` if the given block is above the consistent tip.
+    pub const fn is_degraded(&self, block: BlockNumber) -> bool {
+        block > self.consistent_tip
+    }
+}
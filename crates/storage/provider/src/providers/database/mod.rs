@@ -1,11 +1,13 @@
 use crate::{
-    providers::{state::latest::LatestStateProvider, StaticFileProvider},
+    providers::{
+        state::latest::LatestStateProvider, BytecodeCache, QueryStats, StaticFileProvider,
+    },
     to_range,
     traits::{BlockSource, ReceiptProvider},
-    BlockHashReader, BlockNumReader, BlockReader, ChainSpecProvider, DatabaseProviderFactory,
-    HashedPostStateProvider, HeaderProvider, HeaderSyncGapProvider, ProviderError,
-    PruneCheckpointReader, StageCheckpointReader, StateProviderBox, StaticFileProviderFactory,
-    TransactionVariant, TransactionsProvider,
+    BlockHashReader, BlockNumReader, BlockReader, ChainSpecProvider, DBProvider,
+    DatabaseProviderFactory, HashedPostStateProvider, HeaderProvider, HeaderSyncGapProvider,
+    ProviderError, PruneCheckpointReader, StageCheckpointReader, StateProviderBox,
+    StaticFileProviderFactory, TransactionVariant, TransactionsProvider,
 };
 use alloy_consensus::transaction::TransactionMeta;
 use alloy_eips::BlockHashOrNumber;
@@ -23,7 +25,8 @@ use reth_prune_types::{PruneCheckpoint, PruneModes, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_static_file_types::StaticFileSegment;
 use reth_storage_api::{
-    BlockBodyIndicesProvider, NodePrimitivesProvider, StateCommitmentProvider,
+    BlockBodyIndicesProvider, GasUsageReader, IndexedLogBlocks, NodePrimitivesProvider,
+    StateCommitmentProvider, StateSize, StateSizeReader, TopGasConsumers,
     TryIntoHistoricalStateProvider,
 };
 use reth_storage_errors::provider::ProviderResult;
@@ -48,9 +51,14 @@ pub use builder::{ProviderFactoryBuilder, ReadOnlyConfig};
 
 mod metrics;
 
+mod damage;
+pub use damage::StorageDamage;
+
 mod chain;
 pub use chain::*;
 
+mod state_size;
+
 /// A common provider that fetches data from a database or static file.
 ///
 /// This provider implements most provider or provider factory traits.
@@ -65,6 +73,14 @@ pub struct ProviderFactory<N: NodeTypesWithDB> {
     prune_modes: PruneModes,
     /// The node storage handler.
     storage: Arc<N::Storage>,
+    /// Storage damage that is tolerated because the node runs in safe mode.
+    storage_damage: Option<StorageDamage>,
+    /// Maximum number of changeset entries replayed for historical proofs and state roots.
+    proof_effort_budget: Option<usize>,
+    /// Cache of decompressed bytecodes shared by all state providers.
+    bytecode_cache: Option<BytecodeCache>,
+    /// Statistics that historical state queries are recorded in.
+    query_stats: Option<QueryStats>,
 }
 
 impl<N: NodeTypes> ProviderFactory<NodeTypesWithDBAdapter<N, Arc<DatabaseEnv>>> {
@@ -87,6 +103,10 @@ impl<N: NodeTypesWithDB> ProviderFactory<N> {
             static_file_provider,
             prune_modes: PruneModes::none(),
             storage: Default::default(),
+            storage_damage: None,
+            proof_effort_budget: None,
+            bytecode_cache: None,
+            query_stats: None,
         }
     }
 
@@ -102,6 +122,53 @@ impl<N: NodeTypesWithDB> ProviderFactory<N> {
         self
     }
 
+    /// Marks the storage as damaged above [`StorageDamage::consistent_tip`].
+    ///
+    /// All providers created by this factory treat the consistent tip as the best block and return
+    /// [`ProviderError::StorageDegraded`] for blocks above it.
+    pub fn with_storage_damage(mut self, damage: StorageDamage) -> Self {
+        self.storage_damage = Some(damage);
+        self
+    }
+
+    /// Returns the storage damage this factory was configured with, if any.
+    pub const fn storage_damage(&self) -> Option<&StorageDamage> {
+        self.storage_damage.as_ref()
+    }
+
+    /// Bounds the effort of reconstructing the trie at historical blocks.
+    ///
+    /// Historical state providers created by this factory replay at most `budget` changeset entries
+    /// from the tip back to the requested block to compute proofs and state roots, and return
+    /// [`ProviderError::ProofEffortExceeded`] otherwise.
+    pub const fn with_proof_effort_budget(mut self, budget: usize) -> Self {
+        self.proof_effort_budget = Some(budget);
+        self
+    }
+
+    /// Reads bytecodes of all state providers created by this factory through the given cache.
+    pub fn with_bytecode_cache(mut self, bytecode_cache: BytecodeCache) -> Self {
+        self.bytecode_cache = Some(bytecode_cache);
+        self
+    }
+
+    /// Returns the bytecode cache this factory was configured with, if any.
+    pub const fn bytecode_cache(&self) -> Option<&BytecodeCache> {
+        self.bytecode_cache.as_ref()
+    }
+
+    /// Records the historical state queries of all state providers created by this factory in the
+    /// given statistics.
+    pub fn with_query_stats(mut self, query_stats: QueryStats) -> Self {
+        self.query_stats = Some(query_stats);
+        self
+    }
+
+    /// Returns the query statistics this factory was configured with, if any.
+    pub const fn query_stats(&self) -> Option<&QueryStats> {
+        self.query_stats.as_ref()
+    }
+
     /// Returns reference to the underlying database.
     pub const fn db_ref(&self) -> &N::DB {
         &self.db
@@ -129,6 +196,10 @@ impl<N: NodeTypesWithDB<DB = Arc<DatabaseEnv>>> ProviderFactory<N> {
             static_file_provider,
             prune_modes: PruneModes::none(),
             storage: Default::default(),
+            storage_damage: None,
+            proof_effort_budget: None,
+            bytecode_cache: None,
+            query_stats: None,
         })
     }
 }
@@ -148,7 +219,27 @@ impl<N: ProviderNodeTypes> ProviderFactory<N> {
             self.static_file_provider.clone(),
             self.prune_modes.clone(),
             self.storage.clone(),
-        ))
+        )
+        .with_storage_damage(self.storage_damage)
+        .with_proof_effort_budget(self.proof_effort_budget)
+        .with_bytecode_cache(self.bytecode_cache.clone())
+        .with_query_stats(self.query_stats.clone()))
+    }
+
+    /// Warms the caches with the patterns recorded in the query statistics.
+    ///
+    /// Warms the state history of the `contracts` most read contracts in the `ranges` most queried
+    /// block ranges, see [`QueryStats::warm_history`].
+    ///
+    /// Returns the number of warmed contracts and history entries.
+    pub fn warm_from_query_stats(
+        &self,
+        contracts: usize,
+        ranges: usize,
+    ) -> ProviderResult<(usize, u64)> {
+        let Some(stats) = &self.query_stats else { return Ok((0, 0)) };
+        let provider = self.provider()?.disable_long_read_transaction_safety();
+        stats.warm_history(provider.tx_ref(), contracts, ranges)
     }
 
     /// Returns a provider with a created `DbTxMut` inside, which allows fetching and updating
@@ -166,11 +257,39 @@ impl<N: ProviderNodeTypes> ProviderFactory<N> {
         )))
     }
 
+    /// Reports the state size statistics as metrics, if they are maintained.
+    pub fn report_state_size_metrics(&self) -> ProviderResult<()> {
+        if let Some(size) = self.state_size()? {
+            state_size::StateSizeMetrics::default().record(&size);
+        }
+        Ok(())
+    }
+
     /// State provider for latest block
     #[track_caller]
     pub fn latest(&self) -> ProviderResult<StateProviderBox> {
+        if let Some(damage) = self.storage_damage {
+            // the plain state may be ahead of the consistent tip, so serve the tip from history
+            return self.history_by_block_number(damage.consistent_tip)
+        }
         trace!(target: "providers::db", "Returning latest state provider");
-        Ok(Box::new(LatestStateProvider::new(self.database_provider_ro()?)))
+        let state_provider: StateProviderBox =
+            Box::new(LatestStateProvider::new(self.database_provider_ro()?));
+        Ok(match &self.bytecode_cache {
+            Some(cache) => cache.wrap(state_provider),
+            None => state_provider,
+        })
+    }
+
+    /// Pre-warms the bytecode cache with the bytecodes of the `top_n` most used contracts in
+    /// recent blocks, see [`BytecodeCache::prewarm`].
+    ///
+    /// Returns the number of bytecodes inserted into the cache.
+    pub fn prewarm_bytecode_cache(&self, top_n: usize) -> ProviderResult<usize> {
+        let Some(cache) = &self.bytecode_cache else { return Ok(0) };
+        let provider = self.provider()?;
+        let tip = provider.best_block_number()?;
+        cache.prewarm(provider.tx_ref(), tip, BytecodeCache::DEFAULT_PREWARM_BLOCKS, top_n)
     }
 
     /// Storage provider for state at that given block
@@ -371,6 +490,10 @@ impl<N: ProviderNodeTypes> BlockReader for ProviderFactory<N> {
         self.provider()?.block(id)
     }
 
+    fn ommers(&self, id: BlockHashOrNumber) -> ProviderResult<Option<Vec<Self::Header>>> {
+        self.provider()?.ommers(id)
+    }
+
     fn pending_block(&self) -> ProviderResult<Option<RecoveredBlock<Self::Block>>> {
         self.provider()?.pending_block()
     }
@@ -533,6 +656,15 @@ impl<N: ProviderNodeTypes> ReceiptProvider for ProviderFactory<N> {
     ) -> ProviderResult<Vec<Vec<Self::Receipt>>> {
         self.provider()?.receipts_by_block_range(block_range)
     }
+
+    fn indexed_log_blocks(
+        &self,
+        block_range: RangeInclusive<BlockNumber>,
+        addresses: &[Address],
+        topics: &[Vec<B256>],
+    ) -> ProviderResult<Option<IndexedLogBlocks>> {
+        self.provider()?.indexed_log_blocks(block_range, addresses, topics)
+    }
 }
 
 impl<N: ProviderNodeTypes> BlockBodyIndicesProvider for ProviderFactory<N> {
@@ -600,6 +732,22 @@ impl<N: ProviderNodeTypes> PruneCheckpointReader for ProviderFactory<N> {
     }
 }
 
+impl<N: ProviderNodeTypes> StateSizeReader for ProviderFactory<N> {
+    fn state_size(&self) -> ProviderResult<Option<StateSize>> {
+        self.provider()?.state_size()
+    }
+}
+
+impl<N: ProviderNodeTypes> GasUsageReader for ProviderFactory<N> {
+    fn top_gas_consumers(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        limit: usize,
+    ) -> ProviderResult<Option<TopGasConsumers>> {
+        self.provider()?.top_gas_consumers(range, limit)
+    }
+}
+
 impl<N: ProviderNodeTypes> HashedPostStateProvider for ProviderFactory<N> {
     fn hashed_post_state(&self, bundle_state: &BundleState) -> HashedPostState {
         HashedPostState::from_bundle_state::<<N::StateCommitment as StateCommitment>::KeyHasher>(
@@ -613,13 +761,27 @@ where
     N: NodeTypesWithDB<DB: fmt::Debug, ChainSpec: fmt::Debug, Storage: fmt::Debug>,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let Self { db, chain_spec, static_file_provider, prune_modes, storage } = self;
+        let Self {
+            db,
+            chain_spec,
+            static_file_provider,
+            prune_modes,
+            storage,
+            storage_damage,
+            proof_effort_budget,
+            bytecode_cache,
+            query_stats,
+        } = self;
         f.debug_struct("ProviderFactory")
             .field("db", &db)
             .field("chain_spec", &chain_spec)
             .field("static_file_provider", &static_file_provider)
             .field("prune_modes", &prune_modes)
             .field("storage", &storage)
+            .field("storage_damage", &storage_damage)
+            .field("proof_effort_budget", &proof_effort_budget)
+            .field("bytecode_cache", &bytecode_cache)
+            .field("query_stats", &query_stats)
             .finish()
     }
 }
@@ -632,6 +794,10 @@ impl<N: NodeTypesWithDB> Clone for ProviderFactory<N> {
             static_file_provider: self.static_file_provider.clone(),
             prune_modes: self.prune_modes.clone(),
             storage: self.storage.clone(),
+            storage_damage: self.storage_damage,
+            proof_effort_budget: self.proof_effort_budget,
+            bytecode_cache: self.bytecode_cache.clone(),
+            query_stats: self.query_stats.clone(),
         }
     }
 }
@@ -642,19 +808,24 @@ mod tests {
     use crate::{
         providers::{StaticFileProvider, StaticFileWriter},
         test_utils::{blocks::TEST_BLOCK, create_test_provider_factory, MockNodeTypesWithDB},
-        BlockHashReader, BlockNumReader, BlockWriter, DBProvider, HeaderSyncGapProvider,
-        StorageLocation, TransactionsProvider,
+        BlockHashReader, BlockNumReader, BlockWriter, ChangeSetReader, DBProvider,
+        HeaderSyncGapProvider, StorageChangeSetReader, StorageLocation, StorageSlotChange,
+        TransactionsProvider,
     };
-    use alloy_primitives::{TxNumber, B256, U256};
+    use alloy_primitives::{address, TxNumber, B256, U256};
     use assert_matches::assert_matches;
     use reth_chainspec::ChainSpecBuilder;
     use reth_db::{
         mdbx::DatabaseArguments,
         test_utils::{create_test_static_files_dir, ERROR_TEMPDIR},
     };
-    use reth_db_api::tables;
-    use reth_primitives_traits::SignerRecoverable;
+    use reth_db_api::{
+        models::{storage_sharded_key::StorageShardedKey, AccountBeforeTx, ShardedKey},
+        tables, BlockNumberList,
+    };
+    use reth_primitives_traits::{Account, SignerRecoverable, StorageEntry};
     use reth_prune_types::{PruneMode, PruneModes};
+    use reth_storage_api::{AccountReader, StateProvider};
     use reth_storage_errors::provider::ProviderError;
     use reth_testing_utils::generators::{self, random_block, random_header, BlockParams};
     use std::{ops::RangeInclusive, sync::Arc};
@@ -781,6 +952,73 @@ mod tests {
         }
     }
 
+    #[test]
+    fn ommers_by_id() {
+        let factory = create_test_provider_factory();
+
+        let mut rng = generators::rng();
+        let block =
+            random_block(&mut rng, 0, BlockParams { ommers_count: Some(2), ..Default::default() });
+
+        {
+            let provider = factory.provider_rw().unwrap();
+            assert_matches!(
+                provider
+                    .insert_block(block.clone().try_recover().unwrap(), StorageLocation::Database),
+                Ok(_)
+            );
+            provider.commit().unwrap();
+        }
+
+        let provider = factory.provider().unwrap();
+        assert_eq!(provider.ommers(0.into()).unwrap(), Some(block.body().ommers.clone()));
+        assert_eq!(
+            provider.ommers(block.hash().into()).unwrap(),
+            Some(block.body().ommers.clone())
+        );
+        assert_eq!(provider.ommers(1.into()).unwrap(), None);
+    }
+
+    #[test]
+    fn storage_damage_caps_chain() {
+        let factory = create_test_provider_factory();
+
+        let mut rng = generators::rng();
+        let blocks = [
+            random_block(&mut rng, 0, BlockParams::default()),
+            random_block(&mut rng, 1, BlockParams::default()),
+        ];
+
+        {
+            let provider = factory.provider_rw().unwrap();
+            for block in &blocks {
+                assert_matches!(
+                    provider.insert_block(
+                        block.clone().try_recover().unwrap(),
+                        StorageLocation::Database
+                    ),
+                    Ok(_)
+                );
+            }
+            provider.commit().unwrap();
+        }
+
+        let factory =
+            factory.with_storage_damage(StorageDamage { consistent_tip: 0, highest_block: 1 });
+        let provider = factory.provider().unwrap();
+
+        assert!(provider.header_by_number(0).unwrap().is_some());
+        assert_matches!(
+            provider.header_by_number(1),
+            Err(ProviderError::StorageDegraded { block: 1, consistent_tip: 0 })
+        );
+        assert_matches!(
+            provider.block_number(blocks[1].hash()),
+            Err(ProviderError::StorageDegraded { block: 1, consistent_tip: 0 })
+        );
+        assert_eq!(provider.last_block_number().unwrap(), 0);
+    }
+
     #[test]
     fn header_sync_gap_lookup() {
         let factory = create_test_provider_factory();
@@ -811,4 +1049,175 @@ mod tests {
 
         assert_eq!(local_head, head);
     }
+
+    #[test]
+    fn storage_changes_in_range() {
+        let factory = create_test_provider_factory();
+        let tx = factory.provider_rw().unwrap().into_tx();
+
+        let address = address!("0x0000000000000000000000000000000000000001");
+        let slot = B256::with_last_byte(1);
+        for number in 0..=20 {
+            tx.put::<tables::CanonicalHeaders>(number, B256::with_last_byte(number as u8)).unwrap();
+        }
+        tx.put::<tables::StoragesHistory>(
+            StorageShardedKey {
+                address,
+                sharded_key: ShardedKey { key: slot, highest_block_number: u64::MAX },
+            },
+            BlockNumberList::new([3, 7, 10, 15]).unwrap(),
+        )
+        .unwrap();
+        for (number, previous) in [(3, 0), (7, 7), (10, 10), (15, 15)] {
+            let entry = StorageEntry { key: slot, value: U256::from(previous) };
+            tx.put::<tables::StorageChangeSets>((number, address).into(), entry).unwrap();
+        }
+        tx.put::<tables::PlainStorageState>(
+            address,
+            StorageEntry { key: slot, value: U256::from(100) },
+        )
+        .unwrap();
+
+        // a slot of another account, indexed in two shards
+        let other = address!("0x0000000000000000000000000000000000000002");
+        for (highest_block_number, blocks) in [(4, vec![2, 4]), (u64::MAX, vec![8, 18])] {
+            tx.put::<tables::StoragesHistory>(
+                StorageShardedKey {
+                    address: other,
+                    sharded_key: ShardedKey { key: slot, highest_block_number },
+                },
+                BlockNumberList::new(blocks).unwrap(),
+            )
+            .unwrap();
+        }
+        for (number, previous) in [(2, 0), (4, 2), (8, 4), (18, 8)] {
+            let entry = StorageEntry { key: slot, value: U256::from(previous) };
+            tx.put::<tables::StorageChangeSets>((number, other).into(), entry).unwrap();
+        }
+        tx.put::<tables::PlainStorageState>(
+            other,
+            StorageEntry { key: slot, value: U256::from(50) },
+        )
+        .unwrap();
+        tx.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        let change = |block_number, previous_value: u64, value: u64| StorageSlotChange {
+            block_number,
+            key: slot,
+            previous_value: U256::from(previous_value),
+            value: U256::from(value),
+        };
+
+        // the value after the last change in the range is the value before the next change
+        assert_eq!(
+            provider.storage_changes_in_range(address, 5..=12).unwrap(),
+            vec![change(7, 7, 10), change(10, 10, 15)]
+        );
+        // the range is capped at the last block, the value after the last change is the plain state
+        assert_eq!(
+            provider.storage_changes_in_range(address, 14..=100).unwrap(),
+            vec![change(15, 15, 100)]
+        );
+        assert!(provider.storage_changes_in_range(address, 16..=20).unwrap().is_empty());
+        assert!(provider.storage_changes_in_range(Address::ZERO, 0..=20).unwrap().is_empty());
+
+        // only the blocks indexed in the shards of the range are read
+        assert_eq!(
+            provider.storage_changes_in_range(other, 3..=12).unwrap(),
+            vec![change(4, 2, 4), change(8, 4, 8)]
+        );
+        assert_eq!(
+            provider.storage_changes_in_range(other, 5..=20).unwrap(),
+            vec![change(8, 4, 8), change(18, 8, 50)]
+        );
+    }
+
+    #[test]
+    fn account_change_blocks() {
+        let factory = create_test_provider_factory();
+        let tx = factory.provider_rw().unwrap().into_tx();
+
+        let address = address!("0x0000000000000000000000000000000000000001");
+        tx.put::<tables::AccountsHistory>(
+            ShardedKey::new(address, 7),
+            BlockNumberList::new([1, 3, 7]).unwrap(),
+        )
+        .unwrap();
+        tx.put::<tables::AccountsHistory>(
+            ShardedKey::last(address),
+            BlockNumberList::new([10, 15, 20]).unwrap(),
+        )
+        .unwrap();
+        tx.put::<tables::AccountsHistory>(
+            ShardedKey::last(Address::with_last_byte(2)),
+            BlockNumberList::new([2, 4]).unwrap(),
+        )
+        .unwrap();
+        tx.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        // the range spans both shards of the address
+        assert_eq!(
+            provider.account_change_blocks(address, 2..=15, 100).unwrap(),
+            vec![3, 7, 10, 15]
+        );
+        assert_eq!(provider.account_change_blocks(address, 0..=100, 2).unwrap(), vec![1, 3]);
+        // the next page starts after the last returned block
+        assert_eq!(provider.account_change_blocks(address, 4..=100, 2).unwrap(), vec![7, 10]);
+        assert!(provider.account_change_blocks(address, 8..=9, 100).unwrap().is_empty());
+        assert!(provider.account_change_blocks(Address::ZERO, 0..=100, 100).unwrap().is_empty());
+    }
+
+    #[test]
+    fn warm_from_query_stats() {
+        let factory = create_test_provider_factory().with_query_stats(QueryStats::new());
+        let tx = factory.provider_rw().unwrap().into_tx();
+
+        let (address, other) = (Address::with_last_byte(1), Address::with_last_byte(2));
+        let slot = B256::with_last_byte(1);
+        for address in [address, other] {
+            tx.put::<tables::AccountsHistory>(
+                ShardedKey::new(address, 2),
+                BlockNumberList::new([1, 2]).unwrap(),
+            )
+            .unwrap();
+            tx.put::<tables::AccountsHistory>(
+                ShardedKey::last(address),
+                BlockNumberList::new([5, 12_000]).unwrap(),
+            )
+            .unwrap();
+            for number in [1, 2, 5, 12_000] {
+                let info = Some(Account { nonce: number, ..Default::default() });
+                tx.put::<tables::AccountChangeSets>(number, AccountBeforeTx { address, info })
+                    .unwrap();
+            }
+            tx.put::<tables::StoragesHistory>(
+                StorageShardedKey::new(address, slot, u64::MAX),
+                BlockNumberList::new([3, 11_000]).unwrap(),
+            )
+            .unwrap();
+            for number in [3, 11_000] {
+                let entry = StorageEntry { key: slot, value: U256::from(number) };
+                tx.put::<tables::StorageChangeSets>((number, address).into(), entry).unwrap();
+            }
+        }
+        tx.commit().unwrap();
+
+        {
+            let state = factory.provider().unwrap().try_into_history_at_block(3).unwrap();
+            assert_eq!(
+                state.basic_account(&address).unwrap().map(|account| account.nonce),
+                Some(5)
+            );
+            assert_eq!(state.storage(address, slot).unwrap(), Some(U256::from(11_000)));
+        }
+        let stats = factory.query_stats().unwrap();
+        assert_eq!(stats.hot_contracts(10), vec![address]);
+        assert_eq!(stats.hot_ranges(10), vec![0..=QueryStats::RANGE_SIZE - 1]);
+
+        // the account shards covering the range and their changesets, up to the first change
+        // after the range, and the storage shard of the slot with its changesets
+        assert_eq!(factory.warm_from_query_stats(10, 10).unwrap(), (1, 6 + 3));
+    }
 }
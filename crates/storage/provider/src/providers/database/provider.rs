@@ -1,9 +1,17 @@
 use crate::{
     bundle_state::StorageRevertsIter,
     providers::{
-        database::{chain::ChainStorage, metrics},
+        database::{
+            chain::ChainStorage,
+            metrics,
+            state_size::{
+                apply_state_size_delta, count_state_size, is_state_size_tracked, read_state_size,
+                write_state_size, StateSizeDelta,
+            },
+            StorageDamage,
+        },
         static_file::StaticFileWriter,
-        NodeTypesForProvider, StaticFileProvider,
+        BytecodeCache, NodeTypesForProvider, QueryStats, StaticFileProvider,
     },
     to_range,
     traits::{
@@ -26,7 +34,7 @@ use alloy_eips::{eip2718::Encodable2718, BlockHashOrNumber};
 use alloy_primitives::{
     keccak256,
     map::{hash_map, B256Map, HashMap, HashSet},
-    Address, BlockHash, BlockNumber, TxHash, TxNumber, B256, U256,
+    Address, BlockHash, BlockNumber, Log, TxHash, TxNumber, B256, U256,
 };
 use itertools::Itertools;
 use rayon::slice::ParallelSliceMut;
@@ -55,8 +63,10 @@ use reth_prune_types::{
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_static_file_types::StaticFileSegment;
 use reth_storage_api::{
-    BlockBodyIndicesProvider, BlockBodyReader, NodePrimitivesProvider, StateProvider,
-    StorageChangeSetReader, TryIntoHistoricalStateProvider,
+    block_gas_usage, gas_usage_epoch, BlockBodyIndicesProvider, BlockBodyReader, ContractGasUsage,
+    GasUsageReader, GasUsageWriter, IndexedLogBlocks, NodePrimitivesProvider, StateProvider,
+    StateSize, StateSizeReader, StorageChangeSetReader, StorageSlotChange, TopGasConsumers,
+    TryIntoHistoricalStateProvider, GAS_USAGE_EPOCH_LENGTH,
 };
 use reth_storage_errors::provider::{ProviderResult, RootMismatch};
 use reth_trie::{
@@ -153,6 +163,14 @@ pub struct DatabaseProvider<TX, N: NodeTypes> {
     prune_modes: PruneModes,
     /// Node storage handler.
     storage: Arc<N::Storage>,
+    /// Storage damage that is tolerated because the node runs in safe mode.
+    storage_damage: Option<StorageDamage>,
+    /// Maximum number of changeset entries replayed for historical proofs and state roots.
+    proof_effort_budget: Option<usize>,
+    /// Cache of decompressed bytecodes used by the owned state providers.
+    bytecode_cache: Option<BytecodeCache>,
+    /// Statistics that historical state queries are recorded in.
+    query_stats: Option<QueryStats>,
 }
 
 impl<TX, N: NodeTypes> DatabaseProvider<TX, N> {
@@ -160,6 +178,72 @@ impl<TX, N: NodeTypes> DatabaseProvider<TX, N> {
     pub const fn prune_modes_ref(&self) -> &PruneModes {
         &self.prune_modes
     }
+
+    /// Sets the storage damage above which this provider refuses to serve blocks.
+    pub fn with_storage_damage(mut self, storage_damage: Option<StorageDamage>) -> Self {
+        self.storage_damage = storage_damage;
+        self
+    }
+
+    /// Sets the maximum number of changeset entries that historical state providers replay to
+    /// reconstruct the trie for proofs and state roots.
+    pub const fn with_proof_effort_budget(mut self, budget: Option<usize>) -> Self {
+        self.proof_effort_budget = budget;
+        self
+    }
+
+    /// Sets the cache that bytecode reads of state providers created by
+    /// [`TryIntoHistoricalStateProvider::try_into_history_at_block`] go through.
+    pub fn with_bytecode_cache(mut self, bytecode_cache: Option<BytecodeCache>) -> Self {
+        self.bytecode_cache = bytecode_cache;
+        self
+    }
+
+    /// Wraps the state provider with the bytecode cache, if any.
+    fn with_cached_bytecodes(
+        bytecode_cache: Option<&BytecodeCache>,
+        provider: StateProviderBox,
+    ) -> StateProviderBox {
+        match bytecode_cache {
+            Some(cache) => cache.wrap(provider),
+            None => provider,
+        }
+    }
+
+    /// Sets the statistics that queries of state providers created by
+    /// [`TryIntoHistoricalStateProvider::try_into_history_at_block`] are recorded in.
+    pub fn with_query_stats(mut self, query_stats: Option<QueryStats>) -> Self {
+        self.query_stats = query_stats;
+        self
+    }
+
+    /// Returns [`ProviderError::StorageDegraded`] if the block is above the consistent tip of
+    /// damaged storage.
+    fn ensure_not_degraded(&self, block: BlockNumber) -> ProviderResult<()> {
+        match self.storage_damage {
+            Some(damage) if damage.is_degraded(block) => {
+                Err(ProviderError::StorageDegraded { block, consistent_tip: damage.consistent_tip })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns [`ProviderError::HistoryExpired`] if the transactions and receipts of the block have
+    /// been expired.
+    ///
+    /// See also [`StaticFileProvider::earliest_history_height`].
+    fn ensure_not_expired(&self, block: BlockNumber) -> ProviderResult<()> {
+        let earliest = self.static_file_provider.earliest_history_height();
+        if block < earliest {
+            return Err(ProviderError::HistoryExpired { block, earliest })
+        }
+        Ok(())
+    }
+
+    /// Caps the block number at the consistent tip of damaged storage.
+    fn cap_to_consistent_tip(&self, block: BlockNumber) -> BlockNumber {
+        self.storage_damage.map_or(block, |damage| block.min(damage.consistent_tip))
+    }
 }
 
 impl<TX: DbTx + 'static, N: NodeTypes> DatabaseProvider<TX, N> {
@@ -176,7 +260,8 @@ impl<TX: DbTx + 'static, N: NodeTypes> DatabaseProvider<TX, N> {
     ) -> ProviderResult<Box<dyn StateProvider + 'a>> {
         let mut block_number =
             self.block_number(block_hash)?.ok_or(ProviderError::BlockHashNotFound(block_hash))?;
-        if block_number == self.best_block_number().unwrap_or_default() &&
+        if self.storage_damage.is_none() &&
+            block_number == self.best_block_number().unwrap_or_default() &&
             block_number == self.last_block_number().unwrap_or_default()
         {
             return Ok(Box::new(LatestStateProviderRef::new(self)))
@@ -190,7 +275,8 @@ impl<TX: DbTx + 'static, N: NodeTypes> DatabaseProvider<TX, N> {
         let storage_history_prune_checkpoint =
             self.get_prune_checkpoint(PruneSegment::StorageHistory)?;
 
-        let mut state_provider = HistoricalStateProviderRef::new(self, block_number);
+        let mut state_provider = HistoricalStateProviderRef::new(self, block_number)
+            .with_proof_effort_budget(self.proof_effort_budget);
 
         // If we pruned account or storage history, we can't return state on every historical block.
         // Instead, we should cap it at the latest prune checkpoint for corresponding prune segment.
@@ -217,6 +303,39 @@ impl<TX: DbTx + 'static, N: NodeTypes> DatabaseProvider<TX, N> {
     pub fn set_prune_modes(&mut self, prune_modes: PruneModes) {
         self.prune_modes = prune_modes;
     }
+
+    /// Returns the blocks in the range that contain a log with any of the keys, according to the
+    /// log index table `T`.
+    fn log_index_blocks<T, K>(
+        &self,
+        keys: &[K],
+        range: &RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<BlockNumber>>
+    where
+        T: Table<Key = ShardedKey<K>, Value = BlockNumberList>,
+        K: Copy + PartialEq,
+    {
+        let mut cursor = self.tx.cursor_read::<T>()?;
+        let mut blocks = BTreeSet::new();
+        for key in keys {
+            // the first shard that can contain the start of the range
+            for entry in cursor.walk(Some(ShardedKey::new(*key, *range.start())))? {
+                let (sharded_key, list) = entry?;
+                if sharded_key.key != *key {
+                    break
+                }
+                blocks.extend(
+                    list.iter()
+                        .skip_while(|block| block < range.start())
+                        .take_while(|block| block <= range.end()),
+                );
+                if sharded_key.highest_block_number >= *range.end() {
+                    break
+                }
+            }
+        }
+        Ok(blocks)
+    }
 }
 
 impl<TX, N: NodeTypes> NodePrimitivesProvider for DatabaseProvider<TX, N> {
@@ -249,7 +368,17 @@ impl<TX: DbTxMut, N: NodeTypes> DatabaseProvider<TX, N> {
         prune_modes: PruneModes,
         storage: Arc<N::Storage>,
     ) -> Self {
-        Self { tx, chain_spec, static_file_provider, prune_modes, storage }
+        Self {
+            tx,
+            chain_spec,
+            static_file_provider,
+            prune_modes,
+            storage,
+            storage_damage: None,
+            proof_effort_budget: None,
+            bytecode_cache: None,
+            query_stats: None,
+        }
     }
 }
 
@@ -260,6 +389,28 @@ impl<TX, N: NodeTypes> AsRef<Self> for DatabaseProvider<TX, N> {
 }
 
 impl<TX: DbTx + DbTxMut + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
+    /// Unwinds the log index above the given block, if it's maintained and covers such blocks.
+    ///
+    /// Receipts of the unwound blocks must still be available.
+    pub fn unwind_log_index_above(&self, block: BlockNumber) -> ProviderResult<()> {
+        let Some(checkpoint) = self.get_stage_checkpoint(StageId::INDEX_LOGS)? else {
+            return Ok(())
+        };
+        if checkpoint.block_number <= block {
+            return Ok(())
+        }
+
+        let receipts = self.receipts_by_block_range(block + 1..=checkpoint.block_number)?;
+        self.unwind_log_indices(
+            receipts.iter().flatten().flat_map(|receipt| receipt.logs()),
+            block + 1,
+        )?;
+        self.save_stage_checkpoint(
+            StageId::INDEX_LOGS,
+            StageCheckpoint { block_number: block, ..checkpoint },
+        )
+    }
+
     /// Unwinds trie state for the given range.
     ///
     /// This includes calculating the resulted state root and comparing it with the parent block
@@ -387,12 +538,23 @@ impl<TX: DbTx + 'static, N: NodeTypes> TryIntoHistoricalStateProvider for Databa
         self,
         mut block_number: BlockNumber,
     ) -> ProviderResult<StateProviderBox> {
+        self.ensure_not_degraded(block_number)?;
+        let bytecode_cache = self.bytecode_cache.clone();
+
         // if the block number is the same as the currently best block number on disk we can use the
-        // latest state provider here
-        if block_number == self.best_block_number().unwrap_or_default() {
-            return Ok(Box::new(LatestStateProvider::new(self)))
+        // latest state provider here, unless the latest state is beyond damaged storage
+        if self.storage_damage.is_none() &&
+            block_number == self.best_block_number().unwrap_or_default()
+        {
+            return Ok(Self::with_cached_bytecodes(
+                bytecode_cache.as_ref(),
+                Box::new(LatestStateProvider::new(self)),
+            ))
         }
 
+        // only historical queries are recorded, the latest state is warm anyway
+        let query_stats = self.query_stats.clone().map(|stats| (stats, block_number));
+
         // +1 as the changeset that we want is the one that was applied after this block.
         block_number += 1;
 
@@ -401,7 +563,9 @@ impl<TX: DbTx + 'static, N: NodeTypes> TryIntoHistoricalStateProvider for Databa
         let storage_history_prune_checkpoint =
             self.get_prune_checkpoint(PruneSegment::StorageHistory)?;
 
-        let mut state_provider = HistoricalStateProvider::new(self, block_number);
+        let proof_effort_budget = self.proof_effort_budget;
+        let mut state_provider = HistoricalStateProvider::new(self, block_number)
+            .with_proof_effort_budget(proof_effort_budget);
 
         // If we pruned account or storage history, we can't return state on every historical block.
         // Instead, we should cap it at the latest prune checkpoint for corresponding prune segment.
@@ -420,7 +584,12 @@ impl<TX: DbTx + 'static, N: NodeTypes> TryIntoHistoricalStateProvider for Databa
             );
         }
 
-        Ok(Box::new(state_provider))
+        let state_provider =
+            Self::with_cached_bytecodes(bytecode_cache.as_ref(), Box::new(state_provider));
+        Ok(match query_stats {
+            Some((stats, block)) => stats.wrap(block, state_provider),
+            None => state_provider,
+        })
     }
 }
 
@@ -529,6 +698,33 @@ where
     Ok(Vec::new())
 }
 
+/// Unwinds the shards of all keys of a log index table, see [`unwind_history_shards`].
+fn unwind_log_index_shards<K, T, C>(
+    cursor: &mut C,
+    keys: impl IntoIterator<Item = K>,
+    block_number: BlockNumber,
+) -> ProviderResult<()>
+where
+    K: Copy + PartialEq,
+    T: Table<Key = ShardedKey<K>, Value = BlockNumberList>,
+    C: DbCursorRO<T> + DbCursorRW<T>,
+{
+    for key in keys {
+        let partial_shard = unwind_history_shards::<_, T, _>(
+            cursor,
+            ShardedKey::last(key),
+            block_number,
+            |sharded_key| sharded_key.key == key,
+        )?;
+
+        if !partial_shard.is_empty() {
+            cursor
+                .insert(ShardedKey::last(key), &BlockNumberList::new_pre_sorted(partial_shard))?;
+        }
+    }
+    Ok(())
+}
+
 impl<TX: DbTx + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
     /// Creates a provider with an inner read-only transaction.
     pub const fn new(
@@ -538,7 +734,17 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
         prune_modes: PruneModes,
         storage: Arc<N::Storage>,
     ) -> Self {
-        Self { tx, chain_spec, static_file_provider, prune_modes, storage }
+        Self {
+            tx,
+            chain_spec,
+            static_file_provider,
+            prune_modes,
+            storage,
+            storage_damage: None,
+            proof_effort_budget: None,
+            bytecode_cache: None,
+            query_stats: None,
+        }
     }
 
     /// Consume `DbTx` or `DbTxMut`.
@@ -608,6 +814,7 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
         let (transactions, senders) = if tx_range.is_empty() {
             (vec![], vec![])
         } else {
+            self.ensure_not_expired(block_number)?;
             (self.transactions_by_tx_range(tx_range.clone())?, self.senders_by_tx_range(tx_range)?)
         };
 
@@ -668,6 +875,7 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
             let transactions = if tx_range.is_empty() {
                 Vec::new()
             } else {
+                self.ensure_not_expired(header.as_ref().number())?;
                 self.transactions_by_tx_range_with_cursor(tx_range.clone(), &mut tx_cursor)?
             };
 
@@ -823,6 +1031,25 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> DatabaseProvider<TX, N> {
         Ok(self.tx.commit()?)
     }
 
+    /// Restarts the gas usage index after the given block, dropping the indexed gas usage.
+    fn restart_gas_usage_index(&self, block: BlockNumber) -> ProviderResult<()> {
+        self.tx.clear::<tables::ContractGasUsage>()?;
+        self.tx.put::<tables::ChainState>(tables::ChainStateKey::GasUsageIndexedFrom, block + 1)?;
+        self.tx.put::<tables::ChainState>(tables::ChainStateKey::GasUsageIndexedTo, block)?;
+        Ok(())
+    }
+
+    /// Computes the state size statistics by scanning the plain state and stores them.
+    ///
+    /// From then on, the statistics are updated on every state write.
+    pub fn init_state_size(&self) -> ProviderResult<StateSize> {
+        let mut size = count_state_size(&self.tx)?;
+        write_state_size(&self.tx, &size)?;
+        size.block_number =
+            self.get_stage_checkpoint(StageId::Execution)?.unwrap_or_default().block_number;
+        Ok(size)
+    }
+
     /// Load shard and remove it. If list is empty, last shard was full or
     /// there are no shards at all.
     fn take_shard<T>(
@@ -932,7 +1159,7 @@ impl<TX: DbTx, N: NodeTypes> AccountExtReader for DatabaseProvider<TX, N> {
     }
 }
 
-impl<TX: DbTx, N: NodeTypes> StorageChangeSetReader for DatabaseProvider<TX, N> {
+impl<TX: DbTx + 'static, N: NodeTypes> StorageChangeSetReader for DatabaseProvider<TX, N> {
     fn storage_changeset(
         &self,
         block_number: BlockNumber,
@@ -945,9 +1172,82 @@ impl<TX: DbTx, N: NodeTypes> StorageChangeSetReader for DatabaseProvider<TX, N>
             .map(|result| -> ProviderResult<_> { Ok(result?) })
             .collect()
     }
+
+    fn storage_changes_in_range(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<StorageSlotChange>> {
+        let start = *range.start();
+        let end = (*range.end()).min(self.last_block_number()?);
+        if start > end {
+            return Ok(Vec::new())
+        }
+
+        // The checkpoint stores the highest pruned block number
+        if let Some(pruned) = self
+            .get_prune_checkpoint(PruneSegment::StorageHistory)?
+            .and_then(|checkpoint| checkpoint.block_number)
+        {
+            if start <= pruned {
+                return Err(ProviderError::StateAtBlockPruned(start))
+            }
+        }
+
+        // the blocks in which the slots of the address changed, from the history shards of each
+        // slot, so only those blocks are looked up in the changesets
+        let mut history = self.tx.cursor_read::<tables::StoragesHistory>()?;
+        let mut blocks = BTreeSet::new();
+        let mut next = history.seek(StorageShardedKey::new(address, B256::ZERO, start))?;
+        while let Some((key, list)) = next {
+            if key.address != address {
+                break
+            }
+            let slot = key.sharded_key.key;
+            let highest = key.sharded_key.highest_block_number;
+            next = if highest < start {
+                // skip to the first shard of the slot that can contain the start of the range
+                history.seek(StorageShardedKey::new(address, slot, start))?
+            } else {
+                blocks.extend(
+                    list.iter()
+                        .skip_while(|block| *block < start)
+                        .take_while(|block| *block <= end),
+                );
+                if highest >= end && highest != u64::MAX {
+                    // the remaining shards of the slot are past the range, skip to the next slot
+                    match history.seek(StorageShardedKey::last(address, slot))? {
+                        Some((key, _)) if key.address == address && key.sharded_key.key == slot => {
+                            history.next()?
+                        }
+                        entry => entry,
+                    }
+                } else {
+                    history.next()?
+                }
+            };
+        }
+
+        // the changesets of an address in a block are stored under a single dupsort key
+        let mut cursor = self.tx.cursor_dup_read::<tables::StorageChangeSets>()?;
+        let mut changesets = Vec::new();
+        for block_number in blocks {
+            for entry in cursor.walk_dup(Some(BlockNumberAddress((block_number, address))), None)? {
+                let (_, entry) = entry?;
+                changesets.push((block_number, entry));
+            }
+        }
+
+        // the value after the last change of a slot is looked up in the `StoragesHistory` index,
+        // as the previous value of the next changeset or the plain state
+        let state = HistoricalStateProviderRef::new(self, end + 1);
+        StorageSlotChange::from_changesets(changesets, |key| {
+            Ok(state.storage(address, key)?.unwrap_or_default())
+        })
+    }
 }
 
-impl<TX: DbTx, N: NodeTypes> ChangeSetReader for DatabaseProvider<TX, N> {
+impl<TX: DbTx + 'static, N: NodeTypes> ChangeSetReader for DatabaseProvider<TX, N> {
     fn account_block_changeset(
         &self,
         block_number: BlockNumber,
@@ -962,6 +1262,49 @@ impl<TX: DbTx, N: NodeTypes> ChangeSetReader for DatabaseProvider<TX, N> {
             })
             .collect()
     }
+
+    fn account_change_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+        limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        let start = *range.start();
+        let end = *range.end();
+        if start > end || limit == 0 {
+            return Ok(Vec::new())
+        }
+
+        // The checkpoint stores the highest pruned block number
+        if let Some(pruned) = self
+            .get_prune_checkpoint(PruneSegment::AccountHistory)?
+            .and_then(|checkpoint| checkpoint.block_number)
+        {
+            if start <= pruned {
+                return Err(ProviderError::StateAtBlockPruned(start))
+            }
+        }
+
+        let mut cursor = self.tx.cursor_read::<tables::AccountsHistory>()?;
+        let mut blocks = Vec::new();
+        // the first shard that can contain the start of the range
+        for entry in cursor.walk(Some(ShardedKey::new(address, start)))? {
+            let (sharded_key, list) = entry?;
+            if sharded_key.key != address {
+                break
+            }
+            for block in list.iter().skip_while(|block| *block < start) {
+                if block > end {
+                    return Ok(blocks)
+                }
+                blocks.push(block);
+                if blocks.len() == limit {
+                    return Ok(blocks)
+                }
+            }
+        }
+        Ok(blocks)
+    }
 }
 
 impl<TX: DbTx + 'static, N: NodeTypesForProvider> HeaderSyncGapProvider
@@ -1021,6 +1364,7 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> HeaderProvider for DatabasePro
     }
 
     fn header_by_number(&self, num: BlockNumber) -> ProviderResult<Option<Self::Header>> {
+        self.ensure_not_degraded(num)?;
         self.static_file_provider.get_with_static_file_or_database(
             StaticFileSegment::Headers,
             num,
@@ -1121,6 +1465,7 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> HeaderProvider for DatabasePro
 
 impl<TX: DbTx + 'static, N: NodeTypes> BlockHashReader for DatabaseProvider<TX, N> {
     fn block_hash(&self, number: u64) -> ProviderResult<Option<B256>> {
+        self.ensure_not_degraded(number)?;
         self.static_file_provider.get_with_static_file_or_database(
             StaticFileSegment::Headers,
             number,
@@ -1156,7 +1501,7 @@ impl<TX: DbTx + 'static, N: NodeTypes> BlockNumReader for DatabaseProvider<TX, N
         // when new blocks committed
         Ok(self
             .get_stage_checkpoint(StageId::Finish)?
-            .map(|checkpoint| checkpoint.block_number)
+            .map(|checkpoint| self.cap_to_consistent_tip(checkpoint.block_number))
             .unwrap_or_default())
     }
 
@@ -1169,11 +1514,16 @@ impl<TX: DbTx + 'static, N: NodeTypes> BlockNumReader for DatabaseProvider<TX, N
             .max(
                 self.static_file_provider.get_highest_static_file_block(StaticFileSegment::Headers),
             )
+            .map(|num| self.cap_to_consistent_tip(num))
             .unwrap_or_default())
     }
 
     fn block_number(&self, hash: B256) -> ProviderResult<Option<BlockNumber>> {
-        Ok(self.tx.get::<tables::HeaderNumbers>(hash)?)
+        let number = self.tx.get::<tables::HeaderNumbers>(hash)?;
+        if let Some(number) = number {
+            self.ensure_not_degraded(number)?;
+        }
+        Ok(number)
     }
 }
 
@@ -1221,6 +1571,22 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> BlockReader for DatabaseProvid
 
         Ok(None)
     }
+
+    /// Returns the ommers of the block with matching number, as read by the storage of the node.
+    fn ommers(&self, id: BlockHashOrNumber) -> ProviderResult<Option<Vec<Self::Header>>> {
+        let Some(number) = self.convert_hash_or_number(id)? else { return Ok(None) };
+        let Some(header) = self.header_by_number(number)? else { return Ok(None) };
+
+        let bodies = self.storage.reader().read_block_bodies(self, vec![(&header, Vec::new())])?;
+        Ok(Some(
+            bodies
+                .first()
+                .and_then(|body| body.ommers())
+                .map(|ommers| ommers.to_vec())
+                .unwrap_or_default(),
+        ))
+    }
+
     fn pending_block(&self) -> ProviderResult<Option<RecoveredBlock<Self::Block>>> {
         Ok(None)
     }
@@ -1485,6 +1851,7 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> TransactionsProvider for Datab
                 return if tx_range.is_empty() {
                     Ok(Some(Vec::new()))
                 } else {
+                    self.ensure_not_expired(block_number)?;
                     Ok(Some(self.transactions_by_tx_range_with_cursor(tx_range, &mut tx_cursor)?))
                 }
             }
@@ -1497,6 +1864,9 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> TransactionsProvider for Datab
         range: impl RangeBounds<BlockNumber>,
     ) -> ProviderResult<Vec<Vec<Self::Transaction>>> {
         let range = to_range(range);
+        if !range.is_empty() {
+            self.ensure_not_expired(range.start)?;
+        }
         let mut tx_cursor = self.tx.cursor_read::<tables::Transactions<Self::Transaction>>()?;
 
         self.block_body_indices_range(range.start..=range.end.saturating_sub(1))?
@@ -1567,6 +1937,7 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> ReceiptProvider for DatabasePr
                 return if tx_range.is_empty() {
                     Ok(Some(Vec::new()))
                 } else {
+                    self.ensure_not_expired(number)?;
                     self.receipts_by_tx_range(tx_range).map(Some)
                 }
             }
@@ -1594,6 +1965,7 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> ReceiptProvider for DatabasePr
         if block_range.is_empty() {
             return Ok(Vec::new());
         }
+        self.ensure_not_expired(*block_range.start())?;
 
         // collect block body indices for each block in the range
         let mut block_body_indices = Vec::new();
@@ -1641,12 +2013,54 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> ReceiptProvider for DatabasePr
 
         Ok(result)
     }
+
+    fn indexed_log_blocks(
+        &self,
+        block_range: RangeInclusive<BlockNumber>,
+        addresses: &[Address],
+        topics: &[Vec<B256>],
+    ) -> ProviderResult<Option<IndexedLogBlocks>> {
+        // the index covers all blocks up to the stage checkpoint, except for the blocks with
+        // pruned receipts
+        let Some(checkpoint) = self.get_stage_checkpoint(StageId::INDEX_LOGS)? else {
+            return Ok(None)
+        };
+        let indexed_from = self
+            .get_prune_checkpoint(PruneSegment::Receipts)?
+            .and_then(|checkpoint| checkpoint.block_number)
+            .map_or(0, |block_number| block_number + 1);
+        let indexed_to = checkpoint.block_number.min(*block_range.end());
+        if checkpoint.block_number == 0 ||
+            *block_range.start() < indexed_from ||
+            *block_range.start() > indexed_to
+        {
+            return Ok(None)
+        }
+        let range = *block_range.start()..=indexed_to;
+
+        let mut candidates = None;
+        if !addresses.is_empty() {
+            candidates =
+                Some(self.log_index_blocks::<tables::LogAddressIndex, _>(addresses, &range)?);
+        }
+        for topics in topics.iter().filter(|topics| !topics.is_empty()) {
+            let blocks = self.log_index_blocks::<tables::LogTopicIndex, _>(topics, &range)?;
+            candidates = Some(match candidates {
+                Some(candidates) => blocks.intersection(&candidates).copied().collect(),
+                None => blocks,
+            });
+        }
+
+        Ok(candidates
+            .map(|blocks| IndexedLogBlocks { indexed_to, blocks: blocks.into_iter().collect() }))
+    }
 }
 
 impl<TX: DbTx + 'static, N: NodeTypesForProvider> BlockBodyIndicesProvider
     for DatabaseProvider<TX, N>
 {
     fn block_body_indices(&self, num: u64) -> ProviderResult<Option<StoredBlockBodyIndices>> {
+        self.ensure_not_degraded(num)?;
         self.static_file_provider.get_with_static_file_or_database(
             StaticFileSegment::BlockMeta,
             num,
@@ -1729,6 +2143,20 @@ impl<TX: DbTxMut, N: NodeTypes> StageCheckpointWriter for DatabaseProvider<TX, N
 
         Ok(())
     }
+
+    fn migrate_stage_checkpoints(&self) -> ProviderResult<Vec<String>> {
+        let mut removed = Vec::new();
+        for name in StageId::RETIRED {
+            let checkpoint = self.tx.get::<tables::StageCheckpoints>(name.to_string())?;
+            let progress = self.tx.get::<tables::StageCheckpointProgresses>(name.to_string())?;
+            if checkpoint.is_some() || progress.is_some() {
+                self.tx.delete::<tables::StageCheckpoints>(name.to_string(), None)?;
+                self.tx.delete::<tables::StageCheckpointProgresses>(name.to_string(), None)?;
+                removed.push(name.to_string());
+            }
+        }
+        Ok(removed)
+    }
 }
 
 impl<TX: DbTx + 'static, N: NodeTypes> StorageReader for DatabaseProvider<TX, N> {
@@ -1988,11 +2416,20 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
         changes.storage.par_sort_by_key(|a| a.address);
         changes.contracts.par_sort_by_key(|a| a.0);
 
+        // State size statistics are only maintained once they were initialized.
+        let track_state_size = is_state_size_tracked(self.tx_ref())?;
+        let mut state_size = StateSizeDelta::default();
+
         // Write new account state
         tracing::trace!(len = changes.accounts.len(), "Writing new account state");
         let mut accounts_cursor = self.tx_ref().cursor_write::<tables::PlainAccountState>()?;
         // write account to database.
         for (address, account) in changes.accounts {
+            if track_state_size {
+                let previous = accounts_cursor.seek_exact(address)?.map(|(_, account)| account);
+                let new = account.as_ref().map(Account::from);
+                state_size.account_changed(previous.as_ref(), new.as_ref());
+            }
             if let Some(account) = account {
                 tracing::trace!(?address, "Updating plain state account");
                 accounts_cursor.upsert(address, &account.into())?;
@@ -2006,6 +2443,9 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
         tracing::trace!(len = changes.contracts.len(), "Writing bytecodes");
         let mut bytecodes_cursor = self.tx_ref().cursor_write::<tables::Bytecodes>()?;
         for (hash, bytecode) in changes.contracts {
+            if track_state_size && bytecodes_cursor.seek_exact(hash)?.is_none() {
+                state_size.bytecode_inserted(bytecode.original_byte_slice().len());
+            }
             bytecodes_cursor.upsert(hash, &Bytecode(bytecode))?;
         }
 
@@ -2015,6 +2455,15 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
         for PlainStorageChangeset { address, wipe_storage, storage } in changes.storage {
             // Wiping of storage.
             if wipe_storage && storages_cursor.seek_exact(address)?.is_some() {
+                if track_state_size {
+                    let mut slots = 0;
+                    for entry in storages_cursor.walk_dup(Some(address), None)? {
+                        entry?;
+                        slots += 1;
+                    }
+                    state_size.storage_wiped(slots);
+                    storages_cursor.seek_exact(address)?;
+                }
                 storages_cursor.delete_current_duplicates()?;
             }
             // cast storages to B256.
@@ -2027,8 +2476,10 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
 
             for entry in storage {
                 tracing::trace!(?address, ?entry.key, "Updating plain state storage");
+                let mut existed = false;
                 if let Some(db_entry) = storages_cursor.seek_by_key_subkey(address, entry.key)? {
                     if db_entry.key == entry.key {
+                        existed = true;
                         storages_cursor.delete_current()?;
                     }
                 }
@@ -2036,9 +2487,14 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
                 if !entry.value.is_zero() {
                     storages_cursor.upsert(address, &entry)?;
                 }
+                state_size.storage_slot_changed(existed, !entry.value.is_zero());
             }
         }
 
+        if track_state_size {
+            apply_state_size_delta(self.tx_ref(), state_size)?;
+        }
+
         Ok(())
     }
 
@@ -2140,7 +2596,10 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
         )?;
 
         // iterate over local plain state remove all account and all storages.
+        let mut state_size = StateSizeDelta::default();
         for (address, (old_account, new_account, storage)) in &state {
+            state_size.account_changed(new_account.as_ref(), old_account.as_ref());
+
             // revert account if needed.
             if old_account != new_account {
                 let existing_entry = plain_accounts_cursor.seek_exact(*address)?;
@@ -2152,7 +2611,11 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
             }
 
             // revert storages
-            for (storage_key, (old_storage_value, _new_storage_value)) in storage {
+            for (storage_key, (old_storage_value, new_storage_value)) in storage {
+                state_size.storage_slot_changed(
+                    !new_storage_value.is_zero(),
+                    !old_storage_value.is_zero(),
+                );
                 let storage_entry = StorageEntry { key: *storage_key, value: *old_storage_value };
                 // delete previous value
                 // TODO: This does not use dupsort features
@@ -2170,6 +2633,7 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
                 }
             }
         }
+        apply_state_size_delta(&self.tx, state_size)?;
 
         self.remove_receipts_from(from_transaction_num, block, remove_receipts_from)?;
 
@@ -2240,7 +2704,10 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
         )?;
 
         // iterate over local plain state remove all account and all storages.
+        let mut state_size = StateSizeDelta::default();
         for (address, (old_account, new_account, storage)) in &state {
+            state_size.account_changed(new_account.as_ref(), old_account.as_ref());
+
             // revert account if needed.
             if old_account != new_account {
                 let existing_entry = plain_accounts_cursor.seek_exact(*address)?;
@@ -2252,7 +2719,11 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
             }
 
             // revert storages
-            for (storage_key, (old_storage_value, _new_storage_value)) in storage {
+            for (storage_key, (old_storage_value, new_storage_value)) in storage {
+                state_size.storage_slot_changed(
+                    !new_storage_value.is_zero(),
+                    !old_storage_value.is_zero(),
+                );
                 let storage_entry = StorageEntry { key: *storage_key, value: *old_storage_value };
                 // delete previous value
                 // TODO: This does not use dupsort features
@@ -2270,6 +2741,7 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
                 }
             }
         }
+        apply_state_size_delta(&self.tx, state_size)?;
 
         // Collect receipts into tuples (tx_num, receipt) to correctly handle pruned receipts
         let mut receipts_iter = self
@@ -2763,6 +3235,69 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HistoryWriter for DatabaseProvi
 
         Ok(())
     }
+
+    fn index_logs<'a>(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        logs: impl IntoIterator<Item = (BlockNumber, &'a Log)>,
+    ) -> ProviderResult<()> {
+        let Some(checkpoint) = self.get_stage_checkpoint(StageId::INDEX_LOGS)? else {
+            return Ok(())
+        };
+        if checkpoint.block_number == 0 || checkpoint.block_number + 1 != *range.start() {
+            return Ok(())
+        }
+
+        let mut addresses = BTreeMap::<Address, Vec<BlockNumber>>::new();
+        let mut topics = BTreeMap::<B256, Vec<BlockNumber>>::new();
+        // a block usually contains many logs with the same key
+        let insert = |blocks: &mut Vec<BlockNumber>, block_number| {
+            if blocks.last() != Some(&block_number) {
+                blocks.push(block_number);
+            }
+        };
+        for (block_number, log) in logs {
+            insert(addresses.entry(log.address).or_default(), block_number);
+            for topic in log.topics() {
+                insert(topics.entry(*topic).or_default(), block_number);
+            }
+        }
+
+        self.append_history_index::<_, tables::LogAddressIndex>(addresses, ShardedKey::new)?;
+        self.append_history_index::<_, tables::LogTopicIndex>(topics, ShardedKey::new)?;
+        self.save_stage_checkpoint(
+            StageId::INDEX_LOGS,
+            StageCheckpoint { block_number: *range.end(), ..checkpoint },
+        )
+    }
+
+    fn unwind_log_indices<'a>(
+        &self,
+        logs: impl IntoIterator<Item = &'a Log>,
+        block_number: BlockNumber,
+    ) -> ProviderResult<usize> {
+        let mut addresses = BTreeSet::new();
+        let mut topics = BTreeSet::new();
+        let mut walked = 0;
+        for log in logs {
+            addresses.insert(log.address);
+            topics.extend(log.topics().iter().copied());
+            walked += 1;
+        }
+
+        unwind_log_index_shards::<_, tables::LogAddressIndex, _>(
+            &mut self.tx.cursor_write::<tables::LogAddressIndex>()?,
+            addresses,
+            block_number,
+        )?;
+        unwind_log_index_shards::<_, tables::LogTopicIndex, _>(
+            &mut self.tx.cursor_write::<tables::LogTopicIndex>()?,
+            topics,
+            block_number,
+        )?;
+
+        Ok(walked)
+    }
 }
 
 impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider + 'static> BlockExecutionWriter
@@ -2776,6 +3311,8 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider + 'static> BlockExecu
         let range = block + 1..=self.last_block_number()?;
 
         self.unwind_trie_state_range(range.clone())?;
+        self.unwind_log_index_above(block)?;
+        self.unwind_gas_usage_above(block)?;
 
         // get execution res
         let execution_state = self.take_state_above(block, remove_from)?;
@@ -2800,6 +3337,8 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider + 'static> BlockExecu
         let range = block + 1..=self.last_block_number()?;
 
         self.unwind_trie_state_range(range)?;
+        self.unwind_log_index_above(block)?;
+        self.unwind_gas_usage_above(block)?;
 
         // remove execution res
         self.remove_state_above(block, remove_from)?;
@@ -3165,7 +3704,8 @@ impl<TX: DbTx + 'static, N: NodeTypes> ChainStateBlockReader for DatabaseProvide
             .take(1)
             .collect::<Result<BTreeMap<tables::ChainStateKey, BlockNumber>, _>>()?;
 
-        let last_finalized_block_number = finalized_blocks.pop_first().map(|pair| pair.1);
+        let last_finalized_block_number =
+            finalized_blocks.pop_first().map(|pair| self.cap_to_consistent_tip(pair.1));
         Ok(last_finalized_block_number)
     }
 
@@ -3177,11 +3717,175 @@ impl<TX: DbTx + 'static, N: NodeTypes> ChainStateBlockReader for DatabaseProvide
             .take(1)
             .collect::<Result<BTreeMap<tables::ChainStateKey, BlockNumber>, _>>()?;
 
-        let last_finalized_block_number = finalized_blocks.pop_first().map(|pair| pair.1);
+        let last_finalized_block_number =
+            finalized_blocks.pop_first().map(|pair| self.cap_to_consistent_tip(pair.1));
         Ok(last_finalized_block_number)
     }
 }
 
+impl<TX: DbTx + 'static, N: NodeTypes> StateSizeReader for DatabaseProvider<TX, N> {
+    fn state_size(&self) -> ProviderResult<Option<StateSize>> {
+        let Some(mut size) = read_state_size(&self.tx)? else { return Ok(None) };
+        size.block_number =
+            self.get_stage_checkpoint(StageId::Execution)?.unwrap_or_default().block_number;
+        Ok(Some(size))
+    }
+}
+
+impl<TX: DbTx + 'static, N: NodeTypes> GasUsageReader for DatabaseProvider<TX, N> {
+    fn top_gas_consumers(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        limit: usize,
+    ) -> ProviderResult<Option<TopGasConsumers>> {
+        let (Some(indexed_from), Some(indexed_to)) = (
+            self.tx.get::<tables::ChainState>(tables::ChainStateKey::GasUsageIndexedFrom)?,
+            self.tx.get::<tables::ChainState>(tables::ChainStateKey::GasUsageIndexedTo)?,
+        ) else {
+            return Ok(None)
+        };
+
+        let from_block = gas_usage_epoch(*range.start()).max(indexed_from);
+        let to_block = gas_usage_epoch(*range.end())
+            .saturating_add(GAS_USAGE_EPOCH_LENGTH - 1)
+            .min(indexed_to);
+        if from_block > to_block {
+            return Ok(None)
+        }
+
+        let mut usage = HashMap::<Address, u64>::default();
+        for entry in self.tx.cursor_read::<tables::ContractGasUsage>()?.walk_range(
+            BlockNumberAddress::range(gas_usage_epoch(from_block)..=gas_usage_epoch(to_block)),
+        )? {
+            let (key, gas_used) = entry?;
+            let total = usage.entry(key.address()).or_default();
+            *total = total.saturating_add(gas_used);
+        }
+
+        let mut consumers = usage
+            .into_iter()
+            .map(|(address, gas_used)| ContractGasUsage { address, gas_used })
+            .collect::<Vec<_>>();
+        consumers.sort_unstable_by(|a, b| {
+            b.gas_used.cmp(&a.gas_used).then_with(|| a.address.cmp(&b.address))
+        });
+        consumers.truncate(limit);
+
+        Ok(Some(TopGasConsumers { from_block, to_block, consumers }))
+    }
+}
+
+impl<TX: DbTx + DbTxMut + 'static, N: NodeTypesForProvider> GasUsageWriter
+    for DatabaseProvider<TX, N>
+{
+    fn gas_usage_indexed_to(&self) -> ProviderResult<Option<BlockNumber>> {
+        Ok(self.tx.get::<tables::ChainState>(tables::ChainStateKey::GasUsageIndexedTo)?)
+    }
+
+    fn init_gas_usage_index(&self, block: BlockNumber) -> ProviderResult<()> {
+        if let Some(indexed_to) = self.gas_usage_indexed_to()? {
+            if indexed_to >= block {
+                return Ok(())
+            }
+            debug!(target: "providers::db", indexed_to, block, "Restarting lagging gas usage index");
+        }
+        self.restart_gas_usage_index(block)
+    }
+
+    fn remove_gas_usage_index(&self) -> ProviderResult<()> {
+        self.tx.clear::<tables::ContractGasUsage>()?;
+        self.tx.delete::<tables::ChainState>(tables::ChainStateKey::GasUsageIndexedFrom, None)?;
+        self.tx.delete::<tables::ChainState>(tables::ChainStateKey::GasUsageIndexedTo, None)?;
+        Ok(())
+    }
+
+    fn index_gas_usage(
+        &self,
+        first_block: BlockNumber,
+        blocks: &[Vec<(Address, u64)>],
+    ) -> ProviderResult<()> {
+        let Some(indexed_to) = self.gas_usage_indexed_to()? else { return Ok(()) };
+        if blocks.is_empty() || indexed_to + 1 != first_block {
+            return Ok(())
+        }
+
+        let mut usage = BTreeMap::<BlockNumberAddress, u64>::new();
+        for (idx, block) in blocks.iter().enumerate() {
+            let epoch = gas_usage_epoch(first_block + idx as u64);
+            for (address, gas_used) in block {
+                *usage.entry(BlockNumberAddress((epoch, *address))).or_default() += gas_used;
+            }
+        }
+
+        let mut cursor = self.tx.cursor_write::<tables::ContractGasUsage>()?;
+        for (key, gas_used) in usage {
+            let current = cursor.seek_exact(key)?.map(|(_, gas_used)| gas_used).unwrap_or_default();
+            cursor.upsert(key, &current.saturating_add(gas_used))?;
+        }
+
+        self.tx.put::<tables::ChainState>(
+            tables::ChainStateKey::GasUsageIndexedTo,
+            first_block + blocks.len() as u64 - 1,
+        )?;
+        Ok(())
+    }
+
+    fn unwind_gas_usage_above(&self, block: BlockNumber) -> ProviderResult<()> {
+        let Some(indexed_to) = self.gas_usage_indexed_to()? else { return Ok(()) };
+        if indexed_to <= block {
+            return Ok(())
+        }
+
+        let indexed_from = self
+            .tx
+            .get::<tables::ChainState>(tables::ChainStateKey::GasUsageIndexedFrom)?
+            .unwrap_or_default();
+        let first_block = (block + 1).max(indexed_from);
+        if first_block <= indexed_to {
+            let range = first_block..=indexed_to;
+            let indices = self.block_body_indices_range(range.clone())?;
+            let transactions = self.transactions_by_block_range(range.clone())?;
+            let receipts = self.receipts_by_block_range(range)?;
+
+            // The gas usage of blocks with pruned transactions or receipts can't be subtracted.
+            let complete = indices.len() == transactions.len() &&
+                indices.len() == receipts.len() &&
+                indices.iter().zip(&transactions).zip(&receipts).all(
+                    |((indices, transactions), receipts)| {
+                        indices.tx_count() as usize == transactions.len() &&
+                            transactions.len() == receipts.len()
+                    },
+                );
+            if !complete {
+                debug!(target: "providers::db", block, "Restarting gas usage index, receipts of unwound blocks are pruned");
+                return self.restart_gas_usage_index(block)
+            }
+
+            let mut usage = BTreeMap::<BlockNumberAddress, u64>::new();
+            for (idx, (transactions, receipts)) in transactions.iter().zip(&receipts).enumerate() {
+                let epoch = gas_usage_epoch(first_block + idx as u64);
+                for (address, gas_used) in block_gas_usage(transactions, receipts) {
+                    *usage.entry(BlockNumberAddress((epoch, address))).or_default() += gas_used;
+                }
+            }
+
+            let mut cursor = self.tx.cursor_write::<tables::ContractGasUsage>()?;
+            for (key, gas_used) in usage {
+                let Some((_, current)) = cursor.seek_exact(key)? else { continue };
+                let remaining = current.saturating_sub(gas_used);
+                if remaining == 0 {
+                    cursor.delete_current()?;
+                } else {
+                    cursor.upsert(key, &remaining)?;
+                }
+            }
+        }
+
+        self.tx.put::<tables::ChainState>(tables::ChainStateKey::GasUsageIndexedTo, block)?;
+        Ok(())
+    }
+}
+
 impl<TX: DbTxMut, N: NodeTypes> ChainStateBlockWriter for DatabaseProvider<TX, N> {
     fn save_finalized_block_number(&self, block_number: BlockNumber) -> ProviderResult<()> {
         Ok(self
@@ -3223,8 +3927,181 @@ mod tests {
         test_utils::{blocks::BlockchainTestData, create_test_provider_factory},
         BlockWriter,
     };
+    use reth_chainspec::MAINNET;
+    use reth_db::test_utils::{create_test_rw_db, create_test_static_files_dir};
     use reth_testing_utils::generators::{self, random_block, BlockParams};
 
+    #[test]
+    fn test_expired_history() {
+        let (static_dir, _) = create_test_static_files_dir();
+        let static_file_provider = StaticFileProvider::read_write(static_dir.keep())
+            .unwrap()
+            .with_custom_blocks_per_file(1);
+        {
+            let mut writer =
+                static_file_provider.latest_writer(StaticFileSegment::Transactions).unwrap();
+            writer.increment_block(0).unwrap();
+            writer.increment_block(1).unwrap();
+            writer.commit().unwrap();
+        }
+        let factory = ProviderFactory::<crate::test_utils::MockNodeTypesWithDB>::new(
+            create_test_rw_db(),
+            MAINNET.clone(),
+            static_file_provider.clone(),
+        );
+
+        let provider_rw = factory.provider_rw().unwrap();
+        for block in 0..2 {
+            provider_rw
+                .tx_ref()
+                .put::<tables::BlockBodyIndices>(
+                    block,
+                    StoredBlockBodyIndices { first_tx_num: block, tx_count: 1 },
+                )
+                .unwrap();
+            provider_rw
+                .tx_ref()
+                .put::<tables::Receipts>(block, reth_ethereum_primitives::Receipt::default())
+                .unwrap();
+        }
+        provider_rw.commit().unwrap();
+
+        // the history of block 0 is expired
+        static_file_provider.delete_transactions_below(1).unwrap();
+        assert_eq!(static_file_provider.earliest_history_height(), 1);
+
+        let provider = factory.provider().unwrap();
+        let expired = |result: ProviderResult<()>| {
+            matches!(result, Err(ProviderError::HistoryExpired { block: 0, earliest: 1 }))
+        };
+        assert!(expired(provider.receipts_by_block(0.into()).map(drop)));
+        assert!(expired(provider.receipts_by_block_range(0..=1).map(drop)));
+        assert!(expired(provider.transactions_by_block(0.into()).map(drop)));
+        assert_eq!(provider.receipts_by_block(1.into()).unwrap().map(|r| r.len()), Some(1));
+        assert_eq!(provider.receipts_by_block_range(1..=1).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_migrate_stage_checkpoints() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        provider
+            .tx_ref()
+            .put::<tables::StageCheckpoints>("StaticFile".to_string(), Default::default())
+            .unwrap();
+        provider.save_stage_checkpoint(StageId::Headers, StageCheckpoint::new(10)).unwrap();
+
+        assert_eq!(provider.migrate_stage_checkpoints().unwrap(), vec!["StaticFile".to_string()]);
+        assert!(provider.migrate_stage_checkpoints().unwrap().is_empty());
+
+        let checkpoints = provider.get_all_checkpoints().unwrap();
+        assert_eq!(checkpoints, vec![(StageId::Headers.to_string(), StageCheckpoint::new(10))]);
+    }
+
+    #[test]
+    fn test_gas_usage_index() {
+        let factory = create_test_provider_factory();
+        let data = BlockchainTestData::default();
+
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw
+            .insert_block(
+                data.genesis.clone().try_recover().unwrap(),
+                crate::StorageLocation::Database,
+            )
+            .unwrap();
+        for i in 0..2 {
+            provider_rw
+                .insert_block(data.blocks[i].0.clone(), crate::StorageLocation::Database)
+                .unwrap();
+            provider_rw
+                .write_state(
+                    &data.blocks[i].1,
+                    crate::OriginalValuesKnown::No,
+                    crate::StorageLocation::Database,
+                )
+                .unwrap();
+        }
+
+        // not maintained yet
+        assert_eq!(provider_rw.top_gas_consumers(0..=2, 10).unwrap(), None);
+        provider_rw.init_gas_usage_index(0).unwrap();
+        assert_eq!(provider_rw.gas_usage_indexed_to().unwrap(), Some(0));
+
+        let usage = provider_rw
+            .transactions_by_block_range(1..=2)
+            .unwrap()
+            .iter()
+            .zip(provider_rw.receipts_by_block_range(1..=2).unwrap())
+            .map(|(transactions, receipts)| block_gas_usage(transactions, &receipts))
+            .collect::<Vec<_>>();
+        let mut expected = BTreeMap::<Address, u64>::new();
+        for (address, gas_used) in usage.iter().flatten() {
+            *expected.entry(*address).or_default() += gas_used;
+        }
+        assert!(!expected.is_empty());
+
+        // blocks that don't follow the indexed ones are ignored
+        provider_rw.index_gas_usage(2, &usage[1..]).unwrap();
+        assert_eq!(provider_rw.gas_usage_indexed_to().unwrap(), Some(0));
+
+        provider_rw.index_gas_usage(1, &usage).unwrap();
+        assert_eq!(provider_rw.gas_usage_indexed_to().unwrap(), Some(2));
+
+        let top = provider_rw.top_gas_consumers(0..=2, 10).unwrap().unwrap();
+        assert_eq!((top.from_block, top.to_block), (1, 2));
+        assert_eq!(
+            top.consumers.iter().map(|c| (c.address, c.gas_used)).collect::<BTreeMap<_, _>>(),
+            expected
+        );
+        assert!(top.consumers.windows(2).all(|w| w[0].gas_used >= w[1].gas_used));
+        assert_eq!(provider_rw.top_gas_consumers(0..=2, 1).unwrap().unwrap().consumers.len(), 1);
+
+        // unwinding removes the gas usage of the unwound blocks
+        provider_rw.unwind_gas_usage_above(0).unwrap();
+        assert_eq!(provider_rw.gas_usage_indexed_to().unwrap(), Some(0));
+        assert_eq!(provider_rw.top_gas_consumers(0..=2, 10).unwrap(), None);
+        assert!(provider_rw
+            .tx_ref()
+            .cursor_read::<tables::ContractGasUsage>()
+            .unwrap()
+            .first()
+            .unwrap()
+            .is_none());
+
+        // unwinding blocks with pruned receipts restarts the index
+        provider_rw.index_gas_usage(1, &usage).unwrap();
+        assert!(provider_rw.top_gas_consumers(0..=2, 10).unwrap().is_some());
+        provider_rw
+            .tx_ref()
+            .clear::<tables::Receipts<reth_ethereum_primitives::Receipt>>()
+            .unwrap();
+        provider_rw.unwind_gas_usage_above(1).unwrap();
+        assert_eq!(provider_rw.gas_usage_indexed_to().unwrap(), Some(1));
+        assert_eq!(provider_rw.top_gas_consumers(0..=2, 10).unwrap(), None);
+        assert!(provider_rw
+            .tx_ref()
+            .cursor_read::<tables::ContractGasUsage>()
+            .unwrap()
+            .first()
+            .unwrap()
+            .is_none());
+
+        // a lagging index is restarted instead of leaving a gap
+        provider_rw.init_gas_usage_index(2).unwrap();
+        assert_eq!(provider_rw.gas_usage_indexed_to().unwrap(), Some(2));
+        assert_eq!(
+            provider_rw
+                .tx_ref()
+                .get::<tables::ChainState>(tables::ChainStateKey::GasUsageIndexedFrom),
+            Ok(Some(3))
+        );
+
+        provider_rw.remove_gas_usage_index().unwrap();
+        assert_eq!(provider_rw.gas_usage_indexed_to().unwrap(), None);
+    }
+
     #[test]
     fn test_receipts_by_block_range_empty_range() {
         let factory = create_test_provider_factory();
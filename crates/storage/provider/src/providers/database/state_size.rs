@@ -0,0 +1,310 @@
+//! Size statistics of the plain state, maintained incrementally on every state write.
+//!
+//! The statistics are stored in the [`tables::ChainState`] table. They are only maintained once
+//! they were initialized with [`count_state_size`], which scans the plain state.
+
+use alloy_primitives::KECCAK_EMPTY;
+use metrics::Gauge;
+use reth_db_api::{
+    cursor::DbCursorRO,
+    tables::{self, ChainStateKey},
+    transaction::{DbTx, DbTxMut},
+};
+use reth_metrics::Metrics;
+use reth_primitives_traits::Account;
+use reth_storage_api::StateSize;
+use reth_storage_errors::provider::ProviderResult;
+
+/// Change of the state size statistics caused by a state write.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct StateSizeDelta {
+    accounts: i64,
+    contracts: i64,
+    storage_slots: i64,
+    bytecodes: i64,
+    bytecode_bytes: i64,
+}
+
+impl StateSizeDelta {
+    /// Records an account that changed from `previous` to `new`, `None` if it didn't exist.
+    pub(crate) fn account_changed(&mut self, previous: Option<&Account>, new: Option<&Account>) {
+        self.accounts += new.is_some() as i64 - previous.is_some() as i64;
+        self.contracts +=
+            new.is_some_and(is_contract) as i64 - previous.is_some_and(is_contract) as i64;
+    }
+
+    /// Records a storage slot that changed from `existed` to `exists`.
+    pub(crate) fn storage_slot_changed(&mut self, existed: bool, exists: bool) {
+        self.storage_slots += exists as i64 - existed as i64;
+    }
+
+    /// Records storage slots that were removed when an account's storage was wiped.
+    pub(crate) fn storage_wiped(&mut self, slots: usize) {
+        self.storage_slots -= slots as i64;
+    }
+
+    /// Records a new bytecode of the given size.
+    pub(crate) fn bytecode_inserted(&mut self, len: usize) {
+        self.bytecodes += 1;
+        self.bytecode_bytes += len as i64;
+    }
+
+    /// Applies the delta to the statistics.
+    fn apply(&self, size: &mut StateSize) {
+        size.accounts = size.accounts.saturating_add_signed(self.accounts);
+        size.contracts = size.contracts.saturating_add_signed(self.contracts);
+        size.storage_slots = size.storage_slots.saturating_add_signed(self.storage_slots);
+        size.bytecodes = size.bytecodes.saturating_add_signed(self.bytecodes);
+        size.bytecode_bytes = size.bytecode_bytes.saturating_add_signed(self.bytecode_bytes);
+    }
+}
+
+/// Metrics for the size of the state.
+#[derive(Metrics)]
+#[metrics(scope = "state_size")]
+pub(crate) struct StateSizeMetrics {
+    /// The number of accounts
+    accounts: Gauge,
+    /// The number of accounts with code
+    contracts: Gauge,
+    /// The number of non-zero storage slots
+    storage_slots: Gauge,
+    /// The number of distinct bytecodes
+    bytecodes: Gauge,
+    /// The total size of the distinct bytecodes in bytes
+    bytecode_bytes: Gauge,
+}
+
+impl StateSizeMetrics {
+    /// Records the state size statistics.
+    pub(crate) fn record(&self, size: &StateSize) {
+        self.accounts.set(size.accounts as f64);
+        self.contracts.set(size.contracts as f64);
+        self.storage_slots.set(size.storage_slots as f64);
+        self.bytecodes.set(size.bytecodes as f64);
+        self.bytecode_bytes.set(size.bytecode_bytes as f64);
+    }
+}
+
+/// Returns `true` if the account has code.
+fn is_contract(account: &Account) -> bool {
+    account.bytecode_hash.is_some_and(|hash| hash != KECCAK_EMPTY)
+}
+
+/// Returns `true` if the state size statistics are maintained.
+pub(crate) fn is_state_size_tracked<TX: DbTx>(tx: &TX) -> ProviderResult<bool> {
+    Ok(tx.get::<tables::ChainState>(ChainStateKey::StateSizeAccounts)?.is_some())
+}
+
+/// Reads the stored state size statistics, without the block number.
+///
+/// Returns `None` if the statistics are not maintained.
+pub(crate) fn read_state_size<TX: DbTx>(tx: &TX) -> ProviderResult<Option<StateSize>> {
+    let Some(accounts) = tx.get::<tables::ChainState>(ChainStateKey::StateSizeAccounts)? else {
+        return Ok(None)
+    };
+    let get =
+        |key| -> ProviderResult<u64> { Ok(tx.get::<tables::ChainState>(key)?.unwrap_or_default()) };
+    Ok(Some(StateSize {
+        block_number: 0,
+        accounts,
+        contracts: get(ChainStateKey::StateSizeContracts)?,
+        storage_slots: get(ChainStateKey::StateSizeStorageSlots)?,
+        bytecodes: get(ChainStateKey::StateSizeBytecodes)?,
+        bytecode_bytes: get(ChainStateKey::StateSizeBytecodeBytes)?,
+    }))
+}
+
+/// Stores the state size statistics.
+pub(crate) fn write_state_size<TX: DbTxMut>(tx: &TX, size: &StateSize) -> ProviderResult<()> {
+    tx.put::<tables::ChainState>(ChainStateKey::StateSizeAccounts, size.accounts)?;
+    tx.put::<tables::ChainState>(ChainStateKey::StateSizeContracts, size.contracts)?;
+    tx.put::<tables::ChainState>(ChainStateKey::StateSizeStorageSlots, size.storage_slots)?;
+    tx.put::<tables::ChainState>(ChainStateKey::StateSizeBytecodes, size.bytecodes)?;
+    tx.put::<tables::ChainState>(ChainStateKey::StateSizeBytecodeBytes, size.bytecode_bytes)?;
+    Ok(())
+}
+
+/// Applies the delta to the stored state size statistics, if they are maintained.
+pub(crate) fn apply_state_size_delta<TX: DbTx + DbTxMut>(
+    tx: &TX,
+    delta: StateSizeDelta,
+) -> ProviderResult<()> {
+    if delta == StateSizeDelta::default() {
+        return Ok(())
+    }
+    let Some(mut size) = read_state_size(tx)? else { return Ok(()) };
+    delta.apply(&mut size);
+    write_state_size(tx, &size)
+}
+
+/// Computes the state size statistics by scanning the plain state and the bytecodes.
+pub(crate) fn count_state_size<TX: DbTx>(tx: &TX) -> ProviderResult<StateSize> {
+    let mut size = StateSize::default();
+
+    for entry in tx.cursor_read::<tables::PlainAccountState>()?.walk(None)? {
+        let (_, account) = entry?;
+        size.accounts += 1;
+        size.contracts += is_contract(&account) as u64;
+    }
+
+    // walks the duplicates of all addresses
+    for entry in tx.cursor_read::<tables::PlainStorageState>()?.walk(None)? {
+        entry?;
+        size.storage_slots += 1;
+    }
+
+    for entry in tx.cursor_read::<tables::Bytecodes>()?.walk(None)? {
+        let (_, bytecode) = entry?;
+        size.bytecodes += 1;
+        size.bytecode_bytes += bytecode.original_byte_slice().len() as u64;
+    }
+
+    Ok(size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_utils::create_test_provider_factory, StateWriter, StorageLocation};
+    use alloy_primitives::{map::HashMap, Address, B256, U256};
+    use reth_db_api::{cursor::DbCursorRW, models::StoredBlockBodyIndices};
+    use reth_execution_types::ExecutionOutcome;
+    use reth_primitives_traits::StorageEntry;
+    use revm_database::{states::bundle_state::BundleRetention, OriginalValuesKnown, State};
+    use revm_database_interface::DatabaseCommit;
+    use revm_state::{
+        Account as RevmAccount, AccountInfo as RevmAccountInfo, AccountStatus, EvmStorageSlot,
+    };
+
+    #[test]
+    fn applies_delta() {
+        let eoa = Account::default();
+        let contract = Account { bytecode_hash: Some(B256::with_last_byte(1)), ..eoa };
+
+        let mut delta = StateSizeDelta::default();
+        delta.account_changed(None, Some(&eoa));
+        delta.account_changed(None, Some(&contract));
+        delta.account_changed(Some(&contract), None);
+        delta.storage_slot_changed(false, true);
+        delta.storage_slot_changed(true, true);
+        delta.storage_wiped(1);
+        delta.bytecode_inserted(10);
+
+        let mut size =
+            StateSize { accounts: 5, contracts: 2, storage_slots: 3, ..Default::default() };
+        delta.apply(&mut size);
+        assert_eq!(
+            size,
+            StateSize {
+                block_number: 0,
+                accounts: 6,
+                contracts: 2,
+                storage_slots: 3,
+                bytecodes: 1,
+                bytecode_bytes: 10,
+            }
+        );
+    }
+
+    #[test]
+    fn tracking_matches_rescan() {
+        let factory = create_test_provider_factory();
+        let provider = factory.database_provider_rw().unwrap();
+        let rescan = || count_state_size(provider.tx_ref()).unwrap();
+        let tracked = || read_state_size(provider.tx_ref()).unwrap().unwrap();
+
+        let (address_a, address_b, address_c) =
+            (Address::with_last_byte(1), Address::with_last_byte(2), Address::with_last_byte(3));
+        let info = RevmAccountInfo { nonce: 1, ..Default::default() };
+        let slot = |slot: u8| U256::from(slot);
+
+        // several accounts have storage before the statistics are initialized
+        {
+            let mut accounts =
+                provider.tx_ref().cursor_write::<tables::PlainAccountState>().unwrap();
+            let mut storage =
+                provider.tx_ref().cursor_write::<tables::PlainStorageState>().unwrap();
+            for address in [address_a, address_b] {
+                accounts.upsert(address, &Account { nonce: 1, ..Default::default() }).unwrap();
+                for key in 1..=2 {
+                    let entry =
+                        StorageEntry { key: B256::with_last_byte(key), value: U256::from(1) };
+                    storage.upsert(address, &entry).unwrap();
+                }
+            }
+        }
+        let initial = provider.init_state_size().unwrap();
+        assert_eq!((initial.accounts, initial.storage_slots), (2, 4));
+        assert_eq!(tracked(), rescan());
+
+        // block 1 clears and sets slots, destroys an account and creates another one
+        let mut state = State::builder().with_bundle_update().build();
+        for address in [address_a, address_b] {
+            state.insert_account_with_storage(
+                address,
+                info.clone(),
+                HashMap::from_iter([(slot(1), U256::from(1)), (slot(2), U256::from(1))]),
+            );
+        }
+        state.insert_not_existing(address_c);
+        state.commit(HashMap::from_iter([
+            (
+                address_a,
+                RevmAccount {
+                    status: AccountStatus::Touched,
+                    info: info.clone(),
+                    storage: HashMap::from_iter([
+                        (
+                            slot(1),
+                            EvmStorageSlot { original_value: U256::from(1), ..Default::default() },
+                        ),
+                        (
+                            slot(3),
+                            EvmStorageSlot { present_value: U256::from(5), ..Default::default() },
+                        ),
+                    ]),
+                    transaction_id: 0,
+                },
+            ),
+            (
+                address_b,
+                RevmAccount {
+                    status: AccountStatus::Touched | AccountStatus::SelfDestructed,
+                    info: RevmAccountInfo::default(),
+                    storage: HashMap::default(),
+                    transaction_id: 0,
+                },
+            ),
+            (
+                address_c,
+                RevmAccount {
+                    status: AccountStatus::Touched | AccountStatus::Created,
+                    info: info.clone(),
+                    storage: HashMap::from_iter([(
+                        slot(1),
+                        EvmStorageSlot { present_value: U256::from(7), ..Default::default() },
+                    )]),
+                    transaction_id: 0,
+                },
+            ),
+        ]));
+        state.merge_transitions(BundleRetention::Reverts);
+        let outcome = ExecutionOutcome::new(state.take_bundle(), Default::default(), 1, Vec::new());
+        provider
+            .write_state(&outcome, OriginalValuesKnown::Yes, StorageLocation::Database)
+            .unwrap();
+        assert_eq!((rescan().accounts, rescan().storage_slots), (2, 3));
+        assert_eq!(tracked(), rescan());
+
+        // reverting block 1 restores the initial statistics
+        provider.tx_ref().put::<tables::CanonicalHeaders>(1, B256::ZERO).unwrap();
+        provider
+            .tx_ref()
+            .put::<tables::BlockBodyIndices>(1, StoredBlockBodyIndices::default())
+            .unwrap();
+        provider.remove_state_above(0, StorageLocation::Database).unwrap();
+        assert_eq!(rescan(), StateSize { block_number: 0, ..initial });
+        assert_eq!(tracked(), rescan());
+    }
+}
@@ -9,16 +9,23 @@ pub use database::*;
 
 mod static_file;
 pub use static_file::{
-    StaticFileAccess, StaticFileJarProvider, StaticFileProvider, StaticFileProviderRW,
-    StaticFileProviderRWRefMut, StaticFileWriter,
+    SegmentDirectories, StaticFileAccess, StaticFileBackend, StaticFileJarProvider,
+    StaticFileProvider, StaticFileProviderRW, StaticFileProviderRWRefMut, StaticFileWriter,
+    DEFAULT_BACKEND_CACHE_SIZE, RECOMPRESSION_STAGING_DIR, SEGMENT_DIRECTORIES_FILE_NAME,
 };
 
 mod state;
 pub use state::{
+    bytecode_cache::{BytecodeCache, CachedBytecodeStateProvider},
     historical::{HistoricalStateProvider, HistoricalStateProviderRef, LowestAvailableBlocks},
     latest::{LatestStateProvider, LatestStateProviderRef},
+    overlay::{OverlayStateProvider, OverlayStateProviderRef},
+    query_stats::{QueryRecordingStateProvider, QueryStats},
 };
 
+mod blob_sidecars;
+pub use blob_sidecars::BlobSidecarStore;
+
 mod consistent_view;
 pub use consistent_view::{ConsistentDbView, ConsistentViewError};
 
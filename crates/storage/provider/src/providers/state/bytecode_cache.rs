@@ -0,0 +1,374 @@
+//! Cache of decompressed bytecodes, shared by the state providers of a
+//! [`ProviderFactory`](crate::ProviderFactory).
+
+use alloy_primitives::{
+    map::{DefaultHashBuilder, HashMap},
+    Address, BlockNumber, Bytes, StorageKey, StorageValue, B256,
+};
+use mini_moka::sync::CacheBuilder;
+use reth_db_api::{cursor::DbCursorRO, models::BlockNumberAddress, tables, transaction::DbTx};
+use reth_metrics::{
+    metrics::{Counter, Gauge},
+    Metrics,
+};
+use reth_primitives_traits::{Account, Bytecode};
+use reth_storage_api::{
+    AccountReader, BlockHashReader, BytecodeReader, HashedPostStateProvider, StateProofProvider,
+    StateProvider, StateProviderBox, StateRootProvider, StorageRootProvider,
+};
+use reth_storage_errors::provider::ProviderResult;
+use reth_trie::{
+    updates::TrieUpdates, AccountProof, HashedPostState, HashedStorage, MultiProof,
+    MultiProofTargets, StorageMultiProof, StorageProof, TrieInput,
+};
+use revm_database::BundleState;
+use std::sync::Arc;
+
+/// Metrics for the bytecode cache.
+#[derive(Metrics)]
+#[metrics(scope = "storage.providers.bytecode_cache")]
+struct BytecodeCacheMetrics {
+    /// Number of bytecode reads served from the cache
+    hits: Counter,
+    /// Number of bytecode reads that were decompressed from the database
+    misses: Counter,
+    /// Number of bytecodes inserted by pre-warming
+    prewarmed: Counter,
+    /// Number of cached bytecodes
+    entries: Gauge,
+}
+
+/// Bounded cache of bytecodes by code hash.
+///
+/// Bytecodes are stored compressed in the database and are decompressed on every read, which
+/// adds up for call-heavy workloads that repeatedly load the same contracts. Bytecodes are
+/// content-addressed, so the cache is valid for the state of any block and is never invalidated.
+/// This is the only bytecode cache, state providers that cache the state of a block, like the hot
+/// state cache of the canonical head, read bytecodes through it.
+///
+/// Reads and inserts don't block each other, the least recently used entries are evicted first.
+#[derive(Debug, Clone)]
+pub struct BytecodeCache {
+    inner: Arc<BytecodeCacheInner>,
+}
+
+#[derive(Debug)]
+struct BytecodeCacheInner {
+    /// The cached bytecodes.
+    bytecodes: mini_moka::sync::Cache<B256, Bytecode, DefaultHashBuilder>,
+    /// Metrics for the cache.
+    metrics: BytecodeCacheMetrics,
+}
+
+impl BytecodeCache {
+    /// The default number of recent blocks inspected by [`BytecodeCache::prewarm`].
+    pub const DEFAULT_PREWARM_BLOCKS: u64 = 256;
+
+    /// Creates a new cache that holds at most `max_entries` bytecodes.
+    pub fn new(max_entries: u32) -> Self {
+        Self {
+            inner: Arc::new(BytecodeCacheInner {
+                bytecodes: CacheBuilder::new(max_entries as u64)
+                    .build_with_hasher(Default::default()),
+                metrics: BytecodeCacheMetrics::default(),
+            }),
+        }
+    }
+
+    /// Returns the number of cached bytecodes.
+    ///
+    /// The count is updated lazily and may lag behind recent inserts and evictions.
+    pub fn len(&self) -> usize {
+        self.inner.bytecodes.entry_count() as usize
+    }
+
+    /// Returns `true` if no bytecodes are cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the cached bytecode and marks it as recently used.
+    pub fn get(&self, code_hash: &B256) -> Option<Bytecode> {
+        let bytecode = self.inner.bytecodes.get(code_hash);
+        if bytecode.is_some() {
+            self.inner.metrics.hits.increment(1);
+        } else {
+            self.inner.metrics.misses.increment(1);
+        }
+        bytecode
+    }
+
+    /// Caches the bytecode, evicting the least recently used one if the cache is full.
+    pub fn insert(&self, code_hash: B256, bytecode: Bytecode) {
+        self.inner.bytecodes.insert(code_hash, bytecode);
+        self.inner.metrics.entries.set(self.inner.bytecodes.entry_count() as f64);
+    }
+
+    /// Wraps the state provider, so that its bytecode reads go through the cache.
+    pub fn wrap(&self, provider: StateProviderBox) -> StateProviderBox {
+        Box::new(CachedBytecodeStateProvider { cache: self.clone(), inner: provider })
+    }
+
+    /// Pre-warms the cache with the bytecodes of the `top_n` most used contracts in the `blocks`
+    /// blocks up to and including `tip`.
+    ///
+    /// Contracts are ranked by the number of storage slots they changed. Returns the number of
+    /// bytecodes inserted into the cache.
+    pub fn prewarm<TX: DbTx>(
+        &self,
+        tx: &TX,
+        tip: BlockNumber,
+        blocks: u64,
+        top_n: usize,
+    ) -> ProviderResult<usize> {
+        if blocks == 0 || top_n == 0 {
+            return Ok(0)
+        }
+
+        let start = tip.saturating_sub(blocks - 1);
+        let mut usage = HashMap::<Address, usize>::default();
+        for entry in tx
+            .cursor_read::<tables::StorageChangeSets>()?
+            .walk_range(BlockNumberAddress::range(start..=tip))?
+        {
+            let (key, _) = entry?;
+            *usage.entry(key.address()).or_default() += 1;
+        }
+
+        let mut contracts = usage.into_iter().collect::<Vec<_>>();
+        contracts.sort_unstable_by(|a, b| b.1.cmp(&a.1));
+
+        let mut inserted = 0;
+        for (address, _) in contracts.into_iter().take(top_n) {
+            let Some(code_hash) = tx
+                .get_by_encoded_key::<tables::PlainAccountState>(&address)?
+                .and_then(|account| account.bytecode_hash)
+            else {
+                continue
+            };
+            if self.inner.bytecodes.contains_key(&code_hash) {
+                continue
+            }
+            if let Some(bytecode) = tx.get_by_encoded_key::<tables::Bytecodes>(&code_hash)? {
+                self.insert(code_hash, bytecode);
+                inserted += 1;
+            }
+        }
+        self.inner.metrics.prewarmed.increment(inserted as u64);
+
+        Ok(inserted)
+    }
+}
+
+/// A state provider that reads bytecodes through the [`BytecodeCache`].
+#[expect(missing_debug_implementations)]
+pub struct CachedBytecodeStateProvider {
+    /// The shared cache.
+    cache: BytecodeCache,
+    /// The wrapped provider.
+    inner: StateProviderBox,
+}
+
+impl BytecodeReader for CachedBytecodeStateProvider {
+    fn bytecode_by_hash(&self, code_hash: &B256) -> ProviderResult<Option<Bytecode>> {
+        if let Some(bytecode) = self.cache.get(code_hash) {
+            return Ok(Some(bytecode))
+        }
+        let bytecode = self.inner.bytecode_by_hash(code_hash)?;
+        if let Some(bytecode) = &bytecode {
+            self.cache.insert(*code_hash, bytecode.clone());
+        }
+        Ok(bytecode)
+    }
+}
+
+impl AccountReader for CachedBytecodeStateProvider {
+    fn basic_account(&self, address: &Address) -> ProviderResult<Option<Account>> {
+        self.inner.basic_account(address)
+    }
+}
+
+impl StateProvider for CachedBytecodeStateProvider {
+    fn storage(
+        &self,
+        account: Address,
+        storage_key: StorageKey,
+    ) -> ProviderResult<Option<StorageValue>> {
+        self.inner.storage(account, storage_key)
+    }
+}
+
+impl BlockHashReader for CachedBytecodeStateProvider {
+    fn block_hash(&self, number: BlockNumber) -> ProviderResult<Option<B256>> {
+        self.inner.block_hash(number)
+    }
+
+    fn canonical_hashes_range(
+        &self,
+        start: BlockNumber,
+        end: BlockNumber,
+    ) -> ProviderResult<Vec<B256>> {
+        self.inner.canonical_hashes_range(start, end)
+    }
+}
+
+impl StateRootProvider for CachedBytecodeStateProvider {
+    fn state_root(&self, state: HashedPostState) -> ProviderResult<B256> {
+        self.inner.state_root(state)
+    }
+
+    fn state_root_from_nodes(&self, input: TrieInput) -> ProviderResult<B256> {
+        self.inner.state_root_from_nodes(input)
+    }
+
+    fn state_root_with_updates(
+        &self,
+        state: HashedPostState,
+    ) -> ProviderResult<(B256, TrieUpdates)> {
+        self.inner.state_root_with_updates(state)
+    }
+
+    fn state_root_from_nodes_with_updates(
+        &self,
+        input: TrieInput,
+    ) -> ProviderResult<(B256, TrieUpdates)> {
+        self.inner.state_root_from_nodes_with_updates(input)
+    }
+}
+
+impl StorageRootProvider for CachedBytecodeStateProvider {
+    fn storage_root(&self, address: Address, storage: HashedStorage) -> ProviderResult<B256> {
+        self.inner.storage_root(address, storage)
+    }
+
+    fn storage_proof(
+        &self,
+        address: Address,
+        slot: B256,
+        storage: HashedStorage,
+    ) -> ProviderResult<StorageProof> {
+        self.inner.storage_proof(address, slot, storage)
+    }
+
+    fn storage_multiproof(
+        &self,
+        address: Address,
+        slots: &[B256],
+        storage: HashedStorage,
+    ) -> ProviderResult<StorageMultiProof> {
+        self.inner.storage_multiproof(address, slots, storage)
+    }
+
+    fn storage_multiproof_from_nodes(
+        &self,
+        input: TrieInput,
+        address: Address,
+        slots: &[B256],
+    ) -> ProviderResult<StorageMultiProof> {
+        self.inner.storage_multiproof_from_nodes(input, address, slots)
+    }
+}
+
+impl StateProofProvider for CachedBytecodeStateProvider {
+    fn proof(
+        &self,
+        input: TrieInput,
+        address: Address,
+        slots: &[B256],
+    ) -> ProviderResult<AccountProof> {
+        self.inner.proof(input, address, slots)
+    }
+
+    fn multiproof(
+        &self,
+        input: TrieInput,
+        targets: MultiProofTargets,
+    ) -> ProviderResult<MultiProof> {
+        self.inner.multiproof(input, targets)
+    }
+
+    fn witness(&self, input: TrieInput, target: HashedPostState) -> ProviderResult<Vec<Bytes>> {
+        self.inner.witness(input, target)
+    }
+}
+
+impl HashedPostStateProvider for CachedBytecodeStateProvider {
+    fn hashed_post_state(&self, bundle_state: &BundleState) -> HashedPostState {
+        self.inner.hashed_post_state(bundle_state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_provider_factory;
+    use reth_db_api::transaction::DbTxMut;
+    use reth_primitives_traits::StorageEntry;
+    use reth_storage_api::{noop::NoopProvider, DBProvider};
+
+    #[test]
+    fn caches_bytecodes_read_through_providers() {
+        let factory = create_test_provider_factory();
+        let code_hash = B256::with_last_byte(1);
+        let bytecode = Bytecode::new_raw(Bytes::from_static(&[0x01]));
+        let provider = factory.provider_rw().unwrap();
+        provider.tx_ref().put::<tables::Bytecodes>(code_hash, bytecode.clone()).unwrap();
+        provider.commit().unwrap();
+
+        let cache = BytecodeCache::new(10);
+        let state = cache.wrap(factory.latest().unwrap());
+        assert!(cache.get(&code_hash).is_none());
+        assert_eq!(state.bytecode_by_hash(&code_hash).unwrap(), Some(bytecode.clone()));
+
+        // the bytecode is served to every provider wrapped by the cache
+        assert_eq!(cache.get(&code_hash), Some(bytecode));
+        let other = cache.wrap(Box::new(NoopProvider::default()));
+        assert!(other.bytecode_by_hash(&code_hash).unwrap().is_some());
+    }
+
+    #[test]
+    fn prewarms_most_used_contracts() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+        let tx = provider.tx_ref();
+
+        let hot = Address::with_last_byte(1);
+        let cold = Address::with_last_byte(2);
+        let (hot_code, cold_code) = (B256::with_last_byte(1), B256::with_last_byte(2));
+        for (address, code_hash) in [(hot, hot_code), (cold, cold_code)] {
+            tx.put::<tables::PlainAccountState>(
+                address,
+                Account { bytecode_hash: Some(code_hash), ..Default::default() },
+            )
+            .unwrap();
+            tx.put::<tables::Bytecodes>(
+                code_hash,
+                Bytecode::new_raw(Bytes::copy_from_slice(code_hash.as_slice())),
+            )
+            .unwrap();
+        }
+        for block in 1..=3 {
+            for slot in 0..2 {
+                tx.put::<tables::StorageChangeSets>(
+                    BlockNumberAddress((block, hot)),
+                    StorageEntry { key: B256::with_last_byte(slot), value: Default::default() },
+                )
+                .unwrap();
+            }
+        }
+        tx.put::<tables::StorageChangeSets>(
+            BlockNumberAddress((3, cold)),
+            StorageEntry { key: B256::ZERO, value: Default::default() },
+        )
+        .unwrap();
+
+        let cache = BytecodeCache::new(10);
+        assert_eq!(cache.prewarm(tx, 3, 2, 1).unwrap(), 1);
+        assert!(cache.get(&hot_code).is_some());
+        assert!(cache.get(&cold_code).is_none());
+
+        // already cached bytecodes are not inserted again
+        assert_eq!(cache.prewarm(tx, 3, 2, 2).unwrap(), 1);
+        assert!(cache.get(&cold_code).is_some());
+    }
+}
@@ -3,7 +3,7 @@ use crate::{
     HashedPostStateProvider, ProviderError, StateProvider, StateRootProvider,
 };
 use alloy_eips::merge::EPOCH_SLOTS;
-use alloy_primitives::{Address, BlockNumber, Bytes, StorageKey, StorageValue, B256};
+use alloy_primitives::{keccak256, Address, BlockNumber, Bytes, StorageKey, StorageValue, B256};
 use reth_db_api::{
     cursor::{DbCursorRO, DbDupCursorRO},
     models::{storage_sharded_key::StorageShardedKey, ShardedKey},
@@ -14,8 +14,8 @@ use reth_db_api::{
 };
 use reth_primitives_traits::{Account, Bytecode};
 use reth_storage_api::{
-    BlockNumReader, BytecodeReader, DBProvider, StateCommitmentProvider, StateProofProvider,
-    StorageRootProvider,
+    record_read, BlockNumReader, BytecodeReader, DBProvider, ReadSource, StateCommitmentProvider,
+    StateProofProvider, StorageRootProvider,
 };
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::{
@@ -50,6 +50,8 @@ pub struct HistoricalStateProviderRef<'b, Provider> {
     block_number: BlockNumber,
     /// Lowest blocks at which different parts of the state are available.
     lowest_available_blocks: LowestAvailableBlocks,
+    /// Maximum number of changeset entries replayed to reconstruct the historical trie.
+    proof_effort_budget: Option<usize>,
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -65,7 +67,12 @@ impl<'b, Provider: DBProvider + BlockNumReader + StateCommitmentProvider>
 {
     /// Create new `StateProvider` for historical block number
     pub fn new(provider: &'b Provider, block_number: BlockNumber) -> Self {
-        Self { provider, block_number, lowest_available_blocks: Default::default() }
+        Self {
+            provider,
+            block_number,
+            lowest_available_blocks: Default::default(),
+            proof_effort_budget: None,
+        }
     }
 
     /// Create new `StateProvider` for historical block number and lowest block numbers at which
@@ -75,7 +82,16 @@ impl<'b, Provider: DBProvider + BlockNumReader + StateCommitmentProvider>
         block_number: BlockNumber,
         lowest_available_blocks: LowestAvailableBlocks,
     ) -> Self {
-        Self { provider, block_number, lowest_available_blocks }
+        Self { provider, block_number, lowest_available_blocks, proof_effort_budget: None }
+    }
+
+    /// Sets the maximum number of history entries that are looked up to reconstruct the trie at
+    /// the historical block for proofs and state roots.
+    ///
+    /// Computations that exceed the budget fail with [`ProviderError::ProofEffortExceeded`].
+    pub const fn with_proof_effort_budget(mut self, budget: Option<usize>) -> Self {
+        self.proof_effort_budget = budget;
+        self
     }
 
     /// Lookup an account in the `AccountsHistory` table
@@ -127,6 +143,14 @@ impl<'b, Provider: DBProvider + BlockNumReader + StateCommitmentProvider>
             return Err(ProviderError::StateAtBlockPruned(self.block_number))
         }
 
+        // replay the changesets from the tip back to the block, bounded by the budget
+        if let Some(budget) = self.proof_effort_budget {
+            return HashedPostState::from_reverts_with_limit::<
+                <Provider::StateCommitment as StateCommitment>::KeyHasher,
+            >(self.tx(), self.block_number, budget)?
+            .ok_or(ProviderError::ProofEffortExceeded { block: self.block_number, budget })
+        }
+
         if self.check_distance_against_limit(EPOCH_SLOTS)? {
             tracing::warn!(
                 target: "provider::historical_sp",
@@ -146,17 +170,37 @@ impl<'b, Provider: DBProvider + BlockNumReader + StateCommitmentProvider>
             return Err(ProviderError::StateAtBlockPruned(self.block_number))
         }
 
-        if self.check_distance_against_limit(EPOCH_SLOTS * 10)? {
-            tracing::warn!(
-                target: "provider::historical_sp",
-                target = self.block_number,
-                "Attempt to calculate storage root for an old block might result in OOM"
-            );
+        // only the changesets of the address are replayed, so storage roots and proofs are
+        // available for blocks whose full state would exceed the budget
+        if let Some(budget) = self.proof_effort_budget {
+            return HashedStorage::from_reverts_with_limit(
+                self.tx(),
+                address,
+                self.block_number,
+                budget,
+            )?
+            .ok_or(ProviderError::ProofEffortExceeded { block: self.block_number, budget })
         }
 
         Ok(HashedStorage::from_reverts(self.tx(), address, self.block_number)?)
     }
 
+    /// Retrieve revert hashed state of the target account and storage slots for this history
+    /// provider.
+    fn revert_account(&self, address: Address, slots: &[B256]) -> ProviderResult<HashedPostState> {
+        if !self.lowest_available_blocks.is_account_history_available(self.block_number) ||
+            !self.lowest_available_blocks.is_storage_history_available(self.block_number)
+        {
+            return Err(ProviderError::StateAtBlockPruned(self.block_number))
+        }
+
+        let budget = self.proof_effort_budget.unwrap_or(usize::MAX);
+        HashedPostState::from_reverts_of_account_with_limit::<
+            <Provider::StateCommitment as StateCommitment>::KeyHasher,
+        >(self.tx(), self.block_number, address, slots, budget)?
+        .ok_or(ProviderError::ProofEffortExceeded { block: self.block_number, budget })
+    }
+
     fn history_info<T, K>(
         &self,
         key: K,
@@ -250,6 +294,7 @@ impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> AccountRea
 {
     /// Get basic account information.
     fn basic_account(&self, address: &Address) -> ProviderResult<Option<Account>> {
+        record_read(ReadSource::Database);
         match self.account_history_lookup(*address)? {
             HistoryInfo::NotYetWritten => Ok(None),
             HistoryInfo::InChangeset(changeset_block_number) => Ok(self
@@ -290,6 +335,7 @@ impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StateRootP
     for HistoricalStateProviderRef<'_, Provider>
 {
     fn state_root(&self, hashed_state: HashedPostState) -> ProviderResult<B256> {
+        record_read(ReadSource::Database);
         let mut revert_state = self.revert_state()?;
         revert_state.extend(hashed_state);
         StateRoot::overlay_root(self.tx(), revert_state)
@@ -297,6 +343,7 @@ impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StateRootP
     }
 
     fn state_root_from_nodes(&self, mut input: TrieInput) -> ProviderResult<B256> {
+        record_read(ReadSource::Database);
         input.prepend(self.revert_state()?);
         StateRoot::overlay_root_from_nodes(self.tx(), input)
             .map_err(|err| ProviderError::Database(err.into()))
@@ -306,6 +353,7 @@ impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StateRootP
         &self,
         hashed_state: HashedPostState,
     ) -> ProviderResult<(B256, TrieUpdates)> {
+        record_read(ReadSource::Database);
         let mut revert_state = self.revert_state()?;
         revert_state.extend(hashed_state);
         StateRoot::overlay_root_with_updates(self.tx(), revert_state)
@@ -316,6 +364,7 @@ impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StateRootP
         &self,
         mut input: TrieInput,
     ) -> ProviderResult<(B256, TrieUpdates)> {
+        record_read(ReadSource::Database);
         input.prepend(self.revert_state()?);
         StateRoot::overlay_root_from_nodes_with_updates(self.tx(), input)
             .map_err(|err| ProviderError::Database(err.into()))
@@ -330,6 +379,7 @@ impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StorageRoo
         address: Address,
         hashed_storage: HashedStorage,
     ) -> ProviderResult<B256> {
+        record_read(ReadSource::Database);
         let mut revert_storage = self.revert_storage(address)?;
         revert_storage.extend(&hashed_storage);
         StorageRoot::overlay_root(self.tx(), address, revert_storage)
@@ -342,6 +392,7 @@ impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StorageRoo
         slot: B256,
         hashed_storage: HashedStorage,
     ) -> ProviderResult<reth_trie::StorageProof> {
+        record_read(ReadSource::Database);
         let mut revert_storage = self.revert_storage(address)?;
         revert_storage.extend(&hashed_storage);
         StorageProof::overlay_storage_proof(self.tx(), address, slot, revert_storage)
@@ -354,11 +405,27 @@ impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StorageRoo
         slots: &[B256],
         hashed_storage: HashedStorage,
     ) -> ProviderResult<StorageMultiProof> {
+        record_read(ReadSource::Database);
         let mut revert_storage = self.revert_storage(address)?;
         revert_storage.extend(&hashed_storage);
         StorageProof::overlay_storage_multiproof(self.tx(), address, slots, revert_storage)
             .map_err(ProviderError::from)
     }
+
+    fn storage_multiproof_from_nodes(
+        &self,
+        mut input: TrieInput,
+        address: Address,
+        slots: &[B256],
+    ) -> ProviderResult<StorageMultiProof> {
+        record_read(ReadSource::Database);
+        let revert_storage = self.revert_storage(address)?;
+        input.prepend(
+            HashedPostState::default().with_storages([(keccak256(address), revert_storage)]),
+        );
+        StorageProof::overlay_storage_multiproof_from_nodes(self.tx(), input, address, slots)
+            .map_err(ProviderError::from)
+    }
 }
 
 impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StateProofProvider
@@ -371,7 +438,10 @@ impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StateProof
         address: Address,
         slots: &[B256],
     ) -> ProviderResult<AccountProof> {
-        input.prepend(self.revert_state()?);
+        record_read(ReadSource::Database);
+        // only the target keys are reverted, so the cost doesn't depend on the changes to the rest
+        // of the state since the block
+        input.prepend(self.revert_account(address, slots)?);
         Proof::overlay_account_proof(self.tx(), input, address, slots).map_err(ProviderError::from)
     }
 
@@ -380,11 +450,14 @@ impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StateProof
         mut input: TrieInput,
         targets: MultiProofTargets,
     ) -> ProviderResult<MultiProof> {
+        record_read(ReadSource::Database);
+        // the targets are hashed keys, which can't be looked up in the changesets
         input.prepend(self.revert_state()?);
         Proof::overlay_multiproof(self.tx(), input, targets).map_err(ProviderError::from)
     }
 
     fn witness(&self, mut input: TrieInput, target: HashedPostState) -> ProviderResult<Vec<Bytes>> {
+        record_read(ReadSource::Database);
         input.prepend(self.revert_state()?);
         TrieWitness::overlay_witness(self.tx(), input, target)
             .map_err(ProviderError::from)
@@ -411,6 +484,7 @@ impl<Provider: DBProvider + BlockNumReader + BlockHashReader + StateCommitmentPr
         address: Address,
         storage_key: StorageKey,
     ) -> ProviderResult<Option<StorageValue>> {
+        record_read(ReadSource::Database);
         match self.storage_history_lookup(address, storage_key)? {
             HistoryInfo::NotYetWritten => Ok(None),
             HistoryInfo::InChangeset(changeset_block_number) => Ok(Some(
@@ -441,6 +515,7 @@ impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> BytecodeRe
 {
     /// Get account code by its hash
     fn bytecode_by_hash(&self, code_hash: &B256) -> ProviderResult<Option<Bytecode>> {
+        record_read(ReadSource::Database);
         self.tx().get_by_encoded_key::<tables::Bytecodes>(code_hash).map_err(Into::into)
     }
 }
@@ -461,6 +536,8 @@ pub struct HistoricalStateProvider<Provider> {
     block_number: BlockNumber,
     /// Lowest blocks at which different parts of the state are available.
     lowest_available_blocks: LowestAvailableBlocks,
+    /// Maximum number of changeset entries replayed to reconstruct the historical trie.
+    proof_effort_budget: Option<usize>,
 }
 
 impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider>
@@ -468,7 +545,21 @@ impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider>
 {
     /// Create new `StateProvider` for historical block number
     pub fn new(provider: Provider, block_number: BlockNumber) -> Self {
-        Self { provider, block_number, lowest_available_blocks: Default::default() }
+        Self {
+            provider,
+            block_number,
+            lowest_available_blocks: Default::default(),
+            proof_effort_budget: None,
+        }
+    }
+
+    /// Sets the maximum number of history entries that are looked up to reconstruct the trie at
+    /// the historical block.
+    ///
+    /// See [`HistoricalStateProviderRef::with_proof_effort_budget`].
+    pub const fn with_proof_effort_budget(mut self, budget: Option<usize>) -> Self {
+        self.proof_effort_budget = budget;
+        self
     }
 
     /// Set the lowest block number at which the account history is available.
@@ -497,6 +588,7 @@ impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider>
             self.block_number,
             self.lowest_available_blocks,
         )
+        .with_proof_effort_budget(self.proof_effort_budget)
     }
 }
 
@@ -543,6 +635,7 @@ mod tests {
         providers::state::historical::{HistoryInfo, LowestAvailableBlocks},
         test_utils::create_test_provider_factory,
         AccountReader, HistoricalStateProvider, HistoricalStateProviderRef, StateProvider,
+        StateRootProvider, StorageRootProvider,
     };
     use alloy_primitives::{address, b256, Address, B256, U256};
     use reth_db_api::{
@@ -554,7 +647,7 @@ mod tests {
     use reth_primitives_traits::{Account, StorageEntry};
     use reth_storage_api::{
         BlockHashReader, BlockNumReader, DBProvider, DatabaseProviderFactory,
-        StateCommitmentProvider,
+        StateCommitmentProvider, StateProofProvider,
     };
     use reth_storage_errors::provider::ProviderError;
 
@@ -841,4 +934,86 @@ mod tests {
             Ok(HistoryInfo::MaybeInPlainState)
         ));
     }
+
+    #[test]
+    fn history_provider_proof_effort_budget() {
+        let factory = create_test_provider_factory();
+        let tx = factory.provider_rw().unwrap().into_tx();
+        for block in 1..=3 {
+            tx.put::<tables::AccountChangeSets>(
+                block,
+                AccountBeforeTx { address: ADDRESS, info: None },
+            )
+            .unwrap();
+        }
+        tx.commit().unwrap();
+
+        let db = factory.provider().unwrap();
+
+        // reverting to block 2 replays the changesets of blocks 2 and 3
+        let provider = HistoricalStateProviderRef::new(&db, 2).with_proof_effort_budget(Some(1));
+        assert!(matches!(
+            provider.state_root(Default::default()),
+            Err(ProviderError::ProofEffortExceeded { block: 2, budget: 1 })
+        ));
+
+        let provider = HistoricalStateProviderRef::new(&db, 2).with_proof_effort_budget(Some(2));
+        assert!(provider.state_root(Default::default()).is_ok());
+    }
+
+    #[test]
+    fn history_provider_proofs_revert_target_keys() {
+        let factory = create_test_provider_factory();
+        let tx = factory.provider_rw().unwrap().into_tx();
+        for block in 1..=3 {
+            for address in [ADDRESS, HIGHER_ADDRESS] {
+                tx.put::<tables::AccountChangeSets>(
+                    block,
+                    AccountBeforeTx {
+                        address,
+                        info: Some(Account { nonce: block, ..Default::default() }),
+                    },
+                )
+                .unwrap();
+                tx.put::<tables::StorageChangeSets>(
+                    (block, address).into(),
+                    StorageEntry { key: STORAGE, value: U256::from(block) },
+                )
+                .unwrap();
+            }
+        }
+        for address in [ADDRESS, HIGHER_ADDRESS] {
+            tx.put::<tables::AccountsHistory>(
+                ShardedKey::new(address, u64::MAX),
+                BlockNumberList::new([1, 2, 3]).unwrap(),
+            )
+            .unwrap();
+            tx.put::<tables::StoragesHistory>(
+                StorageShardedKey::new(address, STORAGE, u64::MAX),
+                BlockNumberList::new([1, 2, 3]).unwrap(),
+            )
+            .unwrap();
+        }
+        tx.commit().unwrap();
+
+        let db = factory.provider().unwrap();
+
+        // reverting the full state to block 2 replays the changesets of both addresses, while
+        // proofs only seek the history of the target keys
+        let provider = HistoricalStateProviderRef::new(&db, 2).with_proof_effort_budget(Some(4));
+        assert!(matches!(
+            provider.state_root(Default::default()),
+            Err(ProviderError::ProofEffortExceeded { block: 2, budget: 4 })
+        ));
+        assert!(provider.proof(Default::default(), ADDRESS, &[STORAGE]).is_ok());
+        assert!(provider.storage_proof(ADDRESS, STORAGE, Default::default()).is_ok());
+        assert!(provider.storage_root(HIGHER_ADDRESS, Default::default()).is_ok());
+
+        // the account proof seeks the account and slot shards and their changesets
+        let provider = HistoricalStateProviderRef::new(&db, 2).with_proof_effort_budget(Some(3));
+        assert!(matches!(
+            provider.proof(Default::default(), ADDRESS, &[STORAGE]),
+            Err(ProviderError::ProofEffortExceeded { block: 2, budget: 3 })
+        ));
+    }
 }
@@ -1,4 +1,7 @@
 //! [`StateProvider`](crate::StateProvider) implementations
+pub(crate) mod bytecode_cache;
 pub(crate) mod historical;
 pub(crate) mod latest;
 pub(crate) mod macros;
+pub(crate) mod overlay;
+pub(crate) mod query_stats;
@@ -0,0 +1,348 @@
+use crate::{
+    AccountReader, BlockHashReader, HashedPostStateProvider, StateProvider, StateRootProvider,
+};
+use alloy_primitives::{keccak256, Address, BlockNumber, Bytes, StorageKey, StorageValue, B256};
+use reth_primitives_traits::{Account, Bytecode};
+use reth_storage_api::{BytecodeReader, StateProofProvider, StorageRootProvider};
+use reth_storage_errors::provider::ProviderResult;
+use reth_trie::{
+    updates::TrieUpdates, AccountProof, HashedPostState, HashedStorage, MultiProof,
+    MultiProofTargets, StorageMultiProof, TrieInput,
+};
+use revm_database::BundleState;
+use std::sync::OnceLock;
+
+/// A state provider that layers an arbitrary [`BundleState`] on top of another state provider.
+///
+/// Reads are answered from the bundle first and fall back to the underlying provider. State root
+/// and proof requests are computed against the overlay by prepending the hashed bundle state (and
+/// the optional trie updates produced for it) to the trie input.
+///
+/// Overlays can be stacked with [`OverlayStateProviderRef::overlay`], which allows chaining
+/// speculative states without copying the underlying database.
+#[expect(missing_debug_implementations)]
+pub struct OverlayStateProviderRef<'a> {
+    /// Underlying state provider for lookups that are not found in the bundle.
+    inner: Box<dyn StateProvider + 'a>,
+    /// The state changes layered on top of the underlying provider.
+    bundle: BundleState,
+    /// Trie updates that were produced when computing the state root of the bundle, if known.
+    trie_updates: Option<TrieUpdates>,
+    /// Lazy-loaded trie input for the bundle.
+    trie_input: OnceLock<TrieInput>,
+}
+
+/// A state provider that layers an arbitrary [`BundleState`] on top of an owned state provider.
+pub type OverlayStateProvider = OverlayStateProviderRef<'static>;
+
+impl<'a> OverlayStateProviderRef<'a> {
+    /// Create new overlay state provider on top of the given provider.
+    pub fn new(inner: Box<dyn StateProvider + 'a>, bundle: BundleState) -> Self {
+        Self { inner, bundle, trie_updates: None, trie_input: OnceLock::new() }
+    }
+
+    /// Set the trie updates that correspond to the bundle state.
+    ///
+    /// These are reused as cached intermediate nodes when computing roots and proofs.
+    pub fn with_trie_updates(mut self, trie_updates: TrieUpdates) -> Self {
+        self.trie_updates = Some(trie_updates);
+        self.trie_input = OnceLock::new();
+        self
+    }
+
+    /// Returns the bundle state of this overlay.
+    pub const fn bundle(&self) -> &BundleState {
+        &self.bundle
+    }
+
+    /// Stack another bundle state on top of this overlay.
+    pub fn overlay(self, bundle: BundleState) -> Self {
+        OverlayStateProviderRef::new(self.boxed(), bundle)
+    }
+
+    /// Turn this state provider into a state provider
+    pub fn boxed(self) -> Box<dyn StateProvider + 'a> {
+        Box::new(self)
+    }
+
+    /// Return lazy-loaded trie input for the bundle state.
+    fn trie_input(&self) -> &TrieInput {
+        self.trie_input.get_or_init(|| {
+            let hashed_state = self.inner.hashed_post_state(&self.bundle);
+            TrieInput::from_blocks([(&hashed_state, self.trie_updates.as_ref())])
+        })
+    }
+
+    /// Returns the hashed storage of the overlay for the given address extended with `storage`.
+    fn hashed_storage(&self, address: Address, storage: HashedStorage) -> HashedStorage {
+        let state = &self.trie_input().state;
+        let mut hashed_storage =
+            state.storages.get(&keccak256(address)).cloned().unwrap_or_default();
+        hashed_storage.extend(&storage);
+        hashed_storage
+    }
+}
+
+impl BlockHashReader for OverlayStateProviderRef<'_> {
+    fn block_hash(&self, number: BlockNumber) -> ProviderResult<Option<B256>> {
+        self.inner.block_hash(number)
+    }
+
+    fn canonical_hashes_range(
+        &self,
+        start: BlockNumber,
+        end: BlockNumber,
+    ) -> ProviderResult<Vec<B256>> {
+        self.inner.canonical_hashes_range(start, end)
+    }
+}
+
+impl AccountReader for OverlayStateProviderRef<'_> {
+    fn basic_account(&self, address: &Address) -> ProviderResult<Option<Account>> {
+        if let Some(account) = self.bundle.account(address) {
+            return Ok(account.info.as_ref().map(Into::into))
+        }
+
+        self.inner.basic_account(address)
+    }
+}
+
+impl StateRootProvider for OverlayStateProviderRef<'_> {
+    fn state_root(&self, state: HashedPostState) -> ProviderResult<B256> {
+        self.state_root_from_nodes(TrieInput::from_state(state))
+    }
+
+    fn state_root_from_nodes(&self, mut input: TrieInput) -> ProviderResult<B256> {
+        input.prepend_self(self.trie_input().clone());
+        self.inner.state_root_from_nodes(input)
+    }
+
+    fn state_root_with_updates(
+        &self,
+        state: HashedPostState,
+    ) -> ProviderResult<(B256, TrieUpdates)> {
+        self.state_root_from_nodes_with_updates(TrieInput::from_state(state))
+    }
+
+    fn state_root_from_nodes_with_updates(
+        &self,
+        mut input: TrieInput,
+    ) -> ProviderResult<(B256, TrieUpdates)> {
+        input.prepend_self(self.trie_input().clone());
+        self.inner.state_root_from_nodes_with_updates(input)
+    }
+}
+
+impl StorageRootProvider for OverlayStateProviderRef<'_> {
+    fn storage_root(&self, address: Address, storage: HashedStorage) -> ProviderResult<B256> {
+        self.inner.storage_root(address, self.hashed_storage(address, storage))
+    }
+
+    fn storage_proof(
+        &self,
+        address: Address,
+        slot: B256,
+        storage: HashedStorage,
+    ) -> ProviderResult<reth_trie::StorageProof> {
+        self.inner.storage_proof(address, slot, self.hashed_storage(address, storage))
+    }
+
+    fn storage_multiproof(
+        &self,
+        address: Address,
+        slots: &[B256],
+        storage: HashedStorage,
+    ) -> ProviderResult<StorageMultiProof> {
+        self.inner.storage_multiproof(address, slots, self.hashed_storage(address, storage))
+    }
+
+    fn storage_multiproof_from_nodes(
+        &self,
+        mut input: TrieInput,
+        address: Address,
+        slots: &[B256],
+    ) -> ProviderResult<StorageMultiProof> {
+        input.prepend_self(self.trie_input().clone());
+        self.inner.storage_multiproof_from_nodes(input, address, slots)
+    }
+}
+
+impl StateProofProvider for OverlayStateProviderRef<'_> {
+    fn proof(
+        &self,
+        mut input: TrieInput,
+        address: Address,
+        slots: &[B256],
+    ) -> ProviderResult<AccountProof> {
+        input.prepend_self(self.trie_input().clone());
+        self.inner.proof(input, address, slots)
+    }
+
+    fn multiproof(
+        &self,
+        mut input: TrieInput,
+        targets: MultiProofTargets,
+    ) -> ProviderResult<MultiProof> {
+        input.prepend_self(self.trie_input().clone());
+        self.inner.multiproof(input, targets)
+    }
+
+    fn witness(&self, mut input: TrieInput, target: HashedPostState) -> ProviderResult<Vec<Bytes>> {
+        input.prepend_self(self.trie_input().clone());
+        self.inner.witness(input, target)
+    }
+}
+
+impl HashedPostStateProvider for OverlayStateProviderRef<'_> {
+    fn hashed_post_state(&self, bundle_state: &BundleState) -> HashedPostState {
+        self.inner.hashed_post_state(bundle_state)
+    }
+}
+
+impl StateProvider for OverlayStateProviderRef<'_> {
+    fn storage(
+        &self,
+        address: Address,
+        storage_key: StorageKey,
+    ) -> ProviderResult<Option<StorageValue>> {
+        // the bundle reports slots of wiped accounts and cleared slots as zero, which other
+        // providers return as unset
+        if let Some(value) = self
+            .bundle
+            .account(&address)
+            .and_then(|account| account.storage_slot(storage_key.into()))
+        {
+            return Ok((!value.is_zero()).then_some(value))
+        }
+
+        self.inner.storage(address, storage_key)
+    }
+}
+
+impl BytecodeReader for OverlayStateProviderRef<'_> {
+    fn bytecode_by_hash(&self, code_hash: &B256) -> ProviderResult<Option<Bytecode>> {
+        if let Some(bytecode) = self.bundle.bytecode(code_hash) {
+            return Ok(Some(Bytecode(bytecode)))
+        }
+
+        self.inner.bytecode_by_hash(code_hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{ExtendedAccount, MockEthProvider};
+    use alloy_primitives::{map::HashMap, U256};
+    use revm_database::{AccountStatus, BundleAccount};
+    use revm_state::{AccountInfo, Bytecode as RevmBytecode};
+
+    fn base_provider() -> MockEthProvider {
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            Address::with_last_byte(1),
+            ExtendedAccount::new(1, U256::from(100))
+                .extend_storage([(B256::with_last_byte(1), U256::from(10))]),
+        );
+        provider.add_account(Address::with_last_byte(2), ExtendedAccount::new(7, U256::from(5)));
+        provider
+    }
+
+    #[test]
+    fn reads_prefer_overlay() {
+        let address = Address::with_last_byte(1);
+        let code = RevmBytecode::new_raw(Bytes::from_static(&[0x60, 0x00]));
+        let info = AccountInfo {
+            nonce: 2,
+            balance: U256::from(50),
+            code_hash: code.hash_slow(),
+            code: Some(code.clone()),
+        };
+        let bundle = BundleState::builder(1..=1)
+            .state_present_account_info(address, info.clone())
+            .state_storage(
+                address,
+                HashMap::from_iter([(U256::from(1), (U256::from(10), U256::from(20)))]),
+            )
+            .state_contract(info.code_hash, code)
+            .build();
+
+        let overlay = OverlayStateProvider::new(Box::new(base_provider()), bundle);
+
+        let account = overlay.basic_account(&address).unwrap().unwrap();
+        assert_eq!(account.nonce, 2);
+        assert_eq!(account.balance, U256::from(50));
+        assert_eq!(
+            overlay.storage(address, B256::with_last_byte(1)).unwrap(),
+            Some(U256::from(20))
+        );
+        assert!(overlay.bytecode_by_hash(&info.code_hash).unwrap().is_some());
+
+        // untouched accounts fall through to the underlying provider
+        let other = overlay.basic_account(&Address::with_last_byte(2)).unwrap().unwrap();
+        assert_eq!(other.nonce, 7);
+    }
+
+    #[test]
+    fn stacked_overlays() {
+        let address = Address::with_last_byte(1);
+        let first = BundleState::builder(1..=1)
+            .state_present_account_info(
+                address,
+                AccountInfo { nonce: 2, balance: U256::from(50), ..Default::default() },
+            )
+            .build();
+        let second = BundleState::builder(2..=2)
+            .state_storage(
+                address,
+                HashMap::from_iter([(U256::from(1), (U256::from(10), U256::from(30)))]),
+            )
+            .build();
+
+        let overlay = OverlayStateProvider::new(Box::new(base_provider()), first).overlay(second);
+
+        // account info comes from the first layer, storage from the second
+        assert_eq!(overlay.basic_account(&address).unwrap().unwrap().nonce, 2);
+        assert_eq!(
+            overlay.storage(address, B256::with_last_byte(1)).unwrap(),
+            Some(U256::from(30))
+        );
+        assert_eq!(overlay.storage(address, B256::with_last_byte(2)).unwrap(), None);
+    }
+
+    #[test]
+    fn destroyed_account() {
+        let address = Address::with_last_byte(1);
+        let mut bundle = BundleState::default();
+        bundle.state.insert(
+            address,
+            BundleAccount {
+                info: None,
+                storage: Default::default(),
+                original_info: Some(AccountInfo::default()),
+                status: AccountStatus::Destroyed,
+            },
+        );
+
+        let overlay = OverlayStateProvider::new(Box::new(base_provider()), bundle);
+        assert_eq!(overlay.basic_account(&address).unwrap(), None);
+        assert_eq!(overlay.storage(address, B256::with_last_byte(1)).unwrap(), None);
+    }
+
+    #[test]
+    fn cleared_slot() {
+        let address = Address::with_last_byte(1);
+        let bundle = BundleState::builder(1..=1)
+            .state_storage(
+                address,
+                HashMap::from_iter([(U256::from(1), (U256::from(10), U256::ZERO))]),
+            )
+            .build();
+
+        let overlay = OverlayStateProvider::new(Box::new(base_provider()), bundle);
+
+        // a slot cleared in the overlay is unset, like in the underlying provider
+        assert_eq!(overlay.storage(address, B256::with_last_byte(1)).unwrap(), None);
+        assert_eq!(base_provider().storage(address, B256::with_last_byte(2)).unwrap(), None);
+    }
+}
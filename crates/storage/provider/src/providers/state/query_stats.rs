@@ -0,0 +1,608 @@
+//! Aggregate statistics of historical state queries, used to warm caches after restarts.
+
+use alloy_primitives::{Address, BlockNumber, Bytes, StorageKey, StorageValue, B256, U256};
+use dashmap::DashMap;
+use parking_lot::Mutex;
+use reth_db_api::{
+    cursor::{DbCursorRO, DbDupCursorRO},
+    models::{storage_sharded_key::StorageShardedKey, ShardedKey},
+    tables,
+    transaction::DbTx,
+};
+use reth_metrics::{
+    metrics::{Counter, Gauge},
+    Metrics,
+};
+use reth_primitives_traits::{Account, Bytecode};
+use reth_storage_api::{
+    AccountReader, BlockHashReader, BytecodeReader, HashedPostStateProvider, StateProofProvider,
+    StateProvider, StateProviderBox, StateRootProvider, StorageRootProvider,
+};
+use reth_storage_errors::provider::ProviderResult;
+use reth_trie::{
+    updates::TrieUpdates, AccountProof, HashedPostState, HashedStorage, MultiProof,
+    MultiProofTargets, StorageMultiProof, StorageProof, TrieInput,
+};
+use revm_database::BundleState;
+use std::{collections::HashMap, io::Write, ops::RangeInclusive, path::Path, sync::Arc};
+
+/// The header of the persisted statistics.
+const QUERY_STATS_HEADER: &str = "# reth query stats v1";
+
+/// Metrics for the query statistics.
+#[derive(Metrics)]
+#[metrics(scope = "storage.providers.query_stats")]
+struct QueryStatsMetrics {
+    /// Number of recorded historical state queries
+    queries: Counter,
+    /// Number of tracked block ranges
+    tracked_ranges: Gauge,
+    /// Number of tracked contracts
+    tracked_contracts: Gauge,
+    /// Number of contracts whose state was warmed
+    warmed_contracts: Counter,
+    /// Number of history shards and changesets that were warmed
+    warmed_history_entries: Counter,
+}
+
+/// Aggregate statistics of the historical state queries served by the node.
+///
+/// Only the number of queries per range of [`QueryStats::RANGE_SIZE`] blocks and the number of
+/// state reads per contract are recorded, no individual queries, their parameters or the time
+/// they were made. The statistics are persisted across restarts, so that the state history of the
+/// hot contracts in the hot block ranges can be warmed in the background before the first queries
+/// arrive, see [`QueryStats::warm_history`].
+///
+/// Counts are halved whenever the statistics are loaded and when too many contracts are tracked,
+/// so that old patterns fade out. The state reads of a provider are counted locally and merged
+/// into the shared statistics once the provider is dropped.
+#[derive(Debug, Clone)]
+pub struct QueryStats {
+    inner: Arc<QueryStatsInner>,
+}
+
+#[derive(Debug)]
+struct QueryStatsInner {
+    /// Number of queries by the first block of the range.
+    ranges: DashMap<BlockNumber, u64>,
+    /// Number of state reads by contract.
+    contracts: DashMap<Address, u64>,
+    /// Held while the counts are halved, so that concurrent recorders halve them only once.
+    decay_lock: Mutex<()>,
+    /// Metrics for the statistics.
+    metrics: QueryStatsMetrics,
+}
+
+impl QueryStats {
+    /// The number of blocks the queried blocks are grouped by.
+    pub const RANGE_SIZE: u64 = 10_000;
+
+    /// The maximum number of tracked contracts, before the counts are halved.
+    pub const MAX_TRACKED_CONTRACTS: usize = 100_000;
+
+    /// The number of contracts that are kept when too many contracts are tracked.
+    const RETAINED_CONTRACTS: usize = Self::MAX_TRACKED_CONTRACTS * 3 / 4;
+
+    /// The maximum number of history shards and changesets that are warmed per contract and block
+    /// range.
+    pub const MAX_WARMED_ENTRIES: u64 = 10_000;
+
+    /// Creates empty statistics.
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(QueryStatsInner {
+                ranges: DashMap::default(),
+                contracts: DashMap::default(),
+                decay_lock: Mutex::default(),
+                metrics: QueryStatsMetrics::default(),
+            }),
+        }
+    }
+
+    /// Loads the statistics persisted to the file, with all counts halved.
+    ///
+    /// Returns empty statistics if the file doesn't exist.
+    pub fn load(path: &Path) -> reth_fs_util::Result<Self> {
+        let stats = Self::new();
+        if !path.exists() {
+            return Ok(stats)
+        }
+
+        for line in reth_fs_util::read_to_string(path)?.lines() {
+            let mut columns = line.split_whitespace();
+            let (Some(kind), Some(key), Some(count)) =
+                (columns.next(), columns.next(), columns.next().and_then(|c| c.parse().ok()))
+            else {
+                continue
+            };
+            match kind {
+                "range" => {
+                    if let Ok(start) = key.parse() {
+                        stats.inner.ranges.insert(start, count);
+                    }
+                }
+                "contract" => {
+                    if let Ok(address) = key.parse() {
+                        stats.inner.contracts.insert(address, count);
+                    }
+                }
+                _ => {}
+            }
+        }
+        stats.decay();
+
+        Ok(stats)
+    }
+
+    /// Persists the statistics to the file.
+    pub fn save(&self, path: &Path) -> reth_fs_util::Result<()> {
+        reth_fs_util::atomic_write_file(path, |file| {
+            let mut file = std::io::BufWriter::new(file);
+            writeln!(file, "{QUERY_STATS_HEADER}")?;
+            for entry in &self.inner.ranges {
+                writeln!(file, "range {} {}", entry.key(), entry.value())?;
+            }
+            for entry in &self.inner.contracts {
+                writeln!(file, "contract {} {}", entry.key(), entry.value())?;
+            }
+            file.flush()
+        })
+    }
+
+    /// Records a query of the state at the block.
+    pub fn record_block(&self, block: BlockNumber) {
+        let start = block - block % Self::RANGE_SIZE;
+        *self.inner.ranges.entry(start).or_default() += 1;
+        self.inner.metrics.queries.increment(1);
+        self.inner.metrics.tracked_ranges.set(self.inner.ranges.len() as f64);
+    }
+
+    /// Records a read of the contract's state.
+    pub fn record_contract(&self, address: Address) {
+        self.record_contracts([(address, 1)]);
+    }
+
+    /// Records the given number of reads of each contract's state.
+    pub fn record_contracts(&self, reads: impl IntoIterator<Item = (Address, u64)>) {
+        for (address, count) in reads {
+            *self.inner.contracts.entry(address).or_default() += count;
+        }
+        if self.inner.contracts.len() > Self::MAX_TRACKED_CONTRACTS {
+            // another recorder is already halving the counts
+            if let Some(_guard) = self.inner.decay_lock.try_lock() {
+                if self.inner.contracts.len() > Self::MAX_TRACKED_CONTRACTS {
+                    self.decay_contracts();
+                }
+            }
+        }
+        self.inner.metrics.tracked_contracts.set(self.inner.contracts.len() as f64);
+    }
+
+    /// Returns the `n` most queried block ranges, most queried first.
+    pub fn hot_ranges(&self, n: usize) -> Vec<RangeInclusive<BlockNumber>> {
+        top_n(&self.inner.ranges, n)
+            .into_iter()
+            .map(|start| start..=start + Self::RANGE_SIZE - 1)
+            .collect()
+    }
+
+    /// Returns the `n` most read contracts, most read first.
+    pub fn hot_contracts(&self, n: usize) -> Vec<Address> {
+        top_n(&self.inner.contracts, n)
+    }
+
+    /// Wraps the state provider of the block, so that its state reads are recorded.
+    pub fn wrap(&self, block: BlockNumber, provider: StateProviderBox) -> StateProviderBox {
+        self.record_block(block);
+        Box::new(QueryRecordingStateProvider {
+            stats: self.clone(),
+            reads: Default::default(),
+            inner: provider,
+        })
+    }
+
+    /// Warms the state history of the `contracts` most read contracts in the `ranges` most queried
+    /// block ranges.
+    ///
+    /// Historical reads at a block seek the account or storage history shard that covers the
+    /// block, and read the changeset of the next change after it. For every hot contract, this
+    /// reads its account and bytecode, and for every hot range the account and storage history
+    /// shards that cover the range and the changesets they point to, up to
+    /// [`Self::MAX_WARMED_ENTRIES`] per contract and range.
+    ///
+    /// Returns the number of warmed contracts and history entries.
+    pub fn warm_history<TX: DbTx>(
+        &self,
+        tx: &TX,
+        contracts: usize,
+        ranges: usize,
+    ) -> ProviderResult<(usize, u64)> {
+        let ranges = self.hot_ranges(ranges);
+        let mut warmed_contracts = 0;
+        let mut warmed_entries = 0;
+        for address in self.hot_contracts(contracts) {
+            if let Some(account) = tx.get_by_encoded_key::<tables::PlainAccountState>(&address)? {
+                if let Some(code_hash) = account.bytecode_hash {
+                    tx.get_by_encoded_key::<tables::Bytecodes>(&code_hash)?;
+                }
+            }
+            for range in &ranges {
+                warmed_entries += warm_account_history(tx, address, range)?;
+                warmed_entries += warm_storage_history(tx, address, range)?;
+            }
+            warmed_contracts += 1;
+        }
+        self.inner.metrics.warmed_contracts.increment(warmed_contracts as u64);
+        self.inner.metrics.warmed_history_entries.increment(warmed_entries);
+
+        Ok((warmed_contracts, warmed_entries))
+    }
+
+    /// Halves the counts of the contracts, and removes the least read contracts until at most
+    /// [`Self::RETAINED_CONTRACTS`] are tracked.
+    ///
+    /// The caller must hold the decay lock, so that the counts are halved once per overflow.
+    fn decay_contracts(&self) {
+        self.inner.contracts.retain(|_, count| {
+            *count /= 2;
+            *count > 0
+        });
+
+        let excess = self.inner.contracts.len().saturating_sub(Self::RETAINED_CONTRACTS);
+        if excess > 0 {
+            let mut counts = self
+                .inner
+                .contracts
+                .iter()
+                .map(|entry| (*entry.value(), *entry.key()))
+                .collect::<Vec<_>>();
+            counts.select_nth_unstable(excess - 1);
+            for (_, address) in &counts[..excess] {
+                self.inner.contracts.remove(address);
+            }
+        }
+    }
+
+    /// Halves all counts and removes the entries that drop to zero.
+    fn decay(&self) {
+        self.inner.ranges.retain(|_, count| {
+            *count /= 2;
+            *count > 0
+        });
+        self.inner.contracts.retain(|_, count| {
+            *count /= 2;
+            *count > 0
+        });
+        self.inner.metrics.tracked_ranges.set(self.inner.ranges.len() as f64);
+        self.inner.metrics.tracked_contracts.set(self.inner.contracts.len() as f64);
+    }
+}
+
+impl Default for QueryStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Reads the account history shards of the address that cover the range, and the account
+/// changesets they point to. Returns the number of read entries.
+fn warm_account_history<TX: DbTx>(
+    tx: &TX,
+    address: Address,
+    range: &RangeInclusive<BlockNumber>,
+) -> ProviderResult<u64> {
+    let mut history = tx.cursor_read::<tables::AccountsHistory>()?;
+    let mut changesets = tx.cursor_dup_read::<tables::AccountChangeSets>()?;
+    let mut entries = 0;
+
+    let mut shard = history.seek(ShardedKey::new(address, *range.start()))?;
+    while let Some((key, blocks)) = shard.filter(|(key, _)| key.key == address) {
+        entries += 1;
+        for block in blocks.iter().skip_while(|block| block < range.start()) {
+            changesets.seek_by_key_subkey(block, address)?;
+            entries += 1;
+            // reads at the end of the range look up the first change after it
+            if block > *range.end() || entries >= QueryStats::MAX_WARMED_ENTRIES {
+                return Ok(entries)
+            }
+        }
+        if key.highest_block_number >= *range.end() {
+            break
+        }
+        shard = history.next()?;
+    }
+
+    Ok(entries)
+}
+
+/// Reads the storage history shards of all storage slots of the address that cover the range,
+/// and the storage changesets they point to. Returns the number of read entries.
+fn warm_storage_history<TX: DbTx>(
+    tx: &TX,
+    address: Address,
+    range: &RangeInclusive<BlockNumber>,
+) -> ProviderResult<u64> {
+    let mut history = tx.cursor_read::<tables::StoragesHistory>()?;
+    let mut changesets = tx.cursor_dup_read::<tables::StorageChangeSets>()?;
+    let mut entries = 0;
+
+    let mut shard = history.seek(StorageShardedKey::new(address, B256::ZERO, *range.start()))?;
+    while let Some((key, blocks)) = shard.filter(|(key, _)| key.address == address) {
+        entries += 1;
+        if entries >= QueryStats::MAX_WARMED_ENTRIES {
+            break
+        }
+        let slot = key.sharded_key.key;
+        // the first shard of a slot may end before the range
+        if key.sharded_key.highest_block_number < *range.start() {
+            shard = history.seek(StorageShardedKey::new(address, slot, *range.start()))?;
+            continue
+        }
+
+        let mut covers_range = key.sharded_key.highest_block_number >= *range.end();
+        for block in blocks.iter().skip_while(|block| block < range.start()) {
+            changesets.seek_by_key_subkey((block, address).into(), slot)?;
+            entries += 1;
+            // reads at the end of the range look up the first change after it
+            if block > *range.end() {
+                covers_range = true;
+                break
+            }
+        }
+
+        shard = if covers_range {
+            // skip the remaining shards of the slot
+            let Some(next_slot) = U256::from_be_bytes(slot.0).checked_add(U256::from(1)) else {
+                break
+            };
+            history.seek(StorageShardedKey::new(address, next_slot.into(), *range.start()))?
+        } else {
+            history.next()?
+        };
+    }
+
+    Ok(entries)
+}
+
+/// Returns the `n` keys with the highest counts, highest first.
+fn top_n<K: Copy + Eq + std::hash::Hash + Ord>(counts: &DashMap<K, u64>, n: usize) -> Vec<K> {
+    let mut entries = counts.iter().map(|entry| (*entry.key(), *entry.value())).collect::<Vec<_>>();
+    entries.sort_unstable_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    entries.into_iter().take(n).map(|(key, _)| key).collect()
+}
+
+/// A state provider that records the contracts it reads in the [`QueryStats`].
+///
+/// The reads are counted locally and merged into the shared statistics when the provider is
+/// dropped, so that reads don't contend on the shared statistics.
+#[expect(missing_debug_implementations)]
+pub struct QueryRecordingStateProvider {
+    /// The shared statistics.
+    stats: QueryStats,
+    /// Number of state reads by contract that are not recorded in the statistics yet.
+    reads: Mutex<HashMap<Address, u64>>,
+    /// The wrapped provider.
+    inner: StateProviderBox,
+}
+
+impl QueryRecordingStateProvider {
+    /// Counts a read of the contract's state.
+    fn record_contract(&self, address: Address) {
+        *self.reads.lock().entry(address).or_default() += 1;
+    }
+}
+
+impl Drop for QueryRecordingStateProvider {
+    fn drop(&mut self) {
+        let reads = std::mem::take(self.reads.get_mut());
+        if !reads.is_empty() {
+            self.stats.record_contracts(reads);
+        }
+    }
+}
+
+impl AccountReader for QueryRecordingStateProvider {
+    fn basic_account(&self, address: &Address) -> ProviderResult<Option<Account>> {
+        self.record_contract(*address);
+        self.inner.basic_account(address)
+    }
+}
+
+impl StateProvider for QueryRecordingStateProvider {
+    fn storage(
+        &self,
+        account: Address,
+        storage_key: StorageKey,
+    ) -> ProviderResult<Option<StorageValue>> {
+        self.record_contract(account);
+        self.inner.storage(account, storage_key)
+    }
+}
+
+impl BytecodeReader for QueryRecordingStateProvider {
+    fn bytecode_by_hash(&self, code_hash: &B256) -> ProviderResult<Option<Bytecode>> {
+        self.inner.bytecode_by_hash(code_hash)
+    }
+}
+
+impl BlockHashReader for QueryRecordingStateProvider {
+    fn block_hash(&self, number: BlockNumber) -> ProviderResult<Option<B256>> {
+        self.inner.block_hash(number)
+    }
+
+    fn canonical_hashes_range(
+        &self,
+        start: BlockNumber,
+        end: BlockNumber,
+    ) -> ProviderResult<Vec<B256>> {
+        self.inner.canonical_hashes_range(start, end)
+    }
+}
+
+impl StateRootProvider for QueryRecordingStateProvider {
+    fn state_root(&self, state: HashedPostState) -> ProviderResult<B256> {
+        self.inner.state_root(state)
+    }
+
+    fn state_root_from_nodes(&self, input: TrieInput) -> ProviderResult<B256> {
+        self.inner.state_root_from_nodes(input)
+    }
+
+    fn state_root_with_updates(
+        &self,
+        state: HashedPostState,
+    ) -> ProviderResult<(B256, TrieUpdates)> {
+        self.inner.state_root_with_updates(state)
+    }
+
+    fn state_root_from_nodes_with_updates(
+        &self,
+        input: TrieInput,
+    ) -> ProviderResult<(B256, TrieUpdates)> {
+        self.inner.state_root_from_nodes_with_updates(input)
+    }
+}
+
+impl StorageRootProvider for QueryRecordingStateProvider {
+    fn storage_root(&self, address: Address, storage: HashedStorage) -> ProviderResult<B256> {
+        self.inner.storage_root(address, storage)
+    }
+
+    fn storage_proof(
+        &self,
+        address: Address,
+        slot: B256,
+        storage: HashedStorage,
+    ) -> ProviderResult<StorageProof> {
+        self.inner.storage_proof(address, slot, storage)
+    }
+
+    fn storage_multiproof(
+        &self,
+        address: Address,
+        slots: &[B256],
+        storage: HashedStorage,
+    ) -> ProviderResult<StorageMultiProof> {
+        self.inner.storage_multiproof(address, slots, storage)
+    }
+
+    fn storage_multiproof_from_nodes(
+        &self,
+        input: TrieInput,
+        address: Address,
+        slots: &[B256],
+    ) -> ProviderResult<StorageMultiProof> {
+        self.inner.storage_multiproof_from_nodes(input, address, slots)
+    }
+}
+
+impl StateProofProvider for QueryRecordingStateProvider {
+    fn proof(
+        &self,
+        input: TrieInput,
+        address: Address,
+        slots: &[B256],
+    ) -> ProviderResult<AccountProof> {
+        self.record_contract(address);
+        self.inner.proof(input, address, slots)
+    }
+
+    fn multiproof(
+        &self,
+        input: TrieInput,
+        targets: MultiProofTargets,
+    ) -> ProviderResult<MultiProof> {
+        self.inner.multiproof(input, targets)
+    }
+
+    fn witness(&self, input: TrieInput, target: HashedPostState) -> ProviderResult<Vec<Bytes>> {
+        self.inner.witness(input, target)
+    }
+}
+
+impl HashedPostStateProvider for QueryRecordingStateProvider {
+    fn hashed_post_state(&self, bundle_state: &BundleState) -> HashedPostState {
+        self.inner.hashed_post_state(bundle_state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranks_hot_ranges_and_contracts() {
+        let stats = QueryStats::new();
+        for block in [5, 9_999, 10_000, 25_000, 25_001, 25_002] {
+            stats.record_block(block);
+        }
+        let (hot, cold) = (Address::with_last_byte(1), Address::with_last_byte(2));
+        for address in [hot, hot, cold] {
+            stats.record_contract(address);
+        }
+
+        assert_eq!(stats.hot_ranges(2), vec![20_000..=29_999, 0..=9_999]);
+        assert_eq!(stats.hot_contracts(1), vec![hot]);
+    }
+
+    #[test]
+    fn save_and_load_decays_counts() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("query-stats");
+
+        let stats = QueryStats::new();
+        for _ in 0..4 {
+            stats.record_block(42);
+            stats.record_contract(Address::with_last_byte(1));
+        }
+        stats.record_block(20_000);
+        stats.record_contract(Address::with_last_byte(2));
+        stats.save(&path).unwrap();
+
+        // entries with a single query fade out on load
+        let loaded = QueryStats::load(&path).unwrap();
+        assert_eq!(loaded.hot_ranges(10), vec![0..=9_999]);
+        assert_eq!(loaded.hot_contracts(10), vec![Address::with_last_byte(1)]);
+        assert_eq!(*loaded.inner.ranges.get(&0).unwrap(), 2);
+
+        assert!(QueryStats::load(&dir.path().join("missing")).unwrap().hot_ranges(1).is_empty());
+    }
+
+    #[test]
+    fn merges_reads_when_provider_is_dropped() {
+        let stats = QueryStats::new();
+        let provider = stats.wrap(0, Box::<crate::test_utils::NoopProvider>::default());
+        let address = Address::with_last_byte(1);
+        for _ in 0..3 {
+            provider.basic_account(&address).unwrap();
+            provider.storage(address, StorageKey::ZERO).unwrap();
+        }
+        assert!(stats.hot_contracts(1).is_empty());
+
+        drop(provider);
+        assert_eq!(stats.hot_contracts(1), vec![address]);
+        assert_eq!(*stats.inner.contracts.get(&address).unwrap(), 6);
+    }
+
+    #[test]
+    fn concurrent_recorders_decay_once() {
+        let stats = QueryStats::new();
+        stats.record_contracts(
+            (0..QueryStats::MAX_TRACKED_CONTRACTS as u64)
+                .map(|i| (Address::from_word(B256::from(U256::from(i))), 8)),
+        );
+
+        std::thread::scope(|scope| {
+            for i in 0..8u8 {
+                let stats = &stats;
+                scope.spawn(move || {
+                    stats.record_contracts([(Address::repeat_byte(0xf0 | i), 8)]);
+                });
+            }
+        });
+
+        // the counts are halved once, and the least read contracts are dropped
+        assert!(stats.inner.contracts.len() <= QueryStats::RETAINED_CONTRACTS + 8);
+        assert!(stats.inner.contracts.iter().all(|entry| *entry.value() >= 4));
+    }
+}
@@ -0,0 +1,115 @@
+use reth_nippy_jar::{NippyJar, OFFSETS_FILE_EXTENSION};
+use reth_static_file_types::SegmentHeader;
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
+use std::{
+    ffi::OsString,
+    fmt::Debug,
+    fs::File,
+    io::{self, Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+};
+
+/// The default maximum size of the static files downloaded from a backend that are kept locally,
+/// 100 GiB.
+pub const DEFAULT_BACKEND_CACHE_SIZE: u64 = 100 * 1024 * 1024 * 1024;
+
+/// A remote store of static files, e.g. an object storage bucket shared by multiple nodes.
+///
+/// The static file provider reads static files through the backend: static files that don't exist
+/// locally are downloaded from the backend when they're first accessed and kept in the local
+/// segment directory, see
+/// [`StaticFileProvider::with_backend`](super::StaticFileProvider::with_backend).
+///
+/// Files are addressed by their file name, e.g. `static_file_headers_0_499999.conf`.
+///
+/// The provider calls the backend from the threads that read the static files, so
+/// implementations should bound the time a request can take.
+pub trait StaticFileBackend: Debug + Send + Sync + 'static {
+    /// Returns the names of all files in the backend.
+    fn list(&self) -> io::Result<Vec<String>>;
+
+    /// Downloads the file with the given name to the given path.
+    fn fetch(&self, name: &str, path: &Path) -> io::Result<()>;
+}
+
+/// Downloads the file with the given name from the backend to the given path.
+///
+/// The file is downloaded to a temporary path first, so an interrupted download never leaves a
+/// partial file at the given path.
+pub(super) fn fetch_file(
+    backend: &dyn StaticFileBackend,
+    name: &str,
+    path: &Path,
+) -> ProviderResult<()> {
+    let download_path = download_path(path);
+    backend.fetch(name, &download_path).map_err(ProviderError::other)?;
+    reth_fs_util::rename(&download_path, path).map_err(ProviderError::other)
+}
+
+/// Downloads the data file of the static file at the given path from the backend.
+///
+/// The configuration and offsets files of the static file need to exist already. The downloaded
+/// data file is only moved to the given path if its size matches the last offset, otherwise the
+/// downloaded data and offsets files are removed.
+pub(super) fn fetch_data_file(
+    backend: &dyn StaticFileBackend,
+    name: &str,
+    path: &Path,
+) -> ProviderResult<()> {
+    let download_path = download_path(path);
+    backend.fetch(name, &download_path).map_err(ProviderError::other)?;
+
+    if let Err(err) = verify_data_file(path, &download_path) {
+        let _ = reth_fs_util::remove_file(&download_path);
+        let _ = reth_fs_util::remove_file(path.with_extension(OFFSETS_FILE_EXTENSION));
+        return Err(ProviderError::other(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("static file {name} downloaded from the backend is corrupted: {err}"),
+        )))
+    }
+    reth_fs_util::rename(&download_path, path).map_err(ProviderError::other)
+}
+
+/// Checks that the offsets file of the static file at the given path has an offset for every
+/// column of every row, and that the last offset matches the size of the data file at
+/// `data_path`.
+fn verify_data_file(path: &Path, data_path: &Path) -> io::Result<()> {
+    let jar = NippyJar::<SegmentHeader>::load(path).map_err(io::Error::other)?;
+
+    let mut offsets_file = File::open(path.with_extension(OFFSETS_FILE_EXTENSION))?;
+    let mut offset_size = [0; 1];
+    offsets_file.read_exact(&mut offset_size)?;
+    let offset_size = offset_size[0] as u64;
+    if offset_size == 0 || offset_size > 8 {
+        return Err(io::Error::other(format!("invalid offset size {offset_size}")))
+    }
+
+    // the offsets of all columns of all rows, followed by the expected size of the data file
+    let expected_offsets_len = 1 + offset_size * (jar.rows() as u64 * jar.columns() as u64 + 1);
+    let offsets_len = offsets_file.metadata()?.len();
+    if offsets_len != expected_offsets_len {
+        return Err(io::Error::other(format!(
+            "offsets file has {offsets_len} bytes, expected {expected_offsets_len}"
+        )))
+    }
+
+    let mut last_offset = [0; 8];
+    offsets_file.seek(SeekFrom::End(-(offset_size as i64)))?;
+    offsets_file.read_exact(&mut last_offset[..offset_size as usize])?;
+    let last_offset = u64::from_le_bytes(last_offset);
+    let data_len = reth_fs_util::metadata(data_path).map_err(io::Error::other)?.len();
+    if data_len != last_offset {
+        return Err(io::Error::other(format!(
+            "data file has {data_len} bytes, expected {last_offset}"
+        )))
+    }
+
+    Ok(())
+}
+
+/// Returns the temporary path a file is downloaded to before it's moved to the given path.
+fn download_path(path: &Path) -> PathBuf {
+    let mut download_path = OsString::from(path);
+    download_path.push(".download");
+    download_path.into()
+}
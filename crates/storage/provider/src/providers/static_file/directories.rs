@@ -0,0 +1,93 @@
+use reth_static_file_types::StaticFileSegment;
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+/// Name of the manifest file in the static files directory that records the
+/// [`SegmentDirectories`].
+pub const SEGMENT_DIRECTORIES_FILE_NAME: &str = "segment_directories.json";
+
+/// Directories of the static file segments that are stored outside of the static files directory,
+/// e.g. to keep immutable history on a cheaper volume than the database.
+///
+/// The directories are recorded in a manifest in the static files directory, so that the segments
+/// are found without configuring their directories again.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SegmentDirectories(BTreeMap<StaticFileSegment, PathBuf>);
+
+impl SegmentDirectories {
+    /// Reads the manifest of the static files directory. Returns no directories if there's no
+    /// manifest.
+    pub fn read(static_files_path: &Path) -> ProviderResult<Self> {
+        let path = static_files_path.join(SEGMENT_DIRECTORIES_FILE_NAME);
+        if !path.exists() {
+            return Ok(Self::default())
+        }
+        Ok(Self(reth_fs_util::read_json_file(&path).map_err(ProviderError::other)?))
+    }
+
+    /// Writes the manifest to the static files directory, or removes it if there are no
+    /// directories.
+    pub fn write(&self, static_files_path: &Path) -> ProviderResult<()> {
+        let path = static_files_path.join(SEGMENT_DIRECTORIES_FILE_NAME);
+        if self.0.is_empty() {
+            if path.exists() {
+                reth_fs_util::remove_file(&path).map_err(ProviderError::other)?;
+            }
+            return Ok(())
+        }
+
+        reth_fs_util::create_dir_all(static_files_path).map_err(ProviderError::other)?;
+        reth_fs_util::write_json_file(&path, &self.0).map_err(ProviderError::other)
+    }
+
+    /// Returns the directory of the segment, if it's not stored in the static files directory.
+    pub fn get(&self, segment: StaticFileSegment) -> Option<&Path> {
+        self.0.get(&segment).map(PathBuf::as_path)
+    }
+
+    /// Sets the directory of the segment.
+    pub fn insert(&mut self, segment: StaticFileSegment, directory: PathBuf) {
+        self.0.insert(segment, directory);
+    }
+
+    /// Moves the segment back to the static files directory.
+    pub fn remove(&mut self, segment: StaticFileSegment) {
+        self.0.remove(&segment);
+    }
+
+    /// Returns an iterator over the segments and their directories.
+    pub fn iter(&self) -> impl Iterator<Item = (StaticFileSegment, &Path)> {
+        self.0.iter().map(|(segment, directory)| (*segment, directory.as_path()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manifest_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(SegmentDirectories::read(dir.path()).unwrap(), SegmentDirectories::default());
+
+        let mut directories = SegmentDirectories::default();
+        directories.insert(StaticFileSegment::Transactions, "/mnt/hdd/transactions".into());
+        directories.insert(StaticFileSegment::Receipts, "/mnt/hdd/receipts".into());
+        directories.write(dir.path()).unwrap();
+        assert_eq!(SegmentDirectories::read(dir.path()).unwrap(), directories);
+        assert_eq!(
+            directories.get(StaticFileSegment::Transactions),
+            Some(Path::new("/mnt/hdd/transactions"))
+        );
+        assert_eq!(directories.get(StaticFileSegment::Headers), None);
+
+        // the manifest is removed once all segments are back in the static files directory
+        directories.remove(StaticFileSegment::Transactions);
+        directories.remove(StaticFileSegment::Receipts);
+        directories.write(dir.path()).unwrap();
+        assert!(!dir.path().join(SEGMENT_DIRECTORIES_FILE_NAME).exists());
+    }
+}
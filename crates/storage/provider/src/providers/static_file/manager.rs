@@ -1,6 +1,10 @@
 use super::{
-    metrics::StaticFileProviderMetrics, writer::StaticFileWriters, LoadedJar,
-    StaticFileJarProvider, StaticFileProviderRW, StaticFileProviderRWRefMut,
+    backend::{fetch_data_file, fetch_file, StaticFileBackend, DEFAULT_BACKEND_CACHE_SIZE},
+    metrics::StaticFileProviderMetrics,
+    pool::JarPool,
+    writer::StaticFileWriters,
+    LoadedJar, SegmentDirectories, StaticFileJarProvider, StaticFileProviderRW,
+    StaticFileProviderRWRefMut,
 };
 use crate::{
     to_range, BlockHashReader, BlockNumReader, BlockReader, BlockSource, HeaderProvider,
@@ -17,13 +21,13 @@ use alloy_primitives::{
 };
 use dashmap::DashMap;
 use notify::{RecommendedWatcher, RecursiveMode, Watcher};
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use reth_chainspec::{ChainInfo, ChainSpecProvider, EthChainSpec};
 use reth_db::{
     lockfile::StorageLock,
     static_file::{
         iter_static_files, BlockHashMask, BodyIndicesMask, HeaderMask, HeaderWithHashMask,
-        ReceiptMask, StaticFileCursor, TDWithHashMask, TransactionMask,
+        ReceiptMask, SortedStaticFiles, StaticFileCursor, TDWithHashMask, TransactionMask,
     },
 };
 use reth_db_api::{
@@ -34,7 +38,10 @@ use reth_db_api::{
     transaction::DbTx,
 };
 use reth_ethereum_primitives::{Receipt, TransactionSigned};
-use reth_nippy_jar::{NippyJar, NippyJarChecker, CONFIG_FILE_EXTENSION};
+use reth_nippy_jar::{
+    compression::EncryptionKey, NippyJar, NippyJarChecker, CONFIG_FILE_EXTENSION,
+    OFFSETS_FILE_EXTENSION,
+};
 use reth_node_types::{FullNodePrimitives, NodePrimitives};
 use reth_primitives_traits::{RecoveredBlock, SealedHeader, SignedTransaction};
 use reth_stages_types::{PipelineTarget, StageId};
@@ -42,7 +49,7 @@ use reth_static_file_types::{
     find_fixed_range, HighestStaticFiles, SegmentHeader, SegmentRangeInclusive, StaticFileSegment,
     DEFAULT_BLOCKS_PER_STATIC_FILE,
 };
-use reth_storage_api::{BlockBodyIndicesProvider, DBProvider};
+use reth_storage_api::{record_read, BlockBodyIndicesProvider, DBProvider, ReadSource};
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
 use std::{
     collections::{hash_map::Entry, BTreeMap, HashMap},
@@ -59,6 +66,12 @@ use tracing::{debug, info, trace, warn};
 /// range.
 type SegmentRanges = HashMap<StaticFileSegment, BTreeMap<TxNumber, SegmentRangeInclusive>>;
 
+/// Name of the directory inside the directory of a segment that holds the rewritten static files
+/// of a [`NippyJar::recompress`] until they are swapped in.
+///
+/// Interrupted swaps are completed when a read-write [`StaticFileProvider`] is created.
+pub const RECOMPRESSION_STAGING_DIR: &str = "recompress";
+
 /// Access mode on a static file provider. RO/RW.
 #[derive(Debug, Default, PartialEq, Eq)]
 pub enum StaticFileAccess {
@@ -102,10 +115,29 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
     /// Creates a new [`StaticFileProvider`] with the given [`StaticFileAccess`].
     fn new(path: impl AsRef<Path>, access: StaticFileAccess) -> ProviderResult<Self> {
         let provider = Self(Arc::new(StaticFileProviderInner::new(path, access)?));
+        if provider.access.is_read_write() {
+            provider.finish_recompressions()?;
+        }
         provider.initialize_index()?;
         Ok(provider)
     }
 
+    /// Completes the swaps of the static files whose recompression was interrupted after the
+    /// rewritten files were committed, and discards the rewritten files of uncommitted ones.
+    ///
+    /// See [`RECOMPRESSION_STAGING_DIR`].
+    fn finish_recompressions(&self) -> ProviderResult<()> {
+        for directory in self.directories() {
+            let staging_dir = directory.join(RECOMPRESSION_STAGING_DIR);
+            for path in NippyJar::<SegmentHeader>::finish_recompressions(directory, &staging_dir)
+                .map_err(ProviderError::other)?
+            {
+                info!(target: "reth::providers::static_file", ?path, "Completed interrupted recompression.");
+            }
+        }
+        Ok(())
+    }
+
     /// Creates a new [`StaticFileProvider`] with read-only access.
     ///
     /// Set `watch_directory` to `true` to track the most recent changes in static files. Otherwise,
@@ -145,9 +177,11 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
             )
             .expect("failed to create watcher");
 
-            watcher
-                .watch(&provider.path, RecursiveMode::NonRecursive)
-                .expect("failed to watch path");
+            for directory in provider.directories() {
+                watcher
+                    .watch(directory, RecursiveMode::NonRecursive)
+                    .expect("failed to watch path");
+            }
 
             // Some backends send repeated modified events
             let mut last_event_timestamp = None;
@@ -229,6 +263,8 @@ pub struct StaticFileProviderInner<N> {
     /// Maintains a map which allows for concurrent access to different `NippyJars`, over different
     /// segments and ranges.
     map: DashMap<(BlockNumber, StaticFileSegment), LoadedJar>,
+    /// Bounds the number of jars in `map`, and with it the number of concurrent mmaps.
+    jar_pool: JarPool,
     /// Min static file range for each segment.
     /// This index is initialized on launch to keep track of the lowest, non-expired static file
     /// per segment.
@@ -256,6 +292,18 @@ pub struct StaticFileProviderInner<N> {
     static_files_tx_index: RwLock<SegmentRanges>,
     /// Directory where `static_files` are located
     path: PathBuf,
+    /// Directories of the segments that are not located in `path`.
+    segment_directories: SegmentDirectories,
+    /// Remote store that missing static files are read through, if any.
+    backend: Option<Arc<dyn StaticFileBackend>>,
+    /// Static files of the backend, listed once when the backend is set.
+    backend_static_files: SortedStaticFiles,
+    /// Maximum size of the static files downloaded from the backend that are kept locally.
+    backend_cache_size: u64,
+    /// Locks that serialize the downloads of a static file from the backend, by path.
+    backend_fetch_locks: Mutex<HashMap<PathBuf, Arc<Mutex<()>>>>,
+    /// Key that static files are encrypted and decrypted with, if any.
+    encryption_key: Option<EncryptionKey>,
     /// Maintains a writer set of [`StaticFileSegment`].
     writers: StaticFileWriters<N>,
     /// Metrics for the static files.
@@ -281,12 +329,19 @@ impl<N: NodePrimitives> StaticFileProviderInner<N> {
 
         let provider = Self {
             map: Default::default(),
+            jar_pool: Default::default(),
             writers: Default::default(),
             static_files_min_block: Default::default(),
             earliest_history_height: Default::default(),
             static_files_max_block: Default::default(),
             static_files_tx_index: Default::default(),
             path: path.as_ref().to_path_buf(),
+            segment_directories: SegmentDirectories::read(path.as_ref())?,
+            backend: None,
+            backend_static_files: Default::default(),
+            backend_cache_size: DEFAULT_BACKEND_CACHE_SIZE,
+            backend_fetch_locks: Default::default(),
+            encryption_key: None,
             metrics: None,
             access,
             blocks_per_file: DEFAULT_BLOCKS_PER_STATIC_FILE,
@@ -301,6 +356,192 @@ impl<N: NodePrimitives> StaticFileProviderInner<N> {
         self.access.is_read_only()
     }
 
+    /// Returns the directory where the static files of the segment are located.
+    pub fn segment_directory(&self, segment: StaticFileSegment) -> &Path {
+        self.segment_directories.get(segment).unwrap_or(&self.path)
+    }
+
+    /// Returns the key that static files are encrypted and decrypted with, if any.
+    pub const fn encryption_key(&self) -> Option<&EncryptionKey> {
+        self.encryption_key.as_ref()
+    }
+
+    /// Loads the static file at the given path with the encryption key set, so its values can be
+    /// read and appended.
+    pub(crate) fn load_jar(&self, path: &Path) -> ProviderResult<NippyJar<SegmentHeader>> {
+        let jar = NippyJar::<SegmentHeader>::load(path).map_err(ProviderError::other)?;
+        jar.set_encryption_key(self.encryption_key.as_ref()).map_err(ProviderError::other)?;
+        Ok(jar)
+    }
+
+    /// Returns all directories where static files are located, starting with the static files
+    /// directory.
+    pub fn directories(&self) -> Vec<&Path> {
+        let mut directories = vec![self.path.as_path()];
+        for (_, directory) in self.segment_directories.iter() {
+            if !directories.contains(&directory) {
+                directories.push(directory);
+            }
+        }
+        directories
+    }
+
+    /// Returns the existing static files of all segments, each read from the directory of its
+    /// segment. See also [`iter_static_files`].
+    ///
+    /// If a backend is set, this includes the static files of the backend that don't exist
+    /// locally. The backend is not listed again, see [`StaticFileProvider::with_backend`].
+    pub fn iter_static_files(&self) -> ProviderResult<SortedStaticFiles> {
+        let mut static_files = self.iter_local_static_files()?;
+
+        for (segment, ranges) in &self.backend_static_files {
+            let local = static_files.entry(*segment).or_default();
+            for range in ranges {
+                if !local.iter().any(|(block_range, _)| *block_range == range.0) {
+                    local.push(*range);
+                }
+            }
+            local.sort_by_key(|(block_range, _)| block_range.end());
+        }
+
+        Ok(static_files)
+    }
+
+    /// Returns the static files of all segments that exist locally.
+    fn iter_local_static_files(&self) -> ProviderResult<SortedStaticFiles> {
+        let mut static_files = iter_static_files(&self.path).map_err(ProviderError::other)?;
+        static_files.retain(|segment, _| self.segment_directories.get(*segment).is_none());
+
+        for (segment, directory) in self.segment_directories.iter() {
+            if let Some(ranges) =
+                iter_static_files(directory).map_err(ProviderError::other)?.remove(&segment)
+            {
+                static_files.insert(segment, ranges);
+            }
+        }
+
+        Ok(static_files)
+    }
+
+    /// Returns the static files of the backend.
+    ///
+    /// The configuration files of the static files are downloaded to the segment directories if
+    /// they're not cached yet, the other files are only downloaded when the static file is
+    /// accessed, see [`Self::fetch_jar`].
+    fn iter_backend_static_files(
+        &self,
+        backend: &dyn StaticFileBackend,
+    ) -> ProviderResult<SortedStaticFiles> {
+        let mut static_files = SortedStaticFiles::default();
+        for name in backend.list().map_err(ProviderError::other)? {
+            let Some((segment, _)) = StaticFileSegment::parse_filename(&name) else { continue };
+
+            let path = self.segment_directory(segment).join(&name);
+            let config_path = path.with_extension(CONFIG_FILE_EXTENSION);
+            if !config_path.exists() {
+                fetch_file(backend, &format!("{name}.{CONFIG_FILE_EXTENSION}"), &config_path)?;
+            }
+
+            let jar = NippyJar::<SegmentHeader>::load(&path).map_err(ProviderError::other)?;
+            if let Some(block_range) = jar.user_header().block_range() {
+                static_files
+                    .entry(segment)
+                    .or_default()
+                    .push((*block_range, jar.user_header().tx_range().copied()));
+            }
+        }
+
+        Ok(static_files)
+    }
+
+    /// Returns the lock that serializes the downloads of the static file at the given path.
+    fn backend_fetch_lock(&self, path: &Path) -> Arc<Mutex<()>> {
+        self.backend_fetch_locks.lock().entry(path.to_path_buf()).or_default().clone()
+    }
+
+    /// Downloads the files of the static file at the given path from the backend, and evicts
+    /// other downloaded static files if the local cache exceeds its size.
+    ///
+    /// The caller must hold the [fetch lock](Self::backend_fetch_lock) of the path. The data file
+    /// is downloaded last and verified before it's moved into place, so a static file whose data
+    /// file exists is complete.
+    fn fetch_jar(
+        &self,
+        backend: &dyn StaticFileBackend,
+        segment: StaticFileSegment,
+        path: &Path,
+    ) -> ProviderResult<()> {
+        let name = path
+            .file_name()
+            .ok_or_else(|| ProviderError::MissingStaticFilePath(segment, path.to_path_buf()))?
+            .to_string_lossy();
+        debug!(target: "provider::static_file", %name, "Downloading static file from backend");
+
+        for extension in [CONFIG_FILE_EXTENSION, OFFSETS_FILE_EXTENSION] {
+            let extension_path = path.with_extension(extension);
+            if !extension_path.exists() {
+                fetch_file(backend, &format!("{name}.{extension}"), &extension_path)?;
+            }
+        }
+        fetch_data_file(backend, &name, path)?;
+
+        self.evict_backend_files()
+    }
+
+    /// Removes the data and offsets files of the least recently downloaded static files of the
+    /// backend until the downloaded static files fit into the cache size.
+    ///
+    /// Static files that are memory mapped or whose fetch lock is held are kept. The
+    /// configuration files are kept as well, so evicted static files remain in the index and
+    /// are downloaded again when they're accessed.
+    fn evict_backend_files(&self) -> ProviderResult<()> {
+        let mut cached = Vec::new();
+        let mut cache_size = 0;
+        for (segment, ranges) in &self.backend_static_files {
+            for (block_range, _) in ranges {
+                let fixed_block_range = self.find_fixed_range(block_range.start());
+                let path =
+                    self.segment_directory(*segment).join(segment.filename(&fixed_block_range));
+                let Ok(metadata) = reth_fs_util::metadata(&path) else { continue };
+
+                let offsets_size =
+                    reth_fs_util::metadata(path.with_extension(OFFSETS_FILE_EXTENSION))
+                        .map_or(0, |metadata| metadata.len());
+                let size = metadata.len() + offsets_size;
+                cache_size += size;
+                cached.push((
+                    metadata.modified().map_err(ProviderError::other)?,
+                    (fixed_block_range.end(), *segment),
+                    path,
+                    size,
+                ));
+            }
+        }
+        if cache_size <= self.backend_cache_size {
+            return Ok(())
+        }
+
+        cached.sort_by_key(|(modified, ..)| *modified);
+        for (_, key, path, size) in cached {
+            if cache_size <= self.backend_cache_size {
+                break
+            }
+            if self.map.contains_key(&key) {
+                continue
+            }
+            let lock = self.backend_fetch_lock(&path);
+            let Some(_guard) = lock.try_lock() else { continue };
+
+            debug!(target: "provider::static_file", path = %path.display(), size, "Evicting static file downloaded from backend");
+            reth_fs_util::remove_file(&path).map_err(ProviderError::other)?;
+            reth_fs_util::remove_file(path.with_extension(OFFSETS_FILE_EXTENSION))
+                .map_err(ProviderError::other)?;
+            cache_size -= size;
+        }
+
+        Ok(())
+    }
+
     /// Each static file has a fixed number of blocks. This gives out the range where the requested
     /// block is positioned.
     pub const fn find_fixed_range(&self, block: BlockNumber) -> SegmentRangeInclusive {
@@ -318,6 +559,109 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         Self(Arc::new(provider))
     }
 
+    /// Limits the number of static file jars that are mapped at the same time.
+    ///
+    /// When the limit is reached, the least recently used jar without active readers is unmapped
+    /// before another jar is opened. If all jars are in use, opening a jar waits for one to be
+    /// released, and maps it over the limit if none is released in time.
+    pub fn with_max_open_jars(self, max_open_jars: usize) -> Self {
+        let mut provider =
+            Arc::try_unwrap(self.0).expect("should be called when initializing only");
+        provider.jar_pool = JarPool::new(max_open_jars);
+        Self(Arc::new(provider))
+    }
+
+    /// Locates the static files of the given segments in the given directories instead of the
+    /// static files directory.
+    ///
+    /// The directories are recorded in a manifest in the static files directory, so they don't need
+    /// to be configured again. Static files aren't moved, a segment can only be located in a new
+    /// directory if its static files were moved there before.
+    pub fn with_segment_directories(
+        self,
+        directories: impl IntoIterator<Item = (StaticFileSegment, PathBuf)>,
+    ) -> ProviderResult<Self> {
+        let mut provider =
+            Arc::try_unwrap(self.0).expect("should be called when initializing only");
+
+        let mut segment_directories = provider.segment_directories.clone();
+        for (segment, directory) in directories {
+            let current = provider.segment_directory(segment);
+            if current == directory {
+                continue
+            }
+            if iter_static_files(current).map_err(ProviderError::other)?.contains_key(&segment) {
+                return Err(ProviderError::StaticFileSegmentDirectory {
+                    segment,
+                    current: current.to_path_buf(),
+                    configured: directory,
+                })
+            }
+
+            if directory == provider.path {
+                segment_directories.remove(segment);
+            } else {
+                segment_directories.insert(segment, directory);
+            }
+        }
+
+        if segment_directories != provider.segment_directories {
+            if provider.access.is_read_write() {
+                segment_directories.write(&provider.path)?;
+            }
+            provider.segment_directories = segment_directories;
+        }
+
+        let provider = Self(Arc::new(provider));
+        provider.initialize_index()?;
+        Ok(provider)
+    }
+
+    /// Reads the static files that don't exist locally through the given backend, e.g. an object
+    /// storage bucket shared by multiple nodes.
+    ///
+    /// The backend is listed once and the configuration files of all its static files are
+    /// downloaded right away, the other files of a static file are downloaded when it's first
+    /// accessed and then kept in its segment directory, up to the
+    /// [cache size](Self::with_backend_cache_size). Static files are never uploaded to the
+    /// backend.
+    pub fn with_backend(self, backend: Arc<dyn StaticFileBackend>) -> ProviderResult<Self> {
+        let mut provider =
+            Arc::try_unwrap(self.0).expect("should be called when initializing only");
+        provider.backend_static_files = provider.iter_backend_static_files(backend.as_ref())?;
+        provider.backend = Some(backend);
+
+        let provider = Self(Arc::new(provider));
+        provider.initialize_index()?;
+        Ok(provider)
+    }
+
+    /// Sets the maximum size of the static files downloaded from the backend that are kept
+    /// locally, [`DEFAULT_BACKEND_CACHE_SIZE`] by default.
+    ///
+    /// The least recently downloaded static files are removed first.
+    pub fn with_backend_cache_size(self, size: u64) -> Self {
+        let mut provider =
+            Arc::try_unwrap(self.0).expect("should be called when initializing only");
+        provider.backend_cache_size = size;
+        Self(Arc::new(provider))
+    }
+
+    /// Encrypts new static files with the given key, and decrypts existing encrypted static files
+    /// with it.
+    pub fn with_encryption_key(self, key: Option<EncryptionKey>) -> Self {
+        let mut provider =
+            Arc::try_unwrap(self.0).expect("should be called when initializing only");
+        provider.encryption_key = key;
+        Self(Arc::new(provider))
+    }
+
+    /// Returns the number of cached jars.
+    #[cfg(test)]
+    pub(crate) fn cached_jars(&self) -> usize {
+        self.map.len()
+    }
+
     /// Enables metrics on the [`StaticFileProvider`].
     pub fn with_metrics(self) -> Self {
         let mut provider =
@@ -330,7 +674,8 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
     pub fn report_metrics(&self) -> ProviderResult<()> {
         let Some(metrics) = &self.metrics else { return Ok(()) };
 
-        let static_files = iter_static_files(&self.path).map_err(ProviderError::other)?;
+        // static files of the backend are not downloaded just to report their size
+        let static_files = self.iter_local_static_files()?;
         for (segment, ranges) in static_files {
             let mut entries = 0;
             let mut size = 0;
@@ -443,6 +788,7 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         fixed_block_range_end: BlockNumber,
     ) {
         self.map.remove(&(fixed_block_range_end, segment));
+        self.jar_pool.notify_released();
     }
 
     /// This handles history expiry by deleting all transaction static files below the given block.
@@ -494,9 +840,10 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         let fixed_block_range = self.find_fixed_range(block);
         let key = (fixed_block_range.end(), segment);
         let jar = if let Some((_, jar)) = self.map.remove(&key) {
+            self.jar_pool.notify_released();
             jar.jar
         } else {
-            let file = self.path.join(segment.filename(&fixed_block_range));
+            let file = self.segment_directory(segment).join(segment.filename(&fixed_block_range));
             debug!(
                 target: "provider::static_file",
                 ?file,
@@ -514,9 +861,11 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         Ok(())
     }
 
-    /// Given a segment and block range it returns a cached
-    /// [`StaticFileJarProvider`]. TODO(joshie): we should check the size and pop N if there's too
-    /// many.
+    /// Given a segment and block range it returns a cached [`StaticFileJarProvider`].
+    ///
+    /// If the jar is not cached yet and the number of mapped jars is limited, this may unmap the
+    /// least recently used jar or wait for one to be released, see
+    /// [`StaticFileProvider::with_max_open_jars`].
     fn get_or_create_jar_provider(
         &self,
         segment: StaticFileSegment,
@@ -528,12 +877,33 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         trace!(target: "provider::static_file", ?segment, ?fixed_block_range, "Getting provider");
         let mut provider: StaticFileJarProvider<'_, N> = if let Some(jar) = self.map.get(&key) {
             trace!(target: "provider::static_file", ?segment, ?fixed_block_range, "Jar found in cache");
+            if self.jar_pool.is_bounded() {
+                jar.touch(self.jar_pool.tick());
+            }
             jar.into()
         } else {
             trace!(target: "provider::static_file", ?segment, ?fixed_block_range, "Creating jar from scratch");
-            let path = self.path.join(segment.filename(fixed_block_range));
-            let jar = NippyJar::load(&path).map_err(ProviderError::other)?;
-            self.map.entry(key).insert(LoadedJar::new(jar)?).downgrade().into()
+            let reservation = self.jar_pool.reserve(&self.map);
+            if reservation.overflow {
+                debug!(target: "provider::static_file", ?segment, ?fixed_block_range, open = reservation.open, "No static file jar was released in time, mapping over the limit");
+            }
+            if let Some(metrics) = &self.metrics {
+                metrics.record_jar_reservation(&reservation);
+            }
+
+            let path = self.segment_directory(segment).join(segment.filename(fixed_block_range));
+            // held until the jar is mapped, so the static file isn't evicted in between
+            let fetch_lock = self.backend.as_ref().map(|_| self.backend_fetch_lock(&path));
+            let _fetch_guard = fetch_lock.as_ref().map(|lock| lock.lock());
+            // another reader may have downloaded the static file while we were waiting for the lock
+            if let Some(backend) = self.backend.as_ref().filter(|_| !path.exists()) {
+                self.fetch_jar(backend.as_ref(), segment, &path)?;
+            }
+            let jar = LoadedJar::new(self.load_jar(&path)?)?;
+            jar.touch(self.jar_pool.tick());
+            let jar = self.map.entry(key).insert(jar).downgrade();
+            self.jar_pool.track(key);
+            jar.into()
         };
 
         if let Some(metrics) = &self.metrics {
@@ -604,7 +974,7 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
                 let fixed_range = self.find_fixed_range(segment_max_block);
 
                 let jar = NippyJar::<SegmentHeader>::load(
-                    &self.path.join(segment.filename(&fixed_range)),
+                    &self.segment_directory(segment).join(segment.filename(&fixed_range)),
                 )
                 .map_err(ProviderError::other)?;
 
@@ -650,6 +1020,7 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
 
                 // Update the cached provider.
                 self.map.insert((fixed_range.end(), segment), LoadedJar::new(jar)?);
+                self.jar_pool.track((fixed_range.end(), segment));
 
                 // Delete any cached provider that no longer has an associated jar.
                 self.map.retain(|(end, seg), _| !(*seg == segment && *end > fixed_range.end()));
@@ -673,7 +1044,7 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         max_block.clear();
         tx_index.clear();
 
-        for (segment, ranges) in iter_static_files(&self.path).map_err(ProviderError::other)? {
+        for (segment, ranges) in self.iter_static_files()? {
             // Update first and last block for each segment
             if let Some((first_block_range, _)) = ranges.first() {
                 min_block.insert(segment, *first_block_range);
@@ -701,6 +1072,7 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
 
         // If this is a re-initialization, we need to clear this as well
         self.map.clear();
+        self.jar_pool.notify_released();
 
         // initialize the expired history height to the lowest static file block
         if let Some(lowest_range) = min_block.get(&StaticFileSegment::Transactions) {
@@ -720,11 +1092,15 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
     /// 1) When a static file fails to commit but the underlying data was changed.
     /// 2) When a static file was committed, but the required database transaction was not.
     ///
-    /// For 1) it can self-heal if `self.access.is_read_only()` is set to `false`. Otherwise, it
-    /// will return an error.
+    /// For 1) it can self-heal if `self.access.is_read_only()` is set to `false`. Otherwise, the
+    /// latest static file of the segment is considered lost and its first block is the unwind
+    /// target.
     /// For 2) the invariants below are checked, and if broken, might require a pipeline unwind
     /// to heal.
     ///
+    /// If `self.access.is_read_only()` is set to `true`, nothing is written. The damage that
+    /// would be healed by pruning static files is returned as an unwind target instead.
+    ///
     /// For each static file segment:
     /// * the corresponding database table should overlap or have continuity in their keys
     ///   ([`TxNumber`] or [`BlockNumber`]).
@@ -797,7 +1173,19 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
             //   we are expected to still have. We need to check the Database and unwind everything
             //   accordingly.
             if self.access.is_read_only() {
-                self.check_segment_consistency(segment)?;
+                if let Err(err) = self.check_segment_consistency(segment) {
+                    let unwind = initial_highest_block
+                        .map(|block| self.find_fixed_range(block).start().saturating_sub(1))
+                        .unwrap_or_default();
+                    warn!(
+                        target: "reth::providers::static_file",
+                        %err,
+                        unwind_target = unwind,
+                        ?segment,
+                        "Latest static file is inconsistent, setting unwind target."
+                    );
+                    update_unwind_target(unwind);
+                }
             } else {
                 // Fetching the writer will attempt to heal any file level inconsistency.
                 self.latest_writer(segment)?;
@@ -896,8 +1284,9 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
     /// Read-only.
     pub fn check_segment_consistency(&self, segment: StaticFileSegment) -> ProviderResult<()> {
         if let Some(latest_block) = self.get_highest_static_file_block(segment) {
-            let file_path =
-                self.directory().join(segment.filename(&self.find_fixed_range(latest_block)));
+            let file_path = self
+                .segment_directory(segment)
+                .join(segment.filename(&self.find_fixed_range(latest_block)));
 
             let jar = NippyJar::<SegmentHeader>::load(&file_path).map_err(ProviderError::other)?;
 
@@ -991,6 +1380,19 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         // static files on executing a stage, or the reverse on unwinding a stage.
         // All we need to do is to prune the extra static file rows.
         if checkpoint_block_number < highest_static_file_block {
+            // Read-only access can't prune, the extra rows are reported as beyond the consistent
+            // range instead.
+            if self.access.is_read_only() {
+                info!(
+                    target: "reth::providers::static_file",
+                    checkpoint_block_number,
+                    highest_static_file_block,
+                    ?segment,
+                    "Setting unwind target, static file segment is ahead of the checkpoint."
+                );
+                return Ok(Some(checkpoint_block_number))
+            }
+
             info!(
                 target: "reth::providers",
                 ?segment,
@@ -1248,8 +1650,10 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         if static_file_upper_bound
             .is_some_and(|static_file_upper_bound| static_file_upper_bound >= number)
         {
+            record_read(ReadSource::StaticFiles);
             return fetch_from_static_file(self)
         }
+        record_read(ReadSource::Database);
         fetch_from_database()
     }
 
@@ -1287,6 +1691,7 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         } {
             if block_or_tx_range.start <= static_file_upper_bound {
                 let end = block_or_tx_range.end.min(static_file_upper_bound + 1);
+                record_read(ReadSource::StaticFiles);
                 data.extend(fetch_from_static_file(
                     self,
                     block_or_tx_range.start..end,
@@ -1297,6 +1702,7 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         }
 
         if block_or_tx_range.end > block_or_tx_range.start {
+            record_read(ReadSource::Database);
             data.extend(fetch_from_database(block_or_tx_range, predicate)?)
         }
 
@@ -1,5 +1,7 @@
 use std::{collections::HashMap, time::Duration};
 
+use super::pool::JarReservation;
+
 use itertools::Itertools;
 use metrics::{Counter, Gauge, Histogram};
 use reth_metrics::Metrics;
@@ -14,6 +16,7 @@ pub struct StaticFileProviderMetrics {
         (StaticFileSegment, StaticFileProviderOperation),
         StaticFileProviderOperationMetrics,
     >,
+    jar_pool: StaticFileJarPoolMetrics,
 }
 
 impl Default for StaticFileProviderMetrics {
@@ -39,6 +42,7 @@ impl Default for StaticFileProviderMetrics {
                     )
                 })
                 .collect(),
+            jar_pool: StaticFileJarPoolMetrics::default(),
         }
     }
 }
@@ -102,6 +106,18 @@ impl StaticFileProviderMetrics {
                 .record(duration.as_secs_f64() / count as f64);
         }
     }
+
+    pub(crate) fn record_jar_reservation(&self, reservation: &JarReservation) {
+        self.jar_pool.open_jars.set(reservation.open as f64);
+        self.jar_pool.evictions_total.increment(reservation.evicted as u64);
+        if let Some(waited) = reservation.waited {
+            self.jar_pool.waits_total.increment(1);
+            self.jar_pool.wait_duration_seconds.record(waited.as_secs_f64());
+        }
+        if reservation.overflow {
+            self.jar_pool.overflows_total.increment(1);
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter)]
@@ -147,3 +163,19 @@ pub(crate) struct StaticFileProviderOperationMetrics {
     /// The time it took to execute the static file jar provider operation that writes data.
     write_duration_seconds: Histogram,
 }
+
+/// Metrics for the pool of mapped static file jars.
+#[derive(Metrics)]
+#[metrics(scope = "static_files.jar_pool")]
+pub(crate) struct StaticFileJarPoolMetrics {
+    /// The number of mapped static file jars
+    open_jars: Gauge,
+    /// Total number of jars unmapped to stay within the limit of mapped jars
+    evictions_total: Counter,
+    /// Total number of jar opens that waited for another jar to be released
+    waits_total: Counter,
+    /// The time jar opens waited for another jar to be released
+    wait_duration_seconds: Histogram,
+    /// Total number of jars mapped over the limit because no jar was released in time
+    overflows_total: Counter,
+}
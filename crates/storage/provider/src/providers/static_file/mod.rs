@@ -1,5 +1,7 @@
 mod manager;
-pub use manager::{StaticFileAccess, StaticFileProvider, StaticFileWriter};
+pub use manager::{
+    StaticFileAccess, StaticFileProvider, StaticFileWriter, RECOMPRESSION_STAGING_DIR,
+};
 
 mod jar;
 pub use jar::StaticFileJarProvider;
@@ -7,11 +9,26 @@ pub use jar::StaticFileJarProvider;
 mod writer;
 pub use writer::{StaticFileProviderRW, StaticFileProviderRWRefMut};
 
+mod directories;
+pub use directories::{SegmentDirectories, SEGMENT_DIRECTORIES_FILE_NAME};
+
+mod backend;
+pub use backend::{StaticFileBackend, DEFAULT_BACKEND_CACHE_SIZE};
+
 mod metrics;
+
+mod pool;
+
 use reth_nippy_jar::NippyJar;
 use reth_static_file_types::{SegmentHeader, StaticFileSegment};
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
-use std::{ops::Deref, sync::Arc};
+use std::{
+    ops::Deref,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
 
 /// Alias type for each specific `NippyJar`.
 type LoadedJarRef<'a> = dashmap::mapref::one::Ref<'a, (u64, StaticFileSegment), LoadedJar>;
@@ -21,6 +38,8 @@ type LoadedJarRef<'a> = dashmap::mapref::one::Ref<'a, (u64, StaticFileSegment),
 pub struct LoadedJar {
     jar: NippyJar<SegmentHeader>,
     mmap_handle: Arc<reth_nippy_jar::DataReader>,
+    /// Access tick of the last read, used to unmap the least recently used jar.
+    last_access: AtomicU64,
 }
 
 impl LoadedJar {
@@ -28,7 +47,7 @@ impl LoadedJar {
         match jar.open_data_reader() {
             Ok(data_reader) => {
                 let mmap_handle = Arc::new(data_reader);
-                Ok(Self { jar, mmap_handle })
+                Ok(Self { jar, mmap_handle, last_access: AtomicU64::new(0) })
             }
             Err(e) => Err(ProviderError::other(e)),
         }
@@ -39,6 +58,19 @@ impl LoadedJar {
         self.mmap_handle.clone()
     }
 
+    /// Returns `true` if a cursor still holds the mmap handle.
+    fn is_in_use(&self) -> bool {
+        Arc::strong_count(&self.mmap_handle) > 1
+    }
+
+    fn last_access(&self) -> u64 {
+        self.last_access.load(Ordering::Relaxed)
+    }
+
+    fn touch(&self, tick: u64) {
+        self.last_access.store(tick, Ordering::Relaxed);
+    }
+
     const fn segment(&self) -> StaticFileSegment {
         self.jar.user_header().segment()
     }
@@ -55,16 +87,18 @@ impl Deref for LoadedJar {
 mod tests {
     use super::*;
     use crate::{
-        test_utils::create_test_provider_factory, HeaderProvider, StaticFileProviderFactory,
+        test_utils::create_test_provider_factory, HeaderProvider, StageCheckpointWriter,
+        StaticFileProviderFactory,
     };
     use alloy_consensus::{Header, SignableTransaction, Transaction, TxLegacy};
     use alloy_primitives::{BlockHash, Signature, TxNumber, B256, U256};
     use rand::seq::SliceRandom;
-    use reth_db::test_utils::create_test_static_files_dir;
+    use reth_db::{static_file::iter_static_files, test_utils::create_test_static_files_dir};
     use reth_db_api::{
         transaction::DbTxMut, CanonicalHeaders, HeaderNumbers, HeaderTerminalDifficulties, Headers,
     };
     use reth_ethereum_primitives::{EthPrimitives, Receipt, TransactionSigned};
+    use reth_stages_types::{PipelineTarget, StageCheckpoint, StageId};
     use reth_static_file_types::{
         find_fixed_range, SegmentRangeInclusive, DEFAULT_BLOCKS_PER_STATIC_FILE,
     };
@@ -295,6 +329,293 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_max_open_jars() {
+        let (static_dir, _) = create_test_static_files_dir();
+        let blocks_per_file = 10;
+
+        {
+            let sf_rw = StaticFileProvider::<EthPrimitives>::read_write(&static_dir)
+                .expect("Failed to create static file provider")
+                .with_custom_blocks_per_file(blocks_per_file);
+            let mut header_writer = sf_rw.latest_writer(StaticFileSegment::Headers).unwrap();
+            let mut header = Header::default();
+            for num in 0..blocks_per_file * 3 {
+                header.number = num;
+                header_writer
+                    .append_header(&header, U256::default(), &BlockHash::default())
+                    .unwrap();
+            }
+            header_writer.commit().unwrap();
+        }
+
+        let sf = StaticFileProvider::<EthPrimitives>::read_only(&static_dir, false)
+            .expect("Failed to create static file provider")
+            .with_custom_blocks_per_file(blocks_per_file)
+            .with_max_open_jars(2);
+
+        // every read of a third jar unmaps the least recently used one
+        for num in [0, 15, 25, 5, 18] {
+            assert_eq!(sf.header_by_number(num).unwrap().map(|header| header.number), Some(num));
+            assert!(sf.cached_jars() <= 2);
+        }
+    }
+
+    #[test]
+    fn test_read_only_check_consistency() {
+        let (_static_dir, static_dir) = create_test_static_files_dir();
+        let blocks_per_file = 10;
+
+        {
+            let sf_rw = StaticFileProvider::<EthPrimitives>::read_write(&static_dir)
+                .expect("Failed to create static file provider")
+                .with_custom_blocks_per_file(blocks_per_file);
+            let mut header_writer = sf_rw.latest_writer(StaticFileSegment::Headers).unwrap();
+            let mut header = Header::default();
+            for num in 0..blocks_per_file + 5 {
+                header.number = num;
+                header_writer
+                    .append_header(&header, U256::default(), &BlockHash::default())
+                    .unwrap();
+            }
+            header_writer.commit().unwrap();
+        }
+
+        let sf = StaticFileProvider::<EthPrimitives>::read_only(&static_dir, false)
+            .expect("Failed to create static file provider")
+            .with_custom_blocks_per_file(blocks_per_file);
+        let jar_path = static_dir
+            .join(StaticFileSegment::Headers.filename(&sf.find_fixed_range(blocks_per_file)));
+        let offsets_path = NippyJar::<SegmentHeader>::load(&jar_path).unwrap().offsets_path();
+        let file_lens = || [&jar_path, &offsets_path].map(|path| fs::metadata(path).unwrap().len());
+        let lens = file_lens();
+
+        // static files ahead of the checkpoint are reported instead of pruned
+        let factory = create_test_provider_factory();
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw
+            .save_stage_checkpoint(StageId::Headers, StageCheckpoint::new(blocks_per_file + 2))
+            .unwrap();
+        provider_rw.commit().unwrap();
+        assert_eq!(
+            sf.check_consistency(&factory.provider().unwrap(), false).unwrap(),
+            Some(PipelineTarget::Unwind(blocks_per_file + 2))
+        );
+        assert_eq!(file_lens(), lens);
+        assert!(sf.header_by_number(blocks_per_file + 4).unwrap().is_some());
+
+        // an interrupted prune of the latest static file is reported instead of healed
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw
+            .save_stage_checkpoint(StageId::Headers, StageCheckpoint::new(blocks_per_file + 4))
+            .unwrap();
+        provider_rw.commit().unwrap();
+        let offsets_file = fs::OpenOptions::new().write(true).open(&offsets_path).unwrap();
+        offsets_file.set_len(lens[1] - 8).unwrap();
+        let lens = file_lens();
+        assert_eq!(
+            sf.check_consistency(&factory.provider().unwrap(), false).unwrap(),
+            Some(PipelineTarget::Unwind(blocks_per_file - 1))
+        );
+        assert_eq!(file_lens(), lens);
+    }
+
+    #[test]
+    fn test_segment_directories() {
+        let (_static_dir, static_dir) = create_test_static_files_dir();
+        let headers_dir = tempfile::tempdir().unwrap();
+        let blocks_per_file = 10;
+
+        {
+            let sf_rw = StaticFileProvider::<EthPrimitives>::read_write(&static_dir)
+                .expect("Failed to create static file provider")
+                .with_segment_directories([(
+                    StaticFileSegment::Headers,
+                    headers_dir.path().to_path_buf(),
+                )])
+                .expect("Failed to configure segment directories")
+                .with_custom_blocks_per_file(blocks_per_file);
+            let mut header_writer = sf_rw.latest_writer(StaticFileSegment::Headers).unwrap();
+            let mut header = Header::default();
+            for num in 0..blocks_per_file + 5 {
+                header.number = num;
+                header_writer
+                    .append_header(&header, U256::default(), &BlockHash::default())
+                    .unwrap();
+            }
+            header_writer.commit().unwrap();
+        }
+
+        assert!(iter_static_files(headers_dir.path())
+            .unwrap()
+            .contains_key(&StaticFileSegment::Headers));
+        assert!(!iter_static_files(&static_dir).unwrap().contains_key(&StaticFileSegment::Headers));
+
+        // the segment directory is read from the manifest
+        let sf = StaticFileProvider::<EthPrimitives>::read_only(&static_dir, false)
+            .expect("Failed to create static file provider")
+            .with_custom_blocks_per_file(blocks_per_file);
+        assert_eq!(sf.segment_directory(StaticFileSegment::Headers), headers_dir.path());
+        assert_eq!(sf.header_by_number(12).unwrap().map(|header| header.number), Some(12));
+
+        // the read-only consistency check finds the latest file of the relocated segment
+        let factory = create_test_provider_factory();
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw
+            .save_stage_checkpoint(StageId::Headers, StageCheckpoint::new(blocks_per_file + 4))
+            .unwrap();
+        provider_rw.commit().unwrap();
+        assert_eq!(sf.check_consistency(&factory.provider().unwrap(), false).unwrap(), None);
+
+        // the static files need to be moved before the segment is located somewhere else
+        let result = StaticFileProvider::<EthPrimitives>::read_write(&static_dir)
+            .expect("Failed to create static file provider")
+            .with_segment_directories([(StaticFileSegment::Headers, static_dir.clone())]);
+        assert!(matches!(result, Err(ProviderError::StaticFileSegmentDirectory { .. })));
+    }
+
+    /// A backend serving the files of a local directory.
+    #[derive(Debug)]
+    struct DirectoryBackend {
+        directory: std::path::PathBuf,
+        lists: std::sync::atomic::AtomicUsize,
+    }
+
+    impl DirectoryBackend {
+        fn new(directory: std::path::PathBuf) -> Self {
+            Self { directory, lists: Default::default() }
+        }
+    }
+
+    impl StaticFileBackend for DirectoryBackend {
+        fn list(&self) -> std::io::Result<Vec<String>> {
+            self.lists.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            fs::read_dir(&self.directory)?
+                .map(|entry| Ok(entry?.file_name().to_string_lossy().into_owned()))
+                .collect()
+        }
+
+        fn fetch(&self, name: &str, path: &Path) -> std::io::Result<()> {
+            fs::copy(self.directory.join(name), path).map(|_| ())
+        }
+    }
+
+    /// Writes the headers of blocks `0..=24` to static files in the given directory.
+    fn write_backend_headers(directory: &Path, blocks_per_file: u64) {
+        let sf_rw = StaticFileProvider::<EthPrimitives>::read_write(directory)
+            .expect("Failed to create static file provider")
+            .with_custom_blocks_per_file(blocks_per_file);
+        let mut header_writer = sf_rw.latest_writer(StaticFileSegment::Headers).unwrap();
+        let mut header = Header::default();
+        for num in 0..blocks_per_file * 2 + 5 {
+            header.number = num;
+            header_writer.append_header(&header, U256::default(), &BlockHash::default()).unwrap();
+        }
+        header_writer.commit().unwrap();
+    }
+
+    #[test]
+    fn test_backend() {
+        let (_remote_dir, remote_dir) = create_test_static_files_dir();
+        let (_local_dir, local_dir) = create_test_static_files_dir();
+        let blocks_per_file = 10;
+        write_backend_headers(&remote_dir, blocks_per_file);
+
+        let backend = Arc::new(DirectoryBackend::new(remote_dir.clone()));
+        let sf = StaticFileProvider::<EthPrimitives>::read_only(&local_dir, false)
+            .expect("Failed to create static file provider")
+            .with_custom_blocks_per_file(blocks_per_file)
+            .with_backend(backend.clone())
+            .expect("Failed to set backend");
+        assert_eq!(sf.get_highest_static_file_block(StaticFileSegment::Headers), Some(24));
+
+        // only the configuration files are downloaded until a static file is accessed
+        let filename = StaticFileSegment::Headers.filename(&find_fixed_range(12, blocks_per_file));
+        assert!(local_dir.join(&filename).with_extension("conf").exists());
+        assert!(!local_dir.join(&filename).exists());
+        assert!(!iter_static_files(&local_dir).unwrap().contains_key(&StaticFileSegment::Headers));
+
+        assert_eq!(sf.header_by_number(12).unwrap().map(|header| header.number), Some(12));
+        assert!(local_dir.join(&filename).exists());
+        assert_eq!(
+            iter_static_files(&local_dir).unwrap()[&StaticFileSegment::Headers].len(),
+            1,
+            "only the accessed static file is downloaded"
+        );
+
+        // re-initializing the index, e.g. by the directory watcher, doesn't list the backend again
+        sf.initialize_index().unwrap();
+        assert_eq!(sf.get_highest_static_file_block(StaticFileSegment::Headers), Some(24));
+        assert_eq!(backend.lists.load(std::sync::atomic::Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_backend_corrupted_download() {
+        let (_remote_dir, remote_dir) = create_test_static_files_dir();
+        let (_local_dir, local_dir) = create_test_static_files_dir();
+        let blocks_per_file = 10;
+        write_backend_headers(&remote_dir, blocks_per_file);
+
+        let filename = StaticFileSegment::Headers.filename(&find_fixed_range(12, blocks_per_file));
+        let data = fs::read(remote_dir.join(&filename)).unwrap();
+        fs::write(remote_dir.join(&filename), &data[..data.len() - 1]).unwrap();
+
+        let sf = StaticFileProvider::<EthPrimitives>::read_only(&local_dir, false)
+            .expect("Failed to create static file provider")
+            .with_custom_blocks_per_file(blocks_per_file)
+            .with_backend(Arc::new(DirectoryBackend::new(remote_dir.clone())))
+            .expect("Failed to set backend");
+
+        // the truncated data file is never moved into place
+        assert!(sf.header_by_number(12).is_err());
+        assert!(!local_dir.join(&filename).exists());
+        assert!(!local_dir.join(&filename).with_extension("off").exists());
+
+        // the static file is downloaded again once the backend serves it intact
+        fs::write(remote_dir.join(&filename), &data).unwrap();
+        assert_eq!(sf.header_by_number(12).unwrap().map(|header| header.number), Some(12));
+        assert!(local_dir.join(&filename).exists());
+    }
+
+    #[test]
+    fn test_backend_cache_size() {
+        let (_remote_dir, remote_dir) = create_test_static_files_dir();
+        let (_local_dir, local_dir) = create_test_static_files_dir();
+        let blocks_per_file = 10;
+        write_backend_headers(&remote_dir, blocks_per_file);
+
+        let provider = || {
+            StaticFileProvider::<EthPrimitives>::read_only(&local_dir, false)
+                .expect("Failed to create static file provider")
+                .with_custom_blocks_per_file(blocks_per_file)
+                .with_backend_cache_size(1)
+                .with_backend(Arc::new(DirectoryBackend::new(remote_dir.clone())))
+                .expect("Failed to set backend")
+        };
+        let first = StaticFileSegment::Headers.filename(&find_fixed_range(2, blocks_per_file));
+        let second = StaticFileSegment::Headers.filename(&find_fixed_range(12, blocks_per_file));
+
+        // static files that are mapped are kept, even if the cache is full
+        let sf = provider();
+        assert_eq!(sf.header_by_number(2).unwrap().map(|header| header.number), Some(2));
+        assert_eq!(sf.header_by_number(12).unwrap().map(|header| header.number), Some(12));
+        assert!(local_dir.join(&first).exists());
+        assert!(local_dir.join(&second).exists());
+        drop(sf);
+
+        // the least recently downloaded static file is evicted, its configuration file is kept
+        let sf = provider();
+        assert_eq!(sf.header_by_number(22).unwrap().map(|header| header.number), Some(22));
+        assert!(!local_dir.join(&first).exists());
+        assert!(!local_dir.join(&first).with_extension("off").exists());
+        assert!(local_dir.join(&first).with_extension("conf").exists());
+        assert_eq!(sf.get_highest_static_file_block(StaticFileSegment::Headers), Some(24));
+
+        // evicted static files are downloaded again when they're accessed
+        assert_eq!(sf.header_by_number(2).unwrap().map(|header| header.number), Some(2));
+        assert!(local_dir.join(&first).exists());
+    }
+
     /// 3 block ranges are built
     ///
     /// for `blocks_per_file = 10`:
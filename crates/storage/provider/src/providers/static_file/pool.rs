@@ -0,0 +1,160 @@
+use super::LoadedJar;
+use alloy_primitives::BlockNumber;
+use dashmap::{mapref::entry::Entry, try_result::TryResult, DashMap};
+use parking_lot::{Condvar, Mutex};
+use reth_static_file_types::StaticFileSegment;
+use std::{
+    collections::HashSet,
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, Instant},
+};
+
+/// Key of a loaded jar, the end of its fixed block range and its segment.
+pub(crate) type JarKey = (BlockNumber, StaticFileSegment);
+
+/// Default time a jar open waits for another jar to be released before mapping over the limit.
+pub(crate) const DEFAULT_JAR_WAIT: Duration = Duration::from_secs(1);
+
+/// Interval at which waiting jar opens retry to unmap a jar.
+const JAR_WAIT_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Bounds the number of static file jars that are mapped at the same time.
+///
+/// When the limit is reached, opening another jar unmaps the least recently used jar that has no
+/// active readers. If all jars are in use, the open waits for a reader to release one. The wait is
+/// bounded, afterwards the jar is mapped over the limit, since the waiting reader may itself hold
+/// the jars that would have to be released.
+#[derive(Debug)]
+pub(crate) struct JarPool {
+    /// Maximum number of mapped jars, unbounded if `None`.
+    max_open: Option<usize>,
+    /// Maximum time a jar open waits for another jar to be released.
+    max_wait: Duration,
+    /// Monotonic counter used to order jar accesses.
+    clock: AtomicU64,
+    /// Keys of the jars mapped through the pool.
+    ///
+    /// Jars can be removed from the map without going through the pool, so this may contain keys
+    /// of jars that are no longer mapped. These are pruned before the pool is checked for space.
+    open: Mutex<HashSet<JarKey>>,
+    /// Notified when a jar is unmapped outside of the pool.
+    released: Condvar,
+}
+
+impl Default for JarPool {
+    fn default() -> Self {
+        Self {
+            max_open: None,
+            max_wait: DEFAULT_JAR_WAIT,
+            clock: AtomicU64::new(0),
+            open: Default::default(),
+            released: Condvar::new(),
+        }
+    }
+}
+
+/// Outcome of [`JarPool::reserve`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct JarReservation {
+    /// Number of jars that were unmapped to make space.
+    pub(crate) evicted: usize,
+    /// Time spent waiting for a jar to be released, if the pool was full.
+    pub(crate) waited: Option<Duration>,
+    /// Whether no jar was released in time and the new jar is mapped over the limit.
+    pub(crate) overflow: bool,
+    /// Number of mapped jars, including the reserved one.
+    pub(crate) open: usize,
+}
+
+impl JarPool {
+    /// Creates a pool that maps at most `max_open` jars at the same time.
+    pub(crate) fn new(max_open: usize) -> Self {
+        Self { max_open: Some(max_open.max(1)), ..Default::default() }
+    }
+
+    /// Returns `true` if the number of mapped jars is limited.
+    pub(crate) const fn is_bounded(&self) -> bool {
+        self.max_open.is_some()
+    }
+
+    /// Returns the next access tick, used to find the least recently used jar.
+    pub(crate) fn tick(&self) -> u64 {
+        self.clock.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// Makes space for a new jar in the map, unmapping or waiting for jars if the pool is full.
+    ///
+    /// The map is only accessed with non-blocking operations, so this can't deadlock on a map
+    /// shard that is held by the caller.
+    pub(crate) fn reserve(&self, map: &DashMap<JarKey, LoadedJar>) -> JarReservation {
+        let mut reservation = JarReservation::default();
+        let Some(max_open) = self.max_open else { return reservation };
+
+        let mut open = self.open.lock();
+        let started = Instant::now();
+        let mut waited = false;
+        loop {
+            open.retain(|key| !matches!(map.try_get(key), TryResult::Absent));
+            if open.len() < max_open {
+                break
+            }
+            if Self::evict_least_recently_used(&mut open, map) {
+                reservation.evicted += 1;
+                continue
+            }
+            if started.elapsed() >= self.max_wait {
+                reservation.overflow = true;
+                break
+            }
+            self.released.wait_for(&mut open, JAR_WAIT_POLL_INTERVAL);
+            waited = true;
+        }
+
+        reservation.waited = waited.then(|| started.elapsed());
+        reservation.open = open.len() + 1;
+        reservation
+    }
+
+    /// Records a jar that was mapped.
+    pub(crate) fn track(&self, key: JarKey) {
+        if self.is_bounded() {
+            self.open.lock().insert(key);
+        }
+    }
+
+    /// Wakes up jar opens waiting for space, after a jar was unmapped outside of the pool.
+    pub(crate) fn notify_released(&self) {
+        if self.is_bounded() {
+            self.released.notify_all();
+        }
+    }
+
+    /// Unmaps the least recently used jar without active readers, returns `false` if all jars are
+    /// in use.
+    fn evict_least_recently_used(
+        open: &mut HashSet<JarKey>,
+        map: &DashMap<JarKey, LoadedJar>,
+    ) -> bool {
+        let mut candidates = open
+            .iter()
+            .filter_map(|key| match map.try_get(key) {
+                TryResult::Present(jar) if !jar.is_in_use() => Some((jar.last_access(), *key)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        candidates.sort_unstable_by_key(|(last_access, _)| *last_access);
+
+        for (_, key) in candidates {
+            // A locked entry is being read, skip it
+            if let Some(Entry::Occupied(entry)) = map.try_entry(key) {
+                if entry.get().is_in_use() {
+                    continue
+                }
+                entry.remove();
+                open.remove(&key);
+                return true
+            }
+        }
+        false
+    }
+}
@@ -9,7 +9,7 @@ use reth_codecs::Compact;
 use reth_db_api::models::{
     CompactU256, StoredBlockBodyIndices, StoredBlockOmmers, StoredBlockWithdrawals,
 };
-use reth_nippy_jar::{NippyJar, NippyJarError, NippyJarWriter};
+use reth_nippy_jar::{compression::EncryptionKey, NippyJar, NippyJarError, NippyJarWriter};
 use reth_node_types::NodePrimitives;
 use reth_static_file_types::{SegmentHeader, SegmentRangeInclusive, StaticFileSegment};
 use reth_storage_errors::provider::{ProviderError, ProviderResult, StaticFileWriterError};
@@ -160,13 +160,16 @@ impl<N: NodePrimitives> StaticFileProviderRW<N> {
             block_range.start(),
             None,
         ) {
-            Ok(provider) => (
-                NippyJar::load(provider.data_path()).map_err(ProviderError::other)?,
-                provider.data_path().into(),
-            ),
+            Ok(provider) => {
+                (static_file_provider.load_jar(provider.data_path())?, provider.data_path().into())
+            }
             Err(ProviderError::MissingStaticFileBlock(_, _)) => {
-                let path = static_file_provider.directory().join(segment.filename(&block_range));
-                (create_jar(segment, &path, block_range), path)
+                let path = static_file_provider
+                    .segment_directory(segment)
+                    .join(segment.filename(&block_range));
+                let jar =
+                    create_jar(segment, &path, block_range, static_file_provider.encryption_key());
+                (jar, path)
             }
             Err(err) => return Err(err),
         };
@@ -872,6 +875,7 @@ fn create_jar(
     segment: StaticFileSegment,
     path: &Path,
     expected_block_range: SegmentRangeInclusive,
+    encryption_key: Option<&EncryptionKey>,
 ) -> NippyJar<SegmentHeader> {
     let mut jar = NippyJar::new(
         segment.columns(),
@@ -885,5 +889,9 @@ fn create_jar(
         jar = jar.with_lz4();
     }
 
+    if let Some(key) = encryption_key {
+        jar = jar.with_encryption(key);
+    }
+
     jar
 }
@@ -28,9 +28,10 @@ use reth_primitives_traits::{
 use reth_prune_types::PruneModes;
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_storage_api::{
-    BlockBodyIndicesProvider, BytecodeReader, DBProvider, DatabaseProviderFactory,
+    BlockBodyIndicesProvider, BytecodeReader, DBProvider, DatabaseProviderFactory, GasUsageReader,
     HashedPostStateProvider, NodePrimitivesProvider, StageCheckpointReader,
-    StateCommitmentProvider, StateProofProvider, StorageRootProvider,
+    StateCommitmentProvider, StateProofProvider, StateSize, StateSizeReader, StorageRootProvider,
+    TopGasConsumers,
 };
 use reth_storage_errors::provider::{ConsistentViewError, ProviderError, ProviderResult};
 use reth_trie::{
@@ -825,6 +826,15 @@ where
     ) -> ProviderResult<StorageMultiProof> {
         Ok(StorageMultiProof::empty())
     }
+
+    fn storage_multiproof_from_nodes(
+        &self,
+        _input: TrieInput,
+        _address: Address,
+        _slots: &[B256],
+    ) -> ProviderResult<StorageMultiProof> {
+        Ok(StorageMultiProof::empty())
+    }
 }
 
 impl<T, ChainSpec> StateProofProvider for MockEthProvider<T, ChainSpec>
@@ -972,6 +982,30 @@ impl<T: NodePrimitives, ChainSpec: Send + Sync> ChangeSetReader for MockEthProvi
     ) -> ProviderResult<Vec<AccountBeforeTx>> {
         Ok(Vec::default())
     }
+    fn account_change_blocks(
+        &self,
+        _address: Address,
+        _range: RangeInclusive<BlockNumber>,
+        _limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        Ok(Vec::default())
+    }
+}
+
+impl<T: NodePrimitives, ChainSpec: Send + Sync> StateSizeReader for MockEthProvider<T, ChainSpec> {
+    fn state_size(&self) -> ProviderResult<Option<StateSize>> {
+        Ok(None)
+    }
+}
+
+impl<T: NodePrimitives, ChainSpec: Send + Sync> GasUsageReader for MockEthProvider<T, ChainSpec> {
+    fn top_gas_consumers(
+        &self,
+        _range: RangeInclusive<BlockNumber>,
+        _limit: usize,
+    ) -> ProviderResult<Option<TopGasConsumers>> {
+        Ok(None)
+    }
 }
 
 impl<T: NodePrimitives, ChainSpec: Send + Sync> StateReader for MockEthProvider<T, ChainSpec> {
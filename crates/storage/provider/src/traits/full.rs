@@ -6,7 +6,7 @@ use crate::{
 };
 use reth_chain_state::{CanonStateSubscriptions, ForkChoiceSubscriptions};
 use reth_node_types::{BlockTy, HeaderTy, NodeTypesWithDB, ReceiptTy, TxTy};
-use reth_storage_api::NodePrimitivesProvider;
+use reth_storage_api::{GasUsageReader, NodePrimitivesProvider, StateSizeReader};
 use std::fmt::Debug;
 
 /// Helper trait to unify all provider traits for simplicity.
@@ -26,6 +26,8 @@ pub trait FullProvider<N: NodeTypesWithDB>:
     + CanonStateSubscriptions
     + ForkChoiceSubscriptions<Header = HeaderTy<N>>
     + StageCheckpointReader
+    + StateSizeReader
+    + GasUsageReader
     + Clone
     + Debug
     + Unpin
@@ -49,6 +51,8 @@ impl<T, N: NodeTypesWithDB> FullProvider<N> for T where
         + CanonStateSubscriptions
         + ForkChoiceSubscriptions<Header = HeaderTy<N>>
         + StageCheckpointReader
+        + StateSizeReader
+        + GasUsageReader
         + Clone
         + Debug
         + Unpin
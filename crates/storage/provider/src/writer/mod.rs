@@ -3,13 +3,15 @@ use crate::{
     BlockExecutionWriter, BlockWriter, HistoryWriter, StateWriter, StaticFileProviderFactory,
     StorageLocation, TrieWriter,
 };
-use alloy_consensus::BlockHeader;
+use alloy_consensus::{BlockHeader, TxReceipt};
 use reth_chain_state::{ExecutedBlock, ExecutedBlockWithTrieUpdates};
 use reth_db_api::transaction::{DbTx, DbTxMut};
 use reth_errors::{ProviderError, ProviderResult};
-use reth_primitives_traits::{NodePrimitives, SignedTransaction};
+use reth_primitives_traits::{BlockBody, NodePrimitives, SignedTransaction};
 use reth_static_file_types::StaticFileSegment;
-use reth_storage_api::{DBProvider, StageCheckpointWriter, TransactionsProviderExt};
+use reth_storage_api::{
+    block_gas_usage, DBProvider, GasUsageWriter, StageCheckpointWriter, TransactionsProviderExt,
+};
 use reth_storage_errors::writer::UnifiedStorageWriterError;
 use revm_database::OriginalValuesKnown;
 use std::sync::Arc;
@@ -128,6 +130,7 @@ where
         + HistoryWriter
         + StageCheckpointWriter
         + BlockExecutionWriter
+        + GasUsageWriter
         + AsRef<ProviderDB>
         + StaticFileProviderFactory,
 {
@@ -151,6 +154,10 @@ where
 
         debug!(target: "provider::storage_writer", block_count = %blocks.len(), "Writing blocks and execution data to storage");
 
+        let index_gas_usage = self.database().gas_usage_indexed_to()?.is_some();
+        let mut gas_usage = Vec::new();
+        let mut outcomes = Vec::with_capacity(blocks.len());
+
         // TODO: Do performant / batched writes for each type of object
         // instead of a loop over all blocks,
         // meaning:
@@ -166,6 +173,13 @@ where
         } in blocks
         {
             let block_hash = recovered_block.hash();
+            if index_gas_usage {
+                gas_usage.push(block_gas_usage(
+                    recovered_block.body().transactions(),
+                    execution_output.receipts.first().map(Vec::as_slice).unwrap_or_default(),
+                ));
+            }
+            let block_number = recovered_block.number();
             self.database()
                 .insert_block(Arc::unwrap_or_clone(recovered_block), StorageLocation::Both)?;
 
@@ -183,8 +197,25 @@ where
             self.database().write_trie_updates(
                 trie.as_ref().ok_or(ProviderError::MissingTrieUpdates(block_hash))?,
             )?;
+            outcomes.push((block_number, execution_output));
         }
 
+        // update the optional per-contract gas usage index
+        self.database().index_gas_usage(first_number, &gas_usage)?;
+
+        // update the optional log index
+        self.database().index_logs(
+            first_number..=last_block_number,
+            outcomes.iter().flat_map(|(block_number, outcome)| {
+                outcome
+                    .receipts
+                    .iter()
+                    .flatten()
+                    .flat_map(TxReceipt::logs)
+                    .map(move |log| (*block_number, log))
+            }),
+        )?;
+
         // update history indices
         self.database().update_history_indices(first_number..=last_block_number)?;
 
@@ -54,4 +54,35 @@ pub trait ChangeSetReader {
         &self,
         block_number: BlockNumber,
     ) -> ProviderResult<Vec<AccountBeforeTx>>;
+
+    /// Returns the numbers of the blocks in the given range in which the account changed, in
+    /// ascending order.
+    ///
+    /// A block is included if the balance, nonce or bytecode of the account changed in it, or if
+    /// the account was created or destroyed. At most `limit` block numbers are returned, so the
+    /// next page starts after the last returned block.
+    ///
+    /// The default implementation scans the account changesets of every block in the range,
+    /// implementations backed by the history index should override it.
+    fn account_change_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+        limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        let mut blocks = Vec::new();
+        for block_number in range {
+            if blocks.len() >= limit {
+                break
+            }
+            if self
+                .account_block_changeset(block_number)?
+                .iter()
+                .any(|change| change.address == address)
+            {
+                blocks.push(block_number);
+            }
+        }
+        Ok(blocks)
+    }
 }
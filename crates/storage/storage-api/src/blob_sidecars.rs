@@ -0,0 +1,84 @@
+use alloc::vec::Vec;
+use alloy_eips::eip7594::BlobTransactionSidecarVariant;
+use alloy_primitives::{BlockHash, BlockNumber, TxHash};
+use reth_storage_errors::{db::DatabaseError, provider::ProviderResult};
+
+/// The persisted blob sidecars of the EIP-4844 transactions in a canonical block.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StoredBlobSidecars {
+    /// Hash of the block the sidecars were stored for.
+    pub block_hash: BlockHash,
+    /// The sidecars with the hash of their transaction.
+    pub sidecars: Vec<(TxHash, BlobTransactionSidecarVariant)>,
+    /// Blob transactions of the block whose sidecar was no longer available when the block was
+    /// stored.
+    pub missing: Vec<TxHash>,
+}
+
+impl StoredBlobSidecars {
+    /// Encodes the sidecars for storage.
+    ///
+    /// The block hash and the missing transaction hashes are followed by each sidecar, prefixed
+    /// with its transaction hash and the length of its encoded fields.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(self.block_hash.as_slice());
+        buf.extend_from_slice(&(self.missing.len() as u32).to_be_bytes());
+        for tx_hash in &self.missing {
+            buf.extend_from_slice(tx_hash.as_slice());
+        }
+        for (tx_hash, sidecar) in &self.sidecars {
+            buf.extend_from_slice(tx_hash.as_slice());
+            buf.extend_from_slice(&(sidecar.rlp_encoded_fields_length() as u32).to_be_bytes());
+            sidecar.rlp_encode_fields(&mut buf);
+        }
+        buf
+    }
+
+    /// Decodes sidecars encoded with [`Self::encode`].
+    pub fn decode(mut data: &[u8]) -> Result<Self, DatabaseError> {
+        let block_hash = BlockHash::from_slice(take(&mut data, 32)?);
+        let missing_len = u32::from_be_bytes(take(&mut data, 4)?.try_into().expect("4 bytes"));
+        let missing = (0..missing_len)
+            .map(|_| Ok(TxHash::from_slice(take(&mut data, 32)?)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut sidecars = Vec::new();
+        while !data.is_empty() {
+            let tx_hash = TxHash::from_slice(take(&mut data, 32)?);
+            let len = u32::from_be_bytes(take(&mut data, 4)?.try_into().expect("4 bytes"));
+            let mut fields = take(&mut data, len as usize)?;
+            let sidecar = BlobTransactionSidecarVariant::rlp_decode_fields(&mut fields)
+                .map_err(|_| DatabaseError::Decode)?;
+            sidecars.push((tx_hash, sidecar));
+        }
+        Ok(Self { block_hash, sidecars, missing })
+    }
+}
+
+/// Splits off the first `len` bytes of the data.
+fn take<'a>(data: &mut &'a [u8], len: usize) -> Result<&'a [u8], DatabaseError> {
+    if data.len() < len {
+        return Err(DatabaseError::Decode)
+    }
+    let (head, tail) = data.split_at(len);
+    *data = tail;
+    Ok(head)
+}
+
+/// The trait for persisting the blob sidecars of canonical blocks.
+#[auto_impl::auto_impl(&, Arc, Box)]
+pub trait BlobSidecarWriter: Send + Sync {
+    /// Stores the blob sidecars of the given block, replacing any stored ones.
+    fn insert_blob_sidecars(
+        &self,
+        block: BlockNumber,
+        sidecars: &StoredBlobSidecars,
+    ) -> ProviderResult<()>;
+
+    /// Removes the blob sidecars of the given block, e.g. because it was reorged out.
+    fn remove_blob_sidecars(&self, block: BlockNumber) -> ProviderResult<()>;
+
+    /// Removes the blob sidecars of all blocks below the given block.
+    fn prune_blob_sidecars(&self, below: BlockNumber) -> ProviderResult<()>;
+}
@@ -1,12 +1,12 @@
 use crate::{
     BlockBodyIndicesProvider, BlockNumReader, HeaderProvider, ReceiptProvider,
-    ReceiptProviderIdExt, TransactionVariant, TransactionsProvider,
+    ReceiptProviderIdExt, StoredBlobSidecars, TransactionVariant, TransactionsProvider,
 };
 use alloc::{sync::Arc, vec::Vec};
 use alloy_eips::{BlockHashOrNumber, BlockId, BlockNumberOrTag};
 use alloy_primitives::{BlockNumber, B256};
 use core::ops::RangeInclusive;
-use reth_primitives_traits::{RecoveredBlock, SealedHeader};
+use reth_primitives_traits::{Block as _, BlockBody as _, RecoveredBlock, SealedHeader};
 use reth_storage_errors::provider::ProviderResult;
 
 /// A helper enum that represents the origin of the requested block.
@@ -104,6 +104,17 @@ pub trait BlockReader:
         self.block(num.into())
     }
 
+    /// Returns the ommers of the block with matching hash or number.
+    ///
+    /// Implementers should avoid loading the block's transactions if possible.
+    ///
+    /// Returns `None` if block is not found.
+    fn ommers(&self, id: BlockHashOrNumber) -> ProviderResult<Option<Vec<Self::Header>>> {
+        Ok(self
+            .block(id)?
+            .map(|block| block.body().ommers().map(|ommers| ommers.to_vec()).unwrap_or_default()))
+    }
+
     /// Returns the block with senders with matching number or hash from database.
     ///
     /// Returns the block's transactions in the requested variant.
@@ -126,6 +137,17 @@ pub trait BlockReader:
         transaction_kind: TransactionVariant,
     ) -> ProviderResult<Option<RecoveredBlock<Self::Block>>>;
 
+    /// Returns the persisted blob sidecars of the EIP-4844 transactions in the canonical block
+    /// with the given number, see [`BlobSidecarWriter`](crate::BlobSidecarWriter).
+    ///
+    /// Returns `None` if no sidecars are stored for the block.
+    fn block_blob_sidecars(
+        &self,
+        _number: BlockNumber,
+    ) -> ProviderResult<Option<StoredBlobSidecars>> {
+        Ok(None)
+    }
+
     /// Returns all blocks in the given inclusive range.
     ///
     /// Note: returns only available blocks
@@ -173,6 +195,9 @@ impl<T: BlockReader> BlockReader for Arc<T> {
     fn block_by_number(&self, num: u64) -> ProviderResult<Option<Self::Block>> {
         T::block_by_number(self, num)
     }
+    fn ommers(&self, id: BlockHashOrNumber) -> ProviderResult<Option<Vec<Self::Header>>> {
+        T::ommers(self, id)
+    }
     fn recovered_block(
         &self,
         id: BlockHashOrNumber,
@@ -187,6 +212,12 @@ impl<T: BlockReader> BlockReader for Arc<T> {
     ) -> ProviderResult<Option<RecoveredBlock<Self::Block>>> {
         T::sealed_block_with_senders(self, id, transaction_kind)
     }
+    fn block_blob_sidecars(
+        &self,
+        number: BlockNumber,
+    ) -> ProviderResult<Option<StoredBlobSidecars>> {
+        T::block_blob_sidecars(self, number)
+    }
     fn block_range(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<Vec<Self::Block>> {
         T::block_range(self, range)
     }
@@ -231,6 +262,9 @@ impl<T: BlockReader> BlockReader for &T {
     fn block_by_number(&self, num: u64) -> ProviderResult<Option<Self::Block>> {
         T::block_by_number(self, num)
     }
+    fn ommers(&self, id: BlockHashOrNumber) -> ProviderResult<Option<Vec<Self::Header>>> {
+        T::ommers(self, id)
+    }
     fn recovered_block(
         &self,
         id: BlockHashOrNumber,
@@ -245,6 +279,12 @@ impl<T: BlockReader> BlockReader for &T {
     ) -> ProviderResult<Option<RecoveredBlock<Self::Block>>> {
         T::sealed_block_with_senders(self, id, transaction_kind)
     }
+    fn block_blob_sidecars(
+        &self,
+        number: BlockNumber,
+    ) -> ProviderResult<Option<StoredBlobSidecars>> {
+        T::block_blob_sidecars(self, number)
+    }
     fn block_range(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<Vec<Self::Block>> {
         T::block_range(self, range)
     }
@@ -1,9 +1,101 @@
 use crate::BlockHashReader;
-use alloy_eips::{BlockHashOrNumber, BlockId, BlockNumberOrTag};
+use alloy_eips::{BlockHashOrNumber, BlockId, BlockNumHash, BlockNumberOrTag};
 use alloy_primitives::{BlockNumber, B256};
+use core::{fmt, str::FromStr};
 use reth_chainspec::ChainInfo;
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
 
+/// A [`BlockNumberOrTag`] with a number of blocks to go back from it, e.g. `latest-128`.
+///
+/// Without an offset it's parsed and displayed like the [`BlockNumberOrTag`] itself.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct RelativeBlockNumberOrTag {
+    /// The block to go back from.
+    pub base: BlockNumberOrTag,
+    /// The number of blocks to go back.
+    pub offset: u64,
+}
+
+impl RelativeBlockNumberOrTag {
+    /// Creates a new relative block, `offset` blocks before `base`.
+    pub const fn new(base: BlockNumberOrTag, offset: u64) -> Self {
+        Self { base, offset }
+    }
+}
+
+impl From<BlockNumberOrTag> for RelativeBlockNumberOrTag {
+    fn from(base: BlockNumberOrTag) -> Self {
+        Self::new(base, 0)
+    }
+}
+
+impl From<BlockNumber> for RelativeBlockNumberOrTag {
+    fn from(number: BlockNumber) -> Self {
+        BlockNumberOrTag::Number(number).into()
+    }
+}
+
+impl FromStr for RelativeBlockNumberOrTag {
+    type Err = alloy_eips::eip1898::ParseBlockNumberError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((base, offset)) = s.split_once('-') else {
+            return Ok(s.parse::<BlockNumberOrTag>()?.into())
+        };
+        let offset = match offset.strip_prefix("0x") {
+            Some(hex) => u64::from_str_radix(hex, 16)?,
+            None => offset.parse()?,
+        };
+        Ok(Self::new(base.parse()?, offset))
+    }
+}
+
+impl fmt::Display for RelativeBlockNumberOrTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.offset == 0 {
+            write!(f, "{}", self.base)
+        } else {
+            write!(f, "{}-{}", self.base, self.offset)
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for RelativeBlockNumberOrTag {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.offset == 0 {
+            self.base.serialize(serializer)
+        } else {
+            serializer.collect_str(self)
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for RelativeBlockNumberOrTag {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct RelativeBlockVisitor;
+
+        impl serde::de::Visitor<'_> for RelativeBlockVisitor {
+            type Value = RelativeBlockNumberOrTag;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a block number or tag, optionally followed by `-<offset>`")
+            }
+
+            fn visit_u64<E: serde::de::Error>(self, number: u64) -> Result<Self::Value, E> {
+                Ok(number.into())
+            }
+
+            fn visit_str<E: serde::de::Error>(self, s: &str) -> Result<Self::Value, E> {
+                s.parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_any(RelativeBlockVisitor)
+    }
+}
+
 /// Client trait for getting important block numbers (such as the latest block number), converting
 /// block hashes to numbers, and fetching a block hash from its block number.
 ///
@@ -78,27 +170,73 @@ pub trait BlockIdReader: BlockNumReader + Send + Sync {
         Ok(Some(num))
     }
 
+    /// Converts the [`RelativeBlockNumberOrTag`] to a block number.
+    ///
+    /// Returns `None` if the base block can't be resolved or the offset goes back beyond genesis.
+    fn convert_relative_block_number(
+        &self,
+        num: RelativeBlockNumberOrTag,
+    ) -> ProviderResult<Option<BlockNumber>> {
+        Ok(self.convert_block_number(num.base)?.and_then(|number| number.checked_sub(num.offset)))
+    }
+
+    /// Resolves the given id to the number and hash of an existing block.
+    ///
+    /// This is the single place where all variants of [`BlockId`] are resolved:
+    ///  - `latest`, `pending`, `safe` and `finalized` resolve to the respective block, if known.
+    ///  - `earliest` and numbers resolve to the canonical block at that height, if it exists.
+    ///  - Hashes resolve to the block with that hash. If `requireCanonical` is set, the block must
+    ///    also be part of the canonical chain.
+    ///
+    /// Returns `None` if there is no such block.
+    fn resolve_block_id(&self, block_id: BlockId) -> ProviderResult<Option<BlockNumHash>> {
+        let num = match block_id {
+            BlockId::Hash(hash) => {
+                let Some(number) = self.block_number(hash.block_hash)? else { return Ok(None) };
+                if hash.require_canonical == Some(true) &&
+                    self.block_hash(number)? != Some(hash.block_hash)
+                {
+                    return Ok(None)
+                }
+                return Ok(Some(BlockNumHash::new(number, hash.block_hash)))
+            }
+            BlockId::Number(num) => num,
+        };
+        match num {
+            BlockNumberOrTag::Latest => {
+                let info = self.chain_info()?;
+                Ok(Some(BlockNumHash::new(info.best_number, info.best_hash)))
+            }
+            BlockNumberOrTag::Pending => self.pending_block_num_hash(),
+            BlockNumberOrTag::Finalized => self.finalized_block_num_hash(),
+            BlockNumberOrTag::Safe => self.safe_block_num_hash(),
+            BlockNumberOrTag::Earliest | BlockNumberOrTag::Number(_) => {
+                let Some(number) = self.convert_block_number(num)? else { return Ok(None) };
+                Ok(self.block_hash(number)?.map(|hash| BlockNumHash::new(number, hash)))
+            }
+        }
+    }
+
     /// Get the hash of the block by matching the given id.
+    ///
+    /// Unless the block must be canonical, a hash is returned as is, without checking that the
+    /// block exists. See [`Self::resolve_block_id`].
     fn block_hash_for_id(&self, block_id: BlockId) -> ProviderResult<Option<B256>> {
         match block_id {
-            BlockId::Hash(hash) => Ok(Some(hash.into())),
-            BlockId::Number(num) => match num {
-                BlockNumberOrTag::Latest => Ok(Some(self.chain_info()?.best_hash)),
-                BlockNumberOrTag::Pending => self
-                    .pending_block_num_hash()
-                    .map(|res_opt| res_opt.map(|num_hash| num_hash.hash)),
-                BlockNumberOrTag::Finalized => self.finalized_block_hash(),
-                BlockNumberOrTag::Safe => self.safe_block_hash(),
-                BlockNumberOrTag::Earliest => self.block_hash(self.earliest_block_number()?),
-                BlockNumberOrTag::Number(num) => self.block_hash(num),
-            },
+            BlockId::Hash(hash) if hash.require_canonical != Some(true) => Ok(Some(hash.into())),
+            _ => Ok(self.resolve_block_id(block_id)?.map(|num_hash| num_hash.hash)),
         }
     }
 
     /// Get the number of the block by matching the given id.
+    ///
+    /// Block numbers are returned as is, without checking that the block exists. See
+    /// [`Self::resolve_block_id`].
     fn block_number_for_id(&self, block_id: BlockId) -> ProviderResult<Option<BlockNumber>> {
         match block_id {
-            BlockId::Hash(hash) => self.block_number(hash.into()),
+            BlockId::Hash(_) => {
+                Ok(self.resolve_block_id(block_id)?.map(|num_hash| num_hash.number))
+            }
             BlockId::Number(num) => self.convert_block_number(num),
         }
     }
@@ -135,3 +273,33 @@ pub trait BlockIdReader: BlockNumReader + Send + Sync {
 
 #[cfg(test)]
 fn _object_safe(_: Box<dyn BlockIdReader>) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_relative_block() {
+        assert_eq!(
+            "latest".parse::<RelativeBlockNumberOrTag>().unwrap(),
+            BlockNumberOrTag::Latest.into()
+        );
+        assert_eq!(
+            "latest-128".parse::<RelativeBlockNumberOrTag>().unwrap(),
+            RelativeBlockNumberOrTag::new(BlockNumberOrTag::Latest, 128)
+        );
+        assert_eq!(
+            "finalized-0x10".parse::<RelativeBlockNumberOrTag>().unwrap(),
+            RelativeBlockNumberOrTag::new(BlockNumberOrTag::Finalized, 16)
+        );
+        assert_eq!(
+            "0x100-2".parse::<RelativeBlockNumberOrTag>().unwrap(),
+            RelativeBlockNumberOrTag::new(BlockNumberOrTag::Number(256), 2)
+        );
+        assert!("latest-".parse::<RelativeBlockNumberOrTag>().is_err());
+        assert!("newest-1".parse::<RelativeBlockNumberOrTag>().is_err());
+
+        let relative = RelativeBlockNumberOrTag::new(BlockNumberOrTag::Safe, 5);
+        assert_eq!(relative.to_string().parse::<RelativeBlockNumberOrTag>().unwrap(), relative);
+    }
+}
@@ -0,0 +1,104 @@
+use alloc::vec::Vec;
+use alloy_consensus::{Transaction, TxReceipt};
+use alloy_primitives::{Address, BlockNumber};
+use core::ops::RangeInclusive;
+use reth_storage_errors::provider::ProviderResult;
+
+/// Number of blocks that the gas usage index aggregates into one epoch, roughly a day of
+/// post-merge Ethereum blocks.
+pub const GAS_USAGE_EPOCH_LENGTH: u64 = 7200;
+
+/// Returns the first block of the gas usage epoch that contains the block.
+pub const fn gas_usage_epoch(block: BlockNumber) -> BlockNumber {
+    block - block % GAS_USAGE_EPOCH_LENGTH
+}
+
+/// Returns the gas used by each transaction of a block with a `to` address.
+///
+/// The gas used by a transaction is derived from the cumulative gas used of its receipt and the
+/// previous one. Contract creations are skipped.
+pub fn block_gas_usage<T: Transaction, R: TxReceipt>(
+    transactions: &[T],
+    receipts: &[R],
+) -> Vec<(Address, u64)> {
+    let mut cumulative_gas_used = 0;
+    transactions
+        .iter()
+        .zip(receipts)
+        .filter_map(|(transaction, receipt)| {
+            let gas_used = receipt.cumulative_gas_used().saturating_sub(cumulative_gas_used);
+            cumulative_gas_used = receipt.cumulative_gas_used();
+            transaction.to().map(|to| (to, gas_used))
+        })
+        .collect()
+}
+
+/// Gas used by transactions sent to a contract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContractGasUsage {
+    /// The `to` address of the transactions.
+    pub address: Address,
+    /// Total gas used by the transactions.
+    pub gas_used: u64,
+}
+
+/// The addresses whose transactions used the most gas, see [`GasUsageReader::top_gas_consumers`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TopGasConsumers {
+    /// First block covered by the result.
+    pub from_block: BlockNumber,
+    /// Last block covered by the result.
+    pub to_block: BlockNumber,
+    /// The addresses, ordered by gas used in descending order.
+    pub consumers: Vec<ContractGasUsage>,
+}
+
+/// The trait for fetching the per-contract gas usage index.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait GasUsageReader: Send + Sync {
+    /// Returns the `limit` addresses whose transactions used the most gas in the range.
+    ///
+    /// Gas usage is aggregated per epoch of [`GAS_USAGE_EPOCH_LENGTH`] blocks, so the range is
+    /// widened to the epochs that contain it and then narrowed to the blocks covered by the index,
+    /// the actual range is returned with the result.
+    ///
+    /// Returns `None` if the index is not maintained or doesn't cover the range.
+    fn top_gas_consumers(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        limit: usize,
+    ) -> ProviderResult<Option<TopGasConsumers>>;
+}
+
+/// The trait for maintaining the per-contract gas usage index.
+#[auto_impl::auto_impl(&, Arc, Box)]
+pub trait GasUsageWriter: Send + Sync {
+    /// Returns the last block covered by the index, `None` if the index is not maintained.
+    fn gas_usage_indexed_to(&self) -> ProviderResult<Option<BlockNumber>>;
+
+    /// Starts maintaining the index after the given block.
+    ///
+    /// If the index is already maintained but doesn't reach the block, the blocks in between were
+    /// not indexed, so the index is restarted after the block.
+    fn init_gas_usage_index(&self, block: BlockNumber) -> ProviderResult<()>;
+
+    /// Stops maintaining the index and removes it.
+    fn remove_gas_usage_index(&self) -> ProviderResult<()>;
+
+    /// Adds the gas usage of consecutive blocks starting at `first_block` to the index, see
+    /// [`block_gas_usage`].
+    ///
+    /// Does nothing if the index is not maintained or the blocks don't extend it.
+    fn index_gas_usage(
+        &self,
+        first_block: BlockNumber,
+        blocks: &[Vec<(Address, u64)>],
+    ) -> ProviderResult<()>;
+
+    /// Removes the gas usage of the blocks above the given block from the index, if it's
+    /// maintained and covers such blocks.
+    ///
+    /// The gas usage is derived from the transactions and receipts of the removed blocks. If the
+    /// receipts were pruned, the index is restarted after the block instead.
+    fn unwind_gas_usage_above(&self, block: BlockNumber) -> ProviderResult<()>;
+}
@@ -1,4 +1,4 @@
-use alloy_primitives::{Address, BlockNumber, B256};
+use alloy_primitives::{Address, BlockNumber, Log, B256};
 use auto_impl::auto_impl;
 use core::ops::{RangeBounds, RangeInclusive};
 use reth_db_api::models::BlockNumberAddress;
@@ -55,4 +55,24 @@ pub trait HistoryWriter: Send + Sync {
 
     /// Read account/storage changesets and update account/storage history indices.
     fn update_history_indices(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<()>;
+
+    /// Adds the logs of the consecutive blocks in `range` to the log address and topic indices and
+    /// moves the index checkpoint to the end of the range.
+    ///
+    /// Does nothing if the index is not maintained or the blocks don't extend it.
+    fn index_logs<'a>(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        logs: impl IntoIterator<Item = (BlockNumber, &'a Log)>,
+    ) -> ProviderResult<()>;
+
+    /// Unwind and clear the log address and topic indices of the given logs, removing all blocks
+    /// at or above `block_number`.
+    ///
+    /// Returns number of logs walked.
+    fn unwind_log_indices<'a>(
+        &self,
+        logs: impl IntoIterator<Item = &'a Log>,
+        block_number: BlockNumber,
+    ) -> ProviderResult<usize>;
 }
@@ -16,6 +16,9 @@ pub use reth_storage_errors as errors;
 mod account;
 pub use account::*;
 
+mod blob_sidecars;
+pub use blob_sidecars::*;
+
 mod block;
 pub use block::*;
 
@@ -30,6 +33,9 @@ mod chain;
 #[cfg(feature = "db-api")]
 pub use chain::*;
 
+mod gas_usage;
+pub use gas_usage::*;
+
 mod header;
 pub use header::*;
 
@@ -45,6 +51,9 @@ pub use stage_checkpoint::*;
 mod state;
 pub use state::*;
 
+mod state_size;
+pub use state_size::*;
+
 mod storage;
 pub use storage::*;
 
@@ -96,3 +105,8 @@ pub use header_sync_gap::HeaderSyncGapProvider;
 
 mod full;
 pub use full::*;
+
+#[cfg(feature = "std")]
+mod read_scope;
+#[cfg(feature = "std")]
+pub use read_scope::*;
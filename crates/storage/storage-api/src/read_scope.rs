@@ -0,0 +1,164 @@
+//! Attribution of provider reads to the operation that issued them, e.g. an RPC method.
+//!
+//! Providers record the storage tier every read is served from with [`record_read`]. Reads are only
+//! recorded while a [`ReadScope`] is entered on the current thread, they are aggregated per scope.
+//! Work that is moved to another thread, like cache fetches, enters the scope of the requester.
+//! Trie and proof computations are recorded as a single read.
+
+use metrics::Counter;
+use reth_metrics::Metrics;
+use std::{
+    cell::Cell,
+    collections::HashMap,
+    marker::PhantomData,
+    sync::{LazyLock, RwLock},
+};
+
+std::thread_local! {
+    /// The scope reads of this thread are attributed to.
+    static CURRENT_SCOPE: Cell<Option<&'static str>> = const { Cell::new(None) };
+}
+
+/// Read metrics of all scopes.
+static SCOPE_METRICS: LazyLock<RwLock<HashMap<&'static str, ReadSourceMetrics>>> =
+    LazyLock::new(Default::default);
+
+/// The storage tier a read is served from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReadSource {
+    /// The MDBX database.
+    Database,
+    /// Static files.
+    StaticFiles,
+    /// The in-memory overlay of blocks that are not persisted yet.
+    InMemory,
+}
+
+/// Attributes the provider reads of the current thread to the given name while alive.
+///
+/// Scopes can be nested, the previous scope is restored once dropped.
+#[derive(Debug)]
+#[must_use = "the scope is exited when dropped"]
+pub struct ReadScope {
+    /// The scope to restore.
+    previous: Option<&'static str>,
+    /// The scope is bound to the current thread.
+    _not_send: PhantomData<*const ()>,
+}
+
+impl ReadScope {
+    /// Enters the scope with the given name on the current thread.
+    pub fn enter(name: &'static str) -> Self {
+        let previous = CURRENT_SCOPE.with(|current| current.replace(Some(name)));
+        Self { previous, _not_send: PhantomData }
+    }
+
+    /// Returns the name of the scope entered on the current thread.
+    ///
+    /// Use this to enter the same scope on a thread the work is moved to.
+    pub fn current() -> Option<&'static str> {
+        CURRENT_SCOPE.with(Cell::get)
+    }
+}
+
+impl Drop for ReadScope {
+    fn drop(&mut self) {
+        CURRENT_SCOPE.with(|current| current.set(self.previous));
+    }
+}
+
+/// Records a read served from the given source for the scope of the current thread, if any.
+pub fn record_read(source: ReadSource) {
+    let Some(scope) = ReadScope::current() else { return };
+
+    if let Some(metrics) = SCOPE_METRICS.read().expect("not poisoned").get(scope) {
+        metrics.increment(source);
+        return
+    }
+    SCOPE_METRICS
+        .write()
+        .expect("not poisoned")
+        .entry(scope)
+        .or_insert_with(|| ReadSourceMetrics::new_with_labels(&[("scope", scope)]))
+        .increment(source);
+}
+
+/// Reads per storage tier of a [`ReadScope`].
+#[derive(Metrics, Clone)]
+#[metrics(scope = "storage.reads")]
+struct ReadSourceMetrics {
+    /// The number of reads served from the database
+    database: Counter,
+    /// The number of reads served from static files
+    static_files: Counter,
+    /// The number of reads served from the in-memory overlay
+    in_memory: Counter,
+}
+
+impl ReadSourceMetrics {
+    fn increment(&self, source: ReadSource) {
+        match source {
+            ReadSource::Database => self.database.increment(1),
+            ReadSource::StaticFiles => self.static_files.increment(1),
+            ReadSource::InMemory => self.in_memory.increment(1),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nested_scopes() {
+        assert_eq!(ReadScope::current(), None);
+        {
+            let _outer = ReadScope::enter("eth_getLogs");
+            {
+                let _inner = ReadScope::enter("eth_call");
+                assert_eq!(ReadScope::current(), Some("eth_call"));
+                record_read(ReadSource::StaticFiles);
+            }
+            assert_eq!(ReadScope::current(), Some("eth_getLogs"));
+        }
+        assert_eq!(ReadScope::current(), None);
+
+        // scopes are per thread
+        let _scope = ReadScope::enter("eth_call");
+        std::thread::spawn(|| assert_eq!(ReadScope::current(), None)).join().unwrap();
+    }
+
+    #[test]
+    fn counts_reads_per_scope_and_source() {
+        use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+        metrics::with_local_recorder(&recorder, || {
+            // reads outside of a scope are not recorded
+            record_read(ReadSource::Database);
+
+            let _scope = ReadScope::enter("read_scope_test");
+            record_read(ReadSource::Database);
+            record_read(ReadSource::Database);
+            record_read(ReadSource::InMemory);
+        });
+
+        let snapshot = snapshotter.snapshot().into_hashmap();
+        let reads = |source: &str| {
+            snapshot
+                .iter()
+                .find(|(key, _)| {
+                    key.key().name() == format!("storage.reads.{source}") &&
+                        key.key().labels().any(|label| label.value() == "read_scope_test")
+                })
+                .map_or(0, |(_, (_, _, value))| match value {
+                    DebugValue::Counter(count) => *count,
+                    _ => unreachable!("reads are counters"),
+                })
+        };
+        assert_eq!(reads("database"), 2);
+        assert_eq!(reads("in_memory"), 1);
+        assert_eq!(reads("static_files"), 0);
+    }
+}
@@ -1,7 +1,7 @@
 use crate::BlockIdReader;
 use alloc::vec::Vec;
 use alloy_eips::{BlockHashOrNumber, BlockId, BlockNumberOrTag};
-use alloy_primitives::{BlockNumber, TxHash, TxNumber};
+use alloy_primitives::{Address, BlockNumber, TxHash, TxNumber, B256};
 use core::ops::{RangeBounds, RangeInclusive};
 use reth_primitives_traits::Receipt;
 use reth_storage_errors::provider::ProviderResult;
@@ -52,6 +52,49 @@ pub trait ReceiptProvider: Send + Sync {
         &self,
         block_range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<Vec<Vec<Self::Receipt>>>;
+
+    /// Returns the blocks in the range that may contain a log matching the given addresses and
+    /// topics, according to the optional log index.
+    ///
+    /// A log matches if it's emitted by any of the `addresses` and contains any of the topics of
+    /// each entry in `topics`, empty sets match any log. Topics are indexed regardless of their
+    /// position in the log, so the result is a superset of the blocks with matching logs.
+    ///
+    /// Returns `None` if the log index is not available, doesn't cover the start of the range or
+    /// the filter is unconstrained.
+    fn indexed_log_blocks(
+        &self,
+        _block_range: RangeInclusive<BlockNumber>,
+        _addresses: &[Address],
+        _topics: &[Vec<B256>],
+    ) -> ProviderResult<Option<IndexedLogBlocks>> {
+        Ok(None)
+    }
+}
+
+/// Blocks that may contain matching logs, see [`ReceiptProvider::indexed_log_blocks`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IndexedLogBlocks {
+    /// Highest block covered by the log index.
+    pub indexed_to: BlockNumber,
+    /// Sorted blocks up to [`Self::indexed_to`] that may contain a matching log.
+    pub blocks: Vec<BlockNumber>,
+}
+
+impl IndexedLogBlocks {
+    /// Returns `false` if the block is covered by the index and has no matching logs.
+    pub fn may_contain(&self, block: BlockNumber) -> bool {
+        block > self.indexed_to || self.blocks.binary_search(&block).is_ok()
+    }
+
+    /// Returns `false` if the whole range is covered by the index and has no matching logs.
+    pub fn may_contain_any(&self, range: RangeInclusive<BlockNumber>) -> bool {
+        if *range.end() > self.indexed_to {
+            return true
+        }
+        let first = self.blocks.partition_point(|block| block < range.start());
+        self.blocks.get(first).is_some_and(|block| block <= range.end())
+    }
 }
 
 /// Trait extension for `ReceiptProvider`, for types that implement `BlockId` conversion.
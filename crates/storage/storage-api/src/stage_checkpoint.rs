@@ -37,4 +37,13 @@ pub trait StageCheckpointWriter: Send + Sync {
         block_number: BlockNumber,
         drop_stage_checkpoint: bool,
     ) -> ProviderResult<()>;
+
+    /// Removes checkpoints of stages that were removed from the pipeline, see
+    /// [`StageId::RETIRED`].
+    ///
+    /// Returns the names of the stages whose checkpoints were removed. The default implementation
+    /// doesn't store checkpoints of retired stages, so it removes nothing.
+    fn migrate_stage_checkpoints(&self) -> ProviderResult<Vec<String>> {
+        Ok(Vec::new())
+    }
 }
@@ -0,0 +1,36 @@
+use alloy_primitives::BlockNumber;
+use reth_storage_errors::provider::ProviderResult;
+
+/// Size statistics of the plain state, also returned by `reth_stateSize`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct StateSize {
+    /// The block the statistics were last updated for.
+    #[cfg_attr(feature = "serde", serde(with = "alloy_serde::quantity"))]
+    pub block_number: BlockNumber,
+    /// Number of accounts.
+    #[cfg_attr(feature = "serde", serde(with = "alloy_serde::quantity"))]
+    pub accounts: u64,
+    /// Number of accounts with code.
+    #[cfg_attr(feature = "serde", serde(with = "alloy_serde::quantity"))]
+    pub contracts: u64,
+    /// Number of non-zero storage slots.
+    #[cfg_attr(feature = "serde", serde(with = "alloy_serde::quantity"))]
+    pub storage_slots: u64,
+    /// Number of distinct bytecodes.
+    #[cfg_attr(feature = "serde", serde(with = "alloy_serde::quantity"))]
+    pub bytecodes: u64,
+    /// Total size of the distinct bytecodes in bytes.
+    #[cfg_attr(feature = "serde", serde(with = "alloy_serde::quantity"))]
+    pub bytecode_bytes: u64,
+}
+
+/// The trait for fetching the size statistics of the state.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait StateSizeReader: Send + Sync {
+    /// Returns the size statistics of the persisted state.
+    ///
+    /// Returns `None` if the statistics are not maintained.
+    fn state_size(&self) -> ProviderResult<Option<StateSize>>;
+}
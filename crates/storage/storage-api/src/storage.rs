@@ -2,7 +2,7 @@ use alloc::{
     collections::{BTreeMap, BTreeSet},
     vec::Vec,
 };
-use alloy_primitives::{Address, BlockNumber, B256};
+use alloy_primitives::{Address, BlockNumber, B256, U256};
 use core::ops::RangeInclusive;
 use reth_primitives_traits::StorageEntry;
 use reth_storage_errors::provider::ProviderResult;
@@ -41,6 +41,68 @@ pub trait StorageChangeSetReader: Send + Sync {
         &self,
         block_number: BlockNumber,
     ) -> ProviderResult<Vec<(reth_db_api::models::BlockNumberAddress, StorageEntry)>>;
+
+    /// Returns all changes of the storage slots of the address in the given _inclusive_ block
+    /// range, ordered by block number.
+    ///
+    /// Every change contains the value of the slot before and after the block, so the returned
+    /// changes describe the full history of the slots within the range. The end of the range is
+    /// capped at the best block.
+    ///
+    /// Returns an error if the storage history of the range is pruned.
+    fn storage_changes_in_range(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<StorageSlotChange>>;
+}
+
+/// A change of a storage slot in a block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StorageSlotChange {
+    /// The block in which the slot changed.
+    pub block_number: BlockNumber,
+    /// The storage slot.
+    pub key: B256,
+    /// The value of the slot before the block.
+    pub previous_value: U256,
+    /// The value of the slot after the block.
+    pub value: U256,
+}
+
+impl StorageSlotChange {
+    /// Creates the changes from storage changesets, which contain the value of a slot before the
+    /// block and must be ordered by block number.
+    ///
+    /// The value after a change is the previous value of the next change of the slot. For the
+    /// last change of every slot, the value is returned by `value_after`, which must return the
+    /// value of the slot after the last changeset.
+    pub fn from_changesets(
+        changesets: Vec<(BlockNumber, StorageEntry)>,
+        mut value_after: impl FnMut(B256) -> ProviderResult<U256>,
+    ) -> ProviderResult<Vec<Self>> {
+        let mut changes = changesets
+            .into_iter()
+            .map(|(block_number, entry)| Self {
+                block_number,
+                key: entry.key,
+                previous_value: entry.value,
+                value: U256::ZERO,
+            })
+            .collect::<Vec<_>>();
+
+        // previous value of the next change of every slot
+        let mut next_previous_values = BTreeMap::new();
+        for change in changes.iter_mut().rev() {
+            change.value = match next_previous_values.get(&change.key) {
+                Some(value) => *value,
+                None => value_after(change.key)?,
+            };
+            next_previous_values.insert(change.key, change.previous_value);
+        }
+
+        Ok(changes)
+    }
 }
 
 /// An enum that represents the storage location for a piece of data.
@@ -63,6 +63,16 @@ pub trait StorageRootProvider: Send + Sync {
         slots: &[B256],
         hashed_storage: HashedStorage,
     ) -> ProviderResult<StorageMultiProof>;
+
+    /// Returns the storage multiproof for target slots on top of the current state but reuses the
+    /// intermediate nodes of the [`TrieInput`].
+    /// See [`StateRootProvider::state_root_from_nodes`] for more info.
+    fn storage_multiproof_from_nodes(
+        &self,
+        input: TrieInput,
+        address: Address,
+        slots: &[B256],
+    ) -> ProviderResult<StorageMultiProof>;
 }
 
 /// A type that can generate state proof on top of a given post state.
@@ -0,0 +1,274 @@
+//! Throughput governor for background work.
+//!
+//! Backfilling the chain uses all the CPU and disk bandwidth it can get, which can starve other
+//! services running on the same host. A [`ThroughputGovernor`] caps the gas executed and the bytes
+//! written to disk per second by pausing the calling thread once it gets ahead of the configured
+//! rates. The limits can be changed at any time.
+
+use reth_metrics::{
+    metrics::{Counter, Gauge},
+    Metrics,
+};
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, PoisonError,
+    },
+    time::{Duration, Instant},
+};
+
+/// Minimum time after which the consumption is forgotten, once it's within the limit.
+const WINDOW: Duration = Duration::from_secs(1);
+
+/// Caps the rate at which gas is executed and bytes are written to disk.
+///
+/// Callers report their progress with [`ThroughputGovernor::throttle_gas`] and
+/// [`ThroughputGovernor::throttle_writes`], which block until the progress is within the
+/// configured limits.
+///
+/// This type is cheap to clone.
+#[derive(Clone)]
+pub struct ThroughputGovernor {
+    inner: Arc<ThroughputGovernorInner>,
+}
+
+struct ThroughputGovernorInner {
+    /// The maximum gas per second, `0` if unlimited.
+    max_gas_per_second: AtomicU64,
+    /// The maximum bytes written per second, `0` if unlimited.
+    max_write_bytes_per_second: AtomicU64,
+    /// Gas executed in the current window.
+    gas: Mutex<Pace>,
+    /// Bytes written in the current window.
+    writes: Mutex<WritePace>,
+    /// Governor metrics.
+    metrics: ThroughputGovernorMetrics,
+}
+
+impl ThroughputGovernor {
+    /// Creates a new governor with the given limits, `None` doesn't limit the resource.
+    pub fn new(max_gas_per_second: Option<u64>, max_write_bytes_per_second: Option<u64>) -> Self {
+        let governor = Self {
+            inner: Arc::new(ThroughputGovernorInner {
+                max_gas_per_second: AtomicU64::new(0),
+                max_write_bytes_per_second: AtomicU64::new(0),
+                gas: Mutex::new(Pace::new(Instant::now())),
+                writes: Mutex::new(WritePace::new(Instant::now())),
+                metrics: ThroughputGovernorMetrics::default(),
+            }),
+        };
+        governor.set_max_gas_per_second(max_gas_per_second);
+        governor.set_max_write_bytes_per_second(max_write_bytes_per_second);
+        governor
+    }
+
+    /// Creates a new governor without limits.
+    pub fn unlimited() -> Self {
+        Self::new(None, None)
+    }
+
+    /// Returns the maximum gas executed per second, if limited.
+    pub fn max_gas_per_second(&self) -> Option<u64> {
+        Some(self.inner.max_gas_per_second.load(Ordering::Relaxed)).filter(|limit| *limit > 0)
+    }
+
+    /// Sets the maximum gas executed per second, `None` removes the limit.
+    pub fn set_max_gas_per_second(&self, limit: Option<u64>) {
+        let limit = limit.unwrap_or_default();
+        self.inner.max_gas_per_second.store(limit, Ordering::Relaxed);
+        self.inner.metrics.max_gas_per_second.set(limit as f64);
+    }
+
+    /// Returns the maximum bytes written to disk per second, if limited.
+    pub fn max_write_bytes_per_second(&self) -> Option<u64> {
+        Some(self.inner.max_write_bytes_per_second.load(Ordering::Relaxed))
+            .filter(|limit| *limit > 0)
+    }
+
+    /// Sets the maximum bytes written to disk per second, `None` removes the limit.
+    ///
+    /// The written bytes are measured for the whole process, which is only supported on Linux.
+    pub fn set_max_write_bytes_per_second(&self, limit: Option<u64>) {
+        let limit = limit.unwrap_or_default();
+        // writes that happened while unlimited are not counted
+        self.inner.writes.lock().unwrap_or_else(PoisonError::into_inner).last_written = None;
+        self.inner.max_write_bytes_per_second.store(limit, Ordering::Relaxed);
+        self.inner.metrics.max_write_bytes_per_second.set(limit as f64);
+    }
+
+    /// Records the executed gas and blocks until the executed gas is within the limit.
+    pub fn throttle_gas(&self, gas: u64) {
+        let Some(limit) = self.max_gas_per_second() else { return };
+        let delay = self.inner.gas.lock().unwrap_or_else(PoisonError::into_inner).consume(
+            gas,
+            limit,
+            Instant::now(),
+        );
+        if !delay.is_zero() {
+            self.inner.metrics.gas_throttled_milliseconds.increment(delay.as_millis() as u64);
+            std::thread::sleep(delay);
+        }
+    }
+
+    /// Records the bytes the process wrote to disk since the last call and blocks until the written
+    /// bytes are within the limit.
+    pub fn throttle_writes(&self) {
+        let Some(limit) = self.max_write_bytes_per_second() else { return };
+        let Some(written) = process_written_bytes() else { return };
+        let delay = {
+            let mut writes = self.inner.writes.lock().unwrap_or_else(PoisonError::into_inner);
+            let bytes = writes.last_written.map_or(0, |last| written.saturating_sub(last));
+            writes.last_written = Some(written);
+            writes.pace.consume(bytes, limit, Instant::now())
+        };
+        if !delay.is_zero() {
+            self.inner.metrics.write_throttled_milliseconds.increment(delay.as_millis() as u64);
+            std::thread::sleep(delay);
+        }
+    }
+}
+
+impl Default for ThroughputGovernor {
+    fn default() -> Self {
+        Self::unlimited()
+    }
+}
+
+impl fmt::Debug for ThroughputGovernor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ThroughputGovernor")
+            .field("max_gas_per_second", &self.max_gas_per_second())
+            .field("max_write_bytes_per_second", &self.max_write_bytes_per_second())
+            .finish()
+    }
+}
+
+/// Tracks the consumption of a resource within a window.
+#[derive(Debug)]
+struct Pace {
+    /// Start of the current window.
+    started: Instant,
+    /// Amount consumed since the start of the window.
+    consumed: u64,
+}
+
+impl Pace {
+    const fn new(now: Instant) -> Self {
+        Self { started: now, consumed: 0 }
+    }
+
+    /// Records the consumed amount and returns how long the caller needs to wait to stay within
+    /// `limit` per second.
+    fn consume(&mut self, amount: u64, limit: u64, now: Instant) -> Duration {
+        // start a new window once the previous one is within the limit, so idle time doesn't allow
+        // a burst afterwards
+        let elapsed = now.saturating_duration_since(self.started);
+        if elapsed >= WINDOW && Self::required(self.consumed, limit) <= elapsed {
+            self.started = now;
+            self.consumed = 0;
+        }
+
+        self.consumed = self.consumed.saturating_add(amount);
+        Self::required(self.consumed, limit)
+            .saturating_sub(now.saturating_duration_since(self.started))
+    }
+
+    /// Returns the time it takes to consume the amount at the limit.
+    fn required(amount: u64, limit: u64) -> Duration {
+        Duration::from_secs_f64(amount as f64 / limit as f64)
+    }
+}
+
+/// Tracks the bytes written by the process within a window.
+#[derive(Debug)]
+struct WritePace {
+    pace: Pace,
+    /// The bytes written by the process at the last call, `None` before the first call.
+    last_written: Option<u64>,
+}
+
+impl WritePace {
+    const fn new(now: Instant) -> Self {
+        Self { pace: Pace::new(now), last_written: None }
+    }
+}
+
+/// Returns the bytes the process caused to be written to storage, if supported.
+#[cfg(target_os = "linux")]
+fn process_written_bytes() -> Option<u64> {
+    let io = std::fs::read_to_string("/proc/self/io").ok()?;
+    io.lines()
+        .find_map(|line| line.strip_prefix("write_bytes:"))
+        .and_then(|bytes| bytes.trim().parse().ok())
+}
+
+/// Returns the bytes the process caused to be written to storage, if supported.
+#[cfg(not(target_os = "linux"))]
+const fn process_written_bytes() -> Option<u64> {
+    None
+}
+
+/// Throughput governor metrics
+#[derive(Metrics)]
+#[metrics(scope = "throughput_governor")]
+struct ThroughputGovernorMetrics {
+    /// The configured maximum gas per second, `0` if unlimited
+    max_gas_per_second: Gauge,
+    /// The configured maximum bytes written per second, `0` if unlimited
+    max_write_bytes_per_second: Gauge,
+    /// Time spent waiting to stay within the gas limit
+    gas_throttled_milliseconds: Counter,
+    /// Time spent waiting to stay within the write limit
+    write_throttled_milliseconds: Counter,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paces_consumption() {
+        let start = Instant::now();
+        let mut pace = Pace::new(start);
+
+        // half the limit is consumed immediately
+        assert_eq!(pace.consume(50, 100, start), Duration::from_millis(500));
+        // no more waiting is needed after the wait
+        assert_eq!(pace.consume(0, 100, start + Duration::from_millis(500)), Duration::ZERO);
+        // a new window starts once the previous one is within the limit
+        let now = start + Duration::from_secs(2);
+        assert_eq!(pace.consume(100, 100, now), Duration::from_secs(1));
+        assert_eq!(pace.started, now);
+    }
+
+    #[test]
+    fn carries_over_excess() {
+        let start = Instant::now();
+        let mut pace = Pace::new(start);
+
+        // the excess is not forgotten by the next window
+        assert_eq!(pace.consume(300, 100, start), Duration::from_secs(3));
+        let now = start + Duration::from_secs(1);
+        assert_eq!(pace.consume(0, 100, now), Duration::from_secs(2));
+        assert_eq!(pace.started, start);
+    }
+
+    #[test]
+    fn updates_limits() {
+        let governor = ThroughputGovernor::unlimited();
+        assert_eq!(governor.max_gas_per_second(), None);
+        assert_eq!(governor.max_write_bytes_per_second(), None);
+        // unlimited never blocks
+        governor.throttle_gas(u64::MAX);
+        governor.throttle_writes();
+
+        governor.set_max_gas_per_second(Some(1_000_000));
+        governor.set_max_write_bytes_per_second(Some(1024));
+        assert_eq!(governor.max_gas_per_second(), Some(1_000_000));
+        assert_eq!(governor.max_write_bytes_per_second(), Some(1024));
+
+        governor.set_max_gas_per_second(None);
+        assert_eq!(governor.max_gas_per_second(), None);
+    }
+}
@@ -3,6 +3,9 @@
 //! # Feature Flags
 //!
 //! - `rayon`: Enable rayon thread pool for blocking tasks.
+//! - `serde`: Enable serde support for [`TaskInfo`](registry::TaskInfo).
+//! - `tokio-console`: Name spawned tasks in [tokio-console](https://github.com/tokio-rs/console),
+//!   requires building with `RUSTFLAGS="--cfg tokio_unstable"`.
 
 #![doc(
     html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
@@ -14,6 +17,7 @@
 
 use crate::{
     metrics::{IncCounterOnDrop, TaskExecutorMetrics},
+    registry::TaskRegistry,
     shutdown::{signal, GracefulShutdown, GracefulShutdownGuard, Shutdown, Signal},
 };
 use dyn_clone::DynClone;
@@ -39,12 +43,19 @@ use tokio::{
 use tracing::{debug, error};
 use tracing_futures::Instrument;
 
+pub mod governor;
+pub mod memory;
 pub mod metrics;
+pub mod registry;
 pub mod shutdown;
+pub mod time;
 
 #[cfg(feature = "rayon")]
 pub mod pool;
 
+/// The name of tasks that are spawned without a name.
+const UNNAMED_TASK: &str = "unnamed";
+
 /// Global [`TaskExecutor`] instance that can be accessed from anywhere.
 static GLOBAL_EXECUTOR: OnceLock<TaskExecutor> = OnceLock::new();
 
@@ -96,6 +107,14 @@ pub trait TaskSpawner: Send + Sync + Unpin + std::fmt::Debug + DynClone {
     /// This spawns a critical task onto the runtime.
     fn spawn_critical(&self, name: &'static str, fut: BoxFuture<'static, ()>) -> JoinHandle<()>;
 
+    /// Spawns the task onto the runtime under the given name.
+    ///
+    /// The name identifies the task in diagnostics, see [`TaskRegistry`].
+    fn spawn_named(&self, name: &'static str, fut: BoxFuture<'static, ()>) -> JoinHandle<()> {
+        let _ = name;
+        self.spawn(fut)
+    }
+
     /// Spawns a blocking task onto the runtime.
     fn spawn_blocking(&self, fut: BoxFuture<'static, ()>) -> JoinHandle<()>;
 
@@ -174,6 +193,8 @@ pub struct TaskManager {
     on_shutdown: Shutdown,
     /// How many [`GracefulShutdown`] tasks are currently active
     graceful_tasks: Arc<AtomicUsize>,
+    /// Tracks the spawned tasks, if enabled.
+    task_registry: TaskRegistry,
 }
 
 // === impl TaskManager ===
@@ -206,6 +227,7 @@ impl TaskManager {
             signal: Some(signal),
             on_shutdown,
             graceful_tasks: Arc::new(AtomicUsize::new(0)),
+            task_registry: TaskRegistry::default(),
         };
 
         let _ = GLOBAL_EXECUTOR
@@ -224,9 +246,16 @@ impl TaskManager {
             task_events_tx: self.task_events_tx.clone(),
             metrics: Default::default(),
             graceful_tasks: Arc::clone(&self.graceful_tasks),
+            task_registry: self.task_registry.clone(),
         }
     }
 
+    /// Returns the [`TaskRegistry`] that tracks the tasks spawned by the executors of this
+    /// manager.
+    pub const fn task_registry(&self) -> &TaskRegistry {
+        &self.task_registry
+    }
+
     /// Fires the shutdown signal and awaits until all tasks are shutdown.
     pub fn graceful_shutdown(self) {
         let _ = self.do_graceful_shutdown(None);
@@ -247,7 +276,8 @@ impl TaskManager {
                 debug!("graceful shutdown timed out");
                 return false
             }
-            std::hint::spin_loop();
+            // graceful shutdowns may take a while, don't busy-wait for them
+            std::thread::sleep(std::time::Duration::from_millis(1));
         }
 
         debug!("gracefully shut down");
@@ -330,6 +360,8 @@ pub struct TaskExecutor {
     metrics: TaskExecutorMetrics,
     /// How many [`GracefulShutdown`] tasks are currently active
     graceful_tasks: Arc<AtomicUsize>,
+    /// Tracks the spawned tasks, if enabled.
+    task_registry: TaskRegistry,
 }
 
 // === impl TaskExecutor ===
@@ -362,8 +394,46 @@ impl TaskExecutor {
         &self.on_shutdown
     }
 
+    /// Returns the [`TaskRegistry`] that tracks the spawned tasks.
+    pub const fn task_registry(&self) -> &TaskRegistry {
+        &self.task_registry
+    }
+
     /// Spawns a future on the tokio runtime depending on the [`TaskKind`]
-    fn spawn_on_rt<F>(&self, fut: F, task_kind: TaskKind) -> JoinHandle<()>
+    fn spawn_on_rt<F>(
+        &self,
+        name: &'static str,
+        critical: bool,
+        fut: F,
+        task_kind: TaskKind,
+    ) -> JoinHandle<()>
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let fut = self.task_registry.instrument(name, critical, task_kind, fut);
+        self.spawn_on_handle(name, fut, task_kind)
+    }
+
+    /// Spawns the future onto the runtime as a task that is named in tokio-console.
+    #[cfg(all(tokio_unstable, feature = "tokio-console"))]
+    fn spawn_on_handle<F>(&self, name: &'static str, fut: F, task_kind: TaskKind) -> JoinHandle<()>
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let builder = tokio::task::Builder::new().name(name);
+        let res = match task_kind {
+            TaskKind::Default => builder.spawn_on(fut, &self.handle),
+            TaskKind::Blocking => {
+                let handle = self.handle.clone();
+                builder.spawn_blocking_on(move || handle.block_on(fut), &self.handle)
+            }
+        };
+        res.expect("failed to spawn task")
+    }
+
+    /// Spawns the future onto the runtime.
+    #[cfg(not(all(tokio_unstable, feature = "tokio-console")))]
+    fn spawn_on_handle<F>(&self, _name: &'static str, fut: F, task_kind: TaskKind) -> JoinHandle<()>
     where
         F: Future<Output = ()> + Send + 'static,
     {
@@ -377,7 +447,7 @@ impl TaskExecutor {
     }
 
     /// Spawns a regular task depending on the given [`TaskKind`]
-    fn spawn_task_as<F>(&self, fut: F, task_kind: TaskKind) -> JoinHandle<()>
+    fn spawn_task_as<F>(&self, name: &'static str, fut: F, task_kind: TaskKind) -> JoinHandle<()>
     where
         F: Future<Output = ()> + Send + 'static,
     {
@@ -398,7 +468,7 @@ impl TaskExecutor {
         }
         .in_current_span();
 
-        self.spawn_on_rt(task, task_kind)
+        self.spawn_on_rt(name, false, task, task_kind)
     }
 
     /// Spawns the task onto the runtime.
@@ -409,7 +479,18 @@ impl TaskExecutor {
     where
         F: Future<Output = ()> + Send + 'static,
     {
-        self.spawn_task_as(fut, TaskKind::Default)
+        self.spawn_task_as(UNNAMED_TASK, fut, TaskKind::Default)
+    }
+
+    /// Spawns the task onto the runtime under the given name.
+    /// The given future resolves as soon as the [Shutdown] signal is received.
+    ///
+    /// The name identifies the task in the [`TaskRegistry`] and in tokio-console.
+    pub fn spawn_named<F>(&self, name: &'static str, fut: F) -> JoinHandle<()>
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.spawn_task_as(name, fut, TaskKind::Default)
     }
 
     /// Spawns a blocking task onto the runtime.
@@ -420,7 +501,7 @@ impl TaskExecutor {
     where
         F: Future<Output = ()> + Send + 'static,
     {
-        self.spawn_task_as(fut, TaskKind::Blocking)
+        self.spawn_task_as(UNNAMED_TASK, fut, TaskKind::Blocking)
     }
 
     /// Spawns the task onto the runtime.
@@ -428,6 +509,29 @@ impl TaskExecutor {
     ///
     /// See also [`Handle::spawn`].
     pub fn spawn_with_signal<F>(&self, f: impl FnOnce(Shutdown) -> F) -> JoinHandle<()>
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.spawn_with_signal_as(f, TaskKind::Default)
+    }
+
+    /// Spawns a blocking task onto the runtime.
+    /// The given future is not cancelled by the [Shutdown] signal.
+    ///
+    /// See also [`Handle::spawn_blocking`].
+    pub fn spawn_blocking_with_signal<F>(&self, f: impl FnOnce(Shutdown) -> F) -> JoinHandle<()>
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.spawn_with_signal_as(f, TaskKind::Blocking)
+    }
+
+    /// Spawns a task that is handed the [Shutdown] signal depending on the given [`TaskKind`]
+    fn spawn_with_signal_as<F>(
+        &self,
+        f: impl FnOnce(Shutdown) -> F,
+        task_kind: TaskKind,
+    ) -> JoinHandle<()>
     where
         F: Future<Output = ()> + Send + 'static,
     {
@@ -436,7 +540,7 @@ impl TaskExecutor {
 
         let task = fut.in_current_span();
 
-        self.handle.spawn(task)
+        self.spawn_on_rt(UNNAMED_TASK, false, task, task_kind)
     }
 
     /// Spawns a critical task depending on the given [`TaskKind`]
@@ -472,7 +576,7 @@ impl TaskExecutor {
             let _ = select(on_shutdown, task).await;
         };
 
-        self.spawn_on_rt(task, task_kind)
+        self.spawn_on_rt(name, true, task, task_kind)
     }
 
     /// This spawns a critical blocking task onto the runtime.
@@ -505,6 +609,34 @@ impl TaskExecutor {
         name: &'static str,
         f: impl FnOnce(Shutdown) -> F,
     ) -> JoinHandle<()>
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.spawn_critical_with_shutdown_signal_as(name, f, TaskKind::Default)
+    }
+
+    /// This spawns a critical blocking task onto the runtime.
+    ///
+    /// If this task panics, the [`TaskManager`] is notified.
+    pub fn spawn_critical_blocking_with_shutdown_signal<F>(
+        &self,
+        name: &'static str,
+        f: impl FnOnce(Shutdown) -> F,
+    ) -> JoinHandle<()>
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.spawn_critical_with_shutdown_signal_as(name, f, TaskKind::Blocking)
+    }
+
+    /// Spawns a critical task that is handed the [Shutdown] signal depending on the given
+    /// [`TaskKind`]
+    fn spawn_critical_with_shutdown_signal_as<F>(
+        &self,
+        name: &'static str,
+        f: impl FnOnce(Shutdown) -> F,
+        task_kind: TaskKind,
+    ) -> JoinHandle<()>
     where
         F: Future<Output = ()> + Send + 'static,
     {
@@ -523,7 +655,7 @@ impl TaskExecutor {
             .map(drop)
             .in_current_span();
 
-        self.handle.spawn(task)
+        self.spawn_on_rt(name, true, task, task_kind)
     }
 
     /// This spawns a critical task onto the runtime.
@@ -572,7 +704,7 @@ impl TaskExecutor {
             .map(drop)
             .in_current_span();
 
-        self.handle.spawn(task)
+        self.spawn_on_rt(name, true, task, TaskKind::Default)
     }
 
     /// This spawns a regular task onto the runtime.
@@ -607,7 +739,7 @@ impl TaskExecutor {
         );
         let fut = f(on_shutdown);
 
-        self.handle.spawn(fut)
+        self.spawn_on_rt(UNNAMED_TASK, false, fut, TaskKind::Default)
     }
 
     /// Sends a request to the `TaskManager` to initiate a graceful shutdown.
@@ -641,6 +773,11 @@ impl TaskSpawner for TaskExecutor {
         Self::spawn_critical(self, name, fut)
     }
 
+    fn spawn_named(&self, name: &'static str, fut: BoxFuture<'static, ()>) -> JoinHandle<()> {
+        self.metrics.inc_regular_tasks();
+        Self::spawn_named(self, name, fut)
+    }
+
     fn spawn_blocking(&self, fut: BoxFuture<'static, ()>) -> JoinHandle<()> {
         self.metrics.inc_regular_tasks();
         self.spawn_blocking(fut)
@@ -706,6 +843,7 @@ impl TaskSpawnerExt for TaskExecutor {
 }
 
 /// Determines how a task is spawned
+#[derive(Debug, Clone, Copy)]
 enum TaskKind {
     /// Spawn the task to the default executor [`Handle::spawn`]
     Default,
@@ -777,6 +915,55 @@ mod tests {
         handle.block_on(shutdown);
     }
 
+    #[test]
+    fn test_task_registry() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let handle = runtime.handle().clone();
+        let manager = TaskManager::new(handle.clone());
+        let executor = manager.executor();
+
+        // tasks are only tracked once instrumentation is enabled
+        let (_untracked_tx, untracked_rx) = tokio::sync::oneshot::channel::<()>();
+        executor.spawn(async move {
+            let _ = untracked_rx.await;
+        });
+        assert!(executor.task_registry().is_empty());
+
+        manager.task_registry().enable();
+        let (tx, rx) = tokio::sync::oneshot::channel::<()>();
+        let task = executor.spawn_critical("tracked", async move {
+            let _ = rx.await;
+        });
+        executor.spawn_named("named", std::future::pending());
+
+        // tasks can label what they are doing
+        let (labeled_tx, labeled_rx) = tokio::sync::oneshot::channel();
+        executor.spawn_named("labeled", async move {
+            registry::set_task_label("Execution");
+            let _ = labeled_tx.send(());
+            std::future::pending::<()>().await
+        });
+        handle.block_on(labeled_rx).unwrap();
+        registry::set_task_label("outside of a task");
+
+        let tasks = executor.task_registry().tasks();
+        assert_eq!(tasks.len(), 3);
+        assert_eq!(tasks[0].name, "tracked");
+        assert!(tasks[0].critical);
+        assert_eq!(tasks[1].name, "named");
+        assert!(!tasks[1].critical);
+        assert_eq!(tasks[1].label, None);
+        assert_eq!(tasks[2].name, "labeled");
+        assert_eq!(tasks[2].label.as_deref(), Some("Execution"));
+
+        // finished tasks are removed
+        tx.send(()).unwrap();
+        handle.block_on(task).unwrap();
+        let tasks = executor.task_registry().tasks();
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(tasks[0].name, "named");
+    }
+
     // Tests that spawned tasks are terminated if the `TaskManager` drops
     #[test]
     fn test_manager_shutdown() {
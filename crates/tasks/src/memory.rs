@@ -0,0 +1,355 @@
+//! Global memory budget for in-memory caches.
+//!
+//! Components like the RPC state cache, the fee history cache or the transaction pool each have
+//! their own limits, which can collectively exceed the available memory. A [`MemoryBudget`] tracks
+//! the memory reported by all registered [`MemoryConsumer`]s and asks them to release memory once
+//! the total exceeds the configured limit.
+
+use parking_lot::Mutex;
+use reth_metrics::{
+    metrics::{Counter, Gauge},
+    Metrics,
+};
+use std::{
+    collections::BTreeMap,
+    fmt,
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tracing::{debug, trace};
+
+/// Callback that is asked to release the given number of bytes.
+///
+/// Returns the number of bytes that are expected to be released.
+type ReclaimFn = Box<dyn Fn(usize) -> usize + Send + Sync>;
+
+/// A memory budget shared by all registered [`MemoryConsumer`]s.
+///
+/// Consumers report their current memory usage via [`MemoryConsumer::report`]. If the total tracked
+/// memory exceeds the limit, [`MemoryBudget::reclaim`] distributes the excess across all consumers
+/// proportionally to their usage multiplied by their weight, and invokes their reclamation
+/// callbacks.
+///
+/// This type is cheap to clone.
+#[derive(Clone)]
+pub struct MemoryBudget {
+    inner: Arc<MemoryBudgetInner>,
+}
+
+struct MemoryBudgetInner {
+    /// The limit in bytes.
+    limit: usize,
+    /// Sum of the memory reported by all consumers.
+    tracked: AtomicUsize,
+    /// All registered consumers by their ID.
+    consumers: Mutex<BTreeMap<u64, Arc<ConsumerEntry>>>,
+    /// ID of the next registered consumer.
+    next_id: AtomicU64,
+    /// Budget metrics.
+    metrics: MemoryBudgetMetrics,
+}
+
+struct ConsumerEntry {
+    name: String,
+    weight: u32,
+    usage: AtomicUsize,
+    reclaim: ReclaimFn,
+}
+
+impl MemoryBudget {
+    /// Creates a new budget with the given limit in bytes.
+    pub fn new(limit: usize) -> Self {
+        let metrics = MemoryBudgetMetrics::default();
+        metrics.limit_bytes.set(limit as f64);
+        Self {
+            inner: Arc::new(MemoryBudgetInner {
+                limit,
+                tracked: AtomicUsize::new(0),
+                consumers: Default::default(),
+                next_id: AtomicU64::new(0),
+                metrics,
+            }),
+        }
+    }
+
+    /// Creates a new budget that only tracks memory and never reclaims it.
+    pub fn unlimited() -> Self {
+        Self::new(usize::MAX)
+    }
+
+    /// Returns the limit in bytes.
+    pub fn limit(&self) -> usize {
+        self.inner.limit
+    }
+
+    /// Returns the memory in bytes that is currently reported by all consumers.
+    pub fn tracked(&self) -> usize {
+        self.inner.tracked.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of bytes the tracked memory exceeds the limit by.
+    pub fn excess(&self) -> usize {
+        self.tracked().saturating_sub(self.inner.limit)
+    }
+
+    /// Registers a new consumer with the given name and weight.
+    ///
+    /// Consumers with a higher weight are asked to release more memory relative to their usage,
+    /// consumers with a weight of zero are only tracked. The `reclaim` callback is invoked with the
+    /// number of bytes the consumer should release and returns the number of bytes it expects to
+    /// release. It must not block, consumers that own their memory on a different task should
+    /// forward the request to that task.
+    ///
+    /// The consumer is unregistered when the returned [`MemoryConsumer`] is dropped.
+    pub fn register<F>(&self, name: impl Into<String>, weight: u32, reclaim: F) -> MemoryConsumer
+    where
+        F: Fn(usize) -> usize + Send + Sync + 'static,
+    {
+        let name = name.into();
+        let id = self.inner.next_id.fetch_add(1, Ordering::Relaxed);
+        let entry = Arc::new(ConsumerEntry {
+            name: name.clone(),
+            weight,
+            usage: AtomicUsize::new(0),
+            reclaim: Box::new(reclaim),
+        });
+        self.inner.consumers.lock().insert(id, entry.clone());
+        debug!(target: "tasks::memory", %name, weight, "Registered memory consumer");
+
+        MemoryConsumer {
+            id,
+            entry,
+            budget: self.clone(),
+            metrics: MemoryConsumerMetrics::new_with_labels(&[("consumer", name)]),
+        }
+    }
+
+    /// Asks the registered consumers to release memory if the tracked memory exceeds the limit.
+    ///
+    /// Returns the number of bytes the consumers expect to release.
+    pub fn reclaim(&self) -> usize {
+        let excess = self.excess();
+        if excess == 0 {
+            return 0
+        }
+
+        // release the lock before invoking the callbacks, so they can drop their consumers
+        let consumers = self.inner.consumers.lock().values().cloned().collect::<Vec<_>>();
+        let scores = consumers
+            .iter()
+            .map(|consumer| {
+                consumer.weight as u128 * consumer.usage.load(Ordering::Relaxed) as u128
+            })
+            .collect::<Vec<_>>();
+        let total_score = scores.iter().sum::<u128>();
+        if total_score == 0 {
+            return 0
+        }
+
+        let mut reclaimed = 0;
+        for (consumer, score) in consumers.iter().zip(scores) {
+            let share = (excess as u128 * score / total_score) as usize;
+            let share = share.min(consumer.usage.load(Ordering::Relaxed));
+            if share == 0 {
+                continue
+            }
+            let released = (consumer.reclaim)(share);
+            trace!(target: "tasks::memory", name = %consumer.name, share, released, "Reclaimed memory");
+            reclaimed += released;
+        }
+
+        debug!(target: "tasks::memory", excess, reclaimed, "Reclaimed memory over budget");
+        self.inner.metrics.reclaims_total.increment(1);
+        self.inner.metrics.reclaimed_bytes_total.increment(reclaimed as u64);
+        reclaimed
+    }
+
+    /// Returns a future that calls [`MemoryBudget::reclaim`] at the given interval.
+    pub async fn reclaim_task(self, interval: Duration) {
+        let mut interval = tokio::time::interval(interval);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            self.reclaim();
+        }
+    }
+
+    /// Applies a change in the usage of a consumer to the tracked memory.
+    fn on_usage_change(&self, old: usize, new: usize) {
+        let tracked = if new >= old {
+            self.inner.tracked.fetch_add(new - old, Ordering::Relaxed) + (new - old)
+        } else {
+            self.inner.tracked.fetch_sub(old - new, Ordering::Relaxed) - (old - new)
+        };
+        self.inner.metrics.tracked_bytes.set(tracked as f64);
+    }
+}
+
+impl Default for MemoryBudget {
+    fn default() -> Self {
+        Self::unlimited()
+    }
+}
+
+impl fmt::Debug for MemoryBudget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MemoryBudget")
+            .field("limit", &self.inner.limit)
+            .field("tracked", &self.tracked())
+            .field("consumers", &self.inner.consumers.lock().len())
+            .finish()
+    }
+}
+
+/// A component that is registered with a [`MemoryBudget`].
+///
+/// Unregisters the component from the budget when dropped.
+pub struct MemoryConsumer {
+    id: u64,
+    entry: Arc<ConsumerEntry>,
+    budget: MemoryBudget,
+    metrics: MemoryConsumerMetrics,
+}
+
+impl MemoryConsumer {
+    /// Reports the memory in bytes that is currently used by the component.
+    pub fn report(&self, usage: usize) {
+        let old = self.entry.usage.swap(usage, Ordering::Relaxed);
+        self.budget.on_usage_change(old, usage);
+        self.metrics.usage_bytes.set(usage as f64);
+    }
+
+    /// Returns the last reported memory usage in bytes.
+    pub fn usage(&self) -> usize {
+        self.entry.usage.load(Ordering::Relaxed)
+    }
+
+    /// Returns the budget the component is registered with.
+    pub const fn budget(&self) -> &MemoryBudget {
+        &self.budget
+    }
+
+    /// Returns a future that reports the memory returned by `usage` at the given interval.
+    ///
+    /// This is for components that don't report changes of their memory usage themselves. The
+    /// consumer is unregistered when the future is dropped.
+    pub async fn report_task<F>(self, interval: Duration, mut usage: F)
+    where
+        F: FnMut() -> usize,
+    {
+        let mut interval = tokio::time::interval(interval);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            self.report(usage());
+        }
+    }
+}
+
+impl Drop for MemoryConsumer {
+    fn drop(&mut self) {
+        self.report(0);
+        self.budget.inner.consumers.lock().remove(&self.id);
+    }
+}
+
+impl fmt::Debug for MemoryConsumer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MemoryConsumer")
+            .field("name", &self.entry.name)
+            .field("weight", &self.entry.weight)
+            .field("usage", &self.usage())
+            .finish()
+    }
+}
+
+/// Memory budget metrics
+#[derive(Metrics)]
+#[metrics(scope = "memory_budget")]
+struct MemoryBudgetMetrics {
+    /// Memory in bytes reported by all consumers
+    tracked_bytes: Gauge,
+    /// The configured limit in bytes
+    limit_bytes: Gauge,
+    /// Number of reclamations that were triggered because the limit was exceeded
+    reclaims_total: Counter,
+    /// Number of bytes the consumers were expected to release
+    reclaimed_bytes_total: Counter,
+}
+
+/// Memory budget metrics of a single consumer
+#[derive(Metrics)]
+#[metrics(scope = "memory_budget.consumer")]
+struct MemoryConsumerMetrics {
+    /// Memory in bytes reported by the consumer
+    usage_bytes: Gauge,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recording_consumer(
+        budget: &MemoryBudget,
+        weight: u32,
+    ) -> (MemoryConsumer, Arc<AtomicUsize>) {
+        let requested = Arc::new(AtomicUsize::new(0));
+        let consumer = budget.register("test", weight, {
+            let requested = requested.clone();
+            move |bytes| {
+                requested.fetch_add(bytes, Ordering::Relaxed);
+                bytes
+            }
+        });
+        (consumer, requested)
+    }
+
+    #[test]
+    fn tracks_reported_usage() {
+        let budget = MemoryBudget::new(100);
+        let (a, _) = recording_consumer(&budget, 1);
+        let (b, _) = recording_consumer(&budget, 1);
+
+        a.report(40);
+        b.report(30);
+        assert_eq!(budget.tracked(), 70);
+
+        a.report(10);
+        assert_eq!(budget.tracked(), 40);
+
+        drop(b);
+        assert_eq!(budget.tracked(), 10);
+        assert_eq!(budget.reclaim(), 0);
+    }
+
+    #[test]
+    fn reclaims_by_weighted_usage() {
+        let budget = MemoryBudget::new(100);
+        let (a, requested_a) = recording_consumer(&budget, 1);
+        let (b, requested_b) = recording_consumer(&budget, 3);
+        let (c, requested_c) = recording_consumer(&budget, 0);
+
+        a.report(100);
+        b.report(100);
+        c.report(100);
+        assert_eq!(budget.excess(), 200);
+
+        assert_eq!(budget.reclaim(), 150);
+        assert_eq!(requested_a.load(Ordering::Relaxed), 50);
+        assert_eq!(requested_b.load(Ordering::Relaxed), 100);
+        assert_eq!(requested_c.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn unlimited_never_reclaims() {
+        let budget = MemoryBudget::unlimited();
+        let (a, requested) = recording_consumer(&budget, 1);
+        a.report(usize::MAX / 2);
+
+        assert_eq!(budget.reclaim(), 0);
+        assert_eq!(requested.load(Ordering::Relaxed), 0);
+    }
+}
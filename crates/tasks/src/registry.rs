@@ -0,0 +1,253 @@
+//! Instrumentation of spawned tasks.
+
+use crate::TaskKind;
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+thread_local! {
+    /// The instrumented task that is currently polled on this thread.
+    static CURRENT_TASK: RefCell<Option<Arc<TaskStats>>> = const { RefCell::new(None) };
+}
+
+/// Sets the label of the instrumented task that is currently running, which describes what the
+/// task is doing, e.g. the stage a pipeline task executes.
+///
+/// Does nothing if the caller doesn't run in an instrumented task.
+pub fn set_task_label(label: impl fmt::Display) {
+    CURRENT_TASK.with(|task| {
+        if let Some(stats) = &*task.borrow() {
+            *stats.label.lock().unwrap_or_else(|err| err.into_inner()) = Some(label.to_string());
+        }
+    });
+}
+
+/// Tracks the live tasks spawned by a [`TaskExecutor`](crate::TaskExecutor).
+///
+/// Instrumentation is opt-in, see [`TaskRegistry::enable`]. Once enabled, every task spawned by
+/// the executor is listed with its name and poll statistics until it finishes, which helps to
+/// diagnose leaking or runaway tasks. Tasks spawned before instrumentation was enabled are not
+/// tracked.
+#[derive(Debug, Clone, Default)]
+pub struct TaskRegistry {
+    inner: Arc<TaskRegistryInner>,
+}
+
+impl TaskRegistry {
+    /// Enables the instrumentation of newly spawned tasks.
+    pub fn enable(&self) {
+        self.inner.enabled.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns whether spawned tasks are instrumented.
+    pub fn is_enabled(&self) -> bool {
+        self.inner.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of live instrumented tasks.
+    pub fn len(&self) -> usize {
+        self.inner.tasks.lock().unwrap_or_else(|err| err.into_inner()).len()
+    }
+
+    /// Returns `true` if there are no live instrumented tasks.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a snapshot of the live instrumented tasks, in the order they were spawned.
+    pub fn tasks(&self) -> Vec<TaskInfo> {
+        let now = Instant::now();
+        let mut tasks = self
+            .inner
+            .tasks
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .iter()
+            .map(|(id, task)| TaskInfo {
+                id: *id,
+                name: task.name.to_string(),
+                label: task.label.lock().unwrap_or_else(|err| err.into_inner()).clone(),
+                critical: task.critical,
+                blocking: matches!(task.kind, TaskKind::Blocking),
+                age: now.saturating_duration_since(task.spawned_at),
+                polls: task.polls.load(Ordering::Relaxed),
+                busy: Duration::from_nanos(task.busy_nanos.load(Ordering::Relaxed)),
+            })
+            .collect::<Vec<_>>();
+        tasks.sort_unstable_by_key(|task| task.id);
+        tasks
+    }
+
+    /// Wraps the future so that it's tracked until it finishes or is dropped, if instrumentation
+    /// is enabled.
+    pub(crate) fn instrument<F>(
+        &self,
+        name: &'static str,
+        critical: bool,
+        kind: TaskKind,
+        fut: F,
+    ) -> InstrumentedTask<F>
+    where
+        F: Future<Output = ()>,
+    {
+        if !self.is_enabled() {
+            return InstrumentedTask { fut: Box::pin(fut), tracked: None }
+        }
+
+        let id = self.inner.next_id.fetch_add(1, Ordering::Relaxed);
+        let stats = Arc::new(TaskStats {
+            name,
+            label: Mutex::new(None),
+            critical,
+            kind,
+            spawned_at: Instant::now(),
+            polls: AtomicU64::new(0),
+            busy_nanos: AtomicU64::new(0),
+        });
+        self.inner
+            .tasks
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .insert(id, Arc::clone(&stats));
+
+        InstrumentedTask {
+            fut: Box::pin(fut),
+            tracked: Some(TrackedTask { id, stats, registry: Arc::clone(&self.inner) }),
+        }
+    }
+}
+
+/// A snapshot of a live task.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct TaskInfo {
+    /// Identifier of the task, increasing in spawn order.
+    pub id: u64,
+    /// Name of the task, `unnamed` for tasks spawned without a name.
+    pub name: String,
+    /// What the task is doing, see [`set_task_label`].
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub label: Option<String>,
+    /// Whether the task is critical, i.e. its panic shuts down the node.
+    pub critical: bool,
+    /// Whether the task runs on the blocking thread pool.
+    pub blocking: bool,
+    /// Time since the task was spawned, serialized as milliseconds.
+    #[cfg_attr(feature = "serde", serde(with = "duration_millis"))]
+    pub age: Duration,
+    /// Number of times the task was polled.
+    pub polls: u64,
+    /// Total time spent polling the task, serialized as milliseconds.
+    #[cfg_attr(feature = "serde", serde(with = "duration_millis"))]
+    pub busy: Duration,
+}
+
+/// The shared state of a [`TaskRegistry`].
+#[derive(Debug, Default)]
+struct TaskRegistryInner {
+    /// Whether newly spawned tasks are instrumented.
+    enabled: AtomicBool,
+    /// The identifier of the next instrumented task.
+    next_id: AtomicU64,
+    /// The live instrumented tasks.
+    tasks: Mutex<HashMap<u64, Arc<TaskStats>>>,
+}
+
+/// Statistics of an instrumented task.
+#[derive(Debug)]
+struct TaskStats {
+    name: &'static str,
+    label: Mutex<Option<String>>,
+    critical: bool,
+    kind: TaskKind,
+    spawned_at: Instant,
+    polls: AtomicU64,
+    busy_nanos: AtomicU64,
+}
+
+/// Removes a task from the registry when dropped.
+#[derive(Debug)]
+struct TrackedTask {
+    id: u64,
+    stats: Arc<TaskStats>,
+    registry: Arc<TaskRegistryInner>,
+}
+
+impl Drop for TrackedTask {
+    fn drop(&mut self) {
+        self.registry.tasks.lock().unwrap_or_else(|err| err.into_inner()).remove(&self.id);
+    }
+}
+
+/// Marks a task as the one that is currently polled, until dropped.
+struct CurrentTaskGuard {
+    parent: Option<Arc<TaskStats>>,
+}
+
+impl CurrentTaskGuard {
+    fn enter(stats: &Arc<TaskStats>) -> Self {
+        Self { parent: CURRENT_TASK.with(|task| task.replace(Some(Arc::clone(stats)))) }
+    }
+}
+
+impl Drop for CurrentTaskGuard {
+    fn drop(&mut self) {
+        let parent = self.parent.take();
+        CURRENT_TASK.with(|task| *task.borrow_mut() = parent);
+    }
+}
+
+/// A future that records its polls in the [`TaskRegistry`].
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled"]
+pub(crate) struct InstrumentedTask<F> {
+    fut: Pin<Box<F>>,
+    tracked: Option<TrackedTask>,
+}
+
+impl<F: Future<Output = ()>> Future for InstrumentedTask<F> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        let Some(tracked) = &this.tracked else { return this.fut.as_mut().poll(cx) };
+
+        let started = Instant::now();
+        let _current = CurrentTaskGuard::enter(&tracked.stats);
+        let poll = this.fut.as_mut().poll(cx);
+        let busy = started.elapsed().as_nanos().try_into().unwrap_or(u64::MAX);
+        tracked.stats.polls.fetch_add(1, Ordering::Relaxed);
+        tracked.stats.busy_nanos.fetch_add(busy, Ordering::Relaxed);
+        poll
+    }
+}
+
+#[cfg(feature = "serde")]
+mod duration_millis {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::time::Duration;
+
+    pub(super) fn serialize<S: Serializer>(
+        value: &Duration,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(value.as_millis() as u64)
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Duration, D::Error> {
+        u64::deserialize(deserializer).map(Duration::from_millis)
+    }
+}
@@ -0,0 +1,352 @@
+//! Injectable time source for timer driven components.
+//!
+//! Components that run on an interval or against a deadline take a [`SharedClock`] instead of
+//! calling [`tokio::time`] directly. Production code uses the [`SystemClock`], tests can use a
+//! [`ManualClock`] and advance virtual time deterministically instead of sleeping.
+
+use futures_util::future::poll_fn;
+use parking_lot::Mutex;
+use std::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{ready, Context, Poll, Waker},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+pub use tokio::time::MissedTickBehavior;
+
+/// A future that completes at a deadline of a [`Clock`].
+pub type Sleep = Pin<Box<dyn Future<Output = ()> + Send + Sync>>;
+
+/// A [`Clock`] that can be shared between components.
+pub type SharedClock = Arc<dyn Clock>;
+
+/// A source of time.
+pub trait Clock: fmt::Debug + Send + Sync + 'static {
+    /// Returns the current instant.
+    fn now(&self) -> Instant;
+
+    /// Returns the current time as the duration since the unix epoch.
+    fn unix_now(&self) -> Duration;
+
+    /// Returns a future that completes once the clock reached the given deadline.
+    fn sleep_until(&self, deadline: Instant) -> Sleep;
+
+    /// Returns a future that completes once the given duration has elapsed.
+    fn sleep(&self, duration: Duration) -> Sleep {
+        self.sleep_until(self.now() + duration)
+    }
+
+    /// Returns the time elapsed since the given instant.
+    fn elapsed(&self, since: Instant) -> Duration {
+        self.now().saturating_duration_since(since)
+    }
+}
+
+/// The [`Clock`] of the system, timers are driven by the tokio runtime.
+#[derive(Debug, Clone, Copy, Default)]
+#[non_exhaustive]
+pub struct SystemClock;
+
+impl SystemClock {
+    /// Returns the system clock as a [`SharedClock`].
+    pub fn shared() -> SharedClock {
+        Arc::new(Self)
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn unix_now(&self) -> Duration {
+        SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default()
+    }
+
+    fn sleep_until(&self, deadline: Instant) -> Sleep {
+        Box::pin(tokio::time::sleep_until(deadline.into()))
+    }
+}
+
+/// A [`Clock`] that only advances when [`ManualClock::advance`] is called.
+///
+/// Intended for tests of components that are driven by timers.
+#[derive(Debug, Clone)]
+pub struct ManualClock {
+    state: Arc<Mutex<ManualClockState>>,
+}
+
+#[derive(Debug)]
+struct ManualClockState {
+    now: Instant,
+    unix_now: Duration,
+    /// Tasks waiting for a deadline.
+    sleepers: Vec<(Instant, Waker)>,
+}
+
+impl ManualClock {
+    /// Creates a new clock starting at the current system time.
+    pub fn new() -> Self {
+        Self::with_unix_time(SystemClock.unix_now())
+    }
+
+    /// Creates a new clock starting at the given duration since the unix epoch.
+    pub fn with_unix_time(unix_now: Duration) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(ManualClockState {
+                now: Instant::now(),
+                unix_now,
+                sleepers: Vec::new(),
+            })),
+        }
+    }
+
+    /// Advances the clock by the given duration, waking all tasks whose deadline was reached.
+    pub fn advance(&self, duration: Duration) {
+        let ready = {
+            let mut state = self.state.lock();
+            state.now += duration;
+            state.unix_now += duration;
+            let now = state.now;
+            let (ready, pending) =
+                state.sleepers.drain(..).partition::<Vec<_>, _>(|(deadline, _)| *deadline <= now);
+            state.sleepers = pending;
+            ready
+        };
+        for (_, waker) in ready {
+            waker.wake();
+        }
+    }
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        self.state.lock().now
+    }
+
+    fn unix_now(&self) -> Duration {
+        self.state.lock().unix_now
+    }
+
+    fn sleep_until(&self, deadline: Instant) -> Sleep {
+        let state = self.state.clone();
+        Box::pin(poll_fn(move |cx| {
+            let mut state = state.lock();
+            if state.now >= deadline {
+                return Poll::Ready(())
+            }
+            if !state
+                .sleepers
+                .iter()
+                .any(|(d, waker)| *d == deadline && waker.will_wake(cx.waker()))
+            {
+                state.sleepers.push((deadline, cx.waker().clone()));
+            }
+            Poll::Pending
+        }))
+    }
+}
+
+/// An interval driven by a [`Clock`].
+///
+/// Like [`tokio::time::Interval`], the first tick completes immediately and missed ticks are
+/// handled according to the [`MissedTickBehavior`], which defaults to
+/// [`MissedTickBehavior::Burst`].
+pub struct Interval {
+    clock: SharedClock,
+    period: Duration,
+    missed_tick_behavior: MissedTickBehavior,
+    /// The instant of the next tick.
+    next: Instant,
+    sleep: Sleep,
+}
+
+impl Interval {
+    /// Creates a new interval with the given period that ticks immediately.
+    pub fn new(clock: SharedClock, period: Duration) -> Self {
+        let start = clock.now();
+        Self::new_at(clock, start, period)
+    }
+
+    /// Creates a new interval with the given period that first ticks at the given instant.
+    pub fn new_at(clock: SharedClock, start: Instant, period: Duration) -> Self {
+        assert!(period > Duration::ZERO, "`period` must be non-zero.");
+        let sleep = clock.sleep_until(start);
+        Self {
+            clock,
+            period,
+            missed_tick_behavior: MissedTickBehavior::default(),
+            next: start,
+            sleep,
+        }
+    }
+
+    /// Sets how missed ticks are handled.
+    pub const fn set_missed_tick_behavior(&mut self, behavior: MissedTickBehavior) {
+        self.missed_tick_behavior = behavior;
+    }
+
+    /// Returns how missed ticks are handled.
+    pub const fn missed_tick_behavior(&self) -> MissedTickBehavior {
+        self.missed_tick_behavior
+    }
+
+    /// Returns the period of the interval.
+    pub const fn period(&self) -> Duration {
+        self.period
+    }
+
+    /// Resets the interval to complete one period after the current time.
+    pub fn reset(&mut self) {
+        self.next = self.clock.now() + self.period;
+        self.sleep = self.clock.sleep_until(self.next);
+    }
+
+    /// Polls for the next tick, returning the instant the tick was scheduled for.
+    pub fn poll_tick(&mut self, cx: &mut Context<'_>) -> Poll<Instant> {
+        ready!(self.sleep.as_mut().poll(cx));
+
+        let tick = self.next;
+        let now = self.clock.now();
+        self.next = if now > tick + self.period {
+            match self.missed_tick_behavior {
+                MissedTickBehavior::Burst => tick + self.period,
+                MissedTickBehavior::Delay => now + self.period,
+                MissedTickBehavior::Skip => {
+                    let behind = (now - tick).as_nanos() % self.period.as_nanos();
+                    now + self.period - Duration::from_nanos(behind as u64)
+                }
+            }
+        } else {
+            tick + self.period
+        };
+        self.sleep = self.clock.sleep_until(self.next);
+
+        Poll::Ready(tick)
+    }
+
+    /// Completes when the next tick is reached.
+    pub async fn tick(&mut self) -> Instant {
+        poll_fn(|cx| self.poll_tick(cx)).await
+    }
+}
+
+impl fmt::Debug for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Interval")
+            .field("clock", &self.clock)
+            .field("period", &self.period)
+            .field("missed_tick_behavior", &self.missed_tick_behavior)
+            .field("next", &self.next)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::task::noop_waker_ref;
+
+    #[test]
+    fn manual_clock_sleep() {
+        let clock = ManualClock::new();
+        let mut cx = Context::from_waker(noop_waker_ref());
+
+        let mut sleep = clock.sleep(Duration::from_secs(10));
+        assert!(sleep.as_mut().poll(&mut cx).is_pending());
+
+        clock.advance(Duration::from_secs(9));
+        assert!(sleep.as_mut().poll(&mut cx).is_pending());
+
+        clock.advance(Duration::from_secs(1));
+        assert!(sleep.as_mut().poll(&mut cx).is_ready());
+    }
+
+    #[test]
+    fn interval_ticks_with_clock() {
+        let clock = ManualClock::new();
+        let start = clock.now();
+        let mut cx = Context::from_waker(noop_waker_ref());
+        let mut interval = Interval::new(Arc::new(clock.clone()), Duration::from_secs(120));
+
+        // first tick is immediate
+        assert_eq!(interval.poll_tick(&mut cx), Poll::Ready(start));
+        assert!(interval.poll_tick(&mut cx).is_pending());
+
+        clock.advance(Duration::from_secs(120));
+        assert_eq!(interval.poll_tick(&mut cx), Poll::Ready(start + Duration::from_secs(120)));
+        assert!(interval.poll_tick(&mut cx).is_pending());
+
+        // missed ticks are delayed
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        clock.advance(Duration::from_secs(500));
+        assert_eq!(interval.poll_tick(&mut cx), Poll::Ready(start + Duration::from_secs(240)));
+        assert!(interval.poll_tick(&mut cx).is_pending());
+        clock.advance(Duration::from_secs(119));
+        assert!(interval.poll_tick(&mut cx).is_pending());
+        clock.advance(Duration::from_secs(1));
+        assert!(interval.poll_tick(&mut cx).is_ready());
+    }
+
+    #[test]
+    fn interval_bursts_missed_ticks_by_default() {
+        let clock = ManualClock::new();
+        let start = clock.now();
+        let period = Duration::from_secs(10);
+        let mut cx = Context::from_waker(noop_waker_ref());
+        let mut interval = Interval::new(Arc::new(clock.clone()), period);
+        assert_eq!(interval.missed_tick_behavior(), MissedTickBehavior::Burst);
+        assert_eq!(interval.poll_tick(&mut cx), Poll::Ready(start));
+
+        // all missed ticks complete immediately, then the interval is back on schedule
+        clock.advance(Duration::from_secs(35));
+        for n in 1..=3 {
+            assert_eq!(interval.poll_tick(&mut cx), Poll::Ready(start + period * n));
+        }
+        assert!(interval.poll_tick(&mut cx).is_pending());
+        clock.advance(Duration::from_secs(5));
+        assert_eq!(interval.poll_tick(&mut cx), Poll::Ready(start + period * 4));
+    }
+
+    #[test]
+    fn interval_skips_missed_ticks() {
+        let clock = ManualClock::new();
+        let start = clock.now();
+        let period = Duration::from_secs(10);
+        let mut cx = Context::from_waker(noop_waker_ref());
+        let mut interval = Interval::new(Arc::new(clock.clone()), period);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        assert_eq!(interval.poll_tick(&mut cx), Poll::Ready(start));
+
+        // the missed ticks are skipped, the next tick stays aligned to the period
+        clock.advance(Duration::from_secs(35));
+        assert_eq!(interval.poll_tick(&mut cx), Poll::Ready(start + period));
+        assert!(interval.poll_tick(&mut cx).is_pending());
+        clock.advance(Duration::from_secs(5));
+        assert_eq!(interval.poll_tick(&mut cx), Poll::Ready(start + period * 4));
+    }
+
+    #[test]
+    fn interval_reset() {
+        let clock = ManualClock::new();
+        let start = clock.now();
+        let mut cx = Context::from_waker(noop_waker_ref());
+        let mut interval = Interval::new(Arc::new(clock.clone()), Duration::from_secs(10));
+        assert_eq!(interval.poll_tick(&mut cx), Poll::Ready(start));
+
+        clock.advance(Duration::from_secs(5));
+        interval.reset();
+        clock.advance(Duration::from_secs(9));
+        assert!(interval.poll_tick(&mut cx).is_pending());
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(interval.poll_tick(&mut cx), Poll::Ready(start + Duration::from_secs(15)));
+    }
+}
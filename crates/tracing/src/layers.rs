@@ -5,9 +5,9 @@ use std::{
 
 use rolling_file::{RollingConditionBasic, RollingFileAppender};
 use tracing_appender::non_blocking::WorkerGuard;
-use tracing_subscriber::{filter::Directive, EnvFilter, Layer, Registry};
+use tracing_subscriber::{filter::Directive, reload, EnvFilter, Layer, Registry};
 
-use crate::formatter::LogFormat;
+use crate::{formatter::LogFormat, reload::LogFilterHandle};
 
 /// A worker guard returned by the file layer.
 ///
@@ -34,9 +34,13 @@ const DEFAULT_ENV_FILTER_DIRECTIVES: [&str; 5] = [
 ///
 /// `Layers` acts as a container for different logging layers such as stdout, file, or journald.
 /// Each layer can be configured separately and then combined into a tracing subscriber.
+///
+/// The filters of the stdout and file layers can be changed at runtime with the
+/// [`LogFilterHandle`] of the layers.
 #[derive(Default)]
 pub struct Layers {
     inner: Vec<BoxedLayer<Registry>>,
+    filter_handle: LogFilterHandle,
 }
 
 impl fmt::Debug for Layers {
@@ -59,6 +63,11 @@ impl Layers {
         self.inner.push(layer.boxed());
     }
 
+    /// Returns the handle to change the filters of the stdout and file layers at runtime.
+    pub fn filter_handle(&self) -> LogFilterHandle {
+        self.filter_handle.clone()
+    }
+
     /// Consumes the `Layers` instance, returning the inner vector of layers.
     pub(crate) fn into_inner(self) -> Vec<BoxedLayer<Registry>> {
         self.inner
@@ -98,7 +107,9 @@ impl Layers {
         filters: &str,
         color: Option<String>,
     ) -> eyre::Result<()> {
-        let filter = build_env_filter(Some(default_directive), filters)?;
+        let filter = build_env_filter(Some(default_directive.clone()), filters)?;
+        let (filter, handle) = reload::Layer::new(filter);
+        self.filter_handle.register(handle, Some(default_directive), filters);
         let layer = format.apply(filter, color, None);
         self.add_layer(layer);
         Ok(())
@@ -121,10 +132,22 @@ impl Layers {
     ) -> eyre::Result<FileWorkerGuard> {
         let (writer, guard) = file_info.create_log_writer();
         let file_filter = build_env_filter(None, filter)?;
+        let (file_filter, handle) = reload::Layer::new(file_filter);
+        self.filter_handle.register(handle, None, filter);
         let layer = format.apply(file_filter, None, Some(writer));
         self.add_layer(layer);
         Ok(guard)
     }
+
+    /// Adds a layer that serves the task instrumentation of the tokio runtime to `tokio-console`.
+    ///
+    /// The layer spawns the console server, which listens on `127.0.0.1:6669` by default and is
+    /// configured by the `TOKIO_CONSOLE_*` environment variables. The spawned task data is only
+    /// available if the binary is built with `--cfg tokio_unstable`.
+    #[cfg(feature = "tokio-console")]
+    pub fn tokio_console(&mut self) {
+        self.add_layer(console_subscriber::spawn());
+    }
 }
 
 /// Holds configuration information for file logging.
@@ -184,7 +207,7 @@ impl FileInfo {
 ///
 /// # Returns
 /// An `eyre::Result<EnvFilter>` that can be used to configure a tracing subscriber.
-fn build_env_filter(
+pub(crate) fn build_env_filter(
     default_directive: Option<Directive>,
     directives: &str,
 ) -> eyre::Result<EnvFilter> {
@@ -0,0 +1,340 @@
+//! Runtime reloading of the log filters.
+//!
+//! The filters of the stdout and file layers are wrapped in a [`reload::Layer`], so that
+//! additional directives can be applied while the node is running, e.g. to enable `trace` logs
+//! for a single target while debugging an issue. The runtime directives are appended to the
+//! directives each layer was configured with, and are reset by applying an empty filter.
+
+use crate::layers::build_env_filter;
+use std::{
+    path::{Path, PathBuf},
+    sync::{Arc, Condvar, Mutex, MutexGuard, OnceLock},
+    time::{Duration, Instant, SystemTime},
+};
+use tracing_subscriber::{filter::Directive, reload, EnvFilter, Registry};
+
+/// The handle of the installed global subscriber.
+static LOG_FILTER_HANDLE: OnceLock<LogFilterHandle> = OnceLock::new();
+
+/// The interval in which a watched filter file is checked for changes.
+const FILTER_FILE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The maximum duration of a [`LogFilterHandle::set_filter_for`].
+pub const MAX_LOG_FILTER_DURATION: Duration = Duration::from_secs(60 * 60);
+
+/// Returns the handle to the log filters of the global subscriber, if it was initialized by a
+/// [`Tracer`](crate::Tracer) of this crate.
+pub fn log_filter_handle() -> Option<LogFilterHandle> {
+    LOG_FILTER_HANDLE.get().cloned()
+}
+
+/// Installs the handle as the handle of the global subscriber.
+pub(crate) fn set_log_filter_handle(handle: LogFilterHandle) {
+    let _ = LOG_FILTER_HANDLE.set(handle);
+}
+
+/// Changes the log filters of the stdout and file layers at runtime.
+#[derive(Debug, Clone, Default)]
+pub struct LogFilterHandle {
+    inner: Arc<LogFilterHandleInner>,
+}
+
+impl LogFilterHandle {
+    /// Registers the filter of a layer, configured with the given default directive and filters.
+    pub(crate) fn register(
+        &self,
+        handle: reload::Handle<EnvFilter, Registry>,
+        default_directive: Option<Directive>,
+        filters: &str,
+    ) {
+        self.inner.layers.lock().unwrap_or_else(|err| err.into_inner()).push(ReloadableFilter {
+            handle,
+            default_directive,
+            filters: filters.to_string(),
+        });
+    }
+
+    /// Returns the runtime directives that are currently applied.
+    pub fn directives(&self) -> String {
+        self.inner.directives.lock().unwrap_or_else(|err| err.into_inner()).clone()
+    }
+
+    /// Applies the comma-separated directives on top of the configured filters of each layer,
+    /// replacing the previously applied runtime directives.
+    ///
+    /// An empty string resets the filters to their configured directives. Cancels the revert of
+    /// a previous [`LogFilterHandle::set_filter_for`].
+    pub fn set_filter(&self, directives: &str) -> eyre::Result<()> {
+        let mut revert = self.lock_revert();
+        self.apply(directives)?;
+        *revert = None;
+        Ok(())
+    }
+
+    /// Applies the directives like [`LogFilterHandle::set_filter`], and reverts to the
+    /// previously applied directives once the duration elapsed.
+    ///
+    /// The duration is capped at [`MAX_LOG_FILTER_DURATION`]. A pending revert is rescheduled,
+    /// and still restores the directives that were applied before it. The revert is skipped if
+    /// the filters are changed with [`LogFilterHandle::set_filter`] in the meantime.
+    pub fn set_filter_for(&self, directives: &str, duration: Duration) -> eyre::Result<()> {
+        if duration > MAX_LOG_FILTER_DURATION {
+            eyre::bail!(
+                "log filter duration {duration:?} exceeds the maximum of {MAX_LOG_FILTER_DURATION:?}"
+            )
+        }
+        self.spawn_revert_task()?;
+
+        let mut revert = self.lock_revert();
+        let previous = match revert.as_ref() {
+            Some(pending) => pending.directives.clone(),
+            None => self.directives(),
+        };
+        self.apply(directives)?;
+        *revert = Some(PendingRevert { deadline: Instant::now() + duration, directives: previous });
+        self.inner.revert_scheduled.notify_one();
+        Ok(())
+    }
+
+    /// Returns the pending revert.
+    fn lock_revert(&self) -> MutexGuard<'_, Option<PendingRevert>> {
+        self.inner.revert.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Spawns the task that applies the pending revert once its deadline elapsed, if it isn't
+    /// running yet.
+    fn spawn_revert_task(&self) -> eyre::Result<()> {
+        let mut spawned = self.inner.revert_task.lock().unwrap_or_else(|err| err.into_inner());
+        if !*spawned {
+            let this = self.clone();
+            std::thread::Builder::new()
+                .name("log-filter-revert".to_string())
+                .spawn(move || this.run_revert_task())?;
+            *spawned = true;
+        }
+        Ok(())
+    }
+
+    /// Waits for the deadline of the pending revert and applies it, until the revert is
+    /// rescheduled or cancelled.
+    fn run_revert_task(&self) {
+        let mut revert = self.lock_revert();
+        loop {
+            let Some(deadline) = revert.as_ref().map(|pending| pending.deadline) else {
+                revert =
+                    self.inner.revert_scheduled.wait(revert).unwrap_or_else(|err| err.into_inner());
+                continue
+            };
+            let now = Instant::now();
+            if now < deadline {
+                revert = self
+                    .inner
+                    .revert_scheduled
+                    .wait_timeout(revert, deadline - now)
+                    .unwrap_or_else(|err| err.into_inner())
+                    .0;
+                continue
+            }
+
+            let Some(PendingRevert { directives, .. }) = revert.take() else { continue };
+            if let Err(err) = self.apply(&directives) {
+                tracing::warn!(target: "reth::tracing", %err, "Failed to revert log filter");
+            } else {
+                tracing::info!(target: "reth::tracing", %directives, "Reverted log filter");
+            }
+        }
+    }
+
+    /// Watches the file and applies its directives whenever it changes.
+    ///
+    /// The file contains one or more comma-separated directives per line, empty lines and lines
+    /// starting with `#` are ignored. Removing the file resets the filters.
+    pub fn watch_file(&self, path: PathBuf) -> eyre::Result<()> {
+        let this = self.clone();
+        std::thread::Builder::new().name("log-filter-watcher".to_string()).spawn(move || {
+            let mut last_modified = None;
+            loop {
+                let modified = std::fs::metadata(&path).and_then(|m| m.modified()).ok();
+                if modified != last_modified {
+                    last_modified = modified;
+                    this.apply_file(&path, modified);
+                }
+                std::thread::sleep(FILTER_FILE_POLL_INTERVAL);
+            }
+        })?;
+        Ok(())
+    }
+
+    /// Applies the directives of the file, or resets the filters if it doesn't exist.
+    fn apply_file(&self, path: &Path, modified: Option<SystemTime>) {
+        let directives = match modified.map(|_| std::fs::read_to_string(path)) {
+            Some(Ok(contents)) => parse_filter_file(&contents),
+            Some(Err(err)) => {
+                tracing::warn!(target: "reth::tracing", path = %path.display(), %err, "Failed to read log filter file");
+                return
+            }
+            None => String::new(),
+        };
+        if directives == self.directives() {
+            return
+        }
+        match self.set_filter(&directives) {
+            Ok(()) => {
+                tracing::info!(target: "reth::tracing", path = %path.display(), %directives, "Applied log filter file");
+            }
+            Err(err) => {
+                tracing::warn!(target: "reth::tracing", path = %path.display(), %err, "Invalid log filter file");
+            }
+        }
+    }
+
+    /// Rebuilds the filters of all layers with the directives.
+    ///
+    /// The filters are only replaced if the directives are valid for all layers.
+    fn apply(&self, directives: &str) -> eyre::Result<()> {
+        let layers = self.inner.layers.lock().unwrap_or_else(|err| err.into_inner());
+        let filters = layers
+            .iter()
+            .map(|layer| {
+                build_env_filter(
+                    layer.default_directive.clone(),
+                    &format!("{},{directives}", layer.filters),
+                )
+            })
+            .collect::<eyre::Result<Vec<_>>>()?;
+        for (layer, filter) in layers.iter().zip(filters) {
+            layer.handle.reload(filter)?;
+        }
+        *self.inner.directives.lock().unwrap_or_else(|err| err.into_inner()) =
+            directives.to_string();
+        Ok(())
+    }
+}
+
+/// The shared state of a [`LogFilterHandle`].
+#[derive(Debug, Default)]
+struct LogFilterHandleInner {
+    /// The reloadable filters of the layers.
+    layers: Mutex<Vec<ReloadableFilter>>,
+    /// The runtime directives that are currently applied.
+    directives: Mutex<String>,
+    /// The revert of the last [`LogFilterHandle::set_filter_for`], if it's still pending.
+    revert: Mutex<Option<PendingRevert>>,
+    /// Wakes the revert task when the pending revert changed.
+    revert_scheduled: Condvar,
+    /// Whether the revert task is spawned.
+    revert_task: Mutex<bool>,
+}
+
+/// A revert of the runtime directives that is applied at the deadline.
+#[derive(Debug)]
+struct PendingRevert {
+    deadline: Instant,
+    directives: String,
+}
+
+/// The filter of a layer and the directives it was configured with.
+#[derive(Debug)]
+struct ReloadableFilter {
+    handle: reload::Handle<EnvFilter, Registry>,
+    default_directive: Option<Directive>,
+    filters: String,
+}
+
+/// Joins the directives of a filter file into a comma-separated filter.
+fn parse_filter_file(contents: &str) -> String {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .flat_map(|line| line.split(','))
+        .map(str::trim)
+        .filter(|directive| !directive.is_empty())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    /// Returns a handle with a single registered layer, and the subscriber that owns the layer.
+    fn test_handle() -> (LogFilterHandle, reload::Handle<EnvFilter, Registry>, impl Sized) {
+        let (layer, reload_handle) = reload::Layer::new(EnvFilter::new("info"));
+        let subscriber = Registry::default().with(layer);
+        let handle = LogFilterHandle::default();
+        handle.register(reload_handle.clone(), None, "info");
+        (handle, reload_handle, subscriber)
+    }
+
+    fn current_filter(reload_handle: &reload::Handle<EnvFilter, Registry>) -> String {
+        reload_handle.with_current(|filter| filter.to_string()).unwrap()
+    }
+
+    #[test]
+    fn apply_reloads_filters() {
+        let (handle, reload_handle, _subscriber) = test_handle();
+
+        handle.set_filter("engine=trace").unwrap();
+        assert_eq!(handle.directives(), "engine=trace");
+        assert!(current_filter(&reload_handle).contains("engine=trace"));
+
+        // invalid directives leave the filters untouched
+        assert!(handle.set_filter("engine=nope").is_err());
+        assert_eq!(handle.directives(), "engine=trace");
+        assert!(current_filter(&reload_handle).contains("engine=trace"));
+
+        handle.set_filter("").unwrap();
+        assert_eq!(handle.directives(), "");
+        assert!(!current_filter(&reload_handle).contains("engine=trace"));
+    }
+
+    #[test]
+    fn revert_restores_previous_filter() {
+        let (handle, reload_handle, _subscriber) = test_handle();
+        handle.set_filter("net=debug").unwrap();
+
+        handle.set_filter_for("engine=trace", Duration::from_millis(50)).unwrap();
+        // rescheduling keeps the directives that were applied before the first timed filter
+        handle.set_filter_for("sync=trace", Duration::from_millis(100)).unwrap();
+        assert_eq!(handle.directives(), "sync=trace");
+
+        std::thread::sleep(Duration::from_millis(75));
+        assert_eq!(handle.directives(), "sync=trace");
+
+        std::thread::sleep(Duration::from_millis(500));
+        assert_eq!(handle.directives(), "net=debug");
+        assert!(current_filter(&reload_handle).contains("net=debug"));
+        assert!(!current_filter(&reload_handle).contains("sync=trace"));
+    }
+
+    #[test]
+    fn revert_skipped_when_superseded() {
+        let (handle, _reload_handle, _subscriber) = test_handle();
+
+        handle.set_filter_for("engine=trace", Duration::from_millis(50)).unwrap();
+        handle.set_filter("net=debug").unwrap();
+
+        std::thread::sleep(Duration::from_millis(300));
+        assert_eq!(handle.directives(), "net=debug");
+    }
+
+    #[test]
+    fn filter_duration_is_capped() {
+        let (handle, _reload_handle, _subscriber) = test_handle();
+
+        assert!(handle
+            .set_filter_for("engine=trace", MAX_LOG_FILTER_DURATION + Duration::from_secs(1))
+            .is_err());
+        assert_eq!(handle.directives(), "");
+    }
+
+    #[test]
+    fn parse_filter_file_contents() {
+        let contents =
+            "# enable trace logs of the engine\nengine=trace\n\n net=debug, sync=info \n";
+        assert_eq!(parse_filter_file(contents), "engine=trace,net=debug,sync=info");
+        assert_eq!(parse_filter_file("# nothing\n"), "");
+    }
+}
@@ -1,4 +1,5 @@
 use crate::{
+    firewall::FirewallConfig,
     maintain::MAX_QUEUED_TRANSACTION_LIFETIME,
     pool::{NEW_TX_LISTENER_BUFFER_SIZE, PENDING_TX_LISTENER_BUFFER_SIZE},
     PoolSize, TransactionOrigin,
@@ -31,6 +32,9 @@ pub const REPLACE_BLOB_PRICE_BUMP: u128 = 100;
 /// Default maximum new transactions for broadcasting.
 pub const MAX_NEW_PENDING_TXS_NOTIFICATIONS: usize = 200;
 
+/// Default window in which gossip of a replaced local transaction is suppressed.
+pub const DEFAULT_REPLACEMENT_SUPPRESSION_WINDOW: Duration = Duration::from_secs(12);
+
 /// Configuration options for the Transaction pool.
 #[derive(Debug, Clone)]
 pub struct PoolConfig {
@@ -65,6 +69,13 @@ pub struct PoolConfig {
     pub max_new_pending_txs_notifications: usize,
     /// Maximum lifetime for transactions in the pool
     pub max_queued_lifetime: Duration,
+    /// How long a replaced local transaction is tracked so that gossip of the replaced version is
+    /// suppressed. A zero duration disables tracking.
+    pub replacement_suppression_window: Duration,
+    /// Slot quotas per sender and per [`TransactionOrigin`].
+    pub quotas: PoolQuotas,
+    /// Admission rules evaluated before a transaction is validated.
+    pub firewall: FirewallConfig,
 }
 
 impl PoolConfig {
@@ -96,6 +107,9 @@ impl Default for PoolConfig {
             new_tx_listener_buffer_size: NEW_TX_LISTENER_BUFFER_SIZE,
             max_new_pending_txs_notifications: MAX_NEW_PENDING_TXS_NOTIFICATIONS,
             max_queued_lifetime: MAX_QUEUED_TRANSACTION_LIFETIME,
+            replacement_suppression_window: DEFAULT_REPLACEMENT_SUPPRESSION_WINDOW,
+            quotas: Default::default(),
+            firewall: Default::default(),
         }
     }
 }
@@ -170,6 +184,73 @@ impl Default for PriceBumpConfig {
     }
 }
 
+/// Caps on the number of pooled (pending + queued) transactions per sender and per
+/// [`TransactionOrigin`].
+///
+/// Unlike [`PoolConfig::max_account_slots`], these quotas also apply to local transactions. All
+/// quotas are disabled by default.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PoolQuotas {
+    /// Max number of transactions a single sender may have in the pool.
+    ///
+    /// New transactions of a sender at its quota are always rejected, unless they replace an
+    /// existing transaction or use the sender's on-chain nonce.
+    pub per_sender: Option<usize>,
+    /// Max number of [`TransactionOrigin::Local`] transactions in the pool.
+    pub local: Option<usize>,
+    /// Max number of [`TransactionOrigin::External`] transactions in the pool.
+    pub external: Option<usize>,
+    /// Max number of [`TransactionOrigin::Private`] transactions in the pool.
+    pub private: Option<usize>,
+    /// How to enforce the per-origin quotas.
+    pub eviction: QuotaEvictionPolicy,
+}
+
+impl PoolQuotas {
+    /// Sets the per-sender quota.
+    pub const fn with_per_sender(mut self, max_txs: usize) -> Self {
+        self.per_sender = Some(max_txs);
+        self
+    }
+
+    /// Sets the quota for the given origin.
+    pub const fn with_origin(mut self, origin: TransactionOrigin, max_txs: usize) -> Self {
+        match origin {
+            TransactionOrigin::Local => self.local = Some(max_txs),
+            TransactionOrigin::External => self.external = Some(max_txs),
+            TransactionOrigin::Private => self.private = Some(max_txs),
+        }
+        self
+    }
+
+    /// Sets the eviction policy for the per-origin quotas.
+    pub const fn with_eviction(mut self, eviction: QuotaEvictionPolicy) -> Self {
+        self.eviction = eviction;
+        self
+    }
+
+    /// Returns the quota for the given origin, if any.
+    #[inline]
+    pub const fn origin_quota(&self, origin: TransactionOrigin) -> Option<usize> {
+        match origin {
+            TransactionOrigin::Local => self.local,
+            TransactionOrigin::External => self.external,
+            TransactionOrigin::Private => self.private,
+        }
+    }
+}
+
+/// How a full per-origin quota is enforced.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum QuotaEvictionPolicy {
+    /// Reject new transactions of an origin that is at its quota.
+    #[default]
+    RejectNew,
+    /// Accept new transactions and evict the transactions with the lowest fee cap of an origin
+    /// that is over its quota, together with their descendants.
+    EvictCheapest,
+}
+
 /// Configuration options for the locally received transactions:
 /// [`TransactionOrigin::Local`](TransactionOrigin)
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -6,6 +6,8 @@ use alloy_eips::eip4844::BlobTransactionValidationError;
 use alloy_primitives::{Address, TxHash, U256};
 use reth_primitives_traits::transaction::error::InvalidTransactionError;
 
+use crate::{firewall::FirewallRejection, TransactionOrigin};
+
 /// Transaction pool result type.
 pub type PoolResult<T> = Result<T, PoolError>;
 
@@ -56,10 +58,16 @@ pub enum PoolErrorKind {
     /// Thrown when the number of unique transactions of a sender exceeded the slot capacity.
     #[error("rejected due to {0} being identified as a spammer")]
     SpammerExceededCapacity(Address),
+    /// Thrown when the number of transactions of an origin exceeded its configured quota.
+    #[error("rejected due to {0:?} transactions exceeding their quota")]
+    OriginExceededCapacity(TransactionOrigin),
     /// Thrown when a new transaction is added to the pool, but then immediately discarded to
     /// respect the size limits of the pool.
     #[error("transaction discarded outright due to pool size constraints")]
     DiscardedOnInsert,
+    /// Thrown when a rule of the pool's admission firewall rejected the transaction.
+    #[error(transparent)]
+    FirewallRejected(#[from] FirewallRejection),
     /// Thrown when the transaction is considered invalid.
     #[error(transparent)]
     InvalidTransaction(#[from] InvalidPoolTransactionError),
@@ -126,10 +134,18 @@ impl PoolError {
                 // (pool lags behind) and old transaction still occupy a slot in the pool
                 false
             }
+            PoolErrorKind::OriginExceededCapacity(_) => {
+                // the quota is shared by all senders of the origin, the tx itself is not bad
+                false
+            }
             PoolErrorKind::DiscardedOnInsert => {
                 // valid tx but dropped due to size constraints
                 false
             }
+            PoolErrorKind::FirewallRejected(_) => {
+                // the rules are local policy, the tx itself is not bad
+                false
+            }
             PoolErrorKind::InvalidTransaction(err) => {
                 // transaction rejected because it violates constraints
                 err.is_bad_transaction()
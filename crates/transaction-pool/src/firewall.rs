@@ -0,0 +1,398 @@
+//! Admission firewall evaluated before transactions are validated and inserted into the pool.
+//!
+//! The firewall is a chain of [`FirewallRule`]s. The built-in rules are derived from a
+//! [`FirewallConfig`] that can be replaced at runtime, custom rules can be added with
+//! [`PoolFirewall::add_rule`].
+
+use crate::{metrics::FirewallRuleMetrics, traits::PoolTransaction, TransactionOrigin};
+use alloy_primitives::Address;
+use parking_lot::RwLock;
+use std::{collections::BTreeSet, fmt, sync::Arc};
+
+/// Admission rules evaluated before a transaction is validated.
+///
+/// All rules are disabled by default.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct FirewallConfig {
+    /// Maximum size of the transaction input in bytes.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub max_calldata_size: Option<usize>,
+    /// Senders whose transactions are rejected.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "BTreeSet::is_empty"))]
+    pub blocked_senders: BTreeSet<Address>,
+    /// Recipients to which transactions are rejected.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "BTreeSet::is_empty"))]
+    pub blocked_recipients: BTreeSet<Address>,
+    /// Minimum priority fee per gas, or gas price for legacy transactions, per transaction origin.
+    pub min_priority_fee: MinPriorityFeePerOrigin,
+    /// Which transactions may create contracts.
+    pub contract_creation: ContractCreationPolicy,
+}
+
+/// Minimum priority fee per gas for each transaction origin, in wei.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct MinPriorityFeePerOrigin {
+    /// Minimum for transactions submitted locally.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub local: Option<u64>,
+    /// Minimum for transactions received over the network.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub external: Option<u64>,
+    /// Minimum for private transactions.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub private: Option<u64>,
+}
+
+/// Which transactions may create contracts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum ContractCreationPolicy {
+    /// All transactions may create contracts.
+    #[default]
+    Allow,
+    /// Only locally submitted transactions may create contracts.
+    LocalOnly,
+    /// No transaction may create contracts.
+    Deny,
+}
+
+/// The properties of a transaction the firewall rules are evaluated against.
+#[derive(Debug, Clone, Copy)]
+pub struct FirewallTransaction<'a> {
+    /// Where the transaction originates from.
+    pub origin: TransactionOrigin,
+    /// The sender of the transaction.
+    pub sender: Address,
+    /// The recipient of the transaction, `None` for contract creations.
+    pub to: Option<Address>,
+    /// The input of the transaction.
+    pub input: &'a [u8],
+    /// The priority fee per gas, or the gas price for legacy transactions.
+    pub priority_fee: u128,
+}
+
+impl<'a> FirewallTransaction<'a> {
+    /// Creates a new instance for the given pool transaction.
+    pub fn new<T: PoolTransaction>(origin: TransactionOrigin, transaction: &'a T) -> Self {
+        Self {
+            origin,
+            sender: transaction.sender(),
+            to: transaction.to(),
+            input: transaction.input().as_ref(),
+            priority_fee: transaction
+                .max_priority_fee_per_gas()
+                .unwrap_or_else(|| transaction.max_fee_per_gas()),
+        }
+    }
+
+    /// Returns true if the transaction creates a contract.
+    pub const fn is_create(&self) -> bool {
+        self.to.is_none()
+    }
+}
+
+/// A rule of the [`PoolFirewall`].
+pub trait FirewallRule: fmt::Debug + Send + Sync {
+    /// The name of the rule, used to label rejections.
+    fn name(&self) -> &'static str;
+
+    /// Checks the transaction, returns the reason if it must not enter the pool.
+    fn check(&self, transaction: &FirewallTransaction<'_>) -> Result<(), String>;
+}
+
+/// Rejects transactions with an input larger than the given number of bytes.
+#[derive(Debug, Clone, Copy)]
+pub struct MaxCalldataSize(pub usize);
+
+impl FirewallRule for MaxCalldataSize {
+    fn name(&self) -> &'static str {
+        "max_calldata_size"
+    }
+
+    fn check(&self, transaction: &FirewallTransaction<'_>) -> Result<(), String> {
+        if transaction.input.len() > self.0 {
+            return Err(format!(
+                "input of {} bytes exceeds {} bytes",
+                transaction.input.len(),
+                self.0
+            ))
+        }
+        Ok(())
+    }
+}
+
+/// Rejects transactions from the given senders.
+#[derive(Debug, Clone)]
+pub struct BlockedSenders(pub BTreeSet<Address>);
+
+impl FirewallRule for BlockedSenders {
+    fn name(&self) -> &'static str {
+        "blocked_senders"
+    }
+
+    fn check(&self, transaction: &FirewallTransaction<'_>) -> Result<(), String> {
+        if self.0.contains(&transaction.sender) {
+            return Err(format!("sender {} is blocked", transaction.sender))
+        }
+        Ok(())
+    }
+}
+
+/// Rejects transactions to the given recipients.
+#[derive(Debug, Clone)]
+pub struct BlockedRecipients(pub BTreeSet<Address>);
+
+impl FirewallRule for BlockedRecipients {
+    fn name(&self) -> &'static str {
+        "blocked_recipients"
+    }
+
+    fn check(&self, transaction: &FirewallTransaction<'_>) -> Result<(), String> {
+        if let Some(to) = transaction.to.filter(|to| self.0.contains(to)) {
+            return Err(format!("recipient {to} is blocked"))
+        }
+        Ok(())
+    }
+}
+
+/// Rejects transactions with a priority fee below the minimum of their origin.
+#[derive(Debug, Clone, Copy)]
+pub struct MinPriorityFee(pub MinPriorityFeePerOrigin);
+
+impl FirewallRule for MinPriorityFee {
+    fn name(&self) -> &'static str {
+        "min_priority_fee"
+    }
+
+    fn check(&self, transaction: &FirewallTransaction<'_>) -> Result<(), String> {
+        let minimum = match transaction.origin {
+            TransactionOrigin::Local => self.0.local,
+            TransactionOrigin::External => self.0.external,
+            TransactionOrigin::Private => self.0.private,
+        };
+        if let Some(minimum) = minimum.filter(|min| transaction.priority_fee < *min as u128) {
+            return Err(format!(
+                "priority fee {} below minimum {minimum} for {:?} transactions",
+                transaction.priority_fee, transaction.origin
+            ))
+        }
+        Ok(())
+    }
+}
+
+/// Rejects contract creations not allowed by the [`ContractCreationPolicy`].
+#[derive(Debug, Clone, Copy)]
+pub struct ContractCreation(pub ContractCreationPolicy);
+
+impl FirewallRule for ContractCreation {
+    fn name(&self) -> &'static str {
+        "contract_creation"
+    }
+
+    fn check(&self, transaction: &FirewallTransaction<'_>) -> Result<(), String> {
+        let allowed = match self.0 {
+            ContractCreationPolicy::Allow => true,
+            ContractCreationPolicy::LocalOnly => transaction.origin.is_local(),
+            ContractCreationPolicy::Deny => false,
+        };
+        if transaction.is_create() && !allowed {
+            return Err(format!(
+                "contract creation not allowed for {:?} transactions",
+                transaction.origin
+            ))
+        }
+        Ok(())
+    }
+}
+
+/// Returns the built-in rules enabled by the given config.
+fn config_rules(config: &FirewallConfig) -> Vec<Arc<dyn FirewallRule>> {
+    let mut rules: Vec<Arc<dyn FirewallRule>> = Vec::new();
+    if let Some(max) = config.max_calldata_size {
+        rules.push(Arc::new(MaxCalldataSize(max)));
+    }
+    if !config.blocked_senders.is_empty() {
+        rules.push(Arc::new(BlockedSenders(config.blocked_senders.clone())));
+    }
+    if !config.blocked_recipients.is_empty() {
+        rules.push(Arc::new(BlockedRecipients(config.blocked_recipients.clone())));
+    }
+    if config.min_priority_fee != MinPriorityFeePerOrigin::default() {
+        rules.push(Arc::new(MinPriorityFee(config.min_priority_fee)));
+    }
+    if config.contract_creation != ContractCreationPolicy::Allow {
+        rules.push(Arc::new(ContractCreation(config.contract_creation)));
+    }
+    rules
+}
+
+/// A rule together with its rejection metrics.
+#[derive(Debug)]
+struct ActiveRule {
+    rule: Arc<dyn FirewallRule>,
+    metrics: FirewallRuleMetrics,
+}
+
+impl ActiveRule {
+    fn new(rule: Arc<dyn FirewallRule>) -> Self {
+        let metrics = FirewallRuleMetrics::new_with_labels(&[("rule", rule.name())]);
+        Self { rule, metrics }
+    }
+}
+
+#[derive(Debug, Default)]
+struct FirewallInner {
+    /// The config the built-in rules are derived from.
+    config: FirewallConfig,
+    /// The built-in rules.
+    config_rules: Vec<ActiveRule>,
+    /// Rules added with [`PoolFirewall::add_rule`].
+    custom_rules: Vec<ActiveRule>,
+}
+
+/// The rule chain evaluated before a transaction is validated.
+///
+/// This type is a cheap to clone handle, all clones share the same rules.
+#[derive(Debug, Clone, Default)]
+pub struct PoolFirewall {
+    inner: Arc<RwLock<FirewallInner>>,
+}
+
+impl PoolFirewall {
+    /// Creates a new firewall with the built-in rules of the given config.
+    pub fn new(config: FirewallConfig) -> Self {
+        let firewall = Self::default();
+        firewall.set_config(config);
+        firewall
+    }
+
+    /// Returns the config of the built-in rules.
+    pub fn config(&self) -> FirewallConfig {
+        self.inner.read().config.clone()
+    }
+
+    /// Replaces the built-in rules with the rules of the given config.
+    ///
+    /// Custom rules are kept.
+    pub fn set_config(&self, config: FirewallConfig) {
+        let rules = config_rules(&config).into_iter().map(ActiveRule::new).collect();
+        let mut inner = self.inner.write();
+        inner.config = config;
+        inner.config_rules = rules;
+    }
+
+    /// Adds a custom rule, evaluated after the built-in rules.
+    pub fn add_rule(&self, rule: impl FirewallRule + 'static) {
+        self.inner.write().custom_rules.push(ActiveRule::new(Arc::new(rule)));
+    }
+
+    /// Evaluates all rules, returns the first rejection.
+    pub fn check<T: PoolTransaction>(
+        &self,
+        origin: TransactionOrigin,
+        transaction: &T,
+    ) -> Result<(), FirewallRejection> {
+        let inner = self.inner.read();
+        if inner.config_rules.is_empty() && inner.custom_rules.is_empty() {
+            return Ok(())
+        }
+
+        let transaction = FirewallTransaction::new(origin, transaction);
+        for active in inner.config_rules.iter().chain(&inner.custom_rules) {
+            if let Err(reason) = active.rule.check(&transaction) {
+                active.metrics.rejected_transactions.increment(1);
+                return Err(FirewallRejection { rule: active.rule.name(), reason })
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A transaction rejected by a [`FirewallRule`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("rejected by firewall rule {rule}: {reason}")]
+pub struct FirewallRejection {
+    /// The name of the rule.
+    pub rule: &'static str,
+    /// Why the rule rejected the transaction.
+    pub reason: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockTransaction;
+    use alloy_primitives::{Bytes, TxKind};
+
+    #[test]
+    fn built_in_rules() {
+        let tx = MockTransaction::eip1559().with_input(Bytes::from(vec![0; 100]));
+        let firewall = PoolFirewall::default();
+        assert!(firewall.check(TransactionOrigin::External, &tx).is_ok());
+
+        firewall.set_config(FirewallConfig { max_calldata_size: Some(64), ..Default::default() });
+        let rejection = firewall.check(TransactionOrigin::External, &tx).unwrap_err();
+        assert_eq!(rejection.rule, "max_calldata_size");
+
+        firewall.set_config(FirewallConfig {
+            blocked_senders: BTreeSet::from([tx.sender()]),
+            ..Default::default()
+        });
+        let rejection = firewall.check(TransactionOrigin::Local, &tx).unwrap_err();
+        assert_eq!(rejection.rule, "blocked_senders");
+
+        firewall.set_config(FirewallConfig {
+            min_priority_fee: MinPriorityFeePerOrigin {
+                external: Some(u64::MAX),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        assert!(firewall.check(TransactionOrigin::Local, &tx).is_ok());
+        let rejection = firewall.check(TransactionOrigin::External, &tx).unwrap_err();
+        assert_eq!(rejection.rule, "min_priority_fee");
+    }
+
+    #[test]
+    fn contract_creation_policy() {
+        let mut tx = MockTransaction::eip1559();
+        if let MockTransaction::Eip1559 { to, .. } = &mut tx {
+            *to = TxKind::Create;
+        }
+        let firewall = PoolFirewall::new(FirewallConfig {
+            contract_creation: ContractCreationPolicy::LocalOnly,
+            ..Default::default()
+        });
+        assert!(firewall.check(TransactionOrigin::Local, &tx).is_ok());
+        let rejection = firewall.check(TransactionOrigin::External, &tx).unwrap_err();
+        assert_eq!(rejection.rule, "contract_creation");
+    }
+
+    #[test]
+    fn custom_rules_survive_config_update() {
+        #[derive(Debug)]
+        struct RejectAll;
+
+        impl FirewallRule for RejectAll {
+            fn name(&self) -> &'static str {
+                "reject_all"
+            }
+
+            fn check(&self, _transaction: &FirewallTransaction<'_>) -> Result<(), String> {
+                Err("rejected".to_string())
+            }
+        }
+
+        let tx = MockTransaction::eip1559();
+        let firewall = PoolFirewall::default();
+        firewall.add_rule(RejectAll);
+        firewall.set_config(FirewallConfig::default());
+        let rejection = firewall.check(TransactionOrigin::Local, &tx).unwrap_err();
+        assert_eq!(rejection.rule, "reject_all");
+    }
+}
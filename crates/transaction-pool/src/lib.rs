@@ -278,13 +278,18 @@
 pub use crate::{
     blobstore::{BlobStore, BlobStoreError},
     config::{
-        LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_PRICE_BUMP,
+        LocalTransactionConfig, PoolConfig, PoolQuotas, PriceBumpConfig, QuotaEvictionPolicy,
+        SubPoolLimit, DEFAULT_PRICE_BUMP, DEFAULT_REPLACEMENT_SUPPRESSION_WINDOW,
         DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS, MAX_NEW_PENDING_TXS_NOTIFICATIONS,
         REPLACE_BLOB_PRICE_BUMP, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
         TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT, TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
     },
     error::PoolResult,
-    ordering::{CoinbaseTipOrdering, Priority, TransactionOrdering},
+    firewall::{FirewallRule, PoolFirewall},
+    ordering::{
+        CoinbaseTipOrdering, PolicyOrdering, Priority, PrioritySenders, TransactionOrdering,
+        TransactionPriorityPolicy,
+    },
     pool::{
         blob_tx_priority, fee_delta, state::SubPool, AllTransactionsEvents, FullTransactionEvent,
         NewTransactionEvent, TransactionEvent, TransactionEvents, TransactionListenerKind,
@@ -312,6 +317,7 @@ use tokio::sync::mpsc::Receiver;
 use tracing::{instrument, trace};
 
 pub mod error;
+pub mod firewall;
 pub mod maintain;
 pub mod metrics;
 pub mod noop;
@@ -474,6 +480,10 @@ where
         self.pool.size()
     }
 
+    fn firewall(&self) -> Option<PoolFirewall> {
+        Some(self.pool.firewall().clone())
+    }
+
     fn block_info(&self) -> BlockInfo {
         self.pool.block_info()
     }
@@ -483,6 +493,7 @@ where
         origin: TransactionOrigin,
         transaction: Self::Transaction,
     ) -> PoolResult<TransactionEvents> {
+        self.pool.check_firewall(origin, &transaction)?;
         let (_, tx) = self.validate(origin, transaction).await;
         self.pool.add_transaction_and_subscribe(origin, tx)
     }
@@ -492,6 +503,7 @@ where
         origin: TransactionOrigin,
         transaction: Self::Transaction,
     ) -> PoolResult<TxHash> {
+        self.pool.check_firewall(origin, &transaction)?;
         let (_, tx) = self.validate(origin, transaction).await;
         let mut results = self.pool.add_transactions(origin, std::iter::once(tx));
         results.pop().expect("result length is the same as the input")
@@ -505,9 +517,31 @@ where
         if transactions.is_empty() {
             return Vec::new()
         }
-        let validated = self.validate_all(origin, transactions).await;
 
-        self.pool.add_transactions(origin, validated.into_iter().map(|(_, tx)| tx))
+        // transactions rejected by the firewall are not validated
+        let mut admitted = Vec::with_capacity(transactions.len());
+        let rejected = transactions
+            .into_iter()
+            .map(|tx| match self.pool.check_firewall(origin, &tx) {
+                Ok(()) => {
+                    admitted.push(tx);
+                    None
+                }
+                Err(err) => Some(err),
+            })
+            .collect::<Vec<_>>();
+
+        let validated = self.validate_all(origin, admitted).await;
+        let mut added =
+            self.pool.add_transactions(origin, validated.into_iter().map(|(_, tx)| tx)).into_iter();
+
+        rejected
+            .into_iter()
+            .map(|rejected| match rejected {
+                Some(err) => Err(err),
+                None => added.next().expect("result length is the same as the input"),
+            })
+            .collect()
     }
 
     fn transaction_event_listener(&self, tx_hash: TxHash) -> Option<TransactionEvents> {
@@ -592,6 +626,13 @@ where
         self.pool.pending_transactions_max(max)
     }
 
+    fn pending_transactions_sample(
+        &self,
+        max: usize,
+    ) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>> {
+        self.pool.pending_transactions_sample(max)
+    }
+
     fn queued_transactions(&self) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>> {
         self.pool.queued_transactions()
     }
@@ -640,6 +681,10 @@ where
         self.inner().on_propagated(txs)
     }
 
+    fn replacement_of(&self, tx_hash: &TxHash) -> Option<TxHash> {
+        self.inner().replacement_of(tx_hash)
+    }
+
     fn get_transactions_by_sender(
         &self,
         sender: Address,
@@ -693,6 +738,13 @@ where
         self.inner().get_pool_data().all().get(&transaction_id).map(|tx| tx.transaction.clone())
     }
 
+    fn get_transactions_with_predicate(
+        &self,
+        predicate: impl FnMut(&ValidPoolTransaction<Self::Transaction>) -> bool,
+    ) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>> {
+        self.pool.get_transactions_with_predicate(predicate)
+    }
+
     fn get_transactions_by_origin(
         &self,
         origin: TransactionOrigin,
@@ -8,7 +8,7 @@ use crate::{
     BlockInfo, PoolTransaction, PoolUpdateKind,
 };
 use alloy_consensus::{BlockHeader, Typed2718};
-use alloy_eips::BlockNumberOrTag;
+use alloy_eips::{eip7840::BlobParams, BlockNumberOrTag};
 use alloy_primitives::{Address, BlockHash, BlockNumber};
 use alloy_rlp::Encodable;
 use futures_util::{
@@ -137,10 +137,15 @@ pub async fn maintain_transaction_pool<N, Client, P, St, Tasks>(
 {
     let metrics = MaintainPoolMetrics::default();
     let MaintainPoolConfig { max_update_depth, max_reload_accounts, .. } = config;
+
+    // keeps track of the blob parameters active at the pool's tip
+    let mut blob_params_tracker = BlobParamsTracker::default();
+
     // ensure the pool points to latest state
     if let Ok(Some(latest)) = client.header_by_number_or_tag(BlockNumberOrTag::Latest) {
         let latest = SealedHeader::seal_slow(latest);
         let chain_spec = client.chain_spec();
+        blob_params_tracker.update(chain_spec.blob_params_at_timestamp(latest.timestamp()));
         let info = BlockInfo {
             block_gas_limit: latest.gas_limit(),
             last_seen_block_hash: latest.hash(),
@@ -410,6 +415,13 @@ pub async fn maintain_transaction_pool<N, Client, P, St, Tasks>(
                 };
                 pool.on_canonical_state_change(update);
 
+                // a reorg can cross a blob parameter fork boundary in either direction
+                if let Some(blob_params) = blob_params_tracker
+                    .update(chain_spec.blob_params_at_timestamp(new_tip.timestamp()))
+                {
+                    on_blob_params_change(&pool, blob_params, &metrics);
+                }
+
                 // all transactions that were mined in the old chain but not in the new chain need
                 // to be re-injected
                 //
@@ -459,6 +471,12 @@ pub async fn maintain_transaction_pool<N, Client, P, St, Tasks>(
                     };
                     pool.set_block_info(info);
 
+                    if let Some(blob_params) = blob_params_tracker
+                        .update(chain_spec.blob_params_at_timestamp(tip.timestamp()))
+                    {
+                        on_blob_params_change(&pool, blob_params, &metrics);
+                    }
+
                     // keep track of mined blob transactions
                     blob_store_tracker.add_new_chain_blocks(&blocks);
 
@@ -493,6 +511,12 @@ pub async fn maintain_transaction_pool<N, Client, P, St, Tasks>(
                 };
                 pool.on_canonical_state_change(update);
 
+                if let Some(blob_params) =
+                    blob_params_tracker.update(chain_spec.blob_params_at_timestamp(tip.timestamp()))
+                {
+                    on_blob_params_change(&pool, blob_params, &metrics);
+                }
+
                 // keep track of mined blob transactions
                 blob_store_tracker.add_new_chain_blocks(&blocks);
             }
@@ -519,6 +543,66 @@ impl FinalizedBlockTracker {
     }
 }
 
+/// Keeps track of the blob parameters that are active at the pool's tip.
+///
+/// Blob-parameter-only forks only change the blob limits and fee parameters, so the pool does not
+/// need to be flushed when one activates, it's enough to re-validate the blob transactions against
+/// the new limits.
+#[derive(Debug, Default)]
+struct BlobParamsTracker {
+    last_blob_params: Option<BlobParams>,
+}
+
+impl BlobParamsTracker {
+    /// Updates the tracked blob parameters and returns the new parameters if they changed.
+    ///
+    /// This returns `None` for the initial update, because the pool's validator already validates
+    /// against the parameters of the tip it was initialized with.
+    fn update(&mut self, blob_params: Option<BlobParams>) -> Option<BlobParams> {
+        let blob_params = blob_params?;
+        self.last_blob_params
+            .replace(blob_params)
+            .is_some_and(|last| last != blob_params)
+            .then_some(blob_params)
+    }
+}
+
+/// Re-validates all blob transactions in the pool against new blob parameters.
+///
+/// Transactions that carry more blobs than permitted per transaction can no longer be included and
+/// are removed from the pool together with their descendants and sidecars, which also stops them
+/// from being gossiped. Changes to the blob fee parameters are already reflected in the pending
+/// blob fee of the canonical update, which promotes or demotes blob transactions accordingly.
+fn on_blob_params_change<P>(pool: &P, blob_params: BlobParams, metrics: &MaintainPoolMetrics)
+where
+    P: TransactionPoolExt,
+{
+    let max_blobs_per_tx = blob_params.max_blobs_per_tx;
+    // blob transactions can be in any subpool, including the blob subpool
+    let exceeding = pool
+        .get_transactions_with_predicate(|tx| {
+            tx.blob_count().is_some_and(|count| count > max_blobs_per_tx)
+        })
+        .into_iter()
+        .map(|tx| *tx.hash())
+        .collect::<Vec<_>>();
+
+    debug!(
+        target: "txpool",
+        max_blobs_per_tx,
+        max_blob_count = blob_params.max_blob_count,
+        exceeding = exceeding.len(),
+        "blob parameters changed"
+    );
+
+    let removed = pool.remove_transactions_and_descendants(exceeding);
+    if removed.is_empty() {
+        return
+    }
+    metrics.inc_blob_params_removed_transactions(removed.len());
+    pool.delete_blobs(removed.iter().filter(|tx| tx.is_eip4844()).map(|tx| *tx.hash()).collect());
+}
+
 /// Keeps track of the pool's state, whether the accounts in the pool are in sync with the actual
 /// state.
 #[derive(Debug, PartialEq, Eq)]
@@ -716,11 +800,14 @@ pub async fn backup_local_transactions_task<P>(
 mod tests {
     use super::*;
     use crate::{
-        blobstore::InMemoryBlobStore, validate::EthTransactionValidatorBuilder,
-        CoinbaseTipOrdering, EthPooledTransaction, Pool, TransactionOrigin,
+        blobstore::InMemoryBlobStore,
+        test_utils::{MockTransaction, TestPoolBuilder},
+        validate::{EthTransactionValidatorBuilder, ValidTransaction},
+        BlockInfo, CoinbaseTipOrdering, EthPooledTransaction, Pool, TransactionOrigin,
+        TransactionValidationOutcome,
     };
-    use alloy_eips::eip2718::Decodable2718;
-    use alloy_primitives::{hex, U256};
+    use alloy_eips::{eip2718::Decodable2718, eip7594::BlobTransactionSidecarVariant};
+    use alloy_primitives::{hex, B256, U256};
     use reth_ethereum_primitives::{PooledTransactionVariant, TransactionSigned};
     use reth_fs_util as fs;
     use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
@@ -786,6 +873,47 @@ mod tests {
         temp_dir.close().unwrap();
     }
 
+    #[test]
+    fn blob_params_change_removes_blob_subpool_transactions() {
+        let pool = TestPoolBuilder::default();
+        // blob transactions that can't pay the pending blob fee are parked in the blob subpool
+        pool.set_block_info(BlockInfo { pending_blob_fee: Some(10_000_000), ..Default::default() });
+
+        let with_blobs = |count: usize| {
+            let mut tx = MockTransaction::eip4844();
+            if let MockTransaction::Eip4844 { blob_versioned_hashes, .. } = &mut tx {
+                *blob_versioned_hashes = vec![B256::random(); count];
+            }
+            tx
+        };
+        let exceeding = with_blobs(2);
+        let allowed = with_blobs(1);
+        for tx in [exceeding.clone(), allowed.clone()] {
+            pool.pool.add_transactions(
+                TransactionOrigin::External,
+                [TransactionValidationOutcome::Valid {
+                    balance: U256::MAX,
+                    state_nonce: 0,
+                    bytecode_hash: None,
+                    transaction: ValidTransaction::ValidWithSidecar {
+                        transaction: tx,
+                        sidecar: BlobTransactionSidecarVariant::Eip4844(Default::default()),
+                    },
+                    propagate: true,
+                    authorities: None,
+                }],
+            );
+        }
+        assert_eq!(pool.pool_size().blob, 2);
+
+        let blob_params = BlobParams { max_blobs_per_tx: 1, ..BlobParams::prague() };
+        on_blob_params_change(&*pool, blob_params, &MaintainPoolMetrics::default());
+
+        assert_eq!(pool.pool_size().blob, 1);
+        assert!(!pool.contains(exceeding.get_hash()));
+        assert!(pool.contains(allowed.get_hash()));
+    }
+
     #[test]
     fn test_update_with_higher_finalized_block() {
         let mut tracker = FinalizedBlockTracker::new(Some(10));
@@ -827,4 +955,24 @@ mod tests {
         assert_eq!(tracker.update(None), None);
         assert_eq!(tracker.last_finalized_block, None);
     }
+
+    #[test]
+    fn test_blob_params_tracker() {
+        let mut tracker = BlobParamsTracker::default();
+        let cancun = BlobParams::cancun();
+        let prague = BlobParams::prague();
+
+        // the initial parameters are not reported as a change
+        assert_eq!(tracker.update(Some(cancun)), None);
+        assert_eq!(tracker.update(Some(cancun)), None);
+        assert_eq!(tracker.update(None), None);
+        assert_eq!(tracker.last_blob_params, Some(cancun));
+
+        // fork boundary
+        assert_eq!(tracker.update(Some(prague)), Some(prague));
+        assert_eq!(tracker.update(Some(prague)), None);
+
+        // reorg back across the fork boundary
+        assert_eq!(tracker.update(Some(cancun)), Some(cancun));
+    }
 }
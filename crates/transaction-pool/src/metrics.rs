@@ -60,6 +60,8 @@ pub struct TxPoolMetrics {
     pub(crate) blob_transactions_evicted: Counter,
     /// Counter for the number of queued transactions evicted
     pub(crate) queued_transactions_evicted: Counter,
+    /// Counter for the number of transactions evicted to enforce the per-origin quotas
+    pub(crate) origin_quota_transactions_evicted: Counter,
 }
 
 /// Transaction pool blobstore metrics
@@ -76,6 +78,16 @@ pub struct BlobStoreMetrics {
     pub(crate) blobstore_entries: Gauge,
 }
 
+/// Transaction pool replacement metrics
+#[derive(Metrics)]
+#[metrics(scope = "transaction_pool")]
+pub struct TxReplacementMetrics {
+    /// Number of local transactions that were replaced by another transaction
+    pub(crate) replaced_local_transactions: Counter,
+    /// Number of replaced transactions currently tracked for gossip suppression
+    pub(crate) tracked_replaced_transactions: Gauge,
+}
+
 /// Transaction pool maintenance metrics
 #[derive(Metrics)]
 #[metrics(scope = "transaction_pool")]
@@ -91,6 +103,9 @@ pub struct MaintainPoolMetrics {
     pub(crate) reinserted_transactions: Counter,
     /// Counter for the number of finalized blob transactions that have been removed from tracking.
     pub(crate) deleted_tracked_finalized_blobs: Counter,
+    /// Counter for the number of transactions removed because they exceeded the blob limits of
+    /// a blob parameter fork.
+    pub(crate) blob_params_removed_transactions: Counter,
 }
 
 impl MaintainPoolMetrics {
@@ -109,6 +124,11 @@ impl MaintainPoolMetrics {
         self.deleted_tracked_finalized_blobs.increment(count as u64);
     }
 
+    #[inline]
+    pub(crate) fn inc_blob_params_removed_transactions(&self, count: usize) {
+        self.blob_params_removed_transactions.increment(count as u64);
+    }
+
     #[inline]
     pub(crate) fn inc_drift(&self) {
         self.drift_count.increment(1);
@@ -131,6 +151,16 @@ pub struct AllTransactionsMetrics {
     pub(crate) blob_base_fee: Gauge,
     /// The current base fee
     pub(crate) base_fee: Gauge,
+    /// Number of local transactions in the pool
+    pub(crate) local_transactions: Gauge,
+    /// Number of external transactions in the pool
+    pub(crate) external_transactions: Gauge,
+    /// Number of private transactions in the pool
+    pub(crate) private_transactions: Gauge,
+    /// Number of transactions rejected because their sender reached its quota
+    pub(crate) sender_quota_rejected: Counter,
+    /// Number of transactions rejected because their origin reached its quota
+    pub(crate) origin_quota_rejected: Counter,
 }
 
 /// Transaction pool validation metrics
@@ -140,3 +170,11 @@ pub struct TxPoolValidationMetrics {
     /// How long to successfully validate a blob
     pub(crate) blob_validation_duration: Histogram,
 }
+
+/// Transaction pool firewall metrics, labeled by rule
+#[derive(Metrics)]
+#[metrics(scope = "transaction_pool.firewall")]
+pub struct FirewallRuleMetrics {
+    /// Number of transactions rejected by the rule
+    pub(crate) rejected_transactions: Counter,
+}
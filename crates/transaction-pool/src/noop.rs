@@ -186,6 +186,13 @@ impl<T: EthPoolTransaction> TransactionPool for NoopTransactionPool<T> {
         vec![]
     }
 
+    fn pending_transactions_sample(
+        &self,
+        _max: usize,
+    ) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>> {
+        vec![]
+    }
+
     fn queued_transactions(&self) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>> {
         vec![]
     }
@@ -231,6 +238,10 @@ impl<T: EthPoolTransaction> TransactionPool for NoopTransactionPool<T> {
 
     fn on_propagated(&self, _txs: PropagatedTransactions) {}
 
+    fn replacement_of(&self, _tx_hash: &TxHash) -> Option<TxHash> {
+        None
+    }
+
     fn get_transactions_by_sender(
         &self,
         _sender: Address,
@@ -282,6 +293,13 @@ impl<T: EthPoolTransaction> TransactionPool for NoopTransactionPool<T> {
         None
     }
 
+    fn get_transactions_with_predicate(
+        &self,
+        _predicate: impl FnMut(&ValidPoolTransaction<Self::Transaction>) -> bool,
+    ) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>> {
+        vec![]
+    }
+
     fn get_transactions_by_origin(
         &self,
         _origin: TransactionOrigin,
@@ -4,8 +4,8 @@
 // Higher tips = higher priority. It's like tipping for faster service!
 
 use crate::traits::PoolTransaction;
-use alloy_primitives::U256;
-use std::{cmp::Ordering, fmt::Debug, marker::PhantomData};
+use alloy_primitives::{Address, U256};
+use std::{cmp::Ordering, collections::HashSet, fmt::Debug, marker::PhantomData};
 
 /// Priority of the transaction that can be missing.
 ///
@@ -103,6 +103,92 @@ impl<T> Clone for CoinbaseTipOrdering<T> {
     }
 }
 
+/// A policy that ranks transactions ahead of the fee based priority of a [`TransactionOrdering`].
+///
+/// This is the hook for custom ordering rules, such as giving precedence to certain senders, that
+/// should still fall back to the default ordering among equally ranked transactions. It is used by
+/// [`PolicyOrdering`].
+pub trait TransactionPriorityPolicy<T: PoolTransaction>: Debug + Send + Sync + 'static {
+    /// Rank of a transaction.
+    ///
+    /// Higher is better.
+    type Rank: Ord + Clone + Default + Debug + Send + Sync;
+
+    /// Returns the rank of the given transaction.
+    fn rank(&self, transaction: &T) -> Self::Rank;
+}
+
+/// Ordering that ranks transactions by a [`TransactionPriorityPolicy`] first and by an inner
+/// [`TransactionOrdering`] second.
+///
+/// Transactions the inner ordering assigns no priority to are still ranked lowest, regardless of
+/// the policy.
+#[derive(Debug, Clone, Default)]
+pub struct PolicyOrdering<P, O> {
+    /// The policy that is applied first.
+    policy: P,
+    /// The ordering used among transactions of the same rank.
+    inner: O,
+}
+
+impl<P, O> PolicyOrdering<P, O> {
+    /// Creates a new ordering that applies the given policy on top of the inner ordering.
+    pub const fn new(policy: P, inner: O) -> Self {
+        Self { policy, inner }
+    }
+
+    /// Returns the policy of this ordering.
+    pub const fn policy(&self) -> &P {
+        &self.policy
+    }
+}
+
+impl<P, O> TransactionOrdering for PolicyOrdering<P, O>
+where
+    O: TransactionOrdering,
+    P: TransactionPriorityPolicy<O::Transaction>,
+{
+    type PriorityValue = (P::Rank, O::PriorityValue);
+    type Transaction = O::Transaction;
+
+    fn priority(
+        &self,
+        transaction: &Self::Transaction,
+        base_fee: u64,
+    ) -> Priority<Self::PriorityValue> {
+        match self.inner.priority(transaction, base_fee) {
+            Priority::Value(value) => Priority::Value((self.policy.rank(transaction), value)),
+            Priority::None => Priority::None,
+        }
+    }
+}
+
+/// A [`TransactionPriorityPolicy`] that ranks transactions of the configured senders first.
+#[derive(Debug, Clone, Default)]
+pub struct PrioritySenders {
+    senders: HashSet<Address>,
+}
+
+impl PrioritySenders {
+    /// Creates a new policy that prioritizes the given senders.
+    pub fn new(senders: impl IntoIterator<Item = Address>) -> Self {
+        Self { senders: senders.into_iter().collect() }
+    }
+
+    /// Returns `true` if the sender is prioritized.
+    pub fn contains(&self, sender: &Address) -> bool {
+        self.senders.contains(sender)
+    }
+}
+
+impl<T: PoolTransaction> TransactionPriorityPolicy<T> for PrioritySenders {
+    type Rank = bool;
+
+    fn rank(&self, transaction: &T) -> Self::Rank {
+        self.contains(&transaction.sender())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -118,4 +204,27 @@ mod tests {
         assert!(p2 > p3); // Value(1) > None
         assert_eq!(p3, Priority::None);
     }
+
+    #[test]
+    fn test_policy_ordering() {
+        use crate::test_utils::{MockOrdering, MockTransaction};
+
+        let prioritized = Address::with_last_byte(1);
+        let ordering =
+            PolicyOrdering::new(PrioritySenders::new([prioritized]), MockOrdering::default());
+
+        let cheap = MockTransaction::eip1559()
+            .with_sender(prioritized)
+            .with_max_fee(100)
+            .with_priority_fee(1);
+        let expensive = MockTransaction::eip1559()
+            .with_sender(Address::with_last_byte(2))
+            .with_max_fee(100)
+            .with_priority_fee(10);
+
+        assert!(ordering.priority(&cheap, 0) > ordering.priority(&expensive, 0));
+
+        // transactions below the base fee have no priority, regardless of the policy
+        assert_eq!(ordering.priority(&cheap, 101), Priority::None);
+    }
 }
@@ -76,6 +76,7 @@
 use crate::{
     blobstore::BlobStore,
     error::{PoolError, PoolErrorKind, PoolResult},
+    firewall::PoolFirewall,
     identifier::{SenderId, SenderIdentifiers, TransactionId},
     metrics::BlobStoreMetrics,
     pool::{
@@ -83,6 +84,7 @@ use crate::{
             BlobTransactionSidecarListener, PendingTransactionHashListener, PoolEventBroadcast,
             TransactionListener,
         },
+        replaced::RecentlyReplaced,
         state::SubPool,
         txpool::{SenderInfo, TxPool},
         update::UpdateOutcome,
@@ -102,7 +104,7 @@ use parking_lot::{Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard};
 use reth_eth_wire_types::HandleMempoolData;
 use reth_execution_types::ChangedAccount;
 
-use alloy_eips::{eip7594::BlobTransactionSidecarVariant, Typed2718};
+use alloy_eips::{eip2718::Encodable2718, eip7594::BlobTransactionSidecarVariant, Typed2718};
 use reth_primitives_traits::Recovered;
 use rustc_hash::FxHashMap;
 use std::{collections::HashSet, fmt, sync::Arc, time::Instant};
@@ -122,6 +124,7 @@ mod blob;
 mod listener;
 mod parked;
 pub(crate) mod pending;
+mod replaced;
 pub(crate) mod size;
 pub(crate) mod state;
 pub mod txpool;
@@ -159,6 +162,10 @@ where
     blob_transaction_sidecar_listener: Mutex<Vec<BlobTransactionSidecarListener>>,
     /// Metrics for the blob store
     blob_store_metrics: BlobStoreMetrics,
+    /// Local transactions that were recently replaced.
+    recently_replaced: Mutex<RecentlyReplaced>,
+    /// Admission rules evaluated before a transaction is inserted.
+    firewall: PoolFirewall,
 }
 
 // === impl PoolInner ===
@@ -179,6 +186,10 @@ where
             pending_transaction_listener: Default::default(),
             transaction_listener: Default::default(),
             blob_transaction_sidecar_listener: Default::default(),
+            recently_replaced: Mutex::new(RecentlyReplaced::new(
+                config.replacement_suppression_window,
+            )),
+            firewall: PoolFirewall::new(config.firewall.clone()),
             config,
             blob_store,
             blob_store_metrics: Default::default(),
@@ -240,6 +251,23 @@ where
         &self.config
     }
 
+    /// Returns the admission firewall of the pool.
+    pub const fn firewall(&self) -> &PoolFirewall {
+        &self.firewall
+    }
+
+    /// Checks the transaction against the admission firewall, before it is validated.
+    pub fn check_firewall(
+        &self,
+        origin: TransactionOrigin,
+        transaction: &T::Transaction,
+    ) -> PoolResult<()> {
+        self.firewall.check(origin, transaction).map_err(|rejection| {
+            trace!(target: "txpool", %rejection, "transaction rejected by firewall");
+            PoolError::new(*transaction.hash(), PoolErrorKind::FirewallRejected(rejection))
+        })
+    }
+
     /// Get the validator reference.
     pub const fn validator(&self) -> &V {
         &self.validator
@@ -350,6 +378,10 @@ where
     }
 
     /// Returns pooled transactions for the given transaction hashes.
+    ///
+    /// The size limit is enforced against the network encoding of the pooled transactions, which
+    /// includes the blob sidecars of EIP-4844 transactions. A transaction that would exceed the
+    /// limit is only returned if it's the first one.
     pub fn get_pooled_transaction_elements(
         &self,
         tx_hashes: Vec<TxHash>,
@@ -362,17 +394,17 @@ where
         let mut elements = Vec::with_capacity(transactions.len());
         let mut size = 0;
         for transaction in transactions {
-            let encoded_len = transaction.encoded_length();
             let Some(pooled) = self.to_pooled_transaction(transaction) else {
                 continue;
             };
 
-            size += encoded_len;
-            elements.push(pooled.into_inner());
-
-            if limit.exceeds(size) {
+            let encoded_len = pooled.network_len();
+            if !elements.is_empty() && limit.exceeds(size + encoded_len) {
                 break
             }
+
+            size += encoded_len;
+            elements.push(pooled.into_inner());
         }
 
         elements
@@ -500,6 +532,11 @@ where
                     self.delete_blob(replaced);
                 }
 
+                if let Some(replaced) = added.replaced().filter(|tx| tx.origin.is_local()) {
+                    // track the replaced local transaction so that its gossip can be suppressed
+                    self.recently_replaced.lock().insert(*replaced.hash(), hash);
+                }
+
                 // Notify about new pending transactions
                 if let Some(pending) = added.as_pending() {
                     self.on_new_pending_transaction(pending);
@@ -738,6 +775,14 @@ where
         self.get_pool_data().pending_transactions_iter().take(max).collect()
     }
 
+    /// Returns at most `max` transactions, evenly spread over the pending pool.
+    pub fn pending_transactions_sample(
+        &self,
+        max: usize,
+    ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        self.get_pool_data().pending_transactions_sample(max)
+    }
+
     /// Returns all transactions from the pending sub-pool
     pub fn pending_transactions(&self) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
         self.get_pool_data().pending_transactions()
@@ -897,6 +942,14 @@ where
         self.get_pool_data().all().get(transaction_id).map(|tx| tx.transaction.clone())
     }
 
+    /// Returns all transactions of every subpool filtered by predicate
+    pub fn get_transactions_with_predicate(
+        &self,
+        mut predicate: impl FnMut(&ValidPoolTransaction<T::Transaction>) -> bool,
+    ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        self.get_pool_data().all().transactions_iter().filter(|tx| predicate(tx)).cloned().collect()
+    }
+
     /// Returns all transactions that where submitted with the given [`TransactionOrigin`]
     pub fn get_transactions_by_origin(
         &self,
@@ -938,6 +991,12 @@ where
         txs.0.into_iter().for_each(|(hash, peers)| listener.propagated(&hash, peers))
     }
 
+    /// Returns the hash of the transaction that replaced the given local transaction, if it was
+    /// replaced within the configured suppression window.
+    pub fn replacement_of(&self, tx_hash: &TxHash) -> Option<TxHash> {
+        self.recently_replaced.lock().replacement_of(tx_hash)
+    }
+
     /// Number of transactions in the entire pool
     pub fn len(&self) -> usize {
         self.get_pool_data().len()
@@ -1224,9 +1283,13 @@ mod tests {
         identifier::SenderId,
         test_utils::{MockTransaction, TestPoolBuilder},
         validate::ValidTransaction,
-        BlockInfo, PoolConfig, SubPoolLimit, TransactionOrigin, TransactionValidationOutcome, U256,
+        BlockInfo, GetPooledTransactionLimit, PoolConfig, SubPoolLimit, TransactionOrigin,
+        TransactionValidationOutcome, U256,
+    };
+    use alloy_eips::{
+        eip2718::Encodable2718, eip4844::BlobTransactionSidecar,
+        eip7594::BlobTransactionSidecarVariant,
     };
-    use alloy_eips::{eip4844::BlobTransactionSidecar, eip7594::BlobTransactionSidecarVariant};
     use alloy_primitives::Address;
     use std::{fs, path::PathBuf};
 
@@ -1315,6 +1378,46 @@ mod tests {
         assert_eq!(*test_pool.blob_store(), blob_store);
     }
 
+    #[test]
+    fn test_get_pooled_transaction_elements_size_limit() {
+        let test_pool = &TestPoolBuilder::default().pool;
+
+        let transactions = (0..3).map(|_| MockTransaction::eip1559()).collect::<Vec<_>>();
+        let hashes = transactions.iter().map(|tx| *tx.get_hash()).collect::<Vec<_>>();
+        for transaction in transactions {
+            test_pool.add_transactions(
+                TransactionOrigin::External,
+                [TransactionValidationOutcome::Valid {
+                    balance: U256::from(1_000),
+                    state_nonce: 0,
+                    bytecode_hash: None,
+                    transaction: ValidTransaction::Valid(transaction),
+                    propagate: true,
+                    authorities: None,
+                }],
+            );
+        }
+
+        let all = test_pool
+            .get_pooled_transaction_elements(hashes.clone(), GetPooledTransactionLimit::None);
+        assert_eq!(all.len(), 3);
+
+        // the first transaction is always returned
+        let first = test_pool.get_pooled_transaction_elements(
+            hashes.clone(),
+            GetPooledTransactionLimit::ResponseSizeSoftLimit(0),
+        );
+        assert_eq!(first.len(), 1);
+
+        // the response never exceeds the limit once it holds a transaction
+        let limit = all[0].network_len() + all[1].network_len() + 1;
+        let limited = test_pool.get_pooled_transaction_elements(
+            hashes,
+            GetPooledTransactionLimit::ResponseSizeSoftLimit(limit),
+        );
+        assert_eq!(limited.len(), 2);
+    }
+
     #[test]
     fn test_auths_stored_in_identifiers() {
         // Create a test pool with default configuration.
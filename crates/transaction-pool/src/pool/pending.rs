@@ -162,6 +162,15 @@ impl<T: TransactionOrdering> PendingPool<T> {
         self.by_id.values().map(|tx| tx.transaction.clone())
     }
 
+    /// Returns at most `max` transactions, evenly spread over all transactions of the pool.
+    pub(crate) fn sample(
+        &self,
+        max: usize,
+    ) -> impl Iterator<Item = Arc<ValidPoolTransaction<T::Transaction>>> + '_ {
+        let step = self.by_id.len().div_ceil(max.max(1)).max(1);
+        self.by_id.values().step_by(step).take(max).map(|tx| tx.transaction.clone())
+    }
+
     /// Updates the pool with the new blob fee. Removes
     /// from the subpool all transactions and their dependents that no longer satisfy the given
     /// blob fee (`tx.max_blob_fee < blob_fee`).
@@ -648,6 +657,25 @@ mod tests {
         assert!(pool.is_empty());
     }
 
+    #[test]
+    fn sample_spreads_over_senders() {
+        let mut f = MockTransactionFactory::default();
+        let mut pool = PendingPool::new(MockOrdering::default());
+        let txs = (0..10)
+            .map(|_| {
+                let tx = f.validated_arc(MockTransaction::eip1559());
+                pool.add_transaction(tx.clone(), 0);
+                tx
+            })
+            .collect::<Vec<_>>();
+
+        let sample = pool.sample(3).map(|tx| *tx.id()).collect::<Vec<_>>();
+        assert_eq!(sample, vec![*txs[0].id(), *txs[4].id(), *txs[8].id()]);
+
+        assert_eq!(pool.sample(20).count(), 10);
+        assert_eq!(pool.sample(0).count(), 0);
+    }
+
     #[test]
     fn test_enforce_basefee_descendant() {
         let mut f = MockTransactionFactory::default();
@@ -1036,4 +1064,25 @@ mod tests {
         assert!(pool.get_txs_by_sender(sender_b).is_empty());
         assert!(pool.get_txs_by_sender(sender_c).is_empty());
     }
+
+    #[test]
+    fn best_with_policy_ordering() {
+        let mut f = MockTransactionFactory::default();
+        let prioritized = address!("0x000000000000000000000000000000000000000a");
+        let ordering = crate::PolicyOrdering::new(
+            crate::PrioritySenders::new([prioritized]),
+            MockOrdering::default(),
+        );
+        let mut pool = PendingPool::new(ordering);
+
+        let expensive = f.validated_arc(MockTransaction::eip1559().with_priority_fee(10));
+        let cheap = f.validated_arc(
+            MockTransaction::eip1559().with_sender(prioritized).with_priority_fee(1),
+        );
+        pool.add_transaction(expensive.clone(), 0);
+        pool.add_transaction(cheap.clone(), 0);
+
+        let best = pool.best().map(|tx| *tx.hash()).collect::<Vec<_>>();
+        assert_eq!(best, vec![*cheap.hash(), *expensive.hash()]);
+    }
 }
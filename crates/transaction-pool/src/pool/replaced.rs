@@ -0,0 +1,137 @@
+//! Tracking of recently replaced local transactions.
+
+use crate::metrics::TxReplacementMetrics;
+use alloy_primitives::TxHash;
+use rustc_hash::FxHashMap;
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+/// Upper bound on the number of replaced transactions that are tracked at the same time.
+const MAX_TRACKED_REPLACEMENTS: usize = 4096;
+
+/// Keeps track of local transactions that were replaced by a fee bump within a short window.
+///
+/// The replaced version of a transaction is no longer in the pool, but peers may still announce
+/// it for a while. This is used to suppress gossip of the replaced version and to point peers to
+/// the replacement instead.
+#[derive(Debug)]
+pub(crate) struct RecentlyReplaced {
+    /// How long a replacement is tracked.
+    window: Duration,
+    /// Replaced transaction hash to the hash of the transaction that replaced it and the time of
+    /// the replacement.
+    replaced_by: FxHashMap<TxHash, (TxHash, Instant)>,
+    /// Replaced transaction hashes in insertion order, used for expiry.
+    order: VecDeque<TxHash>,
+    /// Replacement metrics.
+    metrics: TxReplacementMetrics,
+}
+
+impl RecentlyReplaced {
+    /// Creates a new tracker with the given suppression window.
+    pub(crate) fn new(window: Duration) -> Self {
+        Self {
+            window,
+            replaced_by: Default::default(),
+            order: Default::default(),
+            metrics: Default::default(),
+        }
+    }
+
+    /// Records that `replaced` was replaced by `replaced_by`.
+    pub(crate) fn insert(&mut self, replaced: TxHash, replaced_by: TxHash) {
+        if self.window.is_zero() {
+            return
+        }
+
+        let now = Instant::now();
+        self.evict_expired(now);
+
+        if self.replaced_by.insert(replaced, (replaced_by, now)).is_none() {
+            self.order.push_back(replaced);
+        }
+        while self.order.len() > MAX_TRACKED_REPLACEMENTS {
+            if let Some(hash) = self.order.pop_front() {
+                self.replaced_by.remove(&hash);
+            }
+        }
+
+        self.metrics.replaced_local_transactions.increment(1);
+        self.metrics.tracked_replaced_transactions.set(self.replaced_by.len() as f64);
+    }
+
+    /// Returns the hash of the latest replacement of the given transaction, if it was replaced
+    /// within the window.
+    ///
+    /// If the replacement was itself replaced, this follows the chain to the latest version.
+    pub(crate) fn replacement_of(&self, hash: &TxHash) -> Option<TxHash> {
+        let now = Instant::now();
+        let (mut current, replaced_at) = *self.replaced_by.get(hash)?;
+        if now.duration_since(replaced_at) >= self.window {
+            return None
+        }
+        // bounded by the number of tracked entries, guards against cycles
+        for _ in 0..self.replaced_by.len() {
+            match self.replaced_by.get(&current) {
+                Some((next, _)) => current = *next,
+                None => break,
+            }
+        }
+        Some(current)
+    }
+
+    /// Removes all entries that are older than the window.
+    fn evict_expired(&mut self, now: Instant) {
+        while let Some(hash) = self.order.front().copied() {
+            let expired = self
+                .replaced_by
+                .get(&hash)
+                .is_none_or(|(_, replaced_at)| now.duration_since(*replaced_at) >= self.window);
+            if !expired {
+                break
+            }
+            self.replaced_by.remove(&hash);
+            self.order.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tracks_replacement_chain() {
+        let mut replaced = RecentlyReplaced::new(Duration::from_secs(60));
+        let (a, b, c) =
+            (TxHash::with_last_byte(1), TxHash::with_last_byte(2), TxHash::with_last_byte(3));
+
+        replaced.insert(a, b);
+        assert_eq!(replaced.replacement_of(&a), Some(b));
+        assert_eq!(replaced.replacement_of(&b), None);
+
+        replaced.insert(b, c);
+        assert_eq!(replaced.replacement_of(&a), Some(c));
+        assert_eq!(replaced.replacement_of(&b), Some(c));
+    }
+
+    #[test]
+    fn disabled_with_zero_window() {
+        let mut replaced = RecentlyReplaced::new(Duration::ZERO);
+        replaced.insert(TxHash::with_last_byte(1), TxHash::with_last_byte(2));
+        assert_eq!(replaced.replacement_of(&TxHash::with_last_byte(1)), None);
+    }
+
+    #[test]
+    fn evicts_expired() {
+        let mut replaced = RecentlyReplaced::new(Duration::from_millis(10));
+        replaced.insert(TxHash::with_last_byte(1), TxHash::with_last_byte(2));
+        std::thread::sleep(Duration::from_millis(20));
+        assert_eq!(replaced.replacement_of(&TxHash::with_last_byte(1)), None);
+
+        replaced.insert(TxHash::with_last_byte(3), TxHash::with_last_byte(4));
+        assert_eq!(replaced.replaced_by.len(), 1);
+    }
+}
@@ -1,7 +1,10 @@
 //! The internal transaction pool implementation.
 
 use crate::{
-    config::{LocalTransactionConfig, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER},
+    config::{
+        LocalTransactionConfig, PoolQuotas, QuotaEvictionPolicy,
+        TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
+    },
     error::{
         Eip4844PoolTransactionError, Eip7702PoolTransactionError, InvalidPoolTransactionError,
         PoolError, PoolErrorKind,
@@ -19,7 +22,7 @@ use crate::{
     },
     traits::{BestTransactionsAttributes, BlockInfo, PoolSize},
     PoolConfig, PoolResult, PoolTransaction, PoolUpdateKind, PriceBumpConfig, TransactionOrdering,
-    ValidPoolTransaction, U256,
+    TransactionOrigin, ValidPoolTransaction, U256,
 };
 use alloy_consensus::constants::{
     EIP1559_TX_TYPE_ID, EIP2930_TX_TYPE_ID, EIP4844_TX_TYPE_ID, EIP7702_TX_TYPE_ID, KECCAK_EMPTY,
@@ -434,6 +437,14 @@ impl<T: TransactionOrdering> TxPool<T> {
         self.pending_pool.all()
     }
 
+    /// Returns at most `max` transactions, evenly spread over the pending sub-pool.
+    pub(crate) fn pending_transactions_sample(
+        &self,
+        max: usize,
+    ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        self.pending_pool.sample(max).collect()
+    }
+
     /// Returns all pending transactions filtered by predicate
     pub(crate) fn pending_transactions_with_predicate(
         &self,
@@ -710,6 +721,12 @@ impl<T: TransactionOrdering> TxPool<T> {
                             PoolErrorKind::SpammerExceededCapacity(transaction.sender()),
                         ))
                     }
+                    InsertErr::ExceededOriginTransactionsCapacity { transaction } => {
+                        Err(PoolError::new(
+                            *transaction.hash(),
+                            PoolErrorKind::OriginExceededCapacity(transaction.origin),
+                        ))
+                    }
                     InsertErr::TxGasLimitMoreThanAvailableBlockGas {
                         transaction,
                         block_gas_limit,
@@ -1128,6 +1145,24 @@ impl<T: TransactionOrdering> TxPool<T> {
             ]
         );
 
+        // evict the cheapest transactions of origins that exceed their quota
+        for (origin, quota) in self.all_transactions.origins_over_quota() {
+            for id in self.all_transactions.origin_transactions_by_fee(origin) {
+                if self.all_transactions.origin_count(origin) <= quota {
+                    break
+                }
+                trace!(target: "txpool", ?origin, quota, ?id, "evicting transaction over origin quota");
+                // descendants of previously evicted transactions are already removed
+                let Some(tx) = self.remove_transaction(&id) else { continue };
+                let removed_before = removed.len();
+                removed.push(tx);
+                self.remove_descendants(&id, &mut removed);
+                self.metrics
+                    .origin_quota_transactions_evicted
+                    .increment((removed.len() - removed_before) as u64);
+            }
+        }
+
         removed
     }
 
@@ -1221,6 +1256,10 @@ pub(crate) struct AllTransactions<T: PoolTransaction> {
     txs: BTreeMap<TransactionId, PoolInternalTransaction<T>>,
     /// Tracks the number of transactions by sender that are currently in the pool.
     tx_counter: FxHashMap<SenderId, usize>,
+    /// Tracks the number of transactions by origin that are currently in the pool.
+    origin_counter: FxHashMap<TransactionOrigin, usize>,
+    /// Configured per-sender and per-origin quotas.
+    quotas: PoolQuotas,
     /// The current block number the pool keeps track of.
     last_seen_block_number: u64,
     /// The current block hash the pool keeps track of.
@@ -1244,6 +1283,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
             max_account_slots: config.max_account_slots,
             price_bumps: config.price_bumps,
             local_transactions_config: config.local_transactions_config.clone(),
+            quotas: config.quotas,
             minimal_protocol_basefee: config.minimal_protocol_basefee,
             block_gas_limit: config.gas_limit,
             ..Default::default()
@@ -1273,15 +1313,17 @@ impl<T: PoolTransaction> AllTransactions<T> {
         self.txs.get(id)
     }
 
-    /// Increments the transaction counter for the sender
-    pub(crate) fn tx_inc(&mut self, sender: SenderId) {
+    /// Increments the transaction counters for the sender and origin
+    pub(crate) fn tx_inc(&mut self, sender: SenderId, origin: TransactionOrigin) {
         let count = self.tx_counter.entry(sender).or_default();
         *count += 1;
         self.metrics.all_transactions_by_all_senders.increment(1.0);
+        self.origin_inc(origin);
     }
 
-    /// Decrements the transaction counter for the sender
-    pub(crate) fn tx_decr(&mut self, sender: SenderId) {
+    /// Decrements the transaction counters for the sender and origin
+    pub(crate) fn tx_decr(&mut self, sender: SenderId, origin: TransactionOrigin) {
+        self.origin_decr(origin);
         if let hash_map::Entry::Occupied(mut entry) = self.tx_counter.entry(sender) {
             let count = entry.get_mut();
             if *count == 1 {
@@ -1294,6 +1336,64 @@ impl<T: PoolTransaction> AllTransactions<T> {
         }
     }
 
+    /// Increments the transaction counter for the origin
+    fn origin_inc(&mut self, origin: TransactionOrigin) {
+        *self.origin_counter.entry(origin).or_default() += 1;
+        self.origin_gauge(origin).increment(1.0);
+    }
+
+    /// Decrements the transaction counter for the origin
+    fn origin_decr(&mut self, origin: TransactionOrigin) {
+        if let Some(count) = self.origin_counter.get_mut(&origin) {
+            *count = count.saturating_sub(1);
+            self.origin_gauge(origin).decrement(1.0);
+        }
+    }
+
+    /// Returns the per-origin gauge of the given origin
+    const fn origin_gauge(&self, origin: TransactionOrigin) -> &reth_metrics::metrics::Gauge {
+        match origin {
+            TransactionOrigin::Local => &self.metrics.local_transactions,
+            TransactionOrigin::External => &self.metrics.external_transactions,
+            TransactionOrigin::Private => &self.metrics.private_transactions,
+        }
+    }
+
+    /// Returns the number of transactions of the given origin in the pool
+    pub(crate) fn origin_count(&self, origin: TransactionOrigin) -> usize {
+        self.origin_counter.get(&origin).copied().unwrap_or_default()
+    }
+
+    /// Returns the origins that currently exceed their configured quota, if the quotas are
+    /// enforced via eviction.
+    pub(crate) fn origins_over_quota(&self) -> Vec<(TransactionOrigin, usize)> {
+        if self.quotas.eviction != QuotaEvictionPolicy::EvictCheapest {
+            return Vec::new()
+        }
+        [TransactionOrigin::Local, TransactionOrigin::External, TransactionOrigin::Private]
+            .into_iter()
+            .filter_map(|origin| {
+                let quota = self.quotas.origin_quota(origin)?;
+                (self.origin_count(origin) > quota).then_some((origin, quota))
+            })
+            .collect()
+    }
+
+    /// Returns the ids of the transactions of the given origin, ordered by fee cap, lowest first
+    pub(crate) fn origin_transactions_by_fee(
+        &self,
+        origin: TransactionOrigin,
+    ) -> Vec<TransactionId> {
+        let mut txs = self
+            .txs
+            .values()
+            .filter(|tx| tx.transaction.origin == origin)
+            .map(|tx| (tx.transaction.max_fee_per_gas(), *tx.transaction.id()))
+            .collect::<Vec<_>>();
+        txs.sort_by_key(|(max_fee_per_gas, _)| *max_fee_per_gas);
+        txs.into_iter().map(|(_, id)| id).collect()
+    }
+
     /// Updates the block specific info
     fn set_block_info(&mut self, block_info: BlockInfo) {
         let BlockInfo {
@@ -1570,7 +1670,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
         let internal = self.txs.remove(&tx.transaction_id)?;
         self.remove_auths(&internal);
         // decrement the counter for the sender.
-        self.tx_decr(tx.sender_id());
+        self.tx_decr(tx.sender_id(), tx.origin);
         self.update_size_metrics();
         Some((tx, internal.subpool))
     }
@@ -1586,7 +1686,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
         let tx = self.by_hash.remove(internal.transaction.hash())?;
         self.remove_auths(&internal);
         // decrement the counter for the sender.
-        self.tx_decr(tx.sender_id());
+        self.tx_decr(tx.sender_id(), tx.origin);
         Some((tx, internal.subpool))
     }
 
@@ -1630,7 +1730,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
         let internal = self.txs.remove(id)?;
 
         // decrement the counter for the sender.
-        self.tx_decr(internal.transaction.sender_id());
+        self.tx_decr(internal.transaction.sender_id(), internal.transaction.origin);
 
         let result =
             self.by_hash.remove(internal.transaction.hash()).map(|tx| (tx, internal.subpool));
@@ -1696,6 +1796,29 @@ impl<T: PoolTransaction> AllTransactions<T> {
                 })
             }
         }
+        // Replacements don't take up an additional slot, so quotas only apply to new transactions.
+        if !self.txs.contains_key(&transaction.transaction_id) {
+            if let Some(quota) = self.quotas.per_sender {
+                let current_txs =
+                    self.tx_counter.get(&transaction.sender_id()).copied().unwrap_or_default();
+                if current_txs >= quota && transaction.nonce() > on_chain_nonce {
+                    self.metrics.sender_quota_rejected.increment(1);
+                    return Err(InsertErr::ExceededSenderTransactionsCapacity {
+                        transaction: Arc::new(transaction),
+                    })
+                }
+            }
+            if self.quotas.eviction == QuotaEvictionPolicy::RejectNew {
+                if let Some(quota) = self.quotas.origin_quota(transaction.origin) {
+                    if self.origin_count(transaction.origin) >= quota {
+                        self.metrics.origin_quota_rejected.increment(1);
+                        return Err(InsertErr::ExceededOriginTransactionsCapacity {
+                            transaction: Arc::new(transaction),
+                        })
+                    }
+                }
+            }
+        }
         if transaction.gas_limit() > self.block_gas_limit {
             return Err(InsertErr::TxGasLimitMoreThanAvailableBlockGas {
                 block_gas_limit: self.block_gas_limit,
@@ -1979,9 +2102,15 @@ impl<T: PoolTransaction> AllTransactions<T> {
             }
         }
 
-        // If this wasn't a replacement transaction we need to update the counter.
-        if replaced_tx.is_none() {
-            self.tx_inc(inserted_tx_id.sender);
+        // If this wasn't a replacement transaction we need to update the counters, otherwise the
+        // replacement may have moved the slot to another origin.
+        match &replaced_tx {
+            None => self.tx_inc(inserted_tx_id.sender, transaction.origin),
+            Some((replaced, _)) if replaced.origin != transaction.origin => {
+                self.origin_decr(replaced.origin);
+                self.origin_inc(transaction.origin);
+            }
+            Some(_) => {}
         }
 
         self.update_size_metrics();
@@ -2026,6 +2155,8 @@ impl<T: PoolTransaction> Default for AllTransactions<T> {
             by_hash: Default::default(),
             txs: Default::default(),
             tx_counter: Default::default(),
+            origin_counter: Default::default(),
+            quotas: Default::default(),
             last_seen_block_number: Default::default(),
             last_seen_block_hash: Default::default(),
             pending_fees: Default::default(),
@@ -2077,6 +2208,8 @@ pub(crate) enum InsertErr<T: PoolTransaction> {
     ///
     /// The sender can be considered a spammer at this point.
     ExceededSenderTransactionsCapacity { transaction: Arc<ValidPoolTransaction<T>> },
+    /// The transaction's origin is at its configured quota.
+    ExceededOriginTransactionsCapacity { transaction: Arc<ValidPoolTransaction<T>> },
     /// Transaction gas limit exceeds block's gas limit
     TxGasLimitMoreThanAvailableBlockGas {
         transaction: Arc<ValidPoolTransaction<T>>,
@@ -2885,6 +3018,96 @@ mod tests {
         .unwrap();
     }
 
+    #[test]
+    fn sender_quota_applies_to_local() {
+        let on_chain_balance = U256::from(1_000);
+        let on_chain_nonce = 0;
+        let mut f = MockTransactionFactory::default();
+        let config =
+            PoolConfig { quotas: PoolQuotas::default().with_per_sender(2), ..Default::default() };
+        let mut pool = AllTransactions::new(&config);
+
+        let mut tx = MockTransaction::eip1559();
+        for _ in 0..2 {
+            tx = tx.next();
+            pool.insert_tx(
+                f.validated_with_origin(TransactionOrigin::Local, tx.clone()),
+                on_chain_balance,
+                on_chain_nonce,
+            )
+            .unwrap();
+        }
+
+        let err = pool
+            .insert_tx(
+                f.validated_with_origin(TransactionOrigin::Local, tx.next()),
+                on_chain_balance,
+                on_chain_nonce,
+            )
+            .unwrap_err();
+        assert!(matches!(err, InsertErr::ExceededSenderTransactionsCapacity { .. }));
+    }
+
+    #[test]
+    fn rejects_origin_over_quota() {
+        let on_chain_balance = U256::from(1_000);
+        let on_chain_nonce = 0;
+        let mut f = MockTransactionFactory::default();
+        let config = PoolConfig {
+            quotas: PoolQuotas::default().with_origin(TransactionOrigin::External, 2),
+            ..Default::default()
+        };
+        let mut pool = AllTransactions::new(&config);
+
+        let first = MockTransaction::eip1559();
+        pool.insert_tx(f.validated(first.clone()), on_chain_balance, on_chain_nonce).unwrap();
+        pool.insert_tx(f.validated(MockTransaction::eip1559()), on_chain_balance, on_chain_nonce)
+            .unwrap();
+        assert_eq!(pool.origin_count(TransactionOrigin::External), 2);
+
+        let err = pool
+            .insert_tx(f.validated(MockTransaction::eip1559()), on_chain_balance, on_chain_nonce)
+            .unwrap_err();
+        assert!(matches!(err, InsertErr::ExceededOriginTransactionsCapacity { .. }));
+
+        // replacements don't take up an additional slot
+        let replacement = first.inc_price_by(first.get_gas_price()).rng_hash();
+        pool.insert_tx(f.validated(replacement), on_chain_balance, on_chain_nonce).unwrap();
+
+        // other origins are unaffected
+        pool.insert_tx(
+            f.validated_with_origin(TransactionOrigin::Local, MockTransaction::eip1559()),
+            on_chain_balance,
+            on_chain_nonce,
+        )
+        .unwrap();
+        assert_eq!(pool.origin_count(TransactionOrigin::External), 2);
+        assert_eq!(pool.origin_count(TransactionOrigin::Local), 1);
+    }
+
+    #[test]
+    fn evicts_cheapest_over_origin_quota() {
+        let mut f = MockTransactionFactory::default();
+        let config = PoolConfig {
+            quotas: PoolQuotas::default()
+                .with_origin(TransactionOrigin::External, 2)
+                .with_eviction(QuotaEvictionPolicy::EvictCheapest),
+            ..Default::default()
+        };
+        let mut pool = TxPool::new(MockOrdering::default(), config);
+
+        let fees = [300u128, 100, 200];
+        let txs = fees.map(|fee| MockTransaction::eip1559().with_max_fee(fee).with_priority_fee(1));
+        for tx in &txs {
+            pool.add_transaction(f.validated(tx.clone()), U256::from(1_000), 0, None).unwrap();
+        }
+
+        let removed = pool.discard_worst();
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed[0].hash(), txs[1].hash());
+        assert_eq!(pool.all_transactions.origin_count(TransactionOrigin::External), 2);
+    }
+
     #[test]
     fn reject_tx_over_gas_limit() {
         let on_chain_balance = U256::from(1_000);
@@ -53,6 +53,7 @@
 use crate::{
     blobstore::BlobStoreError,
     error::{InvalidPoolTransactionError, PoolResult},
+    firewall::PoolFirewall,
     pool::{
         state::SubPool, BestTransactionFilter, NewTransactionEvent, TransactionEvents,
         TransactionListenerKind,
@@ -116,6 +117,12 @@ pub trait TransactionPool: Clone + Debug + Send + Sync {
     /// Returns stats about the pool and all sub-pools.
     fn pool_size(&self) -> PoolSize;
 
+    /// Returns the admission firewall evaluated before transactions are validated, if the pool has
+    /// one.
+    fn firewall(&self) -> Option<PoolFirewall> {
+        None
+    }
+
     /// Returns the block the pool is currently tracking.
     ///
     /// This tracks the block that the pool has last seen.
@@ -348,6 +355,18 @@ pub trait TransactionPool: Clone + Debug + Send + Sync {
         max: usize,
     ) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>>;
 
+    /// Returns at most `max` transactions that can be included in the next block, evenly spread
+    /// over all of them.
+    ///
+    /// Unlike [`Self::pending_transactions_max`], the sample isn't limited to the first senders of
+    /// the pool.
+    ///
+    /// Consumer: RPC
+    fn pending_transactions_sample(
+        &self,
+        max: usize,
+    ) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>>;
+
     /// Returns all transactions that can be included in _future_ blocks.
     ///
     /// This and [`Self::pending_transactions`] are mutually exclusive.
@@ -418,6 +437,15 @@ pub trait TransactionPool: Clone + Debug + Send + Sync {
     /// Consumer: P2P
     fn on_propagated(&self, txs: PropagatedTransactions);
 
+    /// Returns the hash of the transaction that replaced the given local transaction, if the
+    /// replacement happened within the configured suppression window.
+    ///
+    /// The replaced version should no longer be gossiped, peers that announce it can be pointed to
+    /// the replacement instead.
+    ///
+    /// Consumer: P2P
+    fn replacement_of(&self, tx_hash: &TxHash) -> Option<TxHash>;
+
     /// Returns all transactions sent by a given user
     fn get_transactions_by_sender(
         &self,
@@ -470,6 +498,12 @@ pub trait TransactionPool: Clone + Debug + Send + Sync {
         nonce: u64,
     ) -> Option<Arc<ValidPoolTransaction<Self::Transaction>>>;
 
+    /// Returns all transactions of every subpool filtered by predicate
+    fn get_transactions_with_predicate(
+        &self,
+        predicate: impl FnMut(&ValidPoolTransaction<Self::Transaction>) -> bool,
+    ) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>>;
+
     /// Returns all transactions that where submitted with the given [`TransactionOrigin`]
     fn get_transactions_by_origin(
         &self,
@@ -697,7 +731,7 @@ pub struct NewBlobSidecar {
 ///
 /// Depending on where the transaction was picked up, it affects how the transaction is handled
 /// internally, e.g. limits for simultaneous transaction of one sender.
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
 pub enum TransactionOrigin {
     /// Transaction is coming from a local source.
     #[default]
@@ -875,8 +875,16 @@ impl<Client> EthTransactionValidatorBuilder<Client> {
     }
 
     /// Set the Prague fork.
+    ///
+    /// This also resets the max blob count to the default of the active fork, use
+    /// [`Self::with_head_timestamp`] to apply the blob schedule of the chain spec.
     pub const fn set_prague(mut self, prague: bool) -> Self {
         self.prague = prague;
+        self.max_blob_count = if prague {
+            BlobParams::prague().max_blobs_per_tx
+        } else {
+            BlobParams::cancun().max_blobs_per_tx
+        };
         self
     }
 
@@ -1012,15 +1020,10 @@ impl<Client> EthTransactionValidatorBuilder<Client> {
             local_transactions_config,
             max_tx_input_bytes,
             max_tx_gas_limit,
+            max_blob_count,
             ..
         } = self;
 
-        let max_blob_count = if prague {
-            BlobParams::prague().max_blobs_per_tx
-        } else {
-            BlobParams::cancun().max_blobs_per_tx
-        };
-
         let fork_tracker = ForkTracker {
             shanghai: AtomicBool::new(shanghai),
             cancun: AtomicBool::new(cancun),
@@ -410,6 +410,13 @@ impl<T: PoolTransaction> ValidPoolTransaction<T> {
         self.transaction.is_eip4844()
     }
 
+    /// Returns the number of blobs of this transaction.
+    ///
+    /// Returns `None` if this transaction is not an EIP-4844 blob transaction.
+    pub fn blob_count(&self) -> Option<u64> {
+        self.transaction.blob_versioned_hashes().map(|hashes| hashes.len() as u64)
+    }
+
     /// The heap allocated size of this transaction.
     pub(crate) fn size(&self) -> usize {
         self.transaction.size()
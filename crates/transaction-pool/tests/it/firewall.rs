@@ -0,0 +1,52 @@
+//! Transaction pool firewall tests.
+
+use assert_matches::assert_matches;
+use reth_transaction_pool::{
+    error::{PoolError, PoolErrorKind},
+    firewall::FirewallConfig,
+    noop::MockTransactionValidator,
+    test_utils::{MockTransaction, TestPoolBuilder},
+    PoolConfig, PoolTransaction, TransactionOrigin, TransactionPool,
+};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn firewall_rejects_before_validation() {
+    let blocked = MockTransaction::eip1559();
+    let config = PoolConfig {
+        firewall: FirewallConfig {
+            blocked_senders: [blocked.sender()].into(),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let txpool = TestPoolBuilder::default().with_config(config);
+
+    let results = txpool
+        .add_transactions(
+            TransactionOrigin::External,
+            vec![MockTransaction::eip1559(), blocked.clone(), MockTransaction::eip1559()],
+        )
+        .await;
+    assert_matches!(results[0], Ok(_));
+    assert_matches!(
+        &results[1],
+        Err(PoolError { kind: PoolErrorKind::FirewallRejected(rejection), .. })
+            if rejection.rule == "blocked_senders"
+    );
+    assert_matches!(results[2], Ok(_));
+    assert_eq!(txpool.pool_size().total, 2);
+
+    // the validator rejects everything, the firewall rejection is returned without validating
+    let txpool = txpool.with_validator(MockTransactionValidator::return_invalid());
+    let result = txpool.add_transaction(TransactionOrigin::External, blocked.clone()).await;
+    assert_matches!(result, Err(PoolError { kind: PoolErrorKind::FirewallRejected(_), .. }));
+    let result =
+        txpool.add_transaction(TransactionOrigin::External, MockTransaction::eip1559()).await;
+    assert_matches!(result, Err(PoolError { kind: PoolErrorKind::InvalidTransaction(_), .. }));
+
+    // lifting the rule admits the sender again
+    let txpool = txpool.with_validator(MockTransactionValidator::default());
+    txpool.firewall().unwrap().set_config(Default::default());
+    let result = txpool.add_transaction(TransactionOrigin::External, blocked).await;
+    assert_matches!(result, Ok(_));
+}
@@ -65,6 +65,27 @@ async fn txpool_listener_replace_event() {
     assert_matches!(all_tx_events.next().await, Some(FullTransactionEvent::Replaced { transaction, replaced_by }) if *transaction.transaction.get_hash() == *old_transaction.get_hash() && replaced_by == *replace_transaction.get_hash());
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn txpool_tracks_replaced_local_transactions() {
+    let txpool = TestPoolBuilder::default();
+    let mut mock_tx_factory = MockTransactionFactory::default();
+    let transaction = mock_tx_factory.create_eip1559().transaction;
+
+    txpool.add_transaction(TransactionOrigin::Local, transaction.clone()).await.unwrap();
+    assert_eq!(txpool.replacement_of(transaction.get_hash()), None);
+
+    let replacement = transaction.clone().rng_hash().inc_price();
+    txpool.add_transaction(TransactionOrigin::Local, replacement.clone()).await.unwrap();
+    assert_eq!(txpool.replacement_of(transaction.get_hash()), Some(*replacement.get_hash()));
+
+    // replacements of external transactions are not tracked
+    let external = mock_tx_factory.create_eip1559().transaction;
+    txpool.add_transaction(TransactionOrigin::External, external.clone()).await.unwrap();
+    let external_replacement = external.clone().rng_hash().inc_price();
+    txpool.add_transaction(TransactionOrigin::External, external_replacement).await.unwrap();
+    assert_eq!(txpool.replacement_of(external.get_hash()), None);
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn txpool_listener_queued_event() {
     let txpool = TestPoolBuilder::default();
@@ -5,6 +5,8 @@ mod blobs;
 #[cfg(feature = "test-utils")]
 mod evict;
 #[cfg(feature = "test-utils")]
+mod firewall;
+#[cfg(feature = "test-utils")]
 mod listeners;
 #[cfg(feature = "test-utils")]
 mod pending;
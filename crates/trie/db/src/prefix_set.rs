@@ -13,11 +13,16 @@ use reth_db_api::{
     transaction::DbTx,
     DatabaseError,
 };
+use reth_fs_util::FsPathError;
 use reth_primitives_traits::StorageEntry;
 use reth_trie::{
     prefix_set::{PrefixSetMut, TriePrefixSets},
     KeyHasher, Nibbles,
 };
+use std::{
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
 
 /// A wrapper around a database transaction that loads prefix sets within a given block range.
 #[derive(Debug)]
@@ -41,17 +46,48 @@ impl<TX, KH> Deref for PrefixSetLoader<'_, TX, KH> {
 impl<TX: DbTx, KH: KeyHasher> PrefixSetLoader<'_, TX, KH> {
     /// Load all account and storage changes for the given block range.
     pub fn load(self, range: RangeInclusive<BlockNumber>) -> Result<TriePrefixSets, DatabaseError> {
-        // Initialize prefix sets.
-        let mut account_prefix_set = PrefixSetMut::default();
-        let mut storage_prefix_sets = HashMap::<B256, PrefixSetMut>::default();
-        let mut destroyed_accounts = HashSet::default();
+        let changed_keys = self.load_changed_keys(range)?;
+        self.prefix_sets(changed_keys)
+    }
+
+    /// Load the hashed keys of all accounts and storage slots changed in the given block range.
+    pub fn load_changed_keys(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> Result<ChangedKeys, DatabaseError> {
+        let mut changed_keys = ChangedKeys::default();
 
-        // Walk account changeset and insert account prefixes.
+        // Walk account changeset and collect account keys.
         let mut account_changeset_cursor = self.cursor_read::<tables::AccountChangeSets>()?;
-        let mut account_hashed_state_cursor = self.cursor_read::<tables::HashedAccounts>()?;
         for account_entry in account_changeset_cursor.walk_range(range.clone())? {
             let (_, AccountBeforeTx { address, .. }) = account_entry?;
-            let hashed_address = KH::hash_key(address);
+            changed_keys.accounts.insert(KH::hash_key(address));
+        }
+
+        // Walk storage changeset and collect storage keys.
+        let mut storage_cursor = self.cursor_dup_read::<tables::StorageChangeSets>()?;
+        let storage_range = BlockNumberAddress::range(range);
+        for storage_entry in storage_cursor.walk_range(storage_range)? {
+            let (BlockNumberAddress((_, address)), StorageEntry { key, .. }) = storage_entry?;
+            changed_keys
+                .storages
+                .entry(KH::hash_key(address))
+                .or_default()
+                .insert(KH::hash_key(key));
+        }
+
+        Ok(changed_keys)
+    }
+
+    /// Converts the changed keys into prefix sets.
+    ///
+    /// Changed accounts that no longer exist in the hashed state are marked as destroyed.
+    pub fn prefix_sets(&self, changed_keys: ChangedKeys) -> Result<TriePrefixSets, DatabaseError> {
+        let mut account_prefix_set = PrefixSetMut::default();
+        let mut destroyed_accounts = HashSet::default();
+
+        let mut account_hashed_state_cursor = self.cursor_read::<tables::HashedAccounts>()?;
+        for hashed_address in changed_keys.accounts {
             account_prefix_set.insert(Nibbles::unpack(hashed_address));
 
             if account_hashed_state_cursor.seek_exact(hashed_address)?.is_none() {
@@ -59,27 +95,201 @@ impl<TX: DbTx, KH: KeyHasher> PrefixSetLoader<'_, TX, KH> {
             }
         }
 
-        // Walk storage changeset and insert storage prefixes as well as account prefixes if missing
-        // from the account prefix set.
-        let mut storage_cursor = self.cursor_dup_read::<tables::StorageChangeSets>()?;
-        let storage_range = BlockNumberAddress::range(range);
-        for storage_entry in storage_cursor.walk_range(storage_range)? {
-            let (BlockNumberAddress((_, address)), StorageEntry { key, .. }) = storage_entry?;
-            let hashed_address = KH::hash_key(address);
+        // Accounts with changed storage are part of the account prefix set as well.
+        let mut storage_prefix_sets = HashMap::default();
+        for (hashed_address, slots) in changed_keys.storages {
             account_prefix_set.insert(Nibbles::unpack(hashed_address));
-            storage_prefix_sets
-                .entry(hashed_address)
-                .or_default()
-                .insert(Nibbles::unpack(KH::hash_key(key)));
+            let storage_prefix_set = PrefixSetMut::from(slots.into_iter().map(Nibbles::unpack));
+            storage_prefix_sets.insert(hashed_address, storage_prefix_set.freeze());
         }
 
         Ok(TriePrefixSets {
             account_prefix_set: account_prefix_set.freeze(),
-            storage_prefix_sets: storage_prefix_sets
-                .into_iter()
-                .map(|(k, v)| (k, v.freeze()))
-                .collect(),
+            storage_prefix_sets,
             destroyed_accounts,
         })
     }
 }
+
+/// Hashed keys of the accounts and storage slots that changed within a block range.
+///
+/// These are the inputs of the [`TriePrefixSets`] of an incremental state root.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ChangedKeys {
+    /// Hashed addresses of accounts with changed account info.
+    pub accounts: HashSet<B256>,
+    /// Hashed storage slots of accounts with changed storage, by hashed address.
+    pub storages: HashMap<B256, HashSet<B256>>,
+}
+
+/// Persists the [`ChangedKeys`] of block ranges to files in a directory.
+///
+/// Loading the changed keys of a large block range requires walking all of its changesets. Storing
+/// them allows an interrupted incremental state root to be resumed without walking the changesets
+/// again.
+///
+/// Entries are named after the block range and the hash of its last block, so entries of blocks
+/// that were reorged out are never read.
+#[derive(Clone, Debug)]
+pub struct PrefixSetStore {
+    dir: PathBuf,
+}
+
+impl PrefixSetStore {
+    /// Creates a new store that keeps its files in the given directory.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Returns the directory of the store.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Returns the changed keys of the block range ending in the block with the given hash, if they
+    /// were stored.
+    pub fn get(
+        &self,
+        range: &RangeInclusive<BlockNumber>,
+        last_block_hash: B256,
+    ) -> Result<Option<ChangedKeys>, FsPathError> {
+        let path = self.path(range, last_block_hash);
+        if !path.exists() {
+            return Ok(None)
+        }
+
+        let data = reth_fs_util::read(&path)?;
+        decode_changed_keys(&data)
+            .map(Some)
+            .ok_or_else(|| FsPathError::read(io::ErrorKind::InvalidData.into(), path))
+    }
+
+    /// Stores the changed keys of the block range ending in the block with the given hash.
+    pub fn insert(
+        &self,
+        range: &RangeInclusive<BlockNumber>,
+        last_block_hash: B256,
+        changed_keys: &ChangedKeys,
+    ) -> Result<(), FsPathError> {
+        reth_fs_util::create_dir_all(&self.dir)?;
+        let data = encode_changed_keys(changed_keys);
+        reth_fs_util::atomic_write_file(&self.path(range, last_block_hash), |file| {
+            file.write_all(&data)
+        })
+    }
+
+    /// Removes all entries of block ranges that end at or before the given block.
+    pub fn remove_until(&self, block: BlockNumber) -> Result<usize, FsPathError> {
+        if !self.dir.exists() {
+            return Ok(0)
+        }
+
+        let mut removed = 0;
+        for entry in reth_fs_util::read_dir(&self.dir)? {
+            let path = entry.map_err(|err| FsPathError::read_dir(err, &self.dir))?.path();
+            let last_block = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.split('-').nth(1))
+                .and_then(|number| number.parse::<BlockNumber>().ok());
+            if last_block.is_some_and(|last_block| last_block <= block) {
+                reth_fs_util::remove_file(&path)?;
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+
+    fn path(&self, range: &RangeInclusive<BlockNumber>, last_block_hash: B256) -> PathBuf {
+        self.dir.join(format!("{}-{}-{last_block_hash:x}", range.start(), range.end()))
+    }
+}
+
+/// Encodes the changed keys as length prefixed lists of keys, sorted for deterministic output.
+fn encode_changed_keys(changed_keys: &ChangedKeys) -> Vec<u8> {
+    fn encode_keys<'a>(out: &mut Vec<u8>, keys: impl ExactSizeIterator<Item = &'a B256>) {
+        out.extend_from_slice(&(keys.len() as u64).to_le_bytes());
+        let mut keys = keys.collect::<Vec<_>>();
+        keys.sort_unstable();
+        for key in keys {
+            out.extend_from_slice(key.as_slice());
+        }
+    }
+
+    let mut out = Vec::new();
+    encode_keys(&mut out, changed_keys.accounts.iter());
+    encode_keys(&mut out, changed_keys.storages.keys());
+
+    let mut storages = changed_keys.storages.iter().collect::<Vec<_>>();
+    storages.sort_unstable_by_key(|(hashed_address, _)| *hashed_address);
+    for (_, slots) in storages {
+        encode_keys(&mut out, slots.iter());
+    }
+    out
+}
+
+/// Decodes changed keys encoded with [`encode_changed_keys`], returns `None` if the data is
+/// malformed.
+fn decode_changed_keys(mut data: &[u8]) -> Option<ChangedKeys> {
+    fn decode_keys(data: &mut &[u8]) -> Option<Vec<B256>> {
+        let (len, rest) = data.split_first_chunk::<8>()?;
+        let len = usize::try_from(u64::from_le_bytes(*len)).ok()?;
+        let (keys, rest) = rest.split_at_checked(len.checked_mul(32)?)?;
+        *data = rest;
+        Some(keys.chunks_exact(32).map(B256::from_slice).collect())
+    }
+
+    let accounts = decode_keys(&mut data)?.into_iter().collect();
+    let mut storages = HashMap::default();
+    for hashed_address in decode_keys(&mut data)? {
+        storages.insert(hashed_address, decode_keys(&mut data)?.into_iter().collect());
+    }
+
+    data.is_empty().then_some(ChangedKeys { accounts, storages })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn changed_keys() -> ChangedKeys {
+        ChangedKeys {
+            accounts: HashSet::from_iter([B256::with_last_byte(1), B256::with_last_byte(2)]),
+            storages: HashMap::from_iter([
+                (
+                    B256::with_last_byte(2),
+                    HashSet::from_iter([B256::with_last_byte(3), B256::with_last_byte(4)]),
+                ),
+                (B256::with_last_byte(5), HashSet::from_iter([B256::with_last_byte(6)])),
+            ]),
+        }
+    }
+
+    #[test]
+    fn store_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = PrefixSetStore::new(dir.path());
+        let changed_keys = changed_keys();
+        let hash = B256::with_last_byte(0xff);
+
+        assert_eq!(store.get(&(1..=10), hash).unwrap(), None);
+        store.insert(&(1..=10), hash, &changed_keys).unwrap();
+        store.insert(&(11..=20), hash, &changed_keys).unwrap();
+        assert_eq!(store.get(&(1..=10), hash).unwrap(), Some(changed_keys.clone()));
+
+        // entries of a reorged block are never read
+        assert_eq!(store.get(&(1..=10), B256::ZERO).unwrap(), None);
+
+        assert_eq!(store.remove_until(10).unwrap(), 1);
+        assert_eq!(store.get(&(1..=10), hash).unwrap(), None);
+        assert_eq!(store.get(&(11..=20), hash).unwrap(), Some(changed_keys));
+    }
+
+    #[test]
+    fn decode_malformed() {
+        let encoded = encode_changed_keys(&changed_keys());
+        assert_eq!(decode_changed_keys(&encoded), Some(changed_keys()));
+        assert_eq!(decode_changed_keys(&encoded[..encoded.len() - 1]), None);
+        assert_eq!(decode_changed_keys(&[encoded.as_slice(), &[0]].concat()), None);
+    }
+}
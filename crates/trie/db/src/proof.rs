@@ -101,6 +101,15 @@ pub trait DatabaseStorageProof<'a, TX> {
         slots: &[B256],
         storage: HashedStorage,
     ) -> Result<StorageMultiProof, StateProofError>;
+
+    /// Generates the storage multiproof for target slots based on [`TrieInput`], reusing its
+    /// intermediate nodes.
+    fn overlay_storage_multiproof_from_nodes(
+        tx: &'a TX,
+        input: TrieInput,
+        address: Address,
+        slots: &[B256],
+    ) -> Result<StorageMultiProof, StateProofError>;
 }
 
 impl<'a, TX: DbTx> DatabaseStorageProof<'a, TX>
@@ -152,4 +161,29 @@ impl<'a, TX: DbTx> DatabaseStorageProof<'a, TX>
             .with_prefix_set_mut(prefix_set)
             .storage_multiproof(targets)
     }
+
+    fn overlay_storage_multiproof_from_nodes(
+        tx: &'a TX,
+        mut input: TrieInput,
+        address: Address,
+        slots: &[B256],
+    ) -> Result<StorageMultiProof, StateProofError> {
+        let hashed_address = keccak256(address);
+        let targets = slots.iter().map(keccak256).collect();
+        let prefix_set =
+            input.prefix_sets.storage_prefix_sets.remove(&hashed_address).unwrap_or_default();
+        let nodes_sorted = input.nodes.into_sorted();
+        let state_sorted = input.state.into_sorted();
+        Self::from_tx(tx, address)
+            .with_trie_cursor_factory(InMemoryTrieCursorFactory::new(
+                DatabaseTrieCursorFactory::new(tx),
+                &nodes_sorted,
+            ))
+            .with_hashed_cursor_factory(HashedPostStateCursorFactory::new(
+                DatabaseHashedCursorFactory::new(tx),
+                &state_sorted,
+            ))
+            .with_prefix_set_mut(prefix_set)
+            .storage_multiproof(targets)
+    }
 }
@@ -1,11 +1,16 @@
-use crate::{DatabaseHashedCursorFactory, DatabaseTrieCursorFactory, PrefixSetLoader};
+use crate::{
+    storage::{first_change_at_or_after, storage_revert, SeekBudget},
+    DatabaseHashedCursorFactory, DatabaseTrieCursorFactory, PrefixSetLoader,
+};
 use alloy_primitives::{
     map::{AddressMap, B256Map},
     Address, BlockNumber, B256, U256,
 };
 use reth_db_api::{
-    cursor::DbCursorRO,
-    models::{AccountBeforeTx, BlockNumberAddress},
+    cursor::{DbCursorRO, DbDupCursorRO},
+    models::{
+        storage_sharded_key::StorageShardedKey, AccountBeforeTx, BlockNumberAddress, ShardedKey,
+    },
     tables,
     transaction::DbTx,
     DatabaseError,
@@ -127,6 +132,32 @@ pub trait DatabaseHashedPostState<TX>: Sized {
     /// Initializes [`HashedPostState`] from reverts. Iterates over state reverts from the specified
     /// block up to the current tip and aggregates them into hashed state in reverse.
     fn from_reverts<KH: KeyHasher>(tx: &TX, from: BlockNumber) -> Result<Self, DatabaseError>;
+
+    /// Initializes [`HashedPostState`] from reverts like [`Self::from_reverts`], but stops reading
+    /// once more than `limit` changeset entries were replayed.
+    ///
+    /// Returns `None` if the reverts exceed the limit.
+    fn from_reverts_with_limit<KH: KeyHasher>(
+        tx: &TX,
+        from: BlockNumber,
+        limit: usize,
+    ) -> Result<Option<Self>, DatabaseError>;
+
+    /// Initializes [`HashedPostState`] from the reverts of the account and the given storage slots
+    /// only. All other accounts and slots keep their values at the tip.
+    ///
+    /// The keys are looked up in [`tables::AccountsHistory`] and [`tables::StoragesHistory`], so
+    /// only the changeset of the first change of every key at or after the block is read. Stops
+    /// once more than `limit` seeks into the history indices and the changesets were made.
+    ///
+    /// Returns `None` if the reverts exceed the limit.
+    fn from_reverts_of_account_with_limit<KH: KeyHasher>(
+        tx: &TX,
+        from: BlockNumber,
+        address: Address,
+        slots: &[B256],
+        limit: usize,
+    ) -> Result<Option<Self>, DatabaseError>;
 }
 
 impl<'a, TX: DbTx> DatabaseStateRoot<'a, TX>
@@ -221,10 +252,25 @@ impl<'a, TX: DbTx> DatabaseStateRoot<'a, TX>
 
 impl<TX: DbTx> DatabaseHashedPostState<TX> for HashedPostState {
     fn from_reverts<KH: KeyHasher>(tx: &TX, from: BlockNumber) -> Result<Self, DatabaseError> {
+        Ok(Self::from_reverts_with_limit::<KH>(tx, from, usize::MAX)?
+            .expect("limit can't be exceeded"))
+    }
+
+    fn from_reverts_with_limit<KH: KeyHasher>(
+        tx: &TX,
+        from: BlockNumber,
+        limit: usize,
+    ) -> Result<Option<Self>, DatabaseError> {
+        let mut replayed = 0usize;
+
         // Iterate over account changesets and record value before first occurring account change.
         let mut accounts = HashMap::new();
         let mut account_changesets_cursor = tx.cursor_read::<tables::AccountChangeSets>()?;
         for entry in account_changesets_cursor.walk_range(from..)? {
+            replayed += 1;
+            if replayed > limit {
+                return Ok(None)
+            }
             let (_, AccountBeforeTx { address, info }) = entry?;
             accounts.entry(address).or_insert(info);
         }
@@ -235,6 +281,10 @@ impl<TX: DbTx> DatabaseHashedPostState<TX> for HashedPostState {
         for entry in
             storage_changesets_cursor.walk_range(BlockNumberAddress((from, Address::ZERO))..)?
         {
+            replayed += 1;
+            if replayed > limit {
+                return Ok(None)
+            }
             let (BlockNumberAddress((_, address)), storage) = entry?;
             let account_storage = storages.entry(address).or_default();
             account_storage.entry(storage.key).or_insert(storage.value);
@@ -259,7 +309,67 @@ impl<TX: DbTx> DatabaseHashedPostState<TX> for HashedPostState {
             })
             .collect();
 
-        Ok(Self { accounts: hashed_accounts, storages: hashed_storages })
+        Ok(Some(Self { accounts: hashed_accounts, storages: hashed_storages }))
+    }
+
+    fn from_reverts_of_account_with_limit<KH: KeyHasher>(
+        tx: &TX,
+        from: BlockNumber,
+        address: Address,
+        slots: &[B256],
+        limit: usize,
+    ) -> Result<Option<Self>, DatabaseError> {
+        let mut seeks = SeekBudget::new(limit);
+        let mut state = Self::default();
+
+        if !seeks.spend() {
+            return Ok(None)
+        }
+        let mut history_cursor = tx.cursor_read::<tables::AccountsHistory>()?;
+        if let Some((_, blocks)) = history_cursor
+            .seek(ShardedKey::new(address, from))?
+            .filter(|(key, _)| key.key == address)
+        {
+            if let Some(block) = first_change_at_or_after(&blocks, from) {
+                if !seeks.spend() {
+                    return Ok(None)
+                }
+                let mut changesets_cursor = tx.cursor_dup_read::<tables::AccountChangeSets>()?;
+                if let Some(AccountBeforeTx { info, .. }) = changesets_cursor
+                    .seek_by_key_subkey(block, address)?
+                    .filter(|entry| entry.address == address)
+                {
+                    state.accounts.insert(KH::hash_key(address), info);
+                }
+            }
+        }
+
+        let mut storage = HashedStorage::new(false);
+        let mut history_cursor = tx.cursor_read::<tables::StoragesHistory>()?;
+        let mut changesets_cursor = tx.cursor_dup_read::<tables::StorageChangeSets>()?;
+        for slot in slots {
+            if !seeks.spend() {
+                return Ok(None)
+            }
+            let Some((_, blocks)) = history_cursor
+                .seek(StorageShardedKey::new(address, *slot, from))?
+                .filter(|(key, _)| key.address == address && key.sharded_key.key == *slot)
+            else {
+                continue
+            };
+            let Some(block) = first_change_at_or_after(&blocks, from) else { continue };
+            if !seeks.spend() {
+                return Ok(None)
+            }
+            if let Some(value) = storage_revert(&mut changesets_cursor, block, address, *slot)? {
+                storage.storage.insert(KH::hash_key(slot), value);
+            }
+        }
+        if !storage.storage.is_empty() {
+            state.storages.insert(KH::hash_key(address), storage);
+        }
+
+        Ok(Some(state))
     }
 }
 
@@ -268,7 +378,8 @@ mod tests {
     use super::*;
     use alloy_primitives::{hex, map::HashMap, Address, U256};
     use reth_db::test_utils::create_test_rw_db;
-    use reth_db_api::database::Database;
+    use reth_db_api::{database::Database, transaction::DbTxMut, BlockNumberList};
+    use reth_primitives_traits::{Account, StorageEntry};
     use reth_trie::KeccakKeyHasher;
     use revm::state::AccountInfo;
     use revm_database::BundleState;
@@ -302,4 +413,126 @@ mod tests {
             hex!("b464525710cafcf5d4044ac85b72c08b1e76231b8d91f288fe438cc41d8eaafd")
         );
     }
+
+    #[test]
+    fn from_reverts_with_limit() {
+        let address = Address::with_last_byte(1);
+        let db = create_test_rw_db();
+        let tx = db.tx_mut().expect("failed to create transaction");
+        for block in 1..=3u64 {
+            tx.put::<tables::AccountChangeSets>(
+                block,
+                AccountBeforeTx {
+                    address,
+                    info: Some(Account { nonce: block, ..Default::default() }),
+                },
+            )
+            .unwrap();
+            tx.put::<tables::StorageChangeSets>(
+                BlockNumberAddress((block, address)),
+                StorageEntry { key: B256::ZERO, value: U256::from(block) },
+            )
+            .unwrap();
+        }
+
+        // reverting to block 2 replays two account and two storage changeset entries
+        assert!(HashedPostState::from_reverts_with_limit::<KeccakKeyHasher>(&tx, 2, 3)
+            .unwrap()
+            .is_none());
+        let reverts = HashedPostState::from_reverts_with_limit::<KeccakKeyHasher>(&tx, 2, 4)
+            .unwrap()
+            .unwrap();
+        assert_eq!(reverts, HashedPostState::from_reverts::<KeccakKeyHasher>(&tx, 2).unwrap());
+
+        let hashed_address = KeccakKeyHasher::hash_key(address);
+        assert_eq!(reverts.accounts[&hashed_address].unwrap().nonce, 2);
+        assert_eq!(
+            reverts.storages[&hashed_address].storage[&KeccakKeyHasher::hash_key(B256::ZERO)],
+            U256::from(2)
+        );
+    }
+
+    #[test]
+    fn from_reverts_of_account_with_limit() {
+        let address = Address::with_last_byte(1);
+        let other = Address::with_last_byte(2);
+        let slot = B256::with_last_byte(1);
+        let db = create_test_rw_db();
+        let tx = db.tx_mut().expect("failed to create transaction");
+        for block in 1..=3u64 {
+            for address in [address, other] {
+                tx.put::<tables::AccountChangeSets>(
+                    block,
+                    AccountBeforeTx {
+                        address,
+                        info: Some(Account { nonce: block, ..Default::default() }),
+                    },
+                )
+                .unwrap();
+                for slot in [B256::ZERO, slot] {
+                    tx.put::<tables::StorageChangeSets>(
+                        BlockNumberAddress((block, address)),
+                        StorageEntry { key: slot, value: U256::from(block) },
+                    )
+                    .unwrap();
+                }
+            }
+        }
+        for address in [address, other] {
+            tx.put::<tables::AccountsHistory>(
+                ShardedKey::new(address, u64::MAX),
+                BlockNumberList::new_pre_sorted([1, 2, 3]),
+            )
+            .unwrap();
+            for slot in [B256::ZERO, slot] {
+                tx.put::<tables::StoragesHistory>(
+                    StorageShardedKey::new(address, slot, u64::MAX),
+                    BlockNumberList::new_pre_sorted([1, 2, 3]),
+                )
+                .unwrap();
+            }
+        }
+
+        // reverting the account and one of its slots to block 2 seeks the two history shards and
+        // the two changesets
+        assert!(HashedPostState::from_reverts_of_account_with_limit::<KeccakKeyHasher>(
+            &tx,
+            2,
+            address,
+            &[slot],
+            3
+        )
+        .unwrap()
+        .is_none());
+        let reverts = HashedPostState::from_reverts_of_account_with_limit::<KeccakKeyHasher>(
+            &tx,
+            2,
+            address,
+            &[slot],
+            4,
+        )
+        .unwrap()
+        .unwrap();
+
+        let hashed_address = KeccakKeyHasher::hash_key(address);
+        assert_eq!(reverts.accounts.len(), 1);
+        assert_eq!(reverts.accounts[&hashed_address].unwrap().nonce, 2);
+        assert_eq!(reverts.storages.len(), 1);
+        assert_eq!(
+            reverts.storages[&hashed_address].storage,
+            [(KeccakKeyHasher::hash_key(slot), U256::from(2))].into_iter().collect()
+        );
+
+        // there are no changes at or after block 4
+        assert!(HashedPostState::from_reverts_of_account_with_limit::<KeccakKeyHasher>(
+            &tx,
+            4,
+            address,
+            &[slot],
+            4
+        )
+        .unwrap()
+        .unwrap()
+        .is_empty());
+    }
 }
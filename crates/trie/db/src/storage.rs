@@ -1,7 +1,11 @@
 use crate::{DatabaseHashedCursorFactory, DatabaseTrieCursorFactory};
-use alloy_primitives::{keccak256, map::hash_map, Address, BlockNumber, B256};
+use alloy_primitives::{keccak256, Address, BlockNumber, B256, U256};
 use reth_db_api::{
-    cursor::DbCursorRO, models::BlockNumberAddress, tables, transaction::DbTx, DatabaseError,
+    cursor::{DbCursorRO, DbDupCursorRO},
+    models::{storage_sharded_key::StorageShardedKey, BlockNumberAddress},
+    tables,
+    transaction::DbTx,
+    BlockNumberList, DatabaseError,
 };
 use reth_execution_errors::StorageRootError;
 use reth_trie::{
@@ -31,7 +35,21 @@ pub trait DatabaseStorageRoot<'a, TX> {
 pub trait DatabaseHashedStorage<TX>: Sized {
     /// Initializes [`HashedStorage`] from reverts. Iterates over storage reverts from the specified
     /// block up to the current tip and aggregates them into hashed storage in reverse.
+    ///
+    /// The slots of the address are looked up in [`tables::StoragesHistory`], so only the
+    /// changeset of the first change of every slot at or after the block is read.
     fn from_reverts(tx: &TX, address: Address, from: BlockNumber) -> Result<Self, DatabaseError>;
+
+    /// Initializes [`HashedStorage`] from reverts like [`Self::from_reverts`], but stops reading
+    /// once more than `limit` seeks into the history index and the changesets were made.
+    ///
+    /// Returns `None` if the reverts exceed the limit.
+    fn from_reverts_with_limit(
+        tx: &TX,
+        address: Address,
+        from: BlockNumber,
+        limit: usize,
+    ) -> Result<Option<Self>, DatabaseError>;
 }
 
 impl<'a, TX: DbTx> DatabaseStorageRoot<'a, TX>
@@ -81,17 +99,169 @@ impl<'a, TX: DbTx> DatabaseStorageRoot<'a, TX>
 
 impl<TX: DbTx> DatabaseHashedStorage<TX> for HashedStorage {
     fn from_reverts(tx: &TX, address: Address, from: BlockNumber) -> Result<Self, DatabaseError> {
+        Ok(Self::from_reverts_with_limit(tx, address, from, usize::MAX)?
+            .expect("limit can't be exceeded"))
+    }
+
+    fn from_reverts_with_limit(
+        tx: &TX,
+        address: Address,
+        from: BlockNumber,
+        limit: usize,
+    ) -> Result<Option<Self>, DatabaseError> {
+        let mut seeks = SeekBudget::new(limit);
         let mut storage = Self::new(false);
-        let mut storage_changesets_cursor = tx.cursor_read::<tables::StorageChangeSets>()?;
-        for entry in storage_changesets_cursor.walk_range(BlockNumberAddress((from, address))..)? {
-            let (BlockNumberAddress((_, storage_address)), storage_change) = entry?;
-            if storage_address == address {
-                let hashed_slot = keccak256(storage_change.key);
-                if let hash_map::Entry::Vacant(entry) = storage.storage.entry(hashed_slot) {
-                    entry.insert(storage_change.value);
+        let mut history_cursor = tx.cursor_read::<tables::StoragesHistory>()?;
+        let mut changesets_cursor = tx.cursor_dup_read::<tables::StorageChangeSets>()?;
+
+        // Visit the slots of the address in the storage history index. The shard of a slot with
+        // the first change at or after the block points to the changeset with its value before.
+        let mut slot = B256::ZERO;
+        loop {
+            if !seeks.spend() {
+                return Ok(None)
+            }
+            let Some((key, blocks)) =
+                history_cursor.seek(StorageShardedKey::new(address, slot, from))?
+            else {
+                break
+            };
+            if key.address != address {
+                break
+            }
+            if key.sharded_key.key != slot {
+                // the slot has no shard with changes at or after the block, the seek landed on
+                // the first shard of the next slot
+                slot = key.sharded_key.key;
+                continue
+            }
+
+            if let Some(block) = first_change_at_or_after(&blocks, from) {
+                if !seeks.spend() {
+                    return Ok(None)
+                }
+                if let Some(entry) = storage_revert(&mut changesets_cursor, block, address, slot)? {
+                    storage.storage.insert(keccak256(slot), entry);
                 }
             }
+
+            let Some(next_slot) = U256::from_be_bytes(slot.0).checked_add(U256::from(1)) else {
+                break
+            };
+            slot = B256::new(next_slot.to_be_bytes());
+        }
+
+        Ok(Some(storage))
+    }
+}
+
+/// Returns the first block of the history shard at or after the given block.
+pub(crate) fn first_change_at_or_after(blocks: &BlockNumberList, from: BlockNumber) -> Option<u64> {
+    let changes_before = from.checked_sub(1).map_or(0, |block| blocks.rank(block));
+    blocks.select(changes_before)
+}
+
+/// Looks up the value of the storage slot before it was changed in the given block.
+pub(crate) fn storage_revert<C: DbDupCursorRO<tables::StorageChangeSets>>(
+    cursor: &mut C,
+    block: BlockNumber,
+    address: Address,
+    slot: B256,
+) -> Result<Option<U256>, DatabaseError> {
+    Ok(cursor
+        .seek_by_key_subkey(BlockNumberAddress((block, address)), slot)?
+        .filter(|entry| entry.key == slot)
+        .map(|entry| entry.value))
+}
+
+/// Counts the seeks into the history indices and changesets against a limit.
+#[derive(Debug)]
+pub(crate) struct SeekBudget {
+    remaining: usize,
+}
+
+impl SeekBudget {
+    pub(crate) const fn new(limit: usize) -> Self {
+        Self { remaining: limit }
+    }
+
+    /// Returns `false` if the budget is exhausted.
+    pub(crate) const fn spend(&mut self) -> bool {
+        if self.remaining == 0 {
+            return false
         }
-        Ok(storage)
+        self.remaining -= 1;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::map::B256Map;
+    use reth_db::test_utils::create_test_rw_db;
+    use reth_db_api::{database::Database, transaction::DbTxMut};
+    use reth_primitives_traits::StorageEntry;
+
+    #[test]
+    fn from_reverts_of_address() {
+        let address = Address::with_last_byte(1);
+        let other = Address::with_last_byte(2);
+        let db = create_test_rw_db();
+        let tx = db.tx_mut().expect("failed to create transaction");
+        let put = |block: u64, address: Address, slot: u8, value: u64| {
+            tx.put::<tables::StorageChangeSets>(
+                BlockNumberAddress((block, address)),
+                StorageEntry { key: B256::with_last_byte(slot), value: U256::from(value) },
+            )
+            .unwrap();
+        };
+        let index = |address: Address, slot: u8, highest: u64, blocks: &[u64]| {
+            tx.put::<tables::StoragesHistory>(
+                StorageShardedKey::new(address, B256::with_last_byte(slot), highest),
+                BlockNumberList::new_pre_sorted(blocks.iter().copied()),
+            )
+            .unwrap();
+        };
+        put(1, address, 0, 1);
+        put(2, other, 0, 2);
+        put(4, address, 0, 4);
+        put(4, address, 1, 4);
+        put(4, other, 1, 4);
+        put(6, address, 1, 6);
+        put(6, address, 2, 6);
+        index(address, 0, 1, &[1]);
+        index(address, 0, u64::MAX, &[4]);
+        index(address, 1, u64::MAX, &[4, 6]);
+        index(address, 2, u64::MAX, &[6]);
+        index(other, 0, u64::MAX, &[2]);
+        index(other, 1, u64::MAX, &[4]);
+
+        // the first revert of every slot at or after the block is kept
+        let storage = HashedStorage::from_reverts(&tx, address, 2).unwrap();
+        assert_eq!(
+            storage.storage,
+            [
+                (keccak256(B256::with_last_byte(0)), U256::from(4)),
+                (keccak256(B256::with_last_byte(1)), U256::from(4)),
+                (keccak256(B256::with_last_byte(2)), U256::from(6)),
+            ]
+            .into_iter()
+            .collect::<B256Map<_>>()
+        );
+
+        // reverting to block 5 seeks the shards of the three slots and the next address, and the
+        // changesets of the two slots changed in block 6
+        assert!(HashedStorage::from_reverts_with_limit(&tx, address, 5, 5).unwrap().is_none());
+        assert_eq!(
+            HashedStorage::from_reverts_with_limit(&tx, address, 5, 6)
+                .unwrap()
+                .unwrap()
+                .storage
+                .len(),
+            2
+        );
+
+        assert!(HashedStorage::from_reverts(&tx, address, 7).unwrap().storage.is_empty());
     }
 }
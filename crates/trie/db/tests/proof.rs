@@ -4,10 +4,18 @@ use alloy_consensus::EMPTY_ROOT_HASH;
 use alloy_primitives::{address, b256, keccak256, Address, Bytes, B256, U256};
 use alloy_rlp::EMPTY_STRING_CODE;
 use reth_chainspec::{Chain, ChainSpec, HOLESKY, MAINNET};
-use reth_primitives_traits::Account;
-use reth_provider::test_utils::{create_test_provider_factory, insert_genesis};
-use reth_trie::{proof::Proof, AccountProof, Nibbles, StorageProof};
-use reth_trie_db::DatabaseProof;
+use reth_db::tables;
+use reth_db_api::transaction::DbTxMut;
+use reth_primitives_traits::{Account, StorageEntry};
+use reth_provider::{
+    test_utils::{create_test_provider_factory, insert_genesis},
+    TrieWriter,
+};
+use reth_trie::{
+    proof::Proof, AccountProof, HashedPostState, HashedStorage, Nibbles, StateRoot, StorageProof,
+    StorageRoot, TrieInput,
+};
+use reth_trie_db::{DatabaseProof, DatabaseStateRoot, DatabaseStorageProof, DatabaseStorageRoot};
 use std::{
     str::FromStr,
     sync::{Arc, LazyLock},
@@ -263,3 +271,58 @@ fn holesky_deposit_contract_proof() {
     similar_asserts::assert_eq!(account_proof, expected);
     assert_eq!(account_proof.verify(root), Ok(()));
 }
+
+#[test]
+fn storage_multiproof_from_nodes() {
+    let factory = create_test_provider_factory();
+    let provider = factory.provider_rw().unwrap();
+    let address = Address::random();
+    let hashed_address = keccak256(address);
+    let slot = |i: u64| B256::from(U256::from(i));
+
+    provider.tx_ref().put::<tables::HashedAccounts>(hashed_address, Account::default()).unwrap();
+    for i in 0..100 {
+        let entry = StorageEntry { key: keccak256(slot(i)), value: U256::from(i + 1) };
+        provider.tx_ref().put::<tables::HashedStorages>(hashed_address, entry).unwrap();
+    }
+    let (_, updates) = StateRoot::from_tx(provider.tx_ref()).root_with_updates().unwrap();
+    provider.write_trie_updates(&updates).unwrap();
+
+    // changes of a block that is still in memory, with the trie nodes computed for it
+    let storage = HashedStorage::from_iter(
+        false,
+        [(keccak256(slot(1)), U256::from(1000)), (keccak256(slot(2)), U256::ZERO)],
+    );
+    let state = HashedPostState::default().with_storages([(hashed_address, storage.clone())]);
+    let (_, nodes) =
+        StateRoot::overlay_root_with_updates(provider.tx_ref(), state.clone()).unwrap();
+    let expected = StorageRoot::overlay_root(provider.tx_ref(), address, storage).unwrap();
+
+    // the cached nodes replace the stale ones in the database without any prefix sets
+    let input = TrieInput::new(nodes, state.clone(), Default::default());
+    let slots = [slot(1), slot(2), slot(3)];
+    let multiproof = reth_trie::proof::StorageProof::overlay_storage_multiproof_from_nodes(
+        provider.tx_ref(),
+        input,
+        address,
+        &slots,
+    )
+    .unwrap();
+    assert_eq!(multiproof.root, expected);
+    for (slot, value) in slots.into_iter().zip([U256::from(1000), U256::ZERO, U256::from(4)]) {
+        let proof = multiproof.storage_proof(slot).unwrap();
+        assert_eq!(proof.value, value);
+        assert_eq!(proof.verify(expected), Ok(()));
+    }
+
+    // without the nodes the stale branch nodes of the database would be used
+    let input = TrieInput::new(Default::default(), state, Default::default());
+    let multiproof = reth_trie::proof::StorageProof::overlay_storage_multiproof_from_nodes(
+        provider.tx_ref(),
+        input,
+        address,
+        &[slot(3)],
+    )
+    .unwrap();
+    assert_ne!(multiproof.root, expected);
+}
@@ -372,6 +372,26 @@ export const sidebar: SidebarItem[] = [
                                     }
                                 ]
                             },
+                            {
+                                text: "reth db repair",
+                                link: "/cli/reth/db/repair"
+                            },
+                            {
+                                text: "reth db repair-body-indices",
+                                link: "/cli/reth/db/repair-body-indices"
+                            },
+                            {
+                                text: "reth db state-size",
+                                link: "/cli/reth/db/state-size"
+                            },
+                            {
+                                text: "reth db migrate-env",
+                                link: "/cli/reth/db/migrate-env"
+                            },
+                            {
+                                text: "reth db recompress-static-files",
+                                link: "/cli/reth/db/recompress-static-files"
+                            },
                             {
                                 text: "reth db version",
                                 link: "/cli/reth/db/version"
@@ -386,6 +406,28 @@ export const sidebar: SidebarItem[] = [
                         text: "reth download",
                         link: "/cli/reth/download"
                     },
+                    {
+                        text: "reth exex",
+                        link: "/cli/reth/exex",
+                        collapsed: true,
+                        items: [
+                            {
+                                text: "reth exex wal",
+                                link: "/cli/reth/exex/wal",
+                                collapsed: true,
+                                items: [
+                                    {
+                                        text: "reth exex wal inspect",
+                                        link: "/cli/reth/exex/wal/inspect"
+                                    },
+                                    {
+                                        text: "reth exex wal repair",
+                                        link: "/cli/reth/exex/wal/repair"
+                                    }
+                                ]
+                            }
+                        ]
+                    },
                     {
                         text: "reth stage",
                         link: "/cli/reth/stage",
@@ -469,6 +511,17 @@ export const sidebar: SidebarItem[] = [
                         text: "reth config",
                         link: "/cli/reth/config"
                     },
+                    {
+                        text: "reth engine",
+                        link: "/cli/reth/engine",
+                        collapsed: true,
+                        items: [
+                            {
+                                text: "reth engine replay",
+                                link: "/cli/reth/engine/replay"
+                            }
+                        ]
+                    },
                     {
                         text: "reth debug",
                         link: "/cli/reth/debug",
@@ -506,6 +559,10 @@ export const sidebar: SidebarItem[] = [
                     {
                         text: "reth prune",
                         link: "/cli/reth/prune"
+                    },
+                    {
+                        text: "reth verify-execution",
+                        link: "/cli/reth/verify-execution"
                     }
                 ]
             }
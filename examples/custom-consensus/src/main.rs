@@ -0,0 +1,149 @@
+//! This example shows how to replace the consensus implementation of an ethereum node, to relax
+//! some of the validation rules for a private chain while reusing all other node components.
+//!
+//! The custom consensus wraps the default [`EthBeaconConsensus`] and only overrides the rules
+//! that differ:
+//!  - the extra data of headers may be up to [`MAX_EXTRA_DATA_SIZE`] bytes, instead of 32 bytes.
+//!  - the gas limit may change arbitrarily between blocks, instead of by at most 1/1024.
+//!
+//! Run with
+//!
+//! ```sh
+//! cargo run -p example-custom-consensus node --dev
+//! ```
+
+#![warn(unused_crate_dependencies)]
+
+use reth_ethereum::{
+    chainspec::{ChainSpec, EthChainSpec},
+    cli::interface::Cli,
+    consensus::{
+        validation::{
+            validate_against_parent_4844, validate_against_parent_eip1559_base_fee,
+            validate_against_parent_hash_number, validate_against_parent_timestamp,
+        },
+        Consensus, ConsensusError, EthBeaconConsensus, FullConsensus, HeaderValidator,
+    },
+    node::{
+        api::{FullNodeTypes, NodeTypes},
+        builder::{components::ConsensusBuilder, BuilderContext},
+        node::EthereumAddOns,
+        EthereumNode,
+    },
+    primitives::{Header, RecoveredBlock, SealedBlock, SealedHeader},
+    provider::BlockExecutionResult,
+    Block, BlockBody, EthPrimitives, Receipt,
+};
+use std::sync::Arc;
+
+/// The maximum size of the extra data of headers on the private chain.
+const MAX_EXTRA_DATA_SIZE: usize = 1024;
+
+fn main() {
+    Cli::parse_args()
+        .run(|builder, _| async move {
+            let handle = builder
+                // use the default ethereum node types
+                .with_types::<EthereumNode>()
+                // use the default ethereum components, but replace the consensus
+                .with_components(
+                    EthereumNode::components().consensus(PrivateChainConsensusBuilder::default()),
+                )
+                .with_add_ons(EthereumAddOns::default())
+                .launch()
+                .await?;
+
+            handle.wait_for_node_exit().await
+        })
+        .unwrap();
+}
+
+/// Builds the [`PrivateChainConsensus`] during launch.
+#[derive(Debug, Default, Clone, Copy)]
+#[non_exhaustive]
+pub struct PrivateChainConsensusBuilder;
+
+impl<Node> ConsensusBuilder<Node> for PrivateChainConsensusBuilder
+where
+    Node: FullNodeTypes<Types: NodeTypes<ChainSpec = ChainSpec, Primitives = EthPrimitives>>,
+{
+    type Consensus = Arc<PrivateChainConsensus>;
+
+    async fn build_consensus(self, ctx: &BuilderContext<Node>) -> eyre::Result<Self::Consensus> {
+        Ok(Arc::new(PrivateChainConsensus::new(ctx.chain_spec())))
+    }
+}
+
+/// Ethereum consensus with relaxed extra data and gas limit rules.
+#[derive(Debug)]
+pub struct PrivateChainConsensus {
+    inner: EthBeaconConsensus<ChainSpec>,
+    chain_spec: Arc<ChainSpec>,
+}
+
+impl PrivateChainConsensus {
+    /// Creates the consensus for the given chain.
+    pub fn new(chain_spec: Arc<ChainSpec>) -> Self {
+        Self { inner: EthBeaconConsensus::new(chain_spec.clone()), chain_spec }
+    }
+}
+
+impl HeaderValidator<Header> for PrivateChainConsensus {
+    fn validate_header(&self, header: &SealedHeader<Header>) -> Result<(), ConsensusError> {
+        let len = header.extra_data.len();
+        if len > MAX_EXTRA_DATA_SIZE {
+            return Err(ConsensusError::ExtraDataExceedsMax { len })
+        }
+
+        // all other rules are unchanged, so validate the header without its extra data
+        let mut stripped = header.header().clone();
+        stripped.extra_data = Default::default();
+        self.inner.validate_header(&SealedHeader::new(stripped, header.hash()))
+    }
+
+    fn validate_header_against_parent(
+        &self,
+        header: &SealedHeader<Header>,
+        parent: &SealedHeader<Header>,
+    ) -> Result<(), ConsensusError> {
+        // same as the default rules, without bounding the gas limit change
+        validate_against_parent_hash_number(header.header(), parent)?;
+        validate_against_parent_timestamp(header.header(), parent.header())?;
+        validate_against_parent_eip1559_base_fee(
+            header.header(),
+            parent.header(),
+            &self.chain_spec,
+        )?;
+        if let Some(blob_params) = self.chain_spec.blob_params_at_timestamp(header.timestamp) {
+            validate_against_parent_4844(header.header(), parent.header(), blob_params)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Consensus<Block> for PrivateChainConsensus {
+    type Error = ConsensusError;
+
+    fn validate_body_against_header(
+        &self,
+        body: &BlockBody,
+        header: &SealedHeader<Header>,
+    ) -> Result<(), Self::Error> {
+        Consensus::<Block>::validate_body_against_header(&self.inner, body, header)
+    }
+
+    fn validate_block_pre_execution(&self, block: &SealedBlock<Block>) -> Result<(), Self::Error> {
+        self.inner.validate_block_pre_execution(block)
+    }
+}
+
+impl FullConsensus<EthPrimitives> for PrivateChainConsensus {
+    fn validate_block_post_execution(
+        &self,
+        block: &RecoveredBlock<Block>,
+        result: &BlockExecutionResult<Receipt>,
+    ) -> Result<(), ConsensusError> {
+        FullConsensus::<EthPrimitives>::validate_block_post_execution(&self.inner, block, result)
+    }
+}